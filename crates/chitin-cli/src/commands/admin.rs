@@ -0,0 +1,450 @@
+// crates/chitin-cli/src/commands/admin.rs
+//
+// `chitin admin {logs, config, backup, snapshot, tasks}` — node operator commands
+// wrapping the daemon's `admin/*` RPCs.
+//
+// `logs --follow` polls `admin/logs` for the entries after the last one
+// printed. `config get` reads the configuration in effect, or the value at
+// a dotted key; `config set` and `config unset` edit the node's config
+// file, applying hot-reloadable keys at once. Values parse as TOML (`42`,
+// `true`, `["a", "b"]`) and fall back to plain strings. Backup and snapshot
+// paths are on the node's host.
+
+use std::time::Duration;
+
+use clap::Subcommand;
+use serde::Serialize;
+use tabled::Tabled;
+
+use chitin_rpc::handlers::admin::{
+    BackupResponse, GetConfigResponse, GetLogsResponse, ListTasksResponse, LogEntry,
+    ReloadConfigResponse, SnapshotResponse, UpdateConfigResponse,
+};
+
+use crate::output::{self, format_table, truncate, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Seconds between `admin/logs` polls with `--follow`.
+const FOLLOW_INTERVAL_SECS: u64 = 1;
+
+/// Most entries fetched per `--follow` poll.
+const FOLLOW_BATCH: u32 = 1000;
+
+/// Node administration subcommands.
+#[derive(Debug, Subcommand)]
+pub enum AdminCmd {
+    /// Show recent node log entries.
+    Logs {
+        /// Minimum level: trace, debug, info, warn, or error.
+        #[arg(long)]
+        level: Option<String>,
+        /// Only entries whose message contains this text.
+        #[arg(long)]
+        filter: Option<String>,
+        /// Number of entries to show.
+        #[arg(long, short = 'n', default_value_t = 100)]
+        lines: u32,
+        /// Keep printing new entries until interrupted.
+        #[arg(long, short = 'f')]
+        follow: bool,
+    },
+    /// Read or change the node configuration.
+    #[command(subcommand)]
+    Config(ConfigCmd),
+    /// Back up the node's databases while it runs.
+    Backup {
+        /// Name of the directory to create under `<data_dir>/backups` on the
+        /// node's host (default: a timestamp).
+        #[arg(long)]
+        dest: Option<String>,
+    },
+    /// Write a signed snapshot that new nodes can bootstrap from.
+    Snapshot {
+        /// Name of the file to create under `<data_dir>/snapshots` on the
+        /// node's host (default: `snapshot-<epoch>.chitin`).
+        #[arg(long)]
+        dest: Option<String>,
+        /// Also add the snapshot to IPFS.
+        #[arg(long)]
+        pin: bool,
+    },
+    /// List the daemon's background tasks.
+    Tasks,
+}
+
+/// Configuration subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCmd {
+    /// Show the configuration in effect, or one key of it.
+    Get {
+        /// Dotted key (e.g., "pruning.interval_secs").
+        key: Option<String>,
+    },
+    /// Set a key in the node's config file.
+    Set {
+        /// Dotted key (e.g., "log_level").
+        key: String,
+        /// New value, as TOML or a plain string.
+        value: String,
+        /// Validate the change without writing it.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a key from the node's config file, restoring its default.
+    Unset {
+        /// Dotted key.
+        key: String,
+        /// Validate the change without writing it.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Re-read the config file and apply hot-reloadable changes.
+    Reload,
+}
+
+/// Run the admin subcommand.
+pub async fn run(
+    cmd: &AdminCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        AdminCmd::Logs {
+            level,
+            filter,
+            lines,
+            follow,
+        } => {
+            let params = serde_json::json!({ "lines": lines, "level": level, "filter": filter });
+            let resp: GetLogsResponse = rpc_result(rpc_endpoint, "admin/logs", params).await?;
+            if !*follow {
+                return output::print(&resp, format);
+            }
+            let mut since = 0;
+            let mut entries = resp.entries;
+            loop {
+                for entry in &entries {
+                    print_entry(entry, format)?;
+                    since = entry.seq;
+                }
+                tokio::time::sleep(Duration::from_secs(FOLLOW_INTERVAL_SECS)).await;
+                let params = serde_json::json!({
+                    "lines": FOLLOW_BATCH,
+                    "level": level,
+                    "filter": filter,
+                    "since": since,
+                });
+                let resp: GetLogsResponse = rpc_result(rpc_endpoint, "admin/logs", params).await?;
+                entries = resp.entries;
+            }
+        }
+        AdminCmd::Config(ConfigCmd::Get { key }) => {
+            let section = key
+                .as_deref()
+                .map(|key| key.split('.').next().unwrap_or(key));
+            let params = serde_json::json!({ "section": section });
+            let resp: GetConfigResponse = rpc_result(rpc_endpoint, "admin/config", params).await?;
+            let mut value = resp.config;
+            if let Some(key) = key {
+                for part in key.split('.').skip(1) {
+                    value = match value.get(part) {
+                        Some(nested) => nested.clone(),
+                        None => return Err(format!("Unknown config key: {}", key).into()),
+                    };
+                }
+            }
+            let view = ConfigView {
+                key: key.clone(),
+                value,
+                config_version: resp.config_version,
+            };
+            output::print(&view, format)
+        }
+        AdminCmd::Config(ConfigCmd::Set {
+            key,
+            value,
+            dry_run,
+        }) => update_config(rpc_endpoint, key, parse_value(value), *dry_run, format).await,
+        AdminCmd::Config(ConfigCmd::Unset { key, dry_run }) => {
+            update_config(rpc_endpoint, key, serde_json::Value::Null, *dry_run, format).await
+        }
+        AdminCmd::Config(ConfigCmd::Reload) => {
+            let resp: ReloadConfigResponse =
+                rpc_result(rpc_endpoint, "admin/config/reload", serde_json::json!({})).await?;
+            output::print(&resp, format)
+        }
+        AdminCmd::Backup { dest } => {
+            let params = serde_json::json!({ "dest": dest });
+            let resp: BackupResponse = rpc_result(rpc_endpoint, "admin/backup", params).await?;
+            output::print(&resp, format)
+        }
+        AdminCmd::Snapshot { dest, pin } => {
+            let params = serde_json::json!({ "dest": dest, "pin": pin });
+            let resp: SnapshotResponse = rpc_result(rpc_endpoint, "admin/snapshot", params).await?;
+            output::print(&resp, format)
+        }
+        AdminCmd::Tasks => {
+            let resp: ListTasksResponse =
+                rpc_result(rpc_endpoint, "admin/tasks", serde_json::json!({})).await?;
+            output::print(&resp, format)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Logs
+// ---------------------------------------------------------------------------
+
+/// One log entry as a line of text.
+fn entry_line(entry: &LogEntry) -> String {
+    format!(
+        "{} {:>5} {}: {}",
+        entry.timestamp, entry.level, entry.target, entry.message
+    )
+}
+
+/// Print one followed entry: a line of text, a JSON line, or a YAML document.
+fn print_entry(entry: &LogEntry, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Table => println!("{}", entry_line(entry)),
+        OutputFormat::Json => println!("{}", serde_json::to_string(entry)?),
+        OutputFormat::Yaml => print!("---\n{}", serde_yaml::to_string(entry)?),
+    }
+    Ok(())
+}
+
+impl Render for GetLogsResponse {
+    fn render_table(&self) -> String {
+        if self.entries.is_empty() {
+            return "No log entries.".to_string();
+        }
+        let lines: Vec<String> = self.entries.iter().map(entry_line).collect();
+        lines.join("\n")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+/// The configuration, or the value of one key.
+#[derive(Debug, Serialize)]
+struct ConfigView {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    value: serde_json::Value,
+    config_version: String,
+}
+
+impl Render for ConfigView {
+    fn render_table(&self) -> String {
+        match &self.value {
+            serde_json::Value::Object(_) => {
+                let toml = toml::to_string(&without_nulls(&self.value))
+                    .unwrap_or_else(|e| format!("# not representable as TOML: {}", e));
+                format!("# config version {}\n{}", self.config_version, toml)
+            }
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Null => "(unset)".to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+/// `value` with null fields (unset options) removed, for TOML output.
+fn without_nulls(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key.clone(), without_nulls(value)))
+            .collect(),
+        serde_json::Value::Array(items) => items.iter().map(without_nulls).collect(),
+        value => value.clone(),
+    }
+}
+
+/// Parse a `config set` value as TOML, or else take it as a string.
+fn parse_value(raw: &str) -> serde_json::Value {
+    let parsed = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .and_then(|value| serde_json::to_value(value).ok());
+    parsed.unwrap_or_else(|| serde_json::Value::String(raw.to_string()))
+}
+
+/// Send `admin/config/update` setting the dotted `key` to `value`.
+async fn update_config(
+    rpc_endpoint: &str,
+    key: &str,
+    value: serde_json::Value,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if key.split('.').any(str::is_empty) {
+        return Err(format!("Invalid config key: {:?}", key).into());
+    }
+    let updates = key
+        .rsplit('.')
+        .fold(value, |value, part| serde_json::json!({ part: value }));
+    let params = serde_json::json!({ "updates": updates, "persist": !dry_run });
+    let resp: UpdateConfigResponse =
+        rpc_result(rpc_endpoint, "admin/config/update", params).await?;
+    output::print(&resp, format)
+}
+
+impl Render for UpdateConfigResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![self.message.clone()];
+        let applied = if self.persisted {
+            "Applied"
+        } else {
+            "Would apply"
+        };
+        for change in &self.changes {
+            lines.push(format!("  {}: {}", applied, change));
+        }
+        for key in &self.restart_required {
+            lines.push(format!("  Needs a restart: {}", key));
+        }
+        if let Some(version) = &self.new_config_version {
+            lines.push(format!("  Config version: {}", version));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Render for ReloadConfigResponse {
+    fn render_table(&self) -> String {
+        if self.applied.is_empty() {
+            return format!("Reloaded {}: no hot-reloadable changes", self.path);
+        }
+        let mut lines = vec![format!("Reloaded {}", self.path)];
+        lines.extend(
+            self.applied
+                .iter()
+                .map(|change| format!("  Applied: {}", change)),
+        );
+        lines.join("\n")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Backup
+// ---------------------------------------------------------------------------
+
+/// A row in the backup table.
+#[derive(Tabled)]
+struct DatabaseRow {
+    #[tabled(rename = "Database")]
+    name: String,
+    #[tabled(rename = "Entries")]
+    entries: u64,
+    #[tabled(rename = "Size")]
+    size: String,
+}
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    match bytes as f64 {
+        b if b >= KIB * KIB * KIB => format!("{:.1} GiB", b / (KIB * KIB * KIB)),
+        b if b >= KIB * KIB => format!("{:.1} MiB", b / (KIB * KIB)),
+        b if b >= KIB => format!("{:.1} KiB", b / KIB),
+        _ => format!("{} B", bytes),
+    }
+}
+
+impl Render for BackupResponse {
+    fn render_table(&self) -> String {
+        let rows: Vec<DatabaseRow> = self
+            .databases
+            .iter()
+            .map(|db| DatabaseRow {
+                name: db.name.clone(),
+                entries: db.stats.entries,
+                size: format_size(db.stats.bytes),
+            })
+            .collect();
+        format!(
+            "Backup written to {} at {}\n{}",
+            self.path,
+            self.created_at.to_rfc3339(),
+            format_table(&rows)
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshot
+// ---------------------------------------------------------------------------
+
+impl Render for SnapshotResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Snapshot of epoch {} written to {}", self.epoch, self.path),
+            format!("  Block:        {}", self.block),
+            format!("  Polyps:       {}", self.polyps),
+            format!("  Vectors:      {}", self.vectors),
+            format!(
+                "  Checkpoint:   {}",
+                self.checkpoint_epoch
+                    .map(|epoch| format!("epoch {}", epoch))
+                    .unwrap_or_else(|| "none".to_string())
+            ),
+            format!("  Content hash: {}", self.content_hash),
+            format!("  Signer:       {}", self.signer),
+        ];
+        if let Some(cid) = &self.cid {
+            lines.push(format!("  IPFS:         ipfs://{}", cid));
+        }
+        lines.join("\n")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tasks
+// ---------------------------------------------------------------------------
+
+/// A row in the task table.
+#[derive(Tabled)]
+struct TaskRow {
+    #[tabled(rename = "Task")]
+    name: String,
+    #[tabled(rename = "Priority")]
+    priority: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Restarts")]
+    restarts: u32,
+    #[tabled(rename = "Started")]
+    started: String,
+    #[tabled(rename = "Last Error")]
+    last_error: String,
+}
+
+impl Render for ListTasksResponse {
+    fn render_table(&self) -> String {
+        let rows: Vec<TaskRow> = self
+            .tasks
+            .iter()
+            .map(|task| TaskRow {
+                name: task.name.clone(),
+                priority: task.priority.clone(),
+                state: task.state.clone(),
+                restarts: task.restarts,
+                started: task
+                    .started_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default(),
+                last_error: truncate(task.last_error.as_deref().unwrap_or_default(), 40),
+            })
+            .collect();
+        let health = if self.healthy { "healthy" } else { "UNHEALTHY" };
+        format!(
+            "Tasks ({}, {}):\n{}",
+            self.tasks.len(),
+            health,
+            format_table(&rows)
+        )
+    }
+}
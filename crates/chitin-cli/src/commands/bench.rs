@@ -0,0 +1,480 @@
+// crates/chitin-cli/src/commands/bench.rs
+//
+// `chitin bench {search, ingest}` — measure semantic search and polyp ingest
+// latency and throughput with synthetic polyps and queries.
+//
+// Benchmarks drive a running node over RPC, or with `--embedded` the same
+// RPC handlers over a RocksDB store and vector index in a temporary
+// directory, leaving the network out. Vectors are random unit vectors from
+// a seeded generator, in a model space of their own ("bench/synthetic-<dim>")
+// so searches never mix them with real embeddings. Synthetic polyps
+// submitted to a node stay in its store.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use uuid::Uuid;
+
+use chitin_rpc::handlers::polyp::{
+    handle_submit_polyp, SubmitPolypBatchRequest, SubmitPolypBatchResponse, SubmitPolypRequest,
+    SubmitPolypResponse, MAX_SUBMIT_BATCH,
+};
+use chitin_rpc::handlers::query::{
+    handle_semantic_search, SemanticSearchRequest, SemanticSearchResponse,
+};
+use chitin_store::{InMemoryVectorIndex, RocksStore};
+
+use crate::output::{self, OutputFormat, Render};
+use crate::rpc_client::rpc_result_with;
+
+/// Benchmark subcommands.
+#[derive(Debug, Subcommand)]
+pub enum BenchCmd {
+    /// Load synthetic polyps, then time semantic searches for random vectors.
+    Search {
+        #[command(flatten)]
+        common: BenchArgs,
+        /// Synthetic polyps to load before searching.
+        #[arg(long, default_value_t = 10_000)]
+        n: usize,
+        /// Searches to time.
+        #[arg(long, default_value_t = 1000)]
+        queries: usize,
+        /// Results per search.
+        #[arg(long, default_value_t = 10)]
+        top_k: u32,
+    },
+    /// Time submissions of synthetic polyps.
+    Ingest {
+        #[command(flatten)]
+        common: BenchArgs,
+        /// Synthetic polyps to submit.
+        #[arg(long, default_value_t = 10_000)]
+        n: usize,
+        /// Polyps per request; above 1, requests use `polyp/submit_batch`.
+        #[arg(long, default_value_t = 1)]
+        batch_size: usize,
+    },
+}
+
+/// Options shared by every benchmark.
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Dimensions of the synthetic vectors.
+    #[arg(long, default_value_t = 384)]
+    dim: usize,
+    /// Requests in flight at once.
+    #[arg(long, default_value_t = 8)]
+    concurrency: usize,
+    /// Run against a temporary store in this process instead of the node.
+    #[arg(long)]
+    embedded: bool,
+    /// Seed for the synthetic vectors.
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+/// Run the bench subcommand.
+pub async fn run(
+    cmd: &BenchCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let common = match cmd {
+        BenchCmd::Search { common, .. } | BenchCmd::Ingest { common, .. } => common,
+    };
+    if common.dim == 0 || common.concurrency == 0 {
+        return Err("--dim and --concurrency must be at least 1".into());
+    }
+    let (target, temp_dir) = if common.embedded {
+        let dir = std::env::temp_dir().join(format!("chitin-bench-{}", Uuid::now_v7()));
+        let store = RocksStore::open(&dir.to_string_lossy())?;
+        let target = Target::Embedded {
+            store: Arc::new(store),
+            index: Arc::new(InMemoryVectorIndex::new()),
+        };
+        (target, Some(dir))
+    } else {
+        let target = Target::Rpc {
+            client: reqwest::Client::new(),
+            endpoint: rpc_endpoint.to_string(),
+        };
+        (target, None)
+    };
+    let report = BenchReport {
+        benchmark: match cmd {
+            BenchCmd::Search { .. } => "search",
+            BenchCmd::Ingest { .. } => "ingest",
+        },
+        target: match &temp_dir {
+            Some(_) => "embedded".to_string(),
+            None => rpc_endpoint.to_string(),
+        },
+        dim: common.dim,
+        concurrency: common.concurrency,
+        load: None,
+        run: Stage::default(),
+    };
+    let result = run_bench(cmd, Arc::new(target), report).await;
+    if let Some(dir) = temp_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    output::print(&result?, format)
+}
+
+async fn run_bench(
+    cmd: &BenchCmd,
+    target: Arc<Target>,
+    mut report: BenchReport,
+) -> Result<BenchReport, Box<dyn std::error::Error>> {
+    match cmd {
+        BenchCmd::Search {
+            common,
+            n,
+            queries,
+            top_k,
+        } => {
+            eprintln!("Loading {} synthetic polyps...", n);
+            report.load = Some(ingest(&target, common, *n, MAX_SUBMIT_BATCH).await);
+            let model = bench_model(common.dim);
+            let (dim, seed, top_k) = (common.dim, common.seed, *top_k);
+            report.run = drive(*queries, common.concurrency, 1, move |i| {
+                let request = SemanticSearchRequest {
+                    query_text: None,
+                    // Queries draw from a different stream than the polyps.
+                    query_vector: Some(unit_vector(dim, seed, !(i as u64))),
+                    model_id: Some(model.clone()),
+                    top_k: Some(top_k),
+                    min_trust: None,
+                    hardened_only: None,
+                    reef_zone: None,
+                    state: None,
+                    language: None,
+                    trust_weight: None,
+                    local_only: false,
+                    cross_model: Some(false),
+                    mmr_lambda: None,
+                    rerank: Some(false),
+                    rerank_top_n: None,
+                    rerank_budget_ms: None,
+                };
+                let target = target.clone();
+                async move { target.search(request).await }
+            })
+            .await;
+        }
+        BenchCmd::Ingest {
+            common,
+            n,
+            batch_size,
+        } => {
+            if *batch_size == 0 || *batch_size > MAX_SUBMIT_BATCH {
+                return Err(format!("--batch-size must be 1 to {}", MAX_SUBMIT_BATCH).into());
+            }
+            report.run = ingest(&target, common, *n, *batch_size).await;
+        }
+    }
+    Ok(report)
+}
+
+// ---------------------------------------------------------------------------
+// Targets
+// ---------------------------------------------------------------------------
+
+/// Where benchmark requests go.
+enum Target {
+    /// A running node.
+    Rpc {
+        client: reqwest::Client,
+        endpoint: String,
+    },
+    /// The node's handlers over a store and index in this process.
+    Embedded {
+        store: Arc<RocksStore>,
+        index: Arc<InMemoryVectorIndex>,
+    },
+}
+
+impl Target {
+    /// Submit `polyps` in one request, or one by one when embedded.
+    async fn submit(&self, mut polyps: Vec<SubmitPolypRequest>) -> Result<(), String> {
+        match self {
+            Target::Rpc { client, endpoint } if polyps.len() == 1 => {
+                let params = serde_json::to_value(polyps.remove(0)).map_err(|e| e.to_string())?;
+                rpc_result_with::<SubmitPolypResponse>(client, endpoint, "polyp/submit", params)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            Target::Rpc { client, endpoint } => {
+                let request = SubmitPolypBatchRequest { polyps };
+                let params = serde_json::to_value(request).map_err(|e| e.to_string())?;
+                let resp: SubmitPolypBatchResponse =
+                    rpc_result_with(client, endpoint, "polyp/submit_batch", params)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                match resp.results.into_iter().find_map(|result| result.error) {
+                    Some(error) => Err(error),
+                    None => Ok(()),
+                }
+            }
+            Target::Embedded { store, index } => {
+                for polyp in polyps {
+                    handle_submit_polyp(store, index, polyp).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn search(&self, request: SemanticSearchRequest) -> Result<(), String> {
+        match self {
+            Target::Rpc { client, endpoint } => {
+                let params = serde_json::to_value(request).map_err(|e| e.to_string())?;
+                rpc_result_with::<SemanticSearchResponse>(client, endpoint, "query/search", params)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            Target::Embedded { store, index } => {
+                handle_semantic_search(store, index, request, None)
+                    .await
+                    .map(|_| ())
+            }
+        }
+    }
+}
+
+/// Submit `n` synthetic polyps, `batch_size` per request.
+async fn ingest(target: &Arc<Target>, common: &BenchArgs, n: usize, batch_size: usize) -> Stage {
+    let model = bench_model(common.dim);
+    let (dim, seed) = (common.dim, common.seed);
+    let requests = n.div_ceil(batch_size);
+    let target = target.clone();
+    let mut stage = drive(requests, common.concurrency, batch_size, move |i| {
+        let polyps = (i * batch_size..n.min((i + 1) * batch_size))
+            .map(|j| SubmitPolypRequest {
+                content: format!("Synthetic benchmark polyp {}", j),
+                content_type: "text/plain".to_string(),
+                language: None,
+                vector: Some(unit_vector(dim, seed, j as u64)),
+                model_id: Some(model.clone()),
+                source_url: None,
+                source_title: Some("chitin bench".to_string()),
+                license: None,
+                reef_zone: None,
+                pipeline: Vec::new(),
+            })
+            .collect();
+        let target = target.clone();
+        async move { target.submit(polyps).await }
+    })
+    .await;
+    // The last request may carry fewer polyps.
+    stage.items = stage.items.min(n);
+    stage.throughput_per_sec = per_sec(stage.items, stage.elapsed_secs);
+    stage
+}
+
+// ---------------------------------------------------------------------------
+// Synthetic data
+// ---------------------------------------------------------------------------
+
+/// Model space of synthetic vectors with `dim` dimensions.
+fn bench_model(dim: usize) -> String {
+    format!("bench/synthetic-{}", dim)
+}
+
+/// Random unit vector number `n` of the stream for `seed` (xorshift64).
+fn unit_vector(dim: usize, seed: u64, n: u64) -> Vec<f32> {
+    let mut state = (seed ^ n.wrapping_mul(0x9e37_79b9_7f4a_7c15)) | 1;
+    let mut vector: Vec<f32> = (0..dim)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+        })
+        .collect();
+    let norm = vector
+        .iter()
+        .map(|x| x * x)
+        .sum::<f32>()
+        .sqrt()
+        .max(f32::EPSILON);
+    vector.iter_mut().for_each(|x| *x /= norm);
+    vector
+}
+
+// ---------------------------------------------------------------------------
+// Timing
+// ---------------------------------------------------------------------------
+
+/// Run requests `0..requests`, `concurrency` at a time, timing each one.
+/// `op(i)` builds request `i`; only awaiting it is timed.
+async fn drive<F, Fut>(
+    requests: usize,
+    concurrency: usize,
+    items_per_request: usize,
+    op: F,
+) -> Stage
+where
+    F: Fn(usize) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send,
+{
+    let op = Arc::new(op);
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let workers: Vec<_> = (0..concurrency.min(requests))
+        .map(|_| {
+            let (op, next) = (op.clone(), next.clone());
+            tokio::spawn(async move {
+                let mut latencies = Vec::new();
+                let mut errors = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= requests {
+                        break;
+                    }
+                    let request = op(i);
+                    let sent = Instant::now();
+                    match request.await {
+                        Ok(()) => latencies.push(sent.elapsed()),
+                        Err(error) => errors.push(error),
+                    }
+                }
+                (latencies, errors)
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::with_capacity(requests);
+    let mut errors = Vec::new();
+    for worker in workers {
+        match worker.await {
+            Ok((worker_latencies, worker_errors)) => {
+                latencies.extend(worker_latencies);
+                errors.extend(worker_errors);
+            }
+            Err(e) => errors.push(format!("Benchmark worker panicked: {}", e)),
+        }
+    }
+    let elapsed_secs = started.elapsed().as_secs_f64();
+    let items = latencies.len() * items_per_request;
+    latencies.sort_unstable();
+    Stage {
+        requests,
+        items,
+        errors: errors.len(),
+        first_error: errors.into_iter().next(),
+        elapsed_secs,
+        throughput_per_sec: per_sec(items, elapsed_secs),
+        latency_ms: Latency {
+            p50: percentile_ms(&latencies, 0.50),
+            p95: percentile_ms(&latencies, 0.95),
+            p99: percentile_ms(&latencies, 0.99),
+            max: percentile_ms(&latencies, 1.0),
+        },
+    }
+}
+
+fn per_sec(items: usize, secs: f64) -> f64 {
+    if secs > 0.0 {
+        items as f64 / secs
+    } else {
+        0.0
+    }
+}
+
+/// Nearest-rank percentile of sorted latencies, in milliseconds.
+fn percentile_ms(sorted: &[Duration], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    sorted[rank - 1].as_secs_f64() * 1000.0
+}
+
+// ---------------------------------------------------------------------------
+// Report
+// ---------------------------------------------------------------------------
+
+/// Request latency percentiles, in milliseconds.
+#[derive(Debug, Default, Serialize)]
+struct Latency {
+    p50: f64,
+    p95: f64,
+    p99: f64,
+    max: f64,
+}
+
+/// Timings of one batch of requests.
+#[derive(Debug, Default, Serialize)]
+struct Stage {
+    requests: usize,
+    /// Polyps or queries in the requests that succeeded.
+    items: usize,
+    errors: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_error: Option<String>,
+    elapsed_secs: f64,
+    /// Items per second.
+    throughput_per_sec: f64,
+    latency_ms: Latency,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    benchmark: &'static str,
+    /// The node's RPC endpoint, or "embedded".
+    target: String,
+    dim: usize,
+    concurrency: usize,
+    /// Loading the polyps searched, for `bench search`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    load: Option<Stage>,
+    run: Stage,
+}
+
+fn stage_lines(label: &str, unit: &str, stage: &Stage) -> Vec<String> {
+    let mut lines = vec![format!(
+        "  {:<8} {} {} in {:.2}s ({:.1}/s) over {} requests, {} errors",
+        label,
+        stage.items,
+        unit,
+        stage.elapsed_secs,
+        stage.throughput_per_sec,
+        stage.requests,
+        stage.errors
+    )];
+    lines.push(format!(
+        "  {:<8} p50 {:.2} ms, p95 {:.2} ms, p99 {:.2} ms, max {:.2} ms",
+        "", stage.latency_ms.p50, stage.latency_ms.p95, stage.latency_ms.p99, stage.latency_ms.max
+    ));
+    if let Some(error) = &stage.first_error {
+        lines.push(format!("  {:<8} first error: {}", "", error));
+    }
+    lines
+}
+
+impl Render for BenchReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Benchmark: {} ({} dims, concurrency {}, {})",
+            self.benchmark, self.dim, self.concurrency, self.target
+        )];
+        if let Some(load) = &self.load {
+            lines.extend(stage_lines("Load", "polyps", load));
+        }
+        let unit = match self.benchmark {
+            "search" => "queries",
+            _ => "polyps",
+        };
+        lines.extend(stage_lines("Run", unit, &self.run));
+        lines.join("\n")
+    }
+}
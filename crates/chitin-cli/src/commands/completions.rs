@@ -0,0 +1,30 @@
+// crates/chitin-cli/src/commands/completions.rs
+//
+// `chitin completions <shell>` — print a shell completion script generated
+// from the CLI's own command definitions, so completions never drift from
+// the flags the binary accepts.
+
+use std::io::Write;
+
+use clap::{Args, Command};
+use clap_complete::Shell;
+
+/// Shell completion command.
+#[derive(Debug, Args)]
+pub struct CompletionsCmd {
+    /// Shell to generate completions for.
+    #[arg(value_enum)]
+    shell: Shell,
+}
+
+/// Run the completions command, writing the script for `cli` to stdout.
+/// A closed stdout is not an error, as with other command output.
+pub fn run(cmd: &CompletionsCmd, mut cli: Command) -> Result<(), Box<dyn std::error::Error>> {
+    let name = cli.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(cmd.shell, &mut cli, name, &mut script);
+    match std::io::stdout().lock().write_all(&script) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,174 @@
+// crates/chitin-cli/src/commands/config.rs
+//
+// `chitin config profile {add, use, list, remove}` — manage the named
+// endpoint and keystore profiles in `~/.chitin/cli.toml`.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::profile::{CliConfig, Profile};
+
+/// CLI configuration subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCmd {
+    /// Named RPC endpoint and keystore profiles.
+    #[command(subcommand)]
+    Profile(ProfileCmd),
+}
+
+/// Profile subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ProfileCmd {
+    /// Add a profile, replacing any profile with the same name.
+    Add {
+        /// Profile name (e.g., "devnet").
+        name: String,
+        /// RPC endpoint of the profile's node.
+        #[arg(long)]
+        rpc: String,
+        /// Directory of the profile's key files (default: ~/.chitin/keys).
+        #[arg(long)]
+        keys_dir: Option<PathBuf>,
+        /// Also make it the active profile.
+        #[arg(long = "use")]
+        activate: bool,
+    },
+    /// Make a profile the one commands use without `--profile`.
+    Use {
+        /// Profile name.
+        name: String,
+    },
+    /// List profiles.
+    List,
+    /// Remove a profile.
+    Remove {
+        /// Profile name.
+        name: String,
+    },
+}
+
+/// Run the config subcommand.
+pub fn run(cmd: &ConfigCmd, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let ConfigCmd::Profile(cmd) = cmd;
+    let mut config = CliConfig::load()?;
+    match cmd {
+        ProfileCmd::Add {
+            name,
+            rpc,
+            keys_dir,
+            activate,
+        } => {
+            check_name(name)?;
+            if !rpc.starts_with("http://") && !rpc.starts_with("https://") {
+                return Err(format!("{:?} is not an http(s) URL", rpc).into());
+            }
+            let keys_dir = keys_dir.as_deref().map(std::path::absolute).transpose()?;
+            let profile = Profile {
+                rpc: rpc.clone(),
+                keys_dir,
+            };
+            let replaced = config.profiles.insert(name.clone(), profile).is_some();
+            if *activate {
+                config.active = Some(name.clone());
+            }
+            let path = config.save()?;
+            let verb = if replaced { "Updated" } else { "Added" };
+            println!("{} profile {} in {}", verb, name, path.display());
+            if *activate {
+                println!("Active profile: {}", name);
+            }
+        }
+        ProfileCmd::Use { name } => {
+            if !config.profiles.contains_key(name) {
+                return Err(format!("No profile named {:?}", name).into());
+            }
+            config.active = Some(name.clone());
+            config.save()?;
+            println!("Active profile: {}", name);
+        }
+        ProfileCmd::List => return output::print(&ProfileList::new(config), format),
+        ProfileCmd::Remove { name } => {
+            if config.profiles.remove(name).is_none() {
+                return Err(format!("No profile named {:?}", name).into());
+            }
+            if config.active.as_ref() == Some(name) {
+                config.active = None;
+            }
+            config.save()?;
+            println!("Removed profile {}", name);
+        }
+    }
+    Ok(())
+}
+
+/// Profile names are TOML-friendly words: letters, digits, `-`, `_`, `.`.
+fn check_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if name.is_empty() || !name.chars().all(valid) {
+        return Err(format!(
+            "Invalid profile name {:?}: use letters, digits, '-', '_', or '.'",
+            name
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// A profile in `chitin config profile list`.
+#[derive(Debug, Serialize, Tabled)]
+struct ProfileRow {
+    #[tabled(rename = "Active", display_with = "active_marker")]
+    active: bool,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "RPC")]
+    rpc: String,
+    #[tabled(rename = "Keys Dir")]
+    keys_dir: String,
+}
+
+fn active_marker(active: &bool) -> String {
+    if *active { "*" } else { "" }.to_string()
+}
+
+#[derive(Debug, Serialize)]
+struct ProfileList {
+    active: Option<String>,
+    profiles: Vec<ProfileRow>,
+}
+
+impl ProfileList {
+    fn new(config: CliConfig) -> Self {
+        let profiles = config
+            .profiles
+            .into_iter()
+            .map(|(name, profile)| ProfileRow {
+                active: config.active.as_ref() == Some(&name),
+                rpc: profile.rpc,
+                keys_dir: profile
+                    .keys_dir
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_else(|| "(default)".to_string()),
+                name,
+            })
+            .collect();
+        Self {
+            active: config.active,
+            profiles,
+        }
+    }
+}
+
+impl Render for ProfileList {
+    fn render_table(&self) -> String {
+        if self.profiles.is_empty() {
+            return "No profiles. Add one with `chitin config profile add <name> --rpc <url>`."
+                .to_string();
+        }
+        format_table(&self.profiles)
+    }
+}
@@ -0,0 +1,192 @@
+// crates/chitin-cli/src/commands/drift.rs
+//
+// `chitin drift {status}` — embedding drift inspection.
+//
+// `status` lists the registered model versions and their lifecycle status,
+// canary drift for every migration in progress, and how retrieval compares
+// under each migration's old and new model. Use `chitin molt` to plan and
+// drive the migrations themselves.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use tabled::Tabled;
+
+use chitin_drift::detection::DriftRecommendation;
+use chitin_drift::evaluation::{EvalSet, LabelSource};
+use chitin_rpc::handlers::drift::GetDriftStatusResponse;
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Drift subcommands.
+#[derive(Debug, Subcommand)]
+pub enum DriftCmd {
+    /// Show model versions, drift, and retrieval impact of each migration.
+    Status {
+        /// Labeled queries (JSON eval set) to evaluate retrieval on, instead
+        /// of neighborhoods of hardened Polyps.
+        #[arg(long)]
+        eval_set: Option<PathBuf>,
+        /// Recall cutoff (default: 5).
+        #[arg(long)]
+        k: Option<usize>,
+    },
+}
+
+/// Run the drift subcommand.
+pub async fn run(
+    cmd: &DriftCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        DriftCmd::Status { eval_set, k } => {
+            let eval_set: Option<EvalSet> = match eval_set {
+                Some(path) => {
+                    let contents = std::fs::read_to_string(path)
+                        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+                    Some(serde_json::from_str(&contents).map_err(|e| {
+                        format!("Could not parse eval set {}: {}", path.display(), e)
+                    })?)
+                }
+                None => None,
+            };
+            let params = serde_json::json!({ "eval_set": eval_set, "k": k });
+            let resp: GetDriftStatusResponse =
+                rpc_result(rpc_endpoint, "drift/status", params).await?;
+            output::print(&resp, format)
+        }
+    }
+}
+
+/// A row in the model version table.
+#[derive(Tabled)]
+struct VersionRow {
+    #[tabled(rename = "Model")]
+    model: String,
+    #[tabled(rename = "Version")]
+    version: u32,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Activated")]
+    activated: u64,
+    #[tabled(rename = "Deprecated")]
+    deprecated: String,
+    #[tabled(rename = "Molt Deadline")]
+    deadline: String,
+}
+
+/// A row in the drift report table.
+#[derive(Tabled)]
+struct ReportRow {
+    #[tabled(rename = "Migration")]
+    migration: String,
+    #[tabled(rename = "Mean Shift")]
+    mean_shift: String,
+    #[tabled(rename = "Max Shift")]
+    max_shift: String,
+    #[tabled(rename = "Overlap")]
+    overlap: String,
+    #[tabled(rename = "Affected")]
+    affected: String,
+    #[tabled(rename = "Recommendation")]
+    recommendation: String,
+}
+
+/// A row in the retrieval evaluation table.
+#[derive(Tabled)]
+struct EvaluationRow {
+    #[tabled(rename = "Migration")]
+    migration: String,
+    #[tabled(rename = "Labels")]
+    labels: String,
+    #[tabled(rename = "Queries")]
+    queries: usize,
+    #[tabled(rename = "Recall@k")]
+    recall: String,
+    #[tabled(rename = "MRR")]
+    mrr: String,
+}
+
+fn epoch_or_dash(epoch: Option<u64>) -> String {
+    epoch
+        .map(|e| e.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl Render for GetDriftStatusResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!("Drift status at epoch {}", self.epoch)];
+        if self.versions.is_empty() {
+            lines.push("No model versions registered.".to_string());
+        } else {
+            let rows: Vec<VersionRow> = self
+                .versions
+                .iter()
+                .map(|v| VersionRow {
+                    model: v.version.model_id.clone(),
+                    version: v.version.version,
+                    status: format!("{:?}", v.status),
+                    activated: v.version.activated_at_epoch,
+                    deprecated: epoch_or_dash(v.version.deprecated_at_epoch),
+                    deadline: epoch_or_dash(v.version.molt_deadline_epoch),
+                })
+                .collect();
+            lines.push(format_table(&rows));
+        }
+
+        if self.reports.is_empty() {
+            lines.push("No migrations in progress.".to_string());
+            return lines.join("\n");
+        }
+        lines.push("Canary drift:".to_string());
+        let rows: Vec<ReportRow> = self
+            .reports
+            .iter()
+            .map(|r| ReportRow {
+                migration: format!("{} -> {}", r.old_model, r.new_model),
+                mean_shift: format!("{:.4}", r.metrics.mean_cosine_shift),
+                max_shift: format!("{:.4}", r.metrics.max_cosine_shift),
+                overlap: format!("{:.3}", r.metrics.mean_neighborhood_overlap),
+                affected: format!("{} of {}", r.metrics.affected_polyps, r.canaries),
+                recommendation: match r.recommendation {
+                    DriftRecommendation::Keep => "keep".to_string(),
+                    DriftRecommendation::Reembed => "re-embed".to_string(),
+                },
+            })
+            .collect();
+        lines.push(format_table(&rows));
+        for report in self.reports.iter().filter(|r| !r.reasons.is_empty()) {
+            for reason in &report.reasons {
+                lines.push(format!("  {}: {}", report.old_model, reason));
+            }
+        }
+
+        if !self.evaluations.is_empty() {
+            lines.push("Retrieval (old -> new):".to_string());
+            let rows: Vec<EvaluationRow> = self
+                .evaluations
+                .iter()
+                .map(|e| EvaluationRow {
+                    migration: format!("{} -> {}", e.baseline.model, e.candidate.model),
+                    labels: match e.labels {
+                        LabelSource::Labeled => "labeled".to_string(),
+                        LabelSource::HardenedNeighbors => "hardened".to_string(),
+                    },
+                    queries: e.candidate.queries,
+                    recall: format!(
+                        "{:.3} -> {:.3} ({:+.3})",
+                        e.baseline.recall_at_k, e.candidate.recall_at_k, e.recall_delta
+                    ),
+                    mrr: format!(
+                        "{:.3} -> {:.3} ({:+.3})",
+                        e.baseline.mrr, e.candidate.mrr, e.mrr_delta
+                    ),
+                })
+                .collect();
+            lines.push(format_table(&rows));
+        }
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,185 @@
+// crates/chitin-cli/src/commands/epoch.rs
+//
+// `chitin epoch {status, result}` — epoch progress and consensus results.
+//
+// `result` summarizes an epoch's Yuma-Semantic Consensus: the Coral Nodes
+// with the largest incentive, each Tide Node's share of dividends, and how
+// many Polyps hardened. Node UIDs are positions in the result vectors.
+
+use clap::Subcommand;
+use serde::Serialize;
+use tabled::Tabled;
+
+use chitin_rpc::handlers::validation::{GetConsensusResultResponse, GetEpochStatusResponse};
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Epoch inspection subcommands.
+#[derive(Debug, Subcommand)]
+pub enum EpochCmd {
+    /// Show the current epoch, phase, and block.
+    Status,
+    /// Show the consensus result of an epoch.
+    Result {
+        /// Epoch to show (default: the latest finalized epoch).
+        #[arg(long)]
+        epoch: Option<u64>,
+        /// Number of top-incentive Coral Nodes to list.
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+}
+
+/// Run the epoch subcommand.
+pub async fn run(
+    cmd: &EpochCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        EpochCmd::Status => {
+            let resp: GetEpochStatusResponse =
+                rpc_result(rpc_endpoint, "validation/epoch", serde_json::json!({})).await?;
+            output::print(&resp, format)
+        }
+        EpochCmd::Result { epoch, top } => {
+            let params = serde_json::json!({ "epoch": epoch });
+            let resp: GetConsensusResultResponse =
+                rpc_result(rpc_endpoint, "validation/result", params).await?;
+            output::print(&ResultSummary { resp, top: *top }, format)
+        }
+    }
+}
+
+impl Render for GetEpochStatusResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Epoch {}  |  Phase: {}", self.epoch, self.phase),
+            format!("  Block:            {}", self.block),
+        ];
+        if self.blocks_per_epoch > 0 {
+            lines.push(format!(
+                "  Epoch progress:   {}/{} blocks",
+                self.block % self.blocks_per_epoch,
+                self.blocks_per_epoch
+            ));
+        }
+        lines.push(format!(
+            "  Left in phase:    {} blocks",
+            self.blocks_remaining
+        ));
+        lines.push(format!(
+            "  Scores submitted: {}/{} validators",
+            self.scores_submitted, self.total_validators
+        ));
+        lines.join("\n")
+    }
+}
+
+/// A consensus result, with how many corals the table lists.
+#[derive(Serialize)]
+struct ResultSummary {
+    #[serde(flatten)]
+    resp: GetConsensusResultResponse,
+    #[serde(skip)]
+    top: usize,
+}
+
+/// A row in the top-incentive table.
+#[derive(Tabled)]
+struct IncentiveRow {
+    #[tabled(rename = "Coral UID")]
+    uid: usize,
+    #[tabled(rename = "Incentive")]
+    incentive: String,
+    #[tabled(rename = "Share")]
+    share: String,
+    #[tabled(rename = "Consensus Weight")]
+    consensus: String,
+}
+
+/// A row in the dividends table.
+#[derive(Tabled)]
+struct DividendRow {
+    #[tabled(rename = "Tide UID")]
+    uid: usize,
+    #[tabled(rename = "Dividend")]
+    dividend: String,
+    #[tabled(rename = "Share")]
+    share: String,
+}
+
+/// `value` as a percentage of `total`.
+fn share(value: f64, total: f64) -> String {
+    if total > 0.0 {
+        format!("{:.1}%", 100.0 * value / total)
+    } else {
+        "-".to_string()
+    }
+}
+
+impl Render for ResultSummary {
+    fn render_table(&self) -> String {
+        let resp = &self.resp;
+        let title = match resp.epoch {
+            Some(epoch) => format!("Epoch {} consensus", epoch),
+            None => "Latest consensus".to_string(),
+        };
+        if !resp.finalized {
+            return format!("{}: not finalized.", title);
+        }
+        let mut lines = vec![title, format!("  Hardened polyps: {}", resp.hardened_count)];
+
+        let incentives = resp.incentives.as_deref().unwrap_or_default();
+        let consensus = resp.consensus_weights.as_deref().unwrap_or_default();
+        let total: f64 = incentives.iter().sum();
+        let mut ranked: Vec<(usize, f64)> = incentives.iter().copied().enumerate().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let rows: Vec<IncentiveRow> = ranked
+            .iter()
+            .filter(|(_, incentive)| *incentive > 0.0)
+            .take(self.top)
+            .map(|&(uid, incentive)| IncentiveRow {
+                uid,
+                incentive: format!("{:.4}", incentive),
+                share: share(incentive, total),
+                consensus: consensus
+                    .get(uid)
+                    .map(|c| format!("{:.4}", c))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        lines.push(String::new());
+        if rows.is_empty() {
+            lines.push("No Coral Node earned incentive.".to_string());
+        } else {
+            lines.push(format!(
+                "Top corals by incentive ({} of {})",
+                rows.len(),
+                ranked.len()
+            ));
+            lines.push(format_table(&rows));
+        }
+
+        let dividends = resp.dividends.as_deref().unwrap_or_default();
+        let total: f64 = dividends.iter().sum();
+        let rows: Vec<DividendRow> = dividends
+            .iter()
+            .enumerate()
+            .map(|(uid, &dividend)| DividendRow {
+                uid,
+                dividend: format!("{:.4}", dividend),
+                share: share(dividend, total),
+            })
+            .collect();
+        lines.push(String::new());
+        if rows.is_empty() {
+            lines.push("No dividends.".to_string());
+        } else {
+            lines.push("Dividend shares".to_string());
+            lines.push(format_table(&rows));
+        }
+        lines.join("\n")
+    }
+}
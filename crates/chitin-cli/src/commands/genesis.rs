@@ -0,0 +1,272 @@
+// crates/chitin-cli/src/commands/genesis.rs
+//
+// `chitin genesis {create, verify}` — genesis ceremony commands.
+//
+// `create` assembles a genesis file from the initial nodes, trust seeds,
+// economics parameters, and the model versions nodes will run, and prints its
+// hash for operators to pin as `genesis_hash`. `verify` checks a genesis file
+// and, given a node config, that the config's model versions match it.
+
+use chrono::{DateTime, Utc};
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::genesis::{model_registry_hash, Genesis, GenesisAccount, GenesisNode};
+use chitin_core::embedding::ModelVersion;
+use chitin_core::identity::NodeType;
+use chitin_drift::versioning::VersionRegistry;
+use chitin_economics::{EconomicsParams, RAO_PER_CTN};
+use chitin_reputation::genesis::GenesisValidator;
+
+use crate::output::{self, OutputFormat, Render};
+
+/// Genesis subcommands.
+#[derive(Debug, Subcommand)]
+pub enum GenesisCmd {
+    /// Create a genesis file.
+    Create {
+        /// Network ID of the new network.
+        #[arg(long)]
+        network_id: String,
+        /// Initial node as HOTKEY,COLDKEY,TYPE,STAKE_CTN[,ADDR] (keys in hex;
+        /// TYPE is coral, tide, hybrid, or seed). Repeat per node; UIDs follow
+        /// the order given.
+        #[arg(long = "validator", required = true)]
+        validators: Vec<String>,
+        /// Liquid balance as COLDKEY,BALANCE_CTN (key in hex). Repeat per
+        /// account.
+        #[arg(long = "account")]
+        accounts: Vec<String>,
+        /// Trust seed as DID[=WEIGHT]. Repeat per validator.
+        #[arg(long = "trust-seed")]
+        trust_seeds: Vec<String>,
+        /// TOML file with the `[[model_versions]]` nodes will run (e.g. a
+        /// node config). Without it, nodes must configure no model versions.
+        #[arg(long)]
+        models: Option<String>,
+        /// JSON file of economics parameters; omitted keys keep the defaults.
+        #[arg(long)]
+        economics: Option<String>,
+        /// Launch time (RFC 3339; default: now).
+        #[arg(long)]
+        genesis_time: Option<String>,
+        /// Output path.
+        #[arg(long, default_value = "genesis.json")]
+        out: String,
+    },
+    /// Check a genesis file and print its hash.
+    Verify {
+        /// Path to the genesis file.
+        file: String,
+        /// Expected hash.
+        #[arg(long)]
+        hash: Option<String>,
+        /// TOML file whose `[[model_versions]]` must match the genesis.
+        #[arg(long)]
+        models: Option<String>,
+    },
+}
+
+/// The `[[model_versions]]` entries of a TOML file; other keys are ignored.
+#[derive(Debug, Deserialize)]
+struct ModelsFile {
+    #[serde(default)]
+    model_versions: Vec<ModelVersion>,
+}
+
+/// Run the genesis subcommand.
+pub async fn run(cmd: &GenesisCmd, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        GenesisCmd::Create {
+            network_id,
+            validators,
+            accounts,
+            trust_seeds,
+            models,
+            economics,
+            genesis_time,
+            out,
+        } => {
+            let validators = validators
+                .iter()
+                .map(|spec| parse_validator(spec.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let accounts = accounts
+                .iter()
+                .map(|spec| parse_account(spec.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let trust_seeds = trust_seeds
+                .iter()
+                .map(|spec| parse_trust_seed(spec.as_str()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let economics: EconomicsParams = match economics {
+                Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                None => EconomicsParams::default(),
+            };
+            let genesis_time = match genesis_time {
+                Some(time) => DateTime::parse_from_rfc3339(time)?.with_timezone(&Utc),
+                None => Utc::now(),
+            };
+            let registry = load_models(models.as_deref())?;
+
+            let genesis = Genesis {
+                network_id: network_id.clone(),
+                genesis_time,
+                validators,
+                accounts,
+                trust_seeds,
+                economics,
+                model_registry_hash: model_registry_hash(&registry)?,
+            };
+            genesis.validate()?;
+            genesis.save(out)?;
+
+            output::print(&GenesisSummary::new("Wrote genesis file", out, &genesis)?, format)?;
+        }
+        GenesisCmd::Verify { file, hash, models } => {
+            let genesis = Genesis::load(file)?;
+            genesis.validate()?;
+            let actual = genesis.hash()?;
+            if let Some(expected) = hash {
+                if !expected.eq_ignore_ascii_case(&actual) {
+                    let message = format!("Hash {} does not match expected {}", actual, expected);
+                    return Err(message.into());
+                }
+            }
+            if models.is_some() {
+                genesis.verify_model_registry(&load_models(models.as_deref())?)?;
+            }
+
+            output::print(&GenesisSummary::new("Genesis file OK", file, &genesis)?, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// What a genesis file defines, with its hash.
+#[derive(Debug, Serialize)]
+struct GenesisSummary {
+    #[serde(skip)]
+    outcome: &'static str,
+    file: String,
+    network_id: String,
+    genesis_time: DateTime<Utc>,
+    nodes: usize,
+    total_stake_rao: u64,
+    accounts: usize,
+    total_balance_rao: u64,
+    trust_seeds: usize,
+    model_registry_hash: String,
+    hash: String,
+}
+
+impl GenesisSummary {
+    fn new(
+        outcome: &'static str,
+        file: &str,
+        genesis: &Genesis,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            outcome,
+            file: file.to_string(),
+            network_id: genesis.network_id.clone(),
+            genesis_time: genesis.genesis_time,
+            nodes: genesis.validators.len(),
+            total_stake_rao: genesis.validators.iter().map(|v| v.stake_rao).sum(),
+            accounts: genesis.accounts.len(),
+            total_balance_rao: genesis.accounts.iter().map(|a| a.balance_rao).sum(),
+            trust_seeds: genesis.trust_seeds.len(),
+            model_registry_hash: genesis.model_registry_hash.clone(),
+            hash: genesis.hash()?,
+        })
+    }
+}
+
+impl Render for GenesisSummary {
+    fn render_table(&self) -> String {
+        [
+            format!("{}: {}", self.outcome, self.file),
+            format!("  Network ID:   {}", self.network_id),
+            format!("  Genesis time: {}", self.genesis_time.to_rfc3339()),
+            format!("  Nodes:        {}", self.nodes),
+            format!(
+                "  Total stake:  {} CTN",
+                self.total_stake_rao as f64 / RAO_PER_CTN as f64
+            ),
+            format!(
+                "  Accounts:     {} ({} CTN)",
+                self.accounts,
+                self.total_balance_rao as f64 / RAO_PER_CTN as f64
+            ),
+            format!("  Trust seeds:  {}", self.trust_seeds),
+            format!("  Models hash:  {}", self.model_registry_hash),
+            format!("  Hash:         {}", self.hash),
+        ]
+        .join("\n")
+    }
+}
+
+/// Parse `HOTKEY,COLDKEY,TYPE,STAKE_CTN[,ADDR]`.
+fn parse_validator(spec: &str) -> Result<GenesisNode, Box<dyn std::error::Error>> {
+    let fields: Vec<&str> = spec.split(',').map(str::trim).collect();
+    if !(4..=5).contains(&fields.len()) {
+        return Err(format!("Expected HOTKEY,COLDKEY,TYPE,STAKE_CTN[,ADDR], got {:?}", spec).into());
+    }
+    let node_type = match fields[2].to_ascii_lowercase().as_str() {
+        "coral" => NodeType::Coral,
+        "tide" => NodeType::Tide,
+        "hybrid" => NodeType::Hybrid,
+        "seed" => NodeType::Seed,
+        other => return Err(format!("Unknown node type {:?}", other).into()),
+    };
+    let stake_ctn: f64 = fields[3].parse()?;
+    if !stake_ctn.is_finite() || stake_ctn < 0.0 {
+        return Err(format!("Invalid stake {:?}", fields[3]).into());
+    }
+    Ok(GenesisNode {
+        hotkey: fields[0].to_ascii_lowercase(),
+        coldkey: fields[1].to_ascii_lowercase(),
+        node_type,
+        stake_rao: (stake_ctn * RAO_PER_CTN as f64).round() as u64,
+        axon_addr: fields.get(4).copied().unwrap_or_default().to_string(),
+    })
+}
+
+/// Parse `COLDKEY,BALANCE_CTN`.
+fn parse_account(spec: &str) -> Result<GenesisAccount, Box<dyn std::error::Error>> {
+    let (coldkey, balance) = match spec.split_once(',') {
+        Some((coldkey, balance)) => (coldkey.trim(), balance.trim()),
+        None => return Err(format!("Expected COLDKEY,BALANCE_CTN, got {:?}", spec).into()),
+    };
+    let balance_ctn: f64 = balance.parse()?;
+    if !balance_ctn.is_finite() || balance_ctn < 0.0 {
+        return Err(format!("Invalid balance {:?}", balance).into());
+    }
+    Ok(GenesisAccount {
+        coldkey: coldkey.to_ascii_lowercase(),
+        balance_rao: (balance_ctn * RAO_PER_CTN as f64).round() as u64,
+    })
+}
+
+/// Parse `DID[=WEIGHT]`.
+fn parse_trust_seed(spec: &str) -> Result<GenesisValidator, Box<dyn std::error::Error>> {
+    let (did, weight) = match spec.split_once('=') {
+        Some((did, weight)) => (did, weight.parse()?),
+        None => (spec, 1.0),
+    };
+    Ok(GenesisValidator {
+        did: did.to_string(),
+        weight,
+        domains: Vec::new(),
+    })
+}
+
+/// Load the model version registry from `path`, or an empty one.
+fn load_models(path: Option<&str>) -> Result<VersionRegistry, Box<dyn std::error::Error>> {
+    let versions = match path {
+        Some(path) => toml::from_str::<ModelsFile>(&std::fs::read_to_string(path)?)?.model_versions,
+        None => Vec::new(),
+    };
+    Ok(VersionRegistry::from_versions(versions)?)
+}
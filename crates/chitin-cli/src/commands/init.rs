@@ -52,7 +52,7 @@ log_level = "info"
     }
 
     // Generate initial keypair.
-    let keys_dir = config_dir.join("keys");
+    let keys_dir = super::wallet::get_keys_dir()?;
     if !keys_dir.exists() {
         fs::create_dir_all(&keys_dir)?;
     }
@@ -0,0 +1,570 @@
+// crates/chitin-cli/src/commands/keys.rs
+//
+// `chitin keys {new, import-mnemonic, rotate-hotkey, inspect}` — coldkey
+// and hotkey lifecycle in ~/.chitin/keys.
+//
+// With `--mnemonic`, keys are derived from a 24-word BIP-39 recovery phrase
+// (see `chitin_core::mnemonic`): the coldkey at m/0' and hotkey `n` at
+// m/1'/n', so the phrase alone restores the identity and any hotkey. The
+// phrase is never written to disk. `--encrypt` seals secrets in an
+// `EncryptedKeystore` under the passphrase in CHITIN_KEYSTORE_PASSPHRASE.
+//
+// `rotate-hotkey` replaces the node's hotkey with a fresh (or
+// phrase-derived) one, moves the old key to keys/rotations/, and records a
+// coldkey-signed `HotkeyRotation` certificate next to it. The daemon picks
+// the new hotkey up on restart.
+
+use std::fs;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use chitin_core::crypto::Keypair;
+use chitin_core::identity::{HotkeyRotation, NodeIdentity};
+use chitin_core::keystore::{EncryptedKeystore, SecretKey, Zeroizing};
+use chitin_core::mnemonic::{self, Seed};
+
+use crate::commands::wallet::{
+    confirm, decode_key, get_keys_dir, hex_encode, load_secret, PASSPHRASE_ENV,
+};
+use crate::output::{self, OutputFormat, Render};
+
+/// Environment variable holding a recovery phrase to import.
+const MNEMONIC_ENV: &str = "CHITIN_MNEMONIC";
+
+/// Environment variable holding the optional BIP-39 passphrase ("25th
+/// word") that salts the recovery phrase.
+const MNEMONIC_PASSPHRASE_ENV: &str = "CHITIN_MNEMONIC_PASSPHRASE";
+
+/// Format version of rotation certificate files.
+const ROTATION_FILE_VERSION: u32 = 1;
+
+/// Key management subcommands.
+#[derive(Debug, Subcommand)]
+pub enum KeysCmd {
+    /// Generate a new coldkey and hotkey.
+    New {
+        /// Derive the keys from a new 24-word recovery phrase and print it.
+        #[arg(long)]
+        mnemonic: bool,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Restore the coldkey and a hotkey from a recovery phrase.
+    ///
+    /// The phrase is read from CHITIN_MNEMONIC, or from stdin.
+    ImportMnemonic {
+        /// Index of the hotkey to restore.
+        #[arg(long, default_value_t = 0)]
+        hotkey_index: u32,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Replace the hotkey and record a coldkey-signed rotation certificate.
+    RotateHotkey {
+        /// Derive the new hotkey at this index from the recovery phrase
+        /// (read like `import-mnemonic`) instead of generating one.
+        #[arg(long)]
+        index: Option<u32>,
+        /// Encrypt the new hotkey (default: if the old one was encrypted).
+        #[arg(long)]
+        encrypt: bool,
+        /// Rotate without asking for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Show the DID, public keys, keystore encryption, and rotations.
+    Inspect,
+}
+
+/// How `new` and `import-mnemonic` write keys.
+#[derive(Debug, Args)]
+pub struct WriteArgs {
+    /// Encrypt the secrets with the passphrase in CHITIN_KEYSTORE_PASSPHRASE.
+    #[arg(long)]
+    encrypt: bool,
+    /// Overwrite existing keys.
+    #[arg(long)]
+    force: bool,
+}
+
+/// Run the keys subcommand.
+pub async fn run(cmd: &KeysCmd, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        KeysCmd::New {
+            mnemonic: false,
+            write,
+        } => {
+            let coldkey = SecretKey::new(Keypair::generate().signing_key.to_bytes());
+            let hotkey = SecretKey::new(Keypair::generate().signing_key.to_bytes());
+            write_keys(&coldkey, &hotkey, write)
+        }
+        KeysCmd::New {
+            mnemonic: true,
+            write,
+        } => {
+            let phrase = mnemonic::generate_mnemonic();
+            let seed = seed_from(&phrase)?;
+            let coldkey = mnemonic::derive_coldkey(&seed);
+            write_keys(&coldkey, &mnemonic::derive_hotkey(&seed, 0), write)?;
+            println!();
+            println!("Recovery phrase (write it down; it is not saved anywhere):");
+            println!();
+            for (i, words) in phrase
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .chunks(6)
+                .enumerate()
+            {
+                let numbered: Vec<String> = words
+                    .iter()
+                    .enumerate()
+                    .map(|(j, word)| format!("{:>2}. {:<9}", 6 * i + j + 1, word))
+                    .collect();
+                println!("  {}", numbered.join(" ").trim_end());
+            }
+            println!();
+            println!("IMPORTANT: Anyone with this phrase controls your coldkey.");
+            Ok(())
+        }
+        KeysCmd::ImportMnemonic {
+            hotkey_index,
+            write,
+        } => {
+            let seed = seed_from(&read_mnemonic()?)?;
+            let coldkey = mnemonic::derive_coldkey(&seed);
+            write_keys(
+                &coldkey,
+                &mnemonic::derive_hotkey(&seed, *hotkey_index),
+                write,
+            )
+        }
+        KeysCmd::RotateHotkey {
+            index,
+            encrypt,
+            yes,
+        } => rotate_hotkey(*index, *encrypt, *yes),
+        KeysCmd::Inspect => output::print(&inspect()?, format),
+    }
+}
+
+/// Derive the seed of `phrase`, salted with CHITIN_MNEMONIC_PASSPHRASE.
+fn seed_from(phrase: &str) -> Result<Seed, Box<dyn std::error::Error>> {
+    let passphrase = Zeroizing::new(std::env::var(MNEMONIC_PASSPHRASE_ENV).unwrap_or_default());
+    Ok(mnemonic::mnemonic_seed(phrase, &passphrase)?)
+}
+
+/// Read a recovery phrase from CHITIN_MNEMONIC or stdin.
+fn read_mnemonic() -> Result<Zeroizing<String>, Box<dyn std::error::Error>> {
+    if let Ok(phrase) = std::env::var(MNEMONIC_ENV) {
+        return Ok(Zeroizing::new(phrase));
+    }
+    let mut phrase = Zeroizing::new(String::new());
+    if std::io::stdin().is_terminal() {
+        let mut stderr = std::io::stderr();
+        write!(stderr, "Recovery phrase: ")?;
+        stderr.flush()?;
+        std::io::stdin().read_line(&mut phrase)?;
+    } else {
+        std::io::stdin().read_to_string(&mut phrase)?;
+    }
+    Ok(phrase)
+}
+
+/// Write a coldkey and hotkey (public and secret files) to the keys
+/// directory, refusing to replace existing keys without `--force`.
+fn write_keys(
+    coldkey: &SecretKey,
+    hotkey: &SecretKey,
+    args: &WriteArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let keys_dir = get_keys_dir()?;
+    fs::create_dir_all(&keys_dir)?;
+    if !args.force {
+        for name in ["coldkey.secret", "hotkey.secret"] {
+            if keys_dir.join(name).exists() {
+                return Err(format!(
+                    "{} already exists; pass --force to replace it",
+                    keys_dir.join(name).display()
+                )
+                .into());
+            }
+        }
+    }
+    let passphrase = if args.encrypt {
+        Some(keystore_passphrase()?)
+    } else {
+        None
+    };
+
+    let coldkey_pub = write_key(&keys_dir, "coldkey", coldkey, passphrase.as_ref())?;
+    let hotkey_pub = write_key(&keys_dir, "hotkey", hotkey, passphrase.as_ref())?;
+    println!("Keys written to {}", keys_dir.display());
+    println!("  DID:     {}", NodeIdentity::derive_did(&coldkey_pub));
+    println!("  Coldkey: {}", hex_encode(&coldkey_pub));
+    println!("  Hotkey:  {}", hex_encode(&hotkey_pub));
+    if passphrase.is_none() {
+        println!("  Secrets are unencrypted; pass --encrypt to seal them in a keystore.");
+    }
+    Ok(())
+}
+
+/// The passphrase for new keystores, from CHITIN_KEYSTORE_PASSPHRASE.
+fn keystore_passphrase() -> Result<Zeroizing<String>, Box<dyn std::error::Error>> {
+    match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(Zeroizing::new(passphrase)),
+        _ => Err(format!("--encrypt needs a passphrase in {}", PASSPHRASE_ENV).into()),
+    }
+}
+
+/// Write `<name>.pub` and `<name>.secret`, returning the public key.
+fn write_key(
+    keys_dir: &Path,
+    name: &str,
+    secret: &SecretKey,
+    passphrase: Option<&Zeroizing<String>>,
+) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let public_key = Keypair::from_secret_bytes(secret).public_key_bytes();
+    let contents = match passphrase {
+        Some(passphrase) => {
+            Zeroizing::new(EncryptedKeystore::encrypt(secret, passphrase.as_bytes())?.to_json()?)
+        }
+        None => Zeroizing::new(hex_encode(secret.as_ref())),
+    };
+    write_private(
+        &keys_dir.join(format!("{}.secret", name)),
+        contents.as_bytes(),
+    )?;
+    fs::write(
+        keys_dir.join(format!("{}.pub", name)),
+        hex_encode(&public_key),
+    )?;
+    Ok(public_key)
+}
+
+/// Write a file readable only by its owner on Unix.
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Could not write {} ({})", path.display(), e))?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Hotkey rotation
+// ---------------------------------------------------------------------------
+
+/// A rotation certificate as stored in keys/rotations/.
+#[derive(Debug, Serialize, Deserialize)]
+struct RotationFile {
+    version: u32,
+    did: String,
+    /// Hex coldkey that signed the rotation.
+    coldkey: String,
+    old_hotkey: String,
+    new_hotkey: String,
+    /// Unix time (seconds) the rotation was issued.
+    issued_at: i64,
+    /// Hex ed25519 signature by the coldkey.
+    signature: String,
+}
+
+impl RotationFile {
+    fn new(rotation: &HotkeyRotation) -> Self {
+        Self {
+            version: ROTATION_FILE_VERSION,
+            did: rotation.did(),
+            coldkey: hex_encode(&rotation.coldkey),
+            old_hotkey: hex_encode(&rotation.old_hotkey),
+            new_hotkey: hex_encode(&rotation.new_hotkey),
+            issued_at: rotation.issued_at,
+            signature: hex_encode(&rotation.signature),
+        }
+    }
+
+    fn to_rotation(&self) -> Result<HotkeyRotation, Box<dyn std::error::Error>> {
+        Ok(HotkeyRotation {
+            coldkey: decode_key(&self.coldkey)?,
+            old_hotkey: decode_key(&self.old_hotkey)?,
+            new_hotkey: decode_key(&self.new_hotkey)?,
+            issued_at: self.issued_at,
+            signature: decode_hex(&self.signature).ok_or("Malformed rotation signature")?,
+        })
+    }
+}
+
+fn rotate_hotkey(
+    index: Option<u32>,
+    encrypt: bool,
+    yes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let keys_dir = get_keys_dir()?;
+    let coldkey_pub = read_public_key(&keys_dir.join("coldkey.pub"))?
+        .ok_or("No coldkey found. Run `chitin keys new` first.")?;
+    let old_hotkey = read_public_key(&keys_dir.join("hotkey.pub"))?
+        .ok_or("No hotkey to rotate. Run `chitin keys new` first.")?;
+    let old_secret_path = keys_dir.join("hotkey.secret");
+    let was_encrypted = fs::read_to_string(&old_secret_path)
+        .is_ok_and(|contents| EncryptedKeystore::is_keystore(&contents));
+
+    // A phrase yields both the coldkey that signs and the new hotkey, so the
+    // coldkey secret need not be on this machine.
+    let (coldkey, new_secret) = match index {
+        Some(index) => {
+            let seed = seed_from(&read_mnemonic()?)?;
+            (
+                mnemonic::derive_coldkey(&seed),
+                mnemonic::derive_hotkey(&seed, index),
+            )
+        }
+        None => (
+            crate::commands::wallet::load_coldkey_secret()?,
+            SecretKey::new(Keypair::generate().signing_key.to_bytes()),
+        ),
+    };
+    if Keypair::from_secret_bytes(&coldkey).public_key_bytes() != coldkey_pub {
+        return Err(format!(
+            "The signing coldkey does not match {}",
+            keys_dir.join("coldkey.pub").display()
+        )
+        .into());
+    }
+    let new_hotkey = Keypair::from_secret_bytes(&new_secret).public_key_bytes();
+    if new_hotkey == old_hotkey {
+        return Err("The new hotkey is the current hotkey".into());
+    }
+    let passphrase = if encrypt || was_encrypted {
+        Some(keystore_passphrase()?)
+    } else {
+        None
+    };
+
+    let summary = format!(
+        "Rotate the hotkey of {}\n  from {}\n  to   {}",
+        NodeIdentity::derive_did(&coldkey_pub),
+        hex_encode(&old_hotkey),
+        hex_encode(&new_hotkey)
+    );
+    if !yes && !confirm(&summary)? {
+        println!("Rotation cancelled.");
+        return Ok(());
+    }
+
+    let issued_at = chrono::Utc::now().timestamp();
+    let rotation = HotkeyRotation::sign(&coldkey, old_hotkey, new_hotkey, issued_at)?;
+    let rotations_dir = keys_dir.join("rotations");
+    fs::create_dir_all(&rotations_dir)?;
+    let stem = format!("{}-{}", issued_at, &hex_encode(&old_hotkey)[..8]);
+    if old_secret_path.exists() {
+        fs::rename(
+            &old_secret_path,
+            rotations_dir.join(format!("{}.secret", stem)),
+        )?;
+    }
+    fs::rename(
+        keys_dir.join("hotkey.pub"),
+        rotations_dir.join(format!("{}.pub", stem)),
+    )?;
+    let certificate_path = rotations_dir.join(format!("{}.json", stem));
+    fs::write(
+        &certificate_path,
+        serde_json::to_string_pretty(&RotationFile::new(&rotation))?,
+    )?;
+    write_key(&keys_dir, "hotkey", &new_secret, passphrase.as_ref())?;
+
+    println!("Hotkey rotated.");
+    println!("  New hotkey:  {}", hex_encode(&new_hotkey));
+    println!("  Certificate: {}", certificate_path.display());
+    println!(
+        "  Old key:     {}",
+        rotations_dir.join(format!("{}.*", stem)).display()
+    );
+    println!("Restart chitin-daemon to use the new hotkey.");
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Inspection
+// ---------------------------------------------------------------------------
+
+/// What `keys inspect` reports.
+#[derive(Debug, Serialize)]
+struct KeysReport {
+    keys_dir: String,
+    did: Option<String>,
+    coldkey: KeyStatus,
+    hotkey: KeyStatus,
+    rotations: Vec<RotationStatus>,
+}
+
+/// A key's public half and how its secret is stored.
+#[derive(Debug, Serialize)]
+struct KeyStatus {
+    public_key: Option<String>,
+    /// "encrypted", "plaintext", "invalid", or "missing".
+    secret: &'static str,
+    /// Whether the secret file belongs to the public key, when both exist.
+    matches: Option<bool>,
+}
+
+/// A rotation certificate found in keys/rotations/.
+#[derive(Debug, Serialize)]
+struct RotationStatus {
+    file: String,
+    issued_at: Option<String>,
+    old_hotkey: Option<String>,
+    new_hotkey: Option<String>,
+    /// Signed by this identity's coldkey, and the signature checks out.
+    valid: bool,
+}
+
+fn inspect() -> Result<KeysReport, Box<dyn std::error::Error>> {
+    let keys_dir = get_keys_dir()?;
+    let coldkey = key_status(&keys_dir, "coldkey")?;
+    let coldkey_pub = match &coldkey.public_key {
+        Some(hex) => Some(decode_key(hex)?),
+        None => None,
+    };
+
+    let mut files: Vec<PathBuf> = match fs::read_dir(keys_dir.join("rotations")) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+    let rotations = files
+        .iter()
+        .map(|path| {
+            let rotation = fs::read_to_string(path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<RotationFile>(&json).ok())
+                .and_then(|file| file.to_rotation().ok());
+            RotationStatus {
+                file: path.display().to_string(),
+                issued_at: rotation.as_ref().and_then(|r| {
+                    chrono::DateTime::from_timestamp(r.issued_at, 0).map(|t| t.to_rfc3339())
+                }),
+                old_hotkey: rotation.as_ref().map(|r| hex_encode(&r.old_hotkey)),
+                new_hotkey: rotation.as_ref().map(|r| hex_encode(&r.new_hotkey)),
+                valid: rotation
+                    .as_ref()
+                    .is_some_and(|r| Some(r.coldkey) == coldkey_pub && r.verify()),
+            }
+        })
+        .collect();
+
+    Ok(KeysReport {
+        keys_dir: keys_dir.display().to_string(),
+        did: coldkey_pub.as_ref().map(NodeIdentity::derive_did),
+        coldkey,
+        hotkey: key_status(&keys_dir, "hotkey")?,
+        rotations,
+    })
+}
+
+/// Inspect `<name>.pub` and `<name>.secret` without unlocking anything.
+fn key_status(keys_dir: &Path, name: &str) -> Result<KeyStatus, Box<dyn std::error::Error>> {
+    let public_key = read_public_key(&keys_dir.join(format!("{}.pub", name)))?;
+    let secret_path = keys_dir.join(format!("{}.secret", name));
+    let (secret, secret_pub) = match fs::read_to_string(&secret_path).map(Zeroizing::new) {
+        Err(_) => ("missing", None),
+        Ok(contents) if EncryptedKeystore::is_keystore(&contents) => {
+            let keystore = EncryptedKeystore::from_json(&contents).ok();
+            match keystore.and_then(|k| k.public_key_bytes().ok()) {
+                Some(public_key) => ("encrypted", Some(public_key)),
+                None => ("invalid", None),
+            }
+        }
+        Ok(_) => match load_secret(&secret_path) {
+            Ok(secret) => (
+                "plaintext",
+                Some(Keypair::from_secret_bytes(&secret).public_key_bytes()),
+            ),
+            Err(_) => ("invalid", None),
+        },
+    };
+    Ok(KeyStatus {
+        public_key: public_key.map(|key| hex_encode(&key)),
+        secret,
+        matches: public_key.zip(secret_pub).map(|(a, b)| a == b),
+    })
+}
+
+/// Read a hex public key file, or `None` if it does not exist.
+fn read_public_key(path: &Path) -> Result<Option<[u8; 32]>, Box<dyn std::error::Error>> {
+    match fs::read_to_string(path) {
+        Ok(hex) => Ok(Some(
+            decode_key(hex.trim()).map_err(|e| format!("{}: {}", path.display(), e))?,
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Could not read {} ({})", path.display(), e).into()),
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+impl Render for KeysReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Keys directory: {}", self.keys_dir),
+            format!(
+                "  DID:     {}",
+                self.did
+                    .as_deref()
+                    .unwrap_or("(no coldkey; run `chitin keys new`)")
+            ),
+        ];
+        for (label, key) in [("Coldkey", &self.coldkey), ("Hotkey", &self.hotkey)] {
+            lines.push(format!(
+                "  {:<8} {}",
+                format!("{}:", label),
+                key.public_key.as_deref().unwrap_or("-")
+            ));
+            let mut secret = format!("           secret: {}", key.secret);
+            if key.matches == Some(false) {
+                secret.push_str(" (does not match the public key!)");
+            }
+            lines.push(secret);
+        }
+        if self.rotations.is_empty() {
+            lines.push("  Rotations: none".to_string());
+        } else {
+            lines.push(format!("  Rotations: {}", self.rotations.len()));
+            for rotation in &self.rotations {
+                let short = |key: &Option<String>| match key {
+                    Some(key) => format!("{}…", &key[..16]),
+                    None => "?".to_string(),
+                };
+                lines.push(format!(
+                    "    {}  {} -> {}  {}",
+                    rotation.issued_at.as_deref().unwrap_or("(unreadable)"),
+                    short(&rotation.old_hotkey),
+                    short(&rotation.new_hotkey),
+                    if rotation.valid { "valid" } else { "INVALID" }
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
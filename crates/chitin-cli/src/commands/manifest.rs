@@ -0,0 +1,134 @@
+// crates/chitin-cli/src/commands/manifest.rs
+//
+// `chitin manifest` — print the CLI's full command and flag tree as JSON,
+// read from the same definitions clap parses with, for generating wrappers
+// and reference docs.
+//
+// Global flags (`--rpc`, `--output`) are listed once, on the root command.
+// Hidden commands and flags, and clap's built-in `--help` and `--version`,
+// are left out.
+
+use clap::{Arg, ArgAction, Command};
+use serde::Serialize;
+
+use crate::output::{self, OutputFormat, Render};
+
+/// Manifest format version, bumped on incompatible changes to its shape.
+const MANIFEST_VERSION: u32 = 1;
+
+/// The manifest: the root command and the version of the CLI it describes.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    manifest_version: u32,
+    cli_version: Option<String>,
+    command: CommandEntry,
+}
+
+/// A command and its subcommands.
+#[derive(Debug, Serialize)]
+struct CommandEntry {
+    name: String,
+    /// Words typed to run it (e.g., "chitin polyp export").
+    path: String,
+    about: Option<String>,
+    aliases: Vec<String>,
+    args: Vec<ArgEntry>,
+    subcommands: Vec<CommandEntry>,
+    /// Whether a subcommand must be given.
+    subcommand_required: bool,
+}
+
+/// A flag, option, or positional argument.
+#[derive(Debug, Serialize)]
+struct ArgEntry {
+    id: String,
+    long: Option<String>,
+    short: Option<char>,
+    positional: bool,
+    help: Option<String>,
+    required: bool,
+    /// Whether it takes a value (false for plain flags).
+    takes_value: bool,
+    /// Whether it may be given more than once.
+    multiple: bool,
+    global: bool,
+    value_names: Vec<String>,
+    defaults: Vec<String>,
+    /// Accepted values, for enumerated values.
+    possible_values: Vec<String>,
+}
+
+/// Run the manifest command for the CLI `cli`.
+pub fn run(cli: Command, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = Manifest {
+        manifest_version: MANIFEST_VERSION,
+        cli_version: cli.get_version().map(str::to_string),
+        command: command_entry(&cli, ""),
+    };
+    output::print(&manifest, format)
+}
+
+fn command_entry(cmd: &Command, parent: &str) -> CommandEntry {
+    let path = match parent {
+        "" => cmd.get_name().to_string(),
+        parent => format!("{} {}", parent, cmd.get_name()),
+    };
+    let is_root = parent.is_empty();
+    CommandEntry {
+        name: cmd.get_name().to_string(),
+        about: cmd.get_about().map(|about| about.to_string()),
+        aliases: cmd.get_visible_aliases().map(str::to_string).collect(),
+        args: cmd
+            .get_arguments()
+            .filter(|arg| !arg.is_hide_set() && (is_root || !arg.is_global_set()))
+            .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+            .map(arg_entry)
+            .collect(),
+        subcommands: cmd
+            .get_subcommands()
+            .filter(|sub| !sub.is_hide_set())
+            .map(|sub| command_entry(sub, &path))
+            .collect(),
+        subcommand_required: cmd.is_subcommand_required_set(),
+        path,
+    }
+}
+
+fn arg_entry(arg: &Arg) -> ArgEntry {
+    let action = arg.get_action();
+    ArgEntry {
+        id: arg.get_id().to_string(),
+        long: arg.get_long().map(str::to_string),
+        short: arg.get_short(),
+        positional: arg.is_positional(),
+        help: arg.get_help().map(|help| help.to_string()),
+        required: arg.is_required_set(),
+        takes_value: action.takes_values(),
+        multiple: matches!(action, ArgAction::Append | ArgAction::Count),
+        global: arg.is_global_set(),
+        value_names: arg
+            .get_value_names()
+            .unwrap_or_default()
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        defaults: arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy().into_owned())
+            .collect(),
+        possible_values: arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect(),
+    }
+}
+
+impl Render for Manifest {
+    /// The manifest is for programs, so even the default output is JSON.
+    fn render_table(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| format!("Error: {}", e))
+    }
+}
@@ -1,10 +1,14 @@
 // crates/chitin-cli/src/commands/metagraph.rs
 //
 // `chitin metagraph` — display the Reef Metagraph (network state).
-//
-// Phase 1: Print a placeholder table. Real metagraph query in Phase 2+.
 
-use tabled::{Table, Tabled};
+use tabled::Tabled;
+
+use chitin_economics::RAO_PER_CTN;
+use chitin_rpc::handlers::metagraph::GetMetagraphResponse;
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
 
 /// A row in the metagraph display table.
 #[derive(Tabled)]
@@ -30,41 +34,45 @@ struct MetagraphRow {
 }
 
 /// Run the metagraph command.
-pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Reef Metagraph (Phase 1 placeholder)");
-    println!("Epoch: 0  |  Block: 0  |  Total Stake: 0 CTN");
-    println!();
-
-    // Phase 1: display a placeholder table with sample data.
-    let rows = vec![
-        MetagraphRow {
-            uid: 0,
-            node_type: "Coral".to_string(),
-            stake: "0 CTN".to_string(),
-            trust: "0.000".to_string(),
-            consensus: "0.000".to_string(),
-            incentive: "0.000".to_string(),
-            emission: "0".to_string(),
-            polyps: 0,
-            active: "--".to_string(),
-        },
-        MetagraphRow {
-            uid: 1,
-            node_type: "Tide".to_string(),
-            stake: "0 CTN".to_string(),
-            trust: "0.000".to_string(),
-            consensus: "0.000".to_string(),
-            incentive: "0.000".to_string(),
-            emission: "0".to_string(),
-            polyps: 0,
-            active: "--".to_string(),
-        },
-    ];
+pub async fn run(
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let resp: GetMetagraphResponse =
+        rpc_result(rpc_endpoint, "metagraph/get", serde_json::json!({})).await?;
+    output::print(&resp, format)
+}
 
-    let table = Table::new(&rows).to_string();
-    println!("{}", table);
-    println!();
-    println!("Note: Phase 1 placeholder data. Real metagraph in Phase 2+.");
+fn ctn(rao: u64) -> String {
+    format!("{} CTN", rao as f64 / RAO_PER_CTN as f64)
+}
 
-    Ok(())
+impl Render for GetMetagraphResponse {
+    fn render_table(&self) -> String {
+        let header = format!(
+            "Reef Metagraph\nEpoch: {}  |  Total Stake: {}  |  Hardened Polyps: {}\n",
+            self.epoch,
+            ctn(self.total_stake),
+            self.total_hardened_polyps
+        );
+        if self.nodes.is_empty() {
+            return format!("{}\nNo metagraph yet.", header);
+        }
+        let rows: Vec<MetagraphRow> = self
+            .nodes
+            .iter()
+            .map(|n| MetagraphRow {
+                uid: n.uid,
+                node_type: n.node_type.clone(),
+                stake: ctn(n.stake),
+                trust: format!("{:.3}", n.trust),
+                consensus: format!("{:.3}", n.consensus),
+                incentive: format!("{:.3}", n.incentive),
+                emission: n.emission.to_string(),
+                polyps: n.polyp_count,
+                active: if n.active { "yes" } else { "--" }.to_string(),
+            })
+            .collect();
+        format!("{}\n{}", header, format_table(&rows))
+    }
 }
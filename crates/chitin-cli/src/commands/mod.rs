@@ -2,10 +2,24 @@
 //
 // Command module declarations for the Chitin CLI.
 
+pub mod admin;
+pub mod bench;
+pub mod completions;
+pub mod config;
+pub mod drift;
+pub mod epoch;
+pub mod genesis;
 pub mod init;
+pub mod keys;
+pub mod manifest;
 pub mod metagraph;
+pub mod molt;
 pub mod polyp;
 pub mod query;
+pub mod reputation;
 pub mod stake;
 pub mod status;
+pub mod top;
+pub mod tx;
 pub mod wallet;
+pub mod weights;
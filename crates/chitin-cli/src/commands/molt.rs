@@ -0,0 +1,312 @@
+// crates/chitin-cli/src/commands/molt.rs
+//
+// `chitin molt {dry-run, plan, start, pause, resume, status}` — model
+// migration commands.
+//
+// The daemon molts a batch of each migration's Polyps every epoch. `plan`
+// dry-runs a migration and shows how much of it is left; `start` runs a
+// migration the registry has not scheduled; `pause` and `resume` stop and
+// restart all molting. These three are operator methods, served only to a
+// client on the node's host. `status` draws each migration's progress, per
+// zone, as re-embedded vs remaining Polyps (`--watch` keeps redrawing until
+// every migration is done).
+
+use std::time::Duration;
+
+use clap::Subcommand;
+use tabled::Tabled;
+
+use chitin_drift::molting::MoltProgress;
+use chitin_rpc::handlers::drift::{
+    GetMoltProgressResponse, MoltControlResponse, MoltDryRunResponse, MoltPlanResponse,
+};
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Width of progress bars, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// Molting subcommands.
+#[derive(Debug, Subcommand)]
+pub enum MoltCmd {
+    /// Estimate the cost and retrieval impact of a molt without performing it.
+    DryRun {
+        /// Model the Polyps are currently embedded under.
+        #[arg(long)]
+        from: String,
+        /// Model to re-embed them with.
+        #[arg(long)]
+        to: String,
+        /// Number of Polyps to sample (default: 64).
+        #[arg(long)]
+        sample: Option<usize>,
+        /// Cost of re-embedding one Polyp.
+        #[arg(long)]
+        cost_per_embedding: Option<f64>,
+    },
+    /// Dry-run a molt and show how much is left and how many epochs it takes.
+    Plan {
+        /// Model the Polyps are currently embedded under.
+        #[arg(long)]
+        from: String,
+        /// Model to re-embed them with.
+        #[arg(long)]
+        to: String,
+        /// Number of Polyps to sample (default: 64).
+        #[arg(long)]
+        sample: Option<usize>,
+        /// Cost of re-embedding one Polyp.
+        #[arg(long)]
+        cost_per_embedding: Option<f64>,
+    },
+    /// Start molting from one model to another at the next epoch.
+    Start {
+        /// Model the Polyps are currently embedded under.
+        #[arg(long)]
+        from: String,
+        /// Model to re-embed them with.
+        #[arg(long)]
+        to: String,
+        /// Polyps to molt per migration per epoch (default: unchanged).
+        #[arg(long)]
+        batch_size: Option<usize>,
+    },
+    /// Pause all molting until resumed.
+    Pause,
+    /// Resume paused molting.
+    Resume,
+    /// Show re-embedded vs remaining Polyps of each migration.
+    Status {
+        /// Only show migrations away from this model.
+        #[arg(long)]
+        from: Option<String>,
+        /// Keep redrawing until every migration is done.
+        #[arg(long)]
+        watch: bool,
+        /// Seconds between redraws with `--watch`.
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+    },
+}
+
+/// Run the molt subcommand.
+pub async fn run(
+    cmd: &MoltCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        MoltCmd::DryRun { from, to, sample, cost_per_embedding } => {
+            let params = serde_json::json!({
+                "from_model": from,
+                "to_model": to,
+                "sample_size": sample,
+                "cost_per_embedding": cost_per_embedding,
+            });
+
+            let resp: MoltDryRunResponse =
+                rpc_result(rpc_endpoint, "drift/molt_dry_run", params).await?;
+            output::print(&resp, format)?;
+        }
+        MoltCmd::Plan { from, to, sample, cost_per_embedding } => {
+            let params = serde_json::json!({
+                "from_model": from,
+                "to_model": to,
+                "sample_size": sample,
+                "cost_per_embedding": cost_per_embedding,
+            });
+            let resp: MoltPlanResponse =
+                rpc_result(rpc_endpoint, "drift/molt_plan", params).await?;
+            output::print(&resp, format)?;
+        }
+        MoltCmd::Start { from, to, batch_size } => {
+            let params = serde_json::json!({
+                "from_model": from,
+                "to_model": to,
+                "batch_size": batch_size,
+            });
+            let resp: MoltControlResponse =
+                rpc_result(rpc_endpoint, "admin/molt/start", params).await?;
+            output::print(&resp, format)?;
+        }
+        MoltCmd::Pause | MoltCmd::Resume => {
+            let method = match cmd {
+                MoltCmd::Pause => "admin/molt/pause",
+                _ => "admin/molt/resume",
+            };
+            let resp: MoltControlResponse =
+                rpc_result(rpc_endpoint, method, serde_json::json!({})).await?;
+            output::print(&resp, format)?;
+        }
+        MoltCmd::Status { from, watch, interval } => {
+            if *watch && *interval == 0 {
+                return Err("--interval must be positive".into());
+            }
+            let params = serde_json::json!({ "from_model": from });
+            loop {
+                let resp: GetMoltProgressResponse =
+                    rpc_result(rpc_endpoint, "drift/molt_progress", params.clone()).await?;
+                output::print(&resp, format)?;
+                let done = resp.migrations.iter().all(|m| m.remaining == 0);
+                if !*watch || done {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_secs(*interval)).await;
+                println!();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Render for MoltDryRunResponse {
+    fn render_table(&self) -> String {
+        let report = &self.report;
+        let mut lines = vec![
+            format!("Molt dry run: {} -> {}", report.from_model, report.to_model),
+            format!("  Candidates:        {}", report.candidates),
+            format!("  Sampled:           {}", report.sampled),
+            format!("  Mean embed time:   {:.3} ms", report.mean_embed_ms),
+            format!("  Est. duration:     {:.1} s", report.estimated_duration_secs),
+            format!("  Est. cost:         {:.4}", report.estimated_cost),
+            format!("  Mean cosine shift: {:.4}", report.mean_cosine_shift),
+            format!("  Retrieval overlap: {:.4}", report.retrieval_overlap),
+            format!("  Quality delta:     {:+.4}", report.retrieval_quality_delta),
+        ];
+        if report.reasons.is_empty() {
+            lines.push("  Verdict:           acceptable".to_string());
+        } else {
+            lines.push("  Verdict:           not recommended".to_string());
+            for reason in &report.reasons {
+                lines.push(format!("    - {}", reason));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// A text progress bar for `percent` (0.0 to 100.0).
+fn bar(percent: f64) -> String {
+    let filled = ((percent / 100.0 * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled))
+}
+
+/// A row in the per-zone progress table.
+#[derive(Tabled)]
+struct ZoneRow {
+    #[tabled(rename = "Zone")]
+    zone: String,
+    #[tabled(rename = "Progress")]
+    progress: String,
+    #[tabled(rename = "Re-embedded")]
+    molted: u64,
+    #[tabled(rename = "Remaining")]
+    remaining: u64,
+}
+
+/// Render one migration's overall bar, totals, and per-zone table.
+fn render_progress(progress: &MoltProgress) -> Vec<String> {
+    let task = &progress.task;
+    let deadline = match task.deadline_epoch {
+        Some(epoch) => format!(", deadline epoch {}", epoch),
+        None => String::new(),
+    };
+    let mut lines = vec![
+        format!(
+            "{} v{} -> {} v{}{}",
+            task.from_model, task.from_version, task.to_model, task.to_version, deadline
+        ),
+        format!(
+            "  {} {:5.1}%  {} re-embedded, {} remaining",
+            bar(progress.percent_complete),
+            progress.percent_complete,
+            progress.molted,
+            progress.remaining
+        ),
+    ];
+    if progress.zones.len() > 1 {
+        let rows: Vec<ZoneRow> = progress
+            .zones
+            .iter()
+            .map(|z| ZoneRow {
+                zone: z.reef_zone.clone().unwrap_or_else(|| "(none)".to_string()),
+                progress: format!("{} {:5.1}%", bar(z.percent_complete), z.percent_complete),
+                molted: z.molted,
+                remaining: z.remaining,
+            })
+            .collect();
+        lines.push(format_table(&rows));
+    }
+    lines
+}
+
+impl Render for GetMoltProgressResponse {
+    fn render_table(&self) -> String {
+        let state = if self.paused {
+            "paused".to_string()
+        } else {
+            format!("{} Polyps per migration per epoch", self.batch_size)
+        };
+        let mut lines = vec![format!("Molting at epoch {} ({})", self.epoch, state)];
+        if self.migrations.is_empty() {
+            lines.push("No migrations in progress.".to_string());
+        }
+        for migration in &self.migrations {
+            lines.extend(render_progress(migration));
+        }
+        if self.migrations.len() > 1 {
+            let molted: u64 = self.migrations.iter().map(|m| m.molted).sum();
+            let remaining: u64 = self.migrations.iter().map(|m| m.remaining).sum();
+            lines.push(format!("Total: {} re-embedded, {} remaining", molted, remaining));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Render for MoltPlanResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![self.dry_run.render_table(), "Progress:".to_string()];
+        lines.extend(render_progress(&self.progress));
+        lines.push(format!(
+            "At {} Polyps per epoch, the remaining {} molt in about {} epoch(s).",
+            self.batch_size, self.progress.remaining, self.estimated_epochs
+        ));
+        match &self.running {
+            Some(task) if task.to_model == self.progress.task.to_model => {
+                lines.push("This migration is already running.".to_string())
+            }
+            Some(task) => lines.push(format!(
+                "A migration from {} to {} is already running.",
+                task.from_model, task.to_model
+            )),
+            None => lines.push(format!(
+                "Start it with `chitin molt start --from {} --to {}`.",
+                self.progress.task.from_model, self.progress.task.to_model
+            )),
+        }
+        lines.join("\n")
+    }
+}
+
+impl Render for MoltControlResponse {
+    fn render_table(&self) -> String {
+        let control = &self.control;
+        let mut lines = vec![if control.paused {
+            "Molting: paused (resume with `chitin molt resume`)".to_string()
+        } else {
+            format!(
+                "Molting: running, {} Polyps per migration per epoch",
+                control.batch_size()
+            )
+        }];
+        if !control.started.is_empty() {
+            lines.push("Started migrations:".to_string());
+            for task in &control.started {
+                lines.push(format!("  {} -> {}", task.from_model, task.to_model));
+            }
+        }
+        lines.join("\n")
+    }
+}
@@ -1,22 +1,105 @@
 // crates/chitin-cli/src/commands/polyp.rs
 //
-// `chitin polyp {create, get, list}` — Polyp management commands.
+// `chitin polyp {create, get, list, verify, export, import}` — Polyp
+// management commands.
+//
+// `create` submits `--text` as one polyp, or imports documents: a `--file`,
+// every file under a `--dir` matching `--glob`, or stdin. Imported content is
+// chunked here, like URL ingestion chunks on the node, and the chunks are
+// sent through `polyp/submit_batch`.
+//
+// `verify` re-checks a Polyp client-side rather than trusting the node that
+// served it: the creator's signature, the ZK proof and its public inputs,
+// and for hardened Polyps the Merkle inclusion proof against the epoch's
+// checkpointed root and the validator attestations.
+//
+// `export` pages through `polyp/list` and writes whole Polyps, as JSON
+// Lines or as a CAR archive of JSON blocks whose root lists every Polyp's
+// CID. `import` reads either back into a local node through
+// `admin/polyp/import`, which keeps IDs and signatures, and states the node
+// can verify — for backups, moving a reef between nodes, and publishing
+// datasets.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+use tabled::Tabled;
+use uuid::Uuid;
+
+use chitin_consensus::hardening::{hardening_leaf, verify_inclusion, verify_lineage};
+use chitin_core::provenance::{CheckStatus, ProvenanceCheck};
+use chitin_core::text::{chunk_text, validate_chunking};
+use chitin_core::traits::ProofVerifier;
+use chitin_core::{PipelineStep, Polyp, PolypState};
+use chitin_rpc::handlers::polyp::{
+    GetPolypResponse, ImportPolypsResponse, ListPolypsResponse, SubmitPolypBatchResponse,
+    SubmitPolypRequest, SubmitPolypResponse, MAX_IMPORT_BATCH, MAX_SUBMIT_BATCH,
+};
+use chitin_rpc::handlers::query::GetByCidResponse;
+use chitin_rpc::handlers::sync::GetHardeningCheckpointResponse;
+use chitin_store::car::{self, CarReader, CarWriter, Cid, CODEC_DAG_CBOR, CODEC_JSON};
+use chitin_verify::PlaceholderVerifier;
+
+use crate::output::{self, format_table, truncate, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
 
-use clap::Subcommand;
+/// Version recorded in the pipeline steps of imported chunks.
+const PIPELINE_VERSION: &str = "0.1.0";
 
-use crate::rpc_client::rpc_call;
+/// States `export` walks when no `--state` is given.
+const EXPORT_STATES: [&str; 6] =
+    ["Draft", "Soft", "UnderReview", "Approved", "Hardened", "Rejected"];
+
+/// File format of a Polyp archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// One Polyp JSON object per line.
+    Jsonl,
+    /// A CARv1 archive of Polyp JSON blocks.
+    Car,
+}
 
 /// Polyp management subcommands.
 #[derive(Debug, Subcommand)]
 pub enum PolypCmd {
-    /// Create a new Polyp from text input.
+    /// Create a Polyp from text, or import a file, a directory, or stdin as
+    /// chunk Polyps.
     Create {
-        /// The text content for the Polyp.
+        /// The text content for the Polyp (submitted as-is, unchunked).
+        #[arg(long, conflicts_with_all = ["file", "dir"])]
+        text: Option<String>,
+        /// Import this file ("-" for stdin).
+        #[arg(long, conflicts_with = "dir")]
+        file: Option<String>,
+        /// Import every file under this directory that matches --glob.
         #[arg(long)]
-        text: String,
+        dir: Option<String>,
+        /// File name pattern for --dir, with `*` and `?` wildcards (default:
+        /// all files). Patterns containing `/` match the path under --dir.
+        #[arg(long, requires = "dir")]
+        glob: Option<String>,
+        /// Chunk size in characters for imported content.
+        #[arg(long, default_value_t = 1000)]
+        chunk_size: usize,
+        /// Characters shared by consecutive chunks.
+        #[arg(long, default_value_t = 100)]
+        chunk_overlap: usize,
+        /// Chunks per `polyp/submit_batch` request.
+        #[arg(long, default_value_t = 64)]
+        batch_size: usize,
         /// MIME type of the content (default: text/plain).
         #[arg(long, default_value = "text/plain")]
         content_type: String,
+        /// Reef Zone to submit to (e.g., "code/rust").
+        #[arg(long)]
+        zone: Option<String>,
+        /// License of the content (e.g., "CC-BY-4.0"), for zones that
+        /// restrict licenses.
+        #[arg(long)]
+        license: Option<String>,
     },
     /// Get a Polyp by its UUID.
     Get {
@@ -30,64 +113,106 @@ pub enum PolypCmd {
         #[arg(long)]
         state: Option<String>,
     },
+    /// Verify a Polyp's signature, proof, content hashes, provenance, and
+    /// hardening.
+    Verify {
+        /// Polyp UUID, CID of a hardened Polyp, or a Polyp JSON file.
+        target: String,
+    },
+    /// Export Polyps to a JSON Lines or CAR archive.
+    Export {
+        /// Only export this state (default: every state but Molted).
+        #[arg(long)]
+        state: Option<String>,
+        /// Write here ("-" for stdout, the default).
+        #[arg(long, default_value = "-")]
+        out: String,
+        /// Archive format (default: car for a `.car` --out, else jsonl).
+        #[arg(long, value_enum)]
+        format: Option<ArchiveFormat>,
+        /// Polyps per `polyp/list` page.
+        #[arg(long, default_value_t = 100)]
+        page_size: u32,
+    },
+    /// Import Polyps from a JSON Lines or CAR archive.
+    Import {
+        /// The archive ("-" for stdin).
+        file: String,
+        /// Archive format (default: car for a `.car` file, else jsonl).
+        #[arg(long, value_enum)]
+        format: Option<ArchiveFormat>,
+        /// Polyps per `admin/polyp/import` request.
+        #[arg(long, default_value_t = 64)]
+        batch_size: usize,
+    },
 }
 
 /// Run the polyp subcommand.
-pub async fn run(cmd: &PolypCmd, rpc_endpoint: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(
+    cmd: &PolypCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
-        PolypCmd::Create { text, content_type } => {
+        PolypCmd::Create {
+            text: None,
+            file,
+            dir,
+            glob,
+            chunk_size,
+            chunk_overlap,
+            batch_size,
+            content_type,
+            zone,
+            license,
+        } => {
+            validate_chunking(*chunk_size, *chunk_overlap)?;
+            if !(1..=MAX_SUBMIT_BATCH).contains(batch_size) {
+                let message = format!("--batch-size must be between 1 and {}", MAX_SUBMIT_BATCH);
+                return Err(message.into());
+            }
+            let documents = match (file.as_deref(), dir) {
+                (Some("-"), _) | (None, None) => vec![read_stdin()?],
+                (Some(path), _) => vec![read_file(Path::new(path), path)?],
+                (None, Some(dir)) => read_dir(Path::new(dir), glob.as_deref().unwrap_or("*"))?,
+            };
+            let submissions: Vec<SubmitPolypRequest> = documents
+                .iter()
+                .flat_map(|doc| {
+                    doc.submissions(*chunk_size, *chunk_overlap, content_type, zone, license)
+                })
+                .collect();
+            if submissions.is_empty() {
+                return Err("Nothing to import: no text found".into());
+            }
+            let report = import(rpc_endpoint, documents.len(), submissions, *batch_size).await?;
+            output::print(&report, format)?;
+        }
+        PolypCmd::Create {
+            text: Some(text),
+            content_type,
+            zone,
+            license,
+            ..
+        } => {
             let params = serde_json::json!({
                 "content": text,
                 "content_type": content_type,
                 "language": "en",
+                "reef_zone": zone,
+                "license": license,
             });
-
-            let resp = rpc_call(rpc_endpoint, "polyp/submit", params).await?;
-
-            if resp.success {
-                if let Some(result) = &resp.result {
-                    let polyp_id = result
-                        .get("polyp_id")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
-                    let state = result
-                        .get("state")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
-                    println!("Polyp created successfully");
-                    println!("  ID:    {}", polyp_id);
-                    println!("  State: {}", state);
-                }
-            } else {
-                eprintln!(
-                    "Error: {}",
-                    resp.error.unwrap_or_else(|| "Unknown error".to_string())
-                );
-            }
+            let resp: SubmitPolypResponse = rpc_result(rpc_endpoint, "polyp/submit", params).await?;
+            output::print(&resp, format)?;
         }
         PolypCmd::Get { id } => {
             let params = serde_json::json!({
                 "polyp_id": id,
             });
-
-            let resp = rpc_call(rpc_endpoint, "polyp/get", params).await?;
-
-            if resp.success {
-                if let Some(result) = &resp.result {
-                    let found = result.get("found").and_then(|v| v.as_bool()).unwrap_or(false);
-                    if found {
-                        if let Some(polyp) = result.get("polyp") {
-                            println!("{}", serde_json::to_string_pretty(polyp)?);
-                        }
-                    } else {
-                        println!("Polyp not found: {}", id);
-                    }
-                }
-            } else {
-                eprintln!(
-                    "Error: {}",
-                    resp.error.unwrap_or_else(|| "Unknown error".to_string())
-                );
+            let resp: GetPolypResponse = rpc_result(rpc_endpoint, "polyp/get", params).await?;
+            match resp.polyp {
+                Some(polyp) => output::print(&polyp, format)?,
+                None => return Err(format!("Polyp not found: {}", id).into()),
             }
         }
         PolypCmd::List { state } => {
@@ -96,53 +221,907 @@ pub async fn run(cmd: &PolypCmd, rpc_endpoint: &str) -> Result<(), Box<dyn std::
                 "limit": 100,
                 "offset": 0,
             });
+            let resp: ListPolypsResponse = rpc_result(rpc_endpoint, "polyp/list", params).await?;
+            output::print(&resp, format)?;
+        }
+        PolypCmd::Verify { target } => {
+            let report = verify(rpc_endpoint, target).await?;
+            output::print(&report, format)?;
+            let failed = report.count(CheckStatus::Fail);
+            if failed > 0 {
+                return Err(format!("{} check(s) failed", failed).into());
+            }
+        }
+        PolypCmd::Export {
+            state,
+            out,
+            format: archive,
+            page_size,
+        } => {
+            if *page_size == 0 {
+                return Err("--page-size must be at least 1".into());
+            }
+            let states: Vec<&str> = match state {
+                Some(state) => vec![state.as_str()],
+                None => EXPORT_STATES.to_vec(),
+            };
+            let archive = archive.unwrap_or_else(|| archive_format(out));
+            let report = export(rpc_endpoint, &states, out, archive, *page_size).await?;
+            // The archive itself may be on stdout.
+            if out != "-" {
+                output::print(&report, format)?;
+            }
+        }
+        PolypCmd::Import {
+            file,
+            format: archive,
+            batch_size,
+        } => {
+            if !(1..=MAX_IMPORT_BATCH).contains(batch_size) {
+                let message = format!("--batch-size must be between 1 and {}", MAX_IMPORT_BATCH);
+                return Err(message.into());
+            }
+            let archive = archive.unwrap_or_else(|| archive_format(file));
+            let polyps = read_archive(file, archive)?;
+            let report = import_archive(rpc_endpoint, polyps, *batch_size).await?;
+            output::print(&report, format)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Render for SubmitPolypResponse {
+    fn render_table(&self) -> String {
+        let mut out = format!(
+            "Polyp created successfully\n  ID:    {}\n  State: {}",
+            self.polyp_id, self.state
+        );
+        if let Some(rejection) = &self.rejection {
+            out = format!(
+                "Polyp rejected\n  ID:     {}\n  Code:   {}\n  Reason: {}",
+                self.polyp_id, rejection.code, rejection.reason
+            );
+        }
+        if let Some(duplicate) = &self.duplicate_of {
+            out.push_str(&format!(
+                "\n  Near-duplicate of hardened polyp {} ({:.0}% similar)",
+                duplicate.matched_id,
+                duplicate.similarity * 100.0
+            ));
+        }
+        out
+    }
+}
+
+impl Render for Polyp {
+    fn render_table(&self) -> String {
+        let source = &self.subject.provenance.source;
+        let mut lines = vec![
+            format!("Polyp {}", self.id),
+            format!("  State:    {:?}", self.state),
+            format!("  Zone:     {}", self.reef_zone.as_deref().unwrap_or("-")),
+            format!("  Created:  {}", self.created_at.to_rfc3339()),
+            format!("  Creator:  {}", self.subject.provenance.creator.did),
+            format!("  Model:    {}", self.subject.vector.model_id.key()),
+            format!("  Signed:   {}", if self.signature.is_some() { "yes" } else { "no" }),
+        ];
+        if let Some(title) = source.title.as_deref().or(source.source_url.as_deref()) {
+            lines.push(format!("  Source:   {}", title));
+        }
+        if let Some(license) = &source.license {
+            lines.push(format!("  License:  {}", license));
+        }
+        if let Some(hardening) = &self.hardening {
+            lines.push(format!("  CID:      {}", hardening.cid));
+        }
+        lines.push(String::new());
+        lines.push(self.subject.payload.content.clone());
+        lines.join("\n")
+    }
+}
+
+/// A row in the polyp list table.
+#[derive(Tabled)]
+struct PolypRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Zone")]
+    zone: String,
+    #[tabled(rename = "Content")]
+    content: String,
+}
+
+impl Render for ListPolypsResponse {
+    fn render_table(&self) -> String {
+        let rows: Vec<PolypRow> = self
+            .polyps
+            .iter()
+            .map(|p| PolypRow {
+                id: p.id.to_string(),
+                state: format!("{:?}", p.state),
+                zone: p.reef_zone.clone().unwrap_or_default(),
+                content: truncate(&p.subject.payload.content, 40),
+            })
+            .collect();
+        format!("Polyps ({} total):\n{}", self.total, format_table(&rows))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// A document read for import.
+struct Document {
+    /// Where it came from: its path, or "stdin".
+    source: String,
+    content: String,
+}
+
+impl Document {
+    /// One submission per chunk, with the read and chunk steps recorded in
+    /// provenance.
+    fn submissions(
+        &self,
+        chunk_size: usize,
+        chunk_overlap: usize,
+        content_type: &str,
+        zone: &Option<String>,
+        license: &Option<String>,
+    ) -> Vec<SubmitPolypRequest> {
+        let read = PipelineStep {
+            name: "file-read".to_string(),
+            version: PIPELINE_VERSION.to_string(),
+            params: serde_json::json!({
+                "source": self.source,
+                "chars": self.content.chars().count(),
+            }),
+            hash: None,
+        };
+        let chunks = chunk_text(&self.content, chunk_size, chunk_overlap);
+        let count = chunks.len();
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| SubmitPolypRequest {
+                content,
+                content_type: content_type.to_string(),
+                language: Some("en".to_string()),
+                vector: None,
+                model_id: None,
+                source_url: None,
+                source_title: Some(self.source.clone()),
+                license: license.clone(),
+                reef_zone: zone.clone(),
+                pipeline: vec![
+                    read.clone(),
+                    PipelineStep {
+                        name: "chunk".to_string(),
+                        version: PIPELINE_VERSION.to_string(),
+                        params: serde_json::json!({
+                            "chunk_size": chunk_size,
+                            "chunk_overlap": chunk_overlap,
+                            "index": index,
+                            "count": count,
+                        }),
+                        hash: None,
+                    },
+                ],
+            })
+            .collect()
+    }
+}
+
+fn read_stdin() -> Result<Document, Box<dyn std::error::Error>> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err("Give --text, --file, or --dir, or pipe content on stdin".into());
+    }
+    let mut content = String::new();
+    stdin.read_to_string(&mut content)?;
+    Ok(Document {
+        source: "stdin".to_string(),
+        content,
+    })
+}
+
+fn read_file(path: &Path, source: &str) -> Result<Document, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    Ok(Document {
+        source: source.to_string(),
+        content,
+    })
+}
+
+/// Read every file under `dir` matching `pattern`, in path order. Files
+/// that are not UTF-8 text are skipped with a warning.
+fn read_dir(dir: &Path, pattern: &str) -> Result<Vec<Document>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    collect_files(dir, &mut paths)?;
+    paths.sort();
+
+    let mut documents = Vec::new();
+    for path in paths {
+        let relative = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy().into_owned();
+        let name = if pattern.contains('/') {
+            relative.as_str()
+        } else {
+            path.file_name().and_then(|n| n.to_str()).unwrap_or_default()
+        };
+        if !glob_match(pattern, name) {
+            continue;
+        }
+        match read_file(&path, &path.to_string_lossy()) {
+            Ok(document) => documents.push(document),
+            Err(e) => eprintln!("Skipping {}", e),
+        }
+    }
+    if documents.is_empty() {
+        let message = format!("No files matching {:?} under {}", pattern, dir.display());
+        return Err(message.into());
+    }
+    Ok(documents)
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else if path.is_file() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Match `name` against a pattern where `*` matches any run of characters
+/// and `?` matches one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` seen, and of the name when it was seen.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                // Let the last `*` absorb one more character.
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Outcome of an import.
+#[derive(Debug, Serialize)]
+struct ImportReport {
+    documents: usize,
+    submitted: usize,
+    failures: Vec<ImportFailure>,
+}
+
+/// A chunk the node refused.
+#[derive(Debug, Serialize)]
+struct ImportFailure {
+    source: String,
+    error: String,
+}
+
+impl Render for ImportReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Imported {} polyps from {} document(s)",
+            self.submitted, self.documents
+        )];
+        if !self.failures.is_empty() {
+            lines.push(format!("  Failed: {}", self.failures.len()));
+            for failure in self.failures.iter().take(10) {
+                lines.push(format!("    {}: {}", failure.source, failure.error));
+            }
+            if self.failures.len() > 10 {
+                lines.push(format!("    ... and {} more", self.failures.len() - 10));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Submit `submissions` in batches, drawing a progress bar on a terminal.
+async fn import(
+    rpc_endpoint: &str,
+    documents: usize,
+    submissions: Vec<SubmitPolypRequest>,
+    batch_size: usize,
+) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    let total = submissions.len();
+    let show_progress = std::io::stderr().is_terminal();
+    let mut report = ImportReport {
+        documents,
+        submitted: 0,
+        failures: Vec::new(),
+    };
+
+    for (batch_index, batch) in submissions.chunks(batch_size).enumerate() {
+        let params = serde_json::json!({ "polyps": batch });
+        let resp: SubmitPolypBatchResponse =
+            match rpc_result(rpc_endpoint, "polyp/submit_batch", params).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if show_progress {
+                        eprintln!();
+                    }
+                    let message = format!(
+                        "Import stopped after {} of {} chunks: {}",
+                        report.submitted, total, e
+                    );
+                    return Err(message.into());
+                }
+            };
+        for (chunk, result) in batch.iter().zip(resp.results) {
+            match result.error {
+                Some(error) => report.failures.push(ImportFailure {
+                    source: chunk.source_title.clone().unwrap_or_default(),
+                    error,
+                }),
+                None => report.submitted += 1,
+            }
+        }
+
+        if show_progress {
+            let done = (batch_index * batch_size + batch.len()).min(total);
+            draw_progress(done, total, "chunks", report.failures.len());
+        }
+    }
+    if show_progress {
+        eprintln!();
+    }
+    Ok(report)
+}
+
+/// Redraw the progress bar in place on stderr.
+fn draw_progress(done: usize, total: usize, unit: &str, failed: usize) {
+    const WIDTH: usize = 30;
+    let filled = done * WIDTH / total.max(1);
+    let mut line = format!(
+        "\r[{}{}] {}/{} {}",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        done,
+        total,
+        unit
+    );
+    if failed > 0 {
+        line.push_str(&format!(" ({} failed)", failed));
+    }
+    let mut stderr = std::io::stderr();
+    let _ = stderr.write_all(line.as_bytes());
+    let _ = stderr.flush();
+}
+
+// ---------------------------------------------------------------------------
+// Export / import archives
+// ---------------------------------------------------------------------------
+
+/// The archive format implied by a file name.
+fn archive_format(path: &str) -> ArchiveFormat {
+    if path.ends_with(".car") {
+        ArchiveFormat::Car
+    } else {
+        ArchiveFormat::Jsonl
+    }
+}
+
+/// Result of `chitin polyp export`.
+#[derive(Debug, Serialize)]
+struct ExportReport {
+    out: String,
+    polyps: usize,
+    /// CID of a CAR archive's root index block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<String>,
+}
+
+impl Render for ExportReport {
+    fn render_table(&self) -> String {
+        let mut text = format!("Exported {} polyps to {}", self.polyps, self.out);
+        if let Some(root) = &self.root {
+            text.push_str(&format!("\n  Root: {}", root));
+        }
+        text
+    }
+}
+
+/// Page through `polyp/list` for each state and write the Polyps to `out`.
+///
+/// JSON Lines are written a page at a time. A CAR header names the root
+/// index block, which needs every CID, so CAR blocks are held until the
+/// last page.
+async fn export(
+    rpc_endpoint: &str,
+    states: &[&str],
+    out: &str,
+    archive: ArchiveFormat,
+    page_size: u32,
+) -> Result<ExportReport, Box<dyn std::error::Error>> {
+    let mut writer: Box<dyn Write> = if out == "-" {
+        Box::new(BufWriter::new(std::io::stdout().lock()))
+    } else {
+        let file = File::create(out).map_err(|e| format!("Could not create {}: {}", out, e))?;
+        Box::new(BufWriter::new(file))
+    };
+    let mut blocks: Vec<(Cid, Vec<u8>)> = Vec::new();
+    let mut exported = 0;
+
+    for state in states {
+        let mut offset = 0;
+        loop {
+            let params = serde_json::json!({
+                "state_filter": state,
+                "limit": page_size,
+                "offset": offset,
+            });
+            let page: ListPolypsResponse = rpc_result(rpc_endpoint, "polyp/list", params).await?;
+            let count = page.polyps.len() as u32;
+            for polyp in &page.polyps {
+                let json = serde_json::to_vec(polyp)?;
+                match archive {
+                    ArchiveFormat::Jsonl => {
+                        writer.write_all(&json)?;
+                        writer.write_all(b"\n")?;
+                    }
+                    ArchiveFormat::Car => blocks.push((Cid::of(CODEC_JSON, &json), json)),
+                }
+            }
+            exported += page.polyps.len();
+            offset += count;
+            if count == 0 || offset >= page.total {
+                break;
+            }
+        }
+    }
+
+    let mut root = None;
+    if archive == ArchiveFormat::Car {
+        let cids: Vec<Cid> = blocks.iter().map(|(cid, _)| *cid).collect();
+        let index = car::index_block(&cids);
+        let index_cid = Cid::of(CODEC_DAG_CBOR, &index);
+        let mut car = CarWriter::new(writer, std::slice::from_ref(&index_cid))?;
+        car.write_block(&index_cid, &index)?;
+        for (cid, data) in &blocks {
+            car.write_block(cid, data)?;
+        }
+        writer = car.finish()?;
+        root = Some(index_cid.to_string());
+    }
+    writer.flush()?;
+
+    Ok(ExportReport {
+        out: out.to_string(),
+        polyps: exported,
+        root,
+    })
+}
+
+/// Read every Polyp in an archive. CAR blocks other than JSON (the root
+/// index) are skipped.
+fn read_archive(
+    path: &str,
+    archive: ArchiveFormat,
+) -> Result<Vec<Polyp>, Box<dyn std::error::Error>> {
+    let input: Box<dyn Read> = if path == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = File::open(path).map_err(|e| format!("Could not open {}: {}", path, e))?;
+        Box::new(file)
+    };
+    let mut polyps = Vec::new();
+    match archive {
+        ArchiveFormat::Jsonl => {
+            for (number, line) in BufReader::new(input).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let polyp: Polyp = serde_json::from_str(&line)
+                    .map_err(|e| format!("{} line {}: not a Polyp: {}", path, number + 1, e))?;
+                polyps.push(polyp);
+            }
+        }
+        ArchiveFormat::Car => {
+            let bad_car = |e| format!("{} is not a readable CAR file: {}", path, e);
+            let mut reader = CarReader::new(BufReader::new(input)).map_err(bad_car)?;
+            while let Some((cid, data)) = reader.next_block().map_err(bad_car)? {
+                if cid.codec != CODEC_JSON {
+                    continue;
+                }
+                let polyp: Polyp = serde_json::from_slice(&data)
+                    .map_err(|e| format!("{} block {}: not a Polyp: {}", path, cid, e))?;
+                polyps.push(polyp);
+            }
+        }
+    }
+    Ok(polyps)
+}
+
+/// Result of `chitin polyp import`.
+#[derive(Debug, Serialize)]
+struct ArchiveImportReport {
+    read: usize,
+    imported: usize,
+    duplicates: usize,
+    failures: Vec<ImportFailure>,
+}
+
+impl Render for ArchiveImportReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Imported {} of {} polyps ({} already present)",
+            self.imported, self.read, self.duplicates
+        )];
+        if !self.failures.is_empty() {
+            lines.push(format!("  Failed: {}", self.failures.len()));
+            for failure in self.failures.iter().take(10) {
+                lines.push(format!("    {}: {}", failure.source, failure.error));
+            }
+            if self.failures.len() > 10 {
+                lines.push(format!("    ... and {} more", self.failures.len() - 10));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Send `polyps` to `admin/polyp/import` in batches.
+async fn import_archive(
+    rpc_endpoint: &str,
+    polyps: Vec<Polyp>,
+    batch_size: usize,
+) -> Result<ArchiveImportReport, Box<dyn std::error::Error>> {
+    let total = polyps.len();
+    let show_progress = std::io::stderr().is_terminal();
+    let mut report = ArchiveImportReport {
+        read: total,
+        imported: 0,
+        duplicates: 0,
+        failures: Vec::new(),
+    };
 
-            let resp = rpc_call(rpc_endpoint, "polyp/list", params).await?;
-
-            if resp.success {
-                if let Some(result) = &resp.result {
-                    let total = result.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
-                    let polyps = result
-                        .get("polyps")
-                        .and_then(|v| v.as_array())
-                        .cloned()
-                        .unwrap_or_default();
-
-                    println!("Polyps ({} total):", total);
-                    println!("{:<38} {:<10} {}", "ID", "State", "Content");
-                    println!("{}", "-".repeat(80));
-                    for p in &polyps {
-                        let id = p.get("id").and_then(|v| v.as_str()).unwrap_or("?");
-                        let st = p
-                            .get("state")
-                            .map(|v| format!("{}", v))
-                            .unwrap_or_else(|| "?".to_string());
-                        let content = p
-                            .get("subject")
-                            .and_then(|s| s.get("payload"))
-                            .and_then(|p| p.get("content"))
-                            .and_then(|c| c.as_str())
-                            .unwrap_or("");
-                        println!("{:<38} {:<10} {}", id, st, truncate(content, 40));
+    for (batch_index, batch) in polyps.chunks(batch_size).enumerate() {
+        let params = serde_json::json!({ "polyps": batch });
+        let resp: ImportPolypsResponse =
+            match rpc_result(rpc_endpoint, "admin/polyp/import", params).await {
+                Ok(resp) => resp,
+                Err(e) => {
+                    if show_progress {
+                        eprintln!();
                     }
+                    let done = batch_index * batch_size;
+                    let message =
+                        format!("Import stopped after {} of {} polyps: {}", done, total, e);
+                    return Err(message.into());
                 }
+            };
+        for result in resp.results {
+            if result.imported {
+                report.imported += 1;
+            } else if result.duplicate {
+                report.duplicates += 1;
             } else {
-                eprintln!(
-                    "Error: {}",
-                    resp.error.unwrap_or_else(|| "Unknown error".to_string())
-                );
+                report.failures.push(ImportFailure {
+                    source: result.polyp_id.to_string(),
+                    error: result.error.unwrap_or_else(|| "not stored".to_string()),
+                });
             }
         }
+
+        if show_progress {
+            let done = (batch_index * batch_size + batch.len()).min(total);
+            draw_progress(done, total, "polyps", report.failures.len());
+        }
+    }
+    if show_progress {
+        eprintln!();
     }
+    Ok(report)
+}
 
-    Ok(())
+// ---------------------------------------------------------------------------
+// Verify
+// ---------------------------------------------------------------------------
+
+/// One verification check and what it found.
+#[derive(Debug, Serialize)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &str, passed: bool, pass: String, fail: String) -> Self {
+        let (status, detail) = if passed {
+            (CheckStatus::Pass, pass)
+        } else {
+            (CheckStatus::Fail, fail)
+        };
+        Self {
+            name: name.to_string(),
+            status,
+            detail,
+        }
+    }
+
+    fn skip(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl From<ProvenanceCheck> for Check {
+    fn from(check: ProvenanceCheck) -> Self {
+        Self {
+            name: check.name,
+            status: check.status,
+            detail: check.detail,
+        }
+    }
+}
+
+/// The checks run on one Polyp.
+#[derive(Debug, Serialize)]
+struct VerifyReport {
+    polyp_id: Uuid,
+    /// Where the Polyp came from: the node, a CID, or a file.
+    source: String,
+    checks: Vec<Check>,
 }
 
-/// Truncate a string to the given maximum length, appending "..." if truncated.
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len])
+impl VerifyReport {
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+/// A row in the verification table.
+#[derive(Tabled)]
+struct CheckRow {
+    #[tabled(rename = "Check")]
+    name: String,
+    #[tabled(rename = "Result")]
+    status: &'static str,
+    #[tabled(rename = "Detail")]
+    detail: String,
+}
+
+impl Render for VerifyReport {
+    fn render_table(&self) -> String {
+        let rows: Vec<CheckRow> = self
+            .checks
+            .iter()
+            .map(|c| CheckRow {
+                name: c.name.clone(),
+                status: match c.status {
+                    CheckStatus::Pass => "PASS",
+                    CheckStatus::Fail => "FAIL",
+                    CheckStatus::Skip => "skip",
+                },
+                detail: c.detail.clone(),
+            })
+            .collect();
+        let failed = self.count(CheckStatus::Fail);
+        let verdict = if failed == 0 {
+            format!(
+                "Verified: {} passed, {} skipped",
+                self.count(CheckStatus::Pass),
+                self.count(CheckStatus::Skip)
+            )
+        } else {
+            format!("NOT verified: {} of {} checks failed", failed, self.checks.len())
+        };
+        format!(
+            "Polyp {} ({})\n\n{}\n\n{}",
+            self.polyp_id,
+            self.source,
+            format_table(&rows),
+            verdict
+        )
+    }
+}
+
+/// Fetch the Polyp named by `target` and run every check on it.
+async fn verify(
+    rpc_endpoint: &str,
+    target: &str,
+) -> Result<VerifyReport, Box<dyn std::error::Error>> {
+    let (polyp, source, cid) = if Path::new(target).is_file() {
+        let json = std::fs::read_to_string(target)?;
+        let polyp: Polyp = serde_json::from_str(&json)
+            .map_err(|e| format!("{} is not a Polyp JSON file: {}", target, e))?;
+        (polyp, format!("file {}", target), None)
+    } else if let Ok(id) = Uuid::parse_str(target) {
+        let params = serde_json::json!({ "polyp_id": id });
+        let resp: GetPolypResponse = rpc_result(rpc_endpoint, "polyp/get", params).await?;
+        let polyp = resp.polyp.ok_or_else(|| format!("Polyp not found: {}", id))?;
+        (polyp, "node".to_string(), None)
+    } else {
+        let params = serde_json::json!({ "cid": target });
+        let resp: GetByCidResponse = rpc_result(rpc_endpoint, "query/cid", params).await?;
+        let json = resp.polyp.ok_or_else(|| format!("No hardened Polyp with CID {}", target))?;
+        let polyp: Polyp = serde_json::from_value(json)?;
+        (polyp, format!("CID {}", target), Some(target.to_string()))
+    };
+
+    let mut checks = content_checks(&polyp);
+    checks.extend(hardening_checks(rpc_endpoint, &polyp, cid.as_deref()).await);
+    Ok(VerifyReport {
+        polyp_id: polyp.id,
+        source,
+        checks,
+    })
+}
+
+/// Checks that need nothing but the Polyp itself.
+fn content_checks(polyp: &Polyp) -> Vec<Check> {
+    let creator = &polyp.subject.provenance.creator;
+    let proof = &polyp.proof;
+    let vector = &polyp.subject.vector;
+    let mut checks = Vec::new();
+
+    let provenance = polyp.subject.provenance.verify(polyp.created_at, chrono::Utc::now());
+    checks.extend(provenance.checks.into_iter().map(Check::from));
+    checks.push(match &polyp.signature {
+        None => Check::skip("signature", "Polyp is unsigned"),
+        Some(_) => Check::new(
+            "signature",
+            polyp.verify_signature(&creator.hotkey).unwrap_or(false),
+            format!("signed by creator hotkey {}", hex(&creator.hotkey[..8])),
+            format!("does not verify against creator hotkey {}", hex(&creator.hotkey[..8])),
+        ),
+    });
+    checks.push(match PlaceholderVerifier::new().verify_proof(proof) {
+        Ok(valid) => Check::new(
+            "zk proof",
+            valid,
+            format!("{} proof accepted (placeholder verifier)", proof.proof_type),
+            format!("{} proof rejected", proof.proof_type),
+        ),
+        Err(e) => Check::new("zk proof", false, String::new(), e.to_string()),
+    });
+    checks.push(Check::new(
+        "proof model",
+        proof.public_inputs.model_id == vector.model_id,
+        vector.model_id.key(),
+        format!(
+            "proof commits to {}, vector is from {}",
+            proof.public_inputs.model_id.key(),
+            vector.model_id.key()
+        ),
+    ));
+    // Placeholder proofs commit all-zero hashes rather than real ones.
+    let inputs = &proof.public_inputs;
+    checks.push(if inputs.text_hash == [0; 32] {
+        Check::skip("content hash", "proof commits no text_hash")
     } else {
-        s.to_string()
+        Check::new(
+            "content hash",
+            PlaceholderVerifier::verify_text_hash(proof, &polyp.subject.payload.content),
+            format!("text_hash {}", hex(&inputs.text_hash[..8])),
+            "content does not match the proof's text_hash".to_string(),
+        )
+    });
+    checks.push(if inputs.vector_hash == [0; 32] {
+        Check::skip("vector hash", "proof commits no vector_hash")
+    } else {
+        Check::new(
+            "vector hash",
+            PlaceholderVerifier::verify_vector_hash(proof, &vector.values),
+            format!("vector_hash {}", hex(&inputs.vector_hash[..8])),
+            "vector does not match the proof's vector_hash".to_string(),
+        )
+    });
+    checks
+}
+
+/// Checks of the hardening lineage, against the node's checkpoint.
+async fn hardening_checks(rpc_endpoint: &str, polyp: &Polyp, cid: Option<&str>) -> Vec<Check> {
+    let lineage = match &polyp.hardening {
+        Some(lineage) => lineage,
+        None if polyp.state == PolypState::Hardened => {
+            let detail = "Polyp is hardened but has no lineage".to_string();
+            return vec![Check::new("hardening", false, String::new(), detail)];
+        }
+        None => return vec![Check::skip("hardening", "Polyp is not hardened")],
+    };
+    let mut checks = Vec::new();
+
+    if let Some(cid) = cid {
+        checks.push(Check::new(
+            "cid",
+            lineage.cid == cid,
+            cid.to_string(),
+            format!("lineage names CID {}", lineage.cid),
+        ));
     }
+    let leaf = hardening_leaf(&polyp.id, &lineage.cid);
+    checks.push(Check::new(
+        "merkle inclusion",
+        verify_inclusion(&leaf, &lineage.merkle_proof, &lineage.merkle_root),
+        format!(
+            "{}-step proof to root {}",
+            lineage.merkle_proof.len(),
+            hex(&lineage.merkle_root[..8])
+        ),
+        "proof does not link the Polyp to its claimed root".to_string(),
+    ));
+
+    let epoch = polyp.consensus.as_ref().map(|c| c.epoch);
+    checks.push(match epoch {
+        None => Check::new("checkpoint", false, String::new(), "no consensus epoch".to_string()),
+        Some(epoch) => {
+            let params = serde_json::json!({ "epoch": epoch });
+            let method = "sync/hardening_checkpoint";
+            match rpc_result::<GetHardeningCheckpointResponse>(rpc_endpoint, method, params).await
+            {
+                Ok(GetHardeningCheckpointResponse {
+                    checkpoint: Some(checkpoint),
+                }) => match verify_lineage(&polyp.id, lineage, &checkpoint) {
+                    Ok(()) => Check::new(
+                        "checkpoint",
+                        true,
+                        format!("matches epoch {} root", epoch),
+                        String::new(),
+                    ),
+                    Err(e) => Check::new("checkpoint", false, String::new(), e.to_string()),
+                },
+                Ok(_) => Check::skip(
+                    "checkpoint",
+                    format!("node has no checkpoint for epoch {}", epoch),
+                ),
+                Err(e) => Check::skip("checkpoint", format!("could not fetch: {}", e)),
+            }
+        }
+    });
+
+    let attestations = &lineage.attestations;
+    checks.push(if attestations.is_empty() {
+        Check::skip("attestations", "none recorded")
+    } else {
+        let invalid = attestations
+            .iter()
+            .filter(|a| !(a.polyp_id == polyp.id && a.cid == lineage.cid && a.verify()))
+            .count();
+        Check::new(
+            "attestations",
+            invalid == 0,
+            format!("{} valid validator signature(s)", attestations.len()),
+            format!("{} of {} invalid", invalid, attestations.len()),
+        )
+    });
+    checks
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
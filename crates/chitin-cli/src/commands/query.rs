@@ -1,10 +1,32 @@
 // crates/chitin-cli/src/commands/query.rs
 //
 // `chitin query <text>` — semantic search against the Reef.
+//
+// Results can be narrowed by Reef Zone, lifecycle state, and creator trust,
+// re-ranked with a different trust weight than the node's default,
+// diversified by maximal marginal relevance (`--mmr-lambda`), and rescored
+// by the node's cross-encoder reranker, if it has one (`--rerank`). The
+// node embeds the query text itself unless `--local-embed` is given; local
+// embedding (the hash embedding, or an OpenAI-compatible API called from
+// here) is compiled in with the `embedder` feature. `--watch` keeps
+// the command running and re-runs the query whenever the node stores new
+// Polyps (see `polyp/subscribe`). `--scroll` exports up to `--top-k` results
+// a page at a time (see `query/scroll`), searching only once.
 
 use clap::Args;
+use tabled::Tabled;
+
+use chitin_rpc::handlers::polyp::{SubscribePolypsRequest, SubscribePolypsResponse};
+use chitin_rpc::handlers::query::{
+    ScrollSearchRequest, ScrollSearchResponse, SearchResult, SemanticSearchRequest,
+    SemanticSearchResponse,
+};
 
-use crate::rpc_client::rpc_call;
+use crate::output::{self, format_table, truncate, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// How long each `polyp/subscribe` call in watch mode waits, in ms.
+const WATCH_WAIT_MS: u64 = 30_000;
 
 /// Semantic search query command.
 #[derive(Debug, Args)]
@@ -15,76 +37,307 @@ pub struct QueryCmd {
 
     /// Number of results to return (default: 10).
     #[arg(long, default_value = "10")]
-    pub top_k: usize,
+    pub top_k: u32,
+
+    /// Only return Polyps in this Reef Zone or its subzones (e.g. "code").
+    #[arg(long)]
+    pub zone: Option<String>,
+
+    /// Only return Polyps in this lifecycle state (e.g. "hardened").
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Only return Polyps in this language (an ISO 639 code, e.g. "es").
+    #[arg(long)]
+    pub language: Option<String>,
+
+    /// Minimum normalized creator trust, in [0.0, 1.0].
+    #[arg(long)]
+    pub min_trust: Option<f64>,
+
+    /// Re-rank with this weight of creator trust against similarity, in
+    /// [0.0, 1.0] (default: the node's setting).
+    #[arg(long)]
+    pub trust_weight: Option<f64>,
+
+    /// Diversify results by maximal marginal relevance with this lambda, in
+    /// [0.0, 1.0] (1.0 ranks by relevance alone; default: the node's
+    /// setting).
+    #[arg(long)]
+    pub mmr_lambda: Option<f64>,
+
+    /// Rescore the top candidates with the node's reranker: true or false
+    /// (default: the node's setting).
+    #[arg(long)]
+    pub rerank: Option<bool>,
+
+    /// Candidates to rescore when reranking (default: the node's setting).
+    #[arg(long)]
+    pub rerank_top_n: Option<u32>,
+
+    /// Longest wait for the reranker, in milliseconds, before keeping the
+    /// original order (default: the node's budget).
+    #[arg(long)]
+    pub rerank_budget_ms: Option<u64>,
+
+    /// Embed the query here instead of on the node (needs the `embedder`
+    /// feature).
+    #[arg(long)]
+    pub local_embed: bool,
 
-    /// Embedding model to use for the query vector.
-    #[arg(long, default_value = "bge/bge-small-en-v1.5")]
-    pub model: String,
+    /// Embedding model for `--local-embed` (default: the hash embedding).
+    /// Other models are embedded by an OpenAI-compatible API.
+    #[arg(long, requires = "local_embed")]
+    pub model: Option<String>,
+
+    /// Base URL of the OpenAI-compatible API for `--model` (default:
+    /// https://api.openai.com/v1). The key is read from `OPENAI_API_KEY`.
+    #[arg(long, requires = "model")]
+    pub embed_url: Option<String>,
+
+    /// Keep running and re-run the query when new Polyps are stored.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Page through up to `--top-k` results (for large exports), printing
+    /// each page as it arrives.
+    #[arg(long, conflicts_with = "watch")]
+    pub scroll: bool,
+
+    /// Results per page with `--scroll` (default: 100).
+    #[arg(long, requires = "scroll")]
+    pub page_size: Option<u32>,
 }
 
 /// Run the query command.
-pub async fn run(cmd: &QueryCmd, rpc_endpoint: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let params = serde_json::json!({
-        "query_text": cmd.text,
-        "top_k": cmd.top_k,
-        "model_id": cmd.model,
-    });
-
-    let resp = rpc_call(rpc_endpoint, "query/search", params).await?;
-
-    if resp.success {
-        if let Some(result) = &resp.result {
-            let results = result
-                .get("results")
-                .and_then(|v| v.as_array())
-                .cloned()
-                .unwrap_or_default();
-            let search_time = result
-                .get("search_time_ms")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
-            let total = result
-                .get("total_found")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
+pub async fn run(
+    cmd: &QueryCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (name, value) in [
+        ("--min-trust", cmd.min_trust),
+        ("--trust-weight", cmd.trust_weight),
+        ("--mmr-lambda", cmd.mmr_lambda),
+    ] {
+        if value.is_some_and(|v| !(0.0..=1.0).contains(&v)) {
+            return Err(format!("{} must be between 0.0 and 1.0", name).into());
+        }
+    }
+    let (query_vector, model_id) = if cmd.local_embed {
+        let (vector, model) =
+            embed_locally(&cmd.text, cmd.model.as_deref(), cmd.embed_url.as_deref()).await?;
+        (Some(vector), Some(model))
+    } else {
+        (None, None)
+    };
+    let request = SemanticSearchRequest {
+        query_text: Some(cmd.text.clone()),
+        query_vector,
+        model_id,
+        top_k: Some(cmd.top_k),
+        min_trust: cmd.min_trust,
+        hardened_only: None,
+        reef_zone: cmd.zone.clone(),
+        state: cmd.state.clone(),
+        language: cmd.language.clone(),
+        trust_weight: cmd.trust_weight,
+        local_only: false,
+        cross_model: None,
+        mmr_lambda: cmd.mmr_lambda,
+        rerank: cmd.rerank,
+        rerank_top_n: cmd.rerank_top_n,
+        rerank_budget_ms: cmd.rerank_budget_ms,
+    };
+    if cmd.scroll {
+        return scroll(rpc_endpoint, request, cmd.page_size, format).await;
+    }
+    let params = serde_json::to_value(&request)?;
+
+    if !cmd.watch {
+        let resp: SemanticSearchResponse = rpc_result(rpc_endpoint, "query/search", params).await?;
+        return output::print(&resp, format);
+    }
+
+    // Take the cursor before the first search so nothing stored in between
+    // is missed.
+    let mut cursor = subscribe(rpc_endpoint, None, 0).await?.next_cursor;
+    loop {
+        let resp: SemanticSearchResponse =
+            rpc_result(rpc_endpoint, "query/search", params.clone()).await?;
+        output::print(&resp, format)?;
 
+        let new_polyps = loop {
+            let update = subscribe(rpc_endpoint, Some(cursor), WATCH_WAIT_MS).await?;
+            cursor = update.next_cursor;
+            if !update.events.is_empty() || update.missed > 0 {
+                break update.events.len() as u64 + update.missed;
+            }
+        };
+        if format == OutputFormat::Table {
             println!(
-                "Search results: {} found ({} ms)",
-                total, search_time
+                "\n--- {}: {} new polyp(s), re-running query ---\n",
+                chrono::Local::now().format("%H:%M:%S"),
+                new_polyps
             );
-            println!();
-
-            if results.is_empty() {
-                println!("No results found.");
-            } else {
-                println!(
-                    "{:<38} {:<10} {:<10} {}",
-                    "Polyp ID", "Sim", "State", "Content"
-                );
-                println!("{}", "-".repeat(90));
-                for r in &results {
-                    let id = r.get("polyp_id").and_then(|v| v.as_str()).unwrap_or("?");
-                    let sim = r
-                        .get("similarity")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(0.0);
-                    let state = r.get("state").and_then(|v| v.as_str()).unwrap_or("?");
-                    let content = r.get("content").and_then(|v| v.as_str()).unwrap_or("");
-                    let truncated = if content.len() > 40 {
-                        format!("{}...", &content[..40])
-                    } else {
-                        content.to_string()
-                    };
-                    println!("{:<38} {:<10.4} {:<10} {}", id, sim, state, truncated);
-                }
-            }
         }
+    }
+}
+
+/// Print the ranked results of `search` a page at a time.
+async fn scroll(
+    rpc_endpoint: &str,
+    search: SemanticSearchRequest,
+    page_size: Option<u32>,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut request = ScrollSearchRequest {
+        max_results: search.top_k,
+        search: Some(search),
+        cursor: None,
+        page_size,
+    };
+    loop {
+        let params = serde_json::to_value(&request)?;
+        let page: ScrollSearchResponse = rpc_result(rpc_endpoint, "query/scroll", params).await?;
+        output::print(&page, format)?;
+        let Some(cursor) = page.cursor else {
+            return Ok(());
+        };
+        request = ScrollSearchRequest {
+            cursor: Some(cursor),
+            page_size,
+            ..ScrollSearchRequest::default()
+        };
+    }
+}
+
+/// Wait up to `wait_ms` for Polyps stored at or after `cursor`.
+async fn subscribe(
+    rpc_endpoint: &str,
+    cursor: Option<u64>,
+    wait_ms: u64,
+) -> Result<SubscribePolypsResponse, Box<dyn std::error::Error>> {
+    let request = SubscribePolypsRequest {
+        cursor,
+        wait_ms: Some(wait_ms),
+    };
+    rpc_result(
+        rpc_endpoint,
+        "polyp/subscribe",
+        serde_json::to_value(&request)?,
+    )
+    .await
+}
+
+/// Embed the query here: with the hash embedding, or with `model` through
+/// the OpenAI-compatible API at `embed_url`.
+#[cfg(feature = "embedder")]
+async fn embed_locally(
+    text: &str,
+    model: Option<&str>,
+    embed_url: Option<&str>,
+) -> Result<(Vec<f32>, String), Box<dyn std::error::Error>> {
+    use chitin_core::embedder::{HashEmbedder, OpenAiConfig, OpenAiEmbedder};
+    use chitin_core::{Embedder, EmbeddingModelId, HASH_EMBEDDING_MODEL};
+
+    let key = model.unwrap_or(HASH_EMBEDDING_MODEL);
+    let (embedder, dimensions): (Box<dyn Embedder>, u32) = if key == HASH_EMBEDDING_MODEL {
+        (Box::new(HashEmbedder::new(384)), 384)
     } else {
-        eprintln!(
-            "Error: {}",
-            resp.error.unwrap_or_else(|| "Unknown error".to_string())
+        if !key.contains('/') {
+            return Err(format!("--model must be a space key like \"openai/name\": {}", key).into());
+        }
+        let mut config = OpenAiConfig {
+            api_key: std::env::var("OPENAI_API_KEY").ok(),
+            ..OpenAiConfig::default()
+        };
+        if let Some(url) = embed_url {
+            config.base_url = url.to_string();
+        }
+        (Box::new(OpenAiEmbedder::new(config)?), 0)
+    };
+    let model_id = EmbeddingModelId::from_key(key, dimensions);
+    let embedding = embedder
+        .embed(&[text], &model_id)
+        .await?
+        .pop()
+        .ok_or("The embedder returned no vector")?;
+    Ok((embedding.values, key.to_string()))
+}
+
+#[cfg(not(feature = "embedder"))]
+async fn embed_locally(
+    _text: &str,
+    _model: Option<&str>,
+    _embed_url: Option<&str>,
+) -> Result<(Vec<f32>, String), Box<dyn std::error::Error>> {
+    Err("This chitin was built without a local embedder; rebuild with `--features embedder`".into())
+}
+
+/// A row in the search results table.
+#[derive(Tabled)]
+struct ResultRow {
+    #[tabled(rename = "Polyp ID")]
+    polyp_id: String,
+    #[tabled(rename = "Sim")]
+    similarity: String,
+    #[tabled(rename = "Score")]
+    score: String,
+    #[tabled(rename = "Trust")]
+    trust: String,
+    #[tabled(rename = "State")]
+    state: String,
+    #[tabled(rename = "Content")]
+    content: String,
+}
+
+impl Render for SemanticSearchResponse {
+    fn render_table(&self) -> String {
+        let header = format!(
+            "Search results: {} found ({} ms{})\n",
+            self.total_found,
+            self.search_time_ms,
+            if self.reranked { ", reranked" } else { "" }
+        );
+        if self.results.is_empty() {
+            return format!("{}\nNo results found.", header);
+        }
+        format!("{}\n{}", header, format_table(&result_rows(&self.results)))
+    }
+}
+
+impl Render for ScrollSearchResponse {
+    fn render_table(&self) -> String {
+        if self.results.is_empty() {
+            return "No results found.".to_string();
+        }
+        let header = format!(
+            "Results {}-{} of {} ({} ms{})\n",
+            self.offset + 1,
+            self.offset as usize + self.results.len(),
+            self.total,
+            self.search_time_ms,
+            if self.reranked { ", reranked" } else { "" }
         );
+        format!("{}\n{}", header, format_table(&result_rows(&self.results)))
     }
+}
 
-    Ok(())
+/// Table rows for search results.
+fn result_rows(results: &[SearchResult]) -> Vec<ResultRow> {
+    results
+        .iter()
+        .map(|r| ResultRow {
+            polyp_id: r.polyp_id.to_string(),
+            similarity: format!("{:.4}", r.similarity),
+            score: format!("{:.4}", r.score),
+            trust: r
+                .creator_trust
+                .map(|t| format!("{:.3}", t))
+                .unwrap_or_else(|| "-".to_string()),
+            state: r.state.clone(),
+            content: truncate(r.content.as_deref().unwrap_or(""), 40),
+        })
+        .collect()
 }
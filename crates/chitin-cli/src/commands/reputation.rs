@@ -0,0 +1,188 @@
+// crates/chitin-cli/src/commands/reputation.rs
+//
+// `chitin reputation {node, top}` — inspect domain trust.
+//
+// Scores are each domain's OpenRank global trust, in [0.0, 1.0]. `node`
+// shows one node's score and rank in every domain it has one in, plus the
+// latest trust-evidence events on edges into it: which validator's trust in
+// it changed, in which epoch, and how much their scores agreed. `top` is a
+// domain's leaderboard.
+
+use clap::Subcommand;
+use tabled::Tabled;
+
+use chitin_rpc::handlers::reputation::{GetNodeReputationResponse, GetTopReputationResponse};
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Reputation inspection subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ReputationCmd {
+    /// Show a node's trust in every domain and its recent trust evidence.
+    Node {
+        /// Network UID, DID (`did:chitin:<hex>`), or hex coldkey.
+        node: String,
+        /// Number of recent evidence events to show.
+        #[arg(long, default_value_t = 10)]
+        evidence: usize,
+    },
+    /// Show the most trusted nodes in a domain.
+    Top {
+        /// Domain (Reef Zone) ID (default: "global").
+        #[arg(long)]
+        domain: Option<String>,
+        /// Number of nodes to show.
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+/// Run the reputation subcommand.
+pub async fn run(
+    cmd: &ReputationCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        ReputationCmd::Node { node, evidence } => {
+            let params = match node.parse::<u16>() {
+                Ok(uid) => serde_json::json!({ "uid": uid, "evidence_limit": evidence }),
+                Err(_) => {
+                    let did = if node.starts_with("did:") {
+                        node.clone()
+                    } else {
+                        format!("did:chitin:{}", node.to_ascii_lowercase())
+                    };
+                    serde_json::json!({ "did": did, "evidence_limit": evidence })
+                }
+            };
+            let resp: GetNodeReputationResponse =
+                rpc_result(rpc_endpoint, "reputation/node", params).await?;
+            output::print(&resp, format)
+        }
+        ReputationCmd::Top { domain, limit } => {
+            let params = serde_json::json!({ "domain_id": domain, "limit": limit });
+            let resp: GetTopReputationResponse =
+                rpc_result(rpc_endpoint, "reputation/top", params).await?;
+            output::print(&resp, format)
+        }
+    }
+}
+
+/// A row in the per-domain table.
+#[derive(Tabled)]
+struct DomainRow {
+    #[tabled(rename = "Domain")]
+    domain: String,
+    #[tabled(rename = "Trust")]
+    trust: String,
+    #[tabled(rename = "Rank")]
+    rank: String,
+}
+
+/// A row in the evidence table.
+#[derive(Tabled)]
+struct EvidenceRow {
+    #[tabled(rename = "Epoch")]
+    epoch: u64,
+    #[tabled(rename = "Domain")]
+    domain: String,
+    #[tabled(rename = "From UID")]
+    from: u16,
+    #[tabled(rename = "Agreement")]
+    agreement: String,
+    #[tabled(rename = "Trust")]
+    trust: String,
+    #[tabled(rename = "Polyps")]
+    polyps: usize,
+}
+
+impl Render for GetNodeReputationResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Node {} ({})",
+            self.uid,
+            self.did.as_deref().unwrap_or("DID unknown")
+        )];
+        lines.push(format!("  Global trust (OpenRank): {:.4}", self.global));
+        if self.domains.is_empty() {
+            lines.push("  No trust in any zone yet.".to_string());
+        } else {
+            let rows: Vec<DomainRow> = self
+                .domains
+                .iter()
+                .map(|d| DomainRow {
+                    domain: d.domain_id.clone(),
+                    trust: format!("{:.4}", d.score),
+                    rank: format!("{} of {}", d.rank, d.nodes),
+                })
+                .collect();
+            lines.push(format_table(&rows));
+        }
+
+        if self.recent_evidence.is_empty() {
+            lines.push("No trust evidence recorded for this node.".to_string());
+        } else {
+            lines.push("Recent trust evidence (newest first):".to_string());
+            let rows: Vec<EvidenceRow> = self
+                .recent_evidence
+                .iter()
+                .map(|e| EvidenceRow {
+                    epoch: e.evidence.epoch,
+                    domain: e.domain_id.clone(),
+                    from: e.from,
+                    agreement: format!("{:.3}", e.evidence.agreement),
+                    trust: format!(
+                        "{:.3} -> {:.3} ({:+.3})",
+                        e.evidence.previous_trust,
+                        e.evidence.new_trust,
+                        e.evidence.trust_delta()
+                    ),
+                    polyps: e.evidence.polyp_count,
+                })
+                .collect();
+            lines.push(format_table(&rows));
+        }
+        lines.join("\n")
+    }
+}
+
+/// A row in the leaderboard.
+#[derive(Tabled)]
+struct LeaderboardRow {
+    #[tabled(rename = "Rank")]
+    rank: usize,
+    #[tabled(rename = "UID")]
+    uid: u16,
+    #[tabled(rename = "DID")]
+    did: String,
+    #[tabled(rename = "Trust")]
+    score: String,
+}
+
+impl Render for GetTopReputationResponse {
+    fn render_table(&self) -> String {
+        if self.entries.is_empty() {
+            return format!("No trust scores in domain {:?} yet.", self.domain_id);
+        }
+        let rows: Vec<LeaderboardRow> = self
+            .entries
+            .iter()
+            .map(|e| LeaderboardRow {
+                rank: e.rank,
+                uid: e.uid,
+                did: e.did.clone().unwrap_or_else(|| "-".to_string()),
+                score: format!("{:.4}", e.score),
+            })
+            .collect();
+        format!(
+            "Top nodes in {} (epoch {}, {} of {} nodes):\n{}",
+            self.domain_id,
+            self.epoch,
+            self.entries.len(),
+            self.total_nodes,
+            format_table(&rows)
+        )
+    }
+}
@@ -1,62 +1,384 @@
 // crates/chitin-cli/src/commands/stake.rs
 //
-// `chitin stake {stake, unstake, info}` — staking management commands.
+// `chitin stake {add, remove, info}` — staking management commands.
 //
-// Phase 1: Print placeholder messages. Real staking in Phase 3.
+// `add` and `remove` are signed with the wallet's coldkey (see `wallet`) and
+// share its nonce with transfers. Removed stake stays locked for the
+// cooldown period before it returns to the wallet's balance; `info` shows the
+// blocks remaining, per-node stake, and projected epoch rewards.
 
 use clap::Subcommand;
+use serde::Serialize;
+use tabled::Tabled;
+
+use chitin_core::crypto::Keypair;
+use chitin_economics::staking::CORAL_COOLDOWN_BLOCKS;
+use chitin_economics::{StakeAction, StakeActionKind};
+use chitin_rpc::handlers::staking::{
+    GetStakeInfoResponse, StakeRequest, StakeResponse, UnstakeRequest, UnstakeResponse,
+};
+
+use crate::commands::wallet::{
+    confirm, ctn, get_balance, hex_encode, load_coldkey_secret, parse_ctn, resolve_address,
+};
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
 
 /// Staking subcommands.
 #[derive(Debug, Subcommand)]
 pub enum StakeCmd {
-    /// Stake $CTN tokens to a node.
-    Stake {
+    /// Stake $CTN from the wallet's balance to a node.
+    #[command(alias = "stake")]
+    Add {
         /// Amount of $CTN to stake.
         #[arg(long)]
-        amount: u64,
-        /// Target node hotkey (hex).
+        amount: String,
+        /// Network UID of the node to stake to.
+        #[arg(long)]
+        node: u16,
+        /// Stake without asking for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Begin unstaking $CTN from a node (starts cooldown period).
+    #[command(alias = "unstake")]
+    Remove {
+        /// Amount of $CTN to unstake (default: all stake on the node).
+        #[arg(long)]
+        amount: Option<String>,
+        /// Network UID of the node to unstake from.
         #[arg(long)]
-        target: Option<String>,
+        node: u16,
+        /// Unstake without asking for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
-    /// Begin unstaking $CTN tokens (starts cooldown period).
-    Unstake {
-        /// Amount of $CTN to unstake.
+    /// Show stakes, pending unstakes, and projected rewards.
+    Info {
+        /// Staker coldkey (hex or DID; default: this wallet's coldkey).
+        #[arg(long, conflicts_with = "all")]
+        address: Option<String>,
+        /// Show every staker's stakes.
+        #[arg(long)]
+        all: bool,
+        /// Only show stake on this node UID.
         #[arg(long)]
-        amount: u64,
+        node: Option<u16>,
     },
-    /// Show staking information for the current node.
-    Info,
 }
 
 /// Run the stake subcommand.
-pub async fn run(cmd: &StakeCmd) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(
+    cmd: &StakeCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
-        StakeCmd::Stake { amount, target } => {
-            println!("Staking {} CTN", amount);
-            if let Some(t) = target {
-                println!("  Target node: {}", t);
+        StakeCmd::Add { amount, node, yes } => {
+            add(rpc_endpoint, amount, *node, *yes, format).await
+        }
+        StakeCmd::Remove { amount, node, yes } => {
+            remove(rpc_endpoint, amount.as_deref(), *node, *yes, format).await
+        }
+        StakeCmd::Info { address, all, node } => {
+            let coldkey = if *all {
+                None
             } else {
-                println!("  Target: self (own node)");
-            }
-            println!();
-            println!("Staking not yet implemented (Phase 3).");
+                Some(resolve_address(address.as_deref())?)
+            };
+            let info = get_stake_info(rpc_endpoint, coldkey.as_deref(), *node).await?;
+            output::print(&info, format)
+        }
+    }
+}
+
+/// Result of `stake add`.
+#[derive(Debug, Serialize)]
+struct StakeReport {
+    staker_coldkey: String,
+    node_uid: u16,
+    amount_rao: u64,
+    /// The staker's total active stake on the node afterwards.
+    new_total_rao: u64,
+    message: String,
+}
+
+impl Render for StakeReport {
+    fn render_table(&self) -> String {
+        [
+            format!("Staked {} to node {}", ctn(self.amount_rao), self.node_uid),
+            format!("  Staker:        {}", self.staker_coldkey),
+            format!("  Total on node: {}", ctn(self.new_total_rao)),
+        ]
+        .join("\n")
+    }
+}
+
+async fn add(
+    rpc_endpoint: &str,
+    amount: &str,
+    node_uid: u16,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let amount_rao = parse_ctn(amount)?;
+    let keypair = Keypair::from_secret_bytes(&*load_coldkey_secret()?);
+    let staker_coldkey = hex_encode(&keypair.public_key_bytes());
+    let balance = get_balance(rpc_endpoint, &staker_coldkey).await?;
+    if amount_rao > balance.available_rao {
+        return Err(format!(
+            "Insufficient balance: staking {} but {} is available",
+            ctn(amount_rao),
+            ctn(balance.available_rao)
+        )
+        .into());
+    }
+
+    if !yes {
+        let summary = [
+            format!("Stake {} to node {}", ctn(amount_rao), node_uid),
+            format!("  Balance after: {}", ctn(balance.available_rao - amount_rao)),
+            format!(
+                "  Unstaking later takes {} blocks to unlock.",
+                CORAL_COOLDOWN_BLOCKS
+            ),
+        ];
+        if !confirm(&summary.join("\n"))? {
+            return Err("Stake cancelled".into());
         }
-        StakeCmd::Unstake { amount } => {
-            println!("Unstaking {} CTN", amount);
-            println!();
-            println!("Unstaking not yet implemented (Phase 3).");
-            println!("Cooldown period: ~24-72 hours depending on node type.");
+    }
+
+    let action = StakeAction {
+        kind: StakeActionKind::Stake,
+        staker: keypair.public_key_bytes(),
+        node_uid,
+        amount: amount_rao,
+        nonce: balance.nonce,
+    };
+    let request = StakeRequest {
+        staker_coldkey: staker_coldkey.clone(),
+        node_uid,
+        amount_rao,
+        nonce: balance.nonce,
+        signature: hex_encode(&keypair.sign(&action.signing_bytes())),
+    };
+    let resp: StakeResponse =
+        rpc_result(rpc_endpoint, "staking/stake", serde_json::to_value(&request)?).await?;
+    if !resp.success {
+        return Err(format!("Stake failed: {}", resp.message).into());
+    }
+
+    let report = StakeReport {
+        staker_coldkey,
+        node_uid,
+        amount_rao,
+        new_total_rao: resp.new_total_rao,
+        message: resp.message,
+    };
+    output::print(&report, format)
+}
+
+/// Result of `stake remove`.
+#[derive(Debug, Serialize)]
+struct UnstakeReport {
+    staker_coldkey: String,
+    node_uid: u16,
+    amount_rao: u64,
+    cooldown_complete_block: Option<u64>,
+    message: String,
+}
+
+impl Render for UnstakeReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Unstaking {} from node {}",
+            ctn(self.amount_rao),
+            self.node_uid
+        )];
+        if let Some(block) = self.cooldown_complete_block {
+            lines.push(format!("  Unlocks at block {}", block));
         }
-        StakeCmd::Info => {
-            println!("Staking Information");
-            println!("-------------------");
-            println!("  Staked:       0 CTN (placeholder)");
-            println!("  Delegated:    0 CTN (placeholder)");
-            println!("  Cooldown:     None");
-            println!();
-            println!("Note: Phase 1 placeholder. Real staking info in Phase 3.");
+        lines.push("  Run `chitin stake info` to follow the cooldown.".to_string());
+        lines.join("\n")
+    }
+}
+
+async fn remove(
+    rpc_endpoint: &str,
+    amount: Option<&str>,
+    node_uid: u16,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let keypair = Keypair::from_secret_bytes(&*load_coldkey_secret()?);
+    let staker_coldkey = hex_encode(&keypair.public_key_bytes());
+    let info = get_stake_info(rpc_endpoint, Some(&staker_coldkey), Some(node_uid)).await?;
+    let active = info.nodes.first().map(|n| n.active_rao).unwrap_or(0);
+    if active == 0 {
+        return Err(format!("No active stake on node {}", node_uid).into());
+    }
+    let amount_rao = match amount {
+        Some(amount) => parse_ctn(amount)?,
+        None => active,
+    };
+    if amount_rao == 0 || amount_rao > active {
+        return Err(format!(
+            "Cannot unstake {}: {} is staked to node {}",
+            ctn(amount_rao),
+            ctn(active),
+            node_uid
+        )
+        .into());
+    }
+
+    if !yes {
+        let summary = [
+            format!(
+                "Unstake {} of {} from node {}",
+                ctn(amount_rao),
+                ctn(active),
+                node_uid
+            ),
+            format!(
+                "  Funds unlock around block {} ({} blocks from now).",
+                info.current_block + CORAL_COOLDOWN_BLOCKS,
+                CORAL_COOLDOWN_BLOCKS
+            ),
+        ];
+        if !confirm(&summary.join("\n"))? {
+            return Err("Unstake cancelled".into());
         }
     }
 
-    Ok(())
+    let nonce = get_balance(rpc_endpoint, &staker_coldkey).await?.nonce;
+    // Unstaking everything is sent as 0 so stake added meanwhile is included.
+    let signed_amount = if amount_rao == active { 0 } else { amount_rao };
+    let action = StakeAction {
+        kind: StakeActionKind::Unstake,
+        staker: keypair.public_key_bytes(),
+        node_uid,
+        amount: signed_amount,
+        nonce,
+    };
+    let request = UnstakeRequest {
+        staker_coldkey: staker_coldkey.clone(),
+        node_uid,
+        amount_rao: signed_amount,
+        nonce,
+        signature: hex_encode(&keypair.sign(&action.signing_bytes())),
+    };
+    let resp: UnstakeResponse =
+        rpc_result(rpc_endpoint, "staking/unstake", serde_json::to_value(&request)?).await?;
+    if !resp.success {
+        return Err(format!("Unstake failed: {}", resp.message).into());
+    }
+
+    let report = UnstakeReport {
+        staker_coldkey,
+        node_uid,
+        amount_rao,
+        cooldown_complete_block: resp.cooldown_complete_block,
+        message: resp.message,
+    };
+    output::print(&report, format)
+}
+
+async fn get_stake_info(
+    rpc_endpoint: &str,
+    coldkey: Option<&str>,
+    node_uid: Option<u16>,
+) -> Result<GetStakeInfoResponse, Box<dyn std::error::Error>> {
+    let params = serde_json::json!({ "coldkey": coldkey, "node_uid": node_uid });
+    rpc_result(rpc_endpoint, "staking/info", params).await
+}
+
+/// A row in the per-node stake table.
+#[derive(Tabled)]
+struct NodeRow {
+    #[tabled(rename = "Node")]
+    node_uid: u16,
+    #[tabled(rename = "Active")]
+    active: String,
+    #[tabled(rename = "Unstaking")]
+    unstaking: String,
+    #[tabled(rename = "Node Total")]
+    node_total: String,
+    #[tabled(rename = "Last Emission")]
+    last_emission: String,
+    #[tabled(rename = "Projected Reward")]
+    projected: String,
+}
+
+/// A row in the pending unstakes table.
+#[derive(Tabled)]
+struct UnstakeRow {
+    #[tabled(rename = "Node")]
+    node_uid: u16,
+    #[tabled(rename = "Staker")]
+    staker: String,
+    #[tabled(rename = "Amount")]
+    amount: String,
+    #[tabled(rename = "Unlock Block")]
+    unlock_block: u64,
+    #[tabled(rename = "Blocks Left")]
+    blocks_left: String,
+}
+
+impl Render for GetStakeInfoResponse {
+    fn render_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Staking Information (block {})",
+            self.current_block
+        )];
+        if self.stakes.is_empty() {
+            lines.push("No stake.".to_string());
+            return lines.join("\n");
+        }
+        lines.push(format!("Total staked: {}", ctn(self.total_staked_rao)));
+
+        let or_dash = |rao: Option<u64>| rao.map(ctn).unwrap_or_else(|| "-".to_string());
+        let rows: Vec<NodeRow> = self
+            .nodes
+            .iter()
+            .map(|n| NodeRow {
+                node_uid: n.node_uid,
+                active: ctn(n.active_rao),
+                unstaking: ctn(n.unstaking_rao),
+                node_total: ctn(n.node_total_rao),
+                last_emission: or_dash(n.last_epoch_emission_rao),
+                projected: or_dash(n.projected_reward_rao),
+            })
+            .collect();
+        lines.push(String::new());
+        lines.push(format_table(&rows));
+
+        let pending: Vec<UnstakeRow> = self
+            .stakes
+            .iter()
+            .filter_map(|s| {
+                Some(UnstakeRow {
+                    node_uid: s.node_uid,
+                    staker: output::truncate(&s.staker_coldkey, 16),
+                    amount: ctn(s.amount_rao),
+                    unlock_block: s.cooldown_complete_block?,
+                    blocks_left: match s.cooldown_remaining_blocks {
+                        Some(0) => "unlocking".to_string(),
+                        Some(blocks) => blocks.to_string(),
+                        None => "-".to_string(),
+                    },
+                })
+            })
+            .collect();
+        if !pending.is_empty() {
+            lines.push(String::new());
+            lines.push("Pending unstakes".to_string());
+            lines.push(format_table(&pending));
+        }
+        lines.push(String::new());
+        lines.push(
+            "Projected rewards assume the next epoch matches the last, shared by stake."
+                .to_string(),
+        );
+        lines.join("\n")
+    }
 }
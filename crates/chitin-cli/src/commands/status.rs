@@ -2,58 +2,86 @@
 //
 // `chitin status` — display node connection status and version info.
 
+use serde::Serialize;
+
+use chitin_rpc::handlers::node::GetHealthResponse;
+
+use crate::output::{self, OutputFormat, Render};
+use crate::profile::Settings;
 use crate::rpc_client::rpc_call;
 
-/// Run the status command.
-pub async fn run(rpc_endpoint: &str) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Chitin Protocol v0.1.0");
-    println!();
+/// Node connection status, as reported by `node/health`.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    version: &'static str,
+    rpc_endpoint: String,
+    /// The CLI profile the endpoint came from, if any.
+    profile: Option<String>,
+    /// Whether the daemon answered at all.
+    connected: bool,
+    /// The node's health, if it reported it.
+    health: Option<GetHealthResponse>,
+    /// The error the daemon returned, if any.
+    error: Option<String>,
+}
 
-    let resp = rpc_call(rpc_endpoint, "node/health", serde_json::json!({})).await;
+/// Run the status command.
+pub async fn run(
+    settings: &Settings,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let resp = rpc_call(&settings.rpc, "node/health", serde_json::json!({})).await;
 
+    let mut report = StatusReport {
+        version: "0.1.0",
+        rpc_endpoint: settings.rpc.clone(),
+        profile: settings.profile.clone(),
+        connected: resp.is_ok(),
+        health: None,
+        error: None,
+    };
     match resp {
         Ok(r) if r.success => {
-            println!("Node Status");
-            println!("-----------");
-            println!("  Connection:   CONNECTED");
-            println!("  RPC endpoint: {}", rpc_endpoint);
-
-            if let Some(result) = &r.result {
-                let status = result
-                    .get("status")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                let storage_ok = result
-                    .get("storage_ok")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let index_ok = result
-                    .get("index_ok")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                println!("  Health:       {}", status);
-                println!("  Storage:      {}", if storage_ok { "OK" } else { "DEGRADED" });
-                println!("  Index:        {}", if index_ok { "OK" } else { "DEGRADED" });
-            }
+            report.health = r.result.map(serde_json::from_value).transpose()?;
+        }
+        Ok(r) => report.error = r.error,
+        Err(_) => {}
+    }
+    output::print(&report, format)
+}
+
+impl Render for StatusReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Chitin Protocol v{}", self.version),
+            String::new(),
+            "Node Status".to_string(),
+            "-----------".to_string(),
+        ];
+        let connection = match (self.connected, &self.error) {
+            (false, _) => "NOT CONNECTED",
+            (true, Some(_)) => "CONNECTED (with errors)",
+            (true, None) => "CONNECTED",
+        };
+        lines.push(format!("  Connection:   {}", connection));
+        lines.push(format!("  RPC endpoint: {}", self.rpc_endpoint));
+        if let Some(profile) = &self.profile {
+            lines.push(format!("  Profile:      {}", profile));
         }
-        Ok(r) => {
-            println!("Node Status");
-            println!("-----------");
-            println!("  Connection:   CONNECTED (with errors)");
-            println!("  RPC endpoint: {}", rpc_endpoint);
-            if let Some(err) = &r.error {
-                println!("  Error:        {}", err);
-            }
+
+        if let Some(health) = &self.health {
+            let ok = |ok: bool| if ok { "OK" } else { "DEGRADED" };
+            lines.push(format!("  Health:       {}", health.status));
+            lines.push(format!("  Storage:      {}", ok(health.storage_ok)));
+            lines.push(format!("  Index:        {}", ok(health.index_ok)));
+        }
+        if let Some(err) = &self.error {
+            lines.push(format!("  Error:        {}", err));
         }
-        Err(_) => {
-            println!("Node Status");
-            println!("-----------");
-            println!("  Connection:   NOT CONNECTED");
-            println!("  RPC endpoint: {}", rpc_endpoint);
-            println!();
-            println!("Could not reach daemon. Is chitin-daemon running?");
+        if !self.connected {
+            lines.push(String::new());
+            lines.push("Could not reach daemon. Is chitin-daemon running?".to_string());
         }
+        lines.join("\n")
     }
-
-    Ok(())
 }
@@ -0,0 +1,494 @@
+// crates/chitin-cli/src/commands/top.rs
+//
+// `chitin top` — a live dashboard for node operators.
+//
+// Polls the node's RPC endpoints every `--interval` seconds and redraws the
+// node's identity and health, the epoch phase with a countdown, polyp counts
+// by state, process resources and tasks, peers, and the latest consensus
+// weights. Block times depend on the node's block source, so countdowns are
+// estimated from the block rate observed while the dashboard runs.
+// Keys: `q` or Esc quits, `r` refreshes now.
+
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Gauge, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use serde::de::DeserializeOwned;
+
+use chitin_rpc::handlers::admin::ListTasksResponse;
+use chitin_rpc::handlers::metagraph::GetWeightsResponse;
+use chitin_rpc::handlers::node::{GetHealthResponse, GetNodeInfoResponse, GetPeersResponse};
+use chitin_rpc::handlers::polyp::CountPolypsResponse;
+use chitin_rpc::handlers::validation::GetEpochStatusResponse;
+
+use crate::rpc_client::rpc_result;
+
+/// Live dashboard command.
+#[derive(Debug, Args)]
+pub struct TopCmd {
+    /// Seconds between refreshes.
+    #[arg(long, default_value_t = 2)]
+    pub interval: u64,
+}
+
+/// One poll of the node. Each part is None if its endpoint failed.
+struct Snapshot {
+    info: Option<GetNodeInfoResponse>,
+    health: Option<GetHealthResponse>,
+    epoch: Option<GetEpochStatusResponse>,
+    counts: Option<CountPolypsResponse>,
+    peers: Option<GetPeersResponse>,
+    weights: Option<GetWeightsResponse>,
+    tasks: Option<ListTasksResponse>,
+    /// Why `node/info` failed, shown when the node is unreachable.
+    error: Option<String>,
+    taken_at: chrono::DateTime<chrono::Local>,
+}
+
+/// Rates derived from consecutive snapshots.
+#[derive(Default)]
+struct Rates {
+    /// First block seen, and when; the block rate is averaged from here.
+    first_block: Option<(Instant, u64)>,
+    /// Seconds per block.
+    block_secs: Option<f64>,
+    /// Previous CPU reading of the node process.
+    last_cpu: Option<(Instant, f64)>,
+    /// CPU use over the last interval, in percent of one core.
+    cpu_percent: Option<f64>,
+}
+
+impl Rates {
+    fn update(&mut self, snapshot: &Snapshot, now: Instant) {
+        if let Some(epoch) = &snapshot.epoch {
+            match self.first_block {
+                // A lower block means the node restarted or resynced.
+                Some((_, first)) if epoch.block < first => {
+                    self.first_block = Some((now, epoch.block));
+                    self.block_secs = None;
+                }
+                Some((since, first)) if epoch.block > first => {
+                    let secs = now.duration_since(since).as_secs_f64();
+                    self.block_secs = Some(secs / (epoch.block - first) as f64);
+                }
+                Some(_) => {}
+                None => self.first_block = Some((now, epoch.block)),
+            }
+        }
+
+        let cpu = snapshot.info.as_ref().and_then(|i| i.resources.as_ref());
+        if let Some(cpu_seconds) = cpu.map(|r| r.cpu_seconds) {
+            if let Some((then, last)) = self.last_cpu {
+                let wall = now.duration_since(then).as_secs_f64();
+                if wall > 0.0 {
+                    self.cpu_percent = Some(100.0 * (cpu_seconds - last).max(0.0) / wall);
+                }
+            }
+            self.last_cpu = Some((now, cpu_seconds));
+        }
+    }
+}
+
+/// What ended a wait between refreshes.
+enum Wake {
+    Quit,
+    Refresh,
+    Redraw,
+}
+
+/// Run the top command.
+pub async fn run(cmd: &TopCmd, rpc_endpoint: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if cmd.interval == 0 {
+        return Err("--interval must be positive".into());
+    }
+    if !std::io::stdout().is_terminal() {
+        return Err("chitin top needs a terminal".into());
+    }
+
+    let mut terminal = ratatui::init();
+    let result = dashboard(
+        &mut terminal,
+        rpc_endpoint,
+        Duration::from_secs(cmd.interval),
+    )
+    .await;
+    ratatui::restore();
+    result
+}
+
+async fn dashboard(
+    terminal: &mut DefaultTerminal,
+    rpc_endpoint: &str,
+    interval: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rates = Rates::default();
+    loop {
+        let snapshot = poll(rpc_endpoint).await;
+        rates.update(&snapshot, Instant::now());
+
+        let deadline = Instant::now() + interval;
+        loop {
+            terminal.draw(|frame| draw(frame, rpc_endpoint, &snapshot, &rates))?;
+            match tokio::task::block_in_place(|| wait(deadline))? {
+                Wake::Quit => return Ok(()),
+                Wake::Refresh => break,
+                Wake::Redraw => {}
+            }
+        }
+    }
+}
+
+/// Wait for a key, a resize, or `deadline`.
+fn wait(deadline: Instant) -> std::io::Result<Wake> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || !event::poll(remaining)? {
+            return Ok(Wake::Refresh);
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Wake::Quit),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Wake::Quit)
+                }
+                KeyCode::Char('r') => return Ok(Wake::Refresh),
+                _ => {}
+            },
+            Event::Resize(..) => return Ok(Wake::Redraw),
+            _ => {}
+        }
+    }
+}
+
+async fn poll(rpc_endpoint: &str) -> Snapshot {
+    let (info, health, epoch, counts, peers, weights, tasks) = tokio::join!(
+        call(rpc_endpoint, "node/info"),
+        call(rpc_endpoint, "node/health"),
+        call(rpc_endpoint, "validation/epoch"),
+        call(rpc_endpoint, "polyp/counts"),
+        call(rpc_endpoint, "node/peers"),
+        call(rpc_endpoint, "metagraph/weights"),
+        call(rpc_endpoint, "admin/tasks"),
+    );
+    Snapshot {
+        error: info.as_ref().err().map(|e| e.to_string()),
+        info: info.ok(),
+        health: health.ok(),
+        epoch: epoch.ok(),
+        counts: counts.ok(),
+        peers: peers.ok(),
+        weights: weights.ok(),
+        tasks: tasks.ok(),
+        taken_at: chrono::Local::now(),
+    }
+}
+
+async fn call<T: DeserializeOwned>(
+    rpc_endpoint: &str,
+    method: &str,
+) -> Result<T, Box<dyn std::error::Error>> {
+    rpc_result(rpc_endpoint, method, serde_json::json!({})).await
+}
+
+// ---------------------------------------------------------------------------
+// Drawing
+// ---------------------------------------------------------------------------
+
+fn draw(frame: &mut Frame, rpc_endpoint: &str, snapshot: &Snapshot, rates: &Rates) {
+    let [header, epoch, middle, bottom, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Length(11),
+        Constraint::Min(6),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    draw_header(frame, header, rpc_endpoint, snapshot);
+    let footer_text = format!(
+        " q quit  r refresh  updated {}",
+        snapshot.taken_at.format("%H:%M:%S")
+    );
+    frame.render_widget(Paragraph::new(footer_text).dim(), footer);
+
+    if let Some(error) = &snapshot.error {
+        let message = Paragraph::new(format!("Node unreachable: {}", error))
+            .red()
+            .block(Block::bordered());
+        frame.render_widget(message, epoch.union(middle).union(bottom));
+        return;
+    }
+
+    draw_epoch(frame, epoch, snapshot.epoch.as_ref(), rates);
+    let [polyps, resources] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(middle);
+    draw_polyps(frame, polyps, snapshot.counts.as_ref());
+    draw_resources(frame, resources, snapshot, rates);
+    let [peers, weights] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(bottom);
+    draw_peers(frame, peers, snapshot.peers.as_ref());
+    draw_weights(frame, weights, snapshot.weights.as_ref());
+}
+
+fn draw_header(frame: &mut Frame, area: Rect, rpc_endpoint: &str, snapshot: &Snapshot) {
+    let mut line = rpc_endpoint.to_string();
+    if let Some(info) = &snapshot.info {
+        line.push_str(&format!(
+            "  |  {} v{}  |  {}  |  up {}",
+            info.node_type,
+            info.version,
+            info.did.as_deref().unwrap_or("no DID"),
+            format_duration(info.uptime_seconds as f64)
+        ));
+    }
+    let health = match &snapshot.health {
+        Some(health) if health.identity_conflicts.is_empty() => health.status.clone(),
+        Some(health) => format!(
+            "{} ({} identity conflicts)",
+            health.status,
+            health.identity_conflicts.len()
+        ),
+        None => "unknown".to_string(),
+    };
+    let color = match health.split(' ').next() {
+        Some("healthy") => Color::Green,
+        Some("degraded") => Color::Yellow,
+        _ => Color::Red,
+    };
+    let text = Line::from(vec![line.into(), "  |  health: ".into(), health.fg(color)]);
+    frame.render_widget(
+        Paragraph::new(text).block(Block::bordered().title(" chitin top ")),
+        area,
+    );
+}
+
+fn draw_epoch(
+    frame: &mut Frame,
+    area: Rect,
+    epoch: Option<&GetEpochStatusResponse>,
+    rates: &Rates,
+) {
+    let block = Block::bordered().title(" Epoch ");
+    let epoch = match epoch {
+        Some(epoch) if epoch.blocks_per_epoch > 0 => epoch,
+        _ => {
+            frame.render_widget(
+                Paragraph::new("Epoch status unavailable").block(block),
+                area,
+            );
+            return;
+        }
+    };
+
+    let in_epoch = epoch.block % epoch.blocks_per_epoch;
+    let countdown = match rates.block_secs {
+        Some(secs) => format!(
+            " (~{})",
+            format_duration(secs * epoch.blocks_remaining as f64)
+        ),
+        None => String::new(),
+    };
+    let label = format!(
+        "epoch {}  |  {}  |  block {} ({}/{})  |  phase ends in {} blocks{}",
+        epoch.epoch,
+        epoch.phase,
+        epoch.block,
+        in_epoch,
+        epoch.blocks_per_epoch,
+        epoch.blocks_remaining,
+        countdown
+    );
+    let color = match epoch.phase.as_str() {
+        "Open" => Color::Green,
+        "Scoring" => Color::Yellow,
+        _ => Color::Magenta,
+    };
+    let gauge = Gauge::default()
+        .block(block)
+        .gauge_style(Style::new().fg(color))
+        .ratio(in_epoch as f64 / epoch.blocks_per_epoch as f64)
+        .label(label);
+    frame.render_widget(gauge, area);
+}
+
+fn draw_polyps(frame: &mut Frame, area: Rect, counts: Option<&CountPolypsResponse>) {
+    let counts = match counts {
+        Some(counts) => counts,
+        None => {
+            let block = Block::bordered().title(" Polyps ");
+            frame.render_widget(
+                Paragraph::new("Polyp counts unavailable").block(block),
+                area,
+            );
+            return;
+        }
+    };
+    let bars: Vec<Bar> = counts
+        .counts
+        .iter()
+        .map(|c| Bar::with_label(c.state.clone(), c.count as u64))
+        .collect();
+    let chart = BarChart::default()
+        .block(Block::bordered().title(format!(" Polyps ({} total) ", counts.total)))
+        .bar_width(11)
+        .bar_gap(1)
+        .bar_style(Style::new().fg(Color::Cyan))
+        .data(BarGroup::new(bars));
+    frame.render_widget(chart, area);
+}
+
+fn draw_resources(frame: &mut Frame, area: Rect, snapshot: &Snapshot, rates: &Rates) {
+    let mut lines = Vec::new();
+    match snapshot.info.as_ref().and_then(|i| i.resources.as_ref()) {
+        Some(resources) => {
+            lines.push(format!("Memory:   {}", format_bytes(resources.rss_bytes)));
+            let cpu = match rates.cpu_percent {
+                Some(percent) => format!("{:.1}%", percent),
+                None => "-".to_string(),
+            };
+            lines.push(format!("CPU:      {}", cpu));
+            lines.push(format!("Threads:  {}", resources.threads));
+        }
+        None => lines.push("Process stats unavailable".to_string()),
+    }
+    if let Some(health) = &snapshot.health {
+        lines.push(format!(
+            "Storage:  {}",
+            if health.storage_ok { "OK" } else { "DEGRADED" }
+        ));
+        lines.push(format!(
+            "Index:    {}",
+            if health.index_ok { "OK" } else { "DEGRADED" }
+        ));
+    }
+    if let Some(tasks) = &snapshot.tasks {
+        let running = tasks.tasks.iter().filter(|t| t.state == "running").count();
+        let failed: Vec<&str> = tasks
+            .tasks
+            .iter()
+            .filter(|t| t.state == "failed")
+            .map(|t| t.name.as_str())
+            .collect();
+        lines.push(format!("Tasks:    {} running", running));
+        if !failed.is_empty() {
+            lines.push(format!("Failed:   {}", failed.join(", ")));
+        }
+    }
+    let lines: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    let paragraph = Paragraph::new(lines).block(Block::bordered().title(" Node "));
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_peers(frame: &mut Frame, area: Rect, peers: Option<&GetPeersResponse>) {
+    let block = Block::bordered().title(match peers {
+        Some(peers) => format!(" Peers ({}) ", peers.count),
+        None => " Peers ".to_string(),
+    });
+    let peers = match peers {
+        Some(peers) if !peers.peers.is_empty() => peers,
+        Some(_) => {
+            frame.render_widget(Paragraph::new("No peers configured").block(block), area);
+            return;
+        }
+        None => {
+            frame.render_widget(Paragraph::new("Peers unavailable").block(block), area);
+            return;
+        }
+    };
+
+    let rows = peers.peers.iter().map(|p| {
+        let (status, color) = match p.alive {
+            Some(true) => ("alive", Color::Green),
+            Some(false) => ("down", Color::Red),
+            None => ("?", Color::Gray),
+        };
+        let latency = p
+            .latency_ms
+            .map(|ms| format!("{} ms", ms))
+            .unwrap_or_default();
+        let failures = p
+            .dial
+            .as_ref()
+            .map(|d| d.consecutive_failures.to_string())
+            .unwrap_or_default();
+        Row::new(vec![
+            Line::from(p.address.clone()),
+            Line::from(p.node_type.clone().unwrap_or_default()),
+            Line::from(status).fg(color),
+            Line::from(latency),
+            Line::from(failures),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(8),
+        ],
+    )
+    .header(Row::new(["Address", "Type", "Status", "Latency", "Failures"]).bold())
+    .block(block);
+    frame.render_widget(table, area);
+}
+
+fn draw_weights(frame: &mut Frame, area: Rect, weights: Option<&GetWeightsResponse>) {
+    let weights = match weights {
+        Some(weights) if !weights.weights.is_empty() => weights,
+        _ => {
+            let block = Block::bordered().title(" Weights ");
+            frame.render_widget(Paragraph::new("No weights yet").block(block), area);
+            return;
+        }
+    };
+
+    let mut entries: Vec<(u16, u16, f64)> = weights
+        .weights
+        .iter()
+        .flat_map(|(validator, row)| row.iter().map(|(coral, w)| (*validator, *coral, *w)))
+        .collect();
+    entries.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+    let rows = entries.iter().map(|(validator, coral, weight)| {
+        Row::new(vec![
+            validator.to_string(),
+            coral.to_string(),
+            format!("{:.4}", weight),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(Row::new(["Validator", "Coral", "Weight"]).bold())
+    .block(Block::bordered().title(format!(" Weights (epoch {}) ", weights.epoch)));
+    frame.render_widget(table, area);
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    match secs {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    match bytes as f64 {
+        b if b >= 1024.0 * MIB => format!("{:.1} GiB", b / (1024.0 * MIB)),
+        b => format!("{:.1} MiB", b / MIB),
+    }
+}
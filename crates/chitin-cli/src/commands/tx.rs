@@ -0,0 +1,516 @@
+// crates/chitin-cli/src/commands/tx.rs
+//
+// `chitin tx {build, sign, submit}` — offline transaction signing.
+//
+// A coldkey kept on an air-gapped machine never touches the network:
+// `build` prepares an unsigned transfer, stake, or unstake as a JSON file on
+// an online machine (asking the node for the nonce and fee unless they are
+// given), `sign` signs it offline with a keystore, and `submit` broadcasts
+// the signed file later. Files are read from and written to stdout/stdin
+// when no path (or `-`) is given.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use chitin_core::crypto::Keypair;
+use chitin_economics::{StakeAction, StakeActionKind, Transfer};
+use chitin_rpc::handlers::staking::{StakeRequest, StakeResponse, UnstakeRequest, UnstakeResponse};
+use chitin_rpc::handlers::wallet::{TransferRequest, TransferResponse};
+
+use crate::commands::wallet::{
+    confirm, ctn, decode_key, get_balance, hex_encode, load_secret, parse_address, parse_ctn,
+    resolve_address,
+};
+use crate::output::{self, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Format version of transaction files.
+const TX_FILE_VERSION: u32 = 1;
+
+/// Offline transaction subcommands.
+#[derive(Debug, Subcommand)]
+pub enum TxCmd {
+    /// Build an unsigned transaction file.
+    #[command(subcommand)]
+    Build(BuildCmd),
+    /// Sign a transaction file with a keystore (no network access).
+    Sign {
+        /// Unsigned transaction file (`-` for stdin).
+        file: PathBuf,
+        /// Encrypted keystore or hex secret key file of the signer.
+        #[arg(long)]
+        keystore: PathBuf,
+        /// Where to write the signed transaction (default: stdout).
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Sign without asking for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Broadcast a signed transaction file.
+    Submit {
+        /// Signed transaction file (`-` for stdin).
+        file: PathBuf,
+    },
+}
+
+/// Transactions that can be built for offline signing.
+#[derive(Debug, Subcommand)]
+pub enum BuildCmd {
+    /// Send $CTN to another coldkey.
+    Transfer {
+        /// Recipient coldkey (hex or DID).
+        #[arg(long)]
+        to: String,
+        /// Amount of $CTN to send (up to 9 decimal places).
+        #[arg(long)]
+        amount: String,
+        /// Transfer fee in $CTN (default: the node's current fee).
+        #[arg(long)]
+        fee: Option<String>,
+        #[command(flatten)]
+        common: BuildArgs,
+    },
+    /// Stake $CTN to a node.
+    Stake {
+        /// Amount of $CTN to stake.
+        #[arg(long)]
+        amount: String,
+        /// Network UID of the node to stake to.
+        #[arg(long)]
+        node: u16,
+        #[command(flatten)]
+        common: BuildArgs,
+    },
+    /// Begin unstaking $CTN from a node.
+    Unstake {
+        /// Amount of $CTN to unstake (default: all stake on the node).
+        #[arg(long)]
+        amount: Option<String>,
+        /// Network UID of the node to unstake from.
+        #[arg(long)]
+        node: u16,
+        #[command(flatten)]
+        common: BuildArgs,
+    },
+}
+
+/// Arguments shared by every `tx build` subcommand.
+#[derive(Debug, Args)]
+pub struct BuildArgs {
+    /// Signer coldkey (hex or DID; default: this wallet's coldkey).
+    #[arg(long)]
+    from: Option<String>,
+    /// Signer nonce (default: the signer's next nonce on the node).
+    #[arg(long)]
+    nonce: Option<u64>,
+    /// Where to write the unsigned transaction (default: stdout).
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// A transaction file: the action, who signs it, and (once signed) the
+/// signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TxFile {
+    version: u32,
+    /// Hex coldkey of the signer.
+    signer_coldkey: String,
+    /// The signer's nonce the transaction consumes.
+    nonce: u64,
+    #[serde(flatten)]
+    action: TxAction,
+    /// Hex ed25519 signature over the action's signing bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+/// The action a transaction file performs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TxAction {
+    Transfer {
+        to_coldkey: String,
+        amount_rao: u64,
+        fee_rao: u64,
+    },
+    Stake {
+        node_uid: u16,
+        amount_rao: u64,
+    },
+    /// An `amount_rao` of 0 unstakes everything on the node.
+    Unstake {
+        node_uid: u16,
+        amount_rao: u64,
+    },
+}
+
+impl TxFile {
+    /// The bytes the signer's coldkey signs.
+    fn signing_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let signer = decode_key(&self.signer_coldkey)?;
+        let stake_action = |kind, node_uid, amount| StakeAction {
+            kind,
+            staker: signer,
+            node_uid,
+            amount,
+            nonce: self.nonce,
+        };
+        Ok(match &self.action {
+            TxAction::Transfer {
+                to_coldkey,
+                amount_rao,
+                fee_rao,
+            } => Transfer {
+                from: signer,
+                to: decode_key(to_coldkey)?,
+                amount: *amount_rao,
+                fee: *fee_rao,
+                nonce: self.nonce,
+            }
+            .signing_bytes(),
+            TxAction::Stake {
+                node_uid,
+                amount_rao,
+            } => stake_action(StakeActionKind::Stake, *node_uid, *amount_rao).signing_bytes(),
+            TxAction::Unstake {
+                node_uid,
+                amount_rao,
+            } => stake_action(StakeActionKind::Unstake, *node_uid, *amount_rao).signing_bytes(),
+        })
+    }
+
+    /// One-line description of the action.
+    fn describe(&self) -> String {
+        match &self.action {
+            TxAction::Transfer {
+                to_coldkey,
+                amount_rao,
+                fee_rao,
+            } => format!(
+                "Transfer {} to {} (fee {})",
+                ctn(*amount_rao),
+                to_coldkey,
+                ctn(*fee_rao)
+            ),
+            TxAction::Stake {
+                node_uid,
+                amount_rao,
+            } => format!("Stake {} to node {}", ctn(*amount_rao), node_uid),
+            TxAction::Unstake {
+                node_uid,
+                amount_rao: 0,
+            } => format!("Unstake everything from node {}", node_uid),
+            TxAction::Unstake {
+                node_uid,
+                amount_rao,
+            } => format!("Unstake {} from node {}", ctn(*amount_rao), node_uid),
+        }
+    }
+}
+
+/// Run the tx subcommand.
+pub async fn run(
+    cmd: &TxCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match cmd {
+        TxCmd::Build(build_cmd) => build(build_cmd, rpc_endpoint).await,
+        TxCmd::Sign {
+            file,
+            keystore,
+            out,
+            yes,
+        } => sign(file, keystore, out.as_deref(), *yes),
+        TxCmd::Submit { file } => submit(file, rpc_endpoint, format).await,
+    }
+}
+
+async fn build(cmd: &BuildCmd, rpc_endpoint: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let common = match cmd {
+        BuildCmd::Transfer { common, .. }
+        | BuildCmd::Stake { common, .. }
+        | BuildCmd::Unstake { common, .. } => common,
+    };
+    let signer_coldkey = resolve_address(common.from.as_deref())?;
+    let given_fee = match cmd {
+        BuildCmd::Transfer { fee: Some(fee), .. } => Some(parse_ctn(fee)?),
+        _ => None,
+    };
+
+    // Only ask the node for what wasn't given, so a build can be fully offline.
+    let needs_fee = matches!(cmd, BuildCmd::Transfer { .. }) && given_fee.is_none();
+    let (nonce, node_fee) = match common.nonce {
+        Some(nonce) if !needs_fee => (nonce, 0),
+        _ => {
+            let balance = get_balance(rpc_endpoint, &signer_coldkey).await?;
+            (
+                common.nonce.unwrap_or(balance.nonce),
+                balance.transfer_fee_rao,
+            )
+        }
+    };
+
+    let action = match cmd {
+        BuildCmd::Transfer { to, amount, .. } => {
+            let to_coldkey = parse_address(to)?;
+            if to_coldkey == signer_coldkey {
+                return Err("Cannot transfer to the signer's own coldkey".into());
+            }
+            TxAction::Transfer {
+                to_coldkey,
+                amount_rao: positive(parse_ctn(amount)?)?,
+                fee_rao: given_fee.unwrap_or(node_fee),
+            }
+        }
+        BuildCmd::Stake { amount, node, .. } => TxAction::Stake {
+            node_uid: *node,
+            amount_rao: positive(parse_ctn(amount)?)?,
+        },
+        BuildCmd::Unstake { amount, node, .. } => TxAction::Unstake {
+            node_uid: *node,
+            amount_rao: match amount {
+                Some(amount) => positive(parse_ctn(amount)?)?,
+                None => 0,
+            },
+        },
+    };
+    let tx = TxFile {
+        version: TX_FILE_VERSION,
+        signer_coldkey,
+        nonce,
+        action,
+        signature: None,
+    };
+    let out = to_file(common.out.as_deref());
+    write_tx(&tx, out)?;
+    if let Some(out) = out {
+        eprintln!(
+            "Wrote unsigned transaction to {}: {}",
+            out.display(),
+            tx.describe()
+        );
+    }
+    Ok(())
+}
+
+fn sign(
+    file: &Path,
+    keystore: &Path,
+    out: Option<&Path>,
+    yes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut tx = read_tx(file)?;
+    if tx.signature.is_some() {
+        return Err(format!("{} is already signed", file.display()).into());
+    }
+    let signing_bytes = tx.signing_bytes()?;
+    let keypair = Keypair::from_secret_bytes(&*load_secret(keystore)?);
+    let signer = hex_encode(&keypair.public_key_bytes());
+    if signer != tx.signer_coldkey {
+        return Err(format!(
+            "{} holds coldkey {}, but the transaction is for {}",
+            keystore.display(),
+            signer,
+            tx.signer_coldkey
+        )
+        .into());
+    }
+
+    if !yes {
+        let summary = [
+            tx.describe(),
+            format!("  Signer: {}", tx.signer_coldkey),
+            format!("  Nonce:  {}", tx.nonce),
+        ];
+        if !confirm(&summary.join("\n"))? {
+            return Err("Signing cancelled".into());
+        }
+    }
+
+    tx.signature = Some(hex_encode(&keypair.sign(&signing_bytes)));
+    let out = to_file(out);
+    write_tx(&tx, out)?;
+    if let Some(out) = out {
+        eprintln!("Wrote signed transaction to {}", out.display());
+    }
+    Ok(())
+}
+
+/// Result of `tx submit`.
+#[derive(Debug, Serialize)]
+struct SubmitReport {
+    #[serde(flatten)]
+    tx: TxFile,
+    tx_hash: Option<String>,
+    block: Option<u64>,
+    cooldown_complete_block: Option<u64>,
+    message: String,
+}
+
+impl Render for SubmitReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Submitted: {}", self.tx.describe()),
+            format!("  Signer:  {}", self.tx.signer_coldkey),
+            format!("  Nonce:   {}", self.tx.nonce),
+        ];
+        if let Some(tx_hash) = &self.tx_hash {
+            lines.push(format!("  Tx hash: {}", tx_hash));
+        }
+        if let Some(block) = self.block {
+            lines.push(format!("  Block:   {}", block));
+        }
+        if let Some(block) = self.cooldown_complete_block {
+            lines.push(format!("  Unlocks at block {}", block));
+        }
+        lines.join("\n")
+    }
+}
+
+async fn submit(
+    file: &Path,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tx = read_tx(file)?;
+    let signature = match &tx.signature {
+        Some(signature) => signature.clone(),
+        None => {
+            return Err(format!(
+                "{} is not signed; run `chitin tx sign` first",
+                file.display()
+            )
+            .into())
+        }
+    };
+
+    let mut report = SubmitReport {
+        tx: tx.clone(),
+        tx_hash: None,
+        block: None,
+        cooldown_complete_block: None,
+        message: String::new(),
+    };
+    match tx.action {
+        TxAction::Transfer {
+            to_coldkey,
+            amount_rao,
+            fee_rao,
+        } => {
+            let request = TransferRequest {
+                from_coldkey: tx.signer_coldkey,
+                to_coldkey,
+                amount_rao,
+                fee_rao,
+                nonce: tx.nonce,
+                signature,
+            };
+            let resp: TransferResponse = rpc_result(
+                rpc_endpoint,
+                "wallet/transfer",
+                serde_json::to_value(&request)?,
+            )
+            .await?;
+            if !resp.success {
+                return Err(format!("Transfer failed: {}", resp.message).into());
+            }
+            report.tx_hash = resp.tx_hash;
+            report.block = resp.block;
+            report.message = resp.message;
+        }
+        TxAction::Stake {
+            node_uid,
+            amount_rao,
+        } => {
+            let request = StakeRequest {
+                staker_coldkey: tx.signer_coldkey,
+                node_uid,
+                amount_rao,
+                nonce: tx.nonce,
+                signature,
+            };
+            let resp: StakeResponse = rpc_result(
+                rpc_endpoint,
+                "staking/stake",
+                serde_json::to_value(&request)?,
+            )
+            .await?;
+            if !resp.success {
+                return Err(format!("Stake failed: {}", resp.message).into());
+            }
+            report.message = resp.message;
+        }
+        TxAction::Unstake {
+            node_uid,
+            amount_rao,
+        } => {
+            let request = UnstakeRequest {
+                staker_coldkey: tx.signer_coldkey,
+                node_uid,
+                amount_rao,
+                nonce: tx.nonce,
+                signature,
+            };
+            let resp: UnstakeResponse = rpc_result(
+                rpc_endpoint,
+                "staking/unstake",
+                serde_json::to_value(&request)?,
+            )
+            .await?;
+            if !resp.success {
+                return Err(format!("Unstake failed: {}", resp.message).into());
+            }
+            report.cooldown_complete_block = resp.cooldown_complete_block;
+            report.message = resp.message;
+        }
+    }
+    output::print(&report, format)
+}
+
+fn positive(amount_rao: u64) -> Result<u64, Box<dyn std::error::Error>> {
+    if amount_rao == 0 {
+        return Err("Amount must be positive".into());
+    }
+    Ok(amount_rao)
+}
+
+fn read_tx(path: &Path) -> Result<TxFile, Box<dyn std::error::Error>> {
+    let contents = if path == Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        contents
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {} ({})", path.display(), e))?
+    };
+    let tx: TxFile = serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is not a transaction file: {}", path.display(), e))?;
+    if tx.version != TX_FILE_VERSION {
+        return Err(format!(
+            "Unsupported transaction file version {} (expected {})",
+            tx.version, TX_FILE_VERSION
+        )
+        .into());
+    }
+    Ok(tx)
+}
+
+/// The file to write to, or `None` for stdout.
+fn to_file(out: Option<&Path>) -> Option<&Path> {
+    out.filter(|path| *path != Path::new("-"))
+}
+
+fn write_tx(tx: &TxFile, out: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(tx)?;
+    match out {
+        Some(path) => fs::write(path, json + "\n")?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
@@ -1,11 +1,33 @@
 // crates/chitin-cli/src/commands/wallet.rs
 //
-// `chitin wallet {create, import, export}` — key management commands.
+// `chitin wallet {create, import, export, balance, transfer, history}` —
+// key management and $CTN transfers.
+//
+// Transfers are built and signed locally with the coldkey in
+// ~/.chitin/keys, then submitted to the node with `wallet/transfer`. The
+// fee and nonce come from `wallet/balance`, and a transfer is confirmed at
+// the terminal before it is sent unless `--yes` is given. An encrypted
+// coldkey is unlocked with the passphrase in CHITIN_KEYSTORE_PASSPHRASE.
+
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use chitin_core::crypto::Keypair;
+use chitin_core::keystore::{EncryptedKeystore, SecretKey, Zeroizing};
+use chitin_economics::{Transfer, RAO_PER_CTN};
+use chitin_rpc::handlers::wallet::{
+    GetBalanceResponse, GetHistoryResponse, TransferRequest, TransferResponse,
+};
 use clap::Subcommand;
-use std::fs;
-use std::path::PathBuf;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Environment variable holding the passphrase of an encrypted coldkey.
+pub(crate) const PASSPHRASE_ENV: &str = "CHITIN_KEYSTORE_PASSPHRASE";
 
 /// Wallet management subcommands.
 #[derive(Debug, Subcommand)]
@@ -20,14 +42,52 @@ pub enum WalletCmd {
     },
     /// Export the current public key.
     Export,
+    /// Show the $CTN balance of a coldkey.
+    Balance {
+        /// Coldkey (hex or DID; default: this wallet's coldkey).
+        #[arg(long)]
+        address: Option<String>,
+    },
+    /// Sign and send $CTN to another coldkey.
+    Transfer {
+        /// Recipient coldkey (hex or DID).
+        #[arg(long)]
+        to: String,
+        /// Amount of $CTN to send (up to 9 decimal places).
+        #[arg(long)]
+        amount: String,
+        /// Send without asking for confirmation.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// List transfers sent or received by a coldkey, newest first.
+    History {
+        /// Coldkey (hex or DID; default: this wallet's coldkey).
+        #[arg(long)]
+        address: Option<String>,
+        /// Maximum number of transfers to show.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
 }
 
 /// Run the wallet subcommand.
-pub async fn run(cmd: &WalletCmd) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn run(
+    cmd: &WalletCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
     match cmd {
         WalletCmd::Create => create_wallet().await,
         WalletCmd::Import { path } => import_wallet(path).await,
-        WalletCmd::Export => export_wallet().await,
+        WalletCmd::Export => export_wallet(format).await,
+        WalletCmd::Balance { address } => balance(rpc_endpoint, address.as_deref(), format).await,
+        WalletCmd::Transfer { to, amount, yes } => {
+            transfer(rpc_endpoint, to, amount, *yes, format).await
+        }
+        WalletCmd::History { address, limit } => {
+            history(rpc_endpoint, address.as_deref(), *limit, format).await
+        }
     }
 }
 
@@ -77,31 +137,355 @@ async fn import_wallet(path: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-async fn export_wallet() -> Result<(), Box<dyn std::error::Error>> {
+/// The wallet's public keys.
+#[derive(Debug, Serialize)]
+struct WalletKeys {
+    coldkey: Option<String>,
+    hotkey: Option<String>,
+}
+
+impl Render for WalletKeys {
+    fn render_table(&self) -> String {
+        let mut lines = vec![match &self.coldkey {
+            Some(coldkey) => format!("Coldkey public key: {}", coldkey),
+            None => "No wallet found. Run `chitin wallet create` first.".to_string(),
+        }];
+        if let Some(hotkey) = &self.hotkey {
+            lines.push(format!("Hotkey public key:  {}", hotkey));
+        }
+        lines.join("\n")
+    }
+}
+
+async fn export_wallet(format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
     let keys_dir = get_keys_dir()?;
-    let pub_path = keys_dir.join("coldkey.pub");
+    let read_key = |name: &str| -> Result<Option<String>, std::io::Error> {
+        let path = keys_dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read_to_string(path)?.trim().to_string()))
+    };
 
-    if pub_path.exists() {
-        let pubkey = fs::read_to_string(&pub_path)?;
-        println!("Coldkey public key: {}", pubkey.trim());
-    } else {
-        println!("No wallet found. Run `chitin wallet create` first.");
+    let keys = WalletKeys {
+        coldkey: read_key("coldkey.pub")?,
+        hotkey: read_key("hotkey.pub")?,
+    };
+    output::print(&keys, format)
+}
+
+pub(crate) async fn get_balance(
+    rpc_endpoint: &str,
+    coldkey: &str,
+) -> Result<GetBalanceResponse, Box<dyn std::error::Error>> {
+    let params = serde_json::json!({ "coldkey": coldkey });
+    rpc_result(rpc_endpoint, "wallet/balance", params).await
+}
+
+/// A coldkey's balance.
+#[derive(Debug, Serialize)]
+struct BalanceReport {
+    coldkey: String,
+    #[serde(flatten)]
+    balance: GetBalanceResponse,
+}
+
+impl Render for BalanceReport {
+    fn render_table(&self) -> String {
+        [
+            format!("Coldkey:      {}", self.coldkey),
+            format!("  Balance:    {}", ctn(self.balance.balance_rao)),
+            format!("  Available:  {}", ctn(self.balance.available_rao)),
+            format!("  Staked:     {}", ctn(self.balance.staked_rao)),
+            format!("  Next nonce: {}", self.balance.nonce),
+            format!("  Fee:        {} per transfer", ctn(self.balance.transfer_fee_rao)),
+        ]
+        .join("\n")
     }
+}
+
+async fn balance(
+    rpc_endpoint: &str,
+    address: Option<&str>,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let coldkey = resolve_address(address)?;
+    let balance = get_balance(rpc_endpoint, &coldkey).await?;
+    output::print(&BalanceReport { coldkey, balance }, format)
+}
 
-    let hotkey_path = keys_dir.join("hotkey.pub");
-    if hotkey_path.exists() {
-        let hotkey = fs::read_to_string(&hotkey_path)?;
-        println!("Hotkey public key:  {}", hotkey.trim());
+/// A submitted transfer.
+#[derive(Debug, Serialize)]
+struct TransferReport {
+    from_coldkey: String,
+    to_coldkey: String,
+    amount_rao: u64,
+    fee_rao: u64,
+    nonce: u64,
+    tx_hash: Option<String>,
+    block: Option<u64>,
+}
+
+impl Render for TransferReport {
+    fn render_table(&self) -> String {
+        let mut lines = vec![
+            format!("Sent {} to {}", ctn(self.amount_rao), self.to_coldkey),
+            format!("  Fee:     {}", ctn(self.fee_rao)),
+            format!("  Nonce:   {}", self.nonce),
+        ];
+        if let Some(tx_hash) = &self.tx_hash {
+            lines.push(format!("  Tx hash: {}", tx_hash));
+        }
+        if let Some(block) = self.block {
+            lines.push(format!("  Block:   {}", block));
+        }
+        lines.join("\n")
     }
+}
 
-    Ok(())
+async fn transfer(
+    rpc_endpoint: &str,
+    to: &str,
+    amount: &str,
+    yes: bool,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let amount_rao = parse_ctn(amount)?;
+    if amount_rao == 0 {
+        return Err("Amount must be positive".into());
+    }
+    let to_coldkey = parse_address(to)?;
+    let keypair = Keypair::from_secret_bytes(&*load_coldkey_secret()?);
+    let from = keypair.public_key_bytes();
+    let from_coldkey = hex_encode(&from);
+    if from_coldkey == to_coldkey {
+        return Err("Cannot transfer to this wallet's own coldkey".into());
+    }
+
+    let balance = get_balance(rpc_endpoint, &from_coldkey).await?;
+    let fee_rao = balance.transfer_fee_rao;
+    let total = amount_rao.saturating_add(fee_rao);
+    if total > balance.available_rao {
+        return Err(format!(
+            "Insufficient balance: sending {} plus a {} fee needs {}, but {} is available",
+            ctn(amount_rao),
+            ctn(fee_rao),
+            ctn(total),
+            ctn(balance.available_rao)
+        )
+        .into());
+    }
+
+    if !yes {
+        let summary = [
+            format!("Send {} to {}", ctn(amount_rao), to_coldkey),
+            format!("  Fee:           {}", ctn(fee_rao)),
+            format!("  Total:         {}", ctn(total)),
+            format!("  Balance after: {}", ctn(balance.available_rao - total)),
+        ];
+        if !confirm(&summary.join("\n"))? {
+            return Err("Transfer cancelled".into());
+        }
+    }
+
+    let transfer = Transfer {
+        from,
+        to: decode_key(&to_coldkey)?,
+        amount: amount_rao,
+        fee: fee_rao,
+        nonce: balance.nonce,
+    };
+    let request = TransferRequest {
+        from_coldkey: from_coldkey.clone(),
+        to_coldkey: to_coldkey.clone(),
+        amount_rao,
+        fee_rao,
+        nonce: balance.nonce,
+        signature: hex_encode(&keypair.sign(&transfer.signing_bytes())),
+    };
+    let resp: TransferResponse =
+        rpc_result(rpc_endpoint, "wallet/transfer", serde_json::to_value(&request)?).await?;
+    if !resp.success {
+        return Err(format!("Transfer failed: {}", resp.message).into());
+    }
+
+    let report = TransferReport {
+        from_coldkey,
+        to_coldkey,
+        amount_rao,
+        fee_rao,
+        nonce: balance.nonce,
+        tx_hash: resp.tx_hash,
+        block: resp.block,
+    };
+    output::print(&report, format)
+}
+
+/// A row in the transfer history table.
+#[derive(Tabled)]
+struct HistoryRow {
+    #[tabled(rename = "Block")]
+    block: u64,
+    #[tabled(rename = "Direction")]
+    direction: String,
+    #[tabled(rename = "Counterparty")]
+    counterparty: String,
+    #[tabled(rename = "Amount")]
+    amount: String,
+    #[tabled(rename = "Fee")]
+    fee: String,
+    #[tabled(rename = "Tx Hash")]
+    tx_hash: String,
+}
+
+impl Render for GetHistoryResponse {
+    fn render_table(&self) -> String {
+        if self.transfers.is_empty() {
+            return format!("No transfers for {}.", self.coldkey);
+        }
+        let rows: Vec<HistoryRow> = self
+            .transfers
+            .iter()
+            .map(|t| HistoryRow {
+                block: t.block,
+                direction: t.direction.clone(),
+                counterparty: output::truncate(
+                    if t.direction == "sent" { &t.to_coldkey } else { &t.from_coldkey },
+                    16,
+                ),
+                amount: ctn(t.amount_rao),
+                fee: if t.direction == "sent" { ctn(t.fee_rao) } else { String::new() },
+                tx_hash: output::truncate(&t.tx_hash, 16),
+            })
+            .collect();
+        format!("Transfers for {}\n\n{}", self.coldkey, format_table(&rows))
+    }
+}
+
+async fn history(
+    rpc_endpoint: &str,
+    address: Option<&str>,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let params = serde_json::json!({ "coldkey": resolve_address(address)?, "limit": limit });
+    let resp: GetHistoryResponse = rpc_result(rpc_endpoint, "wallet/history", params).await?;
+    output::print(&resp, format)
+}
+
+/// Ask for confirmation at the terminal; refuses without one.
+pub(crate) fn confirm(summary: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    if !std::io::stdin().is_terminal() {
+        return Err("Refusing to continue without confirmation; pass --yes".into());
+    }
+    let mut stderr = std::io::stderr();
+    write!(stderr, "{}\n\nConfirm? [y/N] ", summary)?;
+    stderr.flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Read the coldkey secret, unlocking it if it is an encrypted keystore.
+pub(crate) fn load_coldkey_secret() -> Result<SecretKey, Box<dyn std::error::Error>> {
+    let path = get_keys_dir()?.join("coldkey.secret");
+    if !path.exists() {
+        return Err(format!(
+            "Could not read {}. Run `chitin wallet create` first.",
+            path.display()
+        )
+        .into());
+    }
+    load_secret(&path)
+}
+
+/// Read a hex secret key file or an encrypted keystore, unlocking the
+/// latter with the passphrase in CHITIN_KEYSTORE_PASSPHRASE.
+pub(crate) fn load_secret(path: &Path) -> Result<SecretKey, Box<dyn std::error::Error>> {
+    let contents = Zeroizing::new(
+        fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {} ({})", path.display(), e))?,
+    );
+    if EncryptedKeystore::is_keystore(&contents) {
+        let passphrase = Zeroizing::new(std::env::var(PASSPHRASE_ENV).map_err(|_| {
+            format!("{} is an encrypted keystore; set {}", path.display(), PASSPHRASE_ENV)
+        })?);
+        return Ok(EncryptedKeystore::from_json(&contents)?.decrypt(passphrase.as_bytes())?);
+    }
+    Ok(SecretKey::new(decode_key(contents.trim())?))
+}
+
+/// The coldkey to query: `address`, or this wallet's coldkey.
+pub(crate) fn resolve_address(address: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    match address {
+        Some(address) => parse_address(address),
+        None => {
+            let path = get_keys_dir()?.join("coldkey.pub");
+            match fs::read_to_string(&path) {
+                Ok(coldkey) => parse_address(coldkey.trim()),
+                Err(_) => {
+                    Err("No wallet found. Run `chitin wallet create` or pass --address.".into())
+                }
+            }
+        }
+    }
+}
+
+/// Normalize a hex coldkey or `did:chitin:<hex>` DID to lowercase hex.
+pub(crate) fn parse_address(address: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let hex = address.trim();
+    let hex = hex.strip_prefix("did:chitin:").unwrap_or(hex).to_ascii_lowercase();
+    decode_key(&hex)?;
+    Ok(hex)
+}
+
+pub(crate) fn decode_key(hex: &str) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let invalid = || format!("{:?} is not a 32-byte hex key", hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid().into());
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(key)
+}
+
+/// Parse a decimal CTN amount (e.g. "1.5") into rao without rounding.
+pub(crate) fn parse_ctn(amount: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    let invalid = || format!("Invalid amount {:?}", amount);
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+        return Err(invalid().into());
+    }
+    if fraction.len() > 9 {
+        return Err(format!("{:?} has more than 9 decimal places", amount).into());
+    }
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse()? };
+    let fraction: u64 = format!("{:0<9}", fraction).parse()?;
+    whole
+        .checked_mul(RAO_PER_CTN)
+        .and_then(|rao| rao.checked_add(fraction))
+        .ok_or_else(|| invalid().into())
+}
+
+/// Format rao as CTN with trailing zeros trimmed.
+pub(crate) fn ctn(rao: u64) -> String {
+    let fraction = format!("{:09}", rao % RAO_PER_CTN);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{} CTN", rao / RAO_PER_CTN)
+    } else {
+        format!("{}.{} CTN", rao / RAO_PER_CTN, fraction)
+    }
 }
 
-fn get_keys_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
-    Ok(home.join(".chitin").join("keys"))
+/// Directory of the key files: the profile's, else ~/.chitin/keys.
+pub(crate) fn get_keys_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    crate::profile::keys_dir()
 }
 
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
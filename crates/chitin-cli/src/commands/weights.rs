@@ -0,0 +1,134 @@
+// crates/chitin-cli/src/commands/weights.rs
+//
+// `chitin weights` and `chitin bonds` — the Tide Nodes' weight and bond
+// matrices, as sparse (validator, coral, value) entries.
+
+use std::collections::HashMap;
+
+use clap::Args;
+use serde::Serialize;
+use tabled::Tabled;
+
+use chitin_rpc::handlers::metagraph::{GetBondsResponse, GetWeightsResponse};
+
+use crate::output::{self, format_table, OutputFormat, Render};
+use crate::rpc_client::rpc_result;
+
+/// Shared options of `weights` and `bonds`.
+#[derive(Debug, Args)]
+pub struct MatrixCmd {
+    /// Only show this validator's (Tide Node's) row.
+    #[arg(long)]
+    pub validator: Option<u16>,
+}
+
+/// Run the weights command.
+pub async fn run_weights(
+    cmd: &MatrixCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let params = serde_json::json!({ "epoch": null, "validator_uid": cmd.validator });
+    let resp: GetWeightsResponse = rpc_result(rpc_endpoint, "metagraph/weights", params).await?;
+    let matrix = Matrix::new("Weights", resp.epoch, resp.weights);
+    output::print(&matrix, format)
+}
+
+/// Run the bonds command.
+pub async fn run_bonds(
+    cmd: &MatrixCmd,
+    rpc_endpoint: &str,
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let params = serde_json::json!({ "epoch": null, "validator_uid": cmd.validator });
+    let resp: GetBondsResponse = rpc_result(rpc_endpoint, "metagraph/bonds", params).await?;
+    let matrix = Matrix::new("Bonds", resp.epoch, resp.bonds);
+    output::print(&matrix, format)
+}
+
+/// One nonzero matrix entry.
+#[derive(Debug, Serialize)]
+struct Entry {
+    validator_uid: u16,
+    coral_uid: u16,
+    value: f64,
+    /// Fraction of the validator's row this entry holds.
+    row_share: f64,
+}
+
+/// A row in the matrix table.
+#[derive(Tabled)]
+struct EntryRow {
+    #[tabled(rename = "Validator")]
+    validator_uid: u16,
+    #[tabled(rename = "Coral")]
+    coral_uid: u16,
+    #[tabled(rename = "Value")]
+    value: String,
+    #[tabled(rename = "Row Share")]
+    row_share: String,
+}
+
+/// A sparse weight or bond matrix, sorted by validator and then by
+/// descending value.
+#[derive(Debug, Serialize)]
+struct Matrix {
+    #[serde(skip)]
+    title: &'static str,
+    epoch: u64,
+    entries: Vec<Entry>,
+}
+
+impl Matrix {
+    fn new(title: &'static str, epoch: u64, rows: HashMap<u16, Vec<(u16, f64)>>) -> Self {
+        let mut rows: Vec<(u16, Vec<(u16, f64)>)> = rows.into_iter().collect();
+        rows.sort_by_key(|(validator, _)| *validator);
+        let mut entries = Vec::new();
+        for (validator_uid, mut row) in rows {
+            row.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            let total: f64 = row.iter().map(|(_, v)| v).sum();
+            entries.extend(row.into_iter().map(|(coral_uid, value)| Entry {
+                validator_uid,
+                coral_uid,
+                value,
+                row_share: if total > 0.0 { value / total } else { 0.0 },
+            }));
+        }
+        Self {
+            title,
+            epoch,
+            entries,
+        }
+    }
+}
+
+impl Render for Matrix {
+    fn render_table(&self) -> String {
+        if self.entries.is_empty() {
+            return format!("{} (epoch {}): none set.", self.title, self.epoch);
+        }
+        let mut validators: Vec<u16> = self.entries.iter().map(|e| e.validator_uid).collect();
+        validators.dedup();
+        let mut corals: Vec<u16> = self.entries.iter().map(|e| e.coral_uid).collect();
+        corals.sort_unstable();
+        corals.dedup();
+        let rows: Vec<EntryRow> = self
+            .entries
+            .iter()
+            .map(|e| EntryRow {
+                validator_uid: e.validator_uid,
+                coral_uid: e.coral_uid,
+                value: format!("{:.4}", e.value),
+                row_share: format!("{:.1}%", 100.0 * e.row_share),
+            })
+            .collect();
+        format!(
+            "{} (epoch {}): {} validator(s), {} coral(s)\n\n{}",
+            self.title,
+            self.epoch,
+            validators.len(),
+            corals.len(),
+            format_table(&rows)
+        )
+    }
+}
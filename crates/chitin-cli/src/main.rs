@@ -2,18 +2,41 @@
 //
 // CLI entrypoint for the Chitin Protocol developer tools.
 //
-// Provides subcommands for initializing a node, managing wallets,
-// creating and querying Polyps, staking, and viewing network status.
+// Provides subcommands for initializing a node, managing wallets and keys,
+// creating and querying Polyps, staking, signing transactions offline,
+// inspecting drift and planning and driving molts, running the genesis
+// ceremony, inspecting epochs and consensus and reputation, viewing network
+// status (once, or live with `top`), administering a running node, and
+// benchmarking search and ingest. Shell
+// completions and a JSON manifest of every command are generated from the
+// same definitions.
+// Command results print as tables, JSON, or YAML (`--output`). Named profiles
+// in ~/.chitin/cli.toml supply the RPC endpoint and keys (`--profile`).
 
 mod commands;
 mod output;
+mod profile;
 pub mod rpc_client;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use commands::admin::AdminCmd;
+use commands::bench::BenchCmd;
+use commands::completions::CompletionsCmd;
+use commands::config::ConfigCmd;
+use commands::drift::DriftCmd;
+use commands::epoch::EpochCmd;
+use commands::genesis::GenesisCmd;
+use commands::keys::KeysCmd;
+use commands::molt::MoltCmd;
 use commands::polyp::PolypCmd;
 use commands::query::QueryCmd;
+use commands::reputation::ReputationCmd;
 use commands::stake::StakeCmd;
+use commands::top::TopCmd;
+use commands::tx::TxCmd;
 use commands::wallet::WalletCmd;
+use commands::weights::MatrixCmd;
+use output::OutputFormat;
 
 /// Chitin Protocol CLI — developer tools for Reefipedia.
 #[derive(Parser, Debug)]
@@ -23,9 +46,18 @@ use commands::wallet::WalletCmd;
     about = "Chitin Protocol CLI for Reefipedia — decentralized semantic knowledge store"
 )]
 struct Cli {
-    /// RPC endpoint for the chitin-daemon.
-    #[arg(long, global = true, default_value = "http://localhost:50051")]
-    rpc: String,
+    /// RPC endpoint for the chitin-daemon (default: the profile's, else
+    /// http://localhost:50051).
+    #[arg(long, global = true)]
+    rpc: Option<String>,
+
+    /// Profile from ~/.chitin/cli.toml to use instead of the active one.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Output format for command results: table, json, or yaml.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
 
     #[command(subcommand)]
     command: Commands,
@@ -37,40 +69,119 @@ enum Commands {
     /// Initialize Chitin configuration and generate keypair.
     Init,
 
-    /// Wallet management: create, import, export keys.
+    /// Wallet management: keys, balances, transfers, and history.
     #[command(subcommand)]
     Wallet(WalletCmd),
 
-    /// Polyp management: create, get, list.
+    /// Key management: recovery phrases, hotkey rotation, and inspection.
+    #[command(subcommand)]
+    Keys(KeysCmd),
+
+    /// Polyp management: create, get, list, verify, export, import.
     #[command(subcommand)]
     Polyp(PolypCmd),
 
     /// Semantic search against the Reef.
     Query(QueryCmd),
 
-    /// Staking management: stake, unstake, info.
+    /// Staking management: add, remove, info.
     #[command(subcommand)]
     Stake(StakeCmd),
 
+    /// Offline signing: build, sign, and submit transaction files.
+    #[command(subcommand)]
+    Tx(TxCmd),
+
     /// Display node connection status and version info.
     Status,
 
     /// Display the Reef Metagraph (network state).
     Metagraph,
+
+    /// Reputation inspection: per-domain trust, leaderboards, and evidence.
+    #[command(subcommand)]
+    Reputation(ReputationCmd),
+
+    /// Epoch inspection: current status and consensus results.
+    #[command(subcommand)]
+    Epoch(EpochCmd),
+
+    /// Show the validators' weight matrix.
+    Weights(MatrixCmd),
+
+    /// Show the validators' bond matrix.
+    Bonds(MatrixCmd),
+
+    /// Live dashboard: epoch, polyps, peers, weights, and node resources.
+    Top(TopCmd),
+
+    /// Embedding drift: model versions and the drift of each migration.
+    #[command(subcommand)]
+    Drift(DriftCmd),
+
+    /// Model migration: plan, start, pause, and track molts.
+    #[command(subcommand)]
+    Molt(MoltCmd),
+
+    /// Genesis ceremony: create and verify a network's genesis file.
+    #[command(subcommand)]
+    Genesis(GenesisCmd),
+
+    /// Node administration: logs, config, backups, snapshots, and background tasks.
+    #[command(subcommand)]
+    Admin(AdminCmd),
+
+    /// Benchmarks: search and ingest latency and throughput.
+    #[command(subcommand)]
+    Bench(BenchCmd),
+
+    /// CLI configuration: named endpoint and keystore profiles.
+    #[command(subcommand)]
+    Config(ConfigCmd),
+
+    /// Print a shell completion script (bash, zsh, fish, ...).
+    Completions(CompletionsCmd),
+
+    /// Print every command and flag as JSON, for wrappers and docs tooling.
+    Manifest,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    let settings = match &cli.command {
+        // These need no node or keys, and profile commands edit the file
+        // the others are resolved from.
+        Commands::Config(_) | Commands::Completions(_) | Commands::Manifest => {
+            profile::Settings::default()
+        }
+        _ => profile::resolve(cli.rpc.as_deref(), cli.profile.as_deref())?,
+    };
+    let rpc = &settings.rpc;
 
     match &cli.command {
         Commands::Init => commands::init::run().await?,
-        Commands::Wallet(cmd) => commands::wallet::run(cmd).await?,
-        Commands::Polyp(cmd) => commands::polyp::run(cmd, &cli.rpc).await?,
-        Commands::Query(cmd) => commands::query::run(cmd, &cli.rpc).await?,
-        Commands::Stake(cmd) => commands::stake::run(cmd).await?,
-        Commands::Status => commands::status::run(&cli.rpc).await?,
-        Commands::Metagraph => commands::metagraph::run().await?,
+        Commands::Wallet(cmd) => commands::wallet::run(cmd, rpc, cli.output).await?,
+        Commands::Keys(cmd) => commands::keys::run(cmd, cli.output).await?,
+        Commands::Polyp(cmd) => commands::polyp::run(cmd, rpc, cli.output).await?,
+        Commands::Query(cmd) => commands::query::run(cmd, rpc, cli.output).await?,
+        Commands::Stake(cmd) => commands::stake::run(cmd, rpc, cli.output).await?,
+        Commands::Tx(cmd) => commands::tx::run(cmd, rpc, cli.output).await?,
+        Commands::Status => commands::status::run(&settings, cli.output).await?,
+        Commands::Metagraph => commands::metagraph::run(rpc, cli.output).await?,
+        Commands::Reputation(cmd) => commands::reputation::run(cmd, rpc, cli.output).await?,
+        Commands::Epoch(cmd) => commands::epoch::run(cmd, rpc, cli.output).await?,
+        Commands::Weights(cmd) => commands::weights::run_weights(cmd, rpc, cli.output).await?,
+        Commands::Bonds(cmd) => commands::weights::run_bonds(cmd, rpc, cli.output).await?,
+        Commands::Top(cmd) => commands::top::run(cmd, rpc).await?,
+        Commands::Drift(cmd) => commands::drift::run(cmd, rpc, cli.output).await?,
+        Commands::Molt(cmd) => commands::molt::run(cmd, rpc, cli.output).await?,
+        Commands::Genesis(cmd) => commands::genesis::run(cmd, cli.output).await?,
+        Commands::Admin(cmd) => commands::admin::run(cmd, rpc, cli.output).await?,
+        Commands::Bench(cmd) => commands::bench::run(cmd, rpc, cli.output).await?,
+        Commands::Config(cmd) => commands::config::run(cmd, cli.output)?,
+        Commands::Completions(cmd) => commands::completions::run(cmd, Cli::command())?,
+        Commands::Manifest => commands::manifest::run(Cli::command(), cli.output)?,
     }
 
     Ok(())
@@ -1,18 +1,46 @@
 // crates/chitin-cli/src/output.rs
 //
 // Output formatting utilities for the Chitin CLI.
-// Supports table and JSON output modes.
+// Supports table, JSON, and YAML output modes, chosen with the global
+// `--output` flag. JSON and YAML serialize a command's result as-is, so
+// scripts see the same fields in both; tables are for reading at a terminal.
 
+use std::io::Write;
+
+use clap::ValueEnum;
 use serde::Serialize;
 use tabled::{Table, Tabled};
 
 /// Output format for CLI commands.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
 pub enum OutputFormat {
     /// Pretty-printed table output (default).
+    #[default]
     Table,
     /// JSON output for machine consumption.
     Json,
+    /// YAML output for machine consumption.
+    Yaml,
+}
+
+/// A command result that can be printed in any output format.
+pub trait Render: Serialize {
+    /// The human-readable rendering used for `--output table`.
+    fn render_table(&self) -> String;
+}
+
+/// Print a command result in the chosen format. A closed stdout (e.g. the
+/// output piped into `head`) is not an error.
+pub fn print<T: Render>(value: &T, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let text = match format {
+        OutputFormat::Table => value.render_table(),
+        OutputFormat::Json => serde_json::to_string_pretty(value)?,
+        OutputFormat::Yaml => serde_yaml::to_string(value)?,
+    };
+    match writeln!(std::io::stdout().lock(), "{}", text.trim_end()) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
 }
 
 /// Format a slice of Tabled items as a table string.
@@ -20,7 +48,11 @@ pub fn format_table<T: Tabled>(data: &[T]) -> String {
     Table::new(data).to_string()
 }
 
-/// Format a serializable value as a pretty-printed JSON string.
-pub fn format_json<T: Serialize>(data: &T) -> String {
-    serde_json::to_string_pretty(data).unwrap_or_else(|e| format!("JSON serialization error: {}", e))
+/// Truncate a string to the given maximum length in characters, appending
+/// "..." if truncated.
+pub fn truncate(s: &str, max_len: usize) -> String {
+    match s.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
+    }
 }
@@ -0,0 +1,131 @@
+// crates/chitin-cli/src/profile.rs
+//
+// Named CLI profiles, kept in `~/.chitin/cli.toml`.
+//
+// A profile pairs a node's RPC endpoint with the directory holding the keys
+// used against it, so one CLI can drive devnet and testnet nodes without
+// retyping `--rpc` or moving key files around. Commands take the profile
+// named by `--profile`, else the active one; the endpoint comes from
+// `--rpc`, else the profile, else the local default, and keys from the
+// profile's `keys_dir`, else `~/.chitin/keys`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Endpoint used when neither `--rpc` nor a profile gives one.
+pub const DEFAULT_RPC: &str = "http://localhost:50051";
+
+/// Keys directory of the profile in use, set once by `resolve`.
+static KEYS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// The contents of `cli.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CliConfig {
+    /// Profile used when `--profile` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named endpoint and keystore combination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// RPC endpoint of the node.
+    pub rpc: String,
+    /// Directory of the profile's key files (default: `~/.chitin/keys`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys_dir: Option<PathBuf>,
+}
+
+/// What commands run against, after applying flags and profiles.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub rpc: String,
+    /// Name of the profile in use, if any.
+    pub profile: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rpc: DEFAULT_RPC.to_string(),
+            profile: None,
+        }
+    }
+}
+
+impl CliConfig {
+    /// Path of the CLI config file.
+    pub fn path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(chitin_dir()?.join("cli.toml"))
+    }
+
+    /// Read the CLI config; a missing file is an empty config.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path()?;
+        match fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| format!("Could not parse {}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Could not read {}: {}", path.display(), e).into()),
+        }
+    }
+
+    /// Write the CLI config, creating `~/.chitin` if needed.
+    pub fn save(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let path = Self::path()?;
+        fs::create_dir_all(chitin_dir()?)?;
+        fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Resolve the endpoint and keys directory from `--rpc`, `--profile`, and
+/// the active profile, and make the keys directory the one commands use.
+pub fn resolve(
+    rpc: Option<&str>,
+    profile: Option<&str>,
+) -> Result<Settings, Box<dyn std::error::Error>> {
+    let config = CliConfig::load()?;
+    let name = profile.map(str::to_string).or(config.active.clone());
+    let chosen = match &name {
+        Some(name) => match config.profiles.get(name) {
+            Some(chosen) => Some(chosen),
+            None => {
+                return Err(format!(
+                    "No profile named {:?}. Run `chitin config profile list` to see profiles.",
+                    name
+                )
+                .into())
+            }
+        },
+        None => None,
+    };
+    if let Some(keys_dir) = chosen.and_then(|p| p.keys_dir.clone()) {
+        let _ = KEYS_DIR.set(keys_dir);
+    }
+    let rpc = rpc
+        .map(str::to_string)
+        .or_else(|| chosen.map(|p| p.rpc.clone()))
+        .unwrap_or_else(|| DEFAULT_RPC.to_string());
+    Ok(Settings { rpc, profile: name })
+}
+
+/// Directory of the key files commands use.
+pub fn keys_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match KEYS_DIR.get() {
+        Some(dir) => Ok(dir.clone()),
+        None => Ok(chitin_dir()?.join("keys")),
+    }
+}
+
+/// The CLI's home directory (~/.chitin/).
+fn chitin_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    Ok(home.join(".chitin"))
+}
@@ -2,6 +2,7 @@
 //
 // Lightweight JSON-RPC client that POSTs to the chitin-daemon HTTP endpoint.
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// Mirrors the server's JsonRpcRequest envelope.
@@ -24,13 +25,22 @@ pub async fn rpc_call(
     endpoint: &str,
     method: &str,
     params: serde_json::Value,
+) -> Result<JsonRpcResponse, Box<dyn std::error::Error>> {
+    rpc_call_with(&reqwest::Client::new(), endpoint, method, params).await
+}
+
+/// `rpc_call` over an existing client, reusing its connections.
+pub async fn rpc_call_with(
+    client: &reqwest::Client,
+    endpoint: &str,
+    method: &str,
+    params: serde_json::Value,
 ) -> Result<JsonRpcResponse, Box<dyn std::error::Error>> {
     let request = JsonRpcRequest {
         method: method.to_string(),
         params,
     };
 
-    let client = reqwest::Client::new();
     let resp = client
         .post(endpoint)
         .json(&request)
@@ -40,3 +50,28 @@ pub async fn rpc_call(
     let rpc_response: JsonRpcResponse = resp.json().await?;
     Ok(rpc_response)
 }
+
+/// Send a JSON-RPC call and decode its result, turning an error response
+/// into an error.
+pub async fn rpc_result<T: DeserializeOwned>(
+    endpoint: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, Box<dyn std::error::Error>> {
+    rpc_result_with(&reqwest::Client::new(), endpoint, method, params).await
+}
+
+/// `rpc_result` over an existing client, reusing its connections.
+pub async fn rpc_result_with<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    endpoint: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let resp = rpc_call_with(client, endpoint, method, params).await?;
+    if !resp.success {
+        return Err(resp.error.unwrap_or_else(|| "Unknown error".to_string()).into());
+    }
+    let result = resp.result.ok_or("Response has no result")?;
+    Ok(serde_json::from_value(result)?)
+}
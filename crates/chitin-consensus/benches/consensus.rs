@@ -0,0 +1,167 @@
+// crates/chitin-consensus/benches/consensus.rs
+//
+// Consensus benchmarks: Yuma-Semantic Consensus, bond updates, and batch
+// Polyp scoring, each on one thread and on rayon's default pool. Results
+// are checked to be identical on both.
+//
+// Run with: cargo bench -p chitin-consensus --bench consensus
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chitin_consensus::bonds::BondMatrix;
+use chitin_consensus::scoring::score_polyps;
+use chitin_consensus::weights::WeightMatrix;
+use chitin_consensus::yuma::yuma_semantic_consensus;
+use chitin_core::{
+    EmbeddingModelId, NodeIdentity, NodeType, Payload, Polyp, PolypState, PolypSubject,
+    ProcessingPipeline, ProofPublicInputs, Provenance, SourceAttribution, VectorEmbedding, ZkProof,
+};
+use chrono::Utc;
+use rayon::ThreadPool;
+use uuid::Uuid;
+
+/// (validators, corals) of the benchmarked weight matrices.
+const SIZES: &[(usize, usize)] = &[(64, 1_000), (128, 4_000), (256, 8_000)];
+
+/// Polyps per scoring batch.
+const POLYPS: usize = 10_000;
+
+/// Deterministic matrix cell in [0, 1).
+fn cell(i: usize, j: usize, salt: usize) -> f64 {
+    ((i * 7919 + j * 104_729 + salt * 31) % 1_000) as f64 / 1_000.0
+}
+
+fn matrix(rows: usize, cols: usize, salt: usize) -> Vec<Vec<f64>> {
+    (0..rows)
+        .map(|i| (0..cols).map(|j| cell(i, j, salt)).collect())
+        .collect()
+}
+
+fn polyp(i: usize) -> Polyp {
+    let model_id = EmbeddingModelId::from_key("bench/model", 384);
+    let values: Vec<f32> = (0..384).map(|d| cell(i, d, 3) as f32 / 20.0).collect();
+    Polyp {
+        id: Uuid::now_v7(),
+        state: PolypState::UnderReview,
+        subject: PolypSubject {
+            payload: Payload {
+                content: "benchmark content ".repeat(1 + i % 20),
+                content_type: "text/plain".to_string(),
+                language: Some("en".to_string()),
+            },
+            vector: VectorEmbedding {
+                model_id: model_id.clone(),
+                values,
+                quantization: "float32".to_string(),
+                normalization: "l2".to_string(),
+            },
+            provenance: Provenance {
+                creator: NodeIdentity {
+                    coldkey: [1; 32],
+                    hotkey: [2; 32],
+                    did: "did:chitin:bench".to_string(),
+                    node_type: NodeType::Coral,
+                },
+                source: SourceAttribution {
+                    source_cid: None,
+                    source_url: Some(format!("https://example.com/{}", i)),
+                    title: None,
+                    license: None,
+                    accessed_at: Utc::now(),
+                },
+                pipeline: ProcessingPipeline {
+                    steps: vec![],
+                    duration_ms: 1,
+                },
+                molted_from: vec![],
+            },
+        },
+        proof: ZkProof {
+            proof_type: "SP1Groth16".to_string(),
+            proof_value: format!("{:064x}", i + 1),
+            vk_hash: "bench".to_string(),
+            public_inputs: ProofPublicInputs {
+                text_hash: [0; 32],
+                vector_hash: [0; 32],
+                model_id,
+            },
+            created_at: Utc::now(),
+        },
+        consensus: None,
+        hardening: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        signature: None,
+        reef_zone: None,
+    }
+}
+
+/// Fastest wall time of `f` over `runs` runs.
+fn time<T>(runs: u32, mut f: impl FnMut() -> T) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn pool(threads: usize) -> ThreadPool {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("thread pool")
+}
+
+fn main() {
+    let serial = pool(1);
+    let parallel = pool(0);
+    println!("threads: {}", parallel.current_num_threads());
+    println!(
+        "{:>10} {:>8} {:>12} {:>12} {:>12} {:>12}",
+        "validators", "corals", "yuma 1t", "yuma Nt", "bonds 1t", "bonds Nt"
+    );
+
+    for &(validators, corals) in SIZES {
+        let stakes: Vec<u64> = (0..validators as u64).map(|i| 1_000 + i * 37).collect();
+        let weights = matrix(validators, corals, 1);
+        let prev_bonds = matrix(validators, corals, 2);
+        let yuma = || yuma_semantic_consensus(&stakes, &weights, &prev_bonds, 0.5, 0.1, 0.1);
+        assert_eq!(serial.install(yuma).bonds, parallel.install(yuma).bonds);
+        let yuma_serial = time(5, || serial.install(yuma));
+        let yuma_parallel = time(5, || parallel.install(yuma));
+
+        let weight_matrix = WeightMatrix {
+            weights: weights.clone(),
+        };
+        let consensus = yuma().consensus_weights;
+        let update = || {
+            let mut bonds = BondMatrix {
+                bonds: prev_bonds.clone(),
+            };
+            bonds.update_ema(&weight_matrix, 0.1, 0.1, &consensus);
+            bonds
+        };
+        let bonds_serial = time(5, || serial.install(update));
+        let bonds_parallel = time(5, || parallel.install(update));
+
+        println!(
+            "{:>10} {:>8} {:>12?} {:>12?} {:>12?} {:>12?}",
+            validators, corals, yuma_serial, yuma_parallel, bonds_serial, bonds_parallel
+        );
+    }
+
+    let polyps: Vec<Polyp> = (0..POLYPS).map(polyp).collect();
+    let score_serial = time(5, || serial.install(|| score_polyps(&polyps)));
+    let score_parallel = time(5, || parallel.install(|| score_polyps(&polyps)));
+    println!(
+        "scoring {} polyps: {:?} on 1 thread, {:?} on {}",
+        POLYPS,
+        score_serial,
+        score_parallel,
+        parallel.current_num_threads()
+    );
+}
@@ -0,0 +1,261 @@
+// crates/chitin-consensus/src/assignment.rs
+//
+// Per-epoch assignment of Polyps to the validators that score them.
+//
+// Each epoch has a seed every node derives alike: SHA-256 over the epoch
+// and the latest `HardeningCheckpoint` up to it (its epoch, Merkle root,
+// and randomness beacon), which peers agree on through checkpoint sync.
+// An epoch's checkpoint is written at its start, with the beacon revealed
+// in the epoch before (see `crate::beacon`). Each Polyp goes to the
+// `validators_per_polyp` validators (active Tide and Hybrid nodes in the
+// metagraph) whose hash of (seed, Polyp ID, hotkey) ranks lowest: a
+// rendezvous hash, so every Polyp gets the same number of scorers, each
+// validator gets an even share, and no validator can pick which Polyps it
+// scores or predict its share before the previous epoch's beacon is
+// revealed.
+//
+// Validators score only their assigned Polyps and list them in their score
+// submissions; nodes refuse submissions listing Polyps assigned elsewhere.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_core::identity::NodeType;
+use chitin_core::{crypto, ChitinError, ReefMetagraph};
+use chitin_store::RocksStore;
+
+use crate::hardening::HardeningCheckpoint;
+
+/// Domain separator for epoch seeds.
+const SEED_DOMAIN: &[u8] = b"chitin-score-assignment-v1";
+
+/// Scoring task assignment (`[task_assignment]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssignmentConfig {
+    /// Assign Polyps to validators and enforce it on score submissions.
+    pub enabled: bool,
+    /// Validators scoring each Polyp. With this many validators or fewer,
+    /// every validator scores every Polyp.
+    pub validators_per_polyp: usize,
+}
+
+impl Default for AssignmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            validators_per_polyp: 3,
+        }
+    }
+}
+
+impl AssignmentConfig {
+    /// Check that each Polyp gets at least one validator.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        if self.validators_per_polyp == 0 {
+            return Err(ChitinError::InvalidState(
+                "task_assignment.validators_per_polyp must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The seed for `epoch`'s assignment: SHA-256 of the epoch and the latest
+/// hardening checkpoint up to it, if any.
+pub fn epoch_seed(epoch: u64, checkpoint: Option<&HardeningCheckpoint>) -> [u8; 32] {
+    let mut preimage = SEED_DOMAIN.to_vec();
+    preimage.extend_from_slice(&epoch.to_be_bytes());
+    if let Some(checkpoint) = checkpoint {
+        preimage.extend_from_slice(&checkpoint.epoch.to_be_bytes());
+        preimage.extend_from_slice(&checkpoint.merkle_root);
+        if let Some(beacon) = &checkpoint.beacon {
+            preimage.extend_from_slice(beacon);
+        }
+    }
+    crypto::hash_bytes(&preimage)
+}
+
+/// Which validators score which Polyps in one epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskAssignment {
+    epoch: u64,
+    seed: [u8; 32],
+    /// Validator hotkeys, sorted and deduplicated.
+    validators: Vec<[u8; 32]>,
+    validators_per_polyp: usize,
+}
+
+impl TaskAssignment {
+    /// An assignment over `validators` for `epoch`, seeded by `seed`.
+    pub fn new(
+        epoch: u64,
+        seed: [u8; 32],
+        mut validators: Vec<[u8; 32]>,
+        validators_per_polyp: usize,
+    ) -> Self {
+        validators.sort_unstable();
+        validators.dedup();
+        Self {
+            epoch,
+            seed,
+            validators,
+            validators_per_polyp: validators_per_polyp.max(1),
+        }
+    }
+
+    /// The assignment for `epoch` over the metagraph's active validators,
+    /// seeded from the store's hardening checkpoints. `None` if assignment
+    /// is disabled or the metagraph has no validators.
+    pub fn for_epoch(
+        config: &AssignmentConfig,
+        store: &RocksStore,
+        metagraph: Option<&ReefMetagraph>,
+        epoch: u64,
+    ) -> Result<Option<Self>, ChitinError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let validators: Vec<[u8; 32]> = metagraph
+            .map(|mg| {
+                mg.nodes
+                    .iter()
+                    .filter(|n| {
+                        n.active && matches!(n.node_type, NodeType::Tide | NodeType::Hybrid)
+                    })
+                    .map(|n| n.hotkey)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if validators.is_empty() {
+            return Ok(None);
+        }
+        let checkpoint = HardeningCheckpoint::latest_at(store, epoch)?;
+        let seed = epoch_seed(epoch, checkpoint.as_ref());
+        Ok(Some(Self::new(
+            epoch,
+            seed,
+            validators,
+            config.validators_per_polyp,
+        )))
+    }
+
+    /// Epoch the assignment is for.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Validator hotkeys, sorted.
+    pub fn validators(&self) -> &[[u8; 32]] {
+        &self.validators
+    }
+
+    /// The validators scoring `polyp_id`, best-ranked first.
+    pub fn validators_for(&self, polyp_id: &Uuid) -> Vec<[u8; 32]> {
+        let mut ranked: Vec<([u8; 32], [u8; 32])> = self
+            .validators
+            .iter()
+            .map(|hotkey| (self.rank(polyp_id, hotkey), *hotkey))
+            .collect();
+        ranked.sort_unstable();
+        ranked
+            .into_iter()
+            .take(self.validators_per_polyp)
+            .map(|(_, hotkey)| hotkey)
+            .collect()
+    }
+
+    /// Whether the validator with `hotkey` scores `polyp_id`.
+    pub fn is_assigned(&self, polyp_id: &Uuid, hotkey: &[u8; 32]) -> bool {
+        if self.validators.binary_search(hotkey).is_err() {
+            return false;
+        }
+        if self.validators.len() <= self.validators_per_polyp {
+            return true;
+        }
+        let own = self.rank(polyp_id, hotkey);
+        let better = self
+            .validators
+            .iter()
+            .filter(|other| self.rank(polyp_id, other) < own)
+            .count();
+        better < self.validators_per_polyp
+    }
+
+    /// The Polyps of `polyp_ids` assigned to `hotkey`, in order.
+    pub fn assigned<'a>(&self, hotkey: &[u8; 32], polyp_ids: &'a [Uuid]) -> Vec<&'a Uuid> {
+        polyp_ids
+            .iter()
+            .filter(|id| self.is_assigned(id, hotkey))
+            .collect()
+    }
+
+    fn rank(&self, polyp_id: &Uuid, hotkey: &[u8; 32]) -> [u8; 32] {
+        let mut preimage = Vec::with_capacity(80);
+        preimage.extend_from_slice(&self.seed);
+        preimage.extend_from_slice(polyp_id.as_bytes());
+        preimage.extend_from_slice(hotkey);
+        crypto::hash_bytes(&preimage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(n: u8) -> Vec<[u8; 32]> {
+        (0..n).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn every_polyp_gets_the_same_number_of_validators() {
+        let assignment = TaskAssignment::new(7, [9; 32], validators(10), 3);
+        let mut load = [0usize; 10];
+        for _ in 0..2000 {
+            let id = Uuid::new_v4();
+            let chosen = assignment.validators_for(&id);
+            assert_eq!(chosen.len(), 3);
+            for hotkey in &validators(10) {
+                assert_eq!(assignment.is_assigned(&id, hotkey), chosen.contains(hotkey));
+            }
+            for hotkey in chosen {
+                load[hotkey[0] as usize] += 1;
+            }
+        }
+        // 600 each on average.
+        assert!(load.iter().all(|&n| (450..750).contains(&n)), "{:?}", load);
+    }
+
+    #[test]
+    fn small_validator_sets_score_everything() {
+        let assignment = TaskAssignment::new(1, [0; 32], validators(2), 3);
+        let id = Uuid::new_v4();
+        assert!(validators(2).iter().all(|v| assignment.is_assigned(&id, v)));
+        assert!(!assignment.is_assigned(&id, &[42; 32]));
+    }
+
+    #[test]
+    fn seeds_change_with_epoch_and_checkpoint() {
+        let checkpoint = HardeningCheckpoint {
+            epoch: 4,
+            merkle_root: [1; 32],
+            count: 2,
+            beacon: None,
+        };
+        let seed = epoch_seed(5, Some(&checkpoint));
+        assert_eq!(seed, epoch_seed(5, Some(&checkpoint)));
+        assert_ne!(seed, epoch_seed(6, Some(&checkpoint)));
+        assert_ne!(seed, epoch_seed(5, None));
+        let beaconed = HardeningCheckpoint {
+            beacon: Some([2; 32]),
+            ..checkpoint
+        };
+        assert_ne!(seed, epoch_seed(5, Some(&beaconed)));
+        assert!(AssignmentConfig {
+            validators_per_polyp: 0,
+            ..AssignmentConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
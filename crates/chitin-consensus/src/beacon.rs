@@ -0,0 +1,283 @@
+// crates/chitin-consensus/src/beacon.rs
+//
+// Commit-reveal randomness beacon, one value per epoch.
+//
+// During an epoch's Scoring phase each validator commits to a secret by
+// publishing SHA-256(domain, epoch, hotkey, secret); during Committing it
+// reveals the secret, which must match its commitment. At the epoch
+// boundary the beacon is SHA-256 over the epoch and every revealed
+// (hotkey, secret) pair in hotkey order, and is stored in the next
+// epoch's `HardeningCheckpoint`. `validation/beacon` only takes contributions
+// from active validators of the current metagraph. No validator learns the
+// others' secrets before committing to its own, but a commitment does not
+// oblige a validator to reveal: the last to reveal can compute the value with
+// and without its secret and withhold it if that suits it better, one bit of
+// bias per withholding validator. Withholding only drops that validator's
+// contribution.
+//
+// Contributions are persisted per validator under
+// `beacon:{epoch:020}:{hotkey hex}`, so concurrent submissions do not
+// overwrite each other. Validators derive their secret from their signing
+// key and the epoch, so a restart between commit and reveal loses nothing.
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::{crypto, ChitinError};
+use chitin_store::{Reclaimed, RocksStore};
+
+use crate::hardening::HardeningCheckpoint;
+
+/// Store key prefix for contributions.
+pub const BEACON_PREFIX: &str = "beacon:";
+
+/// Domain separator for commitments.
+const COMMITMENT_DOMAIN: &[u8] = b"chitin-beacon-commitment-v1";
+
+/// Domain separator for derived secrets.
+const SECRET_DOMAIN: &[u8] = b"chitin-beacon-secret-v1";
+
+/// Domain separator for beacon values.
+const VALUE_DOMAIN: &[u8] = b"chitin-beacon-value-v1";
+
+/// One validator's contribution to an epoch's beacon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contribution {
+    /// The validator's hotkey.
+    pub validator: [u8; 32],
+    /// Commitment to `secret`, published during Scoring.
+    pub commitment: [u8; 32],
+    /// The secret, once revealed during Committing.
+    #[serde(default)]
+    pub secret: Option<[u8; 32]>,
+}
+
+/// The commitment to `secret` by `validator` for `epoch`.
+pub fn commitment(epoch: u64, validator: &[u8; 32], secret: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = COMMITMENT_DOMAIN.to_vec();
+    preimage.extend_from_slice(&epoch.to_be_bytes());
+    preimage.extend_from_slice(validator);
+    preimage.extend_from_slice(secret);
+    crypto::hash_bytes(&preimage)
+}
+
+/// The secret a validator holding `signing_key` contributes for `epoch`.
+pub fn derive_secret(signing_key: &[u8; 32], epoch: u64) -> [u8; 32] {
+    let mut preimage = SECRET_DOMAIN.to_vec();
+    preimage.extend_from_slice(signing_key);
+    preimage.extend_from_slice(&epoch.to_be_bytes());
+    crypto::hash_bytes(&preimage)
+}
+
+/// Record `validator`'s commitment for `epoch`.
+///
+/// Repeating the same commitment is a no-op; a different one fails, so a
+/// validator cannot change its secret after seeing others' reveals.
+pub fn commit(
+    store: &RocksStore,
+    epoch: u64,
+    validator: &[u8; 32],
+    commitment: [u8; 32],
+) -> Result<(), ChitinError> {
+    match load(store, epoch, validator)? {
+        Some(existing) if existing.commitment == commitment => Ok(()),
+        Some(_) => Err(ChitinError::InvalidState(format!(
+            "Validator already committed to a different beacon secret for epoch {}",
+            epoch
+        ))),
+        None => save(
+            store,
+            epoch,
+            &Contribution {
+                validator: *validator,
+                commitment,
+                secret: None,
+            },
+        ),
+    }
+}
+
+/// Record `validator`'s revealed secret for `epoch`.
+///
+/// Fails unless the validator committed for the epoch and `secret` matches
+/// the commitment.
+pub fn reveal(
+    store: &RocksStore,
+    epoch: u64,
+    validator: &[u8; 32],
+    secret: [u8; 32],
+) -> Result<(), ChitinError> {
+    let mut contribution = load(store, epoch, validator)?.ok_or_else(|| {
+        ChitinError::NotFound(format!("No beacon commitment for epoch {}", epoch))
+    })?;
+    if commitment(epoch, validator, &secret) != contribution.commitment {
+        return Err(ChitinError::InvalidState(
+            "Revealed secret does not match the commitment".to_string(),
+        ));
+    }
+    contribution.secret = Some(secret);
+    save(store, epoch, &contribution)
+}
+
+/// Every contribution for `epoch`, in hotkey order.
+pub fn contributions(store: &RocksStore, epoch: u64) -> Result<Vec<Contribution>, ChitinError> {
+    store
+        .scan_prefix(epoch_prefix(epoch).as_bytes())?
+        .into_iter()
+        .map(|(_, bytes)| Ok(serde_json::from_slice(&bytes)?))
+        .collect()
+}
+
+/// The beacon value over `contributions` for `epoch`: `None` if no secret
+/// was revealed. Unrevealed commitments are left out.
+pub fn beacon_value(epoch: u64, contributions: &[Contribution]) -> Option<[u8; 32]> {
+    let mut revealed: Vec<(&[u8; 32], &[u8; 32])> = contributions
+        .iter()
+        .filter_map(|c| c.secret.as_ref().map(|secret| (&c.validator, secret)))
+        .collect();
+    if revealed.is_empty() {
+        return None;
+    }
+    revealed.sort_unstable();
+    let mut preimage = VALUE_DOMAIN.to_vec();
+    preimage.extend_from_slice(&epoch.to_be_bytes());
+    for (validator, secret) in revealed {
+        preimage.extend_from_slice(validator);
+        preimage.extend_from_slice(secret);
+    }
+    Some(crypto::hash_bytes(&preimage))
+}
+
+/// Compute the beacon for `epoch` and store it in the checkpoint of
+/// `checkpoint_epoch` (the epoch it seeds), creating an empty checkpoint
+/// if none was hardened. Returns the value, `None` if nothing was revealed.
+pub fn finalize(
+    store: &RocksStore,
+    epoch: u64,
+    checkpoint_epoch: u64,
+) -> Result<Option<[u8; 32]>, ChitinError> {
+    let Some(value) = beacon_value(epoch, &contributions(store, epoch)?) else {
+        return Ok(None);
+    };
+    let mut checkpoint =
+        HardeningCheckpoint::load(store, checkpoint_epoch)?.unwrap_or(HardeningCheckpoint {
+            epoch: checkpoint_epoch,
+            merkle_root: [0u8; 32],
+            count: 0,
+            beacon: None,
+        });
+    checkpoint.beacon = Some(value);
+    checkpoint.save(store)?;
+    Ok(Some(value))
+}
+
+/// Delete contributions for epochs before `epoch`.
+pub fn prune_before(store: &RocksStore, epoch: u64) -> Result<Reclaimed, ChitinError> {
+    store.prune_prefix(BEACON_PREFIX.as_bytes(), |suffix, _| {
+        std::str::from_utf8(suffix)
+            .ok()
+            .and_then(|key| key.split(':').next())
+            .and_then(|e| e.parse::<u64>().ok())
+            .is_some_and(|e| e < epoch)
+    })
+}
+
+fn load(
+    store: &RocksStore,
+    epoch: u64,
+    validator: &[u8; 32],
+) -> Result<Option<Contribution>, ChitinError> {
+    match store.get_bytes(contribution_key(epoch, validator).as_bytes())? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+fn save(store: &RocksStore, epoch: u64, contribution: &Contribution) -> Result<(), ChitinError> {
+    store.put_bytes(
+        contribution_key(epoch, &contribution.validator).as_bytes(),
+        &serde_json::to_vec(contribution)?,
+    )
+}
+
+fn epoch_prefix(epoch: u64) -> String {
+    format!("{}{:020}:", BEACON_PREFIX, epoch)
+}
+
+fn contribution_key(epoch: u64, validator: &[u8; 32]) -> String {
+    let hotkey: String = validator.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}{}", epoch_prefix(epoch), hotkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(label: &str) -> (RocksStore, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-consensus-beacon-{}-{}",
+            label,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        (RocksStore::open(path.to_str().unwrap()).unwrap(), path)
+    }
+
+    #[test]
+    fn test_reveals_must_match_commitments() {
+        let (store, path) = open_store("reveal");
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let secret = derive_secret(&[7u8; 32], 5);
+
+        commit(&store, 5, &alice, commitment(5, &alice, &secret)).unwrap();
+        // Re-sending the same commitment is fine; changing it is not.
+        commit(&store, 5, &alice, commitment(5, &alice, &secret)).unwrap();
+        assert!(commit(&store, 5, &alice, [0u8; 32]).is_err());
+        assert!(reveal(&store, 5, &alice, [9u8; 32]).is_err());
+        assert!(reveal(&store, 5, &bob, secret).is_err());
+        reveal(&store, 5, &alice, secret).unwrap();
+
+        let stored = contributions(&store, 5).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].secret, Some(secret));
+        assert!(contributions(&store, 6).unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    #[test]
+    fn test_value_ignores_withheld_reveals_and_order() {
+        let revealed = |validator: u8, secret: Option<u8>| Contribution {
+            validator: [validator; 32],
+            commitment: [0u8; 32],
+            secret: secret.map(|s| [s; 32]),
+        };
+        let a = [
+            revealed(1, Some(10)),
+            revealed(2, Some(20)),
+            revealed(3, None),
+        ];
+        let b = [revealed(2, Some(20)), revealed(1, Some(10))];
+        assert_eq!(beacon_value(4, &a), beacon_value(4, &b));
+        assert_ne!(beacon_value(4, &a), beacon_value(5, &a));
+        assert_ne!(beacon_value(4, &a), beacon_value(4, &b[..1]));
+        assert_eq!(beacon_value(4, &[revealed(3, None)]), None);
+    }
+
+    #[test]
+    fn test_finalize_stores_the_value_in_the_checkpoint() {
+        let (store, path) = open_store("finalize");
+        let validator = [3u8; 32];
+        let secret = derive_secret(&[8u8; 32], 2);
+        assert_eq!(finalize(&store, 2, 3).unwrap(), None);
+
+        commit(&store, 2, &validator, commitment(2, &validator, &secret)).unwrap();
+        reveal(&store, 2, &validator, secret).unwrap();
+        let value = finalize(&store, 2, 3).unwrap().unwrap();
+        let checkpoint = HardeningCheckpoint::load(&store, 3).unwrap().unwrap();
+        assert_eq!(checkpoint.beacon, Some(value));
+        assert_eq!(checkpoint.count, 0);
+
+        assert_eq!(prune_before(&store, 3).unwrap().entries, 1);
+        assert!(contributions(&store, 2).unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(path);
+    }
+}
@@ -5,6 +5,7 @@
 // Bonds represent a validator's historical commitment to scoring a specific
 // Coral Node. Built up via EMA over epochs. Higher bonds = higher dividend share.
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::weights::WeightMatrix;
@@ -41,7 +42,8 @@ impl BondMatrix {
     ///
     /// # Phase 3
     /// This will implement the bond EMA update with penalty from
-    /// ARCHITECTURE.md Section 4.2, Step 5.
+    /// ARCHITECTURE.md Section 4.2, Step 5. Validator rows are updated in
+    /// parallel; each cell depends only on its own inputs.
     pub fn update_ema(
         &mut self,
         weights: &WeightMatrix,
@@ -49,12 +51,10 @@ impl BondMatrix {
         bond_penalty: f64,
         consensus_weights: &[f64],
     ) {
-        let num_validators = self.bonds.len();
-        for i in 0..num_validators {
-            let num_corals = self.bonds[i].len();
-            for j in 0..num_corals {
+        self.bonds.par_iter_mut().enumerate().for_each(|(i, row)| {
+            for (j, bond) in row.iter_mut().enumerate() {
                 let w_ij = weights.weights[i][j];
-                let b_prev = self.bonds[i][j];
+                let b_prev = *bond;
                 let consensus_j = if j < consensus_weights.len() {
                     consensus_weights[j]
                 } else {
@@ -62,9 +62,9 @@ impl BondMatrix {
                 };
                 let ema = alpha * w_ij + (1.0 - alpha) * b_prev;
                 let penalty = bond_penalty * (w_ij - consensus_j).abs();
-                self.bonds[i][j] = (ema - penalty).max(0.0);
+                *bond = (ema - penalty).max(0.0);
             }
-        }
+        });
     }
 }
 
@@ -0,0 +1,594 @@
+// crates/chitin-consensus/src/dedup.rs
+//
+// Near-duplicate detection against hardened Polyps.
+//
+// A `DuplicateDetector` fingerprints the content of every hardened Polyp it
+// is given (a MinHash signature and a SimHash over word 3-grams) and keeps
+// its vector. A submitted or scored Polyp is a near-duplicate of a hardened
+// one if their estimated Jaccard similarity reaches `text_threshold`, their
+// SimHashes differ in at most `simhash_distance` bits, or their vectors,
+// embedded under the same model, reach `vector_threshold` cosine similarity.
+//
+// Text candidates come from locality-sensitive buckets (MinHash bands and
+// SimHash blocks), so a check does not compare against every hardened
+// Polyp's text; vectors are compared by brute force within the model space.
+// A Polyp never matches itself or the Polyps it was molted from.
+//
+// Matches are flagged in the store under `duplicate:{polyp_id}` and cap the
+// Polyp's novelty score at one minus the match similarity.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_core::{ChitinError, Polyp, PolypScores};
+use chitin_store::distance::cosine_similarity;
+use chitin_store::RocksStore;
+
+/// Store key prefix for duplicate flags.
+pub const DUPLICATE_PREFIX: &str = "duplicate:";
+
+/// Hash functions in a MinHash signature.
+const MINHASH_SIZE: usize = 64;
+
+/// Rows per MinHash band; candidates share every row of at least one band.
+const BAND_ROWS: usize = 4;
+
+/// SimHash blocks. Hashes differing in fewer bits than there are blocks
+/// agree on at least one whole block.
+const SIMHASH_BLOCKS: u32 = 4;
+
+/// Words per shingle.
+const SHINGLE_WORDS: usize = 3;
+
+/// Near-duplicate thresholds (`[dedup]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// Check submissions and scored Polyps at all.
+    pub enabled: bool,
+    /// Minimum estimated Jaccard similarity of content 3-grams.
+    pub text_threshold: f64,
+    /// Maximum SimHash Hamming distance, below `SIMHASH_BLOCKS` (4).
+    pub simhash_distance: u32,
+    /// Minimum cosine similarity of vectors under the same model.
+    pub vector_threshold: f64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            text_threshold: 0.8,
+            simhash_distance: 3,
+            vector_threshold: 0.98,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Check that the thresholds are in range.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        let unit = 0.0..=1.0;
+        if !unit.contains(&self.text_threshold) || !unit.contains(&self.vector_threshold) {
+            return Err(ChitinError::InvalidState(
+                "dedup thresholds must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        if self.simhash_distance >= SIMHASH_BLOCKS {
+            return Err(ChitinError::InvalidState(format!(
+                "dedup.simhash_distance must be below {}",
+                SIMHASH_BLOCKS
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What a near-duplicate matched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    /// Content: MinHash or SimHash.
+    Text,
+    /// Vector cosine similarity.
+    Vector,
+}
+
+/// The hardened Polyp a Polyp nearly duplicates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateMatch {
+    /// The hardened Polyp matched.
+    pub matched_id: Uuid,
+    pub kind: DuplicateKind,
+    /// Similarity in [0.0, 1.0]: estimated Jaccard, SimHash bit agreement,
+    /// or cosine, whichever matched highest.
+    pub similarity: f64,
+}
+
+/// Content fingerprints of one text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// Minimum of each seeded shingle hash (empty if the text has no words).
+    pub minhash: Vec<u64>,
+    pub simhash: u64,
+}
+
+impl Fingerprint {
+    /// Fingerprint `text`'s lowercase word 3-grams.
+    pub fn of(text: &str) -> Self {
+        let shingles = shingle_hashes(text);
+        if shingles.is_empty() {
+            return Self {
+                minhash: Vec::new(),
+                simhash: 0,
+            };
+        }
+        let minhash = (0..MINHASH_SIZE as u64)
+            .map(|seed| {
+                let seed = mix(seed.wrapping_add(1));
+                shingles
+                    .iter()
+                    .map(|&h| mix(h ^ seed))
+                    .min()
+                    .unwrap_or(u64::MAX)
+            })
+            .collect();
+        let mut votes = [0i64; 64];
+        for &h in &shingles {
+            for (bit, vote) in votes.iter_mut().enumerate() {
+                *vote += if h >> bit & 1 == 1 { 1 } else { -1 };
+            }
+        }
+        let simhash = votes
+            .iter()
+            .enumerate()
+            .filter(|(_, &vote)| vote > 0)
+            .fold(0u64, |hash, (bit, _)| hash | 1 << bit);
+        Self { minhash, simhash }
+    }
+
+    /// Estimated Jaccard similarity of the two texts' shingle sets.
+    pub fn jaccard(&self, other: &Self) -> f64 {
+        if self.minhash.is_empty() || other.minhash.len() != self.minhash.len() {
+            return 0.0;
+        }
+        let same = self
+            .minhash
+            .iter()
+            .zip(&other.minhash)
+            .filter(|(a, b)| a == b)
+            .count();
+        same as f64 / self.minhash.len() as f64
+    }
+
+    /// Bits in which the two SimHashes differ.
+    pub fn simhash_distance(&self, other: &Self) -> u32 {
+        (self.simhash ^ other.simhash).count_ones()
+    }
+
+    /// Bucket keys: one per MinHash band, one per SimHash block.
+    fn buckets(&self) -> Vec<(u8, u64)> {
+        if self.minhash.is_empty() {
+            return Vec::new();
+        }
+        let bands = self
+            .minhash
+            .chunks(BAND_ROWS)
+            .enumerate()
+            .map(|(band, rows)| {
+                let key = rows.iter().fold(band as u64, |key, &row| mix(key ^ row));
+                (0, key)
+            });
+        let width = 64 / SIMHASH_BLOCKS;
+        let blocks = (0..SIMHASH_BLOCKS).map(|block| {
+            let bits = self.simhash >> (block * width) & ((1 << width) - 1);
+            (1 + block as u8, bits)
+        });
+        bands.chain(blocks).collect()
+    }
+}
+
+/// One hardened Polyp as the detector sees it.
+#[derive(Debug, Clone)]
+struct Entry {
+    fingerprint: Fingerprint,
+    model: String,
+    vector: Vec<f32>,
+}
+
+/// Fingerprints and vectors of hardened Polyps, checked for near-duplicates.
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateDetector {
+    config: DedupConfig,
+    entries: HashMap<Uuid, Entry>,
+    buckets: HashMap<(u8, u64), Vec<Uuid>>,
+}
+
+impl DuplicateDetector {
+    /// An empty detector with `config`'s thresholds.
+    pub fn new(config: DedupConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// The thresholds in effect.
+    pub fn config(&self) -> &DedupConfig {
+        &self.config
+    }
+
+    /// Number of hardened Polyps tracked.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no hardened Polyp is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether `id` is tracked.
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    /// Track `polyp` (replacing any earlier entry for its ID).
+    pub fn insert(&mut self, polyp: &Polyp) {
+        self.remove(&polyp.id);
+        let fingerprint = Fingerprint::of(&polyp.subject.payload.content);
+        for bucket in fingerprint.buckets() {
+            self.buckets.entry(bucket).or_default().push(polyp.id);
+        }
+        self.entries.insert(
+            polyp.id,
+            Entry {
+                fingerprint,
+                model: polyp.subject.vector.model_id.key(),
+                vector: polyp.subject.vector.values.clone(),
+            },
+        );
+    }
+
+    /// Stop tracking `id`.
+    pub fn remove(&mut self, id: &Uuid) {
+        if let Some(entry) = self.entries.remove(id) {
+            for bucket in entry.fingerprint.buckets() {
+                if let Some(ids) = self.buckets.get_mut(&bucket) {
+                    ids.retain(|other| other != id);
+                    if ids.is_empty() {
+                        self.buckets.remove(&bucket);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Track exactly `hardened`: add the Polyps not yet tracked and drop
+    /// those no longer in it (molted or pruned). Returns how many were added.
+    pub fn sync(&mut self, hardened: &[Polyp]) -> usize {
+        let current: HashSet<Uuid> = hardened.iter().map(|p| p.id).collect();
+        let stale: Vec<Uuid> = self
+            .entries
+            .keys()
+            .filter(|id| !current.contains(id))
+            .copied()
+            .collect();
+        for id in &stale {
+            self.remove(id);
+        }
+        let mut added = 0;
+        for polyp in hardened {
+            if !self.entries.contains_key(&polyp.id) {
+                self.insert(polyp);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// The closest hardened Polyp `polyp` nearly duplicates, if any.
+    pub fn check(&self, polyp: &Polyp) -> Option<DuplicateMatch> {
+        let own: HashSet<Uuid> = std::iter::once(polyp.id)
+            .chain(
+                polyp
+                    .subject
+                    .provenance
+                    .molted_from
+                    .iter()
+                    .map(|a| a.polyp_id),
+            )
+            .collect();
+        let mut best: Option<DuplicateMatch> = None;
+        let mut consider = |candidate: DuplicateMatch| {
+            if best
+                .as_ref()
+                .is_none_or(|b| candidate.similarity > b.similarity)
+            {
+                best = Some(candidate);
+            }
+        };
+
+        let fingerprint = Fingerprint::of(&polyp.subject.payload.content);
+        let mut candidates = HashSet::new();
+        for bucket in fingerprint.buckets() {
+            for id in self.buckets.get(&bucket).into_iter().flatten() {
+                if !own.contains(id) && candidates.insert(*id) {
+                    let other = &self.entries[id].fingerprint;
+                    let jaccard = fingerprint.jaccard(other);
+                    let distance = fingerprint.simhash_distance(other);
+                    let mut similarity = None;
+                    if distance <= self.config.simhash_distance {
+                        similarity = Some(1.0 - distance as f64 / 64.0);
+                    }
+                    if jaccard >= self.config.text_threshold {
+                        similarity = Some(similarity.map_or(jaccard, |s: f64| s.max(jaccard)));
+                    }
+                    if let Some(similarity) = similarity {
+                        consider(DuplicateMatch {
+                            matched_id: *id,
+                            kind: DuplicateKind::Text,
+                            similarity,
+                        });
+                    }
+                }
+            }
+        }
+
+        let model = polyp.subject.vector.model_id.key();
+        let vector = &polyp.subject.vector.values;
+        for (id, entry) in &self.entries {
+            if own.contains(id) || entry.model != model || entry.vector.len() != vector.len() {
+                continue;
+            }
+            let cosine = cosine_similarity(vector, &entry.vector) as f64;
+            if cosine >= self.config.vector_threshold {
+                consider(DuplicateMatch {
+                    matched_id: *id,
+                    kind: DuplicateKind::Vector,
+                    similarity: cosine.min(1.0),
+                });
+            }
+        }
+        best
+    }
+}
+
+/// Cap `scores.novelty` at the share of `duplicate` that is new.
+pub fn penalize_novelty(scores: &mut PolypScores, duplicate: &DuplicateMatch) {
+    scores.novelty = scores.novelty.min((1.0 - duplicate.similarity).max(0.0));
+}
+
+/// Record that `polyp_id` nearly duplicates `duplicate.matched_id`.
+pub fn save_flag(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+    duplicate: &DuplicateMatch,
+) -> Result<(), ChitinError> {
+    let json =
+        serde_json::to_vec(duplicate).map_err(|e| ChitinError::Serialization(e.to_string()))?;
+    store.put_bytes(flag_key(polyp_id).as_bytes(), &json)
+}
+
+/// The duplicate flag recorded for `polyp_id`, if any.
+pub fn load_flag(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+) -> Result<Option<DuplicateMatch>, ChitinError> {
+    match store.get_bytes(flag_key(polyp_id).as_bytes())? {
+        None => Ok(None),
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| ChitinError::Serialization(e.to_string())),
+    }
+}
+
+fn flag_key(polyp_id: &Uuid) -> String {
+    format!("{}{}", DUPLICATE_PREFIX, polyp_id)
+}
+
+/// FNV-1a hashes of the lowercase word 3-grams of `text` (the whole text
+/// as one shingle if it has fewer words), deduplicated.
+fn shingle_hashes(text: &str) -> Vec<u64> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+    let mut hashes: Vec<u64> = words
+        .windows(SHINGLE_WORDS.min(words.len()))
+        .map(|shingle| fnv1a(&shingle.join(" ")))
+        .collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The SplitMix64 finalizer, to derive independent hash functions.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::{
+        hash_embedding, EmbeddingModelId, MoltAncestor, NodeIdentity, NodeType, Payload,
+        PolypState, PolypSubject, ProcessingPipeline, ProofPublicInputs, Provenance,
+        SourceAttribution, VectorEmbedding, ZkProof,
+    };
+    use chrono::Utc;
+
+    fn polyp(content: &str) -> Polyp {
+        let now = Utc::now();
+        let model_id = EmbeddingModelId::from_key("test/hash", 32);
+        Polyp {
+            id: Uuid::now_v7(),
+            state: PolypState::Hardened,
+            subject: PolypSubject {
+                payload: Payload {
+                    content: content.to_string(),
+                    content_type: "text/plain".to_string(),
+                    language: None,
+                },
+                vector: VectorEmbedding {
+                    values: hash_embedding(content, 32),
+                    model_id: model_id.clone(),
+                    quantization: "float32".to_string(),
+                    normalization: "l2".to_string(),
+                },
+                provenance: Provenance {
+                    creator: NodeIdentity {
+                        coldkey: [0u8; 32],
+                        hotkey: [0u8; 32],
+                        did: "did:chitin:local".to_string(),
+                        node_type: NodeType::Coral,
+                    },
+                    source: SourceAttribution {
+                        source_cid: None,
+                        source_url: None,
+                        title: None,
+                        license: None,
+                        accessed_at: now,
+                    },
+                    pipeline: ProcessingPipeline {
+                        steps: vec![],
+                        duration_ms: 0,
+                    },
+                    molted_from: vec![],
+                },
+            },
+            proof: ZkProof {
+                proof_type: "placeholder".to_string(),
+                proof_value: "0x00".to_string(),
+                vk_hash: "0x00".to_string(),
+                public_inputs: ProofPublicInputs {
+                    text_hash: [0u8; 32],
+                    vector_hash: [0u8; 32],
+                    model_id,
+                },
+                created_at: now,
+            },
+            consensus: None,
+            hardening: None,
+            created_at: now,
+            updated_at: now,
+            signature: None,
+            reef_zone: None,
+        }
+    }
+
+    const ORIGINAL: &str = "Coral reefs are built over centuries by colonies of tiny polyps \
+        that secrete calcium carbonate skeletons, forming the largest living structures \
+        on Earth and sheltering a quarter of all marine species.";
+
+    #[test]
+    fn fingerprints_are_deterministic_and_close_for_near_copies() {
+        let a = Fingerprint::of(ORIGINAL);
+        assert_eq!(a, Fingerprint::of(&ORIGINAL.to_uppercase()));
+        let edited = ORIGINAL.replace("a quarter", "one quarter");
+        let b = Fingerprint::of(&edited);
+        assert!(a.jaccard(&b) > 0.6, "jaccard {}", a.jaccard(&b));
+        let unrelated =
+            Fingerprint::of("Volcanic islands form where magma rises through the crust.");
+        assert!(a.jaccard(&unrelated) < 0.2);
+        assert!(Fingerprint::of("").minhash.is_empty());
+    }
+
+    #[test]
+    fn detects_copies_of_hardened_polyps_but_not_new_content() {
+        let original = polyp(ORIGINAL);
+        let mut detector = DuplicateDetector::new(DedupConfig::default());
+        assert_eq!(detector.sync(std::slice::from_ref(&original)), 1);
+
+        let copy = polyp(&format!("{}!", ORIGINAL));
+        let found = detector.check(&copy).unwrap();
+        assert_eq!(found.matched_id, original.id);
+        assert!(found.similarity > 0.95);
+
+        let fresh = polyp("Mangrove roots trap sediment and nurse juvenile reef fish.");
+        assert_eq!(detector.check(&fresh), None);
+
+        // A Polyp matches neither itself nor what it was molted from.
+        assert_eq!(detector.check(&original), None);
+        let mut successor = polyp(ORIGINAL);
+        successor.subject.provenance.molted_from.push(MoltAncestor {
+            polyp_id: original.id,
+            model_id: "test/hash".to_string(),
+            cid: None,
+            epoch: 1,
+        });
+        assert_eq!(detector.check(&successor), None);
+    }
+
+    #[test]
+    fn vectors_match_within_the_same_model_only() {
+        let original = polyp(ORIGINAL);
+        let mut detector = DuplicateDetector::new(DedupConfig::default());
+        detector.insert(&original);
+
+        // Different words, same vector.
+        let mut paraphrase = polyp("Entirely different wording with no shared trigrams at all.");
+        paraphrase.subject.vector.values = original.subject.vector.values.clone();
+        let found = detector.check(&paraphrase).unwrap();
+        assert_eq!(found.kind, DuplicateKind::Vector);
+
+        paraphrase.subject.vector.model_id = EmbeddingModelId::from_key("other/model", 32);
+        assert_eq!(detector.check(&paraphrase), None);
+    }
+
+    #[test]
+    fn sync_drops_polyps_no_longer_hardened() {
+        let (a, b) = (
+            polyp(ORIGINAL),
+            polyp("Seagrass meadows store carbon in sediment."),
+        );
+        let mut detector = DuplicateDetector::default();
+        detector.sync(&[a.clone(), b.clone()]);
+        assert_eq!(detector.len(), 2);
+        assert_eq!(detector.sync(std::slice::from_ref(&b)), 0);
+        assert!(!detector.contains(&a.id));
+        assert!(detector.buckets.values().flatten().all(|id| *id == b.id));
+    }
+
+    #[test]
+    fn duplicates_cap_novelty_and_flags_round_trip() {
+        let mut scores = PolypScores {
+            zk_validity: 1.0,
+            semantic_quality: 1.0,
+            novelty: 0.9,
+            source_credibility: 1.0,
+            embedding_quality: 1.0,
+            provenance_integrity: 1.0,
+        };
+        let duplicate = DuplicateMatch {
+            matched_id: Uuid::now_v7(),
+            kind: DuplicateKind::Text,
+            similarity: 0.96,
+        };
+        penalize_novelty(&mut scores, &duplicate);
+        assert!((scores.novelty - 0.04).abs() < 1e-9);
+
+        let dir = std::env::temp_dir().join(format!("chitin_dedup_{}", Uuid::now_v7()));
+        let store = RocksStore::open(dir.to_str().unwrap()).unwrap();
+        let id = Uuid::now_v7();
+        assert_eq!(load_flag(&store, &id).unwrap(), None);
+        save_flag(&store, &id, &duplicate).unwrap();
+        assert_eq!(load_flag(&store, &id).unwrap(), Some(duplicate));
+        drop(store);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -30,6 +30,9 @@ pub struct EpochManager {
     phase: EpochPhase,
     /// Number of blocks per epoch (default 360).
     blocks_per_epoch: u64,
+    /// The last block height advanced to.
+    #[serde(default)]
+    current_block: u64,
 }
 
 impl EpochManager {
@@ -42,6 +45,7 @@ impl EpochManager {
             current_epoch: 0,
             phase: EpochPhase::Open,
             blocks_per_epoch,
+            current_block: 0,
         }
     }
 
@@ -50,6 +54,16 @@ impl EpochManager {
         self.current_epoch
     }
 
+    /// Get the last block height advanced to.
+    pub fn current_block(&self) -> u64 {
+        self.current_block
+    }
+
+    /// Get the number of blocks per epoch.
+    pub fn blocks_per_epoch(&self) -> u64 {
+        self.blocks_per_epoch
+    }
+
     /// Advance the epoch state based on the current block height.
     ///
     /// Computes the epoch number and phase from the absolute block height.
@@ -63,6 +77,7 @@ impl EpochManager {
         let block_in_epoch = block % self.blocks_per_epoch;
 
         self.current_epoch = new_epoch;
+        self.current_block = block;
 
         // Determine phase based on position within epoch
         let fraction = block_in_epoch as f64 / self.blocks_per_epoch as f64;
@@ -79,4 +94,36 @@ impl EpochManager {
     pub fn phase(&self) -> &EpochPhase {
         &self.phase
     }
+    /// Blocks left until the current phase ends (at least 1).
+    pub fn blocks_remaining_in_phase(&self) -> u64 {
+        let end = match self.phase {
+            EpochPhase::Open => 0.50,
+            EpochPhase::Scoring => 0.75,
+            EpochPhase::Committing | EpochPhase::Closed => 1.0,
+        };
+        let end_block = (self.blocks_per_epoch as f64 * end).ceil() as u64;
+        end_block
+            .saturating_sub(self.current_block % self.blocks_per_epoch)
+            .max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_remaining_in_phase() {
+        let mut em = EpochManager::new(10);
+        em.advance_block(0);
+        assert_eq!(em.blocks_remaining_in_phase(), 5);
+        em.advance_block(4);
+        assert_eq!(em.blocks_remaining_in_phase(), 1);
+        em.advance_block(5);
+        assert_eq!(*em.phase(), EpochPhase::Scoring);
+        assert_eq!(em.blocks_remaining_in_phase(), 3);
+        em.advance_block(19);
+        assert_eq!(*em.phase(), EpochPhase::Committing);
+        assert_eq!(em.blocks_remaining_in_phase(), 1);
+    }
 }
@@ -0,0 +1,480 @@
+// crates/chitin-consensus/src/genesis.rs
+//
+// Genesis files: the initial state of a network.
+//
+// A genesis file (`genesis.json`) fixes what every node starts from: the
+// network ID and launch time, the initial nodes with their keys and stakes
+// (registered in listed order, so a fresh registry gives them UIDs 0, 1, ...),
+// the liquid account balances, the
+// trust seeds for the reputation bootstrap, the economics parameters, and the
+// hash of the embedding model registry nodes must run. Its hash identifies
+// the network's starting point; operators pin it so a node refuses to start
+// from a different file. Nodes build their first metagraph, stake table,
+// ledger, and genesis trust from it instead of starting empty.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use chitin_core::crypto::hash_bytes;
+use chitin_core::identity::{NodeIdentity, NodeType};
+use chitin_core::registry::IdentityRegistry;
+use chitin_core::{ChitinError, NodeInfo, ReefMetagraph};
+use chitin_drift::versioning::VersionRegistry;
+use chitin_economics::staking::DELEGATION_MINIMUM;
+use chitin_economics::{EconomicsParams, Ledger, StakeEntry, StakeManager, MAX_SUPPLY_RAO};
+use chitin_reputation::genesis::{GenesisTrust, GenesisValidator};
+
+/// A node registered at genesis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisNode {
+    /// Hotkey (hex-encoded ed25519 public key).
+    pub hotkey: String,
+    /// Coldkey (hex-encoded ed25519 public key); the node's DID derives from it.
+    pub coldkey: String,
+    /// Node type.
+    pub node_type: NodeType,
+    /// Initial self-stake in rao.
+    pub stake_rao: u64,
+    /// Network address, if known at genesis.
+    #[serde(default)]
+    pub axon_addr: String,
+}
+
+/// A liquid balance allocated at genesis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisAccount {
+    /// Coldkey (hex-encoded ed25519 public key).
+    pub coldkey: String,
+    /// Initial balance in rao.
+    pub balance_rao: u64,
+}
+
+/// The contents of a genesis file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genesis {
+    /// Network ID exchanged with peers.
+    pub network_id: String,
+    /// When the network launched.
+    pub genesis_time: DateTime<Utc>,
+    /// Initial nodes; a node's UID is its index.
+    pub validators: Vec<GenesisNode>,
+    /// Liquid balances; omitted from the file (and its hash) when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<GenesisAccount>,
+    /// Validators seeded as mutually trusting.
+    #[serde(default)]
+    pub trust_seeds: Vec<GenesisValidator>,
+    /// Emission and staking parameters.
+    #[serde(default)]
+    pub economics: EconomicsParams,
+    /// Hash of the model version registry nodes must run (see
+    /// `model_registry_hash`).
+    pub model_registry_hash: String,
+}
+
+impl Genesis {
+    /// Read a genesis file.
+    pub fn load(path: &str) -> Result<Self, ChitinError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ChitinError::Storage(format!("Failed to read genesis file '{}': {}", path, e))
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write the genesis file as pretty-printed JSON.
+    pub fn save(&self, path: &str) -> Result<(), ChitinError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n").map_err(|e| {
+            ChitinError::Storage(format!("Failed to write genesis file '{}': {}", path, e))
+        })
+    }
+
+    /// Hex SHA-256 of the genesis contents, independent of file formatting.
+    pub fn hash(&self) -> Result<String, ChitinError> {
+        Ok(encode_hex(&hash_bytes(&serde_json::to_vec(self)?)))
+    }
+
+    /// Check keys, stakes, balances, trust seeds, economics, and the
+    /// registry hash.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        if self.network_id.trim().is_empty() {
+            return Err(invalid("network_id is empty".to_string()));
+        }
+        if self.validators.is_empty() {
+            return Err(invalid("no validators".to_string()));
+        }
+        if self.validators.len() > u16::MAX as usize {
+            return Err(invalid(format!(
+                "{} validators exceed the UID space",
+                self.validators.len()
+            )));
+        }
+        self.economics.validate()?;
+
+        let mut hotkeys = HashSet::new();
+        for (uid, node) in self.validators.iter().enumerate() {
+            let hotkey = decode_key(&node.hotkey, uid, "hotkey")?;
+            decode_key(&node.coldkey, uid, "coldkey")?;
+            if !hotkeys.insert(hotkey) {
+                return Err(invalid(format!(
+                    "validator {} repeats hotkey {}",
+                    uid, node.hotkey
+                )));
+            }
+            // Every stake entry must also clear the delegation minimum.
+            let mut minimum = self.economics.minimum_stake(&node.node_type);
+            if node.stake_rao > 0 {
+                minimum = minimum.max(DELEGATION_MINIMUM);
+            }
+            if node.stake_rao < minimum {
+                return Err(invalid(format!(
+                    "validator {} stakes {} rao, below the {:?} minimum of {} rao",
+                    uid, node.stake_rao, node.node_type, minimum
+                )));
+            }
+        }
+
+        let mut coldkeys = HashSet::new();
+        for (index, account) in self.accounts.iter().enumerate() {
+            let coldkey = decode_account(&account.coldkey, index)?;
+            if !coldkeys.insert(coldkey) {
+                return Err(invalid(format!(
+                    "account {} repeats coldkey {}",
+                    index, account.coldkey
+                )));
+            }
+        }
+        let supply = self
+            .validators
+            .iter()
+            .map(|n| n.stake_rao)
+            .chain(self.accounts.iter().map(|a| a.balance_rao))
+            .try_fold(0u64, u64::checked_add);
+        if supply.is_none_or(|supply| supply > MAX_SUPPLY_RAO) {
+            return Err(invalid(
+                "stakes and balances exceed the maximum supply".to_string(),
+            ));
+        }
+
+        self.trust().validate()?;
+        if decode_hex(&self.model_registry_hash).is_none_or(|h| h.len() != 32) {
+            return Err(invalid(format!(
+                "model_registry_hash {:?} is not a hex SHA-256",
+                self.model_registry_hash
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that `registry` is the model registry named by the genesis.
+    pub fn verify_model_registry(&self, registry: &VersionRegistry) -> Result<(), ChitinError> {
+        let actual = model_registry_hash(registry)?;
+        if actual != self.model_registry_hash {
+            return Err(invalid(format!(
+                "model registry hash {} does not match genesis {}",
+                actual, self.model_registry_hash
+            )));
+        }
+        Ok(())
+    }
+
+    /// Register every genesis node in `registry` at epoch 0, in listed order.
+    pub fn register(&self, registry: &mut IdentityRegistry) -> Result<(), ChitinError> {
+        for (index, node) in self.validators.iter().enumerate() {
+            registry.register(
+                decode_key(&node.coldkey, index, "coldkey")?,
+                decode_key(&node.hotkey, index, "hotkey")?,
+                node.node_type.clone(),
+                0,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The genesis metagraph (epoch 0) with every node active, under the
+    /// UIDs `registry` holds for them.
+    pub fn metagraph(
+        &self,
+        blocks_per_epoch: u64,
+        registry: &IdentityRegistry,
+    ) -> Result<ReefMetagraph, ChitinError> {
+        let mut nodes = Vec::with_capacity(self.validators.len());
+        for (index, node) in self.validators.iter().enumerate() {
+            let coldkey = decode_key(&node.coldkey, index, "coldkey")?;
+            nodes.push(NodeInfo {
+                uid: registered_uid(registry, &coldkey, index)?,
+                hotkey: decode_key(&node.hotkey, index, "hotkey")?,
+                coldkey,
+                node_type: node.node_type.clone(),
+                stake: node.stake_rao,
+                trust: 0.0,
+                consensus: 0.0,
+                incentive: 0.0,
+                emission: 0,
+                polyp_count: 0,
+                last_active: 0,
+                axon_addr: node.axon_addr.clone(),
+                active: true,
+            });
+        }
+        Ok(ReefMetagraph {
+            epoch: 0,
+            block: 0,
+            total_stake: nodes.iter().map(|n| n.stake).sum(),
+            nodes,
+            total_hardened_polyps: 0,
+            emission_rate: self.economics.epoch_emission(0, blocks_per_epoch),
+            weights: Default::default(),
+            bonds: Default::default(),
+            model_versions: Vec::new(),
+        })
+    }
+
+    /// The genesis stake table: each node's self-stake under its coldkey,
+    /// staked to the UID `registry` holds for it.
+    pub fn stake_manager(&self, registry: &IdentityRegistry) -> Result<StakeManager, ChitinError> {
+        let mut stakes = StakeManager::new();
+        for (index, node) in self.validators.iter().enumerate() {
+            if node.stake_rao == 0 {
+                continue;
+            }
+            let coldkey = decode_key(&node.coldkey, index, "coldkey")?;
+            stakes.stake(StakeEntry {
+                staker: coldkey,
+                amount: node.stake_rao,
+                node_uid: registered_uid(registry, &coldkey, index)?,
+                staked_at_block: 0,
+                unstake_requested_at: None,
+            })?;
+        }
+        Ok(stakes)
+    }
+
+    /// The genesis ledger: each account's liquid balance.
+    pub fn ledger(&self) -> Result<Ledger, ChitinError> {
+        let mut ledger = Ledger::new();
+        for (index, account) in self.accounts.iter().enumerate() {
+            ledger.credit(decode_account(&account.coldkey, index)?, account.balance_rao)?;
+        }
+        Ok(ledger)
+    }
+
+    /// The genesis trust seeds.
+    pub fn trust(&self) -> GenesisTrust {
+        GenesisTrust::new(self.trust_seeds.clone())
+    }
+}
+
+/// Hex SHA-256 of a model registry's versions, in activation order.
+pub fn model_registry_hash(registry: &VersionRegistry) -> Result<String, ChitinError> {
+    Ok(encode_hex(&hash_bytes(&serde_json::to_vec(
+        &registry.versions,
+    )?)))
+}
+
+fn registered_uid(
+    registry: &IdentityRegistry,
+    coldkey: &[u8; 32],
+    index: usize,
+) -> Result<u16, ChitinError> {
+    registry
+        .uid_of_did(&NodeIdentity::derive_did(coldkey))
+        .ok_or_else(|| invalid(format!("Genesis node {} is not registered", index)))
+}
+
+fn invalid(message: String) -> ChitinError {
+    ChitinError::InvalidState(format!("Invalid genesis: {}", message))
+}
+
+fn decode_key(hex: &str, uid: usize, name: &str) -> Result<[u8; 32], ChitinError> {
+    decode_hex(hex)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            invalid(format!(
+                "validator {} {} is not a 32-byte hex key",
+                uid, name
+            ))
+        })
+}
+
+fn decode_account(hex: &str, index: usize) -> Result<[u8; 32], ChitinError> {
+    decode_hex(hex)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| invalid(format!("account {} coldkey is not a 32-byte hex key", index)))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_drift::versioning::ModelVersion;
+    use chitin_economics::token::RAO_PER_CTN;
+
+    fn make_genesis() -> Genesis {
+        let registry = VersionRegistry::from_versions(vec![ModelVersion {
+            model_id: "bge/bge-small-en-v1.5".to_string(),
+            version: 1,
+            activated_at_epoch: 0,
+            deprecated_at_epoch: None,
+            molt_deadline_epoch: None,
+        }])
+        .unwrap();
+        Genesis {
+            network_id: "chitin-test".to_string(),
+            genesis_time: DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            validators: vec![
+                GenesisNode {
+                    hotkey: encode_hex(&[1u8; 32]),
+                    coldkey: encode_hex(&[2u8; 32]),
+                    node_type: NodeType::Tide,
+                    stake_rao: 1_000 * RAO_PER_CTN,
+                    axon_addr: String::new(),
+                },
+                GenesisNode {
+                    hotkey: encode_hex(&[3u8; 32]),
+                    coldkey: encode_hex(&[4u8; 32]),
+                    node_type: NodeType::Coral,
+                    stake_rao: 100 * RAO_PER_CTN,
+                    axon_addr: "http://10.0.0.2:50051".to_string(),
+                },
+            ],
+            accounts: vec![GenesisAccount {
+                coldkey: encode_hex(&[5u8; 32]),
+                balance_rao: 50 * RAO_PER_CTN,
+            }],
+            trust_seeds: vec![GenesisValidator {
+                did: NodeIdentity::derive_did(&[2u8; 32]),
+                weight: 1.0,
+                domains: vec![],
+            }],
+            economics: EconomicsParams::default(),
+            model_registry_hash: model_registry_hash(&registry).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_valid_genesis_builds_initial_state() {
+        let genesis = make_genesis();
+        genesis.validate().unwrap();
+
+        let mut registry = IdentityRegistry::new();
+        genesis.register(&mut registry).unwrap();
+        let metagraph = genesis.metagraph(360, &registry).unwrap();
+        assert_eq!(metagraph.epoch, 0);
+        assert_eq!(metagraph.nodes.len(), 2);
+        assert_eq!(metagraph.nodes[1].uid, 1);
+        assert_eq!(metagraph.nodes[1].coldkey, [4u8; 32]);
+        assert_eq!(metagraph.total_stake, 1_100 * RAO_PER_CTN);
+        assert_eq!(metagraph.emission_rate, 360 * RAO_PER_CTN);
+
+        let stakes = genesis.stake_manager(&registry).unwrap();
+        assert_eq!(stakes.total_stake_for_node(0), 1_000 * RAO_PER_CTN);
+        assert_eq!(stakes.total_stake_for_node(1), 100 * RAO_PER_CTN);
+
+        let ledger = genesis.ledger().unwrap();
+        assert_eq!(ledger.balance(&[5u8; 32]), 50 * RAO_PER_CTN);
+        assert_eq!(ledger.balance(&[2u8; 32]), 0);
+
+        assert_eq!(
+            registry.uid_of_did(&NodeIdentity::derive_did(&[2u8; 32])),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_genesis_nodes_keep_registered_uids() {
+        let genesis = make_genesis();
+        // A registry that already holds the second node gives it UID 0.
+        let mut registry = IdentityRegistry::new();
+        registry
+            .register([4u8; 32], [3u8; 32], NodeType::Coral, 0)
+            .unwrap();
+        genesis.register(&mut registry).unwrap();
+
+        let metagraph = genesis.metagraph(360, &registry).unwrap();
+        assert_eq!(metagraph.nodes[0].uid, 1);
+        assert_eq!(metagraph.nodes[1].uid, 0);
+        let stakes = genesis.stake_manager(&registry).unwrap();
+        assert_eq!(stakes.total_stake_for_node(1), 1_000 * RAO_PER_CTN);
+
+        assert!(genesis.metagraph(360, &IdentityRegistry::new()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_stake_below_minimum() {
+        let mut genesis = make_genesis();
+        genesis.validators[0].stake_rao = 100 * RAO_PER_CTN;
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_and_duplicate_keys() {
+        let mut genesis = make_genesis();
+        genesis.validators[1].coldkey = "abcd".to_string();
+        assert!(genesis.validate().is_err());
+
+        let mut genesis = make_genesis();
+        genesis.validators[1].hotkey = genesis.validators[0].hotkey.clone();
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_accounts() {
+        let mut genesis = make_genesis();
+        genesis.accounts.push(genesis.accounts[0].clone());
+        assert!(genesis.validate().is_err());
+
+        let mut genesis = make_genesis();
+        genesis.accounts[0].balance_rao = MAX_SUPPLY_RAO;
+        assert!(genesis.validate().is_err());
+    }
+
+    #[test]
+    fn test_hash_is_stable_and_detects_changes() {
+        let genesis = make_genesis();
+        let json = serde_json::to_string_pretty(&genesis).unwrap();
+        let reloaded: Genesis = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.hash().unwrap(), genesis.hash().unwrap());
+
+        let mut changed = genesis.clone();
+        changed.validators[1].stake_rao += 1;
+        assert_ne!(changed.hash().unwrap(), genesis.hash().unwrap());
+    }
+
+    #[test]
+    fn test_verify_model_registry() {
+        let genesis = make_genesis();
+        let mut registry = VersionRegistry::new();
+        assert!(genesis.verify_model_registry(&registry).is_err());
+        registry
+            .register(ModelVersion {
+                model_id: "bge/bge-small-en-v1.5".to_string(),
+                version: 1,
+                activated_at_epoch: 0,
+                deprecated_at_epoch: None,
+                molt_deadline_epoch: None,
+            })
+            .unwrap();
+        genesis.verify_model_registry(&registry).unwrap();
+    }
+}
@@ -1,12 +1,20 @@
 // crates/chitin-consensus/src/hardening.rs
 //
 // Hardening determination and CID anchoring for the Chitin Protocol.
+//
+// The Polyps hardened in an epoch form one Merkle tree whose leaves are
+// SHA-256(polyp_id || cid). Each Polyp's lineage carries the epoch root and
+// its inclusion proof, and the root is recorded as the epoch's
+// `HardeningCheckpoint`. A node syncing hardened Polyps checks each lineage
+// against the checkpointed root instead of trusting the peer serving it.
+
+use std::collections::HashMap;
 
 use chitin_core::consensus::HardeningLineage;
 use chitin_core::ChitinError;
-use chitin_store::IpfsClient;
+use chitin_store::{IpfsClient, Reclaimed, RocksStore};
+use serde::{Deserialize, Serialize};
 use chrono::Utc;
-use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 /// Manages the hardening process for approved Polyps.
@@ -42,10 +50,7 @@ impl HardeningManager {
         self.ipfs.pin(&cid).await?;
 
         // 2. Compute Merkle leaf: SHA-256(polyp_id_bytes || cid_bytes)
-        let mut hasher = Sha256::new();
-        hasher.update(polyp_id.as_bytes());
-        hasher.update(cid.as_bytes());
-        let merkle_leaf: [u8; 32] = hasher.finalize().into();
+        let merkle_leaf = hardening_leaf(&polyp_id, &cid);
 
         // 3. Single-leaf Merkle tree: root = leaf, proof = empty
         let merkle_root = merkle_leaf;
@@ -60,11 +65,201 @@ impl HardeningManager {
             hardened_at: Utc::now(),
         })
     }
+
+    /// Harden every Polyp approved in `epoch` under one Merkle root.
+    ///
+    /// Each `(polyp_id, cid)` is pinned; Polyps whose pin fails are left out
+    /// of the tree and reported in `failed`. Leaves are ordered by Polyp ID,
+    /// so nodes hardening the same set compute the same root.
+    pub async fn harden_epoch(
+        &self,
+        epoch: u64,
+        items: &[(Uuid, String)],
+    ) -> Result<EpochHardening, ChitinError> {
+        let mut pinned = Vec::with_capacity(items.len());
+        let mut failed = Vec::new();
+        for (polyp_id, cid) in items {
+            match self.ipfs.pin(cid).await {
+                Ok(()) => pinned.push((*polyp_id, cid.clone())),
+                Err(e) => failed.push((*polyp_id, e)),
+            }
+        }
+        pinned.sort_by_key(|(polyp_id, _)| *polyp_id);
+
+        let leaves: Vec<[u8; 32]> = pinned
+            .iter()
+            .map(|(polyp_id, cid)| hardening_leaf(polyp_id, cid))
+            .collect();
+        let (merkle_root, proofs) = merkle_tree(&leaves);
+        let hardened_at = Utc::now();
+
+        let lineages = pinned
+            .into_iter()
+            .zip(proofs)
+            .map(|((polyp_id, cid), merkle_proof)| {
+                let lineage = HardeningLineage {
+                    cid,
+                    merkle_proof,
+                    merkle_root,
+                    attestations: vec![],
+                    anchor_tx: None,
+                    hardened_at,
+                };
+                (polyp_id, lineage)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(EpochHardening {
+            checkpoint: HardeningCheckpoint {
+                epoch,
+                merkle_root,
+                count: lineages.len() as u64,
+                beacon: None,
+            },
+            lineages,
+            failed,
+        })
+    }
+}
+
+/// Result of hardening one epoch's approved Polyps.
+#[derive(Debug)]
+pub struct EpochHardening {
+    /// The epoch's Merkle root.
+    pub checkpoint: HardeningCheckpoint,
+    /// Lineage for each hardened Polyp, ordered by Polyp ID.
+    pub lineages: Vec<(Uuid, HardeningLineage)>,
+    /// Polyps that could not be pinned, with the error.
+    pub failed: Vec<(Uuid, ChitinError)>,
+}
+
+// ---------------------------------------------------------------------------
+// Epoch Merkle tree
+// ---------------------------------------------------------------------------
+
+// The tree itself lives in `chitin_core::light`, so light clients verify
+// inclusion with the same code that builds the epoch root.
+pub use chitin_core::light::{merkle_tree, verify_inclusion};
+
+/// Merkle leaf of a hardened Polyp: SHA-256(polyp_id_bytes || cid_bytes).
+pub fn hardening_leaf(polyp_id: &Uuid, cid: &str) -> [u8; 32] {
+    chitin_core::light::hardening_leaf(polyp_id.as_bytes(), cid)
+}
+
+/// Check a hardened Polyp's lineage against its epoch's checkpoint.
+///
+/// The lineage must name the checkpointed root, and its proof must include
+/// the leaf recomputed from the Polyp's own ID and CID.
+pub fn verify_lineage(
+    polyp_id: &Uuid,
+    lineage: &HardeningLineage,
+    checkpoint: &HardeningCheckpoint,
+) -> Result<(), ChitinError> {
+    if lineage.merkle_root != checkpoint.merkle_root {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} claims a Merkle root not checkpointed for epoch {}",
+            polyp_id, checkpoint.epoch
+        )));
+    }
+    let leaf = hardening_leaf(polyp_id, &lineage.cid);
+    if !verify_inclusion(&leaf, &lineage.merkle_proof, &checkpoint.merkle_root) {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} inclusion proof does not match epoch {} root",
+            polyp_id, checkpoint.epoch
+        )));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Checkpoints
+// ---------------------------------------------------------------------------
+
+/// Key prefix for persisted checkpoints: `hardening_checkpoint:{epoch:020}`.
+const CHECKPOINT_PREFIX: &str = "hardening_checkpoint:";
+
+/// The Merkle root of the Polyps hardened in one epoch, and the randomness
+/// beacon revealed in the epoch before it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HardeningCheckpoint {
+    /// Consensus epoch.
+    pub epoch: u64,
+    /// Root over the epoch's hardening leaves.
+    pub merkle_root: [u8; 32],
+    /// Number of Polyps hardened.
+    pub count: u64,
+    /// Beacon value from the previous epoch's validator reveals (see
+    /// `crate::beacon`), if any were revealed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<[u8; 32]>,
+}
+
+impl HardeningCheckpoint {
+    /// Load the checkpoint for `epoch`, if any.
+    pub fn load(store: &RocksStore, epoch: u64) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(checkpoint_key(epoch).as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this checkpoint.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(
+            checkpoint_key(self.epoch).as_bytes(),
+            &serde_json::to_vec(self)?,
+        )
+    }
+
+    /// The checkpoint of the latest epoch at or before `epoch`, if any.
+    pub fn latest_at(store: &RocksStore, epoch: u64) -> Result<Option<Self>, ChitinError> {
+        // Keys are zero-padded, so the scan is in epoch order.
+        let mut latest = None;
+        for (_, bytes) in store.scan_prefix(CHECKPOINT_PREFIX.as_bytes())? {
+            let checkpoint: Self = serde_json::from_slice(&bytes)?;
+            if checkpoint.epoch > epoch {
+                break;
+            }
+            latest = Some(checkpoint);
+        }
+        Ok(latest)
+    }
+
+    /// Delete checkpoints of epochs before `epoch`.
+    pub fn prune_before(store: &RocksStore, epoch: u64) -> Result<Reclaimed, ChitinError> {
+        crate::history::prune_epochs(store, CHECKPOINT_PREFIX, epoch)
+    }
+
+    /// Epoch below which checkpoints have been pruned, if any were.
+    pub fn pruned_before(store: &RocksStore) -> Result<Option<u64>, ChitinError> {
+        crate::history::pruned_before(store, CHECKPOINT_PREFIX)
+    }
+
+    /// The checkpoint reported by a strict majority of `reports`, if any.
+    ///
+    /// Used when a node lacks an epoch's checkpoint and asks its peers: a
+    /// single peer cannot vouch for its own hardened Polyps unless it is
+    /// the only one answering.
+    pub fn quorum(reports: &[HardeningCheckpoint]) -> Option<HardeningCheckpoint> {
+        let mut votes: HashMap<&HardeningCheckpoint, usize> = HashMap::new();
+        for report in reports {
+            *votes.entry(report).or_default() += 1;
+        }
+        votes
+            .into_iter()
+            .find(|&(_, n)| n * 2 > reports.len())
+            .map(|(checkpoint, _)| checkpoint.clone())
+    }
+}
+
+fn checkpoint_key(epoch: u64) -> String {
+    format!("{}{:020}", CHECKPOINT_PREFIX, epoch)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sha2::{Digest, Sha256};
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::TcpListener;
 
@@ -80,7 +275,7 @@ mod tests {
         );
 
         let handle = tokio::spawn(async move {
-            if let Ok((mut stream, _)) = listener.accept().await {
+            while let Ok((mut stream, _)) = listener.accept().await {
                 let mut buf = vec![0u8; 4096];
                 let _ = stream.read(&mut buf).await;
                 let _ = stream.write_all(response.as_bytes()).await;
@@ -126,4 +321,91 @@ mod tests {
         assert_eq!(lineage.cid, "QmABC");
         assert!(!lineage.merkle_root.iter().all(|&b| b == 0)); // Non-zero root
     }
+
+    #[test]
+    fn epoch_tree_proofs_verify_against_root() {
+        for n in [1, 2, 3, 5, 8] {
+            let leaves: Vec<[u8; 32]> = (0..n)
+                .map(|i| hardening_leaf(&Uuid::now_v7(), &format!("Qm{}", i)))
+                .collect();
+            let (root, proofs) = merkle_tree(&leaves);
+            assert_eq!(proofs.len(), n);
+            for (leaf, proof) in leaves.iter().zip(&proofs) {
+                assert!(verify_inclusion(leaf, proof, &root), "n = {}", n);
+            }
+            let stranger = hardening_leaf(&Uuid::now_v7(), "QmOther");
+            assert!(!verify_inclusion(&stranger, &proofs[0], &root));
+        }
+        assert_eq!(merkle_tree(&[]).0, [0; 32]);
+    }
+
+    #[tokio::test]
+    async fn hardened_polyps_verify_against_checkpoint() {
+        let (base_url, _handle) = mock_ipfs_pin_server().await;
+        let manager = HardeningManager::new(IpfsClient::new(&base_url));
+        let items: Vec<(Uuid, String)> = (0..3)
+            .map(|i| (Uuid::now_v7(), format!("QmEpoch{}", i)))
+            .collect();
+
+        let hardening = manager.harden_epoch(7, &items).await.unwrap();
+        assert!(hardening.failed.is_empty());
+        assert_eq!(hardening.checkpoint.count, 3);
+        assert_eq!(hardening.checkpoint.epoch, 7);
+
+        let checkpoint = &hardening.checkpoint;
+        for (polyp_id, lineage) in &hardening.lineages {
+            assert_eq!(lineage.merkle_root, checkpoint.merkle_root);
+            assert!(verify_lineage(polyp_id, lineage, checkpoint).is_ok());
+        }
+
+        // A lineage re-pointed at another CID no longer verifies.
+        let (polyp_id, lineage) = &hardening.lineages[0];
+        let mut tampered = lineage.clone();
+        tampered.cid = "QmForged".to_string();
+        assert!(verify_lineage(polyp_id, &tampered, checkpoint).is_err());
+
+        // Nor does a self-consistent lineage under a root nobody checkpointed.
+        let forged = HardeningLineage {
+            merkle_proof: vec![],
+            merkle_root: hardening_leaf(polyp_id, "QmForged"),
+            ..tampered
+        };
+        assert!(verify_lineage(polyp_id, &forged, checkpoint).is_err());
+    }
+
+    #[test]
+    fn latest_at_skips_later_epochs() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-consensus-checkpoint-latest-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(HardeningCheckpoint::latest_at(&store, 10).unwrap(), None);
+        for epoch in [2, 5, 9] {
+            let root = [epoch as u8; 32];
+            let checkpoint = HardeningCheckpoint { epoch, merkle_root: root, count: 1, beacon: None };
+            checkpoint.save(&store).unwrap();
+        }
+
+        let latest = HardeningCheckpoint::latest_at(&store, 8).unwrap().unwrap();
+        assert_eq!(latest.epoch, 5);
+        assert_eq!(HardeningCheckpoint::latest_at(&store, 9).unwrap().unwrap().epoch, 9);
+        assert_eq!(HardeningCheckpoint::latest_at(&store, 1).unwrap(), None);
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn quorum_requires_a_strict_majority() {
+        let a = HardeningCheckpoint { epoch: 3, merkle_root: [1; 32], count: 2, beacon: None };
+        let b = HardeningCheckpoint { epoch: 3, merkle_root: [2; 32], count: 2, beacon: None };
+        assert_eq!(HardeningCheckpoint::quorum(std::slice::from_ref(&a)), Some(a.clone()));
+        assert_eq!(HardeningCheckpoint::quorum(&[a.clone(), b.clone()]), None);
+        assert_eq!(
+            HardeningCheckpoint::quorum(&[a.clone(), b.clone(), a.clone()]),
+            Some(a)
+        );
+        assert_eq!(HardeningCheckpoint::quorum(&[]), None);
+    }
 }
@@ -0,0 +1,175 @@
+// crates/chitin-consensus/src/history.rs
+//
+// Persisted consensus history: one record per epoch holding that epoch's
+// full Yuma-Semantic consensus result, so historical queries can be answered
+// long after the in-memory result has been replaced. Archival nodes keep
+// every epoch; other nodes prune records older than their retention window
+// and remember the oldest epoch still retained, so queries below it can be
+// told apart from epochs that never reached consensus.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use chitin_core::ChitinError;
+use chitin_store::{Reclaimed, RocksStore};
+
+use crate::yuma::ConsensusResult;
+
+/// Key prefix for persisted records: `consensus_result:{epoch:020}`.
+const KEY_PREFIX: &str = "consensus_result:";
+
+/// Key prefix for pruning watermarks: `pruned_before:{prefix}`.
+const WATERMARK_PREFIX: &str = "pruned_before:";
+
+/// The consensus result of one epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusRecord {
+    /// Consensus epoch.
+    pub epoch: u64,
+    /// The epoch's consensus result.
+    pub result: ConsensusResult,
+    /// When consensus ran.
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ConsensusRecord {
+    /// A record of `result` for `epoch`, timestamped now.
+    pub fn new(epoch: u64, result: ConsensusResult) -> Self {
+        Self {
+            epoch,
+            result,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// Load the record for `epoch`, if any.
+    pub fn load(store: &RocksStore, epoch: u64) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(record_key(epoch).as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this record, replacing any earlier record for its epoch.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(record_key(self.epoch).as_bytes(), &serde_json::to_vec(self)?)
+    }
+
+    /// Epochs with a recorded result, oldest first.
+    pub fn epochs(store: &RocksStore) -> Result<Vec<u64>, ChitinError> {
+        let mut epochs: Vec<u64> = store
+            .scan_prefix(KEY_PREFIX.as_bytes())?
+            .into_iter()
+            .filter_map(|(key, _)| {
+                std::str::from_utf8(&key[KEY_PREFIX.len()..])
+                    .ok()
+                    .and_then(|epoch| epoch.parse().ok())
+            })
+            .collect();
+        epochs.sort_unstable();
+        Ok(epochs)
+    }
+
+    /// Delete records of epochs before `epoch`.
+    pub fn prune_before(store: &RocksStore, epoch: u64) -> Result<Reclaimed, ChitinError> {
+        prune_epochs(store, KEY_PREFIX, epoch)
+    }
+
+    /// Epoch below which records have been pruned, if any were.
+    pub fn pruned_before(store: &RocksStore) -> Result<Option<u64>, ChitinError> {
+        pruned_before(store, KEY_PREFIX)
+    }
+}
+
+/// Delete entries under `prefix` keyed by a zero-padded epoch below
+/// `epoch`, and raise the prefix's pruning watermark to `epoch`.
+pub(crate) fn prune_epochs(
+    store: &RocksStore,
+    prefix: &str,
+    epoch: u64,
+) -> Result<Reclaimed, ChitinError> {
+    let reclaimed = store.prune_prefix(prefix.as_bytes(), |suffix, _| {
+        std::str::from_utf8(suffix)
+            .ok()
+            .and_then(|e| e.parse::<u64>().ok())
+            .is_some_and(|e| e < epoch)
+    })?;
+    if pruned_before(store, prefix)?.is_none_or(|watermark| watermark < epoch) {
+        let key = format!("{}{}", WATERMARK_PREFIX, prefix);
+        store.put_bytes(key.as_bytes(), epoch.to_string().as_bytes())?;
+    }
+    Ok(reclaimed)
+}
+
+/// The pruning watermark of `prefix`, if it was ever pruned.
+pub(crate) fn pruned_before(store: &RocksStore, prefix: &str) -> Result<Option<u64>, ChitinError> {
+    let key = format!("{}{}", WATERMARK_PREFIX, prefix);
+    Ok(store
+        .get_bytes(key.as_bytes())?
+        .and_then(|bytes| String::from_utf8_lossy(&bytes).parse().ok()))
+}
+
+fn record_key(epoch: u64) -> String {
+    format!("{}{:020}", KEY_PREFIX, epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(weight: f64) -> ConsensusResult {
+        ConsensusResult {
+            consensus_weights: vec![weight],
+            incentives: vec![1.0],
+            dividends: vec![1.0],
+            bonds: vec![vec![weight]],
+            hardened_polyp_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_records_roundtrip_by_epoch() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-consensus-history-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+
+        ConsensusRecord::new(12, result(0.5)).save(&store).unwrap();
+        ConsensusRecord::new(3, result(0.25)).save(&store).unwrap();
+
+        let loaded = ConsensusRecord::load(&store, 12).unwrap().unwrap();
+        assert_eq!(loaded.epoch, 12);
+        assert_eq!(loaded.result.consensus_weights, vec![0.5]);
+        assert!(ConsensusRecord::load(&store, 4).unwrap().is_none());
+        assert_eq!(ConsensusRecord::epochs(&store).unwrap(), vec![3, 12]);
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_pruning_drops_old_epochs_and_keeps_watermark() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-consensus-history-prune-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        for epoch in [1, 2, 5] {
+            ConsensusRecord::new(epoch, result(0.5)).save(&store).unwrap();
+        }
+        assert_eq!(ConsensusRecord::pruned_before(&store).unwrap(), None);
+
+        let reclaimed = ConsensusRecord::prune_before(&store, 3).unwrap();
+        assert_eq!(reclaimed.entries, 2);
+        assert_eq!(ConsensusRecord::epochs(&store).unwrap(), vec![5]);
+        assert_eq!(ConsensusRecord::pruned_before(&store).unwrap(), Some(3));
+
+        // The watermark never moves back.
+        ConsensusRecord::prune_before(&store, 2).unwrap();
+        assert_eq!(ConsensusRecord::pruned_before(&store).unwrap(), Some(3));
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
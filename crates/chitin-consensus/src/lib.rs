@@ -8,8 +8,14 @@
 
 pub mod yuma;
 pub mod scoring;
+pub mod assignment;
+pub mod beacon;
+pub mod dedup;
+pub mod moderation;
 pub mod weights;
 pub mod bonds;
 pub mod epoch;
 pub mod metagraph;
+pub mod genesis;
 pub mod hardening;
+pub mod history;
@@ -46,6 +46,14 @@ impl MetagraphManager {
         Ok(())
     }
 
+    /// Start from `metagraph` (e.g. the genesis metagraph) without recording
+    /// its epoch, so a later snapshot of any epoch, including a restored or
+    /// synced one, replaces it.
+    pub fn initialize(&mut self, metagraph: ReefMetagraph) {
+        self.current = Some(metagraph);
+        self.last_epoch = None;
+    }
+
     /// Get a reference to the current metagraph snapshot, if available.
     pub fn current(&self) -> Option<&ReefMetagraph> {
         self.current.as_ref()
@@ -73,6 +81,7 @@ mod tests {
             emission_rate: 0,
             weights: HashMap::new(),
             bonds: HashMap::new(),
+            model_versions: vec![],
         }
     }
 
@@ -111,6 +120,16 @@ mod tests {
         assert_eq!(manager.current().unwrap().epoch, 5);
     }
 
+    #[test]
+    fn test_initialize_accepts_any_later_update() {
+        let mut manager = MetagraphManager::new();
+        manager.initialize(make_metagraph(0));
+        assert_eq!(manager.current().unwrap().epoch, 0);
+
+        manager.update(make_metagraph(0)).unwrap();
+        assert!(manager.update(make_metagraph(0)).is_err());
+    }
+
     #[test]
     fn test_update_accepts_higher_epoch() {
         let mut manager = MetagraphManager::new();
@@ -0,0 +1,619 @@
+// crates/chitin-consensus/src/moderation.rs
+//
+// Content moderation of Polyps before they reach the Reef.
+//
+// A `ModerationPolicy` reviews a Polyp when it is submitted and again just
+// before it hardens, and either allows it or returns a `Violation` with a
+// machine-readable reason code. This module provides the policies running
+// on the `[moderation]` config alone: `KeywordPolicy` (blocked words and
+// phrases), `ZonePolicy` (per Reef Zone rules), and `PolicySet`, which runs
+// several in order and stops at the first rejection. The daemon adds an
+// external HTTP classifier hook on top.
+//
+// Rejected Polyps move to `PolypState::Rejected`, and the rejection is
+// recorded in the store under `moderation:{polyp_id}`, where `polyp/state`
+// reports it.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_core::{ChitinError, Polyp};
+use chitin_store::RocksStore;
+
+/// Store key prefix for moderation records.
+pub const MODERATION_PREFIX: &str = "moderation:";
+
+/// Reason code: the content contains a blocked keyword.
+pub const CODE_BLOCKED_KEYWORD: &str = "blocked_keyword";
+/// Reason code: the Reef Zone does not accept submissions.
+pub const CODE_ZONE_CLOSED: &str = "zone_closed";
+/// Reason code: the Reef Zone requires a source URL or CID.
+pub const CODE_SOURCE_REQUIRED: &str = "source_required";
+/// Reason code: the content is longer than the Reef Zone allows.
+pub const CODE_CONTENT_TOO_LONG: &str = "content_too_long";
+/// Reason code: the external classifier could not be reached.
+pub const CODE_CLASSIFIER_UNAVAILABLE: &str = "classifier_unavailable";
+
+/// Moderation settings (`[moderation]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    /// Moderate at all.
+    pub enabled: bool,
+    /// Review Polyps as they are submitted over RPC.
+    pub on_submit: bool,
+    /// Review approved Polyps before they are hardened.
+    pub before_hardening: bool,
+    /// Words and phrases rejected in any zone (case-insensitive).
+    pub blocked_keywords: Vec<String>,
+    /// Per Reef Zone rules (`[[moderation.zones]]`).
+    pub zones: Vec<ZoneRule>,
+    /// External classifier hook (`[moderation.classifier]`).
+    pub classifier: Option<ClassifierConfig>,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            on_submit: true,
+            before_hardening: true,
+            blocked_keywords: Vec::new(),
+            zones: Vec::new(),
+            classifier: None,
+        }
+    }
+}
+
+impl ModerationConfig {
+    /// Whether any rule is configured.
+    pub fn has_rules(&self) -> bool {
+        !self.blocked_keywords.is_empty() || !self.zones.is_empty() || self.classifier.is_some()
+    }
+
+    /// Check that keywords, zones, and the classifier are well formed.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        let invalid = |message: String| Err(ChitinError::InvalidState(message));
+        if self.blocked_keywords.iter().any(|k| k.trim().is_empty()) {
+            return invalid("moderation.blocked_keywords must not be empty".to_string());
+        }
+        for rule in &self.zones {
+            if rule.zone.trim().is_empty() {
+                return invalid("moderation.zones entries need a zone".to_string());
+            }
+            if rule.blocked_keywords.iter().any(|k| k.trim().is_empty()) {
+                return invalid(format!(
+                    "moderation zone '{}' has an empty blocked keyword",
+                    rule.zone
+                ));
+            }
+        }
+        if let Some(classifier) = &self.classifier {
+            if !classifier.url.starts_with("http://") && !classifier.url.starts_with("https://") {
+                return invalid(format!(
+                    "moderation.classifier.url must be http(s): {}",
+                    classifier.url
+                ));
+            }
+            if classifier.timeout_secs == 0 {
+                return invalid("moderation.classifier.timeout_secs must be at least 1".into());
+            }
+        }
+        Ok(())
+    }
+
+    /// The policies that need nothing but this config: blocked keywords,
+    /// then zone rules.
+    pub fn local_policies(&self) -> Vec<Arc<dyn ModerationPolicy>> {
+        let mut policies: Vec<Arc<dyn ModerationPolicy>> = Vec::new();
+        if !self.blocked_keywords.is_empty() {
+            policies.push(Arc::new(KeywordPolicy::new(&self.blocked_keywords)));
+        }
+        if !self.zones.is_empty() {
+            policies.push(Arc::new(ZonePolicy::new(self.zones.clone())));
+        }
+        policies
+    }
+}
+
+/// Rules for one Reef Zone and the zones under it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneRule {
+    /// Zone path, e.g. `"medicine"` also covers `"medicine/oncology"`.
+    pub zone: String,
+    /// Reject every Polyp in the zone.
+    #[serde(default)]
+    pub closed: bool,
+    /// Reject Polyps without a source URL or CID.
+    #[serde(default)]
+    pub require_source: bool,
+    /// Maximum content length in bytes.
+    #[serde(default)]
+    pub max_content_bytes: Option<usize>,
+    /// Words and phrases rejected in this zone, on top of the global list.
+    #[serde(default)]
+    pub blocked_keywords: Vec<String>,
+}
+
+impl ZoneRule {
+    /// Whether the rule covers Polyps in `zone`.
+    pub fn covers(&self, zone: &str) -> bool {
+        zone == self.zone
+            || zone
+                .strip_prefix(self.zone.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// External classifier settings (`[moderation.classifier]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifierConfig {
+    /// Endpoint receiving a JSON POST per reviewed Polyp.
+    pub url: String,
+    /// Request timeout in seconds.
+    #[serde(default = "default_classifier_timeout")]
+    pub timeout_secs: u64,
+    /// Allow Polyps when the classifier cannot be reached, rather than
+    /// rejecting them with `classifier_unavailable`.
+    #[serde(default)]
+    pub fail_open: bool,
+}
+
+fn default_classifier_timeout() -> u64 {
+    5
+}
+
+/// When a Polyp is reviewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationStage {
+    /// On submission over RPC.
+    Submission,
+    /// After consensus approval, before hardening.
+    PreHardening,
+}
+
+/// Why a policy rejected a Polyp.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    /// Machine-readable reason code, e.g. `blocked_keyword`.
+    pub code: String,
+    /// Human-readable explanation.
+    pub reason: String,
+}
+
+impl Violation {
+    pub fn new(code: &str, reason: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A recorded rejection, as stored and reported by `polyp/state`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationRecord {
+    pub code: String,
+    pub reason: String,
+    /// Name of the policy that rejected the Polyp.
+    pub policy: String,
+    pub stage: ModerationStage,
+    pub rejected_at: DateTime<Utc>,
+}
+
+/// A content policy applied to Polyps before they reach the Reef.
+#[async_trait]
+pub trait ModerationPolicy: Send + Sync {
+    /// Short name recorded with rejections, e.g. `"keywords"`.
+    fn name(&self) -> &str;
+
+    /// Review `polyp` at `stage`: `None` allows it.
+    ///
+    /// Errors mean the policy could not decide; callers allow the Polyp
+    /// and log the error, so a policy that must fail closed returns a
+    /// `Violation` instead.
+    async fn review(
+        &self,
+        polyp: &Polyp,
+        stage: ModerationStage,
+    ) -> Result<Option<Violation>, ChitinError>;
+}
+
+/// Rejects content containing any of a list of words or phrases.
+///
+/// Matching is case-insensitive on whole words: "reef" does not match
+/// "reefs".
+#[derive(Debug, Clone)]
+pub struct KeywordPolicy {
+    /// Lowercase keywords, each normalized to single-space-separated words.
+    keywords: Vec<String>,
+}
+
+impl KeywordPolicy {
+    pub fn new(keywords: &[String]) -> Self {
+        Self {
+            keywords: keywords.iter().map(|k| normalize(k)).collect(),
+        }
+    }
+
+    /// The first keyword found in `content`, if any.
+    pub fn find(&self, content: &str) -> Option<&str> {
+        find_keyword(&self.keywords, &normalize(content))
+    }
+}
+
+#[async_trait]
+impl ModerationPolicy for KeywordPolicy {
+    fn name(&self) -> &str {
+        "keywords"
+    }
+
+    async fn review(
+        &self,
+        polyp: &Polyp,
+        _stage: ModerationStage,
+    ) -> Result<Option<Violation>, ChitinError> {
+        Ok(self.find(&polyp.subject.payload.content).map(|keyword| {
+            Violation::new(
+                CODE_BLOCKED_KEYWORD,
+                format!("content contains blocked keyword '{}'", keyword),
+            )
+        }))
+    }
+}
+
+/// Applies `ZoneRule`s to Polyps by their Reef Zone. Polyps without a zone
+/// are not covered by any rule.
+#[derive(Debug, Clone)]
+pub struct ZonePolicy {
+    rules: Vec<ZoneRule>,
+}
+
+impl ZonePolicy {
+    pub fn new(mut rules: Vec<ZoneRule>) -> Self {
+        for rule in &mut rules {
+            rule.blocked_keywords = rule.blocked_keywords.iter().map(|k| normalize(k)).collect();
+        }
+        Self { rules }
+    }
+
+    /// The first rule violation of `polyp`, checking rules in order.
+    pub fn check(&self, polyp: &Polyp) -> Option<Violation> {
+        let zone = polyp.reef_zone.as_deref()?;
+        let content = &polyp.subject.payload.content;
+        let source = &polyp.subject.provenance.source;
+        let mut words = None;
+        for rule in self.rules.iter().filter(|rule| rule.covers(zone)) {
+            if rule.closed {
+                return Some(Violation::new(
+                    CODE_ZONE_CLOSED,
+                    format!("zone '{}' does not accept submissions", rule.zone),
+                ));
+            }
+            if rule.require_source && source.source_url.is_none() && source.source_cid.is_none() {
+                return Some(Violation::new(
+                    CODE_SOURCE_REQUIRED,
+                    format!("zone '{}' requires a source URL or CID", rule.zone),
+                ));
+            }
+            if let Some(max) = rule.max_content_bytes.filter(|max| content.len() > *max) {
+                return Some(Violation::new(
+                    CODE_CONTENT_TOO_LONG,
+                    format!(
+                        "content is {} bytes, zone '{}' allows {}",
+                        content.len(),
+                        rule.zone,
+                        max
+                    ),
+                ));
+            }
+            let words = words.get_or_insert_with(|| normalize(content));
+            if let Some(keyword) = find_keyword(&rule.blocked_keywords, words) {
+                return Some(Violation::new(
+                    CODE_BLOCKED_KEYWORD,
+                    format!(
+                        "content contains '{}', blocked in zone '{}'",
+                        keyword, rule.zone
+                    ),
+                ));
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl ModerationPolicy for ZonePolicy {
+    fn name(&self) -> &str {
+        "zones"
+    }
+
+    async fn review(
+        &self,
+        polyp: &Polyp,
+        _stage: ModerationStage,
+    ) -> Result<Option<Violation>, ChitinError> {
+        Ok(self.check(polyp))
+    }
+}
+
+/// Several policies run in order; a Polyp is rejected by the first one
+/// that rejects it.
+#[derive(Clone, Default)]
+pub struct PolicySet {
+    policies: Vec<Arc<dyn ModerationPolicy>>,
+}
+
+impl PolicySet {
+    pub fn new(policies: Vec<Arc<dyn ModerationPolicy>>) -> Self {
+        Self { policies }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Review `polyp`, returning a record of the first rejection.
+    ///
+    /// Policies after one that fails still run; the failure is returned if
+    /// none of them rejects the Polyp, for the caller to log before
+    /// allowing it.
+    pub async fn moderate(
+        &self,
+        polyp: &Polyp,
+        stage: ModerationStage,
+    ) -> Result<Option<ModerationRecord>, ChitinError> {
+        let mut failure = None;
+        for policy in &self.policies {
+            match policy.review(polyp, stage).await {
+                Ok(Some(violation)) => {
+                    return Ok(Some(ModerationRecord {
+                        code: violation.code,
+                        reason: violation.reason,
+                        policy: policy.name().to_string(),
+                        stage,
+                        rejected_at: Utc::now(),
+                    }))
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    failure.get_or_insert_with(|| {
+                        ChitinError::InvalidState(format!(
+                            "moderation policy '{}' failed: {}",
+                            policy.name(),
+                            e
+                        ))
+                    });
+                }
+            }
+        }
+        failure.map_or(Ok(None), Err)
+    }
+}
+
+/// Record the rejection of `polyp_id`.
+pub fn save_record(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+    record: &ModerationRecord,
+) -> Result<(), ChitinError> {
+    let json = serde_json::to_vec(record).map_err(|e| ChitinError::Serialization(e.to_string()))?;
+    store.put_bytes(record_key(polyp_id).as_bytes(), &json)
+}
+
+/// The rejection recorded for `polyp_id`, if any.
+pub fn load_record(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+) -> Result<Option<ModerationRecord>, ChitinError> {
+    match store.get_bytes(record_key(polyp_id).as_bytes())? {
+        None => Ok(None),
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| ChitinError::Serialization(e.to_string())),
+    }
+}
+
+fn record_key(polyp_id: &Uuid) -> String {
+    format!("{}{}", MODERATION_PREFIX, polyp_id)
+}
+
+/// Lowercase alphanumeric words of `text`, joined by single spaces.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The first of the normalized `keywords` appearing in the normalized
+/// `words` on word boundaries.
+fn find_keyword<'a>(keywords: &'a [String], words: &str) -> Option<&'a str> {
+    let padded = format!(" {} ", words);
+    keywords
+        .iter()
+        .find(|keyword| !keyword.is_empty() && padded.contains(&format!(" {} ", keyword)))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::{
+        hash_embedding, EmbeddingModelId, NodeIdentity, NodeType, Payload, PolypState,
+        PolypSubject, ProcessingPipeline, ProofPublicInputs, Provenance, SourceAttribution,
+        VectorEmbedding, ZkProof,
+    };
+
+    fn polyp(content: &str, zone: Option<&str>) -> Polyp {
+        let now = Utc::now();
+        let model_id = EmbeddingModelId::from_key("test/hash", 8);
+        Polyp {
+            id: Uuid::now_v7(),
+            state: PolypState::Draft,
+            subject: PolypSubject {
+                payload: Payload {
+                    content: content.to_string(),
+                    content_type: "text/plain".to_string(),
+                    language: None,
+                },
+                vector: VectorEmbedding {
+                    values: hash_embedding(content, 8),
+                    model_id: model_id.clone(),
+                    quantization: "float32".to_string(),
+                    normalization: "l2".to_string(),
+                },
+                provenance: Provenance {
+                    creator: NodeIdentity {
+                        coldkey: [0u8; 32],
+                        hotkey: [0u8; 32],
+                        did: "did:chitin:local".to_string(),
+                        node_type: NodeType::Coral,
+                    },
+                    source: SourceAttribution {
+                        source_cid: None,
+                        source_url: None,
+                        title: None,
+                        license: None,
+                        accessed_at: now,
+                    },
+                    pipeline: ProcessingPipeline {
+                        steps: vec![],
+                        duration_ms: 0,
+                    },
+                    molted_from: vec![],
+                },
+            },
+            proof: ZkProof {
+                proof_type: "placeholder".to_string(),
+                proof_value: "0x00".to_string(),
+                vk_hash: "0x00".to_string(),
+                public_inputs: ProofPublicInputs {
+                    text_hash: [0u8; 32],
+                    vector_hash: [0u8; 32],
+                    model_id,
+                },
+                created_at: now,
+            },
+            consensus: None,
+            hardening: None,
+            created_at: now,
+            updated_at: now,
+            signature: None,
+            reef_zone: zone.map(str::to_string),
+        }
+    }
+
+    fn zone_rule(zone: &str) -> ZoneRule {
+        ZoneRule {
+            zone: zone.to_string(),
+            closed: false,
+            require_source: false,
+            max_content_bytes: None,
+            blocked_keywords: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keywords_match_whole_words_case_insensitively() {
+        let policy = KeywordPolicy::new(&["Dynamite Fishing".to_string(), "cyanide".to_string()]);
+        assert_eq!(
+            policy.find("How to go DYNAMITE  fishing on a reef"),
+            Some("dynamite fishing")
+        );
+        assert_eq!(policy.find("Cyanide, sprayed on coral."), Some("cyanide"));
+        assert_eq!(policy.find("Cyanides and dynamite are banned."), None);
+    }
+
+    #[test]
+    fn zone_rules_cover_subzones_and_apply_in_order() {
+        let mut closed = zone_rule("medicine/trials");
+        closed.closed = true;
+        let mut sourced = zone_rule("medicine");
+        sourced.require_source = true;
+        sourced.max_content_bytes = Some(40);
+        let policy = ZonePolicy::new(vec![closed, sourced]);
+
+        let code = |p: &Polyp| policy.check(p).map(|v| v.code);
+        let trial = polyp("Phase III results.", Some("medicine/trials/oncology"));
+        assert_eq!(code(&trial).as_deref(), Some(CODE_ZONE_CLOSED));
+        let unsourced = polyp("Aspirin thins blood.", Some("medicine"));
+        assert_eq!(code(&unsourced).as_deref(), Some(CODE_SOURCE_REQUIRED));
+
+        let mut long = polyp(
+            &"Statins lower cholesterol. ".repeat(3),
+            Some("medicine/cardio"),
+        );
+        long.subject.provenance.source.source_url = Some("https://example.org".to_string());
+        assert_eq!(code(&long).as_deref(), Some(CODE_CONTENT_TOO_LONG));
+        long.subject.payload.content = "Statins lower cholesterol.".to_string();
+        assert_eq!(code(&long), None);
+
+        // Neither unzoned polyps nor sibling zones are covered.
+        assert_eq!(code(&polyp("Anything.", None)), None);
+        assert_eq!(code(&polyp("Anything.", Some("medicines"))), None);
+    }
+
+    #[tokio::test]
+    async fn policy_set_records_the_first_rejection() {
+        let config = ModerationConfig {
+            blocked_keywords: vec!["poaching".to_string()],
+            zones: vec![ZoneRule {
+                blocked_keywords: vec!["bleach".to_string()],
+                ..zone_rule("ecology")
+            }],
+            ..ModerationConfig::default()
+        };
+        config.validate().unwrap();
+        let policies = PolicySet::new(config.local_policies());
+
+        let allowed = polyp("Bleach kills coral in aquaria.", Some("chemistry"));
+        assert!(policies
+            .moderate(&allowed, ModerationStage::Submission)
+            .await
+            .unwrap()
+            .is_none());
+
+        let zoned = polyp("Use bleach on reefs.", Some("ecology/reefs"));
+        let record = policies
+            .moderate(&zoned, ModerationStage::PreHardening)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.code, CODE_BLOCKED_KEYWORD);
+        assert_eq!(record.policy, "zones");
+        assert_eq!(record.stage, ModerationStage::PreHardening);
+
+        let both = polyp("Poaching and bleach.", Some("ecology"));
+        let record = policies
+            .moderate(&both, ModerationStage::Submission)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.policy, "keywords");
+    }
+
+    #[test]
+    fn validate_rejects_malformed_rules() {
+        let mut config = ModerationConfig {
+            blocked_keywords: vec![" ".to_string()],
+            ..ModerationConfig::default()
+        };
+        assert!(config.validate().is_err());
+        config.blocked_keywords.clear();
+        config.classifier = Some(ClassifierConfig {
+            url: "ftp://classifier".to_string(),
+            timeout_secs: 5,
+            fail_open: false,
+        });
+        assert!(config.validate().is_err());
+        assert!(ModerationConfig::default().validate().is_ok());
+        assert!(!ModerationConfig::default().has_rules());
+    }
+}
@@ -2,12 +2,16 @@
 //
 // Multi-dimensional Polyp scoring for the Chitin Protocol.
 //
-// Tide Nodes use this module to evaluate Polyps across five quality dimensions:
-// ZK validity, semantic quality, novelty, source credibility, and embedding quality.
+// Tide Nodes use this module to evaluate Polyps across six quality dimensions:
+// ZK validity, semantic quality, novelty, source credibility, embedding quality,
+// and provenance integrity.
+// Batches of Polyps are scored in parallel with rayon.
 
 use chitin_core::{Polyp, PolypScores};
+use chrono::Utc;
+use rayon::prelude::*;
 
-/// Score a Polyp across all five quality dimensions.
+/// Score a Polyp across all six quality dimensions.
 ///
 /// # Scoring Dimensions
 /// 1. **ZK Validity** (0.0-1.0): 0.5 for placeholder proofs, 0.8 for non-placeholder.
@@ -15,12 +19,14 @@ use chitin_core::{Polyp, PolypScores};
 /// 3. **Novelty** (0.0-1.0): Embedding variance proxy.
 /// 4. **Source Credibility** (0.0-1.0): Provenance completeness check.
 /// 5. **Embedding Quality** (0.0-1.0): Dimension match + L2 normalization check.
+/// 6. **Provenance Integrity** (0.0-1.0): `Provenance::verify` report score.
 pub fn score_polyp_multi_dimensional(polyp: &Polyp) -> PolypScores {
     let zk_validity = score_zk_validity(polyp);
     let semantic_quality = score_semantic_quality(polyp);
     let novelty = score_novelty(polyp);
     let source_credibility = score_source_credibility(polyp);
     let embedding_quality = score_embedding_quality(polyp);
+    let provenance_integrity = score_provenance_integrity(polyp);
 
     PolypScores {
         zk_validity,
@@ -28,9 +34,33 @@ pub fn score_polyp_multi_dimensional(polyp: &Polyp) -> PolypScores {
         novelty,
         source_credibility,
         embedding_quality,
+        provenance_integrity,
     }
 }
 
+/// Score every Polyp in `polyps`, in parallel.
+///
+/// Each Polyp is scored independently, so the result (in input order) is
+/// identical to scoring them one by one.
+pub fn score_polyps(polyps: &[Polyp]) -> Vec<PolypScores> {
+    polyps.par_iter().map(score_polyp_multi_dimensional).collect()
+}
+
+/// Mean weighted score of `polyps` (0.0 if there are none).
+///
+/// Scores are computed in parallel but summed in input order, so the mean
+/// does not depend on the number of threads.
+pub fn mean_weighted_score(polyps: &[Polyp]) -> f64 {
+    if polyps.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = score_polyps(polyps)
+        .iter()
+        .map(PolypScores::weighted_score)
+        .sum();
+    total / polyps.len() as f64
+}
+
 /// ZK validity: 0.5 for placeholder proofs (all zeros or empty), 0.8 for non-placeholder.
 fn score_zk_validity(polyp: &Polyp) -> f64 {
     let proof_bytes = polyp.proof.proof_value.as_bytes();
@@ -138,6 +168,18 @@ fn score_embedding_quality(polyp: &Polyp) -> f64 {
     score.min(1.0)
 }
 
+/// Provenance integrity: the share of provenance checks that pass.
+///
+/// Timestamps are checked against this validator's clock, so validators
+/// whose clocks disagree by more than the allowed skew may differ here.
+fn score_provenance_integrity(polyp: &Polyp) -> f64 {
+    polyp
+        .subject
+        .provenance
+        .verify(polyp.created_at, Utc::now())
+        .score()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,6 +248,7 @@ mod tests {
                         steps: pipeline_steps,
                         duration_ms: 100,
                     },
+                    molted_from: vec![],
                 },
             },
             proof: ZkProof {
@@ -229,9 +272,24 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             signature: None,
+            reef_zone: None,
         }
     }
 
+    #[test]
+    fn test_batch_scores_match_individual_scores() {
+        let polyps: Vec<Polyp> = (0..50)
+            .map(|i| make_test_polyp("abc123", &"x".repeat(i * 7), vec![0.01 * i as f32; 8], 8))
+            .collect();
+        let batch = score_polyps(&polyps);
+        for (polyp, scores) in polyps.iter().zip(&batch) {
+            assert_eq!(*scores, score_polyp_multi_dimensional(polyp));
+        }
+        let mean = batch.iter().map(PolypScores::weighted_score).sum::<f64>() / 50.0;
+        assert_eq!(mean_weighted_score(&polyps), mean);
+        assert_eq!(mean_weighted_score(&[]), 0.0);
+    }
+
     #[test]
     fn test_placeholder_proof_gets_zk_validity_half() {
         let polyp = make_test_polyp("0000000000", "test content", vec![0.1; 10], 10);
@@ -292,11 +350,13 @@ mod tests {
                 name: "chunk".to_string(),
                 version: "1.0".to_string(),
                 params: serde_json::json!({}),
+                hash: None,
             },
             PipelineStep {
                 name: "embed".to_string(),
                 version: "1.0".to_string(),
                 params: serde_json::json!({}),
+                hash: None,
             },
         ];
 
@@ -324,5 +384,36 @@ mod tests {
         assert!((scores.source_credibility - 0.7).abs() < 1e-10);
         // dimension match(0.5) + L2 norm ~1.0(0.3) + non-zero(0.2) = 1.0
         assert!((scores.embedding_quality - 1.0).abs() < 1e-10);
+        // The DID is not derived from the coldkey, so the creator check fails;
+        // timestamps pass and the rest skip: (1 + 3 * 0.5) / 5 = 0.5
+        assert!((scores.provenance_integrity - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_provenance_integrity_rewards_a_verifiable_chain() {
+        // Placeholder creator, no steps, no CID, never molted: only the
+        // timestamps can be checked.
+        let mut polyp = make_test_polyp("abc123", "test content", vec![0.1; 4], 4);
+        let bare = score_polyp_multi_dimensional(&polyp).provenance_integrity;
+        assert!((bare - 0.6).abs() < 1e-10);
+
+        let provenance = &mut polyp.subject.provenance;
+        provenance.creator = NodeIdentity::from_keypairs([2; 32], [1; 32], NodeType::Coral);
+        provenance.pipeline.steps.push(PipelineStep {
+            name: "chunk".to_string(),
+            version: "1.0".to_string(),
+            params: serde_json::json!({}),
+            hash: None,
+        });
+        provenance.pipeline.seal();
+        let sealed = score_polyp_multi_dimensional(&polyp);
+        assert!(sealed.provenance_integrity > bare);
+        assert!(sealed.weighted_score() > score_polyp_multi_dimensional(&make_test_polyp(
+            "abc123",
+            "test content",
+            vec![0.1; 4],
+            4
+        ))
+        .weighted_score());
     }
 }
@@ -5,7 +5,13 @@
 // Adapts Bittensor's Yuma Consensus for semantic knowledge validation.
 // Evaluates Polyp quality across five dimensions using stake-weighted
 // median scoring, weight clipping, bond penalties, and incentive computation.
+//
+// The per-coral medians and the per-validator agreement and bond rows are
+// computed in parallel with rayon. Each element is computed by the same
+// sequential code whatever the thread count, and sums across elements run
+// in index order, so results are bit-for-bit deterministic.
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -75,7 +81,7 @@ pub fn yuma_semantic_consensus(
 
     // Step 2: Row-normalize weight matrix
     let norm_weights: Vec<Vec<f64>> = weights
-        .iter()
+        .par_iter()
         .map(|row| {
             let sum: f64 = row.iter().sum();
             if sum > 0.0 {
@@ -87,31 +93,34 @@ pub fn yuma_semantic_consensus(
         .collect();
 
     // Step 3: Stake-weighted median per coral
-    let mut consensus_weights = vec![0.0; n_corals];
-    for j in 0..n_corals {
-        // Collect (weight, stake) pairs for this coral
-        let mut pairs: Vec<(f64, f64)> = (0..n_validators)
-            .map(|i| (norm_weights[i][j], norm_stakes[i]))
-            .collect();
-
-        // Sort by weight value
-        pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Walk cumulative stake until reaching kappa threshold
-        let mut cumulative = 0.0;
-        let mut median_val = 0.0;
-        for (w, s) in &pairs {
-            cumulative += s;
-            median_val = *w;
-            if cumulative >= kappa {
-                break;
+    let consensus_weights: Vec<f64> = (0..n_corals)
+        .into_par_iter()
+        .map(|j| {
+            // Collect (weight, stake) pairs for this coral
+            let mut pairs: Vec<(f64, f64)> = (0..n_validators)
+                .map(|i| (norm_weights[i][j], norm_stakes[i]))
+                .collect();
+
+            // Sort by weight value (stable, so ties keep validator order)
+            pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            // Walk cumulative stake until reaching kappa threshold
+            let mut cumulative = 0.0;
+            let mut median_val = 0.0;
+            for (w, s) in &pairs {
+                cumulative += s;
+                median_val = *w;
+                if cumulative >= kappa {
+                    break;
+                }
             }
-        }
-        consensus_weights[j] = median_val;
-    }
+            median_val
+        })
+        .collect();
 
     // Step 4: Validator agreement
     let agreement: Vec<f64> = (0..n_validators)
+        .into_par_iter()
         .map(|i| {
             if n_corals == 0 {
                 return 1.0;
@@ -126,6 +135,7 @@ pub fn yuma_semantic_consensus(
 
     // Step 5: Bond EMA update with penalty
     let bonds: Vec<Vec<f64>> = (0..n_validators)
+        .into_par_iter()
         .map(|i| {
             (0..n_corals)
                 .map(|j| {
@@ -305,6 +315,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_results_do_not_depend_on_thread_count() {
+        let (n_validators, n_corals) = (37, 211);
+        let stakes: Vec<u64> = (0..n_validators).map(|i| 100 + (i * 7919) % 1000).collect();
+        let cell = |i: usize, j: usize| ((i * 31 + j * 17) % 101) as f64 / 100.0;
+        let weights: Vec<Vec<f64>> = (0..n_validators as usize)
+            .map(|i| (0..n_corals).map(|j| cell(i, j)).collect())
+            .collect();
+        let prev_bonds: Vec<Vec<f64>> = (0..n_validators as usize)
+            .map(|i| (0..n_corals).map(|j| cell(j, i) / 2.0).collect())
+            .collect();
+
+        let run = |threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| yuma_semantic_consensus(&stakes, &weights, &prev_bonds, 0.5, 0.1, 0.1))
+        };
+        let (serial, parallel) = (run(1), run(8));
+        assert_eq!(serial.consensus_weights, parallel.consensus_weights);
+        assert_eq!(serial.dividends, parallel.dividends);
+        assert_eq!(serial.bonds, parallel.bonds);
+    }
+
     #[test]
     fn test_bond_decay_over_multiple_rounds() {
         let stakes = vec![100, 100];
@@ -0,0 +1,134 @@
+// crates/chitin-consensus/tests/yuma_properties.rs
+//
+// Property tests for Yuma-Semantic consensus.
+//
+// Random validator sets (stakes and score matrices) are run through
+// `yuma_semantic_consensus`, and each result is checked against invariants
+// every epoch must satisfy:
+//
+// - Incentives sum to 1, or are all 0 when no coral has consensus weight.
+// - Dividends sum to 1, or are all 0.
+// - Bonds are non-negative when the previous bonds are.
+// - Consensus is monotone in stake: raising one validator's stake moves
+//   each coral's consensus weight toward that validator's weight, never
+//   past it and never away from it.
+
+use chitin_consensus::yuma::{yuma_semantic_consensus, ConsensusResult};
+use proptest::prelude::*;
+
+/// Just above the default 0.5, so a cumulative stake landing exactly on
+/// the threshold cannot flip the median on rounding.
+const KAPPA: f64 = 0.5 + 1e-9;
+const BOND_PENALTY: f64 = 0.1;
+const ALPHA: f64 = 0.1;
+const TOLERANCE: f64 = 1e-9;
+
+/// One epoch's inputs: stakes, weights [validators x corals], and previous
+/// bonds of the same shape.
+#[derive(Debug, Clone)]
+struct Epoch {
+    stakes: Vec<u64>,
+    weights: Vec<Vec<f64>>,
+    prev_bonds: Vec<Vec<f64>>,
+}
+
+impl Epoch {
+    fn run(&self) -> ConsensusResult {
+        yuma_semantic_consensus(
+            &self.stakes,
+            &self.weights,
+            &self.prev_bonds,
+            KAPPA,
+            BOND_PENALTY,
+            ALPHA,
+        )
+    }
+
+    /// Validator `i`'s weights as consensus sees them (row-normalized).
+    fn normalized_row(&self, i: usize) -> Vec<f64> {
+        let row = &self.weights[i];
+        let sum: f64 = row.iter().sum();
+        if sum > 0.0 {
+            row.iter().map(|w| w / sum).collect()
+        } else {
+            row.clone()
+        }
+    }
+}
+
+fn epoch() -> impl Strategy<Value = Epoch> {
+    (1usize..=6, 1usize..=8).prop_flat_map(|(validators, corals)| {
+        (
+            prop::collection::vec(1u64..10_000, validators),
+            prop::collection::vec(prop::collection::vec(0.0f64..1.0, corals), validators),
+            prop::collection::vec(prop::collection::vec(0.0f64..1.0, corals), validators),
+        )
+            .prop_map(|(stakes, weights, prev_bonds)| Epoch {
+                stakes,
+                weights,
+                prev_bonds,
+            })
+    })
+}
+
+/// True if `shares` sum to 1 (within tolerance) or are all exactly 0.
+fn is_distribution_or_zero(shares: &[f64]) -> bool {
+    let sum: f64 = shares.iter().sum();
+    (sum - 1.0).abs() < TOLERANCE || shares.iter().all(|&s| s == 0.0)
+}
+
+proptest! {
+    #[test]
+    fn incentives_sum_to_one(epoch in epoch()) {
+        let result = epoch.run();
+        prop_assert!(result.incentives.iter().all(|&s| s >= 0.0));
+        prop_assert!(is_distribution_or_zero(&result.incentives), "{:?}", result.incentives);
+        if result.consensus_weights.iter().any(|&c| c > 0.0) {
+            let sum: f64 = result.incentives.iter().sum();
+            prop_assert!((sum - 1.0).abs() < TOLERANCE, "incentives sum to {}", sum);
+        }
+    }
+
+    #[test]
+    fn dividends_sum_to_one(epoch in epoch()) {
+        let result = epoch.run();
+        prop_assert_eq!(result.dividends.len(), epoch.stakes.len());
+        prop_assert!(result.dividends.iter().all(|&d| d >= 0.0));
+        prop_assert!(is_distribution_or_zero(&result.dividends), "{:?}", result.dividends);
+    }
+
+    #[test]
+    fn bonds_are_non_negative(epoch in epoch()) {
+        let result = epoch.run();
+        prop_assert_eq!(result.bonds.len(), epoch.stakes.len());
+        for row in &result.bonds {
+            prop_assert!(row.iter().all(|&b| b.is_finite() && b >= 0.0), "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn consensus_is_monotone_in_stake(
+        epoch in epoch(),
+        pick in any::<prop::sample::Index>(),
+        extra in 1u64..100_000,
+    ) {
+        let validator = pick.index(epoch.stakes.len());
+        let before = epoch.run();
+
+        let mut raised = epoch.clone();
+        raised.stakes[validator] += extra;
+        let after = raised.run();
+
+        let own = epoch.normalized_row(validator);
+        for (j, &w) in own.iter().enumerate() {
+            let old = before.consensus_weights[j];
+            let new = after.consensus_weights[j];
+            let (lo, hi) = if w <= old { (w, old) } else { (old, w) };
+            prop_assert!(
+                new >= lo && new <= hi,
+                "coral {}: consensus moved from {} to {}, outside [{}, {}]",
+                j, old, new, lo, hi
+            );
+        }
+    }
+}
@@ -4,6 +4,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::crypto::verify_signature;
+use crate::light;
+
 /// Metadata attached to a Polyp after consensus evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusMetadata {
@@ -36,7 +39,7 @@ pub struct ValidatorScore {
 ///
 /// Each dimension is scored 0.0 to 1.0.
 /// The final score is a weighted combination of all dimensions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolypScores {
     /// Did the ZK proof verify? Binary: 0.0 or 1.0.
     /// A score of 0.0 here causes immediate rejection regardless of other scores.
@@ -52,11 +55,15 @@ pub struct PolypScores {
     /// Embedding quality: cosine similarity between the vector and a reference
     /// embedding generated by the validator's own model instance.
     pub embedding_quality: f64,
+    /// Provenance integrity: how much of the provenance chain checks out
+    /// (see `Provenance::verify`). 0.0 in scores recorded before it existed.
+    #[serde(default)]
+    pub provenance_integrity: f64,
 }
 
 impl PolypScores {
     /// Default dimension weights for computing final score.
-    pub const DEFAULT_WEIGHTS: [f64; 5] = [0.30, 0.25, 0.15, 0.15, 0.15];
+    pub const DEFAULT_WEIGHTS: [f64; 6] = [0.30, 0.25, 0.15, 0.10, 0.15, 0.05];
 
     /// Compute weighted final score.
     pub fn weighted_score(&self) -> f64 {
@@ -66,6 +73,7 @@ impl PolypScores {
             self.novelty,
             self.source_credibility,
             self.embedding_quality,
+            self.provenance_integrity,
         ];
         vals.iter()
             .zip(Self::DEFAULT_WEIGHTS.iter())
@@ -89,6 +97,19 @@ pub struct Attestation {
     pub signature: Vec<u8>,
 }
 
+impl Attestation {
+    /// The bytes the validator signs: polyp_id (16 bytes) || cid (UTF-8) ||
+    /// epoch (u64 little-endian).
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        light::attestation_message(self.polyp_id.as_bytes(), &self.cid, self.epoch)
+    }
+
+    /// Whether `validator` signed this attestation.
+    pub fn verify(&self) -> bool {
+        verify_signature(&self.validator, &self.signing_bytes(), &self.signature).unwrap_or(false)
+    }
+}
+
 /// Lineage information for a hardened Polyp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardeningLineage {
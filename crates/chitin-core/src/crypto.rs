@@ -2,7 +2,6 @@
 
 use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 use rand::rngs::OsRng;
-use sha2::{Digest, Sha256};
 
 use crate::error::ChitinError;
 
@@ -23,6 +22,16 @@ impl Keypair {
         }
     }
 
+    /// Rebuild the keypair of a 32-byte secret key.
+    pub fn from_secret_bytes(secret: &[u8; 32]) -> Self {
+        let signing_key = SigningKey::from_bytes(secret);
+        let verifying_key = signing_key.verifying_key();
+        Keypair {
+            signing_key,
+            verifying_key,
+        }
+    }
+
     /// Get the public key bytes (32 bytes).
     pub fn public_key_bytes(&self) -> [u8; 32] {
         self.verifying_key.to_bytes()
@@ -71,12 +80,7 @@ pub fn verify_signature(
 ///
 /// Returns a 32-byte hash.
 pub fn hash_bytes(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    let result = hasher.finalize();
-    let mut output = [0u8; 32];
-    output.copy_from_slice(&result);
-    output
+    crate::light::sha256(data)
 }
 
 #[cfg(test)]
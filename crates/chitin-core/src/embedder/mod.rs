@@ -0,0 +1,128 @@
+// crates/chitin-core/src/embedder/mod.rs
+//
+// `Embedder` implementations.
+//
+// - `HashEmbedder`: the deterministic built-in hash embedding
+//   (`chitin/hash-embedding-v1`), always available.
+// - `OpenAiEmbedder` (feature `openai`): OpenAI-compatible HTTP APIs
+//   (`POST {base_url}/embeddings`). Inputs are split into batches, and
+//   rate-limited (429) or failed (5xx, connection) requests are retried with
+//   exponential backoff, honoring `Retry-After` when the API sends one.
+// - `OnnxEmbedder` (feature `onnx`): a local ONNX sentence-transformer run
+//   with ONNX Runtime on the CPU or a CUDA GPU, loaded only if the model file
+//   matches its registered weights hash.
+//
+// The daemon's embedding workers, the RPC server's query embedding, and
+// `chitin query --local-embed` all embed through this trait.
+
+use async_trait::async_trait;
+
+use crate::embedding::{hash_embedding, EmbeddingModelId, VectorEmbedding};
+use crate::error::ChitinError;
+use crate::traits::Embedder;
+
+/// Quantization of vectors produced here.
+const QUANTIZATION: &str = "float32";
+
+/// Normalization of vectors produced here.
+const NORMALIZATION: &str = "l2";
+
+/// Wrap `values` as an embedding by `model`, filling in the dimensions if
+/// the model leaves them open and rejecting vectors of the wrong length.
+fn to_embedding(
+    model: &EmbeddingModelId,
+    values: Vec<f32>,
+) -> Result<VectorEmbedding, ChitinError> {
+    let dimensions = values.len() as u32;
+    if model.dimensions != 0 && model.dimensions != dimensions {
+        return Err(ChitinError::InvalidState(format!(
+            "{} produced {} dimensions, expected {}",
+            model.key(),
+            dimensions,
+            model.dimensions
+        )));
+    }
+    Ok(VectorEmbedding {
+        model_id: EmbeddingModelId {
+            dimensions,
+            ..model.clone()
+        },
+        values,
+        quantization: QUANTIZATION.to_string(),
+        normalization: NORMALIZATION.to_string(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Hash embedding
+// ---------------------------------------------------------------------------
+
+/// The deterministic built-in hash embedding.
+///
+/// Vectors have the model's dimensions, or `dimensions` if it leaves them
+/// open.
+#[derive(Debug, Clone)]
+pub struct HashEmbedder {
+    dimensions: usize,
+}
+
+impl HashEmbedder {
+    /// Create a hash embedder defaulting to `dimensions`-dimensional vectors.
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+}
+
+#[async_trait]
+impl Embedder for HashEmbedder {
+    async fn embed(
+        &self,
+        texts: &[&str],
+        model: &EmbeddingModelId,
+    ) -> Result<Vec<VectorEmbedding>, ChitinError> {
+        let dimensions = match model.dimensions {
+            0 => self.dimensions,
+            d => d as usize,
+        };
+        texts
+            .iter()
+            .map(|text| to_embedding(model, hash_embedding(text, dimensions)))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTTP APIs and local models
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "openai")]
+pub use openai::{OpenAiConfig, OpenAiEmbedder};
+
+#[cfg(feature = "openai")]
+mod openai;
+
+#[cfg(feature = "onnx")]
+pub use onnx::{verify_weights, Device, OnnxConfig, OnnxEmbedder, Pooling};
+
+#[cfg(feature = "onnx")]
+pub(crate) mod onnx;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hash_embedder_uses_the_model_dimensions() {
+        let embedder = HashEmbedder::new(8);
+        let open = EmbeddingModelId::from_key(crate::HASH_EMBEDDING_MODEL, 0);
+        let fixed = EmbeddingModelId::from_key(crate::HASH_EMBEDDING_MODEL, 4);
+
+        let embeddings = embedder.embed(&["a", "b"], &open).await.unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert_eq!(embeddings[0].values, hash_embedding("a", 8));
+        assert_eq!(embeddings[0].model_id.dimensions, 8);
+
+        let embeddings = embedder.embed(&["a"], &fixed).await.unwrap();
+        assert_eq!(embeddings[0].values.len(), 4);
+    }
+}
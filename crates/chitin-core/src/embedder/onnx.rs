@@ -0,0 +1,420 @@
+// crates/chitin-core/src/embedder/onnx.rs
+//
+// `OnnxEmbedder`: local inference with an ONNX sentence-transformer (e.g.
+// bge-small-en-v1.5) through ONNX Runtime, on the CPU or a CUDA GPU.
+//
+// The model file is hashed when loaded and must match the `sha256:<hex>`
+// weights hash the model is registered with, so a node cannot publish
+// vectors from weights other than the ones the network agreed on.
+//
+// ONNX Runtime is loaded at run time (`load-dynamic`): point `ORT_DYLIB_PATH`
+// at `libonnxruntime` (a GPU build for `Device::Cuda`). Inputs are tokenized
+// with the model's `tokenizer.json`, truncated to `max_tokens`, and padded
+// per batch; token vectors are pooled (mean or CLS) and L2-normalized.
+
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use ort::execution_providers::{CPUExecutionProvider, CUDAExecutionProvider};
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::Tensor;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::to_embedding;
+use crate::embedding::{EmbeddingModelId, VectorEmbedding};
+use crate::error::ChitinError;
+use crate::traits::Embedder;
+
+/// Where inference runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Device {
+    #[default]
+    Cpu,
+    /// A CUDA GPU, by device ordinal.
+    Cuda(i32),
+}
+
+impl FromStr for Device {
+    type Err = ChitinError;
+
+    /// Parse "cpu", "cuda", or "cuda:<ordinal>".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cpu" => Ok(Self::Cpu),
+            "cuda" => Ok(Self::Cuda(0)),
+            _ => s
+                .strip_prefix("cuda:")
+                .and_then(|n| n.parse().ok())
+                .map(Self::Cuda)
+                .ok_or_else(|| {
+                    ChitinError::InvalidState(format!(
+                        "Unknown device {:?} (expected cpu, cuda, or cuda:N)",
+                        s
+                    ))
+                }),
+        }
+    }
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cpu => write!(f, "cpu"),
+            Self::Cuda(n) => write!(f, "cuda:{}", n),
+        }
+    }
+}
+
+/// How token vectors are reduced to one sentence vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Pooling {
+    /// Mean over non-padding tokens (most sentence-transformers).
+    #[default]
+    Mean,
+    /// The first ([CLS]) token (the BGE family).
+    Cls,
+}
+
+/// Settings for an `OnnxEmbedder`.
+#[derive(Debug, Clone)]
+pub struct OnnxConfig {
+    /// The exported model (`model.onnx`).
+    pub model_path: PathBuf,
+    /// The model's Hugging Face `tokenizer.json`.
+    pub tokenizer_path: PathBuf,
+    /// Registered weights hash of the model file, "sha256:<hex>".
+    pub weights_hash: String,
+    pub device: Device,
+    pub pooling: Pooling,
+    /// Longest input, in tokens; longer inputs are truncated.
+    pub max_tokens: usize,
+    /// Most texts run through the model at once.
+    pub batch_size: usize,
+    /// CPU threads per inference (default: ONNX Runtime's choice).
+    pub threads: Option<usize>,
+}
+
+impl OnnxConfig {
+    /// Settings for the model at `model_path`, with its tokenizer next to it,
+    /// on the CPU with mean pooling.
+    pub fn new(model_path: impl Into<PathBuf>, weights_hash: impl Into<String>) -> Self {
+        let model_path = model_path.into();
+        let tokenizer_path = model_path.with_file_name("tokenizer.json");
+        Self {
+            model_path,
+            tokenizer_path,
+            weights_hash: weights_hash.into(),
+            device: Device::Cpu,
+            pooling: Pooling::Mean,
+            max_tokens: 512,
+            batch_size: 32,
+            threads: None,
+        }
+    }
+}
+
+/// A local ONNX sentence-transformer.
+pub struct OnnxEmbedder {
+    model: Arc<LoadedModel>,
+    batch_size: usize,
+}
+
+/// A loaded model and tokenizer, shared with blocking inference tasks.
+struct LoadedModel {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    pooling: Pooling,
+    /// Whether the model takes `token_type_ids` (BERT does, others not).
+    type_ids: bool,
+}
+
+impl OnnxEmbedder {
+    /// Verify the model file against its weights hash, then load it and its
+    /// tokenizer.
+    pub fn load(config: OnnxConfig) -> Result<Self, ChitinError> {
+        if config.batch_size == 0 || config.max_tokens == 0 {
+            return Err(ChitinError::InvalidState(
+                "batch_size and max_tokens must be positive".to_string(),
+            ));
+        }
+        verify_weights(&config.model_path, &config.weights_hash)?;
+
+        let mut tokenizer = Tokenizer::from_file(&config.tokenizer_path).map_err(|e| {
+            ChitinError::InvalidState(format!(
+                "Failed to load tokenizer {}: {}",
+                config.tokenizer_path.display(),
+                e
+            ))
+        })?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: config.max_tokens,
+                ..TruncationParams::default()
+            }))
+            .map_err(|e| ChitinError::InvalidState(format!("Tokenizer truncation: {}", e)))?;
+
+        let session =
+            build_session(&config.model_path, config.device, config.threads).map_err(|e| {
+                ChitinError::InvalidState(format!(
+                    "Failed to load {} on {}: {}",
+                    config.model_path.display(),
+                    config.device,
+                    e
+                ))
+            })?;
+        let type_ids = session.inputs.iter().any(|i| i.name == "token_type_ids");
+        Ok(Self {
+            model: Arc::new(LoadedModel {
+                session: Mutex::new(session),
+                tokenizer,
+                pooling: config.pooling,
+                type_ids,
+            }),
+            batch_size: config.batch_size,
+        })
+    }
+}
+
+impl LoadedModel {
+    /// Tokenize and run one batch, returning one pooled, normalized vector
+    /// per text. Blocks on inference.
+    fn run_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ChitinError> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| ChitinError::InvalidState(format!("Tokenization failed: {}", e)))?;
+        let batch = encodings.len();
+        let seq = encodings.first().map_or(0, |e| e.len());
+        let shape = vec![batch as i64, seq as i64];
+        let column = |f: fn(&tokenizers::Encoding) -> &[u32]| -> Vec<i64> {
+            encodings
+                .iter()
+                .flat_map(|e| f(e).iter().map(|&v| v as i64))
+                .collect()
+        };
+        let mask = column(tokenizers::Encoding::get_attention_mask);
+
+        let tensor =
+            |data: Vec<i64>| Tensor::from_array((shape.clone(), data)).map_err(inference_error);
+        let mut inputs = vec![
+            ("input_ids", tensor(column(tokenizers::Encoding::get_ids))?),
+            ("attention_mask", tensor(mask.clone())?),
+        ];
+        if self.type_ids {
+            inputs.push((
+                "token_type_ids",
+                tensor(column(tokenizers::Encoding::get_type_ids))?,
+            ));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| ChitinError::InvalidState("ONNX session poisoned".to_string()))?;
+        let outputs = session.run(inputs).map_err(inference_error)?;
+        let (dims, values) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(inference_error)?;
+        match **dims {
+            // Token vectors: [batch, seq, hidden].
+            [b, s, hidden] if b as usize == batch && s as usize == seq => Ok(pool(
+                values,
+                &mask,
+                batch,
+                seq,
+                hidden as usize,
+                self.pooling,
+            )),
+            // Already pooled: [batch, hidden].
+            [b, hidden] if b as usize == batch => Ok(values
+                .chunks(hidden as usize)
+                .map(|v| normalize(v.to_vec()))
+                .collect()),
+            _ => Err(ChitinError::InvalidState(format!(
+                "Unexpected model output shape {:?} for {} inputs",
+                &**dims, batch
+            ))),
+        }
+    }
+}
+
+/// Load the model at `model_path` on `device`, optimized for inference.
+pub(crate) fn build_session(
+    model_path: &Path,
+    device: Device,
+    threads: Option<usize>,
+) -> ort::Result<Session> {
+    let provider = match device {
+        Device::Cpu => CPUExecutionProvider::default().build(),
+        Device::Cuda(device) => CUDAExecutionProvider::default()
+            .with_device_id(device)
+            .build()
+            .error_on_failure(),
+    };
+    let mut builder = Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .with_execution_providers([provider])?;
+    if let Some(threads) = threads {
+        builder = builder.with_intra_threads(threads)?;
+    }
+    builder.commit_from_file(model_path)
+}
+
+pub(crate) fn inference_error(e: ort::Error) -> ChitinError {
+    ChitinError::InvalidState(format!("ONNX inference failed: {}", e))
+}
+
+/// Check that the file at `path` hashes to `expected` ("sha256:<hex>").
+pub fn verify_weights(path: &Path, expected: &str) -> Result<(), ChitinError> {
+    let expected_hex = expected.strip_prefix("sha256:").ok_or_else(|| {
+        ChitinError::Verification(format!(
+            "Weights hash {:?} is not of the form sha256:<hex>",
+            expected
+        ))
+    })?;
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| ChitinError::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| {
+            ChitinError::Storage(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let actual: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if !actual.eq_ignore_ascii_case(expected_hex) {
+        return Err(ChitinError::Verification(format!(
+            "{} has weights hash sha256:{}, expected {}",
+            path.display(),
+            actual,
+            expected
+        )));
+    }
+    Ok(())
+}
+
+/// Pool `[batch, seq, hidden]` token vectors into L2-normalized sentence
+/// vectors, ignoring padding (`mask` 0) for mean pooling.
+fn pool(
+    values: &[f32],
+    mask: &[i64],
+    batch: usize,
+    seq: usize,
+    hidden: usize,
+    pooling: Pooling,
+) -> Vec<Vec<f32>> {
+    (0..batch)
+        .map(|b| {
+            let tokens = &values[b * seq * hidden..(b + 1) * seq * hidden];
+            let vector = match pooling {
+                Pooling::Cls => tokens[..hidden].to_vec(),
+                Pooling::Mean => {
+                    let mut sum = vec![0.0f32; hidden];
+                    let mut count = 0.0f32;
+                    for (token, &m) in tokens.chunks(hidden).zip(&mask[b * seq..(b + 1) * seq]) {
+                        if m != 0 {
+                            sum.iter_mut().zip(token).for_each(|(s, v)| *s += v);
+                            count += 1.0;
+                        }
+                    }
+                    sum.iter().map(|s| s / count.max(1.0)).collect()
+                }
+            };
+            normalize(vector)
+        })
+        .collect()
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+#[async_trait]
+impl Embedder for OnnxEmbedder {
+    async fn embed(
+        &self,
+        texts: &[&str],
+        model: &EmbeddingModelId,
+    ) -> Result<Vec<VectorEmbedding>, ChitinError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            let batch: Vec<String> = batch.iter().map(|t| t.to_string()).collect();
+            let loaded = self.model.clone();
+            let vectors = tokio::task::spawn_blocking(move || loaded.run_batch(&batch))
+                .await
+                .map_err(|e| {
+                    ChitinError::InvalidState(format!("Inference task failed: {}", e))
+                })??;
+            for values in vectors {
+                embeddings.push(to_embedding(model, values)?);
+            }
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn devices_parse_and_display() {
+        assert_eq!("cpu".parse::<Device>().unwrap(), Device::Cpu);
+        assert_eq!("cuda".parse::<Device>().unwrap(), Device::Cuda(0));
+        assert_eq!("cuda:2".parse::<Device>().unwrap().to_string(), "cuda:2");
+        assert!("tpu".parse::<Device>().is_err());
+    }
+
+    #[test]
+    fn mean_pooling_skips_padding_and_cls_takes_the_first_token() {
+        // Two sequences of three 2-d tokens; the second has one pad token.
+        let values = [
+            1.0, 0.0, 3.0, 0.0, 2.0, 0.0, //
+            0.0, 4.0, 0.0, 2.0, 9.0, 9.0,
+        ];
+        let mask = [1, 1, 1, 1, 1, 0];
+        let mean = pool(&values, &mask, 2, 3, 2, Pooling::Mean);
+        assert_eq!(mean, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        let cls = pool(&values, &mask, 2, 3, 2, Pooling::Cls);
+        assert_eq!(cls, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        assert_eq!(normalize(vec![3.0, 4.0]), vec![0.6, 0.8]);
+    }
+
+    #[test]
+    fn weights_must_match_the_registered_hash() {
+        let path = std::env::temp_dir().join(format!("chitin-onnx-{}.bin", std::process::id()));
+        std::fs::write(&path, b"weights").unwrap();
+        let hash: String = Sha256::digest(b"weights")
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        assert!(verify_weights(&path, &format!("sha256:{}", hash)).is_ok());
+        assert!(verify_weights(&path, &format!("sha256:{}", hash.to_uppercase())).is_ok());
+        assert!(verify_weights(&path, "sha256:e5f6g7h8").is_err());
+        assert!(verify_weights(&path, &hash).is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(verify_weights(&path, &format!("sha256:{}", hash)).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+// crates/chitin-core/src/embedder/openai.rs
+//
+// `OpenAiEmbedder`: OpenAI-compatible embeddings APIs
+// (`POST {base_url}/embeddings`). Inputs are split into batches, and
+// rate-limited (429) or failed (5xx, connection) requests are retried with
+// exponential backoff, honoring `Retry-After` when the API sends one.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use super::to_embedding;
+use crate::embedding::{EmbeddingModelId, VectorEmbedding};
+use crate::error::ChitinError;
+use crate::traits::Embedder;
+
+/// Settings for an `OpenAiEmbedder`.
+#[derive(Debug, Clone)]
+pub struct OpenAiConfig {
+    /// API base URL, without the `/embeddings` suffix.
+    pub base_url: String,
+    /// Bearer token, if the API needs one.
+    pub api_key: Option<String>,
+    /// Model name sent to the API. Defaults to the name part of the
+    /// model being embedded with ("text-embedding-3-small" for
+    /// "openai/text-embedding-3-small").
+    pub model: Option<String>,
+    /// Requested output dimensions, if the API supports choosing them.
+    pub dimensions: Option<u32>,
+    /// Most texts sent in one request.
+    pub batch_size: usize,
+    /// Retries of a rate-limited or failed request before giving up.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further one.
+    pub initial_backoff: Duration,
+    /// Longest delay between retries, including `Retry-After`.
+    pub max_backoff: Duration,
+    /// Per-request timeout.
+    pub timeout: Duration,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.openai.com/v1".to_string(),
+            api_key: None,
+            model: None,
+            dimensions: None,
+            batch_size: 128,
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// An OpenAI-compatible embeddings API.
+pub struct OpenAiEmbedder {
+    client: reqwest::Client,
+    /// Full `/embeddings` endpoint URL.
+    url: String,
+    config: OpenAiConfig,
+}
+
+impl OpenAiEmbedder {
+    /// Create an embedder for the API described by `config`.
+    pub fn new(config: OpenAiConfig) -> Result<Self, ChitinError> {
+        if config.batch_size == 0 {
+            return Err(ChitinError::InvalidState(
+                "batch_size must be positive".to_string(),
+            ));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            url: format!("{}/embeddings", config.base_url.trim_end_matches('/')),
+            config,
+        })
+    }
+
+    /// Full `/embeddings` endpoint URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Embed one batch, retrying rate limits and transient failures.
+    async fn embed_batch(&self, texts: &[&str], model: &str) -> Result<Vec<Vec<f32>>, ChitinError> {
+        let mut body = serde_json::json!({ "model": model, "input": texts });
+        if let Some(dimensions) = self.config.dimensions {
+            body["dimensions"] = serde_json::json!(dimensions);
+        }
+        let mut attempt = 0;
+        loop {
+            let (error, retry_after) = match self.request(&body).await? {
+                Attempt::Done(vectors) => return self.check(vectors, texts.len()),
+                Attempt::Retry { error, retry_after } => (error, retry_after),
+            };
+            if attempt >= self.config.max_retries {
+                return Err(ChitinError::Network(format!(
+                    "{} (gave up after {} retries)",
+                    error, attempt
+                )));
+            }
+            let delay = backoff(&self.config, attempt, retry_after);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Send one request.
+    async fn request(&self, body: &serde_json::Value) -> Result<Attempt, ChitinError> {
+        #[derive(Deserialize)]
+        struct EmbeddingData {
+            index: usize,
+            embedding: Vec<f32>,
+        }
+        #[derive(Deserialize)]
+        struct EmbeddingsResponse {
+            data: Vec<EmbeddingData>,
+        }
+
+        let mut request = self.client.post(&self.url).json(body);
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(Attempt::Retry {
+                    error: format!("HTTP error: {}", e),
+                    retry_after: None,
+                })
+            }
+        };
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after);
+            let text = response.text().await.unwrap_or_default();
+            let error = format!("{} returned {}: {}", self.url, status, text);
+            if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                return Ok(Attempt::Retry { error, retry_after });
+            }
+            return Err(ChitinError::Network(error));
+        }
+        let mut parsed: EmbeddingsResponse = response
+            .json()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to parse response: {}", e)))?;
+        parsed.data.sort_by_key(|d| d.index);
+        Ok(Attempt::Done(
+            parsed.data.into_iter().map(|d| d.embedding).collect(),
+        ))
+    }
+
+    fn check(&self, vectors: Vec<Vec<f32>>, inputs: usize) -> Result<Vec<Vec<f32>>, ChitinError> {
+        if vectors.len() != inputs {
+            return Err(ChitinError::Network(format!(
+                "{} returned {} embeddings for {} inputs",
+                self.url,
+                vectors.len(),
+                inputs
+            )));
+        }
+        Ok(vectors)
+    }
+}
+
+/// Outcome of one request.
+enum Attempt {
+    Done(Vec<Vec<f32>>),
+    Retry {
+        error: String,
+        retry_after: Option<Duration>,
+    },
+}
+
+/// Delay before retry number `attempt` (from 0): the API's
+/// `Retry-After` if it sent one, else exponential backoff, capped at
+/// `max_backoff` either way.
+fn backoff(config: &OpenAiConfig, attempt: u32, retry_after: Option<Duration>) -> Duration {
+    let delay =
+        retry_after.unwrap_or_else(|| config.initial_backoff.saturating_mul(1 << attempt.min(16)));
+    delay.min(config.max_backoff)
+}
+
+/// A `Retry-After` value in (possibly fractional) seconds. HTTP dates
+/// are not supported and fall back to backoff.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(
+        &self,
+        texts: &[&str],
+        model: &EmbeddingModelId,
+    ) -> Result<Vec<VectorEmbedding>, ChitinError> {
+        let api_model = self.config.model.as_deref().unwrap_or(&model.name);
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.config.batch_size) {
+            for values in self.embed_batch(batch, api_model).await? {
+                embeddings.push(to_embedding(model, values)?);
+            }
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve `/embeddings`: the first `rate_limited` requests get a 429,
+    /// the rest one 2-dimensional vector per input, [index, batch size].
+    async fn mock_api(rate_limited: usize) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 65536];
+                let mut len = 0;
+                let body = loop {
+                    len += socket.read(&mut buf[len..]).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..len]).to_string();
+                    if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length: "))
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let response = if n < rate_limited {
+                    "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\n\
+                     content-length: 4\r\nconnection: close\r\n\r\nslow"
+                        .to_string()
+                } else {
+                    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+                    let inputs = body["input"].as_array().unwrap().len();
+                    let data: Vec<_> = (0..inputs)
+                        .rev()
+                        .map(|i| serde_json::json!({ "index": i, "embedding": [i, inputs] }))
+                        .collect();
+                    let json = serde_json::json!({ "data": data }).to_string();
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{}",
+                        json.len(),
+                        json
+                    )
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn config(base_url: String, max_retries: u32) -> OpenAiConfig {
+        OpenAiConfig {
+            base_url,
+            batch_size: 2,
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            ..OpenAiConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn batches_in_order_and_retries_rate_limits() {
+        let (url, requests) = mock_api(2).await;
+        let embedder = OpenAiEmbedder::new(config(url, 3)).unwrap();
+        let model = EmbeddingModelId::from_key("openai/small", 2);
+
+        let embeddings = embedder.embed(&["a", "b", "c"], &model).await.unwrap();
+        let values: Vec<Vec<f32>> = embeddings.iter().map(|e| e.values.clone()).collect();
+        assert_eq!(values, vec![vec![0.0, 2.0], vec![1.0, 2.0], vec![0.0, 1.0]]);
+        assert_eq!(embeddings[0].model_id.key(), "openai/small");
+        // Two rate-limited attempts, then one request per batch.
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let (url, requests) = mock_api(usize::MAX).await;
+        let embedder = OpenAiEmbedder::new(config(url, 2)).unwrap();
+        let model = EmbeddingModelId::from_key("openai/small", 0);
+
+        let err = embedder.embed(&["a"], &model).await.unwrap_err();
+        assert!(err.to_string().contains("429"), "{}", err);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn backoff_doubles_and_honors_retry_after_up_to_the_cap() {
+        let config = OpenAiConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..OpenAiConfig::default()
+        };
+        assert_eq!(backoff(&config, 0, None), Duration::from_secs(1));
+        assert_eq!(backoff(&config, 2, None), Duration::from_secs(4));
+        assert_eq!(backoff(&config, 3, None), Duration::from_secs(5));
+        let retry_after = parse_retry_after(" 2.5 ");
+        assert_eq!(
+            backoff(&config, 3, retry_after),
+            Duration::from_millis(2500)
+        );
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT"), None);
+    }
+}
@@ -15,6 +15,78 @@ pub struct EmbeddingModelId {
     pub dimensions: u32,
 }
 
+impl EmbeddingModelId {
+    /// The model's space key, `"{provider}/{name}"` (e.g. "bge/bge-small-en-v1.5").
+    /// Vectors are indexed and alignments stored under this key.
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.provider, self.name)
+    }
+
+    /// Parse a space key back into a model ID with the given dimensions and
+    /// an unknown weights hash. A key without `/` is taken as the name.
+    pub fn from_key(key: &str, dimensions: u32) -> Self {
+        let (provider, name) = key.split_once('/').unwrap_or(("", key));
+        Self {
+            provider: provider.to_string(),
+            name: name.to_string(),
+            weights_hash: [0u8; 32],
+            dimensions,
+        }
+    }
+}
+
+/// A specific version of an embedding model and its lifecycle on the network.
+///
+/// A version is active from `activated_at_epoch`. Once deprecated, Polyps
+/// embedded under it are molted to its successor; after `molt_deadline_epoch`
+/// it is retired and new submissions under it are rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelVersion {
+    /// The model identifier (e.g., "bge/bge-small-en-v1.5").
+    pub model_id: String,
+    /// Sequential version number for this model.
+    pub version: u32,
+    /// The epoch at which this model version was activated on the network.
+    pub activated_at_epoch: u64,
+    /// The epoch from which the version is deprecated, if scheduled.
+    #[serde(default)]
+    pub deprecated_at_epoch: Option<u64>,
+    /// The epoch by which its Polyps must be molted; retired from then on.
+    #[serde(default)]
+    pub molt_deadline_epoch: Option<u64>,
+}
+
+/// Lifecycle status of a model version at a given epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModelStatus {
+    /// Registered but not yet activated.
+    Pending,
+    /// Accepted for new submissions.
+    Active,
+    /// Still accepted, but its Polyps are being molted to a successor.
+    Deprecated,
+    /// Past its molt deadline; no longer accepted.
+    Retired,
+}
+
+impl ModelVersion {
+    /// Status of this version at `epoch`.
+    pub fn status_at(&self, epoch: u64) -> ModelStatus {
+        if epoch < self.activated_at_epoch {
+            ModelStatus::Pending
+        } else if self.molt_deadline_epoch.is_some_and(|d| epoch >= d) {
+            ModelStatus::Retired
+        } else if self.deprecated_at_epoch.is_some_and(|d| epoch >= d) {
+            ModelStatus::Deprecated
+        } else {
+            ModelStatus::Active
+        }
+    }
+}
+
+/// Model ID of vectors produced by `hash_embedding` itself.
+pub const HASH_EMBEDDING_MODEL: &str = "chitin/hash-embedding-v1";
+
 /// Deterministic pseudo-embedding: hash text + dimension index to produce a
 /// reproducible float vector, then L2-normalize. Identical text always yields
 /// an identical vector (cosine similarity ~1.0). No ML model required.
@@ -2,6 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::crypto::{sign_message, verify_signature};
+use crate::error::ChitinError;
+
+/// Domain separator prefixed to a hotkey rotation's signing bytes.
+const ROTATION_DOMAIN: &[u8] = b"chitin-hotkey-rotation-v1";
+
 /// Identity of a node on the Chitin network.
 ///
 /// Follows the coldkey/hotkey pattern:
@@ -65,6 +71,70 @@ pub enum NodeType {
     Tide,
     /// Both producer and validator (allowed in Phase 1, restricted later).
     Hybrid,
+    /// Bootstrap node: serves discovery, peer exchange, and metagraph and
+    /// checkpoint state, but neither produces nor validates Polyps.
+    Seed,
+}
+
+/// A coldkey-signed certificate moving a node's operational authority from
+/// one hotkey to another.
+///
+/// Peers that hold a rotation stop accepting `old_hotkey` signatures for the
+/// coldkey's identity once they have checked it with [`HotkeyRotation::verify`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyRotation {
+    /// Coldkey public key that authorizes the rotation.
+    pub coldkey: [u8; 32],
+    /// Hotkey being retired.
+    pub old_hotkey: [u8; 32],
+    /// Hotkey taking over.
+    pub new_hotkey: [u8; 32],
+    /// Unix time (seconds) at which the rotation was issued.
+    pub issued_at: i64,
+    /// 64-byte ed25519 signature by the coldkey over the signing bytes.
+    pub signature: Vec<u8>,
+}
+
+impl HotkeyRotation {
+    /// Issue a rotation signed with `coldkey_secret`.
+    pub fn sign(
+        coldkey_secret: &[u8; 32],
+        old_hotkey: [u8; 32],
+        new_hotkey: [u8; 32],
+        issued_at: i64,
+    ) -> Result<Self, ChitinError> {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(coldkey_secret);
+        let mut rotation = Self {
+            coldkey: signing_key.verifying_key().to_bytes(),
+            old_hotkey,
+            new_hotkey,
+            issued_at,
+            signature: Vec::new(),
+        };
+        rotation.signature = sign_message(coldkey_secret, &rotation.signing_bytes())?;
+        Ok(rotation)
+    }
+
+    /// The bytes the coldkey signs.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ROTATION_DOMAIN.len() + 104);
+        bytes.extend_from_slice(ROTATION_DOMAIN);
+        bytes.extend_from_slice(&self.coldkey);
+        bytes.extend_from_slice(&self.old_hotkey);
+        bytes.extend_from_slice(&self.new_hotkey);
+        bytes.extend_from_slice(&self.issued_at.to_le_bytes());
+        bytes
+    }
+
+    /// Whether the coldkey signed this rotation.
+    pub fn verify(&self) -> bool {
+        verify_signature(&self.coldkey, &self.signing_bytes(), &self.signature).unwrap_or(false)
+    }
+
+    /// DID of the identity whose hotkey rotates.
+    pub fn did(&self) -> String {
+        NodeIdentity::derive_did(&self.coldkey)
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +178,25 @@ mod tests {
         let real = NodeIdentity::from_keypairs([1u8; 32], [2u8; 32], NodeType::Coral);
         assert!(!real.is_placeholder());
     }
+
+    #[test]
+    fn test_hotkey_rotation_verifies_only_untampered() {
+        let coldkey = crate::crypto::Keypair::generate();
+        let secret = coldkey.signing_key.to_bytes();
+        let rotation = HotkeyRotation::sign(&secret, [1u8; 32], [2u8; 32], 1_700_000_000).unwrap();
+        assert_eq!(rotation.coldkey, coldkey.public_key_bytes());
+        assert_eq!(
+            rotation.did(),
+            NodeIdentity::derive_did(&coldkey.public_key_bytes())
+        );
+        assert!(rotation.verify());
+
+        let mut tampered = rotation.clone();
+        tampered.new_hotkey = [3u8; 32];
+        assert!(!tampered.verify());
+
+        let mut forged = rotation;
+        forged.coldkey = crate::crypto::Keypair::generate().public_key_bytes();
+        assert!(!forged.verify());
+    }
 }
@@ -0,0 +1,285 @@
+// crates/chitin-core/src/keystore.rs
+//
+// Passphrase-encrypted keystores for ed25519 secret keys.
+//
+// A keystore is a JSON document holding a 32-byte secret key sealed with
+// ChaCha20-Poly1305 under a key derived from the passphrase with
+// PBKDF2-HMAC-SHA256. The public key is stored in the clear (and bound to
+// the ciphertext as associated data) so a keystore can be identified
+// without unlocking it. Decrypted keys are returned in `Zeroizing` buffers
+// that wipe themselves when dropped.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+pub use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::ChitinError;
+
+/// A 32-byte secret key that is wiped when dropped.
+pub type SecretKey = Zeroizing<[u8; 32]>;
+
+/// Current keystore format version.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Key derivation function identifier.
+pub const KDF_PBKDF2_SHA256: &str = "pbkdf2-hmac-sha256";
+
+/// Cipher identifier.
+pub const CIPHER_CHACHA20_POLY1305: &str = "chacha20poly1305";
+
+/// Default PBKDF2 iteration count for new keystores.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+/// An encrypted ed25519 secret key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    /// Format version.
+    pub version: u32,
+    /// Hex-encoded public key of the sealed secret.
+    pub public_key: String,
+    /// Key derivation function (`pbkdf2-hmac-sha256`).
+    pub kdf: String,
+    /// PBKDF2 iteration count.
+    pub iterations: u32,
+    /// Hex-encoded KDF salt.
+    pub salt: String,
+    /// Cipher (`chacha20poly1305`).
+    pub cipher: String,
+    /// Hex-encoded cipher nonce.
+    pub nonce: String,
+    /// Hex-encoded ciphertext and authentication tag.
+    pub ciphertext: String,
+}
+
+impl EncryptedKeystore {
+    /// Seal `secret` under `passphrase` with the default iteration count.
+    pub fn encrypt(secret: &[u8; 32], passphrase: &[u8]) -> Result<Self, ChitinError> {
+        Self::encrypt_with_iterations(secret, passphrase, DEFAULT_KDF_ITERATIONS)
+    }
+
+    /// Seal `secret` under `passphrase` with `iterations` PBKDF2 rounds.
+    pub fn encrypt_with_iterations(
+        secret: &[u8; 32],
+        passphrase: &[u8],
+        iterations: u32,
+    ) -> Result<Self, ChitinError> {
+        if iterations == 0 {
+            return Err(ChitinError::Crypto(
+                "KDF iterations must be positive".to_string(),
+            ));
+        }
+        let public_key = SigningKey::from_bytes(secret).verifying_key().to_bytes();
+
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+
+        let key = derive_key(passphrase, &salt, iterations);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_slice()));
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: secret,
+                    aad: &public_key,
+                },
+            )
+            .map_err(|_| ChitinError::Crypto("Keystore encryption failed".to_string()))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            public_key: hex_encode(&public_key),
+            kdf: KDF_PBKDF2_SHA256.to_string(),
+            iterations,
+            salt: hex_encode(&salt),
+            cipher: CIPHER_CHACHA20_POLY1305.to_string(),
+            nonce: hex_encode(&nonce),
+            ciphertext: hex_encode(&ciphertext),
+        })
+    }
+
+    /// Recover the secret key with `passphrase`.
+    ///
+    /// Fails on a wrong passphrase, a tampered keystore, or a secret that
+    /// does not match the stored public key.
+    pub fn decrypt(&self, passphrase: &[u8]) -> Result<SecretKey, ChitinError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(ChitinError::Crypto(format!(
+                "Unsupported keystore version {}",
+                self.version
+            )));
+        }
+        if self.kdf != KDF_PBKDF2_SHA256 || self.cipher != CIPHER_CHACHA20_POLY1305 {
+            return Err(ChitinError::Crypto(format!(
+                "Unsupported keystore scheme {}/{}",
+                self.kdf, self.cipher
+            )));
+        }
+        let public_key = self.public_key_bytes()?;
+        let salt = hex_field("salt", &self.salt)?;
+        let nonce = hex_field("nonce", &self.nonce)?;
+        if nonce.len() != 12 {
+            return Err(ChitinError::Crypto(
+                "Keystore nonce must be 12 bytes".to_string(),
+            ));
+        }
+        let ciphertext = hex_field("ciphertext", &self.ciphertext)?;
+
+        let key = derive_key(passphrase, &salt, self.iterations);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key.as_slice()));
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: &public_key,
+                    },
+                )
+                .map_err(|_| {
+                    ChitinError::Crypto("Wrong passphrase or corrupted keystore".to_string())
+                })?,
+        );
+        if plaintext.len() != 32 {
+            return Err(ChitinError::Crypto(
+                "Keystore secret must be 32 bytes".to_string(),
+            ));
+        }
+
+        let mut secret = Zeroizing::new([0u8; 32]);
+        secret.copy_from_slice(&plaintext);
+        if SigningKey::from_bytes(&secret).verifying_key().to_bytes() != public_key {
+            return Err(ChitinError::Crypto(
+                "Keystore secret does not match its public key".to_string(),
+            ));
+        }
+        Ok(secret)
+    }
+
+    /// The public key of the sealed secret.
+    pub fn public_key_bytes(&self) -> Result<[u8; 32], ChitinError> {
+        let bytes = hex_field("public_key", &self.public_key)?;
+        bytes
+            .try_into()
+            .map_err(|_| ChitinError::Crypto("Keystore public key must be 32 bytes".to_string()))
+    }
+
+    /// Parse a keystore from its JSON form.
+    pub fn from_json(json: &str) -> Result<Self, ChitinError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize the keystore as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, ChitinError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Whether `contents` of a key file look like a keystore rather than a
+    /// plaintext hex secret.
+    pub fn is_keystore(contents: &str) -> bool {
+        contents.trim_start().starts_with('{')
+    }
+}
+
+/// Derive a 32-byte cipher key with PBKDF2-HMAC-SHA256 (one output block).
+fn derive_key(passphrase: &[u8], salt: &[u8], iterations: u32) -> Zeroizing<[u8; 32]> {
+    let prf =
+        <Hmac<Sha256> as Mac>::new_from_slice(passphrase).expect("HMAC accepts any key length");
+
+    let mut mac = prf.clone();
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut block = Zeroizing::new([0u8; 32]);
+    block.copy_from_slice(&mac.finalize().into_bytes());
+
+    let mut output = block.clone();
+    for _ in 1..iterations {
+        let mut mac = prf.clone();
+        mac.update(block.as_slice());
+        block.copy_from_slice(&mac.finalize().into_bytes());
+        for (out, b) in output.iter_mut().zip(block.iter()) {
+            *out ^= b;
+        }
+    }
+    output
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_field(name: &str, hex: &str) -> Result<Vec<u8>, ChitinError> {
+    let invalid = || ChitinError::Crypto(format!("Keystore {} is not valid hex", name));
+    if !hex.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::Keypair;
+
+    #[test]
+    fn test_derive_key_matches_pbkdf2_vectors() {
+        assert_eq!(
+            hex_encode(derive_key(b"passwd", b"salt", 1).as_slice()),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc"
+        );
+        assert_eq!(
+            hex_encode(derive_key(b"password", b"salt", 4096).as_slice()),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+    }
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let keypair = Keypair::generate();
+        let secret = keypair.signing_key.to_bytes();
+
+        let keystore = EncryptedKeystore::encrypt_with_iterations(&secret, b"hunter2", 16).unwrap();
+        assert_eq!(
+            keystore.public_key_bytes().unwrap(),
+            keypair.public_key_bytes()
+        );
+        assert!(!keystore.ciphertext.contains(&hex_encode(&secret)));
+
+        let json = keystore.to_json().unwrap();
+        assert!(EncryptedKeystore::is_keystore(&json));
+        assert!(!EncryptedKeystore::is_keystore(&hex_encode(&secret)));
+
+        let parsed = EncryptedKeystore::from_json(&json).unwrap();
+        assert_eq!(*parsed.decrypt(b"hunter2").unwrap(), secret);
+    }
+
+    #[test]
+    fn test_keystore_wrong_passphrase() {
+        let secret = Keypair::generate().signing_key.to_bytes();
+        let keystore = EncryptedKeystore::encrypt_with_iterations(&secret, b"right", 16).unwrap();
+        assert!(keystore.decrypt(b"wrong").is_err());
+    }
+
+    #[test]
+    fn test_keystore_rejects_swapped_public_key() {
+        let secret = Keypair::generate().signing_key.to_bytes();
+        let mut keystore = EncryptedKeystore::encrypt_with_iterations(&secret, b"pw", 16).unwrap();
+        keystore.public_key = hex_encode(&Keypair::generate().public_key_bytes());
+        assert!(keystore.decrypt(b"pw").is_err());
+    }
+}
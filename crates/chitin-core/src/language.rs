@@ -0,0 +1,207 @@
+// crates/chitin-core/src/language.rs
+//
+// Language detection and language tags for Polyp payloads.
+//
+// `Payload::language` holds an ISO 639-1 code ("en", "es"). Callers often
+// leave it out or get it wrong, so submissions detect the language of the
+// content (trigram statistics via `whatlang`) and use the detected code
+// when the detection is reliable, keeping the declared one otherwise.
+// Tags are compared by primary subtag: "en-US" matches "en".
+
+use crate::error::ChitinError;
+
+/// A detected content language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code.
+    pub code: String,
+    /// Detector confidence in [0.0, 1.0].
+    pub confidence: f64,
+    /// Whether the detection is confident enough to override a declared
+    /// language. Short texts rarely are.
+    pub reliable: bool,
+}
+
+/// ISO 639-3 codes `whatlang` detects, with their ISO 639-1 codes.
+const ISO_639_3_TO_1: &[(&str, &str)] = &[
+    ("afr", "af"),
+    ("aka", "ak"),
+    ("amh", "am"),
+    ("ara", "ar"),
+    ("aze", "az"),
+    ("bel", "be"),
+    ("ben", "bn"),
+    ("bul", "bg"),
+    ("cat", "ca"),
+    ("ces", "cs"),
+    ("cmn", "zh"),
+    ("cym", "cy"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ell", "el"),
+    ("eng", "en"),
+    ("epo", "eo"),
+    ("est", "et"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("guj", "gu"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hrv", "hr"),
+    ("hun", "hu"),
+    ("hye", "hy"),
+    ("ind", "id"),
+    ("ita", "it"),
+    ("jav", "jv"),
+    ("jpn", "ja"),
+    ("kan", "kn"),
+    ("kat", "ka"),
+    ("khm", "km"),
+    ("kor", "ko"),
+    ("lat", "la"),
+    ("lav", "lv"),
+    ("lit", "lt"),
+    ("mal", "ml"),
+    ("mar", "mr"),
+    ("mkd", "mk"),
+    ("mya", "my"),
+    ("nep", "ne"),
+    ("nld", "nl"),
+    ("nob", "nb"),
+    ("ori", "or"),
+    ("pan", "pa"),
+    ("pes", "fa"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rus", "ru"),
+    ("sin", "si"),
+    ("slk", "sk"),
+    ("slv", "sl"),
+    ("sna", "sn"),
+    ("spa", "es"),
+    ("srp", "sr"),
+    ("swe", "sv"),
+    ("tam", "ta"),
+    ("tel", "te"),
+    ("tgl", "tl"),
+    ("tha", "th"),
+    ("tuk", "tk"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("urd", "ur"),
+    ("uzb", "uz"),
+    ("vie", "vi"),
+    ("yid", "yi"),
+    ("zul", "zu"),
+];
+
+/// Detect the language of `text`, if it has enough letters to guess.
+pub fn detect_language(text: &str) -> Option<DetectedLanguage> {
+    let info = whatlang::detect(text)?;
+    let code = info.lang().code();
+    let code = ISO_639_3_TO_1
+        .iter()
+        .find(|(three, _)| *three == code)
+        .map_or(code, |(_, one)| one);
+    Some(DetectedLanguage {
+        code: code.to_string(),
+        confidence: info.confidence(),
+        reliable: info.is_reliable(),
+    })
+}
+
+/// Normalize a language tag to its lowercase primary subtag, mapping ISO
+/// 639-3 codes to ISO 639-1 where one exists ("en-US" and "eng" become
+/// "en").
+///
+/// Fails unless the primary subtag is two or three ASCII letters.
+pub fn normalize_language_tag(tag: &str) -> Result<String, ChitinError> {
+    let primary = tag.trim().split(['-', '_']).next().unwrap_or_default();
+    if !(2..=3).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ChitinError::InvalidState(format!(
+            "Invalid language tag '{}': expected an ISO 639 code like \"en\"",
+            tag
+        )));
+    }
+    let primary = primary.to_ascii_lowercase();
+    Ok(ISO_639_3_TO_1
+        .iter()
+        .find(|(three, _)| *three == primary)
+        .map_or(primary, |(_, one)| one.to_string()))
+}
+
+/// Whether two language tags name the same language.
+pub fn languages_match(a: &str, b: &str) -> bool {
+    match (normalize_language_tag(a), normalize_language_tag(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The language to record for `content` declared as `declared`: a
+/// reliable detection wins, then the declared tag (normalized), then an
+/// unreliable detection.
+///
+/// Fails if `declared` is not a valid tag.
+pub fn resolve_language(
+    declared: Option<&str>,
+    content: &str,
+) -> Result<Option<String>, ChitinError> {
+    let declared = declared.map(normalize_language_tag).transpose()?;
+    let detected = detect_language(content);
+    Ok(match (declared, detected) {
+        (_, Some(detected)) if detected.reliable => Some(detected.code),
+        (Some(declared), _) => Some(declared),
+        (None, detected) => detected.map(|d| d.code),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_languages() {
+        let english = "Coral reefs are built over centuries by colonies of tiny animals \
+            that secrete calcium carbonate skeletons.";
+        let spanish = "Los arrecifes de coral se construyen durante siglos por colonias \
+            de pequeños animales que segregan esqueletos de carbonato de calcio.";
+        let german = "Korallenriffe werden über Jahrhunderte von Kolonien winziger Tiere \
+            aufgebaut, die Skelette aus Kalziumkarbonat absondern.";
+        for (text, code) in [(english, "en"), (spanish, "es"), (german, "de")] {
+            let detected = detect_language(text).unwrap();
+            assert_eq!(detected.code, code);
+            assert!(detected.reliable, "{} unreliable", code);
+        }
+        assert!(detect_language("1234 !!").is_none());
+    }
+
+    #[test]
+    fn test_normalizes_tags() {
+        assert_eq!(normalize_language_tag("en-US").unwrap(), "en");
+        assert_eq!(normalize_language_tag(" PT_br ").unwrap(), "pt");
+        assert_eq!(normalize_language_tag("deu").unwrap(), "de");
+        assert_eq!(normalize_language_tag("haw").unwrap(), "haw");
+        assert!(normalize_language_tag("english").is_err());
+        assert!(normalize_language_tag("").is_err());
+        assert!(languages_match("en-GB", "eng"));
+        assert!(!languages_match("en", "es"));
+    }
+
+    #[test]
+    fn test_reliable_detection_overrides_declared_language() {
+        let spanish = "Los arrecifes de coral protegen las costas del oleaje y albergan \
+            a una cuarta parte de todas las especies marinas.";
+        assert_eq!(
+            resolve_language(Some("en"), spanish).unwrap().as_deref(),
+            Some("es")
+        );
+        // Too short to detect reliably: the declared tag stands.
+        assert_eq!(
+            resolve_language(Some("fr-CA"), "OK").unwrap().as_deref(),
+            Some("fr")
+        );
+        assert!(resolve_language(Some("??"), spanish).is_err());
+    }
+}
@@ -5,42 +5,94 @@
 // This is the leaf crate that all other crates in the workspace depend on.
 // It defines the canonical data structures, error types, cryptographic helpers,
 // and trait interfaces used throughout the Reefipedia system.
+//
+// Everything but `light` needs the default `std` feature. With
+// `default-features = false` the crate is `no_std` and exports only the light
+// verification module, for wasm32 explorers and light clients.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod light;
 
+
+#[cfg(feature = "std")]
 pub mod consensus;
+#[cfg(feature = "std")]
 pub mod crypto;
+#[cfg(feature = "std")]
+pub mod embedder;
+#[cfg(feature = "std")]
 pub mod embedding;
+#[cfg(feature = "std")]
 pub mod error;
+#[cfg(feature = "std")]
 pub mod identity;
+#[cfg(feature = "std")]
+pub mod keystore;
+#[cfg(feature = "std")]
+pub mod language;
+#[cfg(feature = "std")]
 pub mod metagraph;
+#[cfg(feature = "std")]
+pub mod mnemonic;
+#[cfg(feature = "std")]
 pub mod polyp;
+#[cfg(feature = "std")]
 pub mod provenance;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod reranker;
+#[cfg(feature = "std")]
+pub mod text;
+#[cfg(feature = "std")]
 pub mod traits;
 
 // Re-export key types for ergonomic access from downstream crates.
 // Usage: `use chitin_core::Polyp;`
 
 // Polyp types
+#[cfg(feature = "std")]
 pub use polyp::{Payload, Polyp, PolypState, PolypSubject, ProofPublicInputs, ZkProof};
 
 // Embedding types
-pub use embedding::{hash_embedding, EmbeddingModelId, VectorEmbedding};
+#[cfg(feature = "std")]
+pub use embedding::{
+    hash_embedding, EmbeddingModelId, ModelStatus, ModelVersion, VectorEmbedding,
+    HASH_EMBEDDING_MODEL,
+};
 
 // Provenance types
-pub use provenance::{PipelineStep, ProcessingPipeline, Provenance, SourceAttribution};
+#[cfg(feature = "std")]
+pub use provenance::{
+    MoltAncestor, PipelineStep, ProcessingPipeline, Provenance, ProvenanceReport,
+    SourceAttribution,
+};
 
 // Identity types
-pub use identity::{NodeIdentity, NodeType};
+#[cfg(feature = "std")]
+pub use identity::{HotkeyRotation, NodeIdentity, NodeType};
+#[cfg(feature = "std")]
+pub use registry::{IdentityRecord, IdentityRegistry};
 
 // Consensus types
+#[cfg(feature = "std")]
 pub use consensus::{
     Attestation, ConsensusMetadata, HardeningLineage, PolypScores, ValidatorScore,
 };
 
 // Metagraph types
+#[cfg(feature = "std")]
 pub use metagraph::{NodeInfo, ReefMetagraph};
 
 // Error type
+#[cfg(feature = "std")]
 pub use error::ChitinError;
 
 // Traits
-pub use traits::{PolypScorer, PolypStore, ProofVerifier, VectorIndex};
+#[cfg(feature = "std")]
+pub use traits::{
+    Embedder, IdentityStore, PolypScorer, PolypStore, ProofVerifier, Reranker, VectorIndex,
+};
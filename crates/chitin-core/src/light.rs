@@ -0,0 +1,294 @@
+// crates/chitin-core/src/light.rs
+//
+// Light verification: what a browser explorer or light client needs to
+// check a hardened Polyp without a node.
+//
+// - Canonical hashing: the Polyp signing digest, the proof's text and
+//   vector hashes, and the attestation message.
+// - Ed25519 signature verification.
+// - Merkle inclusion proofs against an epoch's hardening root.
+// - Attestation thresholds: how many known validators signed a Polyp's CID.
+//
+// This module is `no_std` (it needs only `alloc`) and is all of chitin-core
+// that builds with the default `std` feature off, e.g. for wasm32:
+//
+//     chitin-core = { path = "...", default-features = false }
+//
+// It takes raw parts (IDs as their 16 bytes, timestamps as the RFC 3339
+// strings in the node's JSON) rather than the serde types, so callers can
+// hand over fields straight from a Polyp's JSON. The full crate computes
+// its own signing bytes, Merkle trees, and attestations with these
+// functions, so nodes and light clients agree by construction.
+
+use alloc::vec::Vec;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+// ---------------------------------------------------------------------------
+// Canonical hashing
+// ---------------------------------------------------------------------------
+
+/// SHA-256 of `data`.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// Hash a ZK proof commits for a Polyp's content: SHA-256 of its UTF-8
+/// bytes.
+pub fn text_hash(text: &str) -> [u8; 32] {
+    sha256(text.as_bytes())
+}
+
+/// Hash a ZK proof commits for a Polyp's vector: SHA-256 of its values as
+/// IEEE 754 little-endian bytes.
+pub fn vector_hash(values: &[f32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for value in values {
+        hasher.update(value.to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// The digest a creator signs for a Polyp: SHA-256(id || content ||
+/// vector values (f32 little-endian) || created_at), with `created_at` in
+/// RFC 3339 as the node formats it.
+pub fn polyp_digest(id: &[u8; 16], content: &str, vector: &[f32], created_at: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(id);
+    hasher.update(content.as_bytes());
+    for value in vector {
+        hasher.update(value.to_le_bytes());
+    }
+    hasher.update(created_at.as_bytes());
+    hasher.finalize().into()
+}
+
+/// The bytes a validator signs to attest a hardened Polyp: polyp_id (16
+/// bytes) || cid (UTF-8) || epoch (u64 little-endian).
+pub fn attestation_message(polyp_id: &[u8; 16], cid: &str, epoch: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(24 + cid.len());
+    bytes.extend_from_slice(polyp_id);
+    bytes.extend_from_slice(cid.as_bytes());
+    bytes.extend_from_slice(&epoch.to_le_bytes());
+    bytes
+}
+
+// ---------------------------------------------------------------------------
+// Signatures
+// ---------------------------------------------------------------------------
+
+/// True if `signature` is `public_key`'s Ed25519 signature of `message`.
+///
+/// Malformed keys and signatures of the wrong length do not verify.
+pub fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    let Ok(signature) = <[u8; 64]>::try_from(signature) else {
+        return false;
+    };
+    key.verify(message, &Signature::from_bytes(&signature)).is_ok()
+}
+
+// ---------------------------------------------------------------------------
+// Merkle inclusion
+// ---------------------------------------------------------------------------
+
+/// Merkle leaf of a hardened Polyp: SHA-256(polyp_id || cid).
+pub fn hardening_leaf(polyp_id: &[u8; 16], cid: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(polyp_id);
+    hasher.update(cid.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Parent of two nodes. Children are sorted first, so proofs need no
+/// left/right flags; the prefix byte keeps inner nodes distinct from leaves.
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(lo);
+    hasher.update(hi);
+    hasher.finalize().into()
+}
+
+/// Build a Merkle tree over `leaves`, returning the root and each leaf's
+/// inclusion proof (sibling hashes, bottom up).
+///
+/// An unpaired node is promoted to the next level unchanged. A single leaf
+/// is its own root with an empty proof; an empty tree has an all-zero root.
+pub fn merkle_tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+    if leaves.is_empty() {
+        return ([0; 32], Vec::new());
+    }
+    let mut proofs = alloc::vec![Vec::new(); leaves.len()];
+    let mut positions: Vec<usize> = (0..leaves.len()).collect();
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        for (proof, pos) in proofs.iter_mut().zip(positions.iter_mut()) {
+            if let Some(sibling) = level.get(*pos ^ 1) {
+                proof.push(*sibling);
+            }
+            *pos /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [a] => *a,
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            })
+            .collect();
+    }
+    (level[0], proofs)
+}
+
+/// True if `proof` links `leaf` to `root`.
+pub fn verify_inclusion(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof.iter().fold(*leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
+
+// ---------------------------------------------------------------------------
+// Attestation thresholds
+// ---------------------------------------------------------------------------
+
+/// One validator attestation from a hardened Polyp's lineage.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedAttestation<'a> {
+    /// Hotkey of the validator that signed.
+    pub validator: &'a [u8; 32],
+    /// Epoch of the attestation.
+    pub epoch: u64,
+    /// Ed25519 signature over `attestation_message`.
+    pub signature: &'a [u8],
+}
+
+/// Attestations of one Polyp, sorted by whether they count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttestationTally {
+    /// Distinct known validators with a valid signature.
+    pub valid: usize,
+    /// Signatures that do not verify for this Polyp and CID.
+    pub invalid: usize,
+    /// Valid signatures from hotkeys outside the validator set.
+    pub unknown: usize,
+    /// Further valid signatures from a validator already counted.
+    pub duplicate: usize,
+}
+
+impl AttestationTally {
+    /// True if at least `threshold` distinct validators attested.
+    pub fn meets(&self, threshold: usize) -> bool {
+        self.valid >= threshold
+    }
+}
+
+/// Strict majority of `validators` (the threshold checkpoints use).
+pub fn majority(validators: usize) -> usize {
+    validators / 2 + 1
+}
+
+/// Tally `attestations` of the Polyp `polyp_id` hardened as `cid`.
+///
+/// Only signatures by hotkeys in `validators` count, each once; an
+/// attestation signed for another Polyp or CID counts as invalid.
+pub fn tally_attestations(
+    polyp_id: &[u8; 16],
+    cid: &str,
+    attestations: &[SignedAttestation<'_>],
+    validators: &[[u8; 32]],
+) -> AttestationTally {
+    let mut tally = AttestationTally::default();
+    let mut counted: Vec<&[u8; 32]> = Vec::new();
+    for attestation in attestations {
+        let message = attestation_message(polyp_id, cid, attestation.epoch);
+        if !verify_signature(attestation.validator, &message, attestation.signature) {
+            tally.invalid += 1;
+        } else if !validators.contains(attestation.validator) {
+            tally.unknown += 1;
+        } else if counted.contains(&attestation.validator) {
+            tally.duplicate += 1;
+        } else {
+            counted.push(attestation.validator);
+            tally.valid += 1;
+        }
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const POLYP: [u8; 16] = [7; 16];
+    const CID: &str = "bafyhardened";
+
+    fn attest(key: &SigningKey, polyp_id: &[u8; 16], epoch: u64) -> (Vec<u8>, [u8; 32]) {
+        let message = attestation_message(polyp_id, CID, epoch);
+        (key.sign(&message).to_bytes().to_vec(), key.verifying_key().to_bytes())
+    }
+
+    #[test]
+    fn inclusion_proofs_verify_against_the_root_only() {
+        let leaves: Vec<[u8; 32]> = (0u8..5).map(|i| hardening_leaf(&[i; 16], CID)).collect();
+        let (root, proofs) = merkle_tree(&leaves);
+        for (leaf, proof) in leaves.iter().zip(&proofs) {
+            assert!(verify_inclusion(leaf, proof, &root));
+        }
+        assert!(!verify_inclusion(&leaves[0], &proofs[1], &root));
+        assert!(!verify_inclusion(&hardening_leaf(&POLYP, CID), &proofs[0], &root));
+    }
+
+    #[test]
+    fn signatures_verify_and_malformed_input_does_not() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let public = key.verifying_key().to_bytes();
+        let digest = polyp_digest(&POLYP, "text", &[0.5, -1.0], "2026-01-01T00:00:00+00:00");
+        let signature = key.sign(&digest).to_bytes();
+        assert!(verify_signature(&public, &digest, &signature));
+        assert!(!verify_signature(&public, &text_hash("text"), &signature));
+        assert!(!verify_signature(&public, &digest, &signature[..63]));
+    }
+
+    #[test]
+    fn thresholds_count_each_known_validator_once() {
+        let keys: Vec<SigningKey> = (1u8..=4).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let validators: Vec<[u8; 32]> =
+            keys[..3].iter().map(|k| k.verifying_key().to_bytes()).collect();
+
+        let signed = [
+            attest(&keys[0], &POLYP, 9),
+            attest(&keys[1], &POLYP, 9),
+            attest(&keys[1], &POLYP, 10),
+            attest(&keys[2], &[8; 16], 9),
+            attest(&keys[3], &POLYP, 9),
+        ];
+        let attestations: Vec<SignedAttestation> = signed
+            .iter()
+            .zip([9, 9, 10, 9, 9])
+            .map(|((signature, validator), epoch)| SignedAttestation {
+                validator,
+                epoch,
+                signature,
+            })
+            .collect();
+
+        let tally = tally_attestations(&POLYP, CID, &attestations, &validators);
+        assert_eq!(
+            tally,
+            AttestationTally {
+                valid: 2,
+                invalid: 1,
+                unknown: 1,
+                duplicate: 1
+            }
+        );
+        assert!(tally.meets(majority(validators.len())));
+        assert!(!tally.meets(3));
+    }
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::embedding::ModelVersion;
 use crate::identity::NodeType;
 
 /// The global network state — all nodes, their stakes, trust, and performance.
@@ -27,6 +28,9 @@ pub struct ReefMetagraph {
     pub weights: HashMap<u16, Vec<(u16, f64)>>,
     /// Bond matrix B[validator_uid][coral_uid] = bond.
     pub bonds: HashMap<u16, Vec<(u16, f64)>>,
+    /// Registered embedding model versions, ordered by activation epoch.
+    #[serde(default)]
+    pub model_versions: Vec<ModelVersion>,
 }
 
 /// Information about a single node in the metagraph.
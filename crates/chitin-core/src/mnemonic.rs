@@ -0,0 +1,154 @@
+// crates/chitin-core/src/mnemonic.rs
+//
+// BIP-39 recovery phrases for coldkeys and hotkeys.
+//
+// A 24-word phrase encodes 256 bits of entropy. Its BIP-39 seed (stretched
+// with an optional passphrase) is the root of a SLIP-10 ed25519 key tree:
+// the coldkey lives at m/0' and hotkey `n` at m/1'/n', so one phrase
+// recovers the coldkey and every hotkey rotated to since. SLIP-10 only
+// defines hardened derivation for ed25519; every index here is hardened.
+
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha512;
+
+use crate::error::ChitinError;
+use crate::keystore::{SecretKey, Zeroizing};
+
+/// Number of words in a generated recovery phrase.
+pub const MNEMONIC_WORDS: usize = 24;
+
+/// Hardened index of the coldkey under the master key.
+const COLDKEY_INDEX: u32 = 0;
+
+/// Hardened index of the hotkey branch under the master key.
+const HOTKEY_BRANCH: u32 = 1;
+
+/// Bit marking a SLIP-10 index as hardened.
+const HARDENED: u32 = 0x8000_0000;
+
+/// HMAC key of the SLIP-10 ed25519 master key.
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// A 64-byte BIP-39 seed that is wiped when dropped.
+pub type Seed = Zeroizing<[u8; 64]>;
+
+/// Generate a new 24-word English recovery phrase.
+pub fn generate_mnemonic() -> Zeroizing<String> {
+    let mut entropy = Zeroizing::new([0u8; 32]);
+    rand::rngs::OsRng.fill_bytes(entropy.as_mut());
+    // 32 bytes is always a valid BIP-39 entropy length.
+    let mnemonic = Mnemonic::from_entropy(entropy.as_ref()).expect("256-bit entropy");
+    Zeroizing::new(mnemonic.to_string())
+}
+
+/// Check a recovery phrase and derive its seed under `passphrase`.
+///
+/// Words are matched case-insensitively and extra whitespace is ignored.
+///
+/// # Errors
+/// Returns `ChitinError::Crypto` if the phrase has an unknown word, the
+/// wrong length, or a bad checksum.
+pub fn mnemonic_seed(phrase: &str, passphrase: &str) -> Result<Seed, ChitinError> {
+    let normalized = Zeroizing::new(
+        phrase
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    let mnemonic = Mnemonic::parse_normalized(&normalized)
+        .map_err(|e| ChitinError::Crypto(format!("Invalid recovery phrase: {}", e)))?;
+    Ok(Zeroizing::new(mnemonic.to_seed(passphrase)))
+}
+
+/// The coldkey secret of a seed (m/0').
+pub fn derive_coldkey(seed: &[u8; 64]) -> SecretKey {
+    derive_path(seed, &[COLDKEY_INDEX])
+}
+
+/// The secret of hotkey `index` of a seed (m/1'/index').
+pub fn derive_hotkey(seed: &[u8; 64], index: u32) -> SecretKey {
+    derive_path(seed, &[HOTKEY_BRANCH, index])
+}
+
+/// Derive the SLIP-10 ed25519 secret at a path of hardened indices.
+///
+/// Indices are hardened whether or not their top bit is set.
+pub fn derive_path(seed: &[u8], path: &[u32]) -> SecretKey {
+    let (mut key, mut chain_code) = split(hmac_sha512(ED25519_SEED_KEY, &[seed]));
+    for &index in path {
+        let index = (index | HARDENED).to_be_bytes();
+        (key, chain_code) = split(hmac_sha512(
+            chain_code.as_ref(),
+            &[&[0], key.as_ref(), &index],
+        ));
+    }
+    key
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    let mut out = Zeroizing::new([0u8; 64]);
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// Split an HMAC output into the secret key and chain code.
+fn split(output: Zeroizing<[u8; 64]>) -> (SecretKey, SecretKey) {
+    let mut key = SecretKey::new([0u8; 32]);
+    let mut chain_code = SecretKey::new([0u8; 32]);
+    key.copy_from_slice(&output[..32]);
+    chain_code.copy_from_slice(&output[32..]);
+    (key, chain_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_derive_path_matches_slip10_vector() {
+        // SLIP-10 ed25519 test vector 1.
+        let seed: Vec<u8> = (0u8..16).collect();
+        assert_eq!(
+            hex(derive_path(&seed, &[]).as_ref()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex(derive_path(&seed, &[0]).as_ref()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    #[test]
+    fn test_generated_phrase_recovers_same_keys() {
+        let phrase = generate_mnemonic();
+        assert_eq!(phrase.split_whitespace().count(), MNEMONIC_WORDS);
+
+        let seed = mnemonic_seed(&phrase, "").unwrap();
+        let shouted = mnemonic_seed(&format!("  {}\n", phrase.to_uppercase()), "").unwrap();
+        assert_eq!(*derive_coldkey(&seed), *derive_coldkey(&shouted));
+
+        let salted = mnemonic_seed(&phrase, "extra words").unwrap();
+        assert_ne!(*derive_coldkey(&seed), *derive_coldkey(&salted));
+        assert_ne!(*derive_coldkey(&seed), *derive_hotkey(&seed, 0));
+        assert_ne!(*derive_hotkey(&seed, 0), *derive_hotkey(&seed, 1));
+    }
+
+    #[test]
+    fn test_mnemonic_seed_rejects_bad_checksum() {
+        let valid = format!("{} about", ["abandon"; 11].join(" "));
+        assert!(mnemonic_seed(&valid, "").is_ok());
+        assert!(mnemonic_seed(&["abandon"; 12].join(" "), "").is_err());
+        assert!(mnemonic_seed("not a recovery phrase", "").is_err());
+    }
+}
@@ -2,13 +2,13 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::consensus::{ConsensusMetadata, HardeningLineage};
 use crate::crypto;
 use crate::embedding::{EmbeddingModelId, VectorEmbedding};
 use crate::error::ChitinError;
+use crate::light;
 use crate::provenance::Provenance;
 
 /// Lifecycle states of a Polyp — from initial creation through consensus to hardening.
@@ -60,6 +60,10 @@ pub struct Polyp {
     /// None for unsigned polyps (backward compatible).
     #[serde(default)]
     pub signature: Option<Vec<u8>>,
+    /// Reef Zone (topic path, e.g. "code/rust") the Polyp was submitted to.
+    /// None if unassigned; validators then classify it by content.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
 }
 
 impl Polyp {
@@ -67,24 +71,13 @@ impl Polyp {
     ///
     /// Returns SHA-256(id_bytes || content || vector_values_as_le_bytes || created_at_rfc3339).
     pub fn signable_bytes(&self) -> Vec<u8> {
-        let mut hasher = Sha256::new();
-
-        // id bytes
-        hasher.update(self.id.as_bytes());
-
-        // content
-        hasher.update(self.subject.payload.content.as_bytes());
-
-        // vector values as little-endian bytes
-        for val in &self.subject.vector.values {
-            hasher.update(val.to_le_bytes());
-        }
-
-        // created_at as RFC3339 string
-        let created_str = self.created_at.to_rfc3339();
-        hasher.update(created_str.as_bytes());
-
-        hasher.finalize().to_vec()
+        light::polyp_digest(
+            self.id.as_bytes(),
+            &self.subject.payload.content,
+            &self.subject.vector.values,
+            &self.created_at.to_rfc3339(),
+        )
+        .to_vec()
     }
 
     /// Sign this polyp with the given ed25519 signing key.
@@ -212,9 +205,11 @@ mod tests {
                             name: "test".to_string(),
                             version: "0.1.0".to_string(),
                             params: serde_json::json!({}),
+                            hash: None,
                         }],
                         duration_ms: 0,
                     },
+                    molted_from: vec![],
                 },
             },
             proof: ZkProof {
@@ -238,6 +233,7 @@ mod tests {
             created_at: now,
             updated_at: now,
             signature: None,
+            reef_zone: None,
         }
     }
 
@@ -1,10 +1,25 @@
 // crates/chitin-core/src/provenance.rs
+//
+// Provenance of a Polyp: who made it, from what source, through which
+// processing steps, and which Polyps it was molted from.
+//
+// `Provenance::verify` checks the chain as a whole: the creator identity is
+// self-consistent, the pipeline's hash chain recomputes, CIDs are well
+// formed, and timestamps and molt epochs are in order. Resolving CIDs on
+// IPFS needs a client, so callers that have one add that check themselves.
 
-use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::crypto::hash_bytes;
 use crate::identity::NodeIdentity;
 
+/// How far past the verifier's clock a provenance timestamp may be.
+pub const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
 /// Full provenance chain for a Polyp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Provenance {
@@ -14,6 +29,22 @@ pub struct Provenance {
     pub source: SourceAttribution,
     /// Processing pipeline that produced this Polyp.
     pub pipeline: ProcessingPipeline,
+    /// Polyps this one was molted from, oldest first (empty if never molted).
+    #[serde(default)]
+    pub molted_from: Vec<MoltAncestor>,
+}
+
+/// A predecessor in a Polyp's molt lineage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoltAncestor {
+    /// The predecessor Polyp.
+    pub polyp_id: Uuid,
+    /// Model the predecessor was embedded under.
+    pub model_id: String,
+    /// IPFS CID of the predecessor's hardened version, if it was hardened.
+    pub cid: Option<String>,
+    /// Epoch in which it was molted.
+    pub epoch: u64,
 }
 
 /// Attribution to the original source.
@@ -45,4 +76,354 @@ pub struct PipelineStep {
     pub name: String,
     pub version: String,
     pub params: serde_json::Value,
+    /// Hex link in the pipeline hash chain (see `PipelineStep::chain_hash`),
+    /// if the pipeline was sealed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl PipelineStep {
+    /// This step's link in the pipeline hash chain: SHA-256 over the
+    /// previous link (all zeros for the first step), then the name,
+    /// version, and params, each NUL-terminated.
+    pub fn chain_hash(&self, previous: &[u8; 32]) -> [u8; 32] {
+        let mut bytes = previous.to_vec();
+        for part in [&self.name, &self.version, &self.params.to_string()] {
+            bytes.extend_from_slice(part.as_bytes());
+            bytes.push(0);
+        }
+        hash_bytes(&bytes)
+    }
+}
+
+impl ProcessingPipeline {
+    /// Record every step's chain hash, so that editing, dropping, or
+    /// reordering a step afterwards breaks the chain.
+    pub fn seal(&mut self) {
+        let mut link = [0u8; 32];
+        for step in &mut self.steps {
+            link = step.chain_hash(&link);
+            step.hash = Some(hex(&link));
+        }
+    }
+}
+
+/// Outcome of one provenance check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not applicable, or could not be checked.
+    Skip,
+}
+
+/// One provenance check and what it found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+/// Every check run on one provenance chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceReport {
+    pub checks: Vec<ProvenanceCheck>,
+}
+
+impl ProvenanceReport {
+    /// Record a check that passed or failed.
+    pub fn check(&mut self, name: &str, passed: bool, detail: impl Into<String>) {
+        let status = if passed {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+        self.push(name, status, detail);
+    }
+
+    /// Record a check that was not applicable.
+    pub fn skip(&mut self, name: &str, detail: impl Into<String>) {
+        self.push(name, CheckStatus::Skip, detail);
+    }
+
+    fn push(&mut self, name: &str, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(ProvenanceCheck {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    /// Number of checks with `status`.
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// True if no check failed.
+    pub fn is_valid(&self) -> bool {
+        self.count(CheckStatus::Fail) == 0
+    }
+
+    /// Integrity score in [0.0, 1.0]: passed checks count fully and skipped
+    /// ones half, so a chain with nothing to check scores 0.5 and any
+    /// failure pulls it below that.
+    pub fn score(&self) -> f64 {
+        if self.checks.is_empty() {
+            return 0.5;
+        }
+        let pass = self.count(CheckStatus::Pass) as f64;
+        let skip = self.count(CheckStatus::Skip) as f64;
+        (pass + 0.5 * skip) / self.checks.len() as f64
+    }
+}
+
+impl Provenance {
+    /// Check the whole chain of a Polyp created at `created_at`, against
+    /// the verifier's clock `now`.
+    ///
+    /// Checks, by name: `creator` (the DID is derived from the coldkey and
+    /// the hotkey is set), `pipeline` (step names are set and recorded
+    /// chain hashes recompute), `source cid` (well formed), `timestamps`
+    /// (the source was accessed before the Polyp was created, and neither
+    /// is in the future), and `molt lineage` (ancestors are distinct, in
+    /// epoch order, with well-formed CIDs).
+    pub fn verify(&self, created_at: DateTime<Utc>, now: DateTime<Utc>) -> ProvenanceReport {
+        let mut report = ProvenanceReport::default();
+        self.verify_creator(&mut report);
+        self.verify_pipeline(&mut report);
+        match &self.source.source_cid {
+            None => report.skip("source cid", "no source CID"),
+            Some(cid) => report.check(
+                "source cid",
+                is_well_formed_cid(cid),
+                if is_well_formed_cid(cid) {
+                    cid.clone()
+                } else {
+                    format!("{:?} is not a CID", cid)
+                },
+            ),
+        }
+        self.verify_timestamps(created_at, now, &mut report);
+        self.verify_molt_lineage(&mut report);
+        report
+    }
+
+    fn verify_creator(&self, report: &mut ProvenanceReport) {
+        let creator = &self.creator;
+        if creator.is_placeholder() {
+            report.skip("creator", "placeholder creator identity");
+        } else if NodeIdentity::derive_did(&creator.coldkey) != creator.did {
+            let detail = format!("{} is not derived from the creator coldkey", creator.did);
+            report.check("creator", false, detail);
+        } else if creator.hotkey == [0u8; 32] {
+            report.check("creator", false, "creator has no hotkey");
+        } else {
+            report.check("creator", true, creator.did.clone());
+        }
+    }
+
+    fn verify_pipeline(&self, report: &mut ProvenanceReport) {
+        let steps = &self.pipeline.steps;
+        if steps.is_empty() {
+            report.skip("pipeline", "no pipeline steps");
+            return;
+        }
+        let mut link = [0u8; 32];
+        let mut hashed = 0;
+        for (number, step) in steps.iter().enumerate() {
+            if step.name.trim().is_empty() {
+                report.check("pipeline", false, format!("step {} has no name", number));
+                return;
+            }
+            link = step.chain_hash(&link);
+            if let Some(hash) = &step.hash {
+                if *hash != hex(&link) {
+                    let detail = format!("step {} ({}) hash does not chain", number, step.name);
+                    report.check("pipeline", false, detail);
+                    return;
+                }
+                hashed += 1;
+            }
+        }
+        if hashed == 0 {
+            report.skip("pipeline", format!("{} steps, none hashed", steps.len()));
+        } else {
+            let detail = format!("{} of {} steps hash-chained", hashed, steps.len());
+            report.check("pipeline", true, detail);
+        }
+    }
+
+    fn verify_timestamps(
+        &self,
+        created_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        report: &mut ProvenanceReport,
+    ) {
+        let skew = Duration::seconds(MAX_CLOCK_SKEW_SECS);
+        let accessed_at = self.source.accessed_at;
+        let problem = if created_at > now + skew {
+            Some(format!("created {} is in the future", created_at.to_rfc3339()))
+        } else if accessed_at > now + skew {
+            Some(format!("source accessed {} is in the future", accessed_at.to_rfc3339()))
+        } else if accessed_at > created_at + skew {
+            Some(format!(
+                "source accessed {} after the Polyp was created",
+                accessed_at.to_rfc3339()
+            ))
+        } else {
+            None
+        };
+        match problem {
+            Some(detail) => report.check("timestamps", false, detail),
+            None => report.check("timestamps", true, "in order"),
+        }
+    }
+
+    fn verify_molt_lineage(&self, report: &mut ProvenanceReport) {
+        let ancestors = &self.molted_from;
+        if ancestors.is_empty() {
+            report.skip("molt lineage", "never molted");
+            return;
+        }
+        let mut seen = HashSet::new();
+        let mut epoch = 0;
+        for ancestor in ancestors {
+            let problem = if !seen.insert(ancestor.polyp_id) {
+                Some("appears twice")
+            } else if ancestor.epoch < epoch {
+                Some("is out of epoch order")
+            } else if ancestor.cid.as_deref().is_some_and(|cid| !is_well_formed_cid(cid)) {
+                Some("has a malformed CID")
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                let detail = format!("ancestor {} {}", ancestor.polyp_id, problem);
+                report.check("molt lineage", false, detail);
+                return;
+            }
+            epoch = ancestor.epoch;
+        }
+        report.check("molt lineage", true, format!("{} ancestors", ancestors.len()));
+    }
+}
+
+/// Whether `cid` is shaped like an IPFS CID: base58 CIDv0 (`Qm` and 44
+/// characters) or base32 CIDv1 (`b` and lowercase base32).
+pub fn is_well_formed_cid(cid: &str) -> bool {
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if cid.len() == 46 && cid.starts_with("Qm") {
+        return cid.chars().all(|c| BASE58.contains(c));
+    }
+    match cid.strip_prefix('b') {
+        Some(rest) => {
+            rest.len() >= 16 && rest.chars().all(|c| matches!(c, 'a'..='z' | '2'..='7'))
+        }
+        None => false,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::NodeType;
+
+    const SOURCE_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    fn provenance(now: DateTime<Utc>) -> Provenance {
+        let step = |name: &str| PipelineStep {
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            params: serde_json::json!({ "size": 512 }),
+            hash: None,
+        };
+        let mut pipeline = ProcessingPipeline {
+            steps: vec![step("chunk"), step("embed")],
+            duration_ms: 10,
+        };
+        pipeline.seal();
+        Provenance {
+            creator: NodeIdentity::from_keypairs([2; 32], [1; 32], NodeType::Coral),
+            source: SourceAttribution {
+                source_cid: Some(SOURCE_CID.to_string()),
+                source_url: None,
+                title: None,
+                license: None,
+                accessed_at: now - Duration::minutes(1),
+            },
+            pipeline,
+            molted_from: vec![],
+        }
+    }
+
+    fn status(report: &ProvenanceReport, name: &str) -> CheckStatus {
+        report.checks.iter().find(|c| c.name == name).unwrap().status
+    }
+
+    #[test]
+    fn sealed_provenance_verifies() {
+        let now = Utc::now();
+        let report = provenance(now).verify(now, now);
+        assert!(report.is_valid(), "{:?}", report);
+        assert_eq!(status(&report, "pipeline"), CheckStatus::Pass);
+        assert_eq!(status(&report, "molt lineage"), CheckStatus::Skip);
+        assert!((report.score() - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn edited_steps_and_forged_dids_fail() {
+        let now = Utc::now();
+        let mut edited = provenance(now);
+        edited.pipeline.steps[0].params = serde_json::json!({ "size": 64 });
+        assert_eq!(status(&edited.verify(now, now), "pipeline"), CheckStatus::Fail);
+
+        let mut reordered = provenance(now);
+        reordered.pipeline.steps.swap(0, 1);
+        assert_eq!(status(&reordered.verify(now, now), "pipeline"), CheckStatus::Fail);
+
+        let mut forged = provenance(now);
+        forged.creator.did = "did:chitin:someone-else".to_string();
+        let report = forged.verify(now, now);
+        assert_eq!(status(&report, "creator"), CheckStatus::Fail);
+        assert!(report.score() < 0.9);
+    }
+
+    #[test]
+    fn timestamps_cids_and_lineage_are_sanity_checked() {
+        let now = Utc::now();
+        let mut future = provenance(now);
+        future.source.accessed_at = now + Duration::hours(1);
+        assert_eq!(status(&future.verify(now, now), "timestamps"), CheckStatus::Fail);
+
+        let mut bad_cid = provenance(now);
+        bad_cid.source.source_cid = Some("not a cid".to_string());
+        assert_eq!(status(&bad_cid.verify(now, now), "source cid"), CheckStatus::Fail);
+
+        let ancestor = |epoch| MoltAncestor {
+            polyp_id: Uuid::now_v7(),
+            model_id: "bge/v1".to_string(),
+            cid: None,
+            epoch,
+        };
+        let mut molted = provenance(now);
+        molted.molted_from = vec![ancestor(3), ancestor(5)];
+        assert_eq!(status(&molted.verify(now, now), "molt lineage"), CheckStatus::Pass);
+        molted.molted_from.reverse();
+        assert_eq!(status(&molted.verify(now, now), "molt lineage"), CheckStatus::Fail);
+    }
+
+    #[test]
+    fn cid_shapes() {
+        assert!(is_well_formed_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"));
+        assert!(is_well_formed_cid("bafkreigh2akiscaildcqabsyg3dfr6chu3fgpregiymsck7e7aqa4s52zy"));
+        assert!(!is_well_formed_cid("QmTooShort"));
+        assert!(!is_well_formed_cid("bafy-test"));
+        assert!(!is_well_formed_cid(""));
+    }
 }
@@ -0,0 +1,481 @@
+// crates/chitin-core/src/registry.rs
+//
+// IdentityRegistry: stable UIDs for node identities.
+//
+// Consensus (metagraph nodes, weight rows), reputation (trust matrix
+// indices), economics (stake targets), and RPC all address nodes by a u16
+// UID, so every node must agree on them. UIDs therefore only come from data
+// every node shares: the genesis file, whose nodes take UIDs in listed order,
+// and the metagraph, whose nodes are recorded under the UIDs it gives them.
+// Nothing is registered in the order one node happens to discover others,
+// and a UID is never reused or renumbered, so a node keeps its UID across
+// restarts, hotkey rotations, and metagraph updates, and nothing keyed by UID
+// (trust edges, weight rows, stakes) ever needs remapping. A metagraph that
+// disagrees with a registered UID is refused. Records are written through an
+// `IdentityStore` (RocksDB in chitin-store) before the registry changes, and
+// reloaded on open.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ChitinError;
+use crate::identity::{NodeIdentity, NodeType};
+use crate::metagraph::{NodeInfo, ReefMetagraph};
+use crate::traits::IdentityStore;
+
+/// One registered identity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentityRecord {
+    /// Stable network UID.
+    pub uid: u16,
+    /// DID derived from the coldkey.
+    pub did: String,
+    /// Coldkey public key.
+    pub coldkey: [u8; 32],
+    /// Current hotkey public key.
+    pub hotkey: [u8; 32],
+    /// Node type at the last registration.
+    pub node_type: NodeType,
+    /// Epoch of the first registration.
+    pub registered_epoch: u64,
+}
+
+/// DID, hotkey, and UID lookups over every registered identity.
+#[derive(Default)]
+pub struct IdentityRegistry {
+    /// Records by UID (`records[uid].uid == uid`).
+    records: Vec<IdentityRecord>,
+    by_did: HashMap<String, u16>,
+    by_hotkey: HashMap<[u8; 32], u16>,
+    store: Option<Arc<dyn IdentityStore>>,
+}
+
+impl std::fmt::Debug for IdentityRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdentityRegistry")
+            .field("records", &self.records.len())
+            .field("persistent", &self.store.is_some())
+            .finish()
+    }
+}
+
+impl IdentityRegistry {
+    /// An empty, in-memory registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the registry saved in `store`, writing later registrations
+    /// back to it.
+    pub fn open(store: Arc<dyn IdentityStore>) -> Result<Self, ChitinError> {
+        let mut records = store.load_identities()?;
+        records.sort_by_key(|r| r.uid);
+        let mut registry = Self::new();
+        for record in records {
+            if usize::from(record.uid) != registry.records.len() {
+                return Err(ChitinError::Storage(format!(
+                    "Identity registry has no record for UID {}",
+                    registry.records.len()
+                )));
+            }
+            registry.index(record);
+        }
+        registry.store = Some(store);
+        Ok(registry)
+    }
+
+    /// Register the identity of `coldkey`, returning its UID.
+    ///
+    /// A new DID gets the next UID, so identities must be registered in an
+    /// order every node shares (genesis order). A known DID keeps its UID; a
+    /// changed hotkey (a rotation) or node type is recorded. A hotkey already
+    /// held by another DID is rejected.
+    pub fn register(
+        &mut self,
+        coldkey: [u8; 32],
+        hotkey: [u8; 32],
+        node_type: NodeType,
+        epoch: u64,
+    ) -> Result<u16, ChitinError> {
+        let did = NodeIdentity::derive_did(&coldkey);
+        let existing = self.by_did.get(&did).copied();
+        if let Some(holder) = self.by_hotkey.get(&hotkey) {
+            if Some(*holder) != existing {
+                return Err(ChitinError::InvalidState(format!(
+                    "Hotkey is already registered to UID {}",
+                    holder
+                )));
+            }
+        }
+
+        let record = match existing {
+            Some(uid) => {
+                let current = &self.records[usize::from(uid)];
+                if current.hotkey == hotkey && current.node_type == node_type {
+                    return Ok(uid);
+                }
+                IdentityRecord {
+                    hotkey,
+                    node_type,
+                    ..current.clone()
+                }
+            }
+            None => IdentityRecord {
+                uid: u16::try_from(self.records.len()).map_err(|_| {
+                    ChitinError::InvalidState("Identity registry is full".to_string())
+                })?,
+                did,
+                coldkey,
+                hotkey,
+                node_type,
+                registered_epoch: epoch,
+            },
+        };
+        let uid = record.uid;
+        self.put(record)?;
+        Ok(uid)
+    }
+
+    /// Record every node of `metagraph` under the UID the metagraph gives
+    /// it, registering new identities and recording hotkey rotations and
+    /// node type changes. Returns the number of records added or changed.
+    ///
+    /// New identities must take the next free UIDs. A metagraph that gives
+    /// a registered DID another UID, gives a registered UID to another DID,
+    /// leaves a gap, or gives two identities one hotkey is refused, and the
+    /// registry is left unchanged.
+    pub fn sync_metagraph(
+        &mut self,
+        metagraph: &ReefMetagraph,
+        epoch: u64,
+    ) -> Result<usize, ChitinError> {
+        let mut nodes: Vec<&NodeInfo> = metagraph.nodes.iter().collect();
+        nodes.sort_by_key(|n| n.uid);
+        let mut changes = Vec::new();
+        let mut added = HashSet::new();
+        for node in nodes {
+            let did = NodeIdentity::derive_did(&node.coldkey);
+            let uid = usize::from(node.uid);
+            let record = match (self.by_did.get(&did), self.records.get(uid)) {
+                (Some(&registered), Some(current)) if registered == node.uid => {
+                    if current.hotkey == node.hotkey && current.node_type == node.node_type {
+                        continue;
+                    }
+                    IdentityRecord {
+                        hotkey: node.hotkey,
+                        node_type: node.node_type.clone(),
+                        ..current.clone()
+                    }
+                }
+                (Some(&registered), _) => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Metagraph lists {} as UID {}, but it is registered as UID {}",
+                        did, node.uid, registered
+                    )));
+                }
+                (None, Some(current)) => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Metagraph lists {} as UID {}, which is registered to {}",
+                        did, node.uid, current.did
+                    )));
+                }
+                (None, None) if added.contains(&did) => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Metagraph lists {} twice",
+                        did
+                    )));
+                }
+                (None, None) if uid == self.records.len() + added.len() => {
+                    added.insert(did.clone());
+                    IdentityRecord {
+                        uid: node.uid,
+                        did,
+                        coldkey: node.coldkey,
+                        hotkey: node.hotkey,
+                        node_type: node.node_type.clone(),
+                        registered_epoch: epoch,
+                    }
+                }
+                (None, None) => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Metagraph lists {} as UID {}, but the next free UID is {}",
+                        did,
+                        node.uid,
+                        self.records.len() + added.len()
+                    )));
+                }
+            };
+            changes.push(record);
+        }
+
+        let mut hotkeys: Vec<[u8; 32]> = self.records.iter().map(|r| r.hotkey).collect();
+        for record in &changes {
+            match hotkeys.get_mut(usize::from(record.uid)) {
+                Some(hotkey) => *hotkey = record.hotkey,
+                None => hotkeys.push(record.hotkey),
+            }
+        }
+        let mut holders = HashMap::new();
+        for (uid, hotkey) in hotkeys.into_iter().enumerate() {
+            if let Some(other) = holders.insert(hotkey, uid) {
+                return Err(ChitinError::InvalidState(format!(
+                    "Metagraph gives UIDs {} and {} the same hotkey",
+                    other, uid
+                )));
+            }
+        }
+
+        let changed = changes.len();
+        for record in changes {
+            self.put(record)?;
+        }
+        Ok(changed)
+    }
+
+    /// The record of `uid`.
+    pub fn get(&self, uid: u16) -> Option<&IdentityRecord> {
+        self.records.get(usize::from(uid))
+    }
+
+    /// The UID registered to `did`.
+    pub fn uid_of_did(&self, did: &str) -> Option<u16> {
+        self.by_did.get(did).copied()
+    }
+
+    /// The UID whose current hotkey is `hotkey`.
+    pub fn uid_of_hotkey(&self, hotkey: &[u8; 32]) -> Option<u16> {
+        self.by_hotkey.get(hotkey).copied()
+    }
+
+    /// The DID registered under `uid`.
+    pub fn did_of(&self, uid: u16) -> Option<&str> {
+        self.get(uid).map(|r| r.did.as_str())
+    }
+
+    /// UIDs by DID, for seeding trust.
+    pub fn did_to_uid(&self) -> HashMap<String, u16> {
+        self.by_did.clone()
+    }
+
+    /// UIDs by current hotkey.
+    pub fn hotkey_to_uid(&self) -> HashMap<[u8; 32], u16> {
+        self.by_hotkey.clone()
+    }
+
+    /// Every record, in UID order.
+    pub fn iter(&self) -> impl Iterator<Item = &IdentityRecord> {
+        self.records.iter()
+    }
+
+    /// Number of registered identities.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether no identity is registered.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Save `record` and index it, replacing the record of its UID.
+    fn put(&mut self, record: IdentityRecord) -> Result<(), ChitinError> {
+        if let Some(store) = &self.store {
+            store.save_identity(&record)?;
+        }
+        let (uid, hotkey) = (record.uid, record.hotkey);
+        match self.records.get_mut(usize::from(uid)) {
+            Some(current) => {
+                let old = std::mem::replace(current, record);
+                if self.by_hotkey.get(&old.hotkey) == Some(&uid) {
+                    self.by_hotkey.remove(&old.hotkey);
+                }
+                self.by_hotkey.insert(hotkey, uid);
+            }
+            None => self.index(record),
+        }
+        Ok(())
+    }
+
+    fn index(&mut self, record: IdentityRecord) {
+        self.by_did.insert(record.did.clone(), record.uid);
+        self.by_hotkey.insert(record.hotkey, record.uid);
+        self.records.push(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use crate::metagraph::NodeInfo;
+
+    #[derive(Default)]
+    struct MemoryStore(Mutex<HashMap<u16, IdentityRecord>>);
+
+    impl IdentityStore for MemoryStore {
+        fn save_identity(&self, record: &IdentityRecord) -> Result<(), ChitinError> {
+            self.0.lock().unwrap().insert(record.uid, record.clone());
+            Ok(())
+        }
+
+        fn load_identities(&self) -> Result<Vec<IdentityRecord>, ChitinError> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+    }
+
+    fn node(uid: u16, key: u8) -> NodeInfo {
+        NodeInfo {
+            uid,
+            hotkey: [key; 32],
+            coldkey: [key + 100; 32],
+            node_type: NodeType::Tide,
+            stake: 0,
+            trust: 0.0,
+            consensus: 0.0,
+            incentive: 0.0,
+            emission: 0,
+            polyp_count: 0,
+            last_active: 0,
+            axon_addr: String::new(),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_uids_are_stable_across_rotation_and_reopen() {
+        let store = Arc::new(MemoryStore::default());
+        let mut registry = IdentityRegistry::open(store.clone()).unwrap();
+        let a = registry
+            .register([1; 32], [11; 32], NodeType::Coral, 0)
+            .unwrap();
+        let b = registry
+            .register([2; 32], [12; 32], NodeType::Tide, 3)
+            .unwrap();
+        assert_eq!((a, b), (0, 1));
+        assert_eq!(
+            registry
+                .register([1; 32], [11; 32], NodeType::Coral, 5)
+                .unwrap(),
+            0
+        );
+
+        // A rotated hotkey keeps the UID; the old hotkey no longer resolves.
+        assert_eq!(
+            registry
+                .register([1; 32], [13; 32], NodeType::Coral, 5)
+                .unwrap(),
+            0
+        );
+        assert_eq!(registry.uid_of_hotkey(&[13; 32]), Some(0));
+        assert_eq!(registry.uid_of_hotkey(&[11; 32]), None);
+
+        // Another identity cannot claim a registered hotkey.
+        assert!(registry
+            .register([3; 32], [12; 32], NodeType::Tide, 5)
+            .is_err());
+        assert_eq!(registry.len(), 2);
+
+        let reopened = IdentityRegistry::open(store).unwrap();
+        let did = NodeIdentity::derive_did(&[2; 32]);
+        assert_eq!(reopened.uid_of_did(&did), Some(1));
+        assert_eq!(reopened.did_of(1), Some(did.as_str()));
+        assert_eq!(reopened.uid_of_hotkey(&[13; 32]), Some(0));
+        assert_eq!(reopened.get(1).unwrap().registered_epoch, 3);
+    }
+
+    fn metagraph(nodes: Vec<NodeInfo>) -> ReefMetagraph {
+        ReefMetagraph {
+            epoch: 1,
+            block: 0,
+            nodes,
+            total_stake: 0,
+            total_hardened_polyps: 0,
+            emission_rate: 0,
+            weights: HashMap::new(),
+            bonds: HashMap::new(),
+            model_versions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_uids_follow_the_metagraph_in_any_discovery_order() {
+        // Listed out of UID order; a later metagraph adds a third node.
+        let first = metagraph(vec![node(1, 6), node(0, 5)]);
+        let later = metagraph(vec![node(2, 7), node(0, 5), node(1, 6)]);
+
+        let mut early = IdentityRegistry::new();
+        assert_eq!(early.sync_metagraph(&first, 1).unwrap(), 2);
+        assert_eq!(early.sync_metagraph(&later, 2).unwrap(), 1);
+        assert_eq!(early.sync_metagraph(&later, 3).unwrap(), 0);
+
+        let mut late = IdentityRegistry::new();
+        assert_eq!(late.sync_metagraph(&later, 2).unwrap(), 3);
+
+        for registry in [&early, &late] {
+            assert_eq!(registry.uid_of_hotkey(&[5; 32]), Some(0));
+            assert_eq!(registry.uid_of_hotkey(&[6; 32]), Some(1));
+            assert_eq!(registry.uid_of_hotkey(&[7; 32]), Some(2));
+            assert_eq!(
+                registry.uid_of_did(&NodeIdentity::derive_did(&[107; 32])),
+                Some(2)
+            );
+        }
+        assert_eq!(early.get(2).unwrap().registered_epoch, 2);
+        // The metagraph's own UIDs are never rewritten.
+        assert_eq!(later.nodes[0].uid, 2);
+    }
+
+    #[test]
+    fn test_sync_records_rotations_and_persists() {
+        let store = Arc::new(MemoryStore::default());
+        let mut registry = IdentityRegistry::open(store.clone()).unwrap();
+        registry
+            .sync_metagraph(&metagraph(vec![node(0, 5), node(1, 6)]), 1)
+            .unwrap();
+
+        // Both nodes rotate, one onto the other's old hotkey.
+        let mut rotated = metagraph(vec![node(0, 5), node(1, 6)]);
+        rotated.nodes[0].hotkey = [9; 32];
+        rotated.nodes[1].hotkey = [5; 32];
+        assert_eq!(registry.sync_metagraph(&rotated, 4).unwrap(), 2);
+        assert_eq!(registry.uid_of_hotkey(&[9; 32]), Some(0));
+        assert_eq!(registry.uid_of_hotkey(&[5; 32]), Some(1));
+        assert_eq!(registry.uid_of_hotkey(&[6; 32]), None);
+
+        let reopened = IdentityRegistry::open(store).unwrap();
+        assert_eq!(reopened.uid_of_hotkey(&[9; 32]), Some(0));
+        assert_eq!(reopened.get(0).unwrap().registered_epoch, 1);
+    }
+
+    #[test]
+    fn test_sync_refuses_metagraphs_that_renumber() {
+        let mut registry = IdentityRegistry::new();
+        registry
+            .register([105; 32], [5; 32], NodeType::Tide, 0)
+            .unwrap();
+
+        // A registered DID under another UID, and a registered UID under
+        // another DID.
+        let moved = metagraph(vec![node(0, 6), node(1, 5)]);
+        assert!(registry.sync_metagraph(&moved, 1).is_err());
+        // A gap before the next free UID.
+        let gap = metagraph(vec![node(0, 5), node(2, 6)]);
+        assert!(registry.sync_metagraph(&gap, 1).is_err());
+        // One DID under two UIDs.
+        let mut twice = metagraph(vec![node(0, 5), node(1, 6), node(2, 6)]);
+        twice.nodes[2].hotkey = [8; 32];
+        assert!(registry.sync_metagraph(&twice, 1).is_err());
+        // A new identity taking a registered hotkey.
+        let mut stolen = metagraph(vec![node(0, 5), node(1, 6)]);
+        stolen.nodes[1].hotkey = [5; 32];
+        assert!(registry.sync_metagraph(&stolen, 1).is_err());
+
+        // Refused metagraphs change nothing.
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.uid_of_hotkey(&[6; 32]), None);
+        assert_eq!(registry.uid_of_hotkey(&[8; 32]), None);
+    }
+}
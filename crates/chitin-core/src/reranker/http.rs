@@ -0,0 +1,216 @@
+// crates/chitin-core/src/reranker/http.rs
+//
+// `HttpReranker`: cross-encoder rerank services with the Cohere/Jina-style
+// API. The query and passages are posted to `{base_url}/rerank` as
+// `{model, query, documents}`, and the reply's `results[].relevance_score`
+// are mapped back to the passages by `index`. Requests are not retried: the
+// caller's latency budget is usually shorter than a backoff.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::error::ChitinError;
+use crate::traits::Reranker;
+
+/// Settings for an `HttpReranker`.
+#[derive(Debug, Clone)]
+pub struct HttpRerankerConfig {
+    /// Service base URL, without the `/rerank` suffix.
+    pub base_url: String,
+    /// Bearer token, if the service needs one.
+    pub api_key: Option<String>,
+    /// Model name sent to the service, if it serves more than one.
+    pub model: Option<String>,
+    /// Per-request timeout.
+    pub timeout: Duration,
+}
+
+impl Default for HttpRerankerConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://127.0.0.1:8080".to_string(),
+            api_key: None,
+            model: None,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A cross-encoder rerank service.
+pub struct HttpReranker {
+    client: reqwest::Client,
+    /// Full `/rerank` endpoint URL.
+    url: String,
+    config: HttpRerankerConfig,
+}
+
+impl HttpReranker {
+    /// Create a reranker for the service described by `config`.
+    pub fn new(config: HttpRerankerConfig) -> Result<Self, ChitinError> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            url: format!("{}/rerank", config.base_url.trim_end_matches('/')),
+            config,
+        })
+    }
+
+    /// Full `/rerank` endpoint URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[async_trait]
+impl Reranker for HttpReranker {
+    async fn rerank(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, ChitinError> {
+        #[derive(Deserialize)]
+        struct RerankData {
+            index: usize,
+            relevance_score: f32,
+        }
+        #[derive(Deserialize)]
+        struct RerankResponse {
+            results: Vec<RerankData>,
+        }
+
+        if passages.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut body = serde_json::json!({
+            "query": query,
+            "documents": passages,
+            "return_documents": false,
+        });
+        if let Some(model) = &self.config.model {
+            body["model"] = serde_json::json!(model);
+        }
+        let mut request = self.client.post(&self.url).json(&body);
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChitinError::Network(format!(
+                "{} returned {}: {}",
+                self.url, status, text
+            )));
+        }
+        let parsed: RerankResponse = response
+            .json()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to parse response: {}", e)))?;
+
+        // Services may return the results sorted by score and, with
+        // `top_n`, only some of them; every passage must be scored.
+        let mut scores = vec![None; passages.len()];
+        for data in parsed.results {
+            if let Some(score) = scores.get_mut(data.index) {
+                *score = Some(data.relevance_score);
+            }
+        }
+        scores
+            .into_iter()
+            .collect::<Option<Vec<f32>>>()
+            .ok_or_else(|| {
+                ChitinError::Network(format!(
+                    "{} did not score all {} passages",
+                    self.url,
+                    passages.len()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve `/rerank`, scoring each document by its length, with results
+    /// sorted by descending score. With `drop_last`, the lowest-scored
+    /// document is left out.
+    async fn mock_service(drop_last: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 65536];
+                let mut len = 0;
+                let body = loop {
+                    len += socket.read(&mut buf[len..]).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..len]).to_string();
+                    if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length: "))
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break body.to_string();
+                        }
+                    }
+                };
+                let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let mut results: Vec<_> = body["documents"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| (i, d.as_str().unwrap().len()))
+                    .collect();
+                results.sort_by_key(|r| std::cmp::Reverse(r.1));
+                if drop_last {
+                    results.pop();
+                }
+                let results: Vec<_> = results
+                    .into_iter()
+                    .map(|(i, n)| serde_json::json!({ "index": i, "relevance_score": n }))
+                    .collect();
+                let json = serde_json::json!({ "results": results }).to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    json.len(),
+                    json
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    fn config(base_url: String) -> HttpRerankerConfig {
+        HttpRerankerConfig {
+            base_url,
+            ..HttpRerankerConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn scores_are_returned_in_passage_order() {
+        let reranker = HttpReranker::new(config(mock_service(false).await)).unwrap();
+        let scores = reranker.rerank("q", &["ab", "abcd", "a"]).await.unwrap();
+        assert_eq!(scores, vec![2.0, 4.0, 1.0]);
+        assert!(reranker.rerank("q", &[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unscored_passages_are_an_error() {
+        let reranker = HttpReranker::new(config(mock_service(true).await)).unwrap();
+        let err = reranker.rerank("q", &["ab", "a"]).await.unwrap_err();
+        assert!(err.to_string().contains("did not score"), "{}", err);
+    }
+}
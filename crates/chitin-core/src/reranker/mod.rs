@@ -0,0 +1,25 @@
+// crates/chitin-core/src/reranker/mod.rs
+//
+// `Reranker` implementations.
+//
+// - `HttpReranker` (feature `openai`): cross-encoder rerank services with the
+//   Cohere/Jina-style API (`POST {base_url}/rerank`), as served by vLLM,
+//   Infinity, llama.cpp, and the hosted rerank APIs.
+// - `OnnxReranker` (feature `onnx`): a local ONNX cross-encoder (e.g.
+//   bge-reranker-base) run with ONNX Runtime on the CPU or a CUDA GPU,
+//   loaded only if the model file matches its configured weights hash.
+//
+// The RPC server's optional rerank stage rescores search candidates through
+// this trait.
+
+#[cfg(feature = "openai")]
+pub use http::{HttpReranker, HttpRerankerConfig};
+
+#[cfg(feature = "openai")]
+mod http;
+
+#[cfg(feature = "onnx")]
+pub use onnx::{OnnxReranker, OnnxRerankerConfig};
+
+#[cfg(feature = "onnx")]
+mod onnx;
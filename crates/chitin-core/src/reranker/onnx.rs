@@ -0,0 +1,218 @@
+// crates/chitin-core/src/reranker/onnx.rs
+//
+// `OnnxReranker`: local inference with an ONNX cross-encoder (e.g.
+// bge-reranker-base or ms-marco-MiniLM) through ONNX Runtime, on the CPU or
+// a CUDA GPU.
+//
+// As with `OnnxEmbedder`, the model file is hashed when loaded and must match
+// the configured `sha256:<hex>` weights hash. Each (query, passage) pair is
+// tokenized as one sequence pair, truncated to `max_tokens`, and padded per
+// batch. Models with one output logit are scored by its sigmoid; models with
+// two (not relevant, relevant) by the softmax probability of the second.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use ort::session::Session;
+use ort::value::Tensor;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use crate::embedder::onnx::{build_session, inference_error};
+use crate::embedder::{verify_weights, Device};
+use crate::error::ChitinError;
+use crate::traits::Reranker;
+
+/// Settings for an `OnnxReranker`.
+#[derive(Debug, Clone)]
+pub struct OnnxRerankerConfig {
+    /// The exported cross-encoder (`model.onnx`).
+    pub model_path: PathBuf,
+    /// The model's Hugging Face `tokenizer.json`.
+    pub tokenizer_path: PathBuf,
+    /// Weights hash of the model file, "sha256:<hex>".
+    pub weights_hash: String,
+    pub device: Device,
+    /// Longest (query, passage) pair, in tokens; longer pairs are truncated.
+    pub max_tokens: usize,
+    /// Most pairs run through the model at once.
+    pub batch_size: usize,
+    /// CPU threads per inference (default: ONNX Runtime's choice).
+    pub threads: Option<usize>,
+}
+
+impl OnnxRerankerConfig {
+    /// Settings for the model at `model_path`, with its tokenizer next to it,
+    /// on the CPU.
+    pub fn new(model_path: impl Into<PathBuf>, weights_hash: impl Into<String>) -> Self {
+        let model_path = model_path.into();
+        let tokenizer_path = model_path.with_file_name("tokenizer.json");
+        Self {
+            model_path,
+            tokenizer_path,
+            weights_hash: weights_hash.into(),
+            device: Device::Cpu,
+            max_tokens: 512,
+            batch_size: 16,
+            threads: None,
+        }
+    }
+}
+
+/// A local ONNX cross-encoder.
+pub struct OnnxReranker {
+    model: Arc<LoadedModel>,
+    batch_size: usize,
+}
+
+/// A loaded model and tokenizer, shared with blocking inference tasks.
+struct LoadedModel {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    /// Whether the model takes `token_type_ids` (BERT does, others not).
+    type_ids: bool,
+}
+
+impl OnnxReranker {
+    /// Verify the model file against its weights hash, then load it and its
+    /// tokenizer.
+    pub fn load(config: OnnxRerankerConfig) -> Result<Self, ChitinError> {
+        if config.batch_size == 0 || config.max_tokens == 0 {
+            return Err(ChitinError::InvalidState(
+                "batch_size and max_tokens must be positive".to_string(),
+            ));
+        }
+        verify_weights(&config.model_path, &config.weights_hash)?;
+
+        let mut tokenizer = Tokenizer::from_file(&config.tokenizer_path).map_err(|e| {
+            ChitinError::InvalidState(format!(
+                "Failed to load tokenizer {}: {}",
+                config.tokenizer_path.display(),
+                e
+            ))
+        })?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: config.max_tokens,
+                ..TruncationParams::default()
+            }))
+            .map_err(|e| ChitinError::InvalidState(format!("Tokenizer truncation: {}", e)))?;
+
+        let session =
+            build_session(&config.model_path, config.device, config.threads).map_err(|e| {
+                ChitinError::InvalidState(format!(
+                    "Failed to load {} on {}: {}",
+                    config.model_path.display(),
+                    config.device,
+                    e
+                ))
+            })?;
+        let type_ids = session.inputs.iter().any(|i| i.name == "token_type_ids");
+        Ok(Self {
+            model: Arc::new(LoadedModel {
+                session: Mutex::new(session),
+                tokenizer,
+                type_ids,
+            }),
+            batch_size: config.batch_size,
+        })
+    }
+}
+
+impl LoadedModel {
+    /// Tokenize and run one batch of (query, passage) pairs, returning one
+    /// relevance score per pair. Blocks on inference.
+    fn run_batch(&self, pairs: Vec<(String, String)>) -> Result<Vec<f32>, ChitinError> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(|e| ChitinError::InvalidState(format!("Tokenization failed: {}", e)))?;
+        let batch = encodings.len();
+        let seq = encodings.first().map_or(0, |e| e.len());
+        let shape = vec![batch as i64, seq as i64];
+        let column = |f: fn(&tokenizers::Encoding) -> &[u32]| -> Vec<i64> {
+            encodings
+                .iter()
+                .flat_map(|e| f(e).iter().map(|&v| v as i64))
+                .collect()
+        };
+
+        let tensor =
+            |data: Vec<i64>| Tensor::from_array((shape.clone(), data)).map_err(inference_error);
+        let mut inputs = vec![
+            ("input_ids", tensor(column(tokenizers::Encoding::get_ids))?),
+            (
+                "attention_mask",
+                tensor(column(tokenizers::Encoding::get_attention_mask))?,
+            ),
+        ];
+        if self.type_ids {
+            inputs.push((
+                "token_type_ids",
+                tensor(column(tokenizers::Encoding::get_type_ids))?,
+            ));
+        }
+
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|_| ChitinError::InvalidState("ONNX session poisoned".to_string()))?;
+        let outputs = session.run(inputs).map_err(inference_error)?;
+        let (dims, logits) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(inference_error)?;
+        match **dims {
+            [b] | [b, 1] if b as usize == batch => Ok(logits.iter().map(|&l| sigmoid(l)).collect()),
+            [b, 2] if b as usize == batch => Ok(logits
+                .chunks(2)
+                .map(|pair| sigmoid(pair[1] - pair[0]))
+                .collect()),
+            _ => Err(ChitinError::InvalidState(format!(
+                "Unexpected cross-encoder output shape {:?} for {} pairs",
+                &**dims, batch
+            ))),
+        }
+    }
+}
+
+/// The logistic function. For two logits, `sigmoid(b - a)` is the softmax
+/// probability of the second.
+fn sigmoid(logit: f32) -> f32 {
+    1.0 / (1.0 + (-logit).exp())
+}
+
+#[async_trait]
+impl Reranker for OnnxReranker {
+    async fn rerank(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, ChitinError> {
+        let mut scores = Vec::with_capacity(passages.len());
+        for batch in passages.chunks(self.batch_size) {
+            let pairs: Vec<(String, String)> = batch
+                .iter()
+                .map(|p| (query.to_string(), p.to_string()))
+                .collect();
+            let loaded = self.model.clone();
+            let batch_scores = tokio::task::spawn_blocking(move || loaded.run_batch(pairs))
+                .await
+                .map_err(|e| {
+                    ChitinError::InvalidState(format!("Inference task failed: {}", e))
+                })??;
+            scores.extend(batch_scores);
+        }
+        Ok(scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_logits_score_as_the_softmax_of_the_second() {
+        assert_eq!(sigmoid(0.0), 0.5);
+        assert!(sigmoid(4.0) > 0.98);
+        let (a, b) = (0.3f32, 1.7f32);
+        let softmax = b.exp() / (a.exp() + b.exp());
+        assert!((sigmoid(b - a) - softmax).abs() < 1e-6);
+    }
+}
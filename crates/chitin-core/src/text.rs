@@ -0,0 +1,103 @@
+// crates/chitin-core/src/text.rs
+//
+// Text chunking shared by URL ingestion on Coral nodes and client-side
+// imports in the CLI, so both split documents the same way, and the token
+// estimate used to fit retrieved text into LLM prompt budgets.
+
+/// Split `text` into chunks of at most `chunk_size` characters on word
+/// boundaries, each starting with about `chunk_overlap` characters of the
+/// previous one. A single word longer than `chunk_size` is its own chunk.
+pub fn chunk_text(text: &str, chunk_size: usize, chunk_overlap: usize) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let lengths: Vec<usize> = words.iter().map(|w| w.chars().count()).collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < words.len() {
+        // Take words while they fit (always at least one).
+        let mut end = start + 1;
+        let mut len = lengths[start];
+        while end < words.len() && len + 1 + lengths[end] <= chunk_size {
+            len += 1 + lengths[end];
+            end += 1;
+        }
+        chunks.push(words[start..end].join(" "));
+        if end == words.len() {
+            break;
+        }
+
+        // Back up to share up to `chunk_overlap` characters, always moving on.
+        let mut next = end;
+        let mut shared = 0;
+        while next > start + 1 && shared + lengths[next - 1] < chunk_overlap {
+            next -= 1;
+            shared += lengths[next] + 1;
+        }
+        start = next;
+    }
+    chunks
+}
+
+/// Check chunking parameters: `chunk_size` must be positive and larger
+/// than `chunk_overlap`.
+pub fn validate_chunking(chunk_size: usize, chunk_overlap: usize) -> Result<(), String> {
+    if chunk_size == 0 || chunk_overlap >= chunk_size {
+        return Err(format!(
+            "chunk_size must be positive and larger than chunk_overlap (got {} and {})",
+            chunk_size, chunk_overlap
+        ));
+    }
+    Ok(())
+}
+
+/// Rough LLM token count of `text`: about four characters per token, and
+/// at least one per word. No tokenizer is involved, so budgets built on it
+/// should leave some headroom.
+pub fn estimate_tokens(text: &str) -> usize {
+    let chars = text.chars().count();
+    let words = text.split_whitespace().count();
+    chars.div_ceil(4).max(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_fit_and_overlap() {
+        let text = "one two three four five six seven eight nine ten";
+        let chunks = chunk_text(text, 14, 6);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 14, "{:?} too long", chunk);
+        }
+        // Each chunk after the first starts with the previous chunk's last word.
+        for pair in chunks.windows(2) {
+            let last = pair[0].rsplit(' ').next().unwrap();
+            assert!(pair[1].starts_with(last));
+        }
+        assert!(chunks.last().unwrap().ends_with("ten"));
+    }
+
+    #[test]
+    fn test_long_word_is_its_own_chunk() {
+        let chunks = chunk_text("a supercalifragilistic b", 5, 0);
+        assert_eq!(chunks, vec!["a", "supercalifragilistic", "b"]);
+        assert!(chunk_text("  \n ", 10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_validate_chunking() {
+        assert!(validate_chunking(100, 10).is_ok());
+        assert!(validate_chunking(0, 0).is_err());
+        assert!(validate_chunking(10, 10).is_err());
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("a b c d e"), 5);
+        assert_eq!(estimate_tokens("héllo"), 2);
+    }
+}
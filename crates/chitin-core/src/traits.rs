@@ -4,8 +4,10 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::consensus::PolypScores;
+use crate::embedding::{EmbeddingModelId, VectorEmbedding};
 use crate::error::ChitinError;
 use crate::polyp::{Polyp, PolypState, ZkProof};
+use crate::registry::IdentityRecord;
 
 /// Trait for persistent Polyp storage.
 ///
@@ -25,6 +27,17 @@ pub trait PolypStore: Send + Sync {
     async fn delete_polyp(&self, id: &Uuid) -> Result<(), ChitinError>;
 }
 
+/// Trait for persisting the identity registry.
+///
+/// Implemented by chitin-store (RocksDB backend).
+pub trait IdentityStore: Send + Sync {
+    /// Save a registry record, overwriting any record with the same UID.
+    fn save_identity(&self, record: &IdentityRecord) -> Result<(), ChitinError>;
+
+    /// Load every saved record, in any order.
+    fn load_identities(&self) -> Result<Vec<IdentityRecord>, ChitinError>;
+}
+
 /// Trait for ZK proof verification.
 ///
 /// Implemented by chitin-verify.
@@ -56,3 +69,28 @@ pub trait VectorIndex: Send + Sync {
     /// Delete a vector from the index by its UUID.
     async fn delete(&self, id: &Uuid) -> Result<(), ChitinError>;
 }
+
+/// Trait for turning text into embedding vectors.
+///
+/// Implemented by the `embedder` module (built-in hash embedding;
+/// OpenAI-compatible HTTP APIs with the `openai` feature).
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Embed `texts` with `model`, returning one vector per text in order.
+    async fn embed(
+        &self,
+        texts: &[&str],
+        model: &EmbeddingModelId,
+    ) -> Result<Vec<VectorEmbedding>, ChitinError>;
+}
+
+/// Trait for scoring passages against a query with a cross-encoder.
+///
+/// Implemented by the `reranker` module (HTTP rerank services with the
+/// `openai` feature; local ONNX cross-encoders with the `onnx` feature).
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Score each of `passages` for relevance to `query`, returning one
+    /// score per passage in order; higher is more relevant.
+    async fn rerank(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, ChitinError>;
+}
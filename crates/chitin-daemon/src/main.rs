@@ -2,37 +2,27 @@
 //
 // Binary entrypoint for the Chitin Protocol daemon.
 //
-// Initializes tracing, parses CLI arguments, loads configuration,
-// constructs shared state, spawns epoch scheduler, and starts the
-// appropriate node type (Coral, Tide, or Hybrid).
-
-mod config;
-mod consensus_runner;
-mod coral;
-mod epoch_events;
-mod gossip;
-mod hardening_pipeline;
-mod peers;
-mod scheduler;
-mod shared;
-mod state;
-mod sync_loop;
-mod tide;
+// Initializes tracing, parses CLI arguments, loads configuration, and starts
+// the configured node type (Coral, Tide, Hybrid, or Seed) with
+// `chitin_node::NodeBuilder`, stopping it on Ctrl-C. Hot-reloadable settings
+// are re-read on SIGHUP, config file changes, and `admin/config/reload`.
+// Recent log events are kept in memory for `admin/logs`. The polyp store is
+// migrated to this build's schema at startup; `--migrations-dry-run` only
+// reports what that would change.
 
 use std::sync::Arc;
 
 use clap::Parser;
-use config::DaemonConfig;
-use coral::CoralNode;
-use scheduler::EpochScheduler;
-use shared::DaemonSharedState;
-use state::{NodeState, NodeStateMachine};
-use tide::TideNode;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
-use chitin_core::identity::{NodeIdentity, NodeType};
-use chitin_rpc::{ChitinRpcServer, RpcConfig};
-use chitin_store::{HardenedStore, InMemoryVectorIndex, IpfsClient, RocksStore};
-use peers::PeerRegistry;
+use chitin_node::logs::LogBuffer;
+use chitin_node::node::expand_tilde;
+use chitin_node::reload::LogLevelSetter;
+use chitin_node::telemetry::OtlpLayer;
+use chitin_node::unlock::{self, UnlockOptions};
+use chitin_node::{systemd, DaemonConfig, NodeBuilder};
+use chitin_store::{migration, RocksStore};
 
 /// Chitin Protocol daemon — runs Coral and/or Tide node processes.
 #[derive(Parser, Debug)]
@@ -42,23 +32,96 @@ struct Args {
     #[arg(long, default_value = "~/.chitin/config.toml")]
     config: String,
 
-    /// Node type to run: coral, tide, or hybrid.
+    /// Node type to run: coral, tide, hybrid, or seed (bootstrap only).
     #[arg(long, default_value = "hybrid")]
     node_type: String,
+
+    /// Network profile to run against: devnet, testnet, mainnet, or a
+    /// network defined in `[networks.<name>]`. Overrides `network`.
+    #[arg(long)]
+    network: Option<String>,
+
+    /// Retain all history and serve historical queries. Overrides
+    /// `archival`.
+    #[arg(long)]
+    archival: bool,
+
+    /// Load this signed snapshot (a file, an http(s) URL, or ipfs://CID)
+    /// into an empty store before syncing. Overrides
+    /// `[snapshot] bootstrap_from`.
+    #[arg(long, value_name = "SOURCE")]
+    bootstrap_from_snapshot: Option<String>,
+
+    /// Prompt on the terminal for the hotkey keystore passphrase.
+    #[arg(long)]
+    unlock: bool,
+
+    /// Read the hotkey keystore passphrase from this file descriptor.
+    #[arg(long, value_name = "FD")]
+    passphrase_fd: Option<i32>,
+
+    /// Encrypt the plaintext hotkey secret into a keystore and exit.
+    #[arg(long)]
+    encrypt_hotkey: bool,
+
+    /// Print a systemd unit file for this configuration and exit.
+    #[arg(long)]
+    print_systemd_unit: bool,
+
+    /// Print the schema migrations the polyp store needs, without applying
+    /// them, and exit.
+    #[arg(long)]
+    migrations_dry_run: bool,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize tracing subscriber for structured logging.
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Clear the keystore passphrase from the environment while this is the
+    // only thread, before the runtime starts its workers.
+    unlock::take_env_passphrase();
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(run())
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize tracing subscriber for structured logging. Without
+    // `RUST_LOG`, the filter follows the configured log level below. Spans
+    // are exported over OTLP once `[otlp]` is enabled, and events that pass
+    // the filter are buffered for `admin/logs`.
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env().ok();
+    let log_from_env = env_filter.is_some();
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(env_filter.unwrap_or_else(|| tracing_subscriber::EnvFilter::new("info")))
+        .with_filter_reloading();
+    let log_reload = subscriber.reload_handle();
+    let otlp = OtlpLayer::new();
+    let log_buffer = LogBuffer::default();
+    subscriber
+        .finish()
+        .with(otlp.clone())
+        .with(log_buffer.clone())
         .init();
 
     let args = Args::parse();
 
+    // The unit file goes to stdout, so print it before anything is logged.
+    if args.print_systemd_unit {
+        let mut unit_config = DaemonConfig::load(&args.config).unwrap_or_default();
+        unit_config.node_type = args.node_type.clone();
+        unit_config.archival |= args.archival;
+        if args.network.is_some() {
+            unit_config.network = args.network.clone();
+        }
+        let config_path = expand_tilde(&args.config);
+        let config_path = std::fs::canonicalize(&config_path)
+            .map(|path| path.display().to_string())
+            .unwrap_or(config_path);
+        let data_dir = expand_tilde(&unit_config.data_dir);
+        print!("{}", systemd::unit_file(&unit_config, &config_path, &data_dir));
+        return Ok(());
+    }
+
     // Load configuration from TOML file, falling back to defaults if the file
     // is not found.
     let mut daemon_config = match DaemonConfig::load(&args.config) {
@@ -78,384 +141,88 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // CLI --node-type flag overrides the config file value.
     daemon_config.node_type = args.node_type.clone();
-
-    tracing::info!("Chitin Protocol Daemon v0.1.0");
-    tracing::info!("Node type: {}", daemon_config.node_type);
-    tracing::info!("Data directory: {}", daemon_config.data_dir);
-    tracing::info!(
-        "RPC endpoint: {}:{}",
-        daemon_config.rpc_host,
-        daemon_config.rpc_port
-    );
-    tracing::info!("P2P port: {}", daemon_config.p2p_port);
-    tracing::info!("Blocks per epoch: {}", daemon_config.blocks_per_epoch);
-
-    // ---------------------------------------------------------------
-    // Phase 2: Load cryptographic identity from key files.
-    // ---------------------------------------------------------------
-    let (node_identity, signing_key) = load_node_identity(&daemon_config);
-
-    if node_identity.is_placeholder() {
-        tracing::warn!(
-            "Running with placeholder identity — no key files found. \
-             Generate keys with `chitin init`."
-        );
-    } else {
-        tracing::info!("Node DID: {}", node_identity.did);
+    if args.archival {
+        daemon_config.archival = true;
+    }
+    if args.bootstrap_from_snapshot.is_some() {
+        daemon_config.snapshot.bootstrap_from = args.bootstrap_from_snapshot.clone();
     }
 
-    // ---------------------------------------------------------------
-    // Phase 4: Construct shared state infrastructure.
-    // ---------------------------------------------------------------
-
-    // Create IPFS client and HardenedStore (optional — requires IPFS running).
-    let ipfs_client = IpfsClient::new(&daemon_config.ipfs_api_url);
-    let data_dir = expand_tilde(&daemon_config.data_dir);
-    let hardened_db_path = format!("{}/hardened_rocksdb", data_dir);
+    // Apply the selected network's defaults (CLI --network overrides).
+    if args.network.is_some() {
+        daemon_config.network = args.network.clone();
+    }
 
-    let hardened_store = match RocksStore::open(&hardened_db_path) {
-        Ok(cache_db) => {
-            let hs = HardenedStore::new(cache_db, ipfs_client);
-            tracing::info!("HardenedStore initialized at {}", hardened_db_path);
-            Some(Arc::new(hs))
-        }
-        Err(e) => {
-            tracing::warn!("Failed to open HardenedStore: {}. Hardening disabled.", e);
-            None
-        }
+    let unlock_options = UnlockOptions {
+        prompt: args.unlock,
+        passphrase_fd: args.passphrase_fd,
     };
+    if args.encrypt_hotkey {
+        let hotkey_path = expand_tilde(&daemon_config.hotkey_path);
+        unlock::encrypt_hotkey_file(&hotkey_path, &unlock_options)
+            .map_err(|e| format!("Could not encrypt hotkey: {}", e))?;
+        tracing::info!("Encrypted hotkey keystore written to {}", hotkey_path);
+        return Ok(());
+    }
 
-    // Create DaemonSharedState.
-    let shared_state = DaemonSharedState::new(
-        daemon_config.blocks_per_epoch,
-        hardened_store.clone(),
-    );
-
-    // Create broadcast channel for epoch events.
-    let (event_tx, _) = tokio::sync::broadcast::channel::<epoch_events::EpochEvent>(64);
-
-    // Initialize the node state machine.
-    let mut state_machine = NodeStateMachine::new();
-    state_machine.transition(NodeState::Syncing)?;
-    state_machine.transition(NodeState::Ready)?;
-
-    // Start the appropriate node based on the configured type.
-    match daemon_config.node_type.as_str() {
-        "coral" => {
-            let node = CoralNode::new(&daemon_config)?
-                .with_identity(node_identity.clone(), signing_key);
-            let store = node.store();
-            let index = Arc::new(InMemoryVectorIndex::new());
-
-            let rpc_config = RpcConfig {
-                host: daemon_config.rpc_host.clone(),
-                port: daemon_config.rpc_port,
-            };
-            let mut rpc_server = ChitinRpcServer::new(rpc_config, store.clone(), index.clone())
-                .with_peer_info(daemon_config.peers.clone())
-                .with_identity(node_identity.clone(), signing_key)
-                .with_self_url(daemon_config.self_url.clone())
-                .with_epoch_manager(shared_state.epoch_manager.clone())
-                .with_consensus_result(shared_state.last_consensus_result.clone())
-                .with_weight_matrix(shared_state.weight_matrix.clone())
-                .with_bond_matrix(shared_state.bond_matrix.clone())
-                .with_metagraph_manager(shared_state.metagraph_manager.clone())
-                .with_hardened_store(hardened_store.clone())
-                .with_start_time(shared_state.start_time);
-
-            // Wire up peer networking if peers are configured.
-            if !daemon_config.peers.is_empty() {
-                let registry = Arc::new(PeerRegistry::new(
-                    daemon_config.self_url.clone(),
-                    daemon_config.peers.clone(),
-                ));
-                tracing::info!(
-                    "Peer networking enabled: {} peers configured",
-                    daemon_config.peers.len()
-                );
-
-                // Set up gossip callback for polyp broadcast with real DID.
-                let gossip_registry = registry.clone();
-                let gossip_did = if !node_identity.is_placeholder() {
-                    Some(node_identity.did.clone())
-                } else {
-                    None
-                };
-                rpc_server = rpc_server.with_gossip_callback(Arc::new(move |polyp| {
-                    gossip::broadcast_polyp(gossip_registry.clone(), polyp, gossip_did.clone());
-                }));
-
-                // Spawn announce to all peers.
-                let announce_registry = registry.clone();
-                tokio::spawn(async move {
-                    announce_registry.announce_to_all().await;
-                });
-
-                // Spawn sync loop (30s interval).
-                let sync_registry = registry.clone();
-                let sync_store = store.clone();
-                let sync_index = index.clone();
-                tokio::spawn(async move {
-                    sync_loop::run_sync_loop(sync_registry, sync_store, sync_index, 30).await;
-                });
-            }
-
-            // Spawn epoch scheduler.
-            let mut scheduler = EpochScheduler::new(
-                daemon_config.blocks_per_epoch,
-                shared_state.epoch_manager.clone(),
-                event_tx.clone(),
-            );
-            tokio::spawn(async move {
-                if let Err(e) = scheduler.run().await {
-                    tracing::error!("Epoch scheduler error: {}", e);
-                }
-            });
-
-            // Spawn RPC server in background, run node in foreground.
-            tokio::spawn(async move {
-                if let Err(e) = rpc_server.start().await {
-                    tracing::error!("RPC server error: {}", e);
-                }
-            });
-
-            node.start().await?;
-        }
-        "tide" => {
-            // Tide-only mode needs a store for reading polyps.
-            let rocksdb_path = format!("{}/rocksdb", data_dir);
-            let store = Arc::new(
-                RocksStore::open(&rocksdb_path)
-                    .map_err(|e| format!("Failed to open RocksDB: {}", e))?,
-            );
-
-            let event_rx = event_tx.subscribe();
-            let node = TideNode::new(
-                &daemon_config,
-                event_rx,
-                shared_state.clone(),
-                store,
-            )?;
-
-            // Spawn epoch scheduler.
-            let mut scheduler = EpochScheduler::new(
-                daemon_config.blocks_per_epoch,
-                shared_state.epoch_manager.clone(),
-                event_tx.clone(),
-            );
-            tokio::spawn(async move {
-                if let Err(e) = scheduler.run().await {
-                    tracing::error!("Epoch scheduler error: {}", e);
-                }
-            });
-
-            node.start().await?;
-        }
-        "hybrid" => {
-            tracing::info!("Running in Hybrid mode (Coral + Tide)");
-            let coral = CoralNode::new(&daemon_config)?
-                .with_identity(node_identity.clone(), signing_key);
-            let store = coral.store();
-            let index = Arc::new(InMemoryVectorIndex::new());
-
-            let rpc_config = RpcConfig {
-                host: daemon_config.rpc_host.clone(),
-                port: daemon_config.rpc_port,
-            };
-            let mut rpc_server = ChitinRpcServer::new(rpc_config, store.clone(), index.clone())
-                .with_peer_info(daemon_config.peers.clone())
-                .with_identity(node_identity.clone(), signing_key)
-                .with_self_url(daemon_config.self_url.clone())
-                .with_epoch_manager(shared_state.epoch_manager.clone())
-                .with_consensus_result(shared_state.last_consensus_result.clone())
-                .with_weight_matrix(shared_state.weight_matrix.clone())
-                .with_bond_matrix(shared_state.bond_matrix.clone())
-                .with_metagraph_manager(shared_state.metagraph_manager.clone())
-                .with_hardened_store(hardened_store.clone())
-                .with_start_time(shared_state.start_time);
-
-            // Wire up peer networking if peers are configured.
-            if !daemon_config.peers.is_empty() {
-                let registry = Arc::new(PeerRegistry::new(
-                    daemon_config.self_url.clone(),
-                    daemon_config.peers.clone(),
-                ));
-                tracing::info!(
-                    "Peer networking enabled: {} peers configured",
-                    daemon_config.peers.len()
-                );
-
-                // Set up gossip callback for polyp broadcast with real DID.
-                let gossip_registry = registry.clone();
-                let gossip_did = if !node_identity.is_placeholder() {
-                    Some(node_identity.did.clone())
-                } else {
-                    None
-                };
-                rpc_server = rpc_server.with_gossip_callback(Arc::new(move |polyp| {
-                    gossip::broadcast_polyp(gossip_registry.clone(), polyp, gossip_did.clone());
-                }));
-
-                // Spawn announce to all peers.
-                let announce_registry = registry.clone();
-                tokio::spawn(async move {
-                    announce_registry.announce_to_all().await;
-                });
-
-                // Spawn sync loop (30s interval).
-                let sync_registry = registry.clone();
-                let sync_store = store.clone();
-                let sync_index = index.clone();
-                tokio::spawn(async move {
-                    sync_loop::run_sync_loop(sync_registry, sync_store, sync_index, 30).await;
-                });
-            }
-
-            // Create Tide node with epoch event receiver.
-            let event_rx = event_tx.subscribe();
-            let tide = TideNode::new(
-                &daemon_config,
-                event_rx,
-                shared_state.clone(),
-                store.clone(),
-            )?;
-
-            // Spawn epoch scheduler.
-            let mut scheduler = EpochScheduler::new(
-                daemon_config.blocks_per_epoch,
-                shared_state.epoch_manager.clone(),
-                event_tx.clone(),
+    if args.migrations_dry_run {
+        let data_dir = expand_tilde(&daemon_config.data_dir);
+        let db_name = if daemon_config.node_type == "seed" {
+            "seed_rocksdb"
+        } else {
+            "rocksdb"
+        };
+        let db_path = format!("{}/{}", data_dir, db_name);
+        let store = RocksStore::open_with_durability(&db_path, daemon_config.durability)?;
+        let plan = migration::plan(&store)?;
+        println!(
+            "{}: schema version {}, this build {}",
+            db_path, plan.current, plan.target
+        );
+        for step in &plan.steps {
+            println!(
+                "  {} {}: {} keys to read, {} to change",
+                step.version, step.description, step.scanned, step.changes
             );
-            tokio::spawn(async move {
-                if let Err(e) = scheduler.run().await {
-                    tracing::error!("Epoch scheduler error: {}", e);
-                }
-            });
-
-            // Spawn RPC server in background, run both nodes concurrently.
-            tokio::spawn(async move {
-                if let Err(e) = rpc_server.start().await {
-                    tracing::error!("RPC server error: {}", e);
-                }
-            });
-
-            tokio::select! {
-                result = coral.start() => {
-                    if let Err(e) = result {
-                        tracing::error!("Coral node error: {}", e);
-                    }
-                }
-                result = tide.start() => {
-                    if let Err(e) = result {
-                        tracing::error!("Tide node error: {}", e);
-                    }
-                }
-            }
-        }
-        other => {
-            tracing::error!("Unknown node type: {}. Use 'coral', 'tide', or 'hybrid'.", other);
-            return Err(format!("Unknown node type: {}", other).into());
         }
+        return Ok(());
     }
 
-    // Transition to shutting down.
-    let _ = state_machine.transition(NodeState::ShuttingDown);
-    tracing::info!("Chitin daemon shut down gracefully");
-
-    Ok(())
-}
-
-/// Load the node identity from key files on disk.
-///
-/// Reads the hotkey secret and coldkey public key from hex-encoded files,
-/// derives the hotkey public key from the secret, and constructs a
-/// `NodeIdentity`. Returns a placeholder identity if the files are not found.
-fn load_node_identity(config: &DaemonConfig) -> (NodeIdentity, Option<[u8; 32]>) {
-    let hotkey_path = expand_tilde(&config.hotkey_path);
-    let coldkey_pub_path = expand_tilde(&config.coldkey_pub_path);
-
-    let hotkey_secret = match std::fs::read_to_string(&hotkey_path) {
-        Ok(hex_str) => match hex_decode(hex_str.trim()) {
-            Some(bytes) if bytes.len() == 32 => {
-                let mut arr = [0u8; 32];
-                arr.copy_from_slice(&bytes);
-                Some(arr)
-            }
-            _ => {
-                tracing::warn!("Invalid hotkey secret at {}", hotkey_path);
-                None
-            }
-        },
-        Err(_) => {
-            tracing::debug!("Hotkey secret not found at {}", hotkey_path);
-            None
-        }
-    };
+    if daemon_config.otlp.enabled {
+        otlp.install(&daemon_config.otlp)
+            .map_err(|e| format!("Invalid OTLP config: {}", e))?;
+        tracing::info!("Exporting traces to {}", daemon_config.otlp.endpoint);
+    }
 
-    let coldkey_pub = match std::fs::read_to_string(&coldkey_pub_path) {
-        Ok(hex_str) => match hex_decode(hex_str.trim()) {
-            Some(bytes) if bytes.len() == 32 => {
-                let mut arr = [0u8; 32];
-                arr.copy_from_slice(&bytes);
-                Some(arr)
-            }
-            _ => {
-                tracing::warn!("Invalid coldkey public key at {}", coldkey_pub_path);
-                None
+    tracing::info!("Chitin Protocol Daemon v0.1.0");
+    let mut builder = NodeBuilder::new(daemon_config)
+        .with_config_file(&args.config)
+        .with_unlock(unlock_options)
+        .with_log_buffer(log_buffer);
+    if !log_from_env {
+        let set_log_level: LogLevelSetter = Arc::new(move |level| {
+            let filter = tracing_subscriber::EnvFilter::try_new(level).map_err(|e| e.to_string())?;
+            log_reload.reload(filter).map_err(|e| e.to_string())
+        });
+        builder = builder.with_log_level_setter(set_log_level);
+    }
+    let mut node = builder.start().await?;
+
+    // Run until Ctrl-C, or until the node fails.
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => tracing::info!("Received shutdown signal"),
+        result = node.wait() => {
+            if let Err(e) = result {
+                tracing::error!("Node error: {}", e);
             }
-        },
-        Err(_) => {
-            tracing::debug!("Coldkey public key not found at {}", coldkey_pub_path);
-            None
-        }
-    };
-
-    match (hotkey_secret, coldkey_pub) {
-        (Some(secret), Some(coldkey)) => {
-            // Derive hotkey public key from the secret.
-            let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
-            let hotkey_pub = signing_key.verifying_key().to_bytes();
-
-            // Determine node type from config.
-            let node_type = match config.node_type.as_str() {
-                "coral" => NodeType::Coral,
-                "tide" => NodeType::Tide,
-                _ => NodeType::Hybrid,
-            };
-
-            let identity = NodeIdentity::from_keypairs(hotkey_pub, coldkey, node_type);
-            (identity, Some(secret))
-        }
-        _ => {
-            // Placeholder identity when keys are not available.
-            let identity = NodeIdentity {
-                coldkey: [0u8; 32],
-                hotkey: [0u8; 32],
-                did: "did:chitin:local".to_string(),
-                node_type: NodeType::Hybrid,
-            };
-            (identity, None)
         }
     }
-}
 
-/// Expand `~` at the start of a path to the user's home directory.
-fn expand_tilde(path: &str) -> String {
-    if path.starts_with("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return format!("{}{}", home.display(), &path[1..]);
-        }
+    // Stop background jobs, save runtime state, and wipe the in-memory hotkey.
+    if let Err(e) = node.stop().await {
+        tracing::error!("Node error: {}", e);
     }
-    path.to_string()
-}
+    tracing::info!("Chitin daemon shut down gracefully");
 
-/// Decode a hex string into bytes. Returns None if the string is invalid hex.
-fn hex_decode(hex: &str) -> Option<Vec<u8>> {
-    if hex.len() % 2 != 0 {
-        return None;
-    }
-    (0..hex.len())
-        .step_by(2)
-        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
-        .collect()
+    Ok(())
 }
@@ -0,0 +1,264 @@
+// crates/chitin-daemon/tests/crash_recovery.rs
+//
+// Crash recovery tests for the daemon's write path.
+//
+// Each test re-runs this test binary as a child process that drives polyps
+// through the same writes the consensus and hardening pipelines make
+// (UnderReview -> Approved -> checkpoint -> Hardened, flushing at each
+// commit) and acknowledges every committed polyp on stdout. The parent kills
+// the child with SIGKILL while it is still writing, reopens the store, and
+// checks that every acknowledged polyp survived in its committed state with
+// a consistent state index, under each durability mode.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use chitin_consensus::hardening::HardeningCheckpoint;
+use chitin_core::embedding::{EmbeddingModelId, VectorEmbedding};
+use chitin_core::identity::{NodeIdentity, NodeType};
+use chitin_core::polyp::{Payload, Polyp, PolypState, PolypSubject, ProofPublicInputs, ZkProof};
+use chitin_core::provenance::{PipelineStep, ProcessingPipeline, Provenance, SourceAttribution};
+use chitin_core::traits::PolypStore;
+use chitin_store::{Durability, RocksStore};
+
+/// Store path the child writes to; unset outside a crash test.
+const DB_ENV: &str = "CHITIN_CRASH_TEST_DB";
+/// Durability mode (JSON) the child opens the store with.
+const DURABILITY_ENV: &str = "CHITIN_CRASH_TEST_DURABILITY";
+/// Acknowledged commits to wait for before killing the child.
+const ACKS_BEFORE_KILL: usize = 40;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Create a temporary directory path using UUID to avoid conflicts.
+fn temp_db_path(label: &str) -> String {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("chitin_test_{}_{}", label, Uuid::now_v7()));
+    path.to_string_lossy().to_string()
+}
+
+/// Create a test Polyp with the given content and state.
+fn make_test_polyp(content: &str, state: PolypState) -> Polyp {
+    let now = chrono::Utc::now();
+    let model_id = EmbeddingModelId {
+        provider: "test".to_string(),
+        name: "test-model".to_string(),
+        weights_hash: [0u8; 32],
+        dimensions: 4,
+    };
+
+    Polyp {
+        id: Uuid::now_v7(),
+        state,
+        subject: PolypSubject {
+            payload: Payload {
+                content: content.to_string(),
+                content_type: "text/plain".to_string(),
+                language: Some("en".to_string()),
+            },
+            vector: VectorEmbedding {
+                values: vec![0.5, 0.5, 0.5, 0.5],
+                model_id: model_id.clone(),
+                quantization: "float32".to_string(),
+                normalization: "l2".to_string(),
+            },
+            provenance: Provenance {
+                creator: NodeIdentity {
+                    coldkey: [1u8; 32],
+                    hotkey: [0u8; 32],
+                    did: "did:chitin:test".to_string(),
+                    node_type: NodeType::Coral,
+                },
+                source: SourceAttribution {
+                    source_cid: None,
+                    source_url: Some("https://example.com".to_string()),
+                    title: Some("Test Content".to_string()),
+                    license: None,
+                    accessed_at: now,
+                },
+                pipeline: ProcessingPipeline {
+                    steps: vec![PipelineStep {
+                        name: "embed".to_string(),
+                        version: "1.0".to_string(),
+                        params: serde_json::json!({}),
+                        hash: None,
+                    }],
+                    duration_ms: 50,
+                },
+                molted_from: vec![],
+            },
+        },
+        proof: ZkProof {
+            proof_type: "SP1Groth16".to_string(),
+            proof_value: "abcdef1234567890".to_string(),
+            vk_hash: "test_vk".to_string(),
+            public_inputs: ProofPublicInputs {
+                text_hash: [0u8; 32],
+                vector_hash: [0u8; 32],
+                model_id,
+            },
+            created_at: now,
+        },
+        consensus: None,
+        hardening: None,
+        created_at: now,
+        updated_at: now,
+        signature: None,
+        reef_zone: None,
+    }
+}
+
+/// An acknowledged commit: the polyp, its committed state, and its epoch.
+struct Ack {
+    id: Uuid,
+    hardened: bool,
+    epoch: u64,
+}
+
+/// Parse a child's `ACK {approved|hardened} {epoch} {id}` line.
+fn parse_ack(line: &str) -> Option<Ack> {
+    let mut parts = line.strip_prefix("ACK ")?.split(' ');
+    let hardened = match parts.next()? {
+        "approved" => false,
+        "hardened" => true,
+        _ => return None,
+    };
+    let epoch = parts.next()?.parse().ok()?;
+    let id = parts.next()?.parse().ok()?;
+    Some(Ack { id, hardened, epoch })
+}
+
+/// Run the writer child under `durability`, kill it mid-write, and check the
+/// reopened store against its acknowledgements.
+async fn crash_and_recover(label: &str, durability: Durability) {
+    let path = temp_db_path(label);
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args(["crash_writer", "--exact", "--nocapture", "--test-threads=1"])
+        .env(DB_ENV, &path)
+        .env(DURABILITY_ENV, serde_json::to_string(&durability).unwrap())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn writer");
+
+    // Keep the pipe open until the kill, so the writer dies mid-write rather
+    // than on a failed print.
+    let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+    let mut acks = Vec::new();
+    for line in stdout.by_ref() {
+        let line = line.expect("read writer output");
+        if let Some(ack) = parse_ack(&line) {
+            acks.push(ack);
+        }
+        if acks.len() >= ACKS_BEFORE_KILL {
+            break;
+        }
+    }
+    assert!(acks.len() >= ACKS_BEFORE_KILL, "writer exited before {} acks", ACKS_BEFORE_KILL);
+    // Let the writer get partway into its next commit, then kill it.
+    std::thread::sleep(Duration::from_millis(5));
+    child.kill().expect("kill writer");
+    let _ = child.wait();
+    drop(stdout);
+
+    let store = RocksStore::open_with_durability(&path, durability).expect("reopen store");
+    let approved = store.list_polyps_by_state(&PolypState::Approved).await.unwrap();
+    let hardened = store.list_polyps_by_state(&PolypState::Hardened).await.unwrap();
+    for ack in &acks {
+        let polyp = store
+            .get_polyp(&ack.id)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("acknowledged polyp {} lost", ack.id));
+        if ack.hardened {
+            assert_eq!(polyp.state, PolypState::Hardened, "hardened polyp {} reverted", ack.id);
+            let checkpoint = HardeningCheckpoint::load(&store, ack.epoch).unwrap();
+            assert!(checkpoint.is_some(), "checkpoint for epoch {} lost", ack.epoch);
+        } else {
+            assert!(
+                matches!(polyp.state, PolypState::Approved | PolypState::Hardened),
+                "approved polyp {} reverted to {:?}",
+                ack.id,
+                polyp.state
+            );
+        }
+    }
+
+    // Every indexed polyp is indexed under its stored state, and only once.
+    for (state, listed) in [(PolypState::Approved, &approved), (PolypState::Hardened, &hardened)] {
+        for polyp in listed {
+            assert_eq!(polyp.state, state, "polyp {} indexed under a stale state", polyp.id);
+        }
+    }
+    let under_review = store.list_polyps_by_state(&PolypState::UnderReview).await.unwrap();
+    let indexed = under_review.len() + approved.len() + hardened.len();
+    let stored = store.scan_prefix(b"polyp:").unwrap().len();
+    assert_eq!(indexed, stored, "state index out of sync");
+
+    drop(store);
+    let _ = std::fs::remove_dir_all(&path);
+}
+
+// ===========================================================================
+// Writer child
+// ===========================================================================
+
+/// Commit polyps until killed. Does nothing unless run by `crash_and_recover`.
+#[tokio::test]
+async fn crash_writer() {
+    let path = match std::env::var(DB_ENV) {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+    let durability: Durability =
+        serde_json::from_str(&std::env::var(DURABILITY_ENV).unwrap()).unwrap();
+    let store = RocksStore::open_with_durability(&path, durability).unwrap();
+
+    for epoch in 1..=10_000 {
+        let mut polyp = make_test_polyp(&format!("crash test {}", epoch), PolypState::UnderReview);
+        store.save_polyp(&polyp).await.unwrap();
+
+        // Consensus: approve, then flush before hardening.
+        polyp.state = PolypState::Approved;
+        store.save_polyp(&polyp).await.unwrap();
+        store.flush().unwrap();
+        println!("ACK approved {} {}", epoch, polyp.id);
+
+        // Hardening: checkpoint and hardened state, then flush.
+        let checkpoint = HardeningCheckpoint {
+            epoch,
+            merkle_root: [epoch as u8; 32],
+            count: 1,
+            beacon: None,
+        };
+        checkpoint.save(&store).unwrap();
+        polyp.state = PolypState::Hardened;
+        store.save_polyp(&polyp).await.unwrap();
+        store.flush().unwrap();
+        println!("ACK hardened {} {}", epoch, polyp.id);
+    }
+}
+
+// ===========================================================================
+// Recovery under each durability mode
+// ===========================================================================
+
+#[tokio::test]
+async fn test_crash_recovery_sync() {
+    crash_and_recover("crash_sync", Durability::Sync).await;
+}
+
+#[tokio::test]
+async fn test_crash_recovery_periodic() {
+    crash_and_recover("crash_periodic", Durability::Periodic { interval_ms: 1000 }).await;
+}
+
+#[tokio::test]
+async fn test_crash_recovery_relaxed() {
+    crash_and_recover("crash_relaxed", Durability::Relaxed).await;
+}
@@ -6,8 +6,8 @@
 // hardening pipeline, and the end-to-end epoch flow.
 //
 // These tests use the public APIs of the underlying library crates directly
-// (chitin-consensus, chitin-store, chitin-reputation, chitin-core) since the
-// daemon is a binary crate with no lib.rs.
+// (chitin-consensus, chitin-store, chitin-reputation, chitin-core); whole
+// nodes are tested in-process in chitin-node.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -92,9 +92,11 @@ fn make_test_polyp(content: &str, state: PolypState) -> Polyp {
                         name: "embed".to_string(),
                         version: "1.0".to_string(),
                         params: serde_json::json!({}),
+                        hash: None,
                     }],
                     duration_ms: 50,
                 },
+                molted_from: vec![],
             },
         },
         proof: ZkProof {
@@ -118,6 +120,7 @@ fn make_test_polyp(content: &str, state: PolypState) -> Polyp {
         created_at: now,
         updated_at: now,
         signature: None,
+        reef_zone: None,
     }
 }
 
@@ -755,6 +758,7 @@ async fn test_end_to_end_epoch() {
             emission_rate: 0,
             weights: HashMap::new(),
             bonds: HashMap::new(),
+            model_versions: vec![],
         };
 
         let mut mm = metagraph_manager.write().await;
@@ -803,6 +807,7 @@ async fn test_metagraph_monotonicity() {
         emission_rate: 0,
         weights: HashMap::new(),
         bonds: HashMap::new(),
+        model_versions: vec![],
     };
 
     mm.update(mg1).expect("First update should succeed");
@@ -818,6 +823,7 @@ async fn test_metagraph_monotonicity() {
         emission_rate: 0,
         weights: HashMap::new(),
         bonds: HashMap::new(),
+        model_versions: vec![],
     };
 
     let result = mm.update(mg_stale);
@@ -833,6 +839,7 @@ async fn test_metagraph_monotonicity() {
         emission_rate: 0,
         weights: HashMap::new(),
         bonds: HashMap::new(),
+        model_versions: vec![],
     };
 
     mm.update(mg2).expect("Forward epoch update should succeed");
@@ -1,9 +1,32 @@
 // crates/chitin-drift/src/alignment.rs
 //
 // Cross-model vector space alignment (linear projection) for the Chitin Protocol.
+//
+// A `ModelAlignment` maps vectors of one embedding model into another's space
+// so queries can be answered across a migration before every Polyp has been
+// re-embedded. It is learned in closed form from paired embeddings of the
+// same texts (the canary corpus, or hardened Polyps re-embedded under the new
+// model): orthogonal Procrustes when both spaces have the same dimensions,
+// which preserves distances, or ridge regression for any pair of spaces.
+// Alignments are persisted in RocksDB with their fit quality under
+// `alignment:{from_model}:{to_model}`.
 
+use chitin_core::ChitinError;
+use chitin_store::RocksStore;
 use serde::{Deserialize, Serialize};
 
+/// Key prefix for persisted alignments: `alignment:{from_model}:{to_model}`.
+const KEY_PREFIX: &str = "alignment:";
+
+/// Iteration limit for the Procrustes polar decomposition.
+const POLAR_MAX_ITERATIONS: usize = 100;
+
+/// Relative change at which the polar iteration has converged.
+const POLAR_TOLERANCE: f64 = 1e-12;
+
+/// Pivots smaller than this make a matrix singular.
+const SINGULAR_EPSILON: f64 = 1e-12;
+
 /// A linear projection matrix for aligning two vector spaces.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlignmentMatrix {
@@ -91,6 +114,383 @@ pub fn compute_alignment(
     }
 }
 
+impl AlignmentMatrix {
+    /// Map a row vector: `vector * M`.
+    pub fn apply(&self, vector: &[f32]) -> Result<Vec<f32>, ChitinError> {
+        let (d_from, d_to) = (self.from_dim as usize, self.to_dim as usize);
+        if vector.len() != d_from {
+            return Err(ChitinError::InvalidState(format!(
+                "Vector has {} dimensions, alignment expects {}",
+                vector.len(),
+                d_from
+            )));
+        }
+        let mut mapped = vec![0.0f64; d_to];
+        for (k, &x) in vector.iter().enumerate() {
+            let row = &self.matrix[k * d_to..(k + 1) * d_to];
+            for (out, m) in mapped.iter_mut().zip(row) {
+                *out += x as f64 * m;
+            }
+        }
+        Ok(mapped.into_iter().map(|x| x as f32).collect())
+    }
+}
+
+/// How an alignment is fitted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "method")]
+pub enum AlignmentMethod {
+    /// Orthogonal Procrustes: the rotation (or reflection) best mapping one
+    /// space onto the other. Requires equal dimensions and at least as many
+    /// independent samples as dimensions.
+    Procrustes,
+    /// Ridge regression: the least-squares linear map with L2 penalty
+    /// `lambda` on its entries. Works between any dimensions.
+    Ridge {
+        /// Regularization strength; must be positive when there are fewer
+        /// samples than source dimensions.
+        lambda: f64,
+    },
+}
+
+/// How well an alignment fits its training pairs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentQuality {
+    /// Paired samples the alignment was learned from.
+    pub samples: usize,
+    /// Mean squared error per dimension between mapped and target vectors.
+    pub mse: f64,
+    /// Mean cosine similarity between mapped and target vectors.
+    pub mean_cosine: f64,
+    /// Fraction of samples whose mapped vector is nearest its own target
+    /// among all targets.
+    pub retrieval_accuracy: f64,
+}
+
+/// A learned alignment between two embedding models.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelAlignment {
+    /// The model vectors are mapped from.
+    pub from_model: String,
+    /// The model vectors are mapped into.
+    pub to_model: String,
+    /// How the alignment was fitted.
+    pub method: AlignmentMethod,
+    /// The projection.
+    pub matrix: AlignmentMatrix,
+    /// Fit quality on the training pairs.
+    pub quality: AlignmentQuality,
+}
+
+impl ModelAlignment {
+    /// Learn an alignment from `from_vectors[i]` to `to_vectors[i]`, the same
+    /// texts embedded under `from_model` and `to_model`.
+    pub fn learn(
+        from_model: &str,
+        to_model: &str,
+        from_vectors: &[Vec<f32>],
+        to_vectors: &[Vec<f32>],
+        method: AlignmentMethod,
+    ) -> Result<Self, ChitinError> {
+        let (x, d_from) = to_matrix(from_vectors, "source")?;
+        let (y, d_to) = to_matrix(to_vectors, "target")?;
+        let n = from_vectors.len();
+        if to_vectors.len() != n {
+            return Err(ChitinError::InvalidState(format!(
+                "Alignment needs paired samples, got {} source and {} target vectors",
+                n,
+                to_vectors.len()
+            )));
+        }
+
+        let matrix = match method {
+            AlignmentMethod::Procrustes => {
+                if d_from != d_to {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Procrustes alignment needs equal dimensions, got {} and {}",
+                        d_from, d_to
+                    )));
+                }
+                let cross = matmul(&transpose(&x, n, d_from), &y, d_from, n, d_to);
+                polar_factor(&cross, d_from)?
+            }
+            AlignmentMethod::Ridge { lambda } => ridge(&x, &y, n, d_from, d_to, lambda)?,
+        };
+        let matrix = AlignmentMatrix {
+            from_dim: d_from as u32,
+            to_dim: d_to as u32,
+            matrix,
+        };
+        let quality = evaluate(&matrix, from_vectors, to_vectors)?;
+
+        Ok(Self {
+            from_model: from_model.to_string(),
+            to_model: to_model.to_string(),
+            method,
+            matrix,
+            quality,
+        })
+    }
+
+    /// Confidence in results found through this alignment, in [0.0, 1.0]:
+    /// mean cosine of the fit times its retrieval accuracy.
+    pub fn confidence(&self) -> f64 {
+        self.quality.mean_cosine.clamp(0.0, 1.0) * self.quality.retrieval_accuracy
+    }
+
+    /// Map a query vector from `from_model`'s space into `to_model`'s,
+    /// L2-normalized like the target model's own embeddings.
+    pub fn map_query(&self, vector: &[f32]) -> Result<Vec<f32>, ChitinError> {
+        let mut mapped = self.matrix.apply(vector)?;
+        let norm = mapped.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for x in mapped.iter_mut() {
+                *x /= norm;
+            }
+        }
+        Ok(mapped)
+    }
+
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Load the alignment from `from_model` to `to_model`, if one is stored.
+    pub fn load(
+        store: &RocksStore,
+        from_model: &str,
+        to_model: &str,
+    ) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(key(from_model, to_model).as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this alignment, replacing any previous one for the same pair.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        let key = key(&self.from_model, &self.to_model);
+        store.put_bytes(key.as_bytes(), &serde_json::to_vec(self)?)
+    }
+}
+
+fn key(from_model: &str, to_model: &str) -> String {
+    format!("{}{}:{}", KEY_PREFIX, from_model, to_model)
+}
+
+/// Flatten equally sized vectors into a row-major matrix.
+fn to_matrix(vectors: &[Vec<f32>], side: &str) -> Result<(Vec<f64>, usize), ChitinError> {
+    let dim = match vectors.first() {
+        Some(first) if !first.is_empty() => first.len(),
+        _ => {
+            return Err(ChitinError::InvalidState(format!(
+                "Alignment needs non-empty {} vectors",
+                side
+            )))
+        }
+    };
+    if vectors.iter().any(|v| v.len() != dim) {
+        return Err(ChitinError::InvalidState(format!(
+            "All {} vectors must have {} dimensions",
+            side, dim
+        )));
+    }
+    Ok((vectors.iter().flatten().map(|&x| x as f64).collect(), dim))
+}
+
+/// Ridge regression weights. Solves the `d_from`-sized normal equations, or
+/// the `n`-sized dual system when there are fewer samples than dimensions.
+fn ridge(
+    x: &[f64],
+    y: &[f64],
+    n: usize,
+    d_from: usize,
+    d_to: usize,
+    lambda: f64,
+) -> Result<Vec<f64>, ChitinError> {
+    if lambda < 0.0 || !lambda.is_finite() {
+        return Err(ChitinError::InvalidState(format!(
+            "Ridge lambda must be non-negative, got {}",
+            lambda
+        )));
+    }
+    let xt = transpose(x, n, d_from);
+    if n >= d_from {
+        // M = (X^T X + lambda I)^-1 X^T Y
+        let mut gram = matmul(&xt, x, d_from, n, d_from);
+        add_diagonal(&mut gram, d_from, lambda);
+        let rhs = matmul(&xt, y, d_from, n, d_to);
+        solve(gram, d_from, rhs, d_to)
+    } else {
+        // M = X^T (X X^T + lambda I)^-1 Y
+        let mut kernel = matmul(x, &xt, n, d_from, n);
+        add_diagonal(&mut kernel, n, lambda);
+        let dual = solve(kernel, n, y.to_vec(), d_to)?;
+        Ok(matmul(&xt, &dual, d_from, n, d_to))
+    }
+}
+
+/// Orthogonal polar factor of a square matrix by scaled Newton iteration:
+/// `Q <- (g Q + Q^-T / g) / 2`. For `A = U S V^T` it converges to `U V^T`,
+/// the Procrustes solution.
+fn polar_factor(a: &[f64], d: usize) -> Result<Vec<f64>, ChitinError> {
+    let mut q = a.to_vec();
+    for _ in 0..POLAR_MAX_ITERATIONS {
+        let inverse = invert(&q, d).map_err(|_| {
+            ChitinError::InvalidState(
+                "Procrustes alignment is rank-deficient; use more samples or ridge".to_string(),
+            )
+        })?;
+        let gamma = (frobenius(&inverse) / frobenius(&q)).sqrt();
+        let inverse_t = transpose(&inverse, d, d);
+        let next: Vec<f64> = q
+            .iter()
+            .zip(&inverse_t)
+            .map(|(a, b)| 0.5 * (gamma * a + b / gamma))
+            .collect();
+        let change: f64 = next.iter().zip(&q).map(|(a, b)| (a - b).powi(2)).sum();
+        q = next;
+        if change.sqrt() <= POLAR_TOLERANCE * frobenius(&q) {
+            break;
+        }
+    }
+    Ok(q)
+}
+
+/// Fit quality of `matrix` on the training pairs.
+fn evaluate(
+    matrix: &AlignmentMatrix,
+    from_vectors: &[Vec<f32>],
+    to_vectors: &[Vec<f32>],
+) -> Result<AlignmentQuality, ChitinError> {
+    let mapped = from_vectors
+        .iter()
+        .map(|v| matrix.apply(v))
+        .collect::<Result<Vec<_>, _>>()?;
+    let n = mapped.len();
+    let mut squared_error = 0.0;
+    let mut cosine = 0.0;
+    let mut retrieved = 0;
+    for (i, m) in mapped.iter().enumerate() {
+        squared_error += m
+            .iter()
+            .zip(&to_vectors[i])
+            .map(|(a, b)| (*a as f64 - *b as f64).powi(2))
+            .sum::<f64>();
+        cosine += cosine_similarity(m, &to_vectors[i]);
+        let nearest = (0..n).max_by(|&a, &b| {
+            cosine_similarity(m, &to_vectors[a])
+                .total_cmp(&cosine_similarity(m, &to_vectors[b]))
+                .then(b.cmp(&a))
+        });
+        if nearest == Some(i) {
+            retrieved += 1;
+        }
+    }
+    Ok(AlignmentQuality {
+        samples: n,
+        mse: squared_error / (n * matrix.to_dim as usize) as f64,
+        mean_cosine: cosine / n as f64,
+        retrieval_accuracy: retrieved as f64 / n as f64,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Dense linear algebra (row-major)
+// ---------------------------------------------------------------------------
+
+fn transpose(a: &[f64], rows: usize, cols: usize) -> Vec<f64> {
+    let mut t = vec![0.0; rows * cols];
+    for r in 0..rows {
+        for c in 0..cols {
+            t[c * rows + r] = a[r * cols + c];
+        }
+    }
+    t
+}
+
+/// `(rows x inner) * (inner x cols)`.
+fn matmul(a: &[f64], b: &[f64], rows: usize, inner: usize, cols: usize) -> Vec<f64> {
+    let mut out = vec![0.0; rows * cols];
+    for r in 0..rows {
+        for k in 0..inner {
+            let x = a[r * inner + k];
+            if x == 0.0 {
+                continue;
+            }
+            for c in 0..cols {
+                out[r * cols + c] += x * b[k * cols + c];
+            }
+        }
+    }
+    out
+}
+
+fn add_diagonal(a: &mut [f64], n: usize, value: f64) {
+    for i in 0..n {
+        a[i * n + i] += value;
+    }
+}
+
+fn frobenius(a: &[f64]) -> f64 {
+    a.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+fn invert(a: &[f64], n: usize) -> Result<Vec<f64>, ChitinError> {
+    let mut identity = vec![0.0; n * n];
+    add_diagonal(&mut identity, n, 1.0);
+    solve(a.to_vec(), n, identity, n)
+}
+
+/// Solve `A X = B` for `X` (`A` is `n x n`, `B` is `n x m`) by Gaussian
+/// elimination with partial pivoting.
+fn solve(mut a: Vec<f64>, n: usize, mut b: Vec<f64>, m: usize) -> Result<Vec<f64>, ChitinError> {
+    let scale = a.iter().fold(0.0f64, |acc, x| acc.max(x.abs())).max(1.0);
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))
+            .unwrap_or(col);
+        if a[pivot * n + col].abs() <= SINGULAR_EPSILON * scale {
+            return Err(ChitinError::InvalidState("Singular matrix".to_string()));
+        }
+        if pivot != col {
+            for c in 0..n {
+                a.swap(pivot * n + c, col * n + c);
+            }
+            for c in 0..m {
+                b.swap(pivot * m + c, col * m + c);
+            }
+        }
+        for row in col + 1..n {
+            let factor = a[row * n + col] / a[col * n + col];
+            if factor == 0.0 {
+                continue;
+            }
+            for c in col..n {
+                a[row * n + c] -= factor * a[col * n + c];
+            }
+            for c in 0..m {
+                b[row * m + c] -= factor * b[col * m + c];
+            }
+        }
+    }
+    for col in (0..n).rev() {
+        let diag = a[col * n + col];
+        for c in 0..m {
+            let mut value = b[col * m + c];
+            for k in col + 1..n {
+                value -= a[col * n + k] * b[k * m + c];
+            }
+            b[col * m + c] = value / diag;
+        }
+    }
+    Ok(b)
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    crate::detection::cosine_similarity(a, b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +565,81 @@ mod tests {
             mat.matrix[3]
         );
     }
+
+    /// Deterministic sample vectors.
+    fn samples(count: usize, dim: usize) -> Vec<Vec<f32>> {
+        (0..count)
+            .map(|i| chitin_core::hash_embedding(&format!("canary {}", i), dim))
+            .collect()
+    }
+
+    #[test]
+    fn procrustes_recovers_a_rotation() {
+        let from = samples(24, 8);
+        // Reverse the dimensions and flip the sign of every other one.
+        let rotate = |v: &Vec<f32>| -> Vec<f32> {
+            v.iter()
+                .rev()
+                .enumerate()
+                .map(|(i, x)| if i % 2 == 0 { *x } else { -*x })
+                .collect()
+        };
+        let to: Vec<Vec<f32>> = from.iter().map(rotate).collect();
+        let alignment =
+            ModelAlignment::learn("old", "new", &from, &to, AlignmentMethod::Procrustes).unwrap();
+        assert!(alignment.quality.mse < 1e-10);
+        assert!((alignment.quality.mean_cosine - 1.0).abs() < 1e-6);
+        assert_eq!(alignment.quality.retrieval_accuracy, 1.0);
+        assert!(alignment.confidence() > 0.999);
+
+        let query = chitin_core::hash_embedding("an unseen query", 8);
+        let mapped = alignment.map_query(&query).unwrap();
+        for (a, b) in mapped.iter().zip(rotate(&query)) {
+            assert!((a - b).abs() < 1e-5);
+        }
+        assert!(alignment.map_query(&[1.0, 2.0]).is_err());
+
+        let wide = samples(24, 4);
+        let err = ModelAlignment::learn("old", "new", &from, &wide, AlignmentMethod::Procrustes);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn ridge_maps_between_dimensions() {
+        // The new model keeps a scaled copy of the first four dimensions.
+        let project = |v: &Vec<f32>| -> Vec<f32> { v[..4].iter().map(|x| 2.0 * x).collect() };
+        let method = AlignmentMethod::Ridge { lambda: 1e-6 };
+
+        let from = samples(32, 8);
+        let to: Vec<Vec<f32>> = from.iter().map(project).collect();
+        let alignment = ModelAlignment::learn("old", "new", &from, &to, method).unwrap();
+        assert_eq!((alignment.matrix.from_dim, alignment.matrix.to_dim), (8, 4));
+        assert!(alignment.quality.mse < 1e-8);
+        assert_eq!(alignment.quality.retrieval_accuracy, 1.0);
+
+        // Fewer samples than dimensions uses the dual system.
+        let from = samples(6, 16);
+        let to: Vec<Vec<f32>> = from.iter().map(project).collect();
+        let alignment = ModelAlignment::learn("old", "new", &from, &to, method).unwrap();
+        assert_eq!(alignment.quality.samples, 6);
+        assert!(alignment.quality.mean_cosine > 0.999);
+        assert!(ModelAlignment::learn("old", "new", &from, &to[..5], method).is_err());
+    }
+
+    #[test]
+    fn alignments_persist_with_quality() {
+        let path = std::env::temp_dir().join(format!("chitin_alignment_{}", std::process::id()));
+        let store = RocksStore::open(&path.to_string_lossy()).unwrap();
+
+        let from = samples(12, 4);
+        let alignment =
+            ModelAlignment::learn("bge/v1", "bge/v2", &from, &from, AlignmentMethod::Procrustes)
+                .unwrap();
+        alignment.save(&store).unwrap();
+
+        let loaded = ModelAlignment::load(&store, "bge/v1", "bge/v2").unwrap().unwrap();
+        assert_eq!(loaded.quality, alignment.quality);
+        assert_eq!(loaded.method, AlignmentMethod::Procrustes);
+        assert!(ModelAlignment::load(&store, "bge/v2", "bge/v1").unwrap().is_none());
+    }
 }
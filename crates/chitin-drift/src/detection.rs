@@ -1,10 +1,163 @@
 // crates/chitin-drift/src/detection.rs
 //
 // Semantic drift detection across embedding model versions.
+//
+// Drift is measured on a canary corpus: a fixed set of reference texts
+// embedded under both the old and the new model. Two signals are compared:
+// the cosine shift of each canary's own vector (1.0 - cosine similarity;
+// maximal if the dimensions differ), and neighborhood overlap, the fraction
+// of each canary's k nearest canaries that stay the same across models. Shift
+// catches vectors that are no longer comparable with stored ones; overlap
+// catches changes to the geometry of the space itself. A `DriftReport`
+// recommends re-embedding when either crosses its threshold.
 
-use chitin_core::ChitinError;
+use chitin_core::{ChitinError, EmbeddingModelId, HASH_EMBEDDING_MODEL};
+use chitin_store::EmbeddingCache;
 use serde::{Deserialize, Serialize};
 
+/// Dimensions of the hash embeddings used by `detect_drift`.
+const HASH_DIMENSIONS: usize = 64;
+
+/// Default cosine shift above which drift is significant.
+pub const DEFAULT_DRIFT_THRESHOLD: f64 = 0.01;
+
+/// Built-in canary texts, spread across domains so that neighborhoods are
+/// meaningful for general-purpose models.
+pub const DEFAULT_CANARIES: &[&str] = &[
+    "the quick brown fox jumps over the lazy dog",
+    "hello world",
+    "rust programming language ownership and borrowing",
+    "a memory-safe systems language without garbage collection",
+    "photosynthesis converts sunlight into chemical energy",
+    "plants use chlorophyll to absorb light",
+    "the mitochondria is the powerhouse of the cell",
+    "interest rates rise to curb inflation",
+    "central banks set monetary policy",
+    "the stock market fell sharply on recession fears",
+    "a patient presents with fever and a persistent cough",
+    "antibiotics treat bacterial infections",
+    "the treaty ended the war in 1648",
+    "ancient rome was governed by a senate",
+    "water boils at one hundred degrees celsius at sea level",
+    "the speed of light in a vacuum is constant",
+];
+
+/// An embedding model that drift can be measured for.
+pub trait EmbeddingModel {
+    /// Identifier of the model version.
+    fn model_id(&self) -> &str;
+
+    /// Embed a single text.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError>;
+}
+
+/// Deterministic hash embeddings salted with a model ID, standing in for a
+/// real model where none is loaded.
+#[derive(Debug, Clone)]
+pub struct HashEmbeddingModel {
+    /// Model ID, used as the hash salt.
+    pub model_id: String,
+    /// Output dimensionality.
+    pub dimensions: usize,
+}
+
+impl HashEmbeddingModel {
+    /// Create a hash model for `model_id`.
+    pub fn new(model_id: &str, dimensions: usize) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            dimensions,
+        }
+    }
+}
+
+impl EmbeddingModel for HashEmbeddingModel {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError> {
+        let input = format!("{}:{}", self.model_id, text);
+        Ok(chitin_core::hash_embedding(&input, self.dimensions))
+    }
+}
+
+/// The embedding model a node runs for a model ID.
+///
+/// `HASH_EMBEDDING_MODEL` is the plain `hash_embedding` used by
+/// `polyp/submit`; any other model is stood in for by a `HashEmbeddingModel`.
+#[derive(Debug, Clone)]
+pub struct LocalEmbeddingModel {
+    model_id: String,
+    dimensions: usize,
+    salted: Option<HashEmbeddingModel>,
+}
+
+impl LocalEmbeddingModel {
+    /// The model for `model_id`, producing `dimensions`-wide vectors.
+    pub fn new(model_id: &str, dimensions: usize) -> Self {
+        let salted = if model_id == HASH_EMBEDDING_MODEL {
+            None
+        } else {
+            Some(HashEmbeddingModel::new(model_id, dimensions))
+        };
+        Self {
+            model_id: model_id.to_string(),
+            dimensions,
+            salted,
+        }
+    }
+}
+
+impl EmbeddingModel for LocalEmbeddingModel {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError> {
+        match &self.salted {
+            Some(model) => model.embed(text),
+            None => Ok(chitin_core::hash_embedding(text, self.dimensions)),
+        }
+    }
+}
+
+/// An embedding model whose vectors are read through an `EmbeddingCache`,
+/// so texts already embedded under its model ID (by ingestion, queries, or
+/// an earlier molt) are not embedded again.
+pub struct CachedEmbeddingModel<'a, M> {
+    inner: M,
+    model: EmbeddingModelId,
+    cache: &'a EmbeddingCache,
+}
+
+impl<'a, M: EmbeddingModel> CachedEmbeddingModel<'a, M> {
+    /// Cache `inner`'s `dimensions`-wide vectors in `cache`.
+    pub fn new(inner: M, dimensions: usize, cache: &'a EmbeddingCache) -> Self {
+        let model = EmbeddingModelId::from_key(inner.model_id(), dimensions as u32);
+        Self {
+            inner,
+            model,
+            cache,
+        }
+    }
+}
+
+impl<M: EmbeddingModel> EmbeddingModel for CachedEmbeddingModel<'_, M> {
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError> {
+        if let Some(values) = self.cache.get(&self.model, text)? {
+            return Ok(values);
+        }
+        let values = self.inner.embed(text)?;
+        self.cache.put(&self.model, text, &values)?;
+        Ok(values)
+    }
+}
+
 /// Metrics quantifying semantic drift between two embedding model versions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DriftMetrics {
@@ -14,6 +167,52 @@ pub struct DriftMetrics {
     pub max_cosine_shift: f64,
     /// Number of Polyps whose cosine shift exceeds the drift threshold.
     pub affected_polyps: usize,
+    /// Mean fraction of each canary's nearest neighbors preserved across
+    /// models (1.0 = identical neighborhoods).
+    #[serde(default = "full_overlap")]
+    pub mean_neighborhood_overlap: f64,
+    /// Lowest neighborhood overlap of any canary.
+    #[serde(default = "full_overlap")]
+    pub min_neighborhood_overlap: f64,
+}
+
+fn full_overlap() -> f64 {
+    1.0
+}
+
+/// Whether stored embeddings should be regenerated under the new model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftRecommendation {
+    /// Drift is within thresholds; existing embeddings remain usable.
+    Keep,
+    /// Drift exceeds a threshold; Polyps should be re-embedded (molted).
+    Reembed,
+}
+
+/// Result of measuring drift on the canary corpus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    /// The model being migrated from.
+    pub old_model: String,
+    /// The model being migrated to.
+    pub new_model: String,
+    /// Number of canary texts measured.
+    pub canaries: usize,
+    /// Neighbors compared per canary.
+    pub neighbors: usize,
+    /// Measured drift.
+    pub metrics: DriftMetrics,
+    /// Threshold-based recommendation.
+    pub recommendation: DriftRecommendation,
+    /// Which thresholds were exceeded, if any.
+    pub reasons: Vec<String>,
+}
+
+impl DriftReport {
+    /// True if re-embedding is recommended.
+    pub fn needs_reembedding(&self) -> bool {
+        self.recommendation == DriftRecommendation::Reembed
+    }
 }
 
 /// Detects semantic drift between embedding model versions.
@@ -26,15 +225,16 @@ pub struct DriftDetector {
     pub reference_corpus: Vec<String>,
     /// Threshold above which cosine shift is considered significant drift.
     pub drift_threshold: f64,
+    /// Mean neighborhood overlap below which drift is significant.
+    pub overlap_threshold: f64,
+    /// Nearest neighbors compared per canary.
+    pub neighbors: usize,
 }
 
 impl DriftDetector {
     /// Create a new DriftDetector with default threshold.
     pub fn new() -> Self {
-        Self {
-            reference_corpus: Vec::new(),
-            drift_threshold: 0.01,
-        }
+        Self::with_corpus(Vec::new(), DEFAULT_DRIFT_THRESHOLD)
     }
 
     /// Create a DriftDetector with a specific corpus and threshold.
@@ -42,9 +242,40 @@ impl DriftDetector {
         Self {
             reference_corpus: corpus,
             drift_threshold: threshold,
+            overlap_threshold: 0.8,
+            neighbors: 5,
         }
     }
 
+    /// Create a DriftDetector over the built-in `DEFAULT_CANARIES`.
+    pub fn with_default_canaries(threshold: f64) -> Self {
+        let corpus = DEFAULT_CANARIES.iter().map(|t| t.to_string()).collect();
+        Self::with_corpus(corpus, threshold)
+    }
+
+    /// Set the neighborhood size and overlap threshold.
+    pub fn with_neighborhood(mut self, neighbors: usize, overlap_threshold: f64) -> Self {
+        self.neighbors = neighbors;
+        self.overlap_threshold = overlap_threshold;
+        self
+    }
+
+    /// Add a canary text; returns `false` if it was already present.
+    pub fn add_canary(&mut self, text: &str) -> bool {
+        if self.reference_corpus.iter().any(|t| t == text) {
+            return false;
+        }
+        self.reference_corpus.push(text.to_string());
+        true
+    }
+
+    /// Remove a canary text; returns `false` if it was not present.
+    pub fn remove_canary(&mut self, text: &str) -> bool {
+        let before = self.reference_corpus.len();
+        self.reference_corpus.retain(|t| t != text);
+        self.reference_corpus.len() != before
+    }
+
     /// Detect drift between an old and new embedding model.
     ///
     /// For each reference text, embeds with both the old model (using old_model as salt)
@@ -55,45 +286,89 @@ impl DriftDetector {
         old_model: &str,
         new_model: &str,
     ) -> Result<DriftMetrics, ChitinError> {
-        if self.reference_corpus.is_empty() {
-            return Ok(DriftMetrics {
-                mean_cosine_shift: 0.0,
-                max_cosine_shift: 0.0,
-                affected_polyps: 0,
-            });
-        }
+        Ok(self.report(old_model, new_model)?.metrics)
+    }
 
-        let dimensions = 64; // Use small dims for drift detection
-        let mut shifts = Vec::with_capacity(self.reference_corpus.len());
+    /// Measure drift between two models by ID, using hash embeddings salted
+    /// with each ID in place of the real models.
+    pub fn report(&self, old_model: &str, new_model: &str) -> Result<DriftReport, ChitinError> {
+        let old = HashEmbeddingModel::new(old_model, HASH_DIMENSIONS);
+        let new = HashEmbeddingModel::new(new_model, HASH_DIMENSIONS);
+        self.measure(&old, &new)
+    }
 
-        for text in &self.reference_corpus {
-            // Embed with old model (salt = old_model_id + text)
-            let old_input = format!("{}:{}", old_model, text);
-            let old_vec = chitin_core::hash_embedding(&old_input, dimensions);
+    /// Embed the canary corpus under both models and report drift.
+    ///
+    /// Re-embedding is recommended if the mean cosine shift exceeds
+    /// `drift_threshold` or the mean neighborhood overlap falls below
+    /// `overlap_threshold`.
+    pub fn measure<O, N>(&self, old: &O, new: &N) -> Result<DriftReport, ChitinError>
+    where
+        O: EmbeddingModel + ?Sized,
+        N: EmbeddingModel + ?Sized,
+    {
+        let old_vecs = embed_all(old, &self.reference_corpus)?;
+        let new_vecs = embed_all(new, &self.reference_corpus)?;
+        let neighbors = self.neighbors.min(self.reference_corpus.len().saturating_sub(1));
 
-            // Embed with new model (salt = new_model_id + text)
-            let new_input = format!("{}:{}", new_model, text);
-            let new_vec = chitin_core::hash_embedding(&new_input, dimensions);
+        let shifts: Vec<f64> = old_vecs
+            .iter()
+            .zip(&new_vecs)
+            .map(|(a, b)| cosine_shift(a, b))
+            .collect();
+        let overlaps: Vec<f64> = (0..old_vecs.len())
+            .map(|i| {
+                neighborhood_overlap(
+                    &nearest_neighbors(&old_vecs, i, neighbors),
+                    &nearest_neighbors(&new_vecs, i, neighbors),
+                )
+            })
+            .collect();
 
-            let cosine_sim = cosine_similarity(&old_vec, &new_vec);
-            let shift = 1.0 - cosine_sim;
-            shifts.push(shift);
-        }
+        let metrics = match shifts.len() {
+            0 => DriftMetrics {
+                mean_cosine_shift: 0.0,
+                max_cosine_shift: 0.0,
+                affected_polyps: 0,
+                mean_neighborhood_overlap: 1.0,
+                min_neighborhood_overlap: 1.0,
+            },
+            n => DriftMetrics {
+                mean_cosine_shift: shifts.iter().sum::<f64>() / n as f64,
+                max_cosine_shift: shifts.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                affected_polyps: shifts.iter().filter(|&&s| s > self.drift_threshold).count(),
+                mean_neighborhood_overlap: overlaps.iter().sum::<f64>() / n as f64,
+                min_neighborhood_overlap: overlaps.iter().cloned().fold(1.0, f64::min),
+            },
+        };
 
-        let mean_cosine_shift = shifts.iter().sum::<f64>() / shifts.len() as f64;
-        let max_cosine_shift = shifts
-            .iter()
-            .cloned()
-            .fold(f64::NEG_INFINITY, f64::max);
-        let affected_polyps = shifts
-            .iter()
-            .filter(|&&s| s > self.drift_threshold)
-            .count();
+        let mut reasons = Vec::new();
+        if metrics.mean_cosine_shift > self.drift_threshold {
+            reasons.push(format!(
+                "mean cosine shift {:.4} exceeds {:.4}",
+                metrics.mean_cosine_shift, self.drift_threshold
+            ));
+        }
+        if metrics.mean_neighborhood_overlap < self.overlap_threshold {
+            reasons.push(format!(
+                "mean neighborhood overlap {:.4} below {:.4}",
+                metrics.mean_neighborhood_overlap, self.overlap_threshold
+            ));
+        }
+        let recommendation = if reasons.is_empty() {
+            DriftRecommendation::Keep
+        } else {
+            DriftRecommendation::Reembed
+        };
 
-        Ok(DriftMetrics {
-            mean_cosine_shift,
-            max_cosine_shift,
-            affected_polyps,
+        Ok(DriftReport {
+            old_model: old.model_id().to_string(),
+            new_model: new.model_id().to_string(),
+            canaries: self.reference_corpus.len(),
+            neighbors,
+            metrics,
+            recommendation,
+            reasons,
         })
     }
 }
@@ -104,6 +379,46 @@ impl Default for DriftDetector {
     }
 }
 
+fn embed_all<M: EmbeddingModel + ?Sized>(
+    model: &M,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, ChitinError> {
+    texts.iter().map(|text| model.embed(text)).collect()
+}
+
+/// Indices of the `k` vectors most similar to `vectors[i]`, excluding itself.
+/// Ties go to the lower index.
+pub(crate) fn nearest_neighbors(vectors: &[Vec<f32>], i: usize, k: usize) -> Vec<usize> {
+    let mut scored: Vec<(usize, f64)> = vectors
+        .iter()
+        .enumerate()
+        .filter(|(j, v)| *j != i && v.len() == vectors[i].len())
+        .map(|(j, v)| (j, cosine_similarity(&vectors[i], v)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.into_iter().take(k).map(|(j, _)| j).collect()
+}
+
+/// Fraction of `old` neighbors that are also in `new` (1.0 if `old` is empty).
+pub(crate) fn neighborhood_overlap(old: &[usize], new: &[usize]) -> f64 {
+    if old.is_empty() {
+        return 1.0;
+    }
+    let shared = old.iter().filter(|j| new.contains(j)).count();
+    shared as f64 / old.len() as f64
+}
+
+/// Drift between two embeddings of the same text: 1.0 - cosine similarity.
+///
+/// Vectors of different widths are never comparable and shift by 1.0.
+pub fn cosine_shift(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() == b.len() {
+        1.0 - cosine_similarity(a, b)
+    } else {
+        1.0
+    }
+}
+
 /// Compute cosine similarity between two f32 vectors.
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
     assert_eq!(a.len(), b.len(), "Vectors must have same length");
@@ -126,6 +441,22 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn cached_model_reuses_vectors() {
+        let name = format!("chitin_drift_cache_{}", uuid::Uuid::now_v7());
+        let dir = std::env::temp_dir().join(name);
+        let store = chitin_store::RocksStore::open(&dir.to_string_lossy()).unwrap();
+        let cache = EmbeddingCache::open(std::sync::Arc::new(store), 10).unwrap();
+        let model = CachedEmbeddingModel::new(LocalEmbeddingModel::new("m/v2", 8), 8, &cache);
+
+        let first = model.embed("hello world").unwrap();
+        assert_eq!(model.embed("hello world").unwrap(), first);
+        assert_eq!(first, LocalEmbeddingModel::new("m/v2", 8).embed("hello world").unwrap());
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+        drop(cache);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn same_model_zero_drift() {
         let corpus = vec![
@@ -184,4 +515,78 @@ mod tests {
         let sim = cosine_similarity(&a, &b);
         assert!(sim.abs() < 1e-10);
     }
+
+    /// A model that transforms hash embeddings of the text.
+    struct MappedModel<F: Fn(Vec<f32>) -> Vec<f32>> {
+        id: &'static str,
+        map: F,
+    }
+
+    impl<F: Fn(Vec<f32>) -> Vec<f32>> EmbeddingModel for MappedModel<F> {
+        fn model_id(&self) -> &str {
+            self.id
+        }
+
+        fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError> {
+            Ok((self.map)(chitin_core::hash_embedding(text, 32)))
+        }
+    }
+
+    #[test]
+    fn small_perturbation_keeps_embeddings() {
+        let detector = DriftDetector::with_default_canaries(0.01);
+        let old = MappedModel { id: "v1", map: |v| v };
+        let new = MappedModel {
+            id: "v2",
+            map: |mut v: Vec<f32>| {
+                v[0] += 0.001;
+                v
+            },
+        };
+        let report = detector.measure(&old, &new).unwrap();
+        assert_eq!(report.canaries, DEFAULT_CANARIES.len());
+        assert_eq!(report.neighbors, 5);
+        assert_eq!(report.recommendation, DriftRecommendation::Keep);
+        assert!(report.metrics.mean_neighborhood_overlap > 0.99);
+        assert!(report.reasons.is_empty());
+    }
+
+    #[test]
+    fn rotated_or_resized_spaces_need_reembedding() {
+        let detector = DriftDetector::with_default_canaries(0.01);
+        let old = MappedModel { id: "v1", map: |v| v };
+
+        // Reversing the dimensions preserves geometry but not the vectors.
+        let rotated = MappedModel {
+            id: "v2",
+            map: |mut v: Vec<f32>| {
+                v.reverse();
+                v
+            },
+        };
+        let report = detector.measure(&old, &rotated).unwrap();
+        assert_eq!(report.metrics.min_neighborhood_overlap, 1.0);
+        assert!(report.needs_reembedding());
+        assert_eq!(report.reasons.len(), 1);
+
+        let resized = MappedModel { id: "v3", map: |v: Vec<f32>| v[..16].to_vec() };
+        let report = detector.measure(&old, &resized).unwrap();
+        assert_eq!(report.metrics.max_cosine_shift, 1.0);
+        assert!(report.needs_reembedding());
+
+        // Unrelated models reshuffle neighborhoods as well.
+        let unrelated = HashEmbeddingModel::new("other", 32);
+        let report = detector.measure(&old, &unrelated).unwrap();
+        assert!(report.metrics.mean_neighborhood_overlap < 0.8);
+        assert_eq!(report.reasons.len(), 2);
+    }
+
+    #[test]
+    fn canaries_are_deduplicated() {
+        let mut detector = DriftDetector::new();
+        assert!(detector.add_canary("hello world"));
+        assert!(!detector.add_canary("hello world"));
+        assert!(detector.remove_canary("hello world"));
+        assert!(!detector.remove_canary("hello world"));
+    }
 }
@@ -0,0 +1,275 @@
+// crates/chitin-drift/src/evaluation.rs
+//
+// A/B retrieval evaluation of embedding models.
+//
+// Drift says how much a new model changes the vector space; evaluation says
+// whether retrieval gets better or worse. Both models embed the same corpus
+// and queries, and each query's ranking is scored against its relevant
+// documents with recall@k and mean reciprocal rank (MRR). Relevance comes
+// from a labeled set when one is supplied. Otherwise hardened Polyps serve
+// as pseudo-labels: each sampled Polyp becomes a query whose relevant
+// documents are its nearest hardened neighbors in the stored vector space.
+// Pseudo-labels favour the model the stored vectors came from, so they show
+// how much of the accepted neighborhood structure a candidate preserves.
+
+use chitin_core::polyp::Polyp;
+use chitin_core::ChitinError;
+use serde::{Deserialize, Serialize};
+
+use crate::detection::{cosine_similarity, nearest_neighbors, EmbeddingModel};
+use crate::molting::even_sample;
+
+/// A document in an evaluation corpus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalDocument {
+    /// Identifier that queries refer to.
+    pub id: String,
+    /// Text to embed.
+    pub text: String,
+}
+
+/// A query and the documents relevant to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalQuery {
+    /// Query text to embed.
+    pub text: String,
+    /// IDs of the relevant documents.
+    pub relevant: Vec<String>,
+    /// Document the query was drawn from, excluded from its ranking.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Where an evaluation set's relevance labels came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelSource {
+    /// Supplied relevance judgments.
+    Labeled,
+    /// Nearest neighbors among hardened Polyps.
+    HardenedNeighbors,
+}
+
+/// A corpus and queries with relevance labels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalSet {
+    /// Documents to retrieve from.
+    pub documents: Vec<EvalDocument>,
+    /// Queries to score.
+    pub queries: Vec<EvalQuery>,
+    /// Where the labels came from.
+    #[serde(default = "labeled")]
+    pub labels: LabelSource,
+}
+
+fn labeled() -> LabelSource {
+    LabelSource::Labeled
+}
+
+impl EvalSet {
+    /// Pseudo-label `hardened` Polyps: up to `max_queries` of them, evenly
+    /// spaced, query for their own `neighbors` nearest Polyps by stored
+    /// vector.
+    pub fn from_hardened(hardened: &[Polyp], neighbors: usize, max_queries: usize) -> Self {
+        let documents = hardened
+            .iter()
+            .map(|p| EvalDocument {
+                id: p.id.to_string(),
+                text: p.subject.payload.content.clone(),
+            })
+            .collect();
+        let vectors: Vec<Vec<f32>> = hardened
+            .iter()
+            .map(|p| p.subject.vector.values.clone())
+            .collect();
+        let indices: Vec<usize> = (0..hardened.len()).collect();
+        let queries = even_sample(&indices, max_queries)
+            .into_iter()
+            .map(|i| EvalQuery {
+                text: hardened[i].subject.payload.content.clone(),
+                relevant: nearest_neighbors(&vectors, i, neighbors)
+                    .into_iter()
+                    .map(|j| hardened[j].id.to_string())
+                    .collect(),
+                source: Some(hardened[i].id.to_string()),
+            })
+            .filter(|q| !q.relevant.is_empty())
+            .collect();
+        Self {
+            documents,
+            queries,
+            labels: LabelSource::HardenedNeighbors,
+        }
+    }
+}
+
+/// Retrieval quality of one model on an evaluation set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalMetrics {
+    /// The model evaluated.
+    pub model: String,
+    /// Queries scored.
+    pub queries: usize,
+    /// Cutoff for recall.
+    pub k: usize,
+    /// Mean fraction of each query's relevant documents ranked in the top k.
+    pub recall_at_k: f64,
+    /// Mean reciprocal rank of each query's first relevant document.
+    pub mrr: f64,
+}
+
+/// Retrieval quality of a candidate model against a baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelComparison {
+    /// The model in use.
+    pub baseline: RetrievalMetrics,
+    /// The model being considered.
+    pub candidate: RetrievalMetrics,
+    /// Where the relevance labels came from.
+    pub labels: LabelSource,
+    /// Candidate recall@k minus baseline recall@k.
+    pub recall_delta: f64,
+    /// Candidate MRR minus baseline MRR.
+    pub mrr_delta: f64,
+}
+
+impl ModelComparison {
+    /// True if the candidate retrieves at least as well on both metrics.
+    pub fn candidate_no_worse(&self) -> bool {
+        self.recall_delta >= 0.0 && self.mrr_delta >= 0.0
+    }
+}
+
+/// Score `model`'s retrieval on `set` with recall@`k` and MRR.
+///
+/// Queries without relevant documents are skipped.
+pub fn evaluate<M: EmbeddingModel + ?Sized>(
+    model: &M,
+    set: &EvalSet,
+    k: usize,
+) -> Result<RetrievalMetrics, ChitinError> {
+    let documents = set
+        .documents
+        .iter()
+        .map(|d| Ok((d.id.as_str(), model.embed(&d.text)?)))
+        .collect::<Result<Vec<_>, ChitinError>>()?;
+
+    let (mut scored, mut recall, mut mrr) = (0usize, 0.0, 0.0);
+    for query in set.queries.iter().filter(|q| !q.relevant.is_empty()) {
+        let vector = model.embed(&query.text)?;
+        let mut ranked: Vec<(&str, f64)> = documents
+            .iter()
+            .filter(|(id, v)| query.source.as_deref() != Some(*id) && v.len() == vector.len())
+            .map(|(id, v)| (*id, cosine_similarity(&vector, v)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let is_relevant = |id: &str| query.relevant.iter().any(|r| r == id);
+        let found = ranked.iter().take(k).filter(|(id, _)| is_relevant(id)).count();
+        recall += found as f64 / query.relevant.len() as f64;
+        if let Some(rank) = ranked.iter().position(|(id, _)| is_relevant(id)) {
+            mrr += 1.0 / (rank + 1) as f64;
+        }
+        scored += 1;
+    }
+
+    let mean = |total: f64| if scored == 0 { 0.0 } else { total / scored as f64 };
+    Ok(RetrievalMetrics {
+        model: model.model_id().to_string(),
+        queries: scored,
+        k,
+        recall_at_k: mean(recall),
+        mrr: mean(mrr),
+    })
+}
+
+/// Score `baseline` and `candidate` on the same set and compare them.
+pub fn compare<B, C>(
+    baseline: &B,
+    candidate: &C,
+    set: &EvalSet,
+    k: usize,
+) -> Result<ModelComparison, ChitinError>
+where
+    B: EmbeddingModel + ?Sized,
+    C: EmbeddingModel + ?Sized,
+{
+    let baseline = evaluate(baseline, set, k)?;
+    let candidate = evaluate(candidate, set, k)?;
+    Ok(ModelComparison {
+        recall_delta: candidate.recall_at_k - baseline.recall_at_k,
+        mrr_delta: candidate.mrr - baseline.mrr,
+        labels: set.labels,
+        baseline,
+        candidate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::HashEmbeddingModel;
+
+    fn doc(id: &str, text: &str) -> EvalDocument {
+        EvalDocument {
+            id: id.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    /// Embeds each text as the hash embedding of its first word, so texts
+    /// sharing a first word are identical.
+    struct FirstWord;
+
+    impl EmbeddingModel for FirstWord {
+        fn model_id(&self) -> &str {
+            "first-word"
+        }
+
+        fn embed(&self, text: &str) -> Result<Vec<f32>, ChitinError> {
+            let word = text.split_whitespace().next().unwrap_or("");
+            Ok(chitin_core::hash_embedding(word, 16))
+        }
+    }
+
+    #[test]
+    fn recall_and_mrr_score_rankings() {
+        let set = EvalSet {
+            documents: vec![
+                doc("rust-1", "rust ownership"),
+                doc("rust-2", "rust borrowing"),
+                doc("cell", "mitochondria energy"),
+                doc("bank", "interest rates"),
+            ],
+            queries: vec![
+                EvalQuery {
+                    text: "rust lifetimes".to_string(),
+                    relevant: vec!["rust-1".to_string(), "rust-2".to_string()],
+                    source: None,
+                },
+                EvalQuery {
+                    text: "mitochondria".to_string(),
+                    relevant: vec!["cell".to_string()],
+                    source: None,
+                },
+                EvalQuery {
+                    text: "unlabeled".to_string(),
+                    relevant: vec![],
+                    source: None,
+                },
+            ],
+            labels: LabelSource::Labeled,
+        };
+
+        let metrics = evaluate(&FirstWord, &set, 2).unwrap();
+        assert_eq!(metrics.queries, 2);
+        assert_eq!(metrics.recall_at_k, 1.0);
+        assert_eq!(metrics.mrr, 1.0);
+
+        let unrelated = HashEmbeddingModel::new("other", 16);
+        let comparison = compare(&FirstWord, &unrelated, &set, 2).unwrap();
+        assert!(comparison.recall_delta < 0.0);
+        assert!(!comparison.candidate_no_worse());
+        assert_eq!(comparison.labels, LabelSource::Labeled);
+    }
+}
@@ -6,9 +6,12 @@
 // This crate handles the lifecycle of embedding models: detecting when
 // a new model causes semantic drift, orchestrating the "molting" process
 // to re-embed Polyps, computing alignment matrices between model spaces,
-// and managing model version registries.
+// managing model version registries, monitoring drift on live Polyps, and
+// comparing retrieval quality across models.
 
 pub mod detection;
 pub mod molting;
 pub mod alignment;
 pub mod versioning;
+pub mod monitor;
+pub mod evaluation;
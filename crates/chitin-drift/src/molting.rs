@@ -1,11 +1,73 @@
 // crates/chitin-drift/src/molting.rs
 //
 // Molting orchestration: re-embed + re-prove for the Chitin Protocol.
+//
+// Molting a Polyp creates a successor with the same payload and provenance,
+// embedded under the new model, and moves the predecessor to
+// `Molted { successor_id }`. Under the default `SuccessorPolicy::Inherit`,
+// a successor takes over its predecessor's consensus metadata: approved and
+// hardened knowledge stays approved and is only re-hardened, not re-validated.
+// Under `Revalidate`, every successor goes back through consensus. Each molt
+// is recorded under `molt:{predecessor_id}`, with a reverse index under
+// `molt_successor:{successor_id}`. Successors also carry their ancestry in
+// `Provenance::molted_from`, so lineage survives on nodes that never held a
+// predecessor. Predecessors keep their hardening lineage, and their CIDs keep
+// resolving to the archived hardened version.
+//
+// Before a network-wide molt is approved, a dry run re-embeds a sample of
+// the candidates without storing anything. It times the new model to
+// extrapolate duration and cost, and compares each sample's nearest
+// neighbors within the sample before and after to estimate the change in
+// retrieval quality. Reports are kept under `molt_dry_run:{from}:{to}`.
+//
+// Operators steer the daemon's per-epoch molting through `MoltControl`,
+// kept under `molt_control`: they can pause and resume it, change the batch
+// size, and start migrations the model registry has not scheduled.
 
-use chitin_core::ChitinError;
+use chitin_core::consensus::ConsensusMetadata;
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::provenance::MoltAncestor;
+use chitin_core::traits::PolypStore;
+use chitin_core::{ChitinError, EmbeddingModelId, VectorEmbedding};
+use chitin_store::RocksStore;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::detection::{
+    cosine_shift, cosine_similarity, nearest_neighbors, neighborhood_overlap, DriftDetector,
+    EmbeddingModel,
+};
+use crate::versioning::MoltTask;
+
+/// Key prefix for molt records: `molt:{predecessor_id}`.
+const MOLT_PREFIX: &str = "molt:";
+
+/// Key prefix for the successor index: `molt_successor:{successor_id}`.
+const SUCCESSOR_PREFIX: &str = "molt_successor:";
 
-use crate::detection::DriftDetector;
+/// Key prefix for dry-run reports: `molt_dry_run:{from_model}:{to_model}`.
+const DRY_RUN_PREFIX: &str = "molt_dry_run:";
+
+/// Key of the operator's molt controls.
+const CONTROL_KEY: &str = "molt_control";
+
+/// Polyps molted per migration per epoch, unless the operator sets another
+/// batch size.
+pub const DEFAULT_MOLT_BATCH_SIZE: usize = 64;
+
+/// Every lifecycle state, for scanning the whole store.
+const ALL_STATES: [PolypState; 7] = [
+    PolypState::Draft,
+    PolypState::Soft,
+    PolypState::UnderReview,
+    PolypState::Approved,
+    PolypState::Hardened,
+    PolypState::Rejected,
+    PolypState::Molted {
+        successor_id: Uuid::nil(),
+    },
+];
 
 /// Status of a molting operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +137,588 @@ impl MoltingOrchestrator {
     }
 }
 
+/// How consensus treats the successor of a molted Polyp.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuccessorPolicy {
+    /// Successors inherit the predecessor's consensus metadata; approved and
+    /// hardened predecessors yield approved successors awaiting hardening.
+    #[default]
+    Inherit,
+    /// Successors are re-validated from scratch.
+    Revalidate,
+}
+
+/// A completed molt of one Polyp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoltRecord {
+    /// The Polyp that was molted.
+    pub predecessor_id: Uuid,
+    /// Its re-embedded successor.
+    pub successor_id: Uuid,
+    /// Model the predecessor was embedded under.
+    pub from_model: String,
+    /// Model the successor is embedded under.
+    pub to_model: String,
+    /// Reef Zone of both Polyps.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+    /// Epoch in which the molt happened.
+    pub epoch: u64,
+    /// Whether the successor inherited consensus metadata.
+    pub inherited: bool,
+}
+
+/// Molt completion within one Reef Zone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneMoltProgress {
+    /// The zone (`None` for Polyps without one).
+    pub reef_zone: Option<String>,
+    /// Polyps already molted.
+    pub molted: u64,
+    /// Polyps still embedded under the old model.
+    pub remaining: u64,
+    /// Percentage molted (0.0 to 100.0).
+    pub percent_complete: f64,
+}
+
+/// Molt completion of one migration, per zone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoltProgress {
+    /// The migration.
+    pub task: MoltTask,
+    /// Per-zone completion, ordered by zone.
+    pub zones: Vec<ZoneMoltProgress>,
+    /// Polyps molted across all zones.
+    pub molted: u64,
+    /// Polyps remaining across all zones.
+    pub remaining: u64,
+    /// Percentage molted across all zones.
+    pub percent_complete: f64,
+}
+
+impl MoltingOrchestrator {
+    /// Molt up to `limit` Polyps still embedded under `task.from_model`,
+    /// re-embedding their content with `model`.
+    ///
+    /// Returns the molts performed, oldest Polyps first.
+    pub async fn molt_batch<M: EmbeddingModel>(
+        &self,
+        store: &RocksStore,
+        task: &MoltTask,
+        model: &M,
+        policy: SuccessorPolicy,
+        epoch: u64,
+        limit: usize,
+    ) -> Result<Vec<MoltRecord>, ChitinError> {
+        let mut candidates: Vec<Polyp> = all_polyps(store)
+            .await?
+            .into_iter()
+            .filter(|p| is_molt_candidate(p, &task.from_model))
+            .collect();
+        candidates.sort_by_key(|p| p.id);
+        candidates.truncate(limit);
+
+        let mut records = Vec::with_capacity(candidates.len());
+        for predecessor in candidates {
+            let values = model.embed(&predecessor.subject.payload.content)?;
+            let embedding = VectorEmbedding {
+                model_id: EmbeddingModelId::from_key(&task.to_model, values.len() as u32),
+                values,
+                quantization: predecessor.subject.vector.quantization.clone(),
+                normalization: predecessor.subject.vector.normalization.clone(),
+            };
+            let (successor, record) = molt_polyp(&predecessor, embedding, policy, epoch);
+            store_molt(store, &predecessor, &successor, &record).await?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dry run
+// ---------------------------------------------------------------------------
+
+/// Settings for a molt dry run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DryRunConfig {
+    /// Most candidates re-embedded.
+    pub sample_size: usize,
+    /// Nearest neighbors compared per sample.
+    pub neighbors: usize,
+    /// Cost of re-embedding one Polyp, in whatever unit governance prices
+    /// compute in.
+    pub cost_per_embedding: f64,
+    /// Mean neighbor overlap below which the molt is not recommended.
+    pub min_retrieval_overlap: f64,
+}
+
+impl Default for DryRunConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: 64,
+            neighbors: 5,
+            cost_per_embedding: 0.0,
+            min_retrieval_overlap: 0.8,
+        }
+    }
+}
+
+/// Estimated cost and quality impact of molting from one model to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoltDryRun {
+    /// The model being migrated from.
+    pub from_model: String,
+    /// The model being migrated to.
+    pub to_model: String,
+    /// When the dry run was performed.
+    pub created_at: DateTime<Utc>,
+    /// Polyps that a molt would re-embed.
+    pub candidates: u64,
+    /// Candidates re-embedded in the dry run.
+    pub sampled: u64,
+    /// Mean re-embedding latency per Polyp, in milliseconds.
+    pub mean_embed_ms: f64,
+    /// Slowest re-embedding of any sample, in milliseconds.
+    pub max_embed_ms: f64,
+    /// Estimated time to re-embed every candidate on one node, in seconds.
+    pub estimated_duration_secs: f64,
+    /// Estimated cost of re-embedding every candidate.
+    pub estimated_cost: f64,
+    /// Mean cosine shift between stored and re-embedded vectors.
+    pub mean_cosine_shift: f64,
+    /// Mean fraction of each sample's nearest neighbors (within the sample)
+    /// preserved by the new model (1.0 = identical retrieval).
+    pub retrieval_overlap: f64,
+    /// Change in mean similarity between each sample and its nearest
+    /// neighbors; negative if neighborhoods become less cohesive.
+    pub retrieval_quality_delta: f64,
+    /// Why the molt is not recommended, if it is not.
+    pub reasons: Vec<String>,
+}
+
+impl MoltDryRun {
+    /// True if no concerns were found.
+    pub fn acceptable(&self) -> bool {
+        self.reasons.is_empty()
+    }
+
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Load the latest dry run from `from_model` to `to_model`, if any.
+    pub fn load(
+        store: &RocksStore,
+        from_model: &str,
+        to_model: &str,
+    ) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(dry_run_key(from_model, to_model).as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this report, replacing any earlier dry run of the same molt.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        let key = dry_run_key(&self.from_model, &self.to_model);
+        store.put_bytes(key.as_bytes(), &serde_json::to_vec(self)?)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Operator control
+// ---------------------------------------------------------------------------
+
+/// Operator controls over the daemon's per-epoch molting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MoltControl {
+    /// While paused, no migration molts any Polyps.
+    #[serde(default)]
+    pub paused: bool,
+    /// Polyps molted per migration per epoch (default: 64).
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    /// Migrations started by the operator rather than scheduled by the
+    /// model registry.
+    #[serde(default)]
+    pub started: Vec<MoltTask>,
+}
+
+impl MoltControl {
+    /// Load the controls; a store without any is unpaused with no
+    /// operator migrations.
+    pub fn load(store: &RocksStore) -> Result<Self, ChitinError> {
+        match store.get_bytes(CONTROL_KEY.as_bytes())? {
+            Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Persist the controls.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(CONTROL_KEY.as_bytes(), &serde_json::to_vec(self)?)
+    }
+
+    /// Polyps to molt per migration per epoch.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(DEFAULT_MOLT_BATCH_SIZE)
+    }
+
+    /// Start `task`, replacing any operator migration away from the same
+    /// model.
+    pub fn start(&mut self, task: MoltTask) {
+        self.started.retain(|t| t.from_model != task.from_model);
+        self.started.push(task);
+    }
+
+    /// The `scheduled` migrations followed by the operator's. A schedule
+    /// wins over an operator migration away from the same model.
+    pub fn tasks(&self, scheduled: Vec<MoltTask>) -> Vec<MoltTask> {
+        let mut tasks = scheduled;
+        for task in &self.started {
+            if tasks.iter().all(|t| t.from_model != task.from_model) {
+                tasks.push(task.clone());
+            }
+        }
+        tasks
+    }
+}
+
+impl MoltingOrchestrator {
+    /// Estimate a molt of every Polyp embedded under `from_model` to
+    /// `model`, re-embedding an evenly spaced sample of the candidates.
+    ///
+    /// Nothing is written to the store.
+    pub async fn dry_run<M: EmbeddingModel + ?Sized>(
+        &self,
+        store: &RocksStore,
+        from_model: &str,
+        model: &M,
+        config: &DryRunConfig,
+    ) -> Result<MoltDryRun, ChitinError> {
+        let mut candidates: Vec<Polyp> = all_polyps(store)
+            .await?
+            .into_iter()
+            .filter(|p| is_molt_candidate(p, from_model))
+            .collect();
+        candidates.sort_by_key(|p| p.id);
+        let sample = even_sample(&candidates, config.sample_size);
+
+        let mut old_vecs = Vec::with_capacity(sample.len());
+        let mut new_vecs = Vec::with_capacity(sample.len());
+        let mut latencies = Vec::with_capacity(sample.len());
+        for polyp in &sample {
+            let started = std::time::Instant::now();
+            let values = model.embed(&polyp.subject.payload.content)?;
+            latencies.push(started.elapsed().as_secs_f64() * 1000.0);
+            old_vecs.push(polyp.subject.vector.values.clone());
+            new_vecs.push(values);
+        }
+
+        let neighbors = config.neighbors.min(sample.len().saturating_sub(1));
+        let mut overlaps = Vec::with_capacity(sample.len());
+        let mut cohesion_deltas = Vec::with_capacity(sample.len());
+        for i in 0..sample.len() {
+            let old = nearest_neighbors(&old_vecs, i, neighbors);
+            let new = nearest_neighbors(&new_vecs, i, neighbors);
+            overlaps.push(neighborhood_overlap(&old, &new));
+            cohesion_deltas.push(cohesion(&new_vecs, i, &new) - cohesion(&old_vecs, i, &old));
+        }
+        let shifts: Vec<f64> = old_vecs
+            .iter()
+            .zip(&new_vecs)
+            .map(|(a, b)| cosine_shift(a, b))
+            .collect();
+
+        let mean_embed_ms = mean(&latencies, 0.0);
+        let candidates = candidates.len() as u64;
+        let mut report = MoltDryRun {
+            from_model: from_model.to_string(),
+            to_model: model.model_id().to_string(),
+            created_at: Utc::now(),
+            candidates,
+            sampled: sample.len() as u64,
+            mean_embed_ms,
+            max_embed_ms: latencies.iter().cloned().fold(0.0, f64::max),
+            estimated_duration_secs: mean_embed_ms * candidates as f64 / 1000.0,
+            estimated_cost: config.cost_per_embedding * candidates as f64,
+            mean_cosine_shift: mean(&shifts, 0.0),
+            retrieval_overlap: mean(&overlaps, 1.0),
+            retrieval_quality_delta: mean(&cohesion_deltas, 0.0),
+            reasons: Vec::new(),
+        };
+        if report.retrieval_overlap < config.min_retrieval_overlap {
+            report.reasons.push(format!(
+                "retrieval overlap {:.4} below {:.4}",
+                report.retrieval_overlap, config.min_retrieval_overlap
+            ));
+        }
+        if report.retrieval_quality_delta < 0.0 {
+            report.reasons.push(format!(
+                "neighbor similarity drops by {:.4}",
+                -report.retrieval_quality_delta
+            ));
+        }
+        Ok(report)
+    }
+}
+
+/// Build the successor of `predecessor` under `embedding`, and its record.
+///
+/// Rejected Polyps are never molted; callers should skip them.
+pub fn molt_polyp(
+    predecessor: &Polyp,
+    embedding: VectorEmbedding,
+    policy: SuccessorPolicy,
+    epoch: u64,
+) -> (Polyp, MoltRecord) {
+    let (state, consensus) = successor_state(predecessor, policy);
+    let now = Utc::now();
+    let record = MoltRecord {
+        predecessor_id: predecessor.id,
+        successor_id: Uuid::now_v7(),
+        from_model: predecessor.subject.vector.model_id.key(),
+        to_model: embedding.model_id.key(),
+        reef_zone: predecessor.reef_zone.clone(),
+        epoch,
+        inherited: consensus.is_some(),
+    };
+    let mut successor = predecessor.clone();
+    successor.id = record.successor_id;
+    successor.state = state;
+    successor.subject.vector = embedding;
+    successor.subject.provenance.molted_from.push(MoltAncestor {
+        polyp_id: predecessor.id,
+        model_id: record.from_model.clone(),
+        cid: predecessor.hardening.as_ref().map(|h| h.cid.clone()),
+        epoch,
+    });
+    successor.consensus = consensus;
+    successor.hardening = None;
+    successor.created_at = now;
+    successor.updated_at = now;
+    // The old signature covered the old ID and vector.
+    successor.signature = None;
+    (successor, record)
+}
+
+/// State and consensus metadata a successor starts with under `policy`.
+fn successor_state(
+    predecessor: &Polyp,
+    policy: SuccessorPolicy,
+) -> (PolypState, Option<ConsensusMetadata>) {
+    match (policy, &predecessor.state) {
+        (SuccessorPolicy::Inherit, PolypState::Approved | PolypState::Hardened) => {
+            (PolypState::Approved, predecessor.consensus.clone())
+        }
+        (SuccessorPolicy::Inherit, state @ (PolypState::Draft | PolypState::Soft)) => {
+            (state.clone(), None)
+        }
+        _ => (PolypState::UnderReview, None),
+    }
+}
+
+/// Save `successor`, mark `predecessor` molted, and record the molt.
+pub async fn store_molt(
+    store: &RocksStore,
+    predecessor: &Polyp,
+    successor: &Polyp,
+    record: &MoltRecord,
+) -> Result<(), ChitinError> {
+    store.save_polyp(successor).await?;
+    let mut molted = predecessor.clone();
+    molted.state = PolypState::Molted {
+        successor_id: successor.id,
+    };
+    molted.updated_at = successor.updated_at;
+    store.save_polyp(&molted).await?;
+
+    let value = serde_json::to_vec(record)?;
+    store.put_bytes(molt_key(&record.predecessor_id).as_bytes(), &value)?;
+    store.put_bytes(successor_key(&record.successor_id).as_bytes(), &value)
+}
+
+/// The molt that retired `predecessor_id`, if any.
+pub fn molt_record(
+    store: &RocksStore,
+    predecessor_id: &Uuid,
+) -> Result<Option<MoltRecord>, ChitinError> {
+    load_record(store, &molt_key(predecessor_id))
+}
+
+/// The molt that produced `successor_id`, if it is a successor.
+pub fn successor_record(
+    store: &RocksStore,
+    successor_id: &Uuid,
+) -> Result<Option<MoltRecord>, ChitinError> {
+    load_record(store, &successor_key(successor_id))
+}
+
+/// True if `polyp` is still embedded under `from_model` and can be molted.
+pub fn is_molt_candidate(polyp: &Polyp, from_model: &str) -> bool {
+    polyp.subject.vector.model_id.key() == from_model
+        && !matches!(polyp.state, PolypState::Molted { .. } | PolypState::Rejected)
+}
+
+/// One version of a Polyp in its molt lineage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageEntry {
+    /// The Polyp version.
+    pub polyp_id: Uuid,
+    /// Model it is embedded under.
+    pub model_id: String,
+    /// Its current state, if held locally.
+    pub state: Option<PolypState>,
+    /// IPFS CID of its hardened version, if it was hardened.
+    pub cid: Option<String>,
+    /// Epoch in which it was molted into the next entry, if it was.
+    pub molted_at_epoch: Option<u64>,
+}
+
+/// The full molt chain containing `polyp_id`, oldest version first.
+///
+/// Ancestors come from the earliest held version's `molted_from`
+/// provenance; descendants from molt records. Empty if `polyp_id` is not
+/// held locally.
+pub async fn molt_lineage(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+) -> Result<Vec<LineageEntry>, ChitinError> {
+    let polyp = match store.get_polyp(polyp_id).await? {
+        Some(polyp) => polyp,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut chain = Vec::new();
+    for ancestor in &polyp.subject.provenance.molted_from {
+        let held = store.get_polyp(&ancestor.polyp_id).await?;
+        chain.push(LineageEntry {
+            polyp_id: ancestor.polyp_id,
+            model_id: ancestor.model_id.clone(),
+            state: held.map(|p| p.state),
+            cid: ancestor.cid.clone(),
+            molted_at_epoch: Some(ancestor.epoch),
+        });
+    }
+
+    let mut current = Some(polyp);
+    while let Some(polyp) = current.take() {
+        let record = molt_record(store, &polyp.id)?;
+        chain.push(LineageEntry {
+            polyp_id: polyp.id,
+            model_id: polyp.subject.vector.model_id.key(),
+            state: Some(polyp.state.clone()),
+            cid: polyp.hardening.as_ref().map(|h| h.cid.clone()),
+            molted_at_epoch: record.as_ref().map(|r| r.epoch),
+        });
+        if let Some(record) = record {
+            // Molt records never form cycles, but a corrupt store could.
+            if chain.iter().all(|e| e.polyp_id != record.successor_id) {
+                current = store.get_polyp(&record.successor_id).await?;
+            }
+        }
+    }
+    Ok(chain)
+}
+
+/// Per-zone completion of `task` over the Polyps in `store`.
+pub async fn molt_progress(
+    store: &RocksStore,
+    task: &MoltTask,
+) -> Result<MoltProgress, ChitinError> {
+    let mut zones: std::collections::BTreeMap<Option<String>, (u64, u64)> = Default::default();
+    for polyp in all_polyps(store).await? {
+        if polyp.subject.vector.model_id.key() != task.from_model {
+            continue;
+        }
+        let counts = zones.entry(polyp.reef_zone.clone()).or_default();
+        match polyp.state {
+            PolypState::Molted { .. } => counts.0 += 1,
+            PolypState::Rejected => {}
+            _ => counts.1 += 1,
+        }
+    }
+    let zones: Vec<ZoneMoltProgress> = zones
+        .into_iter()
+        .map(|(reef_zone, (molted, remaining))| ZoneMoltProgress {
+            reef_zone,
+            molted,
+            remaining,
+            percent_complete: percent(molted, remaining),
+        })
+        .collect();
+    let molted = zones.iter().map(|z| z.molted).sum();
+    let remaining = zones.iter().map(|z| z.remaining).sum();
+    Ok(MoltProgress {
+        task: task.clone(),
+        zones,
+        molted,
+        remaining,
+        percent_complete: percent(molted, remaining),
+    })
+}
+
+async fn all_polyps(store: &RocksStore) -> Result<Vec<Polyp>, ChitinError> {
+    let mut polyps = Vec::new();
+    for state in &ALL_STATES {
+        polyps.extend(store.list_polyps_by_state(state).await?);
+    }
+    Ok(polyps)
+}
+
+fn load_record(store: &RocksStore, key: &str) -> Result<Option<MoltRecord>, ChitinError> {
+    match store.get_bytes(key.as_bytes())? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Up to `size` items spread evenly across `items`.
+pub(crate) fn even_sample<T: Clone>(items: &[T], size: usize) -> Vec<T> {
+    if items.len() <= size {
+        return items.to_vec();
+    }
+    (0..size).map(|i| items[i * items.len() / size].clone()).collect()
+}
+
+/// Mean similarity of `vectors[i]` to its `neighbors` (0.0 if none).
+fn cohesion(vectors: &[Vec<f32>], i: usize, neighbors: &[usize]) -> f64 {
+    let similarities: Vec<f64> = neighbors
+        .iter()
+        .map(|&j| cosine_similarity(&vectors[i], &vectors[j]))
+        .collect();
+    mean(&similarities, 0.0)
+}
+
+fn mean(values: &[f64], empty: f64) -> f64 {
+    match values.len() {
+        0 => empty,
+        n => values.iter().sum::<f64>() / n as f64,
+    }
+}
+
+fn percent(done: u64, remaining: u64) -> f64 {
+    match done + remaining {
+        0 => 100.0,
+        total => done as f64 / total as f64 * 100.0,
+    }
+}
+
+fn molt_key(predecessor_id: &Uuid) -> String {
+    format!("{}{}", MOLT_PREFIX, predecessor_id)
+}
+
+fn successor_key(successor_id: &Uuid) -> String {
+    format!("{}{}", SUCCESSOR_PREFIX, successor_id)
+}
+
+fn dry_run_key(from_model: &str, to_model: &str) -> String {
+    format!("{}{}:{}", DRY_RUN_PREFIX, from_model, to_model)
+}
+
 impl Default for MoltingOrchestrator {
     fn default() -> Self {
         Self::new()
@@ -84,7 +728,81 @@ impl Default for MoltingOrchestrator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::detection::DriftDetector;
+    use crate::detection::{DriftDetector, HashEmbeddingModel, LocalEmbeddingModel};
+    use chitin_core::identity::{NodeIdentity, NodeType};
+    use chitin_core::polyp::{Payload, PolypSubject, ProofPublicInputs, ZkProof};
+    use chitin_core::provenance::{ProcessingPipeline, Provenance, SourceAttribution};
+
+    const OLD: &str = "chitin/hash-embedding-v1";
+
+    fn polyp(content: &str, state: PolypState, zone: Option<&str>) -> Polyp {
+        let now = Utc::now();
+        let model_id = EmbeddingModelId::from_key(OLD, 8);
+        Polyp {
+            id: Uuid::now_v7(),
+            state,
+            subject: PolypSubject {
+                payload: Payload {
+                    content: content.to_string(),
+                    content_type: "text/plain".to_string(),
+                    language: None,
+                },
+                vector: VectorEmbedding {
+                    values: chitin_core::hash_embedding(content, 8),
+                    model_id: model_id.clone(),
+                    quantization: "float32".to_string(),
+                    normalization: "l2".to_string(),
+                },
+                provenance: Provenance {
+                    creator: NodeIdentity {
+                        coldkey: [0u8; 32],
+                        hotkey: [0u8; 32],
+                        did: "did:chitin:local".to_string(),
+                        node_type: NodeType::Coral,
+                    },
+                    source: SourceAttribution {
+                        source_cid: None,
+                        source_url: None,
+                        title: None,
+                        license: None,
+                        accessed_at: now,
+                    },
+                    pipeline: ProcessingPipeline {
+                        steps: vec![],
+                        duration_ms: 0,
+                    },
+                    molted_from: vec![],
+                },
+            },
+            proof: ZkProof {
+                proof_type: "placeholder".to_string(),
+                proof_value: "0x00".to_string(),
+                vk_hash: "0x00".to_string(),
+                public_inputs: ProofPublicInputs {
+                    text_hash: [0u8; 32],
+                    vector_hash: [0u8; 32],
+                    model_id,
+                },
+                created_at: now,
+            },
+            consensus: None,
+            hardening: None,
+            created_at: now,
+            updated_at: now,
+            signature: None,
+            reef_zone: zone.map(str::to_string),
+        }
+    }
+
+    fn consensus(epoch: u64) -> ConsensusMetadata {
+        ConsensusMetadata {
+            epoch,
+            final_score: 0.9,
+            validator_scores: vec![],
+            hardened: true,
+            finalized_at: Utc::now(),
+        }
+    }
 
     #[tokio::test]
     async fn molting_same_model_completes() {
@@ -110,6 +828,191 @@ mod tests {
         );
     }
 
+    #[test]
+    fn successors_inherit_consensus_only_under_inherit_policy() {
+        let mut hardened = polyp("hardened knowledge", PolypState::Hardened, None);
+        hardened.consensus = Some(consensus(3));
+        let embedding = VectorEmbedding {
+            values: vec![1.0, 0.0],
+            model_id: EmbeddingModelId::from_key("bge/bge-small-en-v1.5", 2),
+            quantization: "float32".to_string(),
+            normalization: "l2".to_string(),
+        };
+
+        let (successor, record) =
+            molt_polyp(&hardened, embedding.clone(), SuccessorPolicy::Inherit, 7);
+        assert_eq!(successor.state, PolypState::Approved);
+        assert_eq!(successor.consensus.as_ref().unwrap().epoch, 3);
+        assert_eq!(successor.subject.payload.content, "hardened knowledge");
+        assert_eq!(record.to_model, "bge/bge-small-en-v1.5");
+        assert!(record.inherited);
+
+        let (successor, record) =
+            molt_polyp(&hardened, embedding.clone(), SuccessorPolicy::Revalidate, 7);
+        assert_eq!(successor.state, PolypState::UnderReview);
+        assert!(successor.consensus.is_none() && !record.inherited);
+
+        let draft = polyp("draft", PolypState::Draft, None);
+        let (successor, _) = molt_polyp(&draft, embedding, SuccessorPolicy::Inherit, 7);
+        assert_eq!(successor.state, PolypState::Draft);
+    }
+
+    #[tokio::test]
+    async fn molt_batches_report_progress_per_zone() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-molting-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        for (content, zone) in [("a", Some("code")), ("b", Some("code")), ("c", None)] {
+            store.save_polyp(&polyp(content, PolypState::UnderReview, zone)).await.unwrap();
+        }
+        store
+            .save_polyp(&polyp("rejected", PolypState::Rejected, Some("code")))
+            .await
+            .unwrap();
+
+        let task = MoltTask {
+            from_model: OLD.to_string(),
+            from_version: 1,
+            to_model: "bge/bge-small-en-v1.5".to_string(),
+            to_version: 1,
+            deadline_epoch: Some(10),
+        };
+        let model = HashEmbeddingModel::new(&task.to_model, 8);
+        let orch = MoltingOrchestrator::new();
+        let records = orch
+            .molt_batch(&store, &task, &model, SuccessorPolicy::Inherit, 5, 2)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 2);
+
+        let successor = store.get_polyp(&records[0].successor_id).await.unwrap().unwrap();
+        assert_eq!(successor.subject.vector.model_id.key(), task.to_model);
+        let predecessor = store.get_polyp(&records[0].predecessor_id).await.unwrap().unwrap();
+        assert_eq!(
+            predecessor.state,
+            PolypState::Molted { successor_id: successor.id }
+        );
+        assert_eq!(molt_record(&store, &predecessor.id).unwrap().as_ref(), Some(&records[0]));
+        assert_eq!(successor_record(&store, &successor.id).unwrap().as_ref(), Some(&records[0]));
+
+        let progress = molt_progress(&store, &task).await.unwrap();
+        assert_eq!((progress.molted, progress.remaining), (2, 1));
+        assert_eq!(progress.zones.len(), 2);
+
+        orch.molt_batch(&store, &task, &model, SuccessorPolicy::Inherit, 6, 10)
+            .await
+            .unwrap();
+        let progress = molt_progress(&store, &task).await.unwrap();
+        assert_eq!(progress.percent_complete, 100.0);
+        assert!(progress.zones.iter().all(|z| z.percent_complete == 100.0));
+
+        // Molt the successor again; every version resolves to the same chain.
+        let next = MoltTask {
+            from_model: task.to_model.clone(),
+            to_model: "bge/bge-base-en-v1.5".to_string(),
+            ..task.clone()
+        };
+        let model = HashEmbeddingModel::new(&next.to_model, 8);
+        let again = orch
+            .molt_batch(&store, &next, &model, SuccessorPolicy::Inherit, 7, 10)
+            .await
+            .unwrap();
+        let last = again
+            .iter()
+            .find(|r| r.predecessor_id == successor.id)
+            .unwrap()
+            .successor_id;
+        let latest = store.get_polyp(&last).await.unwrap().unwrap();
+        let ancestry = &latest.subject.provenance.molted_from;
+        assert_eq!(ancestry.len(), 2);
+        assert_eq!((ancestry[0].polyp_id, ancestry[0].epoch), (predecessor.id, 5));
+        assert_eq!(ancestry[1].model_id, task.to_model);
+        for id in [predecessor.id, successor.id, last] {
+            let chain = molt_lineage(&store, &id).await.unwrap();
+            let ids: Vec<Uuid> = chain.iter().map(|e| e.polyp_id).collect();
+            assert_eq!(ids, vec![predecessor.id, successor.id, last]);
+            assert_eq!(chain[1].molted_at_epoch, Some(7));
+            assert_eq!(chain[2].molted_at_epoch, None);
+        }
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn dry_runs_estimate_without_molting() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-molting-dry-run-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        for content in ["a", "b", "c", "d", "e", "f"] {
+            store.save_polyp(&polyp(content, PolypState::Hardened, None)).await.unwrap();
+        }
+
+        let config = DryRunConfig {
+            sample_size: 4,
+            neighbors: 2,
+            cost_per_embedding: 0.5,
+            ..DryRunConfig::default()
+        };
+        let orch = MoltingOrchestrator::new();
+        let same = LocalEmbeddingModel::new(OLD, 8);
+        let report = orch.dry_run(&store, OLD, &same, &config).await.unwrap();
+        assert_eq!((report.candidates, report.sampled), (6, 4));
+        assert_eq!(report.estimated_cost, 3.0);
+        assert!(report.mean_cosine_shift.abs() < 1e-6);
+        assert_eq!(report.retrieval_overlap, 1.0);
+        assert!(report.acceptable());
+
+        let other = HashEmbeddingModel::new("bge/bge-small-en-v1.5", 8);
+        let report = orch.dry_run(&store, OLD, &other, &config).await.unwrap();
+        assert!(report.mean_cosine_shift > 0.0);
+        report.save(&store).unwrap();
+        let loaded = MoltDryRun::load(&store, OLD, "bge/bge-small-en-v1.5").unwrap().unwrap();
+        assert_eq!((loaded.candidates, loaded.reasons), (6, report.reasons));
+        // Nothing was molted.
+        let hardened = store.list_polyps_by_state(&PolypState::Hardened).await.unwrap();
+        assert_eq!(hardened.len(), 6);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn molt_control_defers_to_scheduled_migrations_and_persists() {
+        let task = |from: &str, to: &str| MoltTask {
+            from_model: from.to_string(),
+            from_version: 1,
+            to_model: to.to_string(),
+            to_version: 2,
+            deadline_epoch: None,
+        };
+        let path = std::env::temp_dir().join(format!(
+            "chitin-molt-control-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(MoltControl::load(&store).unwrap(), MoltControl::default());
+
+        let mut control = MoltControl::default();
+        control.start(task("a", "b"));
+        control.start(task("c", "d"));
+        control.start(task("a", "e"));
+        assert_eq!(control.started, vec![task("c", "d"), task("a", "e")]);
+        assert_eq!(control.batch_size(), DEFAULT_MOLT_BATCH_SIZE);
+
+        let tasks = control.tasks(vec![task("c", "z")]);
+        assert_eq!(tasks, vec![task("c", "z"), task("a", "e")]);
+
+        control.paused = true;
+        control.batch_size = Some(8);
+        control.save(&store).unwrap();
+        assert_eq!(MoltControl::load(&store).unwrap(), control);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
     #[tokio::test]
     async fn molting_empty_corpus_different_models_completes() {
         // Empty corpus means zero drift, so molting completes
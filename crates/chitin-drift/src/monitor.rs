@@ -0,0 +1,304 @@
+// crates/chitin-drift/src/monitor.rs
+//
+// Incremental drift monitoring over live Polyps.
+//
+// Canary drift (`detection`) compares two model versions on a fixed corpus.
+// The monitor instead watches the Reef itself: on each pass it samples the
+// Polyps hardened since the previous pass, re-embeds their content with the
+// active model, and records the cosine shift against the stored vectors.
+// Shifts are aggregated per Reef Zone per epoch; when an epoch's mean shift
+// first crosses the alarm threshold, the pass reports an alarm. Finished
+// epochs are persisted under `drift_stats:{epoch:020}`.
+
+use chitin_core::polyp::Polyp;
+use chitin_core::ChitinError;
+use chitin_store::RocksStore;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::detection::{cosine_shift, EmbeddingModel};
+
+/// Key prefix for per-epoch statistics: `drift_stats:{epoch:020}`.
+const KEY_PREFIX: &str = "drift_stats:";
+
+/// Settings for the drift monitor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DriftMonitorConfig {
+    /// Seconds between monitoring passes (0 disables the monitor).
+    pub interval_secs: u64,
+    /// Most Polyps re-embedded per pass.
+    pub sample_size: usize,
+    /// Mean cosine shift at which an epoch raises an alarm.
+    pub alarm_threshold: f64,
+}
+
+impl Default for DriftMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            sample_size: 32,
+            alarm_threshold: 0.1,
+        }
+    }
+}
+
+/// Drift observed in one Reef Zone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneDriftStats {
+    /// The zone (`None` for Polyps without one).
+    pub reef_zone: Option<String>,
+    /// Polyps sampled.
+    pub samples: u64,
+    /// Mean cosine shift of the samples.
+    pub mean_cosine_shift: f64,
+    /// Largest cosine shift of any sample.
+    pub max_cosine_shift: f64,
+}
+
+impl ZoneDriftStats {
+    fn record(&mut self, shift: f64) {
+        self.samples += 1;
+        self.mean_cosine_shift += (shift - self.mean_cosine_shift) / self.samples as f64;
+        self.max_cosine_shift = self.max_cosine_shift.max(shift);
+    }
+}
+
+/// Drift observed during one epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochDriftStats {
+    /// The epoch.
+    pub epoch: u64,
+    /// The model samples were re-embedded with.
+    pub model: String,
+    /// All samples, across zones.
+    pub overall: ZoneDriftStats,
+    /// Per-zone statistics, ordered by zone.
+    pub zones: Vec<ZoneDriftStats>,
+    /// Whether the epoch has raised its alarm.
+    pub alarmed: bool,
+}
+
+impl EpochDriftStats {
+    /// Empty statistics for `epoch` under `model`.
+    pub fn new(epoch: u64, model: &str) -> Self {
+        Self {
+            epoch,
+            model: model.to_string(),
+            overall: empty_stats(None),
+            zones: Vec::new(),
+            alarmed: false,
+        }
+    }
+
+    /// Record the cosine shift of one sample from `reef_zone`.
+    pub fn record(&mut self, reef_zone: Option<&str>, shift: f64) {
+        self.overall.record(shift);
+        let zone = reef_zone.map(str::to_string);
+        let at = match self.zones.binary_search_by(|z| z.reef_zone.cmp(&zone)) {
+            Ok(at) => at,
+            Err(at) => {
+                self.zones.insert(at, empty_stats(zone));
+                at
+            }
+        };
+        self.zones[at].record(shift);
+    }
+
+    /// Zones whose mean shift is at or above `threshold`.
+    pub fn zones_above(&self, threshold: f64) -> Vec<Option<String>> {
+        self.zones
+            .iter()
+            .filter(|z| z.mean_cosine_shift >= threshold)
+            .map(|z| z.reef_zone.clone())
+            .collect()
+    }
+
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Load the statistics for `epoch`, if any.
+    pub fn load(store: &RocksStore, epoch: u64) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(key(epoch).as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load the statistics of the latest `limit` epochs, newest first.
+    pub fn load_recent(store: &RocksStore, limit: usize) -> Result<Vec<Self>, ChitinError> {
+        store
+            .scan_prefix(KEY_PREFIX.as_bytes())?
+            .into_iter()
+            .rev()
+            .take(limit)
+            .map(|(_, value)| Ok(serde_json::from_slice(&value)?))
+            .collect()
+    }
+
+    /// Persist these statistics.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(key(self.epoch).as_bytes(), &serde_json::to_vec(self)?)
+    }
+}
+
+/// What one monitoring pass found.
+#[derive(Debug, Clone, Default)]
+pub struct DriftObservation {
+    /// Polyps sampled in this pass.
+    pub sampled: usize,
+    /// The previous epoch's statistics, if this pass started a new epoch.
+    pub finished: Option<EpochDriftStats>,
+    /// The current epoch's statistics, if they crossed the alarm threshold
+    /// in this pass.
+    pub alarm: Option<EpochDriftStats>,
+}
+
+/// Accumulates drift statistics across monitoring passes.
+#[derive(Debug)]
+pub struct DriftMonitor {
+    config: DriftMonitorConfig,
+    current: Option<EpochDriftStats>,
+    /// Latest hardening time already sampled.
+    watermark: Option<DateTime<Utc>>,
+}
+
+impl DriftMonitor {
+    /// Create a monitor with the given settings.
+    pub fn new(config: DriftMonitorConfig) -> Self {
+        Self {
+            config,
+            current: None,
+            watermark: None,
+        }
+    }
+
+    /// The configured settings.
+    pub fn config(&self) -> &DriftMonitorConfig {
+        &self.config
+    }
+
+    /// Statistics accumulated so far in the current epoch.
+    pub fn current(&self) -> Option<&EpochDriftStats> {
+        self.current.as_ref()
+    }
+
+    /// Sample Polyps from `hardened` hardened since the previous pass,
+    /// newest first, re-embed them with `model`, and record their drift
+    /// under `epoch`.
+    pub fn observe<M: EmbeddingModel + ?Sized>(
+        &mut self,
+        epoch: u64,
+        model: &M,
+        hardened: &[Polyp],
+    ) -> Result<DriftObservation, ChitinError> {
+        let mut observation = DriftObservation::default();
+        let starts_epoch = self
+            .current
+            .as_ref()
+            .is_none_or(|c| c.epoch != epoch || c.model != model.model_id());
+        if starts_epoch {
+            observation.finished = self.current.take();
+        }
+        let stats = self
+            .current
+            .get_or_insert_with(|| EpochDriftStats::new(epoch, model.model_id()));
+
+        let mut fresh: Vec<&Polyp> = hardened
+            .iter()
+            .filter(|p| self.watermark.is_none_or(|w| p.updated_at > w))
+            .collect();
+        fresh.sort_by_key(|p| std::cmp::Reverse(p.updated_at));
+        fresh.truncate(self.config.sample_size);
+
+        for polyp in &fresh {
+            let values = model.embed(&polyp.subject.payload.content)?;
+            let shift = cosine_shift(&polyp.subject.vector.values, &values);
+            stats.record(polyp.reef_zone.as_deref(), shift);
+        }
+        if let Some(newest) = fresh.first() {
+            self.watermark = Some(newest.updated_at);
+        }
+        observation.sampled = fresh.len();
+
+        if !stats.alarmed
+            && stats.overall.samples > 0
+            && stats.overall.mean_cosine_shift >= self.config.alarm_threshold
+        {
+            stats.alarmed = true;
+            observation.alarm = Some(stats.clone());
+        }
+        Ok(observation)
+    }
+}
+
+fn empty_stats(reef_zone: Option<String>) -> ZoneDriftStats {
+    ZoneDriftStats {
+        reef_zone,
+        samples: 0,
+        mean_cosine_shift: 0.0,
+        max_cosine_shift: 0.0,
+    }
+}
+
+fn key(epoch: u64) -> String {
+    format!("{}{:020}", KEY_PREFIX, epoch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_aggregate_per_zone_and_persist() {
+        let mut stats = EpochDriftStats::new(4, "m");
+        stats.record(Some("code"), 0.2);
+        stats.record(Some("code"), 0.4);
+        stats.record(None, 0.0);
+        assert_eq!(stats.overall.samples, 3);
+        assert!((stats.overall.mean_cosine_shift - 0.2).abs() < 1e-12);
+        assert_eq!(stats.zones[0].reef_zone, None);
+        assert!((stats.zones[1].mean_cosine_shift - 0.3).abs() < 1e-12);
+        assert_eq!(stats.zones[1].max_cosine_shift, 0.4);
+        assert_eq!(stats.zones_above(0.25), vec![Some("code".to_string())]);
+
+        let path = std::env::temp_dir().join(format!(
+            "chitin-drift-monitor-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        stats.save(&store).unwrap();
+        EpochDriftStats::new(5, "m").save(&store).unwrap();
+        assert_eq!(EpochDriftStats::load(&store, 4).unwrap(), Some(stats));
+        let recent = EpochDriftStats::load_recent(&store, 1).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].epoch, 5);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn alarm_fires_once_per_epoch() {
+        let mut monitor = DriftMonitor::new(DriftMonitorConfig {
+            alarm_threshold: 0.5,
+            ..DriftMonitorConfig::default()
+        });
+        // Empty passes never alarm.
+        let model = crate::detection::HashEmbeddingModel::new("m", 8);
+        let observation = monitor.observe(1, &model, &[]).unwrap();
+        assert_eq!(observation.sampled, 0);
+        assert!(observation.alarm.is_none());
+
+        monitor.current.as_mut().unwrap().record(None, 0.9);
+        let observation = monitor.observe(1, &model, &[]).unwrap();
+        assert!(observation.alarm.is_some());
+        assert!(monitor.observe(1, &model, &[]).unwrap().alarm.is_none());
+
+        let observation = monitor.observe(2, &model, &[]).unwrap();
+        let finished = observation.finished.unwrap();
+        assert_eq!(finished.epoch, 1);
+        assert!(finished.alarmed);
+        assert_eq!(monitor.current().unwrap().overall.samples, 0);
+    }
+}
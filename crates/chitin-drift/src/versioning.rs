@@ -4,24 +4,41 @@
 //
 // Tracks which embedding models are active, their version history,
 // and activation epochs. Each model version defines a vector namespace.
+//
+// Versions are kept ordered by activation epoch. A version may be deprecated
+// from some epoch, opening a window in which its Polyps are molted to the
+// next active version, and retired at its molt deadline, after which new
+// submissions under it are rejected. The registry is persisted under
+// `model_versions` and shared with peers through the metagraph.
 
+use chitin_core::ChitinError;
+use chitin_store::RocksStore;
 use serde::{Deserialize, Serialize};
 
-/// A specific version of an embedding model.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModelVersion {
-    /// The model identifier (e.g., "bge/bge-small-en-v1.5").
-    pub model_id: String,
-    /// Sequential version number for this model.
-    pub version: u32,
-    /// The epoch at which this model version was activated on the network.
-    pub activated_at_epoch: u64,
+pub use chitin_core::embedding::{ModelStatus, ModelVersion};
+
+/// Key under which the registry is persisted.
+const KEY: &str = "model_versions";
+
+/// A scheduled migration of Polyps from a deprecated model version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoltTask {
+    /// The deprecated model being molted away from.
+    pub from_model: String,
+    /// Its version.
+    pub from_version: u32,
+    /// The active model Polyps are re-embedded under.
+    pub to_model: String,
+    /// Its version.
+    pub to_version: u32,
+    /// Epoch by which molting must finish, if one is set.
+    pub deadline_epoch: Option<u64>,
 }
 
 /// Registry tracking all model versions and their activation history.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionRegistry {
-    /// All registered model versions, ordered by registration time.
+    /// All registered model versions, ordered by activation epoch.
     pub versions: Vec<ModelVersion>,
 }
 
@@ -33,9 +50,57 @@ impl VersionRegistry {
         }
     }
 
-    /// Register a new model version.
-    pub fn register(&mut self, version: ModelVersion) {
-        self.versions.push(version);
+    /// Build a registry from versions in any order, validating each.
+    pub fn from_versions(versions: Vec<ModelVersion>) -> Result<Self, ChitinError> {
+        let mut registry = Self::new();
+        for version in versions {
+            registry.register(version)?;
+        }
+        Ok(registry)
+    }
+
+    /// Register a new model version, keeping versions ordered by activation.
+    ///
+    /// Rejects duplicate versions, versions not newer than the model's
+    /// current one, and deprecation or molt deadlines before activation.
+    pub fn register(&mut self, version: ModelVersion) -> Result<(), ChitinError> {
+        validate(&version)?;
+        if let Some(current) = self.current_version(&version.model_id) {
+            if version.version <= current.version {
+                return Err(ChitinError::InvalidState(format!(
+                    "Model {} version {} is not newer than version {}",
+                    version.model_id, version.version, current.version
+                )));
+            }
+        }
+        let at = self
+            .versions
+            .partition_point(|v| v.activated_at_epoch <= version.activated_at_epoch);
+        self.versions.insert(at, version);
+        Ok(())
+    }
+
+    /// Deprecate a model version from `epoch`, retiring it at `molt_deadline`.
+    pub fn deprecate(
+        &mut self,
+        model_id: &str,
+        version: u32,
+        epoch: u64,
+        molt_deadline: Option<u64>,
+    ) -> Result<(), ChitinError> {
+        let entry = self
+            .versions
+            .iter_mut()
+            .find(|v| v.model_id == model_id && v.version == version)
+            .ok_or_else(|| {
+                ChitinError::NotFound(format!("Model {} version {}", model_id, version))
+            })?;
+        let mut updated = entry.clone();
+        updated.deprecated_at_epoch = Some(epoch);
+        updated.molt_deadline_epoch = molt_deadline;
+        validate(&updated)?;
+        *entry = updated;
+        Ok(())
     }
 
     /// Get the current (latest) version of a model by its model_id.
@@ -58,6 +123,118 @@ impl VersionRegistry {
         versions.sort_by_key(|v| v.version);
         versions
     }
+
+    /// Status of a model's current version at `epoch`, if it is registered.
+    pub fn status(&self, model_id: &str, epoch: u64) -> Option<ModelStatus> {
+        self.current_version(model_id).map(|v| v.status_at(epoch))
+    }
+
+    /// Versions accepting submissions at `epoch` (active or deprecated).
+    pub fn accepting_at(&self, epoch: u64) -> Vec<&ModelVersion> {
+        self.versions
+            .iter()
+            .filter(|v| {
+                matches!(v.status_at(epoch), ModelStatus::Active | ModelStatus::Deprecated)
+            })
+            .collect()
+    }
+
+    /// Check that a Polyp embedded under `model_id` may be submitted at
+    /// `epoch`.
+    ///
+    /// Unregistered models are accepted, so nodes without a registry keep
+    /// working; registered models are rejected once retired.
+    pub fn check_submission(&self, model_id: &str, epoch: u64) -> Result<(), ChitinError> {
+        match self.status(model_id, epoch) {
+            Some(ModelStatus::Retired) => Err(ChitinError::InvalidState(format!(
+                "Embedding model {} was retired at epoch {}",
+                model_id,
+                self.current_version(model_id)
+                    .and_then(|v| v.molt_deadline_epoch)
+                    .unwrap_or(epoch)
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The most recently activated version that is active at `epoch`.
+    pub fn active_version(&self, epoch: u64) -> Option<&ModelVersion> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|v| v.status_at(epoch) == ModelStatus::Active)
+    }
+
+    /// Migrations due at `epoch`: each deprecated version paired with the
+    /// active version.
+    pub fn molt_tasks(&self, epoch: u64) -> Vec<MoltTask> {
+        let target = match self.active_version(epoch) {
+            Some(target) => target,
+            None => return Vec::new(),
+        };
+        self.versions
+            .iter()
+            .filter(|v| v.status_at(epoch) == ModelStatus::Deprecated)
+            .map(|v| MoltTask {
+                from_model: v.model_id.clone(),
+                from_version: v.version,
+                to_model: target.model_id.clone(),
+                to_version: target.version,
+                deadline_epoch: v.molt_deadline_epoch,
+            })
+            .collect()
+    }
+
+    /// Merge versions learned from a peer or the metagraph.
+    ///
+    /// Unknown versions are registered if valid. For known versions, the
+    /// earliest deprecation and molt deadline win, so a schedule can only
+    /// be tightened. Returns how many versions were added or changed.
+    pub fn merge(&mut self, versions: &[ModelVersion]) -> usize {
+        let mut changed = 0;
+        for incoming in versions {
+            let existing = self
+                .versions
+                .iter_mut()
+                .find(|v| v.model_id == incoming.model_id && v.version == incoming.version);
+            match existing {
+                Some(existing) => {
+                    let mut updated = existing.clone();
+                    updated.deprecated_at_epoch =
+                        earliest(existing.deprecated_at_epoch, incoming.deprecated_at_epoch);
+                    updated.molt_deadline_epoch =
+                        earliest(existing.molt_deadline_epoch, incoming.molt_deadline_epoch);
+                    if updated != *existing && validate(&updated).is_ok() {
+                        *existing = updated;
+                        changed += 1;
+                    }
+                }
+                None => {
+                    if self.register(incoming.clone()).is_ok() {
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Load the persisted registry, if any.
+    pub fn load(store: &RocksStore) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(KEY.as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this registry.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(KEY.as_bytes(), &serde_json::to_vec(self)?)
+    }
 }
 
 impl Default for VersionRegistry {
@@ -65,3 +242,128 @@ impl Default for VersionRegistry {
         Self::new()
     }
 }
+
+/// Check that a version's deprecation window follows its activation.
+fn validate(version: &ModelVersion) -> Result<(), ChitinError> {
+    let activated = version.activated_at_epoch;
+    if version.deprecated_at_epoch.is_some_and(|d| d < activated) {
+        return Err(ChitinError::InvalidState(format!(
+            "Model {} version {} is deprecated before it activates",
+            version.model_id, version.version
+        )));
+    }
+    let window_start = version.deprecated_at_epoch.unwrap_or(activated);
+    if version.molt_deadline_epoch.is_some_and(|d| d < window_start) {
+        return Err(ChitinError::InvalidState(format!(
+            "Model {} version {} has a molt deadline before its deprecation",
+            version.model_id, version.version
+        )));
+    }
+    Ok(())
+}
+
+fn earliest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(model_id: &str, version: u32, activated_at_epoch: u64) -> ModelVersion {
+        ModelVersion {
+            model_id: model_id.to_string(),
+            version,
+            activated_at_epoch,
+            deprecated_at_epoch: None,
+            molt_deadline_epoch: None,
+        }
+    }
+
+    fn registry() -> VersionRegistry {
+        VersionRegistry::from_versions(vec![
+            version("bge/bge-small-en-v1.5", 2, 10),
+            version("chitin/hash-embedding-v1", 1, 0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn register_keeps_activation_order_and_rejects_stale_versions() {
+        let mut registry = registry();
+        assert_eq!(registry.versions[0].model_id, "chitin/hash-embedding-v1");
+        assert!(registry.register(version("bge/bge-small-en-v1.5", 2, 20)).is_err());
+        assert!(registry.register(version("bge/bge-small-en-v1.5", 1, 20)).is_err());
+
+        let mut early_deadline = version("bge/bge-small-en-v1.5", 3, 20);
+        early_deadline.deprecated_at_epoch = Some(30);
+        early_deadline.molt_deadline_epoch = Some(25);
+        assert!(registry.register(early_deadline).is_err());
+
+        registry.register(version("bge/bge-small-en-v1.5", 3, 5)).unwrap();
+        assert_eq!(registry.versions[1].version, 3);
+        assert_eq!(registry.current_version("bge/bge-small-en-v1.5").unwrap().version, 3);
+    }
+
+    #[test]
+    fn deprecation_window_schedules_molts_then_retires() {
+        let mut registry = registry();
+        registry
+            .deprecate("chitin/hash-embedding-v1", 1, 20, Some(30))
+            .unwrap();
+        assert!(registry.deprecate("unknown/model", 1, 20, None).is_err());
+        assert!(registry
+            .deprecate("chitin/hash-embedding-v1", 1, 20, Some(10))
+            .is_err());
+
+        let hash = "chitin/hash-embedding-v1";
+        assert_eq!(registry.status(hash, 15), Some(ModelStatus::Active));
+        assert!(registry.molt_tasks(15).is_empty());
+
+        assert_eq!(registry.status(hash, 20), Some(ModelStatus::Deprecated));
+        assert!(registry.check_submission(hash, 25).is_ok());
+        let tasks = registry.molt_tasks(25);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].to_model, "bge/bge-small-en-v1.5");
+        assert_eq!(tasks[0].deadline_epoch, Some(30));
+        assert_eq!(registry.active_version(25).unwrap().model_id, "bge/bge-small-en-v1.5");
+
+        assert_eq!(registry.status(hash, 30), Some(ModelStatus::Retired));
+        assert!(registry.check_submission(hash, 30).is_err());
+        assert!(registry.check_submission("unknown/model", 30).is_ok());
+        assert_eq!(registry.status("bge/bge-small-en-v1.5", 5), Some(ModelStatus::Pending));
+        assert_eq!(registry.accepting_at(30).len(), 1);
+    }
+
+    #[test]
+    fn merge_only_tightens_schedules_and_round_trips() {
+        let mut registry = registry();
+        let mut peer = registry.clone();
+        peer.deprecate("chitin/hash-embedding-v1", 1, 20, Some(40))
+            .unwrap();
+        peer.register(version("bge/bge-small-en-v1.5", 3, 50)).unwrap();
+
+        assert_eq!(registry.merge(&peer.versions), 2);
+        assert_eq!(registry.merge(&peer.versions), 0);
+
+        let mut looser = peer.clone();
+        looser.versions[0].molt_deadline_epoch = Some(60);
+        assert_eq!(registry.merge(&looser.versions), 0);
+        assert_eq!(registry.versions[0].molt_deadline_epoch, Some(40));
+
+        let path = std::env::temp_dir().join(format!(
+            "chitin-versioning-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        assert!(VersionRegistry::load(&store).unwrap().is_none());
+        registry.save(&store).unwrap();
+        let loaded = VersionRegistry::load(&store).unwrap().unwrap();
+        assert_eq!(loaded.versions, registry.versions);
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
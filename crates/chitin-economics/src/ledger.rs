@@ -0,0 +1,531 @@
+// crates/chitin-economics/src/ledger.rs
+//
+// Account ledger: liquid $CTN balances per coldkey, signed transfers, and
+// signed stake actions.
+//
+// A transfer moves `amount` from one coldkey to another and pays `fee` on
+// top, which is deposited in the treasury. Staking moves liquid balance into
+// the `StakeManager`; unstaking starts a cooldown, after which
+// `release_unstakes` credits the stake back. Stakes go only to UIDs in the
+// identity registry, so a stake never points at a UID that is later handed
+// to another node. The sender signs each
+// transfer or stake action's canonical bytes with its coldkey. Each carries
+// the sender's nonce (the number of transfers and stake actions it has
+// made before), so a signed action applies at most once. Balances are seeded
+// from the genesis file; the node persists the ledger after every change, so
+// nonces survive restarts.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::staking::{StakeEntry, StakeManager};
+use crate::token::RAO_PER_CTN;
+use crate::treasury::Treasury;
+use chitin_core::crypto::{hash_bytes, verify_signature};
+use chitin_core::error::ChitinError;
+use chitin_core::IdentityRegistry;
+
+/// Fee charged per transfer: 0.001 CTN (in rao).
+pub const TRANSFER_FEE_RAO: u64 = RAO_PER_CTN / 1_000;
+
+/// Domain separator prefixed to a transfer's signing bytes.
+const TRANSFER_DOMAIN: &[u8] = b"chitin-transfer-v1";
+
+/// Domain separator prefixed to a stake action's signing bytes.
+const STAKE_DOMAIN: &[u8] = b"chitin-stake-v1";
+
+/// An unsigned transfer of $CTN between coldkeys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transfer {
+    /// Sender coldkey; must sign the transfer.
+    pub from: [u8; 32],
+    /// Recipient coldkey.
+    pub to: [u8; 32],
+    /// Amount credited to the recipient, in rao.
+    pub amount: u64,
+    /// Fee paid by the sender on top of `amount`, in rao.
+    pub fee: u64,
+    /// The sender's nonce: how many transfers and stake actions it has made
+    /// before this one.
+    pub nonce: u64,
+}
+
+impl Transfer {
+    /// The bytes the sender signs.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TRANSFER_DOMAIN.len() + 88);
+        bytes.extend_from_slice(TRANSFER_DOMAIN);
+        bytes.extend_from_slice(&self.from);
+        bytes.extend_from_slice(&self.to);
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.fee.to_le_bytes());
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    /// The transfer's hash, which identifies it in history.
+    pub fn hash(&self) -> [u8; 32] {
+        hash_bytes(&self.signing_bytes())
+    }
+}
+
+/// A transfer with the sender's ed25519 signature over its signing bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTransfer {
+    pub transfer: Transfer,
+    /// 64-byte ed25519 signature.
+    pub signature: Vec<u8>,
+}
+
+/// Whether a stake action adds or removes stake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeActionKind {
+    Stake,
+    Unstake,
+}
+
+/// An unsigned request to stake to, or unstake from, a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeAction {
+    pub kind: StakeActionKind,
+    /// Staker coldkey; must sign the action.
+    pub staker: [u8; 32],
+    /// Network UID of the node.
+    pub node_uid: u16,
+    /// Amount in rao; for an unstake, 0 unstakes everything on the node.
+    pub amount: u64,
+    /// The staker's nonce, shared with its transfers.
+    pub nonce: u64,
+}
+
+impl StakeAction {
+    /// The bytes the staker signs.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(STAKE_DOMAIN.len() + 51);
+        bytes.extend_from_slice(STAKE_DOMAIN);
+        bytes.push(match self.kind {
+            StakeActionKind::Stake => 0,
+            StakeActionKind::Unstake => 1,
+        });
+        bytes.extend_from_slice(&self.staker);
+        bytes.extend_from_slice(&self.node_uid.to_le_bytes());
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+}
+
+/// A stake action with the staker's ed25519 signature over its signing bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedStakeAction {
+    pub action: StakeAction,
+    /// 64-byte ed25519 signature.
+    pub signature: Vec<u8>,
+}
+
+/// An applied transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferRecord {
+    /// Hash of the transfer.
+    pub tx_hash: [u8; 32],
+    pub transfer: Transfer,
+    /// Block at which the transfer was applied.
+    pub block: u64,
+}
+
+/// Liquid balances and transfer history for all coldkeys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(with = "account_map")]
+    balances: HashMap<[u8; 32], u64>,
+    #[serde(with = "account_map")]
+    nonces: HashMap<[u8; 32], u64>,
+    history: Vec<TransferRecord>,
+    treasury: Treasury,
+}
+
+impl Ledger {
+    /// Create an empty ledger.
+    pub fn new() -> Self {
+        Self {
+            balances: HashMap::new(),
+            nonces: HashMap::new(),
+            history: Vec::new(),
+            treasury: Treasury::new(),
+        }
+    }
+
+    /// Credit `amount` rao to `account` (genesis allocations).
+    ///
+    /// # Errors
+    /// Returns `ChitinError::InvalidState` if the balance would overflow.
+    pub fn credit(&mut self, account: [u8; 32], amount: u64) -> Result<(), ChitinError> {
+        let balance = self.balances.entry(account).or_insert(0);
+        *balance = balance.checked_add(amount).ok_or_else(|| {
+            ChitinError::InvalidState("Balance overflows u64 rao".to_string())
+        })?;
+        Ok(())
+    }
+
+    /// Liquid balance of `account` in rao.
+    pub fn balance(&self, account: &[u8; 32]) -> u64 {
+        self.balances.get(account).copied().unwrap_or(0)
+    }
+
+    /// The nonce the next transfer or stake action from `account` must carry.
+    pub fn nonce(&self, account: &[u8; 32]) -> u64 {
+        self.nonces.get(account).copied().unwrap_or(0)
+    }
+
+    /// The fee a transfer must pay, in rao.
+    pub fn transfer_fee(&self) -> u64 {
+        TRANSFER_FEE_RAO
+    }
+
+    /// Fees collected so far.
+    pub fn treasury(&self) -> &Treasury {
+        &self.treasury
+    }
+
+    /// Verify and apply a signed transfer at `block`.
+    ///
+    /// # Errors
+    /// Returns `ChitinError::Crypto` if the signature is invalid, and
+    /// `ChitinError::InvalidState` if the transfer is malformed, pays too
+    /// small a fee, carries the wrong nonce, or exceeds the sender's balance.
+    pub fn apply(
+        &mut self,
+        signed: &SignedTransfer,
+        block: u64,
+    ) -> Result<TransferRecord, ChitinError> {
+        let transfer = &signed.transfer;
+        if transfer.amount == 0 {
+            return Err(ChitinError::InvalidState(
+                "Transfer amount must be positive".to_string(),
+            ));
+        }
+        if transfer.from == transfer.to {
+            return Err(ChitinError::InvalidState(
+                "Cannot transfer to the sending account".to_string(),
+            ));
+        }
+        if transfer.fee < self.transfer_fee() {
+            return Err(ChitinError::InvalidState(format!(
+                "Fee {} rao is below the transfer fee of {} rao",
+                transfer.fee,
+                self.transfer_fee()
+            )));
+        }
+        self.check_signed(
+            &transfer.from,
+            transfer.nonce,
+            &transfer.signing_bytes(),
+            &signed.signature,
+        )?;
+
+        let balance = self.balance(&transfer.from);
+        let debit = transfer.amount.checked_add(transfer.fee);
+        let remaining = match debit.and_then(|debit| balance.checked_sub(debit)) {
+            Some(remaining) => remaining,
+            None => {
+                return Err(ChitinError::InvalidState(format!(
+                    "Insufficient balance: transfer needs {} rao plus a {} rao fee but only {} rao available",
+                    transfer.amount, transfer.fee, balance
+                )))
+            }
+        };
+        self.credit(transfer.to, transfer.amount)?;
+        self.balances.insert(transfer.from, remaining);
+        self.nonces.insert(transfer.from, transfer.nonce + 1);
+        self.treasury.deposit(transfer.fee);
+
+        let record = TransferRecord {
+            tx_hash: transfer.hash(),
+            transfer: transfer.clone(),
+            block,
+        };
+        self.history.push(record.clone());
+        Ok(record)
+    }
+
+    /// Verify and apply a signed stake or unstake at `block`.
+    ///
+    /// A stake debits the liquid balance; an unstake starts the cooldown and
+    /// returns the block at which it completes. Returns `None` for a stake.
+    ///
+    /// # Errors
+    /// Returns `ChitinError::Crypto` if the signature is invalid,
+    /// `ChitinError::NotFound` if a stake targets a UID not in `identities`,
+    /// and `ChitinError::InvalidState` or `ChitinError::NotFound` if the nonce
+    /// is wrong, the balance is insufficient, or `stakes` refuses the action.
+    pub fn apply_stake(
+        &mut self,
+        signed: &SignedStakeAction,
+        stakes: &mut StakeManager,
+        identities: &IdentityRegistry,
+        block: u64,
+    ) -> Result<Option<u64>, ChitinError> {
+        let action = &signed.action;
+        self.check_signed(
+            &action.staker,
+            action.nonce,
+            &action.signing_bytes(),
+            &signed.signature,
+        )?;
+
+        let complete_at = match action.kind {
+            StakeActionKind::Stake => {
+                if identities.get(action.node_uid).is_none() {
+                    return Err(ChitinError::NotFound(format!(
+                        "node_uid {} is not registered",
+                        action.node_uid
+                    )));
+                }
+                let balance = self.balance(&action.staker);
+                if action.amount > balance {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Insufficient balance: staking {} rao but only {} rao available",
+                        action.amount, balance
+                    )));
+                }
+                stakes.stake(StakeEntry {
+                    staker: action.staker,
+                    amount: action.amount,
+                    node_uid: action.node_uid,
+                    staked_at_block: block,
+                    unstake_requested_at: None,
+                })?;
+                self.balances.insert(action.staker, balance - action.amount);
+                None
+            }
+            StakeActionKind::Unstake => Some(stakes.request_unstake_amount(
+                &action.staker,
+                action.node_uid,
+                action.amount,
+                block,
+            )?),
+        };
+        self.nonces.insert(action.staker, action.nonce + 1);
+        Ok(complete_at)
+    }
+
+    /// Credit back every unstake in `stakes` whose cooldown has completed by
+    /// `block`. Returns the released entries.
+    pub fn release_unstakes(&mut self, stakes: &mut StakeManager, block: u64) -> Vec<StakeEntry> {
+        let released = stakes.process_unstakes(block);
+        for entry in &released {
+            let balance = self.balances.entry(entry.staker).or_insert(0);
+            *balance = balance.saturating_add(entry.amount);
+        }
+        released
+    }
+
+    /// Check that `signature` is `signer`'s over `message` and that `nonce`
+    /// is the signer's next nonce.
+    fn check_signed(
+        &self,
+        signer: &[u8; 32],
+        nonce: u64,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), ChitinError> {
+        if !verify_signature(signer, message, signature)? {
+            return Err(ChitinError::Crypto(
+                "Signature does not match the sending coldkey".to_string(),
+            ));
+        }
+        let expected = self.nonce(signer);
+        if nonce != expected {
+            return Err(ChitinError::InvalidState(format!(
+                "Nonce {} does not match the sender's next nonce {}",
+                nonce, expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Up to `limit` transfers sent or received by `account`, newest first.
+    pub fn history(&self, account: &[u8; 32], limit: usize) -> Vec<&TransferRecord> {
+        self.history
+            .iter()
+            .rev()
+            .filter(|r| &r.transfer.from == account || &r.transfer.to == account)
+            .take(limit)
+            .collect()
+    }
+}
+
+impl Default for Ledger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serde adapter for maps keyed by account, as a sorted list of
+/// `(account, value)` pairs (JSON object keys must be strings).
+mod account_map {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, V>(map: &HashMap<[u8; 32], V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        let mut accounts: Vec<(&[u8; 32], &V)> = map.iter().collect();
+        accounts.sort_by_key(|&(account, _)| account);
+        serializer.collect_seq(accounts)
+    }
+
+    pub fn deserialize<'de, D, V>(deserializer: D) -> Result<HashMap<[u8; 32], V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let accounts: Vec<([u8; 32], V)> = Vec::deserialize(deserializer)?;
+        Ok(accounts.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::crypto::Keypair;
+    use chitin_core::NodeType;
+
+    fn sign(keypair: &Keypair, to: [u8; 32], amount: u64, nonce: u64) -> SignedTransfer {
+        let transfer = Transfer {
+            from: keypair.public_key_bytes(),
+            to,
+            amount,
+            fee: TRANSFER_FEE_RAO,
+            nonce,
+        };
+        SignedTransfer {
+            signature: keypair.sign(&transfer.signing_bytes()),
+            transfer,
+        }
+    }
+
+    #[test]
+    fn test_transfer_moves_amount_and_pays_fee() {
+        let alice = Keypair::generate();
+        let bob = [7u8; 32];
+        let mut ledger = Ledger::new();
+        ledger.credit(alice.public_key_bytes(), 10 * RAO_PER_CTN).unwrap();
+
+        let record = ledger.apply(&sign(&alice, bob, 4 * RAO_PER_CTN, 0), 12).unwrap();
+
+        assert_eq!(record.block, 12);
+        assert_eq!(ledger.balance(&bob), 4 * RAO_PER_CTN);
+        assert_eq!(
+            ledger.balance(&alice.public_key_bytes()),
+            6 * RAO_PER_CTN - TRANSFER_FEE_RAO
+        );
+        assert_eq!(ledger.treasury().balance(), TRANSFER_FEE_RAO);
+        assert_eq!(ledger.nonce(&alice.public_key_bytes()), 1);
+        assert_eq!(ledger.history(&bob, 10), vec![&record]);
+    }
+
+    #[test]
+    fn test_replay_and_bad_signature_are_rejected() {
+        let alice = Keypair::generate();
+        let mut ledger = Ledger::new();
+        ledger.credit(alice.public_key_bytes(), 10 * RAO_PER_CTN).unwrap();
+
+        let signed = sign(&alice, [7u8; 32], RAO_PER_CTN, 0);
+        ledger.apply(&signed, 1).unwrap();
+        assert!(ledger.apply(&signed, 2).is_err());
+
+        let mut forged = sign(&alice, [7u8; 32], RAO_PER_CTN, 1);
+        forged.transfer.amount = 2 * RAO_PER_CTN;
+        assert!(matches!(ledger.apply(&forged, 2), Err(ChitinError::Crypto(_))));
+        assert_eq!(ledger.history(&alice.public_key_bytes(), 10).len(), 1);
+    }
+
+    #[test]
+    fn test_serialized_ledger_keeps_nonces() {
+        let alice = Keypair::generate();
+        let mut ledger = Ledger::new();
+        ledger.credit(alice.public_key_bytes(), 10 * RAO_PER_CTN).unwrap();
+        let signed = sign(&alice, [7u8; 32], RAO_PER_CTN, 0);
+        ledger.apply(&signed, 1).unwrap();
+
+        let bytes = serde_json::to_vec(&ledger).unwrap();
+        let mut restored: Ledger = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(restored.balance(&[7u8; 32]), RAO_PER_CTN);
+        assert_eq!(restored.nonce(&alice.public_key_bytes()), 1);
+        assert_eq!(restored.treasury().balance(), TRANSFER_FEE_RAO);
+        assert!(restored.apply(&signed, 2).is_err());
+    }
+
+    fn sign_stake(
+        keypair: &Keypair,
+        kind: StakeActionKind,
+        amount: u64,
+        nonce: u64,
+    ) -> SignedStakeAction {
+        let action = StakeAction {
+            kind,
+            staker: keypair.public_key_bytes(),
+            node_uid: 0,
+            amount,
+            nonce,
+        };
+        SignedStakeAction {
+            signature: keypair.sign(&action.signing_bytes()),
+            action,
+        }
+    }
+
+    #[test]
+    fn test_stake_unstake_and_release() {
+        use crate::staking::CORAL_COOLDOWN_BLOCKS;
+
+        let alice = Keypair::generate();
+        let key = alice.public_key_bytes();
+        let mut ledger = Ledger::new();
+        let mut stakes = StakeManager::new();
+        let mut identities = IdentityRegistry::new();
+        ledger.credit(key, 100 * RAO_PER_CTN).unwrap();
+
+        // UID 0 is not registered yet, so the stake is refused untouched.
+        let stake = sign_stake(&alice, StakeActionKind::Stake, 60 * RAO_PER_CTN, 0);
+        assert!(matches!(
+            ledger.apply_stake(&stake, &mut stakes, &identities, 10),
+            Err(ChitinError::NotFound(_))
+        ));
+        assert_eq!(ledger.nonce(&key), 0);
+
+        identities.register([1u8; 32], [2u8; 32], NodeType::Coral, 0).unwrap();
+        assert_eq!(ledger.apply_stake(&stake, &mut stakes, &identities, 10).unwrap(), None);
+        assert!(ledger.apply_stake(&stake, &mut stakes, &identities, 11).is_err());
+        assert_eq!(ledger.balance(&key), 40 * RAO_PER_CTN);
+        assert_eq!(stakes.total_stake_for_node(0), 60 * RAO_PER_CTN);
+
+        let unstake = sign_stake(&alice, StakeActionKind::Unstake, 0, 1);
+        let complete_at = ledger.apply_stake(&unstake, &mut stakes, &identities, 20).unwrap();
+        assert_eq!(complete_at, Some(20 + CORAL_COOLDOWN_BLOCKS));
+
+        assert!(ledger.release_unstakes(&mut stakes, 19 + CORAL_COOLDOWN_BLOCKS).is_empty());
+        assert_eq!(ledger.release_unstakes(&mut stakes, 20 + CORAL_COOLDOWN_BLOCKS).len(), 1);
+        assert_eq!(ledger.balance(&key), 100 * RAO_PER_CTN);
+        assert_eq!(ledger.nonce(&key), 2);
+    }
+
+    #[test]
+    fn test_insufficient_balance_leaves_ledger_unchanged() {
+        let alice = Keypair::generate();
+        let mut ledger = Ledger::new();
+        ledger.credit(alice.public_key_bytes(), RAO_PER_CTN).unwrap();
+
+        // The fee is paid on top, so the whole balance cannot be sent.
+        assert!(ledger.apply(&sign(&alice, [7u8; 32], RAO_PER_CTN, 0), 1).is_err());
+        assert_eq!(ledger.balance(&alice.public_key_bytes()), RAO_PER_CTN);
+        assert_eq!(ledger.nonce(&alice.public_key_bytes()), 0);
+        assert_eq!(ledger.treasury().balance(), 0);
+    }
+}
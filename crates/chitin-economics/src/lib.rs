@@ -1,12 +1,14 @@
 // crates/chitin-economics/src/lib.rs
 //
 // chitin-economics: $CTN token economics, emission, staking, rewards,
-// slashing, and treasury management for the Chitin Protocol.
+// slashing, treasury management, and the account ledger for the Chitin Protocol.
 //
 // All monetary values are tracked in rao (the smallest unit of $CTN).
 // 1 CTN = 1,000,000,000 rao (10^9).
 
 pub mod emission;
+pub mod ledger;
+pub mod params;
 pub mod rewards;
 pub mod slashing;
 pub mod staking;
@@ -18,6 +20,11 @@ pub use emission::{
     cumulative_emission, emission_at_block, epoch_emission, HALVING_INTERVAL,
     INITIAL_BLOCK_REWARD_RAO, TREASURY_FRACTION, VALIDATOR_FRACTION,
 };
+pub use ledger::{
+    Ledger, SignedStakeAction, SignedTransfer, StakeAction, StakeActionKind, Transfer,
+    TransferRecord, TRANSFER_FEE_RAO,
+};
+pub use params::EconomicsParams;
 pub use rewards::{compute_rewards, RewardDistribution};
 pub use slashing::{compute_penalty, SlashCondition, SlashResult};
 pub use staking::{StakeEntry, StakeManager};
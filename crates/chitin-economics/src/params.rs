@@ -0,0 +1,174 @@
+// crates/chitin-economics/src/params.rs
+//
+// Per-network economics parameters: the emission schedule and minimum stakes.
+//
+// The defaults are the protocol constants in `emission` and `staking`;
+// networks (and genesis files) may override any of them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::emission::{
+    HALVING_INTERVAL, INITIAL_BLOCK_REWARD_RAO, TREASURY_FRACTION, VALIDATOR_FRACTION,
+};
+use crate::staking::{CORAL_MINIMUM, TIDE_MINIMUM};
+use chitin_core::error::ChitinError;
+use chitin_core::identity::NodeType;
+
+/// Economics parameters of a network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomicsParams {
+    /// Block reward before the first halving, in rao.
+    pub initial_block_reward_rao: u64,
+    /// Blocks between reward halvings.
+    pub halving_interval: u64,
+    /// Fraction of emission paid to the treasury.
+    pub treasury_fraction: f64,
+    /// Fraction of post-treasury emission paid to Tide validators.
+    pub validator_fraction: f64,
+    /// Minimum stake for a Coral node, in rao.
+    pub coral_minimum_stake_rao: u64,
+    /// Minimum stake for a Tide node, in rao.
+    pub tide_minimum_stake_rao: u64,
+}
+
+impl Default for EconomicsParams {
+    fn default() -> Self {
+        Self {
+            initial_block_reward_rao: INITIAL_BLOCK_REWARD_RAO,
+            halving_interval: HALVING_INTERVAL,
+            treasury_fraction: TREASURY_FRACTION,
+            validator_fraction: VALIDATOR_FRACTION,
+            coral_minimum_stake_rao: CORAL_MINIMUM,
+            tide_minimum_stake_rao: TIDE_MINIMUM,
+        }
+    }
+}
+
+impl EconomicsParams {
+    /// Check that the parameters are usable.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        if self.halving_interval == 0 {
+            return Err(ChitinError::InvalidState(
+                "halving_interval must be positive".to_string(),
+            ));
+        }
+        for (name, fraction) in [
+            ("treasury_fraction", self.treasury_fraction),
+            ("validator_fraction", self.validator_fraction),
+        ] {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err(ChitinError::InvalidState(format!(
+                    "{} must be in [0.0, 1.0], got {}",
+                    name, fraction
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Minimum stake for a node of `node_type`, in rao. Hybrid nodes validate,
+    /// so they need the Tide minimum; seeds need no stake.
+    pub fn minimum_stake(&self, node_type: &NodeType) -> u64 {
+        match node_type {
+            NodeType::Coral => self.coral_minimum_stake_rao,
+            NodeType::Tide | NodeType::Hybrid => self.tide_minimum_stake_rao,
+            NodeType::Seed => 0,
+        }
+    }
+
+    /// Block reward at `block`, in rao.
+    pub fn emission_at_block(&self, block: u64) -> u64 {
+        let halvings = block / self.halving_interval;
+        if halvings >= 64 {
+            return 0;
+        }
+        self.initial_block_reward_rao >> halvings
+    }
+
+    /// Total emission of the `blocks` blocks starting at `start_block`, in
+    /// rao, summed per halving period.
+    pub fn epoch_emission(&self, start_block: u64, blocks: u64) -> u64 {
+        let end_block = start_block.saturating_add(blocks);
+        let mut total = 0u64;
+        let mut block = start_block;
+        while block < end_block {
+            let period_end = (block / self.halving_interval + 1)
+                .saturating_mul(self.halving_interval)
+                .min(end_block);
+            let reward = self.emission_at_block(block);
+            if reward == 0 {
+                break;
+            }
+            total = total.saturating_add(reward.saturating_mul(period_end - block));
+            block = period_end;
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emission::{emission_at_block, epoch_emission};
+    use crate::token::RAO_PER_CTN;
+
+    #[test]
+    fn test_default_params_match_protocol_schedule() {
+        let params = EconomicsParams::default();
+        assert!(params.validate().is_ok());
+        for block in [
+            0,
+            HALVING_INTERVAL - 1,
+            HALVING_INTERVAL,
+            HALVING_INTERVAL * 3,
+        ] {
+            assert_eq!(params.emission_at_block(block), emission_at_block(block));
+        }
+        let start = HALVING_INTERVAL - 100;
+        assert_eq!(
+            params.epoch_emission(start, 360),
+            epoch_emission(start, 360)
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_params() {
+        let zero_interval = EconomicsParams {
+            halving_interval: 0,
+            ..EconomicsParams::default()
+        };
+        assert!(zero_interval.validate().is_err());
+        let bad_fraction = EconomicsParams {
+            treasury_fraction: 1.5,
+            ..EconomicsParams::default()
+        };
+        assert!(bad_fraction.validate().is_err());
+    }
+
+    #[test]
+    fn test_minimum_stake_by_node_type() {
+        let params = EconomicsParams {
+            coral_minimum_stake_rao: RAO_PER_CTN,
+            tide_minimum_stake_rao: 2 * RAO_PER_CTN,
+            ..EconomicsParams::default()
+        };
+        assert_eq!(params.minimum_stake(&NodeType::Coral), RAO_PER_CTN);
+        assert_eq!(params.minimum_stake(&NodeType::Tide), 2 * RAO_PER_CTN);
+        assert_eq!(params.minimum_stake(&NodeType::Hybrid), 2 * RAO_PER_CTN);
+        assert_eq!(params.minimum_stake(&NodeType::Seed), 0);
+    }
+
+    #[test]
+    fn test_params_json_roundtrip_with_defaults() {
+        let params: EconomicsParams =
+            serde_json::from_str(r#"{"halving_interval": 1000}"#).unwrap();
+        assert_eq!(params.halving_interval, 1000);
+        assert_eq!(params.treasury_fraction, TREASURY_FRACTION);
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EconomicsParams>(&json).unwrap(),
+            params
+        );
+    }
+}
@@ -2,11 +2,12 @@
 //
 // Slashing conditions and penalty computation for the Chitin Protocol.
 //
-// Four conditions trigger slashing (partial or full stake forfeiture):
+// Five conditions trigger slashing (partial or full stake forfeiture):
 //   1. Invalid ZK Proof — 100% of stake (critical)
 //   2. Consensus Deviation — 5% of stake per offense (moderate)
 //   3. Liveness Failure — 1% of stake per missed epoch (low)
 //   4. Duplicate Submission — 10% of stake (moderate)
+//   5. Sybil Collusion — 25% of stake (high)
 //
 // Slashed tokens flow to the protocol treasury.
 //
@@ -26,6 +27,9 @@ pub const LIVENESS_FAILURE_RATE: f64 = 0.01;
 /// Slash rate for duplicate submission: 10% of stake.
 pub const DUPLICATE_SUBMISSION_RATE: f64 = 0.10;
 
+/// Slash rate for membership in a detected Sybil cluster: 25% of stake.
+pub const SYBIL_COLLUSION_RATE: f64 = 0.25;
+
 /// Conditions that trigger slashing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SlashCondition {
@@ -45,6 +49,10 @@ pub enum SlashCondition {
     /// Coral Node submits a Polyp that is a near-duplicate (cosine similarity > 0.98)
     /// of an existing hardened Polyp in the same model namespace. Severity: Moderate.
     DuplicateSubmission,
+
+    /// Node belongs to a detected Sybil cluster: a dense mutual-trust clique
+    /// of new, low-stake nodes with little external endorsement. Severity: High.
+    SybilCollusion,
 }
 
 /// Result of a slashing event.
@@ -72,6 +80,7 @@ pub fn compute_penalty(condition: &SlashCondition, current_stake: u64) -> u64 {
         SlashCondition::ConsensusDeviation => CONSENSUS_DEVIATION_RATE,
         SlashCondition::LivenessFailure => LIVENESS_FAILURE_RATE,
         SlashCondition::DuplicateSubmission => DUPLICATE_SUBMISSION_RATE,
+        SlashCondition::SybilCollusion => SYBIL_COLLUSION_RATE,
     };
 
     let penalty = (current_stake as f64 * rate) as u64;
@@ -115,6 +124,13 @@ mod tests {
         assert_eq!(penalty, expected);
     }
 
+    #[test]
+    fn test_sybil_collusion_slashes_25_percent() {
+        let stake = 400 * RAO_PER_CTN;
+        let penalty = compute_penalty(&SlashCondition::SybilCollusion, stake);
+        assert_eq!(penalty, 100 * RAO_PER_CTN);
+    }
+
     #[test]
     fn test_penalty_does_not_exceed_stake() {
         // Even with 100% rate, penalty should not exceed stake
@@ -53,10 +53,21 @@ pub struct StakeEntry {
     pub unstake_requested_at: Option<u64>,
 }
 
+impl StakeEntry {
+    /// The block at which a pending unstake completes, if one is pending.
+    ///
+    /// Phase 1: every entry uses the coral cooldown (see `process_unstakes`).
+    pub fn cooldown_complete_block(&self) -> Option<u64> {
+        self.unstake_requested_at
+            .map(|requested_at| requested_at.saturating_add(CORAL_COOLDOWN_BLOCKS))
+    }
+}
+
 /// Manages all stake entries for the network.
 ///
 /// Provides operations for staking, requesting unstakes, and processing
 /// completed cooldown periods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakeManager {
     entries: Vec<StakeEntry>,
 }
@@ -129,6 +140,73 @@ impl StakeManager {
         Ok(())
     }
 
+    /// Request unstaking `amount` rao of a staker's active stake on a node,
+    /// or all of it if `amount` is 0 or covers the whole stake.
+    ///
+    /// Active entries are unstaked oldest first; the last one is split if it
+    /// is only partly unstaked. Returns the block at which the cooldown
+    /// completes.
+    ///
+    /// # Errors
+    /// Returns `ChitinError::NotFound` if the staker has no active stake on the
+    /// node, and `ChitinError::InvalidState` if `amount` exceeds the active
+    /// stake or would leave less than the delegation minimum staked.
+    pub fn request_unstake_amount(
+        &mut self,
+        staker: &[u8; 32],
+        node_uid: u16,
+        amount: u64,
+        current_block: u64,
+    ) -> Result<u64, ChitinError> {
+        let active = |e: &StakeEntry| {
+            e.staker == *staker && e.node_uid == node_uid && e.unstake_requested_at.is_none()
+        };
+        let total: u64 = self.entries.iter().filter(|e| active(e)).map(|e| e.amount).sum();
+        if total == 0 {
+            return Err(ChitinError::NotFound(format!(
+                "No active stake entry found for staker and node_uid {}",
+                node_uid
+            )));
+        }
+        let amount = if amount == 0 { total } else { amount };
+        if amount > total {
+            return Err(ChitinError::InvalidState(format!(
+                "Cannot unstake {} rao: only {} rao is staked to node_uid {}",
+                amount, total, node_uid
+            )));
+        }
+        let remaining = total - amount;
+        if remaining > 0 && remaining < DELEGATION_MINIMUM {
+            return Err(ChitinError::InvalidState(format!(
+                "Unstaking {} rao would leave {} rao staked, below the minimum of {} rao; unstake all of it instead",
+                amount, remaining, DELEGATION_MINIMUM
+            )));
+        }
+
+        let mut left = amount;
+        let mut split = None;
+        for entry in self.entries.iter_mut().filter(|e| active(e)) {
+            if left == 0 {
+                break;
+            }
+            if entry.amount > left {
+                // Keep the rest active; the unstaked part becomes its own entry.
+                entry.amount -= left;
+                split = Some(StakeEntry {
+                    amount: left,
+                    unstake_requested_at: Some(current_block),
+                    ..entry.clone()
+                });
+                left = 0;
+            } else {
+                left -= entry.amount;
+                entry.unstake_requested_at = Some(current_block);
+            }
+        }
+        self.entries.extend(split);
+        Ok(current_block.saturating_add(CORAL_COOLDOWN_BLOCKS))
+    }
+
     /// Process all unstake requests that have completed their cooldown period.
     ///
     /// Returns the list of `StakeEntry` values that have been fully unstaked
@@ -146,11 +224,10 @@ impl StakeManager {
         let mut remaining = Vec::new();
 
         for entry in self.entries.drain(..) {
-            if let Some(requested_at) = entry.unstake_requested_at {
-                // Phase 1: Use the coral cooldown as a conservative default.
-                // Phase 2+: Look up cooldown based on node type.
-                let cooldown = CORAL_COOLDOWN_BLOCKS;
-                if current_block >= requested_at + cooldown {
+            // Phase 1: Use the coral cooldown as a conservative default.
+            // Phase 2+: Look up cooldown based on node type.
+            if let Some(complete_at) = entry.cooldown_complete_block() {
+                if current_block >= complete_at {
                     completed.push(entry);
                 } else {
                     remaining.push(entry);
@@ -298,4 +375,34 @@ mod tests {
         // Pending unstake should not count toward total
         assert_eq!(manager.total_stake_for_node(0), 0);
     }
+
+    #[test]
+    fn test_request_unstake_amount_splits_entries() {
+        let mut manager = StakeManager::new();
+        manager.stake(make_entry(CORAL_MINIMUM, 0, 100)).unwrap();
+        manager.stake(make_entry(CORAL_MINIMUM, 0, 200)).unwrap();
+
+        let complete_at = manager
+            .request_unstake_amount(&test_staker(), 0, CORAL_MINIMUM * 3 / 2, 500)
+            .unwrap();
+        assert_eq!(complete_at, 500 + CORAL_COOLDOWN_BLOCKS);
+        assert_eq!(manager.total_stake_for_node(0), CORAL_MINIMUM / 2);
+        let pending: u64 = manager
+            .entries()
+            .iter()
+            .filter(|e| e.cooldown_complete_block() == Some(complete_at))
+            .map(|e| e.amount)
+            .sum();
+        assert_eq!(pending, CORAL_MINIMUM * 3 / 2);
+
+        // Leaving less than the minimum staked is refused; 0 unstakes the rest.
+        assert!(manager
+            .request_unstake_amount(&test_staker(), 0, CORAL_MINIMUM / 2 - 1, 600)
+            .is_err());
+        manager.request_unstake_amount(&test_staker(), 0, 0, 600).unwrap();
+        assert_eq!(manager.total_stake_for_node(0), 0);
+        assert!(manager
+            .request_unstake_amount(&test_staker(), 0, 0, 700)
+            .is_err());
+    }
 }
@@ -12,6 +12,8 @@
 //
 // Reference: ARCHITECTURE.md Section 7.5
 
+use serde::{Deserialize, Serialize};
+
 use chitin_core::error::ChitinError;
 
 /// The protocol treasury.
@@ -19,6 +21,7 @@ use chitin_core::error::ChitinError;
 /// Tracks the total balance of $CTN held in the treasury (in rao).
 /// Deposits come from emission allocation and slashing proceeds.
 /// Withdrawals are governed by governance (Phase 3+).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Treasury {
     /// Current balance in rao.
     balance: u64,
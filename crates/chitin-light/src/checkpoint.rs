@@ -0,0 +1,50 @@
+// crates/chitin-light/src/checkpoint.rs
+//
+// Epoch hardening checkpoints as peers serve them from
+// `sync/hardening_checkpoint`, and the peer quorum that accepts one.
+
+use serde::{Deserialize, Serialize};
+
+/// The Merkle root of the Polyps hardened in one epoch (the node's
+/// `HardeningCheckpoint`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Consensus epoch.
+    pub epoch: u64,
+    /// Root over the epoch's hardening leaves.
+    pub merkle_root: [u8; 32],
+    /// Number of Polyps hardened.
+    pub count: u64,
+    /// Randomness beacon revealed in the epoch before, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<[u8; 32]>,
+}
+
+impl Checkpoint {
+    /// The checkpoint reported by a strict majority of `reports`, if any.
+    pub fn quorum(reports: &[Checkpoint]) -> Option<Checkpoint> {
+        crate::quorum(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quorum_requires_a_strict_majority() {
+        let a = Checkpoint { epoch: 3, merkle_root: [1; 32], count: 2, beacon: None };
+        let b = Checkpoint { epoch: 3, merkle_root: [2; 32], count: 2, beacon: None };
+        assert_eq!(Checkpoint::quorum(&[a.clone(), b.clone()]), None);
+        assert_eq!(
+            Checkpoint::quorum(&[a.clone(), b.clone(), a.clone()]),
+            Some(a.clone())
+        );
+        assert_eq!(Checkpoint::quorum(&[]), None);
+
+        // The node's JSON decodes as-is.
+        let root = [1u8; 32];
+        let json = serde_json::json!({ "epoch": 3, "merkle_root": root, "count": 2 });
+        assert_eq!(serde_json::from_value::<Checkpoint>(json).unwrap(), a);
+    }
+}
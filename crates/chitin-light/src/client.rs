@@ -0,0 +1,273 @@
+// crates/chitin-light/src/client.rs
+//
+// `LightClient`: tracks metagraph headers and hardening checkpoints from a
+// set of peers and verifies hardened Polyps fetched from them on demand.
+//
+// Peers are asked over the node's JSON-RPC envelope (`{method, params}` in,
+// `{success, result, error}` out): `metagraph/snapshot` for headers,
+// `sync/hardening_checkpoint` for checkpoints, and `polyp/get` for Polyps.
+// Headers and checkpoints need a strict majority of the peers that answer;
+// a Polyp is taken from the first peer whose copy verifies, so one honest
+// peer is enough to read it and no dishonest one can forge it.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use chitin_core::light::majority;
+use chitin_core::{ChitinError, Polyp, ReefMetagraph};
+
+use crate::checkpoint::Checkpoint;
+use crate::header::MetagraphHeader;
+use crate::verify::{verify_hardened, VerifiedPolyp};
+
+/// Light client settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightConfig {
+    /// JSON-RPC endpoints of the peers to follow.
+    pub peers: Vec<String>,
+    /// Valid validator attestations a Polyp needs. `None` requires a strict
+    /// majority of the header's validator set.
+    pub min_attestations: Option<usize>,
+    /// Per-call timeout, in seconds.
+    pub timeout_secs: u64,
+    /// Most epochs of headers and checkpoints kept in memory.
+    pub max_tracked_epochs: usize,
+}
+
+impl Default for LightConfig {
+    fn default() -> Self {
+        Self {
+            peers: Vec::new(),
+            min_attestations: None,
+            timeout_secs: 10,
+            max_tracked_epochs: 1024,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    success: bool,
+    result: Option<Value>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnapshotResult {
+    metagraph: Option<ReefMetagraph>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckpointResult {
+    checkpoint: Option<Checkpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolypResult {
+    polyp: Option<Polyp>,
+}
+
+/// Client that follows the Reef without storing it.
+pub struct LightClient {
+    config: LightConfig,
+    http: reqwest::Client,
+    headers: BTreeMap<u64, MetagraphHeader>,
+    checkpoints: BTreeMap<u64, Checkpoint>,
+}
+
+impl LightClient {
+    /// A client following `config.peers`, tracking nothing yet.
+    pub fn new(config: LightConfig) -> Result<Self, ChitinError> {
+        if config.peers.is_empty() {
+            return Err(ChitinError::InvalidState(
+                "A light client needs at least one peer".to_string(),
+            ));
+        }
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self {
+            config,
+            http,
+            headers: BTreeMap::new(),
+            checkpoints: BTreeMap::new(),
+        })
+    }
+
+    /// The latest tracked header, if any.
+    pub fn header(&self) -> Option<&MetagraphHeader> {
+        self.headers.values().next_back()
+    }
+
+    /// The tracked header of the latest epoch at or before `epoch`, if any.
+    pub fn header_at(&self, epoch: u64) -> Option<&MetagraphHeader> {
+        self.headers.range(..=epoch).next_back().map(|(_, header)| header)
+    }
+
+    /// The tracked checkpoint of `epoch`, if any.
+    pub fn tracked_checkpoint(&self, epoch: u64) -> Option<&Checkpoint> {
+        self.checkpoints.get(&epoch)
+    }
+
+    /// Fetch the current metagraph header from every peer and track the one
+    /// a strict majority reports, along with its epoch's checkpoint if the
+    /// peers agree on one.
+    pub async fn sync(&mut self) -> Result<MetagraphHeader, ChitinError> {
+        let mut reports = Vec::new();
+        for peer in &self.config.peers {
+            match self
+                .call::<SnapshotResult>(peer, "metagraph/snapshot", serde_json::json!({}))
+                .await
+            {
+                Ok(result) => {
+                    reports.extend(result.metagraph.as_ref().map(MetagraphHeader::from_metagraph))
+                }
+                Err(e) => tracing::debug!("Light: no metagraph from {}: {}", peer, e),
+            }
+        }
+        let header = MetagraphHeader::quorum(&reports).ok_or_else(|| {
+            ChitinError::Consensus(format!(
+                "No metagraph header reported by a majority of {} peer(s)",
+                reports.len()
+            ))
+        })?;
+        self.headers.insert(header.epoch, header.clone());
+        if let Err(e) = self.checkpoint(header.epoch).await {
+            tracing::debug!("Light: no checkpoint for epoch {}: {}", header.epoch, e);
+        }
+        self.prune();
+        Ok(header)
+    }
+
+    /// The checkpoint of `epoch`: tracked, or fetched from every peer and
+    /// tracked if a strict majority agree.
+    pub async fn checkpoint(&mut self, epoch: u64) -> Result<Checkpoint, ChitinError> {
+        if let Some(checkpoint) = self.checkpoints.get(&epoch) {
+            return Ok(checkpoint.clone());
+        }
+        let mut reports = Vec::new();
+        for peer in &self.config.peers {
+            let params = serde_json::json!({ "epoch": epoch });
+            match self
+                .call::<CheckpointResult>(peer, "sync/hardening_checkpoint", params)
+                .await
+            {
+                Ok(result) => reports.extend(result.checkpoint.filter(|c| c.epoch == epoch)),
+                Err(e) => tracing::debug!("Light: no checkpoint {} from {}: {}", epoch, peer, e),
+            }
+        }
+        let checkpoint = Checkpoint::quorum(&reports).ok_or_else(|| {
+            ChitinError::Consensus(format!(
+                "No checkpoint for epoch {} reported by a majority of {} peer(s)",
+                epoch,
+                reports.len()
+            ))
+        })?;
+        self.checkpoints.insert(epoch, checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    /// Fetch the hardened Polyp `polyp_id` and verify it against its
+    /// epoch's checkpoint and the validator set of the latest header at or
+    /// before that epoch (or the latest header, for epochs before any
+    /// tracked one).
+    ///
+    /// Peers are tried in order until one serves a copy that verifies.
+    pub async fn fetch_polyp(&mut self, polyp_id: Uuid) -> Result<VerifiedPolyp, ChitinError> {
+        if self.headers.is_empty() {
+            return Err(ChitinError::InvalidState(
+                "No metagraph header tracked; call sync first".to_string(),
+            ));
+        }
+        let mut last_error = ChitinError::NotFound(format!("Polyp {} not found", polyp_id));
+        for peer in self.config.peers.clone() {
+            let params = serde_json::json!({ "polyp_id": polyp_id });
+            let polyp = match self.call::<PolypResult>(&peer, "polyp/get", params).await {
+                Ok(PolypResult { polyp: Some(polyp) }) if polyp.id == polyp_id => polyp,
+                Ok(_) => continue,
+                Err(e) => {
+                    last_error = e;
+                    continue;
+                }
+            };
+            match self.verify(polyp).await {
+                Ok(verified) => return Ok(verified),
+                Err(e) => {
+                    tracing::warn!("Light: polyp {} from {} rejected: {}", polyp_id, peer, e);
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Verify a hardened `polyp` from any source against tracked state,
+    /// fetching its epoch's checkpoint if needed.
+    pub async fn verify(&mut self, polyp: Polyp) -> Result<VerifiedPolyp, ChitinError> {
+        let epoch = polyp.consensus.as_ref().map(|c| c.epoch).ok_or_else(|| {
+            ChitinError::Verification(format!("Polyp {} has no consensus epoch", polyp.id))
+        })?;
+        let checkpoint = self.checkpoint(epoch).await?;
+        let header = self
+            .header_at(epoch)
+            .or_else(|| self.header())
+            .ok_or_else(|| {
+                ChitinError::InvalidState(
+                    "No metagraph header tracked; call sync first".to_string(),
+                )
+            })?;
+        let threshold = self
+            .config
+            .min_attestations
+            .unwrap_or_else(|| majority(header.validators.len()));
+        verify_hardened(polyp, &checkpoint, &header.validators, threshold)
+    }
+
+    /// Keep at most `max_tracked_epochs` of the latest headers and
+    /// checkpoints.
+    fn prune(&mut self) {
+        let Some(latest) = self.header().map(|h| h.epoch) else {
+            return;
+        };
+        let horizon = latest.saturating_sub(self.config.max_tracked_epochs as u64);
+        self.headers = self.headers.split_off(&horizon);
+        self.checkpoints = self.checkpoints.split_off(&horizon);
+    }
+
+    /// Call `method` on `peer` and decode its result.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        peer: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<T, ChitinError> {
+        let response: JsonRpcResponse = self
+            .http
+            .post(peer)
+            .json(&serde_json::json!({ "method": method, "params": params }))
+            .send()
+            .await
+            .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to parse response: {}", e)))?;
+        if !response.success {
+            return Err(ChitinError::Network(
+                response.error.unwrap_or_else(|| "Unknown error".to_string()),
+            ));
+        }
+        let result = response
+            .result
+            .ok_or_else(|| ChitinError::Network("No result in response".to_string()))?;
+        serde_json::from_value(result).map_err(|e| {
+            ChitinError::Serialization(format!("Failed to parse {} result: {}", method, e))
+        })
+    }
+}
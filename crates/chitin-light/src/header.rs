@@ -0,0 +1,108 @@
+// crates/chitin-light/src/header.rs
+//
+// Metagraph headers: the part of a `ReefMetagraph` a light client keeps.
+//
+// Peers serve the full metagraph from `metagraph/snapshot`; the client
+// reduces each to a header and accepts the one a strict majority of peers
+// agree on. The validator set (active Tide and Hybrid nodes, the same set
+// that scores and attests) is what attestation thresholds count against.
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::{NodeType, ReefMetagraph};
+
+/// Summary of one epoch's metagraph.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MetagraphHeader {
+    /// Epoch of the metagraph.
+    pub epoch: u64,
+    /// Block height (if anchored to a chain).
+    pub block: u64,
+    /// Total staked $CTN across all nodes.
+    pub total_stake: u64,
+    /// Total Polyps hardened to date.
+    pub total_hardened_polyps: u64,
+    /// Hotkeys of the active validators, sorted.
+    pub validators: Vec<[u8; 32]>,
+}
+
+impl MetagraphHeader {
+    /// The header of `metagraph`.
+    pub fn from_metagraph(metagraph: &ReefMetagraph) -> Self {
+        let mut validators: Vec<[u8; 32]> = metagraph
+            .nodes
+            .iter()
+            .filter(|n| n.active && matches!(n.node_type, NodeType::Tide | NodeType::Hybrid))
+            .map(|n| n.hotkey)
+            .collect();
+        validators.sort_unstable();
+        validators.dedup();
+        Self {
+            epoch: metagraph.epoch,
+            block: metagraph.block,
+            total_stake: metagraph.total_stake,
+            total_hardened_polyps: metagraph.total_hardened_polyps,
+            validators,
+        }
+    }
+
+    /// The header reported by a strict majority of `reports`, if any.
+    pub fn quorum(reports: &[MetagraphHeader]) -> Option<MetagraphHeader> {
+        crate::quorum(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::NodeInfo;
+    use std::collections::HashMap;
+
+    fn node(uid: u16, node_type: NodeType, active: bool) -> NodeInfo {
+        NodeInfo {
+            uid,
+            hotkey: [uid as u8; 32],
+            coldkey: [0; 32],
+            node_type,
+            stake: 100,
+            trust: 0.0,
+            consensus: 0.0,
+            incentive: 0.0,
+            emission: 0,
+            polyp_count: 0,
+            last_active: 0,
+            axon_addr: String::new(),
+            active,
+        }
+    }
+
+    #[test]
+    fn header_lists_active_validators_only() {
+        let metagraph = ReefMetagraph {
+            epoch: 7,
+            block: 700,
+            nodes: vec![
+                node(3, NodeType::Hybrid, true),
+                node(1, NodeType::Tide, true),
+                node(2, NodeType::Coral, true),
+                node(4, NodeType::Tide, false),
+            ],
+            total_stake: 400,
+            total_hardened_polyps: 12,
+            emission_rate: 0,
+            weights: HashMap::new(),
+            bonds: HashMap::new(),
+            model_versions: Vec::new(),
+        };
+        let header = MetagraphHeader::from_metagraph(&metagraph);
+        assert_eq!(header.epoch, 7);
+        assert_eq!(header.validators, vec![[1; 32], [3; 32]]);
+
+        let stale = MetagraphHeader { epoch: 6, ..header.clone() };
+        assert_eq!(
+            MetagraphHeader::quorum(&[header.clone(), stale.clone(), header.clone()]),
+            Some(header.clone())
+        );
+        assert_eq!(MetagraphHeader::quorum(&[header, stale]), None);
+    }
+}
@@ -0,0 +1,45 @@
+// crates/chitin-light/src/lib.rs
+//
+// chitin-light: a light client for the Reef.
+//
+// Applications that read hardened knowledge (RAG pipelines, explorers)
+// need not run a node to trust what they read. A `LightClient` tracks only
+// two things from a set of peers, each accepted when a strict majority of
+// the answering peers report the same value:
+//
+// - metagraph headers: epoch, block, stake totals, and the active validator
+//   set (not the full weight and bond matrices);
+// - epoch hardening checkpoints: the Merkle root of the Polyps hardened in
+//   each epoch.
+//
+// Individual hardened Polyps are then fetched from any peer on demand and
+// verified locally: the creator's signature, Merkle inclusion against the
+// tracked checkpoint root, and a threshold of validator attestations from
+// the header's validator set. The verification itself is
+// `chitin_core::light`, the same code nodes use, so the crate links
+// chitin-core but none of the node's storage stack.
+
+pub mod checkpoint;
+pub mod client;
+pub mod header;
+pub mod verify;
+
+pub use checkpoint::Checkpoint;
+pub use client::{LightClient, LightConfig};
+pub use header::MetagraphHeader;
+pub use verify::{verify_hardened, VerifiedPolyp};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The value reported by a strict majority of `reports`, if any.
+fn quorum<T: Clone + Eq + Hash>(reports: &[T]) -> Option<T> {
+    let mut votes: HashMap<&T, usize> = HashMap::new();
+    for report in reports {
+        *votes.entry(report).or_default() += 1;
+    }
+    votes
+        .into_iter()
+        .find(|&(_, n)| n * 2 > reports.len())
+        .map(|(value, _)| value.clone())
+}
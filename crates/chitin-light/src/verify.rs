@@ -0,0 +1,156 @@
+// crates/chitin-light/src/verify.rs
+//
+// Local verification of a hardened Polyp fetched from an untrusted peer.
+//
+// A Polyp is accepted when its lineage names the tracked checkpoint root,
+// its Merkle proof includes the leaf recomputed from its own ID and CID,
+// enough distinct validators from the tracked header signed its CID, and
+// (if it is signed) its creator's signature verifies.
+
+use uuid::Uuid;
+
+use chitin_core::light::{self, AttestationTally, SignedAttestation};
+use chitin_core::{ChitinError, HardeningLineage, Polyp};
+
+use crate::checkpoint::Checkpoint;
+
+/// A hardened Polyp that passed verification.
+#[derive(Debug, Clone)]
+pub struct VerifiedPolyp {
+    /// The Polyp.
+    pub polyp: Polyp,
+    /// Epoch it was hardened in.
+    pub epoch: u64,
+    /// Whether it carries a creator signature (unsigned Polyps predate
+    /// signing and are accepted).
+    pub signed: bool,
+    /// Its validator attestations.
+    pub attestations: AttestationTally,
+}
+
+/// Check `lineage` of the Polyp `polyp_id` against `checkpoint`, counting
+/// attestations by `validators`; at least `min_attestations` must be valid.
+pub fn verify_lineage(
+    polyp_id: &Uuid,
+    lineage: &HardeningLineage,
+    checkpoint: &Checkpoint,
+    validators: &[[u8; 32]],
+    min_attestations: usize,
+) -> Result<AttestationTally, ChitinError> {
+    if lineage.merkle_root != checkpoint.merkle_root {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} claims a Merkle root not checkpointed for epoch {}",
+            polyp_id, checkpoint.epoch
+        )));
+    }
+    let leaf = light::hardening_leaf(polyp_id.as_bytes(), &lineage.cid);
+    if !light::verify_inclusion(&leaf, &lineage.merkle_proof, &checkpoint.merkle_root) {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} inclusion proof does not match epoch {} root",
+            polyp_id, checkpoint.epoch
+        )));
+    }
+
+    let attestations: Vec<SignedAttestation> = lineage
+        .attestations
+        .iter()
+        .map(|a| SignedAttestation {
+            validator: &a.validator,
+            epoch: a.epoch,
+            signature: &a.signature,
+        })
+        .collect();
+    let tally =
+        light::tally_attestations(polyp_id.as_bytes(), &lineage.cid, &attestations, validators);
+    if !tally.meets(min_attestations) {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} has {} valid validator attestation(s), {} required",
+            polyp_id, tally.valid, min_attestations
+        )));
+    }
+    Ok(tally)
+}
+
+/// Verify the hardened `polyp` against its epoch's `checkpoint` and the
+/// validator set `validators`.
+pub fn verify_hardened(
+    polyp: Polyp,
+    checkpoint: &Checkpoint,
+    validators: &[[u8; 32]],
+    min_attestations: usize,
+) -> Result<VerifiedPolyp, ChitinError> {
+    let lineage = polyp.hardening.as_ref().ok_or_else(|| {
+        ChitinError::Verification(format!("Polyp {} is not hardened", polyp.id))
+    })?;
+    let epoch = polyp.consensus.as_ref().map(|c| c.epoch);
+    if epoch != Some(checkpoint.epoch) {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} was not hardened in epoch {}",
+            polyp.id, checkpoint.epoch
+        )));
+    }
+    let signed = polyp.signature.is_some();
+    let creator = &polyp.subject.provenance.creator.hotkey;
+    if signed && !polyp.verify_signature(creator).unwrap_or(false) {
+        return Err(ChitinError::Verification(format!(
+            "Polyp {} signature does not verify against its creator hotkey",
+            polyp.id
+        )));
+    }
+    let attestations =
+        verify_lineage(&polyp.id, lineage, checkpoint, validators, min_attestations)?;
+    Ok(VerifiedPolyp {
+        epoch: checkpoint.epoch,
+        polyp,
+        signed,
+        attestations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::crypto::Keypair;
+    use chitin_core::Attestation;
+
+    const CID: &str = "bafyhardened";
+
+    fn attest(key: &Keypair, polyp_id: Uuid, epoch: u64) -> Attestation {
+        let message = light::attestation_message(polyp_id.as_bytes(), CID, epoch);
+        Attestation {
+            validator: key.public_key_bytes(),
+            epoch,
+            polyp_id,
+            cid: CID.to_string(),
+            signature: key.sign(&message),
+        }
+    }
+
+    #[test]
+    fn lineage_needs_the_checkpoint_root_and_enough_attestations() {
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::now_v7()).collect();
+        let leaves: Vec<[u8; 32]> =
+            ids.iter().map(|id| light::hardening_leaf(id.as_bytes(), CID)).collect();
+        let (root, proofs) = light::merkle_tree(&leaves);
+        let checkpoint = Checkpoint { epoch: 4, merkle_root: root, count: 3, beacon: None };
+
+        let keys: Vec<Keypair> = (1u8..=3).map(|i| Keypair::from_secret_bytes(&[i; 32])).collect();
+        let validators: Vec<[u8; 32]> = keys.iter().map(Keypair::public_key_bytes).collect();
+        let lineage = HardeningLineage {
+            cid: CID.to_string(),
+            merkle_proof: proofs[1].clone(),
+            merkle_root: root,
+            attestations: vec![attest(&keys[0], ids[1], 4), attest(&keys[2], ids[1], 4)],
+            anchor_tx: None,
+            hardened_at: chrono::Utc::now(),
+        };
+
+        let tally = verify_lineage(&ids[1], &lineage, &checkpoint, &validators, 2).unwrap();
+        assert_eq!(tally.valid, 2);
+        assert!(verify_lineage(&ids[1], &lineage, &checkpoint, &validators, 3).is_err());
+        // The proof belongs to ids[1] only.
+        assert!(verify_lineage(&ids[0], &lineage, &checkpoint, &validators, 0).is_err());
+        let other = Checkpoint { merkle_root: [9; 32], ..checkpoint };
+        assert!(verify_lineage(&ids[1], &lineage, &other, &validators, 0).is_err());
+    }
+}
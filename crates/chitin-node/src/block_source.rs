@@ -0,0 +1,315 @@
+// crates/chitin-node/src/block_source.rs
+//
+// Block sources for the Chitin Protocol epoch scheduler.
+//
+// The scheduler derives epochs and phases from block height; a `BlockSource`
+// supplies that height. Three sources are available, selected by the
+// `[block_source]` config table:
+//
+// - `timer`: synthetic blocks at a fixed interval (the default, 12s)
+// - `chain_rpc`: the best block of an external chain, polled over JSON-RPC
+//   (Substrate `chain_getHeader` by default, or e.g. `eth_blockNumber`)
+// - `fixed`: a fixed sequence of heights, for tests and replays
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::error::ChitinError;
+
+/// A source of block heights.
+#[async_trait::async_trait]
+pub trait BlockSource: Send {
+    /// Describe the source for logs.
+    fn describe(&self) -> String;
+
+    /// Wait for the next block and return its height.
+    ///
+    /// Heights increase but may skip blocks. Returns `None` once the source
+    /// has no more blocks.
+    async fn next_block(&mut self) -> Result<Option<u64>, ChitinError>;
+
+    /// Continue after `block`, restored from a previous run. Sources whose
+    /// heights come from elsewhere ignore it.
+    fn resume_from(&mut self, _block: u64) {}
+}
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Which block source drives the scheduler (`[block_source]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BlockSourceConfig {
+    /// Synthetic blocks on a timer.
+    Timer {
+        /// Seconds between blocks.
+        #[serde(default = "default_block_time_secs")]
+        block_time_secs: u64,
+    },
+    /// Best block of an external chain, polled over JSON-RPC.
+    ChainRpc {
+        /// JSON-RPC endpoint of a chain node (e.g. "http://127.0.0.1:9933").
+        url: String,
+        /// Method returning the best block: a header with a `number` field
+        /// or a bare block number.
+        #[serde(default = "default_chain_rpc_method")]
+        method: String,
+        /// Seconds between polls.
+        #[serde(default = "default_poll_interval_secs")]
+        poll_interval_secs: u64,
+    },
+    /// A fixed sequence of heights.
+    Fixed {
+        /// Heights to produce, in increasing order.
+        blocks: Vec<u64>,
+        /// Milliseconds between blocks.
+        #[serde(default)]
+        block_time_ms: u64,
+    },
+}
+
+fn default_block_time_secs() -> u64 {
+    12
+}
+
+fn default_chain_rpc_method() -> String {
+    "chain_getHeader".to_string()
+}
+
+fn default_poll_interval_secs() -> u64 {
+    6
+}
+
+impl Default for BlockSourceConfig {
+    fn default() -> Self {
+        Self::Timer {
+            block_time_secs: default_block_time_secs(),
+        }
+    }
+}
+
+impl BlockSourceConfig {
+    /// Build the configured source, validating its settings.
+    pub fn build(&self) -> Result<Box<dyn BlockSource>, ChitinError> {
+        match self {
+            Self::Timer { block_time_secs } => {
+                if *block_time_secs == 0 {
+                    return Err(ChitinError::InvalidState(
+                        "block_time_secs must be positive".to_string(),
+                    ));
+                }
+                Ok(Box::new(TimerBlockSource::new(Duration::from_secs(
+                    *block_time_secs,
+                ))))
+            }
+            Self::ChainRpc {
+                url,
+                method,
+                poll_interval_secs,
+            } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(ChitinError::InvalidState(format!(
+                        "chain RPC url must be http(s): {:?}",
+                        url
+                    )));
+                }
+                if *poll_interval_secs == 0 {
+                    return Err(ChitinError::InvalidState(
+                        "poll_interval_secs must be positive".to_string(),
+                    ));
+                }
+                Ok(Box::new(ChainRpcBlockSource::new(
+                    url,
+                    method,
+                    Duration::from_secs(*poll_interval_secs),
+                )))
+            }
+            Self::Fixed {
+                blocks,
+                block_time_ms,
+            } => {
+                if blocks.windows(2).any(|w| w[1] <= w[0]) {
+                    return Err(ChitinError::InvalidState(
+                        "fixed blocks must be strictly increasing".to_string(),
+                    ));
+                }
+                Ok(Box::new(FixedBlockSource::new(
+                    blocks.clone(),
+                    Duration::from_millis(*block_time_ms),
+                )))
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sources
+// ---------------------------------------------------------------------------
+
+/// Synthetic blocks, one per interval, counting up from 1.
+pub struct TimerBlockSource {
+    interval: Duration,
+    height: u64,
+}
+
+impl TimerBlockSource {
+    /// Create a source producing a block every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            height: 0,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for TimerBlockSource {
+    fn describe(&self) -> String {
+        format!("timer ({}s blocks)", self.interval.as_secs_f64())
+    }
+
+    fn resume_from(&mut self, block: u64) {
+        self.height = self.height.max(block);
+    }
+
+    async fn next_block(&mut self) -> Result<Option<u64>, ChitinError> {
+        tokio::time::sleep(self.interval).await;
+        self.height += 1;
+        Ok(Some(self.height))
+    }
+}
+
+/// The best block of an external chain, polled over JSON-RPC 2.0.
+pub struct ChainRpcBlockSource {
+    client: reqwest::Client,
+    url: String,
+    method: String,
+    poll_interval: Duration,
+    /// Last height returned.
+    last: Option<u64>,
+}
+
+impl ChainRpcBlockSource {
+    /// Create a source polling `method` at `url` every `poll_interval`.
+    pub fn new(url: &str, method: &str, poll_interval: Duration) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.to_string(),
+            method: method.to_string(),
+            poll_interval,
+            last: None,
+        }
+    }
+
+    /// Fetch the chain's current best block height.
+    async fn best_block(&self) -> Result<u64, ChitinError> {
+        #[derive(Deserialize)]
+        struct RpcResponse {
+            #[serde(default)]
+            result: Option<serde_json::Value>,
+            #[serde(default)]
+            error: Option<serde_json::Value>,
+        }
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": self.method,
+            "params": [],
+        });
+        let response: RpcResponse = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to parse response: {}", e)))?;
+
+        if let Some(error) = response.error {
+            return Err(ChitinError::Network(format!("{} failed: {}", self.method, error)));
+        }
+        match response.result {
+            Some(result) => parse_block_number(&result),
+            None => Err(ChitinError::Network(format!("{} returned no result", self.method))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for ChainRpcBlockSource {
+    fn describe(&self) -> String {
+        format!("chain RPC {} ({})", self.url, self.method)
+    }
+
+    async fn next_block(&mut self) -> Result<Option<u64>, ChitinError> {
+        loop {
+            let height = self.best_block().await?;
+            if self.last.is_none_or(|last| height > last) {
+                self.last = Some(height);
+                return Ok(Some(height));
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+/// Read a block number from a header's `number` field or a bare number,
+/// either as an integer or a hex string ("0x1a2b").
+fn parse_block_number(value: &serde_json::Value) -> Result<u64, ChitinError> {
+    let number = match value.get("number") {
+        Some(number) => number,
+        None => value,
+    };
+    if let Some(n) = number.as_u64() {
+        return Ok(n);
+    }
+    let parsed = match number.as_str() {
+        Some(s) => match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+        None => None,
+    };
+    parsed.ok_or_else(|| ChitinError::Serialization(format!("Invalid block number: {}", value)))
+}
+
+/// A fixed sequence of heights, one per interval.
+pub struct FixedBlockSource {
+    blocks: VecDeque<u64>,
+    interval: Duration,
+}
+
+impl FixedBlockSource {
+    /// Create a source producing `blocks` in order, `interval` apart.
+    pub fn new(blocks: Vec<u64>, interval: Duration) -> Self {
+        Self {
+            blocks: blocks.into(),
+            interval,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockSource for FixedBlockSource {
+    fn describe(&self) -> String {
+        format!("fixed sequence ({} blocks)", self.blocks.len())
+    }
+
+    async fn next_block(&mut self) -> Result<Option<u64>, ChitinError> {
+        match self.blocks.pop_front() {
+            Some(block) => {
+                if !self.interval.is_zero() {
+                    tokio::time::sleep(self.interval).await;
+                }
+                Ok(Some(block))
+            }
+            None => Ok(None),
+        }
+    }
+}
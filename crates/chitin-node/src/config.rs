@@ -0,0 +1,579 @@
+// crates/chitin-node/src/config.rs
+//
+// Runtime configuration for the Chitin Protocol daemon.
+// Loaded from a TOML file or populated with sensible defaults. The log level,
+// peer list, and sync interval can be reloaded while running (`reload`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use chitin_consensus::dedup::DedupConfig;
+use chitin_consensus::assignment::AssignmentConfig;
+use chitin_consensus::moderation::ModerationConfig;
+use chitin_consensus::genesis::Genesis;
+use chitin_core::error::ChitinError;
+use chitin_drift::molting::SuccessorPolicy;
+use chitin_drift::monitor::DriftMonitorConfig;
+use chitin_drift::versioning::{ModelVersion, VersionRegistry};
+use chitin_reputation::decay::DecayConfig;
+use chitin_reputation::genesis::{GenesisTrust, GenesisValidator};
+use chitin_reputation::snapshot::EpochCheckpoint;
+use chitin_reputation::taxonomy::{DomainTaxonomy, ZoneDefinition};
+use chitin_rpc::handlers::query::Reranking;
+use chitin_rpc::{QueryCacheConfig, RequestAuthConfig};
+use chitin_store::{AdmissionConfig, Durability, ShardSet};
+use chitin_sync::priority::{SyncPriority, SyncPriorityWeights};
+use chitin_sync::throttle::{SyncThrottle, ThrottleConfig};
+
+use crate::block_source::{BlockSource, BlockSourceConfig};
+use crate::embedding::{EmbeddingConfig, ProviderSet};
+use crate::ingestion::{IngestionConfig, Ingester};
+use crate::metrics::MetricsConfig;
+use crate::network::{NetworkOverrides, NetworkProfile};
+use crate::pruning::PruningConfig;
+use crate::replication::ReplicationConfig;
+use crate::rerank::RerankConfig;
+use crate::scrub::ScrubConfig;
+use crate::seed::SeedConfig;
+use crate::snapshot::SnapshotConfig;
+use crate::telemetry::OtlpConfig;
+use crate::validator::{Validator, ValidatorConfig};
+use crate::webhooks::WebhookConfig;
+
+/// Runtime configuration for the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    /// Node type: "coral", "tide", "hybrid", or "seed".
+    #[serde(default = "default_node_type")]
+    pub node_type: String,
+
+    /// Directory for local data storage (RocksDB, keys, etc.).
+    #[serde(default = "default_data_dir")]
+    pub data_dir: String,
+
+    /// Host address for the RPC server. `admin/*` methods are served only
+    /// to callers on this host whatever the address.
+    #[serde(default = "default_rpc_host")]
+    pub rpc_host: String,
+
+    /// Port for the RPC server.
+    #[serde(default = "default_rpc_port")]
+    pub rpc_port: u16,
+
+    /// Port for P2P communication.
+    #[serde(default = "default_p2p_port")]
+    pub p2p_port: u16,
+
+    /// URL of the IPFS API endpoint.
+    #[serde(default = "default_ipfs_api_url")]
+    pub ipfs_api_url: String,
+
+    /// Log level or filter directives ("trace", "debug", "info", "warn",
+    /// "error", or e.g. "info,chitin_sync=debug"). `RUST_LOG` overrides it.
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+
+    /// Peer URLs for HTTP relay (e.g., ["http://10.0.0.2:50051"]).
+    /// When empty (default), all peer networking is disabled.
+    #[serde(default)]
+    pub peers: Vec<String>,
+
+    /// Seconds between pull-sync rounds.
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+
+    /// This node's publicly reachable URL (e.g., "http://10.0.0.1:50051").
+    /// Used in peer announcements so other nodes know how to reach us.
+    #[serde(default)]
+    pub self_url: Option<String>,
+
+    /// Path to the hotkey secret file (hex-encoded ed25519 signing key).
+    #[serde(default = "default_hotkey_path")]
+    pub hotkey_path: String,
+
+    /// Path to the coldkey public key file (hex-encoded ed25519 public key).
+    #[serde(default = "default_coldkey_pub_path")]
+    pub coldkey_pub_path: String,
+
+    /// Number of blocks per epoch (default 360, ~1 hour at 10s/block).
+    #[serde(default = "default_blocks_per_epoch")]
+    pub blocks_per_epoch: u64,
+
+    /// Retain all history: every consensus result, checkpoint, and molted
+    /// predecessor is kept, and `history/*` queries are answered for any
+    /// epoch. `--archival` turns it on.
+    #[serde(default)]
+    pub archival: bool,
+
+    /// Where block heights come from (`[block_source]` table). Defaults to
+    /// synthetic 12-second blocks.
+    #[serde(default)]
+    pub block_source: BlockSourceConfig,
+
+    /// Seconds between saves of epoch and consensus runtime state (0 saves
+    /// only at shutdown).
+    #[serde(default = "default_state_save_interval_secs")]
+    pub state_save_interval_secs: u64,
+
+    /// Trust half-life in epochs: inactive trust edges halve every N epochs.
+    #[serde(default = "default_trust_half_life_epochs")]
+    pub trust_half_life_epochs: u64,
+
+    /// Per-domain trust half-life overrides (domain_id -> epochs).
+    #[serde(default)]
+    pub trust_domain_half_lives: HashMap<String, u64>,
+
+    /// Pre-trusted node UIDs per domain, used to seed personalized OpenRank
+    /// ("global" applies to domains without their own entry).
+    #[serde(default)]
+    pub trust_pre_trusted: HashMap<String, Vec<u16>>,
+
+    /// Genesis validators (`[[genesis_trust]]` tables) seeded as mutually
+    /// trusting once their DIDs appear in the metagraph.
+    #[serde(default)]
+    pub genesis_trust: Vec<GenesisValidator>,
+
+    /// Genesis file (`genesis.json`) the node starts from: its nodes, stakes,
+    /// trust seeds, and economics replace the empty initial state, and its
+    /// trust seeds replace `genesis_trust`.
+    #[serde(default)]
+    pub genesis_file: Option<String>,
+
+    /// Expected hash of the genesis file (hex, as printed by
+    /// `chitin genesis verify`); startup fails on a different file.
+    #[serde(default)]
+    pub genesis_hash: Option<String>,
+
+    /// Reputation snapshot hashes pinned per epoch
+    /// (`[[pinned_reputation_checkpoints]]` tables). `admin/reputation/import`
+    /// accepts a snapshot for an epoch this node has no checkpoint for only
+    /// if its hash is pinned here.
+    #[serde(default)]
+    pub pinned_reputation_checkpoints: Vec<EpochCheckpoint>,
+
+    /// Minimum cosine similarity for embedding-based domain classification.
+    #[serde(default = "default_domain_confidence_threshold")]
+    pub domain_confidence_threshold: f64,
+
+    /// Weight of creator trust in search ranking, in [0.0, 1.0].
+    #[serde(default = "default_search_trust_weight")]
+    pub search_trust_weight: f64,
+
+    /// Maximal-marginal-relevance lambda for search, in [0.0, 1.0]: 1.0
+    /// ranks by relevance alone, lower values favour results unlike those
+    /// already chosen. Unset leaves results undiversified unless a query
+    /// sets its own.
+    #[serde(default)]
+    pub search_mmr_lambda: Option<f64>,
+
+    /// Reef Zone taxonomy (`[[zones]]` tables). Empty uses the built-in zones.
+    #[serde(default)]
+    pub zones: Vec<ZoneDefinition>,
+
+    /// Reef Zones whose polyps sync first (descendant zones included).
+    #[serde(default)]
+    pub sync_zones: Vec<String>,
+
+    /// Fetch-order weights for pull-sync (`[sync_priority]` table).
+    #[serde(default)]
+    pub sync_priority: SyncPriorityWeights,
+
+    /// Total number of shards the Reef is split into.
+    #[serde(default = "default_num_shards")]
+    pub num_shards: u16,
+
+    /// Shards this node syncs and stores. Empty holds every shard.
+    #[serde(default)]
+    pub assigned_shards: Vec<u16>,
+
+    /// Shards after each assigned shard (on the ring) also held as replicas.
+    #[serde(default)]
+    pub shard_replicas: u16,
+
+    /// Ingestion limits shared by pull-sync and gossip (`[sync_throttle]` table).
+    #[serde(default)]
+    pub sync_throttle: ThrottleConfig,
+
+    /// Write admission from store health, applied to submissions, gossip,
+    /// and pull-sync (`[admission]` table).
+    #[serde(default)]
+    pub admission: AdmissionConfig,
+
+    /// Embedding model versions and their deprecation schedules
+    /// (`[[model_versions]]` entries).
+    #[serde(default)]
+    pub model_versions: Vec<ModelVersion>,
+
+    /// Whether molted successors inherit consensus ("inherit") or are
+    /// re-validated ("revalidate").
+    #[serde(default)]
+    pub molt_successor_policy: SuccessorPolicy,
+
+    /// Drift monitoring of hardened polyps (`[drift_monitor]` table).
+    #[serde(default)]
+    pub drift_monitor: DriftMonitorConfig,
+
+    /// Near-duplicate detection against hardened polyps (`[dedup]` table).
+    #[serde(default)]
+    pub dedup: DedupConfig,
+
+    /// Content moderation at submission and before hardening
+    /// (`[moderation]` table).
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+
+    /// Per-epoch assignment of polyps to the validators scoring them
+    /// (`[task_assignment]` table).
+    #[serde(default)]
+    pub task_assignment: AssignmentConfig,
+
+    /// Embedding of submitted content (`[embedding]` table).
+    #[serde(default)]
+    pub embedding: EmbeddingConfig,
+
+    /// Cross-encoder reranking of search results (`[rerank]` table).
+    #[serde(default)]
+    pub rerank: RerankConfig,
+
+    /// LRU cache of search responses (`[query_cache]` table).
+    #[serde(default)]
+    pub query_cache: QueryCacheConfig,
+
+    /// Key-role checks on coldkey- and hotkey-signed RPC methods
+    /// (`[request_auth]` table).
+    #[serde(default)]
+    pub request_auth: RequestAuthConfig,
+
+    /// URL fetching and chunking for `polyp/ingest_url` (`[ingestion]` table).
+    #[serde(default)]
+    pub ingestion: IngestionConfig,
+    /// Active validation of Coral nodes by Tide nodes (`[validator]` table).
+    #[serde(default)]
+    pub validator: ValidatorConfig,
+
+    /// Prometheus scrape endpoint (`[metrics]` table).
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// OpenTelemetry trace export (`[otlp]` table).
+    #[serde(default)]
+    pub otlp: OtlpConfig,
+
+    /// Seed node refresh settings (`[seed]` table).
+    #[serde(default)]
+    pub seed: SeedConfig,
+
+    /// History retention on non-archival nodes (`[pruning]` table).
+    #[serde(default)]
+    pub pruning: PruningConfig,
+
+    /// Storage integrity scrubbing (`[scrub]` table).
+    #[serde(default)]
+    pub scrub: ScrubConfig,
+
+    /// When store writes reach stable storage (`[durability]` table).
+    #[serde(default)]
+    pub durability: Durability,
+
+    /// Event webhooks (`[webhooks]` table).
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+
+    /// Hot standby replication (`[replication]` table).
+    #[serde(default)]
+    pub replication: ReplicationConfig,
+
+    /// Bootstrap from a signed snapshot (`[snapshot]` table).
+    #[serde(default)]
+    pub snapshot: SnapshotConfig,
+
+    /// Network profile to run against ("devnet", "testnet", "mainnet", or a
+    /// `[networks.<name>]` table). `--network` overrides it. Unset runs
+    /// without a profile.
+    #[serde(default)]
+    pub network: Option<String>,
+
+    /// Overrides for built-in network profiles and custom network
+    /// definitions (`[networks.<name>]` tables).
+    #[serde(default)]
+    pub networks: HashMap<String, NetworkOverrides>,
+}
+
+fn default_node_type() -> String {
+    "hybrid".to_string()
+}
+
+fn default_data_dir() -> String {
+    "~/.chitin/data".to_string()
+}
+
+fn default_rpc_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_rpc_port() -> u16 {
+    50051
+}
+
+fn default_p2p_port() -> u16 {
+    4001
+}
+
+fn default_ipfs_api_url() -> String {
+    "http://127.0.0.1:5001".to_string()
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_sync_interval_secs() -> u64 {
+    30
+}
+
+fn default_hotkey_path() -> String {
+    "~/.chitin/keys/hotkey.secret".to_string()
+}
+
+fn default_coldkey_pub_path() -> String {
+    "~/.chitin/keys/coldkey.pub".to_string()
+}
+
+fn default_blocks_per_epoch() -> u64 {
+    360
+}
+
+fn default_state_save_interval_secs() -> u64 {
+    60
+}
+
+fn default_trust_half_life_epochs() -> u64 {
+    chitin_reputation::decay::DEFAULT_HALF_LIFE_EPOCHS
+}
+
+fn default_domain_confidence_threshold() -> f64 {
+    chitin_reputation::centroid::DEFAULT_CONFIDENCE_THRESHOLD
+}
+
+fn default_search_trust_weight() -> f64 {
+    chitin_rpc::handlers::query::DEFAULT_SEARCH_TRUST_WEIGHT
+}
+
+fn default_num_shards() -> u16 {
+    1
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            node_type: default_node_type(),
+            data_dir: default_data_dir(),
+            rpc_host: default_rpc_host(),
+            rpc_port: default_rpc_port(),
+            p2p_port: default_p2p_port(),
+            ipfs_api_url: default_ipfs_api_url(),
+            log_level: default_log_level(),
+            peers: Vec::new(),
+            sync_interval_secs: default_sync_interval_secs(),
+            self_url: None,
+            hotkey_path: default_hotkey_path(),
+            coldkey_pub_path: default_coldkey_pub_path(),
+            blocks_per_epoch: default_blocks_per_epoch(),
+            archival: false,
+            block_source: BlockSourceConfig::default(),
+            state_save_interval_secs: default_state_save_interval_secs(),
+            trust_half_life_epochs: default_trust_half_life_epochs(),
+            trust_domain_half_lives: HashMap::new(),
+            trust_pre_trusted: HashMap::new(),
+            genesis_trust: Vec::new(),
+            genesis_file: None,
+            genesis_hash: None,
+            pinned_reputation_checkpoints: Vec::new(),
+            domain_confidence_threshold: default_domain_confidence_threshold(),
+            search_trust_weight: default_search_trust_weight(),
+            search_mmr_lambda: None,
+            zones: Vec::new(),
+            sync_zones: Vec::new(),
+            sync_priority: SyncPriorityWeights::default(),
+            num_shards: default_num_shards(),
+            assigned_shards: Vec::new(),
+            shard_replicas: 0,
+            sync_throttle: ThrottleConfig::default(),
+            admission: AdmissionConfig::default(),
+            model_versions: Vec::new(),
+            molt_successor_policy: SuccessorPolicy::default(),
+            drift_monitor: DriftMonitorConfig::default(),
+            dedup: DedupConfig::default(),
+            moderation: ModerationConfig::default(),
+            task_assignment: AssignmentConfig::default(),
+            embedding: EmbeddingConfig::default(),
+            rerank: RerankConfig::default(),
+            query_cache: QueryCacheConfig::default(),
+            request_auth: RequestAuthConfig::default(),
+            ingestion: IngestionConfig::default(),
+            validator: ValidatorConfig::default(),
+            metrics: MetricsConfig::default(),
+            otlp: OtlpConfig::default(),
+            seed: SeedConfig::default(),
+            pruning: PruningConfig::default(),
+            scrub: ScrubConfig::default(),
+            durability: Durability::default(),
+            webhooks: WebhookConfig::default(),
+            replication: ReplicationConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            network: None,
+            networks: HashMap::new(),
+        }
+    }
+}
+
+impl DaemonConfig {
+    /// Build the trust decay configuration from the flat config fields.
+    pub fn decay_config(&self) -> DecayConfig {
+        DecayConfig {
+            default_half_life_epochs: self.trust_half_life_epochs,
+            domain_half_lives: self.trust_domain_half_lives.clone(),
+        }
+    }
+
+    /// Build the genesis trust configuration, validating each entry.
+    pub fn genesis(&self) -> Result<GenesisTrust, ChitinError> {
+        let genesis = GenesisTrust::new(self.genesis_trust.clone());
+        genesis.validate()?;
+        Ok(genesis)
+    }
+
+    /// Load the genesis file at `path`, checking it against the pinned
+    /// `genesis_hash`, the selected network's ID, and the configured model
+    /// versions.
+    pub fn load_genesis(
+        &self,
+        path: &str,
+        network: Option<&NetworkProfile>,
+    ) -> Result<Genesis, ChitinError> {
+        let genesis = Genesis::load(path)?;
+        genesis.validate()?;
+        let hash = genesis.hash()?;
+        if let Some(pinned) = &self.genesis_hash {
+            if !pinned.eq_ignore_ascii_case(&hash) {
+                return Err(ChitinError::InvalidState(format!(
+                    "genesis hash {} does not match pinned genesis_hash {}",
+                    hash, pinned
+                )));
+            }
+        }
+        if let Some(network) = network {
+            if network.network_id != genesis.network_id {
+                return Err(ChitinError::InvalidState(format!(
+                    "genesis is for network {:?}, not {:?}",
+                    genesis.network_id, network.network_id
+                )));
+            }
+        }
+        genesis.verify_model_registry(&self.model_registry()?)?;
+        Ok(genesis)
+    }
+
+    /// Build the Reef Zone taxonomy from the configured zones.
+    pub fn taxonomy(&self) -> Result<DomainTaxonomy, ChitinError> {
+        if self.zones.is_empty() {
+            Ok(DomainTaxonomy::default())
+        } else {
+            DomainTaxonomy::from_zones(&self.zones)
+        }
+    }
+
+    /// Build the pull-sync fetch prioritization from the configured weights
+    /// and zone subscriptions.
+    pub fn sync_priority(&self) -> SyncPriority {
+        SyncPriority::new(self.sync_priority.clone(), self.sync_zones.clone())
+    }
+
+    /// Build the set of shards this node holds, validating the assignment,
+    /// with the zones' replication factors.
+    pub fn shard_set(&self) -> Result<ShardSet, ChitinError> {
+        Ok(
+            ShardSet::new(self.num_shards, &self.assigned_shards, self.shard_replicas)?
+                .with_zone_replication(self.taxonomy()?.replication_factors()),
+        )
+    }
+
+    /// Build the ingestion throttle, validating its limits.
+    pub fn sync_throttle(&self) -> Result<SyncThrottle, ChitinError> {
+        SyncThrottle::new(self.sync_throttle.clone())
+    }
+
+    /// Build the model version registry, validating each configured version.
+    pub fn model_registry(&self) -> Result<VersionRegistry, ChitinError> {
+        VersionRegistry::from_versions(self.model_versions.clone())
+    }
+
+    /// Build the embedding providers, validating the pool settings.
+    pub fn embedding_providers(&self) -> Result<ProviderSet, ChitinError> {
+        self.embedding.providers()
+    }
+
+    /// Build the search rerank stage, validating its settings (`None` if no
+    /// reranker is configured).
+    pub fn reranking(&self) -> Result<Option<Reranking>, ChitinError> {
+        self.rerank.reranking()
+    }
+
+    /// Build the URL ingester, validating its settings.
+    pub fn ingester(&self) -> Result<Ingester, ChitinError> {
+        Ingester::new(self.ingestion.clone())
+    }
+
+    /// Build the Coral validator, validating its settings.
+    pub fn validator(&self) -> Result<Validator, ChitinError> {
+        Validator::new(self.validator.clone())
+    }
+
+    /// Build the block source driving the epoch scheduler, validating its
+    /// settings.
+    pub fn block_source(&self) -> Result<Box<dyn BlockSource>, ChitinError> {
+        self.block_source.build()
+    }
+
+    /// Resolve the selected network profile, if any.
+    pub fn network_profile(&self) -> Result<Option<NetworkProfile>, ChitinError> {
+        match &self.network {
+            Some(name) => NetworkProfile::resolve(name, &self.networks).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Apply the selected network's defaults: move `data_dir` into the
+    /// network's subdirectory, add its bootstrap peers, and seed its genesis
+    /// trust when none is configured. Call once on a freshly loaded config.
+    pub fn apply_network(&mut self) -> Result<Option<NetworkProfile>, ChitinError> {
+        let profile = match self.network_profile()? {
+            Some(profile) => profile,
+            None => return Ok(None),
+        };
+        self.data_dir = format!(
+            "{}/{}",
+            self.data_dir.trim_end_matches('/'),
+            profile.data_dir_suffix
+        );
+        for peer in &profile.bootstrap_peers {
+            if !self.peers.contains(peer) && self.self_url.as_ref() != Some(peer) {
+                self.peers.push(peer.clone());
+            }
+        }
+        if self.genesis_trust.is_empty() {
+            self.genesis_trust = profile.genesis_trust.clone();
+        }
+        Ok(Some(profile))
+    }
+
+    /// Load configuration from a TOML file at the given path.
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let config: DaemonConfig = toml::from_str(&contents)?;
+        Ok(config)
+    }
+}
@@ -0,0 +1,474 @@
+// crates/chitin-node/src/consensus_runner.rs
+//
+// Epoch boundary consensus execution for the Chitin Protocol daemon.
+//
+// Called by TideNode at each EpochBoundary event. Reads the weight and bond
+// matrices from shared state, runs Yuma-Semantic Consensus, stores the result,
+// updates bonds, identifies approved polyps, and triggers hardening.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chitin_consensus::beacon;
+use chitin_consensus::history::ConsensusRecord;
+use chitin_consensus::yuma::yuma_semantic_consensus;
+use chitin_core::consensus::ConsensusMetadata;
+use chitin_core::traits::PolypStore;
+use chitin_core::PolypState;
+use chitin_drift::detection::{CachedEmbeddingModel, LocalEmbeddingModel};
+use chitin_drift::molting::{MoltControl, MoltingOrchestrator};
+use chitin_economics::slashing::{compute_penalty, SlashCondition};
+use chitin_reputation::domain_store::GLOBAL_DOMAIN;
+use chitin_reputation::sybil::{detect_sybil_clusters, flagged_uids, NodeProfile, SybilConfig};
+use chitin_store::RocksStore;
+
+use crate::drift_monitor::EMBEDDING_DIMENSIONS;
+use crate::durability;
+use crate::gossip;
+use crate::hardening_pipeline;
+use crate::shared::DaemonSharedState;
+use crate::webhooks::WebhookEvent;
+
+/// Consensus weight threshold: polyps with consensus_weight above this are approved.
+const APPROVAL_THRESHOLD: f64 = 0.3;
+
+/// Run epoch consensus at an epoch boundary.
+///
+/// Steps:
+/// 0. Decay every domain trust matrix to the current epoch and seed genesis
+///    trust for genesis validators present in the metagraph
+///    (Step 0b: finalize the previous epoch's randomness beacon)
+/// 1. Read weight and bond matrices from shared state
+/// 2. Gather stakes (Phase 4: equal stake=100 for all validators)
+///    (Step 2b: flag Sybil clusters, zero their stake, report slashes)
+/// 3. Run yuma_semantic_consensus
+/// 4. Store ConsensusResult in shared state and in the consensus history
+/// 5. Update bond matrix with result bonds
+/// 6. Identify approved polyps (consensus_weight > threshold)
+/// 7. Transition approved polyps: UnderReview -> Approved
+/// 8. Trigger hardening pipeline for approved polyps
+/// 9. Update per-zone and global trust from validator agreement, persist, and
+///    record the epoch's reputation checkpoint
+/// 10. Update metagraph with new epoch state
+/// 11. Molt polyps embedded under deprecated model versions
+///
+/// Steps 1-10 are skipped when no weights were submitted; the others run,
+/// and the `EpochFinalized` webhook is sent, every epoch.
+pub async fn run_epoch_consensus(
+    shared: &DaemonSharedState,
+    store: &Arc<RocksStore>,
+    epoch: u64,
+) -> Result<(), String> {
+    // Step 0: Materialize trust decay up to this epoch (runs even when no
+    // weights were submitted, so idle epochs still erode stale trust), then
+    // seed genesis trust for newly registered genesis validators, and
+    // persist both
+    {
+        let identities = shared.identities.read().await;
+        let mut ts = shared.trust_store.write().await;
+        let pruned = ts.advance_epoch(epoch);
+        if pruned > 0 {
+            tracing::debug!("Epoch {}: Pruned {} decayed trust edges", epoch, pruned);
+        }
+        let seeded = ts.seed_genesis(&identities, epoch);
+        if seeded > 0 {
+            tracing::info!("Epoch {}: Seeded {} genesis trust edges", epoch, seeded);
+        }
+        if let Err(e) = ts.persist() {
+            tracing::warn!("Epoch {}: Failed to persist trust store: {}", epoch, e);
+        }
+    }
+
+    // Step 0b: Finalize the previous epoch's randomness beacon into this
+    // epoch's hardening checkpoint, where it seeds scoring assignments.
+    if epoch > 0 {
+        match beacon::finalize(store, epoch - 1, epoch) {
+            Ok(Some(value)) => tracing::info!(
+                "Epoch {}: Beacon {} from epoch {} contributions",
+                epoch,
+                hex::encode(value),
+                epoch - 1
+            ),
+            Ok(None) => tracing::debug!("Epoch {}: No beacon reveals in epoch {}", epoch, epoch - 1),
+            Err(e) => tracing::warn!("Epoch {}: Failed to finalize the beacon: {}", epoch, e),
+        }
+    }
+
+    // Steps 1-10 need weights; an epoch without any still molts below.
+    let run = run_consensus(shared, store, epoch).await?;
+
+    // Step 11: Molt polyps embedded under deprecated model versions. Under
+    // the inherit policy, successors of approved polyps skip re-validation
+    // and go straight to hardening. Operator-started migrations run
+    // alongside scheduled ones, and nothing molts while the operator has
+    // molting paused. Content already embedded under the target model is
+    // read from the embedding cache.
+    let control = MoltControl::load(store).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load molt controls: {}", e);
+        MoltControl::default()
+    });
+    let tasks = if control.paused {
+        Vec::new()
+    } else {
+        control.tasks(shared.model_registry.read().await.molt_tasks(epoch))
+    };
+    let orchestrator = MoltingOrchestrator::new();
+    for task in tasks {
+        let model = LocalEmbeddingModel::new(&task.to_model, EMBEDDING_DIMENSIONS);
+        let limit = control.batch_size();
+        let molted = match &shared.embedding_cache {
+            Some(cache) => {
+                let model = CachedEmbeddingModel::new(model, EMBEDDING_DIMENSIONS, cache);
+                orchestrator
+                    .molt_batch(store, &task, &model, shared.molt_policy, epoch, limit)
+                    .await
+            }
+            None => {
+                orchestrator
+                    .molt_batch(store, &task, &model, shared.molt_policy, epoch, limit)
+                    .await
+            }
+        };
+        let records = match molted {
+            Ok(records) => records,
+            Err(e) => {
+                tracing::warn!("Epoch {}: Failed to molt {}: {}", epoch, task.from_model, e);
+                continue;
+            }
+        };
+        if records.is_empty() {
+            continue;
+        }
+        tracing::info!(
+            "Epoch {}: Molted {} polyps from {} v{} to {} v{} (deadline {:?})",
+            epoch,
+            records.len(),
+            task.from_model,
+            task.from_version,
+            task.to_model,
+            task.to_version,
+            task.deadline_epoch
+        );
+
+        let mut inherited = Vec::new();
+        for record in &records {
+            if let Ok(Some(predecessor)) = store.get_polyp(&record.predecessor_id).await {
+                gossip::announce_state_change(shared, store, &predecessor, epoch);
+            }
+            if record.inherited {
+                if let Ok(Some(successor)) = store.get_polyp(&record.successor_id).await {
+                    inherited.push(successor);
+                }
+            }
+        }
+        if !inherited.is_empty() {
+            let hardened =
+                hardening_pipeline::harden_approved_polyps(shared, store, &inherited, epoch)
+                    .await;
+            if let Err(e) = hardened {
+                tracing::error!("Hardening of molted successors failed: {}", e);
+            }
+        }
+    }
+
+    tracing::info!("Epoch {}: Consensus pipeline complete", epoch);
+    let consensus_ran = run.is_some();
+    let run = run.unwrap_or_default();
+    shared.notify_webhooks(WebhookEvent::EpochFinalized {
+        epoch,
+        consensus_ran,
+        validators: run.validators,
+        corals: run.corals,
+        approved: run.approved,
+    });
+    Ok(())
+}
+
+/// Sizes of an epoch's consensus run.
+#[derive(Default)]
+struct ConsensusRun {
+    validators: usize,
+    corals: usize,
+    approved: usize,
+}
+
+/// Steps 1-10 of `run_epoch_consensus`. Returns `None`, having changed
+/// nothing, when no weights were submitted.
+async fn run_consensus(
+    shared: &DaemonSharedState,
+    store: &Arc<RocksStore>,
+    epoch: u64,
+) -> Result<Option<ConsensusRun>, String> {
+    // Step 1: Read weight and bond matrices
+    let weights;
+    let prev_bonds;
+    let n_validators;
+    let n_corals;
+
+    {
+        let wm = shared.weight_matrix.read().await;
+        weights = wm.weights.clone();
+        n_validators = weights.len();
+        n_corals = if n_validators > 0 { weights[0].len() } else { 0 };
+    }
+
+    if n_validators == 0 || n_corals == 0 {
+        tracing::info!("Epoch {}: No weights submitted, skipping consensus", epoch);
+        return Ok(None);
+    }
+
+    {
+        let bm = shared.bond_matrix.read().await;
+        // If bond matrix dimensions don't match, use zeros
+        if bm.bonds.len() == n_validators
+            && bm.bonds.first().is_none_or(|r| r.len() == n_corals)
+        {
+            prev_bonds = bm.bonds.clone();
+        } else {
+            prev_bonds = vec![vec![0.0; n_corals]; n_validators];
+        }
+    }
+
+    // Step 2: All validators get equal stake=100 in Phase 4
+    let mut stakes: Vec<u64> = vec![100; n_validators];
+
+    // Step 2b: Flag Sybil clusters in the global trust graph. Members get
+    // zero consensus stake this epoch and are reported for slashing.
+    {
+        let metagraph_stakes: HashMap<u16, u64> = shared
+            .metagraph_manager
+            .read()
+            .await
+            .current()
+            .map(|mg| mg.nodes.iter().map(|n| (n.uid, n.stake)).collect())
+            .unwrap_or_default();
+
+        let ts = shared.trust_store.read().await;
+        let clusters = match ts.matrix(GLOBAL_DOMAIN) {
+            Some(global) => {
+                let profiles: HashMap<u16, NodeProfile> = global
+                    .first_seen
+                    .iter()
+                    .map(|(&uid, &registered_epoch)| {
+                        let stake = metagraph_stakes
+                            .get(&uid)
+                            .copied()
+                            .or_else(|| stakes.get(uid as usize).copied())
+                            .unwrap_or(0);
+                        (uid, NodeProfile { stake, registered_epoch })
+                    })
+                    .collect();
+                detect_sybil_clusters(global, &profiles, epoch, &SybilConfig::default())
+            }
+            None => Vec::new(),
+        };
+        drop(ts);
+
+        for uid in flagged_uids(&clusters) {
+            if let Some(stake) = stakes.get_mut(uid as usize) {
+                *stake = 0;
+            }
+            let node_stake = metagraph_stakes.get(&uid).copied().unwrap_or(0);
+            let penalty = compute_penalty(&SlashCondition::SybilCollusion, node_stake);
+            tracing::warn!(
+                "Epoch {}: Node {} flagged as Sybil; proposed slash {} rao",
+                epoch,
+                uid,
+                penalty
+            );
+            shared.notify_webhooks(WebhookEvent::ValidatorSlashed {
+                epoch,
+                uid,
+                condition: "sybil_collusion".to_string(),
+                penalty_rao: penalty,
+            });
+        }
+        if !clusters.is_empty() {
+            tracing::warn!("Epoch {}: {} Sybil clusters flagged", epoch, clusters.len());
+        }
+        *shared.sybil_clusters.write().await = clusters;
+    }
+
+    tracing::info!(
+        "Epoch {}: Running consensus ({} validators, {} corals)",
+        epoch,
+        n_validators,
+        n_corals
+    );
+
+    // Step 3: Run Yuma-Semantic Consensus
+    let result = yuma_semantic_consensus(&stakes, &weights, &prev_bonds, 0.5, 0.1, 0.1);
+
+    tracing::info!(
+        "Epoch {}: Consensus complete — {} consensus weights",
+        epoch,
+        result.consensus_weights.len()
+    );
+
+    // Step 4: Store ConsensusResult in shared state
+    {
+        let mut cr = shared.last_consensus_result.write().await;
+        *cr = Some(result.clone());
+    }
+    if let Err(e) = ConsensusRecord::new(epoch, result.clone()).save(store) {
+        tracing::warn!("Epoch {}: Failed to record consensus history: {}", epoch, e);
+    }
+
+    // Step 5: Update bond matrix with result bonds
+    {
+        let mut bm = shared.bond_matrix.write().await;
+        *bm = chitin_consensus::bonds::BondMatrix::new(n_validators, n_corals);
+        for (i, row) in result.bonds.iter().enumerate() {
+            for (j, &val) in row.iter().enumerate() {
+                bm.bonds[i][j] = val;
+            }
+        }
+    }
+
+    // Step 6: Identify approved polyps (consensus_weight > threshold, the
+    // polyp's zone policy overriding the default threshold).
+    // We need to match consensus weights back to actual polyps.
+    // Re-list UnderReview polyps (same order as scored).
+    let under_review_polyps = store
+        .list_polyps_by_state(&PolypState::UnderReview)
+        .await
+        .map_err(|e| format!("Failed to list UnderReview polyps: {}", e))?;
+
+    let mut approved_polyps = Vec::new();
+    for (idx, polyp) in under_review_polyps.iter().enumerate() {
+        let threshold = shared
+            .taxonomy
+            .policy(polyp.reef_zone.as_deref())
+            .hardening_threshold
+            .unwrap_or(APPROVAL_THRESHOLD);
+        if result.consensus_weights.get(idx).is_some_and(|&w| w > threshold) {
+            approved_polyps.push(polyp.clone());
+        }
+    }
+
+    tracing::info!(
+        "Epoch {}: {} polyps approved (default threshold {})",
+        epoch,
+        approved_polyps.len(),
+        APPROVAL_THRESHOLD
+    );
+
+    // Step 7: Transition approved polyps: UnderReview -> Approved. The
+    // updated copies go on to hardening, which keeps their consensus epoch.
+    for polyp in approved_polyps.iter_mut() {
+        let mut updated = polyp.clone();
+        updated.state = PolypState::Approved;
+        updated.consensus = Some(ConsensusMetadata {
+            epoch,
+            final_score: result.consensus_weights
+                .get(under_review_polyps.iter().position(|p| p.id == polyp.id).unwrap_or(0))
+                .copied()
+                .unwrap_or(0.0),
+            validator_scores: vec![],
+            hardened: false,
+            finalized_at: chrono::Utc::now(),
+        });
+        updated.updated_at = chrono::Utc::now();
+        match store.save_polyp(&updated).await {
+            Ok(()) => gossip::announce_state_change(shared, store, &updated, epoch),
+            Err(e) => {
+                tracing::warn!("Failed to transition polyp {} to Approved: {}", polyp.id, e)
+            }
+        }
+        *polyp = updated;
+    }
+    // Approvals must survive a crash during hardening.
+    if !approved_polyps.is_empty() {
+        durability::flush(shared, store);
+    }
+
+    // Step 8: Trigger hardening pipeline for approved polyps
+    if !approved_polyps.is_empty() {
+        let hardened =
+            hardening_pipeline::harden_approved_polyps(shared, store, &approved_polyps, epoch)
+                .await;
+        if let Err(e) = hardened {
+            tracing::error!("Hardening pipeline failed: {}", e);
+        }
+    }
+
+    // Step 9: Update trust from validator agreement, per Reef Zone and globally.
+    // Polyps use their submitted reef_zone if it is in the taxonomy, else are
+    // classified by embedding centroid (keywords as a fallback). Zone trust
+    // rolls up to parent zones; unzoned polyps only count toward global trust.
+    {
+        let classifier = shared.domain_classifier.read().await;
+        let mut zone_columns: BTreeMap<String, Vec<(usize, uuid::Uuid)>> = BTreeMap::new();
+        let mut all_columns = Vec::new();
+        for (idx, polyp) in under_review_polyps.iter().enumerate().take(n_corals) {
+            let zone = polyp
+                .reef_zone
+                .clone()
+                .filter(|z| shared.taxonomy.contains(z))
+                .or_else(|| classifier.classify_polyp(polyp).map(|c| c.domain.domain_id));
+            if let Some(zone) = zone {
+                zone_columns.entry(zone).or_default().push((idx, polyp.id));
+            }
+            all_columns.push((idx, polyp.id));
+        }
+
+        drop(classifier);
+        let zone_columns = shared.taxonomy.rollup(&zone_columns);
+
+        let mut ts = shared.trust_store.write().await;
+        for (domain_id, columns) in &zone_columns {
+            ts.update_from_agreement(domain_id, epoch, &weights, columns);
+        }
+        ts.update_from_agreement(GLOBAL_DOMAIN, epoch, &weights, &all_columns);
+        if let Err(e) = ts.persist() {
+            tracing::warn!("Epoch {}: Failed to persist trust store: {}", epoch, e);
+        }
+        match ts.checkpoint(epoch) {
+            Ok(cp) => tracing::debug!("Epoch {}: Reputation checkpoint {}", epoch, cp.reputation_hash),
+            Err(e) => tracing::warn!("Epoch {}: Failed to checkpoint reputation: {}", epoch, e),
+        }
+    }
+
+    // Step 10: Update metagraph with new epoch state. Registered nodes carry
+    // over, under their UIDs, so that validators keep discovering Coral
+    // nodes; the identity registry records any hotkey rotations.
+    {
+        let nodes = shared
+            .metagraph_manager
+            .read()
+            .await
+            .current()
+            .map(|mg| mg.nodes.clone())
+            .unwrap_or_default();
+        let emission_rate = match &shared.network {
+            Some(network) => {
+                let blocks = shared.epoch_manager.read().await.blocks_per_epoch();
+                network
+                    .economics
+                    .epoch_emission(epoch.saturating_mul(blocks), blocks)
+            }
+            None => 0,
+        };
+        let metagraph = chitin_core::ReefMetagraph {
+            epoch,
+            block: 0, // Phase 4: block tracking is approximate
+            nodes,
+            total_stake: stakes.iter().sum(),
+            total_hardened_polyps: approved_polyps.len() as u64,
+            emission_rate,
+            weights: std::collections::HashMap::new(),
+            bonds: std::collections::HashMap::new(),
+            model_versions: shared.model_registry.read().await.versions.clone(),
+        };
+
+        if let Err(e) = shared.update_metagraph(metagraph, false).await {
+            tracing::warn!("Failed to update metagraph: {}", e);
+        }
+    }
+
+    Ok(Some(ConsensusRun {
+        validators: n_validators,
+        corals: n_corals,
+        approved: approved_polyps.len(),
+    }))
+}
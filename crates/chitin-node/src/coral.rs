@@ -1,4 +1,4 @@
-// crates/chitin-daemon/src/coral.rs
+// crates/chitin-node/src/coral.rs
 //
 // CoralNode: Polyp production pipeline for the Chitin Protocol.
 //
@@ -11,12 +11,14 @@ use chitin_core::{
     PipelineStep, ProcessingPipeline, Provenance, ProofPublicInputs, SourceAttribution,
     VectorEmbedding, ZkProof,
 };
+use chitin_core::keystore::SecretKey;
 use chitin_core::traits::PolypStore;
 use chitin_store::RocksStore;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::config::DaemonConfig;
+use crate::supervisor::ShutdownSignal;
 
 /// A Coral Node that produces Polyps from ingested text.
 pub struct CoralNode {
@@ -25,25 +27,34 @@ pub struct CoralNode {
     store: Arc<RocksStore>,
     /// Node identity for provenance (Phase 2).
     node_identity: Option<NodeIdentity>,
-    /// Signing key for polyp signing (Phase 2).
-    signing_key: Option<[u8; 32]>,
+    /// Signing key for polyp signing (Phase 2), wiped on drop.
+    signing_key: Option<SecretKey>,
 }
 
 impl CoralNode {
     /// Create a new CoralNode, opening a RocksDB store at the configured data directory.
     pub fn new(config: &DaemonConfig) -> Result<Self, Box<dyn std::error::Error>> {
         let db_path = format!("{}/rocksdb", config.data_dir);
-        let store = RocksStore::open(&db_path)?;
-        Ok(Self {
+        let store = RocksStore::open_with_durability(&db_path, config.durability)?;
+        Ok(Self::with_store(config, Arc::new(store)))
+    }
+
+    /// Create a CoralNode over an already open store.
+    pub fn with_store(config: &DaemonConfig, store: Arc<RocksStore>) -> Self {
+        Self {
             config: config.clone(),
-            store: Arc::new(store),
+            store,
             node_identity: None,
             signing_key: None,
-        })
+        }
     }
 
     /// Set the node identity and optional signing key for provenance and polyp signing.
-    pub fn with_identity(mut self, identity: NodeIdentity, signing_key: Option<[u8; 32]>) -> Self {
+    pub fn with_identity(
+        mut self,
+        identity: NodeIdentity,
+        signing_key: Option<SecretKey>,
+    ) -> Self {
         self.node_identity = Some(identity);
         self.signing_key = signing_key;
         self
@@ -56,16 +67,19 @@ impl CoralNode {
 
     /// Start the Coral Node event loop.
     ///
-    /// Phase 1: Logs startup and runs a sleep loop until shutdown signal.
-    pub async fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Phase 1: Logs startup and runs a sleep loop until `shutdown`.
+    pub async fn start(
+        &self,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Coral node started");
         tracing::info!("Listening for Polyp ingestion requests...");
 
         // Phase 1: simple event loop that sleeps and checks for shutdown.
         loop {
             tokio::select! {
-                _ = tokio::signal::ctrl_c() => {
-                    tracing::info!("Coral node received shutdown signal");
+                _ = shutdown.wait() => {
+                    tracing::info!("Coral node shutting down");
                     break;
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_secs(10)) => {
@@ -120,7 +134,7 @@ impl CoralNode {
             did: "did:chitin:local".to_string(),
             node_type: NodeType::Coral,
         });
-        let provenance = Provenance {
+        let mut provenance = Provenance {
             creator,
             source: SourceAttribution {
                 source_cid: None,
@@ -134,10 +148,13 @@ impl CoralNode {
                     name: "ingest".to_string(),
                     version: "0.1.0".to_string(),
                     params: serde_json::json!({}),
+                    hash: None,
                 }],
                 duration_ms: 0,
             },
+            molted_from: vec![],
         };
+        provenance.pipeline.seal();
 
         let subject = PolypSubject {
             payload,
@@ -173,6 +190,7 @@ impl CoralNode {
             created_at: now,
             updated_at: now,
             signature: None,
+            reef_zone: None,
         };
 
         // Sign the polyp if a signing key is available.
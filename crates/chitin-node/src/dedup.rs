@@ -0,0 +1,63 @@
+// crates/chitin-node/src/dedup.rs
+//
+// Keeps the near-duplicate detector in step with the hardened polyps.
+//
+// `DaemonSharedState::duplicates` fingerprints every hardened polyp (see
+// `chitin_consensus::dedup`). The hardening pipeline adds the polyps it
+// hardens as it goes; this task loads the store's hardened polyps at
+// startup and again at every epoch boundary, picking up polyps hardened
+// by peers (through gossip or sync) and dropping ones since molted or
+// pruned. RPC submissions and the Tide scoring pipeline check against it.
+
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use chitin_core::traits::PolypStore;
+use chitin_core::PolypState;
+use chitin_store::RocksStore;
+
+use crate::epoch_events::EpochEvent;
+use crate::shared::DaemonSharedState;
+
+/// Run the refresher until the epoch event channel closes.
+///
+/// Returns immediately if near-duplicate detection is disabled.
+pub async fn run_dedup_refresher(
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    mut event_rx: broadcast::Receiver<EpochEvent>,
+) {
+    if !shared.duplicates.read().await.config().enabled {
+        tracing::info!("Near-duplicate detection disabled");
+        return;
+    }
+    refresh(&shared, &store).await;
+    loop {
+        match event_rx.recv().await {
+            Ok(EpochEvent::EpochBoundary { .. }) => refresh(&shared, &store).await,
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Load the store's hardened polyps into the detector, logging failures.
+pub async fn refresh(shared: &DaemonSharedState, store: &RocksStore) {
+    let hardened = match store.list_polyps_by_state(&PolypState::Hardened).await {
+        Ok(hardened) => hardened,
+        Err(e) => {
+            tracing::warn!("Failed to list hardened polyps for dedup: {}", e);
+            return;
+        }
+    };
+    let mut detector = shared.duplicates.write().await;
+    let added = detector.sync(&hardened);
+    if added > 0 {
+        tracing::info!(
+            "Dedup: fingerprinted {} newly hardened polyps ({} tracked)",
+            added,
+            detector.len()
+        );
+    }
+}
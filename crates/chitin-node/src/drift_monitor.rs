@@ -0,0 +1,117 @@
+// crates/chitin-node/src/drift_monitor.rs
+//
+// Periodic drift monitoring for the Chitin Protocol daemon.
+//
+// Every `interval_secs`, samples polyps hardened since the previous pass,
+// re-embeds them with the model version active at the current epoch, and
+// aggregates their drift per Reef Zone per epoch (`chitin_drift::monitor`).
+// Statistics are persisted after each pass. When an epoch's mean drift first
+// crosses the alarm threshold, an `EpochEvent::DriftAlarm` is broadcast.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use chitin_core::traits::PolypStore;
+use chitin_core::{PolypState, HASH_EMBEDDING_MODEL};
+use chitin_drift::detection::LocalEmbeddingModel;
+use chitin_drift::monitor::{DriftMonitor, DriftMonitorConfig};
+use chitin_store::RocksStore;
+
+use crate::epoch_events::EpochEvent;
+use crate::shared::DaemonSharedState;
+use crate::webhooks::WebhookEvent;
+
+/// Dimensions of embeddings the node produces (matches `polyp/submit`).
+pub const EMBEDDING_DIMENSIONS: usize = 384;
+
+/// Run the drift monitor until the process exits.
+///
+/// Returns immediately if `config.interval_secs` is zero.
+pub async fn run_drift_monitor(
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    event_tx: broadcast::Sender<EpochEvent>,
+    config: DriftMonitorConfig,
+) {
+    if config.interval_secs == 0 {
+        tracing::info!("Drift monitor disabled");
+        return;
+    }
+    tracing::info!(
+        "Drift monitor started (every {}s, {} samples, alarm at {:.3})",
+        config.interval_secs,
+        config.sample_size,
+        config.alarm_threshold
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    let mut monitor = DriftMonitor::new(config);
+    loop {
+        interval.tick().await;
+
+        let epoch = shared.epoch_manager.read().await.current_epoch();
+        let model_id = shared
+            .model_registry
+            .read()
+            .await
+            .active_version(epoch)
+            .map_or_else(|| HASH_EMBEDDING_MODEL.to_string(), |v| v.model_id.clone());
+        let hardened = match store.list_polyps_by_state(&PolypState::Hardened).await {
+            Ok(polyps) => polyps,
+            Err(e) => {
+                tracing::warn!("Drift monitor: failed to list hardened polyps: {}", e);
+                continue;
+            }
+        };
+
+        let model = LocalEmbeddingModel::new(&model_id, EMBEDDING_DIMENSIONS);
+        let observation = match monitor.observe(epoch, &model, &hardened) {
+            Ok(observation) => observation,
+            Err(e) => {
+                tracing::warn!("Drift monitor: failed to measure drift: {}", e);
+                continue;
+            }
+        };
+
+        if let Some(finished) = &observation.finished {
+            if let Err(e) = finished.save(&store) {
+                tracing::warn!("Drift monitor: failed to persist epoch {}: {}", finished.epoch, e);
+            }
+        }
+        if observation.sampled > 0 {
+            if let Some(current) = monitor.current() {
+                tracing::debug!(
+                    "Epoch {}: Drift {:.4} over {} samples under {}",
+                    epoch,
+                    current.overall.mean_cosine_shift,
+                    current.overall.samples,
+                    model_id
+                );
+                if let Err(e) = current.save(&store) {
+                    tracing::warn!("Drift monitor: failed to persist epoch {}: {}", epoch, e);
+                }
+            }
+        }
+        if let Some(alarm) = observation.alarm {
+            let zones = alarm.zones_above(monitor.config().alarm_threshold);
+            shared.notify_webhooks(WebhookEvent::DriftAlarm {
+                epoch,
+                model: alarm.model.clone(),
+                mean_cosine_shift: alarm.overall.mean_cosine_shift,
+                zones: zones.clone(),
+            });
+            let event = EpochEvent::DriftAlarm {
+                epoch,
+                model: alarm.model,
+                mean_cosine_shift: alarm.overall.mean_cosine_shift,
+                zones,
+            };
+            // Subscribers log the alarm; log it here if there are none.
+            if let Err(broadcast::error::SendError(event)) = event_tx.send(event) {
+                tracing::warn!("Drift alarm: {:?}", event);
+            }
+        }
+    }
+}
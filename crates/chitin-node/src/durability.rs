@@ -0,0 +1,82 @@
+// crates/chitin-node/src/durability.rs
+//
+// Write-ahead log syncing for the daemon's stores.
+//
+// `[durability]` sets when RocksStore writes reach stable storage (see
+// `chitin_store::Durability`). All modes sync at the points where losing
+// state would be costly: consensus syncs after approving polyps, and the
+// hardening pipeline after committing an epoch's checkpoint and hardened
+// polyps. In `periodic` mode this task also syncs the polyp store and the
+// hardened cache every `interval_ms`, and in `periodic` and `relaxed` modes at
+// each epoch boundary. In `sync` mode every write is already synced.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use chitin_store::{Durability, RocksStore};
+
+use crate::epoch_events::EpochEvent;
+use crate::shared::DaemonSharedState;
+
+/// Run the WAL flusher until the process exits.
+///
+/// Returns immediately in `sync` mode.
+pub async fn run_wal_flusher(
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    mut event_rx: broadcast::Receiver<EpochEvent>,
+    durability: Durability,
+) {
+    let mut periodic = match durability {
+        Durability::Sync => {
+            tracing::info!("Store writes synced individually");
+            return;
+        }
+        Durability::Periodic { interval_ms } if interval_ms > 0 => {
+            tracing::info!("Store WAL synced every {}ms and at epoch boundaries", interval_ms);
+            Some(tokio::time::interval(Duration::from_millis(interval_ms)))
+        }
+        _ => {
+            tracing::info!("Store WAL synced at epoch boundaries and hardening commits");
+            None
+        }
+    };
+
+    loop {
+        tokio::select! {
+            _ = tick(&mut periodic) => flush(&shared, &store),
+            event = event_rx.recv() => match event {
+                Ok(EpochEvent::EpochBoundary { epoch, .. }) => {
+                    tracing::debug!("Epoch {}: Syncing store WAL", epoch);
+                    flush(&shared, &store);
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+        }
+    }
+}
+
+/// Sync the WAL of `store` and the hardened cache, logging failures.
+pub fn flush(shared: &DaemonSharedState, store: &RocksStore) {
+    if let Err(e) = store.flush() {
+        tracing::error!("Failed to sync store WAL: {}", e);
+    }
+    if let Some(hardened_store) = &shared.hardened_store {
+        if let Err(e) = hardened_store.local_cache.flush() {
+            tracing::error!("Failed to sync hardened cache WAL: {}", e);
+        }
+    }
+}
+
+/// Wait for the next periodic sync, or forever if there is none.
+async fn tick(periodic: &mut Option<tokio::time::Interval>) {
+    match periodic {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,500 @@
+// crates/chitin-node/src/embedding.rs
+//
+// Embedding worker pool for Coral nodes.
+//
+// `polyp/submit` requests that arrive without a vector are queued here. A
+// pool of workers drains the queue in batches, embeds each batch with the
+// provider for the model active at the current epoch (per the model version
+// registry), and hands the vectors back to be attached to the new polyps.
+//
+// Providers:
+// - the built-in hash embedding (`chitin/hash-embedding-v1`), always present
+// - OpenAI-compatible HTTP APIs (`POST {base_url}/embeddings`), configured
+//   as `[[embedding.providers]]` entries
+// - local ONNX sentence-transformers (`kind = "onnx"`, with the `onnx`
+//   feature), so a node can embed without third-party API keys. The model
+//   file must match the weights hash its model config registers.
+//
+// Providers are chitin-core `Embedder`s. Unless `cache_entries = 0`, each
+// sits behind the node's `EmbeddingCache`, so content and queries already
+// embedded under a model are served from RocksDB instead of the provider.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+use chitin_core::embedder::{HashEmbedder, OpenAiConfig, OpenAiEmbedder};
+use chitin_core::{ChitinError, Embedder, EmbeddingModelId, HASH_EMBEDDING_MODEL};
+use chitin_rpc::handlers::polyp::EmbeddedContent;
+use chitin_rpc::EmbedCallback;
+use chitin_store::{CachedEmbedder, EmbeddingCache, RocksStore};
+
+use crate::drift_monitor::EMBEDDING_DIMENSIONS;
+use crate::shared::DaemonSharedState;
+
+/// An embedder and the model it embeds with.
+#[derive(Clone)]
+pub struct Provider {
+    /// The model; its key is the space the vectors are published under.
+    pub model: EmbeddingModelId,
+    pub embedder: Arc<dyn Embedder>,
+}
+
+impl Provider {
+    /// The built-in hash embedding.
+    pub fn hash() -> Self {
+        Self {
+            model: EmbeddingModelId::from_key(HASH_EMBEDDING_MODEL, EMBEDDING_DIMENSIONS as u32),
+            embedder: Arc::new(HashEmbedder::new(EMBEDDING_DIMENSIONS)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Settings for the embedding worker pool (`[embedding]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingConfig {
+    /// Number of workers draining the queue.
+    pub workers: usize,
+    /// Most submissions waiting to be embedded.
+    pub queue_size: usize,
+    /// Most texts embedded in one provider call.
+    pub batch_size: usize,
+    /// Model used when the registry has no active version with a provider.
+    /// Defaults to the first configured provider, else the hash embedding.
+    pub default_model: Option<String>,
+    /// Configured providers (`[[embedding.providers]]` entries).
+    pub providers: Vec<ProviderConfig>,
+    /// Most vectors kept in the embedding cache (0 disables it).
+    pub cache_entries: usize,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            queue_size: 256,
+            batch_size: 16,
+            default_model: None,
+            providers: Vec::new(),
+            cache_entries: 100_000,
+        }
+    }
+}
+
+/// One embedding provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProviderConfig {
+    /// An OpenAI-compatible `/embeddings` endpoint.
+    #[serde(rename = "openai")]
+    OpenAi {
+        /// Space key the vectors are published under.
+        model_id: String,
+        /// API base URL, without the `/embeddings` suffix.
+        #[serde(default = "default_openai_base_url")]
+        base_url: String,
+        /// Model name sent to the API.
+        model: String,
+        /// Environment variable holding the API key, if one is needed.
+        #[serde(default)]
+        api_key_env: Option<String>,
+        /// Requested output dimensions, if the API supports choosing them.
+        #[serde(default)]
+        dimensions: Option<usize>,
+        /// Request timeout in seconds.
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+        /// Retries of a rate-limited or failed request before giving up.
+        #[serde(default = "default_max_retries")]
+        max_retries: u32,
+    },
+    /// A local ONNX sentence-transformer (needs the `onnx` feature).
+    Onnx(OnnxProviderConfig),
+}
+
+/// A local ONNX model (`kind = "onnx"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnnxProviderConfig {
+    /// Space key the vectors are published under. Its model config gives
+    /// the weights hash, dimensions, and token limit.
+    pub model_id: String,
+    /// The exported model (`model.onnx`).
+    pub model_path: PathBuf,
+    /// The model's `tokenizer.json` (default: next to the model).
+    #[serde(default)]
+    pub tokenizer_path: Option<PathBuf>,
+    /// Model config YAML to look `model_id` up in (default: the built-in
+    /// registry).
+    #[serde(default)]
+    pub model_configs: Option<PathBuf>,
+    /// "cpu", "cuda", or "cuda:N".
+    #[serde(default = "default_device")]
+    pub device: String,
+    /// "mean" or "cls" (the BGE family).
+    #[serde(default = "default_pooling")]
+    pub pooling: String,
+    /// CPU threads per inference (default: ONNX Runtime's choice).
+    #[serde(default)]
+    pub threads: Option<usize>,
+}
+
+fn default_openai_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_device() -> String {
+    "cpu".to_string()
+}
+
+fn default_pooling() -> String {
+    "mean".to_string()
+}
+
+impl EmbeddingConfig {
+    /// Build the configured providers, plus the built-in hash embedding,
+    /// validating the pool settings.
+    pub fn providers(&self) -> Result<ProviderSet, ChitinError> {
+        if self.workers == 0 || self.queue_size == 0 || self.batch_size == 0 {
+            return Err(ChitinError::InvalidState(
+                "workers, queue_size, and batch_size must be positive".to_string(),
+            ));
+        }
+
+        let mut providers: HashMap<String, Provider> = HashMap::new();
+        let mut first = None;
+        for config in &self.providers {
+            let provider = config.build()?;
+            let model_id = provider.model.key();
+            if providers.insert(model_id.clone(), provider).is_some() {
+                return Err(ChitinError::InvalidState(format!(
+                    "Duplicate embedding provider for {}",
+                    model_id
+                )));
+            }
+            first.get_or_insert(model_id);
+        }
+        providers
+            .entry(HASH_EMBEDDING_MODEL.to_string())
+            .or_insert_with(Provider::hash);
+
+        let default_model = match (&self.default_model, first) {
+            (Some(model), _) => model.clone(),
+            (None, Some(first)) => first,
+            (None, None) => HASH_EMBEDDING_MODEL.to_string(),
+        };
+        if !providers.contains_key(&default_model) {
+            return Err(ChitinError::InvalidState(format!(
+                "default_model {} has no provider",
+                default_model
+            )));
+        }
+        Ok(ProviderSet {
+            providers: Arc::new(providers),
+            default_model,
+        })
+    }
+
+    /// Open the embedding cache in `store`, unless `cache_entries` is 0.
+    pub fn cache(
+        &self,
+        store: Arc<RocksStore>,
+    ) -> Result<Option<Arc<EmbeddingCache>>, ChitinError> {
+        if self.cache_entries == 0 {
+            return Ok(None);
+        }
+        let cache = EmbeddingCache::open(store, self.cache_entries)?;
+        Ok(Some(Arc::new(cache)))
+    }
+}
+
+impl ProviderConfig {
+    fn build(&self) -> Result<Provider, ChitinError> {
+        match self {
+            Self::OpenAi {
+                model_id,
+                base_url,
+                model,
+                api_key_env,
+                dimensions,
+                timeout_secs,
+                max_retries,
+            } => {
+                if !model_id.contains('/') {
+                    return Err(ChitinError::InvalidState(format!(
+                        "model_id must be a space key like \"provider/name\": {:?}",
+                        model_id
+                    )));
+                }
+                let api_key = match api_key_env {
+                    Some(var) => Some(std::env::var(var).map_err(|_| {
+                        ChitinError::InvalidState(format!("{} is not set", var))
+                    })?),
+                    None => None,
+                };
+                let dimensions = dimensions.map(|d| d as u32);
+                let embedder = OpenAiEmbedder::new(OpenAiConfig {
+                    base_url: base_url.clone(),
+                    api_key,
+                    model: Some(model.clone()),
+                    dimensions,
+                    max_retries: *max_retries,
+                    timeout: Duration::from_secs(*timeout_secs),
+                    ..OpenAiConfig::default()
+                })?;
+                Ok(Provider {
+                    model: EmbeddingModelId::from_key(model_id, dimensions.unwrap_or(0)),
+                    embedder: Arc::new(embedder),
+                })
+            }
+            Self::Onnx(config) => config.build(),
+        }
+    }
+}
+
+impl OnnxProviderConfig {
+    #[cfg(feature = "onnx")]
+    fn build(&self) -> Result<Provider, ChitinError> {
+        use chitin_core::embedder::{OnnxConfig, OnnxEmbedder, Pooling};
+        use chitin_verify::ModelRegistry;
+
+        let registry = match &self.model_configs {
+            Some(path) => ModelRegistry::load_from_yaml(&path.to_string_lossy())?,
+            None => ModelRegistry::default_registry(),
+        };
+        let model = registry.get_model(&self.model_id).ok_or_else(|| {
+            ChitinError::InvalidState(format!(
+                "No model config for {}; its weights hash cannot be verified",
+                self.model_id
+            ))
+        })?;
+        let pooling = match self.pooling.as_str() {
+            "mean" => Pooling::Mean,
+            "cls" => Pooling::Cls,
+            other => {
+                return Err(ChitinError::InvalidState(format!(
+                    "Unknown pooling {:?} (expected mean or cls)",
+                    other
+                )))
+            }
+        };
+
+        let mut config = OnnxConfig::new(&self.model_path, &model.weights_hash);
+        if let Some(path) = &self.tokenizer_path {
+            config.tokenizer_path = path.clone();
+        }
+        config.device = self.device.parse()?;
+        config.pooling = pooling;
+        config.max_tokens = model.max_tokens as usize;
+        config.threads = self.threads;
+        let embedder = OnnxEmbedder::load(config)?;
+        tracing::info!(
+            "Loaded {} from {} ({})",
+            self.model_id,
+            self.model_path.display(),
+            self.device
+        );
+        Ok(Provider {
+            model: EmbeddingModelId::from_key(&self.model_id, model.dimensions),
+            embedder: Arc::new(embedder),
+        })
+    }
+
+    #[cfg(not(feature = "onnx"))]
+    fn build(&self) -> Result<Provider, ChitinError> {
+        Err(ChitinError::InvalidState(format!(
+            "Embedding provider for {} needs ONNX support; rebuild with `--features onnx`",
+            self.model_id
+        )))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Worker pool
+// ---------------------------------------------------------------------------
+
+/// Providers keyed by model, with the model used when none is selected.
+#[derive(Clone)]
+pub struct ProviderSet {
+    providers: Arc<HashMap<String, Provider>>,
+    default_model: String,
+}
+
+impl ProviderSet {
+    /// The provider for `model`, falling back to the default model's.
+    fn select(&self, model: Option<&str>) -> Provider {
+        model
+            .and_then(|m| self.providers.get(m))
+            .or_else(|| self.providers.get(&self.default_model))
+            .cloned()
+            .unwrap_or_else(Provider::hash)
+    }
+
+    /// The same providers, each behind `cache`.
+    fn cached(&self, cache: &Arc<EmbeddingCache>) -> Self {
+        let providers = self
+            .providers
+            .iter()
+            .map(|(key, provider)| {
+                let embedder = CachedEmbedder::new(provider.embedder.clone(), cache.clone());
+                let provider = Provider {
+                    model: provider.model.clone(),
+                    embedder: Arc::new(embedder),
+                };
+                (key.clone(), provider)
+            })
+            .collect();
+        Self {
+            providers: Arc::new(providers),
+            default_model: self.default_model.clone(),
+        }
+    }
+}
+
+/// A queued submission awaiting its vector.
+struct EmbedJob {
+    text: String,
+    reply: oneshot::Sender<Result<EmbeddedContent, String>>,
+}
+
+/// Queue and workers embedding submitted content.
+#[derive(Clone)]
+pub struct EmbeddingPool {
+    tx: mpsc::Sender<EmbedJob>,
+}
+
+impl EmbeddingPool {
+    /// Spawn `config.workers` workers embedding with `providers`, selecting
+    /// the model active at `shared`'s current epoch and reading through its
+    /// embedding cache, if it has one.
+    pub fn start(
+        config: &EmbeddingConfig,
+        providers: ProviderSet,
+        shared: DaemonSharedState,
+    ) -> Self {
+        let providers = match &shared.embedding_cache {
+            Some(cache) => providers.cached(cache),
+            None => providers,
+        };
+        let (tx, rx) = mpsc::channel(config.queue_size);
+        let rx = Arc::new(Mutex::new(rx));
+        for worker in 0..config.workers {
+            let rx = rx.clone();
+            let providers = providers.clone();
+            let shared = shared.clone();
+            let batch_size = config.batch_size;
+            tokio::spawn(async move {
+                run_worker(worker, rx, providers, shared, batch_size).await;
+            });
+        }
+        tracing::info!(
+            "Embedding pool started ({} workers, default model {})",
+            config.workers,
+            providers.default_model
+        );
+        Self { tx }
+    }
+
+    /// Queue `text` and wait for its embedding.
+    pub async fn embed(&self, text: String) -> Result<EmbeddedContent, String> {
+        let (reply, response) = oneshot::channel();
+        self.tx
+            .send(EmbedJob { text, reply })
+            .await
+            .map_err(|_| "Embedding pool is shut down".to_string())?;
+        response
+            .await
+            .map_err(|_| "Embedding worker dropped the request".to_string())?
+    }
+
+    /// Adapt `embed` into the RPC server's embedding callback.
+    pub fn embed_callback(&self) -> EmbedCallback {
+        let pool = self.clone();
+        Arc::new(move |text| {
+            let pool = pool.clone();
+            Box::pin(async move { pool.embed(text).await })
+        })
+    }
+}
+
+/// Drain batches of up to `batch_size` jobs until the queue closes.
+async fn run_worker(
+    worker: usize,
+    rx: Arc<Mutex<mpsc::Receiver<EmbedJob>>>,
+    providers: ProviderSet,
+    shared: DaemonSharedState,
+    batch_size: usize,
+) {
+    loop {
+        let mut batch = Vec::with_capacity(batch_size);
+        {
+            let mut rx = rx.lock().await;
+            match rx.recv().await {
+                Some(job) => batch.push(job),
+                None => break,
+            }
+            while batch.len() < batch_size {
+                match rx.try_recv() {
+                    Ok(job) => batch.push(job),
+                    Err(_) => break,
+                }
+            }
+        }
+
+        let epoch = shared.epoch_manager.read().await.current_epoch();
+        let active = shared
+            .model_registry
+            .read()
+            .await
+            .active_version(epoch)
+            .map(|v| v.model_id.clone());
+        let provider = providers.select(active.as_deref());
+        let model_id = provider.model.key();
+        let texts: Vec<&str> = batch.iter().map(|job| job.text.as_str()).collect();
+        tracing::debug!(
+            "Embedding worker {}: {} texts with {}",
+            worker,
+            texts.len(),
+            model_id
+        );
+
+        match provider.embedder.embed(&texts, &provider.model).await {
+            Ok(embeddings) if embeddings.len() == batch.len() => {
+                for (job, embedding) in batch.into_iter().zip(embeddings) {
+                    let _ = job.reply.send(Ok(EmbeddedContent {
+                        model_id: model_id.clone(),
+                        values: embedding.values,
+                    }));
+                }
+            }
+            Ok(embeddings) => {
+                let e = format!("{} vectors for {} texts", embeddings.len(), batch.len());
+                for job in batch {
+                    let _ = job.reply.send(Err(e.clone()));
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Embedding with {} failed: {}", model_id, e);
+                for job in batch {
+                    let _ = job.reply.send(Err(e.to_string()));
+                }
+            }
+        }
+    }
+}
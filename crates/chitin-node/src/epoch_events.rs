@@ -1,9 +1,10 @@
-// crates/chitin-daemon/src/epoch_events.rs
+// crates/chitin-node/src/epoch_events.rs
 //
 // Epoch event types broadcast from the scheduler to daemon tasks.
 //
 // The EpochScheduler publishes events on a tokio broadcast channel.
 // TideNode and the consensus runner subscribe to receive phase transitions.
+// The drift monitor publishes drift alarms on the same channel.
 
 use chitin_consensus::epoch::EpochPhase;
 
@@ -26,4 +27,15 @@ pub enum EpochEvent {
         /// Block height at the boundary.
         block: u64,
     },
+    /// Drift on recently hardened polyps crossed the alarm threshold.
+    DriftAlarm {
+        /// Epoch in which the threshold was crossed.
+        epoch: u64,
+        /// The active model polyps were re-embedded with.
+        model: String,
+        /// Mean cosine shift across all samples this epoch.
+        mean_cosine_shift: f64,
+        /// Reef Zones at or above the threshold (`None` for unzoned polyps).
+        zones: Vec<Option<String>>,
+    },
 }
@@ -0,0 +1,155 @@
+// crates/chitin-node/src/gossip.rs
+//
+// Single-hop gossip broadcast: push a polyp, or a state transition of a
+// polyp peers already hold, to all configured peers, signed with this node's
+// hotkey when it has a signing key.
+// Fire-and-forget — failures are logged, never block the caller.
+
+use std::sync::Arc;
+
+use chitin_core::polyp::Polyp;
+use chitin_store::RocksStore;
+use chitin_sync::state_update::{record_change, PolypStateUpdate};
+
+use crate::peers::PeerRegistry;
+use crate::shared::DaemonSharedState;
+
+/// Broadcast a polyp to all configured peers via `peer/receive_polyp`.
+///
+/// For each peer, spawns an async task that POSTs the polyp.
+/// Peers backing off after failed calls are skipped; peers that are
+/// unreachable are logged and marked dead in the registry;
+/// successful pushes count toward the peer's sync metrics.
+/// Peers do NOT re-broadcast (single-hop only).
+pub fn broadcast_polyp(registry: Arc<PeerRegistry>, polyp: Polyp, source_did: Option<String>) {
+    let peers = registry.configured_peer_urls();
+
+    if peers.is_empty() {
+        return;
+    }
+
+    tracing::debug!(
+        "Broadcasting polyp {} to {} peers",
+        polyp.id,
+        peers.len()
+    );
+    let payload = serde_json::json!({
+        "polyp": polyp,
+        "source_did": source_did,
+    });
+
+    for peer_url in peers {
+        let client = registry.http_client().clone();
+        let reg = registry.clone();
+        let polyp_id = polyp.id;
+        let payload = payload.clone();
+
+        tokio::spawn(async move {
+            if !reg.is_dial_due(&peer_url).await {
+                return;
+            }
+            let params = reg.sign_request("peer/receive_polyp", payload).await;
+            let request_body = serde_json::json!({
+                "method": "peer/receive_polyp",
+                "params": params,
+            });
+
+            match client.post(&peer_url).json(&request_body).send().await {
+                Ok(resp) => {
+                    if resp.status().is_success() {
+                        tracing::debug!("Pushed polyp {} to peer {}", polyp_id, peer_url);
+                        reg.mark_peer(&peer_url, true, None).await;
+                        reg.sync_metrics().record_pushed(&peer_url, 1);
+                    } else {
+                        tracing::warn!(
+                            "Push polyp {} to peer {} returned status {}",
+                            polyp_id,
+                            peer_url,
+                            resp.status()
+                        );
+                        reg.mark_peer(&peer_url, false, None).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to push polyp {} to peer {}: {}",
+                        polyp_id,
+                        peer_url,
+                        e
+                    );
+                    reg.mark_peer(&peer_url, false, None).await;
+                }
+            }
+        });
+    }
+}
+
+/// Broadcast a state transition to all configured peers via
+/// `peer/receive_state_update`. Single-hop, like `broadcast_polyp`; peers
+/// that miss it catch up from the change log during sync.
+pub fn broadcast_state_update(registry: Arc<PeerRegistry>, update: PolypStateUpdate) {
+    let payload = serde_json::json!({ "update": update });
+    for peer_url in registry.configured_peer_urls() {
+        let client = registry.http_client().clone();
+        let reg = registry.clone();
+        let update = update.clone();
+        let payload = payload.clone();
+
+        tokio::spawn(async move {
+            if !reg.is_dial_due(&peer_url).await {
+                return;
+            }
+            let params = reg.sign_request("peer/receive_state_update", payload).await;
+            let request_body = serde_json::json!({
+                "method": "peer/receive_state_update",
+                "params": params,
+            });
+
+            match client.post(&peer_url).json(&request_body).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    tracing::debug!(
+                        "Pushed state of polyp {} to peer {}",
+                        update.polyp_id,
+                        peer_url
+                    );
+                    reg.mark_peer(&peer_url, true, None).await;
+                }
+                Ok(resp) => {
+                    tracing::warn!(
+                        "Push state of polyp {} to peer {} returned status {}",
+                        update.polyp_id,
+                        peer_url,
+                        resp.status()
+                    );
+                    reg.mark_peer(&peer_url, false, None).await;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to push state of polyp {} to peer {}: {}",
+                        update.polyp_id,
+                        peer_url,
+                        e
+                    );
+                    reg.mark_peer(&peer_url, false, None).await;
+                }
+            }
+        });
+    }
+}
+
+/// Announce that `polyp` (already saved) entered its current state in
+/// `epoch`: record it in the change log peers pull from during sync, and
+/// gossip it if peer networking is set up.
+pub fn announce_state_change(
+    shared: &DaemonSharedState,
+    store: &RocksStore,
+    polyp: &Polyp,
+    epoch: u64,
+) {
+    if let Err(e) = record_change(store, &polyp.id, chrono::Utc::now()) {
+        tracing::warn!("Failed to record state change of polyp {}: {}", polyp.id, e);
+    }
+    if let Some(gossip) = &shared.state_gossip {
+        gossip(PolypStateUpdate::from_polyp(polyp, epoch));
+    }
+}
@@ -0,0 +1,155 @@
+// crates/chitin-node/src/hardening_pipeline.rs
+//
+// Post-consensus hardening pipeline for the Chitin Protocol daemon.
+//
+// After consensus identifies approved polyps, this module serializes them
+// to IPFS via HardenedStore, builds the epoch's Merkle tree via
+// HardeningManager, records its root, and updates polyp state to Hardened.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chitin_consensus::hardening::{HardeningCheckpoint, HardeningManager};
+use chitin_core::consensus::HardeningLineage;
+use chitin_core::polyp::Polyp;
+use chitin_core::traits::PolypStore;
+use chitin_core::PolypState;
+use chitin_store::RocksStore;
+use uuid::Uuid;
+
+use crate::durability;
+use crate::gossip;
+use crate::moderation;
+use crate::shared::DaemonSharedState;
+use crate::webhooks::WebhookEvent;
+
+/// Harden all approved polyps of `epoch` through IPFS storage and one
+/// epoch-wide Merkle tree.
+///
+/// 0. Reject polyps failing the moderation policies (see `moderation`)
+/// 1. Serialize each polyp to IPFS via HardenedStore::store_hardened()
+/// 2. Pin all CIDs and build the epoch tree via HardeningManager::harden_epoch()
+/// 3. Record the epoch root as a HardeningCheckpoint, which syncing peers
+///    verify hardened polyps against
+/// 4. Update each polyp to Hardened with its lineage, save it, and announce
+///    the transition to peers and webhooks
+pub async fn harden_approved_polyps(
+    shared: &DaemonSharedState,
+    store: &Arc<RocksStore>,
+    approved_polyps: &[Polyp],
+    epoch: u64,
+) -> Result<(), String> {
+    let hardened_store = match &shared.hardened_store {
+        Some(hs) => hs.clone(),
+        None => {
+            tracing::warn!("No hardened store configured, skipping hardening pipeline");
+            return Ok(());
+        }
+    };
+
+    let approved_polyps =
+        &moderation::moderate_before_hardening(shared, store, approved_polyps, epoch).await;
+    if approved_polyps.is_empty() {
+        return Ok(());
+    }
+    tracing::info!("Hardening {} approved polyps", approved_polyps.len());
+
+    // Step 1: Serialize to IPFS via HardenedStore
+    let mut stored = HashMap::new();
+    for polyp in approved_polyps {
+        match hardened_store.store_hardened(polyp).await {
+            Ok(cid) => {
+                stored.insert(polyp.id, (polyp, cid));
+            }
+            Err(e) => tracing::error!("Failed to store hardened polyp {}: {}", polyp.id, e),
+        }
+    }
+    if stored.is_empty() {
+        return Ok(());
+    }
+
+    // Step 2: Pin + epoch Merkle tree via HardeningManager
+    let items: Vec<(Uuid, String)> = stored
+        .iter()
+        .map(|(id, (_, cid))| (*id, cid.clone()))
+        .collect();
+    let manager = HardeningManager::new(hardened_store.ipfs.clone());
+    let mut hardening = manager
+        .harden_epoch(epoch, &items)
+        .await
+        .map_err(|e| format!("Failed to harden epoch {}: {}", epoch, e))?;
+    for (polyp_id, e) in &hardening.failed {
+        tracing::error!("Failed to pin hardened polyp {}: {}", polyp_id, e);
+    }
+
+    // Step 3: Record the epoch root, keeping a beacon already recorded.
+    if let Ok(Some(existing)) = HardeningCheckpoint::load(store, epoch) {
+        hardening.checkpoint.beacon = existing.beacon;
+    }
+    if let Err(e) = hardening.checkpoint.save(store) {
+        tracing::warn!("Epoch {}: Failed to save hardening checkpoint: {}", epoch, e);
+    }
+
+    // Step 4: Update polyp state to Hardened with lineage and save
+    let mut hardened_count = 0;
+    let merkle_root = hex::encode(hardening.checkpoint.merkle_root);
+    for (polyp_id, lineage) in hardening.lineages {
+        let (polyp, cid) = match stored.get(&polyp_id) {
+            Some((polyp, cid)) => (*polyp, cid),
+            None => continue,
+        };
+        match save_hardened_polyp(store, polyp, lineage).await {
+            Ok(updated) => {
+                gossip::announce_state_change(shared, store, &updated, epoch);
+                shared.notify_webhooks(WebhookEvent::PolypHardened {
+                    epoch,
+                    polyp_id,
+                    cid: cid.clone(),
+                    merkle_root: merkle_root.clone(),
+                });
+                hardened_count += 1;
+                // Refine domain centroids with newly hardened knowledge.
+                shared.domain_classifier.write().await.learn(polyp);
+                shared.duplicates.write().await.insert(&updated);
+                tracing::debug!("Hardened polyp {}", polyp.id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to harden polyp {}: {}", polyp.id, e);
+            }
+        }
+    }
+
+    // Commit the checkpoint and hardened states before reporting success.
+    durability::flush(shared, store);
+
+    tracing::info!(
+        "Hardening complete: {}/{} polyps hardened",
+        hardened_count,
+        approved_polyps.len()
+    );
+
+    Ok(())
+}
+
+/// Mark a polyp Hardened with its lineage and save it.
+async fn save_hardened_polyp(
+    store: &Arc<RocksStore>,
+    polyp: &Polyp,
+    lineage: HardeningLineage,
+) -> Result<Polyp, String> {
+    let mut updated = polyp.clone();
+    updated.state = PolypState::Hardened;
+    updated.hardening = Some(lineage);
+    // Mark consensus metadata as hardened if present
+    if let Some(ref mut consensus) = updated.consensus {
+        consensus.hardened = true;
+    }
+    updated.updated_at = chrono::Utc::now();
+
+    store
+        .save_polyp(&updated)
+        .await
+        .map_err(|e| format!("Failed to save hardened polyp: {}", e))?;
+
+    Ok(updated)
+}
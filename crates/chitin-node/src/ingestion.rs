@@ -0,0 +1,361 @@
+// crates/chitin-node/src/ingestion.rs
+//
+// URL ingestion for Coral nodes (`polyp/ingest_url`).
+//
+// The ingester fetches a URL, extracts its main text, and splits the text
+// into overlapping chunks. Each chunk carries the pipeline steps that
+// produced it (`url-fetch`, `text-extract`, `chunk`); the RPC server then
+// submits every chunk through the normal `polyp/submit` path, so chunks are
+// embedded, signed, indexed, and gossiped like any other polyp.
+//
+// Extraction is deliberately simple: for HTML, the `<main>` or `<article>`
+// element is preferred when present, boilerplate elements (scripts, styles,
+// navigation, headers, footers, forms) are dropped, and block elements
+// become paragraph breaks. Plain text and Markdown are taken as-is.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::text::{chunk_text, validate_chunking};
+use chitin_core::{ChitinError, PipelineStep};
+use chitin_rpc::handlers::polyp::{IngestUrlRequest, IngestedChunk, IngestedDocument};
+use chitin_rpc::IngestCallback;
+
+/// Version recorded in the pipeline steps of ingested chunks.
+const PIPELINE_VERSION: &str = "0.1.0";
+
+/// Elements whose content is never part of the main text.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "nav", "header", "footer", "aside", "form",
+    "head",
+];
+
+/// Elements that end a paragraph.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "br", "li", "ul", "ol", "h1", "h2", "h3", "h4", "h5", "h6", "tr", "table",
+    "section", "article", "main", "blockquote", "pre", "dd", "dt",
+];
+
+/// Settings for URL ingestion (`[ingestion]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IngestionConfig {
+    /// Default chunk size in characters.
+    pub chunk_size: usize,
+    /// Default characters shared by consecutive chunks.
+    pub chunk_overlap: usize,
+    /// Largest response body fetched, in bytes.
+    pub max_bytes: usize,
+    /// Most chunks submitted per URL.
+    pub max_chunks: usize,
+    /// Fetch timeout in seconds.
+    pub timeout_secs: u64,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 1000,
+            chunk_overlap: 200,
+            max_bytes: 5 * 1024 * 1024,
+            max_chunks: 256,
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// Fetches, extracts, and chunks URLs.
+#[derive(Clone)]
+pub struct Ingester {
+    client: reqwest::Client,
+    config: IngestionConfig,
+}
+
+impl Ingester {
+    /// Create an ingester, validating `config`.
+    pub fn new(config: IngestionConfig) -> Result<Self, ChitinError> {
+        validate_chunking(config.chunk_size, config.chunk_overlap)
+            .map_err(ChitinError::InvalidState)?;
+        if config.max_bytes == 0 || config.max_chunks == 0 {
+            return Err(ChitinError::InvalidState(
+                "max_bytes and max_chunks must be positive".to_string(),
+            ));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(concat!("chitin-daemon/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self { client, config })
+    }
+
+    /// Fetch `request.url`, extract its text, and chunk it.
+    pub async fn ingest(
+        &self,
+        request: &IngestUrlRequest,
+    ) -> Result<IngestedDocument, ChitinError> {
+        let chunk_size = request.chunk_size.unwrap_or(self.config.chunk_size);
+        let chunk_overlap = request.chunk_overlap.unwrap_or(self.config.chunk_overlap);
+        validate_chunking(chunk_size, chunk_overlap).map_err(ChitinError::InvalidState)?;
+        if !request.url.starts_with("http://") && !request.url.starts_with("https://") {
+            return Err(ChitinError::InvalidState(format!(
+                "Only http(s) URLs can be ingested: {}",
+                request.url
+            )));
+        }
+
+        // Fetch.
+        let response = self
+            .client
+            .get(&request.url)
+            .send()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to fetch {}: {}", request.url, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ChitinError::Network(format!("{} returned {}", request.url, status)));
+        }
+        let final_url = response.url().to_string();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap_or("").trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.config.max_bytes as u64)
+        {
+            return Err(too_large(&request.url, self.config.max_bytes));
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ChitinError::Network(format!("Failed to read {}: {}", request.url, e)))?;
+        if body.len() > self.config.max_bytes {
+            return Err(too_large(&request.url, self.config.max_bytes));
+        }
+        let fetch = PipelineStep {
+            name: "url-fetch".to_string(),
+            version: PIPELINE_VERSION.to_string(),
+            params: serde_json::json!({
+                "url": request.url,
+                "final_url": final_url,
+                "status": status.as_u16(),
+                "content_type": content_type,
+                "bytes": body.len(),
+            }),
+            hash: None,
+        };
+
+        // Extract.
+        let raw = String::from_utf8_lossy(&body);
+        let (extractor, title, text) = match content_type.as_str() {
+            "text/html" | "application/xhtml+xml" | "" => {
+                ("html-main-text", html_title(&raw), html_main_text(&raw))
+            }
+            "text/plain" | "text/markdown" => ("plain", None, normalize_text(&raw)),
+            other => {
+                return Err(ChitinError::InvalidState(format!(
+                    "Cannot extract text from {} content",
+                    other
+                )));
+            }
+        };
+        if text.is_empty() {
+            return Err(ChitinError::InvalidState(format!(
+                "No text found at {}",
+                request.url
+            )));
+        }
+        let extract = PipelineStep {
+            name: "text-extract".to_string(),
+            version: PIPELINE_VERSION.to_string(),
+            params: serde_json::json!({ "extractor": extractor, "chars": text.chars().count() }),
+            hash: None,
+        };
+
+        // Chunk.
+        let mut chunks = chunk_text(&text, chunk_size, chunk_overlap);
+        if chunks.len() > self.config.max_chunks {
+            tracing::warn!(
+                "Ingest {}: keeping the first {} of {} chunks",
+                request.url,
+                self.config.max_chunks,
+                chunks.len()
+            );
+            chunks.truncate(self.config.max_chunks);
+        }
+        let count = chunks.len();
+        let chunks = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| IngestedChunk {
+                content,
+                pipeline: vec![
+                    fetch.clone(),
+                    extract.clone(),
+                    PipelineStep {
+                        name: "chunk".to_string(),
+                        version: PIPELINE_VERSION.to_string(),
+                        params: serde_json::json!({
+                            "chunk_size": chunk_size,
+                            "chunk_overlap": chunk_overlap,
+                            "index": index,
+                            "count": count,
+                        }),
+                        hash: None,
+                    },
+                ],
+            })
+            .collect();
+        tracing::info!("Ingested {} ({} chunks)", request.url, count);
+        Ok(IngestedDocument { title, chunks })
+    }
+
+    /// Adapt `ingest` into the RPC server's `polyp/ingest_url` callback.
+    pub fn ingest_callback(&self) -> IngestCallback {
+        let ingester = self.clone();
+        Arc::new(move |request| {
+            let ingester = ingester.clone();
+            Box::pin(async move { ingester.ingest(&request).await.map_err(|e| e.to_string()) })
+        })
+    }
+}
+
+fn too_large(url: &str, max_bytes: usize) -> ChitinError {
+    ChitinError::InvalidState(format!("{} is larger than {} bytes", url, max_bytes))
+}
+
+// ---------------------------------------------------------------------------
+// Extraction
+// ---------------------------------------------------------------------------
+
+/// The contents of the document's `<title>`, if any.
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = normalize_text(&decode_entities(&html[start..end]));
+    (!title.is_empty()).then_some(title)
+}
+
+/// The main text of an HTML document, as paragraphs separated by blank
+/// lines.
+fn html_main_text(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let body = ["main", "article"]
+        .iter()
+        .find_map(|element| {
+            let start = lower.find(&format!("<{}", element))?;
+            let end = lower.rfind(&format!("</{}", element))?;
+            (end > start).then(|| &html[start..end])
+        })
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut skipping: Option<String> = None;
+    let mut rest = body;
+    while let Some(open) = rest.find('<') {
+        if skipping.is_none() {
+            text.push_str(&decode_entities(&rest[..open]));
+        }
+        let close = match rest[open..].find('>') {
+            Some(close) => open + close,
+            None => break,
+        };
+        let tag = &rest[open + 1..close];
+        rest = &rest[close + 1..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match &skipping {
+            Some(skipped) => {
+                if closing && *skipped == name {
+                    skipping = None;
+                }
+            }
+            None if !closing
+                && !tag.ends_with('/')
+                && SKIPPED_ELEMENTS.contains(&name.as_str()) =>
+            {
+                skipping = Some(name);
+            }
+            None if BLOCK_ELEMENTS.contains(&name.as_str()) => text.push_str("\n\n"),
+            None => text.push(' '),
+        }
+    }
+    if skipping.is_none() {
+        text.push_str(&decode_entities(rest));
+    }
+    normalize_text(&text)
+}
+
+/// Decode the common named and numeric HTML character references.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let entity = &rest[1..end];
+        let ch = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => match entity.strip_prefix('#') {
+                Some(num) => match num.strip_prefix('x').or_else(|| num.strip_prefix('X')) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => num.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => None,
+            },
+        };
+        match ch {
+            Some(ch) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Collapse whitespace within paragraphs and separate paragraphs (text
+/// split by blank lines) with a single blank line.
+fn normalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
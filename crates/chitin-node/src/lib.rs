@@ -0,0 +1,52 @@
+// crates/chitin-node/src/lib.rs
+//
+// chitin-node: Coral/Tide node processes, scheduler, and state machine for
+// the Chitin Protocol, as a library.
+//
+// `NodeBuilder` runs a node in-process, returning a `NodeHandle` for direct
+// calls and shutdown; `chitin-daemon` is a thin binary on top of it, and
+// other services and end-to-end tests can embed nodes the same way.
+
+pub mod backup;
+pub mod block_source;
+pub mod config;
+pub mod consensus_runner;
+pub mod coral;
+pub mod dedup;
+pub mod drift_monitor;
+pub mod durability;
+pub mod embedding;
+pub mod epoch_events;
+pub mod gossip;
+pub mod hardening_pipeline;
+pub mod ingestion;
+pub mod logs;
+pub mod metrics;
+pub mod moderation;
+pub mod network;
+pub mod node;
+pub mod peers;
+pub mod pruning;
+pub mod reload;
+pub mod replication;
+pub mod rerank;
+pub mod runtime_state;
+pub mod scrub;
+pub mod scheduler;
+pub mod seed;
+pub mod shard_proxy;
+pub mod shared;
+pub mod snapshot;
+pub mod state;
+pub mod supervisor;
+pub mod sync_loop;
+pub mod systemd;
+pub mod telemetry;
+pub mod tide;
+pub mod unlock;
+pub mod validator;
+pub mod webhooks;
+
+// Re-export the main node types for ergonomic access.
+pub use config::DaemonConfig;
+pub use node::{NodeBuilder, NodeHandle};
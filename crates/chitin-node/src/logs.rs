@@ -0,0 +1,149 @@
+// crates/chitin-node/src/logs.rs
+//
+// In-memory log buffer for `admin/logs`.
+//
+// `LogBuffer` is a tracing layer installed with the log subscriber at
+// startup, next to `OtlpLayer`. It keeps the most recent events that pass
+// the log filter, numbered in order, so `chitin admin logs --follow` can
+// poll for the entries after the last one it printed. An event's fields
+// other than its message are appended to the message as `key=value`.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use chrono::{SecondsFormat, Utc};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use chitin_rpc::handlers::admin::{GetLogsRequest, GetLogsResponse, LogEntry};
+use chitin_rpc::LogQueryCallback;
+
+/// Entries a `LogBuffer` holds by default.
+pub const DEFAULT_LOG_CAPACITY: usize = 2000;
+
+/// Entries returned when a request does not say how many.
+const DEFAULT_LINES: u32 = 100;
+
+/// Held entries, newest last.
+#[derive(Default)]
+struct Entries {
+    next_seq: u64,
+    entries: VecDeque<(Level, LogEntry)>,
+}
+
+/// Tracing layer keeping the most recent log events.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Entries>>,
+    capacity: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+impl LogBuffer {
+    /// A buffer holding up to `capacity` entries, dropping the oldest.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::default(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// The last `request.lines` held entries at or above `request.level`
+    /// that contain `request.filter` and come after `request.since`.
+    pub fn query(&self, request: &GetLogsRequest) -> GetLogsResponse {
+        // Unknown levels are refused by the handler; treat them as "trace".
+        let min_level = request
+            .level
+            .as_deref()
+            .and_then(|level| Level::from_str(level).ok())
+            .unwrap_or(Level::TRACE);
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let matching: Vec<&LogEntry> = inner
+            .entries
+            .iter()
+            .filter(|(level, entry)| {
+                // More verbose levels compare greater.
+                *level <= min_level
+                    && request.since.is_none_or(|since| entry.seq > since)
+                    && request
+                        .filter
+                        .as_deref()
+                        .is_none_or(|pattern| entry.message.contains(pattern))
+            })
+            .map(|(_, entry)| entry)
+            .collect();
+        let lines = request.lines.unwrap_or(DEFAULT_LINES) as usize;
+        let skip = matching.len().saturating_sub(lines);
+        GetLogsResponse {
+            total_available: matching.len() as u32,
+            entries: matching.into_iter().skip(skip).cloned().collect(),
+        }
+    }
+
+    /// Adapt `query` into the RPC server's `admin/logs` callback.
+    pub fn query_callback(&self) -> LogQueryCallback {
+        let buffer = self.clone();
+        Arc::new(move |request| buffer.query(&request))
+    }
+
+    fn push(&self, level: Level, mut entry: LogEntry) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.next_seq += 1;
+        entry.seq = inner.next_seq;
+        if inner.entries.len() == self.capacity {
+            inner.entries.pop_front();
+        }
+        inner.entries.push_back((level, entry));
+    }
+}
+
+/// Collects an event's message and its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBuffer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.push(
+            *metadata.level(),
+            LogEntry {
+                seq: 0,
+                timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                level: metadata.level().to_string(),
+                target: metadata.target().to_string(),
+                message: visitor.message + &visitor.fields,
+            },
+        );
+    }
+}
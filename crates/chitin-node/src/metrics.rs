@@ -0,0 +1,795 @@
+// crates/chitin-node/src/metrics.rs
+//
+// Prometheus metrics for the Chitin daemon.
+//
+// `DaemonMetrics` holds the metric families. Event metrics (consensus runs,
+// sync rounds) are recorded where they happen; gauges are sampled from the
+// store, vector index, epoch state, and peer registry on each scrape. When
+// `[metrics] enabled`, `MetricsExporter` serves them in the OpenMetrics text
+// format at `GET /metrics` on `listen_addr`:
+//
+// - store: `chitin_store_polyps{state}`, `chitin_store_write_stopped`,
+//   `chitin_store_memtable_bytes`, `chitin_store_immutable_memtables`,
+//   `chitin_store_pending_compaction_bytes`
+// - index: `chitin_index_vectors`
+// - consensus: `chitin_epoch`, `chitin_block`, `chitin_epoch_phase{phase}`,
+//   `chitin_consensus_runs_total{outcome}`, `chitin_consensus_duration_seconds`,
+//   `chitin_consensus_validators`, `chitin_consensus_corals`
+// - sync: `chitin_sync_rounds_total`, `chitin_sync_round_duration_seconds`,
+//   `chitin_sync_lag`, `chitin_sync_polyps_pulled_total{peer}`,
+//   `chitin_sync_polyps_pushed_total{peer}`, `chitin_sync_peer_missing{peer}`
+// - P2P: `chitin_peers_known`, `chitin_peers_alive`, `chitin_peer_score{peer}`
+// - pruning: `chitin_pruned_entries_total{kind}`, `chitin_pruned_bytes_total{kind}`
+// - embedding cache: `chitin_embedding_cache_entries`,
+//   `chitin_embedding_cache_hits_total`, `chitin_embedding_cache_misses_total`,
+//   `chitin_embedding_cache_evictions_total`
+// - reranking: `chitin_rerank_runs_total{outcome}`,
+//   `chitin_rerank_passages_total`, `chitin_rerank_seconds_total`
+// - query cache: `chitin_query_cache_entries`, `chitin_query_cache_hit_ratio`,
+//   `chitin_query_cache_hits_total`, `chitin_query_cache_misses_total`,
+//   `chitin_query_cache_evictions_total`,
+//   `chitin_query_cache_invalidations_total`
+// - scrubbing: `chitin_scrub_passes_total`, `chitin_scrub_polyps_checked_total`,
+//   `chitin_scrub_corrupt_total{kind}`, `chitin_scrub_repaired_total{source}`,
+//   `chitin_scrub_unrepaired`
+// - admission: `chitin_admission_admitted_total`,
+//   `chitin_admission_refused_total{pressure}`
+
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::Duration;
+
+use prometheus_client::encoding::text::encode;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::{Registry, Unit};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use chitin_consensus::epoch::EpochPhase;
+use chitin_core::{ChitinError, PolypState};
+use chitin_rpc::handlers::query::RerankStats;
+use chitin_rpc::QueryCache;
+use chitin_store::{AdmissionControl, InMemoryVectorIndex, Reclaimed, RocksStore};
+
+use crate::peers::PeerRegistry;
+use crate::shared::DaemonSharedState;
+
+/// Largest scrape request read, in bytes.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Polyp states reported by `chitin_store_polyps`.
+const STATES: &[(&str, PolypState)] = &[
+    ("draft", PolypState::Draft),
+    ("soft", PolypState::Soft),
+    ("under_review", PolypState::UnderReview),
+    ("approved", PolypState::Approved),
+    ("hardened", PolypState::Hardened),
+    ("rejected", PolypState::Rejected),
+];
+
+/// Epoch phases reported by `chitin_epoch_phase`.
+const PHASES: &[(&str, EpochPhase)] = &[
+    ("open", EpochPhase::Open),
+    ("scoring", EpochPhase::Scoring),
+    ("committing", EpochPhase::Committing),
+    ("closed", EpochPhase::Closed),
+];
+
+type Labels = Vec<(String, String)>;
+
+fn label(name: &str, value: &str) -> Labels {
+    vec![(name.to_string(), value.to_string())]
+}
+
+/// Settings for the Prometheus scrape endpoint (`[metrics]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Whether to serve `GET /metrics`.
+    pub enabled: bool,
+    /// Address to listen on.
+    pub listen_addr: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: "127.0.0.1:9615".to_string(),
+        }
+    }
+}
+
+struct Families {
+    store_polyps: Family<Labels, Gauge>,
+    store_write_stopped: Gauge,
+    store_memtable_bytes: Gauge,
+    store_immutable_memtables: Gauge,
+    store_pending_compaction_bytes: Gauge,
+    index_vectors: Gauge,
+    epoch: Gauge,
+    block: Gauge,
+    epoch_phase: Family<Labels, Gauge>,
+    consensus_runs: Family<Labels, Counter>,
+    consensus_duration: Histogram,
+    consensus_validators: Gauge,
+    consensus_corals: Gauge,
+    sync_rounds: Counter,
+    sync_round_duration: Histogram,
+    sync_lag: Gauge,
+    sync_polyps_pulled: Family<Labels, Counter>,
+    sync_polyps_pushed: Family<Labels, Counter>,
+    sync_peer_missing: Family<Labels, Gauge>,
+    peers_known: Gauge,
+    peers_alive: Gauge,
+    peer_score: Family<Labels, Gauge<f64, AtomicU64>>,
+    pruned_entries: Family<Labels, Counter>,
+    pruned_bytes: Family<Labels, Counter>,
+    embedding_cache_entries: Gauge,
+    embedding_cache_hits: Counter,
+    embedding_cache_misses: Counter,
+    embedding_cache_evictions: Counter,
+    rerank_runs: Family<Labels, Counter>,
+    rerank_passages: Counter,
+    rerank_time: Counter<f64, AtomicU64>,
+    query_cache_entries: Gauge,
+    query_cache_hit_ratio: Gauge<f64, AtomicU64>,
+    query_cache_hits: Counter,
+    query_cache_misses: Counter,
+    query_cache_evictions: Counter,
+    query_cache_invalidations: Counter,
+    scrub_passes: Counter,
+    scrub_checked: Counter,
+    scrub_corrupt: Family<Labels, Counter>,
+    scrub_repaired: Family<Labels, Counter>,
+    scrub_unrepaired: Gauge,
+    admission_admitted: Counter,
+    admission_refused: Family<Labels, Counter>,
+}
+
+/// The daemon's metric families, shared by every task that records them.
+#[derive(Clone)]
+pub struct DaemonMetrics {
+    registry: Arc<Registry>,
+    families: Arc<Families>,
+}
+
+impl DaemonMetrics {
+    /// Create and register every metric family, all at zero.
+    pub fn new() -> Self {
+        let families = Families {
+            store_polyps: Family::default(),
+            store_write_stopped: Gauge::default(),
+            store_memtable_bytes: Gauge::default(),
+            store_immutable_memtables: Gauge::default(),
+            store_pending_compaction_bytes: Gauge::default(),
+            index_vectors: Gauge::default(),
+            epoch: Gauge::default(),
+            block: Gauge::default(),
+            epoch_phase: Family::default(),
+            consensus_runs: Family::default(),
+            consensus_duration: Histogram::new(exponential_buckets(0.01, 2.0, 14)),
+            consensus_validators: Gauge::default(),
+            consensus_corals: Gauge::default(),
+            sync_rounds: Counter::default(),
+            sync_round_duration: Histogram::new(exponential_buckets(0.01, 2.0, 14)),
+            sync_lag: Gauge::default(),
+            sync_polyps_pulled: Family::default(),
+            sync_polyps_pushed: Family::default(),
+            sync_peer_missing: Family::default(),
+            peers_known: Gauge::default(),
+            peers_alive: Gauge::default(),
+            peer_score: Family::default(),
+            pruned_entries: Family::default(),
+            pruned_bytes: Family::default(),
+            embedding_cache_entries: Gauge::default(),
+            embedding_cache_hits: Counter::default(),
+            embedding_cache_misses: Counter::default(),
+            embedding_cache_evictions: Counter::default(),
+            rerank_runs: Family::default(),
+            rerank_passages: Counter::default(),
+            rerank_time: Counter::default(),
+            query_cache_entries: Gauge::default(),
+            query_cache_hit_ratio: Gauge::default(),
+            query_cache_hits: Counter::default(),
+            query_cache_misses: Counter::default(),
+            query_cache_evictions: Counter::default(),
+            query_cache_invalidations: Counter::default(),
+            scrub_passes: Counter::default(),
+            scrub_checked: Counter::default(),
+            scrub_corrupt: Family::default(),
+            scrub_repaired: Family::default(),
+            scrub_unrepaired: Gauge::default(),
+            admission_admitted: Counter::default(),
+            admission_refused: Family::default(),
+        };
+
+        let mut registry = Registry::with_prefix("chitin");
+
+        registry.register(
+            "store_polyps",
+            "Polyps held, by state",
+            families.store_polyps.clone(),
+        );
+        registry.register(
+            "store_write_stopped",
+            "1 while RocksDB has stopped writes, 0 otherwise",
+            families.store_write_stopped.clone(),
+        );
+        registry.register_with_unit(
+            "store_memtable",
+            "Size of the store's active and immutable memtables",
+            Unit::Bytes,
+            families.store_memtable_bytes.clone(),
+        );
+        registry.register(
+            "store_immutable_memtables",
+            "Store memtables waiting to be flushed",
+            families.store_immutable_memtables.clone(),
+        );
+        registry.register_with_unit(
+            "store_pending_compaction",
+            "Estimated data compaction must rewrite to catch up",
+            Unit::Bytes,
+            families.store_pending_compaction_bytes.clone(),
+        );
+        registry.register(
+            "index_vectors",
+            "Vectors in the index",
+            families.index_vectors.clone(),
+        );
+        registry.register("epoch", "Current epoch", families.epoch.clone());
+        registry.register("block", "Current block height", families.block.clone());
+        registry.register(
+            "epoch_phase",
+            "1 for the current epoch phase, 0 otherwise",
+            families.epoch_phase.clone(),
+        );
+        registry.register(
+            "consensus_runs",
+            "Epoch consensus runs, by outcome",
+            families.consensus_runs.clone(),
+        );
+        registry.register_with_unit(
+            "consensus_duration",
+            "Time to run epoch consensus",
+            Unit::Seconds,
+            families.consensus_duration.clone(),
+        );
+        registry.register(
+            "consensus_validators",
+            "Validators in the current weight matrix",
+            families.consensus_validators.clone(),
+        );
+        registry.register(
+            "consensus_corals",
+            "Coral columns in the current weight matrix",
+            families.consensus_corals.clone(),
+        );
+        registry.register(
+            "sync_rounds",
+            "Pull-sync rounds completed",
+            families.sync_rounds.clone(),
+        );
+        registry.register_with_unit(
+            "sync_round_duration",
+            "Time to run a pull-sync round",
+            Unit::Seconds,
+            families.sync_round_duration.clone(),
+        );
+        registry.register(
+            "sync_lag",
+            "Most polyps missing relative to any one peer",
+            families.sync_lag.clone(),
+        );
+        registry.register(
+            "sync_polyps_pulled",
+            "Polyps pulled from each peer",
+            families.sync_polyps_pulled.clone(),
+        );
+        registry.register(
+            "sync_polyps_pushed",
+            "Polyps pushed to each peer by gossip",
+            families.sync_polyps_pushed.clone(),
+        );
+        registry.register(
+            "sync_peer_missing",
+            "Polyps each peer holds that are missing locally",
+            families.sync_peer_missing.clone(),
+        );
+        registry.register("peers_known", "Known peers", families.peers_known.clone());
+        registry.register(
+            "peers_alive",
+            "Peers whose last contact succeeded",
+            families.peers_alive.clone(),
+        );
+        registry.register(
+            "peer_score",
+            "Trust in each peer's data",
+            families.peer_score.clone(),
+        );
+        registry.register(
+            "pruned_entries",
+            "Store entries deleted by pruning, by kind",
+            families.pruned_entries.clone(),
+        );
+        registry.register_with_unit(
+            "pruned",
+            "Store space reclaimed by pruning, by kind",
+            Unit::Bytes,
+            families.pruned_bytes.clone(),
+        );
+        registry.register(
+            "embedding_cache_entries",
+            "Vectors in the embedding cache",
+            families.embedding_cache_entries.clone(),
+        );
+        registry.register(
+            "embedding_cache_hits",
+            "Embedding lookups served from the cache",
+            families.embedding_cache_hits.clone(),
+        );
+        registry.register(
+            "embedding_cache_misses",
+            "Embedding lookups that went to a provider",
+            families.embedding_cache_misses.clone(),
+        );
+        registry.register(
+            "embedding_cache_evictions",
+            "Vectors evicted from the embedding cache",
+            families.embedding_cache_evictions.clone(),
+        );
+        registry.register(
+            "rerank_runs",
+            "Search rerank stages, by outcome",
+            families.rerank_runs.clone(),
+        );
+        registry.register(
+            "rerank_passages",
+            "Passages rescored by the reranker",
+            families.rerank_passages.clone(),
+        );
+        registry.register_with_unit(
+            "rerank",
+            "Time spent waiting for the reranker",
+            Unit::Seconds,
+            families.rerank_time.clone(),
+        );
+        registry.register(
+            "query_cache_entries",
+            "Search responses in the query cache",
+            families.query_cache_entries.clone(),
+        );
+        registry.register(
+            "query_cache_hit_ratio",
+            "Fraction of searches served from the query cache since startup",
+            families.query_cache_hit_ratio.clone(),
+        );
+        registry.register(
+            "query_cache_hits",
+            "Searches served from the query cache",
+            families.query_cache_hits.clone(),
+        );
+        registry.register(
+            "query_cache_misses",
+            "Searches that went to the index",
+            families.query_cache_misses.clone(),
+        );
+        registry.register(
+            "query_cache_evictions",
+            "Search responses evicted from the query cache or expired",
+            families.query_cache_evictions.clone(),
+        );
+        registry.register(
+            "query_cache_invalidations",
+            "Search responses dropped from the query cache by new polyps",
+            families.query_cache_invalidations.clone(),
+        );
+        registry.register(
+            "scrub_passes",
+            "Integrity scrubs of the whole polyp store completed",
+            families.scrub_passes.clone(),
+        );
+        registry.register(
+            "scrub_polyps_checked",
+            "Stored polyps checked by the integrity scrubber",
+            families.scrub_checked.clone(),
+        );
+        registry.register(
+            "scrub_corrupt",
+            "Corrupt store entries found by the integrity scrubber, by kind",
+            families.scrub_corrupt.clone(),
+        );
+        registry.register(
+            "scrub_repaired",
+            "Corrupt store entries repaired, by source",
+            families.scrub_repaired.clone(),
+        );
+        registry.register(
+            "scrub_unrepaired",
+            "Corrupt polyps found by the last scrub and not repaired",
+            families.scrub_unrepaired.clone(),
+        );
+        registry.register(
+            "admission_admitted",
+            "Polyp writes admitted by store admission control",
+            families.admission_admitted.clone(),
+        );
+        registry.register(
+            "admission_refused",
+            "Polyp writes refused or paused by store admission control, by pressure",
+            families.admission_refused.clone(),
+        );
+
+        Self {
+            registry: Arc::new(registry),
+            families: Arc::new(families),
+        }
+    }
+
+    /// Record an epoch consensus run.
+    pub fn observe_consensus(&self, duration: Duration, succeeded: bool) {
+        let outcome = if succeeded { "ok" } else { "error" };
+        self.families
+            .consensus_runs
+            .get_or_create(&label("outcome", outcome))
+            .inc();
+        self.families
+            .consensus_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record a completed pull-sync round.
+    pub fn observe_sync_round(&self, duration: Duration) {
+        self.families.sync_rounds.inc();
+        self.families
+            .sync_round_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record entries of `kind` deleted by a pruning pass.
+    pub fn observe_pruned(&self, kind: &str, reclaimed: Reclaimed) {
+        let labels = label("kind", kind);
+        self.families
+            .pruned_entries
+            .get_or_create(&labels)
+            .inc_by(reclaimed.entries);
+        self.families
+            .pruned_bytes
+            .get_or_create(&labels)
+            .inc_by(reclaimed.bytes);
+    }
+
+    /// Record `checked` polyps checked by the integrity scrubber.
+    pub fn observe_scrubbed(&self, checked: u64) {
+        self.families.scrub_checked.inc_by(checked);
+    }
+
+    /// Record a corrupt entry of `kind`, and the `source` it was repaired
+    /// from, if it was.
+    pub fn observe_scrub_corruption(&self, kind: &str, repaired_from: Option<&str>) {
+        self.families
+            .scrub_corrupt
+            .get_or_create(&label("kind", kind))
+            .inc();
+        if let Some(source) = repaired_from {
+            self.families
+                .scrub_repaired
+                .get_or_create(&label("source", source))
+                .inc();
+        }
+    }
+
+    /// Record a completed scrub pass that left `unrepaired` polyps corrupt.
+    pub fn observe_scrub_pass(&self, unrepaired: usize) {
+        self.families.scrub_passes.inc();
+        self.families.scrub_unrepaired.set(unrepaired as i64);
+    }
+
+    /// Encode every metric in the OpenMetrics text format.
+    pub fn encode(&self) -> Result<String, ChitinError> {
+        let mut body = String::new();
+        encode(&mut body, &self.registry)
+            .map_err(|e| ChitinError::Serialization(format!("Failed to encode metrics: {}", e)))?;
+        Ok(body)
+    }
+}
+
+impl Default for DaemonMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serves `GET /metrics`, sampling gauges from the attached sources.
+#[derive(Clone)]
+pub struct MetricsExporter {
+    config: MetricsConfig,
+    shared: DaemonSharedState,
+    store: Option<Arc<RocksStore>>,
+    index: Option<Arc<InMemoryVectorIndex>>,
+    peers: Option<Arc<PeerRegistry>>,
+    rerank: Option<Arc<RerankStats>>,
+    query_cache: Option<Arc<QueryCache>>,
+    admission: Option<Arc<AdmissionControl>>,
+}
+
+impl MetricsExporter {
+    /// Create an exporter for `shared`'s metrics and epoch state.
+    pub fn new(config: MetricsConfig, shared: DaemonSharedState) -> Self {
+        Self {
+            config,
+            shared,
+            store: None,
+            index: None,
+            peers: None,
+            rerank: None,
+            query_cache: None,
+            admission: None,
+        }
+    }
+
+    /// Report polyp counts from `store`.
+    pub fn with_store(mut self, store: Arc<RocksStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Report the size of `index`.
+    pub fn with_index(mut self, index: Arc<InMemoryVectorIndex>) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Report peer and sync metrics from `registry`.
+    pub fn with_peer_registry(mut self, registry: Arc<PeerRegistry>) -> Self {
+        self.peers = Some(registry);
+        self
+    }
+
+    /// Report search reranking from `stats`.
+    pub fn with_rerank_stats(mut self, stats: Arc<RerankStats>) -> Self {
+        self.rerank = Some(stats);
+        self
+    }
+
+    /// Report the search query cache's size and hit rate.
+    pub fn with_query_cache(mut self, cache: Arc<QueryCache>) -> Self {
+        self.query_cache = Some(cache);
+        self
+    }
+
+    /// Report writes admitted and refused by `admission`.
+    pub fn with_admission(mut self, admission: Arc<AdmissionControl>) -> Self {
+        self.admission = Some(admission);
+        self
+    }
+
+    /// Sample every gauge from its source.
+    async fn refresh(&self) {
+        let metrics = &self.shared.metrics.families;
+
+        if let Some(store) = &self.store {
+            for (tag, state) in STATES {
+                match store.count_polyps_by_state(state) {
+                    Ok(count) => {
+                        metrics
+                            .store_polyps
+                            .get_or_create(&label("state", tag))
+                            .set(count as i64);
+                    }
+                    Err(e) => tracing::debug!("Metrics: failed to count {} polyps: {}", tag, e),
+                }
+            }
+            match store.health() {
+                Ok(health) => {
+                    metrics
+                        .store_write_stopped
+                        .set(i64::from(health.write_stopped));
+                    metrics
+                        .store_memtable_bytes
+                        .set(health.memtable_bytes as i64);
+                    metrics
+                        .store_immutable_memtables
+                        .set(health.immutable_memtables as i64);
+                    metrics
+                        .store_pending_compaction_bytes
+                        .set(health.pending_compaction_bytes as i64);
+                }
+                Err(e) => tracing::debug!("Metrics: failed to sample store health: {}", e),
+            }
+        }
+        if let Some(index) = &self.index {
+            metrics.index_vectors.set(index.len() as i64);
+        }
+
+        {
+            let em = self.shared.epoch_manager.read().await;
+            metrics.epoch.set(em.current_epoch() as i64);
+            metrics.block.set(em.current_block() as i64);
+            for (tag, phase) in PHASES {
+                let current = i64::from(em.phase() == phase);
+                metrics
+                    .epoch_phase
+                    .get_or_create(&label("phase", tag))
+                    .set(current);
+            }
+        }
+        if let Some(cache) = &self.shared.embedding_cache {
+            // The cache counts since startup; advance the counters by what
+            // was recorded since the last scrape.
+            let stats = cache.stats();
+            metrics.embedding_cache_entries.set(stats.entries as i64);
+            let advance = |counter: &Counter, total: u64| {
+                counter.inc_by(total.saturating_sub(counter.get()));
+            };
+            advance(&metrics.embedding_cache_hits, stats.hits);
+            advance(&metrics.embedding_cache_misses, stats.misses);
+            advance(&metrics.embedding_cache_evictions, stats.evictions);
+        }
+        if let Some(rerank) = &self.rerank {
+            // Also counted since startup.
+            let stats = rerank.snapshot();
+            for (outcome, total) in [
+                ("ok", stats.reranked),
+                ("timeout", stats.timeouts),
+                ("error", stats.failures),
+            ] {
+                let counter = metrics.rerank_runs.get_or_create(&label("outcome", outcome));
+                counter.inc_by(total.saturating_sub(counter.get()));
+            }
+            let passages = &metrics.rerank_passages;
+            passages.inc_by(stats.passages.saturating_sub(passages.get()));
+            let seconds = stats.latency_us as f64 / 1e6;
+            let time = &metrics.rerank_time;
+            time.inc_by((seconds - time.get()).max(0.0));
+        }
+        if let Some(cache) = &self.query_cache {
+            // Also counted since startup.
+            let stats = cache.stats();
+            metrics.query_cache_entries.set(stats.entries as i64);
+            metrics.query_cache_hit_ratio.set(stats.hit_rate());
+            for (counter, total) in [
+                (&metrics.query_cache_hits, stats.hits),
+                (&metrics.query_cache_misses, stats.misses),
+                (&metrics.query_cache_evictions, stats.evictions),
+                (&metrics.query_cache_invalidations, stats.invalidations),
+            ] {
+                counter.inc_by(total.saturating_sub(counter.get()));
+            }
+        }
+        if let Some(admission) = &self.admission {
+            // Also counted since startup.
+            let stats = admission.stats();
+            let admitted = &metrics.admission_admitted;
+            admitted.inc_by(stats.admitted.saturating_sub(admitted.get()));
+            for (pressure, total) in &stats.refused {
+                let counter = metrics
+                    .admission_refused
+                    .get_or_create(&label("pressure", pressure));
+                counter.inc_by(total.saturating_sub(counter.get()));
+            }
+        }
+        {
+            let wm = self.shared.weight_matrix.read().await;
+            metrics.consensus_validators.set(wm.weights.len() as i64);
+            metrics
+                .consensus_corals
+                .set(wm.weights.first().map_or(0, |r| r.len()) as i64);
+        }
+
+        if let Some(registry) = &self.peers {
+            let peers = registry.all_peer_states().await;
+            metrics.peers_known.set(peers.len() as i64);
+            metrics
+                .peers_alive
+                .set(peers.iter().filter(|p| p.alive).count() as i64);
+            metrics.peer_score.clear();
+            for peer in &peers {
+                metrics
+                    .peer_score
+                    .get_or_create(&label("peer", &peer.url))
+                    .set(peer.score);
+            }
+
+            let sync = registry.sync_metrics().snapshot();
+            metrics.sync_lag.set(sync.lag() as i64);
+            metrics.sync_peer_missing.clear();
+            for stats in &sync.peers {
+                let peer = label("peer", &stats.peer_url);
+                // The sync metrics count since startup; advance the counters
+                // by what was recorded since the last scrape.
+                let pulled = metrics.sync_polyps_pulled.get_or_create(&peer);
+                pulled.inc_by(stats.polyps_pulled.saturating_sub(pulled.get()));
+                let pushed = metrics.sync_polyps_pushed.get_or_create(&peer);
+                pushed.inc_by(stats.polyps_pushed.saturating_sub(pushed.get()));
+                metrics
+                    .sync_peer_missing
+                    .get_or_create(&peer)
+                    .set(stats.missing as i64);
+            }
+        }
+    }
+
+    /// Serve scrapes until the process exits. Returns immediately unless
+    /// `[metrics] enabled`.
+    pub async fn serve(self) -> Result<(), ChitinError> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        let listener = TcpListener::bind(&self.config.listen_addr)
+            .await
+            .map_err(|e| {
+                ChitinError::Network(format!("Failed to bind {}: {}", self.config.listen_addr, e))
+            })?;
+        tracing::info!(
+            "Metrics endpoint listening on http://{}/metrics",
+            self.config.listen_addr
+        );
+
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::debug!("Metrics: accept failed: {}", e);
+                    continue;
+                }
+            };
+            let exporter = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = exporter.handle(stream).await {
+                    tracing::debug!("Metrics: scrape failed: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Answer one HTTP request.
+    async fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        let request_line = String::from_utf8_lossy(&request);
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next(), parts.next());
+
+        let (status, content_type, body) = match (method, path) {
+            (Some("GET"), Some("/metrics")) => {
+                self.refresh().await;
+                match self.shared.metrics.encode() {
+                    Ok(body) => (
+                        "200 OK",
+                        "application/openmetrics-text; version=1.0.0; charset=utf-8",
+                        body,
+                    ),
+                    Err(e) => ("500 Internal Server Error", "text/plain", e.to_string()),
+                }
+            }
+            (Some("GET"), _) => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+            _ => (
+                "405 Method Not Allowed",
+                "text/plain",
+                "Method not allowed\n".to_string(),
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
@@ -0,0 +1,217 @@
+// crates/chitin-node/src/moderation.rs
+//
+// Content moderation for the daemon: builds the `[moderation]` policies
+// (see `chitin_consensus::moderation`), adds the external classifier hook,
+// and rejects approved polyps that fail them, or their Reef Zone's policy
+// (license and size limits), before hardening.
+//
+// The classifier receives a JSON POST per reviewed polyp:
+//
+//   {"polyp_id": "...", "stage": "submission" | "pre_hardening",
+//    "content": "...", "content_type": "...", "language": "...",
+//    "reef_zone": "...", "creator": "did:chitin:..."}
+//
+// and answers `{"allow": true}` or
+// `{"allow": false, "code": "...", "reason": "..."}`. Unreachable
+// classifiers and non-2xx answers reject with `classifier_unavailable`,
+// unless `fail_open` is set.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::moderation::{
+    self, ClassifierConfig, ModerationConfig, ModerationPolicy, ModerationRecord,
+    ModerationStage, PolicySet, Violation, CODE_CLASSIFIER_UNAVAILABLE,
+};
+use chitin_core::traits::PolypStore;
+use chitin_core::{ChitinError, Polyp, PolypState};
+use chitin_store::RocksStore;
+
+use crate::gossip;
+use crate::shared::DaemonSharedState;
+
+/// The policies `config` configures: blocked keywords, zone rules, then
+/// the classifier. `None` if moderation is disabled or has no rules.
+pub fn build(config: &ModerationConfig) -> Result<Option<Arc<PolicySet>>, ChitinError> {
+    config.validate()?;
+    if !config.enabled || !config.has_rules() {
+        return Ok(None);
+    }
+    let mut policies = config.local_policies();
+    if let Some(classifier) = &config.classifier {
+        policies.push(Arc::new(HttpClassifier::new(classifier)?));
+    }
+    Ok(Some(Arc::new(PolicySet::new(policies))))
+}
+
+/// An external classifier reached over HTTP.
+pub struct HttpClassifier {
+    client: reqwest::Client,
+    url: String,
+    fail_open: bool,
+}
+
+/// Body POSTed to the classifier.
+#[derive(Serialize)]
+struct ClassifyRequest<'a> {
+    polyp_id: uuid::Uuid,
+    stage: ModerationStage,
+    content: &'a str,
+    content_type: &'a str,
+    language: Option<&'a str>,
+    reef_zone: Option<&'a str>,
+    creator: &'a str,
+}
+
+/// The classifier's answer.
+#[derive(Deserialize)]
+struct ClassifyResponse {
+    allow: bool,
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl HttpClassifier {
+    pub fn new(config: &ClassifierConfig) -> Result<Self, ChitinError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            fail_open: config.fail_open,
+        })
+    }
+
+    async fn classify(
+        &self,
+        polyp: &Polyp,
+        stage: ModerationStage,
+    ) -> Result<ClassifyResponse, ChitinError> {
+        let payload = &polyp.subject.payload;
+        let body = ClassifyRequest {
+            polyp_id: polyp.id,
+            stage,
+            content: &payload.content,
+            content_type: &payload.content_type,
+            language: payload.language.as_deref(),
+            reef_zone: polyp.reef_zone.as_deref(),
+            creator: &polyp.subject.provenance.creator.did,
+        };
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ChitinError::Network(format!("classifier {}: {}", self.url, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ChitinError::Network(format!(
+                "classifier {} returned {}",
+                self.url, status
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| ChitinError::Serialization(format!("classifier response: {}", e)))
+    }
+}
+
+#[async_trait]
+impl ModerationPolicy for HttpClassifier {
+    fn name(&self) -> &str {
+        "classifier"
+    }
+
+    async fn review(
+        &self,
+        polyp: &Polyp,
+        stage: ModerationStage,
+    ) -> Result<Option<Violation>, ChitinError> {
+        match self.classify(polyp, stage).await {
+            Ok(answer) if answer.allow => Ok(None),
+            Ok(answer) => Ok(Some(Violation {
+                code: answer.code.unwrap_or_else(|| "classifier_rejected".to_string()),
+                reason: answer
+                    .reason
+                    .unwrap_or_else(|| "rejected by the content classifier".to_string()),
+            })),
+            Err(e) if self.fail_open => Err(e),
+            Err(e) => Ok(Some(Violation::new(CODE_CLASSIFIER_UNAVAILABLE, e.to_string()))),
+        }
+    }
+}
+
+/// Name recorded for rejections by a Reef Zone's policy.
+pub const ZONE_POLICY: &str = "zone_policy";
+
+/// Review approved polyps before hardening, returning those allowed.
+///
+/// Rejected polyps move to Rejected with their moderation record, and the
+/// transition is announced to peers.
+pub async fn moderate_before_hardening(
+    shared: &DaemonSharedState,
+    store: &RocksStore,
+    polyps: &[Polyp],
+    epoch: u64,
+) -> Vec<Polyp> {
+    let mut allowed = Vec::with_capacity(polyps.len());
+    for polyp in polyps {
+        let Some(record) = review_before_hardening(shared, polyp).await else {
+            allowed.push(polyp.clone());
+            continue;
+        };
+        tracing::warn!(
+            "Polyp {} rejected before hardening by {} ({}): {}",
+            polyp.id,
+            record.policy,
+            record.code,
+            record.reason
+        );
+        let mut rejected = polyp.clone();
+        rejected.state = PolypState::Rejected;
+        rejected.updated_at = chrono::Utc::now();
+        if let Err(e) = moderation::save_record(store, &polyp.id, &record) {
+            tracing::warn!("Failed to record rejection of polyp {}: {}", polyp.id, e);
+        }
+        match store.save_polyp(&rejected).await {
+            Ok(()) => gossip::announce_state_change(shared, store, &rejected, epoch),
+            Err(e) => tracing::warn!("Failed to reject polyp {}: {}", polyp.id, e),
+        }
+    }
+    allowed
+}
+
+/// Why `polyp` may not harden: its zone's policy, then the configured
+/// moderation policies. Moderation errors allow it.
+async fn review_before_hardening(
+    shared: &DaemonSharedState,
+    polyp: &Polyp,
+) -> Option<ModerationRecord> {
+    let zone_policy = shared.taxonomy.policy(polyp.reef_zone.as_deref());
+    if let Some((code, reason)) = zone_policy.violation(polyp) {
+        return Some(ModerationRecord {
+            code: code.to_string(),
+            reason,
+            policy: ZONE_POLICY.to_string(),
+            stage: ModerationStage::PreHardening,
+            rejected_at: chrono::Utc::now(),
+        });
+    }
+    let policies = shared.moderation.as_ref()?;
+    match policies.moderate(polyp, ModerationStage::PreHardening).await {
+        Ok(record) => record,
+        Err(e) => {
+            tracing::warn!("Moderation of polyp {} failed, allowing: {}", polyp.id, e);
+            None
+        }
+    }
+}
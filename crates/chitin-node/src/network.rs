@@ -0,0 +1,176 @@
+// crates/chitin-node/src/network.rs
+//
+// Named network profiles (devnet, testnet, mainnet).
+//
+// Selecting a network with `network = "<name>"` or `--network <name>` applies
+// that network's defaults on top of the configuration: its data directory
+// suffix (so nodes on different networks never share a database), bootstrap
+// peers, genesis trust, economics parameters, and network ID. The network ID
+// is exchanged in `peer/announce`, and peers on a different network are
+// refused. Built-in profiles can be overridden, and custom networks defined,
+// with `[networks.<name>]` tables. A genesis file's economics replace the
+// profile's, and a genesis file alone defines a network.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::genesis::Genesis;
+use chitin_core::ChitinError;
+use chitin_economics::token::RAO_PER_CTN;
+pub use chitin_economics::EconomicsParams;
+use chitin_reputation::genesis::GenesisValidator;
+
+/// Built-in network names.
+pub const BUILTIN_NETWORKS: &[&str] = &["devnet", "testnet", "mainnet"];
+
+/// The defaults one network applies to the configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkProfile {
+    /// Profile name (e.g. "testnet").
+    pub name: String,
+    /// Network ID exchanged with peers; nodes only peer within one ID.
+    pub network_id: String,
+    /// Subdirectory of `data_dir` holding this network's data.
+    pub data_dir_suffix: String,
+    /// Peers added to the configured peer list.
+    pub bootstrap_peers: Vec<String>,
+    /// Genesis validators, used when `genesis_trust` is not configured.
+    pub genesis_trust: Vec<GenesisValidator>,
+    /// Emission and staking parameters.
+    pub economics: EconomicsParams,
+}
+
+impl NetworkProfile {
+    /// The built-in profile named `name`, if any.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let economics = match name {
+            // Devnet stakes are nominal so that test nodes can join freely.
+            "devnet" => EconomicsParams {
+                coral_minimum_stake_rao: RAO_PER_CTN,
+                tide_minimum_stake_rao: RAO_PER_CTN,
+                ..EconomicsParams::default()
+            },
+            "testnet" | "mainnet" => EconomicsParams::default(),
+            _ => return None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            network_id: format!("chitin-{}", name),
+            data_dir_suffix: name.to_string(),
+            bootstrap_peers: Vec::new(),
+            genesis_trust: Vec::new(),
+            economics,
+        })
+    }
+
+    /// The profile of a network defined only by its genesis file: its network
+    /// ID and economics, with no data directory suffix or bootstrap peers.
+    pub fn from_genesis(genesis: &Genesis) -> Self {
+        Self {
+            name: genesis.network_id.clone(),
+            network_id: genesis.network_id.clone(),
+            data_dir_suffix: String::new(),
+            bootstrap_peers: Vec::new(),
+            genesis_trust: genesis.trust_seeds.clone(),
+            economics: genesis.economics.clone(),
+        }
+    }
+
+    /// Resolve the profile named `name`: a built-in profile with any
+    /// `[networks.<name>]` overrides applied, or a custom network defined
+    /// entirely by its table.
+    pub fn resolve(
+        name: &str,
+        overrides: &HashMap<String, NetworkOverrides>,
+    ) -> Result<Self, ChitinError> {
+        let table = overrides.get(name);
+        let mut profile = match (Self::builtin(name), table) {
+            (Some(profile), _) => profile,
+            (None, Some(table)) => match &table.network_id {
+                Some(network_id) => Self {
+                    name: name.to_string(),
+                    network_id: network_id.clone(),
+                    data_dir_suffix: name.to_string(),
+                    bootstrap_peers: Vec::new(),
+                    genesis_trust: Vec::new(),
+                    economics: EconomicsParams::default(),
+                },
+                None => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "custom network {:?} must set network_id",
+                        name
+                    )))
+                }
+            },
+            (None, None) => {
+                return Err(ChitinError::InvalidState(format!(
+                    "unknown network {:?}; use one of {:?} or define [networks.{}]",
+                    name, BUILTIN_NETWORKS, name
+                )))
+            }
+        };
+        if let Some(table) = table {
+            table.apply(&mut profile);
+        }
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Check that the profile is usable.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        if self.network_id.trim().is_empty() {
+            return Err(ChitinError::InvalidState(format!(
+                "network {:?} has an empty network_id",
+                self.name
+            )));
+        }
+        let suffix = &self.data_dir_suffix;
+        if suffix.is_empty() || suffix.contains(['/', '\\']) || suffix == "." || suffix == ".." {
+            return Err(ChitinError::InvalidState(format!(
+                "network {:?} data_dir_suffix must be a single directory name, got {:?}",
+                self.name, suffix
+            )));
+        }
+        for peer in &self.bootstrap_peers {
+            if !peer.starts_with("http://") && !peer.starts_with("https://") {
+                return Err(ChitinError::InvalidState(format!(
+                    "network {:?} bootstrap peer must be http(s): {:?}",
+                    self.name, peer
+                )));
+            }
+        }
+        self.economics.validate()
+    }
+}
+
+/// A `[networks.<name>]` table. Unset fields keep the built-in defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkOverrides {
+    pub network_id: Option<String>,
+    pub data_dir_suffix: Option<String>,
+    pub bootstrap_peers: Option<Vec<String>>,
+    pub genesis_trust: Option<Vec<GenesisValidator>>,
+    pub economics: Option<EconomicsParams>,
+}
+
+impl NetworkOverrides {
+    fn apply(&self, profile: &mut NetworkProfile) {
+        if let Some(network_id) = &self.network_id {
+            profile.network_id = network_id.clone();
+        }
+        if let Some(suffix) = &self.data_dir_suffix {
+            profile.data_dir_suffix = suffix.clone();
+        }
+        if let Some(peers) = &self.bootstrap_peers {
+            profile.bootstrap_peers = peers.clone();
+        }
+        if let Some(genesis) = &self.genesis_trust {
+            profile.genesis_trust = genesis.clone();
+        }
+        if let Some(economics) = &self.economics {
+            profile.economics = economics.clone();
+        }
+    }
+}
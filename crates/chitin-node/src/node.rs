@@ -0,0 +1,2109 @@
+// crates/chitin-node/src/node.rs
+//
+// Building, starting, and stopping a node in-process.
+//
+// `NodeBuilder` starts a Coral, Tide, Hybrid, or Seed node from a
+// `DaemonConfig`, the way `chitin-daemon` does: it opens the stores, builds
+// the shared state, spawns the supervised background jobs and the node's own
+// loop, and returns a `NodeHandle`. A store or identity can be passed in
+// instead of read from the data directory and key files, and the RPC server
+// need not listen at all: the handle answers RPC methods in-process through
+// `call`. `NodeHandle::stop` cancels every job, saves the runtime state, and
+// wipes the hotkey.
+
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use chitin_core::identity::{NodeIdentity, NodeType};
+use chitin_core::keystore::{EncryptedKeystore, SecretKey, Zeroizing};
+use chitin_core::{ChitinError, IdentityRegistry};
+use chitin_drift::versioning::VersionRegistry;
+use chitin_reputation::centroid::CentroidClassifier;
+use chitin_reputation::domain_store::DomainTrustStore;
+use chitin_rpc::handlers::admin::ListTasksResponse;
+use chitin_rpc::server::JsonRpcRequest;
+use chitin_rpc::{ChitinRpcServer, RpcConfig};
+use chitin_store::{HardenedStore, InMemoryVectorIndex, IpfsClient, RocksStore};
+
+use crate::backup::BackupSet;
+use crate::block_source::BlockSource;
+use crate::config::DaemonConfig;
+use crate::coral::CoralNode;
+use crate::embedding::EmbeddingPool;
+use crate::logs::LogBuffer;
+use crate::metrics::MetricsExporter;
+use crate::network::NetworkProfile;
+use crate::peers::PeerRegistry;
+use crate::reload::{ConfigHandle, LogLevelSetter};
+use crate::replication::{ReplicationRole, SigningGate};
+use crate::runtime_state::StatePersister;
+use crate::scheduler::EpochScheduler;
+use crate::scrub::ScrubStatus;
+use crate::seed::SeedNode;
+use crate::shared::DaemonSharedState;
+use crate::snapshot::Snapshotter;
+use crate::state::{NodeState, NodeStateMachine};
+use crate::supervisor::{Priority, RestartPolicy, TaskSupervisor};
+use crate::tide::TideNode;
+use crate::unlock::{self, UnlockOptions};
+use crate::webhooks::WebhookNotifier;
+use crate::{
+    dedup, drift_monitor, durability, epoch_events, gossip, moderation, pruning, reload, scrub,
+    seed, shard_proxy, snapshot, sync_loop, systemd,
+};
+
+/// Builds and starts a node.
+pub struct NodeBuilder {
+    config: DaemonConfig,
+    /// File the configuration is reloaded from; unset disables reloading.
+    config_path: Option<String>,
+    identity: Option<(NodeIdentity, Option<SecretKey>)>,
+    unlock: UnlockOptions,
+    /// Polyp store to use instead of opening one in the data directory.
+    store: Option<Arc<RocksStore>>,
+    serve_rpc: bool,
+    log_level: Option<LogLevelSetter>,
+    /// Recent log events, served by `admin/logs`.
+    log_buffer: Option<LogBuffer>,
+    /// Block source to use instead of the configured one.
+    block_source: Option<Box<dyn BlockSource>>,
+}
+
+impl NodeBuilder {
+    /// A node running `config`.
+    pub fn new(config: DaemonConfig) -> Self {
+        Self {
+            config,
+            config_path: None,
+            identity: None,
+            unlock: UnlockOptions::default(),
+            store: None,
+            serve_rpc: true,
+            log_level: None,
+            log_buffer: None,
+            block_source: None,
+        }
+    }
+
+    /// A Coral node with the default configuration.
+    pub fn coral() -> Self {
+        Self::of_type("coral")
+    }
+
+    /// A Tide node with the default configuration.
+    pub fn tide() -> Self {
+        Self::of_type("tide")
+    }
+
+    /// A Hybrid (Coral + Tide) node with the default configuration.
+    pub fn hybrid() -> Self {
+        Self::of_type("hybrid")
+    }
+
+    /// A Seed node with the default configuration.
+    pub fn seed() -> Self {
+        Self::of_type("seed")
+    }
+
+    fn of_type(node_type: &str) -> Self {
+        Self::new(DaemonConfig {
+            node_type: node_type.to_string(),
+            ..DaemonConfig::default()
+        })
+    }
+
+    /// Adjust the configuration.
+    pub fn configure(mut self, f: impl FnOnce(&mut DaemonConfig)) -> Self {
+        f(&mut self.config);
+        self
+    }
+
+    /// Keep stores under `data_dir`.
+    pub fn with_data_dir(mut self, data_dir: &str) -> Self {
+        self.config.data_dir = data_dir.to_string();
+        self
+    }
+
+    /// Store polyps (or, on a Seed node, checkpoints and runtime state) in
+    /// `store` instead of opening one in the data directory.
+    pub fn with_store(mut self, store: Arc<RocksStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Run as `identity`, signing with `signing_key`, instead of reading the
+    /// configured key files.
+    pub fn with_identity(mut self, identity: NodeIdentity, signing_key: Option<SecretKey>) -> Self {
+        self.identity = Some((identity, signing_key));
+        self
+    }
+
+    /// Unlock an encrypted hotkey keystore with `unlock`.
+    pub fn with_unlock(mut self, unlock: UnlockOptions) -> Self {
+        self.unlock = unlock;
+        self
+    }
+
+    /// Reload hot-reloadable settings from `path` on SIGHUP, file change, or
+    /// `admin/config/reload`.
+    pub fn with_config_file(mut self, path: &str) -> Self {
+        self.config_path = Some(path.to_string());
+        self
+    }
+
+    /// Apply the configured `log_level` (and reloaded ones) with `setter`.
+    pub fn with_log_level_setter(mut self, setter: LogLevelSetter) -> Self {
+        self.log_level = Some(setter);
+        self
+    }
+
+    /// Serve `admin/logs` from `buffer`, a layer of the log subscriber.
+    pub fn with_log_buffer(mut self, buffer: LogBuffer) -> Self {
+        self.log_buffer = Some(buffer);
+        self
+    }
+
+    /// Serve RPC only in-process, through `NodeHandle::call`.
+    pub fn without_rpc_server(mut self) -> Self {
+        self.serve_rpc = false;
+        self
+    }
+
+    /// Follow blocks from `source` instead of the `[block_source]` config.
+    pub fn with_block_source(mut self, source: Box<dyn BlockSource>) -> Self {
+        self.block_source = Some(source);
+        self
+    }
+
+    /// Start the node, returning once its jobs are running.
+    pub async fn start(self) -> Result<NodeHandle, Box<dyn std::error::Error>> {
+        let NodeBuilder {
+            config: mut daemon_config,
+            config_path,
+            identity,
+            unlock,
+            store: polyp_store,
+            serve_rpc,
+            log_level,
+            log_buffer,
+            block_source,
+        } = self;
+
+        let network = daemon_config
+            .apply_network()
+            .map_err(|e| format!("Invalid network: {}", e))?;
+
+        // A genesis file sets the network's economics, or defines the network.
+        let genesis = match &daemon_config.genesis_file {
+            Some(path) => Some(
+                daemon_config
+                    .load_genesis(&expand_tilde(path), network.as_ref())
+                    .map_err(|e| format!("Invalid genesis file: {}", e))?,
+            ),
+            None => None,
+        };
+        let network = match (network, &genesis) {
+            (Some(mut network), Some(genesis)) => {
+                network.economics = genesis.economics.clone();
+                Some(network)
+            }
+            (None, Some(genesis)) => Some(NetworkProfile::from_genesis(genesis)),
+            (network, None) => network,
+        };
+        let network_id = network.as_ref().map(|n| n.network_id.clone());
+
+        // A standby follows its primary, syncing polyps from it like any peer.
+        daemon_config
+            .replication
+            .validate()
+            .map_err(|e| format!("Invalid replication config: {}", e))?;
+        if daemon_config.replication.role == ReplicationRole::Standby {
+            if !matches!(daemon_config.node_type.as_str(), "coral" | "hybrid") {
+                return Err("Only coral and hybrid nodes can run as a standby".into());
+            }
+            if let Some(primary_url) = &daemon_config.replication.primary_url {
+                if !daemon_config.peers.contains(primary_url) {
+                    daemon_config.peers.push(primary_url.clone());
+                }
+            }
+        }
+
+        daemon_config
+            .dedup
+            .validate()
+            .map_err(|e| format!("Invalid dedup config: {}", e))?;
+        daemon_config
+            .task_assignment
+            .validate()
+            .map_err(|e| format!("Invalid task_assignment config: {}", e))?;
+        let moderation = moderation::build(&daemon_config.moderation)
+            .map_err(|e| format!("Invalid moderation config: {}", e))?;
+        let hardening_policies = moderation
+            .clone()
+            .filter(|_| daemon_config.moderation.before_hardening);
+        let submission_policies = moderation.filter(|_| daemon_config.moderation.on_submit);
+
+        // Snapshots are loaded into a polyp store and vector index.
+        daemon_config
+            .snapshot
+            .validate()
+            .map_err(|e| format!("Invalid snapshot config: {}", e))?;
+        if daemon_config.snapshot.bootstrap_from.is_some()
+            && !matches!(daemon_config.node_type.as_str(), "coral" | "hybrid")
+        {
+            return Err("Only coral and hybrid nodes can bootstrap from a snapshot".into());
+        }
+
+        if daemon_config.sync_interval_secs == 0 {
+            return Err("Invalid sync interval: sync_interval_secs must be positive".into());
+        }
+
+        // Shared handle to the live configuration, for hot reload.
+        let watch_config = config_path.is_some();
+        let mut config_handle = ConfigHandle::new(
+            config_path.as_deref().unwrap_or_default(),
+            daemon_config.clone(),
+        );
+        if let Some(set_log_level) = log_level {
+            if let Err(e) = set_log_level(&daemon_config.log_level) {
+                tracing::warn!("Invalid log_level {:?}: {}", daemon_config.log_level, e);
+            }
+            config_handle = config_handle.with_log_level_setter(set_log_level);
+        }
+
+        tracing::info!("Node type: {}", daemon_config.node_type);
+        if let Some(network) = &network {
+            tracing::info!("Network: {} ({})", network.name, network.network_id);
+        }
+        tracing::info!("Data directory: {}", daemon_config.data_dir);
+        tracing::info!(
+            "RPC endpoint: {}:{}",
+            daemon_config.rpc_host,
+            daemon_config.rpc_port
+        );
+        tracing::info!("P2P port: {}", daemon_config.p2p_port);
+        tracing::info!("Blocks per epoch: {}", daemon_config.blocks_per_epoch);
+        if daemon_config.archival {
+            tracing::info!("Archival mode: retaining all history");
+        }
+
+        // ---------------------------------------------------------------
+        // Phase 2: Load cryptographic identity from key files.
+        // ---------------------------------------------------------------
+        let (node_identity, signing_key) = match identity {
+            Some(identity) => identity,
+            None => load_node_identity(&daemon_config, &unlock)
+                .map_err(|e| format!("Could not unlock hotkey: {}", e))?,
+        };
+
+        if node_identity.is_placeholder() {
+            tracing::warn!(
+                "Running with placeholder identity — no key files found. \
+                 Generate keys with `chitin init`."
+            );
+        } else {
+            tracing::info!("Node DID: {}", node_identity.did);
+        }
+
+        // ---------------------------------------------------------------
+        // Phase 4: Construct shared state infrastructure.
+        // ---------------------------------------------------------------
+
+        // Create IPFS client and HardenedStore (optional — requires IPFS running).
+        let ipfs_client = IpfsClient::new(&daemon_config.ipfs_api_url);
+        let data_dir = expand_tilde(&daemon_config.data_dir);
+        let hardened_db_path = format!("{}/hardened_rocksdb", data_dir);
+
+        let durability = daemon_config.durability;
+        let hardened_store = match RocksStore::open_with_durability(&hardened_db_path, durability) {
+            Ok(cache_db) => {
+                let hs = HardenedStore::new(cache_db, ipfs_client);
+                tracing::info!("HardenedStore initialized at {}", hardened_db_path);
+                Some(Arc::new(hs))
+            }
+            Err(e) => {
+                tracing::warn!("Failed to open HardenedStore: {}. Hardening disabled.", e);
+                None
+            }
+        };
+
+        let genesis_trust = match &genesis {
+            Some(genesis) => genesis.trust(),
+            None => daemon_config
+                .genesis()
+                .map_err(|e| format!("Invalid genesis trust: {}", e))?,
+        };
+        if !genesis_trust.is_empty() {
+            tracing::info!(
+                "Genesis trust: {} validators",
+                genesis_trust.validators.len()
+            );
+        }
+
+        // Open the domain-scoped trust store (falls back to in-memory).
+        let reputation_db_path = format!("{}/reputation_rocksdb", data_dir);
+        let reputation_db =
+            RocksStore::open_with_durability(&reputation_db_path, durability).map(Arc::new);
+
+        // `admin/backup` copies every database opened here, plus the polyp store.
+        let mut backups = BackupSet::new(&data_dir);
+        if let Some(hardened_store) = &hardened_store {
+            backups = backups.with_hardened_store(hardened_store.clone());
+        }
+        if let Ok(db) = &reputation_db {
+            backups = backups.with_store("reputation_rocksdb", db.clone());
+        }
+
+        // The identity registry shares the reputation database (falls back
+        // to in-memory).
+        let identities = match &reputation_db {
+            Ok(db) => IdentityRegistry::open(db.clone()).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Failed to open identity registry: {}. UIDs will not persist.",
+                    e
+                );
+                IdentityRegistry::new()
+            }),
+            Err(_) => IdentityRegistry::new(),
+        };
+        let trust_store = match reputation_db
+            .and_then(|db| DomainTrustStore::open(db, daemon_config.decay_config()))
+        {
+            Ok(ts) => {
+                tracing::info!(
+                    "Trust store initialized at {} ({} domains)",
+                    reputation_db_path,
+                    ts.domains().len()
+                );
+                ts
+            }
+            Err(e) => {
+                tracing::warn!("Failed to open trust store: {}. Trust will not persist.", e);
+                DomainTrustStore::new(daemon_config.decay_config())
+            }
+        }
+        .with_pre_trusted(daemon_config.trust_pre_trusted.clone())
+        .with_genesis(genesis_trust)
+        .with_checkpoint_retention(if daemon_config.archival {
+            None
+        } else {
+            Some(daemon_config.pruning.reputation_checkpoints)
+        })
+        .with_pinned_checkpoints(daemon_config.pinned_reputation_checkpoints.clone());
+
+        let taxonomy = daemon_config
+            .taxonomy()
+            .map_err(|e| format!("Invalid zone taxonomy: {}", e))?;
+
+        let shard_set = daemon_config
+            .shard_set()
+            .map_err(|e| format!("Invalid shard assignment: {}", e))?;
+        if !shard_set.is_full() {
+            tracing::info!(
+                "Holding shards {:?} of {}",
+                shard_set.shards(),
+                shard_set.num_shards()
+            );
+        }
+        let sync_throttle = Arc::new(
+            daemon_config
+                .sync_throttle()
+                .map_err(|e| format!("Invalid sync throttle: {}", e))?,
+        );
+        let model_registry = daemon_config
+            .model_registry()
+            .map_err(|e| format!("Invalid model versions: {}", e))?;
+        let block_source = match block_source {
+            Some(source) => source,
+            None => daemon_config
+                .block_source()
+                .map_err(|e| format!("Invalid block source: {}", e))?,
+        };
+        let embedding_providers = daemon_config
+            .embedding_providers()
+            .map_err(|e| format!("Invalid embedding config: {}", e))?;
+        let reranking = daemon_config
+            .reranking()
+            .map_err(|e| format!("Invalid rerank config: {}", e))?;
+        let query_cache = daemon_config.query_cache.build().map(Arc::new);
+        let ingester = daemon_config
+            .ingester()
+            .map_err(|e| format!("Invalid ingestion config: {}", e))?;
+        let validator = daemon_config
+            .validator()
+            .map_err(|e| format!("Invalid validator config: {}", e))?
+            .with_identity(node_identity.hotkey, signing_key.clone());
+        let webhooks = WebhookNotifier::start(&daemon_config.webhooks)
+            .map_err(|e| format!("Invalid webhooks config: {}", e))?;
+        if webhooks.is_some() {
+            tracing::info!(
+                "Webhooks: {} endpoints",
+                daemon_config.webhooks.endpoints.len()
+            );
+        }
+
+        // Create DaemonSharedState.
+        let shared_state =
+            DaemonSharedState::new(daemon_config.blocks_per_epoch, hardened_store.clone())
+                .with_trust_store(trust_store)
+                .with_identity_registry(identities)
+                .with_domain_classifier(CentroidClassifier::new(
+                    daemon_config.domain_confidence_threshold,
+                ))
+                .with_taxonomy(taxonomy)
+                .with_model_registry(model_registry)
+                .with_molt_policy(daemon_config.molt_successor_policy)
+                .with_network(network)
+                .with_archival(daemon_config.archival)
+                .with_webhooks(webhooks)
+                .with_dedup(daemon_config.dedup.clone())
+                .with_moderation(hardening_policies);
+        if let Some(genesis) = &genesis {
+            let seeded = shared_state
+                .apply_genesis(genesis)
+                .await
+                .map_err(|e| format!("Could not apply genesis: {}", e))?;
+            tracing::info!(
+                "Genesis {}: {} nodes, {} trust edges seeded",
+                genesis.hash().unwrap_or_default(),
+                genesis.validators.len(),
+                seeded
+            );
+        }
+        // This node has a UID once the genesis or a metagraph lists it; it
+        // never takes one for itself, so that every node agrees on UIDs.
+        if !node_identity.is_placeholder() {
+            let uid = shared_state
+                .identities
+                .read()
+                .await
+                .uid_of_did(&node_identity.did);
+            match uid {
+                Some(uid) => tracing::info!("Node UID: {}", uid),
+                None => tracing::info!("Node has no UID until the metagraph lists it"),
+            }
+        }
+
+        // Create broadcast channel for epoch events.
+        let (event_tx, _) = tokio::sync::broadcast::channel::<epoch_events::EpochEvent>(64);
+
+        // Initialize the node state machine.
+        let mut state_machine = NodeStateMachine::new();
+        state_machine.transition(NodeState::Syncing)?;
+        state_machine.transition(NodeState::Ready)?;
+
+        // Peers identify this node by its DID, unless it is a placeholder.
+        let public_did = (!node_identity.is_placeholder()).then(|| node_identity.did.clone());
+
+        // Background jobs are supervised and listed by `admin/tasks`.
+        let supervisor = TaskSupervisor::new();
+
+        // Start the appropriate node based on the configured type.
+        let shutdown = supervisor.shutdown_signal();
+        let running = match daemon_config.node_type.as_str() {
+            "coral" => {
+                let node = match &polyp_store {
+                    Some(store) => CoralNode::with_store(&daemon_config, store.clone()),
+                    None => CoralNode::new(&daemon_config)?,
+                }
+                .with_identity(node_identity.clone(), signing_key.clone());
+                let store = node.store();
+                migrate_store(&store)?;
+                let admission = daemon_config.admission.build(store.clone()).map(Arc::new);
+                let signing_gate = SigningGate::open(&daemon_config.replication, store.clone())
+                    .map_err(|e| format!("Failed to open replication state: {}", e))?;
+                let index = Arc::new(InMemoryVectorIndex::new());
+                let embedding_cache = daemon_config
+                    .embedding
+                    .cache(store.clone())
+                    .map_err(|e| format!("Failed to open embedding cache: {}", e))?;
+                let shared_state = shared_state.clone().with_embedding_cache(embedding_cache);
+                restore_model_registry(&shared_state, &store).await;
+                restore_economics(&shared_state, &store).await?;
+                // Load a trusted snapshot into an empty store before syncing.
+                if let Some(source) = &daemon_config.snapshot.bootstrap_from {
+                    snapshot::bootstrap(
+                        source,
+                        &daemon_config.snapshot,
+                        &store,
+                        &index,
+                        &shared_state,
+                        &IpfsClient::new(&daemon_config.ipfs_api_url),
+                        &daemon_config.peers,
+                    )
+                    .await
+                    .map_err(|e| format!("Snapshot bootstrap failed: {}", e))?;
+                }
+                let mut persister = StatePersister::new(store.clone(), shared_state.clone());
+                let mut exporter =
+                    MetricsExporter::new(daemon_config.metrics.clone(), shared_state.clone())
+                        .with_store(store.clone())
+                        .with_index(index.clone());
+
+                let rpc_config = RpcConfig {
+                    host: daemon_config.rpc_host.clone(),
+                    port: daemon_config.rpc_port,
+                };
+                let mut rpc_server = ChitinRpcServer::new(rpc_config, store.clone(), index.clone())
+                    .with_peer_info(daemon_config.peers.clone())
+                    .with_identity(node_identity.clone(), signing_key.clone())
+                    .with_self_url(daemon_config.self_url.clone())
+                    .with_network_id(network_id.clone())
+                    .with_archival(daemon_config.archival)
+                    .with_epoch_manager(shared_state.epoch_manager.clone())
+                    .with_consensus_result(shared_state.last_consensus_result.clone())
+                    .with_weight_matrix(shared_state.weight_matrix.clone())
+                    .with_bond_matrix(shared_state.bond_matrix.clone())
+                    .with_metagraph_manager(shared_state.metagraph_manager.clone())
+                    .with_ledger(shared_state.ledger.clone())
+                    .with_stake_manager(shared_state.stake_manager.clone())
+                    .with_identity_registry(shared_state.identities.clone())
+                    .with_request_auth(daemon_config.request_auth.clone())
+                    .with_hardened_store(hardened_store.clone())
+                    .with_trust_store(shared_state.trust_store.clone())
+                    .with_taxonomy(shared_state.taxonomy.clone())
+                    .with_duplicate_detector(shared_state.duplicates.clone())
+                    .with_moderation(submission_policies.clone())
+                    .with_task_assignment(daemon_config.task_assignment.clone())
+                    .with_search_trust_weight(daemon_config.search_trust_weight)
+                    .with_search_mmr_lambda(daemon_config.search_mmr_lambda)
+                    .with_start_time(shared_state.start_time)
+                    .with_shard_set(shard_set.clone())
+                    .with_sync_throttle(sync_throttle.clone())
+                    .with_model_registry(shared_state.model_registry.clone())
+                    .with_embedder(
+                        EmbeddingPool::start(
+                            &daemon_config.embedding,
+                            embedding_providers.clone(),
+                            shared_state.clone(),
+                        )
+                        .embed_callback(),
+                    )
+                    .with_ingester(ingester.ingest_callback())
+                    .with_backup(
+                        backups
+                            .clone()
+                            .with_store("rocksdb", store.clone())
+                            .backup_callback(),
+                    )
+                    .with_snapshot(
+                        Snapshotter::new(
+                            &data_dir,
+                            store.clone(),
+                            index.clone(),
+                            shared_state.clone(),
+                            IpfsClient::new(&daemon_config.ipfs_api_url),
+                        )
+                        .with_identity(node_identity.hotkey, signing_key.clone())
+                        .snapshot_callback(),
+                    );
+                if let Some(log_buffer) = &log_buffer {
+                    rpc_server = rpc_server.with_log_query(log_buffer.query_callback());
+                }
+                if let Some(reranking) = &reranking {
+                    rpc_server = rpc_server.with_reranking(reranking.clone());
+                    exporter = exporter.with_rerank_stats(reranking.stats.clone());
+                }
+                if let Some(cache) = &query_cache {
+                    rpc_server = rpc_server.with_query_cache(cache.clone());
+                    exporter = exporter.with_query_cache(cache.clone());
+                }
+                if let Some(admission) = &admission {
+                    rpc_server = rpc_server.with_admission(admission.clone());
+                    exporter = exporter.with_admission(admission.clone());
+                }
+
+                // Wire up peer networking if peers are configured.
+                let mut announce_registry = None;
+                if !daemon_config.peers.is_empty() {
+                    let registry = Arc::new(
+                        PeerRegistry::new(
+                            daemon_config.self_url.clone(),
+                            daemon_config.peers.clone(),
+                        )
+                        .with_network_id(network_id.clone())
+                        .with_identity(
+                            public_did.clone(),
+                            node_identity.hotkey,
+                            signing_key.clone(),
+                        )
+                        .with_webhooks(shared_state.webhooks.clone())
+                        .with_signing_gate(signing_gate.clone())
+                        .with_identity_registry(shared_state.identities.clone()),
+                    );
+                    tracing::info!(
+                        "Peer networking enabled: {} peers configured",
+                        daemon_config.peers.len()
+                    );
+                    config_handle = config_handle.with_peer_registry(registry.clone());
+                    persister = persister.with_peer_registry(registry.clone());
+                    exporter = exporter.with_peer_registry(registry.clone());
+
+                    // Set up gossip callback for polyp broadcast with real DID.
+                    let gossip_registry = registry.clone();
+                    let gossip_did = if !node_identity.is_placeholder() {
+                        Some(node_identity.did.clone())
+                    } else {
+                        None
+                    };
+                    rpc_server = rpc_server
+                        .with_sync_metrics(registry.sync_metrics().clone())
+                        .with_peer_list(registry.peer_list())
+                        .with_identity_conflicts(registry.conflict_list())
+                        .with_announce_callback(registry.announce_callback())
+                        .with_gossip_callback(Arc::new(move |polyp| {
+                            gossip::broadcast_polyp(
+                                gossip_registry.clone(),
+                                polyp,
+                                gossip_did.clone(),
+                            );
+                        }))
+                        .with_shard_proxy(shard_proxy::shard_proxy(
+                            registry.clone(),
+                            shard_set.num_shards(),
+                        ));
+
+                    // Announce to all peers once the RPC server is up.
+                    announce_registry = Some(registry.clone());
+
+                    // Spawn sync loop (30s interval).
+                    let sync_registry = registry.clone();
+                    let sync_store = store.clone();
+                    let sync_index = index.clone();
+                    let sync_config = config_handle.clone();
+                    let sync_priority = daemon_config.sync_priority();
+                    let sync_epochs = shared_state.epoch_manager.clone();
+                    let sync_shards = shard_set.clone();
+                    let throttle = sync_throttle.clone();
+                    let sync_metrics = shared_state.metrics.clone();
+                    let sync_cache = query_cache.clone();
+                    let sync_admission = admission.clone();
+                    supervisor.spawn(
+                        "sync_loop",
+                        Priority::High,
+                        RestartPolicy::OnFailure,
+                        move || {
+                            sync_loop::run_sync_loop(
+                                sync_registry.clone(),
+                                sync_store.clone(),
+                                sync_index.clone(),
+                                sync_config.clone(),
+                                sync_priority.clone(),
+                                sync_epochs.clone(),
+                                sync_shards.clone(),
+                                throttle.clone(),
+                                sync_metrics.clone(),
+                                sync_cache.clone(),
+                                sync_admission.clone(),
+                            )
+                        },
+                    );
+                }
+
+                // Reload hot-reloadable settings on SIGHUP, config file change,
+                // or `admin/config/reload`; serve and update the config.
+                rpc_server = rpc_server.with_config_access(
+                    config_handle.view_callback(),
+                    config_handle.update_callback(),
+                );
+                if watch_config {
+                    rpc_server = rpc_server.with_config_reload(config_handle.reload_callback());
+                    let reload_handle = config_handle.clone();
+                    supervisor.spawn(
+                        "config_watcher",
+                        Priority::Normal,
+                        RestartPolicy::OnFailure,
+                        move || reload::watch_config(reload_handle.clone()),
+                    );
+                }
+
+                // Serve replication state and promotion; refuse signing on a standby.
+                let standby_hotkey = signing_key
+                    .as_ref()
+                    .map(|_| hex::encode(node_identity.hotkey));
+                rpc_server = rpc_server.with_replication(
+                    signing_gate.state_callback(persister.clone(), standby_hotkey.clone()),
+                    signing_gate.status_callback(),
+                    signing_gate.promote_callback(
+                        shared_state.epoch_manager.clone(),
+                        announce_registry.clone(),
+                    ),
+                    signing_gate.signing_allowed(),
+                );
+
+                // Spawn drift monitor.
+                let drift_shared = shared_state.clone();
+                let drift_store = store.clone();
+                let drift_events = event_tx.clone();
+                let drift_config = daemon_config.drift_monitor.clone();
+                supervisor.spawn(
+                    "drift_monitor",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        drift_monitor::run_drift_monitor(
+                            drift_shared.clone(),
+                            drift_store.clone(),
+                            drift_events.clone(),
+                            drift_config.clone(),
+                        )
+                    },
+                );
+
+                // Prune history outside the retention windows (non-archival).
+                let prune_shared = shared_state.clone();
+                let prune_store = store.clone();
+                let prune_index = index.clone();
+                let prune_config = daemon_config.pruning.clone();
+                supervisor.spawn(
+                    "pruner",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        pruning::run_pruner(
+                            prune_shared.clone(),
+                            prune_store.clone(),
+                            Some(prune_index.clone()),
+                            prune_config.clone(),
+                        )
+                    },
+                );
+
+                // Check stored polyps for corruption, repairing from IPFS and peers.
+                let scrub_status = Arc::new(ScrubStatus::default());
+                let report_status = scrub_status.clone();
+                rpc_server = rpc_server.with_scrub_report(Arc::new(move || report_status.report()));
+                let scrub_store = store.clone();
+                let scrub_index = Some(index.clone());
+                let scrub_hardened = hardened_store.clone();
+                let scrub_registry = announce_registry.clone();
+                let scrub_metrics = shared_state.metrics.clone();
+                let scrub_config = daemon_config.scrub.clone();
+                supervisor.spawn(
+                    "scrubber",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        scrub::run_scrubber(
+                            scrub_store.clone(),
+                            scrub_index.clone(),
+                            scrub_hardened.clone(),
+                            scrub_registry.clone(),
+                            scrub_metrics.clone(),
+                            scrub_status.clone(),
+                            scrub_config.clone(),
+                        )
+                    },
+                );
+
+                // Sync store WALs per the durability mode.
+                let flush_shared = shared_state.clone();
+                let flush_store = store.clone();
+                let flush_events = event_tx.clone();
+                let wal_durability = daemon_config.durability;
+                supervisor.spawn(
+                    "wal_flusher",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        durability::run_wal_flusher(
+                            flush_shared.clone(),
+                            flush_store.clone(),
+                            flush_events.subscribe(),
+                            wal_durability,
+                        )
+                    },
+                );
+
+                // Track hardened polyps for near-duplicate checks.
+                let dedup_shared = shared_state.clone();
+                let dedup_store = store.clone();
+                let dedup_events = event_tx.clone();
+                supervisor.spawn(
+                    "dedup_refresher",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        dedup::run_dedup_refresher(
+                            dedup_shared.clone(),
+                            dedup_store.clone(),
+                            dedup_events.subscribe(),
+                        )
+                    },
+                );
+
+                // Resume the epoch in progress and keep saving runtime state.
+                persister.restore_logged().await;
+
+                // Follow the primary until promoted, if this is a standby.
+                let standby_gate = signing_gate.clone();
+                let standby_persister = persister.clone();
+                let standby_hotkey = standby_hotkey.clone();
+                supervisor.spawn(
+                    "replication",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        standby_gate
+                            .clone()
+                            .run_standby(standby_persister.clone(), standby_hotkey.clone())
+                    },
+                );
+                let state_persister = persister.clone();
+                let save_interval = daemon_config.state_save_interval_secs;
+                supervisor.spawn(
+                    "persister",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || state_persister.clone().run(save_interval),
+                );
+
+                // Serve Prometheus metrics, if enabled.
+                supervisor.spawn(
+                    "metrics_exporter",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || exporter.clone().serve(),
+                );
+
+                // Spawn epoch scheduler.
+                let scheduler = Arc::new(tokio::sync::Mutex::new(
+                    EpochScheduler::new(
+                        daemon_config.blocks_per_epoch,
+                        shared_state.epoch_manager.clone(),
+                        event_tx.clone(),
+                    )
+                    .with_block_source(block_source)
+                    .with_watchdog(systemd::watchdog_interval()),
+                ));
+                supervisor.spawn(
+                    "scheduler",
+                    Priority::Critical,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        let scheduler = scheduler.clone();
+                        async move {
+                            scheduler
+                                .lock()
+                                .await
+                                .run()
+                                .await
+                                .map_err(|e| e.to_string())
+                        }
+                    },
+                );
+
+                // Spawn RPC server in background, run node in foreground.
+                let rpc_server = Arc::new(rpc_server.with_task_list(supervisor.task_list()));
+                if serve_rpc {
+                    let rpc_server = rpc_server.clone();
+                    supervisor.spawn(
+                        "rpc_server",
+                        Priority::Critical,
+                        RestartPolicy::OnFailure,
+                        move || {
+                            let rpc_server = rpc_server.clone();
+                            async move { rpc_server.start().await.map_err(|e| e.to_string()) }
+                        },
+                    );
+                }
+
+                // Report readiness to systemd once RPC and peers are up.
+                let peers = announce_registry.clone();
+                let rpc_addr = serve_rpc
+                    .then(|| format!("{}:{}", daemon_config.rpc_host, daemon_config.rpc_port));
+                supervisor.spawn(
+                    "systemd_ready",
+                    Priority::Low,
+                    RestartPolicy::Never,
+                    move || {
+                        systemd::announce_and_notify_ready(
+                            rpc_addr.clone(),
+                            announce_registry.clone(),
+                        )
+                    },
+                );
+
+                let run =
+                    tokio::spawn(
+                        async move { node.start(shutdown).await.map_err(|e| e.to_string()) },
+                    );
+                Running {
+                    store,
+                    index: Some(index),
+                    rpc: Some(rpc_server),
+                    peers,
+                    persister,
+                    run,
+                }
+            }
+            "tide" => {
+                // Tide-only mode needs a store for reading polyps.
+                let rocksdb_path = format!("{}/rocksdb", data_dir);
+                let store = match &polyp_store {
+                    Some(store) => store.clone(),
+                    None => Arc::new(
+                        RocksStore::open_with_durability(&rocksdb_path, daemon_config.durability)
+                            .map_err(|e| format!("Failed to open RocksDB: {}", e))?,
+                    ),
+                };
+                migrate_store(&store)?;
+                let embedding_cache = daemon_config
+                    .embedding
+                    .cache(store.clone())
+                    .map_err(|e| format!("Failed to open embedding cache: {}", e))?;
+                let shared_state = shared_state.clone().with_embedding_cache(embedding_cache);
+                restore_model_registry(&shared_state, &store).await;
+                let signing_gate = SigningGate::open(&daemon_config.replication, store.clone())
+                    .map_err(|e| format!("Failed to open replication state: {}", e))?;
+                let persister = StatePersister::new(store.clone(), shared_state.clone());
+                let exporter =
+                    MetricsExporter::new(daemon_config.metrics.clone(), shared_state.clone())
+                        .with_store(store.clone());
+
+                let event_rx = event_tx.subscribe();
+                let node = TideNode::new(
+                    &daemon_config,
+                    event_rx,
+                    shared_state.clone(),
+                    store.clone(),
+                )?
+                .with_validator(
+                    validator
+                        .with_signing_gate(signing_gate)
+                        .with_task_assignment(daemon_config.task_assignment.clone(), store.clone())
+                        .with_beacon(store.clone()),
+                );
+
+                // Reload hot-reloadable settings on SIGHUP or config file change.
+                if watch_config {
+                    let reload_handle = config_handle.clone();
+                    supervisor.spawn(
+                        "config_watcher",
+                        Priority::Normal,
+                        RestartPolicy::OnFailure,
+                        move || reload::watch_config(reload_handle.clone()),
+                    );
+                }
+
+                // Spawn drift monitor.
+                let drift_shared = shared_state.clone();
+                let drift_store = store.clone();
+                let drift_events = event_tx.clone();
+                let drift_config = daemon_config.drift_monitor.clone();
+                supervisor.spawn(
+                    "drift_monitor",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        drift_monitor::run_drift_monitor(
+                            drift_shared.clone(),
+                            drift_store.clone(),
+                            drift_events.clone(),
+                            drift_config.clone(),
+                        )
+                    },
+                );
+
+                // Prune history outside the retention windows (non-archival).
+                let prune_shared = shared_state.clone();
+                let prune_store = store.clone();
+                let prune_config = daemon_config.pruning.clone();
+                supervisor.spawn(
+                    "pruner",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        pruning::run_pruner(
+                            prune_shared.clone(),
+                            prune_store.clone(),
+                            None,
+                            prune_config.clone(),
+                        )
+                    },
+                );
+
+                // Check stored polyps for corruption, repairing from IPFS.
+                let scrub_status = Arc::new(ScrubStatus::default());
+                let scrub_store = store.clone();
+                let scrub_hardened = hardened_store.clone();
+                let scrub_metrics = shared_state.metrics.clone();
+                let scrub_config = daemon_config.scrub.clone();
+                supervisor.spawn(
+                    "scrubber",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        scrub::run_scrubber(
+                            scrub_store.clone(),
+                            None,
+                            scrub_hardened.clone(),
+                            None,
+                            scrub_metrics.clone(),
+                            scrub_status.clone(),
+                            scrub_config.clone(),
+                        )
+                    },
+                );
+
+                // Sync store WALs per the durability mode.
+                let flush_shared = shared_state.clone();
+                let flush_store = store.clone();
+                let flush_events = event_tx.clone();
+                let wal_durability = daemon_config.durability;
+                supervisor.spawn(
+                    "wal_flusher",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        durability::run_wal_flusher(
+                            flush_shared.clone(),
+                            flush_store.clone(),
+                            flush_events.subscribe(),
+                            wal_durability,
+                        )
+                    },
+                );
+
+                // Track hardened polyps for near-duplicate checks.
+                let dedup_shared = shared_state.clone();
+                let dedup_store = store.clone();
+                let dedup_events = event_tx.clone();
+                supervisor.spawn(
+                    "dedup_refresher",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        dedup::run_dedup_refresher(
+                            dedup_shared.clone(),
+                            dedup_store.clone(),
+                            dedup_events.subscribe(),
+                        )
+                    },
+                );
+
+                // Resume the epoch in progress and keep saving runtime state.
+                persister.restore_logged().await;
+                let state_persister = persister.clone();
+                let save_interval = daemon_config.state_save_interval_secs;
+                supervisor.spawn(
+                    "persister",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || state_persister.clone().run(save_interval),
+                );
+
+                // Serve Prometheus metrics, if enabled.
+                supervisor.spawn(
+                    "metrics_exporter",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || exporter.clone().serve(),
+                );
+
+                // Spawn epoch scheduler.
+                let scheduler = Arc::new(tokio::sync::Mutex::new(
+                    EpochScheduler::new(
+                        daemon_config.blocks_per_epoch,
+                        shared_state.epoch_manager.clone(),
+                        event_tx.clone(),
+                    )
+                    .with_block_source(block_source)
+                    .with_watchdog(systemd::watchdog_interval()),
+                ));
+                supervisor.spawn(
+                    "scheduler",
+                    Priority::Critical,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        let scheduler = scheduler.clone();
+                        async move {
+                            scheduler
+                                .lock()
+                                .await
+                                .run()
+                                .await
+                                .map_err(|e| e.to_string())
+                        }
+                    },
+                );
+
+                // Tide-only nodes serve no RPC; report readiness to systemd now.
+                supervisor.spawn("systemd_ready", Priority::Low, RestartPolicy::Never, || {
+                    systemd::announce_and_notify_ready(None, None)
+                });
+
+                let run =
+                    tokio::spawn(
+                        async move { node.start(shutdown).await.map_err(|e| e.to_string()) },
+                    );
+                Running {
+                    store,
+                    index: None,
+                    rpc: None,
+                    peers: None,
+                    persister,
+                    run,
+                }
+            }
+            "hybrid" => {
+                tracing::info!("Running in Hybrid mode (Coral + Tide)");
+                let coral = match &polyp_store {
+                    Some(store) => CoralNode::with_store(&daemon_config, store.clone()),
+                    None => CoralNode::new(&daemon_config)?,
+                }
+                .with_identity(node_identity.clone(), signing_key.clone());
+                let store = coral.store();
+                migrate_store(&store)?;
+                let admission = daemon_config.admission.build(store.clone()).map(Arc::new);
+                let signing_gate = SigningGate::open(&daemon_config.replication, store.clone())
+                    .map_err(|e| format!("Failed to open replication state: {}", e))?;
+                let index = Arc::new(InMemoryVectorIndex::new());
+                let embedding_cache = daemon_config
+                    .embedding
+                    .cache(store.clone())
+                    .map_err(|e| format!("Failed to open embedding cache: {}", e))?;
+                let shared_state = shared_state.clone().with_embedding_cache(embedding_cache);
+                restore_model_registry(&shared_state, &store).await;
+                restore_economics(&shared_state, &store).await?;
+                // Load a trusted snapshot into an empty store before syncing.
+                if let Some(source) = &daemon_config.snapshot.bootstrap_from {
+                    snapshot::bootstrap(
+                        source,
+                        &daemon_config.snapshot,
+                        &store,
+                        &index,
+                        &shared_state,
+                        &IpfsClient::new(&daemon_config.ipfs_api_url),
+                        &daemon_config.peers,
+                    )
+                    .await
+                    .map_err(|e| format!("Snapshot bootstrap failed: {}", e))?;
+                }
+                let mut persister = StatePersister::new(store.clone(), shared_state.clone());
+                let mut exporter =
+                    MetricsExporter::new(daemon_config.metrics.clone(), shared_state.clone())
+                        .with_store(store.clone())
+                        .with_index(index.clone());
+
+                let rpc_config = RpcConfig {
+                    host: daemon_config.rpc_host.clone(),
+                    port: daemon_config.rpc_port,
+                };
+                let mut rpc_server = ChitinRpcServer::new(rpc_config, store.clone(), index.clone())
+                    .with_peer_info(daemon_config.peers.clone())
+                    .with_identity(node_identity.clone(), signing_key.clone())
+                    .with_self_url(daemon_config.self_url.clone())
+                    .with_network_id(network_id.clone())
+                    .with_archival(daemon_config.archival)
+                    .with_epoch_manager(shared_state.epoch_manager.clone())
+                    .with_consensus_result(shared_state.last_consensus_result.clone())
+                    .with_weight_matrix(shared_state.weight_matrix.clone())
+                    .with_bond_matrix(shared_state.bond_matrix.clone())
+                    .with_metagraph_manager(shared_state.metagraph_manager.clone())
+                    .with_ledger(shared_state.ledger.clone())
+                    .with_stake_manager(shared_state.stake_manager.clone())
+                    .with_identity_registry(shared_state.identities.clone())
+                    .with_request_auth(daemon_config.request_auth.clone())
+                    .with_hardened_store(hardened_store.clone())
+                    .with_trust_store(shared_state.trust_store.clone())
+                    .with_taxonomy(shared_state.taxonomy.clone())
+                    .with_duplicate_detector(shared_state.duplicates.clone())
+                    .with_moderation(submission_policies.clone())
+                    .with_task_assignment(daemon_config.task_assignment.clone())
+                    .with_search_trust_weight(daemon_config.search_trust_weight)
+                    .with_search_mmr_lambda(daemon_config.search_mmr_lambda)
+                    .with_start_time(shared_state.start_time)
+                    .with_shard_set(shard_set.clone())
+                    .with_sync_throttle(sync_throttle.clone())
+                    .with_model_registry(shared_state.model_registry.clone())
+                    .with_embedder(
+                        EmbeddingPool::start(
+                            &daemon_config.embedding,
+                            embedding_providers.clone(),
+                            shared_state.clone(),
+                        )
+                        .embed_callback(),
+                    )
+                    .with_ingester(ingester.ingest_callback())
+                    .with_backup(
+                        backups
+                            .clone()
+                            .with_store("rocksdb", store.clone())
+                            .backup_callback(),
+                    )
+                    .with_snapshot(
+                        Snapshotter::new(
+                            &data_dir,
+                            store.clone(),
+                            index.clone(),
+                            shared_state.clone(),
+                            IpfsClient::new(&daemon_config.ipfs_api_url),
+                        )
+                        .with_identity(node_identity.hotkey, signing_key.clone())
+                        .snapshot_callback(),
+                    );
+                if let Some(log_buffer) = &log_buffer {
+                    rpc_server = rpc_server.with_log_query(log_buffer.query_callback());
+                }
+                if let Some(reranking) = &reranking {
+                    rpc_server = rpc_server.with_reranking(reranking.clone());
+                    exporter = exporter.with_rerank_stats(reranking.stats.clone());
+                }
+                if let Some(cache) = &query_cache {
+                    rpc_server = rpc_server.with_query_cache(cache.clone());
+                    exporter = exporter.with_query_cache(cache.clone());
+                }
+                if let Some(admission) = &admission {
+                    rpc_server = rpc_server.with_admission(admission.clone());
+                    exporter = exporter.with_admission(admission.clone());
+                }
+
+                let mut tide_shared = shared_state.clone();
+
+                // Wire up peer networking if peers are configured.
+                let mut announce_registry = None;
+                if !daemon_config.peers.is_empty() {
+                    let registry = Arc::new(
+                        PeerRegistry::new(
+                            daemon_config.self_url.clone(),
+                            daemon_config.peers.clone(),
+                        )
+                        .with_network_id(network_id.clone())
+                        .with_identity(
+                            public_did.clone(),
+                            node_identity.hotkey,
+                            signing_key.clone(),
+                        )
+                        .with_webhooks(shared_state.webhooks.clone())
+                        .with_signing_gate(signing_gate.clone())
+                        .with_identity_registry(shared_state.identities.clone()),
+                    );
+                    tracing::info!(
+                        "Peer networking enabled: {} peers configured",
+                        daemon_config.peers.len()
+                    );
+                    config_handle = config_handle.with_peer_registry(registry.clone());
+                    persister = persister.with_peer_registry(registry.clone());
+                    exporter = exporter.with_peer_registry(registry.clone());
+
+                    // Set up gossip callback for polyp broadcast with real DID.
+                    let gossip_registry = registry.clone();
+                    let gossip_did = if !node_identity.is_placeholder() {
+                        Some(node_identity.did.clone())
+                    } else {
+                        None
+                    };
+                    rpc_server = rpc_server
+                        .with_sync_metrics(registry.sync_metrics().clone())
+                        .with_peer_list(registry.peer_list())
+                        .with_identity_conflicts(registry.conflict_list())
+                        .with_announce_callback(registry.announce_callback())
+                        .with_gossip_callback(Arc::new(move |polyp| {
+                            gossip::broadcast_polyp(
+                                gossip_registry.clone(),
+                                polyp,
+                                gossip_did.clone(),
+                            );
+                        }))
+                        .with_shard_proxy(shard_proxy::shard_proxy(
+                            registry.clone(),
+                            shard_set.num_shards(),
+                        ));
+
+                    // Gossip state transitions from the Tide side, signed by this node.
+                    let state_registry = registry.clone();
+                    let state_hotkey = node_identity.hotkey;
+                    let state_key = signing_key.clone();
+                    let state_gate = signing_gate.clone();
+                    tide_shared = tide_shared.with_state_gossip(Arc::new(move |mut update| {
+                        if !state_gate.can_sign() {
+                            return;
+                        }
+                        if let Some(key) = &state_key {
+                            if let Err(e) = update.sign(key, state_hotkey) {
+                                tracing::warn!("Failed to sign state update: {}", e);
+                            }
+                        }
+                        gossip::broadcast_state_update(state_registry.clone(), update);
+                    }));
+
+                    // Announce to all peers once the RPC server is up.
+                    announce_registry = Some(registry.clone());
+
+                    // Spawn sync loop (30s interval).
+                    let sync_registry = registry.clone();
+                    let sync_store = store.clone();
+                    let sync_index = index.clone();
+                    let sync_config = config_handle.clone();
+                    let sync_priority = daemon_config.sync_priority();
+                    let sync_epochs = shared_state.epoch_manager.clone();
+                    let sync_shards = shard_set.clone();
+                    let throttle = sync_throttle.clone();
+                    let sync_metrics = shared_state.metrics.clone();
+                    let sync_cache = query_cache.clone();
+                    let sync_admission = admission.clone();
+                    supervisor.spawn(
+                        "sync_loop",
+                        Priority::High,
+                        RestartPolicy::OnFailure,
+                        move || {
+                            sync_loop::run_sync_loop(
+                                sync_registry.clone(),
+                                sync_store.clone(),
+                                sync_index.clone(),
+                                sync_config.clone(),
+                                sync_priority.clone(),
+                                sync_epochs.clone(),
+                                sync_shards.clone(),
+                                throttle.clone(),
+                                sync_metrics.clone(),
+                                sync_cache.clone(),
+                                sync_admission.clone(),
+                            )
+                        },
+                    );
+                }
+
+                // Create Tide node with epoch event receiver.
+                let event_rx = event_tx.subscribe();
+                let tide = TideNode::new(&daemon_config, event_rx, tide_shared, store.clone())?
+                    .with_validator(
+                        validator
+                            .with_signing_gate(signing_gate.clone())
+                            .with_task_assignment(
+                                daemon_config.task_assignment.clone(),
+                                store.clone(),
+                            )
+                            .with_beacon(store.clone()),
+                    );
+
+                // Reload hot-reloadable settings on SIGHUP, config file change,
+                // or `admin/config/reload`; serve and update the config.
+                rpc_server = rpc_server.with_config_access(
+                    config_handle.view_callback(),
+                    config_handle.update_callback(),
+                );
+                if watch_config {
+                    rpc_server = rpc_server.with_config_reload(config_handle.reload_callback());
+                    let reload_handle = config_handle.clone();
+                    supervisor.spawn(
+                        "config_watcher",
+                        Priority::Normal,
+                        RestartPolicy::OnFailure,
+                        move || reload::watch_config(reload_handle.clone()),
+                    );
+                }
+
+                // Serve replication state and promotion; refuse signing on a standby.
+                let standby_hotkey = signing_key
+                    .as_ref()
+                    .map(|_| hex::encode(node_identity.hotkey));
+                rpc_server = rpc_server.with_replication(
+                    signing_gate.state_callback(persister.clone(), standby_hotkey.clone()),
+                    signing_gate.status_callback(),
+                    signing_gate.promote_callback(
+                        shared_state.epoch_manager.clone(),
+                        announce_registry.clone(),
+                    ),
+                    signing_gate.signing_allowed(),
+                );
+
+                // Spawn drift monitor.
+                let drift_shared = shared_state.clone();
+                let drift_store = store.clone();
+                let drift_events = event_tx.clone();
+                let drift_config = daemon_config.drift_monitor.clone();
+                supervisor.spawn(
+                    "drift_monitor",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        drift_monitor::run_drift_monitor(
+                            drift_shared.clone(),
+                            drift_store.clone(),
+                            drift_events.clone(),
+                            drift_config.clone(),
+                        )
+                    },
+                );
+
+                // Prune history outside the retention windows (non-archival).
+                let prune_shared = shared_state.clone();
+                let prune_store = store.clone();
+                let prune_index = index.clone();
+                let prune_config = daemon_config.pruning.clone();
+                supervisor.spawn(
+                    "pruner",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        pruning::run_pruner(
+                            prune_shared.clone(),
+                            prune_store.clone(),
+                            Some(prune_index.clone()),
+                            prune_config.clone(),
+                        )
+                    },
+                );
+
+                // Check stored polyps for corruption, repairing from IPFS and peers.
+                let scrub_status = Arc::new(ScrubStatus::default());
+                let report_status = scrub_status.clone();
+                rpc_server = rpc_server.with_scrub_report(Arc::new(move || report_status.report()));
+                let scrub_store = store.clone();
+                let scrub_index = Some(index.clone());
+                let scrub_hardened = hardened_store.clone();
+                let scrub_registry = announce_registry.clone();
+                let scrub_metrics = shared_state.metrics.clone();
+                let scrub_config = daemon_config.scrub.clone();
+                supervisor.spawn(
+                    "scrubber",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        scrub::run_scrubber(
+                            scrub_store.clone(),
+                            scrub_index.clone(),
+                            scrub_hardened.clone(),
+                            scrub_registry.clone(),
+                            scrub_metrics.clone(),
+                            scrub_status.clone(),
+                            scrub_config.clone(),
+                        )
+                    },
+                );
+
+                // Sync store WALs per the durability mode.
+                let flush_shared = shared_state.clone();
+                let flush_store = store.clone();
+                let flush_events = event_tx.clone();
+                let wal_durability = daemon_config.durability;
+                supervisor.spawn(
+                    "wal_flusher",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        durability::run_wal_flusher(
+                            flush_shared.clone(),
+                            flush_store.clone(),
+                            flush_events.subscribe(),
+                            wal_durability,
+                        )
+                    },
+                );
+
+                // Track hardened polyps for near-duplicate checks.
+                let dedup_shared = shared_state.clone();
+                let dedup_store = store.clone();
+                let dedup_events = event_tx.clone();
+                supervisor.spawn(
+                    "dedup_refresher",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        dedup::run_dedup_refresher(
+                            dedup_shared.clone(),
+                            dedup_store.clone(),
+                            dedup_events.subscribe(),
+                        )
+                    },
+                );
+
+                // Resume the epoch in progress and keep saving runtime state.
+                persister.restore_logged().await;
+
+                // Follow the primary until promoted, if this is a standby.
+                let standby_gate = signing_gate.clone();
+                let standby_persister = persister.clone();
+                let standby_hotkey = standby_hotkey.clone();
+                supervisor.spawn(
+                    "replication",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        standby_gate
+                            .clone()
+                            .run_standby(standby_persister.clone(), standby_hotkey.clone())
+                    },
+                );
+                let state_persister = persister.clone();
+                let save_interval = daemon_config.state_save_interval_secs;
+                supervisor.spawn(
+                    "persister",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || state_persister.clone().run(save_interval),
+                );
+
+                // Serve Prometheus metrics, if enabled.
+                supervisor.spawn(
+                    "metrics_exporter",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || exporter.clone().serve(),
+                );
+
+                // Spawn epoch scheduler.
+                let scheduler = Arc::new(tokio::sync::Mutex::new(
+                    EpochScheduler::new(
+                        daemon_config.blocks_per_epoch,
+                        shared_state.epoch_manager.clone(),
+                        event_tx.clone(),
+                    )
+                    .with_block_source(block_source)
+                    .with_watchdog(systemd::watchdog_interval()),
+                ));
+                supervisor.spawn(
+                    "scheduler",
+                    Priority::Critical,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        let scheduler = scheduler.clone();
+                        async move {
+                            scheduler
+                                .lock()
+                                .await
+                                .run()
+                                .await
+                                .map_err(|e| e.to_string())
+                        }
+                    },
+                );
+
+                // Spawn RPC server in background, run both nodes concurrently.
+                let rpc_server = Arc::new(rpc_server.with_task_list(supervisor.task_list()));
+                if serve_rpc {
+                    let rpc_server = rpc_server.clone();
+                    supervisor.spawn(
+                        "rpc_server",
+                        Priority::Critical,
+                        RestartPolicy::OnFailure,
+                        move || {
+                            let rpc_server = rpc_server.clone();
+                            async move { rpc_server.start().await.map_err(|e| e.to_string()) }
+                        },
+                    );
+                }
+
+                // Report readiness to systemd once RPC and peers are up.
+                let peers = announce_registry.clone();
+                let rpc_addr = serve_rpc
+                    .then(|| format!("{}:{}", daemon_config.rpc_host, daemon_config.rpc_port));
+                supervisor.spawn(
+                    "systemd_ready",
+                    Priority::Low,
+                    RestartPolicy::Never,
+                    move || {
+                        systemd::announce_and_notify_ready(
+                            rpc_addr.clone(),
+                            announce_registry.clone(),
+                        )
+                    },
+                );
+
+                let run = tokio::spawn(async move {
+                    let tide_shutdown = shutdown.clone();
+                    tokio::select! {
+                        result = coral.start(shutdown) => {
+                            if let Err(e) = result {
+                                tracing::error!("Coral node error: {}", e);
+                            }
+                        }
+                        result = tide.start(tide_shutdown) => {
+                            if let Err(e) = result {
+                                tracing::error!("Tide node error: {}", e);
+                            }
+                        }
+                    }
+                    Ok(())
+                });
+                Running {
+                    store,
+                    index: Some(index),
+                    rpc: Some(rpc_server),
+                    peers,
+                    persister,
+                    run,
+                }
+            }
+            "seed" => {
+                // Seed nodes store only checkpoints and runtime state, no polyps.
+                let seed_db_path = format!("{}/seed_rocksdb", data_dir);
+                let store = match &polyp_store {
+                    Some(store) => store.clone(),
+                    None => Arc::new(
+                        RocksStore::open_with_durability(&seed_db_path, daemon_config.durability)
+                            .map_err(|e| format!("Failed to open RocksDB: {}", e))?,
+                    ),
+                };
+                migrate_store(&store)?;
+                let index = Arc::new(InMemoryVectorIndex::new());
+                let registry = Arc::new(
+                    PeerRegistry::new(daemon_config.self_url.clone(), daemon_config.peers.clone())
+                        .with_network_id(network_id.clone())
+                        .with_identity(
+                            public_did.clone(),
+                            node_identity.hotkey,
+                            signing_key.clone(),
+                        )
+                        .with_webhooks(shared_state.webhooks.clone())
+                        .with_identity_registry(shared_state.identities.clone()),
+                );
+                tracing::info!(
+                    "Running in Seed mode: {} bootstrap peers configured",
+                    daemon_config.peers.len()
+                );
+                config_handle = config_handle.with_peer_registry(registry.clone());
+                let persister = StatePersister::new(store.clone(), shared_state.clone())
+                    .with_peer_registry(registry.clone());
+                let exporter =
+                    MetricsExporter::new(daemon_config.metrics.clone(), shared_state.clone())
+                        .with_store(store.clone())
+                        .with_peer_registry(registry.clone());
+
+                let node = SeedNode::new(
+                    daemon_config.seed.clone(),
+                    shared_state.clone(),
+                    store.clone(),
+                    registry.clone(),
+                );
+                let rpc_config = RpcConfig {
+                    host: daemon_config.rpc_host.clone(),
+                    port: daemon_config.rpc_port,
+                };
+                let rpc_server = ChitinRpcServer::new(rpc_config, store.clone(), index)
+                    .with_allowed_methods(seed::SEED_METHODS)
+                    .with_request_auth(daemon_config.request_auth.clone())
+                    .with_peer_info(daemon_config.peers.clone())
+                    .with_identity(node_identity.clone(), signing_key.clone())
+                    .with_self_url(daemon_config.self_url.clone())
+                    .with_network_id(network_id.clone())
+                    .with_epoch_manager(shared_state.epoch_manager.clone())
+                    .with_metagraph_manager(shared_state.metagraph_manager.clone())
+                    .with_start_time(shared_state.start_time)
+                    .with_peer_directory(node.peer_directory())
+                    .with_peer_list(registry.peer_list())
+                    .with_identity_conflicts(registry.conflict_list())
+                    .with_announce_callback(node.announce_callback());
+
+                // Reload the peer list on SIGHUP or config file change.
+                if watch_config {
+                    let reload_handle = config_handle.clone();
+                    supervisor.spawn(
+                        "config_watcher",
+                        Priority::Normal,
+                        RestartPolicy::OnFailure,
+                        move || reload::watch_config(reload_handle.clone()),
+                    );
+                }
+
+                // Restore known peers and the metagraph, and keep saving them.
+                persister.restore_logged().await;
+                let state_persister = persister.clone();
+                let save_interval = daemon_config.state_save_interval_secs;
+                supervisor.spawn(
+                    "persister",
+                    Priority::Normal,
+                    RestartPolicy::OnFailure,
+                    move || state_persister.clone().run(save_interval),
+                );
+
+                // Sync the store WAL per the durability mode.
+                let flush_shared = shared_state.clone();
+                let flush_store = store.clone();
+                let flush_events = event_tx.clone();
+                let wal_durability = daemon_config.durability;
+                supervisor.spawn(
+                    "wal_flusher",
+                    Priority::High,
+                    RestartPolicy::OnFailure,
+                    move || {
+                        durability::run_wal_flusher(
+                            flush_shared.clone(),
+                            flush_store.clone(),
+                            flush_events.subscribe(),
+                            wal_durability,
+                        )
+                    },
+                );
+
+                // Serve Prometheus metrics, if enabled.
+                supervisor.spawn(
+                    "metrics_exporter",
+                    Priority::Low,
+                    RestartPolicy::OnFailure,
+                    move || exporter.clone().serve(),
+                );
+
+                let rpc_server = Arc::new(rpc_server);
+                if serve_rpc {
+                    let rpc_server = rpc_server.clone();
+                    supervisor.spawn(
+                        "rpc_server",
+                        Priority::Critical,
+                        RestartPolicy::OnFailure,
+                        move || {
+                            let rpc_server = rpc_server.clone();
+                            async move { rpc_server.start().await.map_err(|e| e.to_string()) }
+                        },
+                    );
+                }
+
+                // Report readiness to systemd once RPC is up; the seed
+                // announces itself.
+                let peers = Some(registry.clone());
+                let rpc_addr = serve_rpc
+                    .then(|| format!("{}:{}", daemon_config.rpc_host, daemon_config.rpc_port));
+                supervisor.spawn(
+                    "systemd_ready",
+                    Priority::Low,
+                    RestartPolicy::Never,
+                    move || systemd::announce_and_notify_ready(rpc_addr.clone(), None),
+                );
+
+                let run =
+                    tokio::spawn(
+                        async move { node.start(shutdown).await.map_err(|e| e.to_string()) },
+                    );
+                Running {
+                    store,
+                    index: None,
+                    rpc: Some(rpc_server),
+                    peers,
+                    persister,
+                    run,
+                }
+            }
+            other => {
+                tracing::error!(
+                    "Unknown node type: {}. Use 'coral', 'tide', 'hybrid', or 'seed'.",
+                    other
+                );
+                return Err(format!("Unknown node type: {}", other).into());
+            }
+        };
+
+        Ok(NodeHandle {
+            node_type: daemon_config.node_type.clone(),
+            identity: node_identity,
+            signing_key,
+            shared: shared_state,
+            store: running.store,
+            index: running.index,
+            rpc: running.rpc,
+            peers: running.peers,
+            persister: running.persister,
+            supervisor,
+            state_machine,
+            run: Some(running.run),
+        })
+    }
+}
+
+/// What a started node's branch hands to its `NodeHandle`.
+struct Running {
+    store: Arc<RocksStore>,
+    index: Option<Arc<InMemoryVectorIndex>>,
+    rpc: Option<Arc<ChitinRpcServer>>,
+    peers: Option<Arc<PeerRegistry>>,
+    persister: StatePersister,
+    /// The node's own loop, until shutdown.
+    run: JoinHandle<Result<(), String>>,
+}
+
+/// A running node.
+pub struct NodeHandle {
+    node_type: String,
+    identity: NodeIdentity,
+    /// Wiped when the handle is stopped or dropped.
+    signing_key: Option<SecretKey>,
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    index: Option<Arc<InMemoryVectorIndex>>,
+    /// In-process RPC service (none on Tide nodes).
+    rpc: Option<Arc<ChitinRpcServer>>,
+    /// Peer registry, if peer networking is on.
+    peers: Option<Arc<PeerRegistry>>,
+    persister: StatePersister,
+    supervisor: TaskSupervisor,
+    state_machine: NodeStateMachine,
+    run: Option<JoinHandle<Result<(), String>>>,
+}
+
+impl NodeHandle {
+    /// "coral", "tide", "hybrid", or "seed".
+    pub fn node_type(&self) -> &str {
+        &self.node_type
+    }
+
+    /// The identity the node runs as.
+    pub fn identity(&self) -> &NodeIdentity {
+        &self.identity
+    }
+
+    /// The node's shared epoch, consensus, and reputation state.
+    pub fn shared(&self) -> &DaemonSharedState {
+        &self.shared
+    }
+
+    /// The node's polyp store (checkpoint store on Seed nodes).
+    pub fn store(&self) -> Arc<RocksStore> {
+        self.store.clone()
+    }
+
+    /// The vector index searched by `query/search` (Coral and Hybrid nodes).
+    pub fn index(&self) -> Option<Arc<InMemoryVectorIndex>> {
+        self.index.clone()
+    }
+
+    /// The registry of peers the node syncs and gossips with (Coral and
+    /// Hybrid nodes with peers configured, and Seed nodes).
+    pub fn peers(&self) -> Option<Arc<PeerRegistry>> {
+        self.peers.clone()
+    }
+
+    /// The node's lifecycle state.
+    pub fn state(&self) -> NodeState {
+        self.state_machine.current.clone()
+    }
+
+    /// Status of the node's background jobs, as `admin/tasks` reports it.
+    pub fn tasks(&self) -> ListTasksResponse {
+        self.supervisor.list()
+    }
+
+    /// Call RPC `method` in-process, as a client would over HTTP.
+    pub async fn call<Req, Resp>(&self, method: &str, params: Req) -> Result<Resp, ChitinError>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
+        let rpc = match &self.rpc {
+            Some(rpc) => rpc,
+            None => {
+                return Err(ChitinError::InvalidState(format!(
+                    "{} nodes serve no RPC methods",
+                    self.node_type
+                )))
+            }
+        };
+        let request = JsonRpcRequest {
+            method: method.to_string(),
+            params: serde_json::to_value(params)?,
+        };
+        let response = rpc.call(request).await;
+        match (response.success, response.result) {
+            (true, Some(result)) => Ok(serde_json::from_value(result)?),
+            (true, None) => Ok(serde_json::from_value(serde_json::Value::Null)?),
+            (false, _) => Err(ChitinError::InvalidState(
+                response
+                    .error
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            )),
+        }
+    }
+
+    /// Wait until the node's loop exits on its own (it fails) or the node is
+    /// stopped from another task.
+    pub async fn wait(&mut self) -> Result<(), String> {
+        match &mut self.run {
+            Some(run) => {
+                let result = run.await.unwrap_or_else(|e| Err(e.to_string()));
+                self.run = None;
+                result
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Stop the node: cancel its jobs, wait for its loop, save the runtime
+    /// state, and wipe the hotkey.
+    pub async fn stop(mut self) -> Result<(), String> {
+        let _ = self.state_machine.transition(NodeState::ShuttingDown);
+        self.supervisor.shutdown();
+        let result = self.wait().await;
+        self.persister.save_logged().await;
+        drop(self.signing_key.take());
+        result
+    }
+}
+
+/// Merge the persisted model version registry into the configured one and
+/// persist the result, so schedules learned in earlier runs survive restarts.
+/// Bring `store` to this build's schema version, logging each migration's
+/// progress. Runs before anything reads or writes the store.
+fn migrate_store(store: &RocksStore) -> Result<(), String> {
+    let applied = chitin_store::migration::migrate(store, |p| {
+        if p.done {
+            tracing::info!(
+                "Schema migration {} ({}) done: {} keys read, {} changed",
+                p.version,
+                p.description,
+                p.scanned,
+                p.changes
+            );
+        } else {
+            tracing::info!(
+                "Schema migration {} ({}): {} keys read, {} changed so far",
+                p.version,
+                p.description,
+                p.scanned,
+                p.changes
+            );
+        }
+    })
+    .map_err(|e| format!("Schema migration failed: {}", e))?;
+    if !applied.steps.is_empty() {
+        tracing::info!(
+            "Store schema migrated from version {} to {}",
+            applied.current,
+            applied.target
+        );
+    }
+    Ok(())
+}
+
+/// Replace the genesis ledger and stakes with the persisted ones, if any, so
+/// that balances and nonces carry over from the previous run. A persisted
+/// ledger that cannot be read stops startup rather than falling back to
+/// genesis, where already-applied transactions could be replayed.
+async fn restore_economics(shared: &DaemonSharedState, store: &RocksStore) -> Result<(), String> {
+    let persisted = chitin_rpc::handlers::wallet::load_economics(store)
+        .map_err(|e| format!("Failed to load the ledger: {}", e))?;
+    if let Some((ledger, stakes)) = persisted {
+        *shared.ledger.write().await = ledger;
+        *shared.stake_manager.write().await = stakes;
+        tracing::info!("Ledger and stakes restored");
+    }
+    Ok(())
+}
+
+async fn restore_model_registry(shared: &DaemonSharedState, store: &RocksStore) {
+    let mut registry = shared.model_registry.write().await;
+    match VersionRegistry::load(store) {
+        Ok(Some(persisted)) => {
+            let configured = std::mem::replace(&mut *registry, persisted);
+            registry.merge(&configured.versions);
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to load model versions: {}", e),
+    }
+    if let Err(e) = registry.save(store) {
+        tracing::warn!("Failed to persist model versions: {}", e);
+    }
+    if !registry.versions.is_empty() {
+        tracing::info!("Model registry: {} versions", registry.versions.len());
+    }
+}
+
+/// Load the node identity from key files on disk.
+///
+/// Reads the hotkey secret (a hex-encoded file or an encrypted keystore,
+/// unlocked with `unlock`) and the hex-encoded coldkey public key, derives
+/// the hotkey public key from the secret, and constructs a `NodeIdentity`.
+/// Returns a placeholder identity if the files are not found, and an error
+/// if an encrypted keystore cannot be unlocked.
+fn load_node_identity(
+    config: &DaemonConfig,
+    unlock: &UnlockOptions,
+) -> Result<(NodeIdentity, Option<SecretKey>), ChitinError> {
+    let hotkey_path = expand_tilde(&config.hotkey_path);
+    let coldkey_pub_path = expand_tilde(&config.coldkey_pub_path);
+
+    let hotkey_secret = match std::fs::read_to_string(&hotkey_path).map(Zeroizing::new) {
+        Ok(contents) if EncryptedKeystore::is_keystore(&contents) => {
+            let secret = unlock::unlock_hotkey(&contents, unlock)?;
+            tracing::info!("Unlocked hotkey keystore at {}", hotkey_path);
+            Some(secret)
+        }
+        Ok(contents) => match unlock::decode_secret(contents.trim()) {
+            Some(secret) => {
+                tracing::warn!(
+                    "Hotkey secret at {} is not encrypted; \
+                     encrypt it with `chitin-daemon --encrypt-hotkey --unlock`.",
+                    hotkey_path
+                );
+                Some(secret)
+            }
+            None => {
+                tracing::warn!("Invalid hotkey secret at {}", hotkey_path);
+                None
+            }
+        },
+        Err(_) => {
+            tracing::debug!("Hotkey secret not found at {}", hotkey_path);
+            None
+        }
+    };
+
+    let coldkey_pub = match std::fs::read_to_string(&coldkey_pub_path) {
+        Ok(hex_str) => match hex_decode(hex_str.trim()) {
+            Some(bytes) if bytes.len() == 32 => {
+                let mut arr = [0u8; 32];
+                arr.copy_from_slice(&bytes);
+                Some(arr)
+            }
+            _ => {
+                tracing::warn!("Invalid coldkey public key at {}", coldkey_pub_path);
+                None
+            }
+        },
+        Err(_) => {
+            tracing::debug!("Coldkey public key not found at {}", coldkey_pub_path);
+            None
+        }
+    };
+
+    // Determine node type from config.
+    let node_type = match config.node_type.as_str() {
+        "coral" => NodeType::Coral,
+        "tide" => NodeType::Tide,
+        "seed" => NodeType::Seed,
+        _ => NodeType::Hybrid,
+    };
+
+    match (hotkey_secret, coldkey_pub) {
+        (Some(secret), Some(coldkey)) => {
+            // Derive hotkey public key from the secret.
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
+            let hotkey_pub = signing_key.verifying_key().to_bytes();
+            let identity = NodeIdentity::from_keypairs(hotkey_pub, coldkey, node_type);
+            Ok((identity, Some(secret)))
+        }
+        _ => {
+            // Placeholder identity when keys are not available.
+            let identity = NodeIdentity {
+                coldkey: [0u8; 32],
+                hotkey: [0u8; 32],
+                did: "did:chitin:local".to_string(),
+                node_type,
+            };
+            Ok((identity, None))
+        }
+    }
+}
+
+/// Expand `~` at the start of a path to the user's home directory.
+pub fn expand_tilde(path: &str) -> String {
+    if path.starts_with("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return format!("{}{}", home.display(), &path[1..]);
+        }
+    }
+    path.to_string()
+}
+
+/// Decode a hex string into bytes. Returns None if the string is invalid hex.
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
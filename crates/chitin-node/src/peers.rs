@@ -0,0 +1,1113 @@
+// crates/chitin-node/src/peers.rs
+//
+// PeerRegistry: manages configured peer URLs and a shared HTTP client
+// for inter-node communication in the HTTP relay network. It also records
+// which shards each peer holds, for routing queries, and carries the per-peer
+// sync metrics reported by `sync/status`. The configured peer list can be
+// replaced at runtime when the daemon config is reloaded. Peers that announce
+// a different network ID are left out of sync and gossip. Peers learned from
+// other peers' `peer/discover` lists (peer exchange) are tracked alongside
+// the configured ones.
+//
+// Every outbound call's outcome is recorded in the peer's dial statistics.
+// A failing peer is not dialed again until an exponentially growing, jittered
+// backoff has passed; one successful call resets it. Dial statistics are
+// saved with the rest of the peer table and reported by `node/peers`.
+//
+// The registry also watches for one DID being announced from several URLs,
+// as happens when the same hotkey runs on two machines. Every DID a URL
+// announces, reports in an announce response, or is listed with by peer
+// exchange is recorded as a claim; this node's own announcements are signed
+// with its hotkey. When a DID has claims from more than one reachable URL, the
+// conflict is logged, sent to webhooks, and reported by `node/health`, and
+// the URL with the most recent validly signed announcement is preferred: the
+// other claimants are no longer synced, gossiped with, or listed to peers. A
+// signature only counts if its signer is the hotkey registered for the DID in
+// the identity registry; anyone can sign a claim of someone else's DID with a
+// key of their own, and such a claim is reported but never preferred. A
+// claimant stops counting after `CLAIM_UNREACHABLE_FAILURES` failed calls in
+// a row.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use chitin_core::keystore::SecretKey;
+use chitin_core::{ChitinError, IdentityRegistry};
+use chitin_rpc::handlers::node::{DialStats, IdentityClaim, IdentityConflict, PeerInfo};
+use chitin_rpc::handlers::peer::{AnnounceRequest, DiscoverPeersResponse, DiscoveredPeer};
+use chitin_rpc::{AnnounceCallback, IdentityConflictsCallback, PeerListCallback};
+use chitin_rpc::{KeyRole, SignedRequest};
+use chitin_store::ShardSet;
+use chitin_sync::metrics::SyncMetrics;
+use chitin_sync::state_update::PolypStateUpdate;
+use serde::{Deserialize, Serialize};
+
+use crate::replication::SigningGate;
+use crate::sync_loop::call_peer;
+use crate::webhooks::{WebhookEvent, WebhookNotifier};
+
+/// Information about a peer node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerState {
+    /// The peer's public URL.
+    pub url: String,
+    /// The peer's self-reported node ID (DID), if known.
+    pub node_id: Option<String>,
+    /// Whether the last communication attempt succeeded.
+    pub alive: bool,
+    /// The shards the peer reported holding (`peer/shards`), if known.
+    #[serde(default)]
+    pub shards: Option<ShardSet>,
+    /// Trust in the data the peer serves, from `INITIAL_PEER_SCORE` down to
+    /// 0.0. Lowered when the peer serves polyps that fail verification;
+    /// peers at 0.0 are no longer synced from.
+    #[serde(default = "default_peer_score")]
+    pub score: f64,
+    /// Outcomes of calls to the peer, and its backoff.
+    #[serde(default)]
+    pub dial: DialStats,
+}
+
+/// Score of a newly known peer.
+pub const INITIAL_PEER_SCORE: f64 = 1.0;
+
+/// Backoff after a peer's first consecutive failure, in seconds; it doubles
+/// with each further failure.
+const DIAL_BACKOFF_BASE_SECS: f64 = 10.0;
+
+/// Longest backoff between dials of a failing peer, in seconds.
+const DIAL_BACKOFF_MAX_SECS: f64 = 900.0;
+
+/// Consecutive failed calls after which a URL's identity claims no longer
+/// count toward conflicts.
+const CLAIM_UNREACHABLE_FAILURES: u32 = 3;
+
+/// How far in the future a signed announcement's time may lie before its
+/// signature is disregarded.
+const MAX_ANNOUNCE_CLOCK_SKEW_SECS: i64 = 300;
+
+/// DID of nodes without key material, which many nodes share.
+const PLACEHOLDER_DID: &str = "did:chitin:local";
+
+fn default_peer_score() -> f64 {
+    INITIAL_PEER_SCORE
+}
+
+/// Manages the set of known peers and a shared HTTP client.
+#[derive(Debug, Clone)]
+pub struct PeerRegistry {
+    /// This node's public URL, used in announcements.
+    pub self_url: Option<String>,
+    /// This node's DID, included in announce messages.
+    pub self_did: Option<String>,
+    /// Network ID, included in announce messages; peers announcing a
+    /// different network are not used.
+    pub network_id: Option<String>,
+    /// Configured peer URLs (from config), shared by all clones.
+    configured_peers: Arc<std::sync::RwLock<Vec<String>>>,
+    /// Configured peers found to be on a different network, left out of
+    /// `configured_peer_urls`.
+    foreign_peers: Arc<std::sync::RwLock<BTreeSet<String>>>,
+    /// Live peer state, updated on successful/failed communication.
+    peer_state: Arc<RwLock<HashMap<String, PeerState>>>,
+    /// Shared reqwest client for all outbound HTTP calls.
+    client: reqwest::Client,
+    /// Sync metrics per peer, recorded by the sync loop and gossip.
+    metrics: Arc<SyncMetrics>,
+    /// Key this node's announcements are signed with, if it has one.
+    announce_key: Option<AnnounceKey>,
+    /// This node's claim of its DID, signed by its last announcement.
+    own_claim: Arc<std::sync::RwLock<IdentityClaim>>,
+    /// URLs seen announcing each DID, shared by all clones.
+    claims: Arc<std::sync::RwLock<HashMap<String, BTreeMap<String, Claim>>>>,
+    /// Notified of identity conflicts.
+    webhooks: Option<WebhookNotifier>,
+    /// Announcements are skipped while this refuses signing (a standby).
+    signing_gate: Option<SigningGate>,
+    /// Registered hotkeys, which announcement signatures must come from.
+    identities: Option<Arc<RwLock<IdentityRegistry>>>,
+}
+
+/// A hotkey and its signing key, for signing announcements.
+#[derive(Clone)]
+struct AnnounceKey {
+    hotkey: [u8; 32],
+    signing_key: SecretKey,
+}
+
+impl std::fmt::Debug for AnnounceKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnnounceKey")
+            .field("hotkey", &hex::encode(self.hotkey))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A URL's claim of a DID, and whether the URL still answers.
+#[derive(Debug, Clone)]
+struct Claim {
+    claim: IdentityClaim,
+    unreachable: bool,
+}
+
+/// Response body for `peer/announce`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnounceResponse {
+    pub node_id: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub network_id: Option<String>,
+}
+
+impl PeerRegistry {
+    /// Create a new PeerRegistry from config values.
+    pub fn new(self_url: Option<String>, configured_peers: Vec<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        let state_map = configured_peers
+            .iter()
+            .map(|url| (url.clone(), new_peer_state(url)))
+            .collect();
+
+        let own_claim = IdentityClaim {
+            url: self_url.clone().unwrap_or_default(),
+            signer: None,
+            signed_at: None,
+            last_seen: Utc::now(),
+        };
+
+        Self {
+            self_url,
+            self_did: None,
+            network_id: None,
+            configured_peers: Arc::new(std::sync::RwLock::new(configured_peers)),
+            foreign_peers: Arc::new(std::sync::RwLock::new(BTreeSet::new())),
+            peer_state: Arc::new(RwLock::new(state_map)),
+            client,
+            metrics: Arc::new(SyncMetrics::new()),
+            announce_key: None,
+            own_claim: Arc::new(std::sync::RwLock::new(own_claim)),
+            claims: Arc::new(std::sync::RwLock::new(HashMap::new())),
+            webhooks: None,
+            signing_gate: None,
+            identities: None,
+        }
+    }
+
+    /// Announce this node as `did` (`None` for placeholder identities),
+    /// signing announcements with `signing_key` for `hotkey` if given.
+    pub fn with_identity(
+        mut self,
+        did: Option<String>,
+        hotkey: [u8; 32],
+        signing_key: Option<SecretKey>,
+    ) -> Self {
+        self.self_did = did;
+        self.announce_key = signing_key.map(|signing_key| AnnounceKey {
+            hotkey,
+            signing_key,
+        });
+        self
+    }
+
+    /// Send identity conflicts to `webhooks`.
+    pub fn with_webhooks(mut self, webhooks: Option<WebhookNotifier>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
+    /// Skip announcements while `gate` refuses signing.
+    pub fn with_signing_gate(mut self, gate: SigningGate) -> Self {
+        self.signing_gate = Some(gate);
+        self
+    }
+
+    /// Count announcement signatures only from the hotkeys registered in
+    /// `identities`.
+    pub fn with_identity_registry(mut self, identities: Arc<RwLock<IdentityRegistry>>) -> Self {
+        self.identities = Some(identities);
+        self
+    }
+
+    /// Announce `network_id` to peers and only use peers on the same network.
+    pub fn with_network_id(mut self, network_id: Option<String>) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    /// Return the shared reqwest::Client.
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Return the shared per-peer sync metrics.
+    pub fn sync_metrics(&self) -> &Arc<SyncMetrics> {
+        &self.metrics
+    }
+
+    /// Return the list of configured peer URLs, excluding peers on another
+    /// network and peers that lost an identity conflict.
+    pub fn configured_peer_urls(&self) -> Vec<String> {
+        let excluded = self.excluded_urls();
+        self.configured_peers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|url| !excluded.contains(*url))
+            .cloned()
+            .collect()
+    }
+
+    /// Peers on another network, and peers that lost an identity conflict.
+    fn excluded_urls(&self) -> BTreeSet<String> {
+        let mut excluded = self.foreign_peers.read().unwrap_or_else(|e| e.into_inner()).clone();
+        excluded.extend(self.superseded_urls());
+        excluded
+    }
+
+    /// Record whether the peer at `url` announced a different network.
+    fn set_foreign(&self, url: &str, foreign: bool) {
+        let mut peers = self.foreign_peers.write().unwrap_or_else(|e| e.into_inner());
+        if foreign {
+            peers.insert(url.to_string());
+        } else {
+            peers.remove(url);
+        }
+    }
+
+    /// Return the number of configured peers.
+    #[allow(dead_code)]
+    pub fn peer_count(&self) -> usize {
+        self.configured_peers.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Replace the configured peers. Newly configured peers start out not
+    /// alive; peers no longer configured are forgotten.
+    ///
+    /// Returns the added and removed URLs.
+    pub async fn set_configured_peers(&self, peers: Vec<String>) -> (Vec<String>, Vec<String>) {
+        let mut state = self.peer_state.write().await;
+        let mut configured = self.configured_peers.write().unwrap_or_else(|e| e.into_inner());
+        let added: Vec<String> = peers
+            .iter()
+            .filter(|url| !configured.contains(url))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = configured
+            .iter()
+            .filter(|url| !peers.contains(url))
+            .cloned()
+            .collect();
+        for url in &removed {
+            state.remove(url);
+            self.set_foreign(url, false);
+        }
+        for url in &added {
+            state
+                .entry(url.clone())
+                .or_insert_with(|| new_peer_state(url));
+        }
+        *configured = peers;
+        (added, removed)
+    }
+
+    /// Return URLs of peers that last responded successfully.
+    pub async fn live_peer_urls(&self) -> Vec<String> {
+        let state = self.peer_state.read().await;
+        state
+            .values()
+            .filter(|p| p.alive)
+            .map(|p| p.url.clone())
+            .collect()
+    }
+
+    /// Return all peer states (for the peers RPC endpoint).
+    pub async fn all_peer_states(&self) -> Vec<PeerState> {
+        let state = self.peer_state.read().await;
+        state.values().cloned().collect()
+    }
+
+    /// Restore peer states saved by a previous run: liveness, DIDs, shards,
+    /// and scores of known peers, plus unknown peers that reported a DID.
+    ///
+    /// Peers already heard from in this run keep their current state.
+    /// Returns the number of peers restored.
+    pub async fn restore_peer_states(&self, saved: Vec<PeerState>) -> usize {
+        let mut state = self.peer_state.write().await;
+        let mut restored = 0;
+        for peer in saved {
+            if self.self_url.as_deref() == Some(peer.url.as_str()) {
+                continue;
+            }
+            match state.get_mut(&peer.url) {
+                Some(current) if current.node_id.is_none() && current.shards.is_none() => {
+                    *current = peer;
+                }
+                Some(_) => continue,
+                None if peer.node_id.is_some() => {
+                    state.insert(peer.url.clone(), peer);
+                }
+                None => continue,
+            }
+            restored += 1;
+        }
+        restored
+    }
+
+    /// Live peer states as listed by `node/peers`, by URL.
+    pub async fn peer_infos(&self) -> Vec<PeerInfo> {
+        let mut peers: Vec<PeerInfo> = self
+            .all_peer_states()
+            .await
+            .into_iter()
+            .map(|p| PeerInfo {
+                peer_id: p.node_id.unwrap_or_else(|| p.url.clone()),
+                address: p.url,
+                node_type: None,
+                latency_ms: None,
+                alive: Some(p.alive),
+                dial: Some(p.dial),
+            })
+            .collect();
+        peers.sort_by(|a, b| a.address.cmp(&b.address));
+        peers
+    }
+
+    /// Callback listing peers with their dial statistics for `node/peers`.
+    pub fn peer_list(&self) -> PeerListCallback {
+        let registry = self.clone();
+        Arc::new(move || {
+            let registry = registry.clone();
+            Box::pin(async move { registry.peer_infos().await })
+        })
+    }
+
+    /// Configured peers (on this network) not currently backing off.
+    pub async fn dialable_peer_urls(&self) -> Vec<String> {
+        let now = Utc::now();
+        let state = self.peer_state.read().await;
+        self.configured_peer_urls()
+            .into_iter()
+            .filter(|url| state.get(url).is_none_or(|p| dial_due(&p.dial, now)))
+            .collect()
+    }
+
+    /// True unless the peer at `url` is backing off after failed calls.
+    pub async fn is_dial_due(&self, url: &str) -> bool {
+        let state = self.peer_state.read().await;
+        state.get(url).is_none_or(|p| dial_due(&p.dial, Utc::now()))
+    }
+
+    /// Add a dynamically discovered peer if its URL is not already known.
+    ///
+    /// Returns `true` if the peer was newly added, `false` if it already existed.
+    pub async fn add_discovered_peer(&self, url: String, did: Option<String>) -> bool {
+        if let Some(did) = &did {
+            self.record_claim(did, &url, None);
+        }
+        let mut state = self.peer_state.write().await;
+        if state.contains_key(&url) {
+            // Update DID if we got new info.
+            if let (Some(peer), Some(new_did)) = (state.get_mut(&url), &did) {
+                if peer.node_id.is_none() {
+                    peer.node_id = Some(new_did.clone());
+                }
+            }
+            return false;
+        }
+
+        tracing::info!("Discovered new peer: {} (did={:?})", url, did);
+        state.insert(
+            url.clone(),
+            PeerState {
+                url,
+                node_id: did,
+                alive: true,
+                shards: None,
+                score: INITIAL_PEER_SCORE,
+                dial: DialStats::default(),
+            },
+        );
+        true
+    }
+
+    /// Known peers as listed by `peer/discover`: live peers first, then by
+    /// URL, at most `limit`. This node, peers on another network, and peers
+    /// that lost an identity conflict are left out.
+    pub async fn directory(&self, limit: usize) -> Vec<DiscoveredPeer> {
+        let excluded = self.excluded_urls();
+        let mut peers: Vec<DiscoveredPeer> = self
+            .all_peer_states()
+            .await
+            .into_iter()
+            .filter(|p| !excluded.contains(&p.url))
+            .filter(|p| self.self_url.as_deref() != Some(p.url.as_str()))
+            .map(|p| DiscoveredPeer {
+                url: p.url,
+                did: p.node_id,
+                alive: p.alive,
+            })
+            .collect();
+        peers.sort_by(|a, b| b.alive.cmp(&a.alive).then_with(|| a.url.cmp(&b.url)));
+        peers.truncate(limit);
+        peers
+    }
+
+    /// Peer exchange: ask every known peer not backing off for its
+    /// `peer/discover` list and add the peers not yet known. Peers that
+    /// answer are marked alive, the others not.
+    ///
+    /// Returns the number of newly discovered peers.
+    pub async fn exchange_peers(&self) -> usize {
+        let foreign = self.excluded_urls();
+        let now = Utc::now();
+        let mut known: Vec<String> = self
+            .peer_state
+            .read()
+            .await
+            .values()
+            .filter(|p| !foreign.contains(&p.url) && dial_due(&p.dial, now))
+            .map(|p| p.url.clone())
+            .collect();
+        known.sort();
+
+        let mut added = 0;
+        for url in known {
+            let params = serde_json::json!({});
+            match call_peer::<DiscoverPeersResponse>(&self.client, &url, "peer/discover", params)
+                .await
+            {
+                Ok(response) => {
+                    self.mark_peer(&url, true, None).await;
+                    for peer in response.peers {
+                        if self.self_url.as_deref() == Some(peer.url.as_str())
+                            || foreign.contains(&peer.url)
+                        {
+                            continue;
+                        }
+                        if self.add_discovered_peer(peer.url, peer.did).await {
+                            added += 1;
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Peer exchange with {} failed: {}", url, e);
+                    self.mark_peer(&url, false, None).await;
+                }
+            }
+        }
+        added
+    }
+
+    /// Mark a peer as alive or dead after a communication attempt, and
+    /// record the attempt in its dial statistics.
+    pub async fn mark_peer(&self, url: &str, alive: bool, node_id: Option<String>) {
+        if let Some(id) = &node_id {
+            self.record_claim(id, url, None);
+        }
+        let mut state = self.peer_state.write().await;
+        if let Some(peer) = state.get_mut(url) {
+            peer.alive = alive;
+            record_dial(&mut peer.dial, alive, Utc::now());
+            if let Some(id) = node_id {
+                peer.node_id = Some(id);
+            }
+            self.set_claims_reachable(
+                url,
+                peer.dial.consecutive_failures < CLAIM_UNREACHABLE_FAILURES,
+            );
+        }
+    }
+
+    /// Record an accepted `peer/announce` as a claim of its DID by its URL.
+    /// Its signature counts only if it verifies, the signing time is not in
+    /// the future, and the signer is the hotkey registered for the DID.
+    pub async fn record_announcement(&self, announce: &AnnounceRequest) {
+        let (did, url) = match (&announce.node_id, &announce.url) {
+            (Some(did), Some(url)) => (did, url),
+            _ => return,
+        };
+        let latest = Utc::now() + chrono::Duration::seconds(MAX_ANNOUNCE_CLOCK_SKEW_SECS);
+        let signed = match (&announce.signer, announce.signed_at) {
+            (Some(signer), Some(signed_at))
+                if signed_at <= latest && announce.verify_signature().unwrap_or(false) =>
+            {
+                if self.is_registered_hotkey(did, signer).await {
+                    Some((signer.as_str(), signed_at))
+                } else {
+                    tracing::warn!(
+                        "Disregarding announcement signature from {}: {} is not the hotkey \
+                         registered for {}",
+                        url,
+                        signer,
+                        did
+                    );
+                    None
+                }
+            }
+            (Some(_), _) => {
+                tracing::warn!("Disregarding invalid announcement signature from {}", url);
+                None
+            }
+            _ => None,
+        };
+        self.record_claim(did, url, signed);
+    }
+
+    /// Whether `hotkey` is registered, so peers accept its signatures.
+    /// Without a registry this cannot be known, and it is assumed.
+    async fn is_registered(&self, hotkey: &[u8; 32]) -> bool {
+        match &self.identities {
+            Some(identities) => identities.read().await.uid_of_hotkey(hotkey).is_some(),
+            None => true,
+        }
+    }
+
+    /// Check a state update relayed by a peer (see
+    /// `PolypStateUpdate::verify_origin`). Without an identity registry no
+    /// signer is known to be a validator, so every update is refused.
+    pub async fn verify_state_update(
+        &self,
+        update: &PolypStateUpdate,
+        current_epoch: u64,
+    ) -> Result<(), ChitinError> {
+        match &self.identities {
+            Some(identities) => update.verify_origin(&*identities.read().await, current_epoch),
+            None => Err(ChitinError::Verification(
+                "no identity registry to check state update signers against".to_string(),
+            )),
+        }
+    }
+
+    /// Whether `signer` (hex) is the hotkey registered for `did`.
+    async fn is_registered_hotkey(&self, did: &str, signer: &str) -> bool {
+        let identities = match &self.identities {
+            Some(identities) => identities.read().await,
+            None => return false,
+        };
+        let hotkey = identities
+            .uid_of_did(did)
+            .and_then(|uid| identities.get(uid))
+            .map(|record| record.hotkey);
+        match (hotkey, hex::decode(signer)) {
+            (Some(hotkey), Ok(signer)) => signer == hotkey,
+            _ => false,
+        }
+    }
+
+    /// Callback recording accepted `peer/announce` requests.
+    pub fn announce_callback(&self) -> AnnounceCallback {
+        let registry = self.clone();
+        Arc::new(move |announce| {
+            let registry = registry.clone();
+            tokio::spawn(async move { registry.record_announcement(&announce).await });
+        })
+    }
+
+    /// Record that `url` claims `did`, with the signer and time of a valid
+    /// signed announcement if there was one, and report a new conflict.
+    fn record_claim(&self, did: &str, url: &str, signed: Option<(&str, DateTime<Utc>)>) {
+        if self.self_url.as_deref() == Some(url) || did == PLACEHOLDER_DID {
+            return;
+        }
+        let now = Utc::now();
+        let added = {
+            let mut claims = self.claims.write().unwrap_or_else(|e| e.into_inner());
+            let urls = claims.entry(did.to_string()).or_default();
+            let added = !urls.contains_key(url);
+            let entry = urls.entry(url.to_string()).or_insert_with(|| Claim {
+                claim: IdentityClaim {
+                    url: url.to_string(),
+                    signer: None,
+                    signed_at: None,
+                    last_seen: now,
+                },
+                unreachable: false,
+            });
+            entry.claim.last_seen = now;
+            if let Some((signer, signed_at)) = signed {
+                if entry.claim.signed_at.is_none_or(|last| last < signed_at) {
+                    entry.claim.signer = Some(signer.to_string());
+                    entry.claim.signed_at = Some(signed_at);
+                }
+            }
+            added
+        };
+        if !added {
+            return;
+        }
+        if let Some(conflict) = self.identity_conflicts().into_iter().find(|c| c.did == did) {
+            self.report_conflict(conflict);
+        }
+    }
+
+    /// Count or stop counting the claims of `url` toward conflicts.
+    fn set_claims_reachable(&self, url: &str, reachable: bool) {
+        let mut claims = self.claims.write().unwrap_or_else(|e| e.into_inner());
+        for claim in claims.values_mut().filter_map(|urls| urls.get_mut(url)) {
+            claim.unreachable = !reachable;
+        }
+    }
+
+    /// Log a new conflict and send it to webhooks.
+    fn report_conflict(&self, conflict: IdentityConflict) {
+        let urls: Vec<String> = conflict.claims.iter().map(|c| c.url.clone()).collect();
+        if conflict.local {
+            tracing::error!(
+                "This node's DID {} is also announced from {:?}; is its hotkey running on \
+                 another machine? Preferring {:?}",
+                conflict.did,
+                urls,
+                conflict.preferred
+            );
+        } else {
+            tracing::warn!(
+                "DID {} is announced from {:?}; preferring {:?}",
+                conflict.did,
+                urls,
+                conflict.preferred
+            );
+        }
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify(WebhookEvent::IdentityConflict {
+                did: conflict.did,
+                local: conflict.local,
+                urls,
+                preferred: conflict.preferred,
+            });
+        }
+    }
+
+    /// DIDs claimed by more than one reachable URL (this node counting for
+    /// its own DID), by DID.
+    pub fn identity_conflicts(&self) -> Vec<IdentityConflict> {
+        let claims = self.claims.read().unwrap_or_else(|e| e.into_inner());
+        let mut conflicts: Vec<IdentityConflict> = claims
+            .iter()
+            .filter_map(|(did, urls)| {
+                let local = self.self_did.as_deref() == Some(did.as_str());
+                let mut claims: Vec<IdentityClaim> = urls
+                    .values()
+                    .filter(|c| !c.unreachable)
+                    .map(|c| c.claim.clone())
+                    .collect();
+                if local {
+                    claims.push(self.own_claim());
+                    claims.sort_by(|a, b| a.url.cmp(&b.url));
+                }
+                if claims.len() < 2 {
+                    return None;
+                }
+                let preferred = claims
+                    .iter()
+                    .filter(|c| c.signed_at.is_some())
+                    .max_by_key(|c| c.signed_at)
+                    .map(|c| c.url.clone());
+                Some(IdentityConflict {
+                    did: did.clone(),
+                    local,
+                    claims,
+                    preferred,
+                })
+            })
+            .collect();
+        conflicts.sort_by(|a, b| a.did.cmp(&b.did));
+        conflicts
+    }
+
+    /// Callback listing identity conflicts for `node/health`.
+    pub fn conflict_list(&self) -> IdentityConflictsCallback {
+        let registry = self.clone();
+        Arc::new(move || {
+            let registry = registry.clone();
+            Box::pin(async move { registry.identity_conflicts() })
+        })
+    }
+
+    /// This node's claim of its own DID.
+    fn own_claim(&self) -> IdentityClaim {
+        let mut own = self.own_claim.read().unwrap_or_else(|e| e.into_inner()).clone();
+        own.last_seen = Utc::now();
+        own
+    }
+
+    /// URLs that lost an identity conflict to a more recently signed claim.
+    fn superseded_urls(&self) -> BTreeSet<String> {
+        self.identity_conflicts()
+            .into_iter()
+            .filter_map(|conflict| {
+                let preferred = conflict.preferred?;
+                Some(conflict.claims.into_iter().map(|c| c.url).filter(move |u| *u != preferred))
+            })
+            .flatten()
+            .filter(|url| self.self_url.as_deref() != Some(url.as_str()))
+            .collect()
+    }
+
+    /// Lower a peer's score by `penalty` (floored at 0.0); returns the new score.
+    pub async fn penalize_peer(&self, url: &str, penalty: f64) -> f64 {
+        let mut state = self.peer_state.write().await;
+        match state.get_mut(url) {
+            Some(peer) => {
+                peer.score = (peer.score - penalty).max(0.0);
+                peer.score
+            }
+            None => 0.0,
+        }
+    }
+
+    /// A peer's current score (`INITIAL_PEER_SCORE` if unknown).
+    pub async fn peer_score(&self, url: &str) -> f64 {
+        let state = self.peer_state.read().await;
+        state.get(url).map_or(INITIAL_PEER_SCORE, |p| p.score)
+    }
+
+    /// Record the shards a peer reported holding.
+    pub async fn set_peer_shards(&self, url: &str, shards: ShardSet) {
+        let mut state = self.peer_state.write().await;
+        if let Some(peer) = state.get_mut(url) {
+            peer.shards = Some(shards);
+        }
+    }
+
+    /// Live peers that together hold as many of `shards` as possible.
+    ///
+    /// Greedy cover: repeatedly picks the peer holding the most still
+    /// uncovered shards. Only peers with the same shard count are used.
+    pub async fn peers_for_shards(&self, num_shards: u16, shards: &[u16]) -> Vec<String> {
+        let state = self.peer_state.read().await;
+        let mut candidates: Vec<(&String, &ShardSet)> = state
+            .values()
+            .filter(|p| p.alive)
+            .filter_map(|p| p.shards.as_ref().map(|s| (&p.url, s)))
+            .filter(|(_, s)| s.num_shards() == num_shards)
+            .collect();
+        candidates.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut uncovered: BTreeSet<u16> = shards.iter().copied().collect();
+        let mut chosen = Vec::new();
+        while !uncovered.is_empty() {
+            let best = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, (_, s))| (i, uncovered.iter().filter(|&&u| s.contains_shard(u)).count()))
+                .filter(|&(_, covered)| covered > 0)
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+            let (i, _) = match best {
+                Some(best) => best,
+                None => break,
+            };
+            let (url, set) = candidates.remove(i);
+            uncovered.retain(|u| !set.contains_shard(*u));
+            chosen.push(url.clone());
+        }
+        chosen
+    }
+
+    /// Wrap `params` for `method` in a request signed with this node's
+    /// hotkey. Without a signing key, on a standby, or while the hotkey is
+    /// not registered (peers refuse unregistered hotkeys), they go unsigned.
+    pub async fn sign_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> serde_json::Value {
+        if self.signing_gate.as_ref().is_some_and(|gate| !gate.can_sign()) {
+            return params;
+        }
+        let key = match &self.announce_key {
+            Some(key) if self.is_registered(&key.hotkey).await => key,
+            _ => return params,
+        };
+        let signed = SignedRequest::sign(
+            method,
+            params.clone(),
+            KeyRole::Hotkey,
+            &key.signing_key,
+            key.hotkey,
+        )
+        .and_then(|signed| serde_json::to_value(signed).map_err(ChitinError::from));
+        match signed {
+            Ok(signed) => signed,
+            Err(e) => {
+                tracing::warn!("Failed to sign {} request: {}", method, e);
+                params
+            }
+        }
+    }
+
+    /// Send `peer/announce` to all configured peers, signed if this node has
+    /// a signing key for a registered hotkey.
+    /// Fire-and-forget: failures are logged, not propagated. Peers that
+    /// report a different network ID are marked not alive and no longer
+    /// synced or gossiped with.
+    pub async fn announce_to_all(&self) {
+        if self.signing_gate.as_ref().is_some_and(|gate| !gate.can_sign()) {
+            tracing::debug!("Standby; not announcing to peers");
+            return;
+        }
+        let mut announce = AnnounceRequest {
+            node_id: self.self_did.clone(),
+            url: self.self_url.clone(),
+            network_id: self.network_id.clone(),
+            signed_at: None,
+            signer: None,
+            signature: None,
+        };
+        let key = match &self.announce_key {
+            Some(key) if self.is_registered(&key.hotkey).await => Some(key),
+            _ => None,
+        };
+        if let Some(key) = key {
+            match announce.sign(&key.signing_key, key.hotkey) {
+                Ok(()) => {
+                    let mut own = self.own_claim.write().unwrap_or_else(|e| e.into_inner());
+                    own.signer = announce.signer.clone();
+                    own.signed_at = announce.signed_at;
+                }
+                Err(e) => tracing::warn!("Failed to sign announcement: {}", e),
+            }
+        }
+        let params = match serde_json::to_value(&announce) {
+            Ok(params) => params,
+            Err(e) => {
+                tracing::warn!("Failed to serialize announcement: {}", e);
+                return;
+            }
+        };
+
+        let peers = self
+            .configured_peers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for peer_url in peers {
+            let url = peer_url;
+            let params = params.clone();
+            let registry = self.clone();
+
+            tokio::spawn(async move {
+                let response: Result<AnnounceResponse, _> =
+                    call_peer(&registry.client, &url, "peer/announce", params).await;
+                match response {
+                    Ok(resp) => match (&registry.network_id, &resp.network_id) {
+                        (Some(ours), Some(theirs)) if ours != theirs => {
+                            tracing::warn!(
+                                "Peer {} is on network {:?}, not {:?}; ignoring it",
+                                url,
+                                theirs,
+                                ours
+                            );
+                            registry.set_foreign(&url, true);
+                            registry.mark_peer(&url, false, None).await;
+                        }
+                        _ => {
+                            tracing::info!("Announced to peer {}", url);
+                            registry.set_foreign(&url, false);
+                            registry.mark_peer(&url, true, resp.node_id).await;
+                        }
+                    },
+                    Err(e) => {
+                        tracing::warn!("Failed to announce to peer {}: {}", url, e);
+                        registry.mark_peer(&url, false, None).await;
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn new_peer_state(url: &str) -> PeerState {
+    PeerState {
+        url: url.to_string(),
+        node_id: None,
+        alive: false,
+        shards: None,
+        score: INITIAL_PEER_SCORE,
+        dial: DialStats::default(),
+    }
+}
+
+/// Record a call at `now` that succeeded (`ok`) or failed, backing off
+/// after failures.
+fn record_dial(dial: &mut DialStats, ok: bool, now: DateTime<Utc>) {
+    dial.attempts += 1;
+    if ok {
+        dial.consecutive_failures = 0;
+        dial.last_success_at = Some(now);
+        dial.next_dial_at = None;
+    } else {
+        dial.failures += 1;
+        dial.consecutive_failures = dial.consecutive_failures.saturating_add(1);
+        dial.last_failure_at = Some(now);
+        let delay = backoff_secs(dial.consecutive_failures, rand::random());
+        dial.next_dial_at = Some(now + chrono::Duration::milliseconds((delay * 1000.0) as i64));
+    }
+}
+
+/// True if a peer with `dial` statistics may be called at `now`.
+fn dial_due(dial: &DialStats, now: DateTime<Utc>) -> bool {
+    dial.next_dial_at.is_none_or(|at| at <= now)
+}
+
+/// Backoff in seconds after `failures` consecutive failures: doubling from
+/// `DIAL_BACKOFF_BASE_SECS` up to `DIAL_BACKOFF_MAX_SECS`, with the upper
+/// half scaled by `jitter` (in [0, 1)) so failing peers are not all retried
+/// at once.
+fn backoff_secs(failures: u32, jitter: f64) -> f64 {
+    let exponent = failures.saturating_sub(1).min(16) as i32;
+    let delay = (DIAL_BACKOFF_BASE_SECS * 2f64.powi(exponent)).min(DIAL_BACKOFF_MAX_SECS);
+    delay / 2.0 + delay / 2.0 * jitter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::crypto::Keypair;
+    use chitin_core::identity::NodeIdentity;
+    use chitin_core::NodeType;
+
+    /// A node registered in `identities`: its DID, hotkey, and hotkey
+    /// signing key.
+    fn register(identities: &mut IdentityRegistry) -> (String, [u8; 32], SecretKey) {
+        let coldkey = Keypair::generate().public_key_bytes();
+        let (hotkey, signing_key) = keys();
+        identities
+            .register(coldkey, hotkey, NodeType::Coral, 0)
+            .unwrap();
+        (NodeIdentity::derive_did(&coldkey), hotkey, signing_key)
+    }
+
+    fn keys() -> ([u8; 32], SecretKey) {
+        let keypair = Keypair::generate();
+        (
+            keypair.public_key_bytes(),
+            SecretKey::new(keypair.signing_key.to_bytes()),
+        )
+    }
+
+    fn announcement(did: &str, url: &str, key: Option<(&SecretKey, [u8; 32])>) -> AnnounceRequest {
+        let mut announce = AnnounceRequest {
+            node_id: Some(did.to_string()),
+            url: Some(url.to_string()),
+            network_id: None,
+            signed_at: None,
+            signer: None,
+            signature: None,
+        };
+        if let Some((signing_key, hotkey)) = key {
+            announce.sign(signing_key, hotkey).unwrap();
+        }
+        announce
+    }
+
+    fn peer_registry(identities: IdentityRegistry) -> PeerRegistry {
+        PeerRegistry::new(Some("http://self".to_string()), Vec::new())
+            .with_identity_registry(Arc::new(RwLock::new(identities)))
+    }
+
+    #[tokio::test]
+    async fn test_latest_registered_signature_is_preferred() {
+        let mut identities = IdentityRegistry::new();
+        let (did, hotkey, key) = register(&mut identities);
+        let registry = peer_registry(identities);
+
+        let first = announcement(&did, "http://a", Some((&key, hotkey)));
+        registry.record_announcement(&first).await;
+        assert!(registry.identity_conflicts().is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        let second = announcement(&did, "http://b", Some((&key, hotkey)));
+        registry.record_announcement(&second).await;
+
+        let conflicts = registry.identity_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].did, did);
+        assert!(!conflicts[0].local);
+        assert_eq!(conflicts[0].claims.len(), 2);
+        assert_eq!(conflicts[0].preferred.as_deref(), Some("http://b"));
+        assert_eq!(
+            registry.superseded_urls(),
+            BTreeSet::from(["http://a".to_string()])
+        );
+
+        // A claimant that stops answering no longer counts.
+        registry.set_claims_reachable("http://a", false);
+        assert!(registry.identity_conflicts().is_empty());
+        assert!(registry.superseded_urls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_forged_claim_is_reported_but_never_preferred() {
+        let mut identities = IdentityRegistry::new();
+        let (did, hotkey, key) = register(&mut identities);
+        let registry = peer_registry(identities);
+
+        let genuine = announcement(&did, "http://a", Some((&key, hotkey)));
+        registry.record_announcement(&genuine).await;
+        // Signed later, and validly, but with a key that is not the DID's.
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        let (forger, forger_key) = keys();
+        let forged = announcement(&did, "http://b", Some((&forger_key, forger)));
+        assert!(forged.verify_signature().unwrap());
+        registry.record_announcement(&forged).await;
+
+        let conflicts = registry.identity_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        let forged_claim = conflicts[0]
+            .claims
+            .iter()
+            .find(|c| c.url == "http://b")
+            .unwrap();
+        assert!(forged_claim.signer.is_none());
+        assert_eq!(conflicts[0].preferred.as_deref(), Some("http://a"));
+        assert_eq!(
+            registry.superseded_urls(),
+            BTreeSet::from(["http://b".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_or_unsigned_claims_prefer_no_url() {
+        let (hotkey, key) = keys();
+        let did = NodeIdentity::derive_did(&Keypair::generate().public_key_bytes());
+        let registry = peer_registry(IdentityRegistry::new());
+
+        let signed = announcement(&did, "http://a", Some((&key, hotkey)));
+        registry.record_announcement(&signed).await;
+        registry
+            .record_announcement(&announcement(&did, "http://b", None))
+            .await;
+
+        let conflicts = registry.identity_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].claims.iter().all(|c| c.signer.is_none()));
+        assert_eq!(conflicts[0].preferred, None);
+        assert!(registry.superseded_urls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_own_did_claimed_elsewhere_is_a_local_conflict() {
+        let mut identities = IdentityRegistry::new();
+        let (did, hotkey, key) = register(&mut identities);
+        let registry =
+            peer_registry(identities).with_identity(Some(did.clone()), hotkey, Some(key));
+
+        registry
+            .record_announcement(&announcement(&did, "http://a", None))
+            .await;
+
+        let conflicts = registry.identity_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].local);
+        let urls: Vec<&str> = conflicts[0].claims.iter().map(|c| c.url.as_str()).collect();
+        assert_eq!(urls, vec!["http://a", "http://self"]);
+        // This node never supersedes itself.
+        assert!(!registry.superseded_urls().contains("http://self"));
+    }
+}
@@ -0,0 +1,214 @@
+// crates/chitin-node/src/pruning.rs
+//
+// Background state pruning for non-archival nodes.
+//
+// Every `interval_secs`, deletes history older than its retention window,
+// counted in epochs back from the current one:
+//
+// - consensus history (`ConsensusRecord`) and hardening checkpoints, below
+//   which `history/*` queries answer "pruned", and beacon contributions
+//   (kept as long as consensus history);
+// - molted predecessors, once their successor has existed long enough for
+//   peers to have it, leaving a tombstone so sync does not pull them back
+//   (molt records stay, so lineage still lists them);
+// - the tombstones themselves.
+//
+// Reputation checkpoints are pruned by the trust store as they are recorded;
+// `reputation_checkpoints` sets how many it keeps. Entries and bytes
+// reclaimed are reported as `chitin_pruned_*_total{kind}` metrics. Archival
+// nodes never prune.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::beacon;
+use chitin_consensus::hardening::HardeningCheckpoint;
+use chitin_consensus::history::ConsensusRecord;
+use chitin_core::traits::{PolypStore, VectorIndex};
+use chitin_core::{ChitinError, Polyp, PolypState};
+use chitin_drift::molting::molt_record;
+use chitin_reputation::domain_store::CHECKPOINT_RETENTION;
+use chitin_store::{InMemoryVectorIndex, Reclaimed, RocksStore};
+use chitin_sync::tombstone::Tombstone;
+use uuid::Uuid;
+
+use crate::shared::DaemonSharedState;
+
+/// Retention settings for non-archival nodes (`[pruning]` table).
+///
+/// Windows are in epochs; 0 keeps that kind of state forever.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PruningConfig {
+    /// Seconds between pruning passes (0 disables pruning).
+    pub interval_secs: u64,
+    /// Epochs of consensus results kept.
+    pub consensus_history_epochs: u64,
+    /// Epochs of hardening checkpoints kept.
+    pub hardening_checkpoint_epochs: u64,
+    /// Number of reputation checkpoints kept.
+    pub reputation_checkpoints: usize,
+    /// Epochs a molted predecessor is kept after its molt.
+    pub molted_epochs: u64,
+    /// Epochs a pruned Polyp's tombstone is kept.
+    pub tombstone_epochs: u64,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 3600,
+            consensus_history_epochs: 168,
+            hardening_checkpoint_epochs: 8760,
+            reputation_checkpoints: CHECKPOINT_RETENTION,
+            molted_epochs: 168,
+            tombstone_epochs: 720,
+        }
+    }
+}
+
+/// Run pruning passes until the process exits.
+///
+/// Returns immediately on archival nodes and if `config.interval_secs` is
+/// zero.
+pub async fn run_pruner(
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    index: Option<Arc<InMemoryVectorIndex>>,
+    config: PruningConfig,
+) {
+    if shared.archival || config.interval_secs == 0 {
+        tracing::info!("State pruning disabled");
+        return;
+    }
+    tracing::info!(
+        "State pruning started (every {}s; consensus {}, checkpoints {}, molted {}, \
+         tombstones {} epochs)",
+        config.interval_secs,
+        config.consensus_history_epochs,
+        config.hardening_checkpoint_epochs,
+        config.molted_epochs,
+        config.tombstone_epochs
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    loop {
+        interval.tick().await;
+        let epoch = shared.epoch_manager.read().await.current_epoch();
+        prune_once(&shared, &store, index.as_deref(), &config, epoch).await;
+    }
+}
+
+/// One pruning pass at `epoch`.
+async fn prune_once(
+    shared: &DaemonSharedState,
+    store: &RocksStore,
+    index: Option<&InMemoryVectorIndex>,
+    config: &PruningConfig,
+    epoch: u64,
+) {
+    if let Some(horizon) = horizon(epoch, config.tombstone_epochs) {
+        let pruned = Tombstone::prune_before(store, horizon);
+        report(shared, epoch, "tombstones", horizon, pruned);
+    }
+    if let Some(horizon) = horizon(epoch, config.consensus_history_epochs) {
+        let pruned = ConsensusRecord::prune_before(store, horizon);
+        report(shared, epoch, "consensus_history", horizon, pruned);
+        let pruned = beacon::prune_before(store, horizon);
+        report(shared, epoch, "beacon_contributions", horizon, pruned);
+    }
+    if let Some(horizon) = horizon(epoch, config.hardening_checkpoint_epochs) {
+        let pruned = HardeningCheckpoint::prune_before(store, horizon);
+        report(shared, epoch, "hardening_checkpoints", horizon, pruned);
+    }
+    if let Some(horizon) = horizon(epoch, config.molted_epochs) {
+        let pruned = prune_molted(store, index, horizon, epoch).await;
+        report(shared, epoch, "molted_predecessors", horizon, pruned);
+    }
+}
+
+/// Log and record the outcome of pruning `kind` before `horizon`.
+fn report(
+    shared: &DaemonSharedState,
+    epoch: u64,
+    kind: &str,
+    horizon: u64,
+    pruned: Result<Reclaimed, ChitinError>,
+) {
+    match pruned {
+        Ok(reclaimed) => {
+            shared.metrics.observe_pruned(kind, reclaimed);
+            if reclaimed.entries > 0 {
+                tracing::info!(
+                    "Epoch {}: Pruned {} {} entries before epoch {} ({} bytes)",
+                    epoch,
+                    reclaimed.entries,
+                    kind,
+                    horizon,
+                    reclaimed.bytes
+                );
+            }
+        }
+        Err(e) => tracing::warn!("Epoch {}: Failed to prune {}: {}", epoch, kind, e),
+    }
+}
+
+/// First epoch kept at `epoch` under a `retention`-epoch window, or `None`
+/// if nothing is old enough to prune.
+fn horizon(epoch: u64, retention: u64) -> Option<u64> {
+    match retention {
+        0 => None,
+        retention => Some(epoch.saturating_sub(retention)).filter(|&h| h > 0),
+    }
+}
+
+/// Delete Polyps molted before `horizon`, tombstoning each in `epoch`.
+async fn prune_molted(
+    store: &RocksStore,
+    index: Option<&InMemoryVectorIndex>,
+    horizon: u64,
+    epoch: u64,
+) -> Result<Reclaimed, ChitinError> {
+    let molted = PolypState::Molted {
+        successor_id: Uuid::nil(),
+    };
+    let mut reclaimed = Reclaimed::default();
+    for polyp in store.list_polyps_by_state(&molted).await? {
+        let molted_at = match molted_at_epoch(store, &polyp).await? {
+            Some(molted_at) => molted_at,
+            None => continue,
+        };
+        if molted_at >= horizon {
+            continue;
+        }
+        Tombstone::new(polyp.id, epoch).save(store)?;
+        reclaimed += store.prune_polyp(&polyp.id)?;
+        if let Some(index) = index {
+            index.delete(&polyp.id).await?;
+        }
+    }
+    Ok(reclaimed)
+}
+
+/// Epoch in which `polyp` was molted: from the local molt record, else from
+/// its successor's provenance (for predecessors received by sync).
+async fn molted_at_epoch(store: &RocksStore, polyp: &Polyp) -> Result<Option<u64>, ChitinError> {
+    if let Some(record) = molt_record(store, &polyp.id)? {
+        return Ok(Some(record.epoch));
+    }
+    let successor_id = match &polyp.state {
+        PolypState::Molted { successor_id } => successor_id,
+        _ => return Ok(None),
+    };
+    Ok(store.get_polyp(successor_id).await?.and_then(|successor| {
+        successor
+            .subject
+            .provenance
+            .molted_from
+            .iter()
+            .find(|ancestor| ancestor.polyp_id == polyp.id)
+            .map(|ancestor| ancestor.epoch)
+    }))
+}
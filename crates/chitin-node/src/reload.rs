@@ -0,0 +1,387 @@
+// crates/chitin-node/src/reload.rs
+//
+// Configuration hot reload for the Chitin Protocol daemon.
+//
+// The daemon keeps its configuration behind a shared `ConfigHandle`. On
+// SIGHUP, when the config file's modification time changes, or through the
+// `admin/config/reload` RPC, the file is re-read and the hot-reloadable
+// fields are diffed against the live configuration and applied:
+//
+// - `log_level`: swapped into the tracing filter (unless `RUST_LOG` is set)
+// - `peers`: replaces the peer registry's configured peers
+// - `sync_interval_secs`: read by the sync loop before each round
+//
+// Every other field takes effect on the next restart.
+//
+// `admin/config` serves the live configuration, and `admin/config/update`
+// merges updates into the config file with `toml_edit` (keeping its
+// comments and layout), validates the result as a whole, writes it, and
+// reloads.
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+use toml_edit::{DocumentMut, Item, TableLike};
+
+use chitin_rpc::handlers::admin::{
+    config_version, ReloadConfigResponse, UpdateConfigRequest, UpdateConfigResponse,
+};
+use chitin_rpc::{ConfigReloadCallback, ConfigUpdateCallback, ConfigViewCallback};
+
+use crate::config::DaemonConfig;
+use crate::peers::PeerRegistry;
+
+/// Seconds between checks of the config file's modification time.
+const WATCH_INTERVAL_SECS: u64 = 5;
+
+/// Top-level config keys applied by a reload; the rest need a restart.
+const HOT_RELOADABLE: [&str; 3] = ["log_level", "peers", "sync_interval_secs"];
+
+/// Applies a log filter (e.g. "info,chitin_sync=debug") to the running
+/// tracing subscriber.
+pub type LogLevelSetter = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Shared handle to the live daemon configuration.
+#[derive(Clone)]
+pub struct ConfigHandle {
+    /// Path the configuration is reloaded from.
+    path: String,
+    /// The configuration currently in effect.
+    current: Arc<RwLock<DaemonConfig>>,
+    /// Registry whose configured peers follow `peers`, if networking is on.
+    peers: Option<Arc<PeerRegistry>>,
+    /// Applies `log_level`, unless the filter comes from `RUST_LOG`.
+    log_level: Option<LogLevelSetter>,
+    /// Serializes reloads so that diffs are taken against the latest config.
+    reloading: Arc<tokio::sync::Mutex<()>>,
+    /// Serializes updates so that none is lost between read and write.
+    updating: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ConfigHandle {
+    /// Create a handle for `config`, loaded from `path`.
+    pub fn new(path: &str, config: DaemonConfig) -> Self {
+        Self {
+            path: path.to_string(),
+            current: Arc::new(RwLock::new(config)),
+            peers: None,
+            log_level: None,
+            reloading: Arc::new(tokio::sync::Mutex::new(())),
+            updating: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Apply reloaded peer lists to `registry`.
+    pub fn with_peer_registry(mut self, registry: Arc<PeerRegistry>) -> Self {
+        self.peers = Some(registry);
+        self
+    }
+
+    /// Apply reloaded log levels through `setter`.
+    pub fn with_log_level_setter(mut self, setter: LogLevelSetter) -> Self {
+        self.log_level = Some(setter);
+        self
+    }
+
+    /// Seconds between pull-sync rounds in the live configuration.
+    pub async fn sync_interval_secs(&self) -> u64 {
+        self.current.read().await.sync_interval_secs
+    }
+
+    /// Re-read the config file and apply its hot-reloadable fields.
+    ///
+    /// Returns a description of each change applied. Nothing is applied if
+    /// the file cannot be loaded or a changed field is invalid.
+    pub async fn reload(&self) -> Result<Vec<String>, String> {
+        let _reloading = self.reloading.lock().await;
+        let mut loaded = DaemonConfig::load(&self.path)
+            .map_err(|e| format!("Failed to load {}: {}", self.path, e))?;
+        let old = self.current.read().await.clone();
+        // Keep the network chosen at startup, so its bootstrap peers stay.
+        loaded.network = old.network.clone();
+        loaded
+            .apply_network()
+            .map_err(|e| format!("Invalid network: {}", e))?;
+        let changes = hot_changes(&old, &loaded);
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+        if loaded.sync_interval_secs == 0 {
+            return Err("sync_interval_secs must be positive".to_string());
+        }
+
+        if loaded.log_level != old.log_level {
+            if let Some(set_log_level) = &self.log_level {
+                set_log_level(&loaded.log_level)
+                    .map_err(|e| format!("Invalid log_level {:?}: {}", loaded.log_level, e))?;
+            }
+        }
+        if loaded.peers != old.peers {
+            match &self.peers {
+                Some(registry) => {
+                    let (added, removed) =
+                        registry.set_configured_peers(loaded.peers.clone()).await;
+                    if !added.is_empty() {
+                        registry.announce_to_all().await;
+                    }
+                    tracing::info!("Peers reloaded: {:?} added, {:?} removed", added, removed);
+                }
+                None => tracing::warn!(
+                    "Peer networking was disabled at startup; restart to apply the peer list"
+                ),
+            }
+        }
+
+        let mut current = self.current.write().await;
+        current.log_level = loaded.log_level;
+        current.peers = loaded.peers;
+        current.sync_interval_secs = loaded.sync_interval_secs;
+        Ok(changes)
+    }
+
+    /// The configuration in effect, as JSON.
+    pub async fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(&*self.current.read().await).unwrap_or_default()
+    }
+
+    /// Merge `updates` into the config file and reload it. Unless
+    /// `persist`, the merged file is only validated.
+    ///
+    /// `updates` is a JSON object whose nested objects are tables; `null`
+    /// removes a key, restoring its default.
+    pub async fn update(
+        &self,
+        updates: &serde_json::Value,
+        persist: bool,
+    ) -> Result<UpdateConfigResponse, String> {
+        if self.path.is_empty() {
+            return Err("The node has no config file to update".to_string());
+        }
+        let updates = match updates.as_object() {
+            Some(updates) => updates,
+            None => return Err("Config updates must be a JSON object".to_string()),
+        };
+        let _updating = self.updating.lock().await;
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", self.path, e)),
+        };
+        let mut document: DocumentMut = text
+            .parse()
+            .map_err(|e| format!("Failed to parse {}: {}", self.path, e))?;
+        let mut keys = Vec::new();
+        merge(document.as_table_mut(), updates, "", &mut keys)?;
+
+        // Validate the whole file as a reload would.
+        let updated = document.to_string();
+        let mut loaded: DaemonConfig =
+            toml::from_str(&updated).map_err(|e| format!("Invalid config: {}", e))?;
+        let old = self.current.read().await.clone();
+        loaded.network = old.network.clone();
+        loaded
+            .apply_network()
+            .map_err(|e| format!("Invalid network: {}", e))?;
+        if loaded.sync_interval_secs == 0 {
+            return Err("sync_interval_secs must be positive".to_string());
+        }
+        let restart_required: Vec<String> = keys
+            .into_iter()
+            .filter(|key| {
+                let top = key.split('.').next().unwrap_or_default();
+                !HOT_RELOADABLE.contains(&top)
+            })
+            .collect();
+
+        if !persist {
+            return Ok(UpdateConfigResponse {
+                applied: false,
+                persisted: false,
+                message: format!("Valid; {} not written", self.path),
+                new_config_version: None,
+                changes: hot_changes(&old, &loaded),
+                restart_required,
+            });
+        }
+
+        // Replace the file in one rename, so a crash never leaves half of it.
+        let staged = format!("{}.tmp", self.path);
+        std::fs::write(&staged, &updated)
+            .and_then(|()| std::fs::rename(&staged, &self.path))
+            .map_err(|e| format!("Failed to write {}: {}", self.path, e))?;
+        let changes = self
+            .reload()
+            .await
+            .map_err(|e| format!("{} was updated but not reloaded: {}", self.path, e))?;
+        tracing::info!(
+            "Config updated over RPC: {} applied, {} need a restart",
+            changes.len(),
+            restart_required.len()
+        );
+        let message = if restart_required.is_empty() {
+            format!("Updated {}", self.path)
+        } else {
+            format!("Updated {}; restart to apply the rest", self.path)
+        };
+        Ok(UpdateConfigResponse {
+            applied: true,
+            persisted: true,
+            message,
+            new_config_version: Some(config_version(&self.to_json().await)),
+            changes,
+            restart_required,
+        })
+    }
+
+    /// Adapt `to_json` into the RPC server's `admin/config` callback.
+    pub fn view_callback(&self) -> ConfigViewCallback {
+        let handle = self.clone();
+        Arc::new(move || {
+            let handle = handle.clone();
+            Box::pin(async move { handle.to_json().await })
+        })
+    }
+
+    /// Adapt `update` into the RPC server's `admin/config/update` callback.
+    pub fn update_callback(&self) -> ConfigUpdateCallback {
+        let handle = self.clone();
+        Arc::new(move |request: UpdateConfigRequest| {
+            let handle = handle.clone();
+            Box::pin(async move {
+                handle
+                    .update(&request.updates, request.persist.unwrap_or(true))
+                    .await
+            })
+        })
+    }
+
+    /// Adapt `reload` into the RPC server's `admin/config/reload` callback.
+    pub fn reload_callback(&self) -> ConfigReloadCallback {
+        let handle = self.clone();
+        Arc::new(move || {
+            let handle = handle.clone();
+            Box::pin(async move {
+                let applied = handle.reload().await?;
+                Ok(ReloadConfigResponse {
+                    path: handle.path.clone(),
+                    applied,
+                })
+            })
+        })
+    }
+}
+
+/// Describe the hot-reloadable fields that differ between `old` and `new`.
+pub fn hot_changes(old: &DaemonConfig, new: &DaemonConfig) -> Vec<String> {
+    let mut changes = Vec::new();
+    if old.log_level != new.log_level {
+        changes.push(format!("log_level: {:?} -> {:?}", old.log_level, new.log_level));
+    }
+    if old.peers != new.peers {
+        changes.push(format!("peers: {} -> {}", old.peers.len(), new.peers.len()));
+    }
+    if old.sync_interval_secs != new.sync_interval_secs {
+        changes.push(format!(
+            "sync_interval_secs: {} -> {}",
+            old.sync_interval_secs, new.sync_interval_secs
+        ));
+    }
+    changes
+}
+
+/// Merge JSON `updates` into `table`, recording the dotted path of each key
+/// set or removed in `keys`.
+fn merge(
+    table: &mut dyn TableLike,
+    updates: &serde_json::Map<String, serde_json::Value>,
+    prefix: &str,
+    keys: &mut Vec<String>,
+) -> Result<(), String> {
+    for (key, value) in updates {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            serde_json::Value::Null => {
+                table.remove(key);
+                keys.push(path);
+            }
+            serde_json::Value::Object(fields) => {
+                if table.get(key).and_then(Item::as_table_like).is_none() {
+                    table.insert(key, toml_edit::table());
+                }
+                match table.get_mut(key).and_then(Item::as_table_like_mut) {
+                    Some(nested) => merge(nested, fields, &path, keys)?,
+                    None => return Err(format!("{} is not a table", path)),
+                }
+            }
+            value => {
+                let value = value
+                    .serialize(toml_edit::ser::ValueSerializer::new())
+                    .map_err(|e| format!("Invalid value for {}: {}", path, e))?;
+                table.insert(key, Item::Value(value));
+                keys.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reload `handle` on SIGHUP and whenever the config file is modified,
+/// until the process exits.
+pub async fn watch_config(handle: ConfigHandle) {
+    #[cfg(unix)]
+    let mut hangup =
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGHUP: {}", e);
+                None
+            }
+        };
+
+    let mut modified = modified_at(&handle.path);
+    let mut interval = tokio::time::interval(Duration::from_secs(WATCH_INTERVAL_SECS));
+    loop {
+        #[cfg(unix)]
+        let trigger = tokio::select! {
+            Some(()) = async {
+                match hangup.as_mut() {
+                    Some(signal) => signal.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => "SIGHUP",
+            _ = interval.tick() => "file change",
+        };
+        #[cfg(not(unix))]
+        let trigger = {
+            interval.tick().await;
+            "file change"
+        };
+
+        if trigger == "file change" {
+            let now = modified_at(&handle.path);
+            if now == modified {
+                continue;
+            }
+            modified = now;
+        }
+        match handle.reload().await {
+            Ok(changes) if changes.is_empty() => {
+                tracing::debug!("Config reload ({}): no hot-reloadable changes", trigger);
+            }
+            Ok(changes) => {
+                tracing::info!("Config reloaded ({}): {}", trigger, changes.join(", "));
+            }
+            Err(e) => tracing::warn!("Config reload ({}) failed: {}", trigger, e),
+        }
+    }
+}
+
+fn modified_at(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
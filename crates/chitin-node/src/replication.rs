@@ -0,0 +1,562 @@
+// crates/chitin-node/src/replication.rs
+//
+// Hot standby replication between two daemons.
+//
+// With `[replication] role = "standby"`, a daemon follows the primary at
+// `primary_url`: it pulls the primary's polyps through the ordinary sync loop
+// (the primary is added to its peers), and every `poll_interval_secs` fetches
+// the primary's runtime state from `replication/state` (epoch position,
+// weights, bonds, consensus results, and metagraph; never keys) into its own.
+// While following it signs nothing: it submits no weights, accepts no polyps,
+// serves unsigned state updates, and does not announce itself to peers.
+//
+// `admin/promote` makes a standby the primary; like every `admin/*` method it
+// is served only to callers on the node's own host, and it is refused while
+// the primary answered within `failover_timeout_secs` unless forced. To rule out double
+// signing, the promoted node never signs weights for an epoch up to the later
+// of the latest one the old primary reported signing and the one it is in
+// when promoted, and every node persists the latest epoch it signed and never
+// signs an earlier one. The promotion is persisted, so a restarted node stays
+// primary even if its config still says standby; the old primary must be
+// reconfigured as a standby before it is restarted.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_consensus::epoch::EpochManager;
+use chitin_core::ChitinError;
+use chitin_rpc::handlers::replication::{
+    GetReplicationStateResponse, PromoteResponse, ReplicationStatusResponse,
+};
+use chitin_rpc::{
+    PromoteCallback, ReplicationStateCallback, ReplicationStatusCallback, SigningAllowedCallback,
+};
+use chitin_store::RocksStore;
+
+use crate::peers::PeerRegistry;
+use crate::runtime_state::{RuntimeSnapshot, StatePersister};
+use crate::sync_loop::call_peer;
+
+/// Key of the persisted signing record.
+const KEY: &str = "replication_signing_state";
+
+/// Whether a daemon signs or follows another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplicationRole {
+    /// Signs and serves its state to standbys.
+    #[default]
+    Primary,
+    /// Follows a primary without signing until promoted.
+    Standby,
+}
+
+impl ReplicationRole {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Standby => "standby",
+        }
+    }
+}
+
+/// Replication settings (`[replication]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplicationConfig {
+    /// This daemon's role.
+    pub role: ReplicationRole,
+    /// RPC URL of the primary a standby follows.
+    pub primary_url: Option<String>,
+    /// Seconds between fetches of the primary's state.
+    pub poll_interval_secs: u64,
+    /// Seconds without an answer after which the primary counts as down.
+    pub failover_timeout_secs: u64,
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self {
+            role: ReplicationRole::Primary,
+            primary_url: None,
+            poll_interval_secs: 5,
+            failover_timeout_secs: 30,
+        }
+    }
+}
+
+impl ReplicationConfig {
+    /// Check that the settings are usable.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        if self.poll_interval_secs == 0 || self.failover_timeout_secs < self.poll_interval_secs {
+            return Err(ChitinError::InvalidState(
+                "poll_interval_secs must be positive and at most failover_timeout_secs".to_string(),
+            ));
+        }
+        match (&self.role, &self.primary_url) {
+            (ReplicationRole::Standby, Some(url))
+                if url.starts_with("http://") || url.starts_with("https://") =>
+            {
+                Ok(())
+            }
+            (ReplicationRole::Standby, _) => Err(ChitinError::InvalidState(
+                "a standby needs an http(s) primary_url".to_string(),
+            )),
+            (ReplicationRole::Primary, _) => Ok(()),
+        }
+    }
+}
+
+/// Signing state persisted across restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SigningRecord {
+    /// Whether this node was promoted from standby.
+    promoted: bool,
+    /// Latest epoch weights were signed for.
+    last_signed_epoch: Option<u64>,
+    /// Epochs up to and including this one are never signed.
+    floor_epoch: Option<u64>,
+}
+
+/// What a gate knows, guarded by its lock.
+#[derive(Debug)]
+struct GateState {
+    role: ReplicationRole,
+    record: SigningRecord,
+    /// When the primary last answered, for liveness.
+    last_contact: Option<Instant>,
+    last_contact_at: Option<DateTime<Utc>>,
+    /// The epoch the primary was in and the latest it signed, as last seen.
+    primary_epoch: Option<u64>,
+    primary_last_signed_epoch: Option<u64>,
+}
+
+/// Decides whether this node may sign; clones share it.
+#[derive(Debug, Clone)]
+pub struct SigningGate {
+    state: Arc<std::sync::RwLock<GateState>>,
+    store: Arc<RocksStore>,
+    config: ReplicationConfig,
+}
+
+impl SigningGate {
+    /// Open the gate for `config`, restoring the signing record in `store`.
+    pub fn open(config: &ReplicationConfig, store: Arc<RocksStore>) -> Result<Self, ChitinError> {
+        let record: SigningRecord = match store.get_bytes(KEY.as_bytes())? {
+            Some(bytes) => serde_json::from_slice(&bytes)?,
+            None => SigningRecord::default(),
+        };
+        let role = if record.promoted {
+            if config.role == ReplicationRole::Standby {
+                tracing::warn!(
+                    "This node was promoted to primary; ignoring role = \"standby\" \
+                     (update [replication] in the config)"
+                );
+            }
+            ReplicationRole::Primary
+        } else {
+            config.role
+        };
+        Ok(Self {
+            state: Arc::new(std::sync::RwLock::new(GateState {
+                role,
+                record,
+                last_contact: None,
+                last_contact_at: None,
+                primary_epoch: None,
+                primary_last_signed_epoch: None,
+            })),
+            store,
+            config: config.clone(),
+        })
+    }
+
+    /// Whether this node may sign (false while following a primary).
+    pub fn can_sign(&self) -> bool {
+        self.read().role == ReplicationRole::Primary
+    }
+
+    /// Allow signing weights for `epoch`, recording it, or refuse: on a
+    /// standby, at or below the promotion floor, and before an epoch
+    /// already signed.
+    pub fn authorize_epoch(&self, epoch: u64) -> Result<(), ChitinError> {
+        let record = {
+            let mut state = self.write();
+            if state.role == ReplicationRole::Standby {
+                return Err(ChitinError::InvalidState(
+                    "Standby node: not signing while the primary is active".to_string(),
+                ));
+            }
+            if let Some(floor) = state.record.floor_epoch.filter(|&floor| epoch <= floor) {
+                return Err(ChitinError::InvalidState(format!(
+                    "Refusing to sign epoch {}: the previous primary may have signed up to \
+                     epoch {}",
+                    epoch, floor
+                )));
+            }
+            match state.record.last_signed_epoch {
+                Some(last) if epoch < last => {
+                    return Err(ChitinError::InvalidState(format!(
+                        "Refusing to sign epoch {}: already signed epoch {}",
+                        epoch, last
+                    )))
+                }
+                Some(last) if epoch == last => return Ok(()),
+                _ => {}
+            }
+            state.record.last_signed_epoch = Some(epoch);
+            state.record.clone()
+        };
+        self.save(&record)
+    }
+
+    /// Promote this standby to primary. Refused while the primary is alive
+    /// unless `force`; `current_epoch` is the epoch this node is in.
+    pub fn promote(&self, force: bool, current_epoch: u64) -> Result<PromoteResponse, String> {
+        let record = {
+            let state = self.read();
+            if state.role == ReplicationRole::Primary {
+                return Ok(PromoteResponse {
+                    promoted: false,
+                    signing_floor_epoch: state.record.floor_epoch,
+                    message: "Already primary".to_string(),
+                });
+            }
+            if !force && self.primary_alive(&state) {
+                let secs = state
+                    .last_contact
+                    .map(|t| t.elapsed().as_secs())
+                    .unwrap_or(0);
+                return Err(format!(
+                    "Primary answered {}s ago; stop it before promoting, or pass force",
+                    secs
+                ));
+            }
+            let floor = [
+                Some(current_epoch),
+                state.primary_epoch,
+                state.primary_last_signed_epoch,
+                state.record.last_signed_epoch,
+            ]
+            .into_iter()
+            .flatten()
+            .max();
+            SigningRecord {
+                promoted: true,
+                last_signed_epoch: state.record.last_signed_epoch,
+                floor_epoch: floor,
+            }
+        };
+        self.save(&record).map_err(|e| e.to_string())?;
+        let floor = record.floor_epoch;
+        {
+            let mut state = self.write();
+            state.role = ReplicationRole::Primary;
+            state.record = record;
+        }
+
+        let resumes = floor.map_or(0, |floor| floor + 1);
+        tracing::warn!(
+            "Promoted to primary; signing weights from epoch {}",
+            resumes
+        );
+        Ok(PromoteResponse {
+            promoted: true,
+            signing_floor_epoch: floor,
+            message: format!(
+                "Promoted to primary; signing weights from epoch {}",
+                resumes
+            ),
+        })
+    }
+
+    /// This node's replication status.
+    pub fn status(&self) -> ReplicationStatusResponse {
+        let state = self.read();
+        ReplicationStatusResponse {
+            role: state.role.as_str().to_string(),
+            signing: state.role == ReplicationRole::Primary,
+            last_signed_epoch: state.record.last_signed_epoch,
+            signing_floor_epoch: state.record.floor_epoch,
+            primary_url: self.config.primary_url.clone(),
+            primary_alive: self.primary_alive(&state),
+            last_primary_contact: state.last_contact_at,
+            primary_last_signed_epoch: state.primary_last_signed_epoch,
+        }
+    }
+
+    /// Callback reporting whether this node may sign.
+    pub fn signing_allowed(&self) -> SigningAllowedCallback {
+        let gate = self.clone();
+        Arc::new(move || gate.can_sign())
+    }
+
+    /// Callback serving `replication/status`.
+    pub fn status_callback(&self) -> ReplicationStatusCallback {
+        let gate = self.clone();
+        Arc::new(move || {
+            let gate = gate.clone();
+            Box::pin(async move { gate.status() })
+        })
+    }
+
+    /// Callback serving `replication/state`: `persister`'s snapshot without
+    /// peers, with this node's `hotkey` (hex) and signing progress.
+    pub fn state_callback(
+        &self,
+        persister: StatePersister,
+        hotkey: Option<String>,
+    ) -> ReplicationStateCallback {
+        let gate = self.clone();
+        Arc::new(move || {
+            let gate = gate.clone();
+            let persister = persister.clone();
+            let hotkey = hotkey.clone();
+            Box::pin(async move {
+                let mut snapshot = persister.capture().await;
+                snapshot.peers.clear();
+                let epoch = snapshot.epoch;
+                let snapshot = serde_json::to_value(&snapshot).map_err(|e| e.to_string())?;
+                let state = gate.read();
+                Ok(GetReplicationStateResponse {
+                    role: state.role.as_str().to_string(),
+                    hotkey,
+                    epoch,
+                    last_signed_epoch: state.record.last_signed_epoch,
+                    snapshot,
+                })
+            })
+        })
+    }
+
+    /// Callback serving `admin/promote`. Once promoted, the node announces
+    /// itself to `registry`'s peers.
+    pub fn promote_callback(
+        &self,
+        epochs: Arc<RwLock<EpochManager>>,
+        registry: Option<Arc<PeerRegistry>>,
+    ) -> PromoteCallback {
+        let gate = self.clone();
+        Arc::new(move |request| {
+            let gate = gate.clone();
+            let epochs = epochs.clone();
+            let registry = registry.clone();
+            Box::pin(async move {
+                let epoch = epochs.read().await.current_epoch();
+                let response = gate.promote(request.force, epoch)?;
+                if let (true, Some(registry)) = (response.promoted, registry) {
+                    registry.announce_to_all().await;
+                }
+                Ok(response)
+            })
+        })
+    }
+
+    /// Follow the primary until promoted, applying its runtime state through
+    /// `persister`. Returns immediately on a primary. `hotkey` (hex) is this
+    /// node's; a primary signing with another is not followed.
+    pub async fn run_standby(self, persister: StatePersister, hotkey: Option<String>) {
+        let primary_url = match (&self.config.primary_url, self.can_sign()) {
+            (Some(url), false) => url.clone(),
+            _ => return,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.config.poll_interval_secs.max(5)))
+            .build()
+            .unwrap_or_default();
+        tracing::info!(
+            "Standby: following primary {} every {}s",
+            primary_url,
+            self.config.poll_interval_secs
+        );
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs));
+        let mut reported_down = false;
+        loop {
+            interval.tick().await;
+            if self.can_sign() {
+                tracing::info!("Promoted; no longer following {}", primary_url);
+                return;
+            }
+            match self
+                .follow(&client, &primary_url, &persister, hotkey.as_deref())
+                .await
+            {
+                Ok(epoch) => {
+                    if reported_down {
+                        tracing::info!("Primary {} is answering again", primary_url);
+                        reported_down = false;
+                    }
+                    tracing::debug!(
+                        "Standby: synced state of {} at epoch {}",
+                        primary_url,
+                        epoch
+                    );
+                }
+                Err(e) if !reported_down && !self.primary_alive(&self.read()) => {
+                    tracing::error!(
+                        "Primary {} has not answered for {}s ({}); promote this standby with \
+                         admin/promote",
+                        primary_url,
+                        self.config.failover_timeout_secs,
+                        e
+                    );
+                    reported_down = true;
+                }
+                Err(e) => tracing::debug!("Standby: polling {} failed: {}", primary_url, e),
+            }
+        }
+    }
+
+    /// Fetch and apply the primary's state once, returning its epoch.
+    async fn follow(
+        &self,
+        client: &reqwest::Client,
+        primary_url: &str,
+        persister: &StatePersister,
+        hotkey: Option<&str>,
+    ) -> Result<u64, ChitinError> {
+        let response: GetReplicationStateResponse = call_peer(
+            client,
+            primary_url,
+            "replication/state",
+            serde_json::json!({}),
+        )
+        .await?;
+        if response.role != ReplicationRole::Primary.as_str() {
+            return Err(ChitinError::InvalidState(format!(
+                "{} is a {}, not a primary",
+                primary_url, response.role
+            )));
+        }
+        if let (Some(ours), Some(theirs)) = (hotkey, response.hotkey.as_deref()) {
+            if ours != theirs {
+                return Err(ChitinError::InvalidState(format!(
+                    "primary signs as {}, this node as {}",
+                    theirs, ours
+                )));
+            }
+        }
+        let snapshot: RuntimeSnapshot = serde_json::from_value(response.snapshot)?;
+        persister.apply(&snapshot).await?;
+
+        let mut state = self.write();
+        state.last_contact = Some(Instant::now());
+        state.last_contact_at = Some(Utc::now());
+        state.primary_epoch = Some(response.epoch);
+        state.primary_last_signed_epoch = state
+            .primary_last_signed_epoch
+            .max(response.last_signed_epoch);
+        Ok(response.epoch)
+    }
+
+    /// Whether the primary answered within the failover timeout.
+    fn primary_alive(&self, state: &GateState) -> bool {
+        let timeout = Duration::from_secs(self.config.failover_timeout_secs);
+        state.last_contact.is_some_and(|t| t.elapsed() < timeout)
+    }
+
+    fn save(&self, record: &SigningRecord) -> Result<(), ChitinError> {
+        self.store
+            .put_bytes(KEY.as_bytes(), &serde_json::to_vec(record)?)?;
+        self.store.flush()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, GateState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, GateState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_store(label: &str) -> (Arc<RocksStore>, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-node-replication-{}-{}",
+            label,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        (Arc::new(store), path)
+    }
+
+    fn standby() -> ReplicationConfig {
+        ReplicationConfig {
+            role: ReplicationRole::Standby,
+            primary_url: Some("http://127.0.0.1:50051".to_string()),
+            ..ReplicationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_primary_never_signs_an_earlier_epoch() {
+        let (store, path) = open_store("epochs");
+        let gate = SigningGate::open(&ReplicationConfig::default(), store).unwrap();
+
+        gate.authorize_epoch(5).unwrap();
+        // Re-signing the same epoch is allowed; going back is not.
+        gate.authorize_epoch(5).unwrap();
+        assert!(gate.authorize_epoch(4).is_err());
+        gate.authorize_epoch(6).unwrap();
+        assert_eq!(gate.status().last_signed_epoch, Some(6));
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_standby_signs_only_above_the_promotion_floor() {
+        let (store, path) = open_store("floor");
+        let gate = SigningGate::open(&standby(), store).unwrap();
+        assert!(!gate.can_sign());
+        assert!(gate.authorize_epoch(3).is_err());
+
+        {
+            let mut state = gate.write();
+            state.last_contact = Some(Instant::now());
+            state.primary_epoch = Some(9);
+            state.primary_last_signed_epoch = Some(8);
+        }
+        // The primary just answered: only a forced promotion goes through.
+        assert!(gate.promote(false, 7).is_err());
+        let response = gate.promote(true, 7).unwrap();
+        assert!(response.promoted);
+        assert_eq!(response.signing_floor_epoch, Some(9));
+
+        assert!(gate.can_sign());
+        assert!(gate.authorize_epoch(9).is_err());
+        gate.authorize_epoch(10).unwrap();
+        assert!(!gate.promote(false, 11).unwrap().promoted);
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_promotion_survives_reopen() {
+        let (store, path) = open_store("reopen");
+        let gate = SigningGate::open(&standby(), store.clone()).unwrap();
+        gate.write().primary_last_signed_epoch = Some(12);
+        let response = gate.promote(false, 4).unwrap();
+        assert_eq!(response.signing_floor_epoch, Some(12));
+        drop(gate);
+
+        // The config still says standby, but the persisted promotion wins.
+        let reopened = SigningGate::open(&standby(), store).unwrap();
+        assert!(reopened.can_sign());
+        assert_eq!(reopened.status().signing_floor_epoch, Some(12));
+        assert!(reopened.authorize_epoch(12).is_err());
+        reopened.authorize_epoch(13).unwrap();
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
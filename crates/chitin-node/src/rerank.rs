@@ -0,0 +1,192 @@
+// crates/chitin-node/src/rerank.rs
+//
+// Cross-encoder rerank stage for `query/search` and `query/context`.
+//
+// With a `[rerank.provider]` configured, the RPC server rescores the top
+// `top_n` search candidates with it before returning the top-k, giving up
+// and keeping the original order after `budget_ms`. Queries choose whether
+// to rerank (`rerank`), and may rescore fewer or more candidates or wait
+// less; `enabled` decides for queries that do not say.
+//
+// Providers are chitin-core `Reranker`s:
+// - `kind = "http"`: a Cohere/Jina-style rerank service
+//   (`POST {base_url}/rerank`)
+// - `kind = "onnx"`: a local ONNX cross-encoder (with the `onnx` feature),
+//   loaded only if the model file matches `weights_hash`
+//
+// Outcomes, passages rescored, and time spent are counted in `RerankStats`
+// and exported as `chitin_rerank_*` metrics.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::reranker::{HttpReranker, HttpRerankerConfig};
+use chitin_core::{ChitinError, Reranker};
+use chitin_rpc::handlers::query::{
+    RerankStats, Reranking, DEFAULT_RERANK_BUDGET_MS, DEFAULT_RERANK_TOP_N,
+};
+
+/// Settings for the search rerank stage (`[rerank]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankConfig {
+    /// Rerank queries that do not say whether to.
+    pub enabled: bool,
+    /// Candidates rescored unless a query asks for another number.
+    pub top_n: usize,
+    /// Longest wait for the reranker, in milliseconds; queries may ask for
+    /// less.
+    pub budget_ms: u64,
+    /// The cross-encoder (none disables reranking).
+    pub provider: Option<RerankProviderConfig>,
+}
+
+impl Default for RerankConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            top_n: DEFAULT_RERANK_TOP_N,
+            budget_ms: DEFAULT_RERANK_BUDGET_MS,
+            provider: None,
+        }
+    }
+}
+
+/// One rerank provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RerankProviderConfig {
+    /// A Cohere/Jina-style `/rerank` service.
+    Http {
+        /// Service base URL, without the `/rerank` suffix.
+        base_url: String,
+        /// Model name sent to the service, if it serves more than one.
+        #[serde(default)]
+        model: Option<String>,
+        /// Environment variable holding the API key, if one is needed.
+        #[serde(default)]
+        api_key_env: Option<String>,
+        /// Request timeout in seconds.
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+    /// A local ONNX cross-encoder (needs the `onnx` feature).
+    Onnx(OnnxRerankerProviderConfig),
+}
+
+/// A local ONNX cross-encoder (`kind = "onnx"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnnxRerankerProviderConfig {
+    /// The exported model (`model.onnx`).
+    pub model_path: PathBuf,
+    /// The model's `tokenizer.json` (default: next to the model).
+    #[serde(default)]
+    pub tokenizer_path: Option<PathBuf>,
+    /// Weights hash the model file must match, "sha256:<hex>".
+    pub weights_hash: String,
+    /// "cpu", "cuda", or "cuda:N".
+    #[serde(default = "default_device")]
+    pub device: String,
+    /// Longest (query, passage) pair, in tokens.
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+    /// CPU threads per inference (default: ONNX Runtime's choice).
+    #[serde(default)]
+    pub threads: Option<usize>,
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_device() -> String {
+    "cpu".to_string()
+}
+
+fn default_max_tokens() -> usize {
+    512
+}
+
+impl RerankConfig {
+    /// Build the rerank stage, validating its settings, or `None` if no
+    /// provider is configured.
+    pub fn reranking(&self) -> Result<Option<Reranking>, ChitinError> {
+        let Some(provider) = &self.provider else {
+            return Ok(None);
+        };
+        if self.top_n == 0 || self.budget_ms == 0 {
+            return Err(ChitinError::InvalidState(
+                "rerank top_n and budget_ms must be positive".to_string(),
+            ));
+        }
+        Ok(Some(Reranking {
+            reranker: provider.build()?,
+            default_enabled: self.enabled,
+            top_n: self.top_n,
+            budget: Duration::from_millis(self.budget_ms),
+            stats: Arc::new(RerankStats::default()),
+        }))
+    }
+}
+
+impl RerankProviderConfig {
+    fn build(&self) -> Result<Arc<dyn Reranker>, ChitinError> {
+        match self {
+            Self::Http {
+                base_url,
+                model,
+                api_key_env,
+                timeout_secs,
+            } => {
+                let api_key = match api_key_env {
+                    Some(var) => Some(std::env::var(var).map_err(|_| {
+                        ChitinError::InvalidState(format!("{} is not set", var))
+                    })?),
+                    None => None,
+                };
+                let reranker = HttpReranker::new(HttpRerankerConfig {
+                    base_url: base_url.clone(),
+                    api_key,
+                    model: model.clone(),
+                    timeout: Duration::from_secs(*timeout_secs),
+                })?;
+                tracing::info!("Reranking search results with {}", reranker.url());
+                Ok(Arc::new(reranker))
+            }
+            Self::Onnx(config) => config.build(),
+        }
+    }
+}
+
+impl OnnxRerankerProviderConfig {
+    #[cfg(feature = "onnx")]
+    fn build(&self) -> Result<Arc<dyn Reranker>, ChitinError> {
+        use chitin_core::reranker::{OnnxReranker, OnnxRerankerConfig};
+
+        let mut config = OnnxRerankerConfig::new(&self.model_path, &self.weights_hash);
+        if let Some(path) = &self.tokenizer_path {
+            config.tokenizer_path = path.clone();
+        }
+        config.device = self.device.parse()?;
+        config.max_tokens = self.max_tokens;
+        config.threads = self.threads;
+        let reranker = OnnxReranker::load(config)?;
+        tracing::info!(
+            "Loaded reranker {} ({})",
+            self.model_path.display(),
+            self.device
+        );
+        Ok(Arc::new(reranker))
+    }
+
+    #[cfg(not(feature = "onnx"))]
+    fn build(&self) -> Result<Arc<dyn Reranker>, ChitinError> {
+        Err(ChitinError::InvalidState(format!(
+            "Reranker {} needs ONNX support; rebuild with `--features onnx`",
+            self.model_path.display()
+        )))
+    }
+}
@@ -0,0 +1,224 @@
+// crates/chitin-node/src/runtime_state.rs
+//
+// Persistence of epoch and consensus runtime state for the Chitin daemon.
+//
+// Most of `DaemonSharedState` lives only in memory. So that a restarted node
+// resumes the epoch it was in, the runtime components are snapshotted to
+// RocksDB under `daemon_runtime_state` periodically and at shutdown, and
+// restored at startup before the epoch scheduler starts:
+//
+// - block height (from which the epoch and phase are re-derived)
+// - weight submissions and bonds
+// - the last consensus result and metagraph snapshot
+// - flagged Sybil clusters
+// - peer liveness, DIDs, shards, and scores
+//
+// Trust, model versions, drift statistics, and the ledger and stakes are
+// persisted separately.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::bonds::BondMatrix;
+use chitin_consensus::weights::WeightMatrix;
+use chitin_consensus::yuma::ConsensusResult;
+use chitin_core::{ChitinError, ReefMetagraph};
+use chitin_reputation::sybil::SybilCluster;
+use chitin_store::RocksStore;
+
+use crate::peers::{PeerRegistry, PeerState};
+use crate::shared::DaemonSharedState;
+
+/// Key of the persisted runtime snapshot.
+const KEY: &str = "daemon_runtime_state";
+
+/// Runtime state captured from `DaemonSharedState` and the peer registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    /// When the snapshot was taken.
+    pub saved_at: DateTime<Utc>,
+    /// Last block height the scheduler advanced to.
+    pub block: u64,
+    /// Epoch at that block (informational; re-derived on restore).
+    pub epoch: u64,
+    /// Weight submissions for the current epoch.
+    pub weight_matrix: WeightMatrix,
+    /// EMA-smoothed bonds.
+    pub bond_matrix: BondMatrix,
+    /// Last completed consensus result.
+    pub last_consensus_result: Option<ConsensusResult>,
+    /// Latest metagraph snapshot.
+    pub metagraph: Option<ReefMetagraph>,
+    /// Sybil clusters flagged at the last epoch boundary.
+    #[serde(default)]
+    pub sybil_clusters: Vec<SybilCluster>,
+    /// Known peers (empty without peer networking).
+    #[serde(default)]
+    pub peers: Vec<PeerState>,
+}
+
+impl RuntimeSnapshot {
+    /// Load the persisted snapshot, if any.
+    pub fn load(store: &RocksStore) -> Result<Option<Self>, ChitinError> {
+        match store.get_bytes(KEY.as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist this snapshot, replacing the previous one.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(KEY.as_bytes(), &serde_json::to_vec(self)?)
+    }
+}
+
+/// Saves and restores runtime state for one store.
+#[derive(Clone)]
+pub struct StatePersister {
+    store: Arc<RocksStore>,
+    shared: DaemonSharedState,
+    /// Registry whose peer states are included, if networking is on.
+    peers: Option<Arc<PeerRegistry>>,
+}
+
+impl StatePersister {
+    /// Create a persister for `shared`, backed by `store`.
+    pub fn new(store: Arc<RocksStore>, shared: DaemonSharedState) -> Self {
+        Self {
+            store,
+            shared,
+            peers: None,
+        }
+    }
+
+    /// Include `registry`'s peer states.
+    pub fn with_peer_registry(mut self, registry: Arc<PeerRegistry>) -> Self {
+        self.peers = Some(registry);
+        self
+    }
+
+    /// Snapshot the current runtime state.
+    pub async fn capture(&self) -> RuntimeSnapshot {
+        let (block, epoch) = {
+            let em = self.shared.epoch_manager.read().await;
+            (em.current_block(), em.current_epoch())
+        };
+        let peers = match &self.peers {
+            Some(registry) => registry.all_peer_states().await,
+            None => Vec::new(),
+        };
+        RuntimeSnapshot {
+            saved_at: Utc::now(),
+            block,
+            epoch,
+            weight_matrix: self.shared.weight_matrix.read().await.clone(),
+            bond_matrix: self.shared.bond_matrix.read().await.clone(),
+            last_consensus_result: self.shared.last_consensus_result.read().await.clone(),
+            metagraph: self.shared.metagraph_manager.read().await.current().cloned(),
+            sybil_clusters: self.shared.sybil_clusters.read().await.clone(),
+            peers,
+        }
+    }
+
+    /// Snapshot the runtime state and persist it.
+    pub async fn save(&self) -> Result<RuntimeSnapshot, ChitinError> {
+        let snapshot = self.capture().await;
+        snapshot.save(&self.store)?;
+        Ok(snapshot)
+    }
+
+    /// Restore the persisted snapshot into shared state and the peer
+    /// registry. Returns the snapshot restored, if there was one.
+    ///
+    /// The epoch and phase are re-derived from the saved block height, so
+    /// a changed `blocks_per_epoch` takes effect on restore.
+    pub async fn restore(&self) -> Result<Option<RuntimeSnapshot>, ChitinError> {
+        let snapshot = match RuntimeSnapshot::load(&self.store)? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        self.shared.epoch_manager.write().await.advance_block(snapshot.block);
+        self.apply_state(&snapshot).await;
+        if let Some(metagraph) = &snapshot.metagraph {
+            self.shared
+                .update_metagraph(metagraph.clone(), false)
+                .await?;
+        }
+        if let Some(registry) = &self.peers {
+            registry.restore_peer_states(snapshot.peers.clone()).await;
+        }
+        Ok(Some(snapshot))
+    }
+
+    /// Apply a snapshot taken on another node (a standby following its
+    /// primary) and persist the result. The block height only moves forward,
+    /// the metagraph is replaced only by a later epoch's, and peers are left
+    /// alone.
+    pub async fn apply(&self, snapshot: &RuntimeSnapshot) -> Result<(), ChitinError> {
+        {
+            let mut em = self.shared.epoch_manager.write().await;
+            if snapshot.block > em.current_block() {
+                em.advance_block(snapshot.block);
+            }
+        }
+        self.apply_state(snapshot).await;
+        if let Some(metagraph) = &snapshot.metagraph {
+            self.shared
+                .update_metagraph(metagraph.clone(), true)
+                .await?;
+        }
+        self.save().await.map(|_| ())
+    }
+
+    /// Copy a snapshot's matrices and consensus results into shared state.
+    async fn apply_state(&self, snapshot: &RuntimeSnapshot) {
+        *self.shared.weight_matrix.write().await = snapshot.weight_matrix.clone();
+        *self.shared.bond_matrix.write().await = snapshot.bond_matrix.clone();
+        *self.shared.last_consensus_result.write().await = snapshot.last_consensus_result.clone();
+        *self.shared.sybil_clusters.write().await = snapshot.sybil_clusters.clone();
+    }
+
+    /// Restore the persisted snapshot, logging the outcome.
+    pub async fn restore_logged(&self) {
+        match self.restore().await {
+            Ok(Some(snapshot)) => {
+                let epoch = self.shared.epoch_manager.read().await.current_epoch();
+                tracing::info!(
+                    "Resuming at block {} (epoch {}) from state saved at {}",
+                    snapshot.block,
+                    epoch,
+                    snapshot.saved_at
+                );
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to restore runtime state: {}", e),
+        }
+    }
+
+    /// Persist the runtime state, logging failures.
+    pub async fn save_logged(&self) {
+        match self.save().await {
+            Ok(snapshot) => tracing::debug!("Runtime state saved at block {}", snapshot.block),
+            Err(e) => tracing::warn!("Failed to save runtime state: {}", e),
+        }
+    }
+
+    /// Persist the runtime state every `interval_secs` until the process
+    /// exits. Returns immediately if `interval_secs` is zero.
+    pub async fn run(self, interval_secs: u64) {
+        if interval_secs == 0 {
+            return;
+        }
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        // The first tick completes immediately; state was just restored.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            self.save_logged().await;
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// crates/chitin-node/src/scheduler.rs
+//
+// Epoch scheduler for the Chitin Protocol daemon.
+//
+// Follows block progression from a `BlockSource` (synthetic timer, external
+// chain, or fixed sequence), updates the shared EpochManager, detects phase
+// transitions, and broadcasts EpochEvents to subscribed tasks (TideNode,
+// consensus runner). Under systemd, the loop also pings the watchdog.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{broadcast, RwLock};
+
+use chitin_consensus::epoch::EpochManager;
+
+use crate::block_source::{BlockSource, TimerBlockSource};
+use crate::epoch_events::EpochEvent;
+use crate::systemd;
+
+/// Seconds to wait before retrying after the block source fails.
+const RETRY_SECS: u64 = 5;
+
+/// Scheduler that follows block progression and triggers epoch transitions.
+pub struct EpochScheduler {
+    /// Number of blocks in each epoch.
+    blocks_per_epoch: u64,
+    /// The current block number (0-indexed).
+    current_block: u64,
+    /// Shared epoch manager for updating epoch state.
+    epoch_manager: Arc<RwLock<EpochManager>>,
+    /// Broadcast sender for epoch events.
+    event_tx: broadcast::Sender<EpochEvent>,
+    /// Where block heights come from.
+    source: Box<dyn BlockSource>,
+    /// How often to ping the systemd watchdog, if it is enabled.
+    watchdog: Option<Duration>,
+}
+
+impl EpochScheduler {
+    /// Create a new EpochScheduler with the given blocks-per-epoch count,
+    /// driven by synthetic 12-second blocks.
+    pub fn new(
+        blocks_per_epoch: u64,
+        epoch_manager: Arc<RwLock<EpochManager>>,
+        event_tx: broadcast::Sender<EpochEvent>,
+    ) -> Self {
+        Self {
+            blocks_per_epoch,
+            current_block: 0,
+            epoch_manager,
+            event_tx,
+            source: Box::new(TimerBlockSource::new(Duration::from_secs(12))),
+            watchdog: None,
+        }
+    }
+
+    /// Take block heights from `source` instead of the synthetic timer.
+    pub fn with_block_source(mut self, source: Box<dyn BlockSource>) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Ping the systemd watchdog every `interval` while the loop runs.
+    pub fn with_watchdog(mut self, interval: Option<Duration>) -> Self {
+        self.watchdog = interval;
+        self
+    }
+
+    /// Run the scheduler loop, advancing to each block the source produces.
+    ///
+    /// Updates the EpochManager on each block, detects phase transitions,
+    /// and broadcasts events, starting from the epoch manager's current
+    /// block. Heights at or below the current block are
+    /// ignored; source errors are logged and retried. Returns when the
+    /// source runs out of blocks; on shutdown the supervisor cancels it.
+    pub async fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Resume from the block restored into the epoch manager, if any.
+        self.current_block = self.epoch_manager.read().await.current_block();
+        self.source.resume_from(self.current_block);
+        tracing::info!(
+            "Epoch scheduler started (blocks_per_epoch={}, source={}, block={})",
+            self.blocks_per_epoch,
+            self.source.describe(),
+            self.current_block
+        );
+
+        let mut watchdog = self.watchdog.map(tokio::time::interval);
+        loop {
+            // Block sources are not cancel-safe, so the watchdog is pinged
+            // while the same `next_block` future stays pending.
+            let next = {
+                let block = self.source.next_block();
+                tokio::pin!(block);
+                loop {
+                    tokio::select! {
+                        _ = tick(&mut watchdog) => systemd::notify_watchdog(),
+                        next = &mut block => break next,
+                    }
+                }
+            };
+            match next {
+                Ok(Some(block)) if block > self.current_block => self.advance_to(block).await,
+                Ok(Some(block)) => {
+                    tracing::trace!("Ignoring block {} (at {})", block, self.current_block);
+                }
+                Ok(None) => {
+                    tracing::info!("Block source exhausted at block {}", self.current_block);
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!("Block source error: {}; retrying in {}s", e, RETRY_SECS);
+                    tokio::time::sleep(Duration::from_secs(RETRY_SECS)).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move to `block`, update EpochManager, and emit events.
+    ///
+    /// Skipped blocks are not replayed: a jump across several epochs emits a
+    /// single boundary for the epoch reached.
+    pub async fn advance_to(&mut self, block: u64) {
+        let prev_phase;
+        let prev_epoch;
+
+        // Read previous state
+        {
+            let em = self.epoch_manager.read().await;
+            prev_phase = em.phase().clone();
+            prev_epoch = em.current_epoch();
+        }
+
+        self.current_block = block;
+
+        // Update epoch manager with new block
+        {
+            let mut em = self.epoch_manager.write().await;
+            em.advance_block(self.current_block);
+        }
+
+        let new_phase;
+        let new_epoch;
+
+        // Read new state
+        {
+            let em = self.epoch_manager.read().await;
+            new_phase = em.phase().clone();
+            new_epoch = em.current_epoch();
+        }
+
+        // Detect epoch boundary
+        if new_epoch > prev_epoch {
+            tracing::info!(
+                "=== EPOCH {} BOUNDARY === (block {})",
+                new_epoch,
+                self.current_block
+            );
+            let _ = self.event_tx.send(EpochEvent::EpochBoundary {
+                epoch: new_epoch,
+                block: self.current_block,
+            });
+        }
+
+        // Detect phase transition
+        if new_phase != prev_phase {
+            tracing::info!(
+                "Phase transition: {:?} -> {:?} (epoch {}, block {})",
+                prev_phase,
+                new_phase,
+                new_epoch,
+                self.current_block
+            );
+            let _ = self.event_tx.send(EpochEvent::PhaseChanged {
+                epoch: new_epoch,
+                phase: new_phase,
+                block: self.current_block,
+            });
+        } else {
+            let block_in_epoch = self.current_block % self.blocks_per_epoch;
+            tracing::trace!(
+                "Block {} (epoch {}, block {}/{})",
+                self.current_block,
+                new_epoch,
+                block_in_epoch,
+                self.blocks_per_epoch
+            );
+        }
+    }
+}
+
+/// Wait for the next `watchdog` tick, or forever without a watchdog.
+async fn tick(watchdog: &mut Option<tokio::time::Interval>) {
+    match watchdog {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
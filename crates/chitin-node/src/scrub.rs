@@ -0,0 +1,419 @@
+// crates/chitin-node/src/scrub.rs
+//
+// Background storage integrity scrubber.
+//
+// Every `interval_secs`, walks the polyp store `batch_size` entries at a
+// time, pausing `pause_ms` between batches so serving is not starved, and
+// checks each entry:
+//
+// - it decodes, under its own ID;
+// - its content and vector match the text and vector hashes its proof
+//   commits (placeholder proofs commit none);
+// - its signature, if signed, verifies against the creator's hotkey;
+// - if hardened, its content and vector match the copy in IPFS, and the
+//   hardened store's cached copy matches IPFS (a differing cache entry is
+//   replaced from IPFS).
+//
+// With `repair`, a corrupt polyp is replaced by a copy that passes the same
+// checks: the IPFS copy of a hardened polyp, else the first one a live peer
+// serves (hardened copies must also match the local hardening checkpoint).
+// Repaired polyps are re-indexed. Corruption found and repairs are exported
+// as `chitin_scrub_*` metrics, and `node/health` reports the node degraded
+// while polyps found corrupt remain unrepaired.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_consensus::hardening::{verify_lineage, HardeningCheckpoint};
+use chitin_core::light::{text_hash, vector_hash};
+use chitin_core::traits::VectorIndex;
+use chitin_core::{ChitinError, Polyp, PolypState};
+use chitin_rpc::handlers::node::ScrubReport;
+use chitin_store::{HardenedStore, InMemoryVectorIndex, RocksStore};
+
+use crate::metrics::DaemonMetrics;
+use crate::peers::PeerRegistry;
+use crate::sync_loop::fetch_remote_polyps;
+
+/// Prefix of the polyp store's primary keys.
+const POLYP_PREFIX: &[u8] = b"polyp:";
+
+/// Most unrepaired polyp IDs listed by `node/health`.
+const MAX_REPORTED: usize = 100;
+
+/// Settings for the storage integrity scrubber (`[scrub]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrubConfig {
+    /// Seconds between the starts of scrub passes (0 disables scrubbing).
+    pub interval_secs: u64,
+    /// Polyps checked per batch.
+    pub batch_size: usize,
+    /// Pause between batches, in milliseconds.
+    pub pause_ms: u64,
+    /// Replace corrupt polyps with verified copies from IPFS or peers.
+    pub repair: bool,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 86_400,
+            batch_size: 256,
+            pause_ms: 100,
+            repair: true,
+        }
+    }
+}
+
+/// The scrubber's findings, shared with `node/health`.
+#[derive(Debug, Default)]
+pub struct ScrubStatus {
+    report: Mutex<ScrubReport>,
+}
+
+impl ScrubStatus {
+    /// A snapshot of the findings so far.
+    pub fn report(&self) -> ScrubReport {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScrubReport> {
+        self.report.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// What is wrong with a stored polyp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Corruption {
+    /// The entry does not decode.
+    Unreadable,
+    /// The entry decodes to a polyp with another ID.
+    WrongId,
+    /// The content does not match the proof's text hash.
+    ContentHash,
+    /// The vector does not match the proof's vector hash.
+    VectorHash,
+    /// The signature does not verify against the creator's hotkey.
+    Signature,
+    /// A hardened polyp's content or vector differs from its IPFS copy.
+    HardenedCopy,
+}
+
+impl Corruption {
+    /// Label of the `chitin_scrub_corrupt_total` metric.
+    fn kind(self) -> &'static str {
+        match self {
+            Self::Unreadable => "unreadable",
+            Self::WrongId => "wrong_id",
+            Self::ContentHash => "content_hash",
+            Self::VectorHash => "vector_hash",
+            Self::Signature => "signature",
+            Self::HardenedCopy => "hardened_copy",
+        }
+    }
+}
+
+/// Check a polyp stored under `id` on its own: ID, proof hashes, and
+/// signature.
+fn check_polyp(id: &str, polyp: &Polyp) -> Option<Corruption> {
+    if polyp.id.to_string() != id {
+        return Some(Corruption::WrongId);
+    }
+    // Placeholder proofs commit all-zero hashes rather than real ones.
+    let inputs = &polyp.proof.public_inputs;
+    if inputs.text_hash != [0; 32] && inputs.text_hash != text_hash(&polyp.subject.payload.content)
+    {
+        return Some(Corruption::ContentHash);
+    }
+    if inputs.vector_hash != [0; 32]
+        && inputs.vector_hash != vector_hash(&polyp.subject.vector.values)
+    {
+        return Some(Corruption::VectorHash);
+    }
+    if polyp.signature.is_some() {
+        let hotkey = &polyp.subject.provenance.creator.hotkey;
+        if !polyp.verify_signature(hotkey).unwrap_or(false) {
+            return Some(Corruption::Signature);
+        }
+    }
+    None
+}
+
+/// Whether `polyp` and `copy` hold the same content and vector.
+fn same_subject(polyp: &Polyp, copy: &Polyp) -> bool {
+    polyp.subject.payload.content == copy.subject.payload.content
+        && polyp.subject.vector.values == copy.subject.vector.values
+}
+
+/// Run scrub passes until the process exits.
+///
+/// Returns immediately if `config.interval_secs` is zero. Without a
+/// hardened store, hardened polyps are not compared with IPFS; without a
+/// peer registry, corrupt polyps are repaired only from IPFS.
+pub async fn run_scrubber(
+    store: Arc<RocksStore>,
+    index: Option<Arc<InMemoryVectorIndex>>,
+    hardened: Option<Arc<HardenedStore>>,
+    registry: Option<Arc<PeerRegistry>>,
+    metrics: DaemonMetrics,
+    status: Arc<ScrubStatus>,
+    config: ScrubConfig,
+) {
+    if config.interval_secs == 0 || config.batch_size == 0 {
+        tracing::info!("Storage scrubbing disabled");
+        return;
+    }
+    tracing::info!(
+        "Storage scrubbing started (every {}s, {} polyps per batch, repair {})",
+        config.interval_secs,
+        config.batch_size,
+        if config.repair { "on" } else { "off" }
+    );
+
+    let scrubber = Scrubber {
+        store: &store,
+        index: index.as_deref(),
+        hardened: hardened.as_deref(),
+        registry: registry.as_deref(),
+        metrics: &metrics,
+        status: &status,
+        config: &config,
+    };
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    loop {
+        interval.tick().await;
+        if let Err(e) = scrubber.scrub_pass().await {
+            tracing::warn!("Storage scrub failed: {}", e);
+        }
+    }
+}
+
+struct Scrubber<'a> {
+    store: &'a RocksStore,
+    index: Option<&'a InMemoryVectorIndex>,
+    hardened: Option<&'a HardenedStore>,
+    registry: Option<&'a PeerRegistry>,
+    metrics: &'a DaemonMetrics,
+    status: &'a ScrubStatus,
+    config: &'a ScrubConfig,
+}
+
+impl Scrubber<'_> {
+    /// Check every stored polyp once, repairing what can be repaired.
+    async fn scrub_pass(&self) -> Result<(), ChitinError> {
+        let started = std::time::Instant::now();
+        let mut cursor: Option<Vec<u8>> = None;
+        let mut checked = 0u64;
+        let mut unrepaired = Vec::new();
+        loop {
+            let entries = self.store.scan_prefix_from(
+                POLYP_PREFIX,
+                cursor.as_deref(),
+                self.config.batch_size,
+            )?;
+            for (key, value) in &entries {
+                let id = String::from_utf8_lossy(&key[POLYP_PREFIX.len()..]).to_string();
+                if !self.scrub_entry(&id, value).await {
+                    unrepaired.push(id);
+                }
+            }
+            checked += entries.len() as u64;
+            self.metrics.observe_scrubbed(entries.len() as u64);
+
+            match entries.last() {
+                Some((key, _)) if entries.len() == self.config.batch_size => {
+                    let mut next = key.clone();
+                    next.push(0);
+                    cursor = Some(next);
+                }
+                _ => break,
+            }
+            tokio::time::sleep(Duration::from_millis(self.config.pause_ms)).await;
+        }
+
+        self.metrics.observe_scrub_pass(unrepaired.len());
+        if unrepaired.is_empty() {
+            tracing::info!(
+                "Storage scrub: {} polyps intact ({:.1}s)",
+                checked,
+                started.elapsed().as_secs_f64()
+            );
+        } else {
+            tracing::warn!(
+                "Storage scrub: {} of {} polyps corrupt and not repaired",
+                unrepaired.len(),
+                checked
+            );
+        }
+        let mut report = self.status.lock();
+        report.last_pass = Some(chrono::Utc::now());
+        report.checked = checked;
+        unrepaired.truncate(MAX_REPORTED);
+        report.unrepaired = unrepaired;
+        Ok(())
+    }
+
+    /// Check one entry, repairing it if corrupt. Returns `false` if it is
+    /// corrupt and was not repaired.
+    async fn scrub_entry(&self, id: &str, value: &[u8]) -> bool {
+        let polyp = match serde_json::from_slice::<Polyp>(value) {
+            Ok(polyp) => polyp,
+            Err(_) => return self.repair(id, Corruption::Unreadable, None).await,
+        };
+        if let Some(corruption) = check_polyp(id, &polyp) {
+            return self.repair(id, corruption, None).await;
+        }
+        match self.ipfs_copy(&polyp).await {
+            Some(copy) if !same_subject(&polyp, &copy) => {
+                self.repair(id, Corruption::HardenedCopy, Some((polyp, copy)))
+                    .await
+            }
+            _ => true,
+        }
+    }
+
+    /// The IPFS copy of a hardened polyp, checking the hardened store's
+    /// cached copy against it on the way. `None` if the polyp is not
+    /// hardened or IPFS cannot be reached.
+    async fn ipfs_copy(&self, polyp: &Polyp) -> Option<Polyp> {
+        let hardened = self.hardened?;
+        if polyp.state != PolypState::Hardened {
+            return None;
+        }
+        let cid = &polyp.hardening.as_ref()?.cid;
+        match hardened.verify_cached(cid).await {
+            Ok((copy, cache_corrupt)) => {
+                if cache_corrupt {
+                    tracing::warn!("Storage scrub: replaced corrupt cached copy of {}", cid);
+                    self.metrics
+                        .observe_scrub_corruption("hardened_cache", Some("ipfs"));
+                    let mut report = self.status.lock();
+                    report.corrupt += 1;
+                    report.repaired += 1;
+                }
+                Some(copy)
+            }
+            Err(e) => {
+                tracing::debug!("Storage scrub: could not fetch {} from IPFS: {}", cid, e);
+                None
+            }
+        }
+    }
+
+    /// Record `corruption` of the polyp stored under `id` and try to repair
+    /// it. `hardened` holds the stored polyp and its IPFS copy, if it is a
+    /// hardened polyp whose subject differs from that copy. Returns whether
+    /// it was repaired.
+    async fn repair(
+        &self,
+        id: &str,
+        corruption: Corruption,
+        hardened: Option<(Polyp, Polyp)>,
+    ) -> bool {
+        tracing::warn!(
+            "Storage scrub: polyp {} is corrupt ({})",
+            id,
+            corruption.kind()
+        );
+        let source = if self.config.repair {
+            self.find_repair(id, hardened).await
+        } else {
+            None
+        };
+        self.metrics
+            .observe_scrub_corruption(corruption.kind(), source);
+        let mut report = self.status.lock();
+        report.corrupt += 1;
+        match source {
+            Some(source) => {
+                report.repaired += 1;
+                tracing::info!("Storage scrub: repaired polyp {} from {}", id, source);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace the polyp stored under `id` with a verified copy, returning
+    /// where it came from.
+    async fn find_repair(
+        &self,
+        id: &str,
+        hardened: Option<(Polyp, Polyp)>,
+    ) -> Option<&'static str> {
+        // A hardened polyp's subject is fixed by its IPFS copy; the rest
+        // (state, consensus, lineage) is the local record's.
+        if let Some((polyp, copy)) = hardened {
+            let repaired = Polyp {
+                subject: copy.subject,
+                proof: copy.proof,
+                signature: copy.signature,
+                ..polyp
+            };
+            if self.verified(id, &repaired) && self.replace(&repaired).await {
+                return Some("ipfs");
+            }
+        }
+
+        let polyp_id = Uuid::parse_str(id).ok()?;
+        let registry = self.registry?;
+        for peer_url in registry.live_peer_urls().await {
+            if registry.peer_score(&peer_url).await <= 0.0 {
+                continue;
+            }
+            let copies = fetch_remote_polyps(registry.http_client(), &peer_url, &[polyp_id]).await;
+            for copy in copies {
+                if self.verified(id, &copy) && self.replace(&copy).await {
+                    return Some("peer");
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether `copy` may replace the polyp stored under `id`: it passes
+    /// the scrub checks, and if hardened, its lineage matches the local
+    /// checkpoint of its epoch.
+    fn verified(&self, id: &str, copy: &Polyp) -> bool {
+        if check_polyp(id, copy).is_some() {
+            return false;
+        }
+        if copy.state != PolypState::Hardened {
+            return true;
+        }
+        let (Some(lineage), Some(consensus)) = (&copy.hardening, &copy.consensus) else {
+            return false;
+        };
+        match HardeningCheckpoint::load(self.store, consensus.epoch) {
+            Ok(Some(checkpoint)) => verify_lineage(&copy.id, lineage, &checkpoint).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Overwrite the stored polyp with `polyp` and re-index it.
+    async fn replace(&self, polyp: &Polyp) -> bool {
+        if let Err(e) = self.store.repair_polyp(polyp) {
+            tracing::warn!("Storage scrub: failed to rewrite polyp {}: {}", polyp.id, e);
+            return false;
+        }
+        if let Some(index) = self.index {
+            let model = polyp.subject.vector.model_id.key();
+            let reindexed = match index.delete(&polyp.id).await {
+                Ok(()) => index.upsert_in(&model, polyp.id, &polyp.subject.vector.values),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = reindexed {
+                tracing::warn!(
+                    "Storage scrub: failed to re-index polyp {}: {}",
+                    polyp.id,
+                    e
+                );
+            }
+        }
+        true
+    }
+}
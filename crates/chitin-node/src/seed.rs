@@ -0,0 +1,248 @@
+// crates/chitin-node/src/seed.rs
+//
+// Seed (bootstrap) node mode: `--node-type seed`.
+//
+// A seed node runs no scoring or consensus and stores no Polyps. It keeps a
+// directory of peers — its configured bootstrap peers, peers that announce
+// to it, and peers learned from their `peer/discover` lists — and mirrors
+// the state new nodes need to join: the metagraph reported by a majority of
+// live peers, and the hardening checkpoints a peer quorum agrees on. Only
+// the RPC methods in `SEED_METHODS` are served, so a seed is cheap to run.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_consensus::hardening::HardeningCheckpoint;
+use chitin_core::ReefMetagraph;
+use chitin_rpc::handlers::metagraph::GetMetagraphSnapshotResponse;
+use chitin_rpc::{AnnounceCallback, PeerDirectoryCallback};
+use chitin_store::RocksStore;
+
+use crate::peers::PeerRegistry;
+use crate::shared::DaemonSharedState;
+use crate::supervisor::ShutdownSignal;
+use crate::sync_loop::{call_peer, fetch_checkpoint_quorum};
+
+/// RPC methods served by a seed node.
+pub const SEED_METHODS: &[&str] = &[
+    "node/info",
+    "node/health",
+    "node/peers",
+    "metagraph/get",
+    "metagraph/node",
+    "metagraph/snapshot",
+    "sync/hardening_checkpoint",
+    "peer/announce",
+    "peer/discover",
+];
+
+/// Most peers listed in a seed's `peer/discover` response.
+pub const DIRECTORY_LIMIT: usize = 256;
+
+/// Settings for seed nodes (`[seed]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeedConfig {
+    /// Seconds between refresh rounds (peer exchange, metagraph, and
+    /// checkpoints).
+    pub refresh_interval_secs: u64,
+    /// Hardening checkpoints kept for this many epochs up to the metagraph
+    /// epoch; missing ones are fetched from peers.
+    pub checkpoint_epochs: u64,
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: 60,
+            checkpoint_epochs: 16,
+        }
+    }
+}
+
+/// A bootstrap node serving discovery, peer exchange, and metagraph and
+/// checkpoint state.
+pub struct SeedNode {
+    config: SeedConfig,
+    shared: DaemonSharedState,
+    store: Arc<RocksStore>,
+    registry: Arc<PeerRegistry>,
+}
+
+impl SeedNode {
+    /// Create a seed node saving checkpoints to `store`.
+    pub fn new(
+        config: SeedConfig,
+        shared: DaemonSharedState,
+        store: Arc<RocksStore>,
+        registry: Arc<PeerRegistry>,
+    ) -> Self {
+        Self {
+            config,
+            shared,
+            store,
+            registry,
+        }
+    }
+
+    /// Callback listing the peer directory for `peer/discover`.
+    pub fn peer_directory(&self) -> PeerDirectoryCallback {
+        let registry = self.registry.clone();
+        Arc::new(move || {
+            let registry = registry.clone();
+            Box::pin(async move { registry.directory(DIRECTORY_LIMIT).await })
+        })
+    }
+
+    /// Callback adding announcing peers to the directory and recording the
+    /// DIDs they claim.
+    pub fn announce_callback(&self) -> AnnounceCallback {
+        let registry = self.registry.clone();
+        Arc::new(move |announce| {
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                registry.record_announcement(&announce).await;
+                let url = match announce.url {
+                    Some(url) => url,
+                    None => return,
+                };
+                if registry.self_url.as_deref() != Some(url.as_str()) {
+                    registry.add_discovered_peer(url, announce.node_id).await;
+                }
+            });
+        })
+    }
+
+    /// Run refresh rounds until shutdown.
+    pub async fn start(
+        &self,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        tracing::info!(
+            "Seed node started: serving discovery, metagraph, and checkpoints ({} peers known)",
+            self.registry.all_peer_states().await.len()
+        );
+        let interval = std::time::Duration::from_secs(self.config.refresh_interval_secs.max(1));
+
+        self.registry.announce_to_all().await;
+        loop {
+            self.refresh().await;
+            tokio::select! {
+                _ = shutdown.wait() => {
+                    tracing::info!("Seed node shutting down");
+                    break;
+                }
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// One refresh round: exchange peers, then mirror the metagraph and
+    /// backfill checkpoints from live peers.
+    pub async fn refresh(&self) {
+        let discovered = self.registry.exchange_peers().await;
+        let live = self.registry.live_peer_urls().await;
+        tracing::debug!(
+            "Seed: {} live peers ({} newly discovered)",
+            live.len(),
+            discovered
+        );
+        if live.is_empty() {
+            return;
+        }
+        self.refresh_metagraph(&live).await;
+        self.backfill_checkpoints(&live).await;
+    }
+
+    /// Adopt the metagraph reported by a strict majority of `peers`, if it
+    /// is newer than the one held.
+    async fn refresh_metagraph(&self, peers: &[String]) {
+        let client = self.registry.http_client();
+        let mut reports: Vec<ReefMetagraph> = Vec::new();
+        for url in peers {
+            let params = serde_json::json!({});
+            match call_peer::<GetMetagraphSnapshotResponse>(
+                client,
+                url,
+                "metagraph/snapshot",
+                params,
+            )
+            .await
+            {
+                Ok(response) => reports.extend(response.metagraph),
+                Err(e) => tracing::debug!("Seed: no metagraph from {}: {}", url, e),
+            }
+        }
+
+        let metagraph = match metagraph_quorum(reports) {
+            Some(metagraph) => metagraph,
+            None => return,
+        };
+        let epoch = metagraph.epoch;
+        let nodes = metagraph.nodes.len();
+        match self.shared.update_metagraph(metagraph, true).await {
+            Ok(true) => {
+                tracing::info!("Seed: metagraph updated to epoch {} ({} nodes)", epoch, nodes)
+            }
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Seed: failed to update metagraph: {}", e),
+        }
+    }
+
+    /// Fetch the hardening checkpoints of the last `checkpoint_epochs`
+    /// epochs that are not stored yet.
+    async fn backfill_checkpoints(&self, peers: &[String]) {
+        let latest = match self.shared.metagraph_manager.read().await.current() {
+            Some(metagraph) => metagraph.epoch,
+            None => return,
+        };
+        let first = latest.saturating_sub(self.config.checkpoint_epochs.saturating_sub(1));
+        for epoch in first..=latest {
+            match HardeningCheckpoint::load(&self.store, epoch) {
+                Ok(Some(_)) => continue,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Seed: failed to load hardening checkpoint {}: {}", epoch, e);
+                    continue;
+                }
+            }
+            let client = self.registry.http_client();
+            let checkpoint =
+                match fetch_checkpoint_quorum(client, &self.registry, peers, epoch).await {
+                    Some(checkpoint) => checkpoint,
+                    None => continue,
+                };
+            match checkpoint.save(&self.store) {
+                Ok(()) => tracing::info!(
+                    "Seed: stored hardening checkpoint {} ({} polyps)",
+                    epoch,
+                    checkpoint.count
+                ),
+                Err(e) => {
+                    tracing::warn!("Seed: failed to save hardening checkpoint {}: {}", epoch, e)
+                }
+            }
+        }
+    }
+}
+
+/// The metagraph reported by a strict majority of `reports`, compared by
+/// canonical (key-sorted) JSON.
+fn metagraph_quorum(reports: Vec<ReefMetagraph>) -> Option<ReefMetagraph> {
+    let total = reports.len();
+    let mut votes: HashMap<String, (usize, ReefMetagraph)> = HashMap::new();
+    for report in reports {
+        let key = match serde_json::to_value(&report) {
+            Ok(value) => value.to_string(),
+            Err(_) => continue,
+        };
+        votes.entry(key).or_insert((0, report)).0 += 1;
+    }
+    votes
+        .into_values()
+        .find(|(n, _)| n * 2 > total)
+        .map(|(_, metagraph)| metagraph)
+}
@@ -0,0 +1,60 @@
+// crates/chitin-node/src/shard_proxy.rs
+//
+// Query proxying for nodes that hold only some shards: forwards a JSON-RPC
+// request to the live peers that together hold the requested shards (as
+// learned from `peer/shards` during sync) and collects their results.
+
+use std::sync::Arc;
+
+use chitin_rpc::ShardProxyCallback;
+
+use crate::peers::PeerRegistry;
+use crate::sync_loop::call_peer;
+
+/// Build the RPC server's shard proxy callback over `registry`.
+pub fn shard_proxy(registry: Arc<PeerRegistry>, num_shards: u16) -> ShardProxyCallback {
+    Arc::new(move |method, params, shards| {
+        let registry = registry.clone();
+        Box::pin(async move { forward(&registry, num_shards, method, params, &shards).await })
+    })
+}
+
+/// Call `method` on each peer chosen to cover `shards`, concurrently.
+///
+/// Failed calls are logged and left out of the results.
+async fn forward(
+    registry: &PeerRegistry,
+    num_shards: u16,
+    method: String,
+    params: serde_json::Value,
+    shards: &[u16],
+) -> Vec<serde_json::Value> {
+    let peers = registry.peers_for_shards(num_shards, shards).await;
+    if peers.is_empty() {
+        tracing::debug!("Proxy: no live peer holds shards {:?} for {}", shards, method);
+        return Vec::new();
+    }
+
+    let mut calls = tokio::task::JoinSet::new();
+    for peer_url in peers {
+        let client = registry.http_client().clone();
+        let method = method.clone();
+        let params = params.clone();
+        calls.spawn(async move {
+            let result = call_peer::<serde_json::Value>(&client, &peer_url, &method, params).await;
+            (peer_url, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = calls.join_next().await {
+        match joined {
+            Ok((_, Ok(value))) => results.push(value),
+            Ok((peer_url, Err(e))) => {
+                tracing::debug!("Proxy: {} to {} failed: {}", method, peer_url, e);
+            }
+            Err(e) => tracing::warn!("Proxy: call task failed: {}", e),
+        }
+    }
+    results
+}
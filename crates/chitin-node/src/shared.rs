@@ -0,0 +1,263 @@
+// crates/chitin-node/src/shared.rs
+//
+// DaemonSharedState: centralized shared mutable state for the Chitin daemon.
+//
+// Constructed once in main.rs, then injected into daemon tasks (TideNode,
+// EpochScheduler, consensus runner) and the RPC server via builder methods.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::sync::RwLock;
+
+use chitin_consensus::bonds::BondMatrix;
+use chitin_consensus::dedup::{DedupConfig, DuplicateDetector};
+use chitin_consensus::epoch::EpochManager;
+use chitin_consensus::genesis::Genesis;
+use chitin_consensus::metagraph::MetagraphManager;
+use chitin_consensus::moderation::PolicySet;
+use chitin_consensus::weights::WeightMatrix;
+use chitin_consensus::yuma::ConsensusResult;
+use chitin_core::{ChitinError, IdentityRegistry, ReefMetagraph};
+use chitin_drift::molting::SuccessorPolicy;
+use chitin_drift::versioning::VersionRegistry;
+use chitin_economics::{Ledger, StakeManager};
+use chitin_reputation::centroid::CentroidClassifier;
+use chitin_reputation::domain_store::DomainTrustStore;
+use chitin_reputation::sybil::SybilCluster;
+use chitin_reputation::taxonomy::DomainTaxonomy;
+use chitin_store::{EmbeddingCache, HardenedStore};
+use chitin_sync::state_update::PolypStateUpdate;
+
+use crate::metrics::DaemonMetrics;
+use crate::network::NetworkProfile;
+use crate::webhooks::{WebhookEvent, WebhookNotifier};
+
+/// Callback that gossips a local polyp state transition to peers.
+/// Installed by main.rs once peer networking is set up.
+pub type StateGossipCallback = Arc<dyn Fn(PolypStateUpdate) + Send + Sync>;
+
+/// Shared mutable state for the daemon, wrapped in Arc<RwLock<>> for
+/// safe concurrent access from multiple tokio tasks.
+#[derive(Clone)]
+pub struct DaemonSharedState {
+    /// Epoch lifecycle manager (tracks current epoch + phase).
+    pub epoch_manager: Arc<RwLock<EpochManager>>,
+    /// Last completed consensus result (None until first epoch completes).
+    pub last_consensus_result: Arc<RwLock<Option<ConsensusResult>>>,
+    /// Domain-scoped trust matrices: T(from, to) per Reef Zone plus "global".
+    pub trust_store: Arc<RwLock<DomainTrustStore>>,
+    /// Embedding-centroid domain classifier (Reef Zone assignment).
+    pub domain_classifier: Arc<RwLock<CentroidClassifier>>,
+    /// Reef Zone taxonomy (read-only after startup).
+    pub taxonomy: Arc<DomainTaxonomy>,
+    /// Sybil clusters flagged at the last epoch boundary.
+    pub sybil_clusters: Arc<RwLock<Vec<SybilCluster>>>,
+    /// Weight matrix: W[validator][coral] scores for the current epoch.
+    pub weight_matrix: Arc<RwLock<WeightMatrix>>,
+    /// Bond matrix: EMA-smoothed historical weights.
+    pub bond_matrix: Arc<RwLock<BondMatrix>>,
+    /// Stable UIDs of registered DIDs and hotkeys.
+    pub identities: Arc<RwLock<IdentityRegistry>>,
+    /// Local metagraph snapshot manager.
+    pub metagraph_manager: Arc<RwLock<MetagraphManager>>,
+    /// Stake entries, initialized from the genesis file.
+    pub stake_manager: Arc<RwLock<StakeManager>>,
+    /// Liquid balances and transfers, initialized from the genesis file.
+    pub ledger: Arc<RwLock<Ledger>>,
+    /// Optional hardened store (IPFS-backed immutable storage).
+    pub hardened_store: Option<Arc<HardenedStore>>,
+    /// Daemon start time for uptime calculation.
+    pub start_time: Instant,
+    /// Gossips local state transitions (None without peer networking).
+    pub state_gossip: Option<StateGossipCallback>,
+    /// Embedding model versions, published in the metagraph each epoch.
+    pub model_registry: Arc<RwLock<VersionRegistry>>,
+    /// Whether molted successors inherit consensus or are re-validated.
+    pub molt_policy: SuccessorPolicy,
+    /// Prometheus metrics recorded by daemon tasks.
+    pub metrics: DaemonMetrics,
+    /// The selected network profile, whose economics set the metagraph
+    /// emission rate.
+    pub network: Option<Arc<NetworkProfile>>,
+    /// Whether this node retains all history (nothing is pruned).
+    pub archival: bool,
+    /// Delivers events to configured webhooks (None without endpoints).
+    pub webhooks: Option<WebhookNotifier>,
+    /// Vectors already embedded, shared by the embedding pool and molting
+    /// (None if the cache is disabled).
+    pub embedding_cache: Option<Arc<EmbeddingCache>>,
+    /// Fingerprints of hardened polyps, for near-duplicate checks.
+    pub duplicates: Arc<RwLock<DuplicateDetector>>,
+    /// Content policies approved polyps must pass before hardening (None
+    /// if moderation is off).
+    pub moderation: Option<Arc<PolicySet>>,
+}
+
+impl DaemonSharedState {
+    /// Create a new DaemonSharedState with the given configuration.
+    ///
+    /// Initializes all matrices to a default network size of 0 validators
+    /// and 0 coral nodes. These will be resized as nodes register.
+    pub fn new(blocks_per_epoch: u64, hardened_store: Option<Arc<HardenedStore>>) -> Self {
+        Self {
+            epoch_manager: Arc::new(RwLock::new(EpochManager::new(blocks_per_epoch))),
+            last_consensus_result: Arc::new(RwLock::new(None)),
+            trust_store: Arc::new(RwLock::new(DomainTrustStore::default())),
+            domain_classifier: Arc::new(RwLock::new(CentroidClassifier::default())),
+            taxonomy: Arc::new(DomainTaxonomy::default()),
+            sybil_clusters: Arc::new(RwLock::new(Vec::new())),
+            weight_matrix: Arc::new(RwLock::new(WeightMatrix::new(0, 0))),
+            bond_matrix: Arc::new(RwLock::new(BondMatrix::new(0, 0))),
+            identities: Arc::new(RwLock::new(IdentityRegistry::new())),
+            metagraph_manager: Arc::new(RwLock::new(MetagraphManager::new())),
+            stake_manager: Arc::new(RwLock::new(StakeManager::new())),
+            ledger: Arc::new(RwLock::new(Ledger::new())),
+            hardened_store,
+            start_time: Instant::now(),
+            state_gossip: None,
+            model_registry: Arc::new(RwLock::new(VersionRegistry::default())),
+            molt_policy: SuccessorPolicy::default(),
+            metrics: DaemonMetrics::new(),
+            network: None,
+            archival: false,
+            webhooks: None,
+            embedding_cache: None,
+            duplicates: Arc::new(RwLock::new(DuplicateDetector::default())),
+            moderation: None,
+        }
+    }
+
+    /// Replace the in-memory trust store (e.g., with one backed by RocksDB).
+    pub fn with_trust_store(mut self, trust_store: DomainTrustStore) -> Self {
+        self.trust_store = Arc::new(RwLock::new(trust_store));
+        self
+    }
+
+    /// Replace the in-memory identity registry (e.g., with one backed by
+    /// RocksDB).
+    pub fn with_identity_registry(mut self, registry: IdentityRegistry) -> Self {
+        self.identities = Arc::new(RwLock::new(registry));
+        self
+    }
+
+    /// Replace the domain classifier (e.g., with a configured threshold).
+    pub fn with_domain_classifier(mut self, classifier: CentroidClassifier) -> Self {
+        self.domain_classifier = Arc::new(RwLock::new(classifier));
+        self
+    }
+
+    /// Set the callback that gossips local state transitions.
+    pub fn with_state_gossip(mut self, gossip: StateGossipCallback) -> Self {
+        self.state_gossip = Some(gossip);
+        self
+    }
+
+    /// Replace the model version registry.
+    pub fn with_model_registry(mut self, registry: VersionRegistry) -> Self {
+        self.model_registry = Arc::new(RwLock::new(registry));
+        self
+    }
+
+    /// Set how consensus treats molted successors.
+    pub fn with_molt_policy(mut self, policy: SuccessorPolicy) -> Self {
+        self.molt_policy = policy;
+        self
+    }
+
+    /// Set whether this node retains all history.
+    pub fn with_archival(mut self, archival: bool) -> Self {
+        self.archival = archival;
+        self
+    }
+
+    /// Set the network profile this node runs against.
+    pub fn with_network(mut self, network: Option<NetworkProfile>) -> Self {
+        self.network = network.map(Arc::new);
+        self
+    }
+
+    /// Set the webhook notifier.
+    pub fn with_webhooks(mut self, webhooks: Option<WebhookNotifier>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
+    /// Set the embedding cache (None to disable it).
+    pub fn with_embedding_cache(mut self, cache: Option<Arc<EmbeddingCache>>) -> Self {
+        self.embedding_cache = cache;
+        self
+    }
+
+    /// Set the near-duplicate thresholds.
+    pub fn with_dedup(mut self, config: DedupConfig) -> Self {
+        self.duplicates = Arc::new(RwLock::new(DuplicateDetector::new(config)));
+        self
+    }
+
+    /// Set the content policies applied before hardening.
+    pub fn with_moderation(mut self, policies: Option<Arc<PolicySet>>) -> Self {
+        self.moderation = policies;
+        self
+    }
+
+    /// Start from `genesis`: its nodes registered in the identity registry,
+    /// its metagraph (replaced by any restored or synced one), its stakes
+    /// and balances (replaced by any persisted ledger), and trust among its
+    /// seeded validators.
+    /// Returns the number of trust edges seeded.
+    pub async fn apply_genesis(&self, genesis: &Genesis) -> Result<usize, ChitinError> {
+        let blocks_per_epoch = self.epoch_manager.read().await.blocks_per_epoch();
+        let mut identities = self.identities.write().await;
+        genesis.register(&mut identities)?;
+        self.metagraph_manager
+            .write()
+            .await
+            .initialize(genesis.metagraph(blocks_per_epoch, &identities)?);
+        *self.stake_manager.write().await = genesis.stake_manager(&identities)?;
+        *self.ledger.write().await = genesis.ledger()?;
+
+        let mut ts = self.trust_store.write().await;
+        let seeded = ts.seed_genesis(&identities, 0);
+        if seeded > 0 {
+            ts.persist()?;
+        }
+        Ok(seeded)
+    }
+
+    /// Adopt `metagraph` as the current one after recording its nodes in the
+    /// identity registry under its UIDs. With `newer_only`, a metagraph from
+    /// an epoch no later than the current one is ignored. A metagraph whose
+    /// UIDs the registry disagrees with is refused and changes nothing.
+    /// Returns whether the metagraph was adopted.
+    pub async fn update_metagraph(
+        &self,
+        metagraph: ReefMetagraph,
+        newer_only: bool,
+    ) -> Result<bool, ChitinError> {
+        let mut identities = self.identities.write().await;
+        let mut manager = self.metagraph_manager.write().await;
+        let newer = manager
+            .current()
+            .is_none_or(|current| metagraph.epoch > current.epoch);
+        if newer_only && !newer {
+            return Ok(false);
+        }
+        identities.sync_metagraph(&metagraph, metagraph.epoch)?;
+        manager.update(metagraph)?;
+        Ok(true)
+    }
+
+    /// Send `event` to the configured webhooks, if any.
+    pub fn notify_webhooks(&self, event: WebhookEvent) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify(event);
+        }
+    }
+
+    /// Replace the Reef Zone taxonomy.
+    pub fn with_taxonomy(mut self, taxonomy: DomainTaxonomy) -> Self {
+        self.taxonomy = Arc::new(taxonomy);
+        self
+    }
+}
@@ -0,0 +1,577 @@
+// crates/chitin-node/src/snapshot.rs
+//
+// Signed snapshots for bootstrapping new nodes without replaying every polyp.
+//
+// `admin/snapshot` writes one file holding the node's polyps, vector index,
+// metagraph, and latest hardening checkpoint as of its current epoch. The
+// file is a JSON manifest line followed by the JSON body; the manifest
+// carries the SHA-256 of the body bytes and the node's hotkey signature over
+// its own fields, so snapshots can be handed out by any HTTP server or from
+// IPFS without trusting the transport. Snapshots are only written under
+// `<data_dir>/snapshots`.
+//
+// `--bootstrap-from-snapshot <path | http(s) URL | ipfs://CID>` (or
+// `[snapshot] bootstrap_from`) fetches a snapshot and checks it before
+// anything is loaded: the body hash, a signature from one of
+// `trusted_signers` and/or the body hash pinned in `expected_hash`, the
+// network, and every hardened polyp's lineage against the snapshot's
+// checkpoint, which configured peers must not outvote. It is then loaded
+// into an empty store ahead of the sync loop, which fetches only what is
+// newer. A store that already holds polyps is left alone, so the flag can
+// stay set across restarts.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_consensus::hardening::{verify_lineage, HardeningCheckpoint};
+use chitin_core::crypto::{hash_bytes, sign_message, verify_signature};
+use chitin_core::keystore::SecretKey;
+use chitin_core::{ChitinError, Polyp, ReefMetagraph};
+use chitin_rpc::handlers::admin::{SnapshotRequest, SnapshotResponse};
+use chitin_rpc::SnapshotCallback;
+use chitin_store::{InMemoryVectorIndex, IpfsClient, RocksStore};
+
+use crate::backup::data_path;
+use crate::peers::PeerRegistry;
+use crate::shared::DaemonSharedState;
+use crate::sync_loop::fetch_checkpoint_quorum;
+
+/// Version of the snapshot file layout.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// Snapshot bootstrap settings (`[snapshot]` table).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Snapshot to load into an empty store at startup: a file path, an
+    /// http(s) URL, or `ipfs://<cid>`. `--bootstrap-from-snapshot` sets it.
+    pub bootstrap_from: Option<String>,
+    /// Hotkeys (hex) whose snapshots are trusted.
+    pub trusted_signers: Vec<String>,
+    /// SHA-256 (hex) the snapshot body must have, as published out of band.
+    pub expected_hash: Option<String>,
+}
+
+impl SnapshotConfig {
+    /// Check that the settings are usable: a bootstrap source needs at
+    /// least one trust anchor.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        self.trusted_keys()?;
+        if let Some(hash) = &self.expected_hash {
+            decode_hex32(hash, "expected_hash")?;
+        }
+        if let Some(source) = &self.bootstrap_from {
+            SnapshotSource::parse(source)?;
+            if self.trusted_signers.is_empty() && self.expected_hash.is_none() {
+                return Err(ChitinError::InvalidState(
+                    "bootstrapping from a snapshot needs trusted_signers or expected_hash"
+                        .to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn trusted_keys(&self) -> Result<Vec<[u8; 32]>, ChitinError> {
+        self.trusted_signers
+            .iter()
+            .map(|signer| decode_hex32(signer, "trusted_signers"))
+            .collect()
+    }
+}
+
+/// Where a snapshot is fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// A file on this host.
+    File(String),
+    /// An http(s) URL, fetched with GET.
+    Http(String),
+    /// An IPFS CID, fetched through the node's IPFS API.
+    Ipfs(String),
+}
+
+impl SnapshotSource {
+    /// Parse `source`: `http://` and `https://` URLs, `ipfs://<cid>`, or a
+    /// file path.
+    pub fn parse(source: &str) -> Result<Self, ChitinError> {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            Ok(Self::Http(source.to_string()))
+        } else if let Some(cid) = source.strip_prefix("ipfs://") {
+            if cid.is_empty() {
+                return Err(ChitinError::InvalidState(
+                    "ipfs:// snapshot source needs a CID".to_string(),
+                ));
+            }
+            Ok(Self::Ipfs(cid.to_string()))
+        } else if source.is_empty() {
+            Err(ChitinError::InvalidState(
+                "Empty snapshot source".to_string(),
+            ))
+        } else {
+            Ok(Self::File(crate::node::expand_tilde(source)))
+        }
+    }
+
+    /// Fetch the snapshot file.
+    pub async fn fetch(&self, ipfs: &IpfsClient) -> Result<Vec<u8>, ChitinError> {
+        match self {
+            Self::File(path) => std::fs::read(path)
+                .map_err(|e| ChitinError::Storage(format!("Failed to read {}: {}", path, e))),
+            Self::Http(url) => {
+                let response = reqwest::get(url)
+                    .await
+                    .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?;
+                if !response.status().is_success() {
+                    return Err(ChitinError::Network(format!(
+                        "Snapshot fetch from {} failed: {}",
+                        url,
+                        response.status()
+                    )));
+                }
+                let bytes = response.bytes().await.map_err(|e| {
+                    ChitinError::Network(format!("Snapshot body read failed: {}", e))
+                })?;
+                Ok(bytes.to_vec())
+            }
+            Self::Ipfs(cid) => ipfs.get_by_cid(cid).await,
+        }
+    }
+}
+
+/// First line of a snapshot file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// `SNAPSHOT_FORMAT` of the writer.
+    pub format: u32,
+    /// Network the snapshot was taken on, if the node ran with a profile.
+    pub network_id: Option<String>,
+    /// Epoch the snapshot was taken at.
+    pub epoch: u64,
+    /// Block the snapshot was taken at.
+    pub block: u64,
+    /// When the snapshot was taken.
+    pub created_at: DateTime<Utc>,
+    /// Polyps in the body.
+    pub polyps: usize,
+    /// Vector index entries in the body.
+    pub vectors: usize,
+    /// Epoch of the body's hardening checkpoint, if it has one.
+    pub checkpoint_epoch: Option<u64>,
+    /// SHA-256 of the body bytes (hex).
+    pub content_hash: String,
+    /// Hotkey that signed the manifest (hex).
+    pub signer: String,
+    /// Ed25519 signature over `signable_bytes` (hex).
+    pub signature: String,
+}
+
+impl SnapshotManifest {
+    /// The bytes the signature covers: every field but the signature.
+    fn signable_bytes(&self) -> Vec<u8> {
+        format!(
+            "chitin-snapshot:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}",
+            self.format,
+            self.network_id.as_deref().unwrap_or_default(),
+            self.epoch,
+            self.block,
+            self.created_at.to_rfc3339(),
+            self.polyps,
+            self.vectors,
+            self.checkpoint_epoch
+                .map(|e| e.to_string())
+                .unwrap_or_default(),
+            self.content_hash,
+            self.signer,
+        )
+        .into_bytes()
+    }
+}
+
+/// One vector index entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotVector {
+    /// Polyp the vector belongs to.
+    pub id: Uuid,
+    /// Embedding model space.
+    pub model: String,
+    /// The vector.
+    pub values: Vec<f32>,
+}
+
+/// What a snapshot carries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotBody {
+    /// Every polyp, ordered by ID.
+    pub polyps: Vec<Polyp>,
+    /// The vector index, ordered by ID.
+    pub vectors: Vec<SnapshotVector>,
+    /// The latest metagraph.
+    pub metagraph: Option<ReefMetagraph>,
+    /// The latest hardening checkpoint at or before the snapshot epoch.
+    pub checkpoint: Option<HardeningCheckpoint>,
+}
+
+/// Serialize `body` taken at `epoch` and `block`, and sign the manifest with
+/// `signing_key`. Returns the manifest and the file contents.
+pub fn encode_snapshot(
+    body: &SnapshotBody,
+    network_id: Option<String>,
+    epoch: u64,
+    block: u64,
+    hotkey: &[u8; 32],
+    signing_key: &[u8; 32],
+) -> Result<(SnapshotManifest, Vec<u8>), ChitinError> {
+    let body_bytes = serde_json::to_vec(body)?;
+    let mut manifest = SnapshotManifest {
+        format: SNAPSHOT_FORMAT,
+        network_id,
+        epoch,
+        block,
+        created_at: Utc::now(),
+        polyps: body.polyps.len(),
+        vectors: body.vectors.len(),
+        checkpoint_epoch: body.checkpoint.as_ref().map(|c| c.epoch),
+        content_hash: hex::encode(hash_bytes(&body_bytes)),
+        signer: hex::encode(hotkey),
+        signature: String::new(),
+    };
+    manifest.signature = hex::encode(sign_message(signing_key, &manifest.signable_bytes())?);
+
+    let mut file = serde_json::to_vec(&manifest)?;
+    file.push(b'\n');
+    file.extend_from_slice(&body_bytes);
+    Ok((manifest, file))
+}
+
+/// Parse a snapshot file and check it against `config` and this node's
+/// `network_id`, before anything in it is trusted.
+pub fn decode_snapshot(
+    file: &[u8],
+    config: &SnapshotConfig,
+    network_id: Option<&str>,
+) -> Result<(SnapshotManifest, SnapshotBody), ChitinError> {
+    let invalid = |reason: String| ChitinError::Verification(format!("Snapshot {}", reason));
+
+    let split = file
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| invalid("has no manifest line".to_string()))?;
+    let (manifest_bytes, body_bytes) = (&file[..split], &file[split + 1..]);
+    let manifest: SnapshotManifest = serde_json::from_slice(manifest_bytes)?;
+    if manifest.format != SNAPSHOT_FORMAT {
+        return Err(invalid(format!(
+            "format {} is not supported",
+            manifest.format
+        )));
+    }
+
+    // The body is what the manifest describes, and the manifest is signed.
+    let content_hash = hex::encode(hash_bytes(body_bytes));
+    if content_hash != manifest.content_hash {
+        return Err(invalid("body does not match its content hash".to_string()));
+    }
+    let signer = decode_hex32(&manifest.signer, "signer")?;
+    let signature = hex::decode(&manifest.signature)
+        .map_err(|e| invalid(format!("signature is not hex: {}", e)))?;
+    if !verify_signature(&signer, &manifest.signable_bytes(), &signature)? {
+        return Err(invalid("signature is invalid".to_string()));
+    }
+
+    // Trust comes from the signer, the pinned hash, or both.
+    let trusted = config.trusted_keys()?;
+    if trusted.is_empty() && config.expected_hash.is_none() {
+        return Err(invalid(
+            "cannot be trusted: no trusted_signers or expected_hash".to_string(),
+        ));
+    }
+    if !trusted.is_empty() && !trusted.contains(&signer) {
+        return Err(invalid(format!(
+            "signer {} is not trusted",
+            manifest.signer
+        )));
+    }
+    if let Some(expected) = &config.expected_hash {
+        if !expected.eq_ignore_ascii_case(&content_hash) {
+            return Err(invalid(format!(
+                "hash {} is not the expected one",
+                content_hash
+            )));
+        }
+    }
+    if let (Some(theirs), Some(ours)) = (&manifest.network_id, network_id) {
+        if theirs != ours {
+            return Err(invalid(format!("is for network {}, not {}", theirs, ours)));
+        }
+    }
+
+    let body: SnapshotBody = serde_json::from_slice(body_bytes)?;
+    if body.polyps.len() != manifest.polyps || body.vectors.len() != manifest.vectors {
+        return Err(invalid(
+            "contents do not match the manifest counts".to_string(),
+        ));
+    }
+    if body.checkpoint.as_ref().map(|c| c.epoch) != manifest.checkpoint_epoch
+        || manifest
+            .checkpoint_epoch
+            .is_some_and(|epoch| epoch > manifest.epoch)
+    {
+        return Err(invalid(
+            "checkpoint does not match the manifest".to_string(),
+        ));
+    }
+
+    // Polyps hardened under the checkpointed root must prove it.
+    if let Some(checkpoint) = &body.checkpoint {
+        for polyp in &body.polyps {
+            if let Some(lineage) = &polyp.hardening {
+                if lineage.merkle_root == checkpoint.merkle_root {
+                    verify_lineage(&polyp.id, lineage, checkpoint)?;
+                }
+            }
+        }
+    }
+    Ok((manifest, body))
+}
+
+fn decode_hex32(hex: &str, what: &str) -> Result<[u8; 32], ChitinError> {
+    hex::decode(hex)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| ChitinError::InvalidState(format!("{} must be 32 bytes of hex", what)))
+}
+
+/// Writes snapshots of a running node for `admin/snapshot`.
+#[derive(Clone)]
+pub struct Snapshotter {
+    data_dir: String,
+    store: Arc<RocksStore>,
+    index: Arc<InMemoryVectorIndex>,
+    shared: DaemonSharedState,
+    ipfs: IpfsClient,
+    hotkey: [u8; 32],
+    signing_key: Option<SecretKey>,
+}
+
+impl Snapshotter {
+    /// A snapshotter for a node keeping its data in `data_dir`, pinning
+    /// through `ipfs`.
+    pub fn new(
+        data_dir: &str,
+        store: Arc<RocksStore>,
+        index: Arc<InMemoryVectorIndex>,
+        shared: DaemonSharedState,
+        ipfs: IpfsClient,
+    ) -> Self {
+        Self {
+            data_dir: data_dir.to_string(),
+            store,
+            index,
+            shared,
+            ipfs,
+            hotkey: [0; 32],
+            signing_key: None,
+        }
+    }
+
+    /// Sign snapshots with `signing_key` as `hotkey`; without a key,
+    /// snapshots are refused.
+    pub fn with_identity(mut self, hotkey: [u8; 32], signing_key: Option<SecretKey>) -> Self {
+        self.hotkey = hotkey;
+        self.signing_key = signing_key;
+        self
+    }
+
+    /// Take a snapshot of the node as it is now and sign it.
+    pub async fn capture(&self) -> Result<(SnapshotManifest, Vec<u8>), ChitinError> {
+        let signing_key = self.signing_key.clone().ok_or_else(|| {
+            ChitinError::InvalidState("No hotkey to sign the snapshot with".to_string())
+        })?;
+        let (epoch, block) = {
+            let em = self.shared.epoch_manager.read().await;
+            (em.current_epoch(), em.current_block())
+        };
+        let metagraph = self
+            .shared
+            .metagraph_manager
+            .read()
+            .await
+            .current()
+            .cloned();
+        let network_id = self.shared.network.as_ref().map(|n| n.network_id.clone());
+
+        let store = self.store.clone();
+        let index = self.index.clone();
+        let hotkey = self.hotkey;
+        tokio::task::spawn_blocking(move || {
+            let mut polyps = Vec::new();
+            for (key, _) in store.scan_prefix(b"polyp:")? {
+                let id = std::str::from_utf8(&key[b"polyp:".len()..])
+                    .ok()
+                    .and_then(|id| Uuid::parse_str(id).ok());
+                if let Some(polyp) = id
+                    .map(|id| store.get_polyp_sync(&id))
+                    .transpose()?
+                    .flatten()
+                {
+                    polyps.push(polyp);
+                }
+            }
+            polyps.sort_by_key(|polyp| polyp.id);
+            let vectors = index
+                .entries()
+                .into_iter()
+                .map(|(id, model, values)| SnapshotVector { id, model, values })
+                .collect();
+            let body = SnapshotBody {
+                polyps,
+                vectors,
+                metagraph,
+                checkpoint: HardeningCheckpoint::latest_at(&store, epoch)?,
+            };
+            encode_snapshot(&body, network_id, epoch, block, &hotkey, &signing_key)
+        })
+        .await
+        .map_err(|e| ChitinError::InvalidState(format!("Snapshot task panicked: {}", e)))?
+    }
+
+    /// Write a snapshot to `<data_dir>/snapshots/<request.dest>`, with
+    /// `snapshot-<epoch>.chitin` for `dest` by default, and pin it if asked.
+    pub async fn run(&self, request: SnapshotRequest) -> Result<SnapshotResponse, String> {
+        let dest = match &request.dest {
+            Some(dest) => Some(data_path(&self.data_dir, "snapshots", dest)?),
+            None => None,
+        };
+        let (manifest, file) = self.capture().await.map_err(|e| e.to_string())?;
+        let path = match dest {
+            Some(path) => path,
+            None => format!(
+                "{}/snapshots/snapshot-{:08}.chitin",
+                self.data_dir, manifest.epoch
+            ),
+        };
+        if std::path::Path::new(&path).exists() {
+            return Err(format!("Snapshot destination {} already exists", path));
+        }
+        if let Some(parent) = std::path::Path::new(&path).parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, &file).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+
+        let cid = if request.pin {
+            let cid = self.ipfs.put(&file).await;
+            Some(cid.map_err(|e| format!("Failed to add snapshot to IPFS: {}", e))?)
+        } else {
+            None
+        };
+        tracing::info!(
+            "Wrote snapshot of epoch {} to {} ({} polyps, hash {})",
+            manifest.epoch,
+            path,
+            manifest.polyps,
+            manifest.content_hash
+        );
+        Ok(SnapshotResponse {
+            path,
+            epoch: manifest.epoch,
+            block: manifest.block,
+            polyps: manifest.polyps,
+            vectors: manifest.vectors,
+            checkpoint_epoch: manifest.checkpoint_epoch,
+            content_hash: manifest.content_hash,
+            signer: manifest.signer,
+            cid,
+            created_at: manifest.created_at,
+        })
+    }
+
+    /// Adapt `run` into the RPC server's `admin/snapshot` callback.
+    pub fn snapshot_callback(&self) -> SnapshotCallback {
+        let snapshotter = self.clone();
+        Arc::new(move |request: SnapshotRequest| {
+            let snapshotter = snapshotter.clone();
+            Box::pin(async move { snapshotter.run(request).await })
+        })
+    }
+}
+
+/// Fetch, verify, and load the snapshot at `source` into `store`, `index`,
+/// and `shared`, unless the store already holds polyps. If `peers` are
+/// given, a checkpoint a strict majority of them contradicts is refused.
+/// Returns the manifest of the snapshot loaded, if one was.
+pub async fn bootstrap(
+    source: &str,
+    config: &SnapshotConfig,
+    store: &Arc<RocksStore>,
+    index: &InMemoryVectorIndex,
+    shared: &DaemonSharedState,
+    ipfs: &IpfsClient,
+    peers: &[String],
+) -> Result<Option<SnapshotManifest>, ChitinError> {
+    if !store.scan_prefix(b"polyp:")?.is_empty() {
+        tracing::info!(
+            "Store already holds polyps; not bootstrapping from {}",
+            source
+        );
+        return Ok(None);
+    }
+    let file = SnapshotSource::parse(source)?.fetch(ipfs).await?;
+    let network_id = shared.network.as_ref().map(|n| n.network_id.as_str());
+    let (manifest, body) = decode_snapshot(&file, config, network_id)?;
+
+    if let (Some(checkpoint), false) = (&body.checkpoint, peers.is_empty()) {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        let registry = PeerRegistry::new(None, peers.to_vec());
+        match fetch_checkpoint_quorum(&client, &registry, peers, checkpoint.epoch).await {
+            Some(agreed) if agreed != *checkpoint => {
+                return Err(ChitinError::Verification(format!(
+                    "Snapshot checkpoint for epoch {} is not the one peers agree on",
+                    checkpoint.epoch
+                )));
+            }
+            Some(_) => tracing::info!(
+                "Snapshot checkpoint for epoch {} matches the peer quorum",
+                checkpoint.epoch
+            ),
+            None => tracing::warn!(
+                "No peer quorum on the checkpoint for epoch {}; trusting the snapshot signer",
+                checkpoint.epoch
+            ),
+        }
+    }
+
+    for polyp in &body.polyps {
+        store.save_polyp_sync(polyp)?;
+    }
+    for vector in &body.vectors {
+        index.upsert_in(&vector.model, vector.id, &vector.values)?;
+    }
+    if let Some(checkpoint) = &body.checkpoint {
+        checkpoint.save(store)?;
+    }
+    if let Some(metagraph) = body.metagraph {
+        shared.update_metagraph(metagraph, false).await?;
+    }
+    {
+        let mut em = shared.epoch_manager.write().await;
+        if manifest.block > em.current_block() {
+            em.advance_block(manifest.block);
+        }
+    }
+    tracing::info!(
+        "Bootstrapped from snapshot of epoch {} (block {}, {} polyps, {} vectors) signed by {}",
+        manifest.epoch,
+        manifest.block,
+        manifest.polyps,
+        manifest.vectors,
+        manifest.signer
+    );
+    Ok(Some(manifest))
+}
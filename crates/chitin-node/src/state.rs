@@ -1,4 +1,4 @@
-// crates/chitin-daemon/src/state.rs
+// crates/chitin-node/src/state.rs
 //
 // Node state machine for the Chitin Protocol daemon.
 //
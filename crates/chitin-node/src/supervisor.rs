@@ -0,0 +1,345 @@
+// crates/chitin-node/src/supervisor.rs
+//
+// Supervision of the daemon's long-running background tasks.
+//
+// Each job is registered by name with a priority and a restart policy, and
+// runs as its own tokio task built by a factory, so it can be started again.
+// The supervisor records every run: when it started, how it exited (returned,
+// returned an error, or panicked), and how often it was restarted. Jobs with
+// `RestartPolicy::OnFailure` are restarted after an error or panic, after a
+// delay that grows with each consecutive failure (from a base set by the
+// priority), until they fail `MAX_CONSECUTIVE_FAILURES` times in a row; a run
+// that lasted `FAILURE_RESET` resets the count. A job returning normally
+// (e.g. because it is disabled) is finished, not restarted. The node is
+// unhealthy once a critical or high-priority job has failed for good.
+// `admin/tasks` lists the jobs. Short-lived per-message tasks (gossip pushes,
+// webhook deliveries) are spawned directly. `shutdown` cancels every job and
+// resolves the `ShutdownSignal`s the node loops wait on.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+
+use chitin_rpc::handlers::admin::{ListTasksResponse, TaskInfo};
+use chitin_rpc::TaskListCallback;
+
+/// Consecutive failures after which a job is no longer restarted.
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+/// A run lasting at least this long resets the consecutive failure count.
+const FAILURE_RESET: Duration = Duration::from_secs(300);
+
+/// Longest delay before a restart.
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// How important a job is to the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// The node cannot make progress without it (scheduler, RPC server).
+    Critical,
+    /// Needed to stay in sync with the network.
+    High,
+    /// Maintenance the node can run without for a while.
+    Normal,
+    /// Housekeeping.
+    Low,
+}
+
+impl Priority {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+
+    /// Delay before the first restart after a failure.
+    fn restart_delay(self) -> Duration {
+        match self {
+            Self::Critical => Duration::from_secs(1),
+            Self::High => Duration::from_secs(2),
+            Self::Normal => Duration::from_secs(5),
+            Self::Low => Duration::from_secs(15),
+        }
+    }
+}
+
+/// What to do when a job exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Run once.
+    Never,
+    /// Restart after an error or panic.
+    OnFailure,
+}
+
+impl RestartPolicy {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::OnFailure => "on_failure",
+        }
+    }
+}
+
+/// Outcome of one run of a job: `()` or a `Result` whose error is reported.
+pub trait JobExit {
+    /// The run's error, if it failed.
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl JobExit for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: std::fmt::Display> JobExit for Result<(), E> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/// State of a job's current run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskState {
+    Running,
+    Restarting,
+    Finished,
+    Failed,
+}
+
+impl TaskState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Restarting => "restarting",
+            Self::Finished => "finished",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// What the supervisor knows about a job.
+#[derive(Debug, Clone)]
+struct TaskRecord {
+    priority: Priority,
+    restart: RestartPolicy,
+    state: TaskState,
+    restarts: u32,
+    started_at: Option<DateTime<Utc>>,
+    last_exit_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+/// Registry and supervisor of named background jobs; clones share it.
+#[derive(Debug, Clone)]
+pub struct TaskSupervisor {
+    tasks: Arc<std::sync::RwLock<HashMap<String, TaskRecord>>>,
+    /// Set to true once, on shutdown.
+    stopping: Arc<watch::Sender<bool>>,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self {
+            tasks: Arc::default(),
+            stopping: Arc::new(watch::channel(false).0),
+        }
+    }
+}
+
+/// Resolves once its supervisor is shut down; clones are independent.
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    /// Wait for shutdown.
+    pub async fn wait(&mut self) {
+        // A dropped sender means the supervisor is gone, which counts too.
+        let _ = self.0.wait_for(|stopping| *stopping).await;
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every job, without restarting it, and resolve every
+    /// `ShutdownSignal`.
+    pub fn shutdown(&self) {
+        self.stopping.send_replace(true);
+    }
+
+    /// A signal resolving on `shutdown`.
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        ShutdownSignal(self.stopping.subscribe())
+    }
+
+    /// Run the job built by `factory` as `name`, restarting it per
+    /// `restart`.
+    pub fn spawn<F, Fut>(&self, name: &str, priority: Priority, restart: RestartPolicy, factory: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future + Send + 'static,
+        Fut::Output: JobExit + Send,
+    {
+        let name = name.to_string();
+        self.write().insert(
+            name.clone(),
+            TaskRecord {
+                priority,
+                restart,
+                state: TaskState::Running,
+                restarts: 0,
+                started_at: None,
+                last_exit_at: None,
+                last_error: None,
+            },
+        );
+
+        let supervisor = self.clone();
+        let mut shutdown = self.shutdown_signal();
+        tokio::spawn(async move {
+            let mut consecutive_failures = 0;
+            loop {
+                supervisor.update(&name, |task| {
+                    task.state = TaskState::Running;
+                    task.started_at = Some(Utc::now());
+                });
+                let started = Instant::now();
+                let mut run = tokio::spawn(factory());
+                let joined = tokio::select! {
+                    joined = &mut run => joined,
+                    _ = shutdown.wait() => {
+                        run.abort();
+                        supervisor.update(&name, |task| {
+                            task.state = TaskState::Finished;
+                            task.last_exit_at = Some(Utc::now());
+                        });
+                        return;
+                    }
+                };
+                let outcome = match joined {
+                    Ok(exit) => exit.into_result(),
+                    Err(e) if e.is_panic() => Err(format!("panicked: {}", panic_message(e))),
+                    // Cancelled: the runtime is shutting down.
+                    Err(_) => return,
+                };
+
+                let error = match outcome {
+                    Ok(()) => {
+                        supervisor.update(&name, |task| {
+                            task.state = TaskState::Finished;
+                            task.last_exit_at = Some(Utc::now());
+                        });
+                        tracing::debug!("Task {} finished", name);
+                        return;
+                    }
+                    Err(error) => error,
+                };
+                if started.elapsed() >= FAILURE_RESET {
+                    consecutive_failures = 0;
+                }
+                consecutive_failures += 1;
+                let retry = restart == RestartPolicy::OnFailure
+                    && consecutive_failures < MAX_CONSECUTIVE_FAILURES;
+                supervisor.update(&name, |task| {
+                    task.state = if retry {
+                        TaskState::Restarting
+                    } else {
+                        TaskState::Failed
+                    };
+                    task.last_exit_at = Some(Utc::now());
+                    task.last_error = Some(error.clone());
+                });
+                if !retry {
+                    tracing::error!("Task {} failed: {}", name, error);
+                    return;
+                }
+
+                let delay = priority
+                    .restart_delay()
+                    .saturating_mul(1 << (consecutive_failures - 1).min(6))
+                    .min(MAX_RESTART_DELAY);
+                tracing::warn!("Task {} failed: {}; restarting in {:?}", name, error, delay);
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = shutdown.wait() => return,
+                }
+                supervisor.update(&name, |task| task.restarts += 1);
+            }
+        });
+    }
+
+    /// Every job's status, by priority then name.
+    pub fn list(&self) -> ListTasksResponse {
+        let tasks = self.read();
+        let mut infos: Vec<(Priority, TaskInfo)> = tasks
+            .iter()
+            .map(|(name, task)| {
+                let info = TaskInfo {
+                    name: name.clone(),
+                    priority: task.priority.as_str().to_string(),
+                    restart: task.restart.as_str().to_string(),
+                    state: task.state.as_str().to_string(),
+                    restarts: task.restarts,
+                    started_at: task.started_at,
+                    last_exit_at: task.last_exit_at,
+                    last_error: task.last_error.clone(),
+                };
+                (task.priority, info)
+            })
+            .collect();
+        infos.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        let healthy = !tasks
+            .values()
+            .any(|task| task.state == TaskState::Failed && task.priority <= Priority::High);
+        ListTasksResponse {
+            tasks: infos.into_iter().map(|(_, info)| info).collect(),
+            healthy,
+        }
+    }
+
+    /// Callback listing jobs for `admin/tasks`.
+    pub fn task_list(&self) -> TaskListCallback {
+        let supervisor = self.clone();
+        Arc::new(move || {
+            let supervisor = supervisor.clone();
+            Box::pin(async move { supervisor.list() })
+        })
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut TaskRecord)) {
+        if let Some(task) = self.write().get_mut(name) {
+            f(task);
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, TaskRecord>> {
+        self.tasks.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, TaskRecord>> {
+        self.tasks.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The message a task panicked with.
+fn panic_message(error: tokio::task::JoinError) -> String {
+    let payload = error.into_panic();
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "unknown panic".to_string(),
+        },
+    }
+}
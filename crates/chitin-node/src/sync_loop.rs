@@ -0,0 +1,1330 @@
+// crates/chitin-node/src/sync_loop.rs
+//
+// Background pull-sync loop: periodically negotiates with peers which polyps
+// are missing locally and retrieves them. Each round starts by comparing
+// Merkle roots (`sync/merkle/nodes`); peers whose root matches ours are in
+// sync and cost nothing further. For the rest, negotiation first tries IBLT
+// reconciliation (`sync/reconcile`), which recovers the exact difference in
+// one round trip. If the difference is too large to decode (e.g. after a long
+// outage) it descends the Merkle trees to the divergent subtrees, then falls
+// back to comparing UUIDv7 time ranges (`sync/range/*`) and Vector Bloom
+// Filters (`sync/vbf`) for peers without Merkle support. Full ID lists are
+// only fetched when false positives may hide missing polyps or the peer
+// supports none of these exchanges. Missing polyps are fetched in priority
+// order (`sync/polyp_meta`): current-epoch review work and subscribed Reef
+// Zones first, old hardened history last. They are transferred in compressed
+// batches (`peer/get_polyps_batch`), a few at a time per peer under an
+// ingestion throttle shared with gossip, pausing while store admission
+// control finds RocksDB under write pressure. Large catch-ups are
+// checkpointed per time range in RocksDB and resume where they left off
+// after a restart.
+// Nodes holding only some shards reconcile and store just those shards.
+// Polyps this node has pruned (tombstoned) are never pulled back.
+// Hardened polyps are checked against the epoch's hardening Merkle root
+// (`sync/hardening_checkpoint`) rather than trusted; peers serving polyps
+// that fail the check lose score and are eventually no longer synced from.
+// State transitions of polyps both sides already hold are pulled from each
+// peer's change log (`sync/state_updates`) and applied when they supersede
+// the local state.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use chitin_core::consensus::HardeningLineage;
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::traits::PolypStore;
+use chitin_store::merkle::{MerkleNode, MerklePrefix, MerkleSummary};
+use chitin_store::{AdmissionControl, InMemoryVectorIndex, RocksStore, ShardSet};
+use chitin_core::ChitinError;
+use chitin_consensus::epoch::EpochManager;
+use chitin_consensus::hardening::{verify_lineage, HardeningCheckpoint};
+use chitin_rpc::QueryCache;
+use chitin_sync::merkle::{MerklePeer, MerkleSync};
+use chitin_sync::progress::{overall_percent, PeerSyncProgress, DEFAULT_PROGRESS_RANGES};
+use chitin_sync::priority::{PolypSyncMeta, SyncPriority, SyncQueue};
+use chitin_sync::range::{IdPage, RangeIndex, RangePeer, RangeSummary, RangeSync, TimeRange};
+use chitin_sync::reconcile::{Iblt, SetReconciler};
+use chitin_sync::metrics::SyncPhase;
+use chitin_sync::state_update::{
+    load_cursor, save_cursor, store_state_update, PolypStateUpdate, MAX_STATE_UPDATES,
+};
+use chitin_sync::throttle::SyncThrottle;
+use chitin_sync::tombstone::tombstoned_ids;
+use chitin_sync::transfer::{CompressedPolyps, Compression};
+use chitin_sync::vbf::VectorBloomFilter;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::metrics::DaemonMetrics;
+use crate::peers::PeerRegistry;
+use crate::reload::ConfigHandle;
+
+/// Run the background sync loop.
+///
+/// Every `sync_interval_secs` of the live `config`, iterates configured peers:
+/// 0. Skips peers whose Merkle root matches ours
+/// 1. Learns which remote polyps are missing via `sync/reconcile`, falling
+///    back to Merkle descent, `sync/range/*`, and then `sync/vbf`
+/// 2. Falls back to `peer/list_polyp_ids` only if the VBF exchange is ambiguous
+/// 3. Fetches missing polyps via `peer/get_polyps_batch` (or `polyp/get`),
+///    highest `priority` first
+/// 4. Verifies hardened polyps against the epoch hardening checkpoint,
+///    penalizing the peer for any that fail, then saves + indexes locally
+/// 5. Pulls state transitions of already-held polyps (`sync/state_updates`)
+///
+/// Catch-ups of at least `CHECKPOINT_MIN_MISSING` polyps persist per-range
+/// progress after every batch; after a restart only the pending ranges are
+/// re-negotiated. Only polyps in `shards` are reconciled and stored, under
+/// `throttle`'s inflight limits and write pacing, pausing while
+/// `admission` refuses writes. Each peer's phase, last
+/// success and error, pulled count, and missing count are recorded in the
+/// registry's sync metrics; each round runs in a `sync_round` span and its
+/// duration is recorded in `metrics`. Searches cached in `query_cache` that
+/// pulled polyps could change are dropped.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_sync_loop(
+    registry: Arc<PeerRegistry>,
+    store: Arc<RocksStore>,
+    index: Arc<InMemoryVectorIndex>,
+    config: ConfigHandle,
+    priority: SyncPriority,
+    epoch_manager: Arc<tokio::sync::RwLock<EpochManager>>,
+    shards: ShardSet,
+    throttle: Arc<SyncThrottle>,
+    metrics: DaemonMetrics,
+    query_cache: Option<Arc<QueryCache>>,
+    admission: Option<Arc<AdmissionControl>>,
+) {
+    match PeerSyncProgress::load_all(&store) {
+        Ok(checkpoints) if !checkpoints.is_empty() => {
+            tracing::info!(
+                "Sync: resuming catch-up from {} peer(s), {:.1}% complete",
+                checkpoints.len(),
+                overall_percent(&checkpoints)
+            );
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Sync: failed to load persisted progress: {}", e),
+    }
+
+    loop {
+        let current_epoch = epoch_manager.read().await.current_epoch();
+        let priority = priority.clone().at_epoch(current_epoch);
+        let started = Instant::now();
+        async {
+            let result = sync_once(
+                &registry,
+                &store,
+                &index,
+                &priority,
+                &shards,
+                &throttle,
+                admission.as_deref(),
+                query_cache.as_deref(),
+            )
+            .await;
+            if let Err(e) = result {
+                tracing::warn!("Sync loop error: {}", e);
+            }
+            sync_state_updates(&registry, &store, &shards, current_epoch).await;
+        }
+        .instrument(tracing::info_span!("sync_round", epoch = current_epoch))
+        .await;
+        metrics.observe_sync_round(started.elapsed());
+        registry.sync_metrics().set_phase(SyncPhase::Idle);
+
+        let interval_secs = config.sync_interval_secs().await;
+        tokio::time::sleep(std::time::Duration::from_secs(interval_secs)).await;
+    }
+}
+
+/// Perform a single sync round against all peers.
+#[allow(clippy::too_many_arguments)]
+async fn sync_once(
+    registry: &PeerRegistry,
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    priority: &SyncPriority,
+    shards: &ShardSet,
+    throttle: &SyncThrottle,
+    admission: Option<&AdmissionControl>,
+    query_cache: Option<&QueryCache>,
+) -> Result<(), String> {
+    let peers = registry.dialable_peer_urls().await;
+    let client = registry.http_client();
+    let partial = (!shards.is_full()).then_some(shards);
+    let metrics = registry.sync_metrics();
+
+    // Step 0: Peers with the same Merkle root hold the same polyp set. A node
+    // holding only some shards cannot compare roots; it learns each peer's
+    // shards instead and skips peers holding none of its own.
+    let local_root = store.merkle().read().unwrap_or_else(|e| e.into_inner()).root();
+    let mut divergent = Vec::new();
+    for peer_url in &peers {
+        if registry.peer_score(peer_url).await <= 0.0 {
+            tracing::trace!("Sync: skipping distrusted peer {}", peer_url);
+            continue;
+        }
+        metrics.begin(peer_url, SyncPhase::Comparing);
+        if let Some(shards) = partial {
+            if refresh_peer_shards(registry, peer_url, shards).await {
+                divergent.push(peer_url.clone());
+            } else {
+                metrics.finish(peer_url);
+                tracing::trace!("Sync: peer {} holds none of our shards", peer_url);
+            }
+            continue;
+        }
+        let peer = HttpPeer { client, peer_url };
+        match peer.nodes(&[MerklePrefix::ROOT]).await {
+            Ok(nodes) if nodes.first() == Some(&local_root) => {
+                registry.mark_peer(peer_url, true, None).await;
+                metrics.record_success(peer_url, now_ms());
+                metrics.set_missing(peer_url, 0);
+                metrics.finish(peer_url);
+                if let Err(e) = PeerSyncProgress::clear(store, peer_url) {
+                    tracing::warn!("Sync: failed to clear progress for {}: {}", peer_url, e);
+                }
+                tracing::trace!("Sync: in sync with peer {}", peer_url);
+            }
+            _ => divergent.push(peer_url.clone()),
+        }
+    }
+    if divergent.is_empty() {
+        return Ok(());
+    }
+    metrics.set_phase(SyncPhase::Negotiating);
+
+    // Build set of local polyp IDs and the summaries we send to peers.
+    let mut local_ids = get_local_polyp_ids(store).await?;
+    let pruned = tombstoned_ids(store).map_err(|e| format!("Failed to list tombstones: {}", e))?;
+    local_ids.retain(|id| shards.may_contain(id));
+    let reconciler = SetReconciler::with_local_ids(local_ids.iter().copied().collect());
+    let local_estimator = reconciler.local_estimator().to_hex();
+    let local_vbf = reconciler.local_filter().to_hex();
+    let range_index = RangeIndex::from_ids(&local_ids);
+    let mut roots = HardeningRoots {
+        client,
+        registry,
+        store,
+        known: HashMap::new(),
+    };
+
+    for peer_url in &divergent {
+        // Steps 1-2: Negotiate which remote polyps are missing locally. A
+        // persisted catch-up only re-negotiates its pending ranges.
+        metrics.begin(peer_url, SyncPhase::Negotiating);
+        let summaries = LocalSummaries {
+            estimator: &local_estimator,
+            vbf: &local_vbf,
+            ranges: &range_index,
+            merkle: store.merkle(),
+            shards: partial,
+        };
+        let checkpoint = match PeerSyncProgress::load(store, peer_url) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                tracing::warn!("Sync: failed to load progress for {}: {}", peer_url, e);
+                None
+            }
+        };
+        let negotiated = match &checkpoint {
+            Some(progress) if partial.is_none() => {
+                match resume_missing(client, peer_url, &range_index, progress).await {
+                    Ok(missing) => Ok(missing),
+                    Err(e) => {
+                        tracing::debug!(
+                            "Sync: resuming catch-up with {} failed ({}), renegotiating",
+                            peer_url,
+                            e
+                        );
+                        find_missing(client, peer_url, &reconciler, summaries, &local_ids).await
+                    }
+                }
+            }
+            _ => find_missing(client, peer_url, &reconciler, summaries, &local_ids).await,
+        };
+        let missing = match negotiated {
+            Ok(mut missing) => {
+                registry.mark_peer(peer_url, true, None).await;
+                missing.retain(|id| shards.may_contain(id) && !pruned.contains(id));
+                metrics.record_success(peer_url, now_ms());
+                metrics.set_missing(peer_url, missing.len() as u64);
+                missing
+            }
+            Err(e) => {
+                tracing::debug!("Sync: could not reach peer {}: {}", peer_url, e);
+                registry.mark_peer(peer_url, false, None).await;
+                metrics.record_error(peer_url, &e, now_ms());
+                continue;
+            }
+        };
+
+        let mut checkpoint = match checkpoint {
+            Some(mut progress) => {
+                progress.rebase(&missing, now_ms());
+                Some(progress)
+            }
+            None if missing.len() >= CHECKPOINT_MIN_MISSING => {
+                tracing::info!("Sync: checkpointing catch-up from peer {}", peer_url);
+                Some(PeerSyncProgress::new(
+                    peer_url,
+                    DEFAULT_PROGRESS_RANGES,
+                    &missing,
+                    now_ms(),
+                ))
+            }
+            None => None,
+        };
+        if let Some(progress) = &checkpoint {
+            save_progress(store, progress);
+        }
+
+        if missing.is_empty() {
+            metrics.finish(peer_url);
+            tracing::trace!("Sync: in sync with peer {}", peer_url);
+            continue;
+        }
+
+        tracing::info!(
+            "Sync: {} missing polyps from peer {}",
+            missing.len(),
+            peer_url
+        );
+
+        // Step 3: Fetch and store missing polyps in batches, most relevant
+        // first. Up to the per-peer cap of batches are fetched concurrently;
+        // each holds an ingestion permit until stored, and storing is paced
+        // by store write latency.
+        metrics.begin(peer_url, SyncPhase::Fetching);
+        let mut queue = prioritize(client, peer_url, missing, priority).await;
+        let mut inflight = JoinSet::new();
+        loop {
+            while !queue.is_empty() {
+                let permit = match throttle.try_acquire(peer_url) {
+                    Some(permit) => permit,
+                    None if inflight.is_empty() => throttle.acquire(peer_url).await,
+                    None => break,
+                };
+                let batch: Vec<Uuid> = std::iter::from_fn(|| queue.pop())
+                    .take(FETCH_BATCH_SIZE)
+                    .collect();
+                let (client, peer_url) = (client.clone(), peer_url.clone());
+                inflight.spawn(async move {
+                    (permit, fetch_remote_polyps(&client, &peer_url, &batch).await)
+                });
+            }
+            let (permit, polyps) = match inflight.join_next().await {
+                Some(Ok(fetched)) => fetched,
+                Some(Err(e)) => {
+                    tracing::warn!("Sync: fetch task for {} failed: {}", peer_url, e);
+                    continue;
+                }
+                None => break,
+            };
+
+            throttle.pace().await;
+            // Holding the permit while the store is under pressure keeps
+            // further batches from being fetched until it recovers.
+            if let Some(admission) = admission {
+                admission.wait().await;
+            }
+            for polyp in polyps {
+                let polyp_id = polyp.id;
+                if !shards.contains_polyp(&polyp) {
+                    tracing::debug!("Sync: polyp {} is outside this node's shards", polyp_id);
+                    continue;
+                }
+                if let Err(e) = roots.verify(&polyp).await {
+                    reject_polyp(registry, peer_url, polyp_id, e).await;
+                    continue;
+                }
+                let started = Instant::now();
+                let stored = store_pulled_polyp(store, index, query_cache, peer_url, polyp).await;
+                throttle.record_write(started.elapsed());
+                if stored {
+                    metrics.record_pulled(peer_url, 1);
+                    if let Some(progress) = checkpoint.as_mut() {
+                        progress.record_fetched(&polyp_id, now_ms());
+                    }
+                }
+            }
+            drop(permit);
+            if let Some(progress) = &checkpoint {
+                save_progress(store, progress);
+                tracing::debug!(
+                    "Sync: catch-up from {} {:.1}% complete",
+                    peer_url,
+                    progress.percent_complete()
+                );
+            }
+        }
+        metrics.finish(peer_url);
+    }
+
+    Ok(())
+}
+
+/// Verify, save, and index a polyp pulled from a peer. Returns `true` if
+/// it was saved.
+async fn store_pulled_polyp(
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    query_cache: Option<&QueryCache>,
+    peer_url: &str,
+    polyp: Polyp,
+) -> bool {
+    let polyp_id = polyp.id;
+
+    // Phase 2: Verify signature if present (soft enforcement).
+    if polyp.signature.is_some() {
+        let creator_hotkey = &polyp.subject.provenance.creator.hotkey;
+        match polyp.verify_signature(creator_hotkey) {
+            Ok(true) => {
+                tracing::debug!("Sync: polyp {} signature verified", polyp_id);
+            }
+            Ok(false) => {
+                tracing::warn!(
+                    "Sync: polyp {} has INVALID signature (soft enforcement, accepting anyway)",
+                    polyp_id
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Sync: polyp {} signature verification error: {} (accepting anyway)",
+                    polyp_id,
+                    e
+                );
+            }
+        }
+    }
+
+    let values = polyp.subject.vector.values.clone();
+    let model = polyp.subject.vector.model_id.key();
+
+    if let Err(e) = store.save_polyp(&polyp).await {
+        tracing::warn!("Sync: failed to save polyp {}: {}", polyp_id, e);
+        return false;
+    }
+
+    if let Err(e) = index.upsert_in(&model, polyp_id, &values) {
+        tracing::warn!("Sync: failed to index polyp {}: {}", polyp_id, e);
+    } else if let Some(cache) = query_cache {
+        cache.invalidate_polyp(&polyp);
+    }
+
+    tracing::debug!("Sync: pulled polyp {} from {}", polyp_id, peer_url);
+    true
+}
+
+// ---------------------------------------------------------------------------
+// Hardened-set verification
+// ---------------------------------------------------------------------------
+
+/// Score lost per hardened polyp that fails verification.
+const INVALID_HARDENING_PENALTY: f64 = 0.25;
+
+/// Why a pulled polyp was not stored.
+enum Rejection {
+    /// The polyp's hardening does not check out; the peer is at fault.
+    Invalid(String),
+    /// No trusted root is known for the polyp's epoch yet; retried later.
+    Unverifiable(String),
+    /// A state update the peer relayed but this node does not take from
+    /// peers (see `PolypStateUpdate::verify_origin`); dropped.
+    Untrusted(String),
+}
+
+/// Epoch hardening roots used to verify pulled hardened polyps.
+///
+/// A root comes from the local checkpoint if this node hardened (or has
+/// already verified) that epoch, otherwise from the checkpoint reported by
+/// a majority of trusted peers, which is then saved locally. Never from the
+/// polyp's own lineage.
+struct HardeningRoots<'a> {
+    client: &'a reqwest::Client,
+    registry: &'a PeerRegistry,
+    store: &'a Arc<RocksStore>,
+    /// Epoch -> checkpoint, or `None` if no quorum formed this round.
+    known: HashMap<u64, Option<HardeningCheckpoint>>,
+}
+
+impl HardeningRoots<'_> {
+    /// Check a pulled polyp: hardened polyps must carry a lineage whose
+    /// inclusion proof matches their epoch's checkpointed root.
+    async fn verify(&mut self, polyp: &Polyp) -> Result<(), Rejection> {
+        let epoch = polyp.consensus.as_ref().map(|c| c.epoch);
+        self.verify_hardening(&polyp.id, &polyp.state, polyp.hardening.as_ref(), epoch)
+            .await
+    }
+
+    /// Check a pulled state update the same way, after its origin: it must
+    /// be signed by a registered validator, at or before `current_epoch`.
+    async fn verify_update(
+        &mut self,
+        update: &PolypStateUpdate,
+        current_epoch: u64,
+    ) -> Result<(), Rejection> {
+        if update.signature.is_some() && !update.verify_signature().unwrap_or(false) {
+            return Err(Rejection::Invalid("invalid state update signature".to_string()));
+        }
+        if let Err(e) = self.registry.verify_state_update(update, current_epoch).await {
+            return Err(Rejection::Untrusted(e.to_string()));
+        }
+        let lineage = update.hardening.as_ref();
+        self.verify_hardening(&update.polyp_id, &update.state, lineage, Some(update.epoch))
+            .await
+    }
+
+    async fn verify_hardening(
+        &mut self,
+        polyp_id: &Uuid,
+        state: &PolypState,
+        lineage: Option<&HardeningLineage>,
+        epoch: Option<u64>,
+    ) -> Result<(), Rejection> {
+        if *state != PolypState::Hardened {
+            return Ok(());
+        }
+        let lineage = match lineage {
+            Some(lineage) => lineage,
+            None => return Err(Rejection::Invalid("hardened without lineage".to_string())),
+        };
+        let epoch = match epoch {
+            Some(epoch) => epoch,
+            None => return Err(Rejection::Invalid("hardened without epoch".to_string())),
+        };
+        let checkpoint = match self.checkpoint(epoch).await {
+            Some(checkpoint) => checkpoint,
+            None => {
+                return Err(Rejection::Unverifiable(format!(
+                    "no trusted hardening root for epoch {}",
+                    epoch
+                )))
+            }
+        };
+        verify_lineage(polyp_id, lineage, &checkpoint)
+            .map_err(|e| Rejection::Invalid(e.to_string()))
+    }
+
+    /// The trusted checkpoint for `epoch`, if one is known or can be agreed.
+    async fn checkpoint(&mut self, epoch: u64) -> Option<HardeningCheckpoint> {
+        if let Some(known) = self.known.get(&epoch) {
+            return known.clone();
+        }
+        let checkpoint = match HardeningCheckpoint::load(self.store, epoch) {
+            Ok(Some(checkpoint)) => Some(checkpoint),
+            Ok(None) => self.checkpoint_from_peers(epoch).await,
+            Err(e) => {
+                tracing::warn!("Sync: failed to load hardening checkpoint {}: {}", epoch, e);
+                None
+            }
+        };
+        self.known.insert(epoch, checkpoint.clone());
+        checkpoint
+    }
+
+    /// Ask every trusted peer for `epoch`'s checkpoint and keep the majority.
+    async fn checkpoint_from_peers(&self, epoch: u64) -> Option<HardeningCheckpoint> {
+        let peers = self.registry.dialable_peer_urls().await;
+        let checkpoint = fetch_checkpoint_quorum(self.client, self.registry, &peers, epoch).await?;
+        if let Err(e) = checkpoint.save(self.store) {
+            tracing::warn!("Sync: failed to save hardening checkpoint {}: {}", epoch, e);
+        }
+        Some(checkpoint)
+    }
+}
+
+/// Ask `peers` (skipping those scored down to 0.0) for `epoch`'s hardening
+/// checkpoint and return the one a strict majority reported.
+pub(crate) async fn fetch_checkpoint_quorum(
+    client: &reqwest::Client,
+    registry: &PeerRegistry,
+    peers: &[String],
+    epoch: u64,
+) -> Option<HardeningCheckpoint> {
+    #[derive(serde::Deserialize)]
+    struct CheckpointResult {
+        checkpoint: Option<HardeningCheckpoint>,
+    }
+
+    let mut reports = Vec::new();
+    for peer_url in peers {
+        if registry.peer_score(peer_url).await <= 0.0 {
+            continue;
+        }
+        let params = serde_json::json!({ "epoch": epoch });
+        let method = "sync/hardening_checkpoint";
+        match call_peer::<CheckpointResult>(client, peer_url, method, params).await {
+            Ok(result) => reports.extend(result.checkpoint.filter(|c| c.epoch == epoch)),
+            Err(e) => {
+                tracing::debug!(
+                    "Sync: no hardening checkpoint {} from {}: {}",
+                    epoch,
+                    peer_url,
+                    e
+                );
+            }
+        }
+    }
+    HardeningCheckpoint::quorum(&reports)
+}
+
+/// Log a rejected polyp and, if the peer is at fault, lower its score.
+async fn reject_polyp(registry: &PeerRegistry, peer_url: &str, polyp_id: Uuid, why: Rejection) {
+    match why {
+        Rejection::Invalid(reason) => {
+            let score = registry.penalize_peer(peer_url, INVALID_HARDENING_PENALTY).await;
+            tracing::warn!(
+                "Sync: rejected hardened polyp {} from {}: {} (peer score {:.2})",
+                polyp_id,
+                peer_url,
+                reason,
+                score
+            );
+        }
+        Rejection::Unverifiable(reason) => {
+            tracing::debug!(
+                "Sync: deferring hardened polyp {} from {}: {}",
+                polyp_id,
+                peer_url,
+                reason
+            );
+        }
+        Rejection::Untrusted(reason) => {
+            tracing::debug!(
+                "Sync: ignoring state update for {} from {}: {}",
+                polyp_id,
+                peer_url,
+                reason
+            );
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// State transitions
+// ---------------------------------------------------------------------------
+
+/// Pull state transitions of polyps already held from each trusted peer's
+/// change log (`sync/state_updates`), resuming from a persisted cursor.
+///
+/// A page holding a hardened update that cannot be verified yet is retried
+/// next round rather than skipped, so the cursor never passes it.
+async fn sync_state_updates(
+    registry: &PeerRegistry,
+    store: &Arc<RocksStore>,
+    shards: &ShardSet,
+    current_epoch: u64,
+) {
+    #[derive(serde::Deserialize)]
+    struct StatePage {
+        updates: Vec<PolypStateUpdate>,
+        cursor: Option<String>,
+        more: bool,
+    }
+
+    let client = registry.http_client();
+    let metrics = registry.sync_metrics();
+    let mut roots = HardeningRoots {
+        client,
+        registry,
+        store,
+        known: HashMap::new(),
+    };
+
+    for peer_url in &registry.dialable_peer_urls().await {
+        if registry.peer_score(peer_url).await <= 0.0 {
+            continue;
+        }
+        let mut cursor = match load_cursor(store, peer_url) {
+            Ok(cursor) => cursor,
+            Err(e) => {
+                tracing::warn!("Sync: failed to load state cursor for {}: {}", peer_url, e);
+                None
+            }
+        };
+
+        let mut applied = 0;
+        metrics.begin(peer_url, SyncPhase::StateUpdates);
+        loop {
+            let params = serde_json::json!({ "after": cursor, "limit": MAX_STATE_UPDATES });
+            let page: StatePage =
+                match call_peer(client, peer_url, "sync/state_updates", params).await {
+                    Ok(page) => {
+                        metrics.record_success(peer_url, now_ms());
+                        page
+                    }
+                    Err(e) => {
+                        tracing::debug!("Sync: no state updates from {}: {}", peer_url, e);
+                        metrics.record_error(peer_url, &e.to_string(), now_ms());
+                        break;
+                    }
+                };
+
+            let mut deferred = false;
+            for update in page.updates {
+                if !shards.may_contain(&update.polyp_id) {
+                    continue;
+                }
+                let polyp_id = update.polyp_id;
+                match roots.verify_update(&update, current_epoch).await {
+                    Ok(()) => {}
+                    Err(why) => {
+                        deferred |= matches!(why, Rejection::Unverifiable(_));
+                        reject_polyp(registry, peer_url, polyp_id, why).await;
+                        continue;
+                    }
+                }
+                match store_state_update(store, &update).await {
+                    Ok(true) => applied += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::warn!("Sync: failed to apply state of {}: {}", polyp_id, e);
+                    }
+                }
+            }
+            if deferred {
+                break;
+            }
+
+            if let Some(next) = &page.cursor {
+                if let Err(e) = save_cursor(store, peer_url, next) {
+                    tracing::warn!("Sync: failed to save state cursor for {}: {}", peer_url, e);
+                }
+            }
+            cursor = page.cursor;
+            if !page.more {
+                break;
+            }
+        }
+        metrics.finish(peer_url);
+        if applied > 0 {
+            tracing::info!("Sync: applied {} state updates from {}", applied, peer_url);
+        }
+    }
+}
+
+/// Missing polyps at or above which a catch-up is checkpointed.
+const CHECKPOINT_MIN_MISSING: usize = 1000;
+
+/// Persist a catch-up checkpoint, removing it once complete.
+fn save_progress(store: &RocksStore, progress: &PeerSyncProgress) {
+    let result = if progress.is_complete() {
+        tracing::info!("Sync: catch-up from peer {} complete", progress.peer_url);
+        PeerSyncProgress::clear(store, &progress.peer_url)
+    } else {
+        progress.save(store)
+    };
+    if let Err(e) = result {
+        tracing::warn!("Sync: failed to persist progress for {}: {}", progress.peer_url, e);
+    }
+}
+
+/// Re-negotiate the pending ranges of a persisted catch-up.
+async fn resume_missing(
+    client: &reqwest::Client,
+    peer_url: &str,
+    local: &RangeIndex,
+    progress: &PeerSyncProgress,
+) -> Result<Vec<Uuid>, String> {
+    let peer = HttpPeer { client, peer_url };
+    let mut missing = Vec::new();
+    for range in progress.pending_ranges() {
+        let range_sync = RangeSync::new(range.start_ms, range.end_ms).map_err(|e| e.to_string())?;
+        let outcome = range_sync
+            .sync_range(local, &peer)
+            .await
+            .map_err(|e| e.to_string())?;
+        missing.extend(outcome.missing);
+    }
+    Ok(missing)
+}
+
+/// Current Unix time in milliseconds.
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Polyps requested per `peer/get_polyps_batch` call.
+const FETCH_BATCH_SIZE: usize = 128;
+
+/// Fetch a batch of polyps, falling back to one request per polyp for
+/// peers without batch support.
+pub(crate) async fn fetch_remote_polyps(client: &reqwest::Client, peer_url: &str, ids: &[Uuid]) -> Vec<Polyp> {
+    match fetch_remote_polyps_batch(client, peer_url, ids).await {
+        Ok(polyps) => polyps,
+        Err(e) => {
+            tracing::debug!(
+                "Sync: batch fetch from {} failed ({}), fetching one by one",
+                peer_url,
+                e
+            );
+            fetch_remote_polyps_each(client, peer_url, ids).await
+        }
+    }
+}
+
+/// Fetch a batch of polyps, compressed, via `peer/get_polyps_batch`.
+async fn fetch_remote_polyps_batch(
+    client: &reqwest::Client,
+    peer_url: &str,
+    ids: &[Uuid],
+) -> Result<Vec<Polyp>, ChitinError> {
+    #[derive(serde::Deserialize)]
+    struct BatchResult {
+        polyps: Vec<Polyp>,
+        compressed: Option<CompressedPolyps>,
+        not_found: Vec<Uuid>,
+    }
+
+    let params = serde_json::json!({ "ids": ids, "compression": Compression::Zstd });
+    let result: BatchResult =
+        call_peer(client, peer_url, "peer/get_polyps_batch", params).await?;
+    for polyp_id in &result.not_found {
+        tracing::debug!(
+            "Sync: polyp {} not found on peer {} (may have been deleted)",
+            polyp_id,
+            peer_url
+        );
+    }
+
+    let mut polyps = result.polyps;
+    if let Some(compressed) = result.compressed {
+        polyps.extend(compressed.decompress()?);
+    }
+    Ok(polyps)
+}
+
+/// Fetch polyps one `polyp/get` at a time, for peers without batch support.
+async fn fetch_remote_polyps_each(
+    client: &reqwest::Client,
+    peer_url: &str,
+    ids: &[Uuid],
+) -> Vec<Polyp> {
+    let mut polyps = Vec::with_capacity(ids.len());
+    for &polyp_id in ids {
+        match fetch_remote_polyp(client, peer_url, polyp_id).await {
+            Ok(Some(polyp)) => polyps.push(polyp),
+            Ok(None) => {
+                tracing::debug!(
+                    "Sync: polyp {} not found on peer {} (may have been deleted)",
+                    polyp_id,
+                    peer_url
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Sync: failed to fetch polyp {} from {}: {}",
+                    polyp_id,
+                    peer_url,
+                    e
+                );
+            }
+        }
+    }
+    polyps
+}
+
+/// Most IDs described per `sync/polyp_meta` request.
+const META_BATCH_SIZE: usize = 1024;
+
+/// Order missing polyps by priority using the peer's metadata. IDs the peer
+/// does not describe (or all of them, if it does not support
+/// `sync/polyp_meta`) are queued last.
+async fn prioritize(
+    client: &reqwest::Client,
+    peer_url: &str,
+    missing: Vec<Uuid>,
+    priority: &SyncPriority,
+) -> SyncQueue {
+    let mut queue = SyncQueue::new();
+    for batch in missing.chunks(META_BATCH_SIZE) {
+        #[derive(serde::Deserialize)]
+        struct MetaResult {
+            polyps: Vec<PolypSyncMeta>,
+        }
+
+        let params = serde_json::json!({ "ids": batch });
+        let described = match call_peer::<MetaResult>(client, peer_url, "sync/polyp_meta", params)
+            .await
+        {
+            Ok(result) => result.polyps,
+            Err(e) => {
+                tracing::debug!("Sync: no polyp metadata from {} ({})", peer_url, e);
+                Vec::new()
+            }
+        };
+
+        let batch_ids: HashSet<&Uuid> = batch.iter().collect();
+        let mut seen = HashSet::new();
+        for meta in described.iter().filter(|m| batch_ids.contains(&m.id)) {
+            if seen.insert(meta.id) {
+                queue.push(meta, priority);
+            }
+        }
+        for id in batch.iter().filter(|id| !seen.contains(*id)) {
+            queue.push_unscored(*id);
+        }
+    }
+    queue
+}
+
+/// Get all local polyp IDs as a HashSet for fast lookup.
+async fn get_local_polyp_ids(store: &Arc<RocksStore>) -> Result<HashSet<Uuid>, String> {
+    let states = [
+        PolypState::Draft,
+        PolypState::Soft,
+        PolypState::UnderReview,
+        PolypState::Approved,
+        PolypState::Hardened,
+        PolypState::Rejected,
+    ];
+
+    let mut ids = HashSet::new();
+    for state in &states {
+        let polyps = store
+            .list_polyps_by_state(state)
+            .await
+            .map_err(|e| format!("Failed to list local polyps: {}", e))?;
+        for p in polyps {
+            ids.insert(p.id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Hex-encoded summaries of the local ID set, built once per sync round.
+#[derive(Clone, Copy)]
+struct LocalSummaries<'a> {
+    estimator: &'a str,
+    vbf: &'a str,
+    ranges: &'a RangeIndex,
+    merkle: &'a RwLock<MerkleSummary>,
+    /// Our shards, when we hold only some; peers then filter to them.
+    shards: Option<&'a ShardSet>,
+}
+
+/// Clock skew tolerated when range-syncing up to "now".
+const RANGE_SYNC_SKEW_MS: u64 = 60_000;
+
+/// Determine which of a peer's polyps are missing locally.
+///
+/// Prefers IBLT reconciliation, then Merkle descent, range sync, and VBF
+/// exchange; compares full ID lists only when the VBF exchange reports
+/// false-positive ambiguity or the peer supports none of them. With
+/// `local.shards`, Merkle descent and range sync (which summarize the whole
+/// set) are skipped and the peer filters the other exchanges to our shards.
+async fn find_missing(
+    client: &reqwest::Client,
+    peer_url: &str,
+    reconciler: &SetReconciler,
+    local: LocalSummaries<'_>,
+    local_ids: &HashSet<Uuid>,
+) -> Result<Vec<Uuid>, String> {
+    let shards = local.shards;
+    match fetch_reconcile(client, peer_url, local.estimator, reconciler.len(), shards).await {
+        Ok(remote_iblt) => match reconciler.reconcile_iblt(&remote_iblt) {
+            Ok(diff) => return Ok(diff.remote_only),
+            Err(e) => {
+                tracing::debug!(
+                    "Sync: IBLT reconciliation with {} did not decode ({}), descending Merkle tree",
+                    peer_url,
+                    e
+                );
+            }
+        },
+        Err(e) => {
+            tracing::debug!(
+                "Sync: IBLT reconciliation with {} failed ({}), descending Merkle tree",
+                peer_url,
+                e
+            );
+        }
+    }
+
+    let peer = HttpPeer { client, peer_url };
+    if shards.is_some() {
+        return find_missing_by_vbf(client, peer_url, reconciler, local, local_ids).await;
+    }
+    match MerkleSync::new().find_missing(local.merkle, &peer).await {
+        Ok(outcome) => {
+            tracing::debug!(
+                "Sync: Merkle descent with {} compared {} nodes in {} round trips",
+                peer_url,
+                outcome.nodes_compared,
+                outcome.round_trips
+            );
+            return Ok(outcome.missing);
+        }
+        Err(e) => {
+            tracing::debug!(
+                "Sync: Merkle descent with {} failed ({}), comparing ranges",
+                peer_url,
+                e
+            );
+        }
+    }
+
+    let range_sync = RangeSync::new(0, now_ms() + RANGE_SYNC_SKEW_MS).map_err(|e| e.to_string())?;
+    match range_sync.sync_range(local.ranges, &peer).await {
+        Ok(outcome) => {
+            tracing::debug!(
+                "Sync: range sync with {} compared {} ranges, listed {}",
+                peer_url,
+                outcome.ranges_compared,
+                outcome.ranges_listed
+            );
+            return Ok(outcome.missing);
+        }
+        Err(e) => {
+            tracing::debug!(
+                "Sync: range sync with {} failed ({}), exchanging VBFs",
+                peer_url,
+                e
+            );
+        }
+    }
+
+    find_missing_by_vbf(client, peer_url, reconciler, local, local_ids).await
+}
+
+/// The VBF exchange and ID list stages of `find_missing`.
+async fn find_missing_by_vbf(
+    client: &reqwest::Client,
+    peer_url: &str,
+    reconciler: &SetReconciler,
+    local: LocalSummaries<'_>,
+    local_ids: &HashSet<Uuid>,
+) -> Result<Vec<Uuid>, String> {
+    let shards = local.shards;
+    match fetch_vbf_exchange(client, peer_url, local.vbf, reconciler.len(), shards).await {
+        Ok((remote_vbf, remote_count, reported)) => {
+            let plan = reconciler.plan_vbf_sync(&remote_vbf, remote_count, reported);
+            if !plan.needs_id_list {
+                return Ok(plan.missing);
+            }
+            tracing::debug!(
+                "Sync: VBF exchange with {} is ambiguous, comparing ID lists",
+                peer_url
+            );
+        }
+        Err(e) => {
+            tracing::debug!(
+                "Sync: VBF exchange with {} failed ({}), comparing ID lists",
+                peer_url,
+                e
+            );
+        }
+    }
+
+    let remote_ids = fetch_remote_polyp_ids(client, peer_url, shards).await?;
+    Ok(remote_ids
+        .into_iter()
+        .filter(|id| !local_ids.contains(id))
+        .collect())
+}
+
+/// A peer reached over JSON-RPC, for Merkle and range comparisons.
+struct HttpPeer<'a> {
+    client: &'a reqwest::Client,
+    peer_url: &'a str,
+}
+
+#[async_trait::async_trait]
+impl MerklePeer for HttpPeer<'_> {
+    async fn nodes(&self, prefixes: &[MerklePrefix]) -> Result<Vec<MerkleNode>, ChitinError> {
+        #[derive(serde::Deserialize)]
+        struct NodesResult {
+            nodes: Vec<MerkleNode>,
+        }
+
+        let params = serde_json::json!({ "prefixes": prefixes });
+        let result: NodesResult =
+            call_peer(self.client, self.peer_url, "sync/merkle/nodes", params).await?;
+        Ok(result.nodes)
+    }
+
+    async fn ids(&self, prefixes: &[MerklePrefix]) -> Result<Vec<Uuid>, ChitinError> {
+        #[derive(serde::Deserialize)]
+        struct IdsResult {
+            ids: Vec<Uuid>,
+        }
+
+        let params = serde_json::json!({ "prefixes": prefixes });
+        let result: IdsResult =
+            call_peer(self.client, self.peer_url, "sync/merkle/ids", params).await?;
+        Ok(result.ids)
+    }
+}
+
+#[async_trait::async_trait]
+impl RangePeer for HttpPeer<'_> {
+    async fn summarize(&self, ranges: &[TimeRange]) -> Result<Vec<RangeSummary>, ChitinError> {
+        #[derive(serde::Deserialize)]
+        struct SummaryResult {
+            summaries: Vec<RangeSummary>,
+        }
+
+        let params = serde_json::json!({ "ranges": ranges });
+        let result: SummaryResult =
+            call_peer(self.client, self.peer_url, "sync/range/summary", params).await?;
+        Ok(result.summaries)
+    }
+
+    async fn list_ids(
+        &self,
+        range: TimeRange,
+        after: Option<Uuid>,
+        limit: usize,
+    ) -> Result<IdPage, ChitinError> {
+        let params = serde_json::json!({ "range": range, "after": after, "limit": limit });
+        call_peer(self.client, self.peer_url, "sync/range/ids", params).await
+    }
+}
+
+/// Ask a peer which shards it holds and record the answer. Returns `true`
+/// unless the peer is known to hold none of `shards`.
+async fn refresh_peer_shards(registry: &PeerRegistry, peer_url: &str, shards: &ShardSet) -> bool {
+    #[derive(serde::Deserialize)]
+    struct ShardsResult {
+        shards: ShardSet,
+    }
+
+    let params = serde_json::json!({});
+    match call_peer::<ShardsResult>(registry.http_client(), peer_url, "peer/shards", params).await {
+        Ok(result) => {
+            let remote = result.shards;
+            let overlaps = remote.num_shards() != shards.num_shards()
+                || shards.shards().iter().any(|&s| remote.contains_shard(s));
+            registry.set_peer_shards(peer_url, remote).await;
+            overlaps
+        }
+        // Peers predating shard subscriptions hold everything.
+        Err(_) => true,
+    }
+}
+
+/// Call a JSON-RPC method on a peer and decode its result.
+pub(crate) async fn call_peer<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    peer_url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, ChitinError> {
+    let request_body = serde_json::json!({ "method": method, "params": params });
+
+    let rpc_resp: JsonRpcResponse = client
+        .post(peer_url)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| ChitinError::Network(format!("Failed to parse response: {}", e)))?;
+
+    if !rpc_resp.success {
+        return Err(ChitinError::Network(
+            rpc_resp.error.unwrap_or_else(|| "Unknown error".to_string()),
+        ));
+    }
+
+    let result = rpc_resp
+        .result
+        .ok_or_else(|| ChitinError::Network("No result in response".to_string()))?;
+    serde_json::from_value(result).map_err(|e| {
+        ChitinError::Serialization(format!("Failed to parse {} result: {}", method, e))
+    })
+}
+
+/// Send our strata estimator to a peer and receive its IBLT, sized for the
+/// estimated difference.
+async fn fetch_reconcile(
+    client: &reqwest::Client,
+    peer_url: &str,
+    local_estimator: &str,
+    local_count: usize,
+    shards: Option<&ShardSet>,
+) -> Result<Iblt, String> {
+    let request_body = serde_json::json!({
+        "method": "sync/reconcile",
+        "params": {
+            "estimator": local_estimator,
+            "count": local_count,
+            "shards": shards
+        }
+    });
+
+    let resp = client
+        .post(peer_url)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP error: {}", e))?;
+
+    let rpc_resp: JsonRpcResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if !rpc_resp.success {
+        return Err(rpc_resp.error.unwrap_or_else(|| "Unknown error".to_string()));
+    }
+
+    let result = rpc_resp.result.ok_or("No result in response")?;
+
+    #[derive(serde::Deserialize)]
+    struct ReconcileResult {
+        iblt: String,
+    }
+
+    let reply: ReconcileResult = serde_json::from_value(result)
+        .map_err(|e| format!("Failed to parse reconcile response: {}", e))?;
+    Iblt::from_hex(&reply.iblt).map_err(|e| e.to_string())
+}
+
+/// Exchange VBFs with a peer. Returns the peer's filter, its ID count, and
+/// the IDs it found absent from our filter.
+async fn fetch_vbf_exchange(
+    client: &reqwest::Client,
+    peer_url: &str,
+    local_vbf: &str,
+    local_count: usize,
+    shards: Option<&ShardSet>,
+) -> Result<(VectorBloomFilter, usize, Vec<Uuid>), String> {
+    let request_body = serde_json::json!({
+        "method": "sync/vbf",
+        "params": {
+            "vbf": local_vbf,
+            "count": local_count,
+            "shards": shards
+        }
+    });
+
+    let resp = client
+        .post(peer_url)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP error: {}", e))?;
+
+    let rpc_resp: JsonRpcResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if !rpc_resp.success {
+        return Err(rpc_resp.error.unwrap_or_else(|| "Unknown error".to_string()));
+    }
+
+    let result = rpc_resp.result.ok_or("No result in response")?;
+
+    #[derive(serde::Deserialize)]
+    struct VbfResult {
+        vbf: String,
+        count: usize,
+        missing: Vec<Uuid>,
+    }
+
+    let reply: VbfResult = serde_json::from_value(result)
+        .map_err(|e| format!("Failed to parse VBF exchange: {}", e))?;
+    let remote_vbf = VectorBloomFilter::from_hex(&reply.vbf).map_err(|e| e.to_string())?;
+
+    Ok((remote_vbf, reply.count, reply.missing))
+}
+
+/// JSON-RPC response envelope for parsing peer responses.
+#[derive(serde::Deserialize)]
+struct JsonRpcResponse {
+    success: bool,
+    result: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+/// Fetch the list of polyp IDs from a remote peer.
+async fn fetch_remote_polyp_ids(
+    client: &reqwest::Client,
+    peer_url: &str,
+    shards: Option<&ShardSet>,
+) -> Result<Vec<Uuid>, String> {
+    let request_body = serde_json::json!({
+        "method": "peer/list_polyp_ids",
+        "params": { "shards": shards }
+    });
+
+    let resp = client
+        .post(peer_url)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP error: {}", e))?;
+
+    let rpc_resp: JsonRpcResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if !rpc_resp.success {
+        return Err(rpc_resp.error.unwrap_or_else(|| "Unknown error".to_string()));
+    }
+
+    let result = rpc_resp.result.ok_or("No result in response")?;
+
+    #[derive(serde::Deserialize)]
+    struct ListResult {
+        ids: Vec<Uuid>,
+    }
+
+    let list: ListResult =
+        serde_json::from_value(result).map_err(|e| format!("Failed to parse ID list: {}", e))?;
+
+    Ok(list.ids)
+}
+
+/// Fetch a single polyp from a remote peer by UUID.
+async fn fetch_remote_polyp(
+    client: &reqwest::Client,
+    peer_url: &str,
+    polyp_id: Uuid,
+) -> Result<Option<Polyp>, String> {
+    let request_body = serde_json::json!({
+        "method": "polyp/get",
+        "params": {
+            "polyp_id": polyp_id
+        }
+    });
+
+    let resp = client
+        .post(peer_url)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP error: {}", e))?;
+
+    let rpc_resp: JsonRpcResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if !rpc_resp.success {
+        return Err(rpc_resp.error.unwrap_or_else(|| "Unknown error".to_string()));
+    }
+
+    let result = rpc_resp.result.ok_or("No result in response")?;
+
+    #[derive(serde::Deserialize)]
+    struct GetResult {
+        polyp: Option<Polyp>,
+        #[allow(dead_code)]
+        found: bool,
+    }
+
+    let get: GetResult =
+        serde_json::from_value(result).map_err(|e| format!("Failed to parse polyp: {}", e))?;
+
+    Ok(get.polyp)
+}
@@ -0,0 +1,175 @@
+// crates/chitin-node/src/systemd.rs
+//
+// systemd integration.
+//
+// Built with the `systemd` feature, the daemon speaks the sd_notify protocol
+// to the socket in `NOTIFY_SOCKET`: `READY=1` once the RPC server accepts
+// connections and the configured peers have been announced to, and
+// `WATCHDOG=1` from the epoch scheduler loop at half the service's
+// `WatchdogSec`. Without the feature, or when not started by systemd, both
+// are no-ops. `--print-systemd-unit` writes a unit file for the current
+// configuration to stdout (`Type=notify` with a watchdog when the feature is
+// built in).
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::DaemonConfig;
+use crate::peers::PeerRegistry;
+
+/// `WatchdogSec` in generated unit files.
+#[cfg(feature = "systemd")]
+const UNIT_WATCHDOG_SECS: u64 = 60;
+
+/// Longest wait for the RPC server to accept connections before reporting
+/// readiness anyway.
+const RPC_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Send `state` to the service manager, if there is one.
+#[cfg(feature = "systemd")]
+fn notify(state: &str) {
+    use std::os::unix::net::UnixDatagram;
+
+    let path = match std::env::var("NOTIFY_SOCKET") {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+    let result = UnixDatagram::unbound().and_then(|socket| match path.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            socket.send_to_addr(state.as_bytes(), &addr)
+        }
+        _ => socket.send_to(state.as_bytes(), &path),
+    });
+    if let Err(e) = result {
+        tracing::warn!("sd_notify {:?} failed: {}", state, e);
+    }
+}
+
+#[cfg(not(feature = "systemd"))]
+fn notify(_state: &str) {}
+
+/// How often to ping the watchdog: half of `WATCHDOG_USEC`, if systemd set
+/// one for this process.
+#[cfg(feature = "systemd")]
+pub fn watchdog_interval() -> Option<Duration> {
+    let pid = std::env::var("WATCHDOG_PID").ok();
+    if pid.is_some_and(|pid| pid.parse() != Ok(std::process::id())) {
+        return None;
+    }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
+
+#[cfg(not(feature = "systemd"))]
+pub fn watchdog_interval() -> Option<Duration> {
+    None
+}
+
+/// Ping the service manager's watchdog.
+pub fn notify_watchdog() {
+    notify("WATCHDOG=1");
+}
+
+/// Announce to `registry`'s peers once the RPC server at `rpc_addr` accepts
+/// connections, then report the node ready.
+///
+/// Nodes without an RPC server or peers pass `None` for them.
+pub async fn announce_and_notify_ready(
+    rpc_addr: Option<String>,
+    registry: Option<Arc<PeerRegistry>>,
+) {
+    if let Some(addr) = &rpc_addr {
+        let listening = tokio::time::timeout(RPC_READY_TIMEOUT, async {
+            while tokio::net::TcpStream::connect(addr.as_str()).await.is_err() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+        if listening.is_err() {
+            tracing::warn!("RPC server at {} is not accepting connections yet", addr);
+        }
+    }
+    if let Some(registry) = registry {
+        registry.announce_to_all().await;
+    }
+    let status = match &rpc_addr {
+        Some(addr) => format!("Serving RPC on {}", addr),
+        None => "Running".to_string(),
+    };
+    notify(&format!("READY=1\nSTATUS={}", status));
+}
+
+/// A unit file starting this daemon with `config_path` and the settings in
+/// `config`, keeping its data in `data_dir`.
+pub fn unit_file(config: &DaemonConfig, config_path: &str, data_dir: &str) -> String {
+    let exe = std::env::current_exe()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| "chitin-daemon".to_string());
+    let mut exec = vec![
+        quote(&exe),
+        "--config".to_string(),
+        quote(config_path),
+        "--node-type".to_string(),
+        quote(&config.node_type),
+    ];
+    if let Some(network) = &config.network {
+        exec.push("--network".to_string());
+        exec.push(quote(network));
+    }
+    if config.archival {
+        exec.push("--archival".to_string());
+    }
+    let description = match &config.network {
+        Some(network) => format!("Chitin Protocol {} node ({})", config.node_type, network),
+        None => format!("Chitin Protocol {} node", config.node_type),
+    };
+
+    let mut unit = format!(
+        "[Unit]\n\
+         Description={}\n\
+         After=network-online.target\n\
+         Wants=network-online.target\n\
+         \n\
+         [Service]\n",
+        description
+    );
+    #[cfg(feature = "systemd")]
+    {
+        unit.push_str("Type=notify\nNotifyAccess=main\n");
+        // Seed nodes run no epoch scheduler to ping the watchdog.
+        if config.node_type != "seed" {
+            unit.push_str(&format!("WatchdogSec={}\n", UNIT_WATCHDOG_SECS));
+        }
+    }
+    #[cfg(not(feature = "systemd"))]
+    unit.push_str("Type=simple\n");
+    if let Some(user) = std::env::var("USER").ok().filter(|user| user != "root") {
+        unit.push_str(&format!("User={}\n", user));
+    }
+    unit.push_str(&format!(
+        "ExecStart={}\n\
+         ExecReload=/bin/kill -HUP $MAINPID\n\
+         Restart=on-failure\n\
+         RestartSec=5\n\
+         ReadWritePaths={}\n\
+         LimitNOFILE=65536\n\
+         \n\
+         [Install]\n\
+         WantedBy=multi-user.target\n",
+        exec.join(" "),
+        quote(data_dir)
+    ));
+    unit
+}
+
+/// `arg` quoted for a unit file if it contains whitespace or quotes.
+fn quote(arg: &str) -> String {
+    if arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
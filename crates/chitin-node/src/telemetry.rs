@@ -0,0 +1,357 @@
+// crates/chitin-node/src/telemetry.rs
+//
+// OpenTelemetry trace export for the Chitin daemon.
+//
+// `OtlpLayer` is a tracing layer installed with the log subscriber at
+// startup. Until `install` is called it ignores spans; once `[otlp]` is
+// enabled, every span that passes the log filter (epoch consensus and
+// scoring runs, sync rounds, RPC dispatches, and anything nested in them)
+// is recorded with its parent, fields, and start and end times, and closed
+// spans are batched to an OTLP/HTTP collector as JSON (`/v1/traces`).
+//
+// Spans are dropped rather than delaying the daemon when the export queue
+// is full.
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use chitin_core::ChitinError;
+
+/// OTLP span kind for spans within the daemon.
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// Settings for OTLP trace export (`[otlp]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpConfig {
+    /// Whether spans are exported.
+    pub enabled: bool,
+    /// OTLP/HTTP traces endpoint of the collector.
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// Seconds between exports.
+    pub export_interval_secs: u64,
+    /// Most spans per export request.
+    pub max_batch_size: usize,
+    /// Closed spans held while waiting for export.
+    pub queue_size: usize,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://127.0.0.1:4318/v1/traces".to_string(),
+            service_name: "chitin-daemon".to_string(),
+            export_interval_secs: 5,
+            max_batch_size: 512,
+            queue_size: 2048,
+        }
+    }
+}
+
+/// A span attribute value.
+#[derive(Debug, Clone)]
+enum AttributeValue {
+    Str(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+/// A span being recorded, kept in the span's extensions.
+#[derive(Debug, Clone)]
+struct SpanData {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    name: &'static str,
+    start_ns: u64,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+/// A closed span awaiting export.
+#[derive(Debug)]
+struct FinishedSpan {
+    data: SpanData,
+    end_ns: u64,
+}
+
+/// Records span fields as attributes.
+struct AttributeVisitor<'a>(&'a mut Vec<(String, AttributeValue)>);
+
+impl AttributeVisitor<'_> {
+    fn set(&mut self, field: &Field, value: AttributeValue) {
+        let name = field.name();
+        match self.0.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.0.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.set(field, AttributeValue::Str(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.set(field, AttributeValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(value) => self.set(field, AttributeValue::Int(value)),
+            Err(_) => self.set(field, AttributeValue::Str(value.to_string())),
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.set(field, AttributeValue::Double(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.set(field, AttributeValue::Bool(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.set(field, AttributeValue::Str(format!("{:?}", value)));
+    }
+}
+
+/// Tracing layer that exports spans over OTLP once installed.
+#[derive(Clone, Default)]
+pub struct OtlpLayer {
+    sender: Arc<OnceLock<mpsc::Sender<FinishedSpan>>>,
+}
+
+impl OtlpLayer {
+    /// Create a layer that records nothing until `install` is called.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start exporting spans as configured by `config`.
+    pub fn install(&self, config: &OtlpConfig) -> Result<(), ChitinError> {
+        if !config.endpoint.starts_with("http://") && !config.endpoint.starts_with("https://") {
+            return Err(ChitinError::InvalidState(format!(
+                "OTLP endpoint must be http(s): {:?}",
+                config.endpoint
+            )));
+        }
+        if config.export_interval_secs == 0 || config.max_batch_size == 0 || config.queue_size == 0
+        {
+            return Err(ChitinError::InvalidState(
+                "export_interval_secs, max_batch_size, and queue_size must be positive".to_string(),
+            ));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+
+        let (tx, rx) = mpsc::channel(config.queue_size);
+        self.sender
+            .set(tx)
+            .map_err(|_| ChitinError::InvalidState("OTLP export already installed".to_string()))?;
+        tokio::spawn(run_exporter(client, config.clone(), rx));
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if self.sender.get().is_none() {
+            return;
+        }
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|data| (data.trace_id, data.span_id))
+        });
+        let (trace_id, parent_span_id) = match parent {
+            Some((trace_id, parent_id)) => (trace_id, Some(parent_id)),
+            None => (*uuid::Uuid::now_v7().as_bytes(), None),
+        };
+        let mut data = SpanData {
+            trace_id,
+            span_id: new_span_id(),
+            parent_span_id,
+            name: attrs.metadata().name(),
+            start_ns: unix_nanos(),
+            attributes: Vec::new(),
+        };
+        attrs.record(&mut AttributeVisitor(&mut data.attributes));
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                values.record(&mut AttributeVisitor(&mut data.attributes));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let sender = match self.sender.get() {
+            Some(sender) => sender,
+            None => return,
+        };
+        let data = match ctx.span(&id) {
+            Some(span) => span.extensions_mut().remove::<SpanData>(),
+            None => None,
+        };
+        if let Some(data) = data {
+            // A full queue drops the span rather than blocking the caller.
+            let _ = sender.try_send(FinishedSpan {
+                data,
+                end_ns: unix_nanos(),
+            });
+        }
+    }
+}
+
+/// Batch closed spans and post them to the collector until the layer is
+/// dropped.
+async fn run_exporter(
+    client: reqwest::Client,
+    config: OtlpConfig,
+    mut rx: mpsc::Receiver<FinishedSpan>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.export_interval_secs));
+    let mut batch = Vec::new();
+    let mut failing = false;
+    loop {
+        tokio::select! {
+            span = rx.recv() => match span {
+                Some(span) => {
+                    batch.push(span);
+                    if batch.len() < config.max_batch_size {
+                        continue;
+                    }
+                }
+                None => break,
+            },
+            _ = interval.tick() => {}
+        }
+        if batch.is_empty() {
+            continue;
+        }
+        let spans = std::mem::take(&mut batch);
+        match export(&client, &config, &spans).await {
+            Ok(()) if failing => {
+                failing = false;
+                tracing::info!("OTLP export to {} recovered", config.endpoint);
+            }
+            Ok(()) => {}
+            Err(e) if !failing => {
+                failing = true;
+                tracing::warn!("OTLP export to {} failed: {}", config.endpoint, e);
+            }
+            Err(e) => tracing::debug!("OTLP export failed: {}", e),
+        }
+    }
+}
+
+/// Post `spans` to the collector as an OTLP/JSON `ExportTraceServiceRequest`.
+async fn export(
+    client: &reqwest::Client,
+    config: &OtlpConfig,
+    spans: &[FinishedSpan],
+) -> Result<(), ChitinError> {
+    let spans: Vec<serde_json::Value> = spans.iter().map(span_json).collect();
+    let body = serde_json::json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attribute_json(
+                    "service.name",
+                    &AttributeValue::Str(config.service_name.clone()),
+                )],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "chitin-daemon", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    });
+    let response = client
+        .post(&config.endpoint)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ChitinError::Network(format!("HTTP error: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(ChitinError::Network(format!(
+            "collector returned {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+fn span_json(span: &FinishedSpan) -> serde_json::Value {
+    let data = &span.data;
+    let attributes: Vec<serde_json::Value> = data
+        .attributes
+        .iter()
+        .map(|(key, value)| attribute_json(key, value))
+        .collect();
+    let mut json = serde_json::json!({
+        "traceId": hex(&data.trace_id),
+        "spanId": hex(&data.span_id),
+        "name": data.name,
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": data.start_ns.to_string(),
+        "endTimeUnixNano": span.end_ns.to_string(),
+        "attributes": attributes,
+    });
+    if let Some(parent) = &data.parent_span_id {
+        json["parentSpanId"] = serde_json::Value::String(hex(parent));
+    }
+    json
+}
+
+fn attribute_json(key: &str, value: &AttributeValue) -> serde_json::Value {
+    let value = match value {
+        AttributeValue::Str(s) => serde_json::json!({ "stringValue": s }),
+        AttributeValue::Int(i) => serde_json::json!({ "intValue": i.to_string() }),
+        AttributeValue::Double(d) => serde_json::json!({ "doubleValue": d }),
+        AttributeValue::Bool(b) => serde_json::json!({ "boolValue": b }),
+    };
+    serde_json::json!({ "key": key, "value": value })
+}
+
+/// A span ID from the random bits of a UUID v7.
+fn new_span_id() -> [u8; 8] {
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&uuid::Uuid::now_v7().as_bytes()[8..]);
+    id
+}
+
+fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
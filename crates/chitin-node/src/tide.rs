@@ -1,4 +1,4 @@
-// crates/chitin-daemon/src/tide.rs
+// crates/chitin-node/src/tide.rs
 //
 // TideNode: Validation and scoring pipeline for the Chitin Protocol.
 //
@@ -6,13 +6,16 @@
 // participate in Yuma-Semantic Consensus, and submit weight vectors.
 //
 // Phase 4: Epoch-event-driven validation pipeline. On Scoring phase,
-// scores polyps and populates weight matrix. On EpochBoundary, triggers
-// consensus runner.
+// scores polyps and populates weight matrix, and queries and scores remote
+// Coral nodes (`validator`). On EpochBoundary, triggers consensus runner.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::sync::broadcast;
+use tracing::Instrument;
 
+use chitin_consensus::dedup;
 use chitin_consensus::epoch::EpochPhase;
 use chitin_consensus::scoring::score_polyp_multi_dimensional;
 use chitin_core::traits::PolypStore;
@@ -22,7 +25,10 @@ use chitin_store::RocksStore;
 use crate::config::DaemonConfig;
 use crate::consensus_runner;
 use crate::epoch_events::EpochEvent;
+use crate::gossip;
 use crate::shared::DaemonSharedState;
+use crate::supervisor::ShutdownSignal;
+use crate::validator::Validator;
 
 /// A Tide Node that validates and scores Polyps.
 pub struct TideNode {
@@ -34,6 +40,8 @@ pub struct TideNode {
     shared: DaemonSharedState,
     /// Polyp store for reading polyps to score.
     store: Arc<RocksStore>,
+    /// Validator of remote Coral nodes, if configured.
+    validator: Option<Validator>,
 }
 
 impl TideNode {
@@ -49,19 +57,53 @@ impl TideNode {
             event_rx,
             shared,
             store,
+            validator: None,
         })
     }
 
+    /// Query and score remote Coral nodes at each Scoring phase.
+    pub fn with_validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator).filter(Validator::enabled);
+        self
+    }
+
+    /// Seed the domain classifier's centroids from already-hardened polyps.
+    async fn bootstrap_domain_centroids(&self) {
+        match self.store.list_polyps_by_state(&PolypState::Hardened).await {
+            Ok(hardened) => {
+                let learned = self
+                    .shared
+                    .domain_classifier
+                    .write()
+                    .await
+                    .bootstrap(&hardened);
+                tracing::info!(
+                    "Domain centroids bootstrapped from {}/{} hardened polyps",
+                    learned,
+                    hardened.len()
+                );
+            }
+            Err(e) => {
+                tracing::warn!("Failed to list hardened polyps for centroid bootstrap: {}", e);
+            }
+        }
+    }
+
     /// Start the Tide Node event loop.
     ///
     /// Listens for epoch events and runs validation/scoring pipelines.
-    pub async fn start(mut self) -> Result<(), Box<dyn std::error::Error>> {
+    pub async fn start(
+        mut self,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Tide node started (epoch-event-driven)");
 
+        self.bootstrap_domain_centroids().await;
+
         loop {
             tokio::select! {
-                _ = tokio::signal::ctrl_c() => {
-                    tracing::info!("Tide node received shutdown signal");
+                _ = shutdown.wait() => {
+                    tracing::info!("Tide node shutting down");
                     break;
                 }
                 event = self.event_rx.recv() => {
@@ -72,6 +114,15 @@ impl TideNode {
                         Ok(EpochEvent::EpochBoundary { epoch, block }) => {
                             self.handle_epoch_boundary(epoch, block).await;
                         }
+                        Ok(EpochEvent::DriftAlarm { epoch, model, mean_cosine_shift, zones }) => {
+                            tracing::warn!(
+                                "Epoch {}: Drift alarm under {}: mean shift {:.4} (zones {:?})",
+                                epoch,
+                                model,
+                                mean_cosine_shift,
+                                zones
+                            );
+                        }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
                             tracing::warn!("Tide node lagged behind {} epoch events", n);
                         }
@@ -91,16 +142,47 @@ impl TideNode {
     async fn handle_phase_change(&self, epoch: u64, phase: EpochPhase, _block: u64) {
         if phase == EpochPhase::Scoring {
             tracing::info!("Epoch {}: Scoring phase — running validation pipeline", epoch);
-            if let Err(e) = self.run_scoring_pipeline(epoch).await {
+            let scoring = self
+                .run_scoring_pipeline(epoch)
+                .instrument(tracing::info_span!("epoch_scoring", epoch))
+                .await;
+            if let Err(e) = scoring {
                 tracing::error!("Scoring pipeline failed: {}", e);
             }
+            // Remote Corals are queried in the background so that slow
+            // peers do not hold up epoch events.
+            if let Some(validator) = self.validator.clone() {
+                let shared = self.shared.clone();
+                let span = tracing::info_span!("coral_validation", epoch);
+                tokio::spawn(
+                    async move {
+                        validator.contribute_to_beacon_logged(&shared, epoch, false).await;
+                        validator.validate_epoch_logged(&shared, epoch).await
+                    }
+                    .instrument(span),
+                );
+            }
+        } else if phase == EpochPhase::Committing {
+            if let Some(validator) = self.validator.clone() {
+                let shared = self.shared.clone();
+                let span = tracing::info_span!("beacon_reveal", epoch);
+                tokio::spawn(
+                    async move { validator.contribute_to_beacon_logged(&shared, epoch, true).await }
+                        .instrument(span),
+                );
+            }
         }
     }
 
     /// Handle an epoch boundary event.
     async fn handle_epoch_boundary(&self, epoch: u64, _block: u64) {
         tracing::info!("Epoch {}: Boundary — triggering consensus", epoch);
-        if let Err(e) = consensus_runner::run_epoch_consensus(&self.shared, &self.store, epoch).await {
+        let started = Instant::now();
+        let result = consensus_runner::run_epoch_consensus(&self.shared, &self.store, epoch)
+            .instrument(tracing::info_span!("epoch_consensus", epoch))
+            .await;
+        self.shared.metrics.observe_consensus(started.elapsed(), result.is_ok());
+        if let Err(e) = result {
             tracing::error!("Consensus runner failed at epoch {}: {}", epoch, e);
         }
     }
@@ -130,11 +212,28 @@ impl TideNode {
 
         // Resize weight matrix: 1 validator, n_corals coral nodes
         {
+            let detector = self.shared.duplicates.read().await;
+            let check_duplicates = detector.config().enabled && !detector.is_empty();
             let mut wm = self.shared.weight_matrix.write().await;
             *wm = chitin_consensus::weights::WeightMatrix::new(1, n_corals);
 
             for (coral_idx, polyp) in all_polyps.iter().enumerate() {
-                let scores = score_polyp_multi_dimensional(polyp);
+                let mut scores = score_polyp_multi_dimensional(polyp);
+                // A near-copy of hardened knowledge adds nothing new.
+                if let Some(duplicate) = check_duplicates.then(|| detector.check(polyp)).flatten()
+                {
+                    tracing::info!(
+                        "Epoch {}: polyp {} nearly duplicates hardened polyp {} ({:.3})",
+                        epoch,
+                        polyp.id,
+                        duplicate.matched_id,
+                        duplicate.similarity
+                    );
+                    if let Err(e) = dedup::save_flag(&self.store, &polyp.id, &duplicate) {
+                        tracing::warn!("Failed to flag duplicate polyp {}: {}", polyp.id, e);
+                    }
+                    dedup::penalize_novelty(&mut scores, &duplicate);
+                }
                 let weight = scores.weighted_score();
                 wm.set(0, coral_idx, weight);
             }
@@ -148,8 +247,15 @@ impl TideNode {
                 let mut updated = polyp.clone();
                 updated.state = PolypState::UnderReview;
                 updated.updated_at = chrono::Utc::now();
-                if let Err(e) = self.store.save_polyp(&updated).await {
-                    tracing::warn!("Failed to transition polyp {} to UnderReview: {}", polyp.id, e);
+                match self.store.save_polyp(&updated).await {
+                    Ok(()) => {
+                        gossip::announce_state_change(&self.shared, &self.store, &updated, epoch)
+                    }
+                    Err(e) => tracing::warn!(
+                        "Failed to transition polyp {} to UnderReview: {}",
+                        polyp.id,
+                        e
+                    ),
                 }
             }
         }
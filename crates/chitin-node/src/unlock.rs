@@ -0,0 +1,283 @@
+// crates/chitin-node/src/unlock.rs
+//
+// Unlocking an encrypted hotkey keystore at daemon startup.
+//
+// `hotkey_path` may hold either a plaintext hex secret or an
+// `EncryptedKeystore` JSON document. For a keystore, the passphrase comes
+// from (in order) `--passphrase-fd`, the `CHITIN_KEYSTORE_PASSPHRASE`
+// environment variable, or an interactive prompt on the controlling
+// terminal when started with `--unlock`. The passphrase and the decrypted
+// secret only ever live in `Zeroizing` buffers, so they are wiped when the
+// daemon drops them at shutdown.
+//
+// Removing an environment variable is only sound while no other thread can
+// read the environment, so the daemon moves the passphrase out of it with
+// `take_env_passphrase` before starting its runtime. Embedders that do not
+// call it leave the variable set; it is then only read.
+
+use std::io::{Read, Write};
+use std::sync::Mutex;
+
+use chitin_core::keystore::{EncryptedKeystore, SecretKey, Zeroizing};
+use chitin_core::ChitinError;
+
+/// Environment variable holding the hotkey keystore passphrase.
+pub const PASSPHRASE_ENV: &str = "CHITIN_KEYSTORE_PASSPHRASE";
+
+/// The passphrase moved out of the environment by `take_env_passphrase`.
+static ENV_PASSPHRASE: Mutex<Option<Result<Zeroizing<String>, ChitinError>>> = Mutex::new(None);
+
+/// Move the passphrase out of `PASSPHRASE_ENV`, if set, so it is not
+/// inherited by child processes. Must be called before any other thread is
+/// started, e.g. first thing in `main` ahead of the async runtime.
+pub fn take_env_passphrase() {
+    if let Some(value) = std::env::var_os(PASSPHRASE_ENV) {
+        std::env::remove_var(PASSPHRASE_ENV);
+        let passphrase = value
+            .into_string()
+            .map(Zeroizing::new)
+            .map_err(|_| ChitinError::Crypto(format!("{} is not valid UTF-8", PASSPHRASE_ENV)));
+        *ENV_PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = Some(passphrase);
+    }
+}
+
+/// Whether a passphrase was given in `PASSPHRASE_ENV`, taken or not.
+fn env_passphrase_given() -> bool {
+    ENV_PASSPHRASE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+        || std::env::var_os(PASSPHRASE_ENV).is_some()
+}
+
+/// Where the keystore passphrase is read from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnlockOptions {
+    /// Prompt on the terminal when no other source is available.
+    pub prompt: bool,
+    /// Read the passphrase from this inherited file descriptor.
+    pub passphrase_fd: Option<i32>,
+}
+
+impl UnlockOptions {
+    /// Read the passphrase from the first available source.
+    ///
+    /// A passphrase taken by `take_env_passphrase` is used once; otherwise
+    /// the environment variable is read but left in place.
+    pub fn passphrase(&self, prompt: &str) -> Result<Zeroizing<String>, ChitinError> {
+        if let Some(fd) = self.passphrase_fd {
+            return read_fd(fd);
+        }
+        if let Some(passphrase) = ENV_PASSPHRASE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        {
+            return passphrase;
+        }
+        if let Some(value) = std::env::var_os(PASSPHRASE_ENV) {
+            return match value.into_string() {
+                Ok(value) => Ok(Zeroizing::new(value)),
+                Err(_) => Err(ChitinError::Crypto(format!(
+                    "{} is not valid UTF-8",
+                    PASSPHRASE_ENV
+                ))),
+            };
+        }
+        if self.prompt {
+            return prompt_hidden(prompt);
+        }
+        Err(ChitinError::InvalidState(format!(
+            "no passphrase given; start with --unlock, --passphrase-fd, or {}",
+            PASSPHRASE_ENV
+        )))
+    }
+}
+
+/// Decrypt the hotkey secret sealed in keystore `contents`.
+pub fn unlock_hotkey(
+    contents: &str,
+    options: &UnlockOptions,
+) -> Result<SecretKey, ChitinError> {
+    let keystore = EncryptedKeystore::from_json(contents)?;
+    let passphrase = options.passphrase("Hotkey passphrase: ")?;
+    keystore.decrypt(passphrase.as_bytes())
+}
+
+/// Replace the plaintext hotkey secret at `path` with an encrypted keystore.
+///
+/// The passphrase is asked for twice when prompting. The keystore is
+/// written to a temporary file (mode 0600 on Unix) and renamed over the
+/// original so the plaintext is never left half-overwritten.
+pub fn encrypt_hotkey_file(path: &str, options: &UnlockOptions) -> Result<(), ChitinError> {
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(path)
+            .map_err(|e| ChitinError::NotFound(format!("{}: {}", path, e)))?,
+    );
+    if EncryptedKeystore::is_keystore(&contents) {
+        return Err(ChitinError::InvalidState(format!(
+            "{} is already an encrypted keystore",
+            path
+        )));
+    }
+    let secret = match decode_secret(contents.trim()) {
+        Some(secret) => secret,
+        None => {
+            return Err(ChitinError::Crypto(format!(
+                "{} does not hold a 32-byte hex secret",
+                path
+            )))
+        }
+    };
+
+    let prompted = options.prompt && options.passphrase_fd.is_none() && !env_passphrase_given();
+    let passphrase = options.passphrase("New hotkey passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(ChitinError::InvalidState("passphrase must not be empty".to_string()));
+    }
+    if prompted {
+        let confirm = prompt_hidden("Repeat passphrase: ")?;
+        if *confirm != *passphrase {
+            return Err(ChitinError::InvalidState("passphrases do not match".to_string()));
+        }
+    }
+
+    let keystore = EncryptedKeystore::encrypt(&secret, passphrase.as_bytes())?;
+    let tmp_path = format!("{}.tmp", path);
+    write_private(&tmp_path, keystore.to_json()?.as_bytes())?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| ChitinError::Storage(format!("rename {}: {}", tmp_path, e)))?;
+    Ok(())
+}
+
+/// Decode a hex-encoded 32-byte secret.
+pub fn decode_secret(hex: &str) -> Option<SecretKey> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut secret = Zeroizing::new([0u8; 32]);
+    for (i, byte) in secret.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(secret)
+}
+
+/// Read the first line from an inherited file descriptor.
+#[cfg(unix)]
+fn read_fd(fd: i32) -> Result<Zeroizing<String>, ChitinError> {
+    use std::os::unix::io::FromRawFd;
+
+    if fd < 0 {
+        return Err(ChitinError::InvalidState(format!("invalid passphrase fd {}", fd)));
+    }
+    // SAFETY: the descriptor was handed to us for this purpose and is not
+    // used elsewhere; the `File` takes ownership and closes it.
+    let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    let mut contents = Zeroizing::new(Vec::new());
+    file.take(4096)
+        .read_to_end(&mut contents)
+        .map_err(|e| ChitinError::InvalidState(format!("read passphrase fd {}: {}", fd, e)))?;
+    first_line(&contents)
+}
+
+#[cfg(not(unix))]
+fn read_fd(_fd: i32) -> Result<Zeroizing<String>, ChitinError> {
+    Err(ChitinError::InvalidState(
+        "--passphrase-fd is only supported on Unix".to_string(),
+    ))
+}
+
+/// Prompt on the controlling terminal with echo disabled.
+#[cfg(unix)]
+fn prompt_hidden(prompt: &str) -> Result<Zeroizing<String>, ChitinError> {
+    use std::os::unix::io::AsRawFd;
+
+    let tty_error = |e: std::io::Error| ChitinError::InvalidState(format!("terminal: {}", e));
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .map_err(tty_error)?;
+    let fd = tty.as_raw_fd();
+
+    // SAFETY: `termios` is plain data and `fd` is an open terminal.
+    let mut original: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+        return Err(tty_error(std::io::Error::last_os_error()));
+    }
+    let mut hidden = original;
+    hidden.c_lflag &= !libc::ECHO;
+    hidden.c_lflag |= libc::ECHONL;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &hidden) } != 0 {
+        return Err(tty_error(std::io::Error::last_os_error()));
+    }
+
+    let result = tty
+        .write_all(prompt.as_bytes())
+        .and_then(|_| tty.flush())
+        .and_then(|_| read_line_unbuffered(&mut tty));
+    // SAFETY: restores the settings read above on the same descriptor.
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+
+    first_line(&result.map_err(tty_error)?)
+}
+
+#[cfg(not(unix))]
+fn prompt_hidden(prompt: &str) -> Result<Zeroizing<String>, ChitinError> {
+    use std::io::{BufRead, BufReader};
+
+    let mut stderr = std::io::stderr();
+    let _ = stderr.write_all(prompt.as_bytes());
+    let _ = stderr.flush();
+    let mut line = Zeroizing::new(Vec::new());
+    BufReader::new(std::io::stdin())
+        .read_until(b'\n', &mut line)
+        .map_err(|e| ChitinError::InvalidState(format!("stdin: {}", e)))?;
+    first_line(&line)
+}
+
+/// Read up to a newline one byte at a time, so no unwiped buffer holds
+/// the passphrase.
+#[cfg(unix)]
+fn read_line_unbuffered(reader: &mut impl Read) -> std::io::Result<Zeroizing<Vec<u8>>> {
+    use chitin_core::keystore::Zeroize;
+
+    let mut line = Zeroizing::new(Vec::with_capacity(4096));
+    let mut byte = [0u8; 1];
+    while line.len() < 4096 {
+        match reader.read(&mut byte)? {
+            0 => break,
+            _ if byte[0] == b'\n' => break,
+            _ => line.push(byte[0]),
+        }
+    }
+    byte.zeroize();
+    Ok(line)
+}
+
+/// The first line of `bytes` without its line ending.
+fn first_line(bytes: &[u8]) -> Result<Zeroizing<String>, ChitinError> {
+    let end = bytes.iter().position(|&b| b == b'\n').unwrap_or(bytes.len());
+    let line = bytes[..end].strip_suffix(b"\r").unwrap_or(&bytes[..end]);
+    match std::str::from_utf8(line) {
+        Ok(line) => Ok(Zeroizing::new(line.to_string())),
+        Err(_) => Err(ChitinError::Crypto("passphrase is not valid UTF-8".to_string())),
+    }
+}
+
+/// Write `contents` to `path`, readable only by the owner on Unix.
+fn write_private(path: &str, contents: &[u8]) -> Result<(), ChitinError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| ChitinError::Storage(format!("{}: {}", path, e)))?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| ChitinError::Storage(format!("{}: {}", path, e)))
+}
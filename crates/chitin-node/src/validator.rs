@@ -0,0 +1,557 @@
+// crates/chitin-node/src/validator.rs
+//
+// Active validation of Coral nodes for Tide nodes.
+//
+// At each Scoring phase the validator:
+//
+// 1. discovers Coral nodes: active Coral and Hybrid nodes in the metagraph
+//    with an http(s) axon address, plus any `[[validator.corals]]` entries
+// 2. fetches each Coral's recent Soft polyps over JSON-RPC (`polyp/list`)
+// 3. scores every polyp with the multi-dimensional scorer and spot-checks
+//    a sample: proof verification, proof hashes against the content and
+//    vector, and the creator's signature
+// 4. weights each Coral by its mean polyp score (zero if any spot check
+//    fails), normalizes, and submits the signed weights to every Coral
+//    queried through `validation/scores`
+//
+// The sample is chosen by hashing the epoch with each polyp ID, so a Coral
+// cannot predict which of its polyps will be checked.
+//
+// With a scoring task assignment configured, the validator scores only the
+// polyps assigned to it for the epoch (see `chitin_consensus::assignment`)
+// and lists them in its submission, each under its Coral's weight; Corals
+// refuse any others.
+//
+// Validators also contribute to the epoch's randomness beacon (see
+// `chitin_consensus::beacon`): a commitment at the start of Scoring and the
+// secret at the start of Committing, recorded locally and sent to every
+// reachable peer through `validation/beacon`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_consensus::assignment::{AssignmentConfig, TaskAssignment};
+use chitin_consensus::beacon;
+use chitin_consensus::scoring::mean_weighted_score;
+use chitin_core::identity::NodeType;
+use chitin_core::keystore::SecretKey;
+use chitin_core::traits::ProofVerifier;
+use chitin_core::{crypto, ChitinError, Polyp};
+use chitin_rpc::handlers::polyp::ListPolypsResponse;
+use chitin_rpc::handlers::validation::{
+    BeaconContributionRequest, BeaconContributionResponse, SubmitScoresRequest,
+    SubmitScoresResponse, WeightEntry,
+};
+use chitin_store::RocksStore;
+use chitin_verify::PlaceholderVerifier;
+
+use crate::replication::SigningGate;
+use crate::shared::DaemonSharedState;
+use crate::sync_loop::call_peer;
+
+/// Settings for active validation of Coral nodes (`[validator]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorConfig {
+    /// Whether Tide nodes query and score Coral nodes each epoch.
+    pub enabled: bool,
+    /// Coral nodes to validate in addition to those in the metagraph; an
+    /// entry replaces the metagraph node with the same UID.
+    pub corals: Vec<CoralEndpoint>,
+    /// Most Soft polyps fetched from each Coral per epoch.
+    pub polyps_per_coral: u32,
+    /// Polyps per Coral whose proofs and signatures are checked.
+    pub spot_checks: usize,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+}
+
+impl Default for ValidatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            corals: Vec::new(),
+            polyps_per_coral: 64,
+            spot_checks: 4,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// A Coral node reachable over JSON-RPC.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoralEndpoint {
+    /// The Coral's network UID.
+    pub uid: u16,
+    /// Its RPC URL (e.g. "http://10.0.0.2:50051").
+    pub url: String,
+}
+
+/// A Coral node to validate this epoch.
+#[derive(Debug, Clone)]
+struct Target {
+    uid: u16,
+    url: String,
+    /// Hotkey from the metagraph; only polyps it created are scored.
+    hotkey: Option<[u8; 32]>,
+}
+
+/// How one Coral node fared this epoch.
+#[derive(Debug, Clone)]
+pub struct CoralScore {
+    /// The Coral's network UID.
+    pub uid: u16,
+    /// Its RPC URL.
+    pub url: String,
+    /// Polyps scored.
+    pub polyp_ids: Vec<Uuid>,
+    /// Mean weighted score of those polyps, zeroed by failed spot checks.
+    pub score: f64,
+    /// Why spot-checked polyps failed, one entry per failure.
+    pub failures: Vec<String>,
+}
+
+/// Queries, scores, and submits weights for Coral nodes.
+#[derive(Clone)]
+pub struct Validator {
+    client: reqwest::Client,
+    config: ValidatorConfig,
+    /// This node's hotkey, excluded from discovery and signing submissions.
+    hotkey: [u8; 32],
+    signing_key: Option<SecretKey>,
+    /// Refuses signing on a standby and for epochs that were already signed.
+    signing_gate: Option<SigningGate>,
+    /// Scoring task assignment, seeded from the hardening checkpoints in
+    /// `store`.
+    task_assignment: Option<AssignmentConfig>,
+    /// Whether to contribute to the randomness beacon, recorded in `store`.
+    beacon: bool,
+    store: Option<Arc<RocksStore>>,
+}
+
+impl Validator {
+    /// Create a validator, validating `config`.
+    pub fn new(config: ValidatorConfig) -> Result<Self, ChitinError> {
+        if config.polyps_per_coral == 0 || config.timeout_secs == 0 {
+            return Err(ChitinError::InvalidState(
+                "polyps_per_coral and timeout_secs must be positive".to_string(),
+            ));
+        }
+        for coral in &config.corals {
+            if !coral.url.starts_with("http://") && !coral.url.starts_with("https://") {
+                return Err(ChitinError::InvalidState(format!(
+                    "Coral {} url must be http(s): {:?}",
+                    coral.uid, coral.url
+                )));
+            }
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            config,
+            hotkey: [0u8; 32],
+            signing_key: None,
+            signing_gate: None,
+            task_assignment: None,
+            beacon: false,
+            store: None,
+        })
+    }
+
+    /// Sign submissions with this node's hotkey.
+    pub fn with_identity(
+        mut self,
+        hotkey: [u8; 32],
+        signing_key: Option<SecretKey>,
+    ) -> Self {
+        self.hotkey = hotkey;
+        self.signing_key = signing_key;
+        self
+    }
+
+    /// Check every submission against `gate` before signing it.
+    pub fn with_signing_gate(mut self, gate: SigningGate) -> Self {
+        self.signing_gate = Some(gate);
+        self
+    }
+
+    /// Score only the polyps assigned to this validator each epoch.
+    pub fn with_task_assignment(
+        mut self,
+        config: AssignmentConfig,
+        store: Arc<RocksStore>,
+    ) -> Self {
+        self.task_assignment = Some(config);
+        self.store = Some(store);
+        self
+    }
+
+    /// Contribute to each epoch's randomness beacon, recording contributions
+    /// in `store`.
+    pub fn with_beacon(mut self, store: Arc<RocksStore>) -> Self {
+        self.beacon = true;
+        self.store = Some(store);
+        self
+    }
+
+    /// This epoch's scoring assignment, if one is configured and the
+    /// metagraph lists validators.
+    async fn assignment(
+        &self,
+        shared: &DaemonSharedState,
+        epoch: u64,
+    ) -> Result<Option<TaskAssignment>, ChitinError> {
+        let (Some(config), Some(store)) = (&self.task_assignment, &self.store) else {
+            return Ok(None);
+        };
+        let metagraph = shared.metagraph_manager.read().await.current().cloned();
+        TaskAssignment::for_epoch(config, store, metagraph.as_ref(), epoch)
+    }
+
+    /// Whether active validation is enabled.
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Coral nodes to validate, by UID.
+    async fn discover(&self, shared: &DaemonSharedState) -> Vec<Target> {
+        let mut targets: BTreeMap<u16, Target> = BTreeMap::new();
+        if let Some(metagraph) = shared.metagraph_manager.read().await.current() {
+            for node in &metagraph.nodes {
+                let produces = matches!(node.node_type, NodeType::Coral | NodeType::Hybrid);
+                let reachable =
+                    node.axon_addr.starts_with("http://") || node.axon_addr.starts_with("https://");
+                if node.active && produces && reachable && node.hotkey != self.hotkey {
+                    targets.insert(
+                        node.uid,
+                        Target {
+                            uid: node.uid,
+                            url: node.axon_addr.clone(),
+                            hotkey: Some(node.hotkey),
+                        },
+                    );
+                }
+            }
+        }
+        for coral in &self.config.corals {
+            targets.insert(
+                coral.uid,
+                Target {
+                    uid: coral.uid,
+                    url: coral.url.clone(),
+                    hotkey: None,
+                },
+            );
+        }
+        targets.into_values().collect()
+    }
+
+    /// Fetch `target`'s recent Soft polyps, newest first.
+    async fn fetch_soft_polyps(&self, target: &Target) -> Result<Vec<Polyp>, ChitinError> {
+        let params = serde_json::json!({
+            "state_filter": "Soft",
+            "limit": self.config.polyps_per_coral,
+            "offset": 0,
+        });
+        let response: ListPolypsResponse =
+            call_peer(&self.client, &target.url, "polyp/list", params).await?;
+        let mut polyps: Vec<Polyp> = response
+            .polyps
+            .into_iter()
+            .filter(|p| {
+                target
+                    .hotkey
+                    .is_none_or(|hotkey| p.subject.provenance.creator.hotkey == hotkey)
+            })
+            .collect();
+        polyps.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+        Ok(polyps)
+    }
+
+    /// Score `target`'s polyps for `epoch`.
+    fn evaluate(&self, target: &Target, polyps: &[Polyp], epoch: u64) -> CoralScore {
+        let failures: Vec<String> = spot_check_sample(polyps, epoch, self.config.spot_checks)
+            .into_iter()
+            .filter_map(|polyp| spot_check(polyp).err().map(|e| format!("{}: {}", polyp.id, e)))
+            .collect();
+        let score = if failures.is_empty() {
+            mean_weighted_score(polyps)
+        } else {
+            0.0
+        };
+        CoralScore {
+            uid: target.uid,
+            url: target.url.clone(),
+            polyp_ids: polyps.iter().map(|p| p.id).collect(),
+            score,
+            failures,
+        }
+    }
+
+    /// Query and score every discovered Coral node, then submit the signed
+    /// weights to each of them. Returns the per-Coral scores.
+    pub async fn validate_epoch(
+        &self,
+        shared: &DaemonSharedState,
+        epoch: u64,
+    ) -> Result<Vec<CoralScore>, ChitinError> {
+        if self.signing_gate.as_ref().is_some_and(|gate| !gate.can_sign()) {
+            tracing::debug!("Epoch {}: Standby; leaving validation to the primary", epoch);
+            return Ok(Vec::new());
+        }
+        let targets = self.discover(shared).await;
+        if targets.is_empty() {
+            return Ok(Vec::new());
+        }
+        let assignment = self.assignment(shared, epoch).await?;
+
+        let mut scores = Vec::new();
+        let mut scored_ids = Vec::new();
+        for target in &targets {
+            match self.fetch_soft_polyps(target).await {
+                Ok(mut polyps) => {
+                    if let Some(assignment) = &assignment {
+                        polyps.retain(|p| assignment.is_assigned(&p.id, &self.hotkey));
+                        if polyps.is_empty() {
+                            tracing::debug!(
+                                "Epoch {}: No polyps of coral {} assigned to this validator",
+                                epoch,
+                                target.uid
+                            );
+                            continue;
+                        }
+                    }
+                    scored_ids.extend(polyps.iter().map(|p| p.id));
+                    scores.push(self.evaluate(target, &polyps, epoch));
+                }
+                Err(e) => tracing::warn!(
+                    "Epoch {}: Failed to fetch polyps from coral {} ({}): {}",
+                    epoch,
+                    target.uid,
+                    target.url,
+                    e
+                ),
+            }
+        }
+        if scores.is_empty() {
+            return Ok(scores);
+        }
+
+        let signing_key = match &self.signing_key {
+            Some(key) => key,
+            None => {
+                return Err(ChitinError::InvalidState(
+                    "No signing key; weights cannot be submitted".to_string(),
+                ))
+            }
+        };
+        if let Some(gate) = &self.signing_gate {
+            gate.authorize_epoch(epoch)?;
+        }
+        let mut request = SubmitScoresRequest {
+            validator_hotkey: String::new(),
+            epoch,
+            weights: normalized_weights(&scores),
+            polyp_ids: scored_ids,
+            signature: String::new(),
+        };
+        request.sign(signing_key, self.hotkey)?;
+
+        let params = serde_json::to_value(&request)?;
+        for score in &scores {
+            let submitted: Result<SubmitScoresResponse, ChitinError> =
+                call_peer(&self.client, &score.url, "validation/scores", params.clone()).await;
+            match submitted {
+                Ok(response) if response.accepted => {}
+                Ok(response) => tracing::warn!(
+                    "Epoch {}: Coral {} rejected weights: {}",
+                    epoch,
+                    score.uid,
+                    response.message
+                ),
+                Err(e) => tracing::warn!(
+                    "Epoch {}: Failed to submit weights to coral {}: {}",
+                    epoch,
+                    score.uid,
+                    e
+                ),
+            }
+        }
+        Ok(scores)
+    }
+
+    /// Peers that receive beacon contributions: active metagraph nodes with
+    /// an http(s) axon address, plus the configured Corals.
+    async fn beacon_peers(&self, shared: &DaemonSharedState) -> Vec<String> {
+        let mut urls: Vec<String> = self.config.corals.iter().map(|c| c.url.clone()).collect();
+        if let Some(metagraph) = shared.metagraph_manager.read().await.current() {
+            urls.extend(
+                metagraph
+                    .nodes
+                    .iter()
+                    .filter(|n| n.active && n.hotkey != self.hotkey)
+                    .filter(|n| {
+                        n.axon_addr.starts_with("http://") || n.axon_addr.starts_with("https://")
+                    })
+                    .map(|n| n.axon_addr.clone()),
+            );
+        }
+        urls.sort();
+        urls.dedup();
+        urls
+    }
+
+    /// Commit to this validator's beacon secret for `epoch` (`reveal`
+    /// false), or reveal it, locally and to every beacon peer.
+    pub async fn contribute_to_beacon(
+        &self,
+        shared: &DaemonSharedState,
+        epoch: u64,
+        reveal: bool,
+    ) -> Result<(), ChitinError> {
+        let (true, Some(store), Some(signing_key)) = (self.beacon, &self.store, &self.signing_key)
+        else {
+            return Ok(());
+        };
+        if self.signing_gate.as_ref().is_some_and(|gate| !gate.can_sign()) {
+            return Ok(());
+        }
+        let secret = beacon::derive_secret(signing_key, epoch);
+        let request = if reveal {
+            beacon::reveal(store, epoch, &self.hotkey, secret)?;
+            BeaconContributionRequest::reveal(epoch, &secret, signing_key, self.hotkey)?
+        } else {
+            let commitment = beacon::commitment(epoch, &self.hotkey, &secret);
+            beacon::commit(store, epoch, &self.hotkey, commitment)?;
+            BeaconContributionRequest::commit(epoch, &secret, signing_key, self.hotkey)?
+        };
+
+        let params = serde_json::to_value(&request)?;
+        for url in self.beacon_peers(shared).await {
+            let sent: Result<BeaconContributionResponse, ChitinError> =
+                call_peer(&self.client, &url, "validation/beacon", params.clone()).await;
+            match sent {
+                Ok(response) if response.accepted => {}
+                Ok(response) => tracing::debug!(
+                    "Epoch {}: {} rejected beacon contribution: {}",
+                    epoch,
+                    url,
+                    response.message
+                ),
+                Err(e) => tracing::debug!(
+                    "Epoch {}: Failed to send beacon contribution to {}: {}",
+                    epoch,
+                    url,
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Run `contribute_to_beacon`, logging failures.
+    pub async fn contribute_to_beacon_logged(
+        &self,
+        shared: &DaemonSharedState,
+        epoch: u64,
+        reveal: bool,
+    ) {
+        let action = if reveal { "reveal" } else { "commitment" };
+        if let Err(e) = self.contribute_to_beacon(shared, epoch, reveal).await {
+            tracing::warn!("Epoch {}: Beacon {} failed: {}", epoch, action, e);
+        }
+    }
+
+    /// Run `validate_epoch`, logging the outcome.
+    pub async fn validate_epoch_logged(&self, shared: &DaemonSharedState, epoch: u64) {
+        match self.validate_epoch(shared, epoch).await {
+            Ok(scores) if scores.is_empty() => {
+                tracing::debug!("Epoch {}: No coral nodes to validate", epoch);
+            }
+            Ok(scores) => {
+                for score in scores.iter().filter(|s| !s.failures.is_empty()) {
+                    tracing::warn!(
+                        "Epoch {}: Coral {} failed {} spot checks: {}",
+                        epoch,
+                        score.uid,
+                        score.failures.len(),
+                        score.failures.join("; ")
+                    );
+                }
+                let polyps: usize = scores.iter().map(|s| s.polyp_ids.len()).sum();
+                tracing::info!(
+                    "Epoch {}: Validated {} coral nodes ({} polyps)",
+                    epoch,
+                    scores.len(),
+                    polyps
+                );
+            }
+            Err(e) => tracing::warn!("Epoch {}: Coral validation failed: {}", epoch, e),
+        }
+    }
+}
+
+/// Up to `count` of `polyps`, chosen by hashing `epoch` with each polyp ID.
+fn spot_check_sample(polyps: &[Polyp], epoch: u64, count: usize) -> Vec<&Polyp> {
+    let mut keyed: Vec<([u8; 32], &Polyp)> = polyps
+        .iter()
+        .map(|p| {
+            let mut seed = epoch.to_le_bytes().to_vec();
+            seed.extend_from_slice(p.id.as_bytes());
+            (crypto::hash_bytes(&seed), p)
+        })
+        .collect();
+    keyed.sort_by_key(|(key, _)| *key);
+    keyed.into_iter().take(count).map(|(_, p)| p).collect()
+}
+
+/// Check `polyp`'s proof and signature.
+///
+/// Proof hashes left zeroed (placeholder proofs) are not compared; unsigned
+/// polyps pass the signature check.
+fn spot_check(polyp: &Polyp) -> Result<(), String> {
+    let proof = &polyp.proof;
+    match PlaceholderVerifier::new().verify_proof(proof) {
+        Ok(true) => {}
+        Ok(false) => return Err("proof does not verify".to_string()),
+        Err(e) => return Err(format!("proof verification failed: {}", e)),
+    }
+    let inputs = &proof.public_inputs;
+    if inputs.text_hash != [0u8; 32]
+        && !PlaceholderVerifier::verify_text_hash(proof, &polyp.subject.payload.content)
+    {
+        return Err("text hash does not match content".to_string());
+    }
+    if inputs.vector_hash != [0u8; 32]
+        && !PlaceholderVerifier::verify_vector_hash(proof, &polyp.subject.vector.values)
+    {
+        return Err("vector hash does not match embedding".to_string());
+    }
+    if polyp.signature.is_some() {
+        let creator = &polyp.subject.provenance.creator.hotkey;
+        if !polyp.verify_signature(creator).unwrap_or(false) {
+            return Err("invalid creator signature".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Coral scores as weights summing to 1.0 (all zero if every score is).
+fn normalized_weights(scores: &[CoralScore]) -> Vec<WeightEntry> {
+    let total: f64 = scores.iter().map(|s| s.score).sum();
+    scores
+        .iter()
+        .map(|s| WeightEntry {
+            coral_uid: s.uid,
+            weight: if total > 0.0 { s.score / total } else { 0.0 },
+            polyp_ids: s.polyp_ids.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,407 @@
+// crates/chitin-node/src/webhooks.rs
+//
+// Webhook notifications of epoch events for external systems.
+//
+// Each `[[webhooks.endpoints]]` entry receives a JSON POST for the events it
+// subscribes to: an epoch's consensus finalizing, a polyp hardening, a
+// validator being flagged for slashing, drift alarms, and a DID being
+// announced from more than one URL. Bodies are signed
+// with HMAC-SHA256 under the endpoint's secret over `{timestamp}.{body}`,
+// sent as `X-Chitin-Signature: sha256=<hex>` alongside `X-Chitin-Timestamp`,
+// `X-Chitin-Event`, and a `X-Chitin-Delivery` ID that stays the same across
+// retries. Failed deliveries (network errors, 429, and 5xx) are retried with
+// exponential backoff. Events are queued and delivered in the background;
+// when the queue is full, new events are dropped.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use chitin_core::ChitinError;
+
+/// Webhook settings (`[webhooks]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Endpoints notified of events.
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Retries after a failed delivery.
+    pub max_retries: u32,
+    /// Delay before the first retry in milliseconds; doubles per retry.
+    pub retry_base_ms: u64,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+    /// Events queued for delivery before new ones are dropped.
+    pub queue_size: usize,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            max_retries: 5,
+            retry_base_ms: 1000,
+            timeout_secs: 10,
+            queue_size: 256,
+        }
+    }
+}
+
+/// One webhook receiver (`[[webhooks.endpoints]]` entry).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// URL events are POSTed to.
+    pub url: String,
+    /// Environment variable holding the HMAC signing secret. Unsigned if
+    /// unset.
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    /// Event names delivered (see `WebhookEvent::name`); all if empty.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// An event delivered to webhooks, serialized with its name under `event`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// An epoch boundary was processed, with or without consensus.
+    EpochFinalized {
+        /// Consensus epoch.
+        epoch: u64,
+        /// Whether consensus ran; false when no weights were submitted, in
+        /// which case the counts below are zero.
+        consensus_ran: bool,
+        /// Validators whose weights were counted.
+        validators: usize,
+        /// Coral nodes scored.
+        corals: usize,
+        /// Polyps approved.
+        approved: usize,
+    },
+    /// A polyp was hardened.
+    PolypHardened {
+        /// Consensus epoch.
+        epoch: u64,
+        /// The hardened polyp.
+        polyp_id: Uuid,
+        /// IPFS CID of the hardened polyp.
+        cid: String,
+        /// Root of the epoch's hardening tree (hex).
+        merkle_root: String,
+    },
+    /// A validator was flagged for slashing.
+    ValidatorSlashed {
+        /// Epoch in which it was flagged.
+        epoch: u64,
+        /// The validator's UID.
+        uid: u16,
+        /// Slash condition, e.g. "sybil_collusion".
+        condition: String,
+        /// Proposed penalty in rao.
+        penalty_rao: u64,
+    },
+    /// Drift crossed the alarm threshold.
+    DriftAlarm {
+        /// Epoch in which the threshold was crossed.
+        epoch: u64,
+        /// The active model polyps were re-embedded with.
+        model: String,
+        /// Mean cosine shift across all samples this epoch.
+        mean_cosine_shift: f64,
+        /// Reef Zones at or above the threshold (`None` for unzoned polyps).
+        zones: Vec<Option<String>>,
+    },
+    /// A DID was first seen announced from more than one URL.
+    IdentityConflict {
+        /// The contested DID.
+        did: String,
+        /// Whether the DID is this node's own.
+        local: bool,
+        /// The URLs announcing it.
+        urls: Vec<String>,
+        /// The URL with the most recent signed announcement, if any.
+        preferred: Option<String>,
+    },
+}
+
+impl WebhookEvent {
+    /// The event's name, as in `X-Chitin-Event` and endpoint `events` lists.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EpochFinalized { .. } => "epoch_finalized",
+            Self::PolypHardened { .. } => "polyp_hardened",
+            Self::ValidatorSlashed { .. } => "validator_slashed",
+            Self::DriftAlarm { .. } => "drift_alarm",
+            Self::IdentityConflict { .. } => "identity_conflict",
+        }
+    }
+}
+
+/// Every event name.
+const EVENT_NAMES: [&str; 5] = [
+    "epoch_finalized",
+    "polyp_hardened",
+    "validator_slashed",
+    "drift_alarm",
+    "identity_conflict",
+];
+
+/// An endpoint with its secret resolved.
+struct Target {
+    url: String,
+    secret: Option<Vec<u8>>,
+    events: Vec<String>,
+}
+
+impl Target {
+    fn wants(&self, event: &WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.iter().any(|name| name == event.name())
+    }
+}
+
+/// Queues events for delivery to the configured webhooks.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    queue: mpsc::Sender<WebhookEvent>,
+}
+
+impl WebhookNotifier {
+    /// Start delivering to `config`'s endpoints, or `None` if there are
+    /// none. Fails on unknown event names and unset secret variables.
+    pub fn start(config: &WebhookConfig) -> Result<Option<Self>, ChitinError> {
+        if config.endpoints.is_empty() {
+            return Ok(None);
+        }
+        let mut targets = Vec::new();
+        for endpoint in &config.endpoints {
+            let unknown = endpoint.events.iter().find(|n| !EVENT_NAMES.contains(&n.as_str()));
+            if let Some(name) = unknown {
+                return Err(ChitinError::InvalidState(format!(
+                    "unknown webhook event {:?} for {}",
+                    name, endpoint.url
+                )));
+            }
+            let secret = match &endpoint.secret_env {
+                Some(var) => Some(
+                    std::env::var(var)
+                        .map_err(|_| ChitinError::InvalidState(format!("{} is not set", var)))?
+                        .into_bytes(),
+                ),
+                None => None,
+            };
+            targets.push(Target {
+                url: endpoint.url.clone(),
+                secret,
+                events: endpoint.events.clone(),
+            });
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| ChitinError::Network(format!("HTTP client: {}", e)))?;
+
+        let (queue, events) = mpsc::channel(config.queue_size.max(1));
+        tokio::spawn(deliver_all(events, targets, client, config.clone()));
+        Ok(Some(Self { queue }))
+    }
+
+    /// Queue `event` for delivery.
+    pub fn notify(&self, event: WebhookEvent) {
+        if let Err(e) = self.queue.try_send(event) {
+            tracing::warn!("Webhook queue full; dropping {} event", e.into_inner().name());
+        }
+    }
+}
+
+/// Deliver queued events to every target that wants them.
+async fn deliver_all(
+    mut events: mpsc::Receiver<WebhookEvent>,
+    targets: Vec<Target>,
+    client: reqwest::Client,
+    config: WebhookConfig,
+) {
+    let targets: Vec<Arc<Target>> = targets.into_iter().map(Into::into).collect();
+    while let Some(event) = events.recv().await {
+        let body = match serde_json::to_vec(&event) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize {} webhook event: {}", event.name(), e);
+                continue;
+            }
+        };
+        for target in targets.iter().filter(|t| t.wants(&event)) {
+            tokio::spawn(deliver(
+                client.clone(),
+                target.clone(),
+                event.name(),
+                body.clone(),
+                config.max_retries,
+                config.retry_base_ms,
+            ));
+        }
+    }
+}
+
+/// POST `body` to `target`, retrying transient failures.
+async fn deliver(
+    client: reqwest::Client,
+    target: Arc<Target>,
+    event: &'static str,
+    body: Vec<u8>,
+    max_retries: u32,
+    retry_base_ms: u64,
+) {
+    let delivery = Uuid::now_v7().to_string();
+    for attempt in 0..=max_retries {
+        if attempt > 0 {
+            let delay = retry_base_ms.saturating_mul(1 << (attempt - 1).min(16));
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+        let timestamp = Utc::now().timestamp().to_string();
+        let mut request = client
+            .post(&target.url)
+            .header("Content-Type", "application/json")
+            .header("X-Chitin-Event", event)
+            .header("X-Chitin-Delivery", &delivery)
+            .header("X-Chitin-Timestamp", &timestamp);
+        if let Some(secret) = &target.secret {
+            request = request.header("X-Chitin-Signature", signature(secret, &timestamp, &body));
+        }
+        match request.body(body.clone()).send().await {
+            Ok(response) if response.status().is_success() => {
+                tracing::debug!("Delivered {} webhook to {}", event, target.url);
+                return;
+            }
+            Ok(response) => {
+                let status = response.status();
+                let retryable = status.is_server_error() || status.as_u16() == 429;
+                tracing::warn!("{} webhook to {} returned {}", event, target.url, status);
+                if !retryable {
+                    return;
+                }
+            }
+            Err(e) => tracing::warn!("{} webhook to {} failed: {}", event, target.url, e),
+        }
+    }
+    tracing::error!(
+        "Giving up on {} webhook to {} after {} attempts",
+        event,
+        target.url,
+        max_retries + 1
+    );
+}
+
+/// `sha256=<hex>` HMAC of `{timestamp}.{body}` under `secret`.
+fn signature(secret: &[u8], timestamp: &str, body: &[u8]) -> String {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn events() -> Vec<WebhookEvent> {
+        vec![
+            WebhookEvent::EpochFinalized {
+                epoch: 7,
+                consensus_ran: true,
+                validators: 3,
+                corals: 12,
+                approved: 40,
+            },
+            WebhookEvent::PolypHardened {
+                epoch: 7,
+                polyp_id: Uuid::nil(),
+                cid: "bafy".to_string(),
+                merkle_root: "00".to_string(),
+            },
+            WebhookEvent::ValidatorSlashed {
+                epoch: 7,
+                uid: 2,
+                condition: "sybil_collusion".to_string(),
+                penalty_rao: 1_000,
+            },
+            WebhookEvent::DriftAlarm {
+                epoch: 7,
+                model: "openai/text-embedding-3-small".to_string(),
+                mean_cosine_shift: 0.25,
+                zones: vec![Some("code".to_string()), None],
+            },
+            WebhookEvent::IdentityConflict {
+                did: "did:chitin:a".to_string(),
+                local: false,
+                urls: vec!["http://a".to_string(), "http://b".to_string()],
+                preferred: None,
+            },
+        ]
+    }
+
+    fn target(events: &[&str]) -> Target {
+        Target {
+            url: "http://hook".to_string(),
+            secret: None,
+            events: events.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_signature_is_hmac_of_timestamp_and_body() {
+        // Checked against `openssl dgst -sha256 -hmac whsec`.
+        let body = br#"{"event":"epoch_finalized","epoch":7}"#;
+        assert_eq!(
+            signature(b"whsec", "1700000000", body),
+            "sha256=b9c9fd29b2fffab7c3485d1b7b02d9b7318f634ebfe36e8a4dfb0594535cbdfb"
+        );
+        assert_ne!(
+            signature(b"whsec", "1700000001", body),
+            signature(b"whsec", "1700000000", body)
+        );
+    }
+
+    #[test]
+    fn test_events_serialize_with_their_name() {
+        assert_eq!(
+            serde_json::to_value(&events()[0]).unwrap(),
+            json!({
+                "event": "epoch_finalized",
+                "epoch": 7,
+                "consensus_ran": true,
+                "validators": 3,
+                "corals": 12,
+                "approved": 40,
+            })
+        );
+        let names: Vec<&str> = events().iter().map(WebhookEvent::name).collect();
+        assert_eq!(names, EVENT_NAMES);
+        for event in events() {
+            assert_eq!(serde_json::to_value(&event).unwrap()["event"], event.name());
+        }
+    }
+
+    #[test]
+    fn test_targets_want_their_subscribed_events() {
+        let all = target(&[]);
+        let slashes = target(&["validator_slashed", "drift_alarm"]);
+        for event in events() {
+            assert!(all.wants(&event));
+            let subscribed = matches!(
+                event,
+                WebhookEvent::ValidatorSlashed { .. } | WebhookEvent::DriftAlarm { .. }
+            );
+            assert_eq!(slashes.wants(&event), subscribed);
+        }
+    }
+}
@@ -0,0 +1,656 @@
+// crates/chitin-node/tests/embedded_node.rs
+//
+// Tests for running nodes in-process with `NodeBuilder`: a node starts over a
+// store the test opened, answers RPC methods through `NodeHandle::call`
+// without listening, bootstraps from another node's snapshot, and stops
+// cleanly.
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use chitin_consensus::moderation::ModerationStage;
+use chitin_core::crypto::Keypair;
+use chitin_core::identity::{NodeIdentity, NodeType};
+use chitin_core::keystore::SecretKey;
+use chitin_core::traits::PolypStore;
+use chitin_core::PolypState;
+use chitin_economics::{Ledger, StakeManager, Transfer, RAO_PER_CTN, TRANSFER_FEE_RAO};
+use chitin_node::NodeBuilder;
+use chitin_reputation::taxonomy::{ZoneDefinition, ZonePolicy};
+use chitin_rpc::handlers::admin::{
+    BackupRequest, BackupResponse, SnapshotRequest, SnapshotResponse,
+};
+use chitin_rpc::handlers::polyp::{
+    GetPolypRequest, GetPolypResponse, GetPolypStateRequest, GetPolypStateResponse,
+    ImportPolypsRequest, ImportPolypsResponse, ListPolypsResponse, SubmitPolypRequest,
+    SubmitPolypResponse, VerifyProvenanceRequest, VerifyProvenanceResponse,
+};
+use chitin_rpc::handlers::wallet::{
+    GetBalanceRequest, GetBalanceResponse, TransferRequest, TransferResponse,
+};
+use chitin_store::RocksStore;
+
+/// Create a temporary directory path using UUID to avoid conflicts.
+fn temp_dir_path(label: &str) -> String {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("chitin_test_{}_{}", label, Uuid::now_v7()));
+    path.to_string_lossy().to_string()
+}
+
+fn submit_request(content: &str) -> SubmitPolypRequest {
+    SubmitPolypRequest {
+        content: content.to_string(),
+        content_type: "text/plain".to_string(),
+        language: Some("en".to_string()),
+        vector: None,
+        model_id: None,
+        source_url: None,
+        source_title: None,
+        license: None,
+        reef_zone: None,
+        pipeline: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_coral_node_serves_calls_in_process() {
+    let data_dir = temp_dir_path("embedded_coral");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let store = Arc::new(RocksStore::open(&format!("{}/rocksdb", data_dir)).unwrap());
+
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .with_store(store.clone())
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    assert_eq!(node.node_type(), "coral");
+
+    let submitted: SubmitPolypResponse = node
+        .call(
+            "polyp/submit",
+            submit_request("Coral reefs are built by polyps."),
+        )
+        .await
+        .unwrap();
+    let fetched: GetPolypResponse = node
+        .call(
+            "polyp/get",
+            GetPolypRequest {
+                polyp_id: submitted.polyp_id,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(fetched.found);
+
+    // Submission seals the pipeline, so its hash chain is checked.
+    let verified: VerifyProvenanceResponse = node
+        .call(
+            "polyp/verify_provenance",
+            VerifyProvenanceRequest {
+                polyp_id: submitted.polyp_id,
+                resolve_cids: false,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(verified.valid);
+    let report = verified.report.unwrap();
+    let pipeline = report.checks.iter().find(|c| c.name == "pipeline").unwrap();
+    assert_eq!(pipeline.status, chitin_core::provenance::CheckStatus::Pass);
+
+    // The polyp is in the store the test passed in.
+    assert!(store
+        .get_polyp(&submitted.polyp_id)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Jobs run under the supervisor, but no RPC listener does.
+    let tasks = node.tasks();
+    assert!(tasks.tasks.iter().any(|task| task.name == "scheduler"));
+    assert!(!tasks.tasks.iter().any(|task| task.name == "rpc_server"));
+
+    let unknown: Result<serde_json::Value, _> =
+        node.call("no/such_method", serde_json::json!({})).await;
+    assert!(unknown.is_err());
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_polyps_move_between_nodes_by_export_and_import() {
+    // Only signed polyps are imported.
+    let hotkey = [0x3c; 32];
+    let public = Keypair::from_secret_bytes(&hotkey).public_key_bytes();
+    let identity = NodeIdentity::from_keypairs(public, [0xc1; 32], NodeType::Coral);
+
+    let source_dir = temp_dir_path("embedded_export");
+    let target_dir = temp_dir_path("embedded_import");
+    let source = NodeBuilder::coral()
+        .with_data_dir(&source_dir)
+        .with_identity(identity, Some(SecretKey::new(hotkey)))
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let target = NodeBuilder::coral()
+        .with_data_dir(&target_dir)
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+
+    let submitted: SubmitPolypResponse = source
+        .call("polyp/submit", submit_request("Polyps secrete aragonite."))
+        .await
+        .unwrap();
+    let exported: ListPolypsResponse = source
+        .call(
+            "polyp/list",
+            serde_json::json!({ "state_filter": "Draft", "limit": 10, "offset": 0 }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(exported.total, 1);
+
+    // A claimed state the target cannot verify is not kept.
+    let mut claimed = exported.polyps.clone();
+    claimed[0].state = PolypState::Approved;
+    let request = ImportPolypsRequest { polyps: claimed };
+    let first: ImportPolypsResponse = target
+        .call("admin/polyp/import", request.clone())
+        .await
+        .unwrap();
+    assert!(first.results[0].imported);
+    let fetched: GetPolypResponse = target
+        .call(
+            "polyp/get",
+            GetPolypRequest {
+                polyp_id: submitted.polyp_id,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(fetched.polyp.unwrap().state, PolypState::Soft);
+
+    // Importing again is a no-op.
+    let again: ImportPolypsResponse = target
+        .call("admin/polyp/import", request)
+        .await
+        .unwrap();
+    assert!(again.results[0].duplicate && !again.results[0].imported);
+
+    // A copy signed by someone other than its creator is refused.
+    let mut forged = exported.polyps[0].clone();
+    forged.id = Uuid::now_v7();
+    forged.sign(&[7u8; 32]).unwrap();
+    let refused: ImportPolypsResponse = target
+        .call("admin/polyp/import", ImportPolypsRequest { polyps: vec![forged] })
+        .await
+        .unwrap();
+    assert!(!refused.results[0].imported);
+    assert!(refused.results[0].error.is_some());
+
+    // So is an unsigned one.
+    let mut unsigned = exported.polyps[0].clone();
+    unsigned.id = Uuid::now_v7();
+    unsigned.signature = None;
+    let refused: ImportPolypsResponse = target
+        .call("admin/polyp/import", ImportPolypsRequest { polyps: vec![unsigned] })
+        .await
+        .unwrap();
+    assert!(!refused.results[0].imported);
+
+    source.stop().await.unwrap();
+    target.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&source_dir);
+    let _ = std::fs::remove_dir_all(&target_dir);
+}
+
+#[tokio::test]
+async fn test_backup_copies_every_database() {
+    let data_dir = temp_dir_path("embedded_backup");
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let _: SubmitPolypResponse = node
+        .call("polyp/submit", submit_request("Zooxanthellae feed the coral."))
+        .await
+        .unwrap();
+
+    let dest = format!("{}/backup", data_dir);
+    let request = BackupRequest {
+        dest: Some(dest.clone()),
+    };
+    let backup: BackupResponse = node.call("admin/backup", request.clone()).await.unwrap();
+    let names: Vec<&str> = backup.databases.iter().map(|db| db.name.as_str()).collect();
+    assert!(names.contains(&"rocksdb") && names.contains(&"hardened_rocksdb"));
+
+    // The copy opens as a store holding the submitted polyp.
+    let copy = RocksStore::open(&format!("{}/rocksdb", dest)).unwrap();
+    assert_eq!(copy.count_polyps_by_state(&PolypState::Draft).unwrap(), 1);
+
+    // An existing destination is never overwritten.
+    let again: Result<BackupResponse, _> = node.call("admin/backup", request).await;
+    assert!(again.is_err());
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_new_node_bootstraps_from_a_signed_snapshot() {
+    let hotkey = [0x5a; 32];
+    let public = Keypair::from_secret_bytes(&hotkey).public_key_bytes();
+    let identity = NodeIdentity::from_keypairs(public, [0xc0; 32], NodeType::Hybrid);
+
+    let source_dir = temp_dir_path("snapshot_source");
+    let source = NodeBuilder::hybrid()
+        .with_data_dir(&source_dir)
+        .with_identity(identity, Some(SecretKey::new(hotkey)))
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    for content in ["Brain corals grow in slow domes.", "Staghorn corals branch quickly."] {
+        let _: SubmitPolypResponse = source
+            .call("polyp/submit", submit_request(content))
+            .await
+            .unwrap();
+    }
+    let path = format!("{}/bootstrap.chitin", source_dir);
+    let request = SnapshotRequest {
+        dest: Some(path.clone()),
+        pin: false,
+    };
+    let snapshot: SnapshotResponse = source.call("admin/snapshot", request).await.unwrap();
+    assert_eq!((snapshot.polyps, snapshot.vectors), (2, 2));
+    assert_eq!(snapshot.signer, hex::encode(public));
+    source.stop().await.unwrap();
+
+    // A node trusting the signer loads the polyps and index before syncing.
+    let target_dir = temp_dir_path("snapshot_target");
+    let target = NodeBuilder::hybrid()
+        .with_data_dir(&target_dir)
+        .configure(|config| {
+            config.snapshot.bootstrap_from = Some(path.clone());
+            config.snapshot.trusted_signers = vec![snapshot.signer.clone()];
+        })
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let store = target.store();
+    assert_eq!(store.count_polyps_by_state(&PolypState::Draft).unwrap(), 2);
+    assert_eq!(target.index().unwrap().len(), 2);
+    target.stop().await.unwrap();
+
+    // One trusting only another signer, or another body hash, refuses it.
+    let untrusted_dir = temp_dir_path("snapshot_untrusted");
+    let untrusted = NodeBuilder::hybrid()
+        .with_data_dir(&untrusted_dir)
+        .configure(|config| {
+            config.snapshot.bootstrap_from = Some(path.clone());
+            config.snapshot.expected_hash = Some(hex::encode([0u8; 32]));
+        })
+        .without_rpc_server()
+        .start()
+        .await;
+    assert!(untrusted.is_err());
+
+    for dir in [&source_dir, &target_dir, &untrusted_dir] {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+#[tokio::test]
+async fn test_resubmitted_hardened_content_is_flagged_as_a_duplicate() {
+    let data_dir = temp_dir_path("embedded_dedup");
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let store = node.store();
+    let content = "Brain corals grow in slow, rounded colonies over centuries.";
+
+    let original: SubmitPolypResponse = node
+        .call("polyp/submit", submit_request(content))
+        .await
+        .unwrap();
+    assert!(original.duplicate_of.is_none());
+    let mut polyp = store.get_polyp(&original.polyp_id).await.unwrap().unwrap();
+    polyp.state = PolypState::Hardened;
+    store.save_polyp(&polyp).await.unwrap();
+    chitin_node::dedup::refresh(node.shared(), &store).await;
+
+    let copy: SubmitPolypResponse = node
+        .call("polyp/submit", submit_request(content))
+        .await
+        .unwrap();
+    let duplicate = copy.duplicate_of.unwrap();
+    assert_eq!(duplicate.matched_id, original.polyp_id);
+    assert!(duplicate.similarity > 0.99);
+
+    // The flag is kept with the polyp.
+    let fetched: GetPolypResponse = node
+        .call(
+            "polyp/get",
+            GetPolypRequest {
+                polyp_id: copy.polyp_id,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(fetched.duplicate_of.unwrap().matched_id, original.polyp_id);
+
+    let distinct: SubmitPolypResponse = node
+        .call(
+            "polyp/submit",
+            submit_request("Staghorn corals branch quickly in shallow, bright water."),
+        )
+        .await
+        .unwrap();
+    assert!(distinct.duplicate_of.is_none());
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_moderation_rejects_blocked_content_at_submission_and_hardening() {
+    let data_dir = temp_dir_path("embedded_moderation");
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .configure(|config| {
+            config.moderation.blocked_keywords = vec!["dynamite fishing".to_string()]
+        })
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let store = node.store();
+
+    let rejected: SubmitPolypResponse = node
+        .call(
+            "polyp/submit",
+            submit_request("Dynamite fishing stuns every fish on a reef."),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rejected.state, "Rejected");
+    assert_eq!(rejected.rejection.unwrap().code, "blocked_keyword");
+    let state: GetPolypStateResponse = node
+        .call(
+            "polyp/state",
+            GetPolypStateRequest {
+                polyp_id: rejected.polyp_id,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(state.state.as_deref(), Some("Rejected"));
+    let rejection = state.rejection.unwrap();
+    assert_eq!(rejection.stage, ModerationStage::Submission);
+    assert_eq!(rejection.policy, "keywords");
+
+    let allowed: SubmitPolypResponse = node
+        .call(
+            "polyp/submit",
+            submit_request("Reef fish shelter among branching corals."),
+        )
+        .await
+        .unwrap();
+    assert_eq!(allowed.state, "Draft");
+    assert!(allowed.rejection.is_none());
+
+    // Content edited after submission is caught before it hardens.
+    let mut edited = store.get_polyp(&allowed.polyp_id).await.unwrap().unwrap();
+    edited.subject.payload.content = "Dynamite fishing is quick.".to_string();
+    let hardenable =
+        chitin_node::moderation::moderate_before_hardening(node.shared(), &store, &[edited], 1)
+            .await;
+    assert!(hardenable.is_empty());
+    let stored = store.get_polyp(&allowed.polyp_id).await.unwrap().unwrap();
+    assert_eq!(stored.state, PolypState::Rejected);
+    let record = chitin_consensus::moderation::load_record(&store, &allowed.polyp_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.stage, ModerationStage::PreHardening);
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_submissions_are_routed_to_the_zone_for_their_language() {
+    let data_dir = temp_dir_path("embedded_language");
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .configure(|config| {
+            config.zones = vec![
+                ZoneDefinition {
+                    id: "science".to_string(),
+                    name: None,
+                    languages: Vec::new(),
+                    policy: ZonePolicy::default(),
+                },
+                ZoneDefinition {
+                    id: "science/es".to_string(),
+                    name: None,
+                    languages: vec!["es".to_string()],
+                    policy: ZonePolicy::default(),
+                },
+            ]
+        })
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+
+    // Declared English, but detected as Spanish.
+    let spanish = "Los arrecifes de coral protegen las costas del oleaje y albergan \
+        a una cuarta parte de todas las especies marinas.";
+    let submitted: SubmitPolypResponse = node
+        .call(
+            "polyp/submit",
+            SubmitPolypRequest {
+                reef_zone: Some("science".to_string()),
+                ..submit_request(spanish)
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(submitted.language.as_deref(), Some("es"));
+    assert_eq!(submitted.reef_zone.as_deref(), Some("science/es"));
+
+    // The Spanish zone refuses English polyps.
+    let english = "Coral reefs protect coastlines from waves and storms, and shelter \
+        a quarter of all marine species.";
+    let refused: Result<SubmitPolypResponse, _> = node
+        .call(
+            "polyp/submit",
+            SubmitPolypRequest {
+                reef_zone: Some("science/es".to_string()),
+                ..submit_request(english)
+            },
+        )
+        .await;
+    assert!(refused.is_err());
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_zone_policies_limit_licenses_and_size() {
+    let data_dir = temp_dir_path("embedded_zone_policy");
+    let node = NodeBuilder::coral()
+        .with_data_dir(&data_dir)
+        .configure(|config| {
+            config.zones = vec![ZoneDefinition {
+                id: "medical".to_string(),
+                name: None,
+                languages: Vec::new(),
+                policy: ZonePolicy {
+                    replication_factor: Some(3),
+                    hardening_threshold: Some(0.6),
+                    allowed_licenses: vec!["CC-BY-4.0".to_string()],
+                    max_polyp_bytes: Some(64),
+                },
+            }];
+            // Inherits the medical policy.
+            config.zones.push(ZoneDefinition {
+                id: "medical/oncology".to_string(),
+                name: None,
+                languages: Vec::new(),
+                policy: ZonePolicy::default(),
+            });
+        })
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let store = node.store();
+    let medical = |content: &str, license: Option<&str>| SubmitPolypRequest {
+        reef_zone: Some("medical/oncology".to_string()),
+        license: license.map(str::to_string),
+        ..submit_request(content)
+    };
+
+    let unlicensed: Result<SubmitPolypResponse, _> = node
+        .call("polyp/submit", medical("Aspirin thins the blood.", None))
+        .await;
+    assert!(unlicensed.is_err());
+    let oversized: Result<SubmitPolypResponse, _> = node
+        .call("polyp/submit", medical(&"dose ".repeat(20), Some("CC-BY-4.0")))
+        .await;
+    assert!(oversized.is_err());
+    let accepted: SubmitPolypResponse = node
+        .call("polyp/submit", medical("Aspirin thins the blood.", Some("cc-by-4.0")))
+        .await
+        .unwrap();
+
+    // A license stripped after submission is caught before it hardens.
+    let mut stripped = store.get_polyp(&accepted.polyp_id).await.unwrap().unwrap();
+    stripped.subject.provenance.source.license = None;
+    let hardenable =
+        chitin_node::moderation::moderate_before_hardening(node.shared(), &store, &[stripped], 1)
+            .await;
+    assert!(hardenable.is_empty());
+    let record = chitin_consensus::moderation::load_record(&store, &accepted.polyp_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.policy, "zone_policy");
+    assert_eq!(record.code, "license_not_allowed");
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_stop_saves_runtime_state() {
+    let data_dir = temp_dir_path("embedded_stop");
+    let node = NodeBuilder::hybrid()
+        .with_data_dir(&data_dir)
+        .configure(|config| config.blocks_per_epoch = 10)
+        .without_rpc_server()
+        .start()
+        .await
+        .unwrap();
+    let store = node.store();
+    assert!(!node.tasks().tasks.is_empty());
+
+    node.stop().await.unwrap();
+    assert!(chitin_node::runtime_state::RuntimeSnapshot::load(&store)
+        .unwrap()
+        .is_some());
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_ledger_survives_a_restart() {
+    let data_dir = temp_dir_path("embedded_ledger");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let store = Arc::new(RocksStore::open(&format!("{}/rocksdb", data_dir)).unwrap());
+    let alice = Keypair::generate();
+    let mut ledger = Ledger::new();
+    ledger
+        .credit(alice.public_key_bytes(), 10 * RAO_PER_CTN)
+        .unwrap();
+    chitin_rpc::handlers::wallet::save_economics(&store, &ledger, &StakeManager::new()).unwrap();
+
+    let transfer = Transfer {
+        from: alice.public_key_bytes(),
+        to: [7u8; 32],
+        amount: RAO_PER_CTN,
+        fee: TRANSFER_FEE_RAO,
+        nonce: 0,
+    };
+    let request = TransferRequest {
+        from_coldkey: hex::encode(transfer.from),
+        to_coldkey: hex::encode(transfer.to),
+        amount_rao: transfer.amount,
+        fee_rao: transfer.fee,
+        nonce: transfer.nonce,
+        signature: hex::encode(alice.sign(&transfer.signing_bytes())),
+    };
+    let start = || {
+        NodeBuilder::coral()
+            .with_data_dir(&data_dir)
+            .with_store(store.clone())
+            .without_rpc_server()
+            .start()
+    };
+
+    let node = start().await.unwrap();
+    let sent: TransferResponse = node.call("wallet/transfer", request.clone()).await.unwrap();
+    assert!(sent.success);
+    node.stop().await.unwrap();
+
+    // The restarted node keeps the balance and nonce, so the same signed
+    // transfer cannot be applied again.
+    let node = start().await.unwrap();
+    let replayed: Result<TransferResponse, _> = node.call("wallet/transfer", request).await;
+    assert!(replayed.is_err());
+    let balance: GetBalanceResponse = node
+        .call(
+            "wallet/balance",
+            GetBalanceRequest {
+                coldkey: hex::encode(alice.public_key_bytes()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(balance.nonce, 1);
+    assert_eq!(balance.balance_rao, 9 * RAO_PER_CTN - TRANSFER_FEE_RAO);
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+#[tokio::test]
+async fn test_tide_node_has_no_rpc_methods() {
+    let data_dir = temp_dir_path("embedded_tide");
+    let node = NodeBuilder::tide()
+        .with_data_dir(&data_dir)
+        .start()
+        .await
+        .unwrap();
+
+    let result: Result<serde_json::Value, _> =
+        node.call("node/health", serde_json::json!({})).await;
+    assert!(result.is_err());
+
+    node.stop().await.unwrap();
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
@@ -0,0 +1,363 @@
+// crates/chitin-py/src/client.rs
+//
+// Blocking JSON-RPC client for a chitin-daemon, exposed to Python as
+// `Client`.
+//
+// Requests use the node's JSON-RPC envelope (`{method, params}` in,
+// `{success, result, error}` out), the same as the CLI's `--rpc` endpoint.
+// Calls release the GIL while waiting on the node, so other Python threads
+// keep running. Batches larger than the node accepts are split across
+// several `polyp/submit_batch` calls.
+
+use std::time::Duration;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::{matrix_from_py, vector_from_py, Polyp, SearchResult, SubmitResult};
+use crate::ChitinError;
+
+/// Endpoint used when none is given (the CLI's default).
+const DEFAULT_RPC: &str = "http://localhost:50051";
+
+/// Most submissions the node accepts per `polyp/submit_batch` call.
+const MAX_SUBMIT_BATCH: usize = 256;
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    method: &'a str,
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    success: bool,
+    result: Option<Value>,
+    error: Option<String>,
+}
+
+/// One Polyp to submit (the node's `SubmitPolypRequest`).
+#[derive(Debug, Clone, Serialize)]
+struct Submission {
+    content: String,
+    content_type: String,
+    language: Option<String>,
+    vector: Option<Vec<f32>>,
+    model_id: Option<String>,
+    source_url: Option<String>,
+    source_title: Option<String>,
+    reef_zone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitResponse {
+    polyp_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitBatchResponse {
+    results: Vec<SubmitResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    results: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetPolypResponse {
+    polyp: Option<chitin_core::Polyp>,
+}
+
+/// Client for a chitin-daemon's JSON-RPC endpoint.
+#[pyclass(module = "chitin_native", frozen)]
+pub struct Client {
+    endpoint: String,
+    http: reqwest::blocking::Client,
+}
+
+impl Client {
+    /// Call `method` and decode its result, raising `ChitinError` if the
+    /// call fails or the node returns an error.
+    fn call<T: DeserializeOwned + Send>(
+        &self,
+        py: Python<'_>,
+        method: &str,
+        params: Value,
+    ) -> PyResult<T> {
+        py.allow_threads(|| self.call_blocking(method, params))
+            .map_err(ChitinError::new_err)
+    }
+
+    fn call_blocking<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, String> {
+        let response: JsonRpcResponse = self
+            .http
+            .post(&self.endpoint)
+            .json(&JsonRpcRequest { method, params })
+            .send()
+            .and_then(|r| r.json())
+            .map_err(|e| format!("{} failed: {}", method, e))?;
+        if !response.success {
+            return Err(response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string()));
+        }
+        let result = response
+            .result
+            .ok_or_else(|| format!("{} returned no result", method))?;
+        serde_json::from_value(result).map_err(|e| format!("Unexpected {} result: {}", method, e))
+    }
+}
+
+#[pymethods]
+impl Client {
+    /// Connect to the node at `endpoint`, waiting at most `timeout` seconds
+    /// per call (default: no limit).
+    #[new]
+    #[pyo3(signature = (endpoint = DEFAULT_RPC, timeout = None))]
+    fn new(endpoint: &str, timeout: Option<f64>) -> PyResult<Self> {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = timeout {
+            let timeout = Duration::try_from_secs_f64(timeout)
+                .map_err(|e| PyValueError::new_err(format!("Invalid timeout: {}", e)))?;
+            builder = builder.timeout(timeout);
+        }
+        let http = builder
+            .build()
+            .map_err(|e| ChitinError::new_err(e.to_string()))?;
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            http,
+        })
+    }
+
+    #[getter]
+    fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Submit one Polyp and return its UUID.
+    ///
+    /// Without `vector`, the node embeds `content` itself.
+    #[pyo3(signature = (
+        content,
+        *,
+        vector = None,
+        model_id = None,
+        content_type = "text/plain",
+        language = None,
+        reef_zone = None,
+        source_url = None,
+        source_title = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn submit(
+        &self,
+        py: Python<'_>,
+        content: String,
+        vector: Option<&Bound<'_, PyAny>>,
+        model_id: Option<String>,
+        content_type: &str,
+        language: Option<String>,
+        reef_zone: Option<String>,
+        source_url: Option<String>,
+        source_title: Option<String>,
+    ) -> PyResult<String> {
+        let submission = Submission {
+            content,
+            content_type: content_type.to_string(),
+            language,
+            vector: vector.map(vector_from_py).transpose()?,
+            model_id,
+            source_url,
+            source_title,
+            reef_zone,
+        };
+        let params =
+            serde_json::to_value(submission).map_err(|e| ChitinError::new_err(e.to_string()))?;
+        let resp: SubmitResponse = self.call(py, "polyp/submit", params)?;
+        Ok(resp.polyp_id)
+    }
+
+    /// Submit many Polyps, one result per content, in order.
+    ///
+    /// `vectors` (a 2-D array or a sequence of vectors) gives one row per
+    /// content. Failed submissions are reported in their result rather than
+    /// raised.
+    #[pyo3(signature = (
+        contents,
+        *,
+        vectors = None,
+        model_id = None,
+        content_type = "text/plain",
+        reef_zone = None,
+        source_url = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn submit_batch(
+        &self,
+        py: Python<'_>,
+        contents: Vec<String>,
+        vectors: Option<&Bound<'_, PyAny>>,
+        model_id: Option<String>,
+        content_type: &str,
+        reef_zone: Option<String>,
+        source_url: Option<String>,
+    ) -> PyResult<Vec<SubmitResult>> {
+        let vectors = match vectors {
+            Some(vectors) => {
+                let rows = matrix_from_py(vectors)?;
+                if rows.len() != contents.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "{} vectors for {} contents",
+                        rows.len(),
+                        contents.len()
+                    )));
+                }
+                rows.into_iter().map(Some).collect()
+            }
+            None => vec![None; contents.len()],
+        };
+        let submissions: Vec<Submission> = contents
+            .into_iter()
+            .zip(vectors)
+            .map(|(content, vector)| Submission {
+                content,
+                content_type: content_type.to_string(),
+                language: None,
+                vector,
+                model_id: model_id.clone(),
+                source_url: source_url.clone(),
+                source_title: None,
+                reef_zone: reef_zone.clone(),
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(submissions.len());
+        for params in batch_params(&submissions) {
+            let resp: SubmitBatchResponse = self.call(py, "polyp/submit_batch", params)?;
+            results.extend(resp.results);
+        }
+        Ok(results)
+    }
+
+    /// Semantic search by `query` text or by `vector`, best results first.
+    #[pyo3(signature = (
+        query = None,
+        *,
+        vector = None,
+        top_k = 10,
+        model_id = None,
+        reef_zone = None,
+        state = None,
+        language = None,
+        hardened_only = None,
+        min_trust = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        py: Python<'_>,
+        query: Option<String>,
+        vector: Option<&Bound<'_, PyAny>>,
+        top_k: u32,
+        model_id: Option<String>,
+        reef_zone: Option<String>,
+        state: Option<String>,
+        language: Option<String>,
+        hardened_only: Option<bool>,
+        min_trust: Option<f64>,
+    ) -> PyResult<Vec<SearchResult>> {
+        if query.is_none() && vector.is_none() {
+            return Err(PyValueError::new_err("search needs a query or a vector"));
+        }
+        let params = serde_json::json!({
+            "query_text": query,
+            "query_vector": vector.map(vector_from_py).transpose()?,
+            "model_id": model_id,
+            "top_k": top_k,
+            "reef_zone": reef_zone,
+            "state": state,
+            "language": language,
+            "hardened_only": hardened_only,
+            "min_trust": min_trust,
+        });
+        let resp: SearchResponse = self.call(py, "query/search", params)?;
+        Ok(resp.results)
+    }
+
+    /// The Polyp with UUID `polyp_id`, or None if the node does not hold it.
+    fn get(&self, py: Python<'_>, polyp_id: &str) -> PyResult<Option<Polyp>> {
+        let polyp_id = uuid::Uuid::parse_str(polyp_id).map_err(|e| {
+            PyValueError::new_err(format!("Invalid Polyp ID {:?}: {}", polyp_id, e))
+        })?;
+        let params = serde_json::json!({ "polyp_id": polyp_id });
+        let resp: GetPolypResponse = self.call(py, "polyp/get", params)?;
+        Ok(resp.polyp.map(|inner| Polyp { inner }))
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_exc_info))]
+    fn __exit__(&self, _exc_info: &Bound<'_, PyTuple>) {}
+
+    fn __repr__(&self) -> String {
+        format!("Client(endpoint={:?})", self.endpoint)
+    }
+}
+
+/// `polyp/submit_batch` params for `submissions`, at most
+/// `MAX_SUBMIT_BATCH` per call.
+fn batch_params(submissions: &[Submission]) -> Vec<Value> {
+    submissions
+        .chunks(MAX_SUBMIT_BATCH)
+        .map(|chunk| serde_json::json!({ "polyps": chunk }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(content: &str) -> Submission {
+        Submission {
+            content: content.to_string(),
+            content_type: "text/plain".to_string(),
+            language: None,
+            vector: Some(vec![0.5, -0.5]),
+            model_id: Some("bge/v2".to_string()),
+            source_url: None,
+            source_title: None,
+            reef_zone: Some("code".to_string()),
+        }
+    }
+
+    #[test]
+    fn batches_are_split_at_the_node_limit() {
+        let submissions: Vec<Submission> = (0..MAX_SUBMIT_BATCH * 2 + 3)
+            .map(|i| submission(&i.to_string()))
+            .collect();
+        let sizes: Vec<usize> = batch_params(&submissions)
+            .iter()
+            .map(|params| params["polyps"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(sizes, vec![MAX_SUBMIT_BATCH, MAX_SUBMIT_BATCH, 3]);
+        assert!(batch_params(&[]).is_empty());
+    }
+
+    #[test]
+    fn submissions_use_the_node_request_fields() {
+        let params = serde_json::to_value(submission("text")).unwrap();
+        assert_eq!(params["content"], "text");
+        assert_eq!(params["model_id"], "bge/v2");
+        assert_eq!(params["reef_zone"], "code");
+        assert_eq!(params["vector"], serde_json::json!([0.5, -0.5]));
+        assert!(params["language"].is_null());
+    }
+}
@@ -0,0 +1,41 @@
+// crates/chitin-py/src/lib.rs
+//
+// chitin-py: Python bindings for the Chitin Protocol.
+//
+// Builds the `chitin_native` extension module (`maturin develop` in this
+// directory), for pushing corpora into and querying the Reef from Python:
+// a `Client` for a chitin-daemon's JSON-RPC endpoint (submit, batch submit,
+// search, get) and the core `Polyp` and `VectorEmbedding` types. Vectors
+// cross the boundary as numpy arrays.
+//
+// The client speaks the same JSON-RPC envelope as the CLI and mirrors only
+// the response fields it exposes, so the module links chitin-core but none
+// of the node's storage stack.
+
+mod client;
+mod types;
+
+use pyo3::prelude::*;
+
+pub use client::Client;
+pub use types::{Polyp, SearchResult, SubmitResult, VectorEmbedding};
+
+pyo3::create_exception!(
+    chitin_native,
+    ChitinError,
+    pyo3::exceptions::PyException,
+    "An RPC call failed or the node returned an error."
+);
+
+/// Python bindings for the Chitin Protocol.
+#[pymodule]
+fn chitin_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Client>()?;
+    m.add_class::<Polyp>()?;
+    m.add_class::<VectorEmbedding>()?;
+    m.add_class::<SearchResult>()?;
+    m.add_class::<SubmitResult>()?;
+    m.add("ChitinError", m.py().get_type::<ChitinError>())?;
+    m.add("HASH_EMBEDDING_MODEL", chitin_core::HASH_EMBEDDING_MODEL)?;
+    Ok(())
+}
@@ -0,0 +1,334 @@
+// crates/chitin-py/src/types.rs
+//
+// Python classes for core types and RPC results.
+//
+// `Polyp` and `VectorEmbedding` wrap the chitin-core types, so
+// `Polyp.to_json()` round-trips with the node and the CLI's exports. Vector
+// values are returned as float32 numpy arrays; vectors passed in may be
+// float32 or float64 arrays, or lists of floats.
+
+use numpy::{PyArray1, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+use serde::Deserialize;
+
+use chitin_core::{EmbeddingModelId, PolypState, HASH_EMBEDDING_MODEL};
+
+// ---------------------------------------------------------------------------
+// VectorEmbedding
+// ---------------------------------------------------------------------------
+
+/// An embedding vector and the model that produced it.
+#[pyclass(module = "chitin_native")]
+#[derive(Debug, Clone)]
+pub struct VectorEmbedding {
+    pub(crate) inner: chitin_core::VectorEmbedding,
+}
+
+#[pymethods]
+impl VectorEmbedding {
+    #[new]
+    #[pyo3(signature = (
+        values,
+        model_id = HASH_EMBEDDING_MODEL,
+        quantization = "float32",
+        normalization = "l2"
+    ))]
+    fn new(
+        values: &Bound<'_, PyAny>,
+        model_id: &str,
+        quantization: &str,
+        normalization: &str,
+    ) -> PyResult<Self> {
+        let values = vector_from_py(values)?;
+        Ok(Self {
+            inner: chitin_core::VectorEmbedding {
+                model_id: EmbeddingModelId::from_key(model_id, values.len() as u32),
+                values,
+                quantization: quantization.to_string(),
+                normalization: normalization.to_string(),
+            },
+        })
+    }
+
+    /// The vector, as a float32 numpy array.
+    #[getter]
+    fn values<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f32>> {
+        PyArray1::from_slice(py, &self.inner.values)
+    }
+
+    /// Space key of the model (e.g., "bge/bge-small-en-v1.5").
+    #[getter]
+    fn model_id(&self) -> String {
+        self.inner.model_id.key()
+    }
+
+    #[getter]
+    fn dimensions(&self) -> usize {
+        self.inner.values.len()
+    }
+
+    #[getter]
+    fn quantization(&self) -> &str {
+        &self.inner.quantization
+    }
+
+    #[getter]
+    fn normalization(&self) -> &str {
+        &self.inner.normalization
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.values.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VectorEmbedding(model_id={:?}, dimensions={})",
+            self.inner.model_id.key(),
+            self.inner.values.len()
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Polyp
+// ---------------------------------------------------------------------------
+
+/// A unit of knowledge in the Reef: content, embedding, provenance, and
+/// lifecycle state.
+#[pyclass(module = "chitin_native")]
+#[derive(Debug, Clone)]
+pub struct Polyp {
+    pub(crate) inner: chitin_core::Polyp,
+}
+
+#[pymethods]
+impl Polyp {
+    /// The Polyp's UUID.
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    /// Lifecycle state (e.g., "Soft", "Hardened", "Molted").
+    #[getter]
+    fn state(&self) -> &'static str {
+        state_name(&self.inner.state)
+    }
+
+    /// UUID of the Polyp this one was molted into, if it was molted.
+    #[getter]
+    fn successor_id(&self) -> Option<String> {
+        match &self.inner.state {
+            PolypState::Molted { successor_id } => Some(successor_id.to_string()),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn content(&self) -> &str {
+        &self.inner.subject.payload.content
+    }
+
+    #[getter]
+    fn content_type(&self) -> &str {
+        &self.inner.subject.payload.content_type
+    }
+
+    #[getter]
+    fn language(&self) -> Option<&str> {
+        self.inner.subject.payload.language.as_deref()
+    }
+
+    /// Reef Zone the Polyp was submitted to, if any.
+    #[getter]
+    fn reef_zone(&self) -> Option<&str> {
+        self.inner.reef_zone.as_deref()
+    }
+
+    #[getter]
+    fn vector(&self) -> VectorEmbedding {
+        VectorEmbedding {
+            inner: self.inner.subject.vector.clone(),
+        }
+    }
+
+    /// DID of the node that created the Polyp.
+    #[getter]
+    fn creator(&self) -> &str {
+        &self.inner.subject.provenance.creator.did
+    }
+
+    #[getter]
+    fn source_url(&self) -> Option<&str> {
+        self.inner.subject.provenance.source.source_url.as_deref()
+    }
+
+    #[getter]
+    fn source_title(&self) -> Option<&str> {
+        self.inner.subject.provenance.source.title.as_deref()
+    }
+
+    /// IPFS CID of the hardened Polyp, if it was hardened.
+    #[getter]
+    fn cid(&self) -> Option<&str> {
+        self.inner.hardening.as_ref().map(|h| h.cid.as_str())
+    }
+
+    /// Creation time, RFC 3339.
+    #[getter]
+    fn created_at(&self) -> String {
+        self.inner.created_at.to_rfc3339()
+    }
+
+    /// Time of the last state transition, RFC 3339.
+    #[getter]
+    fn updated_at(&self) -> String {
+        self.inner.updated_at.to_rfc3339()
+    }
+
+    /// The Polyp as JSON, in the node's format.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Parse a Polyp from the node's JSON format.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json)
+            .map(|inner| Self { inner })
+            .map_err(|e| PyValueError::new_err(format!("Invalid Polyp JSON: {}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Polyp(id={:?}, state={:?}, model_id={:?})",
+            self.inner.id.to_string(),
+            state_name(&self.inner.state),
+            self.inner.subject.vector.model_id.key()
+        )
+    }
+}
+
+/// The name of a lifecycle state, without any fields.
+fn state_name(state: &PolypState) -> &'static str {
+    match state {
+        PolypState::Draft => "Draft",
+        PolypState::Soft => "Soft",
+        PolypState::UnderReview => "UnderReview",
+        PolypState::Approved => "Approved",
+        PolypState::Hardened => "Hardened",
+        PolypState::Rejected => "Rejected",
+        PolypState::Molted { .. } => "Molted",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RPC results
+// ---------------------------------------------------------------------------
+
+/// One result of a semantic search (the node's `SearchResult`).
+#[pyclass(module = "chitin_native", get_all, frozen)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResult {
+    pub polyp_id: String,
+    /// Cosine similarity to the query.
+    pub similarity: f32,
+    /// Ranking score (similarity blended with creator trust).
+    #[serde(default)]
+    pub score: f64,
+    pub content: Option<String>,
+    pub state: String,
+    pub cid: Option<String>,
+    /// Creator's normalized trust in the Polyp's zone, if ranking applied.
+    #[serde(default)]
+    pub creator_trust: Option<f64>,
+    /// Model space the Polyp was found in, if the query named one.
+    #[serde(default)]
+    pub model_id: Option<String>,
+}
+
+#[pymethods]
+impl SearchResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "SearchResult(polyp_id={:?}, similarity={:.4}, score={:.4})",
+            self.polyp_id, self.similarity, self.score
+        )
+    }
+}
+
+/// Outcome of one submission in a batch.
+#[pyclass(module = "chitin_native", get_all, frozen)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubmitResult {
+    /// UUID of the new Polyp, if it was submitted.
+    pub polyp_id: Option<String>,
+    /// Why the submission failed, if it did.
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl SubmitResult {
+    /// True if the Polyp was submitted.
+    #[getter]
+    fn ok(&self) -> bool {
+        self.polyp_id.is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        match (&self.polyp_id, &self.error) {
+            (Some(id), _) => format!("SubmitResult(polyp_id={:?})", id),
+            (None, error) => format!("SubmitResult(error={:?})", error.as_deref().unwrap_or("")),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// numpy interop
+// ---------------------------------------------------------------------------
+
+/// A vector from a list or tuple of floats, or a 1-D float32 or float64
+/// array. Lists are checked first, so they work without numpy installed.
+pub(crate) fn vector_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Vec<f32>> {
+    if is_sequence(obj) {
+        return obj.extract::<Vec<f32>>();
+    }
+    if let Ok(array) = obj.extract::<PyReadonlyArray1<f32>>() {
+        return Ok(array.as_array().iter().copied().collect());
+    }
+    if let Ok(array) = obj.extract::<PyReadonlyArray1<f64>>() {
+        return Ok(array.as_array().iter().map(|&v| v as f32).collect());
+    }
+    Err(PyTypeError::new_err(
+        "expected a 1-D float32 or float64 array, or a list of floats",
+    ))
+}
+
+/// Row vectors from a list or tuple of vectors, or a 2-D float32 or float64
+/// array.
+pub(crate) fn matrix_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<f32>>> {
+    if is_sequence(obj) {
+        return obj.try_iter()?.map(|row| vector_from_py(&row?)).collect();
+    }
+    if let Ok(array) = obj.extract::<PyReadonlyArray2<f32>>() {
+        let array = array.as_array();
+        return Ok(array.rows().into_iter().map(|row| row.to_vec()).collect());
+    }
+    if let Ok(array) = obj.extract::<PyReadonlyArray2<f64>>() {
+        let array = array.as_array();
+        let rows = array.rows().into_iter();
+        return Ok(rows
+            .map(|row| row.iter().map(|&v| v as f32).collect())
+            .collect());
+    }
+    Err(PyTypeError::new_err(
+        "expected a 2-D float32 or float64 array, or a list of vectors",
+    ))
+}
+
+fn is_sequence(obj: &Bound<'_, PyAny>) -> bool {
+    obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>()
+}
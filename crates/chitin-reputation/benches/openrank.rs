@@ -0,0 +1,139 @@
+// crates/chitin-reputation/benches/openrank.rs
+//
+// OpenRank benchmarks: dense reference vs. CSR, and cold vs. incremental
+// recomputation after a few edge changes.
+//
+// Run with: cargo bench -p chitin-reputation --bench openrank
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chitin_reputation::openrank::{
+    compute_openrank, IncrementalOpenRank, OpenRankConfig, Personalization,
+};
+use chitin_reputation::trust_matrix::TrustMatrix;
+
+/// Out-edges per node in the synthetic graphs.
+const DEGREE: u16 = 8;
+
+/// Edges changed per incremental update.
+const CHANGED_EDGES: u16 = 5;
+
+/// Deterministic sparse trust graph with `n` nodes and `DEGREE` out-edges each.
+fn sparse_graph(n: u16) -> TrustMatrix {
+    let mut tm = TrustMatrix::new();
+    let mut seed: u32 = 0x9e37_79b9;
+    for from in 0..n {
+        for _ in 0..DEGREE {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let to = (seed % n as u32) as u16;
+            if to != from {
+                tm.set_trust(from, to, 0.1 + (seed % 90) as f64 / 100.0);
+            }
+        }
+    }
+    tm
+}
+
+/// The original dense O(n²)-per-iteration implementation, kept as a baseline.
+fn dense_openrank(trust: &TrustMatrix, config: &OpenRankConfig) -> HashMap<u16, f64> {
+    let entries = trust.decayed_entries();
+    let mut uids: Vec<u16> = entries.keys().flat_map(|&(f, t)| [f, t]).collect();
+    uids.sort_unstable();
+    uids.dedup();
+    let n = uids.len();
+    let idx: HashMap<u16, usize> = uids.iter().enumerate().map(|(i, &u)| (u, i)).collect();
+
+    let mut adj = vec![vec![0.0_f64; n]; n];
+    for (&(from, to), &val) in &entries {
+        adj[idx[&to]][idx[&from]] = val;
+    }
+    let mut col_sums = vec![0.0_f64; n];
+    for row in &adj {
+        for (j, &v) in row.iter().enumerate() {
+            col_sums[j] += v;
+        }
+    }
+    for row in adj.iter_mut() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            if col_sums[j] > 0.0 {
+                *cell /= col_sums[j];
+            }
+        }
+    }
+
+    let p = 1.0 / n as f64;
+    let d = config.damping_factor;
+    let mut scores = vec![p; n];
+    for _ in 0..config.max_iterations {
+        let dangling: f64 = (0..n).filter(|&j| col_sums[j] <= 0.0).map(|j| scores[j]).sum();
+        let mut next = vec![0.0_f64; n];
+        for i in 0..n {
+            let mut sum = dangling * p;
+            for j in 0..n {
+                sum += adj[i][j] * scores[j];
+            }
+            next[i] = d * sum + (1.0 - d) * p;
+        }
+        let delta: f64 = scores.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if delta < config.convergence_threshold {
+            break;
+        }
+    }
+    uids.into_iter().zip(scores).collect()
+}
+
+/// Mean wall time of `f` over `runs` runs.
+fn time<T>(runs: u32, mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        black_box(f());
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let config = OpenRankConfig::default();
+    println!(
+        "{:>6} {:>8} {:>12} {:>12} {:>14} {:>10}",
+        "nodes", "edges", "dense", "csr", "incremental", "iters"
+    );
+
+    for &n in &[100u16, 500, 2000] {
+        let tm = sparse_graph(n);
+        let runs = if n >= 2000 { 3 } else { 10 };
+
+        let dense = time(runs, || dense_openrank(&tm, &config));
+        let csr = time(runs, || compute_openrank(&tm, &config));
+
+        let base = IncrementalOpenRank::new(&tm, config.clone(), Personalization::Uniform);
+        let changes: Vec<((u16, u16), f64)> = base
+            .graph()
+            .edges()
+            .step_by(base.graph().edge_count() / CHANGED_EDGES as usize)
+            .take(CHANGED_EDGES as usize)
+            .map(|(edge, w)| (edge, (w * 0.5).max(0.05)))
+            .collect();
+        let mut iterations = 0;
+        let incremental = time(runs, || {
+            let mut state = base.clone();
+            iterations = state.update_edges(&changes);
+            state
+        });
+
+        println!(
+            "{:>6} {:>8} {:>12?} {:>12?} {:>14?} {:>4} -> {:<3}",
+            n,
+            base.graph().edge_count(),
+            dense,
+            csr,
+            incremental,
+            base.last_iterations(),
+            iterations
+        );
+    }
+}
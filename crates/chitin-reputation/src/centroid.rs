@@ -0,0 +1,269 @@
+// crates/chitin-reputation/src/centroid.rs
+//
+// Embedding-based domain classification for the Chitin Protocol.
+//
+// Maintains a centroid embedding per domain (running mean of member Polyp
+// vectors) and classifies by cosine similarity against those centroids.
+// Centroids are bootstrapped from hardened Polyps, labeled with the keyword
+// DomainClassifier, and refined as new Polyps are hardened. The keyword
+// rules are only used when a Polyp carries no usable embedding or no
+// centroid matches its dimensionality.
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::polyp::Polyp;
+
+use crate::domain::{DomainClassifier, DomainContext};
+
+/// Minimum cosine similarity for a centroid match to be accepted.
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f64 = 0.5;
+
+/// How a classification was produced.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ClassificationMethod {
+    /// Cosine similarity against a domain centroid.
+    Centroid,
+    /// Keyword rule fallback (no usable embedding or centroid).
+    Keyword,
+}
+
+/// Result of classifying a Polyp into a domain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DomainClassification {
+    /// The matched domain.
+    pub domain: DomainContext,
+    /// Cosine similarity to the centroid, or 1.0 for keyword matches.
+    pub confidence: f64,
+    /// Which classifier produced the match.
+    pub method: ClassificationMethod,
+}
+
+/// Running-mean centroid embedding for one domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainCentroid {
+    /// The domain this centroid represents.
+    pub domain: DomainContext,
+    /// Mean of all member embeddings.
+    pub centroid: Vec<f32>,
+    /// Number of embeddings folded into the centroid.
+    pub count: u64,
+}
+
+/// Classifies Polyps by embedding similarity to per-domain centroids.
+#[derive(Debug)]
+pub struct CentroidClassifier {
+    /// One centroid per (domain, embedding dimensionality).
+    centroids: Vec<DomainCentroid>,
+    /// Minimum cosine similarity for an accepted match.
+    threshold: f64,
+    /// Keyword classifier used for bootstrapping labels and as a fallback.
+    keywords: DomainClassifier,
+}
+
+impl CentroidClassifier {
+    /// Create a classifier with no centroids and the given confidence threshold.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            centroids: Vec::new(),
+            threshold,
+            keywords: DomainClassifier::new(),
+        }
+    }
+
+    /// All centroids currently maintained.
+    pub fn centroids(&self) -> &[DomainCentroid] {
+        &self.centroids
+    }
+
+    /// Fold an embedding into a domain's centroid, creating it if needed.
+    ///
+    /// Zero or empty embeddings are ignored.
+    pub fn add_sample(&mut self, domain: &DomainContext, embedding: &[f32]) {
+        if !is_usable(embedding) {
+            return;
+        }
+        match self
+            .centroids
+            .iter_mut()
+            .find(|c| c.domain.domain_id == domain.domain_id && c.centroid.len() == embedding.len())
+        {
+            Some(c) => {
+                c.count += 1;
+                let n = c.count as f32;
+                for (m, &x) in c.centroid.iter_mut().zip(embedding) {
+                    *m += (x - *m) / n;
+                }
+            }
+            None => self.centroids.push(DomainCentroid {
+                domain: domain.clone(),
+                centroid: embedding.to_vec(),
+                count: 1,
+            }),
+        }
+    }
+
+    /// Learn from a hardened Polyp: label it by a confident centroid match,
+    /// or by keywords otherwise (so new domains can still seed centroids),
+    /// and fold its embedding into that domain's centroid.
+    ///
+    /// Returns the domain the Polyp was assigned to, if any.
+    pub fn learn(&mut self, polyp: &Polyp) -> Option<DomainContext> {
+        let domain = self
+            .classify_embedding(&polyp.subject.vector.values)
+            .map(|c| c.domain)
+            .or_else(|| self.keywords.classify(&polyp.subject.payload.content))?;
+        self.add_sample(&domain, &polyp.subject.vector.values);
+        Some(domain)
+    }
+
+    /// Seed centroids from a batch of hardened Polyps. Returns how many
+    /// Polyps contributed to a centroid.
+    pub fn bootstrap(&mut self, polyps: &[Polyp]) -> usize {
+        polyps
+            .iter()
+            .filter(|p| is_usable(&p.subject.vector.values))
+            .filter_map(|p| self.learn(p))
+            .count()
+    }
+
+    /// Classify an embedding against the centroids.
+    ///
+    /// Returns `None` if no centroid has matching dimensionality or the best
+    /// match is below the confidence threshold.
+    pub fn classify_embedding(&self, embedding: &[f32]) -> Option<DomainClassification> {
+        self.best_match(embedding)
+            .filter(|(_, similarity)| *similarity >= self.threshold)
+            .map(|(c, similarity)| DomainClassification {
+                domain: c.domain.clone(),
+                confidence: similarity,
+                method: ClassificationMethod::Centroid,
+            })
+    }
+
+    /// Classify content with an optional embedding.
+    ///
+    /// Uses centroids whenever the embedding is usable and at least one
+    /// centroid matches its dimensionality; keyword rules are used otherwise.
+    pub fn classify(&self, text: &str, embedding: Option<&[f32]>) -> Option<DomainClassification> {
+        if let Some(embedding) = embedding.filter(|e| self.can_embed(e)) {
+            return self.classify_embedding(embedding);
+        }
+        self.keywords.classify(text).map(|domain| DomainClassification {
+            domain,
+            confidence: 1.0,
+            method: ClassificationMethod::Keyword,
+        })
+    }
+
+    /// Classify a Polyp using its content and embedding.
+    pub fn classify_polyp(&self, polyp: &Polyp) -> Option<DomainClassification> {
+        self.classify(
+            &polyp.subject.payload.content,
+            Some(&polyp.subject.vector.values),
+        )
+    }
+
+    /// Whether centroid classification is possible for this embedding.
+    fn can_embed(&self, embedding: &[f32]) -> bool {
+        is_usable(embedding) && self.centroids.iter().any(|c| c.centroid.len() == embedding.len())
+    }
+
+    /// The most similar centroid of matching dimensionality.
+    fn best_match(&self, embedding: &[f32]) -> Option<(&DomainCentroid, f64)> {
+        self.centroids
+            .iter()
+            .filter(|c| c.centroid.len() == embedding.len())
+            .map(|c| (c, cosine_similarity(&c.centroid, embedding)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+impl Default for CentroidClassifier {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONFIDENCE_THRESHOLD)
+    }
+}
+
+/// An embedding is usable if it is non-empty and not all zeros.
+fn is_usable(embedding: &[f32]) -> bool {
+    embedding.iter().any(|&v| v != 0.0)
+}
+
+/// Cosine similarity between two equal-length vectors. Returns 0.0 if either
+/// has zero magnitude.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let dot: f64 = a.iter().zip(b).map(|(&x, &y)| x as f64 * y as f64).sum();
+    let norm_a: f64 = a.iter().map(|&x| (x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b: f64 = b.iter().map(|&x| (x as f64).powi(2)).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domain(id: &str) -> DomainContext {
+        DomainContext {
+            domain_id: id.to_string(),
+            name: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn centroid_is_running_mean() {
+        let mut c = CentroidClassifier::default();
+        c.add_sample(&domain("medical"), &[1.0, 0.0]);
+        c.add_sample(&domain("medical"), &[0.0, 1.0]);
+        assert_eq!(c.centroids().len(), 1);
+        assert_eq!(c.centroids()[0].count, 2);
+        assert_eq!(c.centroids()[0].centroid, vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn classifies_by_nearest_centroid() {
+        let mut c = CentroidClassifier::default();
+        c.add_sample(&domain("medical"), &[1.0, 0.0, 0.0]);
+        c.add_sample(&domain("finance"), &[0.0, 1.0, 0.0]);
+
+        // Keyword text says "finance" but the embedding is medical.
+        let result = c.classify("stock market dividend", Some(&[0.9, 0.1, 0.0])).unwrap();
+        assert_eq!(result.domain.domain_id, "medical");
+        assert_eq!(result.method, ClassificationMethod::Centroid);
+        assert!(result.confidence > 0.9);
+    }
+
+    #[test]
+    fn below_threshold_is_unclassified() {
+        let mut c = CentroidClassifier::new(0.8);
+        c.add_sample(&domain("medical"), &[1.0, 0.0, 0.0]);
+        assert!(c.classify("patient diagnosis", Some(&[0.0, 0.0, 1.0])).is_none());
+    }
+
+    #[test]
+    fn falls_back_to_keywords_without_embedding() {
+        let mut c = CentroidClassifier::default();
+        c.add_sample(&domain("medical"), &[1.0, 0.0]);
+
+        let no_vector = c.classify("stock market dividend", None).unwrap();
+        assert_eq!(no_vector.domain.domain_id, "finance");
+        assert_eq!(no_vector.method, ClassificationMethod::Keyword);
+
+        let zero_vector = c.classify("stock market dividend", Some(&[0.0, 0.0])).unwrap();
+        assert_eq!(zero_vector.method, ClassificationMethod::Keyword);
+
+        // No centroid of this dimensionality yet.
+        let other_dim = c.classify("stock market dividend", Some(&[1.0, 0.0, 0.0])).unwrap();
+        assert_eq!(other_dim.method, ClassificationMethod::Keyword);
+    }
+
+    #[test]
+    fn zero_samples_are_ignored() {
+        let mut c = CentroidClassifier::default();
+        c.add_sample(&domain("medical"), &[0.0, 0.0]);
+        c.add_sample(&domain("medical"), &[]);
+        assert!(c.centroids().is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+// crates/chitin-reputation/src/csr.rs
+//
+// Compressed sparse row (CSR) adjacency for trust graphs.
+//
+// Trust matrices are sparse: each validator agrees with a handful of peers,
+// not with every node. Storing out-edges in CSR form makes one power
+// iteration O(E) instead of O(n²), and keeps per-source out-weight sums so a
+// changed edge can be patched in place without rebuilding the graph.
+
+use std::collections::HashMap;
+
+use crate::trust_matrix::TrustMatrix;
+
+/// Trust graph in CSR form, indexed by source node.
+///
+/// Nodes are the UIDs appearing on any edge, sorted ascending. Targets within
+/// each row are sorted, so edge lookups are a binary search.
+#[derive(Debug, Clone, Default)]
+pub struct CsrGraph {
+    /// Node UIDs, sorted. Position is the node index.
+    uids: Vec<u16>,
+    /// UID -> node index.
+    index: HashMap<u16, usize>,
+    /// Row offsets into `targets`/`weights` (length = nodes + 1).
+    row_ptr: Vec<usize>,
+    /// Target node index of each edge.
+    targets: Vec<usize>,
+    /// Raw trust weight of each edge.
+    weights: Vec<f64>,
+    /// Sum of outgoing weights per source node.
+    out_sums: Vec<f64>,
+}
+
+impl CsrGraph {
+    /// Build a graph from (from, to) -> weight edges.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = ((u16, u16), f64)>,
+    {
+        let mut edges: Vec<((u16, u16), f64)> = edges.into_iter().collect();
+        edges.sort_by_key(|&(edge, _)| edge);
+        edges.dedup_by_key(|&mut (edge, _)| edge);
+
+        let mut uids: Vec<u16> = edges.iter().flat_map(|&((f, t), _)| [f, t]).collect();
+        uids.sort_unstable();
+        uids.dedup();
+        let index: HashMap<u16, usize> = uids.iter().enumerate().map(|(i, &u)| (u, i)).collect();
+
+        let n = uids.len();
+        let mut row_ptr = vec![0usize; n + 1];
+        let mut targets = Vec::with_capacity(edges.len());
+        let mut weights = Vec::with_capacity(edges.len());
+        let mut out_sums = vec![0.0_f64; n];
+        // Edges are sorted by (from, to), so rows and their targets come out sorted.
+        for &((from, to), w) in &edges {
+            let s = index[&from];
+            row_ptr[s + 1] += 1;
+            targets.push(index[&to]);
+            weights.push(w);
+            out_sums[s] += w;
+        }
+        for i in 0..n {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        Self {
+            uids,
+            index,
+            row_ptr,
+            targets,
+            weights,
+            out_sums,
+        }
+    }
+
+    /// Build a graph from a trust matrix's decayed entries.
+    pub fn from_trust_matrix(trust: &TrustMatrix) -> Self {
+        Self::from_edges(trust.decayed_entries())
+    }
+
+    /// Number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.uids.len()
+    }
+
+    /// Number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Node UIDs, sorted ascending. Position is the node index.
+    pub fn uids(&self) -> &[u16] {
+        &self.uids
+    }
+
+    /// Node index of a UID, if it is in the graph.
+    pub fn index_of(&self, uid: u16) -> Option<usize> {
+        self.index.get(&uid).copied()
+    }
+
+    /// Weight of the edge `from -> to`, if it exists.
+    pub fn weight(&self, from: u16, to: u16) -> Option<f64> {
+        self.position(from, to).map(|p| self.weights[p])
+    }
+
+    /// All edges as (from, to) -> weight, in (from, to) order.
+    pub fn edges(&self) -> impl Iterator<Item = ((u16, u16), f64)> + '_ {
+        (0..self.node_count()).flat_map(move |s| {
+            (self.row_ptr[s]..self.row_ptr[s + 1])
+                .map(move |p| ((self.uids[s], self.uids[self.targets[p]]), self.weights[p]))
+        })
+    }
+
+    /// Patch the weight of an existing edge in place.
+    ///
+    /// Returns `false` (leaving the graph unchanged) if the edge does not
+    /// exist; adding edges changes the structure and requires a rebuild.
+    pub fn set_weight(&mut self, from: u16, to: u16, weight: f64) -> bool {
+        match self.position(from, to) {
+            Some(p) => {
+                let s = self.index[&from];
+                self.out_sums[s] += weight - self.weights[p];
+                self.weights[p] = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// True if node `i` has no outgoing trust mass.
+    pub fn is_dangling(&self, i: usize) -> bool {
+        self.out_sums[i] <= 0.0
+    }
+
+    /// Propagate `scores` along normalized out-edges into `out` (which is
+    /// overwritten). Returns the total score held by dangling nodes.
+    pub fn propagate(&self, scores: &[f64], out: &mut [f64]) -> f64 {
+        out.iter_mut().for_each(|v| *v = 0.0);
+        let mut dangling = 0.0;
+        for (s, &score) in scores.iter().enumerate() {
+            if self.is_dangling(s) {
+                dangling += score;
+                continue;
+            }
+            let share = score / self.out_sums[s];
+            for p in self.row_ptr[s]..self.row_ptr[s + 1] {
+                out[self.targets[p]] += share * self.weights[p];
+            }
+        }
+        dangling
+    }
+
+    fn position(&self, from: u16, to: u16) -> Option<usize> {
+        let s = self.index_of(from)?;
+        let t = self.index_of(to)?;
+        let (start, end) = (self.row_ptr[s], self.row_ptr[s + 1]);
+        self.targets[start..end]
+            .binary_search(&t)
+            .ok()
+            .map(|offset| start + offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> CsrGraph {
+        CsrGraph::from_edges([((3, 1), 0.5), ((1, 2), 1.0), ((1, 3), 3.0), ((2, 1), 0.0)])
+    }
+
+    #[test]
+    fn builds_sorted_rows() {
+        let g = graph();
+        assert_eq!(g.uids(), &[1, 2, 3]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 4);
+        let edges: Vec<_> = g.edges().collect();
+        assert_eq!(
+            edges,
+            vec![((1, 2), 1.0), ((1, 3), 3.0), ((2, 1), 0.0), ((3, 1), 0.5)]
+        );
+        // Node 2's only edge has zero weight.
+        assert!(g.is_dangling(1));
+    }
+
+    #[test]
+    fn propagate_normalizes_by_out_weight() {
+        let g = graph();
+        let mut out = vec![0.0; 3];
+        let dangling = g.propagate(&[1.0, 1.0, 1.0], &mut out);
+        assert!((dangling - 1.0).abs() < 1e-12);
+        assert!((out[0] - 1.0).abs() < 1e-12); // all of node 3's mass
+        assert!((out[1] - 0.25).abs() < 1e-12);
+        assert!((out[2] - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn set_weight_patches_existing_edges_only() {
+        let mut g = graph();
+        assert!(g.set_weight(2, 1, 0.4));
+        assert!(!g.is_dangling(1));
+        assert_eq!(g.weight(2, 1), Some(0.4));
+        assert!(!g.set_weight(2, 3, 1.0));
+        assert_eq!(g.weight(2, 3), None);
+    }
+}
@@ -4,9 +4,19 @@
 //
 // Trust scores decay over time to ensure nodes must continue participating
 // to maintain their reputation. Supports exponential and linear decay.
+//
+// Both decay functions compose over consecutive intervals
+// (decay(decay(v, a), b) == decay(v, a + b), modulo the linear floor at zero),
+// so trust can be decayed lazily on read and materialized in batches at
+// epoch boundaries without changing the result.
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+/// Default trust half-life in epochs (~1 week at 1-hour epochs).
+pub const DEFAULT_HALF_LIFE_EPOCHS: u64 = 168;
+
 /// Decay function for trust score attenuation over time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DecayFunction {
@@ -24,6 +34,65 @@ pub enum DecayFunction {
     },
 }
 
+impl DecayFunction {
+    /// Exponential decay with the given half-life.
+    pub fn half_life(half_life_epochs: u64) -> Self {
+        DecayFunction::Exponential { half_life_epochs }
+    }
+}
+
+impl Default for DecayFunction {
+    fn default() -> Self {
+        DecayFunction::half_life(DEFAULT_HALF_LIFE_EPOCHS)
+    }
+}
+
+/// Per-domain decay configuration.
+///
+/// Fast-moving domains (e.g., "code/rust") can use a shorter half-life than
+/// slow-moving ones (e.g., "legal"). Domains without an override use
+/// `default_half_life_epochs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecayConfig {
+    /// Half-life applied to domains without an explicit override.
+    pub default_half_life_epochs: u64,
+    /// Per-domain half-life overrides, keyed by `DomainContext::domain_id`.
+    #[serde(default)]
+    pub domain_half_lives: HashMap<String, u64>,
+}
+
+impl DecayConfig {
+    /// Create a config with a single default half-life and no overrides.
+    pub fn new(default_half_life_epochs: u64) -> Self {
+        Self {
+            default_half_life_epochs,
+            domain_half_lives: HashMap::new(),
+        }
+    }
+
+    /// Override the half-life for a specific domain.
+    pub fn with_domain_half_life(mut self, domain_id: &str, half_life_epochs: u64) -> Self {
+        self.domain_half_lives
+            .insert(domain_id.to_string(), half_life_epochs);
+        self
+    }
+
+    /// Resolve the decay function for a domain (`None` = global trust).
+    pub fn function_for(&self, domain_id: Option<&str>) -> DecayFunction {
+        let half_life = domain_id
+            .and_then(|d| self.domain_half_lives.get(d))
+            .copied()
+            .unwrap_or(self.default_half_life_epochs);
+        DecayFunction::half_life(half_life)
+    }
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_HALF_LIFE_EPOCHS)
+    }
+}
+
 /// Apply a decay function to a trust value.
 ///
 /// # Arguments
@@ -83,6 +152,32 @@ mod tests {
         assert!((result - 0.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_exponential_decay_composes() {
+        let func = DecayFunction::half_life(7);
+        let stepwise = apply_decay(apply_decay(0.8, 3, &func), 4, &func);
+        let direct = apply_decay(0.8, 7, &func);
+        assert!((stepwise - direct).abs() < 1e-12);
+        assert!((direct - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_decay_config_domain_override() {
+        let config = DecayConfig::new(100).with_domain_half_life("code/rust", 10);
+        match config.function_for(Some("code/rust")) {
+            DecayFunction::Exponential { half_life_epochs } => assert_eq!(half_life_epochs, 10),
+            other => panic!("Expected exponential decay, got {:?}", other),
+        }
+        match config.function_for(Some("legal")) {
+            DecayFunction::Exponential { half_life_epochs } => assert_eq!(half_life_epochs, 100),
+            other => panic!("Expected exponential decay, got {:?}", other),
+        }
+        match config.function_for(None) {
+            DecayFunction::Exponential { half_life_epochs } => assert_eq!(half_life_epochs, 100),
+            other => panic!("Expected exponential decay, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_exponential_decay_zero_half_life() {
         let func = DecayFunction::Exponential { half_life_epochs: 0 };
@@ -24,6 +24,8 @@ struct DomainRule {
 ///
 /// Used to determine which Reef Zone a Polyp belongs to,
 /// and to scope trust computations to relevant domains.
+/// Superseded by `CentroidClassifier` for Polyps with embeddings; these
+/// rules label its bootstrap samples and act as the fallback.
 #[derive(Debug)]
 pub struct DomainClassifier {
     /// Domain rules with keyword lists.
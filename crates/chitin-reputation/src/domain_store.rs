@@ -0,0 +1,849 @@
+// crates/chitin-reputation/src/domain_store.rs
+//
+// Domain-scoped trust matrices with RocksDB persistence.
+//
+// Each Reef Zone (DomainContext) gets its own TrustMatrix, plus a "global"
+// matrix that aggregates agreement across every zone. Matrices are updated
+// from per-zone scoring agreement between validators at each epoch and
+// persisted under `trust:{domain_id}` keys. Each change is also recorded in a
+// per-domain EvidenceLog, persisted under `trust_evidence:{domain_id}`.
+// Genesis validators are seeded once per edge as their DIDs resolve; the set
+// of seeded edges is persisted under `trust_genesis` so decayed genesis trust
+// is never re-seeded. Epoch checkpoints (reputation snapshot hashes) are
+// persisted under `trust_checkpoint:{epoch}`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use chitin_core::error::ChitinError;
+use chitin_core::IdentityRegistry;
+use chitin_store::RocksStore;
+use uuid::Uuid;
+
+use crate::decay::DecayConfig;
+use crate::evidence::{EvidenceLog, TrustEvidence, TrustExplanation};
+use crate::genesis::GenesisTrust;
+use crate::openrank::{compute_openrank, compute_personalized_openrank, OpenRankConfig, Personalization};
+use crate::snapshot::{DomainSnapshot, EpochCheckpoint, ReputationSnapshot};
+use crate::trust_matrix::TrustMatrix;
+
+/// Domain ID of the matrix that aggregates agreement across all zones.
+pub const GLOBAL_DOMAIN: &str = "global";
+
+/// Key prefix for persisted matrices: `trust:{domain_id}`.
+const KEY_PREFIX: &str = "trust:";
+
+/// Key prefix for persisted evidence logs: `trust_evidence:{domain_id}`.
+const EVIDENCE_KEY_PREFIX: &str = "trust_evidence:";
+
+/// Key for the persisted set of seeded genesis edges.
+const GENESIS_KEY: &str = "trust_genesis";
+
+/// Key prefix for persisted epoch checkpoints: `trust_checkpoint:{epoch:020}`.
+const CHECKPOINT_KEY_PREFIX: &str = "trust_checkpoint:";
+
+/// Number of most recent epoch checkpoints retained by default.
+pub const CHECKPOINT_RETENTION: usize = 168;
+
+/// A seeded genesis edge: (domain_id, from, to).
+type SeededEdge = (String, u16, u16);
+
+/// Weight of the current epoch's agreement when blending into an existing edge.
+pub const AGREEMENT_EMA_ALPHA: f64 = 0.3;
+
+/// Per-domain trust matrices, optionally backed by RocksDB.
+#[derive(Debug)]
+pub struct DomainTrustStore {
+    /// Trust matrix per domain ID.
+    matrices: HashMap<String, TrustMatrix>,
+    /// Evidence log per domain ID.
+    evidence: HashMap<String, EvidenceLog>,
+    /// Half-life configuration used when creating new domain matrices.
+    decay_config: DecayConfig,
+    /// Pre-trusted node UIDs per domain (OpenRank seed sets).
+    pre_trusted: HashMap<String, Vec<u16>>,
+    /// Genesis validators to seed as their DIDs resolve.
+    genesis: GenesisTrust,
+    /// Genesis edges already seeded (never re-seeded after decay).
+    genesis_seeded: BTreeSet<SeededEdge>,
+    /// Reputation snapshot hash per epoch, most recent `checkpoint_retention`.
+    checkpoints: BTreeMap<u64, String>,
+    /// Number of checkpoints kept; `None` keeps every checkpoint.
+    checkpoint_retention: Option<usize>,
+    /// Operator-pinned snapshot hash per epoch, trusted for imports of
+    /// epochs with no local checkpoint.
+    pinned_checkpoints: BTreeMap<u64, String>,
+    /// Persistent backend. `None` keeps everything in memory.
+    backend: Option<Arc<RocksStore>>,
+    /// Domains modified since the last `persist()`.
+    dirty: HashSet<String>,
+}
+
+impl DomainTrustStore {
+    /// Create an in-memory store with no persistence.
+    pub fn new(decay_config: DecayConfig) -> Self {
+        Self {
+            matrices: HashMap::new(),
+            evidence: HashMap::new(),
+            decay_config,
+            pre_trusted: HashMap::new(),
+            genesis: GenesisTrust::default(),
+            genesis_seeded: BTreeSet::new(),
+            checkpoints: BTreeMap::new(),
+            checkpoint_retention: Some(CHECKPOINT_RETENTION),
+            pinned_checkpoints: BTreeMap::new(),
+            backend: None,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Open a store backed by RocksDB, loading every persisted domain matrix.
+    /// Loaded matrices decay under `decay_config`, not the half-life they
+    /// were saved with.
+    pub fn open(backend: Arc<RocksStore>, decay_config: DecayConfig) -> Result<Self, ChitinError> {
+        let mut matrices = HashMap::new();
+        for (key, value) in backend.scan_prefix(KEY_PREFIX.as_bytes())? {
+            let domain_id = String::from_utf8_lossy(&key[KEY_PREFIX.len()..]).into_owned();
+            let mut matrix: TrustMatrix = serde_json::from_slice(&value)?;
+            matrix.decay = domain_decay(&decay_config, &domain_id);
+            matrices.insert(domain_id, matrix);
+        }
+        let mut evidence = HashMap::new();
+        for (key, value) in backend.scan_prefix(EVIDENCE_KEY_PREFIX.as_bytes())? {
+            let domain_id = String::from_utf8_lossy(&key[EVIDENCE_KEY_PREFIX.len()..]).into_owned();
+            let log: EvidenceLog = serde_json::from_slice(&value)?;
+            evidence.insert(domain_id, log);
+        }
+        let genesis_seeded = match backend.get_bytes(GENESIS_KEY.as_bytes())? {
+            Some(bytes) => serde_json::from_slice(&bytes)?,
+            None => BTreeSet::new(),
+        };
+        let mut checkpoints = BTreeMap::new();
+        for (_, value) in backend.scan_prefix(CHECKPOINT_KEY_PREFIX.as_bytes())? {
+            let checkpoint: EpochCheckpoint = serde_json::from_slice(&value)?;
+            checkpoints.insert(checkpoint.epoch, checkpoint.reputation_hash);
+        }
+
+        Ok(Self {
+            matrices,
+            evidence,
+            decay_config,
+            pre_trusted: HashMap::new(),
+            genesis: GenesisTrust::default(),
+            genesis_seeded,
+            checkpoints,
+            checkpoint_retention: Some(CHECKPOINT_RETENTION),
+            pinned_checkpoints: BTreeMap::new(),
+            backend: Some(backend),
+            dirty: HashSet::new(),
+        })
+    }
+
+    /// Set pre-trusted node UIDs per domain. The `GLOBAL_DOMAIN` entry applies
+    /// to domains without their own.
+    pub fn with_pre_trusted(mut self, pre_trusted: HashMap<String, Vec<u16>>) -> Self {
+        self.pre_trusted = pre_trusted;
+        self
+    }
+
+    /// Set the genesis validators seeded by `seed_genesis`.
+    pub fn with_genesis(mut self, genesis: GenesisTrust) -> Self {
+        self.genesis = genesis;
+        self
+    }
+
+    /// Set how many epoch checkpoints are kept (`None` keeps all of them,
+    /// as archival nodes do).
+    pub fn with_checkpoint_retention(mut self, retention: Option<usize>) -> Self {
+        self.checkpoint_retention = retention;
+        self
+    }
+
+    /// Pin snapshot hashes from the node's configuration. A snapshot for an
+    /// epoch this node has no checkpoint for imports only if it matches a
+    /// pinned hash.
+    pub fn with_pinned_checkpoints(mut self, pinned: Vec<EpochCheckpoint>) -> Self {
+        self.pinned_checkpoints = pinned
+            .into_iter()
+            .map(|c| (c.epoch, c.reputation_hash))
+            .collect();
+        self
+    }
+
+    /// Seed trust between genesis validators now registered in
+    /// `identities`, under their registered UIDs.
+    ///
+    /// Each genesis edge is seeded at most once, and only if the edge has no
+    /// trust yet, so real agreement data always takes precedence. Seeded
+    /// edges are recorded as evidence with no Polyps and decay normally.
+    /// Returns the number of edges seeded.
+    pub fn seed_genesis(&mut self, identities: &IdentityRegistry, epoch: u64) -> usize {
+        if self.genesis.is_empty() {
+            return 0;
+        }
+        let mut seeded = 0;
+        for (domain_id, edges) in self.genesis.edges(identities) {
+            let pending: Vec<_> = edges
+                .into_iter()
+                .filter(|e| {
+                    !self
+                        .genesis_seeded
+                        .contains(&(domain_id.clone(), e.from, e.to))
+                })
+                .collect();
+            if pending.is_empty() {
+                continue;
+            }
+
+            let decay = self.decay_function(&domain_id);
+            self.dirty.insert(domain_id.clone());
+            let matrix = self
+                .matrices
+                .entry(domain_id.clone())
+                .or_insert_with(|| TrustMatrix::with_decay(decay));
+            let log = self.evidence.entry(domain_id.clone()).or_default();
+            for edge in pending {
+                self.genesis_seeded
+                    .insert((domain_id.clone(), edge.from, edge.to));
+                if matrix.last_interaction(edge.from, edge.to).is_some() {
+                    continue;
+                }
+                matrix.record_interaction(edge.from, edge.to, edge.value, epoch);
+                log.record(
+                    edge.from,
+                    edge.to,
+                    TrustEvidence::new(epoch, &[], edge.value, 0.0, edge.value),
+                );
+                seeded += 1;
+            }
+        }
+        seeded
+    }
+
+    /// Personalization seeded from a domain's pre-trusted nodes, falling back
+    /// to the global set and then to uniform.
+    pub fn pre_trusted(&self, domain_id: &str) -> Personalization {
+        self.pre_trusted
+            .get(domain_id)
+            .or_else(|| self.pre_trusted.get(GLOBAL_DOMAIN))
+            .filter(|uids| !uids.is_empty())
+            .map(|uids| Personalization::TrustedSet(uids.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Domain IDs with a trust matrix, sorted.
+    pub fn domains(&self) -> Vec<String> {
+        let mut domains: Vec<String> = self.matrices.keys().cloned().collect();
+        domains.sort();
+        domains
+    }
+
+    /// Get the trust matrix for a domain, if one exists.
+    pub fn matrix(&self, domain_id: &str) -> Option<&TrustMatrix> {
+        self.matrices.get(domain_id)
+    }
+
+    /// Get the trust matrix for a domain, creating it with the domain's
+    /// configured half-life if it does not exist yet.
+    pub fn matrix_mut(&mut self, domain_id: &str) -> &mut TrustMatrix {
+        self.dirty.insert(domain_id.to_string());
+        let decay = self.decay_function(domain_id);
+        self.matrices
+            .entry(domain_id.to_string())
+            .or_insert_with(|| TrustMatrix::with_decay(decay))
+    }
+
+    /// Advance every domain matrix to `epoch`, materializing decay.
+    ///
+    /// Evidence for pruned edges is discarded. Returns the total number of
+    /// edges pruned across all domains.
+    pub fn advance_epoch(&mut self, epoch: u64) -> usize {
+        let mut pruned = 0;
+        for (domain_id, matrix) in self.matrices.iter_mut() {
+            let domain_pruned = matrix.advance_epoch(epoch);
+            if domain_pruned > 0 {
+                if let Some(log) = self.evidence.get_mut(domain_id) {
+                    log.retain_edges(|from, to| matrix.entries.contains_key(&(from, to)));
+                }
+            }
+            pruned += domain_pruned;
+            self.dirty.insert(domain_id.clone());
+        }
+        pruned
+    }
+
+    /// Update a domain's trust from validator agreement on the zone's Polyps.
+    ///
+    /// `weights` is the epoch's validator × polyp weight matrix and `columns`
+    /// are the (polyp index, polyp ID) pairs that belong to this domain.
+    /// Every edge change is recorded as evidence. Each validator's
+    /// weights over those columns are normalized to a distribution; agreement
+    /// between two validators is `1 - total_variation_distance`. Validators
+    /// that scored nothing in the zone are skipped, and a validator alone in
+    /// a zone records self-trust.
+    ///
+    /// Returns the number of edges updated.
+    pub fn update_from_agreement(
+        &mut self,
+        domain_id: &str,
+        epoch: u64,
+        weights: &[Vec<f64>],
+        columns: &[(usize, Uuid)],
+    ) -> usize {
+        let polyp_ids: Vec<Uuid> = columns.iter().map(|&(_, id)| id).collect();
+        let distributions: Vec<(u16, Vec<f64>)> = weights
+            .iter()
+            .enumerate()
+            .filter_map(|(uid, row)| {
+                let values: Vec<f64> = columns
+                    .iter()
+                    .map(|&(c, _)| row.get(c).copied().unwrap_or(0.0).max(0.0))
+                    .collect();
+                let sum: f64 = values.iter().sum();
+                (sum > 0.0).then(|| (uid as u16, values.iter().map(|v| v / sum).collect()))
+            })
+            .collect();
+
+        if distributions.is_empty() {
+            return 0;
+        }
+
+        let decay = self.decay_function(domain_id);
+        self.dirty.insert(domain_id.to_string());
+        let matrix = self
+            .matrices
+            .entry(domain_id.to_string())
+            .or_insert_with(|| TrustMatrix::with_decay(decay));
+        let log = self.evidence.entry(domain_id.to_string()).or_default();
+
+        if distributions.len() == 1 {
+            let uid = distributions[0].0;
+            let previous = matrix.get_trust(uid, uid);
+            matrix.record_interaction(uid, uid, 1.0, epoch);
+            log.record(uid, uid, TrustEvidence::new(epoch, &polyp_ids, 1.0, previous, 1.0));
+            return 1;
+        }
+
+        let mut updated = 0;
+        for (i, (from, dist_i)) in distributions.iter().enumerate() {
+            for (j, (to, dist_j)) in distributions.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let distance: f64 = dist_i
+                    .iter()
+                    .zip(dist_j.iter())
+                    .map(|(a, b)| (a - b).abs())
+                    .sum::<f64>()
+                    / 2.0;
+                let agreement = (1.0 - distance).clamp(0.0, 1.0);
+                let previous = matrix.get_trust(*from, *to);
+                let value = match matrix.last_interaction(*from, *to) {
+                    Some(_) => {
+                        (1.0 - AGREEMENT_EMA_ALPHA) * previous + AGREEMENT_EMA_ALPHA * agreement
+                    }
+                    None => agreement,
+                };
+                matrix.record_interaction(*from, *to, value, epoch);
+                log.record(
+                    *from,
+                    *to,
+                    TrustEvidence::new(epoch, &polyp_ids, agreement, previous, value),
+                );
+                updated += 1;
+            }
+        }
+        updated
+    }
+
+    /// Compute global (EigenTrust) scores for a domain. Unknown domains yield
+    /// an empty map.
+    pub fn global_trust(&self, domain_id: &str) -> HashMap<u16, f64> {
+        self.matrices
+            .get(domain_id)
+            .map(|m| m.compute_global_trust())
+            .unwrap_or_default()
+    }
+
+    /// Compute personalized OpenRank scores for a domain from the given
+    /// vantage point. Unknown domains yield an empty map.
+    pub fn personalized_trust(
+        &self,
+        domain_id: &str,
+        personalization: &Personalization,
+    ) -> HashMap<u16, f64> {
+        self.matrices
+            .get(domain_id)
+            .map(|m| compute_personalized_openrank(m, &OpenRankConfig::default(), personalization))
+            .unwrap_or_default()
+    }
+
+    /// Retained evidence for the edge `from -> to` in a domain, oldest first.
+    pub fn evidence(&self, domain_id: &str, from: u16, to: u16) -> Vec<TrustEvidence> {
+        self.evidence
+            .get(domain_id)
+            .map(|log| log.events(from, to))
+            .unwrap_or_default()
+    }
+
+    /// The `limit` most recent evidence events on edges into `to`, across
+    /// every domain, newest first, each with its domain and trusting node.
+    pub fn evidence_to(&self, to: u16, limit: usize) -> Vec<(String, u16, TrustEvidence)> {
+        let mut events: Vec<(String, u16, TrustEvidence)> = self
+            .evidence
+            .iter()
+            .flat_map(|(domain_id, log)| {
+                log.events_to(to)
+                    .into_iter()
+                    .map(move |(from, e)| (domain_id.clone(), from, e))
+            })
+            .collect();
+        events.sort_by(|a, b| {
+            b.2.epoch
+                .cmp(&a.2.epoch)
+                .then_with(|| a.0.cmp(&b.0))
+                .then_with(|| a.1.cmp(&b.1))
+        });
+        events.truncate(limit);
+        events
+    }
+
+    /// Explain the current trust level of `from -> to` in a domain: the
+    /// recorded evidence plus how much has decayed since the last event.
+    pub fn explain(&self, domain_id: &str, from: u16, to: u16) -> TrustExplanation {
+        let evidence = self.evidence(domain_id, from, to);
+        let (epoch, current_trust) = self
+            .matrices
+            .get(domain_id)
+            .map(|m| (m.epoch, m.get_trust(from, to)))
+            .unwrap_or((0, 0.0));
+        let last = evidence.last();
+
+        TrustExplanation {
+            domain_id: domain_id.to_string(),
+            from,
+            to,
+            epoch,
+            current_trust,
+            last_evidence_epoch: last.map(|e| e.epoch),
+            decay_since_evidence: last
+                .map(|e| (e.new_trust - current_trust).max(0.0))
+                .unwrap_or(0.0),
+            evidence,
+        }
+    }
+
+    /// Most recent epoch of any domain matrix (0 for an empty store).
+    pub fn epoch(&self) -> u64 {
+        self.matrices.values().map(|m| m.epoch).max().unwrap_or(0)
+    }
+
+    /// Snapshot every domain matrix and its OpenRank scores, stamped with
+    /// `epoch` and a content hash.
+    pub fn snapshot(&self, epoch: u64) -> Result<ReputationSnapshot, ChitinError> {
+        let config = OpenRankConfig::default();
+        let domains = self
+            .matrices
+            .iter()
+            .map(|(domain_id, matrix)| {
+                let scores = compute_openrank(matrix, &config).into_iter().collect();
+                let snapshot = DomainSnapshot {
+                    matrix: matrix.clone(),
+                    scores,
+                };
+                (domain_id.clone(), snapshot)
+            })
+            .collect();
+        ReputationSnapshot::new(epoch, domains)
+    }
+
+    /// Record the current state's snapshot hash as the checkpoint for `epoch`.
+    ///
+    /// Only the most recent `checkpoint_retention` checkpoints are kept.
+    pub fn checkpoint(&mut self, epoch: u64) -> Result<EpochCheckpoint, ChitinError> {
+        let checkpoint = EpochCheckpoint {
+            epoch,
+            reputation_hash: self.snapshot(epoch)?.hash,
+        };
+        self.checkpoints
+            .insert(epoch, checkpoint.reputation_hash.clone());
+        if let Some(backend) = &self.backend {
+            let key = format!("{}{:020}", CHECKPOINT_KEY_PREFIX, epoch);
+            backend.put_bytes(key.as_bytes(), &serde_json::to_vec(&checkpoint)?)?;
+        }
+        let retention = self.checkpoint_retention.unwrap_or(usize::MAX);
+        while self.checkpoints.len() > retention {
+            let oldest = match self.checkpoints.keys().next() {
+                Some(&e) => e,
+                None => break,
+            };
+            self.checkpoints.remove(&oldest);
+            if let Some(backend) = &self.backend {
+                let key = format!("{}{:020}", CHECKPOINT_KEY_PREFIX, oldest);
+                backend.delete_bytes(key.as_bytes())?;
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    /// The checkpoint recorded for `epoch`, if retained.
+    pub fn checkpoint_at(&self, epoch: u64) -> Option<EpochCheckpoint> {
+        self.checkpoints.get(&epoch).map(|hash| EpochCheckpoint {
+            epoch,
+            reputation_hash: hash.clone(),
+        })
+    }
+
+    /// Epoch of the oldest retained checkpoint, if any.
+    pub fn oldest_checkpoint_epoch(&self) -> Option<u64> {
+        self.checkpoints.keys().next().copied()
+    }
+
+    /// Replace all trust state with a verified snapshot.
+    ///
+    /// The snapshot's stamped hash must match its content and the checkpoint
+    /// for its epoch: the locally recorded one if retained, else the one
+    /// pinned by `with_pinned_checkpoints`. The snapshot's source never
+    /// supplies the hash it is checked against. Evidence logs are cleared,
+    /// since a snapshot does not carry them. Returns the number of domains
+    /// imported.
+    pub fn import_snapshot(&mut self, snapshot: ReputationSnapshot) -> Result<usize, ChitinError> {
+        snapshot.verify()?;
+        let checkpoint_hash = match self
+            .checkpoints
+            .get(&snapshot.epoch)
+            .or_else(|| self.pinned_checkpoints.get(&snapshot.epoch))
+        {
+            Some(hash) => hash.clone(),
+            None => {
+                return Err(ChitinError::NotFound(format!(
+                    "No reputation checkpoint recorded or pinned for epoch {}",
+                    snapshot.epoch
+                )))
+            }
+        };
+        if checkpoint_hash != snapshot.hash {
+            return Err(ChitinError::InvalidState(format!(
+                "Snapshot hash {} does not match epoch {} checkpoint {}",
+                snapshot.hash, snapshot.epoch, checkpoint_hash
+            )));
+        }
+
+        if let Some(backend) = &self.backend {
+            for domain_id in self.matrices.keys() {
+                if !snapshot.domains.contains_key(domain_id) {
+                    backend.delete_bytes(format!("{}{}", KEY_PREFIX, domain_id).as_bytes())?;
+                }
+            }
+            for domain_id in self.evidence.keys() {
+                backend.delete_bytes(format!("{}{}", EVIDENCE_KEY_PREFIX, domain_id).as_bytes())?;
+            }
+        }
+        self.evidence.clear();
+        self.matrices = snapshot
+            .domains
+            .into_iter()
+            .map(|(domain_id, d)| (domain_id, d.matrix))
+            .collect();
+        self.dirty = self.matrices.keys().cloned().collect();
+        self.checkpoints.insert(snapshot.epoch, snapshot.hash);
+        self.persist()?;
+        Ok(self.matrices.len())
+    }
+
+    /// Write every matrix modified since the last call to the backend.
+    ///
+    /// No-op for in-memory stores.
+    pub fn persist(&mut self) -> Result<(), ChitinError> {
+        let backend = match &self.backend {
+            Some(b) => b,
+            None => {
+                self.dirty.clear();
+                return Ok(());
+            }
+        };
+
+        // Deterministic write order keeps replays reproducible.
+        let dirty: BTreeMap<&String, &TrustMatrix> = self
+            .dirty
+            .iter()
+            .filter_map(|d| self.matrices.get(d).map(|m| (d, m)))
+            .collect();
+        for (domain_id, matrix) in dirty {
+            let key = format!("{}{}", KEY_PREFIX, domain_id);
+            backend.put_bytes(key.as_bytes(), &serde_json::to_vec(matrix)?)?;
+            if let Some(log) = self.evidence.get(domain_id) {
+                let key = format!("{}{}", EVIDENCE_KEY_PREFIX, domain_id);
+                backend.put_bytes(key.as_bytes(), &serde_json::to_vec(log)?)?;
+            }
+        }
+        if !self.genesis_seeded.is_empty() {
+            backend.put_bytes(GENESIS_KEY.as_bytes(), &serde_json::to_vec(&self.genesis_seeded)?)?;
+        }
+        self.dirty.clear();
+        Ok(())
+    }
+
+    fn decay_function(&self, domain_id: &str) -> crate::decay::DecayFunction {
+        domain_decay(&self.decay_config, domain_id)
+    }
+}
+
+/// The decay function `config` gives `domain_id`'s matrix.
+fn domain_decay(config: &DecayConfig, domain_id: &str) -> crate::decay::DecayFunction {
+    if domain_id == GLOBAL_DOMAIN {
+        config.function_for(None)
+    } else {
+        config.function_for(Some(domain_id))
+    }
+}
+
+impl Default for DomainTrustStore {
+    fn default() -> Self {
+        Self::new(DecayConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cols(indices: &[usize]) -> Vec<(usize, Uuid)> {
+        indices.iter().map(|&i| (i, Uuid::from_u128(i as u128 + 1))).collect()
+    }
+
+    fn temp_db_path(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("chitin_domain_store_{}_{}", name, uuid::Uuid::now_v7()));
+        dir.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn agreeing_validators_trust_each_other_more() {
+        let mut store = DomainTrustStore::default();
+        // Validators 0 and 1 agree on the zone's polyps; validator 2 disagrees.
+        let weights = vec![
+            vec![0.8, 0.2, 0.0],
+            vec![0.7, 0.3, 0.0],
+            vec![0.0, 1.0, 0.0],
+        ];
+        store.update_from_agreement("medical", 1, &weights, &cols(&[0, 1]));
+
+        let m = store.matrix("medical").unwrap();
+        assert!(m.get_trust(0, 1) > m.get_trust(0, 2));
+        assert!((m.get_trust(0, 1) - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zones_are_independent() {
+        let mut store = DomainTrustStore::default();
+        let weights = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+        store.update_from_agreement("code/rust", 1, &weights, &cols(&[0]));
+
+        assert!(store.matrix("code/rust").is_some());
+        assert!(store.matrix("medical").is_none());
+        assert!(store.global_trust("medical").is_empty());
+        assert_eq!(store.domains(), vec!["code/rust".to_string()]);
+    }
+
+    #[test]
+    fn lone_validator_records_self_trust() {
+        let mut store = DomainTrustStore::default();
+        let weights = vec![vec![0.0, 1.0], vec![0.0, 0.0]];
+        assert_eq!(store.update_from_agreement("medical", 1, &weights, &cols(&[1])), 1);
+        assert_eq!(store.matrix("medical").unwrap().get_trust(0, 0), 1.0);
+    }
+
+    #[test]
+    fn updates_are_recorded_as_evidence() {
+        let mut store = DomainTrustStore::default();
+        let agree = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+        let disagree = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        store.update_from_agreement("medical", 1, &agree, &cols(&[0, 1]));
+        store.update_from_agreement("medical", 2, &disagree, &cols(&[0, 1]));
+
+        let evidence = store.evidence("medical", 0, 1);
+        assert_eq!(evidence.len(), 2);
+        assert_eq!(evidence[0].polyp_ids, vec![Uuid::from_u128(1), Uuid::from_u128(2)]);
+        assert_eq!(evidence[0].new_trust, 1.0);
+        assert_eq!(evidence[1].agreement, 0.0);
+        assert!(evidence[1].trust_delta() < 0.0);
+
+        let explanation = store.explain("medical", 0, 1);
+        assert_eq!(explanation.last_evidence_epoch, Some(2));
+        assert!((explanation.current_trust - evidence[1].new_trust).abs() < 1e-12);
+        assert_eq!(explanation.decay_since_evidence, 0.0);
+    }
+
+    #[test]
+    fn evidence_to_lists_incoming_events_newest_first() {
+        let mut store = DomainTrustStore::default();
+        let agree = vec![vec![1.0, 1.0, 0.0], vec![1.0, 1.0, 0.0], vec![1.0, 1.0, 0.0]];
+        store.update_from_agreement("medical", 1, &agree, &cols(&[0, 1, 2]));
+        store.update_from_agreement("code", 2, &agree, &cols(&[0, 1, 2]));
+
+        let events = store.evidence_to(1, 10);
+        assert!(events.iter().any(|(domain, _, _)| domain == "medical"));
+        assert_eq!(events[0].0, "code");
+        assert!(events.windows(2).all(|w| w[0].2.epoch >= w[1].2.epoch));
+        // Each event is in the log of an edge into node 1.
+        assert!(events
+            .iter()
+            .all(|(domain, from, _)| !store.evidence(domain, *from, 1).is_empty()));
+        assert_eq!(store.evidence_to(1, 1).len(), 1);
+    }
+
+    #[test]
+    fn explain_reports_decay_since_last_evidence() {
+        let config = DecayConfig::new(1);
+        let mut store = DomainTrustStore::new(config);
+        let weights = vec![vec![1.0], vec![1.0]];
+        store.update_from_agreement("medical", 1, &weights, &cols(&[0]));
+        store.advance_epoch(2);
+
+        let explanation = store.explain("medical", 0, 1);
+        assert!((explanation.current_trust - 0.5).abs() < 1e-9);
+        assert!((explanation.decay_since_evidence - 0.5).abs() < 1e-9);
+        assert!(store.explain("code/rust", 0, 1).evidence.is_empty());
+    }
+
+    #[test]
+    fn pre_trusted_falls_back_to_global_then_uniform() {
+        let store = DomainTrustStore::default();
+        assert_eq!(store.pre_trusted("medical"), Personalization::Uniform);
+
+        let pre: HashMap<String, Vec<u16>> = [
+            (GLOBAL_DOMAIN.to_string(), vec![0]),
+            ("medical".to_string(), vec![2, 3]),
+        ]
+        .into_iter()
+        .collect();
+        let store = DomainTrustStore::default().with_pre_trusted(pre);
+        assert_eq!(store.pre_trusted("medical"), Personalization::TrustedSet(vec![2, 3]));
+        assert_eq!(store.pre_trusted("legal"), Personalization::TrustedSet(vec![0]));
+    }
+
+    #[test]
+    fn genesis_edges_are_seeded_once_and_yield_to_real_data() {
+        use crate::genesis::GenesisValidator;
+        use chitin_core::NodeType;
+
+        let mut uids = IdentityRegistry::new();
+        for key in [0xaa, 0xbb] {
+            uids.register([key; 32], [key + 1; 32], NodeType::Tide, 0).unwrap();
+        }
+        let genesis = GenesisTrust::new(vec![
+            GenesisValidator { did: uids.did_of(0).unwrap().into(), weight: 1.0, domains: vec![] },
+            GenesisValidator { did: uids.did_of(1).unwrap().into(), weight: 0.6, domains: vec![] },
+        ]);
+        let mut store = DomainTrustStore::default().with_genesis(genesis);
+        // Real agreement already exists for 1 -> 0.
+        store.matrix_mut(GLOBAL_DOMAIN).record_interaction(1, 0, 0.9, 1);
+
+        assert_eq!(store.seed_genesis(&uids, 1), 1);
+        let m = store.matrix(GLOBAL_DOMAIN).unwrap();
+        assert!((m.get_trust(0, 1) - 0.6).abs() < 1e-9);
+        assert!((m.get_trust(1, 0) - 0.9).abs() < 1e-9);
+        assert_eq!(store.evidence(GLOBAL_DOMAIN, 0, 1)[0].polyp_count, 0);
+
+        // Once decayed away, genesis trust is not re-seeded.
+        store.matrix_mut(GLOBAL_DOMAIN).entries.clear();
+        assert_eq!(store.seed_genesis(&uids, 2), 0);
+        assert_eq!(store.matrix(GLOBAL_DOMAIN).unwrap().get_trust(0, 1), 0.0);
+    }
+
+    #[test]
+    fn snapshot_import_requires_matching_checkpoint() {
+        let weights = vec![vec![1.0, 0.0], vec![0.5, 0.5]];
+        let mut source = DomainTrustStore::default();
+        source.update_from_agreement("medical", 3, &weights, &cols(&[0, 1]));
+        let checkpoint = source.checkpoint(3).unwrap();
+        let snapshot = source.snapshot(3).unwrap();
+        assert_eq!(snapshot.hash, checkpoint.reputation_hash);
+        assert_eq!(source.checkpoint_at(3), Some(checkpoint.clone()));
+
+        // A fresh node has no checkpoint and needs a pinned hash.
+        let mut target = DomainTrustStore::default();
+        assert!(target.import_snapshot(snapshot.clone()).is_err());
+        let mut target = DomainTrustStore::default().with_pinned_checkpoints(vec![EpochCheckpoint {
+            epoch: 3,
+            reputation_hash: "00".into(),
+        }]);
+        assert!(target.import_snapshot(snapshot.clone()).is_err());
+        let mut target =
+            DomainTrustStore::default().with_pinned_checkpoints(vec![checkpoint.clone()]);
+        assert_eq!(target.import_snapshot(snapshot.clone()).unwrap(), 1);
+        assert!((target.matrix("medical").unwrap().get_trust(0, 1) - 0.5).abs() < 1e-9);
+
+        // A diverged local checkpoint for the epoch takes precedence.
+        let mut diverged = DomainTrustStore::default().with_pinned_checkpoints(vec![checkpoint]);
+        diverged.update_from_agreement("legal", 3, &weights, &cols(&[0]));
+        diverged.checkpoint(3).unwrap();
+        assert!(diverged.import_snapshot(snapshot).is_err());
+    }
+
+    #[test]
+    fn checkpoint_retention_drops_oldest_unless_unlimited() {
+        let mut store = DomainTrustStore::default().with_checkpoint_retention(Some(2));
+        for epoch in 1..=4 {
+            store.checkpoint(epoch).unwrap();
+        }
+        assert!(store.checkpoint_at(2).is_none());
+        assert!(store.checkpoint_at(3).is_some() && store.checkpoint_at(4).is_some());
+
+        let mut archival = DomainTrustStore::default().with_checkpoint_retention(None);
+        for epoch in 1..=(CHECKPOINT_RETENTION as u64 + 1) {
+            archival.checkpoint(epoch).unwrap();
+        }
+        assert!(archival.checkpoint_at(1).is_some());
+    }
+
+    #[test]
+    fn persisted_matrices_survive_reopen() {
+        let path = temp_db_path("reopen");
+        {
+            let backend = Arc::new(RocksStore::open(&path).unwrap());
+            let mut store = DomainTrustStore::open(backend, DecayConfig::default()).unwrap();
+            let weights = vec![vec![1.0, 0.0], vec![0.5, 0.5]];
+            store.update_from_agreement("medical", 3, &weights, &cols(&[0, 1]));
+            store.update_from_agreement(GLOBAL_DOMAIN, 3, &weights, &cols(&[0, 1]));
+            store.persist().unwrap();
+        }
+
+        let backend = Arc::new(RocksStore::open(&path).unwrap());
+        let store = DomainTrustStore::open(backend, DecayConfig::default()).unwrap();
+        assert_eq!(store.domains(), vec![GLOBAL_DOMAIN.to_string(), "medical".to_string()]);
+        let m = store.matrix("medical").unwrap();
+        assert_eq!(m.last_interaction(0, 1), Some(3));
+        assert!((m.get_trust(0, 1) - 0.5).abs() < 1e-9);
+        assert_eq!(store.evidence("medical", 0, 1).len(), 1);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn reopened_matrices_take_the_configured_half_life() {
+        use crate::decay::DecayFunction;
+
+        let path = temp_db_path("reopen_decay");
+        {
+            let backend = Arc::new(RocksStore::open(&path).unwrap());
+            let mut store = DomainTrustStore::open(backend, DecayConfig::new(100)).unwrap();
+            let weights = vec![vec![1.0, 0.0], vec![0.5, 0.5]];
+            store.update_from_agreement("medical", 3, &weights, &cols(&[0, 1]));
+            store.update_from_agreement(GLOBAL_DOMAIN, 3, &weights, &cols(&[0, 1]));
+            store.persist().unwrap();
+        }
+
+        let backend = Arc::new(RocksStore::open(&path).unwrap());
+        let config = DecayConfig::new(20).with_domain_half_life("medical", 5);
+        let store = DomainTrustStore::open(backend, config).unwrap();
+        for (domain_id, expected) in [("medical", 5), (GLOBAL_DOMAIN, 20)] {
+            match &store.matrix(domain_id).unwrap().decay {
+                DecayFunction::Exponential { half_life_epochs } => {
+                    assert_eq!(*half_life_epochs, expected)
+                }
+                other => panic!("Expected exponential decay, got {:?}", other),
+            }
+        }
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -0,0 +1,201 @@
+// crates/chitin-reputation/src/evidence.rs
+//
+// Trust evidence log for the Chitin Protocol.
+//
+// Every change to a trust edge is recorded as a TrustEvidence event so that
+// a node's trust in another can be explained after the fact. Events are kept
+// per (from, to) edge in a bounded ring, oldest evicted first.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::trust_matrix::edge_map;
+
+/// Default number of evidence events retained per edge.
+pub const MAX_EVIDENCE_PER_EDGE: usize = 32;
+
+/// Maximum number of Polyp IDs recorded in a single evidence event.
+pub const MAX_EVIDENCE_POLYPS: usize = 16;
+
+/// A single recorded change to a trust edge.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustEvidence {
+    /// Epoch in which the change was made.
+    pub epoch: u64,
+    /// Polyps whose scores produced this evidence (truncated to
+    /// `MAX_EVIDENCE_POLYPS`; see `polyp_count` for the full number).
+    pub polyp_ids: Vec<Uuid>,
+    /// Total number of Polyps involved.
+    pub polyp_count: usize,
+    /// Scoring agreement observed between the two nodes this epoch, in [0.0, 1.0].
+    pub agreement: f64,
+    /// Trust value (after decay) immediately before the change.
+    pub previous_trust: f64,
+    /// Trust value after the change.
+    pub new_trust: f64,
+}
+
+impl TrustEvidence {
+    /// Build an evidence event, truncating the Polyp list.
+    pub fn new(
+        epoch: u64,
+        polyp_ids: &[Uuid],
+        agreement: f64,
+        previous_trust: f64,
+        new_trust: f64,
+    ) -> Self {
+        Self {
+            epoch,
+            polyp_ids: polyp_ids.iter().take(MAX_EVIDENCE_POLYPS).copied().collect(),
+            polyp_count: polyp_ids.len(),
+            agreement,
+            previous_trust,
+            new_trust,
+        }
+    }
+
+    /// Agreement relative to the prior trust level: positive evidence pulled
+    /// trust up, negative evidence pulled it down.
+    pub fn agreement_delta(&self) -> f64 {
+        self.agreement - self.previous_trust
+    }
+
+    /// Net change in trust caused by this event.
+    pub fn trust_delta(&self) -> f64 {
+        self.new_trust - self.previous_trust
+    }
+}
+
+/// Bounded per-edge log of trust evidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceLog {
+    /// Evidence events per edge, oldest first.
+    #[serde(with = "edge_map")]
+    edges: HashMap<(u16, u16), VecDeque<TrustEvidence>>,
+    /// Maximum events retained per edge.
+    capacity: usize,
+}
+
+impl EvidenceLog {
+    /// Create an empty log retaining `MAX_EVIDENCE_PER_EDGE` events per edge.
+    pub fn new() -> Self {
+        Self::with_capacity(MAX_EVIDENCE_PER_EDGE)
+    }
+
+    /// Create an empty log retaining at most `capacity` events per edge.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            edges: HashMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record an event for the edge `from -> to`, evicting the oldest if full.
+    pub fn record(&mut self, from: u16, to: u16, evidence: TrustEvidence) {
+        let events = self.edges.entry((from, to)).or_default();
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back(evidence);
+    }
+
+    /// Events for the edge `from -> to`, oldest first.
+    pub fn events(&self, from: u16, to: u16) -> Vec<TrustEvidence> {
+        self.edges
+            .get(&(from, to))
+            .map(|e| e.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Events for every edge into `to`, paired with the trusting node, in no
+    /// particular order.
+    pub fn events_to(&self, to: u16) -> Vec<(u16, TrustEvidence)> {
+        self.edges
+            .iter()
+            .filter(|((_, edge_to), _)| *edge_to == to)
+            .flat_map(|(&(from, _), events)| events.iter().map(move |e| (from, e.clone())))
+            .collect()
+    }
+
+    /// Drop the history of edges for which `keep` returns false
+    /// (e.g., edges pruned from the trust matrix).
+    pub fn retain_edges<F: FnMut(u16, u16) -> bool>(&mut self, mut keep: F) {
+        self.edges.retain(|&(from, to), _| keep(from, to));
+    }
+
+    /// Number of edges with recorded evidence.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// Why one node trusts another at its current level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustExplanation {
+    /// Domain the trust edge belongs to.
+    pub domain_id: String,
+    /// Trusting node UID.
+    pub from: u16,
+    /// Trusted node UID.
+    pub to: u16,
+    /// The domain matrix's current epoch.
+    pub epoch: u64,
+    /// Trust value at the current epoch (decay applied).
+    pub current_trust: f64,
+    /// Epoch of the most recent evidence event, if any.
+    pub last_evidence_epoch: Option<u64>,
+    /// Trust lost to decay since the most recent evidence event.
+    pub decay_since_evidence: f64,
+    /// Retained evidence events, oldest first.
+    pub evidence: Vec<TrustEvidence>,
+}
+
+impl Default for EvidenceLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(epoch: u64) -> TrustEvidence {
+        TrustEvidence::new(epoch, &[Uuid::nil()], 0.8, 0.5, 0.6)
+    }
+
+    #[test]
+    fn log_is_bounded_per_edge() {
+        let mut log = EvidenceLog::with_capacity(3);
+        for epoch in 1..=5 {
+            log.record(0, 1, event(epoch));
+        }
+        log.record(1, 0, event(9));
+
+        let epochs: Vec<u64> = log.events(0, 1).iter().map(|e| e.epoch).collect();
+        assert_eq!(epochs, vec![3, 4, 5]);
+        assert_eq!(log.events(1, 0).len(), 1);
+        assert!(log.events(2, 3).is_empty());
+    }
+
+    #[test]
+    fn polyp_list_is_truncated() {
+        let ids: Vec<Uuid> = (0..40).map(|_| Uuid::now_v7()).collect();
+        let e = TrustEvidence::new(1, &ids, 1.0, 0.0, 0.3);
+        assert_eq!(e.polyp_ids.len(), MAX_EVIDENCE_POLYPS);
+        assert_eq!(e.polyp_count, 40);
+        assert!((e.trust_delta() - 0.3).abs() < 1e-12);
+        assert!((e.agreement_delta() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let mut log = EvidenceLog::new();
+        log.record(4, 2, event(7));
+        let json = serde_json::to_string(&log).unwrap();
+        let back: EvidenceLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.events(4, 2), log.events(4, 2));
+    }
+}
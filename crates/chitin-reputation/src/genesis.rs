@@ -0,0 +1,217 @@
+// crates/chitin-reputation/src/genesis.rs
+//
+// Genesis trust bootstrap for new networks.
+//
+// A fresh network has no validator agreement history, so every trust matrix
+// is empty and OpenRank degenerates to uniform. Operators can name a set of
+// genesis validators by DID with initial weights; once those DIDs are in the
+// identity registry, the validators are seeded as mutually trusting in the
+// global matrix and any listed domains. Seeded edges are ordinary trust
+// entries: they decay with the domain's half-life and are blended away by
+// real agreement data.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::error::ChitinError;
+use chitin_core::IdentityRegistry;
+
+use crate::domain_store::GLOBAL_DOMAIN;
+
+/// DID prefix accepted for genesis validators.
+const DID_PREFIX: &str = "did:chitin:";
+
+/// A pre-trusted validator named in the genesis configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GenesisValidator {
+    /// Validator DID (`did:chitin:<hex coldkey>`).
+    pub did: String,
+    /// Initial trust other genesis validators place in this one, in (0.0, 1.0].
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Domains to seed in addition to "global". Empty seeds only "global".
+    #[serde(default)]
+    pub domains: Vec<String>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// A seeded trust edge: `from` trusts `to` with `value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenesisEdge {
+    /// Trusting node UID.
+    pub from: u16,
+    /// Trusted node UID.
+    pub to: u16,
+    /// Initial trust value.
+    pub value: f64,
+}
+
+/// Genesis trust configuration: the validators trusted at network launch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GenesisTrust {
+    /// Pre-trusted validators.
+    #[serde(default)]
+    pub validators: Vec<GenesisValidator>,
+}
+
+impl GenesisTrust {
+    /// Create a genesis configuration from a list of validators.
+    pub fn new(validators: Vec<GenesisValidator>) -> Self {
+        Self { validators }
+    }
+
+    /// True if no genesis validators are configured.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Check DIDs, weights, and duplicates.
+    pub fn validate(&self) -> Result<(), ChitinError> {
+        let mut seen = HashSet::new();
+        for v in &self.validators {
+            if !v.did.starts_with(DID_PREFIX) || v.did.len() == DID_PREFIX.len() {
+                return Err(ChitinError::InvalidState(format!(
+                    "Genesis validator DID '{}' must start with '{}'",
+                    v.did, DID_PREFIX
+                )));
+            }
+            if !(v.weight > 0.0 && v.weight <= 1.0) {
+                return Err(ChitinError::InvalidState(format!(
+                    "Genesis validator '{}' weight {} is outside (0.0, 1.0]",
+                    v.did, v.weight
+                )));
+            }
+            if !seen.insert(v.did.as_str()) {
+                return Err(ChitinError::InvalidState(format!(
+                    "Genesis validator '{}' is listed twice",
+                    v.did
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the seed edges per domain to the UIDs `identities` holds.
+    ///
+    /// Within each domain every registered genesis validator trusts every
+    /// other one with the target's weight. A validator alone in a domain gets
+    /// self-trust at its own weight. Unregistered DIDs are skipped.
+    pub fn edges(&self, identities: &IdentityRegistry) -> BTreeMap<String, Vec<GenesisEdge>> {
+        let mut members: BTreeMap<String, BTreeMap<u16, f64>> = BTreeMap::new();
+        for v in &self.validators {
+            let uid = match identities.uid_of_did(&v.did) {
+                Some(uid) => uid,
+                None => continue,
+            };
+            let domains = std::iter::once(GLOBAL_DOMAIN).chain(
+                v.domains
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|d| *d != GLOBAL_DOMAIN),
+            );
+            for domain in domains {
+                members
+                    .entry(domain.to_string())
+                    .or_default()
+                    .insert(uid, v.weight);
+            }
+        }
+
+        members
+            .into_iter()
+            .map(|(domain, uids)| {
+                let edges = if uids.len() == 1 {
+                    uids.iter()
+                        .map(|(&uid, &value)| GenesisEdge { from: uid, to: uid, value })
+                        .collect()
+                } else {
+                    uids.keys()
+                        .flat_map(|&from| {
+                            uids.iter()
+                                .filter(move |(&to, _)| to != from)
+                                .map(move |(&to, &value)| GenesisEdge { from, to, value })
+                        })
+                        .collect()
+                };
+                (domain, edges)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::identity::{NodeIdentity, NodeType};
+
+    fn validator(did: &str, weight: f64, domains: &[&str]) -> GenesisValidator {
+        GenesisValidator {
+            did: did.to_string(),
+            weight,
+            domains: domains.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    fn did(key: u8) -> String {
+        NodeIdentity::derive_did(&[key; 32])
+    }
+
+    /// A registry holding the coldkeys `keys`, in order from UID 0.
+    fn registry(keys: &[u8]) -> IdentityRegistry {
+        let mut registry = IdentityRegistry::new();
+        for &key in keys {
+            registry
+                .register([key; 32], [key.wrapping_add(1); 32], NodeType::Tide, 0)
+                .unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn genesis_validators_trust_each_other_globally() {
+        let genesis = GenesisTrust::new(vec![
+            validator(&did(0xaa), 1.0, &[]),
+            validator(&did(0xbb), 0.5, &[]),
+        ]);
+        let edges = genesis.edges(&registry(&[0xaa, 0xbb]));
+
+        assert_eq!(edges.len(), 1);
+        let global = &edges[GLOBAL_DOMAIN];
+        assert_eq!(global.len(), 2);
+        assert!(global.contains(&GenesisEdge { from: 0, to: 1, value: 0.5 }));
+        assert!(global.contains(&GenesisEdge { from: 1, to: 0, value: 1.0 }));
+    }
+
+    #[test]
+    fn domains_and_unresolved_dids() {
+        let genesis = GenesisTrust::new(vec![
+            validator(&did(0xaa), 0.8, &["medical"]),
+            validator(&did(0xbb), 1.0, &[]),
+            validator(&did(0xcc), 1.0, &["medical"]),
+        ]);
+        // cc has not registered yet; aa and bb follow three other nodes.
+        let edges = genesis.edges(&registry(&[0x10, 0x20, 0x30, 0xaa, 0xbb]));
+
+        assert_eq!(edges[GLOBAL_DOMAIN].len(), 2);
+        // aa is alone in "medical": self-trust at its own weight.
+        assert_eq!(edges["medical"], vec![GenesisEdge { from: 3, to: 3, value: 0.8 }]);
+    }
+
+    #[test]
+    fn validate_rejects_bad_entries() {
+        assert!(GenesisTrust::new(vec![validator("did:chitin:aa", 1.0, &[])]).validate().is_ok());
+        assert!(GenesisTrust::new(vec![validator("aa", 1.0, &[])]).validate().is_err());
+        assert!(GenesisTrust::new(vec![validator("did:chitin:aa", 0.0, &[])]).validate().is_err());
+        assert!(GenesisTrust::new(vec![validator("did:chitin:aa", 1.5, &[])]).validate().is_err());
+        assert!(GenesisTrust::new(vec![
+            validator("did:chitin:aa", 1.0, &[]),
+            validator("did:chitin:aa", 0.5, &[]),
+        ])
+        .validate()
+        .is_err());
+    }
+}
@@ -9,5 +9,13 @@
 
 pub mod trust_matrix;
 pub mod openrank;
+pub mod csr;
 pub mod domain;
+pub mod centroid;
 pub mod decay;
+pub mod sybil;
+pub mod domain_store;
+pub mod taxonomy;
+pub mod evidence;
+pub mod genesis;
+pub mod snapshot;
@@ -3,12 +3,20 @@
 // OpenRank integration for context-aware trust in the Chitin Protocol.
 //
 // OpenRank extends EigenTrust with domain-aware, context-sensitive trust
-// computation using personalized PageRank with damping.
+// computation using personalized PageRank with damping. The personalization
+// (teleport) vector sets the vantage point: uniform, stake-weighted, or a
+// trusted seed set such as a domain's pre-trusted nodes.
+//
+// The graph is held in CSR form so each iteration is O(E), and
+// `IncrementalOpenRank` keeps scores across epochs to warm-start iteration
+// when only a few edges changed.
 
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::csr::CsrGraph;
+
 /// Configuration for the OpenRank trust computation algorithm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenRankConfig {
@@ -30,109 +38,244 @@ impl Default for OpenRankConfig {
     }
 }
 
+/// Personalization (teleport) vector for OpenRank.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum Personalization {
+    /// Every node in the graph is equally likely.
+    #[default]
+    Uniform,
+    /// Proportional to each node's stake.
+    Stake(HashMap<u16, u64>),
+    /// Uniform over a trusted seed set (e.g., a domain's pre-trusted nodes).
+    TrustedSet(Vec<u16>),
+}
+
+impl Personalization {
+    /// Resolve the vector over `uids`, normalized to sum to 1.0.
+    ///
+    /// Falls back to uniform when none of the weighted nodes appear in the
+    /// graph, so a stale or empty seed set never zeroes every score.
+    pub fn vector(&self, uids: &[u16]) -> Vec<f64> {
+        let raw: Vec<f64> = match self {
+            Personalization::Uniform => vec![1.0; uids.len()],
+            Personalization::Stake(stakes) => uids
+                .iter()
+                .map(|u| stakes.get(u).copied().unwrap_or(0) as f64)
+                .collect(),
+            Personalization::TrustedSet(trusted) => uids
+                .iter()
+                .map(|u| if trusted.contains(u) { 1.0 } else { 0.0 })
+                .collect(),
+        };
+        let total: f64 = raw.iter().sum();
+        if total <= 0.0 {
+            let uniform = 1.0 / uids.len().max(1) as f64;
+            return vec![uniform; uids.len()];
+        }
+        raw.into_iter().map(|v| v / total).collect()
+    }
+}
+
+/// Compute OpenRank trust scores from a trust matrix with a uniform
+/// personalization vector.
+pub fn compute_openrank(
+    trust: &super::trust_matrix::TrustMatrix,
+    config: &OpenRankConfig,
+) -> HashMap<u16, f64> {
+    compute_personalized_openrank(trust, config, &Personalization::Uniform)
+}
+
 /// Compute OpenRank trust scores from a trust matrix.
 ///
 /// Uses personalized PageRank with damping to compute context-aware
 /// global trust scores that account for domain expertise.
 ///
 /// Algorithm (Personalized PageRank):
-/// 1. Build column-normalized transition matrix from trust entries
-/// 2. Handle dangling nodes (distribute uniform)
+/// 1. Build a sparse (CSR) adjacency of trust entries, normalized per source
+/// 2. Handle dangling nodes (redistribute by personalization)
 /// 3. Power iteration: scores = d * M * scores + (1-d) * personalization
 /// 4. Converge per OpenRankConfig thresholds
-pub fn compute_openrank(
+///
+/// Each iteration is O(E). To reuse the previous epoch's scores as a starting
+/// point, see `compute_openrank_warm` and `IncrementalOpenRank`.
+pub fn compute_personalized_openrank(
+    trust: &super::trust_matrix::TrustMatrix,
+    config: &OpenRankConfig,
+    personalization: &Personalization,
+) -> HashMap<u16, f64> {
+    IncrementalOpenRank::new(trust, config.clone(), personalization.clone()).scores()
+}
+
+/// Compute personalized OpenRank starting from `previous` scores.
+///
+/// When the graph changed little since `previous` was computed, power
+/// iteration converges in far fewer steps. Nodes missing from `previous`
+/// start at their personalization weight.
+pub fn compute_openrank_warm(
     trust: &super::trust_matrix::TrustMatrix,
     config: &OpenRankConfig,
+    personalization: &Personalization,
+    previous: &HashMap<u16, f64>,
 ) -> HashMap<u16, f64> {
-    // Step 1: Collect unique node UIDs
-    let mut uid_set = std::collections::HashSet::new();
-    for &(from, to) in trust.entries.keys() {
-        uid_set.insert(from);
-        uid_set.insert(to);
+    let graph = CsrGraph::from_trust_matrix(trust);
+    let teleport = personalization.vector(graph.uids());
+    let mut scores = warm_start(&graph, &teleport, previous);
+    power_iterate(&graph, config, &teleport, &mut scores);
+    to_map(&graph, &scores)
+}
+
+/// OpenRank state kept across epochs for incremental recomputation.
+///
+/// Holds the CSR graph and the last converged scores. Changing a few edge
+/// weights patches the graph in place; adding edges or nodes rebuilds the
+/// graph. Either way, iteration resumes from the previous scores.
+#[derive(Debug, Clone)]
+pub struct IncrementalOpenRank {
+    config: OpenRankConfig,
+    personalization: Personalization,
+    graph: CsrGraph,
+    /// Personalization vector resolved over the graph's nodes.
+    teleport: Vec<f64>,
+    /// Scores by node index.
+    scores: Vec<f64>,
+    /// Iterations used by the last computation.
+    last_iterations: u32,
+}
+
+impl IncrementalOpenRank {
+    /// Build the graph from a trust matrix and compute scores from scratch.
+    pub fn new(
+        trust: &super::trust_matrix::TrustMatrix,
+        config: OpenRankConfig,
+        personalization: Personalization,
+    ) -> Self {
+        let graph = CsrGraph::from_trust_matrix(trust);
+        let teleport = personalization.vector(graph.uids());
+        let mut state = Self {
+            config,
+            personalization,
+            graph,
+            scores: teleport.clone(),
+            teleport,
+            last_iterations: 0,
+        };
+        state.last_iterations = power_iterate(
+            &state.graph,
+            &state.config,
+            &state.teleport,
+            &mut state.scores,
+        );
+        state
     }
-    if uid_set.is_empty() {
-        return HashMap::new();
+
+    /// Apply changed edge weights and recompute, warm-starting from the
+    /// current scores. Returns the number of iterations used.
+    pub fn update_edges(&mut self, changes: &[((u16, u16), f64)]) -> u32 {
+        let mut structural = Vec::new();
+        for &((from, to), weight) in changes {
+            let weight = weight.clamp(0.0, 1.0);
+            if !self.graph.set_weight(from, to, weight) {
+                structural.push(((from, to), weight));
+            }
+        }
+        if !structural.is_empty() {
+            let edges: Vec<_> = self.graph.edges().chain(structural).collect();
+            self.replace_graph(CsrGraph::from_edges(edges));
+        }
+        self.iterate()
     }
-    let mut uids: Vec<u16> = uid_set.into_iter().collect();
-    uids.sort();
-    let n = uids.len();
-    let uid_to_idx: HashMap<u16, usize> = uids.iter().enumerate().map(|(i, &u)| (u, i)).collect();
-
-    // Step 2: Build adjacency matrix and column-normalize
-    // M[i][j] represents the transition from j to i (column-stochastic)
-    let mut adj = vec![vec![0.0_f64; n]; n];
-    for (&(from, to), &val) in &trust.entries {
-        let i = uid_to_idx[&from]; // source
-        let j = uid_to_idx[&to];   // target
-        // In PageRank, an edge from `from` to `to` means `from` endorses `to`.
-        // In the column-normalized matrix M, M[to][from] = weight / col_sum
-        adj[j][i] = val;
+
+    /// Rebuild the graph from a trust matrix (e.g., after epoch decay) and
+    /// recompute, warm-starting from the current scores. Returns the number
+    /// of iterations used.
+    pub fn rebuild(&mut self, trust: &super::trust_matrix::TrustMatrix) -> u32 {
+        self.replace_graph(CsrGraph::from_trust_matrix(trust));
+        self.iterate()
     }
 
-    // Column-normalize; track dangling nodes (columns that sum to zero)
-    let mut col_sums = vec![0.0_f64; n];
-    for j in 0..n {
-        for i in 0..n {
-            col_sums[j] += adj[i][j];
-        }
+    /// Current scores by node UID.
+    pub fn scores(&self) -> HashMap<u16, f64> {
+        to_map(&self.graph, &self.scores)
     }
-    let mut is_dangling = vec![false; n];
-    for j in 0..n {
-        if col_sums[j] > 0.0 {
-            for i in 0..n {
-                adj[i][j] /= col_sums[j];
-            }
-        } else {
-            is_dangling[j] = true;
-        }
+
+    /// Current score of a node, if it is in the graph.
+    pub fn score(&self, uid: u16) -> Option<f64> {
+        self.graph.index_of(uid).map(|i| self.scores[i])
+    }
+
+    /// Iterations used by the last computation.
+    pub fn last_iterations(&self) -> u32 {
+        self.last_iterations
     }
 
-    // Step 3: Personalization vector (uniform)
-    let uniform = 1.0 / n as f64;
-    let personalization = vec![uniform; n];
+    /// The current sparse trust graph.
+    pub fn graph(&self) -> &CsrGraph {
+        &self.graph
+    }
+
+    fn replace_graph(&mut self, graph: CsrGraph) {
+        let previous = to_map(&self.graph, &self.scores);
+        self.teleport = self.personalization.vector(graph.uids());
+        self.scores = warm_start(&graph, &self.teleport, &previous);
+        self.graph = graph;
+    }
+
+    fn iterate(&mut self) -> u32 {
+        self.last_iterations =
+            power_iterate(&self.graph, &self.config, &self.teleport, &mut self.scores);
+        self.last_iterations
+    }
+}
 
-    // Initialize scores to uniform
-    let mut scores = vec![uniform; n];
+/// Starting vector from previous scores (teleport weight for new nodes),
+/// normalized to sum to 1.0.
+fn warm_start(graph: &CsrGraph, teleport: &[f64], previous: &HashMap<u16, f64>) -> Vec<f64> {
+    let start: Vec<f64> = graph
+        .uids()
+        .iter()
+        .zip(teleport)
+        .map(|(uid, &t)| previous.get(uid).copied().unwrap_or(t).max(0.0))
+        .collect();
+    let total: f64 = start.iter().sum();
+    if total <= 0.0 {
+        return teleport.to_vec();
+    }
+    start.into_iter().map(|v| v / total).collect()
+}
 
+/// Run power iteration in place from `scores`. Returns iterations used.
+fn power_iterate(
+    graph: &CsrGraph,
+    config: &OpenRankConfig,
+    teleport: &[f64],
+    scores: &mut [f64],
+) -> u32 {
+    let n = graph.node_count();
     let d = config.damping_factor;
+    let mut propagated = vec![0.0_f64; n];
+    let mut iterations = 0;
 
-    // Step 4: Power iteration
     for _ in 0..config.max_iterations {
-        let mut new_scores = vec![0.0_f64; n];
-
-        // Compute dangling contribution: sum of scores of dangling nodes
-        let dangling_sum: f64 = scores
-            .iter()
-            .enumerate()
-            .filter(|(j, _)| is_dangling[*j])
-            .map(|(_, &s)| s)
-            .sum();
+        iterations += 1;
+        let dangling_sum = graph.propagate(scores, &mut propagated);
 
         // new_scores = d * (M * scores + dangling_contribution) + (1-d) * personalization
+        // Dangling node mass teleports like the personalization vector.
+        let mut delta = 0.0;
         for i in 0..n {
-            let mut m_times_scores = 0.0;
-            for j in 0..n {
-                m_times_scores += adj[i][j] * scores[j];
-            }
-            // Add uniform share of dangling node mass
-            m_times_scores += dangling_sum * uniform;
-            new_scores[i] = d * m_times_scores + (1.0 - d) * personalization[i];
+            let new_score = d * (propagated[i] + dangling_sum * teleport[i]) + (1.0 - d) * teleport[i];
+            delta += (scores[i] - new_score).abs();
+            scores[i] = new_score;
         }
-
-        // Check convergence (L1 norm)
-        let delta: f64 = scores
-            .iter()
-            .zip(new_scores.iter())
-            .map(|(a, b)| (a - b).abs())
-            .sum();
-        scores = new_scores;
         if delta < config.convergence_threshold {
             break;
         }
     }
+    iterations
+}
 
-    // Return HashMap<u16, f64>
-    uids.iter().enumerate().map(|(i, &uid)| (uid, scores[i])).collect()
+fn to_map(graph: &CsrGraph, scores: &[f64]) -> HashMap<u16, f64> {
+    graph.uids().iter().copied().zip(scores.iter().copied()).collect()
 }
 
 #[cfg(test)]
@@ -227,6 +370,108 @@ mod tests {
         );
     }
 
+    /// Two disjoint cliques: {1, 2} and {3, 4}.
+    fn two_cliques() -> TrustMatrix {
+        let mut tm = TrustMatrix::new();
+        tm.set_trust(1, 2, 1.0);
+        tm.set_trust(2, 1, 1.0);
+        tm.set_trust(3, 4, 1.0);
+        tm.set_trust(4, 3, 1.0);
+        tm
+    }
+
+    #[test]
+    fn uniform_personalization_matches_default() {
+        let tm = two_cliques();
+        let config = OpenRankConfig::default();
+        let a = compute_openrank(&tm, &config);
+        let b = compute_personalized_openrank(&tm, &config, &Personalization::Uniform);
+        for (uid, score) in &a {
+            assert!((score - b[uid]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn trusted_set_shifts_vantage_point() {
+        let tm = two_cliques();
+        let config = OpenRankConfig::default();
+        let result =
+            compute_personalized_openrank(&tm, &config, &Personalization::TrustedSet(vec![1]));
+        // Nothing reaches {3, 4} from the seed, so they get no trust.
+        assert!(result[&1] > 0.4 && result[&2] > 0.4);
+        assert!(result[&3] < 1e-9 && result[&4] < 1e-9);
+    }
+
+    #[test]
+    fn stake_personalization_weights_seeds() {
+        let tm = two_cliques();
+        let config = OpenRankConfig::default();
+        let stakes: HashMap<u16, u64> = [(1, 300), (3, 100)].into_iter().collect();
+        let result = compute_personalized_openrank(&tm, &config, &Personalization::Stake(stakes));
+        let left = result[&1] + result[&2];
+        let right = result[&3] + result[&4];
+        assert!((left - 0.75).abs() < 1e-4, "left={}", left);
+        assert!((right - 0.25).abs() < 1e-4, "right={}", right);
+    }
+
+    #[test]
+    fn unknown_seeds_fall_back_to_uniform() {
+        let p = Personalization::TrustedSet(vec![99]);
+        assert_eq!(p.vector(&[1, 2]), vec![0.5, 0.5]);
+        assert_eq!(Personalization::Stake(HashMap::new()).vector(&[1, 2, 3, 4]), vec![0.25; 4]);
+    }
+
+    /// A ring of `n` nodes with chords, so every node has a few out-edges.
+    fn ring(n: u16) -> TrustMatrix {
+        let mut tm = TrustMatrix::new();
+        for i in 0..n {
+            tm.set_trust(i, (i + 1) % n, 0.9);
+            tm.set_trust(i, (i + 7) % n, 0.4);
+            tm.set_trust(i, (i * 3 + 1) % n, 0.2);
+        }
+        tm
+    }
+
+    fn assert_close(a: &HashMap<u16, f64>, b: &HashMap<u16, f64>, tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (uid, score) in a {
+            assert!((score - b[uid]).abs() < tol, "uid {}: {} vs {}", uid, score, b[uid]);
+        }
+    }
+
+    #[test]
+    fn warm_start_converges_faster_to_same_scores() {
+        let tm = ring(50);
+        let config = OpenRankConfig::default();
+        let cold = IncrementalOpenRank::new(&tm, config.clone(), Personalization::Uniform);
+        let warm =
+            compute_openrank_warm(&tm, &config, &Personalization::Uniform, &cold.scores());
+        assert_close(&cold.scores(), &warm, 1e-6);
+
+        let mut rebuilt = cold.clone();
+        assert!(rebuilt.rebuild(&tm) < cold.last_iterations());
+    }
+
+    #[test]
+    fn incremental_update_matches_full_recompute() {
+        let mut tm = ring(50);
+        let config = OpenRankConfig::default();
+        let mut state = IncrementalOpenRank::new(&tm, config.clone(), Personalization::Uniform);
+        let cold_iterations = state.last_iterations();
+
+        // Patch an existing edge in place.
+        tm.set_trust(3, 4, 0.1);
+        let iterations = state.update_edges(&[((3, 4), 0.1)]);
+        assert!(iterations < cold_iterations);
+        assert_close(&state.scores(), &compute_openrank(&tm, &config), 1e-5);
+
+        // Add an edge and a new node (structural change).
+        tm.set_trust(10, 99, 1.0);
+        state.update_edges(&[((10, 99), 1.0)]);
+        assert_eq!(state.graph().node_count(), 51);
+        assert_close(&state.scores(), &compute_openrank(&tm, &config), 1e-5);
+    }
+
     #[test]
     fn convergence_within_max_iterations() {
         let mut tm = TrustMatrix::new();
@@ -0,0 +1,131 @@
+// crates/chitin-reputation/src/snapshot.rs
+//
+// Versioned, hashed snapshots of reputation state.
+//
+// A snapshot captures every domain trust matrix together with its OpenRank
+// scores at one epoch. The content hash is SHA-256 over a canonical JSON
+// encoding (object keys sorted), so two nodes with the same trust state
+// produce the same hash. Each epoch the daemon records the hash in an
+// EpochCheckpoint; importing a snapshot requires its hash to match the
+// checkpoint for its epoch.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::crypto::hash_bytes;
+use chitin_core::error::ChitinError;
+
+use crate::trust_matrix::TrustMatrix;
+
+/// Current snapshot format version.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Trust state for a single domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainSnapshot {
+    /// The domain's trust matrix.
+    pub matrix: TrustMatrix,
+    /// OpenRank scores computed from the matrix.
+    pub scores: BTreeMap<u16, f64>,
+}
+
+/// Snapshot of all domain trust matrices and scores at an epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationSnapshot {
+    /// Snapshot format version.
+    pub version: u32,
+    /// Epoch the snapshot was taken at.
+    pub epoch: u64,
+    /// Trust state per domain ID.
+    pub domains: BTreeMap<String, DomainSnapshot>,
+    /// Hex-encoded SHA-256 content hash (see `compute_hash`).
+    pub hash: String,
+}
+
+impl ReputationSnapshot {
+    /// Build a snapshot and stamp it with its content hash.
+    pub fn new(epoch: u64, domains: BTreeMap<String, DomainSnapshot>) -> Result<Self, ChitinError> {
+        let mut snapshot = Self {
+            version: SNAPSHOT_VERSION,
+            epoch,
+            domains,
+            hash: String::new(),
+        };
+        snapshot.hash = snapshot.compute_hash()?;
+        Ok(snapshot)
+    }
+
+    /// Hash of the snapshot's version, epoch, and domains (excluding `hash`).
+    pub fn compute_hash(&self) -> Result<String, ChitinError> {
+        // Round-trip through `Value` so map keys are sorted regardless of the
+        // in-memory map types.
+        let canonical = serde_json::to_value((&self.version, &self.epoch, &self.domains))?;
+        let digest = hash_bytes(&serde_json::to_vec(&canonical)?);
+        Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Check the version is supported and the stamped hash matches the content.
+    pub fn verify(&self) -> Result<(), ChitinError> {
+        if self.version != SNAPSHOT_VERSION {
+            return Err(ChitinError::InvalidState(format!(
+                "Unsupported reputation snapshot version {} (expected {})",
+                self.version, SNAPSHOT_VERSION
+            )));
+        }
+        let actual = self.compute_hash()?;
+        if actual != self.hash {
+            return Err(ChitinError::InvalidState(format!(
+                "Reputation snapshot hash mismatch: stamped {}, computed {}",
+                self.hash, actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reputation state hash recorded at the end of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochCheckpoint {
+    /// Epoch number.
+    pub epoch: u64,
+    /// Hash of the reputation snapshot taken at this epoch.
+    pub reputation_hash: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domains() -> BTreeMap<String, DomainSnapshot> {
+        let mut matrix = TrustMatrix::new();
+        matrix.set_trust(1, 2, 0.5);
+        matrix.set_trust(3, 1, 0.25);
+        let scores = [(1, 0.4), (2, 0.4), (3, 0.2)].into_iter().collect();
+        [("medical".to_string(), DomainSnapshot { matrix, scores })]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn hash_is_stable_across_serialization() {
+        let snapshot = ReputationSnapshot::new(7, domains()).unwrap();
+        assert_eq!(snapshot.hash.len(), 64);
+        assert_eq!(snapshot.hash, ReputationSnapshot::new(7, domains()).unwrap().hash);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let decoded: ReputationSnapshot = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify().is_ok());
+    }
+
+    #[test]
+    fn tampering_and_unknown_versions_fail_verification() {
+        let mut snapshot = ReputationSnapshot::new(7, domains()).unwrap();
+        snapshot.domains.get_mut("medical").unwrap().matrix.set_trust(2, 1, 1.0);
+        assert!(snapshot.verify().is_err());
+
+        let mut snapshot = ReputationSnapshot::new(7, domains()).unwrap();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        assert!(snapshot.verify().is_err());
+    }
+}
@@ -0,0 +1,275 @@
+// crates/chitin-reputation/src/sybil.rs
+//
+// Sybil cluster detection over the trust graph.
+//
+// A Sybil attack shows up as a clique of nodes that trust each other
+// strongly but receive little trust from the rest of the network. This pass
+// restricts the graph to low-stake, recently registered nodes, links pairs
+// with strong mutual trust, and finds connected components. Components that
+// are dense, weakly endorsed from outside, and a minority of the network are
+// flagged for consensus (stake exclusion) and slashing.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::trust_matrix::TrustMatrix;
+
+/// Thresholds for Sybil cluster detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SybilConfig {
+    /// Minimum number of members for a cluster to be flagged. Default: 3.
+    pub min_cluster_size: usize,
+    /// Trust required in both directions for a pair to count as mutual. Default: 0.5.
+    pub mutual_trust_threshold: f64,
+    /// Minimum fraction of member pairs with mutual trust. Default: 0.8.
+    pub min_density: f64,
+    /// Nodes at or below this stake (rao) are candidates. Default: 10 CTN.
+    pub max_stake: u64,
+    /// Nodes registered within this many epochs are candidates. Default: 168.
+    pub max_age_epochs: u64,
+    /// Maximum share of a cluster's incoming trust that may come from
+    /// outside it. Default: 0.2.
+    pub max_external_endorsement: f64,
+    /// Maximum fraction of the graph a cluster may cover; larger groups are
+    /// treated as the honest majority. Default: 0.5.
+    pub max_cluster_fraction: f64,
+}
+
+impl Default for SybilConfig {
+    fn default() -> Self {
+        Self {
+            min_cluster_size: 3,
+            mutual_trust_threshold: 0.5,
+            min_density: 0.8,
+            max_stake: 10_000_000_000,
+            max_age_epochs: 168,
+            max_external_endorsement: 0.2,
+            max_cluster_fraction: 0.5,
+        }
+    }
+}
+
+/// Stake and registration data for a node.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeProfile {
+    /// Total stake in rao.
+    pub stake: u64,
+    /// Epoch at which the node registered (or first appeared).
+    pub registered_epoch: u64,
+}
+
+/// A flagged group of suspected Sybil nodes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SybilCluster {
+    /// Member UIDs, sorted.
+    pub members: Vec<u16>,
+    /// Fraction of member pairs with mutual trust, in [0.0, 1.0].
+    pub density: f64,
+    /// Share of incoming trust from non-members, in [0.0, 1.0].
+    pub external_endorsement: f64,
+    /// Combined stake of all members (rao).
+    pub total_stake: u64,
+    /// `density * (1 - external_endorsement)`; higher is more suspicious.
+    pub suspicion: f64,
+}
+
+impl SybilCluster {
+    /// Whether `uid` is a member of this cluster.
+    pub fn contains(&self, uid: u16) -> bool {
+        self.members.binary_search(&uid).is_ok()
+    }
+}
+
+/// All UIDs that belong to any flagged cluster.
+pub fn flagged_uids(clusters: &[SybilCluster]) -> HashSet<u16> {
+    clusters.iter().flat_map(|c| c.members.iter().copied()).collect()
+}
+
+/// Detect suspicious trust cliques in a trust matrix.
+///
+/// Nodes without a profile are never candidates. Returns flagged clusters,
+/// most suspicious first.
+pub fn detect_sybil_clusters(
+    trust: &TrustMatrix,
+    profiles: &HashMap<u16, NodeProfile>,
+    current_epoch: u64,
+    config: &SybilConfig,
+) -> Vec<SybilCluster> {
+    let entries = trust.decayed_entries();
+    let all_nodes: BTreeSet<u16> = entries.keys().flat_map(|&(f, t)| [f, t]).collect();
+    if all_nodes.is_empty() {
+        return Vec::new();
+    }
+
+    let is_candidate = |uid: u16| {
+        profiles.get(&uid).is_some_and(|p| {
+            p.stake <= config.max_stake
+                && current_epoch.saturating_sub(p.registered_epoch) <= config.max_age_epochs
+        })
+    };
+    let trust_of = |from: u16, to: u16| entries.get(&(from, to)).copied().unwrap_or(0.0);
+
+    // Mutual-trust adjacency among candidates.
+    let mut adjacency: HashMap<u16, Vec<u16>> = HashMap::new();
+    for &(from, to) in entries.keys() {
+        if from < to
+            && is_candidate(from)
+            && is_candidate(to)
+            && trust_of(from, to) >= config.mutual_trust_threshold
+            && trust_of(to, from) >= config.mutual_trust_threshold
+        {
+            adjacency.entry(from).or_default().push(to);
+            adjacency.entry(to).or_default().push(from);
+        }
+    }
+
+    // Connected components (BFS), visited in UID order for determinism.
+    let mut starts: Vec<u16> = adjacency.keys().copied().collect();
+    starts.sort_unstable();
+    let mut visited = HashSet::new();
+    let mut clusters = Vec::new();
+    for start in starts {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &adjacency[&node] {
+                if visited.insert(next) {
+                    component.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        component.sort_unstable();
+
+        let size = component.len();
+        if size < config.min_cluster_size
+            || size as f64 / all_nodes.len() as f64 > config.max_cluster_fraction
+        {
+            continue;
+        }
+
+        let members: HashSet<u16> = component.iter().copied().collect();
+        let mutual_edges: usize = component.iter().map(|u| adjacency[u].len()).sum::<usize>() / 2;
+        let density = mutual_edges as f64 / (size * (size - 1) / 2) as f64;
+
+        let (mut internal_in, mut external_in) = (0.0, 0.0);
+        for (&(from, to), &value) in &entries {
+            if from != to && members.contains(&to) {
+                if members.contains(&from) {
+                    internal_in += value;
+                } else {
+                    external_in += value;
+                }
+            }
+        }
+        let total_in = internal_in + external_in;
+        let external_endorsement = if total_in > 0.0 { external_in / total_in } else { 0.0 };
+
+        if density >= config.min_density && external_endorsement <= config.max_external_endorsement {
+            clusters.push(SybilCluster {
+                total_stake: component.iter().map(|u| profiles[u].stake).sum(),
+                members: component,
+                density,
+                external_endorsement,
+                suspicion: density * (1.0 - external_endorsement),
+            });
+        }
+    }
+
+    clusters.sort_by(|a, b| b.suspicion.total_cmp(&a.suspicion));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(stake: u64, registered_epoch: u64) -> NodeProfile {
+        NodeProfile {
+            stake,
+            registered_epoch,
+        }
+    }
+
+    /// Honest nodes 0..5 trust each other; every node gets a profile.
+    fn honest_network() -> (TrustMatrix, HashMap<u16, NodeProfile>) {
+        let mut tm = TrustMatrix::new();
+        let mut profiles = HashMap::new();
+        for i in 0..5u16 {
+            profiles.insert(i, profile(1_000_000_000_000, 0));
+            for j in 0..5u16 {
+                if i != j {
+                    tm.set_trust(i, j, 0.9);
+                }
+            }
+        }
+        (tm, profiles)
+    }
+
+    fn add_clique(tm: &mut TrustMatrix, profiles: &mut HashMap<u16, NodeProfile>, uids: &[u16]) {
+        for &a in uids {
+            profiles.insert(a, profile(1_000, 95));
+            for &b in uids {
+                if a != b {
+                    tm.set_trust(a, b, 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn isolated_new_low_stake_clique_is_flagged() {
+        let (mut tm, mut profiles) = honest_network();
+        add_clique(&mut tm, &mut profiles, &[10, 11, 12]);
+
+        let clusters = detect_sybil_clusters(&tm, &profiles, 100, &SybilConfig::default());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members, vec![10, 11, 12]);
+        assert_eq!(clusters[0].density, 1.0);
+        assert_eq!(clusters[0].external_endorsement, 0.0);
+        assert!(clusters[0].contains(11));
+        assert_eq!(flagged_uids(&clusters).len(), 3);
+    }
+
+    #[test]
+    fn externally_endorsed_clique_is_not_flagged() {
+        let (mut tm, mut profiles) = honest_network();
+        add_clique(&mut tm, &mut profiles, &[10, 11, 12]);
+        for honest in 0..5u16 {
+            for member in [10, 11, 12] {
+                tm.set_trust(honest, member, 0.8);
+            }
+        }
+        let clusters = detect_sybil_clusters(&tm, &profiles, 100, &SybilConfig::default());
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn established_or_staked_nodes_are_not_candidates() {
+        let (mut tm, mut profiles) = honest_network();
+        add_clique(&mut tm, &mut profiles, &[10, 11, 12]);
+
+        // Old registrations.
+        let clusters = detect_sybil_clusters(&tm, &profiles, 1_000, &SybilConfig::default());
+        assert!(clusters.is_empty());
+
+        // One member has real stake: the remaining pair is below min size.
+        profiles.insert(11, profile(1_000_000_000_000, 95));
+        let clusters = detect_sybil_clusters(&tm, &profiles, 100, &SybilConfig::default());
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn honest_majority_is_not_flagged() {
+        // An all-new network where everyone trusts everyone.
+        let mut tm = TrustMatrix::new();
+        let mut profiles = HashMap::new();
+        add_clique(&mut tm, &mut profiles, &[1, 2, 3, 4]);
+        let clusters = detect_sybil_clusters(&tm, &profiles, 100, &SybilConfig::default());
+        assert!(clusters.is_empty());
+    }
+}
@@ -0,0 +1,515 @@
+// crates/chitin-reputation/src/taxonomy.rs
+//
+// Hierarchical Reef Zone taxonomy for the Chitin Protocol.
+//
+// Zone IDs are '/'-separated paths ("code/rust" is a child of "code").
+// The taxonomy is loaded from config as a flat list of zones; missing
+// ancestors are created implicitly so the tree is always closed under
+// parents. Trust rolls up from child zones to their ancestors, and search
+// filters on a zone include all of its descendants.
+//
+// A zone may be limited to some languages ("science/es" to Spanish), and
+// inherits its nearest limited ancestor's languages otherwise. Polyps
+// submitted to a zone are routed down into a child limited to their
+// language, so "science" Polyps in Spanish land in "science/es".
+//
+// A zone may also set a `ZonePolicy` (replication factor, hardening
+// threshold, allowed licenses, maximum size). Each field not set on a zone
+// is inherited from its nearest ancestor that sets it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::error::ChitinError;
+use chitin_core::language::normalize_language_tag;
+use chitin_core::Polyp;
+
+use crate::domain::DomainContext;
+
+/// Separator between zone path segments.
+pub const ZONE_SEPARATOR: char = '/';
+
+/// Zone policy violation code: the Polyp's license is not allowed.
+pub const CODE_LICENSE_NOT_ALLOWED: &str = "license_not_allowed";
+/// Zone policy violation code: the Polyp's content is too large.
+pub const CODE_POLYP_TOO_LARGE: &str = "polyp_too_large";
+
+/// A zone entry as written in configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ZoneDefinition {
+    /// Zone path, e.g. "code/rust".
+    pub id: String,
+    /// Human-readable name. Defaults to the last path segment.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Languages (ISO 639 codes) the zone accepts. Empty inherits the
+    /// parent's, and a zone with no limited ancestor accepts any.
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Storage and hardening policy, written inline in the zone's table.
+    #[serde(flatten)]
+    pub policy: ZonePolicy,
+}
+
+/// Storage and hardening rules for a zone's Polyps. Unset fields inherit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ZonePolicy {
+    /// Shards each Polyp is stored on: its own and the ones after it on
+    /// the ring (default 1).
+    pub replication_factor: Option<u16>,
+    /// Consensus weight a Polyp needs to be approved for hardening,
+    /// overriding the network default.
+    pub hardening_threshold: Option<f64>,
+    /// License identifiers Polyps may carry (case-insensitive, e.g.
+    /// "CC-BY-4.0"). Empty allows any, including none.
+    pub allowed_licenses: Vec<String>,
+    /// Maximum content size in bytes.
+    pub max_polyp_bytes: Option<usize>,
+}
+
+impl ZonePolicy {
+    /// Whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill the fields not set here from `parent`.
+    fn inherit(&mut self, parent: &ZonePolicy) {
+        self.replication_factor = self.replication_factor.or(parent.replication_factor);
+        self.hardening_threshold = self.hardening_threshold.or(parent.hardening_threshold);
+        if self.allowed_licenses.is_empty() {
+            self.allowed_licenses = parent.allowed_licenses.clone();
+        }
+        self.max_polyp_bytes = self.max_polyp_bytes.or(parent.max_polyp_bytes);
+    }
+
+    fn validate(&self, zone_id: &str) -> Result<(), ChitinError> {
+        let invalid = |what: &str| {
+            Err(ChitinError::InvalidState(format!(
+                "Invalid policy for reef zone '{}': {}",
+                zone_id, what
+            )))
+        };
+        if self.replication_factor == Some(0) {
+            return invalid("replication_factor must be at least 1");
+        }
+        if self.hardening_threshold.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+            return invalid("hardening_threshold must be between 0.0 and 1.0");
+        }
+        if self.allowed_licenses.iter().any(|l| l.trim().is_empty()) {
+            return invalid("allowed_licenses must not be empty strings");
+        }
+        if self.max_polyp_bytes == Some(0) {
+            return invalid("max_polyp_bytes must be at least 1");
+        }
+        Ok(())
+    }
+
+    /// The first rule `polyp` breaks, as a violation code and reason.
+    pub fn violation(&self, polyp: &Polyp) -> Option<(&'static str, String)> {
+        if !self.allowed_licenses.is_empty() {
+            let license = polyp.subject.provenance.source.license.as_deref();
+            let allowed = license
+                .is_some_and(|l| self.allowed_licenses.iter().any(|a| a.eq_ignore_ascii_case(l)));
+            if !allowed {
+                return Some((
+                    CODE_LICENSE_NOT_ALLOWED,
+                    format!(
+                        "license {} is not one of {}",
+                        license.unwrap_or("(none)"),
+                        self.allowed_licenses.join(", ")
+                    ),
+                ));
+            }
+        }
+        let size = polyp.subject.payload.content.len();
+        if let Some(max) = self.max_polyp_bytes.filter(|max| size > *max) {
+            return Some((
+                CODE_POLYP_TOO_LARGE,
+                format!("content is {} bytes, the zone allows {}", size, max),
+            ));
+        }
+        None
+    }
+}
+
+/// A tree of Reef Zones keyed by path.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DomainTaxonomy {
+    zones: BTreeMap<String, DomainContext>,
+    /// Zones limited to some languages, with their normalized codes.
+    #[serde(default)]
+    languages: BTreeMap<String, Vec<String>>,
+    /// Zones setting a policy, as written (not inherited).
+    #[serde(default)]
+    policies: BTreeMap<String, ZonePolicy>,
+}
+
+impl DomainTaxonomy {
+    /// Build a taxonomy from zone definitions, creating missing ancestors.
+    ///
+    /// Fails if any zone ID is empty or has empty path segments, any
+    /// language is not an ISO 639 code, or a policy is out of range.
+    pub fn from_zones(definitions: &[ZoneDefinition]) -> Result<Self, ChitinError> {
+        let mut zones = BTreeMap::new();
+        let mut languages = BTreeMap::new();
+        let mut policies = BTreeMap::new();
+        for def in definitions {
+            validate_zone_id(&def.id)?;
+            if !def.policy.is_empty() {
+                def.policy.validate(&def.id)?;
+                policies.insert(def.id.clone(), def.policy.clone());
+            }
+            if !def.languages.is_empty() {
+                let codes = def
+                    .languages
+                    .iter()
+                    .map(|tag| normalize_language_tag(tag))
+                    .collect::<Result<Vec<_>, _>>()?;
+                languages.insert(def.id.clone(), codes);
+            }
+            for ancestor in ancestors_of(&def.id) {
+                zones.entry(ancestor.clone()).or_insert_with(|| DomainContext {
+                    name: last_segment(&ancestor).to_string(),
+                    domain_id: ancestor,
+                });
+            }
+            zones.insert(
+                def.id.clone(),
+                DomainContext {
+                    domain_id: def.id.clone(),
+                    name: def
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| last_segment(&def.id).to_string()),
+                },
+            );
+        }
+        Ok(Self {
+            zones,
+            languages,
+            policies,
+        })
+    }
+
+    /// The built-in zones covered by the keyword `DomainClassifier`.
+    pub fn default_zones() -> Vec<ZoneDefinition> {
+        [
+            ("medical", "Medical & Health"),
+            ("code", "Programming"),
+            ("code/python", "Python Programming"),
+            ("code/rust", "Rust Programming"),
+            ("science", "Science & Research"),
+            ("finance", "Finance & Economics"),
+            ("legal", "Legal & Compliance"),
+        ]
+        .iter()
+        .map(|(id, name)| ZoneDefinition {
+            id: id.to_string(),
+            name: Some(name.to_string()),
+            languages: Vec::new(),
+            policy: ZonePolicy::default(),
+        })
+        .collect()
+    }
+
+    /// Whether `zone_id` is a known zone.
+    pub fn contains(&self, zone_id: &str) -> bool {
+        self.zones.contains_key(zone_id)
+    }
+
+    /// Look up a zone.
+    pub fn get(&self, zone_id: &str) -> Option<&DomainContext> {
+        self.zones.get(zone_id)
+    }
+
+    /// All zones, in path order (parents before children).
+    pub fn zones(&self) -> impl Iterator<Item = &DomainContext> {
+        self.zones.values()
+    }
+
+    /// Reject zone IDs that are not in the taxonomy.
+    pub fn validate(&self, zone_id: &str) -> Result<(), ChitinError> {
+        if self.contains(zone_id) {
+            Ok(())
+        } else {
+            Err(ChitinError::NotFound(format!(
+                "Reef zone '{}' is not in the taxonomy",
+                zone_id
+            )))
+        }
+    }
+
+    /// Languages `zone_id` accepts: its own, else its nearest limited
+    /// ancestor's. `None` accepts any language.
+    pub fn languages(&self, zone_id: &str) -> Option<&[String]> {
+        std::iter::once(zone_id.to_string())
+            .chain(ancestors_of(zone_id))
+            .find_map(|zone| self.languages.get(&zone))
+            .map(Vec::as_slice)
+    }
+
+    /// The policy for Polyps in `zone_id` (none for unzoned Polyps), with
+    /// fields inherited from ancestors.
+    pub fn policy(&self, zone_id: Option<&str>) -> ZonePolicy {
+        let mut policy = ZonePolicy::default();
+        if let Some(zone_id) = zone_id {
+            for zone in std::iter::once(zone_id.to_string()).chain(ancestors_of(zone_id)) {
+                if let Some(own) = self.policies.get(&zone) {
+                    policy.inherit(own);
+                }
+            }
+        }
+        policy
+    }
+
+    /// Zones setting a replication factor, with the factor. Their
+    /// subzones inherit it unless they set their own.
+    pub fn replication_factors(&self) -> BTreeMap<String, u16> {
+        self.policies
+            .iter()
+            .filter_map(|(zone, p)| p.replication_factor.map(|r| (zone.clone(), r)))
+            .collect()
+    }
+
+    /// Route a Polyp in `language` submitted to `zone_id` down through
+    /// children limited to that language, and check the zone it lands in
+    /// accepts the language. Polyps of unknown language stay where they
+    /// were submitted.
+    pub fn route(&self, zone_id: &str, language: Option<&str>) -> Result<String, ChitinError> {
+        let Some(language) = language else {
+            return Ok(zone_id.to_string());
+        };
+        let language = normalize_language_tag(language)?;
+        let mut zone = zone_id.to_string();
+        while let Some(child) = self.children(&zone).into_iter().find(|child| {
+            self.languages
+                .get(&child.domain_id)
+                .is_some_and(|codes| codes.contains(&language))
+        }) {
+            zone = child.domain_id.clone();
+        }
+        match self.languages(&zone) {
+            Some(codes) if !codes.contains(&language) => Err(ChitinError::InvalidState(format!(
+                "Reef zone '{}' accepts {} Polyps, not '{}'",
+                zone,
+                codes.join("/"),
+                language
+            ))),
+            _ => Ok(zone),
+        }
+    }
+
+    /// Direct parent of a zone, if it has one.
+    pub fn parent(&self, zone_id: &str) -> Option<&DomainContext> {
+        zone_id
+            .rsplit_once(ZONE_SEPARATOR)
+            .and_then(|(parent, _)| self.zones.get(parent))
+    }
+
+    /// Direct children of a zone.
+    pub fn children(&self, zone_id: &str) -> Vec<&DomainContext> {
+        self.zones
+            .values()
+            .filter(|z| {
+                z.domain_id
+                    .rsplit_once(ZONE_SEPARATOR)
+                    .is_some_and(|(parent, _)| parent == zone_id)
+            })
+            .collect()
+    }
+
+    /// Roll grouped items up the tree: each zone's items are also added to
+    /// every ancestor. Items for zones not in the taxonomy are dropped.
+    ///
+    /// Used to build per-zone trust updates where "code" aggregates the
+    /// evidence of "code/python" and "code/rust".
+    pub fn rollup<T: Clone>(&self, groups: &BTreeMap<String, Vec<T>>) -> BTreeMap<String, Vec<T>> {
+        let mut out: BTreeMap<String, Vec<T>> = BTreeMap::new();
+        for (zone_id, items) in groups {
+            if !self.contains(zone_id) {
+                continue;
+            }
+            out.entry(zone_id.clone()).or_default().extend(items.iter().cloned());
+            for ancestor in ancestors_of(zone_id) {
+                out.entry(ancestor).or_default().extend(items.iter().cloned());
+            }
+        }
+        out
+    }
+}
+
+impl Default for DomainTaxonomy {
+    fn default() -> Self {
+        Self::from_zones(&Self::default_zones()).expect("built-in zones are valid")
+    }
+}
+
+/// Whether `zone_id` is `ancestor` or one of its descendants.
+///
+/// `is_within("code/rust", "code")` is true; `is_within("codex", "code")` is not.
+pub fn is_within(zone_id: &str, ancestor: &str) -> bool {
+    zone_id == ancestor
+        || (zone_id.starts_with(ancestor)
+            && zone_id[ancestor.len()..].starts_with(ZONE_SEPARATOR))
+}
+
+/// Ancestors of a zone path, nearest first (excluding the zone itself).
+fn ancestors_of(zone_id: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = zone_id;
+    while let Some((parent, _)) = current.rsplit_once(ZONE_SEPARATOR) {
+        out.push(parent.to_string());
+        current = parent;
+    }
+    out
+}
+
+fn last_segment(zone_id: &str) -> &str {
+    zone_id.rsplit(ZONE_SEPARATOR).next().unwrap_or(zone_id)
+}
+
+fn validate_zone_id(zone_id: &str) -> Result<(), ChitinError> {
+    if zone_id.split(ZONE_SEPARATOR).any(|s| s.trim().is_empty()) {
+        return Err(ChitinError::InvalidState(format!(
+            "Invalid reef zone id '{}': empty path segment",
+            zone_id
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(id: &str) -> ZoneDefinition {
+        ZoneDefinition {
+            id: id.to_string(),
+            name: None,
+            languages: Vec::new(),
+            policy: ZonePolicy::default(),
+        }
+    }
+
+    fn language_zone(id: &str, languages: &[&str]) -> ZoneDefinition {
+        ZoneDefinition {
+            languages: languages.iter().map(|l| l.to_string()).collect(),
+            ..zone(id)
+        }
+    }
+
+    #[test]
+    fn missing_ancestors_are_created() {
+        let t = DomainTaxonomy::from_zones(&[zone("code/rust/async")]).unwrap();
+        assert!(t.contains("code"));
+        assert!(t.contains("code/rust"));
+        assert_eq!(t.parent("code/rust/async").unwrap().domain_id, "code/rust");
+        assert!(t.parent("code").is_none());
+        assert_eq!(t.get("code/rust").unwrap().name, "rust");
+    }
+
+    #[test]
+    fn malformed_ids_are_rejected() {
+        assert!(DomainTaxonomy::from_zones(&[zone("")]).is_err());
+        assert!(DomainTaxonomy::from_zones(&[zone("code//rust")]).is_err());
+        assert!(DomainTaxonomy::from_zones(&[zone("code/")]).is_err());
+    }
+
+    #[test]
+    fn validate_unknown_zone() {
+        let t = DomainTaxonomy::default();
+        assert!(t.validate("code/python").is_ok());
+        assert!(t.validate("code/haskell").is_err());
+    }
+
+    #[test]
+    fn children_are_direct_only() {
+        let t = DomainTaxonomy::from_zones(&[zone("code/rust/async"), zone("code/python")]).unwrap();
+        let children: Vec<&str> = t.children("code").iter().map(|z| z.domain_id.as_str()).collect();
+        assert_eq!(children, vec!["code/python", "code/rust"]);
+    }
+
+    #[test]
+    fn within_respects_segment_boundaries() {
+        assert!(is_within("code/rust", "code"));
+        assert!(is_within("code", "code"));
+        assert!(!is_within("codex", "code"));
+        assert!(!is_within("code", "code/rust"));
+    }
+
+    #[test]
+    fn polyps_are_routed_to_zones_in_their_language() {
+        let t = DomainTaxonomy::from_zones(&[
+            zone("science"),
+            language_zone("science/es", &["es"]),
+            language_zone("science/es/biology", &["spa"]),
+            language_zone("legal", &["en", "fr-CA"]),
+        ])
+        .unwrap();
+        assert_eq!(t.route("science", Some("es-MX")).unwrap(), "science/es/biology");
+        assert_eq!(t.route("science", Some("en")).unwrap(), "science");
+        assert_eq!(t.route("science", None).unwrap(), "science");
+        assert_eq!(t.languages("legal"), Some(&["en".to_string(), "fr".to_string()][..]));
+        assert!(t.route("legal", Some("fr")).is_ok());
+        assert!(t.route("legal", Some("de")).is_err());
+        assert!(t.route("science/es", Some("en")).is_err());
+        assert!(DomainTaxonomy::from_zones(&[language_zone("x", &["english"])]).is_err());
+    }
+
+    #[test]
+    fn policies_inherit_field_by_field() {
+        let mut medical = zone("medical");
+        medical.policy = ZonePolicy {
+            replication_factor: Some(3),
+            hardening_threshold: Some(0.6),
+            allowed_licenses: vec!["CC-BY-4.0".to_string()],
+            max_polyp_bytes: None,
+        };
+        let mut oncology = zone("medical/oncology");
+        oncology.policy.max_polyp_bytes = Some(16);
+        oncology.policy.replication_factor = Some(5);
+        let t = DomainTaxonomy::from_zones(&[medical, oncology, zone("code")]).unwrap();
+
+        let policy = t.policy(Some("medical/oncology/trials"));
+        assert_eq!(policy.replication_factor, Some(5));
+        assert_eq!(policy.hardening_threshold, Some(0.6));
+        assert_eq!(policy.allowed_licenses, vec!["CC-BY-4.0".to_string()]);
+        assert_eq!(policy.max_polyp_bytes, Some(16));
+        assert!(t.policy(Some("code")).is_empty());
+        assert!(t.policy(None).is_empty());
+        assert_eq!(t.replication_factors().len(), 2);
+
+        let mut bad = zone("legal");
+        bad.policy.hardening_threshold = Some(1.5);
+        assert!(DomainTaxonomy::from_zones(&[bad]).is_err());
+    }
+
+    #[test]
+    fn zone_policies_parse_inline() {
+        let def: ZoneDefinition = serde_json::from_value(serde_json::json!({
+            "id": "medical",
+            "replication_factor": 3,
+            "allowed_licenses": ["CC0-1.0"],
+        }))
+        .unwrap();
+        assert_eq!(def.policy.replication_factor, Some(3));
+        assert_eq!(def.policy.allowed_licenses, vec!["CC0-1.0".to_string()]);
+    }
+
+    #[test]
+    fn rollup_aggregates_into_parents() {
+        let t = DomainTaxonomy::default();
+        let mut groups = BTreeMap::new();
+        groups.insert("code/rust".to_string(), vec![1]);
+        groups.insert("code/python".to_string(), vec![2]);
+        groups.insert("unknown".to_string(), vec![3]);
+
+        let rolled = t.rollup(&groups);
+        assert_eq!(rolled["code"], vec![2, 1]);
+        assert_eq!(rolled["code/rust"], vec![1]);
+        assert!(!rolled.contains_key("unknown"));
+    }
+}
@@ -4,11 +4,22 @@
 //
 // Each entry T(from, to) represents how much node `from` trusts node `to`,
 // based on historical scoring agreement and Polyp quality.
+//
+// Entries decay with time since they were last refreshed. Decay is applied
+// lazily on read (`get_trust`, global trust computation) and materialized in
+// batches at epoch boundaries (`advance_epoch`), so inactive nodes lose
+// influence even if nobody touches their edges.
 
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::decay::{apply_decay, DecayFunction};
+
+/// Edges whose decayed trust falls below this value are pruned at epoch
+/// boundaries to keep the matrix sparse.
+pub const PRUNE_THRESHOLD: f64 = 1e-6;
+
 /// A sparse trust matrix where T(from, to) = trust value.
 ///
 /// Trust values range from 0.0 (no trust) to 1.0 (full trust).
@@ -17,30 +28,133 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrustMatrix {
     /// Sparse trust entries: (from_uid, to_uid) -> trust_value.
+    ///
+    /// Values are as of the epoch recorded in `materialized_at`; use
+    /// `get_trust` or `decayed_entries` for the value at the current epoch.
+    #[serde(with = "edge_map")]
     pub entries: HashMap<(u16, u16), f64>,
+    /// Epoch at which each entry was last refreshed by an interaction.
+    #[serde(default, with = "edge_map")]
+    pub updated_at: HashMap<(u16, u16), u64>,
+    /// Epoch to which each entry's stored value has been decayed: its last
+    /// refresh or the last `advance_epoch`, whichever is later. Falls back to
+    /// `updated_at` for entries without one.
+    #[serde(default, with = "edge_map")]
+    pub materialized_at: HashMap<(u16, u16), u64>,
+    /// The matrix's current epoch (advanced at epoch boundaries).
+    #[serde(default)]
+    pub epoch: u64,
+    /// Decay applied to entries as epochs pass.
+    #[serde(default)]
+    pub decay: DecayFunction,
+    /// Epoch at which each node first appeared on any edge.
+    #[serde(default)]
+    pub first_seen: HashMap<u16, u64>,
 }
 
 impl TrustMatrix {
-    /// Create a new empty trust matrix.
+    /// Create a new empty trust matrix with the default decay function.
     pub fn new() -> Self {
+        Self::with_decay(DecayFunction::default())
+    }
+
+    /// Create a new empty trust matrix with the given decay function.
+    pub fn with_decay(decay: DecayFunction) -> Self {
         Self {
             entries: HashMap::new(),
+            updated_at: HashMap::new(),
+            materialized_at: HashMap::new(),
+            epoch: 0,
+            decay,
+            first_seen: HashMap::new(),
         }
     }
 
-    /// Set the trust value from node `from` to node `to`.
+    /// Set the trust value from node `from` to node `to` at the current epoch.
     ///
     /// Values are clamped to [0.0, 1.0].
     pub fn set_trust(&mut self, from: u16, to: u16, value: f64) {
         let clamped = value.clamp(0.0, 1.0);
         self.entries.insert((from, to), clamped);
+        self.updated_at.insert((from, to), self.epoch);
+        self.materialized_at.insert((from, to), self.epoch);
+        self.first_seen.entry(from).or_insert(self.epoch);
+        self.first_seen.entry(to).or_insert(self.epoch);
+    }
+
+    /// Record an interaction at `epoch`, refreshing the edge's decay clock.
+    ///
+    /// Advances the matrix to `epoch` first if it is ahead of the current epoch.
+    pub fn record_interaction(&mut self, from: u16, to: u16, value: f64, epoch: u64) {
+        if epoch > self.epoch {
+            self.epoch = epoch;
+        }
+        self.set_trust(from, to, value);
     }
 
-    /// Get the trust value from node `from` to node `to`.
+    /// Get the trust value from node `from` to node `to` at the current epoch.
     ///
     /// Returns 0.0 if no trust relationship exists.
     pub fn get_trust(&self, from: u16, to: u16) -> f64 {
-        self.entries.get(&(from, to)).copied().unwrap_or(0.0)
+        self.entries
+            .get(&(from, to))
+            .map(|&v| self.decayed_value((from, to), v))
+            .unwrap_or(0.0)
+    }
+
+    /// Epoch at which the edge was last refreshed, if it exists.
+    pub fn last_interaction(&self, from: u16, to: u16) -> Option<u64> {
+        self.updated_at.get(&(from, to)).copied()
+    }
+
+    /// Epoch at which `uid` first appeared on any edge.
+    pub fn first_seen(&self, uid: u16) -> Option<u64> {
+        self.first_seen.get(&uid).copied()
+    }
+
+    /// All entries with decay applied up to the current epoch.
+    pub fn decayed_entries(&self) -> HashMap<(u16, u16), f64> {
+        self.entries
+            .iter()
+            .map(|(&edge, &v)| (edge, self.decayed_value(edge, v)))
+            .collect()
+    }
+
+    /// Advance the matrix to `epoch` and materialize decay for every edge.
+    ///
+    /// Called at epoch boundaries. Edges that decay below `PRUNE_THRESHOLD`
+    /// are removed. Materializing does not count as an interaction, so
+    /// `last_interaction` is unchanged. Returns the number of pruned edges.
+    pub fn advance_epoch(&mut self, epoch: u64) -> usize {
+        if epoch > self.epoch {
+            self.epoch = epoch;
+        }
+        let decayed = self.decayed_entries();
+        let mut pruned = 0;
+        for (edge, value) in decayed {
+            if value < PRUNE_THRESHOLD {
+                self.entries.remove(&edge);
+                self.updated_at.remove(&edge);
+                self.materialized_at.remove(&edge);
+                pruned += 1;
+            } else {
+                self.entries.insert(edge, value);
+                self.materialized_at.insert(edge, self.epoch);
+            }
+        }
+        pruned
+    }
+
+    /// Apply decay to a stored value based on epochs since it was last
+    /// materialized.
+    fn decayed_value(&self, edge: (u16, u16), value: f64) -> f64 {
+        let since = self
+            .materialized_at
+            .get(&edge)
+            .or_else(|| self.updated_at.get(&edge))
+            .copied()
+            .unwrap_or(self.epoch);
+        apply_decay(value, self.epoch.saturating_sub(since), &self.decay)
     }
 
     /// Compute global trust scores using EigenTrust-style iterative aggregation.
@@ -52,8 +166,9 @@ impl TrustMatrix {
     /// local trust into global trust until convergence.
     pub fn compute_global_trust(&self) -> HashMap<u16, f64> {
         // Step 1: Collect unique node UIDs
+        let entries = self.decayed_entries();
         let mut uid_set = std::collections::HashSet::new();
-        for &(from, to) in self.entries.keys() {
+        for &(from, to) in entries.keys() {
             uid_set.insert(from);
             uid_set.insert(to);
         }
@@ -68,22 +183,22 @@ impl TrustMatrix {
         // Step 2: Build row-normalized trust matrix C[i][j]
         // C[i][j] = local_trust(i,j) / sum_k(local_trust(i,k))
         let mut c = vec![vec![0.0_f64; n]; n];
-        for (&(from, to), &val) in &self.entries {
+        for (&(from, to), &val) in &entries {
             let i = uid_to_idx[&from];
             let j = uid_to_idx[&to];
             c[i][j] = val;
         }
         // Row-normalize; if row is all zeros, use uniform
-        for i in 0..n {
-            let row_sum: f64 = c[i].iter().sum();
+        for row in c.iter_mut() {
+            let row_sum: f64 = row.iter().sum();
             if row_sum > 0.0 {
-                for j in 0..n {
-                    c[i][j] /= row_sum;
+                for cell in row.iter_mut() {
+                    *cell /= row_sum;
                 }
             } else {
                 // Uniform distribution for dangling nodes
-                for j in 0..n {
-                    c[i][j] = 1.0 / n as f64;
+                for cell in row.iter_mut() {
+                    *cell = 1.0 / n as f64;
                 }
             }
         }
@@ -124,6 +239,35 @@ impl TrustMatrix {
     }
 }
 
+/// Serde helper for edge-keyed maps.
+///
+/// JSON object keys must be strings, so `(from, to)` keys are serialized as a
+/// list of `[from, to, value]` triples, sorted by edge for deterministic output.
+pub(crate) mod edge_map {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, V>(map: &HashMap<(u16, u16), V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        let mut edges: Vec<(u16, u16, &V)> = map.iter().map(|(&(f, t), v)| (f, t, v)).collect();
+        edges.sort_by_key(|&(f, t, _)| (f, t));
+        serializer.collect_seq(edges)
+    }
+
+    pub fn deserialize<'de, D, V>(deserializer: D) -> Result<HashMap<(u16, u16), V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let edges: Vec<(u16, u16, V)> = Vec::deserialize(deserializer)?;
+        Ok(edges.into_iter().map(|(f, t, v)| ((f, t), v)).collect())
+    }
+}
+
 impl Default for TrustMatrix {
     fn default() -> Self {
         Self::new()
@@ -196,6 +340,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trust_decays_lazily_on_read() {
+        let mut tm = TrustMatrix::with_decay(DecayFunction::half_life(10));
+        tm.record_interaction(1, 2, 0.8, 5);
+        assert!((tm.get_trust(1, 2) - 0.8).abs() < 1e-12);
+
+        // Moving the clock forward without materializing still decays reads.
+        tm.epoch = 15;
+        assert!((tm.get_trust(1, 2) - 0.4).abs() < 1e-12);
+        // The stored value is untouched until the next batch.
+        assert!((tm.entries[&(1, 2)] - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn batched_decay_matches_lazy_decay() {
+        let mut lazy = TrustMatrix::with_decay(DecayFunction::half_life(4));
+        let mut batched = lazy.clone();
+        lazy.record_interaction(1, 2, 1.0, 0);
+        batched.record_interaction(1, 2, 1.0, 0);
+
+        lazy.epoch = 12;
+        for epoch in 1..=12 {
+            batched.advance_epoch(epoch);
+        }
+        assert!((lazy.get_trust(1, 2) - batched.get_trust(1, 2)).abs() < 1e-12);
+        assert!((batched.get_trust(1, 2) - 0.125).abs() < 1e-12);
+        // Materializing decay is not an interaction.
+        assert_eq!(batched.last_interaction(1, 2), Some(0));
+    }
+
+    #[test]
+    fn refreshed_edges_outlast_inactive_ones() {
+        let mut tm = TrustMatrix::with_decay(DecayFunction::half_life(2));
+        tm.record_interaction(1, 2, 1.0, 0);
+        tm.record_interaction(1, 3, 1.0, 0);
+        for epoch in 1..=6 {
+            tm.advance_epoch(epoch);
+            // Only node 2 keeps interacting.
+            tm.record_interaction(1, 2, 1.0, epoch);
+        }
+        assert!((tm.get_trust(1, 2) - 1.0).abs() < 1e-12);
+        assert!((tm.get_trust(1, 3) - 0.125).abs() < 1e-12);
+
+        let global = tm.compute_global_trust();
+        assert!(global[&2] > global[&3]);
+    }
+
+    #[test]
+    fn advance_epoch_prunes_fully_decayed_edges() {
+        let mut tm = TrustMatrix::with_decay(DecayFunction::half_life(1));
+        tm.record_interaction(1, 2, 1.0, 0);
+        let pruned = tm.advance_epoch(40);
+        assert_eq!(pruned, 1);
+        assert!(tm.entries.is_empty());
+        assert_eq!(tm.get_trust(1, 2), 0.0);
+    }
+
+    #[test]
+    fn json_roundtrip_preserves_edges_and_clock() {
+        let mut tm = TrustMatrix::with_decay(DecayFunction::half_life(5));
+        tm.record_interaction(3, 1, 0.7, 4);
+        tm.record_interaction(1, 2, 0.9, 6);
+        let json = serde_json::to_string(&tm).unwrap();
+        let back: TrustMatrix = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.epoch, 6);
+        assert_eq!(back.last_interaction(3, 1), Some(4));
+        assert_eq!(back.first_seen(1), Some(4));
+        assert_eq!(back.first_seen(2), Some(6));
+        assert!((back.get_trust(3, 1) - tm.get_trust(3, 1)).abs() < 1e-12);
+        assert!((back.get_trust(1, 2) - 0.9).abs() < 1e-12);
+    }
+
     #[test]
     fn sybil_resistance_untrusted_sybils_get_low_scores() {
         let mut tm = TrustMatrix::new();
@@ -0,0 +1,669 @@
+// crates/chitin-rpc/src/auth.rs
+//
+// Request authorization by key role.
+//
+// A node's coldkey holds its funds and its hotkey runs the node, so the two
+// sign different things: transfers, stakes, and unstakes must come from a
+// coldkey, while scores, beacon contributions, announcements, and gossip
+// come from a hotkey. `METHOD_POLICIES` names the role each sensitive method
+// needs. A request is signed either by its payload (transfers, stakes,
+// scores, and beacon contributions carry their own signature by the key in
+// `signer_field`) or by a `SignedRequest` envelope around the params: the
+// payload, the signer and its role, a nonce, and a signature over all of them
+// and the method. Gossip envelopes are also bound to the sender their payload
+// names (`sender`), so a node cannot relay Polyps or state updates as
+// another. `RequestAuth` verifies envelopes, rejects nonces that are stale
+// or already used by their signer, and, with an identity registry, accepts
+// only registered hotkeys where a hotkey is needed and rejects a hotkey used
+// where a coldkey is needed. Unsigned requests to envelope-only methods
+// (gossip) are refused when `require_signed` is set.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_core::crypto;
+use chitin_core::identity::NodeIdentity;
+use chitin_core::{ChitinError, IdentityRegistry};
+
+use crate::handlers::validation::{decode_hex, encode_hex};
+
+/// Signers tracked before signers with no recent nonces are pruned.
+const MAX_TRACKED_SIGNERS: usize = 10_000;
+
+/// Settings for request authorization (`[request_auth]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestAuthConfig {
+    /// Refuse unsigned requests to methods that can only be signed by an
+    /// envelope (gossip).
+    pub require_signed: bool,
+    /// Largest distance between an envelope's nonce (milliseconds since the
+    /// Unix epoch) and this node's clock, in seconds (0 for no limit).
+    pub max_clock_skew_secs: u64,
+}
+
+impl Default for RequestAuthConfig {
+    fn default() -> Self {
+        Self {
+            require_signed: false,
+            max_clock_skew_secs: 300,
+        }
+    }
+}
+
+/// Which of a node's keys signs a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyRole {
+    /// The key holding funds and stake.
+    Coldkey,
+    /// The key the node runs with.
+    Hotkey,
+}
+
+impl KeyRole {
+    /// Label used in envelopes and errors.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyRole::Coldkey => "coldkey",
+            KeyRole::Hotkey => "hotkey",
+        }
+    }
+}
+
+/// The key role a method needs, and where its payload names its signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MethodPolicy {
+    /// RPC method name.
+    pub method: &'static str,
+    /// Role of the key that must sign it.
+    pub role: KeyRole,
+    /// Payload field holding the hex key that signs the payload itself, if
+    /// the payload is signed (otherwise only an envelope signs it).
+    pub signer_field: Option<&'static str>,
+    /// Where an envelope-only payload names its sender, which the envelope's
+    /// signer must be.
+    pub sender: Option<Sender>,
+}
+
+/// A payload field naming the sender of an envelope-only request, as a
+/// JSON pointer into the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sender {
+    /// The sender's key, hex or a byte array; the signer must be this key.
+    Key(&'static str),
+    /// The sender's DID; the signer must be the DID's registered hotkey.
+    Did(&'static str),
+}
+
+/// Methods that must be signed by a particular key role.
+pub const METHOD_POLICIES: &[MethodPolicy] = &[
+    MethodPolicy {
+        method: "wallet/transfer",
+        role: KeyRole::Coldkey,
+        signer_field: Some("from_coldkey"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "staking/stake",
+        role: KeyRole::Coldkey,
+        signer_field: Some("staker_coldkey"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "staking/unstake",
+        role: KeyRole::Coldkey,
+        signer_field: Some("staker_coldkey"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "validation/scores",
+        role: KeyRole::Hotkey,
+        signer_field: Some("validator_hotkey"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "validation/beacon",
+        role: KeyRole::Hotkey,
+        signer_field: Some("validator_hotkey"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "peer/announce",
+        role: KeyRole::Hotkey,
+        signer_field: Some("signer"),
+        sender: None,
+    },
+    MethodPolicy {
+        method: "peer/receive_polyp",
+        role: KeyRole::Hotkey,
+        signer_field: None,
+        sender: Some(Sender::Did("/source_did")),
+    },
+    MethodPolicy {
+        method: "peer/receive_state_update",
+        role: KeyRole::Hotkey,
+        signer_field: None,
+        sender: Some(Sender::Key("/update/signer")),
+    },
+];
+
+/// The policy of `method`, if it has one.
+pub fn policy(method: &str) -> Option<&'static MethodPolicy> {
+    METHOD_POLICIES.iter().find(|p| p.method == method)
+}
+
+/// A strictly increasing nonce for this process: the current time in
+/// milliseconds, or one past the last nonce if the clock has not moved on.
+pub fn next_nonce() -> u64 {
+    static LAST: AtomicU64 = AtomicU64::new(0);
+    let now = now_millis();
+    let previous = LAST
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+            Some(now.max(last + 1))
+        })
+        .unwrap_or(now);
+    now.max(previous + 1)
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Request params signed by one of the sender's keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedRequest {
+    /// The method's params.
+    pub payload: serde_json::Value,
+    /// Hex-encoded public key of the signer.
+    pub signer: String,
+    /// Which of the sender's keys `signer` is.
+    pub role: KeyRole,
+    /// Milliseconds since the Unix epoch, unique per signer.
+    pub nonce: u64,
+    /// Hex ed25519 signature over `signable_bytes(method)` by `signer`.
+    pub signature: String,
+}
+
+impl SignedRequest {
+    /// Sign `payload` for `method` with the `role` key `signer`, using the
+    /// next nonce.
+    pub fn sign(
+        method: &str,
+        payload: serde_json::Value,
+        role: KeyRole,
+        signing_key: &[u8; 32],
+        signer: [u8; 32],
+    ) -> Result<Self, ChitinError> {
+        let mut request = Self {
+            payload,
+            signer: encode_hex(&signer),
+            role,
+            nonce: next_nonce(),
+            signature: String::new(),
+        };
+        let signature = crypto::sign_message(signing_key, &request.signable_bytes(method)?)?;
+        request.signature = encode_hex(&signature);
+        Ok(request)
+    }
+
+    /// Canonical bytes to sign: SHA-256 of the JSON-encoded method, payload,
+    /// signer, role, and nonce.
+    pub fn signable_bytes(&self, method: &str) -> Result<Vec<u8>, ChitinError> {
+        let body =
+            serde_json::to_vec(&(method, &self.payload, &self.signer, self.role, self.nonce))?;
+        Ok(crypto::hash_bytes(&body).to_vec())
+    }
+
+    /// Verify the signature against `signer`. Returns `Ok(false)` for
+    /// malformed hex and invalid signatures.
+    pub fn verify_signature(&self, method: &str) -> Result<bool, ChitinError> {
+        let signer = match parse_key(&self.signer) {
+            Some(signer) => signer,
+            None => return Ok(false),
+        };
+        let signature = match decode_hex(&self.signature) {
+            Some(signature) if signature.len() == 64 => signature,
+            _ => return Ok(false),
+        };
+        crypto::verify_signature(&signer, &self.signable_bytes(method)?, &signature)
+    }
+
+    /// Whether `params` is an envelope rather than a method's own params.
+    fn is_envelope(params: &serde_json::Value) -> bool {
+        ["payload", "signer", "role", "nonce", "signature"]
+            .iter()
+            .all(|field| params.get(field).is_some())
+    }
+}
+
+/// Enforces `METHOD_POLICIES` and verifies envelopes. Shared by every
+/// connection, so envelope nonces are tracked per signer across them.
+#[derive(Debug, Default)]
+pub struct RequestAuth {
+    config: RequestAuthConfig,
+    /// Envelope nonces accepted per signer: those within the clock skew, or
+    /// only the highest without a skew limit.
+    nonces: Mutex<HashMap<[u8; 32], BTreeSet<u64>>>,
+}
+
+impl RequestAuth {
+    /// Authorization with `config`'s settings.
+    pub fn new(config: RequestAuthConfig) -> Self {
+        Self {
+            config,
+            nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The configured settings.
+    pub fn config(&self) -> &RequestAuthConfig {
+        &self.config
+    }
+
+    /// Check `params` for `method` against its policy, returning the params
+    /// to dispatch (an envelope's payload). `identities` tells coldkeys and
+    /// hotkeys apart.
+    pub async fn authorize(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+    ) -> Result<serde_json::Value, String> {
+        let policy = policy(method);
+        if !SignedRequest::is_envelope(&params) {
+            let policy = match policy {
+                Some(policy) => policy,
+                None => return Ok(params),
+            };
+            match policy.signer_field {
+                Some(field) => {
+                    if let Some(key) = params.get(field).and_then(|v| v.as_str()) {
+                        let key = parse_key(key)
+                            .ok_or_else(|| format!("{} is not a 32-byte hex key", field))?;
+                        check_role(method, policy.role, &key, identities).await?;
+                    }
+                }
+                None if self.config.require_signed => {
+                    return Err(format!(
+                        "{} requires a request signed with the sender's {}",
+                        method,
+                        policy.role.as_str()
+                    ));
+                }
+                None => {}
+            }
+            return Ok(params);
+        }
+
+        let envelope: SignedRequest =
+            serde_json::from_value(params).map_err(|e| format!("Invalid signed request: {}", e))?;
+        let signer = parse_key(&envelope.signer)
+            .ok_or_else(|| "Signed request signer is not a 32-byte hex key".to_string())?;
+        if !envelope
+            .verify_signature(method)
+            .map_err(|e| format!("Failed to verify request signature: {}", e))?
+        {
+            return Err("Invalid request signature".to_string());
+        }
+        if let Some(policy) = policy {
+            if envelope.role != policy.role {
+                return Err(format!(
+                    "{} must be signed with the {}, not the {}",
+                    method,
+                    policy.role.as_str(),
+                    envelope.role.as_str()
+                ));
+            }
+            check_role(method, policy.role, &signer, identities).await?;
+            if let Some(field) = policy.signer_field {
+                let named = envelope.payload.get(field).and_then(|v| v.as_str());
+                if named.and_then(parse_key) != Some(signer) {
+                    return Err(format!(
+                        "{} is signed by {} but its {} is {}",
+                        method,
+                        envelope.signer,
+                        field,
+                        named.unwrap_or("missing")
+                    ));
+                }
+            }
+            if let Some(sender) = policy.sender {
+                check_sender(method, sender, &envelope.payload, &signer, identities).await?;
+            }
+        }
+        self.accept_nonce(signer, envelope.nonce)?;
+        Ok(envelope.payload)
+    }
+
+    /// Record `nonce` for `signer` if it is fresh and unused. Gossip is
+    /// sent concurrently, so nonces within the clock skew may arrive out of
+    /// order; without a skew limit they must increase.
+    fn accept_nonce(&self, signer: [u8; 32], nonce: u64) -> Result<(), String> {
+        let now = now_millis();
+        let skew = self.config.max_clock_skew_secs.saturating_mul(1000);
+        if skew > 0 && now.abs_diff(nonce) > skew {
+            return Err(format!(
+                "Request nonce {} is more than {}s from this node's clock",
+                nonce, self.config.max_clock_skew_secs
+            ));
+        }
+        let oldest = now.saturating_sub(skew);
+        let mut nonces = self.nonces.lock().unwrap_or_else(|e| e.into_inner());
+        if skew > 0 && nonces.len() >= MAX_TRACKED_SIGNERS {
+            nonces.retain(|_, seen| seen.last().is_some_and(|&last| last >= oldest));
+        }
+        let seen = nonces.entry(signer).or_default();
+        if skew > 0 {
+            *seen = seen.split_off(&oldest);
+            if !seen.insert(nonce) {
+                return Err(format!("Request nonce {} was already used", nonce));
+            }
+        } else {
+            if let Some(&last) = seen.last() {
+                if nonce <= last {
+                    return Err(format!(
+                        "Request nonce {} is not above the signer's last nonce {}",
+                        nonce, last
+                    ));
+                }
+            }
+            *seen = BTreeSet::from([nonce]);
+        }
+        Ok(())
+    }
+}
+
+/// Reject `key` signing `method` unless it can hold `role`: a hotkey must be
+/// registered, and a coldkey must not be a registered hotkey.
+async fn check_role(
+    method: &str,
+    role: KeyRole,
+    key: &[u8; 32],
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+) -> Result<(), String> {
+    let registry = match identities {
+        Some(identities) => identities.read().await,
+        None => return Ok(()),
+    };
+    let misused = match role {
+        KeyRole::Coldkey => registry
+            .uid_of_hotkey(key)
+            .map(|uid| (uid, KeyRole::Hotkey)),
+        KeyRole::Hotkey if registry.uid_of_hotkey(key).is_some() => None,
+        KeyRole::Hotkey => match registry.uid_of_did(&NodeIdentity::derive_did(key)) {
+            Some(uid) => Some((uid, KeyRole::Coldkey)),
+            None => {
+                return Err(format!(
+                    "{} must be signed with a registered hotkey; {} is not one",
+                    method,
+                    encode_hex(key)
+                ))
+            }
+        },
+    };
+    match misused {
+        Some((uid, actual)) => Err(format!(
+            "{} must be signed with a {}; {} is the {} of UID {}",
+            method,
+            role.as_str(),
+            encode_hex(key),
+            actual.as_str(),
+            uid
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Reject an envelope for `method` whose signer is not the `sender` its
+/// payload names.
+async fn check_sender(
+    method: &str,
+    sender: Sender,
+    payload: &serde_json::Value,
+    signer: &[u8; 32],
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+) -> Result<(), String> {
+    let (pointer, sent_by_signer) = match sender {
+        Sender::Key(pointer) => {
+            let key = payload.pointer(pointer).and_then(|v| match v {
+                serde_json::Value::String(hex) => parse_key(hex),
+                value => serde_json::from_value::<[u8; 32]>(value.clone()).ok(),
+            });
+            (pointer, key.map(|key| key == *signer))
+        }
+        Sender::Did(pointer) => {
+            let did = payload.pointer(pointer).and_then(|v| v.as_str());
+            let sent_by_signer = match (did, identities) {
+                (Some(did), Some(identities)) => {
+                    let registry = identities.read().await;
+                    let hotkey = registry
+                        .uid_of_did(did)
+                        .and_then(|uid| registry.get(uid))
+                        .map(|record| record.hotkey);
+                    Some(hotkey == Some(*signer))
+                }
+                // Without a registry a DID cannot be resolved, as in
+                // `check_role`.
+                (Some(_), None) => Some(true),
+                (None, _) => None,
+            };
+            (pointer, sent_by_signer)
+        }
+    };
+    match sent_by_signer {
+        Some(true) => Ok(()),
+        Some(false) => Err(format!(
+            "{} is signed by {}, which is not the sender at {}",
+            method,
+            encode_hex(signer),
+            pointer
+        )),
+        None => Err(format!(
+            "{} is signed but names no sender at {}",
+            method, pointer
+        )),
+    }
+}
+
+fn parse_key(hex: &str) -> Option<[u8; 32]> {
+    decode_hex(hex.trim()).and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::crypto::Keypair;
+    use chitin_core::NodeType;
+    use serde_json::json;
+
+    /// Register a node with fresh keys, returning its coldkey and hotkey.
+    fn register(registry: &mut IdentityRegistry) -> (Keypair, Keypair) {
+        let coldkey = Keypair::generate();
+        let hotkey = Keypair::generate();
+        registry
+            .register(
+                coldkey.public_key_bytes(),
+                hotkey.public_key_bytes(),
+                NodeType::Tide,
+                0,
+            )
+            .unwrap();
+        (coldkey, hotkey)
+    }
+
+    fn envelope(
+        method: &str,
+        payload: serde_json::Value,
+        role: KeyRole,
+        key: &Keypair,
+    ) -> serde_json::Value {
+        let signed = SignedRequest::sign(
+            method,
+            payload,
+            role,
+            &key.signing_key.to_bytes(),
+            key.public_key_bytes(),
+        )
+        .unwrap();
+        serde_json::to_value(signed).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_envelope_signature_covers_method_and_payload() {
+        let auth = RequestAuth::default();
+        let key = Keypair::generate();
+        let params = envelope("polyp/get", json!({ "id": 1 }), KeyRole::Hotkey, &key);
+
+        let signed: SignedRequest = serde_json::from_value(params.clone()).unwrap();
+        assert!(signed.verify_signature("polyp/get").unwrap());
+        assert!(!signed.verify_signature("polyp/list").unwrap());
+        let err = auth
+            .authorize("polyp/list", params.clone(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Invalid request signature");
+
+        let mut tampered = params.clone();
+        tampered["payload"]["id"] = json!(2);
+        assert!(auth.authorize("polyp/get", tampered, None).await.is_err());
+
+        let payload = auth.authorize("polyp/get", params, None).await.unwrap();
+        assert_eq!(payload, json!({ "id": 1 }));
+    }
+
+    #[test]
+    fn test_nonces_are_single_use_and_near_the_clock() {
+        let auth = RequestAuth::default();
+        let signer = [1u8; 32];
+        let now = now_millis();
+        assert!(auth.accept_nonce(signer, now).is_ok());
+        assert!(auth.accept_nonce(signer, now).is_err());
+        // Concurrent gossip may arrive out of order within the skew.
+        assert!(auth.accept_nonce(signer, now - 1).is_ok());
+        // Nonces are tracked per signer.
+        assert!(auth.accept_nonce([2u8; 32], now).is_ok());
+        assert!(auth.accept_nonce(signer, now - 301_000).is_err());
+        assert!(auth.accept_nonce(signer, now + 301_000).is_err());
+
+        // Without a skew limit, nonces must increase.
+        let unlimited = RequestAuth::new(RequestAuthConfig {
+            require_signed: false,
+            max_clock_skew_secs: 0,
+        });
+        assert!(unlimited.accept_nonce(signer, 10).is_ok());
+        assert!(unlimited.accept_nonce(signer, 10).is_err());
+        assert!(unlimited.accept_nonce(signer, 9).is_err());
+        assert!(unlimited.accept_nonce(signer, 11).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_methods_need_keys_in_their_role() {
+        let auth = RequestAuth::default();
+        let mut registry = IdentityRegistry::new();
+        let (coldkey, hotkey) = register(&mut registry);
+        let ids = Arc::new(RwLock::new(registry));
+        let method = "peer/receive_state_update";
+        let update = |key: &Keypair| json!({ "update": { "signer": key.public_key_bytes() } });
+
+        // The envelope must claim the method's role.
+        let params = envelope(method, update(&hotkey), KeyRole::Coldkey, &hotkey);
+        let err = auth
+            .authorize(method, params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("must be signed with the hotkey"), "{}", err);
+
+        // A coldkey cannot sign as a hotkey, nor can an unregistered key.
+        let params = envelope(method, update(&coldkey), KeyRole::Hotkey, &coldkey);
+        let err = auth
+            .authorize(method, params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("is the coldkey of UID 0"), "{}", err);
+        let stranger = Keypair::generate();
+        let params = envelope(method, update(&stranger), KeyRole::Hotkey, &stranger);
+        let err = auth
+            .authorize(method, params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("registered hotkey"), "{}", err);
+
+        // A hotkey cannot sign a payload as a coldkey.
+        let params = json!({ "from_coldkey": encode_hex(&hotkey.public_key_bytes()) });
+        let err = auth
+            .authorize("wallet/transfer", params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("is the hotkey of UID 0"), "{}", err);
+
+        let params = envelope(method, update(&hotkey), KeyRole::Hotkey, &hotkey);
+        assert!(auth.authorize(method, params, Some(&ids)).await.is_ok());
+
+        // With `require_signed`, gossip must come in an envelope.
+        let strict = RequestAuth::new(RequestAuthConfig {
+            require_signed: true,
+            ..RequestAuthConfig::default()
+        });
+        assert!(strict
+            .authorize(method, update(&hotkey), Some(&ids))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_payload_signer_must_sign_the_envelope() {
+        let auth = RequestAuth::default();
+        let mut registry = IdentityRegistry::new();
+        let (_, hotkey) = register(&mut registry);
+        let (_, other) = register(&mut registry);
+        let ids = Arc::new(RwLock::new(registry));
+        let method = "validation/scores";
+        let scores =
+            |key: &Keypair| json!({ "validator_hotkey": encode_hex(&key.public_key_bytes()) });
+
+        let params = envelope(method, scores(&other), KeyRole::Hotkey, &hotkey);
+        let err = auth
+            .authorize(method, params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("but its validator_hotkey is"), "{}", err);
+
+        let params = envelope(method, scores(&hotkey), KeyRole::Hotkey, &hotkey);
+        assert!(auth.authorize(method, params, Some(&ids)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_gossip_is_bound_to_its_sender() {
+        let auth = RequestAuth::default();
+        let mut registry = IdentityRegistry::new();
+        let (coldkey, hotkey) = register(&mut registry);
+        let (other_coldkey, other_hotkey) = register(&mut registry);
+        let ids = Arc::new(RwLock::new(registry));
+        let polyp = |did: Option<String>| json!({ "polyp": {}, "source_did": did });
+        let did = NodeIdentity::derive_did(&coldkey.public_key_bytes());
+        let other_did = NodeIdentity::derive_did(&other_coldkey.public_key_bytes());
+
+        let method = "peer/receive_polyp";
+        for payload in [polyp(Some(other_did)), polyp(None)] {
+            let params = envelope(method, payload, KeyRole::Hotkey, &hotkey);
+            assert!(auth.authorize(method, params, Some(&ids)).await.is_err());
+        }
+        let params = envelope(method, polyp(Some(did)), KeyRole::Hotkey, &hotkey);
+        assert!(auth.authorize(method, params, Some(&ids)).await.is_ok());
+
+        // A state update signed by one node cannot be relayed as another's.
+        let method = "peer/receive_state_update";
+        let update = json!({ "update": { "signer": other_hotkey.public_key_bytes() } });
+        let params = envelope(method, update, KeyRole::Hotkey, &hotkey);
+        let err = auth
+            .authorize(method, params, Some(&ids))
+            .await
+            .unwrap_err();
+        assert!(err.contains("not the sender at /update/signer"), "{}", err);
+    }
+}
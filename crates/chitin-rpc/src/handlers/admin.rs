@@ -1,10 +1,26 @@
 // crates/chitin-rpc/src/handlers/admin.rs
 //
-// Admin handlers: GetConfig, UpdateConfig, GetLogs.
-// Phase 1: Stub implementations. These will be gated behind admin
+// Admin handlers: GetConfig, UpdateConfig, ReloadConfig, GetLogs, Backup,
+// Snapshot, ExportReputation, ImportReputation, ListTasks.
+// Each is served through a callback the daemon sets; without one the method
+// reports that it is not available. These will be gated behind admin
 // authentication in Phase 2+.
 
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_core::crypto::hash_bytes;
+use chitin_reputation::domain_store::DomainTrustStore;
+use chitin_reputation::snapshot::ReputationSnapshot;
+use chitin_store::BackupStats;
+
+use crate::server::{
+    BackupCallback, ConfigReloadCallback, ConfigUpdateCallback, ConfigViewCallback,
+    LogQueryCallback, SnapshotCallback, TaskListCallback,
+};
 
 // ---------------------------------------------------------------------------
 // GetConfig
@@ -28,37 +44,36 @@ pub struct GetConfigResponse {
 
 /// Handle a GetConfig request.
 ///
-/// Phase 1: Returns a minimal placeholder configuration.
+/// Returns the configuration in effect, with defaults filled in, or one
+/// top-level section of it. The version covers the whole configuration.
 pub async fn handle_get_config(
-    _request: GetConfigRequest,
+    request: GetConfigRequest,
+    view: Option<&ConfigViewCallback>,
 ) -> Result<GetConfigResponse, String> {
-    let config = serde_json::json!({
-        "node": {
-            "type": "Hybrid",
-            "version": env!("CARGO_PKG_VERSION"),
-            "phase": 1
-        },
-        "rpc": {
-            "host": "127.0.0.1",
-            "port": 50051
-        },
-        "storage": {
-            "backend": "rocksdb",
-            "path": "./data/rocks"
-        },
-        "consensus": {
-            "epoch_length": 360,
-            "kappa": 0.5,
-            "alpha": 0.1
-        }
-    });
-
+    let config = match view {
+        Some(view) => view().await,
+        None => return Err("Config not available".to_string()),
+    };
+    let config_version = config_version(&config);
+    let config = match request.section {
+        Some(section) => config
+            .get(&section)
+            .cloned()
+            .ok_or_else(|| format!("Unknown config section: {}", section))?,
+        None => config,
+    };
     Ok(GetConfigResponse {
         config,
-        config_version: "phase1-default".to_string(),
+        config_version,
     })
 }
 
+/// A short hash identifying a configuration.
+pub fn config_version(config: &serde_json::Value) -> String {
+    let json = serde_json::to_vec(config).unwrap_or_default();
+    hash_bytes(&json)[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 // ---------------------------------------------------------------------------
 // UpdateConfig
 // ---------------------------------------------------------------------------
@@ -66,9 +81,11 @@ pub async fn handle_get_config(
 /// Request to update node configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateConfigRequest {
-    /// Configuration updates as a JSON object (merged with existing config).
+    /// Configuration updates as a JSON object, merged into the config file
+    /// (nested objects are tables; `null` removes a key).
     pub updates: serde_json::Value,
-    /// Whether to persist the changes to disk.
+    /// Whether to write the changes to the config file (default true).
+    /// Without it the updates are only validated.
     pub persist: Option<bool>,
 }
 
@@ -83,21 +100,60 @@ pub struct UpdateConfigResponse {
     pub message: String,
     /// New configuration version after the update.
     pub new_config_version: Option<String>,
+    /// Hot-reloadable changes applied (or, unpersisted, that would be).
+    #[serde(default)]
+    pub changes: Vec<String>,
+    /// Updated keys that take effect on the next restart.
+    #[serde(default)]
+    pub restart_required: Vec<String>,
 }
 
 /// Handle an UpdateConfig request.
 ///
-/// Phase 1 stub: Configuration updates are not yet implemented.
+/// The updated file is validated as a whole before it is written, then
+/// reloaded, so hot-reloadable fields apply at once.
 pub async fn handle_update_config(
-    _request: UpdateConfigRequest,
+    request: UpdateConfigRequest,
+    update: Option<&ConfigUpdateCallback>,
 ) -> Result<UpdateConfigResponse, String> {
-    // Phase 2: Apply config updates and optionally persist to disk
-    Ok(UpdateConfigResponse {
-        applied: false,
-        persisted: false,
-        message: "Phase 1 stub: configuration updates not yet implemented".to_string(),
-        new_config_version: None,
-    })
+    if !request.updates.is_object() {
+        return Err("Config updates must be a JSON object".to_string());
+    }
+    match update {
+        Some(update) => update(request).await,
+        None => Err("Config updates not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ReloadConfig
+// ---------------------------------------------------------------------------
+
+/// Request to reload the node configuration from disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadConfigRequest {}
+
+/// Response from a configuration reload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadConfigResponse {
+    /// Path the configuration was reloaded from.
+    pub path: String,
+    /// Hot-reloadable changes applied (empty if nothing changed).
+    pub applied: Vec<String>,
+}
+
+/// Handle a ReloadConfig request.
+///
+/// Only the log level, peer list, and sync interval are applied; other
+/// settings take effect on restart.
+pub async fn handle_reload_config(
+    _request: ReloadConfigRequest,
+    reload: Option<&ConfigReloadCallback>,
+) -> Result<ReloadConfigResponse, String> {
+    match reload {
+        Some(reload) => reload().await,
+        None => Err("Config reload not available".to_string()),
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -113,11 +169,17 @@ pub struct GetLogsRequest {
     pub level: Option<String>,
     /// Filter pattern (substring match on log messages).
     pub filter: Option<String>,
+    /// Only return entries after this sequence number, for following.
+    #[serde(default)]
+    pub since: Option<u64>,
 }
 
 /// A single log entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
+    /// Sequence number, increasing from daemon start.
+    #[serde(default)]
+    pub seq: u64,
     /// ISO 8601 timestamp.
     pub timestamp: String,
     /// Log level.
@@ -133,18 +195,253 @@ pub struct LogEntry {
 pub struct GetLogsResponse {
     /// Log entries.
     pub entries: Vec<LogEntry>,
-    /// Total available log entries (before pagination).
+    /// Total matching log entries held (before `lines` is applied).
     pub total_available: u32,
 }
 
 /// Handle a GetLogs request.
 ///
-/// Phase 1 stub: Returns empty log list. Phase 2+ will integrate with
-/// the tracing subscriber to provide real log streaming.
-pub async fn handle_get_logs(_request: GetLogsRequest) -> Result<GetLogsResponse, String> {
-    // Phase 2: Integrate with tracing subscriber for real log retrieval
-    Ok(GetLogsResponse {
-        entries: Vec::new(),
-        total_available: 0,
+/// Entries come from the daemon's in-memory log buffer, oldest first, so
+/// only recent entries that passed the log filter are available.
+pub async fn handle_get_logs(
+    request: GetLogsRequest,
+    logs: Option<&LogQueryCallback>,
+) -> Result<GetLogsResponse, String> {
+    if let Some(level) = &request.level {
+        if !LOG_LEVELS.contains(&level.to_ascii_lowercase().as_str()) {
+            return Err(format!("Unknown log level: {}", level));
+        }
+    }
+    match logs {
+        Some(logs) => Ok(logs(request)),
+        None => Err("Logs not available".to_string()),
+    }
+}
+
+/// Log levels accepted by `GetLogsRequest::level`, least severe first.
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+// ---------------------------------------------------------------------------
+// Backup
+// ---------------------------------------------------------------------------
+
+/// Request to back up the node's databases.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupRequest {
+    /// Name of the directory to create under `<data_dir>/backups` on the
+    /// node's host (default: a timestamp).
+    pub dest: Option<String>,
+}
+
+/// One database copied by a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupDatabase {
+    /// Directory name under the data directory (e.g. "rocksdb").
+    pub name: String,
+    /// Entries and bytes copied.
+    pub stats: BackupStats,
+}
+
+/// Response from a backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupResponse {
+    /// Backup directory, laid out like the data directory.
+    pub path: String,
+    /// Databases copied.
+    pub databases: Vec<BackupDatabase>,
+    /// When the backup was taken.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Handle a Backup request (`admin/backup`).
+///
+/// Each database is copied from a consistent view of it; databases are
+/// copied one after another while the node keeps running.
+pub async fn handle_backup(
+    request: BackupRequest,
+    backup: Option<&BackupCallback>,
+) -> Result<BackupResponse, String> {
+    match backup {
+        Some(backup) => backup(request).await,
+        None => Err("Backup not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshot
+// ---------------------------------------------------------------------------
+
+/// Request to write a signed snapshot for bootstrapping new nodes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotRequest {
+    /// Name of the file to create under `<data_dir>/snapshots` on the node's
+    /// host (default: `snapshot-<epoch>.chitin`).
+    pub dest: Option<String>,
+    /// Also add the file to IPFS.
+    #[serde(default)]
+    pub pin: bool,
+}
+
+/// Response from a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResponse {
+    /// Snapshot file written.
+    pub path: String,
+    /// Epoch the snapshot was taken at.
+    pub epoch: u64,
+    /// Block the snapshot was taken at.
+    pub block: u64,
+    /// Polyps included.
+    pub polyps: usize,
+    /// Vector index entries included.
+    pub vectors: usize,
+    /// Epoch of the hardening checkpoint included, if any.
+    pub checkpoint_epoch: Option<u64>,
+    /// SHA-256 of the snapshot body (hex), for `[snapshot] expected_hash`.
+    pub content_hash: String,
+    /// Hotkey that signed the snapshot (hex).
+    pub signer: String,
+    /// IPFS CID, if pinned.
+    pub cid: Option<String>,
+    /// When the snapshot was taken.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Handle a Snapshot request (`admin/snapshot`).
+///
+/// The snapshot holds the node's polyps, vector index, metagraph, and
+/// latest hardening checkpoint, signed with its hotkey.
+pub async fn handle_snapshot(
+    request: SnapshotRequest,
+    snapshot: Option<&SnapshotCallback>,
+) -> Result<SnapshotResponse, String> {
+    match snapshot {
+        Some(snapshot) => snapshot(request).await,
+        None => Err("Snapshot not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ExportReputation
+// ---------------------------------------------------------------------------
+
+/// Request to export a reputation snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportReputationRequest {
+    /// Epoch to stamp the snapshot with (default: the store's current epoch).
+    pub epoch: Option<u64>,
+}
+
+/// Handle an ExportReputation request.
+///
+/// Snapshots every domain trust matrix with its OpenRank scores.
+pub async fn handle_export_reputation(
+    request: ExportReputationRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+) -> Result<ReputationSnapshot, String> {
+    let ts = match trust_store {
+        Some(ts) => ts.read().await,
+        None => return Err("Trust store not available".to_string()),
+    };
+    let epoch = request.epoch.unwrap_or_else(|| ts.epoch());
+    ts.snapshot(epoch).map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// ImportReputation
+// ---------------------------------------------------------------------------
+
+/// Request to import a reputation snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReputationRequest {
+    /// The snapshot to import. Its hash must match this node's checkpoint
+    /// for the snapshot's epoch, recorded locally or pinned in its config.
+    pub snapshot: ReputationSnapshot,
+}
+
+/// Response from a reputation import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReputationResponse {
+    /// Epoch of the imported snapshot.
+    pub epoch: u64,
+    /// Number of domains imported.
+    pub domains: usize,
+    /// Verified snapshot hash.
+    pub hash: String,
+}
+
+/// Handle an ImportReputation request.
+///
+/// Verifies the snapshot's hash against the epoch checkpoint, then replaces
+/// all local trust state with it.
+pub async fn handle_import_reputation(
+    request: ImportReputationRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+) -> Result<ImportReputationResponse, String> {
+    let ts = match trust_store {
+        Some(ts) => ts,
+        None => return Err("Trust store not available".to_string()),
+    };
+    let epoch = request.snapshot.epoch;
+    let hash = request.snapshot.hash.clone();
+    let domains = ts
+        .write()
+        .await
+        .import_snapshot(request.snapshot)
+        .map_err(|e| e.to_string())?;
+
+    Ok(ImportReputationResponse {
+        epoch,
+        domains,
+        hash,
     })
 }
+
+// ---------------------------------------------------------------------------
+// ListTasks
+// ---------------------------------------------------------------------------
+
+/// Request for the daemon's background tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTasksRequest {}
+
+/// Status of one supervised background task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInfo {
+    /// Task name.
+    pub name: String,
+    /// Priority: "critical", "high", "normal", or "low".
+    pub priority: String,
+    /// Restart policy: "never" or "on_failure".
+    pub restart: String,
+    /// State: "running", "restarting", "finished", or "failed".
+    pub state: String,
+    /// Restarts since the daemon started.
+    pub restarts: u32,
+    /// When the current (or last) run started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the task last exited, if it has.
+    pub last_exit_at: Option<DateTime<Utc>>,
+    /// The last error or panic message, if any.
+    pub last_error: Option<String>,
+}
+
+/// Response listing background tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTasksResponse {
+    /// Tasks by priority, then name.
+    pub tasks: Vec<TaskInfo>,
+    /// False if a critical or high-priority task has failed for good.
+    pub healthy: bool,
+}
+
+/// Handle a ListTasks request (`admin/tasks`).
+pub async fn handle_list_tasks(
+    _request: ListTasksRequest,
+    tasks: Option<&TaskListCallback>,
+) -> Result<ListTasksResponse, String> {
+    match tasks {
+        Some(tasks) => Ok(tasks().await),
+        None => Err("Task listing not available".to_string()),
+    }
+}
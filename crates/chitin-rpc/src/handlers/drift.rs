@@ -0,0 +1,415 @@
+// crates/chitin-rpc/src/handlers/drift.rs
+//
+// Embedding drift and molting handlers: GetDriftStatus, GetMoltProgress,
+// MoltDryRun, MoltPlan, MoltStart, MoltPause, MoltResume. Status and
+// progress read the model version registry at the current epoch;
+// migrations are the registry's molt tasks (deprecated versions paired with
+// their target) plus any the operator started. Status also compares
+// retrieval under both models of each migration, on a supplied labeled set
+// or on hardened neighborhoods. Dry runs estimate any proposed molt and
+// persist the report for governance to review; plans add how far the molt
+// has come and how many epochs the rest will take. Start, pause, and resume
+// (`admin/molt/*`, served only on this host) change the operator's molt
+// controls, which the daemon reads every epoch.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::traits::PolypStore;
+use chitin_drift::detection::{
+    DriftDetector, DriftReport, LocalEmbeddingModel, DEFAULT_DRIFT_THRESHOLD,
+};
+use chitin_drift::evaluation::{compare, EvalSet, ModelComparison};
+use chitin_drift::molting::{
+    molt_progress, DryRunConfig, MoltControl, MoltDryRun, MoltProgress, MoltingOrchestrator,
+};
+use chitin_drift::versioning::MoltTask;
+use chitin_drift::versioning::{ModelStatus, ModelVersion, VersionRegistry};
+use chitin_store::RocksStore;
+
+/// Dimensions of embeddings the node produces (matches `polyp/submit`).
+const EMBEDDING_DIMENSIONS: usize = 384;
+
+// ---------------------------------------------------------------------------
+// GetDriftStatus
+// ---------------------------------------------------------------------------
+
+/// Hardened Polyps used as the pseudo-labeled corpus for each evaluation.
+const EVAL_CORPUS_SIZE: usize = 256;
+
+/// Pseudo-labeled queries per evaluation.
+const EVAL_QUERIES: usize = 32;
+
+/// Default recall cutoff, and neighbors taken as relevant per pseudo-label.
+const EVAL_K: usize = 5;
+
+/// Request for drift status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDriftStatusRequest {
+    /// Labeled queries to evaluate migrations on, instead of pseudo-labels
+    /// from hardened neighborhoods.
+    #[serde(default)]
+    pub eval_set: Option<EvalSet>,
+    /// Recall cutoff (default: 5).
+    #[serde(default)]
+    pub k: Option<usize>,
+}
+
+/// A registered model version and its status at the current epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVersionStatus {
+    /// The version and its schedule.
+    #[serde(flatten)]
+    pub version: ModelVersion,
+    /// Its lifecycle status.
+    pub status: ModelStatus,
+}
+
+/// Response containing drift status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDriftStatusResponse {
+    /// Epoch the status was computed at.
+    pub epoch: u64,
+    /// Registered model versions, ordered by activation epoch.
+    pub versions: Vec<ModelVersionStatus>,
+    /// Canary drift for each migration currently in progress.
+    pub reports: Vec<DriftReport>,
+    /// Retrieval under the old and new model of each migration.
+    pub evaluations: Vec<ModelComparison>,
+}
+
+/// Handle a GetDriftStatus request.
+///
+/// Drift is measured on the built-in canary corpus for each molt task, and
+/// retrieval evaluated on the request's labeled set or, failing that, on
+/// hardened Polyps embedded under the old model.
+pub async fn handle_get_drift_status(
+    store: &Arc<RocksStore>,
+    request: GetDriftStatusRequest,
+    registry: Option<&VersionRegistry>,
+    epoch: u64,
+) -> Result<GetDriftStatusResponse, String> {
+    let control = MoltControl::load(store).map_err(|e| e.to_string())?;
+    let tasks = control.tasks(registry.map(|r| r.molt_tasks(epoch)).unwrap_or_default());
+    let detector = DriftDetector::with_default_canaries(DEFAULT_DRIFT_THRESHOLD);
+    let reports = tasks
+        .iter()
+        .map(|task| detector.report(&task.from_model, &task.to_model))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let versions = registry
+        .map(|r| r.versions.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|v| ModelVersionStatus {
+            version: v.clone(),
+            status: v.status_at(epoch),
+        })
+        .collect();
+
+    let k = request.k.unwrap_or(EVAL_K);
+    let hardened = match request.eval_set {
+        Some(_) => vec![],
+        None => store
+            .list_polyps_by_state(&PolypState::Hardened)
+            .await
+            .map_err(|e| e.to_string())?,
+    };
+    let mut evaluations = Vec::new();
+    for task in tasks {
+        let set = match &request.eval_set {
+            Some(set) => set.clone(),
+            None => pseudo_labels(&hardened, &task.from_model, k),
+        };
+        let baseline = LocalEmbeddingModel::new(&task.from_model, EMBEDDING_DIMENSIONS);
+        let candidate = LocalEmbeddingModel::new(&task.to_model, EMBEDDING_DIMENSIONS);
+        evaluations.push(compare(&baseline, &candidate, &set, k).map_err(|e| e.to_string())?);
+    }
+
+    Ok(GetDriftStatusResponse {
+        epoch,
+        versions,
+        reports,
+        evaluations,
+    })
+}
+
+/// Pseudo-labeled evaluation set over hardened Polyps embedded under `model`.
+fn pseudo_labels(hardened: &[Polyp], model: &str, neighbors: usize) -> EvalSet {
+    let mut corpus: Vec<Polyp> = hardened
+        .iter()
+        .filter(|p| p.subject.vector.model_id.key() == model)
+        .cloned()
+        .collect();
+    corpus.sort_by_key(|p| p.id);
+    corpus.truncate(EVAL_CORPUS_SIZE);
+    EvalSet::from_hardened(&corpus, neighbors, EVAL_QUERIES)
+}
+
+// ---------------------------------------------------------------------------
+// GetMoltProgress
+// ---------------------------------------------------------------------------
+
+/// Request for molt progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMoltProgressRequest {
+    /// Only report migrations away from this model, if set.
+    #[serde(default)]
+    pub from_model: Option<String>,
+}
+
+/// Response containing molt progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMoltProgressResponse {
+    /// Epoch the progress was computed at.
+    pub epoch: u64,
+    /// Per-zone completion of each migration.
+    pub migrations: Vec<MoltProgress>,
+    /// Whether the operator has paused molting.
+    #[serde(default)]
+    pub paused: bool,
+    /// Polyps molted per migration per epoch.
+    #[serde(default)]
+    pub batch_size: usize,
+}
+
+/// Handle a GetMoltProgress request.
+pub async fn handle_get_molt_progress(
+    store: &Arc<RocksStore>,
+    request: GetMoltProgressRequest,
+    registry: Option<&VersionRegistry>,
+    epoch: u64,
+) -> Result<GetMoltProgressResponse, String> {
+    let control = MoltControl::load(store).map_err(|e| e.to_string())?;
+    let tasks = control.tasks(registry.map(|r| r.molt_tasks(epoch)).unwrap_or_default());
+    let mut migrations = Vec::new();
+    for task in tasks {
+        if request
+            .from_model
+            .as_ref()
+            .is_some_and(|model| *model != task.from_model)
+        {
+            continue;
+        }
+        migrations.push(molt_progress(store, &task).await.map_err(|e| e.to_string())?);
+    }
+    Ok(GetMoltProgressResponse {
+        epoch,
+        migrations,
+        paused: control.paused,
+        batch_size: control.batch_size(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// MoltDryRun
+// ---------------------------------------------------------------------------
+
+/// Request to estimate a molt without performing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltDryRunRequest {
+    /// Model the Polyps are currently embedded under.
+    pub from_model: String,
+    /// Model to re-embed them with.
+    pub to_model: String,
+    /// Candidates to re-embed (default: 64).
+    #[serde(default)]
+    pub sample_size: Option<usize>,
+    /// Cost of re-embedding one Polyp (default: 0.0).
+    #[serde(default)]
+    pub cost_per_embedding: Option<f64>,
+}
+
+/// Response containing the dry-run report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltDryRunResponse {
+    /// The estimate, as persisted for governance.
+    pub report: MoltDryRun,
+    /// True if no concerns were found.
+    pub acceptable: bool,
+}
+
+/// Handle a MoltDryRun request.
+///
+/// Re-embeds a sample of the candidates with the node's model for
+/// `to_model`, saves the report, and returns it. No Polyps are modified.
+pub async fn handle_molt_dry_run(
+    store: &Arc<RocksStore>,
+    request: MoltDryRunRequest,
+) -> Result<MoltDryRunResponse, String> {
+    if request.from_model == request.to_model {
+        return Err("from_model and to_model must differ".to_string());
+    }
+    let defaults = DryRunConfig::default();
+    let config = DryRunConfig {
+        sample_size: request.sample_size.unwrap_or(defaults.sample_size),
+        cost_per_embedding: request.cost_per_embedding.unwrap_or(defaults.cost_per_embedding),
+        ..defaults
+    };
+    let model = LocalEmbeddingModel::new(&request.to_model, EMBEDDING_DIMENSIONS);
+    let report = MoltingOrchestrator::new()
+        .dry_run(store, &request.from_model, &model, &config)
+        .await
+        .map_err(|e| e.to_string())?;
+    report.save(store).map_err(|e| e.to_string())?;
+    Ok(MoltDryRunResponse {
+        acceptable: report.acceptable(),
+        report,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// MoltPlan
+// ---------------------------------------------------------------------------
+
+/// Request to plan a molt: a dry run plus how much of it is left.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltPlanRequest {
+    /// Model the Polyps are currently embedded under.
+    pub from_model: String,
+    /// Model to re-embed them with.
+    pub to_model: String,
+    /// Candidates to re-embed in the dry run (default: 64).
+    #[serde(default)]
+    pub sample_size: Option<usize>,
+    /// Cost of re-embedding one Polyp (default: 0.0).
+    #[serde(default)]
+    pub cost_per_embedding: Option<f64>,
+}
+
+/// Response containing a molt plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltPlanResponse {
+    /// The dry-run estimate, as persisted for governance.
+    pub dry_run: MoltDryRunResponse,
+    /// Per-zone counts of Polyps already molted and still to molt.
+    pub progress: MoltProgress,
+    /// Polyps the daemon molts per migration per epoch.
+    pub batch_size: usize,
+    /// Epochs needed to molt the remaining Polyps at that batch size.
+    pub estimated_epochs: u64,
+    /// The migration away from `from_model` already running, if any.
+    pub running: Option<MoltTask>,
+}
+
+/// Handle a MoltPlan request.
+pub async fn handle_molt_plan(
+    store: &Arc<RocksStore>,
+    request: MoltPlanRequest,
+    registry: Option<&VersionRegistry>,
+    epoch: u64,
+) -> Result<MoltPlanResponse, String> {
+    let task = molt_task(&request.from_model, &request.to_model, registry);
+    let dry_run = handle_molt_dry_run(
+        store,
+        MoltDryRunRequest {
+            from_model: request.from_model,
+            to_model: request.to_model,
+            sample_size: request.sample_size,
+            cost_per_embedding: request.cost_per_embedding,
+        },
+    )
+    .await?;
+    let progress = molt_progress(store, &task).await.map_err(|e| e.to_string())?;
+    let control = MoltControl::load(store).map_err(|e| e.to_string())?;
+    let batch_size = control.batch_size();
+    let running = control
+        .tasks(registry.map(|r| r.molt_tasks(epoch)).unwrap_or_default())
+        .into_iter()
+        .find(|t| t.from_model == task.from_model);
+    Ok(MoltPlanResponse {
+        dry_run,
+        estimated_epochs: progress.remaining.div_ceil(batch_size as u64),
+        progress,
+        batch_size,
+        running,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// MoltStart / MoltPause / MoltResume
+// ---------------------------------------------------------------------------
+
+/// Request to start molting Polyps from one model to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltStartRequest {
+    /// Model the Polyps are currently embedded under.
+    pub from_model: String,
+    /// Model to re-embed them with.
+    pub to_model: String,
+    /// Polyps to molt per migration per epoch, if it should change.
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+}
+
+/// Request to pause or resume molting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltControlRequest {}
+
+/// Response containing the operator's molt controls after the change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoltControlResponse {
+    /// The controls the daemon applies from the next epoch.
+    #[serde(flatten)]
+    pub control: MoltControl,
+}
+
+/// Handle a MoltStart request.
+///
+/// The daemon begins molting at the next epoch boundary, unless molting is
+/// paused or the registry already schedules a migration away from
+/// `from_model`.
+pub async fn handle_molt_start(
+    store: &Arc<RocksStore>,
+    request: MoltStartRequest,
+    registry: Option<&VersionRegistry>,
+) -> Result<MoltControlResponse, String> {
+    if request.from_model == request.to_model {
+        return Err("from_model and to_model must differ".to_string());
+    }
+    if request.batch_size == Some(0) {
+        return Err("batch_size must be at least 1".to_string());
+    }
+    let mut control = MoltControl::load(store).map_err(|e| e.to_string())?;
+    control.start(molt_task(&request.from_model, &request.to_model, registry));
+    if request.batch_size.is_some() {
+        control.batch_size = request.batch_size;
+    }
+    control.save(store).map_err(|e| e.to_string())?;
+    Ok(MoltControlResponse { control })
+}
+
+/// Handle a MoltPause (`paused`) or MoltResume (`!paused`) request.
+pub async fn handle_set_molt_paused(
+    store: &Arc<RocksStore>,
+    _request: MoltControlRequest,
+    paused: bool,
+) -> Result<MoltControlResponse, String> {
+    let mut control = MoltControl::load(store).map_err(|e| e.to_string())?;
+    control.paused = paused;
+    control.save(store).map_err(|e| e.to_string())?;
+    Ok(MoltControlResponse { control })
+}
+
+/// A migration from `from_model` to `to_model`, with the latest versions
+/// the registry knows of each (0 if unregistered).
+fn molt_task(from_model: &str, to_model: &str, registry: Option<&VersionRegistry>) -> MoltTask {
+    let version = |model: &str| {
+        registry
+            .into_iter()
+            .flat_map(|r| &r.versions)
+            .filter(|v| v.model_id == model)
+            .map(|v| v.version)
+            .max()
+            .unwrap_or(0)
+    };
+    MoltTask {
+        from_model: from_model.to_string(),
+        from_version: version(from_model),
+        to_model: to_model.to_string(),
+        to_version: version(to_model),
+        deadline_epoch: None,
+    }
+}
@@ -0,0 +1,173 @@
+// crates/chitin-rpc/src/handlers/history.rs
+//
+// Historical query handlers: HistoryStatus, ConsensusHistory,
+// HardeningCheckpointHistory, ReputationCheckpointHistory.
+//
+// Archival nodes retain every consensus result and checkpoint and answer
+// these for any epoch they have seen. Other nodes keep only recent state;
+// queries for epochs they have pruned fail with a "pruned" error so clients
+// know to ask an archival node instead.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_consensus::hardening::HardeningCheckpoint;
+use chitin_consensus::history::ConsensusRecord;
+use chitin_reputation::domain_store::DomainTrustStore;
+use chitin_reputation::snapshot::EpochCheckpoint;
+use chitin_store::RocksStore;
+
+/// Prefix of errors for history this node does not retain.
+pub const PRUNED_ERROR: &str = "pruned";
+
+/// The error for `what` at `epoch` being missing: "pruned" if this node
+/// discarded it, "not found" otherwise.
+fn missing(what: &str, epoch: u64, pruned: bool) -> String {
+    if pruned {
+        format!(
+            "{}: {} for epoch {} is not retained by this node; query an archival node",
+            PRUNED_ERROR, what, epoch
+        )
+    } else {
+        format!("No {} recorded for epoch {}", what, epoch)
+    }
+}
+
+/// True if `epoch` lies below a pruning `watermark` on a non-archival node.
+fn below(watermark: Option<u64>, epoch: u64, archival: bool) -> bool {
+    !archival && watermark.is_some_and(|watermark| epoch < watermark)
+}
+
+// ---------------------------------------------------------------------------
+// HistoryStatus
+// ---------------------------------------------------------------------------
+
+/// Request for this node's history retention.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryStatusRequest {}
+
+/// Response describing which history this node retains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryStatusResponse {
+    /// Whether this node retains all history.
+    pub archival: bool,
+    /// Oldest epoch with a recorded consensus result, if any.
+    pub oldest_consensus_epoch: Option<u64>,
+    /// Newest epoch with a recorded consensus result, if any.
+    pub newest_consensus_epoch: Option<u64>,
+    /// Epoch below which consensus results have been pruned, if any were.
+    pub consensus_pruned_before: Option<u64>,
+    /// Epoch below which hardening checkpoints have been pruned, if any were.
+    pub hardening_checkpoint_pruned_before: Option<u64>,
+}
+
+/// Handle a HistoryStatus request (`history/status`).
+pub async fn handle_history_status(
+    store: &Arc<RocksStore>,
+    _request: HistoryStatusRequest,
+    archival: bool,
+) -> Result<HistoryStatusResponse, String> {
+    let epochs = ConsensusRecord::epochs(store)
+        .map_err(|e| format!("Failed to list consensus history: {}", e))?;
+    let consensus_pruned_before = ConsensusRecord::pruned_before(store)
+        .map_err(|e| format!("Failed to load consensus history watermark: {}", e))?;
+    let hardening_checkpoint_pruned_before = HardeningCheckpoint::pruned_before(store)
+        .map_err(|e| format!("Failed to load hardening checkpoint watermark: {}", e))?;
+    Ok(HistoryStatusResponse {
+        archival,
+        oldest_consensus_epoch: epochs.first().copied(),
+        newest_consensus_epoch: epochs.last().copied(),
+        consensus_pruned_before,
+        hardening_checkpoint_pruned_before,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ConsensusHistory
+// ---------------------------------------------------------------------------
+
+/// Request for the consensus result of a past epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusHistoryRequest {
+    /// Consensus epoch.
+    pub epoch: u64,
+}
+
+/// Handle a ConsensusHistory request (`history/consensus`).
+pub async fn handle_consensus_history(
+    store: &Arc<RocksStore>,
+    request: ConsensusHistoryRequest,
+    archival: bool,
+) -> Result<ConsensusRecord, String> {
+    let record = ConsensusRecord::load(store, request.epoch)
+        .map_err(|e| format!("Failed to load consensus result: {}", e))?;
+    match record {
+        Some(record) => Ok(record),
+        None => {
+            let watermark = ConsensusRecord::pruned_before(store)
+                .map_err(|e| format!("Failed to load consensus history watermark: {}", e))?;
+            let pruned = below(watermark, request.epoch, archival);
+            Err(missing("consensus result", request.epoch, pruned))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HardeningCheckpointHistory
+// ---------------------------------------------------------------------------
+
+/// Request for the hardening checkpoint of a past epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointHistoryRequest {
+    /// Consensus epoch.
+    pub epoch: u64,
+}
+
+/// Handle a HardeningCheckpointHistory request
+/// (`history/hardening_checkpoint`).
+///
+/// Unlike `sync/hardening_checkpoint`, a missing checkpoint is an error, so
+/// clients can tell a pruned epoch from one not yet hardened.
+pub async fn handle_hardening_checkpoint_history(
+    store: &Arc<RocksStore>,
+    request: CheckpointHistoryRequest,
+    archival: bool,
+) -> Result<HardeningCheckpoint, String> {
+    let checkpoint = HardeningCheckpoint::load(store, request.epoch)
+        .map_err(|e| format!("Failed to load hardening checkpoint: {}", e))?;
+    match checkpoint {
+        Some(checkpoint) => Ok(checkpoint),
+        None => {
+            let watermark = HardeningCheckpoint::pruned_before(store)
+                .map_err(|e| format!("Failed to load hardening checkpoint watermark: {}", e))?;
+            let pruned = below(watermark, request.epoch, archival);
+            Err(missing("hardening checkpoint", request.epoch, pruned))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ReputationCheckpointHistory
+// ---------------------------------------------------------------------------
+
+/// Handle a ReputationCheckpointHistory request
+/// (`history/reputation_checkpoint`).
+pub async fn handle_reputation_checkpoint_history(
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+    request: CheckpointHistoryRequest,
+    archival: bool,
+) -> Result<EpochCheckpoint, String> {
+    let (checkpoint, oldest) = match trust_store {
+        Some(ts) => {
+            let ts = ts.read().await;
+            (ts.checkpoint_at(request.epoch), ts.oldest_checkpoint_epoch())
+        }
+        None => (None, None),
+    };
+    // Only the most recent checkpoints are retained, so any older epoch
+    // was dropped.
+    let pruned = below(oldest, request.epoch, archival);
+    checkpoint.ok_or_else(|| missing("reputation checkpoint", request.epoch, pruned))
+}
@@ -1,6 +1,7 @@
 // crates/chitin-rpc/src/handlers/metagraph.rs
 //
-// Metagraph query handlers: GetMetagraph, GetNodeMetrics, GetWeights, GetBonds.
+// Metagraph query handlers: GetMetagraph, GetNodeMetrics, GetWeights, GetBonds,
+// GetMetagraphSnapshot.
 // Phase 4: Wired to live MetagraphManager, WeightMatrix, and BondMatrix state.
 
 use std::collections::HashMap;
@@ -13,6 +14,7 @@ use chitin_consensus::bonds::BondMatrix;
 use chitin_consensus::epoch::EpochManager;
 use chitin_consensus::metagraph::MetagraphManager;
 use chitin_consensus::weights::WeightMatrix;
+use chitin_core::ReefMetagraph;
 
 // ---------------------------------------------------------------------------
 // GetMetagraph
@@ -298,3 +300,33 @@ pub async fn handle_get_bonds(
         bonds: HashMap::new(),
     })
 }
+
+// ---------------------------------------------------------------------------
+// GetMetagraphSnapshot
+// ---------------------------------------------------------------------------
+
+/// Request for the complete current metagraph, as used to bootstrap peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMetagraphSnapshotRequest {}
+
+/// Response containing the complete current metagraph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMetagraphSnapshotResponse {
+    /// The current metagraph, if this node has one.
+    pub metagraph: Option<ReefMetagraph>,
+}
+
+/// Handle a GetMetagraphSnapshot request.
+///
+/// Unlike `metagraph/get`, returns the metagraph unabridged (weights, bonds,
+/// and model versions included) so seed nodes can mirror it.
+pub async fn handle_get_metagraph_snapshot(
+    _request: GetMetagraphSnapshotRequest,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+) -> Result<GetMetagraphSnapshotResponse, String> {
+    let metagraph = match metagraph_manager {
+        Some(mm) => mm.read().await.current().cloned(),
+        None => None,
+    };
+    Ok(GetMetagraphSnapshotResponse { metagraph })
+}
@@ -5,11 +5,15 @@
 // for a specific API group.
 
 pub mod admin;
+pub mod drift;
+pub mod history;
 pub mod metagraph;
 pub mod node;
 pub mod peer;
 pub mod polyp;
 pub mod query;
+pub mod replication;
+pub mod reputation;
 pub mod staking;
 pub mod sync;
 pub mod validation;
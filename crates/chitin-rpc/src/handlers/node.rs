@@ -1,10 +1,14 @@
 // crates/chitin-rpc/src/handlers/node.rs
 //
 // Node info and health handlers: GetNodeInfo, GetHealth, GetPeers.
-// Phase 4: GetNodeInfo wired to real identity and uptime.
+// Phase 4: GetNodeInfo wired to real identity and uptime, plus the process's
+// resource usage where /proc provides it. GetHealth reports DIDs that peers
+// announce from more than one URL, and corrupt polyps the storage scrubber
+// could not repair.
 
 use std::time::Instant;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use chitin_core::identity::NodeIdentity;
@@ -30,6 +34,20 @@ pub struct GetNodeInfoResponse {
     pub did: Option<String>,
     /// Capabilities list (e.g., ["polyp-submit", "query", "validate"]).
     pub capabilities: Vec<String>,
+    /// Resource usage of the node process, if the platform reports it.
+    #[serde(default)]
+    pub resources: Option<ProcessResources>,
+}
+
+/// Resource usage of the node process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessResources {
+    /// Resident memory in bytes.
+    pub rss_bytes: u64,
+    /// Threads in the process.
+    pub threads: u64,
+    /// CPU time used (user + system) since start, in seconds.
+    pub cpu_seconds: f64,
 }
 
 /// Handle a GetNodeInfo request.
@@ -63,13 +81,20 @@ pub async fn handle_get_node_info(
         "local-store".to_string(),
     ];
 
-    // Add validation capability for Tide/Hybrid nodes
+    // Add validation capability for Tide/Hybrid nodes; seed nodes only
+    // serve bootstrap state.
     if let Some(id) = identity {
         match id.node_type {
             chitin_core::identity::NodeType::Tide | chitin_core::identity::NodeType::Hybrid => {
                 capabilities.push("validate".to_string());
                 capabilities.push("consensus".to_string());
             }
+            chitin_core::identity::NodeType::Seed => {
+                capabilities = ["discovery", "peer-exchange", "metagraph", "checkpoints"]
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect();
+            }
             _ => {}
         }
     }
@@ -80,6 +105,31 @@ pub async fn handle_get_node_info(
         uptime_seconds: uptime,
         did,
         capabilities,
+        resources: process_resources(),
+    })
+}
+
+/// Read this process's resource usage from /proc (Linux only).
+fn process_resources() -> Option<ProcessResources> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let field = |name: &str| -> Option<u64> {
+        let line = status.lines().find(|l| l.starts_with(name))?;
+        line[name.len()..].split_whitespace().next()?.parse().ok()
+    };
+    let rss_kb = field("VmRSS:")?;
+    let threads = field("Threads:")?;
+
+    // utime and stime are fields 14 and 15, counted after the
+    // parenthesized command name; the clock ticks at USER_HZ (100 on Linux).
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    let ticks: u64 = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+
+    Some(ProcessResources {
+        rss_bytes: rss_kb * 1024,
+        threads,
+        cpu_seconds: ticks as f64 / 100.0,
     })
 }
 
@@ -104,30 +154,99 @@ pub struct GetHealthResponse {
     pub index_ok: bool,
     /// Number of configured peers (0 if peer networking is disabled).
     pub peer_count: usize,
+    /// DIDs announced from more than one URL.
+    #[serde(default)]
+    pub identity_conflicts: Vec<IdentityConflict>,
+    /// The storage integrity scrubber's findings, if it runs.
+    #[serde(default)]
+    pub scrub: Option<ScrubReport>,
     /// Human-readable details.
     pub details: Option<String>,
 }
 
+/// A DID announced from more than one URL, e.g. because the same hotkey
+/// runs on two machines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityConflict {
+    /// The contested DID.
+    pub did: String,
+    /// Whether the DID is this node's own.
+    pub local: bool,
+    /// The URLs announcing the DID, by URL.
+    pub claims: Vec<IdentityClaim>,
+    /// The URL with the most recent announcement signed by the DID's
+    /// registered hotkey, if any claim is. The other URLs are not synced or
+    /// gossiped with.
+    pub preferred: Option<String>,
+}
+
+/// What the storage integrity scrubber has found.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScrubReport {
+    /// When the last full pass over the store finished, if one has.
+    pub last_pass: Option<DateTime<Utc>>,
+    /// Polyps checked by the last full pass.
+    pub checked: u64,
+    /// Corrupt entries found since startup.
+    pub corrupt: u64,
+    /// Corrupt entries repaired since startup.
+    pub repaired: u64,
+    /// IDs of polyps the last full pass left corrupt (at most 100).
+    pub unrepaired: Vec<String>,
+}
+
+/// One URL's announcement of a DID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityClaim {
+    /// The announcing URL.
+    pub url: String,
+    /// Hex-encoded hotkey of the latest valid signed announcement.
+    pub signer: Option<String>,
+    /// Signing time of the latest valid signed announcement.
+    pub signed_at: Option<DateTime<Utc>>,
+    /// When the URL last announced (or reported) the DID.
+    pub last_seen: DateTime<Utc>,
+}
+
 /// Handle a GetHealth request.
 ///
-/// When peer_count > 0, reports p2p_ok as true.
+/// When peer_count > 0, reports p2p_ok as true. Identity conflicts and
+/// unrepaired corrupt polyps make the node "degraded"; the latter also clear
+/// storage_ok.
 pub async fn handle_get_health(
     _request: GetHealthRequest,
     peer_count: usize,
+    identity_conflicts: Vec<IdentityConflict>,
+    scrub: Option<ScrubReport>,
 ) -> Result<GetHealthResponse, String> {
     let p2p_ok = peer_count > 0;
-    let details = if p2p_ok {
+    let mut details = if p2p_ok {
         format!("HTTP relay active: {} peers configured", peer_count)
     } else {
         "Local-only mode (no peers configured)".to_string()
     };
+    let mut status = "healthy";
+    if !identity_conflicts.is_empty() {
+        details.push_str(&format!(
+            "; {} DIDs announced from more than one URL",
+            identity_conflicts.len()
+        ));
+        status = "degraded";
+    }
+    let corrupt = scrub.as_ref().map_or(0, |s| s.unrepaired.len());
+    if corrupt > 0 {
+        details.push_str(&format!("; {} corrupt polyps not repaired", corrupt));
+        status = "degraded";
+    }
 
     Ok(GetHealthResponse {
-        status: "healthy".to_string(),
-        storage_ok: true,
+        status: status.to_string(),
+        storage_ok: corrupt == 0,
         p2p_ok,
         index_ok: true,
         peer_count,
+        identity_conflicts,
+        scrub,
         details: Some(details),
     })
 }
@@ -151,6 +270,30 @@ pub struct PeerInfo {
     pub node_type: Option<String>,
     /// Connection latency in milliseconds.
     pub latency_ms: Option<u64>,
+    /// Whether the last dial succeeded (if known).
+    #[serde(default)]
+    pub alive: Option<bool>,
+    /// Outbound dial statistics (if tracked).
+    #[serde(default)]
+    pub dial: Option<DialStats>,
+}
+
+/// Outbound dial statistics for a peer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DialStats {
+    /// Calls made to the peer.
+    pub attempts: u64,
+    /// Calls that failed.
+    pub failures: u64,
+    /// Failures since the last successful call.
+    pub consecutive_failures: u32,
+    /// When a call last succeeded.
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// When a call last failed.
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// While backing off, the earliest time the peer is dialed again.
+    pub next_dial_at: Option<DateTime<Utc>>,
 }
 
 /// Response containing the list of connected peers.
@@ -1,15 +1,35 @@
 // crates/chitin-rpc/src/handlers/peer.rs
 //
-// Peer-to-peer relay handlers: Announce, ReceivePolyp, ListPolypIds.
+// Peer-to-peer relay handlers: Announce, ReceivePolyp, ReceiveStateUpdate,
+// ListPolypIds, GetPolypsBatch, GetShards.
 // These endpoints enable HTTP-based polyp propagation between nodes.
+// Announcements may be signed with the announcer's hotkey, so that when two
+// nodes announce the same DID the receiver can tell which announced last. Nodes
+// holding only some shards drop relayed polyps outside them, and every node
+// drops relayed polyps it has pruned. Relayed polyps share the pull-sync
+// ingestion throttle.
 
 use std::sync::Arc;
+use std::time::Instant;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use chitin_core::traits::{PolypStore, VectorIndex};
-use chitin_store::{InMemoryVectorIndex, RocksStore};
+use chitin_consensus::hardening::{verify_lineage, HardeningCheckpoint};
+use chitin_core::crypto;
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::ChitinError;
+use chitin_core::IdentityRegistry;
+use chitin_core::traits::PolypStore;
+use chitin_store::{InMemoryVectorIndex, RocksStore, ShardSet};
+use chitin_sync::state_update::{store_state_update, PolypStateUpdate};
+use chitin_sync::throttle::SyncThrottle;
+use chitin_sync::tombstone::is_tombstoned;
+use chitin_sync::transfer::{CompressedPolyps, Compression, MAX_POLYP_BATCH};
+
+use super::validation::{decode_hex, encode_hex};
 
 // ---------------------------------------------------------------------------
 // peer/announce
@@ -22,6 +42,67 @@ pub struct AnnounceRequest {
     pub node_id: Option<String>,
     /// The announcing node's public URL.
     pub url: Option<String>,
+    /// The network the announcing node runs on.
+    #[serde(default)]
+    pub network_id: Option<String>,
+    /// When the announcement was signed.
+    #[serde(default)]
+    pub signed_at: Option<DateTime<Utc>>,
+    /// Hex-encoded hotkey the announcement is signed with.
+    #[serde(default)]
+    pub signer: Option<String>,
+    /// Hex-encoded ed25519 signature over `signable_bytes()` by `signer`.
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+impl AnnounceRequest {
+    /// Canonical bytes to sign: SHA-256 of the JSON-encoded node ID, URL,
+    /// network ID, and signing time.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, ChitinError> {
+        let body =
+            serde_json::to_vec(&(&self.node_id, &self.url, &self.network_id, &self.signed_at))?;
+        Ok(crypto::hash_bytes(&body).to_vec())
+    }
+
+    /// Sign the announcement with the node's hotkey as of now, filling in
+    /// `signed_at`, `signer`, and `signature`.
+    pub fn sign(&mut self, signing_key: &[u8; 32], hotkey: [u8; 32]) -> Result<(), ChitinError> {
+        self.signed_at = Some(Utc::now());
+        self.signer = Some(encode_hex(&hotkey));
+        let signature = crypto::sign_message(signing_key, &self.signable_bytes()?)?;
+        self.signature = Some(encode_hex(&signature));
+        Ok(())
+    }
+
+    /// Verify the signature against `signer`.
+    ///
+    /// Returns `Ok(false)` for unsigned announcements, malformed hex, and
+    /// invalid signatures.
+    pub fn verify_signature(&self) -> Result<bool, ChitinError> {
+        let (signer, signature) = match (&self.signer, &self.signature, &self.signed_at) {
+            (Some(signer), Some(signature), Some(_)) => (signer, signature),
+            _ => return Ok(false),
+        };
+        let hotkey: [u8; 32] = match decode_hex(signer).map(|bytes| bytes.try_into()) {
+            Some(Ok(hotkey)) => hotkey,
+            _ => return Ok(false),
+        };
+        let signature = match decode_hex(signature) {
+            Some(signature) if signature.len() == 64 => signature,
+            _ => return Ok(false),
+        };
+        crypto::verify_signature(&hotkey, &self.signable_bytes()?, &signature)
+    }
+
+    /// Whether the announcer is on `network_id`. Announcements where either
+    /// side has no network ID are accepted.
+    pub fn same_network(&self, network_id: Option<&str>) -> bool {
+        match (network_id, self.network_id.as_deref()) {
+            (Some(ours), Some(theirs)) => ours == theirs,
+            _ => true,
+        }
+    }
 }
 
 /// Response to a peer announcement.
@@ -31,6 +112,9 @@ pub struct AnnounceResponse {
     pub node_id: Option<String>,
     /// This node's public URL.
     pub url: Option<String>,
+    /// The network this node runs on.
+    #[serde(default)]
+    pub network_id: Option<String>,
     /// Acknowledgement message.
     pub message: String,
 }
@@ -55,18 +139,22 @@ pub async fn handle_announce(
     Ok(AnnounceResponse {
         node_id: None, // Overridden by dispatch if identity is set
         url: None,     // Overridden by dispatch if self_url is set
+        network_id: None,
         message: "Announcement received".to_string(),
     })
 }
 
 /// Handle a peer/announce request with node identity context.
 ///
-/// This version receives the node's DID and self URL from the service layer
-/// and includes them in the response.
+/// This version receives the node's DID, self URL, and network ID from the
+/// service layer and includes them in the response. Announcements from a
+/// different network are refused; the response still carries this node's
+/// network ID so the announcer can tell why.
 pub async fn handle_announce_with_identity(
     request: AnnounceRequest,
     self_did: Option<String>,
     self_url: Option<String>,
+    network_id: Option<String>,
 ) -> Result<AnnounceResponse, String> {
     tracing::info!(
         "Received peer announcement from node_id={:?} url={:?}",
@@ -74,9 +162,25 @@ pub async fn handle_announce_with_identity(
         request.url
     );
 
+    if !request.same_network(network_id.as_deref()) {
+        tracing::warn!(
+            "Refusing announcement from {:?} on network {:?} (this node: {:?})",
+            request.url,
+            request.network_id,
+            network_id
+        );
+        return Ok(AnnounceResponse {
+            node_id: self_did,
+            url: self_url,
+            network_id,
+            message: "Announcement refused: network mismatch".to_string(),
+        });
+    }
+
     Ok(AnnounceResponse {
         node_id: self_did,
         url: self_url,
+        network_id,
         message: "Announcement received".to_string(),
     })
 }
@@ -108,15 +212,27 @@ pub struct ReceivePolypResponse {
 /// Handle a peer/receive_polyp request.
 ///
 /// Deduplicates by UUID — if the polyp already exists locally, it's a no-op.
-/// If new, saves to store and indexes the vector.
+/// If new, saves to store and indexes the vector. Polyps outside `shards`
+/// are not stored.
 pub async fn handle_receive_polyp(
     store: &Arc<RocksStore>,
     index: &Arc<InMemoryVectorIndex>,
     request: ReceivePolypRequest,
+    shards: Option<&ShardSet>,
+    throttle: Option<&SyncThrottle>,
 ) -> Result<ReceivePolypResponse, String> {
     let polyp = request.polyp;
     let polyp_id = polyp.id;
 
+    if shards.is_some_and(|s| !s.contains_polyp(&polyp)) {
+        tracing::debug!("Polyp {} is outside this node's shards, skipping", polyp_id);
+        return Ok(ReceivePolypResponse {
+            accepted: false,
+            duplicate: false,
+            message: format!("Polyp {} is outside this node's shards", polyp_id),
+        });
+    }
+
     // Phase 2: Log signature verification status if polyp has a signature.
     if polyp.signature.is_some() {
         let creator_hotkey = &polyp.subject.provenance.creator.hotkey;
@@ -157,19 +273,47 @@ pub async fn handle_receive_polyp(
         });
     }
 
+    let pruned = is_tombstoned(store, &polyp_id)
+        .map_err(|e| format!("Failed to check polyp tombstone: {}", e))?;
+    if pruned {
+        tracing::debug!("Polyp {} was pruned locally, skipping", polyp_id);
+        return Ok(ReceivePolypResponse {
+            accepted: false,
+            duplicate: true,
+            message: format!("Polyp {} was pruned by this node", polyp_id),
+        });
+    }
+
+    // Backpressure: wait for an ingestion permit shared with pull-sync and
+    // pace while store writes are slow. Holding the request open slows the
+    // relaying peer down.
+    let _permit = match throttle {
+        Some(throttle) => {
+            let source = request.source_did.as_deref().unwrap_or("unknown");
+            let permit = throttle.acquire(&format!("gossip:{}", source)).await;
+            throttle.pace().await;
+            Some(permit)
+        }
+        None => None,
+    };
+
     // Extract vector values before saving (we need them for indexing).
     let values = polyp.subject.vector.values.clone();
+    let model = polyp.subject.vector.model_id.key();
 
     // Save to RocksDB.
+    let started = Instant::now();
     store
         .save_polyp(&polyp)
         .await
         .map_err(|e| format!("Failed to save received polyp: {}", e))?;
+    if let Some(throttle) = throttle {
+        throttle.record_write(started.elapsed());
+    }
 
     // Index the vector.
     index
-        .upsert(polyp_id, &values)
-        .await
+        .upsert_in(&model, polyp_id, &values)
         .map_err(|e| format!("Failed to index received polyp: {}", e))?;
 
     tracing::info!(
@@ -185,13 +329,104 @@ pub async fn handle_receive_polyp(
     })
 }
 
+// ---------------------------------------------------------------------------
+// peer/receive_state_update
+// ---------------------------------------------------------------------------
+
+/// A gossiped polyp state transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiveStateUpdateRequest {
+    pub update: PolypStateUpdate,
+}
+
+/// Whether the transition was applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiveStateUpdateResponse {
+    pub applied: bool,
+    pub message: String,
+}
+
+/// Handle a gossiped state transition (`peer/receive_state_update`).
+///
+/// Updates must be signed by a registered validator and not be from an
+/// epoch after `current_epoch`; molts are not accepted. Hardened updates are
+/// applied only if their lineage checks out against this node's checkpoint
+/// for the epoch; without one they are left for sync, which can obtain the
+/// root from peers.
+pub async fn handle_receive_state_update(
+    store: &Arc<RocksStore>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+    current_epoch: u64,
+    request: ReceiveStateUpdateRequest,
+) -> Result<ReceiveStateUpdateResponse, String> {
+    let update = request.update;
+    let rejected = |message: String| {
+        Ok(ReceiveStateUpdateResponse {
+            applied: false,
+            message,
+        })
+    };
+
+    let identities = identities.ok_or_else(|| "State updates not available".to_string())?;
+    if let Err(e) = update.verify_origin(&*identities.read().await, current_epoch) {
+        tracing::warn!("Rejected state update for polyp {}: {}", update.polyp_id, e);
+        return rejected(e.to_string());
+    }
+
+    if update.state == PolypState::Hardened {
+        let lineage = match &update.hardening {
+            Some(lineage) => lineage,
+            None => return rejected("Hardened update without lineage".to_string()),
+        };
+        let checkpoint = HardeningCheckpoint::load(store, update.epoch)
+            .map_err(|e| format!("Failed to load hardening checkpoint: {}", e))?;
+        match checkpoint {
+            Some(checkpoint) => {
+                if let Err(e) = verify_lineage(&update.polyp_id, lineage, &checkpoint) {
+                    return rejected(e.to_string());
+                }
+            }
+            None => {
+                return rejected(format!(
+                    "No hardening checkpoint for epoch {}, deferring to sync",
+                    update.epoch
+                ))
+            }
+        }
+    }
+
+    let applied = store_state_update(store, &update)
+        .await
+        .map_err(|e| format!("Failed to apply state update: {}", e))?;
+    if applied {
+        tracing::info!(
+            "Polyp {} moved to {:?} (epoch {}) by peer update",
+            update.polyp_id,
+            update.state,
+            update.epoch
+        );
+    }
+    Ok(ReceiveStateUpdateResponse {
+        applied,
+        message: if applied {
+            format!("Polyp {} updated", update.polyp_id)
+        } else {
+            format!("Polyp {} unknown or already current", update.polyp_id)
+        },
+    })
+}
+
 // ---------------------------------------------------------------------------
 // peer/list_polyp_ids
 // ---------------------------------------------------------------------------
 
 /// Request to list all polyp UUIDs on this node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ListPolypIdsRequest {}
+pub struct ListPolypIdsRequest {
+    /// Only list polyps in these shards (the requester's subscription).
+    #[serde(default)]
+    pub shards: Option<ShardSet>,
+}
 
 /// Response containing all local polyp UUIDs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,8 +443,24 @@ pub struct ListPolypIdsResponse {
 /// find which polyps the remote has that we're missing.
 pub async fn handle_list_polyp_ids(
     store: &Arc<RocksStore>,
-    _request: ListPolypIdsRequest,
+    request: ListPolypIdsRequest,
 ) -> Result<ListPolypIdsResponse, String> {
+    let all_ids = held_polyp_ids(store, request.shards.as_ref()).await?;
+    let count = all_ids.len();
+    Ok(ListPolypIdsResponse { ids: all_ids, count })
+}
+
+/// All polyp UUIDs in the local store, across every state.
+pub(crate) async fn local_polyp_ids(store: &Arc<RocksStore>) -> Result<Vec<Uuid>, String> {
+    held_polyp_ids(store, None).await
+}
+
+/// Local polyp UUIDs held by `shards` (all of them if `None`), taking each
+/// polyp's zone replication into account.
+pub(crate) async fn held_polyp_ids(
+    store: &Arc<RocksStore>,
+    shards: Option<&ShardSet>,
+) -> Result<Vec<Uuid>, String> {
     // Collect IDs from all states.
     let states = [
         chitin_core::polyp::PolypState::Draft,
@@ -227,12 +478,111 @@ pub async fn handle_list_polyp_ids(
             .await
             .map_err(|e| format!("Failed to list polyps in state {:?}: {}", state, e))?;
         for p in polyps {
-            all_ids.push(p.id);
+            if shards.is_none_or(|s| s.contains_polyp(&p)) {
+                all_ids.push(p.id);
+            }
         }
     }
+    Ok(all_ids)
+}
 
-    let count = all_ids.len();
-    Ok(ListPolypIdsResponse { ids: all_ids, count })
+// ---------------------------------------------------------------------------
+// peer/get_polyps_batch
+// ---------------------------------------------------------------------------
+
+/// Request for several polyps at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPolypsBatchRequest {
+    /// Polyp UUIDs to fetch (at most `MAX_POLYP_BATCH`).
+    pub ids: Vec<Uuid>,
+    /// Compress the returned polyps with this codec.
+    #[serde(default)]
+    pub compression: Option<Compression>,
+}
+
+/// The requested polyps that exist on this node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPolypsBatchResponse {
+    /// Found polyps, in request order (empty when `compressed` is set).
+    pub polyps: Vec<Polyp>,
+    /// Found polyps, compressed, if the request asked for compression.
+    pub compressed: Option<CompressedPolyps>,
+    /// Requested IDs not stored on this node.
+    pub not_found: Vec<Uuid>,
+}
+
+/// Handle a peer/get_polyps_batch request.
+///
+/// Used by pull-sync in place of one `polyp/get` per missing polyp.
+pub async fn handle_get_polyps_batch(
+    store: &Arc<RocksStore>,
+    request: GetPolypsBatchRequest,
+) -> Result<GetPolypsBatchResponse, String> {
+    if request.ids.len() > MAX_POLYP_BATCH {
+        return Err(format!(
+            "Batch of {} polyps exceeds limit of {}",
+            request.ids.len(),
+            MAX_POLYP_BATCH
+        ));
+    }
+
+    let mut polyps = Vec::with_capacity(request.ids.len());
+    let mut not_found = Vec::new();
+    for id in &request.ids {
+        match store
+            .get_polyp(id)
+            .await
+            .map_err(|e| format!("Failed to get polyp {}: {}", id, e))?
+        {
+            Some(polyp) => polyps.push(polyp),
+            None => not_found.push(*id),
+        }
+    }
+
+    match request.compression {
+        Some(compression) => {
+            let compressed =
+                CompressedPolyps::compress(&polyps, compression).map_err(|e| e.to_string())?;
+            Ok(GetPolypsBatchResponse {
+                polyps: Vec::new(),
+                compressed: Some(compressed),
+                not_found,
+            })
+        }
+        None => Ok(GetPolypsBatchResponse {
+            polyps,
+            compressed: None,
+            not_found,
+        }),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// peer/shards
+// ---------------------------------------------------------------------------
+
+/// Request for the shards this node holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetShardsRequest {}
+
+/// The shards this node syncs and stores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetShardsResponse {
+    /// Held shards.
+    pub shards: ShardSet,
+}
+
+/// Handle a peer/shards request.
+///
+/// Peers use the reply to route queries for shards they do not hold. A node
+/// without a shard subscription holds everything.
+pub async fn handle_get_shards(
+    _request: GetShardsRequest,
+    shards: Option<&ShardSet>,
+) -> Result<GetShardsResponse, String> {
+    Ok(GetShardsResponse {
+        shards: shards.cloned().unwrap_or_else(|| ShardSet::all(1)),
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -1,22 +1,41 @@
 // crates/chitin-rpc/src/handlers/polyp.rs
 //
-// Polyp management handlers: Submit, Get, List, GetState, GetProvenance, GetHardeningReceipt.
-// These handlers interact with chitin-store's RocksStore and HardenedStore.
+// Polyp management handlers: Submit, SubmitBatch, IngestUrl, Get, List,
+// Count, GetState, GetProvenance, VerifyProvenance, GetHardeningReceipt,
+// GetLineage, ImportPolyps, SubscribePolyps. These handlers interact with
+// chitin-store's RocksStore and HardenedStore. On nodes holding only some
+// shards, Get asks the responsible peers. ImportPolyps stores complete Polyps exported from
+// another node. SubscribePolyps long-polls the node's feed of newly stored
+// Polyps.
 
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
 use uuid::Uuid;
 
+use chitin_core::language::resolve_language;
 use chitin_core::polyp::{Polyp, PolypState};
-use chitin_core::traits::{PolypStore, VectorIndex};
+use chitin_core::traits::PolypStore;
 use chitin_core::{
     hash_embedding, EmbeddingModelId, NodeIdentity, NodeType, Payload, PolypSubject,
-    PipelineStep, ProcessingPipeline, Provenance, ProofPublicInputs, SourceAttribution,
-    VectorEmbedding, ZkProof,
+    PipelineStep, ProcessingPipeline, Provenance, ProvenanceReport, ProofPublicInputs,
+    SourceAttribution, VectorEmbedding, ZkProof, HASH_EMBEDDING_MODEL,
 };
-use chitin_store::{InMemoryVectorIndex, RocksStore};
+use chitin_consensus::dedup::{load_flag, DuplicateMatch};
+use chitin_consensus::hardening::{verify_lineage, HardeningCheckpoint};
+use chitin_consensus::moderation::{load_record, ModerationRecord};
+use chitin_drift::molting::{molt_lineage, LineageEntry};
+use chitin_drift::versioning::VersionRegistry;
+use chitin_reputation::taxonomy::DomainTaxonomy;
+use chitin_store::{InMemoryVectorIndex, IpfsClient, RocksStore, ShardAssigner, ShardSet};
+
+use crate::handlers::peer::{handle_receive_polyp, ReceivePolypRequest};
+use crate::server::ShardRouting;
 
 // ---------------------------------------------------------------------------
 // SubmitPolyp
@@ -33,10 +52,37 @@ pub struct SubmitPolypRequest {
     pub language: Option<String>,
     /// Pre-computed vector embedding values (if the caller already embedded).
     pub vector: Option<Vec<f32>>,
+    /// Model `vector` came from, as a space key (e.g. "openai/text-embedding-3-small").
+    /// Defaults to the built-in hash embedding.
+    #[serde(default)]
+    pub model_id: Option<String>,
     /// Source URL for provenance.
     pub source_url: Option<String>,
     /// Source title for provenance.
     pub source_title: Option<String>,
+    /// License of the source (e.g. "CC-BY-4.0"), checked against the
+    /// zone's allowed licenses.
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Reef Zone to submit to (e.g., "code/rust"). Must exist in the taxonomy.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+    /// Steps that produced `content` before submission (e.g. fetch, extract,
+    /// chunk), recorded in provenance ahead of `rpc-submit`.
+    #[serde(default)]
+    pub pipeline: Vec<PipelineStep>,
+}
+
+/// Dimensions of the hash embedding used when no vector is supplied.
+pub const DEFAULT_DIMENSIONS: usize = 384;
+
+/// A vector produced for submitted content by the node's embedder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedContent {
+    /// Space key of the model that produced the vector.
+    pub model_id: String,
+    /// The embedding values.
+    pub values: Vec<f32>,
 }
 
 /// Response from submitting a Polyp.
@@ -48,6 +94,18 @@ pub struct SubmitPolypResponse {
     pub state: String,
     /// Human-readable status message.
     pub message: String,
+    /// The hardened Polyp this one nearly duplicates, if any.
+    #[serde(default)]
+    pub duplicate_of: Option<DuplicateMatch>,
+    /// Why moderation rejected the Polyp, if it did.
+    #[serde(default)]
+    pub rejection: Option<ModerationRecord>,
+    /// Language recorded for the Polyp: detected, or as declared.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Reef Zone the Polyp was routed to.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
 }
 
 /// Handle a SubmitPolyp request.
@@ -60,43 +118,75 @@ pub async fn handle_submit_polyp(
     index: &Arc<InMemoryVectorIndex>,
     request: SubmitPolypRequest,
 ) -> Result<SubmitPolypResponse, String> {
-    handle_submit_polyp_with_identity(store, index, request, None, None).await
+    handle_submit_polyp_with_identity(store, index, request, None, None, None, None).await
 }
 
 /// Handle a SubmitPolyp request with optional identity and signing key.
 ///
 /// When `node_identity` is provided, it is used for provenance instead of
 /// the placeholder. When `signing_key` is provided, the polyp is signed.
+/// The content's language is detected and recorded when the detection is
+/// reliable, overriding the declared one. When `taxonomy` is provided, a
+/// requested `reef_zone` must exist in it, the Polyp is routed to the zone
+/// for its language, and it must meet that zone's policy. When `models` is
+/// provided, the embedding model must not be retired at the given epoch.
 pub async fn handle_submit_polyp_with_identity(
     store: &Arc<RocksStore>,
     index: &Arc<InMemoryVectorIndex>,
     request: SubmitPolypRequest,
     node_identity: Option<&NodeIdentity>,
     signing_key: Option<&[u8; 32]>,
+    taxonomy: Option<&DomainTaxonomy>,
+    models: Option<(&VersionRegistry, u64)>,
 ) -> Result<SubmitPolypResponse, String> {
+    let language = resolve_language(request.language.as_deref(), &request.content)
+        .map_err(|e| e.to_string())?;
+    let reef_zone = match (request.reef_zone, taxonomy) {
+        (Some(zone), Some(taxonomy)) => {
+            taxonomy.validate(&zone).map_err(|e| e.to_string())?;
+            let routed = taxonomy
+                .route(&zone, language.as_deref())
+                .map_err(|e| e.to_string())?;
+            Some(routed)
+        }
+        (zone, _) => zone,
+    };
+
     let now = Utc::now();
     let polyp_id = Uuid::now_v7();
 
     // Generate embedding: use caller-provided vector or deterministic hash embedding.
-    let dimensions = 384usize;
-    let values = request.vector.unwrap_or_else(|| hash_embedding(&request.content, dimensions));
+    let values = request
+        .vector
+        .unwrap_or_else(|| hash_embedding(&request.content, DEFAULT_DIMENSIONS));
+    let model_key = request.model_id.as_deref().unwrap_or(HASH_EMBEDDING_MODEL);
+    let model_id = EmbeddingModelId::from_key(model_key, values.len() as u32);
 
     let embedding = VectorEmbedding {
         values: values.clone(),
-        model_id: EmbeddingModelId {
-            provider: "chitin".to_string(),
-            name: "hash-embedding-v1".to_string(),
-            weights_hash: [0u8; 32],
-            dimensions: dimensions as u32,
-        },
+        model_id: model_id.clone(),
         quantization: "float32".to_string(),
         normalization: "l2".to_string(),
     };
+    let model = embedding.model_id.key();
+    if let Some((registry, epoch)) = models {
+        registry
+            .check_submission(&model, epoch)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut steps = request.pipeline;
+    steps.push(PipelineStep {
+        name: "rpc-submit".to_string(),
+        version: "0.1.0".to_string(),
+        params: serde_json::json!({}),
+        hash: None,
+    });
 
     let payload = Payload {
         content: request.content,
         content_type: request.content_type,
-        language: request.language,
+        language: language.clone(),
     };
 
     // Use real identity for provenance if available, otherwise placeholder.
@@ -106,24 +196,22 @@ pub async fn handle_submit_polyp_with_identity(
         did: "did:chitin:local".to_string(),
         node_type: NodeType::Coral,
     });
-    let provenance = Provenance {
+    let mut provenance = Provenance {
         creator,
         source: SourceAttribution {
             source_cid: None,
             source_url: request.source_url,
             title: request.source_title,
-            license: None,
+            license: request.license,
             accessed_at: now,
         },
         pipeline: ProcessingPipeline {
-            steps: vec![PipelineStep {
-                name: "rpc-submit".to_string(),
-                version: "0.1.0".to_string(),
-                params: serde_json::json!({}),
-            }],
+            steps,
             duration_ms: 0,
         },
+        molted_from: vec![],
     };
+    provenance.pipeline.seal();
 
     let subject = PolypSubject {
         payload,
@@ -138,12 +226,7 @@ pub async fn handle_submit_polyp_with_identity(
         public_inputs: ProofPublicInputs {
             text_hash: [0u8; 32],
             vector_hash: [0u8; 32],
-            model_id: EmbeddingModelId {
-                provider: "chitin".to_string(),
-                name: "hash-embedding-v1".to_string(),
-                weights_hash: [0u8; 32],
-                dimensions: dimensions as u32,
-            },
+            model_id,
         },
         created_at: now,
     };
@@ -158,7 +241,19 @@ pub async fn handle_submit_polyp_with_identity(
         created_at: now,
         updated_at: now,
         signature: None,
+        reef_zone: reef_zone.clone(),
     };
+    if let Some(taxonomy) = taxonomy {
+        let policy = taxonomy.policy(reef_zone.as_deref());
+        if let Some((code, reason)) = policy.violation(&polyp) {
+            return Err(format!(
+                "Polyp violates the policy of reef zone {} ({}): {}",
+                reef_zone.as_deref().unwrap_or_default(),
+                code,
+                reason
+            ));
+        }
+    }
 
     // Sign the polyp if a signing key is available.
     if let Some(key) = signing_key {
@@ -177,17 +272,139 @@ pub async fn handle_submit_polyp_with_identity(
 
     // Upsert into vector index for search.
     index
-        .upsert(polyp_id, &values)
-        .await
+        .upsert_in(&model, polyp_id, &values)
         .map_err(|e| format!("Failed to index polyp: {}", e))?;
 
     Ok(SubmitPolypResponse {
         polyp_id,
         state: "Draft".to_string(),
         message: "Polyp submitted and indexed successfully".to_string(),
+        duplicate_of: None,
+        rejection: None,
+        language,
+        reef_zone,
     })
 }
 
+// ---------------------------------------------------------------------------
+// SubmitPolypBatch
+// ---------------------------------------------------------------------------
+
+/// Most polyps a single `polyp/submit_batch` request may carry.
+pub const MAX_SUBMIT_BATCH: usize = 256;
+
+/// Request to submit several Polyps at once (e.g. the chunks of an imported
+/// document).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitPolypBatchRequest {
+    /// The submissions, at most `MAX_SUBMIT_BATCH`.
+    pub polyps: Vec<SubmitPolypRequest>,
+}
+
+/// Outcome of one submission in a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSubmitResult {
+    /// The UUID assigned to the new Polyp, if it was submitted.
+    pub polyp_id: Option<Uuid>,
+    /// Why the submission failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Response from submitting a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitPolypBatchResponse {
+    /// One result per submission, in request order.
+    pub results: Vec<BatchSubmitResult>,
+}
+
+/// Check that a batch is within `MAX_SUBMIT_BATCH`.
+pub fn check_submit_batch(request: &SubmitPolypBatchRequest) -> Result<(), String> {
+    if request.polyps.len() > MAX_SUBMIT_BATCH {
+        return Err(format!(
+            "Batch of {} polyps exceeds limit of {}",
+            request.polyps.len(),
+            MAX_SUBMIT_BATCH
+        ));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// IngestUrl
+// ---------------------------------------------------------------------------
+
+/// Request to fetch a URL and submit its text as chunk Polyps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestUrlRequest {
+    /// The http(s) URL to fetch.
+    pub url: String,
+    /// Reef Zone to submit the chunks to.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+    /// Language code of the content (e.g., "en").
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Chunk size in characters (defaults to the node's setting).
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
+    /// Characters shared by consecutive chunks (defaults to the node's setting).
+    #[serde(default)]
+    pub chunk_overlap: Option<usize>,
+}
+
+/// One chunk of an ingested document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestedChunk {
+    /// The chunk's text.
+    pub content: String,
+    /// Steps that produced the chunk, for provenance.
+    pub pipeline: Vec<PipelineStep>,
+}
+
+/// A fetched document, extracted and chunked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestedDocument {
+    /// The document title, if one was found.
+    pub title: Option<String>,
+    /// The chunks, in document order.
+    pub chunks: Vec<IngestedChunk>,
+}
+
+/// Response from ingesting a URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestUrlResponse {
+    /// The URL ingested.
+    pub url: String,
+    /// The document title, if one was found.
+    pub title: Option<String>,
+    /// The submitted chunk Polyps, in document order.
+    pub polyp_ids: Vec<Uuid>,
+}
+
+/// Build one submission per chunk of `document`, attributed to the
+/// requested URL.
+pub fn chunk_submissions(
+    request: &IngestUrlRequest,
+    document: &IngestedDocument,
+) -> Vec<SubmitPolypRequest> {
+    document
+        .chunks
+        .iter()
+        .map(|chunk| SubmitPolypRequest {
+            content: chunk.content.clone(),
+            content_type: "text/plain".to_string(),
+            language: request.language.clone(),
+            vector: None,
+            model_id: None,
+            source_url: Some(request.url.clone()),
+            source_title: document.title.clone(),
+            license: None,
+            reef_zone: request.reef_zone.clone(),
+            pipeline: chunk.pipeline.clone(),
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // GetPolyp
 // ---------------------------------------------------------------------------
@@ -206,6 +423,9 @@ pub struct GetPolypResponse {
     pub polyp: Option<Polyp>,
     /// Whether the Polyp was found.
     pub found: bool,
+    /// The hardened Polyp it was flagged as nearly duplicating, if any.
+    #[serde(default)]
+    pub duplicate_of: Option<DuplicateMatch>,
 }
 
 /// Handle a GetPolyp request.
@@ -217,13 +437,45 @@ pub async fn handle_get_polyp(
         .get_polyp(&request.polyp_id)
         .await
         .map_err(|e| format!("Failed to get polyp: {}", e))?;
+    let duplicate_of = match &polyp {
+        Some(_) => load_flag(store, &request.polyp_id)
+            .map_err(|e| format!("Failed to get duplicate flag: {}", e))?,
+        None => None,
+    };
 
     Ok(GetPolypResponse {
         found: polyp.is_some(),
         polyp,
+        duplicate_of,
     })
 }
 
+/// Handle a GetPolyp request, asking the responsible peers for polyps in
+/// shards this node does not hold.
+pub async fn handle_get_polyp_routed(
+    store: &Arc<RocksStore>,
+    request: GetPolypRequest,
+    routing: Option<&ShardRouting>,
+) -> Result<GetPolypResponse, String> {
+    let polyp_id = request.polyp_id;
+    let local = handle_get_polyp(store, request).await?;
+    let routing = match routing {
+        Some(routing) if !local.found && !routing.shards.contains(&polyp_id) => routing,
+        _ => return Ok(local),
+    };
+
+    let shard = ShardAssigner::new(routing.shards.num_shards()).assign_shard(&polyp_id);
+    let params = serde_json::json!({ "polyp_id": polyp_id });
+    for reply in routing.forward("polyp/get", params, vec![shard]).await {
+        match serde_json::from_value::<GetPolypResponse>(reply) {
+            Ok(resp) if resp.found => return Ok(resp),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("GetPolyp: ignoring malformed peer reply: {}", e),
+        }
+    }
+    Ok(local)
+}
+
 // ---------------------------------------------------------------------------
 // ListPolyps
 // ---------------------------------------------------------------------------
@@ -283,6 +535,63 @@ pub async fn handle_list_polyps(
     })
 }
 
+// ---------------------------------------------------------------------------
+// CountPolyps
+// ---------------------------------------------------------------------------
+
+/// Request for the number of Polyps in each lifecycle state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountPolypsRequest {}
+
+/// The number of Polyps in one lifecycle state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateCount {
+    /// The state name, as accepted by `polyp/list`'s `state_filter`
+    /// (plus "Molted").
+    pub state: String,
+    /// Polyps in the state.
+    pub count: usize,
+}
+
+/// Response containing Polyp counts by state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountPolypsResponse {
+    /// Counts in lifecycle order.
+    pub counts: Vec<StateCount>,
+    /// Polyps in any state.
+    pub total: usize,
+}
+
+/// Handle a CountPolyps request (`polyp/counts`).
+///
+/// Counts from the state index without loading any Polyps.
+pub async fn handle_count_polyps(
+    store: &Arc<RocksStore>,
+    _request: CountPolypsRequest,
+) -> Result<CountPolypsResponse, String> {
+    let states = [
+        ("Draft", PolypState::Draft),
+        ("Soft", PolypState::Soft),
+        ("UnderReview", PolypState::UnderReview),
+        ("Approved", PolypState::Approved),
+        ("Hardened", PolypState::Hardened),
+        ("Rejected", PolypState::Rejected),
+        ("Molted", PolypState::Molted { successor_id: Uuid::nil() }),
+    ];
+    let mut counts = Vec::with_capacity(states.len());
+    for (name, state) in &states {
+        let count = store
+            .count_polyps_by_state(state)
+            .map_err(|e| format!("Failed to count polyps: {}", e))?;
+        counts.push(StateCount {
+            state: name.to_string(),
+            count,
+        });
+    }
+    let total = counts.iter().map(|c| c.count).sum();
+    Ok(CountPolypsResponse { counts, total })
+}
+
 // ---------------------------------------------------------------------------
 // GetPolypState
 // ---------------------------------------------------------------------------
@@ -301,6 +610,9 @@ pub struct GetPolypStateResponse {
     pub state: Option<String>,
     /// Whether the Polyp was found.
     pub found: bool,
+    /// Why moderation rejected the Polyp, if it did.
+    #[serde(default)]
+    pub rejection: Option<ModerationRecord>,
 }
 
 /// Handle a GetPolypState request.
@@ -317,10 +629,13 @@ pub async fn handle_get_polyp_state(
         Some(p) => Ok(GetPolypStateResponse {
             state: Some(format!("{:?}", p.state)),
             found: true,
+            rejection: load_record(store, &request.polyp_id)
+                .map_err(|e| format!("Failed to get moderation record: {}", e))?,
         }),
         None => Ok(GetPolypStateResponse {
             state: None,
             found: false,
+            rejection: None,
         }),
     }
 }
@@ -371,6 +686,77 @@ pub async fn handle_get_polyp_provenance(
     }
 }
 
+// ---------------------------------------------------------------------------
+// VerifyProvenance
+// ---------------------------------------------------------------------------
+
+/// Request to verify a Polyp's provenance chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyProvenanceRequest {
+    /// The UUID of the Polyp.
+    pub polyp_id: Uuid,
+    /// Also fetch the source CID from IPFS.
+    #[serde(default)]
+    pub resolve_cids: bool,
+}
+
+/// Response with the provenance checks run on a Polyp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyProvenanceResponse {
+    /// Whether the Polyp was found.
+    pub found: bool,
+    /// True if no check failed.
+    pub valid: bool,
+    /// Integrity score in [0.0, 1.0], as used in scoring.
+    pub score: f64,
+    /// Every check and what it found, if the Polyp was found.
+    pub report: Option<ProvenanceReport>,
+}
+
+/// Handle a VerifyProvenance request.
+///
+/// With `resolve_cids`, a `source resolution` check fetches the source CID
+/// through `ipfs`; it is skipped if the node has no IPFS client.
+pub async fn handle_verify_provenance(
+    store: &Arc<RocksStore>,
+    ipfs: Option<&IpfsClient>,
+    request: VerifyProvenanceRequest,
+) -> Result<VerifyProvenanceResponse, String> {
+    let polyp = store
+        .get_polyp(&request.polyp_id)
+        .await
+        .map_err(|e| format!("Failed to get polyp: {}", e))?;
+    let Some(polyp) = polyp else {
+        return Ok(VerifyProvenanceResponse {
+            found: false,
+            valid: false,
+            score: 0.0,
+            report: None,
+        });
+    };
+
+    let provenance = &polyp.subject.provenance;
+    let mut report = provenance.verify(polyp.created_at, Utc::now());
+    if request.resolve_cids {
+        match (&provenance.source.source_cid, ipfs) {
+            (None, _) => report.skip("source resolution", "no source CID"),
+            (Some(_), None) => report.skip("source resolution", "no IPFS client on this node"),
+            (Some(cid), Some(ipfs)) => match ipfs.get_by_cid(cid).await {
+                Ok(bytes) => {
+                    report.check("source resolution", true, format!("{} bytes", bytes.len()))
+                }
+                Err(e) => report.check("source resolution", false, e.to_string()),
+            },
+        }
+    }
+    Ok(VerifyProvenanceResponse {
+        found: true,
+        valid: report.is_valid(),
+        score: report.score(),
+        report: Some(report),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // GetHardeningReceipt
 // ---------------------------------------------------------------------------
@@ -422,3 +808,284 @@ pub async fn handle_get_hardening_receipt(
         }),
     }
 }
+
+// ---------------------------------------------------------------------------
+// GetLineage
+// ---------------------------------------------------------------------------
+
+/// Request to get the molt lineage of a Polyp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetLineageRequest {
+    /// The UUID of any version of the Polyp.
+    pub polyp_id: Uuid,
+}
+
+/// Response containing every version of the Polyp, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetLineageResponse {
+    /// The molt chain, oldest first; the last entry is the current version.
+    pub chain: Vec<LineageEntry>,
+    /// Whether the Polyp was found.
+    pub found: bool,
+}
+
+/// Handle a GetLineage request.
+pub async fn handle_get_lineage(
+    store: &Arc<RocksStore>,
+    request: GetLineageRequest,
+) -> Result<GetLineageResponse, String> {
+    let chain = molt_lineage(store, &request.polyp_id)
+        .await
+        .map_err(|e| format!("Failed to get polyp lineage: {}", e))?;
+    Ok(GetLineageResponse {
+        found: !chain.is_empty(),
+        chain,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ImportPolyps
+// ---------------------------------------------------------------------------
+
+/// Most polyps a single `admin/polyp/import` request may carry.
+pub const MAX_IMPORT_BATCH: usize = 256;
+
+/// Request to store complete Polyps, e.g. from another node's export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPolypsRequest {
+    /// The Polyps, at most `MAX_IMPORT_BATCH`.
+    pub polyps: Vec<Polyp>,
+}
+
+/// Outcome of importing one Polyp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPolypResult {
+    pub polyp_id: Uuid,
+    /// Whether the Polyp was stored and passed moderation.
+    pub imported: bool,
+    /// Whether the node already had (or had pruned) the Polyp.
+    pub duplicate: bool,
+    /// Why a Polyp that is not a duplicate was not stored, or was rejected.
+    pub error: Option<String>,
+}
+
+/// Response from importing a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPolypsResponse {
+    /// One result per Polyp, in request order.
+    pub results: Vec<ImportPolypResult>,
+}
+
+/// Handle an ImportPolyps request (`admin/polyp/import`).
+///
+/// Polyps keep their IDs, signatures, and Draft or Soft state and are stored
+/// like gossiped ones: duplicates and pruned Polyps are skipped, as are
+/// Polyps outside `shards`. Unlike gossip, a Polyp that is unsigned or whose
+/// signature does not verify against its creator hotkey is refused. A
+/// Hardened Polyp stays Hardened only if its lineage verifies against this
+/// node's checkpoint for its epoch; that and every later state is otherwise
+/// untrusted, and the Polyp is stored as Soft to be evaluated again.
+pub async fn handle_import_polyps(
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    request: ImportPolypsRequest,
+    shards: Option<&ShardSet>,
+) -> Result<ImportPolypsResponse, String> {
+    if request.polyps.len() > MAX_IMPORT_BATCH {
+        return Err(format!(
+            "Batch of {} polyps exceeds limit of {}",
+            request.polyps.len(),
+            MAX_IMPORT_BATCH
+        ));
+    }
+
+    let mut results = Vec::with_capacity(request.polyps.len());
+    for mut polyp in request.polyps {
+        let polyp_id = polyp.id;
+        let creator_hotkey = polyp.subject.provenance.creator.hotkey;
+        if !polyp.verify_signature(&creator_hotkey).unwrap_or(false) {
+            results.push(ImportPolypResult {
+                polyp_id,
+                imported: false,
+                duplicate: false,
+                error: Some("Not signed by the creator hotkey".to_string()),
+            });
+            continue;
+        }
+        if !import_state_trusted(store, &polyp) {
+            polyp.state = PolypState::Soft;
+            polyp.consensus = None;
+            polyp.hardening = None;
+        }
+        let request = ReceivePolypRequest {
+            polyp,
+            source_did: Some("import".to_string()),
+        };
+        results.push(match handle_receive_polyp(store, index, request, shards, None).await {
+            Ok(resp) => ImportPolypResult {
+                polyp_id,
+                imported: resp.accepted,
+                duplicate: resp.duplicate,
+                error: (!resp.accepted && !resp.duplicate).then_some(resp.message),
+            },
+            Err(e) => ImportPolypResult {
+                polyp_id,
+                imported: false,
+                duplicate: false,
+                error: Some(e),
+            },
+        });
+    }
+    Ok(ImportPolypsResponse { results })
+}
+
+/// Whether an imported Polyp's state can be kept: Draft and Soft need no
+/// evaluation, and Hardened needs a lineage that verifies against this
+/// node's checkpoint for the Polyp's epoch.
+fn import_state_trusted(store: &RocksStore, polyp: &Polyp) -> bool {
+    match polyp.state {
+        PolypState::Draft | PolypState::Soft => true,
+        PolypState::Hardened => {
+            let (Some(lineage), Some(consensus)) = (&polyp.hardening, &polyp.consensus) else {
+                return false;
+            };
+            match HardeningCheckpoint::load(store, consensus.epoch) {
+                Ok(Some(checkpoint)) => verify_lineage(&polyp.id, lineage, &checkpoint).is_ok(),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SubscribePolyps
+// ---------------------------------------------------------------------------
+
+/// New-polyp events kept for subscribers that fall behind.
+pub const POLYP_FEED_CAPACITY: usize = 1024;
+
+/// Default time a `polyp/subscribe` call waits for an event.
+pub const DEFAULT_SUBSCRIBE_WAIT_MS: u64 = 10_000;
+
+/// Longest time a `polyp/subscribe` call may wait for an event.
+pub const MAX_SUBSCRIBE_WAIT_MS: u64 = 30_000;
+
+/// A Polyp this node stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolypEvent {
+    /// Position in the feed; consecutive events have consecutive numbers.
+    pub seq: u64,
+    /// The new Polyp.
+    pub polyp_id: Uuid,
+    /// How the Polyp arrived: "submitted" or "gossip".
+    pub source: String,
+}
+
+/// The most recent Polyps this node stored, for `polyp/subscribe`
+/// long-polling.
+#[derive(Debug, Default)]
+pub struct PolypFeed {
+    events: Mutex<VecDeque<PolypEvent>>,
+    next_seq: AtomicU64,
+    notify: Notify,
+}
+
+impl PolypFeed {
+    /// Record a new Polyp and wake waiting subscribers.
+    pub fn publish(&self, polyp_id: Uuid, source: &str) {
+        {
+            let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+            let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+            if events.len() == POLYP_FEED_CAPACITY {
+                events.pop_front();
+            }
+            events.push_back(PolypEvent {
+                seq,
+                polyp_id,
+                source: source.to_string(),
+            });
+        }
+        self.notify.notify_waiters();
+    }
+
+    /// Events from `cursor` on, and how many before them were dropped.
+    fn since(&self, cursor: u64) -> (Vec<PolypEvent>, u64) {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        let oldest = events.front().map(|e| e.seq).unwrap_or(cursor);
+        let missed = oldest.saturating_sub(cursor);
+        let events = events.iter().filter(|e| e.seq >= cursor).cloned().collect();
+        (events, missed)
+    }
+
+    /// Sequence number the next event will get.
+    fn head(&self) -> u64 {
+        self.next_seq.load(Ordering::SeqCst)
+    }
+}
+
+/// Request to wait for new Polyps (`polyp/subscribe`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubscribePolypsRequest {
+    /// Return events from this sequence number on: the previous response's
+    /// `next_cursor`. If omitted, only Polyps stored after the call.
+    #[serde(default)]
+    pub cursor: Option<u64>,
+    /// How long to wait for an event, in milliseconds (default 10s, at most
+    /// 30s).
+    #[serde(default)]
+    pub wait_ms: Option<u64>,
+}
+
+/// Response with the Polyps stored since the cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribePolypsResponse {
+    /// New Polyps, oldest first. Empty if the wait timed out.
+    pub events: Vec<PolypEvent>,
+    /// Cursor to pass to the next call.
+    pub next_cursor: u64,
+    /// Events after the cursor that were dropped from the feed before this
+    /// call; the subscriber should treat its view as stale.
+    pub missed: u64,
+}
+
+/// Handle a SubscribePolyps request.
+///
+/// Long-polls: returns as soon as a Polyp at or after the cursor is stored,
+/// or with no events once the wait runs out.
+pub async fn handle_subscribe_polyps(
+    feed: &PolypFeed,
+    request: SubscribePolypsRequest,
+) -> Result<SubscribePolypsResponse, String> {
+    let wait_ms = request
+        .wait_ms
+        .unwrap_or(DEFAULT_SUBSCRIBE_WAIT_MS)
+        .min(MAX_SUBSCRIBE_WAIT_MS);
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(wait_ms);
+    // A cursor past the head is from before a restart; start over from now.
+    let cursor = request.cursor.unwrap_or(u64::MAX).min(feed.head());
+
+    loop {
+        // Register for wakeups before looking, so a publish in between is
+        // not missed.
+        let notified = feed.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let (events, missed) = feed.since(cursor);
+        if !events.is_empty() {
+            return Ok(SubscribePolypsResponse {
+                next_cursor: events.last().map(|e| e.seq + 1).unwrap_or(cursor),
+                events,
+                missed,
+            });
+        }
+        if tokio::time::timeout_at(deadline, notified).await.is_err() {
+            return Ok(SubscribePolypsResponse {
+                events,
+                next_cursor: cursor,
+                missed,
+            });
+        }
+    }
+}
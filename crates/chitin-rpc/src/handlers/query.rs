@@ -1,21 +1,139 @@
 // crates/chitin-rpc/src/handlers/query.rs
 //
-// Query and retrieval handlers: SemanticSearch, HybridSearch, GetByCid, ExplainResult.
+// Query and retrieval handlers: SemanticSearch, HybridSearch, GetContext, GetByCid,
+// ExplainResult.
 // These handlers interact with chitin-store's InMemoryVectorIndex and RocksStore.
+// Semantic search results are re-ranked by blending cosine similarity with the
+// creator's domain-scoped trust when a reputation store is available. Nodes
+// holding only some shards merge in results from peers holding the rest.
+// While a model migration is under way, queries are translated into other
+// model spaces with stored alignment matrices and those results weighted by
+// alignment confidence. With an MMR lambda, results are diversified by
+// maximal marginal relevance over the candidates' vectors. With a reranker
+// attached, the top candidates can be rescored by a cross-encoder before the
+// top-k are returned, within a latency budget. GetContext runs a search and
+// packs the best distinct results into a token budget for an LLM prompt.
+// Facets count matches of a query or filter set by zone, state, model,
+// creator, and hardening epoch. Scrolls run a search once for up to
+// thousands of results and hand them out a page at a time, for exports.
 
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use chitin_consensus::dedup::{DedupConfig, Fingerprint};
+use chitin_consensus::metagraph::MetagraphManager;
 use chitin_core::hash_embedding;
-use chitin_core::traits::{PolypStore, VectorIndex};
+use chitin_core::language::{languages_match, normalize_language_tag};
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::text::estimate_tokens;
+use chitin_core::traits::{PolypStore, Reranker, VectorIndex};
+use chitin_core::IdentityRegistry;
+use chitin_drift::alignment::ModelAlignment;
+use chitin_drift::molting::molt_lineage;
+use chitin_reputation::domain_store::{DomainTrustStore, GLOBAL_DOMAIN};
+use chitin_reputation::taxonomy::{is_within, ZONE_SEPARATOR};
+use chitin_store::distance::cosine_similarity;
 use chitin_store::{HardenedStore, InMemoryVectorIndex, RocksStore};
 
+use crate::server::ShardRouting;
+
+// ---------------------------------------------------------------------------
+// Reputation ranking
+// ---------------------------------------------------------------------------
+
+/// Default weight of creator trust in the ranking blend.
+pub const DEFAULT_SEARCH_TRUST_WEIGHT: f64 = 0.2;
+
+/// Candidate multiplier applied when results are re-ranked by reputation.
+const RERANK_OVERFETCH: usize = 3;
+
+/// Reputation inputs for the search ranking stage.
+///
+/// Ranking score is `(1 - trust_weight) * similarity + trust_weight * trust`,
+/// where `trust` is the creator's global trust in the Polyp's zone (nearest
+/// ancestor zone with trust data, else "global"), normalized so the most
+/// trusted node in that zone scores 1.0.
+#[derive(Debug, Clone)]
+pub struct ReputationRanking {
+    /// Domain-scoped trust matrices.
+    pub trust_store: Arc<RwLock<DomainTrustStore>>,
+    /// Registry used to map creator hotkeys to UIDs.
+    pub identities: Option<Arc<RwLock<IdentityRegistry>>>,
+    /// Metagraph used to map creator hotkeys to UIDs without a registry.
+    pub metagraph_manager: Option<Arc<RwLock<MetagraphManager>>>,
+    /// Default blend weight in [0.0, 1.0].
+    pub trust_weight: f64,
+}
+
+impl ReputationRanking {
+    /// Normalized creator trust for each Polyp (0.0 for unknown creators).
+    async fn creator_trust(&self, polyps: &[&Polyp]) -> Vec<f64> {
+        let hotkey_to_uid: HashMap<[u8; 32], u16> =
+            match (&self.identities, &self.metagraph_manager) {
+                (Some(identities), _) => identities.read().await.hotkey_to_uid(),
+                (None, Some(mm)) => mm
+                    .read()
+                    .await
+                    .current()
+                    .map(|mg| mg.nodes.iter().map(|n| (n.hotkey, n.uid)).collect())
+                    .unwrap_or_default(),
+                (None, None) => HashMap::new(),
+            };
+
+        let ts = self.trust_store.read().await;
+        let mut zone_scores: HashMap<String, HashMap<u16, f64>> = HashMap::new();
+        polyps
+            .iter()
+            .map(|p| {
+                let uid = match hotkey_to_uid.get(&p.subject.provenance.creator.hotkey) {
+                    Some(&uid) => uid,
+                    None => return 0.0,
+                };
+                let domain = trust_domain(&ts, p.reef_zone.as_deref());
+                let scores = zone_scores
+                    .entry(domain.clone())
+                    .or_insert_with(|| normalized(ts.global_trust(&domain)));
+                scores.get(&uid).copied().unwrap_or(0.0)
+            })
+            .collect()
+    }
+}
+
+/// The nearest zone (self, then ancestors) with a trust matrix, else "global".
+fn trust_domain(ts: &DomainTrustStore, zone: Option<&str>) -> String {
+    let mut current = zone;
+    while let Some(z) = current {
+        if ts.matrix(z).is_some() {
+            return z.to_string();
+        }
+        current = z.rsplit_once(ZONE_SEPARATOR).map(|(parent, _)| parent);
+    }
+    GLOBAL_DOMAIN.to_string()
+}
+
+/// Scale scores so the maximum is 1.0.
+fn normalized(scores: HashMap<u16, f64>) -> HashMap<u16, f64> {
+    let max = scores.values().copied().fold(0.0_f64, f64::max);
+    if max <= 0.0 {
+        return scores;
+    }
+    scores.into_iter().map(|(uid, s)| (uid, s / max)).collect()
+}
+
 // ---------------------------------------------------------------------------
 // SemanticSearch
 // ---------------------------------------------------------------------------
 
+/// Candidate multiplier applied when a `reef_zone`, `state`, or `language`
+/// filter is set.
+const FILTER_OVERFETCH: usize = 4;
+
 /// Request for ANN semantic search.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticSearchRequest {
@@ -23,16 +141,53 @@ pub struct SemanticSearchRequest {
     pub query_text: Option<String>,
     /// Pre-computed query vector (if the caller already embedded).
     pub query_vector: Option<Vec<f32>>,
-    /// Which embedding model space to search in.
+    /// Which embedding model space `query_vector` is in. Ignored for
+    /// `query_text`, which the server embeds in `HASH_EMBEDDING_MODEL`.
     pub model_id: Option<String>,
     /// Number of results to return (default 10).
     pub top_k: Option<u32>,
-    /// Minimum trust score filter (default 0.0).
+    /// Minimum normalized creator trust (default 0.0). Only applied when
+    /// reputation ranking is available.
     pub min_trust: Option<f64>,
     /// Only return hardened Polyps (default true).
     pub hardened_only: Option<bool>,
-    /// Topic filter (optional).
+    /// Topic filter (optional). Includes descendant zones: "code" matches
+    /// Polyps in "code/rust".
     pub reef_zone: Option<String>,
+    /// Lifecycle state filter (optional, e.g. "Soft" or "Hardened"; case
+    /// insensitive).
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Content language filter (optional, an ISO 639 code; "en" matches
+    /// Polyps recorded as "en-GB").
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Override the server's creator-trust blend weight, in [0.0, 1.0].
+    #[serde(default)]
+    pub trust_weight: Option<f64>,
+    /// Search only this node's shards (set on queries forwarded between
+    /// peers so they are not forwarded again).
+    #[serde(default)]
+    pub local_only: bool,
+    /// Also search other model spaces through stored alignments (default
+    /// true).
+    #[serde(default)]
+    pub cross_model: Option<bool>,
+    /// Diversify results by maximal marginal relevance with this lambda in
+    /// [0.0, 1.0] (1.0 is pure relevance). Unset uses the server's default.
+    #[serde(default)]
+    pub mmr_lambda: Option<f64>,
+    /// Rescore the top candidates with the server's reranker (needs
+    /// `query_text`). Unset uses the server's default.
+    #[serde(default)]
+    pub rerank: Option<bool>,
+    /// Candidates to rescore when reranking (default: the server's).
+    #[serde(default)]
+    pub rerank_top_n: Option<u32>,
+    /// Longest wait for the reranker, in milliseconds, before falling back
+    /// to the original order (default: the server's; capped by it).
+    #[serde(default)]
+    pub rerank_budget_ms: Option<u64>,
 }
 
 /// A single search result.
@@ -48,35 +203,60 @@ pub struct SearchResult {
     pub state: String,
     /// CID if hardened.
     pub cid: Option<String>,
+    /// Ranking score (similarity blended with creator trust).
+    #[serde(default)]
+    pub score: f64,
+    /// Creator's normalized trust in the Polyp's zone, if ranking applied.
+    #[serde(default)]
+    pub creator_trust: Option<f64>,
+    /// Model space the Polyp was found in, if the query named one.
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Confidence of the alignment the query was mapped through (1.0 in the
+    /// query's own space).
+    #[serde(default)]
+    pub alignment_confidence: Option<f64>,
+    /// Cross-encoder relevance score, if the result was reranked.
+    #[serde(default)]
+    pub rerank_score: Option<f64>,
 }
 
 /// Response from a semantic search.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticSearchResponse {
-    /// The search results, sorted by descending similarity.
+    /// The search results, sorted by descending ranking score (or by
+    /// rerank score first, when reranked).
     pub results: Vec<SearchResult>,
     /// Time taken for the search in milliseconds.
     pub search_time_ms: u64,
     /// Total results found before filtering.
     pub total_found: u32,
+    /// Whether the results were reranked.
+    #[serde(default)]
+    pub reranked: bool,
 }
 
 /// Handle a SemanticSearch request.
 ///
 /// Searches the in-memory vector index for the nearest neighbors
 /// of the query vector, then enriches results with Polyp data from the store.
+/// With `ranking`, results are re-ranked by creator reputation.
 pub async fn handle_semantic_search(
     store: &Arc<RocksStore>,
     index: &Arc<InMemoryVectorIndex>,
     request: SemanticSearchRequest,
+    ranking: Option<&ReputationRanking>,
 ) -> Result<SemanticSearchResponse, String> {
     let start = std::time::Instant::now();
 
     // Use provided vector or generate deterministic hash embedding from query text.
-    let query_vector = match request.query_vector {
-        Some(v) => v,
+    let (query_vector, query_model) = match request.query_vector {
+        Some(v) => (v, request.model_id.clone()),
         None => match &request.query_text {
-            Some(text) => hash_embedding(text, 384),
+            Some(text) => (
+                hash_embedding(text, 384),
+                Some(HASH_EMBEDDING_MODEL.to_string()),
+            ),
             None => {
                 return Err("Either query_vector or query_text must be provided".to_string());
             }
@@ -84,41 +264,146 @@ pub async fn handle_semantic_search(
     };
 
     let top_k = request.top_k.unwrap_or(10) as usize;
+    let state_filter = match request.state.as_deref() {
+        Some(name) => match STATE_NAMES.iter().find(|s| s.eq_ignore_ascii_case(name)) {
+            Some(name) => Some(*name),
+            None => return Err(format!("Unknown state filter: {}", name)),
+        },
+        None => None,
+    };
+
+    // Over-fetch when filtering so the filters don't starve results, and
+    // when re-ranking so trusted results below the cut can surface.
+    let mut fetch_k = top_k;
+    let language_filter = request
+        .language
+        .as_deref()
+        .map(normalize_language_tag)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    if request.reef_zone.is_some() || state_filter.is_some() || language_filter.is_some() {
+        fetch_k = fetch_k.saturating_mul(FILTER_OVERFETCH);
+    }
+    if ranking.is_some() {
+        fetch_k = fetch_k.saturating_mul(RERANK_OVERFETCH);
+    }
+    let mmr_lambda = request.mmr_lambda.map(|l| l.clamp(0.0, 1.0));
+    if mmr_lambda.is_some() {
+        fetch_k = fetch_k.saturating_mul(MMR_OVERFETCH);
+    }
 
     // Search the vector index.
-    let raw_results = index
-        .search(&query_vector, top_k)
-        .await
-        .map_err(|e| format!("Vector search failed: {}", e))?;
+    let cross_model = request.cross_model.unwrap_or(true);
+    let raw_results = search_model_spaces(
+        store,
+        index,
+        &query_vector,
+        query_model.as_deref(),
+        cross_model,
+        fetch_k,
+    )
+    .await?;
 
     let total_found = raw_results.len() as u32;
 
     // Enrich results with Polyp data from the store.
-    let mut results = Vec::with_capacity(raw_results.len());
-    for (polyp_id, similarity) in raw_results {
+    let mut candidates = Vec::with_capacity(raw_results.len());
+    for hit in raw_results {
+        let polyp_id = hit.polyp_id;
         let polyp = store
             .get_polyp(&polyp_id)
             .await
             .map_err(|e| format!("Failed to fetch polyp {}: {}", polyp_id, e))?;
 
-        let (content, state, cid) = match polyp {
+        if let Some(zone) = &request.reef_zone {
+            let in_zone = polyp
+                .as_ref()
+                .and_then(|p| p.reef_zone.as_deref())
+                .is_some_and(|z| is_within(z, zone));
+            if !in_zone {
+                continue;
+            }
+        }
+        if let Some(name) = state_filter {
+            if polyp.as_ref().is_none_or(|p| state_name(&p.state) != name) {
+                continue;
+            }
+        }
+        if let Some(language) = &language_filter {
+            let in_language = polyp
+                .as_ref()
+                .and_then(|p| p.subject.payload.language.as_deref())
+                .is_some_and(|l| languages_match(l, language));
+            if !in_language {
+                continue;
+            }
+        }
+        candidates.push((hit, polyp));
+    }
+
+    // Ranking stage: blend similarity with creator trust.
+    let trust: Vec<Option<f64>> = match ranking {
+        Some(r) => {
+            let with_polyp: Vec<&Polyp> =
+                candidates.iter().filter_map(|(_, p)| p.as_ref()).collect();
+            let mut scores = r.creator_trust(&with_polyp).await.into_iter();
+            candidates
+                .iter()
+                .map(|(_, p)| match p {
+                    Some(_) => Some(scores.next().unwrap_or(0.0)),
+                    None => Some(0.0),
+                })
+                .collect()
+        }
+        None => vec![None; candidates.len()],
+    };
+    let trust_weight = ranking
+        .map(|r| request.trust_weight.unwrap_or(r.trust_weight).clamp(0.0, 1.0))
+        .unwrap_or(0.0);
+    let min_trust = request.min_trust.unwrap_or(0.0);
+
+    let mut results = Vec::with_capacity(candidates.len());
+    for ((hit, polyp), creator_trust) in candidates.into_iter().zip(trust) {
+        if creator_trust.is_some_and(|t| t < min_trust) {
+            continue;
+        }
+        let relevance = hit.similarity as f64 * hit.confidence.unwrap_or(1.0);
+        let score = match creator_trust {
+            Some(t) => (1.0 - trust_weight) * relevance + trust_weight * t,
+            None => relevance,
+        };
+
+        let (content, state, cid, vector) = match polyp {
             Some(p) => {
                 let content = Some(p.subject.payload.content.clone());
                 let state = format!("{:?}", p.state);
                 let cid = p.hardening.as_ref().map(|h| h.cid.clone());
-                (content, state, cid)
+                (content, state, cid, Some(p.subject.vector.values))
             }
-            None => (None, "Unknown".to_string(), None),
+            None => (None, "Unknown".to_string(), None, None),
         };
 
-        results.push(SearchResult {
-            polyp_id,
-            similarity,
-            content,
-            state,
-            cid,
-        });
+        results.push((
+            SearchResult {
+                polyp_id: hit.polyp_id,
+                similarity: hit.similarity,
+                content,
+                state,
+                cid,
+                score,
+                creator_trust,
+                model_id: hit.model_id,
+                alignment_confidence: hit.confidence,
+                rerank_score: None,
+            },
+            vector,
+        ));
     }
+    results.sort_by(|a, b| b.0.score.total_cmp(&a.0.score));
+    let results = match mmr_lambda {
+        Some(lambda) => mmr_select(results, lambda, top_k),
+        None => results.into_iter().take(top_k).map(|(r, _)| r).collect(),
+    };
 
     let elapsed = start.elapsed().as_millis() as u64;
 
@@ -126,9 +411,789 @@ pub async fn handle_semantic_search(
         results,
         search_time_ms: elapsed,
         total_found,
+        reranked: false,
     })
 }
 
+/// Lifecycle state names accepted by the `state` filter.
+const STATE_NAMES: [&str; 7] = [
+    "Draft",
+    "Soft",
+    "UnderReview",
+    "Approved",
+    "Hardened",
+    "Rejected",
+    "Molted",
+];
+
+/// The name of a lifecycle state, without any fields.
+fn state_name(state: &PolypState) -> &'static str {
+    match state {
+        PolypState::Draft => "Draft",
+        PolypState::Soft => "Soft",
+        PolypState::UnderReview => "UnderReview",
+        PolypState::Approved => "Approved",
+        PolypState::Hardened => "Hardened",
+        PolypState::Rejected => "Rejected",
+        PolypState::Molted { .. } => "Molted",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Diversification
+// ---------------------------------------------------------------------------
+
+/// Candidate multiplier applied when results are diversified.
+const MMR_OVERFETCH: usize = 3;
+
+/// Choose up to `k` of `ranked` (sorted by descending score, each with its
+/// Polyp's vector if known) by maximal marginal relevance: repeatedly take
+/// the candidate maximizing `lambda * score - (1 - lambda) * s`, where `s`
+/// is its highest cosine similarity to a result already taken. Candidates
+/// without a vector, or in a different space (dimension), count as
+/// dissimilar. Results keep the order they were taken in.
+fn mmr_select(
+    ranked: Vec<(SearchResult, Option<Vec<f32>>)>,
+    lambda: f64,
+    k: usize,
+) -> Vec<SearchResult> {
+    let mut remaining = ranked;
+    let mut taken: Vec<(SearchResult, Option<Vec<f32>>)> = Vec::with_capacity(k);
+    while taken.len() < k && !remaining.is_empty() {
+        let mmr = |(result, vector): &(SearchResult, Option<Vec<f32>>)| {
+            let redundancy = taken
+                .iter()
+                .filter_map(|(_, other)| match (vector, other) {
+                    (Some(a), Some(b)) if a.len() == b.len() => {
+                        Some(cosine_similarity(a, b) as f64)
+                    }
+                    _ => None,
+                })
+                .fold(0.0_f64, f64::max);
+            lambda * result.score - (1.0 - lambda) * redundancy
+        };
+        let best = remaining
+            .iter()
+            .enumerate()
+            .max_by(|(i, a), (j, b)| mmr(a).total_cmp(&mmr(b)).then(j.cmp(i)))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        taken.push(remaining.remove(best));
+    }
+    taken.into_iter().map(|(result, _)| result).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Reranking
+// ---------------------------------------------------------------------------
+
+/// Default number of candidates rescored by the reranker.
+pub const DEFAULT_RERANK_TOP_N: usize = 50;
+
+/// Default latency budget of the rerank stage, in milliseconds.
+pub const DEFAULT_RERANK_BUDGET_MS: u64 = 300;
+
+/// A cross-encoder rerank stage for search results.
+///
+/// The first `top_n` candidates are rescored by the reranker and sorted by
+/// its score; candidates past `top_n`, or without content to score, follow
+/// in their original order. A rerank that fails or runs past its budget
+/// leaves the original order.
+#[derive(Clone)]
+pub struct Reranking {
+    /// The cross-encoder.
+    pub reranker: Arc<dyn Reranker>,
+    /// Whether requests that do not say are reranked.
+    pub default_enabled: bool,
+    /// Default candidates to rescore; requests may ask for more or fewer.
+    pub top_n: usize,
+    /// Latency budget; requests may ask for less.
+    pub budget: Duration,
+    /// Counters since startup.
+    pub stats: Arc<RerankStats>,
+}
+
+/// One rerank stage to run: the query, the candidates to rescore, and the
+/// budget.
+#[derive(Debug, Clone)]
+pub struct RerankPlan {
+    pub query: String,
+    pub top_n: usize,
+    pub budget: Duration,
+}
+
+impl Reranking {
+    /// The rerank stage for a request, if it wants one and has query text.
+    pub fn plan(
+        &self,
+        query_text: Option<&str>,
+        rerank: Option<bool>,
+        top_n: Option<u32>,
+        budget_ms: Option<u64>,
+    ) -> Option<RerankPlan> {
+        if !rerank.unwrap_or(self.default_enabled) {
+            return None;
+        }
+        let query = query_text.filter(|q| !q.trim().is_empty())?;
+        let budget = budget_ms
+            .map(Duration::from_millis)
+            .map_or(self.budget, |b| b.min(self.budget));
+        Some(RerankPlan {
+            query: query.to_string(),
+            top_n: top_n.map_or(self.top_n, |n| n as usize).max(1),
+            budget,
+        })
+    }
+
+    /// Rescore the first `plan.top_n` of `results` and return the first
+    /// `top_k`, reranked if the reranker answered within the budget.
+    pub async fn apply(
+        &self,
+        plan: &RerankPlan,
+        mut results: Vec<SearchResult>,
+        top_k: usize,
+    ) -> (Vec<SearchResult>, bool) {
+        let head_len = plan.top_n.min(results.len());
+        let tail = results.split_off(head_len);
+        let mut head = results;
+        let passages: Vec<&str> = head.iter().filter_map(|r| r.content.as_deref()).collect();
+        let count = passages.len();
+
+        let reranked = if count == 0 {
+            false
+        } else {
+            let start = std::time::Instant::now();
+            let outcome =
+                tokio::time::timeout(plan.budget, self.reranker.rerank(&plan.query, &passages))
+                    .await;
+            let elapsed = start.elapsed();
+            match outcome {
+                Ok(Ok(scores)) if scores.len() == count => {
+                    self.stats.record(RerankOutcome::Ok, count as u64, elapsed);
+                    let mut scores = scores.into_iter();
+                    for result in head.iter_mut().filter(|r| r.content.is_some()) {
+                        result.rerank_score = scores.next().map(f64::from);
+                    }
+                    let score = |r: &SearchResult| r.rerank_score.unwrap_or(f64::NEG_INFINITY);
+                    head.sort_by(|a, b| score(b).total_cmp(&score(a)));
+                    true
+                }
+                Ok(Ok(scores)) => {
+                    tracing::debug!("Rerank: {} scores for {} passages", scores.len(), count);
+                    self.stats.record(RerankOutcome::Error, 0, elapsed);
+                    false
+                }
+                Ok(Err(e)) => {
+                    tracing::debug!("Rerank failed: {}", e);
+                    self.stats.record(RerankOutcome::Error, 0, elapsed);
+                    false
+                }
+                Err(_) => {
+                    tracing::debug!("Rerank exceeded its {} ms budget", plan.budget.as_millis());
+                    self.stats.record(RerankOutcome::Timeout, 0, elapsed);
+                    false
+                }
+            }
+        };
+        head.extend(tail);
+        head.truncate(top_k);
+        (head, reranked)
+    }
+}
+
+/// How one rerank stage ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RerankOutcome {
+    Ok,
+    Timeout,
+    Error,
+}
+
+/// Rerank counters since startup.
+#[derive(Debug, Default)]
+pub struct RerankStats {
+    reranked: AtomicU64,
+    timeouts: AtomicU64,
+    failures: AtomicU64,
+    passages: AtomicU64,
+    latency_us: AtomicU64,
+}
+
+/// A point-in-time view of `RerankStats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RerankStatsSnapshot {
+    /// Searches reranked.
+    pub reranked: u64,
+    /// Reranks abandoned for running past their budget.
+    pub timeouts: u64,
+    /// Reranks that failed.
+    pub failures: u64,
+    /// Passages rescored.
+    pub passages: u64,
+    /// Time spent waiting for the reranker, in microseconds.
+    pub latency_us: u64,
+}
+
+impl RerankStats {
+    fn record(&self, outcome: RerankOutcome, passages: u64, elapsed: Duration) {
+        let counter = match outcome {
+            RerankOutcome::Ok => &self.reranked,
+            RerankOutcome::Timeout => &self.timeouts,
+            RerankOutcome::Error => &self.failures,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.passages.fetch_add(passages, Ordering::Relaxed);
+        self.latency_us
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Current counts.
+    pub fn snapshot(&self) -> RerankStatsSnapshot {
+        RerankStatsSnapshot {
+            reranked: self.reranked.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            passages: self.passages.load(Ordering::Relaxed),
+            latency_us: self.latency_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Facets
+// ---------------------------------------------------------------------------
+
+/// Search matches counted unless the caller asks for more.
+pub const DEFAULT_FACET_MATCHES: u32 = 1000;
+
+/// Most search matches counted.
+pub const MAX_FACET_MATCHES: u32 = 10_000;
+
+/// Values listed per facet unless the caller asks for more.
+pub const DEFAULT_FACET_LIMIT: usize = 20;
+
+/// Width of the hardening epoch buckets unless the caller asks for another.
+pub const DEFAULT_EPOCH_BUCKET: u64 = 100;
+
+/// States counted when no state filter is given.
+const FACET_STATES: [PolypState; 7] = [
+    PolypState::Draft,
+    PolypState::Soft,
+    PolypState::UnderReview,
+    PolypState::Approved,
+    PolypState::Hardened,
+    PolypState::Rejected,
+    PolypState::Molted {
+        successor_id: Uuid::nil(),
+    },
+];
+
+/// Request for match counts grouped by facet (`query/facets`).
+///
+/// With a query (`query_text` or `query_vector`), the top `max_matches`
+/// search results are counted; without one, every Polyp this node holds.
+/// Either way only Polyps passing the filters are counted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FacetsRequest {
+    /// Natural language query text.
+    #[serde(default)]
+    pub query_text: Option<String>,
+    /// Pre-computed query vector.
+    #[serde(default)]
+    pub query_vector: Option<Vec<f32>>,
+    /// Which embedding model space `query_vector` is in.
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Topic filter, including descendant zones.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+    /// Lifecycle state filter (case insensitive).
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Content language filter (an ISO 639 code).
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Search matches counted (default 1000, at most 10000).
+    #[serde(default)]
+    pub max_matches: Option<u32>,
+    /// Values listed per facet, most frequent first (default 20).
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Width of the hardening epoch buckets (default 100).
+    #[serde(default)]
+    pub epoch_bucket: Option<u64>,
+}
+
+/// Matches sharing one facet value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Match counts grouped by facet.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FacetsResponse {
+    /// Polyps counted.
+    pub matched: u64,
+    /// Counts by Reef Zone (Polyps without one are not listed).
+    pub reef_zone: Vec<FacetCount>,
+    /// Counts by lifecycle state.
+    pub state: Vec<FacetCount>,
+    /// Counts by embedding model space.
+    pub model: Vec<FacetCount>,
+    /// Counts by creator DID.
+    pub creator: Vec<FacetCount>,
+    /// Counts of hardened Polyps by consensus epoch bucket ("100-199"),
+    /// oldest first.
+    pub hardened_epoch: Vec<FacetCount>,
+    /// Whether a query matched more than `max_matches` (only the top ones
+    /// were counted).
+    pub truncated: bool,
+    /// Time taken in milliseconds.
+    pub took_ms: u64,
+}
+
+/// Handle a `query/facets` request.
+pub async fn handle_facets(
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    request: FacetsRequest,
+) -> Result<FacetsResponse, String> {
+    let start = std::time::Instant::now();
+    let limit = request.limit.unwrap_or(DEFAULT_FACET_LIMIT).max(1);
+    let bucket = request.epoch_bucket.unwrap_or(DEFAULT_EPOCH_BUCKET).max(1);
+
+    let mut truncated = false;
+    let polyps = if request.query_vector.is_some() || request.query_text.is_some() {
+        let max_matches = request
+            .max_matches
+            .unwrap_or(DEFAULT_FACET_MATCHES)
+            .clamp(1, MAX_FACET_MATCHES);
+        let search = SemanticSearchRequest {
+            query_text: request.query_text,
+            query_vector: request.query_vector,
+            model_id: request.model_id,
+            top_k: Some(max_matches),
+            min_trust: None,
+            hardened_only: None,
+            reef_zone: request.reef_zone,
+            state: request.state,
+            language: request.language,
+            trust_weight: None,
+            local_only: true,
+            cross_model: None,
+            mmr_lambda: None,
+            rerank: Some(false),
+            rerank_top_n: None,
+            rerank_budget_ms: None,
+        };
+        let response = handle_semantic_search(store, index, search, None).await?;
+        truncated =
+            response.results.len() as u32 == max_matches && response.total_found > max_matches;
+        let mut polyps = Vec::with_capacity(response.results.len());
+        for result in response.results {
+            let polyp = store
+                .get_polyp(&result.polyp_id)
+                .await
+                .map_err(|e| format!("Failed to fetch polyp {}: {}", result.polyp_id, e))?;
+            polyps.extend(polyp);
+        }
+        polyps
+    } else {
+        let state_filter = match request.state.as_deref() {
+            Some(name) => Some(
+                FACET_STATES
+                    .iter()
+                    .find(|s| state_name(s).eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("Unknown state filter: {}", name))?,
+            ),
+            None => None,
+        };
+        let language = request
+            .language
+            .as_deref()
+            .map(normalize_language_tag)
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let mut polyps = Vec::new();
+        for state in FACET_STATES
+            .iter()
+            .filter(|s| state_filter.is_none_or(|f| f == *s))
+        {
+            let listed = store
+                .list_polyps_by_state(state)
+                .await
+                .map_err(|e| format!("Failed to list {} polyps: {}", state_name(state), e))?;
+            polyps.extend(listed.into_iter().filter(|p| {
+                let in_zone = request
+                    .reef_zone
+                    .as_deref()
+                    .is_none_or(|zone| p.reef_zone.as_deref().is_some_and(|z| is_within(z, zone)));
+                let in_language = language.as_deref().is_none_or(|language| {
+                    p.subject
+                        .payload
+                        .language
+                        .as_deref()
+                        .is_some_and(|l| languages_match(l, language))
+                });
+                in_zone && in_language
+            }));
+        }
+        polyps
+    };
+
+    let mut zones = HashMap::new();
+    let mut states = HashMap::new();
+    let mut models = HashMap::new();
+    let mut creators = HashMap::new();
+    let mut epochs = std::collections::BTreeMap::new();
+    for polyp in &polyps {
+        if let Some(zone) = &polyp.reef_zone {
+            *zones.entry(zone.clone()).or_insert(0u64) += 1;
+        }
+        *states
+            .entry(state_name(&polyp.state).to_string())
+            .or_insert(0u64) += 1;
+        *models
+            .entry(polyp.subject.vector.model_id.key())
+            .or_insert(0u64) += 1;
+        *creators
+            .entry(polyp.subject.provenance.creator.did.clone())
+            .or_insert(0u64) += 1;
+        if polyp.hardening.is_some() {
+            if let Some(consensus) = &polyp.consensus {
+                *epochs.entry(consensus.epoch / bucket).or_insert(0u64) += 1;
+            }
+        }
+    }
+
+    Ok(FacetsResponse {
+        matched: polyps.len() as u64,
+        reef_zone: top_facet_values(zones, limit),
+        state: top_facet_values(states, limit),
+        model: top_facet_values(models, limit),
+        creator: top_facet_values(creators, limit),
+        hardened_epoch: epochs
+            .into_iter()
+            .map(|(b, count)| FacetCount {
+                value: format!("{}-{}", b * bucket, (b + 1) * bucket - 1),
+                count,
+            })
+            .collect(),
+        truncated,
+        took_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// The `limit` most frequent values, ties by value.
+fn top_facet_values(counts: HashMap<String, u64>, limit: usize) -> Vec<FacetCount> {
+    let mut values: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(value, count)| FacetCount { value, count })
+        .collect();
+    values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    values.truncate(limit);
+    values
+}
+
+// ---------------------------------------------------------------------------
+// Scrolling
+// ---------------------------------------------------------------------------
+
+/// Results per `query/scroll` page unless the caller asks for another size.
+pub const DEFAULT_SCROLL_PAGE_SIZE: u32 = 100;
+
+/// Most results per `query/scroll` page.
+pub const MAX_SCROLL_PAGE_SIZE: u32 = 1000;
+
+/// Ranked results a scroll pages through unless the caller asks for more.
+pub const DEFAULT_SCROLL_RESULTS: u32 = 1000;
+
+/// Most ranked results a scroll pages through.
+pub const MAX_SCROLL_RESULTS: u32 = 10_000;
+
+/// Most scrolls held open; opening another drops the least recently read.
+const MAX_OPEN_SCROLLS: usize = 64;
+
+/// How long an unread scroll is kept.
+const SCROLL_IDLE: Duration = Duration::from_secs(300);
+
+/// Request to page through ranked search results (`query/scroll`).
+///
+/// The first call carries `search`, which is run once for up to
+/// `max_results` results; later calls carry the `cursor` of the previous
+/// page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScrollSearchRequest {
+    /// The search to page through (first page only).
+    #[serde(default)]
+    pub search: Option<SemanticSearchRequest>,
+    /// `cursor` of the previous page.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Results per page (default 100, at most 1000).
+    #[serde(default)]
+    pub page_size: Option<u32>,
+    /// Ranked results to page through (first page only; default 1000, at
+    /// most 10000). Replaces `search.top_k`.
+    #[serde(default)]
+    pub max_results: Option<u32>,
+}
+
+/// One page of ranked search results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrollSearchResponse {
+    /// Results of this page, in rank order.
+    pub results: Vec<SearchResult>,
+    /// Rank of the first result of this page, from 0.
+    pub offset: u32,
+    /// Ranked results in the scroll.
+    pub total: u32,
+    /// Cursor of the next page, or `None` after the last one.
+    pub cursor: Option<String>,
+    /// Time taken for the search in milliseconds.
+    pub search_time_ms: u64,
+    /// Whether the results were reranked.
+    #[serde(default)]
+    pub reranked: bool,
+}
+
+/// An open scroll: the ranked results of one search.
+#[derive(Debug)]
+struct Scroll {
+    results: Vec<SearchResult>,
+    search_time_ms: u64,
+    reranked: bool,
+    last_read: std::time::Instant,
+}
+
+/// Scrolls open on this node. Each holds its search's ranked results until
+/// its last page is read, it has gone unread for five minutes, or newer
+/// scrolls push it out.
+#[derive(Debug, Default)]
+pub struct ScrollSessions {
+    open: std::sync::Mutex<HashMap<Uuid, Scroll>>,
+}
+
+impl Scroll {
+    /// The page starting at `offset`, marking the scroll read.
+    fn page(&mut self, id: Uuid, offset: u32, page_size: u32) -> ScrollSearchResponse {
+        self.last_read = std::time::Instant::now();
+        let total = self.results.len() as u32;
+        let start = offset.min(total);
+        let end = start.saturating_add(page_size).min(total);
+        ScrollSearchResponse {
+            results: self.results[start as usize..end as usize].to_vec(),
+            offset: start,
+            total,
+            cursor: (end < total).then(|| format!("{}:{}", id, end)),
+            search_time_ms: self.search_time_ms,
+            reranked: self.reranked,
+        }
+    }
+}
+
+impl ScrollSessions {
+    /// Hold the results of a new scroll's search and return its first page.
+    pub fn open(&self, response: SemanticSearchResponse, page_size: u32) -> ScrollSearchResponse {
+        let id = Uuid::now_v7();
+        let mut scroll = Scroll {
+            results: response.results,
+            search_time_ms: response.search_time_ms,
+            reranked: response.reranked,
+            last_read: std::time::Instant::now(),
+        };
+        let page = scroll.page(id, 0, page_size);
+        if page.cursor.is_some() {
+            let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+            open.retain(|_, scroll| scroll.last_read.elapsed() < SCROLL_IDLE);
+            while open.len() >= MAX_OPEN_SCROLLS {
+                let Some(oldest) = open
+                    .iter()
+                    .min_by_key(|(_, scroll)| scroll.last_read)
+                    .map(|(id, _)| *id)
+                else {
+                    break;
+                };
+                open.remove(&oldest);
+            }
+            open.insert(id, scroll);
+        }
+        page
+    }
+
+    /// The page at `cursor`. Reading a page again (e.g. after a lost reply)
+    /// returns it again, until the last page has been read.
+    pub fn page(&self, cursor: &str, page_size: u32) -> Result<ScrollSearchResponse, String> {
+        let (id, offset) = cursor
+            .split_once(':')
+            .and_then(|(id, offset)| Some((id.parse::<Uuid>().ok()?, offset.parse::<u32>().ok()?)))
+            .ok_or_else(|| format!("Invalid scroll cursor: {}", cursor))?;
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        let scroll = open
+            .get_mut(&id)
+            .ok_or_else(|| format!("Scroll {} has expired or ended", id))?;
+        let page = scroll.page(id, offset, page_size);
+        if page.cursor.is_none() {
+            open.remove(&id);
+        }
+        Ok(page)
+    }
+}
+
+/// The page size of a `query/scroll` request.
+pub fn scroll_page_size(request: &ScrollSearchRequest) -> Result<u32, String> {
+    match request.page_size.unwrap_or(DEFAULT_SCROLL_PAGE_SIZE) {
+        0 => Err("page_size must be positive".to_string()),
+        size => Ok(size.min(MAX_SCROLL_PAGE_SIZE)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cross-model search
+// ---------------------------------------------------------------------------
+
+/// Model space of vectors the server embeds itself with `hash_embedding`.
+pub use chitin_core::embedding::HASH_EMBEDDING_MODEL;
+
+/// A vector index hit and the model space it was found in.
+#[derive(Debug, Clone)]
+struct SpaceHit {
+    polyp_id: Uuid,
+    similarity: f32,
+    model_id: Option<String>,
+    confidence: Option<f64>,
+}
+
+/// Search the query's own model space, plus (with `cross_model`) every other
+/// space in the index that a stored alignment from `query_model` reaches,
+/// with the query mapped through the alignment. Hits are ordered by
+/// similarity weighted by alignment confidence.
+///
+/// Without a query model, or when it has neither vectors nor alignments,
+/// every space is searched as is.
+async fn search_model_spaces(
+    store: &RocksStore,
+    index: &InMemoryVectorIndex,
+    query: &[f32],
+    query_model: Option<&str>,
+    cross_model: bool,
+    top_k: usize,
+) -> Result<Vec<SpaceHit>, String> {
+    let spaces = index.model_spaces();
+    let mut searches = Vec::new();
+    if let Some(model) = query_model {
+        if spaces.contains_key(model) {
+            searches.push((model.to_string(), query.to_vec(), 1.0));
+        }
+        for space in spaces.keys().filter(|s| cross_model && s.as_str() != model) {
+            let alignment = match ModelAlignment::load(store, model, space) {
+                Ok(Some(alignment)) => alignment,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::debug!(
+                        "Search: failed to load alignment {} -> {}: {}",
+                        model,
+                        space,
+                        e
+                    );
+                    continue;
+                }
+            };
+            match alignment.map_query(query) {
+                Ok(mapped) => searches.push((space.clone(), mapped, alignment.confidence())),
+                Err(e) => tracing::debug!("Search: cannot map query into {}: {}", space, e),
+            }
+        }
+    }
+
+    if searches.is_empty() {
+        let raw = index
+            .search(query, top_k)
+            .await
+            .map_err(|e| format!("Vector search failed: {}", e))?;
+        return Ok(raw
+            .into_iter()
+            .map(|(polyp_id, similarity)| SpaceHit {
+                polyp_id,
+                similarity,
+                model_id: None,
+                confidence: None,
+            })
+            .collect());
+    }
+
+    let mut hits = Vec::new();
+    for (space, vector, confidence) in searches {
+        let raw = index
+            .search_in(&space, &vector, top_k)
+            .map_err(|e| format!("Vector search in {} failed: {}", space, e))?;
+        hits.extend(raw.into_iter().map(|(polyp_id, similarity)| SpaceHit {
+            polyp_id,
+            similarity,
+            model_id: Some(space.clone()),
+            confidence: Some(confidence),
+        }));
+    }
+    let weighted = |h: &SpaceHit| h.similarity as f64 * h.confidence.unwrap_or(1.0);
+    hits.sort_by(|a, b| weighted(b).total_cmp(&weighted(a)));
+    hits.truncate(top_k);
+    Ok(hits)
+}
+
+/// Handle a SemanticSearch request across shards.
+///
+/// With `routing`, the query is also sent (as `local_only`) to peers holding
+/// the shards this node lacks, and their results are merged with the local
+/// ones by ranking score (each node diversifies its own results, so the
+/// merged list is diverse per shard but ordered by score).
+pub async fn handle_sharded_search(
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    request: SemanticSearchRequest,
+    ranking: Option<&ReputationRanking>,
+    routing: Option<&ShardRouting>,
+) -> Result<SemanticSearchResponse, String> {
+    let routing = match routing {
+        Some(routing) if !request.local_only => routing,
+        _ => return handle_semantic_search(store, index, request, ranking).await,
+    };
+
+    let start = std::time::Instant::now();
+    let top_k = request.top_k.unwrap_or(10) as usize;
+    // Peers' results are merged by ranking score, so peers do not rerank;
+    // the merged list is reranked here.
+    let forwarded = SemanticSearchRequest {
+        local_only: true,
+        rerank: Some(false),
+        ..request.clone()
+    };
+    let params = serde_json::to_value(&forwarded)
+        .map_err(|e| format!("Failed to serialize forwarded query: {}", e))?;
+
+    let mut merged = handle_semantic_search(store, index, request, ranking).await?;
+    let replies = routing
+        .forward("query/search", params, routing.shards.missing_shards())
+        .await;
+    for reply in replies {
+        match serde_json::from_value::<SemanticSearchResponse>(reply) {
+            Ok(resp) => {
+                merged.total_found += resp.total_found;
+                merged.results.extend(resp.results);
+            }
+            Err(e) => tracing::debug!("SemanticSearch: ignoring malformed peer reply: {}", e),
+        }
+    }
+
+    merged.results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut seen = HashSet::new();
+    merged.results.retain(|r| seen.insert(r.polyp_id));
+    merged.results.truncate(top_k);
+    merged.search_time_ms = start.elapsed().as_millis() as u64;
+    Ok(merged)
+}
+
 // ---------------------------------------------------------------------------
 // HybridSearch
 // ---------------------------------------------------------------------------
@@ -144,6 +1209,9 @@ pub struct HybridSearchRequest {
     pub top_k: Option<u32>,
     /// Weight for semantic vs keyword: 0.0 = all keyword, 1.0 = all semantic.
     pub semantic_weight: Option<f64>,
+    /// Embedding model space of `query_vector`.
+    #[serde(default)]
+    pub model_id: Option<String>,
 }
 
 /// Response from a hybrid search.
@@ -163,19 +1231,29 @@ pub async fn handle_hybrid_search(
     store: &Arc<RocksStore>,
     index: &Arc<InMemoryVectorIndex>,
     request: HybridSearchRequest,
+    ranking: Option<&ReputationRanking>,
 ) -> Result<HybridSearchResponse, String> {
     // Phase 1: If a vector is provided, delegate to semantic search.
     if let Some(vec) = request.query_vector {
         let semantic_request = SemanticSearchRequest {
             query_text: Some(request.query_text),
             query_vector: Some(vec),
-            model_id: None,
+            model_id: request.model_id,
             top_k: request.top_k,
             min_trust: None,
             hardened_only: None,
             reef_zone: None,
+            state: None,
+            language: None,
+            trust_weight: None,
+            local_only: false,
+            cross_model: None,
+            mmr_lambda: None,
+            rerank: Some(false),
+            rerank_top_n: None,
+            rerank_budget_ms: None,
         };
-        let resp = handle_semantic_search(store, index, semantic_request).await?;
+        let resp = handle_semantic_search(store, index, semantic_request, ranking).await?;
         Ok(HybridSearchResponse {
             results: resp.results,
             search_time_ms: resp.search_time_ms,
@@ -185,6 +1263,233 @@ pub async fn handle_hybrid_search(
     }
 }
 
+// ---------------------------------------------------------------------------
+// GetContext
+// ---------------------------------------------------------------------------
+
+/// Default token budget of a context pack.
+pub const DEFAULT_CONTEXT_TOKENS: u32 = 2000;
+
+/// Default number of search results considered for a context pack.
+const DEFAULT_CONTEXT_CANDIDATES: u32 = 50;
+
+/// Request for a token-budgeted context pack (`query/context`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetContextRequest {
+    /// Natural language query text.
+    pub query_text: Option<String>,
+    /// Pre-computed query vector (if the caller already embedded).
+    #[serde(default)]
+    pub query_vector: Option<Vec<f32>>,
+    /// Embedding model space of `query_vector`.
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Most tokens of passage text to return (default
+    /// `DEFAULT_CONTEXT_TOKENS`), as estimated by `estimate_tokens`.
+    #[serde(default)]
+    pub token_budget: Option<u32>,
+    /// Search results to choose passages from (default 50).
+    #[serde(default)]
+    pub candidates: Option<u32>,
+    /// Only use hardened Polyps (default true).
+    #[serde(default)]
+    pub hardened_only: Option<bool>,
+    /// Topic filter; includes descendant zones.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+    /// Content language filter (an ISO 639 code).
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Minimum normalized creator trust (default 0.0).
+    #[serde(default)]
+    pub min_trust: Option<f64>,
+    /// Override the server's creator-trust blend weight, in [0.0, 1.0].
+    #[serde(default)]
+    pub trust_weight: Option<f64>,
+    /// Diversify candidates by maximal marginal relevance with this lambda
+    /// (see `SemanticSearchRequest::mmr_lambda`).
+    #[serde(default)]
+    pub mmr_lambda: Option<f64>,
+    /// Rescore candidates with the server's reranker before packing (see
+    /// `SemanticSearchRequest::rerank`).
+    #[serde(default)]
+    pub rerank: Option<bool>,
+    /// Candidates to rescore when reranking (default: the server's).
+    #[serde(default)]
+    pub rerank_top_n: Option<u32>,
+    /// Longest wait for the reranker, in milliseconds.
+    #[serde(default)]
+    pub rerank_budget_ms: Option<u64>,
+}
+
+/// One passage of a context pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPassage {
+    /// Citation number, as it appears in `GetContextResponse::context`.
+    pub citation: u32,
+    /// The Polyp UUID.
+    pub polyp_id: Uuid,
+    /// The passage text (the Polyp's content).
+    pub content: String,
+    /// CID if hardened.
+    pub cid: Option<String>,
+    /// URL of the source the Polyp was extracted from, if known.
+    pub source_url: Option<String>,
+    /// Title of the source, if known.
+    pub source_title: Option<String>,
+    /// Ranking score (similarity blended with creator trust).
+    pub score: f64,
+    /// Creator's normalized trust in the Polyp's zone, if ranking applied.
+    pub creator_trust: Option<f64>,
+    /// Estimated tokens of this passage in `context`.
+    pub tokens: u32,
+}
+
+/// A context pack, ready to place in a prompt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetContextResponse {
+    /// Passages in rank order, each prefixed with its citation number
+    /// ("[1] ...") and separated by blank lines.
+    pub context: String,
+    /// The passages in `context`, in the same order.
+    pub passages: Vec<ContextPassage>,
+    /// Estimated tokens used, at most the budget.
+    pub tokens_used: u32,
+    /// The budget applied.
+    pub token_budget: u32,
+    /// Results left out as near-duplicates of a higher-ranked passage.
+    pub duplicates_dropped: u32,
+    /// Results left out because they did not fit the remaining budget.
+    pub over_budget: u32,
+    /// Whether the candidates were reranked.
+    #[serde(default)]
+    pub reranked: bool,
+    /// Time taken in milliseconds.
+    pub search_time_ms: u64,
+}
+
+/// Handle a GetContext request.
+///
+/// Runs a sharded semantic search (re-ranked by creator trust when
+/// `ranking` is set, and by `reranking`'s cross-encoder when the request
+/// wants it), drops results whose content nearly duplicates a
+/// higher-ranked one (the `[dedup]` text threshold on MinHash Jaccard), and
+/// takes the rest in rank order while they fit the token budget; a passage
+/// too large for what is left is skipped in favour of smaller ones below.
+pub async fn handle_get_context(
+    store: &Arc<RocksStore>,
+    index: &Arc<InMemoryVectorIndex>,
+    request: GetContextRequest,
+    ranking: Option<&ReputationRanking>,
+    routing: Option<&ShardRouting>,
+    reranking: Option<&Reranking>,
+) -> Result<GetContextResponse, String> {
+    let start = std::time::Instant::now();
+    let token_budget = request.token_budget.unwrap_or(DEFAULT_CONTEXT_TOKENS);
+    if token_budget == 0 {
+        return Err("token_budget must be positive".to_string());
+    }
+    let hardened_only = request.hardened_only.unwrap_or(true);
+    let candidates = request.candidates.unwrap_or(DEFAULT_CONTEXT_CANDIDATES);
+    let rerank = reranking.and_then(|r| {
+        let plan = r.plan(
+            request.query_text.as_deref(),
+            request.rerank,
+            request.rerank_top_n,
+            request.rerank_budget_ms,
+        )?;
+        Some((r, plan))
+    });
+    let fetch = rerank
+        .as_ref()
+        .map_or(candidates, |(_, plan)| candidates.max(plan.top_n as u32));
+    let search = SemanticSearchRequest {
+        query_text: request.query_text,
+        query_vector: request.query_vector,
+        model_id: request.model_id,
+        top_k: Some(fetch),
+        min_trust: request.min_trust,
+        hardened_only: Some(hardened_only),
+        reef_zone: request.reef_zone,
+        state: hardened_only.then(|| "Hardened".to_string()),
+        language: request.language,
+        trust_weight: request.trust_weight,
+        local_only: false,
+        cross_model: None,
+        mmr_lambda: request.mmr_lambda,
+        rerank: Some(false),
+        rerank_top_n: None,
+        rerank_budget_ms: None,
+    };
+    let mut found = handle_sharded_search(store, index, search, ranking, routing).await?;
+    if let Some((reranking, plan)) = &rerank {
+        (found.results, found.reranked) = reranking
+            .apply(plan, found.results, candidates as usize)
+            .await;
+    }
+
+    let text_threshold = DedupConfig::default().text_threshold;
+    let mut kept: Vec<(String, Fingerprint)> = Vec::new();
+    let mut response = GetContextResponse {
+        context: String::new(),
+        passages: Vec::new(),
+        tokens_used: 0,
+        token_budget,
+        duplicates_dropped: 0,
+        over_budget: 0,
+        reranked: found.reranked,
+        search_time_ms: 0,
+    };
+    for result in found.results {
+        let Some(content) = result.content else {
+            continue;
+        };
+        let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        let fingerprint = Fingerprint::of(&normalized);
+        let duplicate = kept.iter().any(|(text, other)| {
+            text.eq_ignore_ascii_case(&normalized) || other.jaccard(&fingerprint) >= text_threshold
+        });
+        if duplicate {
+            response.duplicates_dropped += 1;
+            continue;
+        }
+
+        let citation = response.passages.len() as u32 + 1;
+        let block = format!("[{}] {}", citation, normalized);
+        let separator = if response.passages.is_empty() { 0 } else { 1 };
+        let tokens = (estimate_tokens(&block) + separator) as u32;
+        if response.tokens_used + tokens > token_budget {
+            response.over_budget += 1;
+            continue;
+        }
+
+        // Peers' results may not be stored here; they are cited by CID only.
+        let source = match store.get_polyp(&result.polyp_id).await {
+            Ok(Some(polyp)) => Some(polyp.subject.provenance.source),
+            _ => None,
+        };
+        if separator > 0 {
+            response.context.push_str("\n\n");
+        }
+        response.context.push_str(&block);
+        response.tokens_used += tokens;
+        response.passages.push(ContextPassage {
+            citation,
+            polyp_id: result.polyp_id,
+            content,
+            cid: result.cid,
+            source_url: source.as_ref().and_then(|s| s.source_url.clone()),
+            source_title: source.and_then(|s| s.title),
+            score: result.score,
+            creator_trust: result.creator_trust,
+            tokens,
+        });
+        kept.push((normalized, fingerprint));
+    }
+    response.search_time_ms = start.elapsed().as_millis() as u64;
+    Ok(response)
+}
+
 // ---------------------------------------------------------------------------
 // GetByCid
 // ---------------------------------------------------------------------------
@@ -203,13 +1508,20 @@ pub struct GetByCidResponse {
     pub polyp: Option<serde_json::Value>,
     /// Whether the Polyp was found.
     pub found: bool,
+    /// The current version of the Polyp, if this archived version has
+    /// since been molted.
+    #[serde(default)]
+    pub current_id: Option<Uuid>,
 }
 
 /// Handle a GetByCid request.
 ///
-/// Phase 4: Retrieves a hardened Polyp by CID from the HardenedStore.
+/// Phase 4: Retrieves a hardened Polyp by CID from the HardenedStore. CIDs
+/// are content-addressed, so a molted Polyp's CID still returns the archived
+/// version; `current_id` points at its latest successor.
 pub async fn handle_get_by_cid(
     hardened_store: Option<&Arc<HardenedStore>>,
+    store: &Arc<RocksStore>,
     request: GetByCidRequest,
 ) -> Result<GetByCidResponse, String> {
     match hardened_store {
@@ -218,14 +1530,22 @@ pub async fn handle_get_by_cid(
                 Ok(polyp) => {
                     let json = serde_json::to_value(&polyp)
                         .map_err(|e| format!("Failed to serialize polyp: {}", e))?;
+                    let current_id = molt_lineage(store, &polyp.id)
+                        .await
+                        .map_err(|e| format!("Failed to get polyp lineage: {}", e))?
+                        .last()
+                        .map(|entry| entry.polyp_id)
+                        .filter(|id| *id != polyp.id);
                     Ok(GetByCidResponse {
                         polyp: Some(json),
                         found: true,
+                        current_id,
                     })
                 }
                 Err(_) => Ok(GetByCidResponse {
                     polyp: None,
                     found: false,
+                    current_id: None,
                 }),
             }
         }
@@ -237,6 +1557,7 @@ pub async fn handle_get_by_cid(
             Ok(GetByCidResponse {
                 polyp: None,
                 found: false,
+                current_id: None,
             })
         }
     }
@@ -284,11 +1605,8 @@ pub async fn handle_explain_result(
     match polyp {
         Some(p) => {
             let stored_vec = &p.subject.vector.values;
-            let similarity = cosine_similarity_f32(&request.query_vector, stored_vec);
-            let model_id = format!(
-                "{}/{}",
-                p.subject.vector.model_id.provider, p.subject.vector.model_id.name
-            );
+            let similarity = cosine_similarity(&request.query_vector, stored_vec);
+            let model_id = p.subject.vector.model_id.key();
 
             Ok(ExplainResultResponse {
                 cosine_similarity: similarity,
@@ -305,28 +1623,71 @@ pub async fn handle_explain_result(
     }
 }
 
-/// Compute cosine similarity between two f32 vectors.
-fn cosine_similarity_f32(a: &[f32], b: &[f32]) -> f32 {
-    if a.len() != b.len() || a.is_empty() {
-        return 0.0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Candidate `id` with ranking `score` and `vector`.
+    fn candidate(
+        id: u128,
+        score: f64,
+        vector: Option<Vec<f32>>,
+    ) -> (SearchResult, Option<Vec<f32>>) {
+        let result = SearchResult {
+            polyp_id: Uuid::from_u128(id),
+            similarity: score as f32,
+            content: None,
+            state: "Hardened".to_string(),
+            cid: None,
+            score,
+            creator_trust: None,
+            model_id: None,
+            alignment_confidence: None,
+            rerank_score: None,
+        };
+        (result, vector)
+    }
+
+    /// Two near-duplicates and a distinct, lower-scored candidate.
+    fn ranked() -> Vec<(SearchResult, Option<Vec<f32>>)> {
+        vec![
+            candidate(1, 1.0, Some(vec![1.0, 0.0])),
+            candidate(2, 0.9, Some(vec![1.0, 0.0])),
+            candidate(3, 0.5, Some(vec![0.0, 1.0])),
+        ]
     }
 
-    let mut dot = 0.0_f64;
-    let mut norm_a = 0.0_f64;
-    let mut norm_b = 0.0_f64;
+    fn ids(results: &[SearchResult]) -> Vec<u128> {
+        results.iter().map(|r| r.polyp_id.as_u128()).collect()
+    }
+
+    #[test]
+    fn test_mmr_with_lambda_one_keeps_score_order() {
+        assert_eq!(ids(&mmr_select(ranked(), 1.0, 3)), [1, 2, 3]);
+        assert_eq!(ids(&mmr_select(ranked(), 1.0, 2)), [1, 2]);
+    }
 
-    for (x, y) in a.iter().zip(b.iter()) {
-        let x = *x as f64;
-        let y = *y as f64;
-        dot += x * y;
-        norm_a += x * x;
-        norm_b += y * y;
+    #[test]
+    fn test_mmr_prefers_diverse_results() {
+        assert_eq!(ids(&mmr_select(ranked(), 0.0, 2)), [1, 3]);
+        assert_eq!(ids(&mmr_select(ranked(), 0.5, 2)), [1, 3]);
     }
 
-    let denom = norm_a.sqrt() * norm_b.sqrt();
-    if denom == 0.0 {
-        return 0.0;
+    #[test]
+    fn test_mmr_treats_unknown_and_other_space_vectors_as_dissimilar() {
+        let ranked = vec![
+            candidate(1, 1.0, Some(vec![1.0, 0.0])),
+            candidate(2, 0.95, Some(vec![1.0, 0.0])),
+            candidate(3, 0.9, None),
+            candidate(4, 0.8, Some(vec![1.0, 0.0, 0.0])),
+        ];
+        assert_eq!(ids(&mmr_select(ranked, 0.5, 3)), [1, 3, 4]);
     }
 
-    (dot / denom) as f32
+    #[test]
+    fn test_mmr_returns_at_most_the_candidates_given() {
+        assert_eq!(ids(&mmr_select(ranked(), 0.5, 10)), [1, 3, 2]);
+        assert!(mmr_select(ranked(), 0.5, 0).is_empty());
+        assert!(mmr_select(Vec::new(), 0.5, 5).is_empty());
+    }
 }
@@ -0,0 +1,125 @@
+// crates/chitin-rpc/src/handlers/replication.rs
+//
+// Replication handlers: GetReplicationState, GetReplicationStatus, Promote.
+//
+// A standby daemon follows a primary by polling `replication/state` for the
+// primary's runtime state (epoch position, weights, bonds, consensus results,
+// and metagraph; never key material) and pulling its polyps like any peer.
+// It refuses to sign while following, and is made the signer with
+// `admin/promote`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::server::{PromoteCallback, ReplicationStateCallback, ReplicationStatusCallback};
+
+// ---------------------------------------------------------------------------
+// GetReplicationState
+// ---------------------------------------------------------------------------
+
+/// Request for a node's replicable runtime state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReplicationStateRequest {}
+
+/// A node's replicable runtime state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReplicationStateResponse {
+    /// Role of the serving node: "primary" or "standby".
+    pub role: String,
+    /// Hotkey of the serving node (hex), if it has one.
+    pub hotkey: Option<String>,
+    /// Epoch the serving node is in.
+    pub epoch: u64,
+    /// Latest epoch the serving node signed weights for.
+    pub last_signed_epoch: Option<u64>,
+    /// The runtime snapshot, as persisted by the daemon.
+    pub snapshot: serde_json::Value,
+}
+
+/// Handle a GetReplicationState request (`replication/state`).
+pub async fn handle_get_replication_state(
+    _request: GetReplicationStateRequest,
+    state: Option<&ReplicationStateCallback>,
+) -> Result<GetReplicationStateResponse, String> {
+    match state {
+        Some(state) => state().await,
+        None => Err("Replication not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GetReplicationStatus
+// ---------------------------------------------------------------------------
+
+/// Request for a node's replication status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReplicationStatusRequest {}
+
+/// Replication status of a node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationStatusResponse {
+    /// "primary" or "standby".
+    pub role: String,
+    /// Whether the node currently signs.
+    pub signing: bool,
+    /// Latest epoch this node signed weights for.
+    pub last_signed_epoch: Option<u64>,
+    /// Epochs up to and including this one are never signed (set on
+    /// promotion, so nothing the old primary may have signed is signed again).
+    pub signing_floor_epoch: Option<u64>,
+    /// The primary a standby follows.
+    pub primary_url: Option<String>,
+    /// Whether the primary answered within the failover timeout.
+    pub primary_alive: bool,
+    /// When the primary last answered.
+    pub last_primary_contact: Option<DateTime<Utc>>,
+    /// Latest epoch the primary reported signing weights for.
+    pub primary_last_signed_epoch: Option<u64>,
+}
+
+/// Handle a GetReplicationStatus request (`replication/status`).
+pub async fn handle_get_replication_status(
+    _request: GetReplicationStatusRequest,
+    status: Option<&ReplicationStatusCallback>,
+) -> Result<ReplicationStatusResponse, String> {
+    match status {
+        Some(status) => Ok(status().await),
+        None => Err("Replication not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Promote
+// ---------------------------------------------------------------------------
+
+/// Request to promote a standby to primary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromoteRequest {
+    /// Promote even though the primary still answers. Only safe once the
+    /// primary can no longer sign (e.g. it is partitioned and will be
+    /// stopped).
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Result of a promotion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromoteResponse {
+    /// Whether the node was promoted by this request.
+    pub promoted: bool,
+    /// Epochs up to and including this one will not be signed.
+    pub signing_floor_epoch: Option<u64>,
+    /// Human-readable outcome.
+    pub message: String,
+}
+
+/// Handle a Promote request (`admin/promote`).
+pub async fn handle_promote(
+    request: PromoteRequest,
+    promote: Option<&PromoteCallback>,
+) -> Result<PromoteResponse, String> {
+    match promote {
+        Some(promote) => promote(request).await,
+        None => Err("Replication not available".to_string()),
+    }
+}
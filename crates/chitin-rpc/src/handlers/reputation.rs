@@ -0,0 +1,509 @@
+// crates/chitin-rpc/src/handlers/reputation.rs
+//
+// Reputation query handlers: GetReputationScore, ExplainTrust,
+// GetNodeReputation, GetTopReputation, GetTrustGraph.
+// Reads per-domain global trust from the daemon's DomainTrustStore.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_consensus::metagraph::MetagraphManager;
+use chitin_core::identity::NodeIdentity;
+use chitin_core::IdentityRegistry;
+use chitin_reputation::domain_store::{DomainTrustStore, GLOBAL_DOMAIN};
+use chitin_reputation::openrank::Personalization;
+use chitin_reputation::evidence::{TrustEvidence, TrustExplanation};
+
+// ---------------------------------------------------------------------------
+// GetReputationScore
+// ---------------------------------------------------------------------------
+
+/// Vantage point for personalized trust scores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Vantage {
+    /// Seed by stake from the current metagraph.
+    Stake,
+    /// Seed uniformly from a caller-specified trusted set.
+    Trusted {
+        /// Trusted node UIDs.
+        uids: Vec<u16>,
+    },
+    /// Seed from the domain's configured pre-trusted nodes.
+    PreTrusted,
+}
+
+/// Request for global trust scores within a domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReputationScoreRequest {
+    /// Domain (Reef Zone) ID, e.g. "medical". If omitted, uses "global".
+    pub domain_id: Option<String>,
+    /// Restrict the response to a single node UID.
+    pub uid: Option<u16>,
+    /// Compute personalized OpenRank from this vantage point instead of
+    /// network-wide EigenTrust.
+    #[serde(default)]
+    pub vantage: Option<Vantage>,
+}
+
+/// A single node's trust score within a domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationScoreEntry {
+    /// Network UID.
+    pub uid: u16,
+    /// Trust score in [0.0, 1.0] (EigenTrust, or OpenRank with a vantage).
+    pub score: f64,
+}
+
+/// Response containing trust scores for a domain, highest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetReputationScoreResponse {
+    /// Domain the scores were computed for.
+    pub domain_id: String,
+    /// Epoch the domain's trust matrix was last advanced to.
+    pub epoch: u64,
+    /// Trust scores, sorted by score descending.
+    pub scores: Vec<ReputationScoreEntry>,
+    /// Domains with a trust matrix.
+    pub domains: Vec<String>,
+}
+
+/// Handle a GetReputationScore request.
+///
+/// Returns an empty score list if the trust store is unavailable or the
+/// domain has no trust data yet. Stake vantage reads stakes from the
+/// metagraph; without one it falls back to uniform personalization.
+pub async fn handle_get_reputation_score(
+    request: GetReputationScoreRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+) -> Result<GetReputationScoreResponse, String> {
+    let domain_id = request
+        .domain_id
+        .unwrap_or_else(|| GLOBAL_DOMAIN.to_string());
+
+    let ts = match trust_store {
+        Some(ts) => ts.read().await,
+        None => {
+            return Ok(GetReputationScoreResponse {
+                domain_id,
+                epoch: 0,
+                scores: vec![],
+                domains: vec![],
+            })
+        }
+    };
+
+    let epoch = ts.matrix(&domain_id).map(|m| m.epoch).unwrap_or(0);
+    let raw_scores = match &request.vantage {
+        None => ts.global_trust(&domain_id),
+        Some(vantage) => {
+            let personalization = match vantage {
+                Vantage::Stake => Personalization::Stake(current_stakes(metagraph_manager).await),
+                Vantage::Trusted { uids } => Personalization::TrustedSet(uids.clone()),
+                Vantage::PreTrusted => ts.pre_trusted(&domain_id),
+            };
+            ts.personalized_trust(&domain_id, &personalization)
+        }
+    };
+    let mut scores: Vec<ReputationScoreEntry> = raw_scores
+        .into_iter()
+        .filter(|(uid, _)| request.uid.is_none_or(|u| u == *uid))
+        .map(|(uid, score)| ReputationScoreEntry { uid, score })
+        .collect();
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.uid.cmp(&b.uid)));
+
+    Ok(GetReputationScoreResponse {
+        domain_id,
+        epoch,
+        scores,
+        domains: ts.domains(),
+    })
+}
+
+/// Stake per UID from the current metagraph snapshot.
+async fn current_stakes(
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+) -> HashMap<u16, u64> {
+    let mm = match metagraph_manager {
+        Some(mm) => mm.read().await,
+        None => return HashMap::new(),
+    };
+    mm.current()
+        .map(|mg| mg.nodes.iter().map(|n| (n.uid, n.stake)).collect())
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// ExplainTrust
+// ---------------------------------------------------------------------------
+
+/// Request to explain why node `from` trusts node `to` at its current level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainTrustRequest {
+    /// Domain (Reef Zone) ID. If omitted, uses "global".
+    pub domain_id: Option<String>,
+    /// Trusting node UID.
+    pub from: u16,
+    /// Trusted node UID.
+    pub to: u16,
+}
+
+/// Handle an ExplainTrust request.
+///
+/// Returns the edge's current trust, retained evidence events, and decay
+/// since the most recent event. Edges with no history yield zero trust and
+/// an empty evidence list.
+pub async fn handle_explain_trust(
+    request: ExplainTrustRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+) -> Result<TrustExplanation, String> {
+    let domain_id = request
+        .domain_id
+        .unwrap_or_else(|| GLOBAL_DOMAIN.to_string());
+
+    match trust_store {
+        Some(ts) => Ok(ts.read().await.explain(&domain_id, request.from, request.to)),
+        None => Err("Trust store not available".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GetNodeReputation
+// ---------------------------------------------------------------------------
+
+/// Request for one node's reputation across every domain. Exactly one of
+/// `uid` or `did` must be set; DIDs are resolved through the identity
+/// registry, else the metagraph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetNodeReputationRequest {
+    /// Network UID.
+    pub uid: Option<u16>,
+    /// Node DID (`did:chitin:<hex coldkey>`).
+    pub did: Option<String>,
+    /// Number of recent trust-evidence events to return (default 10).
+    #[serde(default)]
+    pub evidence_limit: Option<usize>,
+}
+
+/// Default number of evidence events in `reputation/node`.
+pub const DEFAULT_NODE_EVIDENCE: usize = 10;
+
+/// A trust-evidence event on an edge into a node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeEvidence {
+    /// Domain (Reef Zone) ID of the edge.
+    pub domain_id: String,
+    /// UID of the node whose trust changed.
+    pub from: u16,
+    /// The event.
+    pub evidence: TrustEvidence,
+}
+
+/// A node's score and rank within one domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainReputation {
+    /// Domain (Reef Zone) ID.
+    pub domain_id: String,
+    /// Trust score in [0.0, 1.0].
+    pub score: f64,
+    /// 1-based rank among the domain's nodes.
+    pub rank: usize,
+    /// Number of nodes with a score in the domain.
+    pub nodes: usize,
+}
+
+/// Response containing a node's global and per-domain reputation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetNodeReputationResponse {
+    /// Network UID.
+    pub uid: u16,
+    /// Node DID, if registered.
+    pub did: Option<String>,
+    /// Score in the "global" domain (0.0 if the node has none).
+    pub global: f64,
+    /// Per-zone reputation, for zones where the node has a score. Excludes
+    /// "global".
+    pub domains: Vec<DomainReputation>,
+    /// Most recent evidence events on edges into the node, newest first.
+    #[serde(default)]
+    pub recent_evidence: Vec<NodeEvidence>,
+}
+
+/// Handle a GetNodeReputation request.
+pub async fn handle_get_node_reputation(
+    request: GetNodeReputationRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+) -> Result<GetNodeReputationResponse, String> {
+    let dids = uid_dids(identities, metagraph_manager).await;
+    let uid = match (request.uid, &request.did) {
+        (Some(uid), None) => uid,
+        (None, Some(did)) => dids
+            .iter()
+            .find(|(_, d)| *d == did)
+            .map(|(&uid, _)| uid)
+            .ok_or_else(|| format!("DID not registered: {}", did))?,
+        _ => return Err("Exactly one of uid or did must be provided".to_string()),
+    };
+    let ts = match trust_store {
+        Some(ts) => ts.read().await,
+        None => return Err("Trust store not available".to_string()),
+    };
+
+    let mut global = 0.0;
+    let mut domains = Vec::new();
+    for domain_id in ts.domains() {
+        let ranked = ranked_scores(ts.global_trust(&domain_id));
+        let position = match ranked.iter().position(|&(u, _)| u == uid) {
+            Some(p) => p,
+            None => continue,
+        };
+        let score = ranked[position].1;
+        if domain_id == GLOBAL_DOMAIN {
+            global = score;
+            continue;
+        }
+        domains.push(DomainReputation {
+            domain_id,
+            score,
+            rank: position + 1,
+            nodes: ranked.len(),
+        });
+    }
+
+    let limit = request.evidence_limit.unwrap_or(DEFAULT_NODE_EVIDENCE);
+    let recent_evidence = ts
+        .evidence_to(uid, limit)
+        .into_iter()
+        .map(|(domain_id, from, evidence)| NodeEvidence {
+            domain_id,
+            from,
+            evidence,
+        })
+        .collect();
+
+    Ok(GetNodeReputationResponse {
+        uid,
+        did: dids.get(&uid).cloned(),
+        global,
+        domains,
+        recent_evidence,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// GetTopReputation
+// ---------------------------------------------------------------------------
+
+/// Default leaderboard length.
+pub const DEFAULT_TOP_LIMIT: usize = 10;
+
+/// Request for a domain's reputation leaderboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTopReputationRequest {
+    /// Domain (Reef Zone) ID. If omitted, uses "global".
+    pub domain_id: Option<String>,
+    /// Number of entries to return (default 10).
+    pub limit: Option<usize>,
+}
+
+/// A leaderboard row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// 1-based rank.
+    pub rank: usize,
+    /// Network UID.
+    pub uid: u16,
+    /// Node DID, if registered.
+    pub did: Option<String>,
+    /// Trust score in [0.0, 1.0].
+    pub score: f64,
+}
+
+/// Response containing a domain's top nodes by trust.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTopReputationResponse {
+    /// Domain the leaderboard was computed for.
+    pub domain_id: String,
+    /// Epoch the domain's trust matrix was last advanced to.
+    pub epoch: u64,
+    /// Top nodes, highest score first.
+    pub entries: Vec<LeaderboardEntry>,
+    /// Total number of nodes with a score in the domain.
+    pub total_nodes: usize,
+}
+
+/// Handle a GetTopReputation request.
+///
+/// Unknown domains yield an empty leaderboard.
+pub async fn handle_get_top_reputation(
+    request: GetTopReputationRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+) -> Result<GetTopReputationResponse, String> {
+    let domain_id = request
+        .domain_id
+        .unwrap_or_else(|| GLOBAL_DOMAIN.to_string());
+    let limit = request.limit.unwrap_or(DEFAULT_TOP_LIMIT);
+    let ts = match trust_store {
+        Some(ts) => ts.read().await,
+        None => return Err("Trust store not available".to_string()),
+    };
+
+    let epoch = ts.matrix(&domain_id).map(|m| m.epoch).unwrap_or(0);
+    let ranked = ranked_scores(ts.global_trust(&domain_id));
+    drop(ts);
+    let dids = uid_dids(identities, metagraph_manager).await;
+
+    Ok(GetTopReputationResponse {
+        domain_id,
+        epoch,
+        total_nodes: ranked.len(),
+        entries: ranked
+            .into_iter()
+            .take(limit)
+            .enumerate()
+            .map(|(i, (uid, score))| LeaderboardEntry {
+                rank: i + 1,
+                uid,
+                did: dids.get(&uid).cloned(),
+                score,
+            })
+            .collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// GetTrustGraph
+// ---------------------------------------------------------------------------
+
+/// Default maximum number of edges returned by `reputation/graph`.
+pub const DEFAULT_GRAPH_EDGE_LIMIT: usize = 1000;
+
+/// Request for a domain's sparse trust graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTrustGraphRequest {
+    /// Domain (Reef Zone) ID. If omitted, uses "global".
+    pub domain_id: Option<String>,
+    /// Only include edges with decayed trust strictly above this value
+    /// (default 0.0).
+    pub min_trust: Option<f64>,
+    /// Maximum number of edges, strongest first (default 1000).
+    pub limit: Option<usize>,
+}
+
+/// A directed trust edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustEdge {
+    /// Trusting node UID.
+    pub from: u16,
+    /// Trusted node UID.
+    pub to: u16,
+    /// Decayed trust value.
+    pub trust: f64,
+}
+
+/// Response containing a domain's trust edges above a threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTrustGraphResponse {
+    /// Domain the graph belongs to.
+    pub domain_id: String,
+    /// Epoch the domain's trust matrix was last advanced to.
+    pub epoch: u64,
+    /// UIDs appearing on any returned edge, sorted.
+    pub nodes: Vec<u16>,
+    /// Edges, strongest first.
+    pub edges: Vec<TrustEdge>,
+    /// Whether edges were dropped by `limit`.
+    pub truncated: bool,
+}
+
+/// Handle a GetTrustGraph request.
+///
+/// Unknown domains yield an empty graph.
+pub async fn handle_get_trust_graph(
+    request: GetTrustGraphRequest,
+    trust_store: Option<&Arc<RwLock<DomainTrustStore>>>,
+) -> Result<GetTrustGraphResponse, String> {
+    let domain_id = request
+        .domain_id
+        .unwrap_or_else(|| GLOBAL_DOMAIN.to_string());
+    let min_trust = request.min_trust.unwrap_or(0.0);
+    let limit = request.limit.unwrap_or(DEFAULT_GRAPH_EDGE_LIMIT);
+    let ts = match trust_store {
+        Some(ts) => ts.read().await,
+        None => return Err("Trust store not available".to_string()),
+    };
+
+    let (epoch, mut edges): (u64, Vec<TrustEdge>) = match ts.matrix(&domain_id) {
+        Some(m) => (
+            m.epoch,
+            m.decayed_entries()
+                .into_iter()
+                .filter(|&(_, trust)| trust > min_trust)
+                .map(|((from, to), trust)| TrustEdge { from, to, trust })
+                .collect(),
+        ),
+        None => (0, Vec::new()),
+    };
+    edges.sort_by(|a, b| {
+        b.trust
+            .total_cmp(&a.trust)
+            .then((a.from, a.to).cmp(&(b.from, b.to)))
+    });
+    let truncated = edges.len() > limit;
+    edges.truncate(limit);
+
+    let mut nodes: Vec<u16> = edges.iter().flat_map(|e| [e.from, e.to]).collect();
+    nodes.sort_unstable();
+    nodes.dedup();
+
+    Ok(GetTrustGraphResponse {
+        domain_id,
+        epoch,
+        nodes,
+        edges,
+        truncated,
+    })
+}
+
+/// Scores sorted by score descending, ties by UID.
+fn ranked_scores(scores: HashMap<u16, f64>) -> Vec<(u16, f64)> {
+    let mut ranked: Vec<(u16, f64)> = scores.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+/// DID per UID from the identity registry, else the current metagraph
+/// snapshot.
+async fn uid_dids(
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+) -> HashMap<u16, String> {
+    if let Some(identities) = identities {
+        return identities
+            .read()
+            .await
+            .iter()
+            .map(|r| (r.uid, r.did.clone()))
+            .collect();
+    }
+    let mm = match metagraph_manager {
+        Some(mm) => mm.read().await,
+        None => return HashMap::new(),
+    };
+    mm.current()
+        .map(|mg| {
+            mg.nodes
+                .iter()
+                .map(|n| (n.uid, NodeIdentity::derive_did(&n.coldkey)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
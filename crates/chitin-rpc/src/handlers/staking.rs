@@ -1,16 +1,34 @@
 // crates/chitin-rpc/src/handlers/staking.rs
 //
 // Staking handlers: Stake, Unstake, GetStakeInfo.
-// Phase 1: Stub implementations. Phase 3 will implement real staking
-// using chitin-economics::StakeManager.
+// Backed by the node's ledger and chitin-economics::StakeManager. Stake and
+// unstake requests are signed by the staker's coldkey and share its ledger
+// nonce. Unstakes whose cooldown has completed are credited back to the
+// ledger before any staking request is served. Accepted stakes and unstakes
+// are persisted with the ledger (see `wallet::save_economics`).
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_consensus::epoch::EpochManager;
+use chitin_consensus::metagraph::MetagraphManager;
+use chitin_core::IdentityRegistry;
+use chitin_economics::{
+    Ledger, SignedStakeAction, StakeAction, StakeActionKind, StakeManager, RAO_PER_CTN,
+};
+use chitin_store::RocksStore;
+
+use super::validation::{decode_hex, encode_hex};
+use super::wallet::{parse_coldkey, save_economics};
 
 // ---------------------------------------------------------------------------
 // Stake
 // ---------------------------------------------------------------------------
 
-/// Request to stake $CTN to a node.
+/// Request to stake $CTN to a node, signed by the staker.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakeRequest {
     /// Hex-encoded coldkey of the staker.
@@ -19,6 +37,24 @@ pub struct StakeRequest {
     pub node_uid: u16,
     /// Amount to stake in rao.
     pub amount_rao: u64,
+    /// The staker's next nonce (see `wallet/balance`).
+    pub nonce: u64,
+    /// Hex ed25519 signature over the stake action's signing bytes.
+    pub signature: String,
+}
+
+impl StakeRequest {
+    /// Decode the request into a signed stake action.
+    pub fn to_signed(&self) -> Result<SignedStakeAction, String> {
+        signed_action(
+            StakeActionKind::Stake,
+            &self.staker_coldkey,
+            self.node_uid,
+            self.amount_rao,
+            self.nonce,
+            &self.signature,
+        )
+    }
 }
 
 /// Response from a stake operation.
@@ -34,13 +70,43 @@ pub struct StakeResponse {
 
 /// Handle a Stake request.
 ///
-/// Phase 1 stub: Staking is not yet active.
-pub async fn handle_stake(_request: StakeRequest) -> Result<StakeResponse, String> {
-    // Phase 3: Use chitin_economics::StakeManager to process the stake
+/// Moves the amount from the staker's liquid balance into a stake on the
+/// node, then persists the ledger and stakes to `store`. The node UID must be
+/// registered in the identity registry.
+pub async fn handle_stake(
+    request: StakeRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&Arc<RwLock<StakeManager>>>,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+    store: &RocksStore,
+) -> Result<StakeResponse, String> {
+    let signed = request.to_signed()?;
+    let (ledger, stakes) = staking_state(ledger, stake_manager)?;
+    let identities = identities.ok_or("Staking not available")?.read().await;
+    let block = current_block(epoch_manager).await;
+    let mut ledger = ledger.write().await;
+    let mut stakes = stakes.write().await;
+    ledger.release_unstakes(&mut stakes, block);
+    ledger
+        .apply_stake(&signed, &mut stakes, &identities, block)
+        .map_err(|e| e.to_string())?;
+    save_economics(store, &ledger, &stakes).map_err(|e| e.to_string())?;
+
+    let new_total_rao = stakes
+        .entries()
+        .iter()
+        .filter(|e| e.staker == signed.action.staker && e.node_uid == request.node_uid)
+        .filter(|e| e.unstake_requested_at.is_none())
+        .map(|e| e.amount)
+        .sum();
     Ok(StakeResponse {
-        success: false,
-        new_total_rao: 0,
-        message: "Phase 1 stub: staking not yet implemented".to_string(),
+        success: true,
+        new_total_rao,
+        message: format!(
+            "Staked {} rao to node_uid {} at block {}",
+            request.amount_rao, request.node_uid, block
+        ),
     })
 }
 
@@ -48,7 +114,7 @@ pub async fn handle_stake(_request: StakeRequest) -> Result<StakeResponse, Strin
 // Unstake
 // ---------------------------------------------------------------------------
 
-/// Request to begin unstaking $CTN from a node.
+/// Request to begin unstaking $CTN from a node, signed by the staker.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnstakeRequest {
     /// Hex-encoded coldkey of the staker.
@@ -57,6 +123,24 @@ pub struct UnstakeRequest {
     pub node_uid: u16,
     /// Amount to unstake in rao. Use 0 for full unstake.
     pub amount_rao: u64,
+    /// The staker's next nonce (see `wallet/balance`).
+    pub nonce: u64,
+    /// Hex ed25519 signature over the stake action's signing bytes.
+    pub signature: String,
+}
+
+impl UnstakeRequest {
+    /// Decode the request into a signed unstake action.
+    pub fn to_signed(&self) -> Result<SignedStakeAction, String> {
+        signed_action(
+            StakeActionKind::Unstake,
+            &self.staker_coldkey,
+            self.node_uid,
+            self.amount_rao,
+            self.nonce,
+            &self.signature,
+        )
+    }
 }
 
 /// Response from an unstake operation.
@@ -72,13 +156,36 @@ pub struct UnstakeResponse {
 
 /// Handle an Unstake request.
 ///
-/// Phase 1 stub: Unstaking is not yet active.
-pub async fn handle_unstake(_request: UnstakeRequest) -> Result<UnstakeResponse, String> {
-    // Phase 3: Use chitin_economics::StakeManager to request unstake
+/// Starts the cooldown on the requested amount; it stays locked until the
+/// cooldown completes and is then credited back to the liquid balance.
+/// Stake on a node that has since left the registry can still be unstaked.
+/// The ledger and stakes are persisted to `store` once the cooldown starts.
+pub async fn handle_unstake(
+    request: UnstakeRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&Arc<RwLock<StakeManager>>>,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    store: &RocksStore,
+) -> Result<UnstakeResponse, String> {
+    let signed = request.to_signed()?;
+    let (ledger, stakes) = staking_state(ledger, stake_manager)?;
+    let block = current_block(epoch_manager).await;
+    let mut ledger = ledger.write().await;
+    let mut stakes = stakes.write().await;
+    ledger.release_unstakes(&mut stakes, block);
+    let complete_at = ledger
+        .apply_stake(&signed, &mut stakes, &IdentityRegistry::new(), block)
+        .map_err(|e| e.to_string())?;
+    save_economics(store, &ledger, &stakes).map_err(|e| e.to_string())?;
+
     Ok(UnstakeResponse {
-        success: false,
-        cooldown_complete_block: None,
-        message: "Phase 1 stub: unstaking not yet implemented".to_string(),
+        success: true,
+        cooldown_complete_block: complete_at,
+        message: format!(
+            "Unstaking from node_uid {}; funds unlock at block {}",
+            request.node_uid,
+            complete_at.unwrap_or(block)
+        ),
     })
 }
 
@@ -112,6 +219,30 @@ pub struct StakeInfo {
     pub unstake_pending: bool,
     /// Block at which the cooldown completes (if unstake pending).
     pub cooldown_complete_block: Option<u64>,
+    /// Blocks left until the cooldown completes (if unstake pending).
+    #[serde(default)]
+    pub cooldown_remaining_blocks: Option<u64>,
+}
+
+/// Matching stake on one node, with its projected reward.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStake {
+    /// Node UID.
+    pub node_uid: u16,
+    /// DID registered under the UID, if known.
+    #[serde(default)]
+    pub did: Option<String>,
+    /// Active stake among the matching entries, in rao.
+    pub active_rao: u64,
+    /// Stake among the matching entries still in cooldown, in rao.
+    pub unstaking_rao: u64,
+    /// Active stake on the node from every staker, in rao.
+    pub node_total_rao: u64,
+    /// The node's emission in the last epoch, if it is in the metagraph.
+    pub last_epoch_emission_rao: Option<u64>,
+    /// The matching stake's share of that emission, assuming the next epoch
+    /// emits the same and rewards are shared in proportion to active stake.
+    pub projected_reward_rao: Option<u64>,
 }
 
 /// Response containing staking information.
@@ -121,17 +252,140 @@ pub struct GetStakeInfoResponse {
     pub stakes: Vec<StakeInfo>,
     /// Total staked amount across all matching entries (in rao).
     pub total_staked_rao: u64,
+    /// The node's current block.
+    #[serde(default)]
+    pub current_block: u64,
+    /// Matching stake per node, by UID.
+    #[serde(default)]
+    pub nodes: Vec<NodeStake>,
 }
 
 /// Handle a GetStakeInfo request.
-///
-/// Phase 1 stub: Returns empty list since staking is not active.
 pub async fn handle_get_stake_info(
-    _request: GetStakeInfoRequest,
+    request: GetStakeInfoRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&Arc<RwLock<StakeManager>>>,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    metagraph_manager: Option<&Arc<RwLock<MetagraphManager>>>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
 ) -> Result<GetStakeInfoResponse, String> {
-    // Phase 3: Query chitin_economics::StakeManager for stake data
+    let coldkey = match &request.coldkey {
+        Some(coldkey) => Some(parse_coldkey(coldkey, "coldkey")?),
+        None => None,
+    };
+    let (ledger, stake_manager) = staking_state(ledger, stake_manager)?;
+    let block = current_block(epoch_manager).await;
+    let mut ledger = ledger.write().await;
+    let mut stakes = stake_manager.write().await;
+    ledger.release_unstakes(&mut stakes, block);
+    drop(ledger);
+
+    let matching: Vec<_> = stakes
+        .entries()
+        .iter()
+        .filter(|e| coldkey.is_none_or(|c| e.staker == c))
+        .filter(|e| request.node_uid.is_none_or(|uid| e.node_uid == uid))
+        .collect();
+
+    let mut nodes: BTreeMap<u16, NodeStake> = BTreeMap::new();
+    for entry in &matching {
+        let node = nodes.entry(entry.node_uid).or_insert_with(|| NodeStake {
+            node_uid: entry.node_uid,
+            did: None,
+            active_rao: 0,
+            unstaking_rao: 0,
+            node_total_rao: stakes.total_stake_for_node(entry.node_uid),
+            last_epoch_emission_rao: None,
+            projected_reward_rao: None,
+        });
+        match entry.unstake_requested_at {
+            Some(_) => node.unstaking_rao += entry.amount,
+            None => node.active_rao += entry.amount,
+        }
+    }
+    if let Some(identities) = identities {
+        let registry = identities.read().await;
+        for node in nodes.values_mut() {
+            node.did = registry.did_of(node.node_uid).map(str::to_string);
+        }
+    }
+    if let Some(mm) = metagraph_manager {
+        if let Some(metagraph) = mm.read().await.current() {
+            for info in &metagraph.nodes {
+                if let Some(node) = nodes.get_mut(&info.uid) {
+                    node.last_epoch_emission_rao = Some(info.emission);
+                    if node.node_total_rao > 0 {
+                        let share = node.active_rao as f64 / node.node_total_rao as f64;
+                        node.projected_reward_rao = Some((info.emission as f64 * share) as u64);
+                    }
+                }
+            }
+        }
+    }
+
+    let infos: Vec<StakeInfo> = matching
+        .iter()
+        .map(|e| {
+            let complete_at = e.cooldown_complete_block();
+            StakeInfo {
+                staker_coldkey: encode_hex(&e.staker),
+                node_uid: e.node_uid,
+                amount_rao: e.amount,
+                amount_ctn: e.amount as f64 / RAO_PER_CTN as f64,
+                staked_at_block: e.staked_at_block,
+                unstake_pending: e.unstake_requested_at.is_some(),
+                cooldown_complete_block: complete_at,
+                cooldown_remaining_blocks: complete_at.map(|at| at.saturating_sub(block)),
+            }
+        })
+        .collect();
     Ok(GetStakeInfoResponse {
-        stakes: Vec::new(),
-        total_staked_rao: 0,
+        total_staked_rao: infos.iter().map(|s| s.amount_rao).sum(),
+        stakes: infos,
+        current_block: block,
+        nodes: nodes.into_values().collect(),
+    })
+}
+
+type StakingState<'a> = (&'a Arc<RwLock<Ledger>>, &'a Arc<RwLock<StakeManager>>);
+
+pub(crate) fn staking_state<'a>(
+    ledger: Option<&'a Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&'a Arc<RwLock<StakeManager>>>,
+) -> Result<StakingState<'a>, String> {
+    match (ledger, stake_manager) {
+        (Some(ledger), Some(stakes)) => Ok((ledger, stakes)),
+        _ => Err("Staking not available".to_string()),
+    }
+}
+
+pub(crate) async fn current_block(epoch_manager: Option<&Arc<RwLock<EpochManager>>>) -> u64 {
+    match epoch_manager {
+        Some(em) => em.read().await.current_block(),
+        None => 0,
+    }
+}
+
+fn signed_action(
+    kind: StakeActionKind,
+    staker_coldkey: &str,
+    node_uid: u16,
+    amount_rao: u64,
+    nonce: u64,
+    signature: &str,
+) -> Result<SignedStakeAction, String> {
+    let signature = match decode_hex(signature) {
+        Some(signature) if signature.len() == 64 => signature,
+        _ => return Err("signature is not a 64-byte hex signature".to_string()),
+    };
+    Ok(SignedStakeAction {
+        action: StakeAction {
+            kind,
+            staker: parse_coldkey(staker_coldkey, "staker_coldkey")?,
+            node_uid,
+            amount: amount_rao,
+            nonce,
+        },
+        signature,
     })
 }
@@ -1,9 +1,31 @@
 // crates/chitin-rpc/src/handlers/sync.rs
 //
-// Sync status and trigger handlers: GetSyncStatus, TriggerSync.
-// Phase 4: Reports more accurate status based on peer count.
+// Sync status and trigger handlers: GetSyncStatus, TriggerSync, VbfExchange,
+// Reconcile, RangeSummary, RangeIds, MerkleNodes, MerkleIds, PolypMeta,
+// HardeningCheckpoint, StateUpdates.
+// Phase 4: Reports more accurate status based on peer count. Status reports
+// percent-complete of persisted catch-up checkpoints and the sync loop's live
+// per-peer metrics.
+
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_consensus::hardening::HardeningCheckpoint;
+use chitin_core::traits::PolypStore;
+use chitin_store::merkle::{MerkleNode, MerklePrefix};
+use chitin_store::{RocksStore, ShardSet};
+use chitin_sync::merkle::{MAX_MERKLE_IDS, MAX_MERKLE_PREFIXES};
+use chitin_sync::metrics::{PeerSyncStats, SyncMetrics, SyncPhase};
+use chitin_sync::priority::PolypSyncMeta;
+use chitin_sync::progress::{overall_percent, PeerSyncProgress};
+use chitin_sync::range::{
+    IdPage, RangeIndex, RangeSummary, TimeRange, MAX_ID_BATCH_SIZE, MAX_RANGES_PER_REQUEST,
+};
+use chitin_sync::reconcile::{SetReconciler, StrataEstimator};
+use chitin_sync::state_update::{changes_after, PolypStateUpdate, MAX_STATE_UPDATES};
+use chitin_sync::vbf::VectorBloomFilter;
 
 // ---------------------------------------------------------------------------
 // GetSyncStatus
@@ -26,24 +48,99 @@ pub struct GetSyncStatusResponse {
     pub sync_progress_percent: f64,
     /// Estimated time to completion in seconds.
     pub estimated_time_seconds: Option<u64>,
+    /// Per-peer progress of catch-ups still in flight.
+    #[serde(default)]
+    pub catch_ups: Vec<PeerCatchUp>,
+    /// What the sync loop is currently doing.
+    #[serde(default)]
+    pub phase: SyncPhase,
+    /// Estimated lag: the most polyps missing relative to any one peer.
+    #[serde(default)]
+    pub missing_polyps: u64,
+    /// Per-peer sync metrics: last success and error, polyps pulled and
+    /// pushed, missing count, and current phase.
+    #[serde(default)]
+    pub peers: Vec<PeerSyncStats>,
+}
+
+/// Progress of a catch-up from one peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerCatchUp {
+    /// The peer being synced from.
+    pub peer_url: String,
+    /// Polyps fetched so far.
+    pub fetched: u64,
+    /// Polyps missing when the catch-up started (or last resumed).
+    pub total: u64,
+    /// Percentage of completion (0.0 to 100.0).
+    pub percent_complete: f64,
 }
 
 /// Handle a GetSyncStatus request.
 ///
-/// Phase 4: Reports sync status based on peer connectivity.
+/// Phase 4: Reports sync status based on peer connectivity. Progress comes
+/// from the catch-up checkpoints the sync loop persists; the node is synced
+/// when none are outstanding and no peer holds polyps still missing locally.
+/// The time estimate extrapolates each checkpoint's fetch rate since it was
+/// created. With `metrics`, peers count toward `syncing_from_peers` only
+/// while polyps are missing from them or a sync with them is under way.
 pub async fn handle_get_sync_status(
+    store: &Arc<RocksStore>,
     _request: GetSyncStatusRequest,
     peer_count: usize,
+    metrics: Option<&SyncMetrics>,
 ) -> Result<GetSyncStatusResponse, String> {
+    let checkpoints = PeerSyncProgress::load_all(store)
+        .map_err(|e| format!("Failed to load sync progress: {}", e))?;
+    let catch_ups = checkpoints
+        .iter()
+        .map(|progress| PeerCatchUp {
+            peer_url: progress.peer_url.clone(),
+            fetched: progress.fetched(),
+            total: progress.total(),
+            percent_complete: progress.percent_complete(),
+        })
+        .collect();
+    let snapshot = metrics.map(SyncMetrics::snapshot).unwrap_or_default();
+    let syncing_from_peers = match metrics {
+        Some(_) => snapshot
+            .peers
+            .iter()
+            .filter(|p| p.missing > 0 || p.phase != SyncPhase::Idle)
+            .count(),
+        None => peer_count,
+    };
+
     Ok(GetSyncStatusResponse {
-        is_synced: true,
+        is_synced: checkpoints.iter().all(PeerSyncProgress::is_complete) && snapshot.lag() == 0,
         blocks_behind: 0,
-        syncing_from_peers: peer_count as u32,
-        sync_progress_percent: 100.0,
-        estimated_time_seconds: None,
+        syncing_from_peers: syncing_from_peers as u32,
+        sync_progress_percent: overall_percent(&checkpoints),
+        estimated_time_seconds: estimate_remaining_secs(&checkpoints),
+        catch_ups,
+        phase: snapshot.phase,
+        missing_polyps: snapshot.lag(),
+        peers: snapshot.peers,
     })
 }
 
+/// Seconds until the slowest checkpoint completes at its observed rate, or
+/// `None` if any rate is still unknown.
+fn estimate_remaining_secs(checkpoints: &[PeerSyncProgress]) -> Option<u64> {
+    let mut slowest = None;
+    for progress in checkpoints.iter().filter(|p| !p.is_complete()) {
+        let elapsed_ms = progress.updated_ms.saturating_sub(progress.started_ms);
+        if progress.fetched() == 0 || elapsed_ms == 0 {
+            return None;
+        }
+        let remaining = (progress.total() - progress.fetched()) as f64;
+        let ms_per_polyp = elapsed_ms as f64 / progress.fetched() as f64;
+        let secs = (remaining * ms_per_polyp / 1000.0).ceil() as u64;
+        slowest = slowest.max(Some(secs));
+    }
+    slowest
+}
+
 // ---------------------------------------------------------------------------
 // TriggerSync
 // ---------------------------------------------------------------------------
@@ -88,3 +185,418 @@ pub async fn handle_trigger_sync(
         })
     }
 }
+
+// ---------------------------------------------------------------------------
+// VbfExchange
+// ---------------------------------------------------------------------------
+
+/// A peer's Vector Bloom Filter over its polyp IDs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VbfExchangeRequest {
+    /// Hex-encoded VBF of the requester's polyp IDs.
+    pub vbf: String,
+    /// Number of polyp IDs in the requester's filter.
+    pub count: usize,
+    /// Only consider polyps in these shards (the requester's subscription).
+    #[serde(default)]
+    pub shards: Option<ShardSet>,
+}
+
+/// This node's filter plus the IDs the requester is definitely missing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VbfExchangeResponse {
+    /// Hex-encoded VBF of this node's polyp IDs.
+    pub vbf: String,
+    /// Number of polyp IDs on this node.
+    pub count: usize,
+    /// Local IDs absent from the requester's filter.
+    pub missing: Vec<Uuid>,
+}
+
+/// Handle a VbfExchange request (`sync/vbf`).
+///
+/// Replaces full ID list exchange for pull-sync: the requester learns what
+/// it is missing without either side sending its whole ID list.
+pub async fn handle_vbf_exchange(
+    store: &Arc<RocksStore>,
+    request: VbfExchangeRequest,
+) -> Result<VbfExchangeResponse, String> {
+    let remote = VectorBloomFilter::from_hex(&request.vbf).map_err(|e| e.to_string())?;
+    let ids = super::peer::held_polyp_ids(store, request.shards.as_ref()).await?;
+    let reconciler = SetReconciler::with_local_ids(ids);
+
+    Ok(VbfExchangeResponse {
+        vbf: reconciler.local_filter().to_hex(),
+        count: reconciler.len(),
+        missing: reconciler.missing_from(&remote),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Reconcile
+// ---------------------------------------------------------------------------
+
+/// A peer's strata estimator, opening IBLT reconciliation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileRequest {
+    /// Hex-encoded strata estimator over the requester's polyp IDs.
+    pub estimator: String,
+    /// Number of polyp IDs on the requester.
+    pub count: usize,
+    /// Only consider polyps in these shards (the requester's subscription).
+    #[serde(default)]
+    pub shards: Option<ShardSet>,
+}
+
+/// This node's IBLT, sized for the estimated difference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileResponse {
+    /// Hex-encoded IBLT of this node's polyp IDs.
+    pub iblt: String,
+    /// Number of polyp IDs on this node.
+    pub count: usize,
+    /// Estimated size of the symmetric difference.
+    pub estimated_difference: usize,
+}
+
+/// Handle a Reconcile request (`sync/reconcile`).
+///
+/// The requester subtracts its own IBLT from the reply and decodes the exact
+/// difference. Errors if the difference is too large for an IBLT; the
+/// requester then falls back to `sync/vbf`.
+pub async fn handle_reconcile(
+    store: &Arc<RocksStore>,
+    request: ReconcileRequest,
+) -> Result<ReconcileResponse, String> {
+    let remote = StrataEstimator::from_hex(&request.estimator).map_err(|e| e.to_string())?;
+    let ids = super::peer::held_polyp_ids(store, request.shards.as_ref()).await?;
+    let reconciler = SetReconciler::with_local_ids(ids);
+    let (iblt, estimated_difference) = reconciler.iblt_for(&remote).map_err(|e| e.to_string())?;
+
+    Ok(ReconcileResponse {
+        iblt: iblt.to_hex(),
+        count: reconciler.len(),
+        estimated_difference,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// RangeSummary
+// ---------------------------------------------------------------------------
+
+/// Time ranges to summarize.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeSummaryRequest {
+    /// Ranges to summarize (at most `MAX_RANGES_PER_REQUEST`).
+    pub ranges: Vec<TimeRange>,
+}
+
+/// Per-range counts and hashes, in request order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeSummaryResponse {
+    /// One summary per requested range.
+    pub summaries: Vec<RangeSummary>,
+}
+
+/// Handle a RangeSummary request (`sync/range/summary`).
+pub async fn handle_range_summary(
+    store: &Arc<RocksStore>,
+    request: RangeSummaryRequest,
+) -> Result<RangeSummaryResponse, String> {
+    if request.ranges.len() > MAX_RANGES_PER_REQUEST {
+        return Err(format!(
+            "Too many ranges: {} (max {})",
+            request.ranges.len(),
+            MAX_RANGES_PER_REQUEST
+        ));
+    }
+    let ranges = validated_ranges(&request.ranges)?;
+    let index = RangeIndex::from_ids(&super::peer::local_polyp_ids(store).await?);
+
+    Ok(RangeSummaryResponse {
+        summaries: ranges.into_iter().map(|r| index.summarize(r)).collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// RangeIds
+// ---------------------------------------------------------------------------
+
+/// A batch of polyp IDs from one time range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeIdsRequest {
+    /// Range to list.
+    pub range: TimeRange,
+    /// Continue after this ID (the previous batch's `next`).
+    #[serde(default)]
+    pub after: Option<Uuid>,
+    /// Maximum IDs to return (capped at `MAX_ID_BATCH_SIZE`).
+    pub limit: usize,
+}
+
+/// Handle a RangeIds request (`sync/range/ids`).
+pub async fn handle_range_ids(
+    store: &Arc<RocksStore>,
+    request: RangeIdsRequest,
+) -> Result<IdPage, String> {
+    let range = validated_ranges(&[request.range])?[0];
+    let index = RangeIndex::from_ids(&super::peer::local_polyp_ids(store).await?);
+    let limit = request.limit.clamp(1, MAX_ID_BATCH_SIZE);
+
+    Ok(index.list_ids(range, request.after, limit))
+}
+
+/// Re-check deserialized ranges, which bypass `TimeRange::new`.
+fn validated_ranges(ranges: &[TimeRange]) -> Result<Vec<TimeRange>, String> {
+    ranges
+        .iter()
+        .map(|r| TimeRange::new(r.start_ms, r.end_ms).map_err(|e| e.to_string()))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// MerkleNodes
+// ---------------------------------------------------------------------------
+
+/// Merkle subtrees to look up.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleNodesRequest {
+    /// Subtree prefixes (at most `MAX_MERKLE_PREFIXES`).
+    pub prefixes: Vec<MerklePrefix>,
+}
+
+/// Counts and hashes of the requested subtrees, in request order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleNodesResponse {
+    /// One node per requested prefix.
+    pub nodes: Vec<MerkleNode>,
+}
+
+/// Handle a MerkleNodes request (`sync/merkle/nodes`).
+///
+/// Answered from the store's incrementally maintained summary, so an in-sync
+/// peer's root check costs no scan.
+pub async fn handle_merkle_nodes(
+    store: &Arc<RocksStore>,
+    request: MerkleNodesRequest,
+) -> Result<MerkleNodesResponse, String> {
+    validate_prefixes(&request.prefixes)?;
+    let summary = store.merkle().read().unwrap_or_else(|e| e.into_inner());
+
+    Ok(MerkleNodesResponse {
+        nodes: request.prefixes.iter().map(|p| summary.node(*p)).collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// MerkleIds
+// ---------------------------------------------------------------------------
+
+/// Merkle subtrees whose IDs to list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleIdsRequest {
+    /// Subtree prefixes (at most `MAX_MERKLE_PREFIXES`).
+    pub prefixes: Vec<MerklePrefix>,
+}
+
+/// All polyp IDs under the requested subtrees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleIdsResponse {
+    /// IDs, grouped by prefix in request order.
+    pub ids: Vec<Uuid>,
+}
+
+/// Handle a MerkleIds request (`sync/merkle/ids`).
+///
+/// Errors if the subtrees hold more than `MAX_MERKLE_IDS` IDs; the requester
+/// should descend further instead.
+pub async fn handle_merkle_ids(
+    store: &Arc<RocksStore>,
+    request: MerkleIdsRequest,
+) -> Result<MerkleIdsResponse, String> {
+    validate_prefixes(&request.prefixes)?;
+    let summary = store.merkle().read().unwrap_or_else(|e| e.into_inner());
+
+    let total: u64 = request.prefixes.iter().map(|p| summary.node(*p).count).sum();
+    if total > MAX_MERKLE_IDS as u64 {
+        return Err(format!(
+            "Requested subtrees hold {} IDs (max {})",
+            total, MAX_MERKLE_IDS
+        ));
+    }
+
+    Ok(MerkleIdsResponse {
+        ids: request
+            .prefixes
+            .iter()
+            .flat_map(|p| summary.ids_under(*p))
+            .collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// PolypMeta
+// ---------------------------------------------------------------------------
+
+/// Most polyps described by one PolypMeta request.
+pub const MAX_META_BATCH: usize = 1024;
+
+/// Polyps to describe before fetching.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolypMetaRequest {
+    /// Polyp IDs (at most `MAX_META_BATCH`).
+    pub ids: Vec<Uuid>,
+}
+
+/// State, epoch, and zone of each known polyp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolypMetaResponse {
+    /// One entry per requested ID held by this node; unknown IDs are omitted.
+    pub polyps: Vec<PolypSyncMeta>,
+}
+
+/// Handle a PolypMeta request (`sync/polyp_meta`).
+///
+/// Lets a syncing peer order its fetches by priority without downloading
+/// the polyps first.
+pub async fn handle_polyp_meta(
+    store: &Arc<RocksStore>,
+    request: PolypMetaRequest,
+) -> Result<PolypMetaResponse, String> {
+    if request.ids.len() > MAX_META_BATCH {
+        return Err(format!(
+            "Too many IDs: {} (max {})",
+            request.ids.len(),
+            MAX_META_BATCH
+        ));
+    }
+
+    let mut polyps = Vec::with_capacity(request.ids.len());
+    for id in &request.ids {
+        let polyp = store
+            .get_polyp(id)
+            .await
+            .map_err(|e| format!("Failed to get polyp {}: {}", id, e))?;
+        if let Some(p) = polyp {
+            polyps.push(PolypSyncMeta {
+                id: p.id,
+                state: p.state,
+                epoch: p.consensus.map(|c| c.epoch),
+                reef_zone: p.reef_zone,
+            });
+        }
+    }
+
+    Ok(PolypMetaResponse { polyps })
+}
+
+// ---------------------------------------------------------------------------
+// HardeningCheckpoint
+// ---------------------------------------------------------------------------
+
+/// Request for the hardening Merkle root of an epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetHardeningCheckpointRequest {
+    /// Consensus epoch.
+    pub epoch: u64,
+}
+
+/// The epoch's hardening checkpoint, if this node recorded one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetHardeningCheckpointResponse {
+    pub checkpoint: Option<HardeningCheckpoint>,
+}
+
+/// Handle a GetHardeningCheckpoint request (`sync/hardening_checkpoint`).
+///
+/// Syncing peers that lack an epoch's checkpoint collect it from several
+/// nodes and verify hardened polyps against the majority root.
+pub async fn handle_get_hardening_checkpoint(
+    store: &Arc<RocksStore>,
+    request: GetHardeningCheckpointRequest,
+) -> Result<GetHardeningCheckpointResponse, String> {
+    let checkpoint = HardeningCheckpoint::load(store, request.epoch)
+        .map_err(|e| format!("Failed to load hardening checkpoint: {}", e))?;
+    Ok(GetHardeningCheckpointResponse { checkpoint })
+}
+
+// ---------------------------------------------------------------------------
+// StateUpdates
+// ---------------------------------------------------------------------------
+
+/// Request for state transitions recorded after a change-log cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateUpdatesRequest {
+    /// Cursor returned by the previous request (`None` from the start).
+    #[serde(default)]
+    pub after: Option<String>,
+    /// Most updates to return (capped at `MAX_STATE_UPDATES`).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Current state of each polyp changed after the cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateUpdatesResponse {
+    pub updates: Vec<PolypStateUpdate>,
+    /// Cursor to pass as `after` next (unchanged if nothing new).
+    pub cursor: Option<String>,
+    /// Whether more changes remain after `cursor`.
+    pub more: bool,
+}
+
+/// Handle a StateUpdates request (`sync/state_updates`).
+///
+/// Lets peers that already hold a polyp learn its later transitions. Each
+/// update describes the polyp's current state and is signed by this node
+/// when it has a signing key.
+pub async fn handle_state_updates(
+    store: &Arc<RocksStore>,
+    request: StateUpdatesRequest,
+    signer: Option<(&[u8; 32], [u8; 32])>,
+) -> Result<StateUpdatesResponse, String> {
+    let limit = request
+        .limit
+        .unwrap_or(MAX_STATE_UPDATES)
+        .clamp(1, MAX_STATE_UPDATES);
+    let (ids, last) = changes_after(store, request.after.as_deref(), limit)
+        .map_err(|e| format!("Failed to read change log: {}", e))?;
+
+    let mut updates = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let polyp = store
+            .get_polyp(id)
+            .await
+            .map_err(|e| format!("Failed to get polyp {}: {}", id, e))?;
+        if let Some(polyp) = polyp {
+            let mut update = PolypStateUpdate::from_polyp(&polyp, 0);
+            if let Some((signing_key, hotkey)) = signer {
+                update
+                    .sign(signing_key, hotkey)
+                    .map_err(|e| format!("Failed to sign state update: {}", e))?;
+            }
+            updates.push(update);
+        }
+    }
+
+    Ok(StateUpdatesResponse {
+        updates,
+        more: ids.len() == limit,
+        cursor: last.or(request.after),
+    })
+}
+
+/// Check the count and shape of requested Merkle prefixes.
+fn validate_prefixes(prefixes: &[MerklePrefix]) -> Result<(), String> {
+    if prefixes.len() > MAX_MERKLE_PREFIXES {
+        return Err(format!(
+            "Too many prefixes: {} (max {})",
+            prefixes.len(),
+            MAX_MERKLE_PREFIXES
+        ));
+    }
+    match prefixes.iter().find(|p| !p.is_valid()) {
+        Some(p) => Err(format!("Invalid Merkle prefix {:?}", p)),
+        None => Ok(()),
+    }
+}
@@ -1,16 +1,34 @@
 // crates/chitin-rpc/src/handlers/validation.rs
 //
-// Validation and scoring handlers: SubmitScores, GetEpochStatus, GetConsensusResult.
+// Validation and scoring handlers: SubmitScores, BeaconContribution, GetEpochStatus,
+// GetConsensusResult.
 // Phase 4: Wired to live epoch manager and consensus result state.
+// Consensus results of past epochs come from the recorded consensus history.
+// Score submissions list the polyps they scored, which must be assigned to
+// the submitting validator for the epoch (see `chitin_consensus::assignment`).
+// Each weight lists the polyps behind it, so a validator only weights the
+// Corals whose polyps it was assigned; polyps held locally must have been
+// created by the Coral weighted.
+// Validators also send their randomness beacon commitments and reveals here.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
+use chitin_consensus::assignment::TaskAssignment;
+use chitin_consensus::beacon;
 use chitin_consensus::epoch::{EpochManager, EpochPhase};
+use chitin_consensus::history::ConsensusRecord;
+use chitin_consensus::metagraph::MetagraphManager;
 use chitin_consensus::weights::WeightMatrix;
 use chitin_consensus::yuma::ConsensusResult;
+use chitin_core::crypto;
+use chitin_core::traits::PolypStore;
+use chitin_core::{ChitinError, IdentityRegistry, NodeType};
+use chitin_store::RocksStore;
 
 // ---------------------------------------------------------------------------
 // SubmitScores
@@ -23,6 +41,9 @@ pub struct WeightEntry {
     pub coral_uid: u16,
     /// The weight (aggregated score) for this Coral Node.
     pub weight: f64,
+    /// Polyps of this Coral Node scored for the weight.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub polyp_ids: Vec<Uuid>,
 }
 
 /// Request for a Tide Node to submit epoch scores/weights.
@@ -34,10 +55,83 @@ pub struct SubmitScoresRequest {
     pub epoch: u64,
     /// Sparse weight vector: (coral_uid, weight) pairs.
     pub weights: Vec<WeightEntry>,
-    /// Hex-encoded signature over the score payload.
+    /// Polyps scored to produce `weights`, each assigned to this validator
+    /// for the epoch.
+    #[serde(default)]
+    pub polyp_ids: Vec<Uuid>,
+    /// Hex-encoded ed25519 signature over `signable_bytes()` by the hotkey.
     pub signature: String,
 }
 
+impl SubmitScoresRequest {
+    /// Canonical bytes to sign: SHA-256 of the JSON-encoded hotkey, epoch,
+    /// weights, and scored polyp IDs (left out when there are none).
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, ChitinError> {
+        let (hotkey, epoch, weights) = (&self.validator_hotkey, self.epoch, &self.weights);
+        let body = if self.polyp_ids.is_empty() {
+            serde_json::to_vec(&(hotkey, epoch, weights))?
+        } else {
+            serde_json::to_vec(&(hotkey, epoch, weights, &self.polyp_ids))?
+        };
+        Ok(crypto::hash_bytes(&body).to_vec())
+    }
+
+    /// Sign the submission with the validator's hotkey, filling in
+    /// `validator_hotkey` and `signature`.
+    pub fn sign(&mut self, signing_key: &[u8; 32], hotkey: [u8; 32]) -> Result<(), ChitinError> {
+        self.validator_hotkey = encode_hex(&hotkey);
+        let signature = crypto::sign_message(signing_key, &self.signable_bytes()?)?;
+        self.signature = encode_hex(&signature);
+        Ok(())
+    }
+
+    /// Verify the signature against `validator_hotkey`.
+    ///
+    /// Returns `Ok(false)` for malformed hex and invalid signatures.
+    pub fn verify_signature(&self) -> Result<bool, ChitinError> {
+        verify_hex_signature(
+            &self.validator_hotkey,
+            &self.signature,
+            &self.signable_bytes()?,
+        )
+    }
+}
+
+/// Verify a hex-encoded signature by a hex-encoded hotkey over `message`.
+/// Returns `Ok(false)` for malformed hex and invalid signatures.
+fn verify_hex_signature(
+    hotkey: &str,
+    signature: &str,
+    message: &[u8],
+) -> Result<bool, ChitinError> {
+    let hotkey: [u8; 32] = match decode_hex(hotkey) {
+        Some(bytes) => match bytes.try_into() {
+            Ok(hotkey) => hotkey,
+            Err(_) => return Ok(false),
+        },
+        None => return Ok(false),
+    };
+    let signature = match decode_hex(signature) {
+        Some(signature) if signature.len() == 64 => signature,
+        _ => return Ok(false),
+    };
+    crypto::verify_signature(&hotkey, message, &signature)
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
 /// Response from score submission.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubmitScoresResponse {
@@ -49,12 +143,20 @@ pub struct SubmitScoresResponse {
 
 /// Handle a SubmitScores request.
 ///
-/// Phase 4: Validates epoch phase is Scoring or Committing, stores weights
-/// in the shared weight matrix.
+/// Phase 4: Validates epoch phase is Scoring or Committing and the
+/// validator's signature, then stores weights in the shared weight matrix.
+/// With an `assignment` for the epoch, the submission must list the polyps
+/// it scored, all assigned to the validator, and each weight the polyps of
+/// its Coral behind it; polyps in `store` must have been created by the
+/// Coral weighted. Weights go in the row of the validator's registered UID;
+/// unregistered validators, and UIDs the matrix has no row for, are refused.
 pub async fn handle_submit_scores(
     request: SubmitScoresRequest,
     weight_matrix: Option<&Arc<RwLock<WeightMatrix>>>,
     epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    assignment: Option<&TaskAssignment>,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+    store: &RocksStore,
 ) -> Result<SubmitScoresResponse, String> {
     // Validate epoch manager is available
     let em = match epoch_manager {
@@ -93,15 +195,65 @@ pub async fn handle_submit_scores(
         });
     }
 
+    // Check the validator's signature over the payload
+    match request.verify_signature() {
+        Ok(true) => {}
+        Ok(false) => {
+            return Ok(SubmitScoresResponse {
+                accepted: false,
+                message: "Invalid signature for validator hotkey".to_string(),
+            });
+        }
+        Err(e) => return Err(format!("Failed to verify signature: {}", e)),
+    }
+
+    if let Some(assignment) = assignment.filter(|a| a.epoch() == request.epoch) {
+        let creators = polyp_creators(&request, store, identities).await;
+        if let Some(message) = assignment_violation(&request, assignment, &creators) {
+            return Ok(SubmitScoresResponse {
+                accepted: false,
+                message,
+            });
+        }
+    }
+
     // Store weights in the weight matrix
     if let Some(wm) = weight_matrix {
+        let hotkey = decode_hex(&request.validator_hotkey)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok());
+        let validator_uid = match (identities, hotkey) {
+            (Some(identities), Some(hotkey)) => {
+                Some(identities.read().await.uid_of_hotkey(&hotkey))
+            }
+            _ => None,
+        };
         let mut wm = wm.write().await;
-        // For Phase 4, we use validator_uid=0 (single validator)
-        // and store each weight entry by coral_uid
+        // A validator writes only its own UID's row. Without an identity
+        // registry, Phase 4 matrices hold a single validator row. Each weight
+        // entry is stored by coral_uid.
+        let row = match validator_uid {
+            None => 0,
+            Some(Some(uid)) if usize::from(uid) < wm.weights.len() => usize::from(uid),
+            Some(Some(uid)) => {
+                return Ok(SubmitScoresResponse {
+                    accepted: false,
+                    message: format!(
+                        "Validator UID {} has no row in the weight matrix for epoch {}",
+                        uid, request.epoch
+                    ),
+                });
+            }
+            Some(None) => {
+                return Ok(SubmitScoresResponse {
+                    accepted: false,
+                    message: "Validator hotkey is not registered".to_string(),
+                });
+            }
+        };
         for entry in &request.weights {
             let coral_idx = entry.coral_uid as usize;
             if coral_idx < wm.weights.get(0).map_or(0, |r| r.len()) {
-                wm.set(0, coral_idx, entry.weight);
+                wm.set(row, coral_idx, entry.weight);
             }
         }
     }
@@ -116,6 +268,292 @@ pub async fn handle_submit_scores(
     })
 }
 
+/// UIDs of the Corals that created the polyps behind `request`'s weights,
+/// for those polyps held in `store` whose creators are registered.
+async fn polyp_creators(
+    request: &SubmitScoresRequest,
+    store: &RocksStore,
+    identities: Option<&Arc<RwLock<IdentityRegistry>>>,
+) -> HashMap<Uuid, u16> {
+    let mut creators = HashMap::new();
+    let Some(identities) = identities else {
+        return creators;
+    };
+    let identities = identities.read().await;
+    for id in request.weights.iter().flat_map(|entry| &entry.polyp_ids) {
+        let Ok(Some(polyp)) = store.get_polyp(id).await else {
+            continue;
+        };
+        if let Some(uid) = identities.uid_of_hotkey(&polyp.subject.provenance.creator.hotkey) {
+            creators.insert(*id, uid);
+        }
+    }
+    creators
+}
+
+/// Why `request` breaks the epoch's scoring assignment, if it does.
+/// `creators` maps polyps to the UIDs of the Corals that created them.
+fn assignment_violation(
+    request: &SubmitScoresRequest,
+    assignment: &TaskAssignment,
+    creators: &HashMap<Uuid, u16>,
+) -> Option<String> {
+    if request.polyp_ids.is_empty() && !request.weights.is_empty() {
+        return Some("Score submissions must list the polyps scored".to_string());
+    }
+    // The signature has been checked, so the hotkey is well-formed.
+    let hotkey: [u8; 32] = decode_hex(&request.validator_hotkey)?.try_into().ok()?;
+    let unassigned = request
+        .polyp_ids
+        .iter()
+        .filter(|id| !assignment.is_assigned(id, &hotkey))
+        .count();
+    if unassigned > 0 {
+        return Some(format!(
+            "{} of {} scored polyps are not assigned to this validator in epoch {}",
+            unassigned,
+            request.polyp_ids.len(),
+            request.epoch
+        ));
+    }
+    // Each weight is backed by assigned polyps of its own Coral, and each
+    // polyp backs one weight, so no column is weighted without an assignment.
+    let mut backing = HashSet::new();
+    for entry in &request.weights {
+        if entry.polyp_ids.is_empty() {
+            return Some(format!(
+                "Weight for coral {} lists no scored polyps",
+                entry.coral_uid
+            ));
+        }
+        for id in &entry.polyp_ids {
+            if !assignment.is_assigned(id, &hotkey) {
+                return Some(format!(
+                    "Weight for coral {} lists polyp {}, which is not assigned to this \
+                     validator in epoch {}",
+                    entry.coral_uid, id, request.epoch
+                ));
+            }
+            if !backing.insert(*id) {
+                return Some(format!("Polyp {} backs more than one weight", id));
+            }
+            if let Some(&creator) = creators.get(id).filter(|&&uid| uid != entry.coral_uid) {
+                return Some(format!(
+                    "Weight for coral {} lists polyp {}, created by coral {}",
+                    entry.coral_uid, id, creator
+                ));
+            }
+        }
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// BeaconContribution
+// ---------------------------------------------------------------------------
+
+/// A validator's contribution to the epoch's randomness beacon (see
+/// `chitin_consensus::beacon`): a commitment during Scoring, then the
+/// secret during Committing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconContributionRequest {
+    /// Hex-encoded validator hotkey.
+    pub validator_hotkey: String,
+    /// Epoch the contribution is for.
+    pub epoch: u64,
+    /// Hex-encoded commitment to the validator's secret.
+    #[serde(default)]
+    pub commitment: Option<String>,
+    /// Hex-encoded secret, revealed after committing.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Hex-encoded ed25519 signature over `signable_bytes()` by the hotkey.
+    pub signature: String,
+}
+
+impl BeaconContributionRequest {
+    /// A signed commitment to `secret` for `epoch`.
+    pub fn commit(
+        epoch: u64,
+        secret: &[u8; 32],
+        signing_key: &[u8; 32],
+        hotkey: [u8; 32],
+    ) -> Result<Self, ChitinError> {
+        let commitment = beacon::commitment(epoch, &hotkey, secret);
+        Self::signed(epoch, Some(encode_hex(&commitment)), None, signing_key, hotkey)
+    }
+
+    /// A signed reveal of `secret` for `epoch`.
+    pub fn reveal(
+        epoch: u64,
+        secret: &[u8; 32],
+        signing_key: &[u8; 32],
+        hotkey: [u8; 32],
+    ) -> Result<Self, ChitinError> {
+        Self::signed(epoch, None, Some(encode_hex(secret)), signing_key, hotkey)
+    }
+
+    fn signed(
+        epoch: u64,
+        commitment: Option<String>,
+        secret: Option<String>,
+        signing_key: &[u8; 32],
+        hotkey: [u8; 32],
+    ) -> Result<Self, ChitinError> {
+        let mut request = Self {
+            validator_hotkey: encode_hex(&hotkey),
+            epoch,
+            commitment,
+            secret,
+            signature: String::new(),
+        };
+        let signature = crypto::sign_message(signing_key, &request.signable_bytes()?)?;
+        request.signature = encode_hex(&signature);
+        Ok(request)
+    }
+
+    /// Canonical bytes to sign: SHA-256 of the JSON-encoded hotkey, epoch,
+    /// commitment, and secret.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, ChitinError> {
+        let body = serde_json::to_vec(&(
+            &self.validator_hotkey,
+            self.epoch,
+            &self.commitment,
+            &self.secret,
+        ))?;
+        Ok(crypto::hash_bytes(&body).to_vec())
+    }
+
+    /// Verify the signature against `validator_hotkey`.
+    pub fn verify_signature(&self) -> Result<bool, ChitinError> {
+        verify_hex_signature(
+            &self.validator_hotkey,
+            &self.signature,
+            &self.signable_bytes()?,
+        )
+    }
+}
+
+/// Response to a beacon contribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconContributionResponse {
+    /// Whether the contribution was recorded.
+    pub accepted: bool,
+    /// Human-readable message.
+    pub message: String,
+}
+
+impl BeaconContributionResponse {
+    fn rejected(message: impl Into<String>) -> Self {
+        Self {
+            accepted: false,
+            message: message.into(),
+        }
+    }
+}
+
+/// Handle a beacon contribution.
+///
+/// Commitments are accepted for the current epoch during Scoring, secrets
+/// during Committing. Each request carries exactly one of the two, and only
+/// active Tide and Hybrid nodes of the current metagraph may contribute.
+pub async fn handle_beacon_contribution(
+    request: BeaconContributionRequest,
+    store: &RocksStore,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    metagraph: Option<&Arc<RwLock<MetagraphManager>>>,
+) -> Result<BeaconContributionResponse, String> {
+    let Some(em) = epoch_manager else {
+        return Ok(BeaconContributionResponse::rejected(
+            "This node does not track epochs",
+        ));
+    };
+    let (current_epoch, phase) = {
+        let em = em.read().await;
+        (em.current_epoch(), em.phase().clone())
+    };
+    if request.epoch != current_epoch {
+        return Ok(BeaconContributionResponse::rejected(format!(
+            "Epoch mismatch: contribution for epoch {} but current is {}",
+            request.epoch, current_epoch
+        )));
+    }
+    match request.verify_signature() {
+        Ok(true) => {}
+        Ok(false) => {
+            return Ok(BeaconContributionResponse::rejected(
+                "Invalid signature for validator hotkey",
+            ));
+        }
+        Err(e) => return Err(format!("Failed to verify signature: {}", e)),
+    }
+    let hotkey: [u8; 32] = match decode_hex(&request.validator_hotkey).map(TryInto::try_into) {
+        Some(Ok(hotkey)) => hotkey,
+        _ => return Ok(BeaconContributionResponse::rejected("Malformed validator hotkey")),
+    };
+    let is_validator = match metagraph {
+        Some(mm) => mm.read().await.current().is_some_and(|metagraph| {
+            metagraph.nodes.iter().any(|node| {
+                node.hotkey == hotkey
+                    && node.active
+                    && matches!(node.node_type, NodeType::Tide | NodeType::Hybrid)
+            })
+        }),
+        None => false,
+    };
+    if !is_validator {
+        return Ok(BeaconContributionResponse::rejected(
+            "Hotkey is not an active validator in the current metagraph",
+        ));
+    }
+    let decode = |hex: &str| -> Option<[u8; 32]> { decode_hex(hex)?.try_into().ok() };
+
+    let (result, action) = match (&request.commitment, &request.secret, phase) {
+        (Some(commitment), None, EpochPhase::Scoring) => {
+            let Some(commitment) = decode(commitment) else {
+                return Ok(BeaconContributionResponse::rejected("Malformed commitment"));
+            };
+            (
+                beacon::commit(store, request.epoch, &hotkey, commitment),
+                "commitment",
+            )
+        }
+        (None, Some(secret), EpochPhase::Committing) => {
+            let Some(secret) = decode(secret) else {
+                return Ok(BeaconContributionResponse::rejected("Malformed secret"));
+            };
+            (
+                beacon::reveal(store, request.epoch, &hotkey, secret),
+                "reveal",
+            )
+        }
+        (Some(_), None, phase) => {
+            return Ok(BeaconContributionResponse::rejected(format!(
+                "Cannot accept a beacon commitment during {:?} phase. Wait for Scoring phase.",
+                phase
+            )));
+        }
+        (None, Some(_), phase) => {
+            return Ok(BeaconContributionResponse::rejected(format!(
+                "Cannot accept a beacon reveal during {:?} phase. Wait for Committing phase.",
+                phase
+            )));
+        }
+        _ => {
+            return Ok(BeaconContributionResponse::rejected(
+                "A beacon contribution carries either a commitment or a secret",
+            ));
+        }
+    };
+    Ok(match result {
+        Ok(()) => BeaconContributionResponse {
+            accepted: true,
+            message: format!("Recorded beacon {} for epoch {}", action, request.epoch),
+        },
+        Err(e) => BeaconContributionResponse::rejected(e.to_string()),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // GetEpochStatus
 // ---------------------------------------------------------------------------
@@ -131,6 +569,12 @@ pub struct GetEpochStatusResponse {
     pub epoch: u64,
     /// Current phase: "Open", "Scoring", "Committing", or "Closed".
     pub phase: String,
+    /// Latest block height.
+    #[serde(default)]
+    pub block: u64,
+    /// Blocks per epoch.
+    #[serde(default)]
+    pub blocks_per_epoch: u64,
     /// Blocks remaining in the current phase.
     pub blocks_remaining: u64,
     /// Estimated time remaining in seconds.
@@ -160,7 +604,9 @@ pub async fn handle_get_epoch_status(
             Ok(GetEpochStatusResponse {
                 epoch: em.current_epoch(),
                 phase: phase_str.to_string(),
-                blocks_remaining: 0, // Phase 5: compute from block position
+                block: em.current_block(),
+                blocks_per_epoch: em.blocks_per_epoch(),
+                blocks_remaining: em.blocks_remaining_in_phase(),
                 time_remaining_seconds: 0,
                 scores_submitted: 0,
                 total_validators: 1, // Phase 4: single validator
@@ -170,6 +616,8 @@ pub async fn handle_get_epoch_status(
             Ok(GetEpochStatusResponse {
                 epoch: 0,
                 phase: "Open".to_string(),
+                block: 0,
+                blocks_per_epoch: 0,
                 blocks_remaining: 0,
                 time_remaining_seconds: 0,
                 scores_submitted: 0,
@@ -186,13 +634,17 @@ pub async fn handle_get_epoch_status(
 /// Request for the consensus result of a completed epoch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetConsensusResultRequest {
-    /// Epoch number to query.
-    pub epoch: u64,
+    /// Epoch number to query. If omitted, the latest finalized epoch.
+    #[serde(default)]
+    pub epoch: Option<u64>,
 }
 
 /// Response containing the consensus result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetConsensusResultResponse {
+    /// The epoch the result is for, if known.
+    #[serde(default)]
+    pub epoch: Option<u64>,
     /// Whether the epoch has been finalized.
     pub finalized: bool,
     /// Consensus weights per Coral Node (if finalized).
@@ -205,39 +657,106 @@ pub struct GetConsensusResultResponse {
     pub hardened_count: u32,
 }
 
+impl GetConsensusResultResponse {
+    fn finalized(epoch: Option<u64>, result: &ConsensusResult) -> Self {
+        Self {
+            epoch,
+            finalized: true,
+            consensus_weights: Some(result.consensus_weights.clone()),
+            incentives: Some(result.incentives.clone()),
+            dividends: Some(result.dividends.clone()),
+            hardened_count: result.hardened_polyp_ids.len() as u32,
+        }
+    }
+
+    fn pending(epoch: Option<u64>) -> Self {
+        Self {
+            epoch,
+            finalized: false,
+            consensus_weights: None,
+            incentives: None,
+            dividends: None,
+            hardened_count: 0,
+        }
+    }
+}
+
 /// Handle a GetConsensusResult request.
 ///
-/// Phase 4: Returns the last consensus result from shared state.
+/// Reads the epoch's result from the recorded consensus history. Without an
+/// epoch, returns the newest recorded result, or the last result in shared
+/// state if none has been recorded.
 pub async fn handle_get_consensus_result(
-    _request: GetConsensusResultRequest,
+    request: GetConsensusResultRequest,
     consensus_result: Option<&Arc<RwLock<Option<ConsensusResult>>>>,
+    store: &RocksStore,
 ) -> Result<GetConsensusResultResponse, String> {
+    let epoch = match request.epoch {
+        Some(epoch) => Some(epoch),
+        None => ConsensusRecord::epochs(store)
+            .map_err(|e| format!("Failed to list consensus history: {}", e))?
+            .last()
+            .copied(),
+    };
+    if let Some(epoch) = epoch {
+        let record = ConsensusRecord::load(store, epoch)
+            .map_err(|e| format!("Failed to load consensus result: {}", e))?;
+        return Ok(match record {
+            Some(record) => GetConsensusResultResponse::finalized(Some(epoch), &record.result),
+            None => GetConsensusResultResponse::pending(Some(epoch)),
+        });
+    }
+
     match consensus_result {
-        Some(cr) => {
-            let cr = cr.read().await;
-            match cr.as_ref() {
-                Some(result) => Ok(GetConsensusResultResponse {
-                    finalized: true,
-                    consensus_weights: Some(result.consensus_weights.clone()),
-                    incentives: Some(result.incentives.clone()),
-                    dividends: Some(result.dividends.clone()),
-                    hardened_count: result.hardened_polyp_ids.len() as u32,
-                }),
-                None => Ok(GetConsensusResultResponse {
-                    finalized: false,
-                    consensus_weights: None,
-                    incentives: None,
-                    dividends: None,
-                    hardened_count: 0,
-                }),
-            }
+        Some(cr) => match cr.read().await.as_ref() {
+            Some(result) => Ok(GetConsensusResultResponse::finalized(None, result)),
+            None => Ok(GetConsensusResultResponse::pending(None)),
+        },
+        None => Ok(GetConsensusResultResponse::pending(None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOTKEY: [u8; 32] = [1u8; 32];
+
+    fn entry(coral_uid: u16, polyp_ids: &[Uuid]) -> WeightEntry {
+        WeightEntry {
+            coral_uid,
+            weight: 0.5,
+            polyp_ids: polyp_ids.to_vec(),
         }
-        None => Ok(GetConsensusResultResponse {
-            finalized: false,
-            consensus_weights: None,
-            incentives: None,
-            dividends: None,
-            hardened_count: 0,
-        }),
+    }
+
+    fn request(polyp_ids: &[Uuid], weights: Vec<WeightEntry>) -> SubmitScoresRequest {
+        SubmitScoresRequest {
+            validator_hotkey: encode_hex(&HOTKEY),
+            epoch: 7,
+            weights,
+            polyp_ids: polyp_ids.to_vec(),
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_weights_need_assigned_polyps_of_their_coral() {
+        let assignment = TaskAssignment::new(7, [9u8; 32], vec![HOTKEY, [2u8; 32]], 1);
+        let (mine, theirs): (Vec<Uuid>, Vec<Uuid>) = (0..64u128)
+            .map(Uuid::from_u128)
+            .partition(|id| assignment.is_assigned(id, &HOTKEY));
+        let (a, b, other) = (mine[0], mine[1], theirs[0]);
+        let creators = HashMap::from([(a, 3), (b, 4)]);
+        let check =
+            |weights| assignment_violation(&request(&[a, b], weights), &assignment, &creators);
+
+        assert_eq!(check(vec![entry(3, &[a]), entry(4, &[b])]), None);
+        // A column with no assigned polyps behind it.
+        assert!(check(vec![entry(3, &[a]), entry(9, &[])]).is_some());
+        assert!(check(vec![entry(3, &[a]), entry(9, &[other])]).is_some());
+        // Another Coral's polyp, or one polyp behind two columns.
+        assert!(check(vec![entry(3, &[a]), entry(9, &[b])]).is_some());
+        assert!(check(vec![entry(3, &[a]), entry(9, &[a])]).is_some());
     }
 }
@@ -1,10 +1,58 @@
 // crates/chitin-rpc/src/handlers/wallet.rs
 //
-// Wallet management handlers: CreateWallet, ImportWallet, GetBalance, Transfer.
-// Phase 1: Stub implementations. Phase 3 will implement real key management
-// and $CTN token operations.
+// Wallet management handlers: CreateWallet, ImportWallet, GetBalance, Transfer,
+// GetHistory.
+// CreateWallet and ImportWallet are Phase 1 stubs (keys are managed by the
+// CLI). Balances, transfers, and history are served from the node's ledger;
+// transfers arrive signed by the sender's coldkey. Completed unstakes are
+// credited back before balances are read or transfers applied. The ledger and
+// stakes are persisted to the node's store after every transfer, stake, and
+// unstake, so that nonces survive a restart and a signed action cannot be
+// replayed against a ledger reset to genesis.
+
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use chitin_consensus::epoch::EpochManager;
+use chitin_core::ChitinError;
+use chitin_economics::{
+    Ledger, SignedTransfer, StakeManager, Transfer, TransferRecord, RAO_PER_CTN,
+};
+use chitin_store::RocksStore;
+
+use super::staking::{current_block, staking_state};
+use super::validation::{decode_hex, encode_hex};
+
+/// Default number of transfers returned by `wallet/history`.
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// Maximum number of transfers returned by `wallet/history`.
+pub const MAX_HISTORY_LIMIT: usize = 1_000;
+
+/// Key of the persisted ledger and stakes.
+const ECONOMICS_KEY: &str = "economics_state";
+
+/// Load the persisted ledger and stakes, if any.
+pub fn load_economics(store: &RocksStore) -> Result<Option<(Ledger, StakeManager)>, ChitinError> {
+    match store.get_bytes(ECONOMICS_KEY.as_bytes())? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Persist the ledger and stakes, replacing the previous copy.
+pub fn save_economics(
+    store: &RocksStore,
+    ledger: &Ledger,
+    stakes: &StakeManager,
+) -> Result<(), ChitinError> {
+    store.put_bytes(
+        ECONOMICS_KEY.as_bytes(),
+        &serde_json::to_vec(&(ledger, stakes))?,
+    )
+}
 
 // ---------------------------------------------------------------------------
 // CreateWallet
@@ -107,20 +155,48 @@ pub struct GetBalanceResponse {
     pub staked_rao: u64,
     /// Available (unstaked) balance in rao.
     pub available_rao: u64,
+    /// Nonce the coldkey's next transfer must carry.
+    #[serde(default)]
+    pub nonce: u64,
+    /// Fee charged per transfer, in rao.
+    #[serde(default)]
+    pub transfer_fee_rao: u64,
 }
 
 /// Handle a GetBalance request.
 ///
-/// Phase 1 stub: Returns zero balance.
+/// The balance is the coldkey's liquid (available) balance plus its stake,
+/// including stake still in an unstaking cooldown.
 pub async fn handle_get_balance(
-    _request: GetBalanceRequest,
+    request: GetBalanceRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&Arc<RwLock<StakeManager>>>,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
 ) -> Result<GetBalanceResponse, String> {
-    // Phase 3: Look up actual balance from chitin-economics state
+    let coldkey = parse_coldkey(&request.coldkey, "coldkey")?;
+    let mut ledger = ledger.ok_or("Ledger not available")?.write().await;
+    let staked = match stake_manager {
+        Some(stakes) => {
+            let mut stakes = stakes.write().await;
+            ledger.release_unstakes(&mut stakes, current_block(epoch_manager).await);
+            stakes
+                .entries()
+                .iter()
+                .filter(|e| e.staker == coldkey)
+                .map(|e| e.amount)
+                .sum()
+        }
+        None => 0,
+    };
+    let available = ledger.balance(&coldkey);
+    let balance = available.saturating_add(staked);
     Ok(GetBalanceResponse {
-        balance_rao: 0,
-        balance_ctn: 0.0,
-        staked_rao: 0,
-        available_rao: 0,
+        balance_rao: balance,
+        balance_ctn: balance as f64 / RAO_PER_CTN as f64,
+        staked_rao: staked,
+        available_rao: available,
+        nonce: ledger.nonce(&coldkey),
+        transfer_fee_rao: ledger.transfer_fee(),
     })
 }
 
@@ -128,7 +204,7 @@ pub async fn handle_get_balance(
 // Transfer
 // ---------------------------------------------------------------------------
 
-/// Request to transfer $CTN between coldkeys.
+/// Request to transfer $CTN between coldkeys, signed by the sender.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferRequest {
     /// Hex-encoded sender coldkey.
@@ -137,6 +213,32 @@ pub struct TransferRequest {
     pub to_coldkey: String,
     /// Amount to transfer in rao.
     pub amount_rao: u64,
+    /// Fee paid on top of the amount, in rao.
+    pub fee_rao: u64,
+    /// The sender's next nonce (see `wallet/balance`).
+    pub nonce: u64,
+    /// Hex ed25519 signature over the transfer's signing bytes.
+    pub signature: String,
+}
+
+impl TransferRequest {
+    /// Decode the request into a signed transfer.
+    pub fn to_signed(&self) -> Result<SignedTransfer, String> {
+        let signature = match decode_hex(&self.signature) {
+            Some(signature) if signature.len() == 64 => signature,
+            _ => return Err("signature is not a 64-byte hex signature".to_string()),
+        };
+        Ok(SignedTransfer {
+            transfer: Transfer {
+                from: parse_coldkey(&self.from_coldkey, "from_coldkey")?,
+                to: parse_coldkey(&self.to_coldkey, "to_coldkey")?,
+                amount: self.amount_rao,
+                fee: self.fee_rao,
+                nonce: self.nonce,
+            },
+            signature,
+        })
+    }
 }
 
 /// Response from a transfer.
@@ -148,16 +250,125 @@ pub struct TransferResponse {
     pub tx_hash: Option<String>,
     /// Human-readable message.
     pub message: String,
+    /// Block at which the transfer was applied.
+    #[serde(default)]
+    pub block: Option<u64>,
 }
 
 /// Handle a Transfer request.
 ///
-/// Phase 1 stub: Transfers are not yet implemented.
-pub async fn handle_transfer(_request: TransferRequest) -> Result<TransferResponse, String> {
-    // Phase 3: Implement actual token transfers
+/// Verifies the signature, nonce, fee, and balance, then applies the
+/// transfer at the current block and persists the ledger and stakes to
+/// `store`. Rejected transfers are errors.
+pub async fn handle_transfer(
+    request: TransferRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+    stake_manager: Option<&Arc<RwLock<StakeManager>>>,
+    epoch_manager: Option<&Arc<RwLock<EpochManager>>>,
+    store: &RocksStore,
+) -> Result<TransferResponse, String> {
+    let signed = request.to_signed()?;
+    let (ledger, stakes) =
+        staking_state(ledger, stake_manager).map_err(|_| "Ledger not available".to_string())?;
+    let block = current_block(epoch_manager).await;
+    let mut ledger = ledger.write().await;
+    let mut stakes = stakes.write().await;
+    ledger.release_unstakes(&mut stakes, block);
+    let record = ledger.apply(&signed, block).map_err(|e| e.to_string())?;
+    save_economics(store, &ledger, &stakes).map_err(|e| e.to_string())?;
     Ok(TransferResponse {
-        success: false,
-        tx_hash: None,
-        message: "Phase 1 stub: $CTN transfers not yet implemented".to_string(),
+        success: true,
+        tx_hash: Some(encode_hex(&record.tx_hash)),
+        message: format!(
+            "Transferred {} rao to {}",
+            record.transfer.amount, request.to_coldkey
+        ),
+        block: Some(record.block),
     })
 }
+
+// ---------------------------------------------------------------------------
+// GetHistory
+// ---------------------------------------------------------------------------
+
+/// Request for the transfers sent or received by a coldkey.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetHistoryRequest {
+    /// Hex-encoded coldkey public key.
+    pub coldkey: String,
+    /// Maximum number of transfers (default 20, at most 1,000).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// One transfer in a coldkey's history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Hex transaction hash.
+    pub tx_hash: String,
+    /// "sent" or "received", relative to the requested coldkey.
+    pub direction: String,
+    /// Hex sender coldkey.
+    pub from_coldkey: String,
+    /// Hex recipient coldkey.
+    pub to_coldkey: String,
+    /// Amount transferred, in rao.
+    pub amount_rao: u64,
+    /// Fee paid by the sender, in rao.
+    pub fee_rao: u64,
+    /// Sender nonce.
+    pub nonce: u64,
+    /// Block at which the transfer was applied.
+    pub block: u64,
+}
+
+/// Response containing a coldkey's transfers, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetHistoryResponse {
+    /// Hex-encoded coldkey public key.
+    pub coldkey: String,
+    pub transfers: Vec<HistoryEntry>,
+}
+
+/// Handle a GetHistory request.
+pub async fn handle_get_history(
+    request: GetHistoryRequest,
+    ledger: Option<&Arc<RwLock<Ledger>>>,
+) -> Result<GetHistoryResponse, String> {
+    let coldkey = parse_coldkey(&request.coldkey, "coldkey")?;
+    let limit = request.limit.unwrap_or(DEFAULT_HISTORY_LIMIT);
+    if limit > MAX_HISTORY_LIMIT {
+        return Err(format!("limit {} exceeds the maximum of {}", limit, MAX_HISTORY_LIMIT));
+    }
+    let ledger = ledger.ok_or("Ledger not available")?.read().await;
+    let transfers = ledger
+        .history(&coldkey, limit)
+        .into_iter()
+        .map(|record| history_entry(record, &coldkey))
+        .collect();
+    Ok(GetHistoryResponse {
+        coldkey: encode_hex(&coldkey),
+        transfers,
+    })
+}
+
+fn history_entry(record: &TransferRecord, coldkey: &[u8; 32]) -> HistoryEntry {
+    let transfer = &record.transfer;
+    let direction = if &transfer.from == coldkey { "sent" } else { "received" };
+    HistoryEntry {
+        tx_hash: encode_hex(&record.tx_hash),
+        direction: direction.to_string(),
+        from_coldkey: encode_hex(&transfer.from),
+        to_coldkey: encode_hex(&transfer.to),
+        amount_rao: transfer.amount,
+        fee_rao: transfer.fee,
+        nonce: transfer.nonce,
+        block: record.block,
+    }
+}
+
+pub(crate) fn parse_coldkey(hex: &str, field: &str) -> Result<[u8; 32], String> {
+    decode_hex(hex.trim())
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| format!("{} is not a 32-byte hex key", field))
+}
@@ -6,11 +6,33 @@
 // defined in ARCHITECTURE.md Section 10. Phase 1 uses JSON-based RPC
 // over tonic rather than full protobuf codegen.
 
+pub mod auth;
 pub mod handlers;
 pub mod middleware;
+pub mod query_cache;
 pub mod server;
 
 // Re-export the main server types for ergonomic access.
 pub use server::ChitinRpcServer;
+pub use server::AnnounceCallback;
+pub use auth::{KeyRole, RequestAuth, RequestAuthConfig, SignedRequest};
+pub use server::{BackupCallback, BackupFuture};
+pub use server::{ConfigReloadCallback, ConfigReloadFuture};
+pub use server::{ConfigUpdateCallback, ConfigUpdateFuture, ConfigViewCallback, ConfigViewFuture};
+pub use server::{EmbedCallback, EmbedFuture};
+pub use server::{IngestCallback, IngestFuture};
+pub use server::LogQueryCallback;
 pub use server::GossipCallback;
+pub use server::{IdentityConflictsCallback, IdentityConflictsFuture};
+pub use server::{PeerDirectoryCallback, PeerDirectoryFuture};
+pub use server::{PeerListCallback, PeerListFuture};
+pub use server::{PromoteCallback, PromoteFuture};
+pub use server::{ReplicationStateCallback, ReplicationStateFuture};
+pub use server::{ReplicationStatusCallback, ReplicationStatusFuture};
+pub use server::{ShardProxyCallback, ShardProxyFuture, ShardRouting};
+pub use query_cache::{QueryCache, QueryCacheConfig, QueryCacheStats};
 pub use server::RpcConfig;
+pub use server::ScrubReportCallback;
+pub use server::SigningAllowedCallback;
+pub use server::{SnapshotCallback, SnapshotFuture};
+pub use server::{TaskListCallback, TaskListFuture};
@@ -0,0 +1,459 @@
+// crates/chitin-rpc/src/query_cache.rs
+//
+// `QueryCache`: an in-memory LRU cache of `query/search` responses.
+//
+// Entries are keyed by a hash of the query vector and the query's other
+// parameters (filters, top_k, ranking and rerank options), and remember the
+// model space and zone they searched. When a Polyp is indexed or removed,
+// entries that could have returned it (same model space, or any space for
+// cross-model and unscoped queries; a zone filter covering its zone) are
+// dropped. Changes that do not touch the index, e.g. state transitions,
+// trust updates, or new Polyps on sharded peers, are bounded by `ttl_secs`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use chitin_core::crypto::hash_bytes;
+use chitin_core::Polyp;
+use chitin_reputation::taxonomy::is_within;
+
+use crate::handlers::query::{SemanticSearchRequest, SemanticSearchResponse};
+
+/// Settings for the query cache (`[query_cache]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryCacheConfig {
+    /// Cache search responses.
+    pub enabled: bool,
+    /// Most responses held; the least recently used are evicted.
+    pub max_entries: usize,
+    /// Longest a response is served, in seconds.
+    pub ttl_secs: u64,
+}
+
+impl Default for QueryCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 1024,
+            ttl_secs: 60,
+        }
+    }
+}
+
+impl QueryCacheConfig {
+    /// The configured cache, or `None` if disabled.
+    pub fn build(&self) -> Option<QueryCache> {
+        (self.enabled && self.max_entries > 0 && self.ttl_secs > 0)
+            .then(|| QueryCache::new(self.max_entries, Duration::from_secs(self.ttl_secs)))
+    }
+}
+
+/// Cache key: the query vector's hash and the query's other parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QueryKey {
+    vector: [u8; 32],
+    params: String,
+}
+
+impl QueryKey {
+    /// The key of `request`, or `None` if it has no query vector.
+    pub fn of(request: &SemanticSearchRequest) -> Option<Self> {
+        let vector = request.query_vector.as_ref()?;
+        let bytes: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let params = SemanticSearchRequest {
+            query_vector: None,
+            ..request.clone()
+        };
+        Some(Self {
+            vector: hash_bytes(&bytes),
+            params: serde_json::to_string(&params).ok()?,
+        })
+    }
+}
+
+/// What a cached search could have returned.
+#[derive(Debug, Clone, Default)]
+pub struct QueryScope {
+    /// The one model space searched, or `None` for any (unscoped and
+    /// cross-model queries).
+    pub model_space: Option<String>,
+    /// The query's zone filter, if any.
+    pub reef_zone: Option<String>,
+}
+
+impl QueryScope {
+    /// Whether a Polyp indexed in `model` and `zone` could change the
+    /// results.
+    fn covers(&self, model: &str, zone: Option<&str>) -> bool {
+        let in_space = self.model_space.as_deref().is_none_or(|m| m == model);
+        let in_zone = match (&self.reef_zone, zone) {
+            (None, _) => true,
+            (Some(filter), Some(zone)) => is_within(zone, filter),
+            (Some(_), None) => false,
+        };
+        in_space && in_zone
+    }
+}
+
+struct Entry {
+    response: SemanticSearchResponse,
+    scope: QueryScope,
+    stored: Instant,
+    /// Position in `Inner::recency`.
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<QueryKey, Entry>,
+    /// Keys by last use, least recent first.
+    recency: BTreeMap<u64, QueryKey>,
+    next_tick: u64,
+}
+
+impl Inner {
+    fn touch(&mut self, key: &QueryKey) {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.tick);
+            entry.tick = tick;
+            self.recency.insert(tick, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &QueryKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.tick);
+        }
+    }
+}
+
+/// Hit and eviction counters of a `QueryCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryCacheStats {
+    pub entries: usize,
+    pub max_entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries evicted to make room or expired.
+    pub evictions: u64,
+    /// Entries dropped because a Polyp they could return was (un)indexed.
+    pub invalidations: u64,
+}
+
+impl QueryCacheStats {
+    /// Fraction of lookups served from the cache (0.0 before any).
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// LRU cache of search responses.
+pub struct QueryCache {
+    max_entries: usize,
+    ttl: Duration,
+    inner: Mutex<Inner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl QueryCache {
+    /// A cache holding at most `max_entries` responses (at least one), each
+    /// for at most `ttl`.
+    pub fn new(max_entries: usize, ttl: Duration) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            ttl,
+            inner: Mutex::new(Inner::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The cached response for `key`, counting a hit or miss.
+    pub fn get(&self, key: &QueryKey) -> Option<SemanticSearchResponse> {
+        let mut inner = self.lock();
+        let fresh = inner
+            .entries
+            .get(key)
+            .map(|entry| entry.stored.elapsed() < self.ttl);
+        let response = match fresh {
+            Some(true) => {
+                inner.touch(key);
+                inner.entries.get(key).map(|entry| entry.response.clone())
+            }
+            Some(false) => {
+                inner.remove(key);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => None,
+        };
+        let counter = if response.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        response
+    }
+
+    /// Cache `response` for `key`, evicting the least recently used entries
+    /// if the cache is full.
+    pub fn insert(&self, key: QueryKey, response: SemanticSearchResponse, scope: QueryScope) {
+        let mut inner = self.lock();
+        inner.remove(&key);
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+        inner.recency.insert(tick, key.clone());
+        inner.entries.insert(
+            key,
+            Entry {
+                response,
+                scope,
+                stored: Instant::now(),
+                tick,
+            },
+        );
+        while inner.entries.len() > self.max_entries {
+            let Some((_, oldest)) = inner.recency.pop_first() else {
+                break;
+            };
+            inner.entries.remove(&oldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Drop the entries that a Polyp (un)indexed in model space `model`, in
+    /// `zone`, could change. Returns how many were dropped.
+    pub fn invalidate(&self, model: &str, zone: Option<&str>) -> usize {
+        let mut inner = self.lock();
+        let stale: Vec<QueryKey> = inner
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.scope.covers(model, zone))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &stale {
+            inner.remove(key);
+        }
+        self.invalidations
+            .fetch_add(stale.len() as u64, Ordering::Relaxed);
+        stale.len()
+    }
+
+    /// Drop the entries that `polyp`, just (un)indexed, could change.
+    pub fn invalidate_polyp(&self, polyp: &Polyp) -> usize {
+        self.invalidate(
+            &polyp.subject.vector.model_id.key(),
+            polyp.reef_zone.as_deref(),
+        )
+    }
+
+    /// Entry count, cap, and counters since startup.
+    pub fn stats(&self) -> QueryCacheStats {
+        QueryCacheStats {
+            entries: self.lock().entries.len(),
+            max_entries: self.max_entries,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::{
+        EmbeddingModelId, NodeIdentity, NodeType, Payload, PolypState, PolypSubject,
+        ProcessingPipeline, ProofPublicInputs, Provenance, SourceAttribution, VectorEmbedding,
+        ZkProof,
+    };
+    use serde_json::json;
+
+    fn key(request: serde_json::Value) -> QueryKey {
+        QueryKey::of(&serde_json::from_value(request).unwrap()).unwrap()
+    }
+
+    /// A response told apart by `total_found`.
+    fn response(id: u32) -> SemanticSearchResponse {
+        SemanticSearchResponse {
+            results: Vec::new(),
+            search_time_ms: 0,
+            total_found: id,
+            reranked: false,
+        }
+    }
+
+    fn scope(model_space: Option<&str>, reef_zone: Option<&str>) -> QueryScope {
+        QueryScope {
+            model_space: model_space.map(str::to_string),
+            reef_zone: reef_zone.map(str::to_string),
+        }
+    }
+
+    fn polyp(model: &str, zone: Option<&str>) -> Polyp {
+        let now = chrono::Utc::now();
+        let model_id = EmbeddingModelId::from_key(model, 2);
+        Polyp {
+            id: uuid::Uuid::now_v7(),
+            state: PolypState::Draft,
+            subject: PolypSubject {
+                payload: Payload {
+                    content: "content".to_string(),
+                    content_type: "text/plain".to_string(),
+                    language: None,
+                },
+                vector: VectorEmbedding {
+                    values: vec![1.0, 0.0],
+                    model_id: model_id.clone(),
+                    quantization: "float32".to_string(),
+                    normalization: "l2".to_string(),
+                },
+                provenance: Provenance {
+                    creator: NodeIdentity {
+                        coldkey: [0u8; 32],
+                        hotkey: [0u8; 32],
+                        did: "did:chitin:local".to_string(),
+                        node_type: NodeType::Coral,
+                    },
+                    source: SourceAttribution {
+                        source_cid: None,
+                        source_url: None,
+                        title: None,
+                        license: None,
+                        accessed_at: now,
+                    },
+                    pipeline: ProcessingPipeline {
+                        steps: vec![],
+                        duration_ms: 0,
+                    },
+                    molted_from: vec![],
+                },
+            },
+            proof: ZkProof {
+                proof_type: "placeholder".to_string(),
+                proof_value: "0x00".to_string(),
+                vk_hash: "0x00".to_string(),
+                public_inputs: ProofPublicInputs {
+                    text_hash: [0u8; 32],
+                    vector_hash: [0u8; 32],
+                    model_id,
+                },
+                created_at: now,
+            },
+            consensus: None,
+            hardening: None,
+            created_at: now,
+            updated_at: now,
+            signature: None,
+            reef_zone: zone.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_key_covers_the_vector_and_every_parameter() {
+        let base = key(json!({ "query_vector": [1.0, 0.0], "top_k": 5 }));
+        assert_eq!(base, key(json!({ "query_vector": [1.0, 0.0], "top_k": 5 })));
+        for other in [
+            json!({ "query_vector": [0.0, 1.0], "top_k": 5 }),
+            json!({ "query_vector": [1.0, 0.0], "top_k": 6 }),
+            json!({ "query_vector": [1.0, 0.0], "top_k": 5, "reef_zone": "code" }),
+            json!({ "query_vector": [1.0, 0.0], "top_k": 5, "mmr_lambda": 0.5 }),
+            json!({ "query_vector": [1.0, 0.0], "top_k": 5, "rerank": true }),
+        ] {
+            assert_ne!(base, key(other));
+        }
+
+        // Text-only queries are embedded first and have no key.
+        let text_only = serde_json::from_value(json!({ "query_text": "rust" })).unwrap();
+        assert!(QueryKey::of(&text_only).is_none());
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = QueryCache::new(2, Duration::from_secs(60));
+        let (a, b, c) = (
+            key(json!({ "query_vector": [1.0] })),
+            key(json!({ "query_vector": [2.0] })),
+            key(json!({ "query_vector": [3.0] })),
+        );
+        cache.insert(a.clone(), response(1), QueryScope::default());
+        cache.insert(b.clone(), response(2), QueryScope::default());
+        // Using `a` leaves `b` as the least recently used.
+        assert_eq!(cache.get(&a).unwrap().total_found, 1);
+        cache.insert(c.clone(), response(3), QueryScope::default());
+
+        assert!(cache.get(&b).is_none());
+        assert_eq!(cache.get(&a).unwrap().total_found, 1);
+        assert_eq!(cache.get(&c).unwrap().total_found, 3);
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.evictions), (2, 1));
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+    }
+
+    #[test]
+    fn test_entries_expire_after_the_ttl() {
+        let cache = QueryCache::new(4, Duration::from_millis(20));
+        let a = key(json!({ "query_vector": [1.0] }));
+        cache.insert(a.clone(), response(1), QueryScope::default());
+        assert!(cache.get(&a).is_some());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(cache.get(&a).is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.evictions), (0, 1));
+    }
+
+    #[test]
+    fn test_invalidate_polyp_drops_only_entries_it_could_change() {
+        let cache = QueryCache::new(16, Duration::from_secs(60));
+        let scopes = [
+            scope(Some("test/a"), None),
+            scope(Some("test/b"), None),
+            // Cross-model and unscoped queries search every space.
+            scope(None, None),
+            scope(Some("test/a"), Some("code")),
+            scope(Some("test/a"), Some("law")),
+        ];
+        let keys: Vec<QueryKey> = (0..scopes.len())
+            .map(|i| key(json!({ "query_vector": [i as f32] })))
+            .collect();
+        for (i, (key, scope)) in keys.iter().zip(scopes).enumerate() {
+            cache.insert(key.clone(), response(i as u32), scope);
+        }
+
+        assert_eq!(
+            cache.invalidate_polyp(&polyp("test/a", Some("code/rust"))),
+            3
+        );
+        let kept: Vec<bool> = keys.iter().map(|k| cache.get(k).is_some()).collect();
+        assert_eq!(kept, [false, true, false, false, true]);
+
+        // A Polyp outside any zone cannot match a zone filter.
+        assert_eq!(cache.invalidate_polyp(&polyp("test/a", None)), 0);
+        assert_eq!(cache.invalidate_polyp(&polyp("test/b", None)), 1);
+        assert_eq!(cache.stats().invalidations, 4);
+    }
+}
@@ -9,6 +9,9 @@
 // This avoids the need for proto codegen while still using tonic's server
 // infrastructure for transport, streaming, and middleware.
 
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -18,17 +21,32 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tonic::transport::Server;
 use tonic::Status;
+use tracing::Instrument;
 
 use chitin_consensus::bonds::BondMatrix;
+use chitin_consensus::dedup::{self, DuplicateDetector, DuplicateMatch};
+use chitin_consensus::assignment::{AssignmentConfig, TaskAssignment};
+use chitin_consensus::moderation::{self, ModerationStage, PolicySet};
 use chitin_consensus::epoch::EpochManager;
 use chitin_consensus::metagraph::MetagraphManager;
 use chitin_consensus::weights::WeightMatrix;
 use chitin_consensus::yuma::ConsensusResult;
 use chitin_core::identity::NodeIdentity;
-use chitin_store::{HardenedStore, InMemoryVectorIndex, RocksStore};
+use chitin_core::keystore::SecretKey;
+use chitin_core::IdentityRegistry;
+use chitin_drift::versioning::VersionRegistry;
+use chitin_economics::{Ledger, StakeManager};
+use chitin_reputation::domain_store::DomainTrustStore;
+use chitin_reputation::taxonomy::DomainTaxonomy;
+use chitin_store::{AdmissionControl, HardenedStore, InMemoryVectorIndex, RocksStore, ShardSet};
+use chitin_sync::metrics::SyncMetrics;
+use chitin_sync::throttle::SyncThrottle;
 
+use crate::auth::{RequestAuth, RequestAuthConfig};
 use crate::handlers;
+use crate::handlers::validation::SubmitScoresRequest;
 use crate::middleware;
+use crate::query_cache::{QueryCache, QueryKey, QueryScope};
 
 /// Callback type for broadcasting a polyp to peers after creation.
 /// The daemon provides this closure to wire gossip into the RPC layer
@@ -36,6 +54,172 @@ use crate::middleware;
 pub type GossipCallback =
     Arc<dyn Fn(chitin_core::polyp::Polyp) + Send + Sync>;
 
+/// Future returned by a `ShardProxyCallback`: one JSON result per peer.
+pub type ShardProxyFuture = Pin<Box<dyn Future<Output = Vec<serde_json::Value>> + Send>>;
+
+/// Callback type for forwarding a request to peers holding other shards.
+/// Given a method, its params, and the shards to cover, the daemon calls
+/// responsible peers and returns their results.
+pub type ShardProxyCallback =
+    Arc<dyn Fn(String, serde_json::Value, Vec<u16>) -> ShardProxyFuture + Send + Sync>;
+
+/// Future returned by a `ConfigReloadCallback`.
+pub type ConfigReloadFuture = Pin<
+    Box<dyn Future<Output = Result<handlers::admin::ReloadConfigResponse, String>> + Send>,
+>;
+
+/// Callback type for `admin/config/reload`: the daemon re-reads its config
+/// file and applies the hot-reloadable fields.
+pub type ConfigReloadCallback = Arc<dyn Fn() -> ConfigReloadFuture + Send + Sync>;
+
+/// Future returned by a `ConfigViewCallback`.
+pub type ConfigViewFuture = Pin<Box<dyn Future<Output = serde_json::Value> + Send>>;
+
+/// Callback type for `admin/config`: the daemon serializes the
+/// configuration in effect.
+pub type ConfigViewCallback = Arc<dyn Fn() -> ConfigViewFuture + Send + Sync>;
+
+/// Future returned by a `ConfigUpdateCallback`.
+pub type ConfigUpdateFuture = Pin<
+    Box<dyn Future<Output = Result<handlers::admin::UpdateConfigResponse, String>> + Send>,
+>;
+
+/// Callback type for `admin/config/update`: the daemon merges the updates
+/// into its config file and reloads it.
+pub type ConfigUpdateCallback =
+    Arc<dyn Fn(handlers::admin::UpdateConfigRequest) -> ConfigUpdateFuture + Send + Sync>;
+
+/// Callback type for `admin/logs`: the daemon returns matching entries from
+/// its log buffer.
+pub type LogQueryCallback = Arc<
+    dyn Fn(handlers::admin::GetLogsRequest) -> handlers::admin::GetLogsResponse + Send + Sync,
+>;
+
+/// Future returned by a `BackupCallback`.
+pub type BackupFuture =
+    Pin<Box<dyn Future<Output = Result<handlers::admin::BackupResponse, String>> + Send>>;
+
+/// Callback type for `admin/backup`: the daemon copies its databases.
+pub type BackupCallback =
+    Arc<dyn Fn(handlers::admin::BackupRequest) -> BackupFuture + Send + Sync>;
+
+/// Future returned by a `SnapshotCallback`.
+pub type SnapshotFuture =
+    Pin<Box<dyn Future<Output = Result<handlers::admin::SnapshotResponse, String>> + Send>>;
+
+/// Callback type for `admin/snapshot`: the daemon writes a signed snapshot.
+pub type SnapshotCallback =
+    Arc<dyn Fn(handlers::admin::SnapshotRequest) -> SnapshotFuture + Send + Sync>;
+
+/// Future returned by a `TaskListCallback`.
+pub type TaskListFuture = Pin<Box<dyn Future<Output = handlers::admin::ListTasksResponse> + Send>>;
+
+/// Callback type for `admin/tasks`: the daemon lists its supervised
+/// background tasks.
+pub type TaskListCallback = Arc<dyn Fn() -> TaskListFuture + Send + Sync>;
+
+/// Future returned by an `EmbedCallback`.
+pub type EmbedFuture =
+    Pin<Box<dyn Future<Output = Result<handlers::polyp::EmbeddedContent, String>> + Send>>;
+
+/// Callback type for embedding submitted content and query text: the daemon
+/// queues the text on its embedding workers and returns the vector and the
+/// model used.
+pub type EmbedCallback = Arc<dyn Fn(String) -> EmbedFuture + Send + Sync>;
+
+/// Future returned by an `IngestCallback`.
+pub type IngestFuture =
+    Pin<Box<dyn Future<Output = Result<handlers::polyp::IngestedDocument, String>> + Send>>;
+
+/// Callback type for `polyp/ingest_url`: the daemon fetches the URL,
+/// extracts its text, and splits it into chunks.
+pub type IngestCallback =
+    Arc<dyn Fn(handlers::polyp::IngestUrlRequest) -> IngestFuture + Send + Sync>;
+
+/// Future returned by a `PeerDirectoryCallback`.
+pub type PeerDirectoryFuture =
+    Pin<Box<dyn Future<Output = Vec<handlers::peer::DiscoveredPeer>> + Send>>;
+
+/// Callback type for `peer/discover`: the daemon lists the peers it knows,
+/// configured and discovered, with their liveness.
+pub type PeerDirectoryCallback = Arc<dyn Fn() -> PeerDirectoryFuture + Send + Sync>;
+
+/// Future returned by a `PeerListCallback`.
+pub type PeerListFuture = Pin<Box<dyn Future<Output = Vec<handlers::node::PeerInfo>> + Send>>;
+
+/// Callback type for `node/peers`: the daemon lists its peers with their
+/// liveness and dial statistics.
+pub type PeerListCallback = Arc<dyn Fn() -> PeerListFuture + Send + Sync>;
+
+/// Future returned by an `IdentityConflictsCallback`.
+pub type IdentityConflictsFuture =
+    Pin<Box<dyn Future<Output = Vec<handlers::node::IdentityConflict>> + Send>>;
+
+/// Callback type for `node/health`: the daemon lists DIDs announced from
+/// more than one URL.
+pub type IdentityConflictsCallback = Arc<dyn Fn() -> IdentityConflictsFuture + Send + Sync>;
+
+/// Callback type for `node/health`: the daemon reports what its storage
+/// scrubber has found.
+pub type ScrubReportCallback = Arc<dyn Fn() -> handlers::node::ScrubReport + Send + Sync>;
+
+/// Future returned by a `ReplicationStateCallback`.
+pub type ReplicationStateFuture = Pin<
+    Box<
+        dyn Future<Output = Result<handlers::replication::GetReplicationStateResponse, String>>
+            + Send,
+    >,
+>;
+
+/// Callback type for `replication/state`: the daemon snapshots the runtime
+/// state a standby follows.
+pub type ReplicationStateCallback = Arc<dyn Fn() -> ReplicationStateFuture + Send + Sync>;
+
+/// Future returned by a `ReplicationStatusCallback`.
+pub type ReplicationStatusFuture =
+    Pin<Box<dyn Future<Output = handlers::replication::ReplicationStatusResponse> + Send>>;
+
+/// Callback type for `replication/status`: the daemon reports its role and
+/// signing state.
+pub type ReplicationStatusCallback = Arc<dyn Fn() -> ReplicationStatusFuture + Send + Sync>;
+
+/// Future returned by a `PromoteCallback`.
+pub type PromoteFuture =
+    Pin<Box<dyn Future<Output = Result<handlers::replication::PromoteResponse, String>> + Send>>;
+
+/// Callback type for `admin/promote`: the daemon promotes itself from
+/// standby to primary.
+pub type PromoteCallback =
+    Arc<dyn Fn(handlers::replication::PromoteRequest) -> PromoteFuture + Send + Sync>;
+
+/// Callback type reporting whether this node may sign (false on a standby).
+pub type SigningAllowedCallback = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Callback type invoked for each accepted `peer/announce`, so the daemon
+/// can learn about the announcing peer.
+pub type AnnounceCallback = Arc<dyn Fn(handlers::peer::AnnounceRequest) + Send + Sync>;
+
+/// Routing for a node that holds only some shards.
+#[derive(Clone)]
+pub struct ShardRouting {
+    /// Shards held locally.
+    pub shards: ShardSet,
+    /// Forwards requests for the other shards.
+    pub proxy: ShardProxyCallback,
+}
+
+impl ShardRouting {
+    /// Forward `method` to the peers responsible for `shards`.
+    pub async fn forward(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        shards: Vec<u16>,
+    ) -> Vec<serde_json::Value> {
+        (self.proxy)(method.to_string(), params, shards).await
+    }
+}
+
 // ---------------------------------------------------------------------------
 // RpcConfig
 // ---------------------------------------------------------------------------
@@ -81,8 +265,25 @@ pub struct JsonRpcResponse {
     pub result: Option<serde_json::Value>,
     /// Error message (if not success).
     pub error: Option<String>,
+    /// Milliseconds to wait before retrying, when a write was refused
+    /// because the store is busy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
 }
 
+/// Prefix of operator methods (config, backups, promotion, molt controls),
+/// served only to callers on this host.
+const ADMIN_PREFIX: &str = "admin/";
+
+/// Methods that write new Polyps, refused while the store is under pressure.
+const ADMITTED_METHODS: &[&str] = &[
+    "polyp/submit",
+    "polyp/submit_batch",
+    "polyp/ingest_url",
+    "peer/receive_polyp",
+    "admin/polyp/import",
+];
+
 // ---------------------------------------------------------------------------
 // ChitinRpcServer
 // ---------------------------------------------------------------------------
@@ -110,10 +311,12 @@ pub struct ChitinRpcServer {
     peer_urls: Vec<String>,
     /// Node identity for provenance and announce responses (Phase 2).
     node_identity: Option<NodeIdentity>,
-    /// Signing key for polyp signing (Phase 2).
-    signing_key: Option<[u8; 32]>,
+    /// Signing key for polyp signing (Phase 2), wiped on drop.
+    signing_key: Option<SecretKey>,
     /// This node's publicly reachable URL.
     self_url: Option<String>,
+    /// Network ID exchanged in `peer/announce`.
+    network_id: Option<String>,
     // Phase 4: Shared consensus/epoch state
     /// Epoch manager for epoch status queries.
     epoch_manager: Option<Arc<RwLock<EpochManager>>>,
@@ -125,10 +328,92 @@ pub struct ChitinRpcServer {
     bond_matrix: Option<Arc<RwLock<BondMatrix>>>,
     /// Metagraph manager for metagraph queries.
     metagraph_manager: Option<Arc<RwLock<MetagraphManager>>>,
+    /// Account ledger for wallet balances and transfers.
+    ledger: Option<Arc<RwLock<Ledger>>>,
+    /// Stake entries for staking requests.
+    stake_manager: Option<Arc<RwLock<StakeManager>>>,
+    /// Stable UIDs of registered DIDs and hotkeys.
+    identities: Option<Arc<RwLock<IdentityRegistry>>>,
+    /// Newly stored polyps, for `polyp/subscribe`.
+    polyp_feed: Arc<handlers::polyp::PolypFeed>,
     /// Hardened store for CID-based retrieval.
     hardened_store: Option<Arc<HardenedStore>>,
+    /// Domain-scoped trust store for reputation queries.
+    trust_store: Option<Arc<RwLock<DomainTrustStore>>>,
+    /// Reef Zone taxonomy for submission validation.
+    taxonomy: Option<Arc<DomainTaxonomy>>,
+    /// Hardened polyp fingerprints; submissions are checked for near-duplicates.
+    duplicates: Option<Arc<RwLock<DuplicateDetector>>>,
+    /// Content policies submissions must pass.
+    moderation: Option<Arc<PolicySet>>,
+    /// Scoring task assignment enforced on score submissions.
+    task_assignment: Option<AssignmentConfig>,
+    /// Weight of creator trust in search ranking.
+    search_trust_weight: f64,
+    /// Default MMR lambda for search diversification (`None` disables it).
+    search_mmr_lambda: Option<f64>,
+    /// Cross-encoder rerank stage for search (`None` disables reranking).
+    reranking: Option<handlers::query::Reranking>,
+    /// Cache of search responses (`None` disables caching).
+    query_cache: Option<Arc<QueryCache>>,
+    /// Open `query/scroll` searches.
+    scrolls: Arc<handlers::query::ScrollSessions>,
     /// Daemon start time for uptime calculation.
     start_time: Option<Instant>,
+    /// Shards held locally (`None` holds every shard).
+    shard_set: Option<ShardSet>,
+    /// Forwards requests for shards not held locally.
+    shard_proxy: Option<ShardProxyCallback>,
+    /// Ingestion throttle shared with pull-sync, applied to relayed polyps.
+    sync_throttle: Option<Arc<SyncThrottle>>,
+    /// Write admission control shared with pull-sync (`None` admits all).
+    admission: Option<Arc<AdmissionControl>>,
+    /// Key-role checks and envelope nonces for signed methods.
+    request_auth: Arc<RequestAuth>,
+    /// Per-peer sync metrics recorded by the sync loop and gossip.
+    sync_metrics: Option<Arc<SyncMetrics>>,
+    /// Embedding model versions; submissions under retired models are rejected.
+    model_registry: Option<Arc<RwLock<VersionRegistry>>>,
+    /// Reloads the daemon configuration (`admin/config/reload`).
+    config_reload: Option<ConfigReloadCallback>,
+    /// Serializes the configuration in effect (`admin/config`).
+    config_view: Option<ConfigViewCallback>,
+    /// Updates the config file (`admin/config/update`).
+    config_update: Option<ConfigUpdateCallback>,
+    /// Queries the daemon's log buffer (`admin/logs`).
+    log_query: Option<LogQueryCallback>,
+    /// Backs up the node's databases (`admin/backup`).
+    backup: Option<BackupCallback>,
+    /// Writes a signed bootstrap snapshot (`admin/snapshot`).
+    snapshot: Option<SnapshotCallback>,
+    /// Lists supervised background tasks (`admin/tasks`).
+    task_list: Option<TaskListCallback>,
+    /// Embeds submitted content and query text that arrive without a vector.
+    embedder: Option<EmbedCallback>,
+    /// Fetches and chunks URLs for `polyp/ingest_url`.
+    ingester: Option<IngestCallback>,
+    /// Lists known peers for `peer/discover`.
+    peer_directory: Option<PeerDirectoryCallback>,
+    /// Lists peers with dial statistics for `node/peers`.
+    peer_list: Option<PeerListCallback>,
+    /// Lists identity conflicts for `node/health`.
+    identity_conflicts: Option<IdentityConflictsCallback>,
+    /// Reports the storage scrubber's findings for `node/health`.
+    scrub_report: Option<ScrubReportCallback>,
+    /// Snapshots runtime state for `replication/state`.
+    replication_state: Option<ReplicationStateCallback>,
+    /// Reports the replication role for `replication/status`.
+    replication_status: Option<ReplicationStatusCallback>,
+    /// Promotes a standby for `admin/promote`.
+    promote: Option<PromoteCallback>,
+    /// Whether this node may sign; always if unset.
+    signing_allowed: Option<SigningAllowedCallback>,
+    /// Notified of accepted `peer/announce` requests.
+    announce_callback: Option<AnnounceCallback>,
+    /// Methods served by this node; all methods if unset.
+    allowed_methods: Option<&'static [&'static str]>,
+    /// Whether this node retains all history (`history/*` queries).
+    archival: bool,
 }
 
 impl std::fmt::Debug for ChitinRpcServer {
@@ -163,13 +448,55 @@ impl ChitinRpcServer {
             node_identity: None,
             signing_key: None,
             self_url: None,
+            network_id: None,
             epoch_manager: None,
             last_consensus_result: None,
             weight_matrix: None,
             bond_matrix: None,
             metagraph_manager: None,
+            ledger: None,
+            stake_manager: None,
+            identities: None,
+            polyp_feed: Arc::default(),
             hardened_store: None,
+            trust_store: None,
+            taxonomy: None,
+            duplicates: None,
+            moderation: None,
+            task_assignment: None,
+            search_trust_weight: handlers::query::DEFAULT_SEARCH_TRUST_WEIGHT,
+            search_mmr_lambda: None,
+            reranking: None,
+            query_cache: None,
+            scrolls: Arc::default(),
             start_time: None,
+            shard_set: None,
+            shard_proxy: None,
+            sync_throttle: None,
+            admission: None,
+            request_auth: Arc::new(RequestAuth::default()),
+            sync_metrics: None,
+            model_registry: None,
+            config_reload: None,
+            config_view: None,
+            config_update: None,
+            log_query: None,
+            backup: None,
+            snapshot: None,
+            task_list: None,
+            embedder: None,
+            ingester: None,
+            peer_directory: None,
+            peer_list: None,
+            identity_conflicts: None,
+            scrub_report: None,
+            replication_state: None,
+            replication_status: None,
+            promote: None,
+            signing_allowed: None,
+            announce_callback: None,
+            allowed_methods: None,
+            archival: false,
         }
     }
 
@@ -187,7 +514,11 @@ impl ChitinRpcServer {
     }
 
     /// Set the node identity and optional signing key for provenance and polyp signing.
-    pub fn with_identity(mut self, identity: NodeIdentity, signing_key: Option<[u8; 32]>) -> Self {
+    pub fn with_identity(
+        mut self,
+        identity: NodeIdentity,
+        signing_key: Option<SecretKey>,
+    ) -> Self {
         self.node_identity = Some(identity);
         self.signing_key = signing_key;
         self
@@ -199,6 +530,12 @@ impl ChitinRpcServer {
         self
     }
 
+    /// Set the network ID; announcements from other networks are refused.
+    pub fn with_network_id(mut self, network_id: Option<String>) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
     /// Set the shared epoch manager for epoch status queries.
     pub fn with_epoch_manager(mut self, em: Arc<RwLock<EpochManager>>) -> Self {
         self.epoch_manager = Some(em);
@@ -229,18 +566,269 @@ impl ChitinRpcServer {
         self
     }
 
+    /// Set the account ledger for wallet balances and transfers.
+    pub fn with_ledger(mut self, ledger: Arc<RwLock<Ledger>>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Set the stake entries for staking requests.
+    pub fn with_stake_manager(mut self, stake_manager: Arc<RwLock<StakeManager>>) -> Self {
+        self.stake_manager = Some(stake_manager);
+        self
+    }
+
+    /// Set the identity registry that maps DIDs and hotkeys to UIDs.
+    pub fn with_identity_registry(mut self, identities: Arc<RwLock<IdentityRegistry>>) -> Self {
+        self.identities = Some(identities);
+        self
+    }
+
+    /// Share a feed of newly stored polyps with another server.
+    pub fn with_polyp_feed(mut self, feed: Arc<handlers::polyp::PolypFeed>) -> Self {
+        self.polyp_feed = feed;
+        self
+    }
+
+    /// The feed of polyps this server stores, for `polyp/subscribe`.
+    pub fn polyp_feed(&self) -> Arc<handlers::polyp::PolypFeed> {
+        self.polyp_feed.clone()
+    }
+
     /// Set the hardened store for CID-based retrieval.
     pub fn with_hardened_store(mut self, hs: Option<Arc<HardenedStore>>) -> Self {
         self.hardened_store = hs;
         self
     }
 
+    /// Set the shared trust store for reputation queries.
+    pub fn with_trust_store(mut self, ts: Arc<RwLock<DomainTrustStore>>) -> Self {
+        self.trust_store = Some(ts);
+        self
+    }
+
+    /// Set the Reef Zone taxonomy used to validate submitted zones.
+    pub fn with_taxonomy(mut self, taxonomy: Arc<DomainTaxonomy>) -> Self {
+        self.taxonomy = Some(taxonomy);
+        self
+    }
+
+    /// Set the near-duplicate detector submissions are checked against.
+    pub fn with_duplicate_detector(mut self, detector: Arc<RwLock<DuplicateDetector>>) -> Self {
+        self.duplicates = Some(detector);
+        self
+    }
+
+    /// Set the content policies submissions must pass (None disables
+    /// moderation).
+    pub fn with_moderation(mut self, policies: Option<Arc<PolicySet>>) -> Self {
+        self.moderation = policies;
+        self
+    }
+
+    /// Set the scoring task assignment score submissions must follow.
+    pub fn with_task_assignment(mut self, config: AssignmentConfig) -> Self {
+        self.task_assignment = Some(config);
+        self
+    }
+
+    /// Set the weight of creator trust in search ranking (0.0 disables it).
+    pub fn with_search_trust_weight(mut self, weight: f64) -> Self {
+        self.search_trust_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Diversify search results by maximal marginal relevance with
+    /// `lambda` unless a query sets its own (`None` disables it).
+    pub fn with_search_mmr_lambda(mut self, lambda: Option<f64>) -> Self {
+        self.search_mmr_lambda = lambda.map(|l| l.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Rescore the top search candidates with `reranking`'s cross-encoder
+    /// when a query asks for it (or by default, if it is enabled).
+    pub fn with_reranking(mut self, reranking: handlers::query::Reranking) -> Self {
+        self.reranking = Some(reranking);
+        self
+    }
+
+    /// Serve repeated searches from `cache`, dropping entries as Polyps are
+    /// indexed.
+    pub fn with_query_cache(mut self, cache: Arc<QueryCache>) -> Self {
+        self.query_cache = Some(cache);
+        self
+    }
+
     /// Set the daemon start time for uptime calculation.
     pub fn with_start_time(mut self, st: Instant) -> Self {
         self.start_time = Some(st);
         self
     }
 
+    /// Set the shards this node holds. Relayed polyps outside them are dropped.
+    pub fn with_shard_set(mut self, shards: ShardSet) -> Self {
+        self.shard_set = Some(shards);
+        self
+    }
+
+    /// Set the callback that forwards queries for shards not held locally.
+    pub fn with_shard_proxy(mut self, proxy: ShardProxyCallback) -> Self {
+        self.shard_proxy = Some(proxy);
+        self
+    }
+
+    /// Set the ingestion throttle shared with pull-sync. Relayed polyps
+    /// (`peer/receive_polyp`) wait for a permit and are paced with sync.
+    pub fn with_sync_throttle(mut self, throttle: Arc<SyncThrottle>) -> Self {
+        self.sync_throttle = Some(throttle);
+        self
+    }
+
+    /// Refuse Polyp submissions and relayed polyps, with a retry-after
+    /// delay, while `admission` finds the store under write pressure.
+    pub fn with_admission(mut self, admission: Arc<AdmissionControl>) -> Self {
+        self.admission = Some(admission);
+        self
+    }
+
+    /// Set how requests to coldkey- and hotkey-signed methods are
+    /// authorized.
+    pub fn with_request_auth(mut self, config: RequestAuthConfig) -> Self {
+        self.request_auth = Arc::new(RequestAuth::new(config));
+        self
+    }
+
+    /// Set the sync metrics reported by `sync/status`.
+    pub fn with_sync_metrics(mut self, metrics: Arc<SyncMetrics>) -> Self {
+        self.sync_metrics = Some(metrics);
+        self
+    }
+
+    /// Set the model version registry consulted by `polyp/submit`.
+    pub fn with_model_registry(mut self, registry: Arc<RwLock<VersionRegistry>>) -> Self {
+        self.model_registry = Some(registry);
+        self
+    }
+
+    /// Set the callback that reloads the daemon configuration.
+    pub fn with_config_reload(mut self, reload: ConfigReloadCallback) -> Self {
+        self.config_reload = Some(reload);
+        self
+    }
+
+    /// Set the callbacks serving `admin/config` and `admin/config/update`.
+    pub fn with_config_access(
+        mut self,
+        view: ConfigViewCallback,
+        update: ConfigUpdateCallback,
+    ) -> Self {
+        self.config_view = Some(view);
+        self.config_update = Some(update);
+        self
+    }
+
+    /// Set the callback serving `admin/logs` from the daemon's log buffer.
+    pub fn with_log_query(mut self, logs: LogQueryCallback) -> Self {
+        self.log_query = Some(logs);
+        self
+    }
+
+    /// Set the callback that backs up the node's databases.
+    pub fn with_backup(mut self, backup: BackupCallback) -> Self {
+        self.backup = Some(backup);
+        self
+    }
+
+    /// Set the callback that writes bootstrap snapshots.
+    pub fn with_snapshot(mut self, snapshot: SnapshotCallback) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
+    /// Set the callback listing background tasks for `admin/tasks`.
+    pub fn with_task_list(mut self, tasks: TaskListCallback) -> Self {
+        self.task_list = Some(tasks);
+        self
+    }
+
+    /// Set the callback that embeds content submitted without a vector, and
+    /// query text for `query/search` and `query/hybrid`. Without one, both
+    /// fall back to the hash embedding.
+    pub fn with_embedder(mut self, embedder: EmbedCallback) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Set the callback that fetches and chunks URLs for `polyp/ingest_url`.
+    pub fn with_ingester(mut self, ingester: IngestCallback) -> Self {
+        self.ingester = Some(ingester);
+        self
+    }
+
+    /// Set the callback listing known peers for `peer/discover`. Without
+    /// one, the configured peer URLs are returned.
+    pub fn with_peer_directory(mut self, directory: PeerDirectoryCallback) -> Self {
+        self.peer_directory = Some(directory);
+        self
+    }
+
+    /// Set the callback listing peers for `node/peers`. Without one, the
+    /// configured peer URLs are returned.
+    pub fn with_peer_list(mut self, list: PeerListCallback) -> Self {
+        self.peer_list = Some(list);
+        self
+    }
+
+    /// Set the callback listing identity conflicts for `node/health`.
+    pub fn with_identity_conflicts(mut self, conflicts: IdentityConflictsCallback) -> Self {
+        self.identity_conflicts = Some(conflicts);
+        self
+    }
+
+    /// Set the callback reporting the storage scrubber's findings for
+    /// `node/health`.
+    pub fn with_scrub_report(mut self, report: ScrubReportCallback) -> Self {
+        self.scrub_report = Some(report);
+        self
+    }
+
+    /// Set the callbacks serving `replication/state`, `replication/status`,
+    /// and `admin/promote`, and reporting whether this node may sign. A node
+    /// that may not sign rejects submissions and serves unsigned state
+    /// updates.
+    pub fn with_replication(
+        mut self,
+        state: ReplicationStateCallback,
+        status: ReplicationStatusCallback,
+        promote: PromoteCallback,
+        signing_allowed: SigningAllowedCallback,
+    ) -> Self {
+        self.replication_state = Some(state);
+        self.replication_status = Some(status);
+        self.promote = Some(promote);
+        self.signing_allowed = Some(signing_allowed);
+        self
+    }
+
+    /// Set the callback notified of each accepted `peer/announce`.
+    pub fn with_announce_callback(mut self, callback: AnnounceCallback) -> Self {
+        self.announce_callback = Some(callback);
+        self
+    }
+
+    /// Mark this node as archival: missing history is reported as not
+    /// found rather than pruned.
+    pub fn with_archival(mut self, archival: bool) -> Self {
+        self.archival = archival;
+        self
+    }
+
+    /// Serve only `methods`; other methods are rejected.
+    pub fn with_allowed_methods(mut self, methods: &'static [&'static str]) -> Self {
+        self.allowed_methods = Some(methods);
+        self
+    }
+
     /// Start the RPC server and listen for requests.
     ///
     /// This binds to the configured address and serves requests until
@@ -250,36 +838,91 @@ impl ChitinRpcServer {
 
         tracing::info!("Chitin RPC server starting on {}", addr);
 
-        let service = ChitinServiceImpl {
+        let service = tonic::service::interceptor::InterceptedService::new(
+            ChitinJsonRpcServer::new(self.service()),
+            middleware::logging_interceptor,
+        );
+        // Peers and the CLI POST JSON to the endpoint root rather than to a
+        // gRPC method path, so every other path falls back to the service.
+        let routes = tonic::service::Routes::new(service.clone())
+            .into_axum_router()
+            .fallback_service(service);
+
+        Server::builder()
+            .accept_http1(true)
+            .add_routes(routes.into())
+            .serve(addr)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handle `request` in-process, as the server would over HTTP for a
+    /// local caller, without binding a listener.
+    pub async fn call(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        self.service().dispatch(request, None).await
+    }
+
+    fn service(&self) -> ChitinServiceImpl {
+        ChitinServiceImpl {
             store: self.store.clone(),
             index: self.index.clone(),
             gossip_callback: self.gossip_callback.clone(),
             peer_count: self.peer_count,
             peer_urls: self.peer_urls.clone(),
             node_identity: self.node_identity.clone(),
-            signing_key: self.signing_key,
+            signing_key: self.signing_key.clone(),
             self_url: self.self_url.clone(),
+            network_id: self.network_id.clone(),
             epoch_manager: self.epoch_manager.clone(),
             last_consensus_result: self.last_consensus_result.clone(),
             weight_matrix: self.weight_matrix.clone(),
             bond_matrix: self.bond_matrix.clone(),
             metagraph_manager: self.metagraph_manager.clone(),
+            ledger: self.ledger.clone(),
+            stake_manager: self.stake_manager.clone(),
+            identities: self.identities.clone(),
+            polyp_feed: self.polyp_feed.clone(),
             hardened_store: self.hardened_store.clone(),
+            trust_store: self.trust_store.clone(),
+            taxonomy: self.taxonomy.clone(),
+            duplicates: self.duplicates.clone(),
+            moderation: self.moderation.clone(),
+            task_assignment: self.task_assignment.clone(),
+            search_trust_weight: self.search_trust_weight,
+            search_mmr_lambda: self.search_mmr_lambda,
+            reranking: self.reranking.clone(),
+            query_cache: self.query_cache.clone(),
+            scrolls: self.scrolls.clone(),
             start_time: self.start_time,
-        };
-
-        Server::builder()
-            .accept_http1(true)
-            .add_service(
-                tonic::service::interceptor::InterceptedService::new(
-                    ChitinJsonRpcServer::new(service),
-                    middleware::logging_interceptor,
-                ),
-            )
-            .serve(addr)
-            .await?;
-
-        Ok(())
+            shard_set: self.shard_set.clone(),
+            shard_proxy: self.shard_proxy.clone(),
+            sync_throttle: self.sync_throttle.clone(),
+            admission: self.admission.clone(),
+            request_auth: self.request_auth.clone(),
+            sync_metrics: self.sync_metrics.clone(),
+            model_registry: self.model_registry.clone(),
+            config_reload: self.config_reload.clone(),
+            config_view: self.config_view.clone(),
+            config_update: self.config_update.clone(),
+            log_query: self.log_query.clone(),
+            backup: self.backup.clone(),
+            snapshot: self.snapshot.clone(),
+            task_list: self.task_list.clone(),
+            embedder: self.embedder.clone(),
+            ingester: self.ingester.clone(),
+            peer_directory: self.peer_directory.clone(),
+            peer_list: self.peer_list.clone(),
+            identity_conflicts: self.identity_conflicts.clone(),
+            scrub_report: self.scrub_report.clone(),
+            replication_state: self.replication_state.clone(),
+            replication_status: self.replication_status.clone(),
+            promote: self.promote.clone(),
+            signing_allowed: self.signing_allowed.clone(),
+            announce_callback: self.announce_callback.clone(),
+            allowed_methods: self.allowed_methods,
+            archival: self.archival,
+        }
     }
 }
 
@@ -300,120 +943,697 @@ struct ChitinServiceImpl {
     peer_urls: Vec<String>,
     /// Node identity for provenance and announce responses (Phase 2).
     node_identity: Option<NodeIdentity>,
-    /// Signing key for polyp signing (Phase 2).
-    signing_key: Option<[u8; 32]>,
+    /// Signing key for polyp signing (Phase 2), wiped on drop.
+    signing_key: Option<SecretKey>,
     /// This node's publicly reachable URL.
     self_url: Option<String>,
+    /// Network ID exchanged in `peer/announce`.
+    network_id: Option<String>,
     // Phase 4: Shared consensus/epoch state
     epoch_manager: Option<Arc<RwLock<EpochManager>>>,
     last_consensus_result: Option<Arc<RwLock<Option<ConsensusResult>>>>,
     weight_matrix: Option<Arc<RwLock<WeightMatrix>>>,
     bond_matrix: Option<Arc<RwLock<BondMatrix>>>,
     metagraph_manager: Option<Arc<RwLock<MetagraphManager>>>,
+    ledger: Option<Arc<RwLock<Ledger>>>,
+    stake_manager: Option<Arc<RwLock<StakeManager>>>,
+    identities: Option<Arc<RwLock<IdentityRegistry>>>,
+    polyp_feed: Arc<handlers::polyp::PolypFeed>,
     hardened_store: Option<Arc<HardenedStore>>,
+    trust_store: Option<Arc<RwLock<DomainTrustStore>>>,
+    taxonomy: Option<Arc<DomainTaxonomy>>,
+    duplicates: Option<Arc<RwLock<DuplicateDetector>>>,
+    moderation: Option<Arc<PolicySet>>,
+    task_assignment: Option<AssignmentConfig>,
+    search_trust_weight: f64,
+    search_mmr_lambda: Option<f64>,
+    reranking: Option<handlers::query::Reranking>,
+    query_cache: Option<Arc<QueryCache>>,
+    scrolls: Arc<handlers::query::ScrollSessions>,
     start_time: Option<Instant>,
+    shard_set: Option<ShardSet>,
+    shard_proxy: Option<ShardProxyCallback>,
+    sync_throttle: Option<Arc<SyncThrottle>>,
+    admission: Option<Arc<AdmissionControl>>,
+    request_auth: Arc<RequestAuth>,
+    sync_metrics: Option<Arc<SyncMetrics>>,
+    model_registry: Option<Arc<RwLock<VersionRegistry>>>,
+    config_reload: Option<ConfigReloadCallback>,
+    config_view: Option<ConfigViewCallback>,
+    config_update: Option<ConfigUpdateCallback>,
+    log_query: Option<LogQueryCallback>,
+    backup: Option<BackupCallback>,
+    snapshot: Option<SnapshotCallback>,
+    task_list: Option<TaskListCallback>,
+    embedder: Option<EmbedCallback>,
+    ingester: Option<IngestCallback>,
+    peer_directory: Option<PeerDirectoryCallback>,
+    peer_list: Option<PeerListCallback>,
+    identity_conflicts: Option<IdentityConflictsCallback>,
+    scrub_report: Option<ScrubReportCallback>,
+    replication_state: Option<ReplicationStateCallback>,
+    replication_status: Option<ReplicationStatusCallback>,
+    promote: Option<PromoteCallback>,
+    signing_allowed: Option<SigningAllowedCallback>,
+    announce_callback: Option<AnnounceCallback>,
+    allowed_methods: Option<&'static [&'static str]>,
+    archival: bool,
 }
 
 impl ChitinServiceImpl {
-    /// Dispatch a JSON-RPC request to the appropriate handler based on the method name.
-    async fn dispatch(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        let result = match request.method.as_str() {
-            // Polyp Management
-            "polyp/submit" => {
-                let store = self.store.clone();
-                let index = self.index.clone();
-                let gossip_cb = self.gossip_callback.clone();
-                let identity = self.node_identity.clone();
-                let sign_key = self.signing_key;
-                let req: Result<handlers::polyp::SubmitPolypRequest, _> =
-                    serde_json::from_value(request.params);
-                match req {
-                    Ok(r) => {
-                        match handlers::polyp::handle_submit_polyp_with_identity(
-                            &store,
-                            &index,
-                            r,
-                            identity.as_ref(),
-                            sign_key.as_ref(),
-                        ).await {
-                            Ok(resp) => {
-                                // Trigger gossip broadcast if callback is set.
-                                if let Some(cb) = gossip_cb {
-                                    if let Ok(Some(polyp)) = chitin_core::traits::PolypStore::get_polyp(
-                                        store.as_ref(),
-                                        &resp.polyp_id,
-                                    )
-                                    .await
-                                    {
-                                        cb(polyp);
-                                    }
-                                }
-                                serde_json::to_value(resp)
-                                    .map_err(|e| format!("Failed to serialize response: {}", e))
-                            }
-                            Err(e) => Err(e),
-                        }
-                    }
-                    Err(e) => Err(format!("Failed to deserialize request: {}", e)),
-                }
-            }
-            "polyp/get" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    async move { handlers::polyp::handle_get_polyp(&store, r).await }
-                })
-                .await
-            }
-            "polyp/list" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    async move { handlers::polyp::handle_list_polyps(&store, r).await }
-                })
-                .await
-            }
-            "polyp/state" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    async move { handlers::polyp::handle_get_polyp_state(&store, r).await }
-                })
-                .await
-            }
-            "polyp/provenance" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    async move { handlers::polyp::handle_get_polyp_provenance(&store, r).await }
-                })
-                .await
-            }
-            "polyp/hardening" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    async move { handlers::polyp::handle_get_hardening_receipt(&store, r).await }
-                })
-                .await
+    /// Shard routing, if this node holds only some shards and can proxy.
+    fn shard_routing(&self) -> Option<ShardRouting> {
+        match (&self.shard_set, &self.shard_proxy) {
+            (Some(shards), Some(proxy)) if !shards.is_full() => Some(ShardRouting {
+                shards: shards.clone(),
+                proxy: proxy.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The current epoch (0 without an epoch manager).
+    async fn current_epoch(&self) -> u64 {
+        match &self.epoch_manager {
+            Some(em) => em.read().await.current_epoch(),
+            None => 0,
+        }
+    }
+
+    /// Whether this node may sign (false on a standby).
+    fn may_sign(&self) -> bool {
+        self.signing_allowed.as_ref().is_none_or(|allowed| allowed())
+    }
+
+    /// A snapshot of the model version registry, if one is attached.
+    async fn model_versions(&self) -> Option<VersionRegistry> {
+        match &self.model_registry {
+            Some(registry) => Some(registry.read().await.clone()),
+            None => None,
+        }
+    }
+
+    /// Embed, persist, index, and gossip a new polyp.
+    async fn submit_polyp(
+        &self,
+        mut request: handlers::polyp::SubmitPolypRequest,
+    ) -> Result<handlers::polyp::SubmitPolypResponse, String> {
+        if !self.may_sign() {
+            return Err("Standby node: submit polyps to the primary".to_string());
+        }
+        self.attach_embedding(&mut request).await?;
+        let models = self.model_versions().await;
+        let epoch = self.current_epoch().await;
+        let mut resp = handlers::polyp::handle_submit_polyp_with_identity(
+            &self.store,
+            &self.index,
+            request,
+            self.node_identity.as_ref(),
+            self.signing_key.as_deref(),
+            self.taxonomy.as_deref(),
+            models.as_ref().map(|registry| (registry, epoch)),
+        )
+        .await?;
+        let rejection = self.moderate(&resp.polyp_id).await;
+        self.forget_cached_searches(&resp.polyp_id).await;
+        if let Some(record) = rejection {
+            resp.state = "Rejected".to_string();
+            resp.message = format!("Polyp rejected ({}): {}", record.code, record.reason);
+            resp.rejection = Some(record);
+            return Ok(resp);
+        }
+        resp.duplicate_of = self.flag_duplicate(&resp.polyp_id).await;
+        self.polyp_feed.publish(resp.polyp_id, "submitted");
+
+        // Trigger gossip broadcast if callback is set.
+        if let Some(cb) = &self.gossip_callback {
+            if let Ok(Some(polyp)) =
+                chitin_core::traits::PolypStore::get_polyp(self.store.as_ref(), &resp.polyp_id)
+                    .await
+            {
+                cb(polyp);
             }
+        }
+        Ok(resp)
+    }
 
-            // Query / Retrieval
-            "query/search" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    let index = self.index.clone();
-                    async move { handlers::query::handle_semantic_search(&store, &index, r).await }
-                })
-                .await
+    /// Drop cached searches that the just-(un)indexed polyp could change.
+    async fn forget_cached_searches(&self, polyp_id: &uuid::Uuid) {
+        let Some(cache) = &self.query_cache else {
+            return;
+        };
+        if let Ok(Some(polyp)) =
+            chitin_core::traits::PolypStore::get_polyp(self.store.as_ref(), polyp_id).await
+        {
+            cache.invalidate_polyp(&polyp);
+        }
+    }
+
+    /// Review a just-submitted polyp against the moderation policies. A
+    /// rejected polyp is taken out of the index and kept only as Rejected,
+    /// with its moderation record, for `polyp/state`.
+    async fn moderate(&self, polyp_id: &uuid::Uuid) -> Option<moderation::ModerationRecord> {
+        let policies = self.moderation.as_ref()?;
+        let mut polyp = chitin_core::traits::PolypStore::get_polyp(self.store.as_ref(), polyp_id)
+            .await
+            .ok()
+            .flatten()?;
+        let record = match policies.moderate(&polyp, ModerationStage::Submission).await {
+            Ok(record) => record?,
+            Err(e) => {
+                tracing::warn!("Moderation of polyp {} failed, allowing: {}", polyp_id, e);
+                return None;
             }
-            "query/hybrid" => {
-                dispatch_handler(request.params, |r| {
-                    let store = self.store.clone();
-                    let index = self.index.clone();
-                    async move { handlers::query::handle_hybrid_search(&store, &index, r).await }
+        };
+        tracing::info!(
+            "Polyp {} rejected on submission by {} ({}): {}",
+            polyp_id,
+            record.policy,
+            record.code,
+            record.reason
+        );
+        if let Err(e) = moderation::save_record(&self.store, polyp_id, &record) {
+            tracing::warn!("Failed to record rejection of polyp {}: {}", polyp_id, e);
+        }
+        polyp.state = chitin_core::PolypState::Rejected;
+        polyp.updated_at = chrono::Utc::now();
+        if let Err(e) =
+            chitin_core::traits::PolypStore::save_polyp(self.store.as_ref(), &polyp).await
+        {
+            tracing::warn!("Failed to reject polyp {}: {}", polyp_id, e);
+        }
+        let unindexed = chitin_core::traits::VectorIndex::delete(self.index.as_ref(), polyp_id);
+        if let Err(e) = unindexed.await {
+            tracing::warn!("Failed to unindex rejected polyp {}: {}", polyp_id, e);
+        }
+        Some(record)
+    }
+
+    /// The scoring assignment for `epoch`, if one is configured and the
+    /// metagraph lists validators.
+    async fn task_assignment(&self, epoch: u64) -> Result<Option<TaskAssignment>, String> {
+        let Some(config) = &self.task_assignment else {
+            return Ok(None);
+        };
+        let metagraph = match &self.metagraph_manager {
+            Some(mm) => mm.read().await.current().cloned(),
+            None => None,
+        };
+        TaskAssignment::for_epoch(config, &self.store, metagraph.as_ref(), epoch)
+            .map_err(|e| format!("Failed to compute the scoring assignment: {}", e))
+    }
+
+    /// Check a stored polyp against the hardened polyps, flagging it in the
+    /// store if it nearly duplicates one. Submission goes ahead either way.
+    async fn flag_duplicate(&self, polyp_id: &uuid::Uuid) -> Option<DuplicateMatch> {
+        let detector = self.duplicates.as_ref()?.read().await;
+        if !detector.config().enabled || detector.is_empty() {
+            return None;
+        }
+        let polyp = chitin_core::traits::PolypStore::get_polyp(self.store.as_ref(), polyp_id)
+            .await
+            .ok()
+            .flatten()?;
+        let duplicate = detector.check(&polyp)?;
+        tracing::info!(
+            "Polyp {} nearly duplicates hardened polyp {} ({:?}, similarity {:.3})",
+            polyp_id,
+            duplicate.matched_id,
+            duplicate.kind,
+            duplicate.similarity
+        );
+        if let Err(e) = dedup::save_flag(&self.store, polyp_id, &duplicate) {
+            tracing::warn!("Failed to flag duplicate polyp {}: {}", polyp_id, e);
+        }
+        Some(duplicate)
+    }
+
+    /// Submit every polyp of a batch, reporting failures per polyp so one
+    /// bad submission does not fail the rest.
+    async fn submit_polyp_batch(
+        &self,
+        request: handlers::polyp::SubmitPolypBatchRequest,
+    ) -> Result<handlers::polyp::SubmitPolypBatchResponse, String> {
+        handlers::polyp::check_submit_batch(&request)?;
+        if !self.may_sign() {
+            return Err("Standby node: submit polyps to the primary".to_string());
+        }
+
+        let mut results = Vec::with_capacity(request.polyps.len());
+        for submission in request.polyps {
+            results.push(match self.submit_polyp(submission).await {
+                Ok(resp) => handlers::polyp::BatchSubmitResult {
+                    polyp_id: Some(resp.polyp_id),
+                    error: None,
+                },
+                Err(e) => handlers::polyp::BatchSubmitResult {
+                    polyp_id: None,
+                    error: Some(e),
+                },
+            });
+        }
+        Ok(handlers::polyp::SubmitPolypBatchResponse { results })
+    }
+
+    /// Store complete Polyps exported from another node, moderating them
+    /// like submissions and announcing the accepted ones on the polyp feed.
+    async fn import_polyps(
+        &self,
+        request: handlers::polyp::ImportPolypsRequest,
+    ) -> Result<handlers::polyp::ImportPolypsResponse, String> {
+        if !self.may_sign() {
+            return Err("Standby node: import polyps into the primary".to_string());
+        }
+        let mut resp = handlers::polyp::handle_import_polyps(
+            &self.store,
+            &self.index,
+            request,
+            self.shard_set.as_ref(),
+        )
+        .await?;
+        for result in resp.results.iter_mut().filter(|r| r.imported) {
+            let rejection = self.moderate(&result.polyp_id).await;
+            self.forget_cached_searches(&result.polyp_id).await;
+            if let Some(record) = rejection {
+                result.imported = false;
+                result.error = Some(format!("Polyp rejected ({}): {}", record.code, record.reason));
+                continue;
+            }
+            self.polyp_feed.publish(result.polyp_id, "import");
+        }
+        Ok(resp)
+    }
+
+    /// Fetch, extract, and chunk a URL through the daemon's ingestion
+    /// service, then submit each chunk as a polyp.
+    async fn ingest_url(
+        &self,
+        request: handlers::polyp::IngestUrlRequest,
+    ) -> Result<handlers::polyp::IngestUrlResponse, String> {
+        let ingest = self
+            .ingester
+            .as_ref()
+            .ok_or_else(|| "URL ingestion is not enabled on this node".to_string())?;
+        let document = ingest(request.clone()).await?;
+
+        let mut polyp_ids = Vec::with_capacity(document.chunks.len());
+        for submission in handlers::polyp::chunk_submissions(&request, &document) {
+            polyp_ids.push(self.submit_polyp(submission).await?.polyp_id);
+        }
+        Ok(handlers::polyp::IngestUrlResponse {
+            url: request.url,
+            title: document.title,
+            polyp_ids,
+        })
+    }
+
+    /// Embed a submission that arrived without a vector, if an embedder is
+    /// attached.
+    async fn attach_embedding(
+        &self,
+        request: &mut handlers::polyp::SubmitPolypRequest,
+    ) -> Result<(), String> {
+        let embed = match (&request.vector, &self.embedder) {
+            (None, Some(embed)) => embed,
+            _ => return Ok(()),
+        };
+        let embedded = embed(request.content.clone())
+            .await
+            .map_err(|e| format!("Failed to embed content: {}", e))?;
+        request.vector = Some(embedded.values);
+        request.model_id = Some(embedded.model_id);
+        Ok(())
+    }
+
+    /// Embed query text with the attached embedder (the model the node
+    /// embeds submissions with), or None without one.
+    async fn embed_query(
+        &self,
+        text: &str,
+    ) -> Result<Option<handlers::polyp::EmbeddedContent>, String> {
+        let Some(embed) = &self.embedder else {
+            return Ok(None);
+        };
+        embed(text.to_string())
+            .await
+            .map(Some)
+            .map_err(|e| format!("Failed to embed query: {}", e))
+    }
+
+    /// Semantic search. Query text without a vector is embedded here when
+    /// an embedder is attached, so queries land in the same model space as
+    /// submissions; otherwise the handler falls back to the hash embedding.
+    /// With a rerank stage attached, the merged results are reranked here.
+    /// With a query cache attached, repeated queries are answered from it.
+    async fn semantic_search(
+        &self,
+        mut request: handlers::query::SemanticSearchRequest,
+    ) -> Result<handlers::query::SemanticSearchResponse, String> {
+        self.prepare_search(&mut request).await?;
+        let cached = self
+            .query_cache
+            .as_ref()
+            .and_then(|cache| Some((cache, QueryKey::of(&request)?)));
+        if let Some((cache, key)) = &cached {
+            if let Some(response) = cache.get(key) {
+                return Ok(response);
+            }
+        }
+        // Without cross-model search, a query in an indexed model space only
+        // sees that space.
+        let scope = cached.is_some().then(|| QueryScope {
+            model_space: request.model_id.clone().filter(|m| {
+                request.cross_model == Some(false) && self.index.model_spaces().contains_key(m)
+            }),
+            reef_zone: request.reef_zone.clone(),
+        });
+        let planned = self.rerank_plan(&request).is_some();
+        let response = self.ranked_search(request).await?;
+        // A rerank that failed or ran out of time is retried next time.
+        if let (Some((cache, key)), Some(scope)) = (cached, scope) {
+            if !planned || response.reranked {
+                cache.insert(key, response.clone(), scope);
+            }
+        }
+        Ok(response)
+    }
+
+    /// Embed the query text of `request` if it has no vector, and apply the
+    /// server's default MMR lambda.
+    async fn prepare_search(
+        &self,
+        request: &mut handlers::query::SemanticSearchRequest,
+    ) -> Result<(), String> {
+        if let (None, Some(text)) = (&request.query_vector, &request.query_text) {
+            if let Some(embedded) = self.embed_query(text).await? {
+                request.query_vector = Some(embedded.values);
+                request.model_id = Some(embedded.model_id);
+            }
+        }
+        request.mmr_lambda = request.mmr_lambda.or(self.search_mmr_lambda);
+        Ok(())
+    }
+
+    /// The rerank stage `request` gets, if any.
+    fn rerank_plan(
+        &self,
+        request: &handlers::query::SemanticSearchRequest,
+    ) -> Option<(&handlers::query::Reranking, handlers::query::RerankPlan)> {
+        let reranking = self.reranking.as_ref()?;
+        let plan = reranking.plan(
+            request.query_text.as_deref(),
+            request.rerank,
+            request.rerank_top_n,
+            request.rerank_budget_ms,
+        )?;
+        Some((reranking, plan))
+    }
+
+    /// Run a prepared search across shards, ranked and (if asked) reranked.
+    async fn ranked_search(
+        &self,
+        mut request: handlers::query::SemanticSearchRequest,
+    ) -> Result<handlers::query::SemanticSearchResponse, String> {
+        let ranking = self.reputation_ranking();
+        let routing = self.shard_routing();
+
+        // Fetch enough candidates for the rerank stage, then cut to top_k.
+        let top_k = request.top_k.unwrap_or(10) as usize;
+        let rerank = self.rerank_plan(&request);
+        if let Some((_, plan)) = &rerank {
+            request.top_k = Some(top_k.max(plan.top_n) as u32);
+        }
+        let mut response = handlers::query::handle_sharded_search(
+            &self.store,
+            &self.index,
+            request,
+            ranking.as_ref(),
+            routing.as_ref(),
+        )
+        .await?;
+        if let Some((reranking, plan)) = rerank {
+            (response.results, response.reranked) =
+                reranking.apply(&plan, response.results, top_k).await;
+        }
+        Ok(response)
+    }
+
+    /// Facet counts, embedding the query text like `semantic_search`.
+    async fn facets(
+        &self,
+        mut request: handlers::query::FacetsRequest,
+    ) -> Result<handlers::query::FacetsResponse, String> {
+        if let (None, Some(text)) = (&request.query_vector, &request.query_text) {
+            if let Some(embedded) = self.embed_query(text).await? {
+                request.query_vector = Some(embedded.values);
+                request.model_id = Some(embedded.model_id);
+            }
+        }
+        handlers::query::handle_facets(&self.store, &self.index, request).await
+    }
+
+    /// Page through ranked search results: the first call runs the search
+    /// once, uncached, for up to `max_results` results; later calls read the
+    /// held results by cursor.
+    async fn scroll_search(
+        &self,
+        request: handlers::query::ScrollSearchRequest,
+    ) -> Result<handlers::query::ScrollSearchResponse, String> {
+        let page_size = handlers::query::scroll_page_size(&request)?;
+        if let Some(cursor) = &request.cursor {
+            return self.scrolls.page(cursor, page_size);
+        }
+        let mut search = request
+            .search
+            .ok_or_else(|| "Either cursor or search must be provided".to_string())?;
+        search.top_k = Some(
+            request
+                .max_results
+                .unwrap_or(handlers::query::DEFAULT_SCROLL_RESULTS)
+                .clamp(1, handlers::query::MAX_SCROLL_RESULTS),
+        );
+        self.prepare_search(&mut search).await?;
+        let response = self.ranked_search(search).await?;
+        Ok(self.scrolls.open(response, page_size))
+    }
+
+    /// Context pack assembly, embedding the query text like
+    /// `semantic_search`.
+    async fn get_context(
+        &self,
+        mut request: handlers::query::GetContextRequest,
+    ) -> Result<handlers::query::GetContextResponse, String> {
+        if let (None, Some(text)) = (&request.query_vector, &request.query_text) {
+            if let Some(embedded) = self.embed_query(text).await? {
+                request.query_vector = Some(embedded.values);
+                request.model_id = Some(embedded.model_id);
+            }
+        }
+        request.mmr_lambda = request.mmr_lambda.or(self.search_mmr_lambda);
+        let ranking = self.reputation_ranking();
+        let routing = self.shard_routing();
+        handlers::query::handle_get_context(
+            &self.store,
+            &self.index,
+            request,
+            ranking.as_ref(),
+            routing.as_ref(),
+            self.reranking.as_ref(),
+        )
+        .await
+    }
+
+    /// Hybrid search, embedding the query text like `semantic_search`.
+    async fn hybrid_search(
+        &self,
+        mut request: handlers::query::HybridSearchRequest,
+    ) -> Result<handlers::query::HybridSearchResponse, String> {
+        if request.query_vector.is_none() {
+            if let Some(embedded) = self.embed_query(&request.query_text).await? {
+                request.query_vector = Some(embedded.values);
+                request.model_id = Some(embedded.model_id);
+            }
+        }
+        let ranking = self.reputation_ranking();
+        handlers::query::handle_hybrid_search(&self.store, &self.index, request, ranking.as_ref())
+            .await
+    }
+
+    /// Reputation inputs for search ranking, if a trust store is attached.
+    fn reputation_ranking(&self) -> Option<handlers::query::ReputationRanking> {
+        self.trust_store
+            .as_ref()
+            .map(|ts| handlers::query::ReputationRanking {
+                trust_store: ts.clone(),
+                identities: self.identities.clone(),
+                metagraph_manager: self.metagraph_manager.clone(),
+                trust_weight: self.search_trust_weight,
+            })
+    }
+
+    /// Dispatch a JSON-RPC request to the appropriate handler based on the method name.
+    /// `remote` is the caller's address, or `None` for an in-process call.
+    async fn dispatch(
+        &self,
+        mut request: JsonRpcRequest,
+        remote: Option<SocketAddr>,
+    ) -> JsonRpcResponse {
+        if request.method.starts_with(ADMIN_PREFIX)
+            && remote.is_some_and(|addr| !addr.ip().to_canonical().is_loopback())
+        {
+            return JsonRpcResponse {
+                success: false,
+                result: None,
+                error: Some(format!(
+                    "Method {} is only served to callers on this host",
+                    request.method
+                )),
+                retry_after_ms: None,
+            };
+        }
+        if let Some(allowed) = self.allowed_methods {
+            if !allowed.contains(&request.method.as_str()) {
+                return JsonRpcResponse {
+                    success: false,
+                    result: None,
+                    error: Some(format!("Method {} is not served by this node", request.method)),
+                    retry_after_ms: None,
+                };
+            }
+        }
+        let params = std::mem::take(&mut request.params);
+        request.params = match self
+            .request_auth
+            .authorize(&request.method, params, self.identities.as_ref())
+            .await
+        {
+            Ok(params) => params,
+            Err(e) => {
+                return JsonRpcResponse {
+                    success: false,
+                    result: None,
+                    error: Some(e),
+                    retry_after_ms: None,
+                };
+            }
+        };
+        if let Some(admission) = &self.admission {
+            if ADMITTED_METHODS.contains(&request.method.as_str()) {
+                if let Err(throttled) = admission.admit() {
+                    return JsonRpcResponse {
+                        success: false,
+                        result: None,
+                        error: Some(throttled.to_string()),
+                        retry_after_ms: Some(throttled.retry_after.as_millis() as u64),
+                    };
+                }
+            }
+        }
+        let result = match request.method.as_str() {
+            // Polyp Management
+            "polyp/submit" => {
+                dispatch_handler(request.params, |r| self.submit_polyp(r)).await
+            }
+            "polyp/submit_batch" => {
+                dispatch_handler(request.params, |r| self.submit_polyp_batch(r)).await
+            }
+            "polyp/ingest_url" => {
+                dispatch_handler(request.params, |r| self.ingest_url(r)).await
+            }
+            "polyp/get" => {
+                let routing = self.shard_routing();
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::polyp::handle_get_polyp_routed(&store, r, routing.as_ref()).await
+                    }
+                })
+                .await
+            }
+            "polyp/list" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_list_polyps(&store, r).await }
+                })
+                .await
+            }
+            "polyp/counts" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_count_polyps(&store, r).await }
+                })
+                .await
+            }
+            "polyp/state" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_get_polyp_state(&store, r).await }
                 })
                 .await
             }
+            "polyp/provenance" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_get_polyp_provenance(&store, r).await }
+                })
+                .await
+            }
+            "polyp/verify_provenance" => {
+                let hardened_store = self.hardened_store.clone();
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        let ipfs = hardened_store.as_ref().map(|h| &h.ipfs);
+                        handlers::polyp::handle_verify_provenance(&store, ipfs, r).await
+                    }
+                })
+                .await
+            }
+            "polyp/hardening" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_get_hardening_receipt(&store, r).await }
+                })
+                .await
+            }
+            "polyp/lineage" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::polyp::handle_get_lineage(&store, r).await }
+                })
+                .await
+            }
+            "polyp/subscribe" => {
+                dispatch_handler(request.params, |r| {
+                    let feed = self.polyp_feed.clone();
+                    async move { handlers::polyp::handle_subscribe_polyps(&feed, r).await }
+                })
+                .await
+            }
+
+            // Query / Retrieval
+            "query/search" => {
+                dispatch_handler(request.params, |r| self.semantic_search(r)).await
+            }
+            "query/facets" => {
+                dispatch_handler(request.params, |r| self.facets(r)).await
+            }
+            "query/scroll" => {
+                dispatch_handler(request.params, |r| self.scroll_search(r)).await
+            }
+            "query/hybrid" => {
+                dispatch_handler(request.params, |r| self.hybrid_search(r)).await
+            }
+            "query/context" => {
+                dispatch_handler(request.params, |r| self.get_context(r)).await
+            }
             "query/cid" => {
                 let hardened_store = self.hardened_store.clone();
                 dispatch_handler(request.params, |r| {
-                    async move { handlers::query::handle_get_by_cid(hardened_store.as_ref(), r).await }
+                    let store = self.store.clone();
+                    async move {
+                        handlers::query::handle_get_by_cid(hardened_store.as_ref(), &store, r).await
+                    }
                 })
                 .await
             }
@@ -436,23 +1656,35 @@ impl ChitinServiceImpl {
             }
             "node/health" => {
                 let peer_count = self.peer_count;
+                let conflicts = self.identity_conflicts.clone();
+                let scrub = self.scrub_report.as_ref().map(|report| report());
                 dispatch_handler(request.params, |r| async move {
-                    handlers::node::handle_get_health(r, peer_count).await
+                    let conflicts = match conflicts {
+                        Some(conflicts) => conflicts().await,
+                        None => Vec::new(),
+                    };
+                    handlers::node::handle_get_health(r, peer_count, conflicts, scrub).await
                 })
                 .await
             }
             "node/peers" => {
                 let peer_urls = self.peer_urls.clone();
+                let list = self.peer_list.clone();
                 dispatch_handler(request.params, |r| async move {
-                    let peer_data: Vec<handlers::node::PeerInfo> = peer_urls
-                        .into_iter()
-                        .map(|url| handlers::node::PeerInfo {
-                            peer_id: url.clone(),
-                            address: url,
-                            node_type: None,
-                            latency_ms: None,
-                        })
-                        .collect();
+                    let peer_data: Vec<handlers::node::PeerInfo> = match list {
+                        Some(list) => list().await,
+                        None => peer_urls
+                            .into_iter()
+                            .map(|url| handlers::node::PeerInfo {
+                                peer_id: url.clone(),
+                                address: url,
+                                node_type: None,
+                                latency_ms: None,
+                                alive: None,
+                                dial: None,
+                            })
+                            .collect(),
+                    };
                     handlers::node::handle_get_peers(r, peer_data).await
                 })
                 .await
@@ -472,34 +1704,98 @@ impl ChitinServiceImpl {
                 .await
             }
             "wallet/balance" => {
+                let ledger = self.ledger.clone();
+                let sm = self.stake_manager.clone();
+                let em = self.epoch_manager.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::wallet::handle_get_balance(r).await
+                    handlers::wallet::handle_get_balance(
+                        r,
+                        ledger.as_ref(),
+                        sm.as_ref(),
+                        em.as_ref(),
+                    )
+                    .await
                 })
                 .await
             }
             "wallet/transfer" => {
+                let ledger = self.ledger.clone();
+                let sm = self.stake_manager.clone();
+                let em = self.epoch_manager.clone();
+                let store = self.store.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::wallet::handle_transfer(
+                        r,
+                        ledger.as_ref(),
+                        sm.as_ref(),
+                        em.as_ref(),
+                        &store,
+                    )
+                    .await
+                })
+                .await
+            }
+            "wallet/history" => {
+                let ledger = self.ledger.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::wallet::handle_transfer(r).await
+                    handlers::wallet::handle_get_history(r, ledger.as_ref()).await
                 })
                 .await
             }
 
             // Staking
             "staking/stake" => {
+                let ledger = self.ledger.clone();
+                let sm = self.stake_manager.clone();
+                let em = self.epoch_manager.clone();
+                let ids = self.identities.clone();
+                let store = self.store.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::staking::handle_stake(r).await
+                    handlers::staking::handle_stake(
+                        r,
+                        ledger.as_ref(),
+                        sm.as_ref(),
+                        em.as_ref(),
+                        ids.as_ref(),
+                        &store,
+                    )
+                    .await
                 })
                 .await
             }
             "staking/unstake" => {
+                let ledger = self.ledger.clone();
+                let sm = self.stake_manager.clone();
+                let em = self.epoch_manager.clone();
+                let store = self.store.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::staking::handle_unstake(r).await
+                    handlers::staking::handle_unstake(
+                        r,
+                        ledger.as_ref(),
+                        sm.as_ref(),
+                        em.as_ref(),
+                        &store,
+                    )
+                    .await
                 })
                 .await
             }
             "staking/info" => {
+                let ledger = self.ledger.clone();
+                let sm = self.stake_manager.clone();
+                let em = self.epoch_manager.clone();
+                let mm = self.metagraph_manager.clone();
+                let ids = self.identities.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::staking::handle_get_stake_info(r).await
+                    handlers::staking::handle_get_stake_info(
+                        r,
+                        ledger.as_ref(),
+                        sm.as_ref(),
+                        em.as_ref(),
+                        mm.as_ref(),
+                        ids.as_ref(),
+                    )
+                    .await
                 })
                 .await
             }
@@ -535,13 +1831,103 @@ impl ChitinServiceImpl {
                 })
                 .await
             }
+            "metagraph/snapshot" => {
+                let mm = self.metagraph_manager.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::metagraph::handle_get_metagraph_snapshot(r, mm.as_ref()).await
+                })
+                .await
+            }
+
+            // Reputation
+            "reputation/score" => {
+                let ts = self.trust_store.clone();
+                let mm = self.metagraph_manager.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::reputation::handle_get_reputation_score(r, ts.as_ref(), mm.as_ref())
+                        .await
+                })
+                .await
+            }
+            "reputation/explain" => {
+                let ts = self.trust_store.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::reputation::handle_explain_trust(r, ts.as_ref()).await
+                })
+                .await
+            }
+            "reputation/node" => {
+                let ts = self.trust_store.clone();
+                let mm = self.metagraph_manager.clone();
+                let ids = self.identities.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::reputation::handle_get_node_reputation(
+                        r,
+                        ts.as_ref(),
+                        mm.as_ref(),
+                        ids.as_ref(),
+                    )
+                    .await
+                })
+                .await
+            }
+            "reputation/top" => {
+                let ts = self.trust_store.clone();
+                let mm = self.metagraph_manager.clone();
+                let ids = self.identities.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::reputation::handle_get_top_reputation(
+                        r,
+                        ts.as_ref(),
+                        mm.as_ref(),
+                        ids.as_ref(),
+                    )
+                    .await
+                })
+                .await
+            }
+            "reputation/graph" => {
+                let ts = self.trust_store.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::reputation::handle_get_trust_graph(r, ts.as_ref()).await
+                })
+                .await
+            }
 
             // Validation
             "validation/scores" => {
                 let wm = self.weight_matrix.clone();
                 let em = self.epoch_manager.clone();
-                dispatch_handler(request.params, |r| async move {
-                    handlers::validation::handle_submit_scores(r, wm.as_ref(), em.as_ref()).await
+                let ids = self.identities.clone();
+                let store = self.store.clone();
+                dispatch_handler(request.params, |r: SubmitScoresRequest| async move {
+                    let assignment = self.task_assignment(r.epoch).await?;
+                    handlers::validation::handle_submit_scores(
+                        r,
+                        wm.as_ref(),
+                        em.as_ref(),
+                        assignment.as_ref(),
+                        ids.as_ref(),
+                        &store,
+                    )
+                    .await
+                })
+                .await
+            }
+            "validation/beacon" => {
+                let em = self.epoch_manager.clone();
+                let mm = self.metagraph_manager.clone();
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::validation::handle_beacon_contribution(
+                            r,
+                            &store,
+                            em.as_ref(),
+                            mm.as_ref(),
+                        )
+                        .await
+                    }
                 })
                 .await
             }
@@ -554,17 +1940,120 @@ impl ChitinServiceImpl {
             }
             "validation/result" => {
                 let cr = self.last_consensus_result.clone();
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::validation::handle_get_consensus_result(r, cr.as_ref(), &store)
+                            .await
+                    }
+                })
+                .await
+            }
+
+            // History
+            "history/status" => {
+                let archival = self.archival;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::history::handle_history_status(&store, r, archival).await
+                    }
+                })
+                .await
+            }
+            "history/consensus" => {
+                let archival = self.archival;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::history::handle_consensus_history(&store, r, archival).await
+                    }
+                })
+                .await
+            }
+            "history/hardening_checkpoint" => {
+                let archival = self.archival;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::history::handle_hardening_checkpoint_history(&store, r, archival)
+                            .await
+                    }
+                })
+                .await
+            }
+            "history/reputation_checkpoint" => {
+                let archival = self.archival;
+                let ts = self.trust_store.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::validation::handle_get_consensus_result(r, cr.as_ref()).await
+                    handlers::history::handle_reputation_checkpoint_history(
+                        ts.as_ref(),
+                        r,
+                        archival,
+                    )
+                    .await
                 })
                 .await
             }
 
+            // Drift
+            "drift/status" => {
+                let models = self.model_versions().await;
+                let epoch = self.current_epoch().await;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::drift::handle_get_drift_status(&store, r, models.as_ref(), epoch)
+                            .await
+                    }
+                })
+                .await
+            }
+            "drift/molt_progress" => {
+                let models = self.model_versions().await;
+                let epoch = self.current_epoch().await;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::drift::handle_get_molt_progress(&store, r, models.as_ref(), epoch)
+                            .await
+                    }
+                })
+                .await
+            }
+            "drift/molt_dry_run" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::drift::handle_molt_dry_run(&store, r).await }
+                })
+                .await
+            }
+            "drift/molt_plan" => {
+                let models = self.model_versions().await;
+                let epoch = self.current_epoch().await;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::drift::handle_molt_plan(&store, r, models.as_ref(), epoch).await
+                    }
+                })
+                .await
+            }
             // Sync
             "sync/status" => {
                 let peer_count = self.peer_count;
-                dispatch_handler(request.params, |r| async move {
-                    handlers::sync::handle_get_sync_status(r, peer_count).await
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    let metrics = self.sync_metrics.clone();
+                    async move {
+                        handlers::sync::handle_get_sync_status(
+                            &store,
+                            r,
+                            peer_count,
+                            metrics.as_deref(),
+                        )
+                        .await
+                    }
                 })
                 .await
             }
@@ -575,23 +2064,192 @@ impl ChitinServiceImpl {
                 })
                 .await
             }
+            "sync/vbf" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_vbf_exchange(&store, r).await }
+                })
+                .await
+            }
+            "sync/reconcile" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_reconcile(&store, r).await }
+                })
+                .await
+            }
+            "sync/range/summary" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_range_summary(&store, r).await }
+                })
+                .await
+            }
+            "sync/range/ids" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_range_ids(&store, r).await }
+                })
+                .await
+            }
+            "sync/merkle/nodes" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_merkle_nodes(&store, r).await }
+                })
+                .await
+            }
+            "sync/merkle/ids" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_merkle_ids(&store, r).await }
+                })
+                .await
+            }
+            "sync/polyp_meta" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::sync::handle_polyp_meta(&store, r).await }
+                })
+                .await
+            }
+            "sync/state_updates" => {
+                let signer = match (&self.signing_key, &self.node_identity) {
+                    (Some(key), Some(identity)) if self.may_sign() => {
+                        Some((key.clone(), identity.hotkey))
+                    }
+                    _ => None,
+                };
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        let signer = signer.as_ref().map(|(key, hotkey)| (&**key, *hotkey));
+                        handlers::sync::handle_state_updates(&store, r, signer).await
+                    }
+                })
+                .await
+            }
+            "sync/hardening_checkpoint" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::sync::handle_get_hardening_checkpoint(&store, r).await
+                    }
+                })
+                .await
+            }
 
             // Admin
             "admin/config" => {
+                let view = self.config_view.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::admin::handle_get_config(r).await
+                    handlers::admin::handle_get_config(r, view.as_ref()).await
                 })
                 .await
             }
             "admin/config/update" => {
+                let update = self.config_update.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_update_config(r, update.as_ref()).await
+                })
+                .await
+            }
+            "admin/config/reload" => {
+                let reload = self.config_reload.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::admin::handle_update_config(r).await
+                    handlers::admin::handle_reload_config(r, reload.as_ref()).await
+                })
+                .await
+            }
+            "admin/tasks" => {
+                let tasks = self.task_list.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_list_tasks(r, tasks.as_ref()).await
+                })
+                .await
+            }
+            "admin/promote" => {
+                let promote = self.promote.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::replication::handle_promote(r, promote.as_ref()).await
                 })
                 .await
             }
             "admin/logs" => {
+                let logs = self.log_query.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_get_logs(r, logs.as_ref()).await
+                })
+                .await
+            }
+            "admin/backup" => {
+                let backup = self.backup.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_backup(r, backup.as_ref()).await
+                })
+                .await
+            }
+            "admin/snapshot" => {
+                let snapshot = self.snapshot.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_snapshot(r, snapshot.as_ref()).await
+                })
+                .await
+            }
+            "admin/molt/start" => {
+                let models = self.model_versions().await;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move {
+                        handlers::drift::handle_molt_start(&store, r, models.as_ref()).await
+                    }
+                })
+                .await
+            }
+            "admin/molt/pause" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::drift::handle_set_molt_paused(&store, r, true).await }
+                })
+                .await
+            }
+            "admin/molt/resume" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::drift::handle_set_molt_paused(&store, r, false).await }
+                })
+                .await
+            }
+            "admin/polyp/import" => {
+                dispatch_handler(request.params, |r| self.import_polyps(r)).await
+            }
+            "admin/reputation/export" => {
+                let ts = self.trust_store.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_export_reputation(r, ts.as_ref()).await
+                })
+                .await
+            }
+            "admin/reputation/import" => {
+                let ts = self.trust_store.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::admin::handle_import_reputation(r, ts.as_ref()).await
+                })
+                .await
+            }
+
+            // Replication
+            "replication/state" => {
+                let state = self.replication_state.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::replication::handle_get_replication_state(r, state.as_ref()).await
+                })
+                .await
+            }
+            "replication/status" => {
+                let status = self.replication_status.clone();
                 dispatch_handler(request.params, |r| async move {
-                    handlers::admin::handle_get_logs(r).await
+                    handlers::replication::handle_get_replication_status(r, status.as_ref()).await
                 })
                 .await
             }
@@ -600,21 +2258,72 @@ impl ChitinServiceImpl {
             "peer/announce" => {
                 let self_did = self.node_identity.as_ref().map(|id| id.did.clone());
                 let self_url = self.self_url.clone();
-                dispatch_handler(request.params, |r| async move {
-                    handlers::peer::handle_announce_with_identity(r, self_did, self_url).await
+                let network_id = self.network_id.clone();
+                let callback = self.announce_callback.clone();
+                dispatch_handler(request.params, |r: handlers::peer::AnnounceRequest| async move {
+                    let accepted = r.same_network(network_id.as_deref());
+                    if let (Some(callback), true) = (callback, accepted) {
+                        callback(r.clone());
+                    }
+                    handlers::peer::handle_announce_with_identity(r, self_did, self_url, network_id)
+                        .await
                 })
                 .await
             }
             "peer/receive_polyp" => {
-                dispatch_handler(request.params, |r| {
+                let shards = self.shard_set.clone();
+                let throttle = self.sync_throttle.clone();
+                dispatch_handler(request.params, |r: handlers::peer::ReceivePolypRequest| {
                     let store = self.store.clone();
                     let index = self.index.clone();
+                    let feed = self.polyp_feed.clone();
+                    let cache = self.query_cache.clone();
                     async move {
-                        handlers::peer::handle_receive_polyp(&store, &index, r).await
+                        let indexed_in = cache.as_ref().map(|_| {
+                            (
+                                r.polyp.subject.vector.model_id.key(),
+                                r.polyp.reef_zone.clone(),
+                            )
+                        });
+                        let polyp_id = r.polyp.id;
+                        let resp = handlers::peer::handle_receive_polyp(
+                            &store,
+                            &index,
+                            r,
+                            shards.as_ref(),
+                            throttle.as_deref(),
+                        )
+                        .await?;
+                        if resp.accepted {
+                            if let (Some(cache), Some((model, zone))) = (&cache, &indexed_in) {
+                                cache.invalidate(model, zone.as_deref());
+                            }
+                            feed.publish(polyp_id, "gossip");
+                        }
+                        Ok(resp)
                     }
                 })
                 .await
             }
+            "peer/receive_state_update" => {
+                let epoch = self.current_epoch().await;
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    let ids = self.identities.clone();
+                    async move {
+                        handlers::peer::handle_receive_state_update(&store, ids.as_ref(), epoch, r)
+                            .await
+                    }
+                })
+                .await
+            }
+            "peer/shards" => {
+                let shards = self.shard_set.clone();
+                dispatch_handler(request.params, |r| async move {
+                    handlers::peer::handle_get_shards(r, shards.as_ref()).await
+                })
+                .await
+            }
             "peer/list_polyp_ids" => {
                 dispatch_handler(request.params, |r| {
                     let store = self.store.clone();
@@ -624,17 +2333,28 @@ impl ChitinServiceImpl {
                 })
                 .await
             }
+            "peer/get_polyps_batch" => {
+                dispatch_handler(request.params, |r| {
+                    let store = self.store.clone();
+                    async move { handlers::peer::handle_get_polyps_batch(&store, r).await }
+                })
+                .await
+            }
             "peer/discover" => {
                 let peer_urls = self.peer_urls.clone();
+                let directory = self.peer_directory.clone();
                 dispatch_handler(request.params, |r| async move {
-                    let peer_data: Vec<handlers::peer::DiscoveredPeer> = peer_urls
-                        .into_iter()
-                        .map(|url| handlers::peer::DiscoveredPeer {
-                            url,
-                            did: None,
-                            alive: false,
-                        })
-                        .collect();
+                    let peer_data: Vec<handlers::peer::DiscoveredPeer> = match directory {
+                        Some(directory) => directory().await,
+                        None => peer_urls
+                            .into_iter()
+                            .map(|url| handlers::peer::DiscoveredPeer {
+                                url,
+                                did: None,
+                                alive: false,
+                            })
+                            .collect(),
+                    };
                     handlers::peer::handle_discover_peers(r, peer_data).await
                 })
                 .await
@@ -648,11 +2368,13 @@ impl ChitinServiceImpl {
                 success: true,
                 result: Some(value),
                 error: None,
+                retry_after_ms: None,
             },
             Err(err) => JsonRpcResponse {
                 success: false,
                 result: None,
                 error: Some(err),
+                retry_after_ms: None,
             },
         }
     }
@@ -729,6 +2451,10 @@ where
 
     fn call(&mut self, req: http::Request<B>) -> Self::Future {
         let inner = self.inner.clone();
+        let remote = req
+            .extensions()
+            .get::<tonic::transport::server::TcpConnectInfo>()
+            .and_then(|info| info.remote_addr());
 
         Box::pin(async move {
             // Read the full request body.
@@ -741,6 +2467,7 @@ where
                         success: false,
                         result: None,
                         error: Some(format!("Failed to read request body: {}", e)),
+                        retry_after_ms: None,
                     };
                     let json = serde_json::to_vec(&resp).unwrap_or_default();
                     return Ok(build_response(json));
@@ -755,14 +2482,24 @@ where
                         success: false,
                         result: None,
                         error: Some(format!("Invalid JSON-RPC request: {}", e)),
+                        retry_after_ms: None,
                     };
                     let json = serde_json::to_vec(&resp).unwrap_or_default();
                     return Ok(build_response(json));
                 }
             };
 
-            // Dispatch to the appropriate handler.
-            let rpc_response = inner.dispatch(rpc_request).await;
+            // Dispatch to the appropriate handler, in a span per call.
+            let span = tracing::info_span!(
+                "rpc",
+                method = %rpc_request.method,
+                success = tracing::field::Empty
+            );
+            let rpc_response = inner
+                .dispatch(rpc_request, remote)
+                .instrument(span.clone())
+                .await;
+            span.record("success", rpc_response.success);
             let json = serde_json::to_vec(&rpc_response).unwrap_or_default();
             Ok(build_response(json))
         })
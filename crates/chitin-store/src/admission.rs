@@ -0,0 +1,337 @@
+// crates/chitin-store/src/admission.rs
+//
+// Write admission control from store health.
+//
+// Under heavy ingest RocksDB falls behind on memtable flushes and
+// compaction, then delays and finally stops every write, and latency
+// collapses for readers and writers alike. `AdmissionControl` samples
+// `RocksStore::health` at most every `sample_interval_ms` and refuses new
+// writes (submissions, gossip, pulled sync batches) while the store is
+// stalled, delayed, or over its memtable or compaction-debt limits, telling
+// the caller when to retry. Health sampling errors admit writes rather than
+// block them. Admissions and refusals (by pressure) are counted for metrics.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::rocks::{RocksStore, StoreHealth};
+
+const MIB: u64 = 1024 * 1024;
+
+/// Thresholds for write admission (`[admission]` table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// Refuse writes while the store is under pressure.
+    pub enabled: bool,
+    /// Longest a health sample is reused, in milliseconds.
+    pub sample_interval_ms: u64,
+    /// Memtables waiting to be flushed before writes are refused.
+    pub max_immutable_memtables: u64,
+    /// Memtable size before writes are refused, in MiB (0 for no limit).
+    pub max_memtable_mb: u64,
+    /// Compaction debt before writes are refused, in MiB (0 for no limit).
+    pub max_pending_compaction_mb: u64,
+    /// Delay refused callers are told to wait, in milliseconds.
+    pub retry_after_ms: u64,
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval_ms: 250,
+            max_immutable_memtables: 4,
+            max_memtable_mb: 0,
+            // Half of RocksDB's soft limit, where it starts delaying writes.
+            max_pending_compaction_mb: 32 * 1024,
+            retry_after_ms: 1000,
+        }
+    }
+}
+
+impl AdmissionConfig {
+    /// Admission control for `store`, or `None` if disabled.
+    pub fn build(&self, store: Arc<RocksStore>) -> Option<AdmissionControl> {
+        self.enabled
+            .then(|| AdmissionControl::new(store, self.clone()))
+    }
+
+    /// The pressure `health` shows, most severe first, if any.
+    pub fn pressure(&self, health: &StoreHealth) -> Option<Pressure> {
+        let over = |value: u64, limit_mb: u64| limit_mb > 0 && value > limit_mb * MIB;
+        if health.write_stopped {
+            Some(Pressure::WriteStopped)
+        } else if health.delayed_write_rate > 0 {
+            Some(Pressure::WriteDelayed)
+        } else if health.immutable_memtables > self.max_immutable_memtables {
+            Some(Pressure::FlushBacklog)
+        } else if over(health.memtable_bytes, self.max_memtable_mb) {
+            Some(Pressure::MemtableSize)
+        } else if over(
+            health.pending_compaction_bytes,
+            self.max_pending_compaction_mb,
+        ) {
+            Some(Pressure::CompactionDebt)
+        } else {
+            None
+        }
+    }
+}
+
+/// Why the store is refusing writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pressure {
+    /// RocksDB has stopped writes.
+    WriteStopped,
+    /// RocksDB is slowing writes down.
+    WriteDelayed,
+    /// Too many memtables are waiting to be flushed.
+    FlushBacklog,
+    /// The memtables are over their size limit.
+    MemtableSize,
+    /// Compaction is too far behind.
+    CompactionDebt,
+}
+
+impl Pressure {
+    /// Every pressure, most severe first.
+    pub const ALL: [Pressure; 5] = [
+        Pressure::WriteStopped,
+        Pressure::WriteDelayed,
+        Pressure::FlushBacklog,
+        Pressure::MemtableSize,
+        Pressure::CompactionDebt,
+    ];
+
+    /// Label used in errors and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Pressure::WriteStopped => "write_stopped",
+            Pressure::WriteDelayed => "write_delayed",
+            Pressure::FlushBacklog => "flush_backlog",
+            Pressure::MemtableSize => "memtable_size",
+            Pressure::CompactionDebt => "compaction_debt",
+        }
+    }
+}
+
+/// A refused write: why, and when to try again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttled {
+    pub pressure: Pressure,
+    pub retry_after: Duration,
+}
+
+impl std::fmt::Display for Throttled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Store is busy ({}); retry after {}ms",
+            self.pressure.as_str(),
+            self.retry_after.as_millis()
+        )
+    }
+}
+
+/// Admission counters of an `AdmissionControl`, since startup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdmissionStats {
+    /// Writes admitted.
+    pub admitted: u64,
+    /// Writes refused, by pressure label.
+    pub refused: BTreeMap<String, u64>,
+    /// The pressure at the last sample, if any.
+    pub pressure: Option<Pressure>,
+}
+
+/// Admits or refuses writes by the store's sampled health.
+pub struct AdmissionControl {
+    store: Arc<RocksStore>,
+    config: AdmissionConfig,
+    /// The last sample's time and pressure.
+    sample: Mutex<Option<(Instant, Option<Pressure>)>>,
+    admitted: AtomicU64,
+    /// Refusals, indexed like `Pressure::ALL`.
+    refused: [AtomicU64; 5],
+}
+
+impl AdmissionControl {
+    /// Admission control for `store` with `config`'s thresholds.
+    pub fn new(store: Arc<RocksStore>, config: AdmissionConfig) -> Self {
+        Self {
+            store,
+            config,
+            sample: Mutex::new(None),
+            admitted: AtomicU64::new(0),
+            refused: Default::default(),
+        }
+    }
+
+    /// The configured thresholds.
+    pub fn config(&self) -> &AdmissionConfig {
+        &self.config
+    }
+
+    /// The store's current pressure, resampled if the last sample is older
+    /// than `sample_interval_ms`.
+    pub fn pressure(&self) -> Option<Pressure> {
+        let mut sample = self.sample.lock().unwrap_or_else(|e| e.into_inner());
+        let interval = Duration::from_millis(self.config.sample_interval_ms);
+        if let Some((taken, pressure)) = *sample {
+            if taken.elapsed() < interval {
+                return pressure;
+            }
+        }
+        let pressure = self
+            .store
+            .health()
+            .ok()
+            .and_then(|health| self.config.pressure(&health));
+        *sample = Some((Instant::now(), pressure));
+        pressure
+    }
+
+    /// Admit one write, or refuse it while the store is under pressure.
+    pub fn admit(&self) -> Result<(), Throttled> {
+        self.decide(self.pressure())
+    }
+
+    /// Count and answer an admission decision under `pressure`.
+    fn decide(&self, pressure: Option<Pressure>) -> Result<(), Throttled> {
+        match pressure {
+            None => {
+                self.admitted.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Some(pressure) => {
+                let slot = Pressure::ALL.iter().position(|p| *p == pressure);
+                if let Some(slot) = slot {
+                    self.refused[slot].fetch_add(1, Ordering::Relaxed);
+                }
+                Err(Throttled {
+                    pressure,
+                    retry_after: Duration::from_millis(self.config.retry_after_ms),
+                })
+            }
+        }
+    }
+
+    /// Wait until a write is admitted, sleeping for each refusal's
+    /// retry-after delay.
+    pub async fn wait(&self) {
+        while let Err(throttled) = self.admit() {
+            tokio::time::sleep(throttled.retry_after).await;
+        }
+    }
+
+    /// Admissions and refusals since startup, and the last pressure seen.
+    pub fn stats(&self) -> AdmissionStats {
+        let refused = Pressure::ALL
+            .iter()
+            .zip(&self.refused)
+            .map(|(p, count)| (p.as_str().to_string(), count.load(Ordering::Relaxed)))
+            .collect();
+        let pressure = self
+            .sample
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .and_then(|(_, pressure)| pressure);
+        AdmissionStats {
+            admitted: self.admitted.load(Ordering::Relaxed),
+            refused,
+            pressure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn store() -> Arc<RocksStore> {
+        let name = std::env::temp_dir()
+            .join(format!("chitin_admission_{}", Uuid::now_v7()))
+            .to_string_lossy()
+            .to_string();
+        Arc::new(RocksStore::open_in_memory(&name).unwrap())
+    }
+
+    #[test]
+    fn test_pressure_from_health() {
+        let config = AdmissionConfig {
+            max_memtable_mb: 64,
+            ..AdmissionConfig::default()
+        };
+        let healthy = StoreHealth {
+            memtable_bytes: 8 * MIB,
+            running_compactions: 2,
+            ..StoreHealth::default()
+        };
+        assert_eq!(config.pressure(&healthy), None);
+
+        let backlog = StoreHealth {
+            immutable_memtables: 5,
+            memtable_bytes: 128 * MIB,
+            ..healthy
+        };
+        assert_eq!(config.pressure(&backlog), Some(Pressure::FlushBacklog));
+        let stopped = StoreHealth {
+            write_stopped: true,
+            delayed_write_rate: 16 * MIB,
+            ..backlog
+        };
+        assert_eq!(config.pressure(&stopped), Some(Pressure::WriteStopped));
+
+        let debt = StoreHealth {
+            pending_compaction_bytes: 40 * 1024 * MIB,
+            ..healthy
+        };
+        assert_eq!(config.pressure(&debt), Some(Pressure::CompactionDebt));
+        let unlimited = AdmissionConfig {
+            max_pending_compaction_mb: 0,
+            ..config
+        };
+        assert_eq!(unlimited.pressure(&debt), None);
+    }
+
+    #[test]
+    fn test_admits_while_healthy_and_counts_refusals() {
+        let control = AdmissionControl::new(store(), AdmissionConfig::default());
+        assert!(control.admit().is_ok());
+        assert!(control.admit().is_ok());
+        assert_eq!(control.pressure(), None);
+
+        let refused = control.decide(Some(Pressure::FlushBacklog)).unwrap_err();
+        assert_eq!(refused.pressure, Pressure::FlushBacklog);
+        assert_eq!(refused.retry_after, Duration::from_millis(1000));
+        let stats = control.stats();
+        assert_eq!(stats.admitted, 2);
+        assert_eq!(stats.refused["flush_backlog"], 1);
+        assert_eq!(stats.refused["write_stopped"], 0);
+
+        let disabled = AdmissionConfig {
+            enabled: false,
+            ..AdmissionConfig::default()
+        };
+        assert!(disabled.build(store()).is_none());
+    }
+
+    #[test]
+    fn test_throttled_message_names_retry_delay() {
+        let throttled = Throttled {
+            pressure: Pressure::WriteDelayed,
+            retry_after: Duration::from_millis(1500),
+        };
+        assert_eq!(
+            throttled.to_string(),
+            "Store is busy (write_delayed); retry after 1500ms"
+        );
+    }
+}
@@ -0,0 +1,443 @@
+// crates/chitin-store/src/car.rs
+//
+// CARv1 (Content Addressable aRchive) reading and writing.
+//
+// A CAR file is a DAG-CBOR header `{roots: [CID], version: 1}` followed by
+// blocks, each prefixed by the varint length of its CID plus its data.
+// Polyp archives hold one JSON block (multicodec `json`) per Polyp and a
+// DAG-CBOR root block listing links to all of them, so an archive can be
+// imported into IPFS (`ipfs dag import`) and published as one dataset. Only
+// CIDv1 with SHA-2-256 multihashes are supported; the reader checks every
+// block's digest against its data.
+
+use std::fmt;
+use std::io::{Read, Write};
+
+use chitin_core::crypto::hash_bytes;
+use chitin_core::error::ChitinError;
+
+/// Multicodec of a JSON block.
+pub const CODEC_JSON: u64 = 0x0200;
+
+/// Multicodec of a DAG-CBOR block.
+pub const CODEC_DAG_CBOR: u64 = 0x71;
+
+/// Multihash code of SHA-2-256.
+const MULTIHASH_SHA2_256: u64 = 0x12;
+
+/// CBOR tag for an IPLD link.
+const CID_TAG: u64 = 42;
+
+/// Largest block (or header) the reader accepts.
+pub const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+/// A CIDv1 with a SHA-2-256 multihash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cid {
+    /// Multicodec of the block's data.
+    pub codec: u64,
+    /// SHA-256 of the block's data.
+    pub digest: [u8; 32],
+}
+
+impl Cid {
+    /// The CID of `data` under `codec`.
+    pub fn of(codec: u64, data: &[u8]) -> Self {
+        Self {
+            codec,
+            digest: hash_bytes(data),
+        }
+    }
+
+    /// Binary form: version, codec, and multihash as varints.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(38);
+        write_varint(&mut bytes, 1);
+        write_varint(&mut bytes, self.codec);
+        write_varint(&mut bytes, MULTIHASH_SHA2_256);
+        write_varint(&mut bytes, 32);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+
+    /// Parse a binary CID from the start of `bytes`, returning it and the
+    /// number of bytes it used.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), ChitinError> {
+        let mut pos = 0;
+        let mut next = || -> Result<u64, ChitinError> {
+            let (value, used) = read_varint_slice(&bytes[pos..])?;
+            pos += used;
+            Ok(value)
+        };
+        let version = next()?;
+        let codec = next()?;
+        let hash = next()?;
+        let len = next()?;
+        if version != 1 || hash != MULTIHASH_SHA2_256 || len != 32 {
+            return Err(ChitinError::Serialization(
+                "Only CIDv1 with SHA-2-256 multihashes are supported".to_string(),
+            ));
+        }
+        let digest = bytes
+            .get(pos..pos + 32)
+            .and_then(|d| <[u8; 32]>::try_from(d).ok())
+            .ok_or_else(|| ChitinError::Serialization("Truncated CID".to_string()))?;
+        Ok((Self { codec, digest }, pos + 32))
+    }
+}
+
+impl fmt::Display for Cid {
+    /// Multibase base32 (lowercase, unpadded), as IPFS prints CIDv1s.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+        let mut out = String::from("b");
+        let (mut buffer, mut bits) = (0u32, 0u32);
+        for byte in self.to_bytes() {
+            buffer = (buffer << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+            }
+        }
+        if bits > 0 {
+            out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+        }
+        f.write_str(&out)
+    }
+}
+
+/// A DAG-CBOR block holding a list of links to `cids`.
+pub fn index_block(cids: &[Cid]) -> Vec<u8> {
+    let mut block = Vec::new();
+    cbor_head(&mut block, 4, cids.len() as u64);
+    for cid in cids {
+        cbor_link(&mut block, cid);
+    }
+    block
+}
+
+/// Writes a CARv1 archive.
+pub struct CarWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> CarWriter<W> {
+    /// Start an archive with the given roots.
+    pub fn new(mut out: W, roots: &[Cid]) -> Result<Self, ChitinError> {
+        let mut header = Vec::new();
+        cbor_head(&mut header, 5, 2);
+        cbor_text(&mut header, "roots");
+        cbor_head(&mut header, 4, roots.len() as u64);
+        for root in roots {
+            cbor_link(&mut header, root);
+        }
+        cbor_text(&mut header, "version");
+        cbor_head(&mut header, 0, 1);
+
+        let mut prefix = Vec::new();
+        write_varint(&mut prefix, header.len() as u64);
+        out.write_all(&prefix).map_err(io_error)?;
+        out.write_all(&header).map_err(io_error)?;
+        Ok(Self { out })
+    }
+
+    /// Append a block.
+    pub fn write_block(&mut self, cid: &Cid, data: &[u8]) -> Result<(), ChitinError> {
+        let cid = cid.to_bytes();
+        let mut prefix = Vec::new();
+        write_varint(&mut prefix, (cid.len() + data.len()) as u64);
+        self.out.write_all(&prefix).map_err(io_error)?;
+        self.out.write_all(&cid).map_err(io_error)?;
+        self.out.write_all(data).map_err(io_error)
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn finish(mut self) -> Result<W, ChitinError> {
+        self.out.flush().map_err(io_error)?;
+        Ok(self.out)
+    }
+}
+
+/// Reads a CARv1 archive block by block.
+pub struct CarReader<R: Read> {
+    input: R,
+    roots: Vec<Cid>,
+}
+
+impl<R: Read> CarReader<R> {
+    /// Read the archive header.
+    pub fn new(mut input: R) -> Result<Self, ChitinError> {
+        let len = match read_varint(&mut input)? {
+            Some(len) => len,
+            None => return Err(ChitinError::Serialization("Empty CAR file".to_string())),
+        };
+        let header = read_exact(&mut input, len)?;
+        let roots = parse_header(&header)?;
+        Ok(Self { input, roots })
+    }
+
+    /// The archive's root CIDs.
+    pub fn roots(&self) -> &[Cid] {
+        &self.roots
+    }
+
+    /// The next block, or `None` at the end of the archive.
+    ///
+    /// # Errors
+    /// Returns `ChitinError::Verification` if the block's data does not
+    /// hash to its CID.
+    pub fn next_block(&mut self) -> Result<Option<(Cid, Vec<u8>)>, ChitinError> {
+        let len = match read_varint(&mut self.input)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        let mut section = read_exact(&mut self.input, len)?;
+        let (cid, used) = Cid::from_bytes(&section)?;
+        let data = section.split_off(used);
+        if hash_bytes(&data) != cid.digest {
+            return Err(ChitinError::Verification(format!(
+                "Block {} does not match its CID",
+                cid
+            )));
+        }
+        Ok(Some((cid, data)))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Encoding helpers
+// ---------------------------------------------------------------------------
+
+fn io_error(e: std::io::Error) -> ChitinError {
+    ChitinError::Storage(format!("CAR I/O: {}", e))
+}
+
+/// Append an unsigned LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint_slice(bytes: &[u8]) -> Result<(u64, usize), ChitinError> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(ChitinError::Serialization("Malformed varint".to_string()))
+}
+
+/// Read a varint from a stream, or `None` at a clean end of stream.
+fn read_varint<R: Read>(input: &mut R) -> Result<Option<u64>, ChitinError> {
+    let mut value = 0u64;
+    for i in 0..10 {
+        let mut byte = [0u8];
+        if input.read(&mut byte).map_err(io_error)? == 0 {
+            if i == 0 {
+                return Ok(None);
+            }
+            break;
+        }
+        value |= ((byte[0] & 0x7f) as u64) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(ChitinError::Serialization("Malformed varint".to_string()))
+}
+
+fn read_exact<R: Read>(input: &mut R, len: u64) -> Result<Vec<u8>, ChitinError> {
+    if len as usize > MAX_BLOCK_SIZE {
+        return Err(ChitinError::Serialization(format!(
+            "CAR section of {} bytes exceeds {} bytes",
+            len, MAX_BLOCK_SIZE
+        )));
+    }
+    let mut buf = vec![0u8; len as usize];
+    input
+        .read_exact(&mut buf)
+        .map_err(|_| ChitinError::Serialization("Truncated CAR file".to_string()))?;
+    Ok(buf)
+}
+
+/// Append a CBOR item head of major type `major`.
+fn cbor_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+fn cbor_text(out: &mut Vec<u8>, text: &str) {
+    cbor_head(out, 3, text.len() as u64);
+    out.extend_from_slice(text.as_bytes());
+}
+
+/// An IPLD link: tag 42 over the binary CID with a leading zero byte.
+fn cbor_link(out: &mut Vec<u8>, cid: &Cid) {
+    let bytes = cid.to_bytes();
+    cbor_head(out, 6, CID_TAG);
+    cbor_head(out, 2, bytes.len() as u64 + 1);
+    out.push(0);
+    out.extend_from_slice(&bytes);
+}
+
+/// A minimal CBOR cursor for the CAR header.
+struct Cbor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cbor<'_> {
+    fn head(&mut self) -> Result<(u8, u64), ChitinError> {
+        let malformed = || ChitinError::Serialization("Malformed CAR header".to_string());
+        let first = *self.bytes.get(self.pos).ok_or_else(malformed)?;
+        self.pos += 1;
+        let width = match first & 0x1f {
+            info @ 0..=23 => return Ok((first >> 5, info as u64)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(malformed()),
+        };
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + width)
+            .ok_or_else(malformed)?;
+        self.pos += width;
+        let value = bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        Ok((first >> 5, value))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&[u8], ChitinError> {
+        let end = self.pos.checked_add(len as usize);
+        let bytes = end
+            .and_then(|end| self.bytes.get(self.pos..end))
+            .ok_or_else(|| ChitinError::Serialization("Malformed CAR header".to_string()))?;
+        self.pos += len as usize;
+        Ok(bytes)
+    }
+}
+
+/// Parse `{roots: [CID], version: 1}` in either key order.
+fn parse_header(bytes: &[u8]) -> Result<Vec<Cid>, ChitinError> {
+    let malformed = || ChitinError::Serialization("Malformed CAR header".to_string());
+    let mut cbor = Cbor { bytes, pos: 0 };
+    let (major, entries) = cbor.head()?;
+    if major != 5 {
+        return Err(malformed());
+    }
+    let (mut roots, mut version) = (None, None);
+    for _ in 0..entries {
+        let (major, len) = cbor.head()?;
+        if major != 3 {
+            return Err(malformed());
+        }
+        match cbor.take(len)? {
+            b"version" => match cbor.head()? {
+                (0, value) => version = Some(value),
+                _ => return Err(malformed()),
+            },
+            b"roots" => {
+                let (major, count) = cbor.head()?;
+                if major != 4 {
+                    return Err(malformed());
+                }
+                let mut cids = Vec::new();
+                for _ in 0..count {
+                    if cbor.head()? != (6, CID_TAG) {
+                        return Err(malformed());
+                    }
+                    let (major, len) = cbor.head()?;
+                    match cbor.take(len)? {
+                        [0, cid @ ..] if major == 2 => cids.push(Cid::from_bytes(cid)?.0),
+                        _ => return Err(malformed()),
+                    }
+                }
+                roots = Some(cids);
+            }
+            _ => return Err(malformed()),
+        }
+    }
+    match (roots, version) {
+        (Some(roots), Some(1)) => Ok(roots),
+        (_, Some(version)) => Err(ChitinError::Serialization(format!(
+            "Unsupported CAR version {}",
+            version
+        ))),
+        _ => Err(malformed()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_car_round_trip() {
+        let blocks: Vec<Vec<u8>> = (0..3)
+            .map(|i| format!("{{\"n\":{}}}", i).into_bytes())
+            .collect();
+        let cids: Vec<Cid> = blocks.iter().map(|b| Cid::of(CODEC_JSON, b)).collect();
+        let index = index_block(&cids);
+        let root = Cid::of(CODEC_DAG_CBOR, &index);
+
+        let mut writer = CarWriter::new(Vec::new(), &[root]).unwrap();
+        writer.write_block(&root, &index).unwrap();
+        for (cid, block) in cids.iter().zip(&blocks) {
+            writer.write_block(cid, block).unwrap();
+        }
+        let archive = writer.finish().unwrap();
+
+        let mut reader = CarReader::new(archive.as_slice()).unwrap();
+        assert_eq!(reader.roots(), &[root]);
+        assert_eq!(reader.next_block().unwrap(), Some((root, index)));
+        for (cid, block) in cids.iter().zip(&blocks) {
+            assert_eq!(reader.next_block().unwrap(), Some((*cid, block.clone())));
+        }
+        assert_eq!(reader.next_block().unwrap(), None);
+    }
+
+    #[test]
+    fn test_car_reader_rejects_tampered_block() {
+        let data = b"{\"n\":1}".to_vec();
+        let cid = Cid::of(CODEC_JSON, &data);
+        let mut writer = CarWriter::new(Vec::new(), &[cid]).unwrap();
+        writer.write_block(&cid, &data).unwrap();
+        let mut archive = writer.finish().unwrap();
+        *archive.last_mut().unwrap() ^= 1;
+
+        let mut reader = CarReader::new(archive.as_slice()).unwrap();
+        assert!(reader.next_block().is_err());
+    }
+
+    #[test]
+    fn test_cid_display_is_multibase_base32() {
+        // CID of the empty raw block, as printed by IPFS.
+        let cid = Cid::of(0x55, b"");
+        assert_eq!(
+            cid.to_string(),
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+        );
+    }
+}
@@ -0,0 +1,350 @@
+// crates/chitin-store/src/embedding_cache.rs
+//
+// EmbeddingCache: RocksDB-backed vectors keyed by model and content hash.
+//
+// Embedding is deterministic for a given model, so a text embedded once
+// never needs to go back to the provider: ingestion of duplicate content,
+// repeated queries, and molting a corpus the node already embedded under
+// the target model are served from here instead.
+//
+// Entries live under `embedding_cache:{model_key}:{dimensions}:{sha256}`,
+// where the hash is of the text's UTF-8 bytes; the value is the entry's
+// sequence number (u64 little-endian) followed by the vector (f32
+// little-endian). An insertion-order index under
+// `embedding_cache_seq:{seq:020}` lets the cache evict its oldest entries
+// once it holds more than `max_entries`, and survives restarts.
+//
+// `CachedEmbedder` puts a cache in front of any `Embedder`, embedding only
+// the texts it misses.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use chitin_core::error::ChitinError;
+use chitin_core::light::text_hash;
+use chitin_core::{Embedder, EmbeddingModelId, VectorEmbedding};
+
+use crate::rocks::RocksStore;
+
+/// Key prefix for cached vectors.
+const KEY_PREFIX: &str = "embedding_cache:";
+
+/// Key prefix for the insertion-order index.
+const SEQ_PREFIX: &str = "embedding_cache_seq:";
+
+/// Quantization of cached vectors (the cache stores f32 values).
+const QUANTIZATION: &str = "float32";
+
+/// Normalization reported for cached vectors, as every `Embedder` in
+/// chitin-core produces.
+const NORMALIZATION: &str = "l2";
+
+/// Counters describing a cache since startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Vectors held.
+    pub entries: u64,
+    /// Most vectors held before the oldest are evicted.
+    pub max_entries: u64,
+    /// Lookups served from the cache.
+    pub hits: u64,
+    /// Lookups that had to be embedded.
+    pub misses: u64,
+    /// Entries evicted to stay under `max_entries`.
+    pub evictions: u64,
+}
+
+/// Embedding vectors cached in RocksDB, evicted oldest first.
+#[derive(Debug)]
+pub struct EmbeddingCache {
+    store: Arc<RocksStore>,
+    max_entries: usize,
+    /// Sequence numbers of held entries, oldest first, and the next one.
+    order: Mutex<(VecDeque<u64>, u64)>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl EmbeddingCache {
+    /// Open the cache kept in `store`, holding at most `max_entries`
+    /// vectors (at least one). Entries beyond the cap, e.g. after it was
+    /// lowered, are evicted now.
+    pub fn open(store: Arc<RocksStore>, max_entries: usize) -> Result<Self, ChitinError> {
+        let mut order = VecDeque::new();
+        for (key, _) in store.scan_prefix(SEQ_PREFIX.as_bytes())? {
+            let seq = std::str::from_utf8(&key[SEQ_PREFIX.len()..])
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok());
+            match seq {
+                Some(seq) => order.push_back(seq),
+                None => store.delete_bytes(&key)?,
+            }
+        }
+        let next = order.back().map_or(0, |seq| seq + 1);
+        let cache = Self {
+            store,
+            max_entries: max_entries.max(1),
+            order: Mutex::new((order, next)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        };
+        cache.evict()?;
+        Ok(cache)
+    }
+
+    fn key(model: &EmbeddingModelId, text: &str) -> Vec<u8> {
+        let hash: String = text_hash(text)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!(
+            "{}{}:{}:{}",
+            KEY_PREFIX,
+            model.key(),
+            model.dimensions,
+            hash
+        )
+        .into_bytes()
+    }
+
+    fn seq_key(seq: u64) -> Vec<u8> {
+        format!("{}{:020}", SEQ_PREFIX, seq).into_bytes()
+    }
+
+    /// The cached vector of `text` under `model`, counting a hit or miss.
+    pub fn get(
+        &self,
+        model: &EmbeddingModelId,
+        text: &str,
+    ) -> Result<Option<Vec<f32>>, ChitinError> {
+        let values = self
+            .store
+            .get_bytes(&Self::key(model, text))?
+            .and_then(|bytes| decode_values(&bytes));
+        let counter = if values.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(values)
+    }
+
+    /// Cache `values` as the vector of `text` under `model`, evicting the
+    /// oldest entries if the cache is full. A text already cached keeps its
+    /// entry.
+    pub fn put(
+        &self,
+        model: &EmbeddingModelId,
+        text: &str,
+        values: &[f32],
+    ) -> Result<(), ChitinError> {
+        let key = Self::key(model, text);
+        {
+            let mut order = self.order.lock().unwrap_or_else(|e| e.into_inner());
+            if self.store.get_bytes(&key)?.is_some() {
+                return Ok(());
+            }
+            let seq = order.1;
+            let mut value = Vec::with_capacity(8 + values.len() * 4);
+            value.extend_from_slice(&seq.to_le_bytes());
+            for v in values {
+                value.extend_from_slice(&v.to_le_bytes());
+            }
+            self.store.put_bytes(&key, &value)?;
+            self.store.put_bytes(&Self::seq_key(seq), &key)?;
+            order.0.push_back(seq);
+            order.1 = seq + 1;
+        }
+        self.evict()
+    }
+
+    /// Drop the oldest entries until at most `max_entries` remain.
+    fn evict(&self) -> Result<(), ChitinError> {
+        let mut order = self.order.lock().unwrap_or_else(|e| e.into_inner());
+        while order.0.len() > self.max_entries {
+            let Some(seq) = order.0.pop_front() else {
+                break;
+            };
+            let seq_key = Self::seq_key(seq);
+            if let Some(key) = self.store.get_bytes(&seq_key)? {
+                self.store.delete_bytes(&key)?;
+            }
+            self.store.delete_bytes(&seq_key)?;
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Entry count, cap, and hit counters since startup.
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.order.lock().unwrap_or_else(|e| e.into_inner()).0.len();
+        CacheStats {
+            entries: entries as u64,
+            max_entries: self.max_entries as u64,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The vector stored after an entry's sequence number.
+fn decode_values(bytes: &[u8]) -> Option<Vec<f32>> {
+    let values = bytes.get(8..)?;
+    if values.len() % 4 != 0 {
+        return None;
+    }
+    Some(
+        values
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+    )
+}
+
+/// An `Embedder` that serves cached vectors and embeds only the rest with
+/// `inner`, caching what it embeds.
+pub struct CachedEmbedder {
+    inner: Arc<dyn Embedder>,
+    cache: Arc<EmbeddingCache>,
+}
+
+impl CachedEmbedder {
+    /// Put `cache` in front of `inner`.
+    pub fn new(inner: Arc<dyn Embedder>, cache: Arc<EmbeddingCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl Embedder for CachedEmbedder {
+    async fn embed(
+        &self,
+        texts: &[&str],
+        model: &EmbeddingModelId,
+    ) -> Result<Vec<VectorEmbedding>, ChitinError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        let mut missed = Vec::new();
+        for (i, text) in texts.iter().enumerate() {
+            let cached = self.cache.get(model, text)?;
+            if cached.is_none() {
+                missed.push(i);
+            }
+            embeddings.push(cached.map(|values| VectorEmbedding {
+                model_id: EmbeddingModelId {
+                    dimensions: values.len() as u32,
+                    ..model.clone()
+                },
+                values,
+                quantization: QUANTIZATION.to_string(),
+                normalization: NORMALIZATION.to_string(),
+            }));
+        }
+
+        if !missed.is_empty() {
+            let batch: Vec<&str> = missed.iter().map(|&i| texts[i]).collect();
+            let embedded = self.inner.embed(&batch, model).await?;
+            if embedded.len() != batch.len() {
+                return Err(ChitinError::InvalidState(format!(
+                    "{} vectors for {} texts",
+                    embedded.len(),
+                    batch.len()
+                )));
+            }
+            for (i, embedding) in missed.into_iter().zip(embedded) {
+                self.cache.put(model, texts[i], &embedding.values)?;
+                embeddings[i] = Some(embedding);
+            }
+        }
+        Ok(embeddings.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    use chitin_core::embedder::HashEmbedder;
+    use uuid::Uuid;
+
+    fn open_store() -> (Arc<RocksStore>, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("chitin_embed_cache_{}", Uuid::now_v7()));
+        let store = RocksStore::open(&dir.to_string_lossy()).unwrap();
+        (Arc::new(store), dir)
+    }
+
+    fn model() -> EmbeddingModelId {
+        EmbeddingModelId::from_key("test/model", 4)
+    }
+
+    /// Counts the texts it is asked to embed.
+    struct Counting {
+        texts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Embedder for Counting {
+        async fn embed(
+            &self,
+            texts: &[&str],
+            model: &EmbeddingModelId,
+        ) -> Result<Vec<VectorEmbedding>, ChitinError> {
+            self.texts.fetch_add(texts.len(), Ordering::Relaxed);
+            HashEmbedder::new(4).embed(texts, model).await
+        }
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted_over_the_cap() {
+        let (store, dir) = open_store();
+        let cache = EmbeddingCache::open(store.clone(), 2).unwrap();
+        cache.put(&model(), "a", &[1.0, 2.0]).unwrap();
+        cache.put(&model(), "b", &[3.0]).unwrap();
+        cache.put(&model(), "a", &[9.0]).unwrap();
+        cache.put(&model(), "c", &[4.0]).unwrap();
+
+        assert_eq!(cache.get(&model(), "a").unwrap(), None);
+        assert_eq!(cache.get(&model(), "b").unwrap(), Some(vec![3.0]));
+        let other = EmbeddingModelId::from_key("test/model", 8);
+        assert_eq!(cache.get(&other, "b").unwrap(), None);
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.evictions), (2, 1));
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+
+        // The order survives reopening, and a lower cap evicts at once.
+        drop(cache);
+        let cache = EmbeddingCache::open(store.clone(), 1).unwrap();
+        assert_eq!(cache.get(&model(), "b").unwrap(), None);
+        assert_eq!(cache.get(&model(), "c").unwrap(), Some(vec![4.0]));
+        drop((cache, store));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn cached_embedder_embeds_only_misses() {
+        let (store, dir) = open_store();
+        let cache = Arc::new(EmbeddingCache::open(store.clone(), 100).unwrap());
+        let inner = Arc::new(Counting {
+            texts: AtomicUsize::new(0),
+        });
+        let embedder = CachedEmbedder::new(inner.clone(), cache.clone());
+
+        let first = embedder.embed(&["x", "y"], &model()).await.unwrap();
+        let second = embedder.embed(&["y", "z", "x"], &model()).await.unwrap();
+        assert_eq!(inner.texts.load(Ordering::Relaxed), 3);
+        assert_eq!(second[0].values, first[1].values);
+        assert_eq!(second[2].values, first[0].values);
+        assert_eq!(second[1].values.len(), 4);
+        assert_eq!(cache.stats().hits, 2);
+        drop((embedder, cache, store));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -94,6 +94,25 @@ impl HardenedStore {
         Ok(polyp)
     }
 
+    /// Check the cached copy of `cid` against IPFS, replacing it if it
+    /// differs. Returns the Polyp as stored in IPFS, and whether the cached
+    /// copy was corrupt and replaced. A missing cache entry is not corrupt.
+    pub async fn verify_cached(&self, cid: &str) -> Result<(Polyp, bool), ChitinError> {
+        let bytes = self.ipfs.get_by_cid(cid).await?;
+        let polyp: Polyp = serde_json::from_slice(&bytes)
+            .map_err(|e| ChitinError::Serialization(e.to_string()))?;
+
+        let key = Self::cid_key(cid);
+        let corrupt = self
+            .local_cache
+            .get_bytes(&key)?
+            .is_some_and(|cached| cached != bytes);
+        if corrupt {
+            self.local_cache.put_bytes(&key, &bytes)?;
+        }
+        Ok((polyp, corrupt))
+    }
+
     /// Check whether a given Polyp ID has been hardened (has a CID mapping).
     pub fn is_hardened(&self, polyp_id: Uuid) -> Result<bool, ChitinError> {
         let result = self.local_cache.get_bytes(&Self::map_key(&polyp_id))?;
@@ -3,13 +3,16 @@
 // In-memory vector index implementing the `VectorIndex` trait.
 //
 // Phase 1: Simple brute-force cosine similarity search over an in-memory
-// HashMap of vectors. Sufficient for local development and small datasets.
+// HashMap of vectors, scored with the SIMD kernels in `distance`. Sufficient
+// for local development and small datasets. Each vector is tagged with the
+// embedding model space it belongs to, so that while a model migration is
+// under way each space can be searched on its own.
 //
 // Phase 2: This will be replaced by a Qdrant client integration
 // (`qdrant-client` crate) providing production-grade HNSW-based ANN search
 // with persistence, filtering, and horizontal scaling.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::RwLock;
 
 use async_trait::async_trait;
@@ -18,6 +21,8 @@ use uuid::Uuid;
 use chitin_core::error::ChitinError;
 use chitin_core::traits::VectorIndex;
 
+use crate::distance::cosine_similarity;
+
 /// In-memory vector index using brute-force cosine similarity.
 ///
 /// This is a Phase 1 placeholder. For production use, replace with
@@ -25,10 +30,13 @@ use chitin_core::traits::VectorIndex;
 /// on-disk persistence, payload filtering, and multi-node sharding.
 #[derive(Debug)]
 pub struct InMemoryVectorIndex {
-    /// Map from Polyp UUID to its vector embedding.
-    vectors: RwLock<HashMap<Uuid, Vec<f32>>>,
+    /// Map from Polyp UUID to its model space and vector embedding.
+    vectors: RwLock<HashMap<Uuid, (String, Vec<f32>)>>,
 }
 
+/// Model space of vectors upserted without one (`VectorIndex::upsert`).
+pub const UNSPECIFIED_MODEL: &str = "";
+
 impl InMemoryVectorIndex {
     /// Create a new empty in-memory vector index.
     pub fn new() -> Self {
@@ -49,54 +57,55 @@ impl InMemoryVectorIndex {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
-}
 
-impl Default for InMemoryVectorIndex {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// Compute cosine similarity between two vectors.
-///
-/// Returns a value in [-1.0, 1.0]. Returns 0.0 if either vector has zero magnitude.
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
-    if a.len() != b.len() {
-        return 0.0;
+    /// Insert or update a vector in the space of `model` (e.g. "bge/bge-small-en-v1.5"),
+    /// moving it out of any space it was in before.
+    pub fn upsert_in(&self, model: &str, id: Uuid, vector: &[f32]) -> Result<(), ChitinError> {
+        let mut store = self
+            .vectors
+            .write()
+            .map_err(|e| ChitinError::Storage(format!("RwLock poisoned: {}", e)))?;
+        store.insert(id, (model.to_string(), vector.to_vec()));
+        Ok(())
     }
 
-    let mut dot = 0.0_f64;
-    let mut norm_a = 0.0_f64;
-    let mut norm_b = 0.0_f64;
-
-    for (x, y) in a.iter().zip(b.iter()) {
-        let x = *x as f64;
-        let y = *y as f64;
-        dot += x * y;
-        norm_a += x * x;
-        norm_b += y * y;
+    /// Search only the vectors in the space of `model`.
+    pub fn search_in(
+        &self,
+        model: &str,
+        query: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<(Uuid, f32)>, ChitinError> {
+        self.search_where(query, top_k, |space| space == model)
     }
 
-    let denom = norm_a.sqrt() * norm_b.sqrt();
-    if denom == 0.0 {
-        return 0.0;
+    /// Model spaces present in the index and how many vectors each holds.
+    pub fn model_spaces(&self) -> BTreeMap<String, usize> {
+        let store = self.vectors.read().expect("RwLock poisoned");
+        let mut spaces = BTreeMap::new();
+        for (space, _) in store.values() {
+            *spaces.entry(space.clone()).or_insert(0) += 1;
+        }
+        spaces
     }
 
-    (dot / denom) as f32
-}
-
-#[async_trait]
-impl VectorIndex for InMemoryVectorIndex {
-    async fn upsert(&self, id: Uuid, vector: &[f32]) -> Result<(), ChitinError> {
-        let mut store = self
-            .vectors
-            .write()
-            .map_err(|e| ChitinError::Storage(format!("RwLock poisoned: {}", e)))?;
-        store.insert(id, vector.to_vec());
-        Ok(())
+    /// Every vector with its ID and model space, ordered by ID.
+    pub fn entries(&self) -> Vec<(Uuid, String, Vec<f32>)> {
+        let store = self.vectors.read().expect("RwLock poisoned");
+        let mut entries: Vec<(Uuid, String, Vec<f32>)> = store
+            .iter()
+            .map(|(id, (space, vector))| (*id, space.clone(), vector.clone()))
+            .collect();
+        entries.sort_by_key(|(id, _, _)| *id);
+        entries
     }
 
-    async fn search(&self, query: &[f32], top_k: usize) -> Result<Vec<(Uuid, f32)>, ChitinError> {
+    fn search_where(
+        &self,
+        query: &[f32],
+        top_k: usize,
+        in_space: impl Fn(&str) -> bool,
+    ) -> Result<Vec<(Uuid, f32)>, ChitinError> {
         let store = self
             .vectors
             .read()
@@ -105,7 +114,8 @@ impl VectorIndex for InMemoryVectorIndex {
         // Brute-force: compute cosine similarity against every stored vector.
         let mut scored: Vec<(Uuid, f32)> = store
             .iter()
-            .map(|(id, vec)| (*id, cosine_similarity(query, vec)))
+            .filter(|(_, (space, _))| in_space(space))
+            .map(|(id, (_, vec))| (*id, cosine_similarity(query, vec)))
             .collect();
 
         // Sort by descending similarity.
@@ -115,6 +125,24 @@ impl VectorIndex for InMemoryVectorIndex {
         scored.truncate(top_k);
         Ok(scored)
     }
+}
+
+impl Default for InMemoryVectorIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VectorIndex for InMemoryVectorIndex {
+    async fn upsert(&self, id: Uuid, vector: &[f32]) -> Result<(), ChitinError> {
+        self.upsert_in(UNSPECIFIED_MODEL, id, vector)
+    }
+
+    /// Searches every model space.
+    async fn search(&self, query: &[f32], top_k: usize) -> Result<Vec<(Uuid, f32)>, ChitinError> {
+        self.search_where(query, top_k, |_| true)
+    }
 
     async fn delete(&self, id: &Uuid) -> Result<(), ChitinError> {
         let mut store = self
@@ -161,6 +189,42 @@ mod tests {
         assert_eq!(sim, 0.0);
     }
 
+    #[tokio::test]
+    async fn model_spaces_are_searched_separately() {
+        let index = InMemoryVectorIndex::new();
+        let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+        index.upsert_in("bge/v1", a, &[1.0, 0.0]).unwrap();
+        index.upsert_in("bge/v2", b, &[1.0, 0.0]).unwrap();
+        assert_eq!(index.model_spaces().get("bge/v1"), Some(&1));
+
+        let hits = index.search_in("bge/v2", &[1.0, 0.0], 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, b);
+        assert_eq!(index.search(&[1.0, 0.0], 10).await.unwrap().len(), 2);
+
+        // Re-embedding moves a vector between spaces.
+        index.upsert_in("bge/v2", a, &[0.0, 1.0]).unwrap();
+        assert!(index.search_in("bge/v1", &[1.0, 0.0], 10).unwrap().is_empty());
+        assert_eq!(index.model_spaces().get("bge/v2"), Some(&2));
+    }
+
+    #[test]
+    fn entries_list_every_vector_by_id() {
+        let index = InMemoryVectorIndex::new();
+        let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+        index.upsert_in("bge/v1", b, &[0.0, 1.0]).unwrap();
+        index.upsert_in("bge/v2", a, &[1.0, 0.0]).unwrap();
+
+        let entries = index.entries();
+        assert_eq!(
+            entries,
+            vec![
+                (a, "bge/v2".to_string(), vec![1.0, 0.0]),
+                (b, "bge/v1".to_string(), vec![0.0, 1.0]),
+            ]
+        );
+    }
+
     #[test]
     fn test_cosine_similarity_different_lengths() {
         let a = vec![1.0, 2.0, 3.0];
@@ -4,20 +4,34 @@
 //
 // Provides RocksDB-backed Polyp persistence, IPFS client stubs for
 // content-addressed immutable storage, a hardened store for CID-indexed
-// Polyps, an in-memory vector index (Phase 1 placeholder for Qdrant),
-// Bloom filters for set membership, and consistent-hash shard assignment.
+// Polyps, an in-memory vector index (Phase 1 placeholder for Qdrant) with
+// SIMD distance kernels, Bloom filters for set membership, a Merkle summary
+// of the stored Polyp IDs, consistent-hash shard assignment, CAR archives of
+// Polyps, a content-hash-keyed embedding cache, versioned schema
+// migrations for RocksDB stores, and write admission control from store
+// health.
 
+pub mod admission;
 pub mod bloom;
+pub mod car;
+pub mod distance;
+pub mod embedding_cache;
 pub mod hardened;
 pub mod hnsw;
 pub mod ipfs;
+pub mod merkle;
+pub mod migration;
 pub mod rocks;
 pub mod shard;
 
 // Re-export key types for ergonomic access from downstream crates.
+pub use admission::{AdmissionConfig, AdmissionControl};
 pub use bloom::PolypBloomFilter;
+pub use embedding_cache::{CacheStats, CachedEmbedder, EmbeddingCache};
 pub use hardened::HardenedStore;
 pub use hnsw::InMemoryVectorIndex;
 pub use ipfs::IpfsClient;
-pub use rocks::RocksStore;
-pub use shard::ShardAssigner;
+pub use merkle::MerkleSummary;
+pub use migration::{MigrationPlan, MigrationProgress};
+pub use rocks::{BackupStats, Durability, Reclaimed, RocksStore, StoreHealth};
+pub use shard::{ShardAssigner, ShardSet};
@@ -0,0 +1,328 @@
+// crates/chitin-store/src/merkle.rs
+//
+// Content-addressed Merkle summary of a Polyp ID set.
+//
+// IDs are placed in a fixed-shape prefix tree keyed by the SHA-256 of the
+// ID: each level consumes one hex nibble, so the tree has fanout 16 and
+// `MERKLE_DEPTH` levels below the root. Because the shape depends only on
+// the IDs (not on insertion order), two nodes holding the same set have the
+// same root hash, and differing sets differ only along the paths to the
+// differing IDs. Updates re-hash one root-to-leaf path, so the summary can
+// be maintained on every save.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_core::crypto::hash_bytes;
+
+/// Children per node.
+pub const MERKLE_FANOUT: usize = 16;
+
+/// Levels below the root (leaf count = 16^depth).
+pub const MERKLE_DEPTH: u8 = 4;
+
+/// Bits consumed per level.
+const BITS_PER_LEVEL: u8 = 4;
+
+/// A node position: the first `depth` nibbles of the ID hash.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct MerklePrefix {
+    /// Number of nibbles fixed (0 = root, `MERKLE_DEPTH` = leaf).
+    pub depth: u8,
+    /// The fixed nibbles, right-aligned.
+    pub path: u16,
+}
+
+impl MerklePrefix {
+    /// The root node.
+    pub const ROOT: MerklePrefix = MerklePrefix { depth: 0, path: 0 };
+
+    /// True for well-formed prefixes (depth in range, path fits in depth).
+    pub fn is_valid(&self) -> bool {
+        self.depth <= MERKLE_DEPTH
+            && (self.path as u32) < (1u32 << (BITS_PER_LEVEL as u32 * self.depth as u32))
+    }
+
+    /// True at the bottom level.
+    pub fn is_leaf(&self) -> bool {
+        self.depth == MERKLE_DEPTH
+    }
+
+    /// Child prefixes, in order. Empty for leaves.
+    pub fn children(&self) -> Vec<MerklePrefix> {
+        if self.is_leaf() {
+            return Vec::new();
+        }
+        (0..MERKLE_FANOUT as u16)
+            .map(|nibble| MerklePrefix {
+                depth: self.depth + 1,
+                path: (self.path << BITS_PER_LEVEL) | nibble,
+            })
+            .collect()
+    }
+
+    fn parent(&self) -> MerklePrefix {
+        MerklePrefix {
+            depth: self.depth - 1,
+            path: self.path >> BITS_PER_LEVEL,
+        }
+    }
+
+    /// Leaf paths under this prefix, as a half-open range.
+    fn leaf_range(&self) -> std::ops::Range<u32> {
+        let shift = BITS_PER_LEVEL as u32 * (MERKLE_DEPTH - self.depth) as u32;
+        let start = (self.path as u32) << shift;
+        start..start + (1u32 << shift)
+    }
+}
+
+/// Count and hash of one subtree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleNode {
+    /// Position of the subtree.
+    pub prefix: MerklePrefix,
+    /// Number of IDs under it.
+    pub count: u64,
+    /// Hex-encoded subtree hash (all zeros when empty).
+    pub hash: String,
+}
+
+/// Incrementally maintained Merkle summary of a set of Polyp IDs.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleSummary {
+    /// Leaf path -> IDs in that leaf.
+    leaves: BTreeMap<u16, BTreeSet<Uuid>>,
+    /// Non-empty subtree -> (hash, count).
+    nodes: HashMap<MerklePrefix, ([u8; 32], u64)>,
+}
+
+impl MerkleSummary {
+    /// Create an empty summary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a summary over `ids`, hashing each node once.
+    pub fn from_ids<'a>(ids: impl IntoIterator<Item = &'a Uuid>) -> Self {
+        let mut summary = Self::new();
+        for id in ids {
+            summary.leaves.entry(leaf_of(id)).or_default().insert(*id);
+        }
+
+        let mut level: BTreeSet<MerklePrefix> = BTreeSet::new();
+        for (&path, ids) in &summary.leaves {
+            let prefix = MerklePrefix {
+                depth: MERKLE_DEPTH,
+                path,
+            };
+            summary.nodes.insert(prefix, leaf_entry(ids));
+            level.insert(prefix.parent());
+        }
+        while let Some(depth) = level.first().map(|p| p.depth) {
+            let mut parents = BTreeSet::new();
+            for prefix in level {
+                let entry = summary.inner_entry(prefix);
+                summary.set_node(prefix, entry);
+                if depth > 0 {
+                    parents.insert(prefix.parent());
+                }
+            }
+            level = parents;
+        }
+        summary
+    }
+
+    /// Number of IDs.
+    pub fn len(&self) -> usize {
+        self.nodes.get(&MerklePrefix::ROOT).map_or(0, |&(_, c)| c as usize)
+    }
+
+    /// True if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add an ID. Returns `false` if it was already present.
+    pub fn insert(&mut self, id: Uuid) -> bool {
+        let leaf = leaf_of(&id);
+        if !self.leaves.entry(leaf).or_default().insert(id) {
+            return false;
+        }
+        self.rehash(leaf);
+        true
+    }
+
+    /// Remove an ID. Returns `false` if it was absent.
+    pub fn remove(&mut self, id: &Uuid) -> bool {
+        let leaf = leaf_of(id);
+        let removed = match self.leaves.get_mut(&leaf) {
+            Some(ids) => ids.remove(id),
+            None => false,
+        };
+        if removed {
+            if self.leaves.get(&leaf).is_some_and(BTreeSet::is_empty) {
+                self.leaves.remove(&leaf);
+            }
+            self.rehash(leaf);
+        }
+        removed
+    }
+
+    /// The root node.
+    pub fn root(&self) -> MerkleNode {
+        self.node(MerklePrefix::ROOT)
+    }
+
+    /// The node at `prefix` (empty if nothing is stored under it).
+    pub fn node(&self, prefix: MerklePrefix) -> MerkleNode {
+        let (hash, count) = self.nodes.get(&prefix).copied().unwrap_or(([0; 32], 0));
+        MerkleNode {
+            prefix,
+            count,
+            hash: hash.iter().map(|b| format!("{:02x}", b)).collect(),
+        }
+    }
+
+    /// All IDs under `prefix`, sorted by leaf then ID.
+    pub fn ids_under(&self, prefix: MerklePrefix) -> Vec<Uuid> {
+        let range = prefix.leaf_range();
+        self.leaves
+            .range(range.start as u16..=(range.end - 1) as u16)
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect()
+    }
+
+    /// Recompute the hashes from `leaf` up to the root.
+    fn rehash(&mut self, leaf: u16) {
+        let mut prefix = MerklePrefix {
+            depth: MERKLE_DEPTH,
+            path: leaf,
+        };
+        let entry = match self.leaves.get(&leaf) {
+            Some(ids) if !ids.is_empty() => Some(leaf_entry(ids)),
+            _ => None,
+        };
+        self.set_node(prefix, entry);
+
+        while prefix.depth > 0 {
+            prefix = prefix.parent();
+            let entry = self.inner_entry(prefix);
+            self.set_node(prefix, entry);
+        }
+    }
+
+    /// Hash and count of an inner node from its children; `None` if empty.
+    fn inner_entry(&self, prefix: MerklePrefix) -> Option<([u8; 32], u64)> {
+        let mut bytes = Vec::with_capacity(MERKLE_FANOUT * 32);
+        let mut count = 0;
+        for child in prefix.children() {
+            let (hash, c) = self.nodes.get(&child).copied().unwrap_or(([0; 32], 0));
+            bytes.extend_from_slice(&hash);
+            count += c;
+        }
+        if count > 0 {
+            Some((hash_bytes(&bytes), count))
+        } else {
+            None
+        }
+    }
+
+    fn set_node(&mut self, prefix: MerklePrefix, entry: Option<([u8; 32], u64)>) {
+        match entry {
+            Some(entry) => {
+                self.nodes.insert(prefix, entry);
+            }
+            None => {
+                self.nodes.remove(&prefix);
+            }
+        }
+    }
+}
+
+/// Hash and count of a non-empty leaf.
+fn leaf_entry(ids: &BTreeSet<Uuid>) -> ([u8; 32], u64) {
+    let bytes: Vec<u8> = ids.iter().flat_map(|id| *id.as_bytes()).collect();
+    (hash_bytes(&bytes), ids.len() as u64)
+}
+
+/// Leaf path of an ID: the top `MERKLE_DEPTH` nibbles of its hash.
+fn leaf_of(id: &Uuid) -> u16 {
+    let hash = hash_bytes(id.as_bytes());
+    u16::from_be_bytes([hash[0], hash[1]]) >> (16 - BITS_PER_LEVEL as u32 * MERKLE_DEPTH as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(n: usize) -> Vec<Uuid> {
+        (0..n).map(|_| Uuid::now_v7()).collect()
+    }
+
+    #[test]
+    fn root_is_independent_of_insertion_order() {
+        let ids = ids(300);
+        let forward = MerkleSummary::from_ids(&ids);
+        let mut incremental = MerkleSummary::new();
+        for id in ids.iter().rev() {
+            incremental.insert(*id);
+        }
+        assert_eq!(forward.root(), incremental.root());
+        let inner = MerklePrefix { depth: 2, path: 7 };
+        assert_eq!(forward.node(inner), incremental.node(inner));
+        assert_eq!(forward.len(), 300);
+        assert_ne!(forward.root(), MerkleSummary::new().root());
+    }
+
+    #[test]
+    fn insert_then_remove_restores_root() {
+        let ids = ids(50);
+        let mut summary = MerkleSummary::from_ids(&ids);
+        let before = summary.root();
+
+        let extra = Uuid::now_v7();
+        assert!(summary.insert(extra));
+        assert!(!summary.insert(extra));
+        assert_ne!(summary.root(), before);
+        assert!(summary.remove(&extra));
+        assert!(!summary.remove(&extra));
+        assert_eq!(summary.root(), before);
+    }
+
+    #[test]
+    fn differences_are_confined_to_one_path() {
+        let shared = ids(1000);
+        let a = MerkleSummary::from_ids(&shared);
+        let mut b = a.clone();
+        let extra = Uuid::now_v7();
+        b.insert(extra);
+
+        let leaf = MerklePrefix {
+            depth: MERKLE_DEPTH,
+            path: leaf_of(&extra),
+        };
+        let differing: Vec<MerklePrefix> = MerklePrefix::ROOT
+            .children()
+            .into_iter()
+            .filter(|p| a.node(*p) != b.node(*p))
+            .collect();
+        assert_eq!(differing.len(), 1);
+        assert_eq!(differing[0].path, leaf.path >> 12);
+        assert!(b.ids_under(leaf).contains(&extra));
+        assert_eq!(b.ids_under(MerklePrefix::ROOT).len(), 1001);
+    }
+
+    #[test]
+    fn prefix_validity() {
+        assert!(MerklePrefix::ROOT.is_valid());
+        assert!(MerklePrefix { depth: 1, path: 15 }.is_valid());
+        assert!(!MerklePrefix { depth: 1, path: 16 }.is_valid());
+        assert!(!MerklePrefix { depth: MERKLE_DEPTH + 1, path: 0 }.is_valid());
+        assert_eq!(MerklePrefix::ROOT.children().len(), MERKLE_FANOUT);
+        assert!(MerklePrefix { depth: MERKLE_DEPTH, path: 0 }.children().is_empty());
+    }
+}
@@ -0,0 +1,485 @@
+// crates/chitin-store/src/migration.rs
+//
+// Versioned schema migrations for `RocksStore`.
+//
+// A store records its schema version under `schema:version` (absent: 0, a
+// store from before migrations). Each `Migration` in `MIGRATIONS` takes a
+// store from `version - 1` to `version`, reading at most
+// `MIGRATION_BATCH_SIZE` keys per batch and returning the writes they need
+// without applying them. `migrate` applies each batch in one atomic write
+// together with a resume cursor (`schema:cursor:{version}`), and the last
+// batch with the new version, so a node stopped mid-migration resumes where
+// it left off and never records a version whose migration is incomplete.
+// `plan` runs the same batches without writing, as a dry run.
+//
+// Migrations run while the store is open but before the node serves reads
+// or writes; a store written by a newer build is refused rather than
+// downgraded.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use chitin_core::error::ChitinError;
+use chitin_core::polyp::Polyp;
+
+use crate::rocks::{KeyValue, RocksStore};
+
+/// Key holding a store's schema version.
+pub const SCHEMA_VERSION_KEY: &[u8] = b"schema:version";
+
+/// Prefix of the resume cursors, followed by the migration's version.
+const CURSOR_PREFIX: &str = "schema:cursor:";
+
+/// Most keys a migration reads per batch.
+pub const MIGRATION_BATCH_SIZE: usize = 1024;
+
+/// The writes one batch of a migration needs, and where the next starts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationBatch {
+    /// Keys read.
+    pub scanned: u64,
+    /// Entries to write.
+    pub puts: Vec<KeyValue>,
+    /// Keys to delete.
+    pub deletes: Vec<Vec<u8>>,
+    /// Key the next batch starts at, or `None` if this was the last.
+    pub next: Option<Vec<u8>>,
+}
+
+impl MigrationBatch {
+    fn changes(&self) -> u64 {
+        (self.puts.len() + self.deletes.len()) as u64
+    }
+}
+
+/// Reads one batch of a migration: at most `limit` keys from the cursor
+/// (`None`: the beginning). Must not write, and must be safe to repeat, as
+/// the batch in flight when a node stops is read again on restart.
+pub type MigrationStep =
+    fn(&RocksStore, Option<&[u8]>, usize) -> Result<MigrationBatch, ChitinError>;
+
+/// One schema change, from `version - 1` to `version`.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub step: MigrationStep,
+}
+
+/// Every migration, in version order. Append only: a released migration is
+/// never changed or removed.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "Rebuild the Polyp state index",
+    step: rebuild_state_index,
+}];
+
+/// A pending (or applied) migration and the keys it reads and changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationSummary {
+    pub version: u32,
+    pub description: String,
+    /// Keys read.
+    pub scanned: u64,
+    /// Entries written or deleted.
+    pub changes: u64,
+}
+
+/// The migrations between a store's schema version and this build's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    /// The store's schema version.
+    pub current: u32,
+    /// The schema version of this build.
+    pub target: u32,
+    /// Migrations from `current` to `target`, in order.
+    pub steps: Vec<MigrationSummary>,
+}
+
+impl MigrationPlan {
+    /// Whether the store is already at this build's schema version.
+    pub fn is_current(&self) -> bool {
+        self.current == self.target
+    }
+}
+
+/// Progress of a running migration, reported after every batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationProgress {
+    pub version: u32,
+    pub description: &'static str,
+    /// Keys read so far.
+    pub scanned: u64,
+    /// Entries written or deleted so far.
+    pub changes: u64,
+    /// Whether the migration has finished.
+    pub done: bool,
+}
+
+/// The schema version this build writes.
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// The schema version `store` is at (0 if it has none).
+pub fn schema_version(store: &RocksStore) -> Result<u32, ChitinError> {
+    let Some(bytes) = store.get_bytes(SCHEMA_VERSION_KEY)? else {
+        return Ok(0);
+    };
+    std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| {
+            ChitinError::Storage(format!(
+                "Unreadable schema version {:?}",
+                String::from_utf8_lossy(&bytes)
+            ))
+        })
+}
+
+/// Dry run: the migrations `store` needs and what each would change,
+/// counted against the store as it is now (later migrations do not see
+/// earlier ones' changes). Writes nothing.
+pub fn plan(store: &RocksStore) -> Result<MigrationPlan, ChitinError> {
+    plan_with(store, MIGRATIONS)
+}
+
+/// Apply the migrations `store` needs, calling `progress` after every
+/// batch, and return what was applied.
+pub fn migrate(
+    store: &RocksStore,
+    progress: impl FnMut(&MigrationProgress),
+) -> Result<MigrationPlan, ChitinError> {
+    migrate_with(store, MIGRATIONS, progress)
+}
+
+fn cursor_key(version: u32) -> Vec<u8> {
+    format!("{}{}", CURSOR_PREFIX, version).into_bytes()
+}
+
+/// The store's version and the migrations after it, checking that
+/// `migrations` are numbered 1, 2, ... and the store is not newer.
+fn pending<'a>(
+    store: &RocksStore,
+    migrations: &'a [Migration],
+) -> Result<(u32, &'a [Migration]), ChitinError> {
+    for (i, migration) in migrations.iter().enumerate() {
+        if migration.version as usize != i + 1 {
+            return Err(ChitinError::InvalidState(format!(
+                "Migration {} is out of order; expected version {}",
+                migration.version,
+                i + 1
+            )));
+        }
+    }
+    let current = schema_version(store)?;
+    let target = migrations.len() as u32;
+    if current > target {
+        return Err(ChitinError::InvalidState(format!(
+            "Store schema version {} is newer than this build supports ({}); upgrade the node",
+            current, target
+        )));
+    }
+    Ok((current, &migrations[current as usize..]))
+}
+
+fn plan_with(store: &RocksStore, migrations: &[Migration]) -> Result<MigrationPlan, ChitinError> {
+    let (current, pending) = pending(store, migrations)?;
+    let mut steps = Vec::with_capacity(pending.len());
+    for migration in pending {
+        let mut summary = MigrationSummary {
+            version: migration.version,
+            description: migration.description.to_string(),
+            scanned: 0,
+            changes: 0,
+        };
+        let mut cursor = store.get_bytes(&cursor_key(migration.version))?;
+        loop {
+            let batch = (migration.step)(store, cursor.as_deref(), MIGRATION_BATCH_SIZE)?;
+            summary.scanned += batch.scanned;
+            summary.changes += batch.changes();
+            match batch.next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        steps.push(summary);
+    }
+    Ok(MigrationPlan {
+        current,
+        target: migrations.len() as u32,
+        steps,
+    })
+}
+
+fn migrate_with(
+    store: &RocksStore,
+    migrations: &[Migration],
+    mut progress: impl FnMut(&MigrationProgress),
+) -> Result<MigrationPlan, ChitinError> {
+    let (current, pending) = pending(store, migrations)?;
+    let mut steps = Vec::with_capacity(pending.len());
+    for migration in pending {
+        let cursor_key = cursor_key(migration.version);
+        let mut cursor = store.get_bytes(&cursor_key)?;
+        let mut report = MigrationProgress {
+            version: migration.version,
+            description: migration.description,
+            scanned: 0,
+            changes: 0,
+            done: false,
+        };
+        loop {
+            let batch = (migration.step)(store, cursor.as_deref(), MIGRATION_BATCH_SIZE)?;
+            report.scanned += batch.scanned;
+            report.changes += batch.changes();
+            report.done = batch.next.is_none();
+
+            let (mut puts, mut deletes) = (batch.puts, batch.deletes);
+            match &batch.next {
+                Some(next) => puts.push((cursor_key.clone(), next.clone())),
+                None => {
+                    deletes.push(cursor_key.clone());
+                    puts.push((
+                        SCHEMA_VERSION_KEY.to_vec(),
+                        migration.version.to_string().into_bytes(),
+                    ));
+                }
+            }
+            store.write_entries(&puts, &deletes)?;
+            progress(&report);
+
+            match batch.next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        steps.push(MigrationSummary {
+            version: migration.version,
+            description: migration.description.to_string(),
+            scanned: report.scanned,
+            changes: report.changes,
+        });
+    }
+    if !steps.is_empty() {
+        store.flush()?;
+    }
+    Ok(MigrationPlan {
+        current,
+        target: migrations.len() as u32,
+        steps,
+    })
+}
+
+/// The smallest key after `key`, to resume a scan past it.
+fn successor(key: &[u8]) -> Vec<u8> {
+    let mut next = key.to_vec();
+    next.push(0);
+    next
+}
+
+/// Version 1: make the `state:` index match the stored Polyps. Older
+/// builds wrote a Polyp and its index entries separately, so a crash could
+/// leave a Polyp unindexed or indexed under a state it has left.
+///
+/// Scans `polyp:` first, adding missing entries, then `state:`, deleting
+/// entries whose Polyp is gone or in another state; the cursor's prefix
+/// says which scan it is in.
+fn rebuild_state_index(
+    store: &RocksStore,
+    cursor: Option<&[u8]>,
+    limit: usize,
+) -> Result<MigrationBatch, ChitinError> {
+    const POLYPS: &[u8] = b"polyp:";
+    const STATES: &[u8] = b"state:";
+
+    let mut batch = MigrationBatch::default();
+    if cursor.is_none_or(|c| c.starts_with(POLYPS)) {
+        let entries = store.scan_prefix_from(POLYPS, cursor, limit)?;
+        for (key, value) in &entries {
+            batch.scanned += 1;
+            let polyp: Polyp = serde_json::from_slice(value).map_err(|e| {
+                ChitinError::Serialization(format!(
+                    "Unreadable {}: {}",
+                    String::from_utf8_lossy(key),
+                    e
+                ))
+            })?;
+            let index_key = RocksStore::state_key(&polyp.state, &polyp.id);
+            if store.get_bytes(&index_key)?.is_none() {
+                batch.puts.push((index_key, Vec::new()));
+            }
+        }
+        batch.next = Some(match entries.last() {
+            Some((key, _)) if entries.len() == limit => successor(key),
+            _ => STATES.to_vec(),
+        });
+        return Ok(batch);
+    }
+
+    let entries = store.scan_prefix_from(STATES, cursor, limit)?;
+    for (key, _) in &entries {
+        batch.scanned += 1;
+        let id = std::str::from_utf8(key)
+            .ok()
+            .and_then(|k| k.rsplit(':').next())
+            .and_then(|id| Uuid::parse_str(id).ok());
+        let indexed = match id {
+            Some(id) => store
+                .get_polyp_sync(&id)?
+                .is_some_and(|polyp| RocksStore::state_key(&polyp.state, &id) == *key),
+            None => false,
+        };
+        if !indexed {
+            batch.deletes.push(key.clone());
+        }
+    }
+    batch.next = match entries.last() {
+        Some((key, _)) if entries.len() == limit => Some(successor(key)),
+        _ => None,
+    };
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_store() -> RocksStore {
+        RocksStore::open_in_memory(&format!("chitin_migration_{}", Uuid::now_v7())).unwrap()
+    }
+
+    /// Test migration: copy every `old:` entry to `new:`, two keys per batch.
+    fn copy_old_to_new(
+        store: &RocksStore,
+        cursor: Option<&[u8]>,
+        _limit: usize,
+    ) -> Result<MigrationBatch, ChitinError> {
+        let entries = store.scan_prefix_from(b"old:", cursor, 2)?;
+        let mut batch = MigrationBatch {
+            scanned: entries.len() as u64,
+            ..MigrationBatch::default()
+        };
+        for (key, value) in &entries {
+            batch
+                .puts
+                .push(([b"new:", &key[4..]].concat(), value.clone()));
+        }
+        if entries.len() == 2 {
+            batch.next = Some(successor(&entries[1].0));
+        }
+        Ok(batch)
+    }
+
+    /// Test migration: fail on the first batch.
+    fn fail(_: &RocksStore, _: Option<&[u8]>, _: usize) -> Result<MigrationBatch, ChitinError> {
+        Err(ChitinError::Storage("disk on fire".to_string()))
+    }
+
+    const COPY: &[Migration] = &[Migration {
+        version: 1,
+        description: "Copy old to new",
+        step: copy_old_to_new,
+    }];
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let store = memory_store();
+        for i in 0..5 {
+            store
+                .put_bytes(format!("old:{}", i).as_bytes(), b"v")
+                .unwrap();
+        }
+        let plan = plan_with(&store, COPY).unwrap();
+        assert_eq!((plan.current, plan.target), (0, 1));
+        assert_eq!(plan.steps[0].scanned, 5);
+        assert_eq!(plan.steps[0].changes, 5);
+        assert!(store.get_bytes(b"new:0").unwrap().is_none());
+        assert_eq!(schema_version(&store).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_migrate_applies_batches_and_records_version() {
+        let store = memory_store();
+        for i in 0..5 {
+            store
+                .put_bytes(format!("old:{}", i).as_bytes(), b"v")
+                .unwrap();
+        }
+        let mut reports = Vec::new();
+        let applied = migrate_with(&store, COPY, |p| reports.push(*p)).unwrap();
+        assert_eq!(applied.steps[0].changes, 5);
+        assert_eq!(reports.len(), 3);
+        assert!(reports.last().unwrap().done);
+        assert_eq!(store.scan_prefix(b"new:").unwrap().len(), 5);
+        assert_eq!(schema_version(&store).unwrap(), 1);
+        assert!(store.get_bytes(&cursor_key(1)).unwrap().is_none());
+
+        // Nothing left to do.
+        assert!(plan_with(&store, COPY).unwrap().is_current());
+        let again = migrate_with(&store, COPY, |_| panic!("no batches expected")).unwrap();
+        assert!(again.steps.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_resumes_from_cursor() {
+        let store = memory_store();
+        for i in 0..4 {
+            store
+                .put_bytes(format!("old:{}", i).as_bytes(), b"v")
+                .unwrap();
+        }
+        // As if the node stopped after the first batch.
+        store.put_bytes(&cursor_key(1), b"old:2").unwrap();
+        let applied = migrate_with(&store, COPY, |_| {}).unwrap();
+        assert_eq!(applied.steps[0].scanned, 2);
+        assert!(store.get_bytes(b"new:1").unwrap().is_none());
+        assert!(store.get_bytes(b"new:3").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_failed_migration_keeps_version() {
+        let store = memory_store();
+        let migrations = [
+            COPY[0],
+            Migration {
+                version: 2,
+                description: "Fail",
+                step: fail,
+            },
+        ];
+        assert!(migrate_with(&store, &migrations, |_| {}).is_err());
+        assert_eq!(schema_version(&store).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_newer_or_misnumbered_schema_is_refused() {
+        let store = memory_store();
+        store.put_bytes(SCHEMA_VERSION_KEY, b"7").unwrap();
+        let err = plan_with(&store, COPY).unwrap_err();
+        assert!(err.to_string().contains("newer"), "{}", err);
+
+        let skipped = [Migration {
+            version: 2,
+            ..COPY[0]
+        }];
+        let store = memory_store();
+        assert!(plan_with(&store, &skipped).is_err());
+    }
+
+    #[test]
+    fn test_state_index_rebuild_drops_orphaned_entries() {
+        let store = memory_store();
+        let orphan = format!("state:draft:{}", Uuid::now_v7());
+        store.put_bytes(orphan.as_bytes(), b"").unwrap();
+        store.put_bytes(b"state:draft:not-a-uuid", b"").unwrap();
+
+        let plan = plan(&store).unwrap();
+        assert_eq!(plan.target, latest_version());
+        assert_eq!(plan.steps[0].changes, 2);
+
+        migrate(&store, |_| {}).unwrap();
+        assert!(store.scan_prefix(b"state:").unwrap().is_empty());
+        assert_eq!(schema_version(&store).unwrap(), latest_version());
+    }
+}
@@ -5,36 +5,243 @@
 // Key format:
 //   - Primary:   `polyp:{uuid}` -> JSON-serialized Polyp
 //   - Secondary: `state:{state_tag}:{uuid}` -> empty value (index only)
+//   - Identities: `identity:{uid}` -> JSON-serialized `IdentityRecord`
 //
 // The secondary index allows efficient listing of Polyps by lifecycle state
-// without scanning the entire keyspace.
+// without scanning the entire keyspace. A Merkle summary of all stored Polyp
+// IDs is rebuilt from the primary keys on open and updated on every save and
+// delete, for cheap set comparison with peers.
+//
+// A Polyp's primary key and state index entry are written in one atomic
+// batch, so a crash never leaves a Polyp indexed under a stale state. How
+// soon writes reach stable storage is set by the store's `Durability`: every
+// write synced, the write-ahead log synced periodically by the caller (and
+// at `flush`), or syncing left to the OS. Every mode survives a process
+// crash; only `Sync` also survives power loss without an explicit `flush`.
+//
+// `backup_to` copies the whole keyspace into a new database from a single
+// iterator, which reads one consistent view while writes continue.
+//
+// `health` samples RocksDB's write-stall and memtable properties, which
+// admission control uses to push back on writers before compaction falls
+// behind.
+
+use std::path::Path;
+use std::sync::{RwLock, RwLockWriteGuard};
 
 use async_trait::async_trait;
-use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use rocksdb::{
+    DBWithThreadMode, Direction, Env, IteratorMode, MultiThreaded, Options, WriteBatch,
+    WriteOptions,
+};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use chitin_core::error::ChitinError;
 use chitin_core::polyp::{Polyp, PolypState};
-use chitin_core::traits::PolypStore;
+use chitin_core::registry::IdentityRecord;
+use chitin_core::traits::{IdentityStore, PolypStore};
+
+use crate::merkle::MerkleSummary;
+
+/// A raw (key, value) pair returned by prefix scans.
+pub type KeyValue = (Vec<u8>, Vec<u8>);
+
+/// Entries deleted by pruning, and the bytes their keys and values held.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reclaimed {
+    /// Entries deleted.
+    pub entries: u64,
+    /// Bytes of key and value data deleted.
+    pub bytes: u64,
+}
+
+impl Reclaimed {
+    fn entry(key: &[u8], value: &[u8]) -> Self {
+        Self {
+            entries: 1,
+            bytes: (key.len() + value.len()) as u64,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Reclaimed {
+    fn add_assign(&mut self, other: Self) {
+        self.entries += other.entries;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Entries copied by a backup, and the bytes their keys and values hold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupStats {
+    /// Entries copied.
+    pub entries: u64,
+    /// Bytes of key and value data copied.
+    pub bytes: u64,
+}
+
+/// Entries written per batch while copying a backup.
+const BACKUP_BATCH_SIZE: usize = 1024;
+
+/// When writes reach stable storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Durability {
+    /// Sync the write-ahead log on every write.
+    Sync,
+    /// Sync the write-ahead log every `interval_ms` (the caller calls
+    /// `flush` on that schedule), and at epoch boundaries and hardening
+    /// commits.
+    Periodic {
+        /// Milliseconds between syncs.
+        #[serde(default = "default_flush_interval_ms")]
+        interval_ms: u64,
+    },
+    /// Sync only at explicit flushes; otherwise leave it to the OS.
+    Relaxed,
+}
+
+impl Default for Durability {
+    fn default() -> Self {
+        Self::Periodic {
+            interval_ms: default_flush_interval_ms(),
+        }
+    }
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1000
+}
+
+/// Write-path health of a store, from RocksDB's internal properties.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreHealth {
+    /// RocksDB has stopped writes until compaction or flushes catch up.
+    pub write_stopped: bool,
+    /// Rate writes are slowed to, in bytes per second (0 if not delayed).
+    pub delayed_write_rate: u64,
+    /// Bytes held in active and immutable memtables.
+    pub memtable_bytes: u64,
+    /// Memtables waiting to be flushed.
+    pub immutable_memtables: u64,
+    /// Bytes compaction must rewrite to bring every level under target.
+    pub pending_compaction_bytes: u64,
+    /// Compactions running now.
+    pub running_compactions: u64,
+}
 
 /// RocksDB wrapper implementing the `PolypStore` trait.
 #[derive(Debug)]
 pub struct RocksStore {
     db: DBWithThreadMode<MultiThreaded>,
+    /// Merkle summary of the IDs under `polyp:`.
+    merkle: RwLock<MerkleSummary>,
+    /// When writes reach stable storage.
+    durability: Durability,
 }
 
 impl RocksStore {
-    /// Open a RocksDB database at the given filesystem path.
+    /// Open a RocksDB database at the given filesystem path, with the
+    /// default durability.
     ///
     /// Creates the database directory if it does not exist.
     pub fn open(path: &str) -> Result<Self, ChitinError> {
+        Self::open_with_durability(path, Durability::default())
+    }
+
+    /// Open a RocksDB database at `path`, syncing writes per `durability`.
+    pub fn open_with_durability(path: &str, durability: Durability) -> Result<Self, ChitinError> {
         let mut opts = Options::default();
         opts.create_if_missing(true);
+        Self::open_with_options(&opts, path, durability)
+    }
+
+    /// Open a database held in RocksDB's in-memory environment, for tests
+    /// and simulations. Nothing reaches the disk, and the data is gone once
+    /// the store is dropped; `name` only labels the database.
+    pub fn open_in_memory(name: &str) -> Result<Self, ChitinError> {
+        let env = Env::mem_env()
+            .map_err(|e| ChitinError::Storage(format!("Failed to create memory env: {}", e)))?;
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_env(&env);
+        Self::open_with_options(&opts, name, Durability::default())
+    }
 
-        let db = DBWithThreadMode::<MultiThreaded>::open(&opts, path)
+    fn open_with_options(
+        opts: &Options,
+        path: &str,
+        durability: Durability,
+    ) -> Result<Self, ChitinError> {
+        let db = DBWithThreadMode::<MultiThreaded>::open(opts, path)
             .map_err(|e| ChitinError::Storage(format!("Failed to open RocksDB at {}: {}", path, e)))?;
 
-        Ok(Self { db })
+        let store = Self {
+            db,
+            merkle: RwLock::new(MerkleSummary::new()),
+            durability,
+        };
+        store.rebuild_merkle()?;
+        Ok(store)
+    }
+
+    /// When this store's writes reach stable storage.
+    pub fn durability(&self) -> Durability {
+        self.durability
+    }
+
+    /// Sync the write-ahead log, making every write so far durable.
+    pub fn flush(&self) -> Result<(), ChitinError> {
+        self.db
+            .flush_wal(true)
+            .map_err(|e| ChitinError::Storage(format!("RocksDB WAL sync failed: {}", e)))
+    }
+
+    /// Sample the store's write-stall and memtable properties.
+    pub fn health(&self) -> Result<StoreHealth, ChitinError> {
+        let property = |name: &str| {
+            self.db
+                .property_int_value(name)
+                .map(Option::unwrap_or_default)
+                .map_err(|e| ChitinError::Storage(format!("Failed to read {}: {}", name, e)))
+        };
+        Ok(StoreHealth {
+            write_stopped: property("rocksdb.is-write-stopped")? != 0,
+            delayed_write_rate: property("rocksdb.actual-delayed-write-rate")?,
+            memtable_bytes: property("rocksdb.cur-size-all-mem-tables")?,
+            immutable_memtables: property("rocksdb.num-immutable-mem-table")?,
+            pending_compaction_bytes: property("rocksdb.estimate-pending-compaction-bytes")?,
+            running_compactions: property("rocksdb.num-running-compactions")?,
+        })
+    }
+
+    /// The Merkle summary of stored Polyp IDs.
+    pub fn merkle(&self) -> &RwLock<MerkleSummary> {
+        &self.merkle
+    }
+
+    fn merkle_mut(&self) -> RwLockWriteGuard<'_, MerkleSummary> {
+        self.merkle.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Rebuild the Merkle summary from the primary keys.
+    fn rebuild_merkle(&self) -> Result<(), ChitinError> {
+        let prefix = b"polyp:";
+        let mut ids = Vec::new();
+        for item in self.db.prefix_iterator(prefix) {
+            let (key, _value) = item
+                .map_err(|e| ChitinError::Storage(format!("RocksDB iteration error: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            let uuid_str = std::str::from_utf8(&key[prefix.len()..]).unwrap_or("");
+            if let Ok(id) = Uuid::parse_str(uuid_str) {
+                ids.push(id);
+            }
+        }
+        *self.merkle_mut() = MerkleSummary::from_ids(&ids);
+        Ok(())
     }
 
     /// Build the primary key for a Polyp: `polyp:{uuid}`.
@@ -43,17 +250,31 @@ impl RocksStore {
     }
 
     /// Build the secondary index key: `state:{tag}:{uuid}`.
-    fn state_key(state: &PolypState, id: &Uuid) -> Vec<u8> {
+    pub(crate) fn state_key(state: &PolypState, id: &Uuid) -> Vec<u8> {
         format!("state:{}:{}", state_tag(state), id).into_bytes()
     }
 
+    /// Write options for this store's durability.
+    fn write_options(&self) -> WriteOptions {
+        let mut opts = WriteOptions::new();
+        opts.set_sync(self.durability == Durability::Sync);
+        opts
+    }
+
     /// Put raw bytes into RocksDB, mapping errors to ChitinError::Storage.
     fn put_raw(&self, key: &[u8], value: &[u8]) -> Result<(), ChitinError> {
         self.db
-            .put(key, value)
+            .put_opt(key, value, &self.write_options())
             .map_err(|e| ChitinError::Storage(format!("RocksDB put failed: {}", e)))
     }
 
+    /// Apply `batch` atomically, mapping errors to ChitinError::Storage.
+    fn write_batch(&self, batch: WriteBatch) -> Result<(), ChitinError> {
+        self.db
+            .write_opt(batch, &self.write_options())
+            .map_err(|e| ChitinError::Storage(format!("RocksDB write failed: {}", e)))
+    }
+
     /// Get raw bytes from RocksDB, mapping errors to ChitinError::Storage.
     fn get_raw(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ChitinError> {
         self.db
@@ -64,22 +285,42 @@ impl RocksStore {
     /// Delete a key from RocksDB, mapping errors to ChitinError::Storage.
     fn delete_raw(&self, key: &[u8]) -> Result<(), ChitinError> {
         self.db
-            .delete(key)
+            .delete_opt(key, &self.write_options())
             .map_err(|e| ChitinError::Storage(format!("RocksDB delete failed: {}", e)))
     }
 
-    /// Low-level: store a Polyp with its primary key and secondary state index entry.
-    fn store_polyp_inner(&self, polyp: &Polyp) -> Result<(), ChitinError> {
+    /// Low-level: store a Polyp with its primary key and secondary state
+    /// index entry, removing its index entry under `previous` (its stored
+    /// state, if different) in the same batch.
+    fn store_polyp_inner(
+        &self,
+        polyp: &Polyp,
+        previous: Option<&PolypState>,
+    ) -> Result<(), ChitinError> {
         let json = serde_json::to_vec(polyp)?;
-        self.put_raw(&Self::polyp_key(&polyp.id), &json)?;
+        let mut batch = WriteBatch::default();
+        if let Some(previous) = previous {
+            batch.delete(Self::state_key(previous, &polyp.id));
+        }
+        batch.put(Self::polyp_key(&polyp.id), &json);
         // Write secondary state index (empty value — existence is the signal).
-        self.put_raw(&Self::state_key(&polyp.state, &polyp.id), &[])?;
+        batch.put(Self::state_key(&polyp.state, &polyp.id), []);
+        self.write_batch(batch)?;
+        self.merkle_mut().insert(polyp.id);
         Ok(())
     }
 
-    /// Low-level: remove the secondary state index entry for a Polyp.
-    fn remove_state_index(&self, state: &PolypState, id: &Uuid) -> Result<(), ChitinError> {
-        self.delete_raw(&Self::state_key(state, id))
+    /// Low-level: delete a Polyp's primary key and its index entry under
+    /// `state` in one batch.
+    fn remove_polyp_inner(&self, state: Option<&PolypState>, id: &Uuid) -> Result<(), ChitinError> {
+        let mut batch = WriteBatch::default();
+        if let Some(state) = state {
+            batch.delete(Self::state_key(state, id));
+        }
+        batch.delete(Self::polyp_key(id));
+        self.write_batch(batch)?;
+        self.merkle_mut().remove(id);
+        Ok(())
     }
 
     /// Public accessor: get a Polyp by UUID without going through the async trait.
@@ -98,12 +339,28 @@ impl RocksStore {
     pub fn save_polyp_sync(&self, polyp: &Polyp) -> Result<(), ChitinError> {
         // If the Polyp already exists, remove the old state index entry
         // before writing the new one (the state may have changed).
-        if let Some(existing) = self.get_polyp_sync(&polyp.id)? {
-            if existing.state != polyp.state {
-                self.remove_state_index(&existing.state, &polyp.id)?;
-            }
+        let previous = self
+            .get_polyp_sync(&polyp.id)?
+            .map(|existing| existing.state)
+            .filter(|state| *state != polyp.state);
+        self.store_polyp_inner(polyp, previous.as_ref())
+    }
+
+    /// Overwrite the stored copy of `polyp` with a known-good one, even if
+    /// the stored copy is unreadable, dropping its index entries under every
+    /// other state in the same batch.
+    pub fn repair_polyp(&self, polyp: &Polyp) -> Result<(), ChitinError> {
+        let json = serde_json::to_vec(polyp)?;
+        let current = state_tag(&polyp.state);
+        let mut batch = WriteBatch::default();
+        for tag in STATE_TAGS.iter().filter(|&&tag| tag != current) {
+            batch.delete(format!("state:{}:{}", tag, polyp.id).into_bytes());
         }
-        self.store_polyp_inner(polyp)
+        batch.put(Self::polyp_key(&polyp.id), &json);
+        batch.put(Self::state_key(&polyp.state, &polyp.id), []);
+        self.write_batch(batch)?;
+        self.merkle_mut().insert(polyp.id);
+        Ok(())
     }
 
     /// Store a value under an arbitrary key. Used by `HardenedStore` for CID-indexed entries.
@@ -115,6 +372,158 @@ impl RocksStore {
     pub fn get_bytes(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ChitinError> {
         self.get_raw(key)
     }
+
+    /// Delete a value stored under an arbitrary key.
+    pub fn delete_bytes(&self, key: &[u8]) -> Result<(), ChitinError> {
+        self.delete_raw(key)
+    }
+
+    /// Count Polyps in `state` from the state index, without loading them.
+    pub fn count_polyps_by_state(&self, state: &PolypState) -> Result<usize, ChitinError> {
+        let prefix_str = format!("state:{}:", state_tag(state));
+        let prefix = prefix_str.as_bytes();
+        let mut count = 0;
+        for item in self.db.prefix_iterator(prefix) {
+            let (key, _value) = item
+                .map_err(|e| ChitinError::Storage(format!("RocksDB iteration error: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Delete every entry under `prefix` for which `prune(suffix, value)`
+    /// returns true, where `suffix` is the key with `prefix` removed.
+    pub fn prune_prefix(
+        &self,
+        prefix: &[u8],
+        mut prune: impl FnMut(&[u8], &[u8]) -> bool,
+    ) -> Result<Reclaimed, ChitinError> {
+        let mut reclaimed = Reclaimed::default();
+        for (key, value) in self.scan_prefix(prefix)? {
+            if prune(&key[prefix.len()..], &value) {
+                self.delete_raw(&key)?;
+                reclaimed += Reclaimed::entry(&key, &value);
+            }
+        }
+        Ok(reclaimed)
+    }
+
+    /// Delete a Polyp as `delete_polyp` does, returning the space reclaimed
+    /// (nothing if it is not stored).
+    pub fn prune_polyp(&self, id: &Uuid) -> Result<Reclaimed, ChitinError> {
+        let key = Self::polyp_key(id);
+        let value = match self.get_raw(&key)? {
+            Some(value) => value,
+            None => return Ok(Reclaimed::default()),
+        };
+        let polyp: Polyp = serde_json::from_slice(&value)?;
+        let state_key = Self::state_key(&polyp.state, id);
+        self.remove_polyp_inner(Some(&polyp.state), id)?;
+        let mut reclaimed = Reclaimed::entry(&key, &value);
+        reclaimed += Reclaimed::entry(&state_key, &[]);
+        Ok(reclaimed)
+    }
+
+    /// Copy every entry into a new database at `path`, which must not exist.
+    ///
+    /// The copy is a point-in-time view of the store, synced before this
+    /// returns; it opens with `open` like any other store.
+    pub fn backup_to(&self, path: &str) -> Result<BackupStats, ChitinError> {
+        if Path::new(path).exists() {
+            return Err(ChitinError::Storage(format!(
+                "Backup destination {} already exists",
+                path
+            )));
+        }
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let backup = DBWithThreadMode::<MultiThreaded>::open(&opts, path).map_err(|e| {
+            ChitinError::Storage(format!("Failed to create backup at {}: {}", path, e))
+        })?;
+        let write = |batch: WriteBatch| {
+            backup
+                .write(batch)
+                .map_err(|e| ChitinError::Storage(format!("Backup write failed: {}", e)))
+        };
+
+        let mut stats = BackupStats::default();
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator(IteratorMode::Start) {
+            let (key, value) = item
+                .map_err(|e| ChitinError::Storage(format!("RocksDB iteration error: {}", e)))?;
+            batch.put(&key, &value);
+            stats.entries += 1;
+            stats.bytes += (key.len() + value.len()) as u64;
+            if batch.len() >= BACKUP_BATCH_SIZE {
+                write(std::mem::take(&mut batch))?;
+            }
+        }
+        if !batch.is_empty() {
+            write(batch)?;
+        }
+        backup
+            .flush_wal(true)
+            .map_err(|e| ChitinError::Storage(format!("Backup WAL sync failed: {}", e)))?;
+        Ok(stats)
+    }
+
+    /// Return all (key, value) pairs whose key starts with `prefix`, in key order.
+    ///
+    /// Used by auxiliary stores (e.g., reputation) that keep their own keyspace
+    /// alongside Polyps.
+    pub fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<KeyValue>, ChitinError> {
+        let mut out = Vec::new();
+        for item in self.db.prefix_iterator(prefix) {
+            let (key, value) = item
+                .map_err(|e| ChitinError::Storage(format!("RocksDB iteration error: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            out.push((key.to_vec(), value.to_vec()));
+        }
+        Ok(out)
+    }
+
+    /// Return at most `limit` (key, value) pairs under `prefix`, in key
+    /// order, starting at `start` (inclusive) or the first key of `prefix`.
+    pub fn scan_prefix_from(
+        &self,
+        prefix: &[u8],
+        start: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<KeyValue>, ChitinError> {
+        let from = start.filter(|s| s.starts_with(prefix)).unwrap_or(prefix);
+        let mut out = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(from, Direction::Forward)) {
+            let (key, value) = item
+                .map_err(|e| ChitinError::Storage(format!("RocksDB iteration error: {}", e)))?;
+            if !key.starts_with(prefix) || out.len() == limit {
+                break;
+            }
+            out.push((key.to_vec(), value.to_vec()));
+        }
+        Ok(out)
+    }
+
+    /// Write `puts` and delete `deletes` in one atomic batch. Bypasses the
+    /// Merkle summary, so not for `polyp:` keys.
+    pub(crate) fn write_entries(
+        &self,
+        puts: &[KeyValue],
+        deletes: &[Vec<u8>],
+    ) -> Result<(), ChitinError> {
+        let mut batch = WriteBatch::default();
+        for key in deletes {
+            batch.delete(key);
+        }
+        for (key, value) in puts {
+            batch.put(key, value);
+        }
+        self.write_batch(batch)
+    }
 }
 
 #[async_trait]
@@ -156,11 +565,27 @@ impl PolypStore for RocksStore {
     }
 
     async fn delete_polyp(&self, id: &Uuid) -> Result<(), ChitinError> {
-        // Remove the state index entry first, if the Polyp exists.
-        if let Some(existing) = self.get_polyp_sync(id)? {
-            self.remove_state_index(&existing.state, id)?;
-        }
-        self.delete_raw(&Self::polyp_key(id))
+        // Remove the state index entry too, if the Polyp exists.
+        let state = self.get_polyp_sync(id)?.map(|existing| existing.state);
+        self.remove_polyp_inner(state.as_ref(), id)
+    }
+}
+
+/// Key prefix of identity registry records.
+const IDENTITY_PREFIX: &[u8] = b"identity:";
+
+impl IdentityStore for RocksStore {
+    fn save_identity(&self, record: &IdentityRecord) -> Result<(), ChitinError> {
+        // Zero-padded so records scan in UID order.
+        let key = format!("identity:{:05}", record.uid);
+        self.put_raw(key.as_bytes(), &serde_json::to_vec(record)?)
+    }
+
+    fn load_identities(&self) -> Result<Vec<IdentityRecord>, ChitinError> {
+        self.scan_prefix(IDENTITY_PREFIX)?
+            .into_iter()
+            .map(|(_, value)| Ok(serde_json::from_slice(&value)?))
+            .collect()
     }
 }
 
@@ -168,6 +593,17 @@ impl PolypStore for RocksStore {
 ///
 /// This avoids relying on `Display` or `Debug` which might include variant data
 /// (e.g., `Molted { successor_id: ... }`). We use a stable, compact tag instead.
+/// Every tag `state_tag` returns.
+const STATE_TAGS: &[&str] = &[
+    "draft",
+    "soft",
+    "under_review",
+    "approved",
+    "hardened",
+    "rejected",
+    "molted",
+];
+
 fn state_tag(state: &PolypState) -> &'static str {
     match state {
         PolypState::Draft => "draft",
@@ -199,4 +635,117 @@ mod tests {
             "molted"
         );
     }
+
+    #[test]
+    fn test_state_tags_cover_every_state() {
+        let states = [
+            PolypState::Draft,
+            PolypState::Soft,
+            PolypState::UnderReview,
+            PolypState::Approved,
+            PolypState::Hardened,
+            PolypState::Rejected,
+            PolypState::Molted {
+                successor_id: Uuid::nil(),
+            },
+        ];
+        for state in &states {
+            assert!(STATE_TAGS.contains(&state_tag(state)));
+        }
+        assert_eq!(STATE_TAGS.len(), states.len());
+    }
+
+    #[test]
+    fn test_durability_config_forms() {
+        let parse = |json: &str| serde_json::from_str::<Durability>(json).unwrap();
+        assert_eq!(parse(r#"{"mode":"sync"}"#), Durability::Sync);
+        assert_eq!(parse(r#"{"mode":"relaxed"}"#), Durability::Relaxed);
+        assert_eq!(parse(r#"{"mode":"periodic"}"#), Durability::default());
+        assert_eq!(
+            parse(r#"{"mode":"periodic","interval_ms":250}"#),
+            Durability::Periodic { interval_ms: 250 }
+        );
+    }
+
+    #[test]
+    fn test_backup_copies_every_entry() {
+        let dir = std::env::temp_dir().join(format!("chitin_backup_{}", Uuid::now_v7()));
+        let source_path = dir.join("source").to_string_lossy().to_string();
+        let backup_path = dir.join("backup").to_string_lossy().to_string();
+        let store = RocksStore::open(&source_path).unwrap();
+        for i in 0..(BACKUP_BATCH_SIZE + 10) {
+            store.put_bytes(format!("key:{:05}", i).as_bytes(), b"v").unwrap();
+        }
+
+        let stats = store.backup_to(&backup_path).unwrap();
+        assert_eq!(stats.entries, BACKUP_BATCH_SIZE as u64 + 10);
+        assert_eq!(stats.bytes, stats.entries * 10);
+        // Later writes are not in the backup, and it is never overwritten.
+        store.put_bytes(b"key:later", b"v").unwrap();
+        assert!(store.backup_to(&backup_path).is_err());
+
+        let backup = RocksStore::open(&backup_path).unwrap();
+        assert_eq!(backup.get_bytes(b"key:00000").unwrap(), Some(b"v".to_vec()));
+        assert_eq!(backup.scan_prefix(b"key:").unwrap().len(), BACKUP_BATCH_SIZE + 10);
+        drop((store, backup));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_in_memory_store_leaves_no_files() {
+        let name = std::env::temp_dir()
+            .join(format!("chitin_memory_{}", Uuid::now_v7()))
+            .to_string_lossy()
+            .to_string();
+        let store = RocksStore::open_in_memory(&name).unwrap();
+        store.put_bytes(b"key", b"v").unwrap();
+        store.flush().unwrap();
+        assert_eq!(store.get_bytes(b"key").unwrap(), Some(b"v".to_vec()));
+        assert!(!Path::new(&name).exists());
+
+        // Each in-memory store starts empty, even under the same name.
+        let other = RocksStore::open_in_memory(&name).unwrap();
+        assert_eq!(other.get_bytes(b"key").unwrap(), None);
+    }
+
+    #[test]
+    fn test_health_of_idle_store() {
+        let name = std::env::temp_dir()
+            .join(format!("chitin_health_{}", Uuid::now_v7()))
+            .to_string_lossy()
+            .to_string();
+        let store = RocksStore::open_in_memory(&name).unwrap();
+        store.put_bytes(b"key", b"v").unwrap();
+        let health = store.health().unwrap();
+        assert!(!health.write_stopped);
+        assert_eq!(health.delayed_write_rate, 0);
+        assert_eq!(health.immutable_memtables, 0);
+        assert!(health.memtable_bytes > 0);
+    }
+
+    #[test]
+    fn test_identity_records_round_trip() {
+        use chitin_core::identity::NodeType;
+
+        let name = std::env::temp_dir()
+            .join(format!("chitin_identities_{}", Uuid::now_v7()))
+            .to_string_lossy()
+            .to_string();
+        let store = RocksStore::open_in_memory(&name).unwrap();
+        let record = |uid: u16, hotkey: u8| IdentityRecord {
+            uid,
+            did: format!("did:chitin:{}", uid),
+            coldkey: [uid as u8; 32],
+            hotkey: [hotkey; 32],
+            node_type: NodeType::Tide,
+            registered_epoch: 0,
+        };
+        store.save_identity(&record(10, 1)).unwrap();
+        store.save_identity(&record(2, 2)).unwrap();
+        store.save_identity(&record(2, 3)).unwrap();
+        assert_eq!(
+            store.load_identities().unwrap(),
+            vec![record(2, 3), record(10, 1)]
+        );
+    }
 }
@@ -9,9 +9,23 @@
 // The hash function uses the UUID's raw bytes to compute a stable shard
 // assignment. This ensures that the same Polyp ID always maps to the same
 // shard, regardless of which node computes the assignment.
+//
+// A `ShardSet` is the subset of shards a node holds: its assigned shards
+// plus replicas of the next shards around the ring. Nodes sync and store
+// only Polyps in their set and exchange sets so queries for other shards
+// can be routed to responsible peers.
+//
+// Reef Zones may raise their replication factor: a Polyp in a zone with
+// factor `r` lives on its own shard and the `r - 1` shards after it, so
+// every node holding any of those shards keeps a copy.
 
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use chitin_core::{ChitinError, Polyp};
+
 /// Assigns Polyp IDs to shards using a simple hash-based scheme.
 ///
 /// In Phase 1 this is configured with `num_shards=1`, meaning all Polyps
@@ -55,6 +69,138 @@ impl ShardAssigner {
     }
 }
 
+/// The shards a node holds, out of `num_shards`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardSet {
+    /// Total number of shards in the system.
+    num_shards: u16,
+    /// Held shard indices, each in `[0, num_shards)`.
+    shards: BTreeSet<u16>,
+    /// Replication factors of zones storing Polyps on more than their own
+    /// shard. Subzones inherit their nearest listed ancestor's factor.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    zone_replication: BTreeMap<String, u16>,
+}
+
+impl ShardSet {
+    /// Every shard (a node holding the whole Reef).
+    pub fn all(num_shards: u16) -> Self {
+        Self {
+            num_shards,
+            shards: (0..num_shards).collect(),
+            zone_replication: BTreeMap::new(),
+        }
+    }
+
+    /// The `assigned` shards plus, for each, the `replicas` shards after it
+    /// on the ring. An empty assignment holds every shard.
+    pub fn new(num_shards: u16, assigned: &[u16], replicas: u16) -> Result<Self, ChitinError> {
+        if num_shards == 0 {
+            return Err(ChitinError::InvalidState("num_shards must be > 0".to_string()));
+        }
+        if let Some(&shard) = assigned.iter().find(|&&s| s >= num_shards) {
+            return Err(ChitinError::InvalidState(format!(
+                "Assigned shard {} is out of range for {} shards",
+                shard, num_shards
+            )));
+        }
+        if assigned.is_empty() {
+            return Ok(Self::all(num_shards));
+        }
+
+        let mut shards = BTreeSet::new();
+        for &shard in assigned {
+            for offset in 0..=replicas.min(num_shards - 1) {
+                shards.insert(((shard as u32 + offset as u32) % num_shards as u32) as u16);
+            }
+        }
+        Ok(Self {
+            num_shards,
+            shards,
+            zone_replication: BTreeMap::new(),
+        })
+    }
+
+    /// Replicate Polyps in the given zones (and their subzones) on as many
+    /// shards as their factor.
+    pub fn with_zone_replication(mut self, factors: BTreeMap<String, u16>) -> Self {
+        self.zone_replication = factors;
+        self
+    }
+
+    /// Replication factor for Polyps in `zone` (1 outside listed zones).
+    pub fn replication(&self, zone: Option<&str>) -> u16 {
+        let mut current = match zone {
+            Some(zone) => zone,
+            None => return 1,
+        };
+        loop {
+            if let Some(&factor) = self.zone_replication.get(current) {
+                return factor.max(1);
+            }
+            match current.rsplit_once('/') {
+                Some((parent, _)) => current = parent,
+                None => return 1,
+            }
+        }
+    }
+
+    /// Total number of shards in the system.
+    pub fn num_shards(&self) -> u16 {
+        self.num_shards
+    }
+
+    /// Held shard indices, ascending.
+    pub fn shards(&self) -> &BTreeSet<u16> {
+        &self.shards
+    }
+
+    /// True if every shard is held.
+    pub fn is_full(&self) -> bool {
+        (0..self.num_shards).all(|s| self.shards.contains(&s))
+    }
+
+    /// True if `shard` is held.
+    pub fn contains_shard(&self, shard: u16) -> bool {
+        self.shards.contains(&shard)
+    }
+
+    /// True if the Polyp with `polyp_id` belongs to a held shard.
+    pub fn contains(&self, polyp_id: &Uuid) -> bool {
+        self.holds(polyp_id, 1)
+    }
+
+    /// True if `polyp` is stored here: its shard or, for zones replicated
+    /// further, one of the shards after it is held.
+    pub fn contains_polyp(&self, polyp: &Polyp) -> bool {
+        self.holds(&polyp.id, self.replication(polyp.reef_zone.as_deref()))
+    }
+
+    /// True if the Polyp with `polyp_id` could be stored here, whatever its
+    /// zone. Use to filter IDs before the Polyp itself is known.
+    pub fn may_contain(&self, polyp_id: &Uuid) -> bool {
+        let widest = self.zone_replication.values().copied().max().unwrap_or(1);
+        self.holds(polyp_id, widest)
+    }
+
+    /// True if any of the `replicas` shards starting at the Polyp's own is
+    /// held.
+    fn holds(&self, polyp_id: &Uuid, replicas: u16) -> bool {
+        let n = self.num_shards;
+        if n == 0 {
+            return false;
+        }
+        let primary = ShardAssigner::new(n).assign_shard(polyp_id) as u32;
+        (0..replicas.clamp(1, n) as u32)
+            .any(|offset| self.contains_shard(((primary + offset) % n as u32) as u16))
+    }
+
+    /// Shards not held, ascending.
+    pub fn missing_shards(&self) -> Vec<u16> {
+        (0..self.num_shards).filter(|s| !self.shards.contains(s)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,6 +240,50 @@ mod tests {
         let _ = ShardAssigner::new(0);
     }
 
+    #[test]
+    fn test_shard_set_replicas_wrap_around_ring() {
+        let set = ShardSet::new(8, &[2, 7], 1).unwrap();
+        assert_eq!(set.shards().iter().copied().collect::<Vec<_>>(), vec![0, 2, 3, 7]);
+        assert_eq!(set.missing_shards(), vec![1, 4, 5, 6]);
+        assert!(!set.is_full());
+
+        // Replicas beyond the ring size hold everything once.
+        assert!(ShardSet::new(4, &[1], 10).unwrap().is_full());
+        assert!(ShardSet::new(4, &[], 0).unwrap().is_full());
+        assert!(ShardSet::new(4, &[4], 0).is_err());
+        assert!(ShardSet::new(0, &[], 0).is_err());
+    }
+
+    #[test]
+    fn test_shard_set_membership_matches_assigner() {
+        let assigner = ShardAssigner::new(4);
+        let set = ShardSet::new(4, &[1], 0).unwrap();
+        for _ in 0..200 {
+            let id = Uuid::new_v4();
+            assert_eq!(set.contains(&id), assigner.assign_shard(&id) == 1);
+        }
+        assert!(ShardSet::all(1).contains(&Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_zone_replication_widens_membership() {
+        let factors = BTreeMap::from([("medical".to_string(), 3)]);
+        let set = ShardSet::new(4, &[1], 0).unwrap().with_zone_replication(factors);
+        assert_eq!(set.replication(Some("medical/oncology")), 3);
+        assert_eq!(set.replication(Some("code")), 1);
+        assert_eq!(set.replication(None), 1);
+
+        let assigner = ShardAssigner::new(4);
+        for _ in 0..200 {
+            let id = Uuid::new_v4();
+            let primary = assigner.assign_shard(&id);
+            // Shard 1 replicates shards 3, 0 and 1 for medical Polyps.
+            assert_eq!(set.holds(&id, set.replication(Some("medical"))), primary != 2);
+            assert_eq!(set.may_contain(&id), primary != 2);
+            assert_eq!(set.holds(&id, set.replication(Some("code"))), primary == 1);
+        }
+    }
+
     #[test]
     fn test_distribution_roughly_uniform() {
         let num_shards = 4;
@@ -0,0 +1,27 @@
+// crates/chitin-sync/src/hex.rs
+//
+// Hex encoding for sync structures carried over JSON-RPC.
+
+use chitin_core::ChitinError;
+
+/// Encode bytes as lowercase hex.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hex string; `what` names the payload in error messages.
+pub(crate) fn decode(hex: &str, what: &str) -> Result<Vec<u8>, ChitinError> {
+    if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+        return Err(ChitinError::Serialization(format!(
+            "{} hex must be an even-length ASCII string",
+            what
+        )));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| ChitinError::Serialization(format!("Invalid {} hex: {}", what, e)))
+        })
+        .collect()
+}
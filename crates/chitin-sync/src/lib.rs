@@ -1,12 +1,30 @@
 // crates/chitin-sync/src/lib.rs
 //
-// chitin-sync: Vector Bloom Filters, set reconciliation, and range sync
-// for the Chitin Protocol.
+// chitin-sync: Vector Bloom Filters, set reconciliation, range sync, and
+// Merkle anti-entropy for the Chitin Protocol.
 //
 // This crate enables efficient synchronization of Polyp sets between nodes.
 // Vector Bloom Filters provide compact set summaries, set reconciliation
-// identifies missing Polyps, and range sync handles shard catchup.
+// identifies missing Polyps, range sync handles shard catchup, and Merkle
+// comparison locates divergent subtrees of the stored ID set. Missing
+// Polyps are fetched in priority order (state, epoch, zone subscription),
+// in optionally compressed batches. Large catch-ups persist per-range
+// progress so they resume after a restart. Ingestion from sync and gossip
+// shares a throttle that bounds work in flight and paces writes. State
+// transitions of already-held Polyps propagate as signed state updates.
+// Polyps pruned locally are tombstoned so sync does not pull them back.
+// Per-peer sync metrics are kept in memory for status reporting.
 
 pub mod vbf;
 pub mod reconcile;
 pub mod range;
+pub mod merkle;
+pub mod priority;
+pub mod transfer;
+pub mod progress;
+pub mod throttle;
+pub mod state_update;
+pub mod tombstone;
+pub mod metrics;
+
+mod hex;
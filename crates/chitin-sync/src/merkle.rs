@@ -0,0 +1,210 @@
+// crates/chitin-sync/src/merkle.rs
+//
+// Merkle-summary anti-entropy for the Chitin Protocol.
+//
+// Both peers maintain a `MerkleSummary` of their Polyp IDs (see
+// chitin-store). The requester compares the peer's root with its own; if
+// they match the sets are identical and nothing else is sent. Otherwise it
+// requests the children of every divergent node, level by level, so locating
+// the differing leaves takes at most `MERKLE_DEPTH` round trips. Subtrees
+// that are small on the peer's side are listed directly rather than
+// descended into.
+
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chitin_core::ChitinError;
+use chitin_store::merkle::{MerkleNode, MerklePrefix, MerkleSummary};
+use uuid::Uuid;
+
+/// Divergent subtrees with at most this many remote IDs are listed directly.
+pub const DEFAULT_MERKLE_LIST_THRESHOLD: u64 = 64;
+
+/// Most prefixes a peer will answer in one request.
+pub const MAX_MERKLE_PREFIXES: usize = 1024;
+
+/// Most IDs a peer will return from one `ids` request.
+pub const MAX_MERKLE_IDS: usize = 16_384;
+
+/// The remote side of a Merkle comparison.
+#[async_trait]
+pub trait MerklePeer: Send + Sync {
+    /// Nodes at each of `prefixes` (at most `MAX_MERKLE_PREFIXES`), in order.
+    async fn nodes(&self, prefixes: &[MerklePrefix]) -> Result<Vec<MerkleNode>, ChitinError>;
+
+    /// All IDs under each of `prefixes`.
+    async fn ids(&self, prefixes: &[MerklePrefix]) -> Result<Vec<Uuid>, ChitinError>;
+}
+
+/// Outcome of a Merkle comparison.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleSyncOutcome {
+    /// Remote IDs missing locally.
+    pub missing: Vec<Uuid>,
+    /// Requests sent to the peer.
+    pub round_trips: usize,
+    /// Nodes compared.
+    pub nodes_compared: usize,
+}
+
+/// Locates divergent subtrees between a local and a remote Merkle summary.
+#[derive(Debug, Clone)]
+pub struct MerkleSync {
+    /// Remote subtree size at or below which IDs are listed directly.
+    pub list_threshold: u64,
+}
+
+impl MerkleSync {
+    /// Create a MerkleSync with the default list threshold.
+    pub fn new() -> Self {
+        Self {
+            list_threshold: DEFAULT_MERKLE_LIST_THRESHOLD,
+        }
+    }
+
+    /// Find the peer's IDs missing from `local`.
+    ///
+    /// The local summary is locked only between requests, so saves can
+    /// proceed while the comparison waits on the peer.
+    pub async fn find_missing<P: MerklePeer + ?Sized>(
+        &self,
+        local: &RwLock<MerkleSummary>,
+        peer: &P,
+    ) -> Result<MerkleSyncOutcome, ChitinError> {
+        let mut outcome = MerkleSyncOutcome::default();
+        let mut pending = vec![MerklePrefix::ROOT];
+        let mut listed = Vec::new();
+
+        while !pending.is_empty() {
+            let mut next = Vec::new();
+            for chunk in pending.chunks(MAX_MERKLE_PREFIXES) {
+                let remote = peer.nodes(chunk).await?;
+                outcome.round_trips += 1;
+                let mismatched = remote.iter().zip(chunk).any(|(n, p)| n.prefix != *p);
+                if remote.len() != chunk.len() || mismatched {
+                    return Err(ChitinError::Network(format!(
+                        "Peer returned {} Merkle nodes for {} prefixes",
+                        remote.len(),
+                        chunk.len()
+                    )));
+                }
+                outcome.nodes_compared += remote.len();
+
+                let summary = local.read().unwrap_or_else(|e| e.into_inner());
+                for node in remote {
+                    // Pull-only: subtrees the peer has nothing in need no work.
+                    if node.count == 0 || summary.node(node.prefix) == node {
+                        continue;
+                    }
+                    if node.count <= self.list_threshold || node.prefix.is_leaf() {
+                        listed.push((node.prefix, node.count));
+                    } else {
+                        next.extend(node.prefix.children());
+                    }
+                }
+            }
+            pending = next;
+        }
+
+        let mut missing = HashSet::new();
+        for chunk in id_batches(&listed) {
+            let remote_ids = peer.ids(&chunk).await?;
+            outcome.round_trips += 1;
+            let summary = local.read().unwrap_or_else(|e| e.into_inner());
+            let local_ids: HashSet<Uuid> =
+                chunk.iter().flat_map(|p| summary.ids_under(*p)).collect();
+            missing.extend(remote_ids.into_iter().filter(|id| !local_ids.contains(id)));
+        }
+        outcome.missing = missing.into_iter().collect();
+        outcome.missing.sort();
+
+        Ok(outcome)
+    }
+}
+
+/// Group listed subtrees into requests within the prefix and ID limits.
+fn id_batches(listed: &[(MerklePrefix, u64)]) -> Vec<Vec<MerklePrefix>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut ids = 0;
+    for &(prefix, count) in listed {
+        if !batch.is_empty()
+            && (batch.len() == MAX_MERKLE_PREFIXES || ids + count > MAX_MERKLE_IDS as u64)
+        {
+            batches.push(std::mem::take(&mut batch));
+            ids = 0;
+        }
+        batch.push(prefix);
+        ids += count;
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+impl Default for MerkleSync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// In-process peer backed by a MerkleSummary.
+    struct LocalPeer(MerkleSummary);
+
+    #[async_trait]
+    impl MerklePeer for LocalPeer {
+        async fn nodes(&self, prefixes: &[MerklePrefix]) -> Result<Vec<MerkleNode>, ChitinError> {
+            Ok(prefixes.iter().map(|p| self.0.node(*p)).collect())
+        }
+
+        async fn ids(&self, prefixes: &[MerklePrefix]) -> Result<Vec<Uuid>, ChitinError> {
+            Ok(prefixes.iter().flat_map(|p| self.0.ids_under(*p)).collect())
+        }
+    }
+
+    fn ids(n: usize) -> Vec<Uuid> {
+        (0..n).map(|_| Uuid::now_v7()).collect()
+    }
+
+    #[tokio::test]
+    async fn identical_sets_take_one_round_trip() {
+        let ids = ids(5000);
+        let local = RwLock::new(MerkleSummary::from_ids(&ids));
+        let peer = LocalPeer(MerkleSummary::from_ids(&ids));
+
+        let outcome = MerkleSync::new().find_missing(&local, &peer).await.unwrap();
+        assert!(outcome.missing.is_empty());
+        assert_eq!(outcome.round_trips, 1);
+        assert_eq!(outcome.nodes_compared, 1);
+    }
+
+    #[tokio::test]
+    async fn finds_scattered_missing_ids_in_few_round_trips() {
+        let shared = ids(20_000);
+        let mut extra = ids(25);
+        let local = RwLock::new(MerkleSummary::from_ids(&shared));
+        let peer = LocalPeer(MerkleSummary::from_ids(shared.iter().chain(&extra)));
+
+        let outcome = MerkleSync::new().find_missing(&local, &peer).await.unwrap();
+        extra.sort();
+        assert_eq!(outcome.missing, extra);
+        // Root, up to MERKLE_DEPTH levels of children, then one listing.
+        assert!(outcome.round_trips <= 6, "round trips {}", outcome.round_trips);
+    }
+
+    #[tokio::test]
+    async fn local_only_ids_are_ignored() {
+        let shared = ids(200);
+        let local = RwLock::new(MerkleSummary::from_ids(shared.iter().chain(&ids(10))));
+        let peer = LocalPeer(MerkleSummary::from_ids(&shared));
+
+        let outcome = MerkleSync::new().find_missing(&local, &peer).await.unwrap();
+        assert!(outcome.missing.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+// crates/chitin-sync/src/metrics.rs
+//
+// Live per-peer sync metrics.
+//
+// The sync loop and gossip record what happens with each peer as it happens:
+// the current phase, when the last exchange succeeded, the last error, how
+// many Polyps were pulled from and pushed to the peer, and how many the peer
+// holds that are still missing locally (the node's estimated lag behind it).
+// `sync/status` reports a snapshot. Metrics live in memory only; persisted
+// catch-up progress is tracked separately in `progress`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// What the sync loop is currently doing, overall or with one peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    /// Waiting for the next round.
+    #[default]
+    Idle,
+    /// Comparing Merkle roots or shard assignments.
+    Comparing,
+    /// Working out which Polyps are missing.
+    Negotiating,
+    /// Fetching and storing missing Polyps.
+    Fetching,
+    /// Pulling state transitions of Polyps already held.
+    StateUpdates,
+}
+
+/// Sync metrics for one peer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerSyncStats {
+    /// The peer's URL.
+    pub peer_url: String,
+    /// What the sync loop is doing with this peer.
+    pub phase: SyncPhase,
+    /// When an exchange with the peer last succeeded (Unix ms).
+    pub last_success_ms: Option<u64>,
+    /// The last error talking to the peer, if any since the last success.
+    pub last_error: Option<String>,
+    /// When `last_error` happened (Unix ms).
+    pub last_error_ms: Option<u64>,
+    /// Polyps pulled from the peer and stored.
+    pub polyps_pulled: u64,
+    /// Polyps pushed to the peer by gossip.
+    pub polyps_pushed: u64,
+    /// Polyps the peer holds that are still missing locally.
+    pub missing: u64,
+}
+
+/// A point-in-time view of all sync metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncMetricsSnapshot {
+    /// Overall phase of the sync loop.
+    pub phase: SyncPhase,
+    /// Per-peer metrics, ordered by peer URL.
+    pub peers: Vec<PeerSyncStats>,
+}
+
+impl SyncMetricsSnapshot {
+    /// Estimated lag: the most Polyps missing relative to any one peer.
+    /// Peers largely hold the same Polyps, so counts are not summed.
+    pub fn lag(&self) -> u64 {
+        self.peers.iter().map(|p| p.missing).max().unwrap_or(0)
+    }
+}
+
+/// Shared, thread-safe sync metrics.
+#[derive(Debug, Default)]
+pub struct SyncMetrics {
+    phase: Mutex<SyncPhase>,
+    peers: Mutex<HashMap<String, PeerSyncStats>>,
+}
+
+impl SyncMetrics {
+    /// Create empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the overall phase of the sync loop.
+    pub fn set_phase(&self, phase: SyncPhase) {
+        *self.phase.lock().unwrap_or_else(|e| e.into_inner()) = phase;
+    }
+
+    /// Set the overall phase and the phase with `peer_url`.
+    pub fn begin(&self, peer_url: &str, phase: SyncPhase) {
+        self.set_phase(phase);
+        self.update(peer_url, |stats| stats.phase = phase);
+    }
+
+    /// Mark the peer idle again.
+    pub fn finish(&self, peer_url: &str) {
+        self.update(peer_url, |stats| stats.phase = SyncPhase::Idle);
+    }
+
+    /// Record a successful exchange with the peer, clearing its last error.
+    pub fn record_success(&self, peer_url: &str, now_ms: u64) {
+        self.update(peer_url, |stats| {
+            stats.last_success_ms = Some(now_ms);
+            stats.last_error = None;
+            stats.last_error_ms = None;
+        });
+    }
+
+    /// Record a failed exchange with the peer; the peer becomes idle.
+    pub fn record_error(&self, peer_url: &str, error: &str, now_ms: u64) {
+        self.update(peer_url, |stats| {
+            stats.phase = SyncPhase::Idle;
+            stats.last_error = Some(error.to_string());
+            stats.last_error_ms = Some(now_ms);
+        });
+    }
+
+    /// Set how many of the peer's Polyps are missing locally.
+    pub fn set_missing(&self, peer_url: &str, missing: u64) {
+        self.update(peer_url, |stats| stats.missing = missing);
+    }
+
+    /// Count Polyps pulled from the peer, shrinking its missing count.
+    pub fn record_pulled(&self, peer_url: &str, count: u64) {
+        self.update(peer_url, |stats| {
+            stats.polyps_pulled += count;
+            stats.missing = stats.missing.saturating_sub(count);
+        });
+    }
+
+    /// Count Polyps pushed to the peer.
+    pub fn record_pushed(&self, peer_url: &str, count: u64) {
+        self.update(peer_url, |stats| stats.polyps_pushed += count);
+    }
+
+    /// Metrics for one peer, if any were recorded.
+    pub fn peer(&self, peer_url: &str) -> Option<PeerSyncStats> {
+        let peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.get(peer_url).cloned()
+    }
+
+    /// Copy out the current metrics.
+    pub fn snapshot(&self) -> SyncMetricsSnapshot {
+        let phase = *self.phase.lock().unwrap_or_else(|e| e.into_inner());
+        let mut peers: Vec<PeerSyncStats> = self
+            .peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        peers.sort_by(|a, b| a.peer_url.cmp(&b.peer_url));
+        SyncMetricsSnapshot { phase, peers }
+    }
+
+    fn update(&self, peer_url: &str, f: impl FnOnce(&mut PeerSyncStats)) {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = peers
+            .entry(peer_url.to_string())
+            .or_insert_with(|| PeerSyncStats {
+                peer_url: peer_url.to_string(),
+                ..PeerSyncStats::default()
+            });
+        f(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_track_each_peer() {
+        let metrics = SyncMetrics::new();
+        metrics.begin("http://b", SyncPhase::Negotiating);
+        metrics.record_success("http://b", 10);
+        metrics.set_missing("http://b", 5);
+        metrics.begin("http://b", SyncPhase::Fetching);
+        metrics.record_pulled("http://b", 3);
+        metrics.record_pushed("http://a", 2);
+        metrics.record_error("http://a", "connection refused", 20);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.phase, SyncPhase::Fetching);
+        assert_eq!(snapshot.peers.len(), 2);
+        assert_eq!(snapshot.peers[0].peer_url, "http://a");
+        assert_eq!(snapshot.peers[0].polyps_pushed, 2);
+        assert_eq!(snapshot.peers[0].last_error.as_deref(), Some("connection refused"));
+
+        let b = &snapshot.peers[1];
+        assert_eq!(b.phase, SyncPhase::Fetching);
+        assert_eq!((b.polyps_pulled, b.missing), (3, 2));
+        assert_eq!(b.last_success_ms, Some(10));
+        assert_eq!(snapshot.lag(), 2);
+
+        metrics.record_pulled("http://b", 10);
+        metrics.record_success("http://a", 30);
+        metrics.finish("http://b");
+        assert_eq!(metrics.peer("http://b").unwrap().missing, 0);
+        assert_eq!(metrics.peer("http://b").unwrap().phase, SyncPhase::Idle);
+        assert!(metrics.peer("http://a").unwrap().last_error.is_none());
+    }
+}
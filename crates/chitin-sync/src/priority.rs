@@ -0,0 +1,269 @@
+// crates/chitin-sync/src/priority.rs
+//
+// Fetch prioritization for pull-sync.
+//
+// Once a node knows which polyps it is missing, the order it fetches them in
+// matters during catch-up: polyps under review or approved in the current
+// epoch feed consensus now, while years-old hardened history can wait. Each
+// missing polyp is scored from its lifecycle state, how close its consensus
+// epoch is to the current one, and whether it belongs to a Reef Zone the node
+// subscribes to; the sync loop fetches from a max-priority queue.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use chitin_core::polyp::PolypState;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Reef Zone path separator (matches the reputation taxonomy).
+const ZONE_SEPARATOR: char = '/';
+
+/// What a peer reports about a polyp before it is fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolypSyncMeta {
+    /// Polyp ID.
+    pub id: Uuid,
+    /// Lifecycle state on the peer.
+    pub state: PolypState,
+    /// Consensus epoch, if the polyp has been evaluated.
+    #[serde(default)]
+    pub epoch: Option<u64>,
+    /// Reef Zone the polyp was submitted to.
+    #[serde(default)]
+    pub reef_zone: Option<String>,
+}
+
+/// Weights for each prioritization signal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncPriorityWeights {
+    /// Weight for `UnderReview` polyps.
+    pub under_review: f64,
+    /// Weight for `Approved` polyps.
+    pub approved: f64,
+    /// Weight for `Soft` polyps.
+    pub soft: f64,
+    /// Weight for `Draft` polyps.
+    pub draft: f64,
+    /// Weight for `Hardened` polyps.
+    pub hardened: f64,
+    /// Weight for `Rejected` polyps.
+    pub rejected: f64,
+    /// Weight for `Molted` polyps.
+    pub molted: f64,
+    /// Weight of epoch relevance: 1.0 for the current epoch (or no epoch
+    /// yet), falling off as 1 / (1 + epochs behind).
+    pub epoch: f64,
+    /// Bonus for polyps in a subscribed Reef Zone.
+    pub zone: f64,
+}
+
+impl Default for SyncPriorityWeights {
+    fn default() -> Self {
+        Self {
+            under_review: 1.0,
+            approved: 0.9,
+            soft: 0.6,
+            draft: 0.3,
+            hardened: 0.2,
+            rejected: 0.05,
+            molted: 0.05,
+            epoch: 1.0,
+            zone: 0.5,
+        }
+    }
+}
+
+impl SyncPriorityWeights {
+    /// Weight for a lifecycle state.
+    pub fn state_weight(&self, state: &PolypState) -> f64 {
+        match state {
+            PolypState::UnderReview => self.under_review,
+            PolypState::Approved => self.approved,
+            PolypState::Soft => self.soft,
+            PolypState::Draft => self.draft,
+            PolypState::Hardened => self.hardened,
+            PolypState::Rejected => self.rejected,
+            PolypState::Molted { .. } => self.molted,
+        }
+    }
+}
+
+/// Scores missing polyps for fetch order.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPriority {
+    /// Signal weights.
+    pub weights: SyncPriorityWeights,
+    /// Subscribed Reef Zones; descendants of a zone also match.
+    pub zones: Vec<String>,
+    /// The node's current epoch.
+    pub current_epoch: u64,
+}
+
+impl SyncPriority {
+    /// Create a scorer for the given weights and zone subscriptions.
+    pub fn new(weights: SyncPriorityWeights, zones: Vec<String>) -> Self {
+        Self {
+            weights,
+            zones,
+            current_epoch: 0,
+        }
+    }
+
+    /// Set the current epoch used for epoch relevance.
+    pub fn at_epoch(mut self, epoch: u64) -> Self {
+        self.current_epoch = epoch;
+        self
+    }
+
+    /// Priority of a polyp; higher is fetched first.
+    pub fn score(&self, meta: &PolypSyncMeta) -> f64 {
+        let relevance = match meta.epoch {
+            Some(epoch) => 1.0 / (1.0 + self.current_epoch.saturating_sub(epoch) as f64),
+            None => 1.0,
+        };
+        let subscribed = meta.reef_zone.as_deref().is_some_and(|z| self.is_subscribed(z));
+
+        self.weights.state_weight(&meta.state)
+            + self.weights.epoch * relevance
+            + if subscribed { self.weights.zone } else { 0.0 }
+    }
+
+    /// True if `zone` is a subscribed zone or one of its descendants.
+    pub fn is_subscribed(&self, zone: &str) -> bool {
+        self.zones.iter().any(|sub| {
+            zone == sub
+                || zone
+                    .strip_prefix(sub.as_str())
+                    .is_some_and(|rest| rest.starts_with(ZONE_SEPARATOR))
+        })
+    }
+}
+
+/// A queued polyp and its priority.
+#[derive(Debug, Clone)]
+struct Entry {
+    score: f64,
+    id: Uuid,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    /// Higher score first; ties go to the newer (larger UUIDv7) polyp.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+/// Max-priority queue of polyps to fetch.
+#[derive(Debug, Clone, Default)]
+pub struct SyncQueue {
+    heap: BinaryHeap<Entry>,
+}
+
+impl SyncQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a polyp, scored by `priority`.
+    pub fn push(&mut self, meta: &PolypSyncMeta, priority: &SyncPriority) {
+        self.heap.push(Entry {
+            score: priority.score(meta),
+            id: meta.id,
+        });
+    }
+
+    /// Queue a polyp whose metadata is unknown, behind every scored polyp.
+    pub fn push_unscored(&mut self, id: Uuid) {
+        self.heap.push(Entry {
+            score: f64::NEG_INFINITY,
+            id,
+        });
+    }
+
+    /// Take the highest-priority polyp.
+    pub fn pop(&mut self) -> Option<Uuid> {
+        self.heap.pop().map(|e| e.id)
+    }
+
+    /// Number of queued polyps.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if nothing is queued.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(state: PolypState, epoch: Option<u64>, zone: Option<&str>) -> PolypSyncMeta {
+        PolypSyncMeta {
+            id: Uuid::now_v7(),
+            state,
+            epoch,
+            reef_zone: zone.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn current_review_work_outranks_old_history() {
+        let priority = SyncPriority::new(SyncPriorityWeights::default(), vec![]).at_epoch(1000);
+        let old_hardened = meta(PolypState::Hardened, Some(3), None);
+        let approved_now = meta(PolypState::Approved, Some(1000), None);
+        let under_review = meta(PolypState::UnderReview, None, None);
+
+        let mut queue = SyncQueue::new();
+        for m in [&old_hardened, &approved_now, &under_review] {
+            queue.push(m, &priority);
+        }
+        queue.push_unscored(Uuid::now_v7());
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.pop(), Some(under_review.id));
+        assert_eq!(queue.pop(), Some(approved_now.id));
+        assert_eq!(queue.pop(), Some(old_hardened.id));
+    }
+
+    #[test]
+    fn subscribed_zones_include_descendants() {
+        let priority =
+            SyncPriority::new(SyncPriorityWeights::default(), vec!["code/rust".to_string()]);
+        assert!(priority.is_subscribed("code/rust"));
+        assert!(priority.is_subscribed("code/rust/async"));
+        assert!(!priority.is_subscribed("code/rustacean"));
+        assert!(!priority.is_subscribed("code"));
+
+        let inside = meta(PolypState::Hardened, Some(0), Some("code/rust/async"));
+        let outside = meta(PolypState::Hardened, Some(0), Some("medical"));
+        assert!(priority.score(&inside) > priority.score(&outside));
+    }
+
+    #[test]
+    fn weights_deserialize_with_defaults() {
+        let weights: SyncPriorityWeights = serde_json::from_str(r#"{"hardened": 2.0}"#).unwrap();
+        assert_eq!(weights.hardened, 2.0);
+        assert_eq!(weights.approved, SyncPriorityWeights::default().approved);
+    }
+}
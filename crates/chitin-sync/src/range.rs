@@ -1,46 +1,284 @@
 // crates/chitin-sync/src/range.rs
 //
 // Range-based sync for shard catchup in the Chitin Protocol.
+//
+// Polyp IDs are UUIDv7, so their leading 48 bits are a millisecond creation
+// timestamp and the keyspace can be divided into time ranges. Peers compare
+// per-range counts and hashes; ranges that match are skipped, ranges that
+// diverge are split and compared again, and once a divergent range is small
+// enough its IDs are listed in batches. A node that was offline for a week
+// therefore only descends into the ranges written while it was away, instead
+// of comparing its whole polyp set.
+
+use std::collections::HashSet;
 
+use async_trait::async_trait;
+use chitin_core::crypto::hash_bytes;
 use chitin_core::ChitinError;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Number of sub-ranges a divergent range is split into.
+pub const DEFAULT_RANGE_FANOUT: usize = 16;
+
+/// Divergent ranges with at most this many remote IDs are listed directly.
+pub const DEFAULT_LEAF_SIZE: u64 = 256;
+
+/// IDs requested per `list_ids` batch.
+pub const DEFAULT_ID_BATCH_SIZE: usize = 512;
+
+/// Most ranges a peer will summarize in one request.
+pub const MAX_RANGES_PER_REQUEST: usize = 256;
+
+/// Most IDs a peer will return in one batch.
+pub const MAX_ID_BATCH_SIZE: usize = 4096;
+
+/// Millisecond timestamp of a UUIDv7. Other versions have no usable
+/// timestamp and sort at 0.
+pub fn uuid_timestamp_ms(id: &Uuid) -> u64 {
+    if id.get_version_num() != 7 {
+        return 0;
+    }
+    let b = id.as_bytes();
+    u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]])
+}
+
+/// A half-open millisecond time range `[start_ms, end_ms)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimeRange {
+    /// First millisecond in the range.
+    pub start_ms: u64,
+    /// First millisecond after the range.
+    pub end_ms: u64,
+}
+
+impl TimeRange {
+    /// Create a range, rejecting `start_ms > end_ms`.
+    pub fn new(start_ms: u64, end_ms: u64) -> Result<Self, ChitinError> {
+        if start_ms > end_ms {
+            return Err(ChitinError::InvalidState(format!(
+                "Invalid time range: start {} > end {}",
+                start_ms, end_ms
+            )));
+        }
+        Ok(Self { start_ms, end_ms })
+    }
+
+    /// Width of the range in milliseconds.
+    pub fn width(&self) -> u64 {
+        self.end_ms - self.start_ms
+    }
+
+    /// True if `ts` falls in the range.
+    pub fn contains(&self, ts: u64) -> bool {
+        ts >= self.start_ms && ts < self.end_ms
+    }
 
-/// Range-based synchronization for catching up on missed epochs.
+    /// Split into at most `parts` contiguous, non-empty sub-ranges.
+    pub fn split(&self, parts: usize) -> Vec<TimeRange> {
+        let parts = (parts.max(1) as u64).min(self.width().max(1));
+        let step = self.width().div_ceil(parts).max(1);
+        let mut out = Vec::with_capacity(parts as usize);
+        let mut start = self.start_ms;
+        while start < self.end_ms {
+            let end = start.saturating_add(step).min(self.end_ms);
+            out.push(TimeRange { start_ms: start, end_ms: end });
+            start = end;
+        }
+        out
+    }
+}
+
+/// Count and content hash of the IDs in a time range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeSummary {
+    /// The summarized range.
+    pub range: TimeRange,
+    /// Number of IDs in the range.
+    pub count: u64,
+    /// Hex-encoded SHA-256 over the range's IDs in (timestamp, ID) order.
+    pub hash: String,
+}
+
+/// One batch of IDs from a range, in (timestamp, ID) order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdPage {
+    /// IDs in this batch.
+    pub ids: Vec<Uuid>,
+    /// Pass as `after` to fetch the next batch; `None` on the last batch.
+    pub next: Option<Uuid>,
+}
+
+/// Local polyp IDs ordered by UUIDv7 timestamp, for answering range queries.
+#[derive(Debug, Clone, Default)]
+pub struct RangeIndex {
+    /// (timestamp, ID), sorted.
+    entries: Vec<(u64, Uuid)>,
+}
+
+impl RangeIndex {
+    /// Build an index over `ids`.
+    pub fn from_ids<'a>(ids: impl IntoIterator<Item = &'a Uuid>) -> Self {
+        let mut entries: Vec<(u64, Uuid)> =
+            ids.into_iter().map(|id| (uuid_timestamp_ms(id), *id)).collect();
+        entries.sort_unstable();
+        entries.dedup();
+        Self { entries }
+    }
+
+    /// Number of indexed IDs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if no IDs are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Summarize a range.
+    pub fn summarize(&self, range: TimeRange) -> RangeSummary {
+        let slice = self.slice(range);
+        let bytes: Vec<u8> = slice.iter().flat_map(|(_, id)| *id.as_bytes()).collect();
+        RangeSummary {
+            range,
+            count: slice.len() as u64,
+            hash: crate::hex::encode(&hash_bytes(&bytes)),
+        }
+    }
+
+    /// List up to `limit` IDs in `range` that sort after `after`.
+    pub fn list_ids(&self, range: TimeRange, after: Option<Uuid>, limit: usize) -> IdPage {
+        let slice = self.slice(range);
+        let start = match after {
+            Some(after) => slice.partition_point(|&e| e <= (uuid_timestamp_ms(&after), after)),
+            None => 0,
+        };
+        let rest = &slice[start..];
+        let ids: Vec<Uuid> = rest.iter().take(limit).map(|(_, id)| *id).collect();
+        let next = if rest.len() > ids.len() {
+            ids.last().copied()
+        } else {
+            None
+        };
+        IdPage { ids, next }
+    }
+
+    fn slice(&self, range: TimeRange) -> &[(u64, Uuid)] {
+        let start = self.entries.partition_point(|&(ts, _)| ts < range.start_ms);
+        let end = self.entries.partition_point(|&(ts, _)| ts < range.end_ms);
+        &self.entries[start..end.max(start)]
+    }
+}
+
+/// The remote side of a range sync.
+#[async_trait]
+pub trait RangePeer: Send + Sync {
+    /// Summarize each of `ranges` (at most `MAX_RANGES_PER_REQUEST`).
+    async fn summarize(&self, ranges: &[TimeRange]) -> Result<Vec<RangeSummary>, ChitinError>;
+
+    /// List up to `limit` IDs in `range` after `after`.
+    async fn list_ids(
+        &self,
+        range: TimeRange,
+        after: Option<Uuid>,
+        limit: usize,
+    ) -> Result<IdPage, ChitinError>;
+}
+
+/// Outcome of a range sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSyncOutcome {
+    /// Remote IDs missing locally, in (timestamp, ID) order.
+    pub missing: Vec<Uuid>,
+    /// Number of range summaries compared.
+    pub ranges_compared: usize,
+    /// Number of divergent leaf ranges whose IDs were listed.
+    pub ranges_listed: usize,
+}
+
+/// Range-based synchronization for catching up on a span of time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RangeSync {
-    /// First epoch to sync (inclusive).
-    pub start_epoch: u64,
-    /// Last epoch to sync (inclusive).
-    pub end_epoch: u64,
+    /// The time span to reconcile.
+    pub range: TimeRange,
+    /// Sub-ranges per split of a divergent range.
+    pub fanout: usize,
+    /// Remote ID count at or below which a range is listed instead of split.
+    pub leaf_size: u64,
+    /// IDs requested per batch.
+    pub batch_size: usize,
 }
 
 impl RangeSync {
-    /// Create a new RangeSync for the given epoch range.
-    pub fn new(start: u64, end: u64) -> Self {
-        Self {
-            start_epoch: start,
-            end_epoch: end,
-        }
+    /// Create a RangeSync over `[start_ms, end_ms)` with default tuning.
+    pub fn new(start_ms: u64, end_ms: u64) -> Result<Self, ChitinError> {
+        Ok(Self {
+            range: TimeRange::new(start_ms, end_ms)?,
+            fanout: DEFAULT_RANGE_FANOUT,
+            leaf_size: DEFAULT_LEAF_SIZE,
+            batch_size: DEFAULT_ID_BATCH_SIZE,
+        })
     }
 
-    /// Execute the range sync, fetching all Polyps from the specified epochs.
+    /// Find the remote IDs in the range that are missing from `local`.
     ///
-    /// Returns the total number of Polyps synced.
-    ///
-    /// Validates that start_epoch <= end_epoch. Returns 0 because no peers
-    /// are connected yet — the framework is ready for P2P integration.
-    pub async fn sync_range(&self) -> Result<u64, ChitinError> {
-        // Validate epoch range
-        if self.start_epoch > self.end_epoch {
-            return Err(ChitinError::InvalidState(format!(
-                "Invalid epoch range: start {} > end {}",
-                self.start_epoch, self.end_epoch
-            )));
+    /// Compares summaries level by level, one request per
+    /// `MAX_RANGES_PER_REQUEST` divergent ranges, and lists IDs in batches
+    /// only for divergent leaf ranges.
+    pub async fn sync_range<P: RangePeer + ?Sized>(
+        &self,
+        local: &RangeIndex,
+        peer: &P,
+    ) -> Result<RangeSyncOutcome, ChitinError> {
+        let mut outcome = RangeSyncOutcome::default();
+        let mut pending = vec![self.range];
+        let mut leaves = Vec::new();
+
+        while !pending.is_empty() {
+            let mut next = Vec::new();
+            for chunk in pending.chunks(MAX_RANGES_PER_REQUEST) {
+                let remote = peer.summarize(chunk).await?;
+                if remote.len() != chunk.len() {
+                    return Err(ChitinError::Network(format!(
+                        "Peer returned {} range summaries for {} ranges",
+                        remote.len(),
+                        chunk.len()
+                    )));
+                }
+                outcome.ranges_compared += remote.len();
+                for (range, summary) in chunk.iter().zip(remote) {
+                    // Pull-only: ranges the peer has nothing in need no work.
+                    if summary.count == 0 || summary == local.summarize(*range) {
+                        continue;
+                    }
+                    if summary.count <= self.leaf_size || range.width() <= 1 {
+                        leaves.push(*range);
+                    } else {
+                        next.extend(range.split(self.fanout));
+                    }
+                }
+            }
+            pending = next;
         }
 
-        // No peers connected yet — return 0 synced Polyps.
-        // Framework is ready for P2P integration.
-        Ok(0)
+        let local_ids: HashSet<Uuid> = local.entries.iter().map(|(_, id)| *id).collect();
+        for range in leaves {
+            outcome.ranges_listed += 1;
+            let mut after = None;
+            loop {
+                let page = peer.list_ids(range, after, self.batch_size).await?;
+                outcome
+                    .missing
+                    .extend(page.ids.into_iter().filter(|id| !local_ids.contains(id)));
+                match page.next {
+                    Some(next) if after != Some(next) => after = Some(next),
+                    _ => break,
+                }
+            }
+        }
+
+        Ok(outcome)
     }
 }
 
@@ -48,39 +286,107 @@ impl RangeSync {
 mod tests {
     use super::*;
 
-    #[tokio::test]
-    async fn valid_range_returns_ok() {
-        let sync = RangeSync::new(1, 10);
-        let result = sync.sync_range().await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 0);
+    /// A v7 UUID with the given millisecond timestamp.
+    fn id_at(ms: u64, n: u16) -> Uuid {
+        let mut b = [0u8; 16];
+        b[0..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+        b[6] = 0x70;
+        b[8] = 0x80;
+        b[14..].copy_from_slice(&n.to_be_bytes());
+        Uuid::from_bytes(b)
     }
 
-    #[tokio::test]
-    async fn same_epoch_range_returns_ok() {
-        let sync = RangeSync::new(5, 5);
-        let result = sync.sync_range().await;
-        assert!(result.is_ok());
+    /// In-process peer backed by a RangeIndex.
+    struct LocalPeer(RangeIndex);
+
+    #[async_trait]
+    impl RangePeer for LocalPeer {
+        async fn summarize(&self, ranges: &[TimeRange]) -> Result<Vec<RangeSummary>, ChitinError> {
+            Ok(ranges.iter().map(|r| self.0.summarize(*r)).collect())
+        }
+
+        async fn list_ids(
+            &self,
+            range: TimeRange,
+            after: Option<Uuid>,
+            limit: usize,
+        ) -> Result<IdPage, ChitinError> {
+            Ok(self.0.list_ids(range, after, limit))
+        }
     }
 
-    #[tokio::test]
-    async fn inverted_range_returns_error() {
-        let sync = RangeSync::new(10, 1);
-        let result = sync.sync_range().await;
-        assert!(result.is_err());
-        match result.unwrap_err() {
+    #[test]
+    fn timestamps_and_splits() {
+        assert_eq!(uuid_timestamp_ms(&id_at(1_700_000_000_123, 0)), 1_700_000_000_123);
+        assert_eq!(uuid_timestamp_ms(&Uuid::nil()), 0);
+
+        let parts = TimeRange::new(0, 10).unwrap().split(4);
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], TimeRange { start_ms: 0, end_ms: 3 });
+        assert_eq!(parts[3], TimeRange { start_ms: 9, end_ms: 10 });
+        assert_eq!(TimeRange::new(5, 6).unwrap().split(16).len(), 1);
+    }
+
+    #[test]
+    fn inverted_range_returns_error() {
+        match RangeSync::new(10, 1).unwrap_err() {
             ChitinError::InvalidState(msg) => {
                 assert!(msg.contains("start"));
                 assert!(msg.contains("end"));
             }
             other => panic!("Expected InvalidState, got: {:?}", other),
         }
+        assert!(RangeSync::new(5, 5).is_ok());
+    }
+
+    #[test]
+    fn list_ids_pages_through_a_range() {
+        let ids: Vec<Uuid> = (0..10).map(|i| id_at(100 + i, 0)).collect();
+        let index = RangeIndex::from_ids(&ids);
+        let range = TimeRange::new(102, 108).unwrap();
+
+        let first = index.list_ids(range, None, 4);
+        assert_eq!(first.ids, ids[2..6].to_vec());
+        let second = index.list_ids(range, first.next, 4);
+        assert_eq!(second.ids, ids[6..8].to_vec());
+        assert_eq!(second.next, None);
+    }
+
+    #[tokio::test]
+    async fn identical_sets_compare_one_summary() {
+        let ids: Vec<Uuid> = (0..1000).map(|i| id_at(i * 10, 0)).collect();
+        let local = RangeIndex::from_ids(&ids);
+        let peer = LocalPeer(RangeIndex::from_ids(&ids));
+
+        let outcome = RangeSync::new(0, 10_000).unwrap().sync_range(&local, &peer).await.unwrap();
+        assert!(outcome.missing.is_empty());
+        assert_eq!(outcome.ranges_compared, 1);
+        assert_eq!(outcome.ranges_listed, 0);
     }
 
     #[tokio::test]
-    async fn zero_epoch_range_returns_ok() {
-        let sync = RangeSync::new(0, 0);
-        let result = sync.sync_range().await;
-        assert!(result.is_ok());
+    async fn catch_up_fetches_only_divergent_ranges() {
+        // A week of history; the local node missed a recent hour.
+        let old: Vec<Uuid> = (0..5000).map(|i| id_at(i * 100_000, 0)).collect();
+        let recent: Vec<Uuid> = (0..300).map(|i| id_at(500_000_000 + i * 10_000, 1)).collect();
+        let local = RangeIndex::from_ids(&old);
+        let peer = LocalPeer(RangeIndex::from_ids(old.iter().chain(&recent)));
+
+        let sync = RangeSync::new(0, 604_800_000).unwrap();
+        let outcome = sync.sync_range(&local, &peer).await.unwrap();
+        assert_eq!(outcome.missing, recent);
+        assert!(outcome.ranges_listed <= 4, "listed {}", outcome.ranges_listed);
+        assert!(outcome.ranges_compared < 100, "compared {}", outcome.ranges_compared);
+    }
+
+    #[tokio::test]
+    async fn local_only_ids_are_ignored() {
+        let shared: Vec<Uuid> = (0..50).map(|i| id_at(i, 0)).collect();
+        let extra = id_at(60, 9);
+        let local = RangeIndex::from_ids(shared.iter().chain([&extra]));
+        let peer = LocalPeer(RangeIndex::from_ids(&shared));
+
+        let outcome = RangeSync::new(0, 100).unwrap().sync_range(&local, &peer).await.unwrap();
+        assert!(outcome.missing.is_empty());
     }
 }
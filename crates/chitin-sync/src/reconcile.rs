@@ -4,12 +4,37 @@
 //
 // After exchanging Vector Bloom Filters, nodes determine which Polyps
 // the remote peer is missing and request them.
+//
+// Negotiation: the requester sends its VBF and ID count; the responder
+// replies with its own VBF, its count, and the IDs that are definitely
+// absent from the requester's filter. Bloom filters have no false
+// negatives, so every reported ID is truly missing. False positives can
+// hide a few more; the requester detects this from the counts and only then
+// falls back to a full ID list exchange.
+//
+// Preferred path: invertible Bloom lookup tables (IBLTs). The requester sends
+// a strata estimator; the responder estimates the size of the symmetric
+// difference and replies with an IBLT of its IDs sized for it. Subtracting
+// the requester's own IBLT and peeling recovers the exact difference in both
+// directions. If peeling fails (estimate too low), the VBF path is used.
+
+use std::collections::HashSet;
 
 use chitin_core::ChitinError;
 use uuid::Uuid;
 
 use crate::vbf::VectorBloomFilter;
 
+/// What a requester should do after a VBF exchange with one peer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VbfSyncPlan {
+    /// IDs the peer has that we do not; fetch these.
+    pub missing: Vec<Uuid>,
+    /// True if false positives in our filter may have hidden further
+    /// missing IDs, so the peer's full ID list should be compared.
+    pub needs_id_list: bool,
+}
+
 /// Manages set reconciliation between peers.
 ///
 /// Compares local Polyp IDs against a remote VBF (received as bytes)
@@ -58,6 +83,85 @@ impl SetReconciler {
         Ok(missing)
     }
 
+    /// Number of local IDs.
+    pub fn len(&self) -> usize {
+        self.local_ids.len()
+    }
+
+    /// True if there are no local IDs.
+    pub fn is_empty(&self) -> bool {
+        self.local_ids.is_empty()
+    }
+
+    /// Build a VBF over the local IDs.
+    pub fn local_filter(&self) -> VectorBloomFilter {
+        VectorBloomFilter::from_ids(self.local_ids.iter())
+    }
+
+    /// Local IDs that are definitely absent from `remote`.
+    pub fn missing_from(&self, remote: &VectorBloomFilter) -> Vec<Uuid> {
+        self.local_ids
+            .iter()
+            .filter(|id| !remote.contains(id))
+            .copied()
+            .collect()
+    }
+
+    /// Plan a pull from a peer given its VBF exchange reply.
+    ///
+    /// `reported` are the IDs the peer found absent from our filter and
+    /// `remote_count` is the peer's total ID count. The peer believes we
+    /// already hold `remote_count - reported.len()` of its IDs; if fewer of
+    /// our IDs even match the peer's filter, some of those beliefs are false
+    /// positives and the ID list is needed to find the rest.
+    pub fn plan_vbf_sync(
+        &self,
+        remote: &VectorBloomFilter,
+        remote_count: usize,
+        reported: Vec<Uuid>,
+    ) -> VbfSyncPlan {
+        let local: std::collections::HashSet<&Uuid> = self.local_ids.iter().collect();
+        let missing: Vec<Uuid> = reported.into_iter().filter(|id| !local.contains(id)).collect();
+        let probably_shared = self.local_ids.iter().filter(|id| remote.contains(id)).count();
+        let believed_shared = remote_count.saturating_sub(missing.len());
+
+        VbfSyncPlan {
+            missing,
+            needs_id_list: believed_shared > probably_shared,
+        }
+    }
+
+    /// Strata estimator over the local IDs, sent to open IBLT reconciliation.
+    pub fn local_estimator(&self) -> StrataEstimator {
+        StrataEstimator::from_ids(&self.local_ids)
+    }
+
+    /// IBLT of the local IDs sized for the difference between our IDs and
+    /// those summarized by `remote` (a peer's estimator), together with the
+    /// estimated difference.
+    ///
+    /// Fails if the estimate needs more than `MAX_IBLT_CELLS` cells; the
+    /// peers should fall back to VBF sync instead.
+    pub fn iblt_for(&self, remote: &StrataEstimator) -> Result<(Iblt, usize), ChitinError> {
+        let estimate = self.local_estimator().estimate_difference(remote)?;
+        let cells = Iblt::cells_for_difference(estimate);
+        if estimate as f64 * IBLT_CELLS_PER_DIFF > MAX_IBLT_CELLS as f64 {
+            return Err(ChitinError::InvalidState(format!(
+                "Estimated difference {} is too large for IBLT reconciliation",
+                estimate
+            )));
+        }
+        Ok((Iblt::from_ids(cells, &self.local_ids), estimate))
+    }
+
+    /// Recover the exact difference against a peer's IBLT. `remote_only`
+    /// are the IDs to fetch; `local_only` are IDs the peer lacks.
+    pub fn reconcile_iblt(&self, remote: &Iblt) -> Result<IbltDiff, ChitinError> {
+        Iblt::from_ids(remote.cell_count(), &self.local_ids)
+            .subtract(remote)?
+            .decode()
+    }
+
     /// Request missing Polyps from a remote peer.
     ///
     /// # Phase 2 (incomplete)
@@ -85,6 +189,361 @@ impl Default for SetReconciler {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Invertible Bloom lookup tables
+// ---------------------------------------------------------------------------
+
+/// Number of cells each ID is hashed into (one per sub-table).
+pub const IBLT_HASH_COUNT: usize = 3;
+
+/// Smallest IBLT exchanged, in cells.
+pub const MIN_IBLT_CELLS: usize = 60;
+
+/// Largest IBLT exchanged, in cells. Bigger differences use VBF sync.
+pub const MAX_IBLT_CELLS: usize = 30_000;
+
+/// Cells allocated per estimated differing ID. Peeling needs ~1.25 cells
+/// per ID with three hashes; the rest absorbs strata underestimates.
+pub const IBLT_CELLS_PER_DIFF: f64 = 3.0;
+
+/// Number of strata in a difference estimator.
+pub const STRATA_COUNT: usize = 16;
+
+/// Cells per stratum IBLT.
+pub const STRATA_CELLS: usize = 33;
+
+/// Bytes per serialized cell: count (i32) + key sum (u128) + hash sum (u64).
+const CELL_BYTES: usize = 4 + 16 + 8;
+
+/// Seed for the per-cell checksum hash (distinct from index seeds).
+const CHECKSUM_SEED: u64 = 0xC417_1A5E_ED00_0001;
+
+/// Seed for strata assignment.
+const STRATA_SEED: u64 = 0xC417_1A5E_ED00_0002;
+
+/// Deterministic 64-bit hash of a UUID (splitmix64 finalizer over both
+/// halves). Must be identical on every node, so std hashers are not used.
+fn hash_id(key: u128, seed: u64) -> u64 {
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    let lo = key as u64;
+    let hi = (key >> 64) as u64;
+    mix(mix(lo ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15)) ^ hi)
+}
+
+/// One IBLT cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct IbltCell {
+    /// Net insertions minus removals.
+    count: i32,
+    /// XOR of keys.
+    key_sum: u128,
+    /// XOR of key checksums.
+    hash_sum: u64,
+}
+
+impl IbltCell {
+    fn toggle(&mut self, key: u128, delta: i32) {
+        self.count = self.count.wrapping_add(delta);
+        self.key_sum ^= key;
+        self.hash_sum ^= hash_id(key, CHECKSUM_SEED);
+    }
+
+    fn is_pure(&self) -> bool {
+        (self.count == 1 || self.count == -1)
+            && self.hash_sum == hash_id(self.key_sum, CHECKSUM_SEED)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.count == 0 && self.key_sum == 0 && self.hash_sum == 0
+    }
+}
+
+/// The exact set difference recovered from an IBLT.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IbltDiff {
+    /// IDs only in the minuend (left-hand) table.
+    pub local_only: Vec<Uuid>,
+    /// IDs only in the subtrahend (right-hand) table.
+    pub remote_only: Vec<Uuid>,
+}
+
+impl IbltDiff {
+    /// Total number of differing IDs.
+    pub fn len(&self) -> usize {
+        self.local_only.len() + self.remote_only.len()
+    }
+
+    /// True if the sets were identical.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Invertible Bloom lookup table over Polyp IDs.
+///
+/// Each ID is XORed into one cell of each of `IBLT_HASH_COUNT` sub-tables.
+/// Subtracting two tables cancels shared IDs, leaving only the difference,
+/// which can be listed by repeatedly peeling pure cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iblt {
+    cells: Vec<IbltCell>,
+}
+
+impl Iblt {
+    /// Create an empty table with at least `cells` cells (rounded up to a
+    /// multiple of `IBLT_HASH_COUNT`).
+    pub fn new(cells: usize) -> Self {
+        let per_table = cells.max(IBLT_HASH_COUNT).div_ceil(IBLT_HASH_COUNT);
+        Self {
+            cells: vec![IbltCell::default(); per_table * IBLT_HASH_COUNT],
+        }
+    }
+
+    /// Cell count suited to an estimated difference of `estimated_diff` IDs,
+    /// clamped to [`MIN_IBLT_CELLS`, `MAX_IBLT_CELLS`].
+    pub fn cells_for_difference(estimated_diff: usize) -> usize {
+        ((estimated_diff as f64 * IBLT_CELLS_PER_DIFF).ceil() as usize)
+            .clamp(MIN_IBLT_CELLS, MAX_IBLT_CELLS)
+    }
+
+    /// Build a table with `cells` cells holding `ids`.
+    pub fn from_ids<'a>(cells: usize, ids: impl IntoIterator<Item = &'a Uuid>) -> Self {
+        let mut iblt = Self::new(cells);
+        for id in ids {
+            iblt.insert(id);
+        }
+        iblt
+    }
+
+    /// Number of cells.
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Add an ID.
+    pub fn insert(&mut self, id: &Uuid) {
+        self.toggle(id.as_u128(), 1);
+    }
+
+    /// Remove an ID.
+    pub fn remove(&mut self, id: &Uuid) {
+        self.toggle(id.as_u128(), -1);
+    }
+
+    /// Cell-wise `self - other`. Both tables must have the same size.
+    pub fn subtract(&self, other: &Iblt) -> Result<Iblt, ChitinError> {
+        if self.cells.len() != other.cells.len() {
+            return Err(ChitinError::InvalidState(format!(
+                "IBLT size mismatch: {} vs {} cells",
+                self.cells.len(),
+                other.cells.len()
+            )));
+        }
+        let cells = self
+            .cells
+            .iter()
+            .zip(&other.cells)
+            .map(|(a, b)| IbltCell {
+                count: a.count.wrapping_sub(b.count),
+                key_sum: a.key_sum ^ b.key_sum,
+                hash_sum: a.hash_sum ^ b.hash_sum,
+            })
+            .collect();
+        Ok(Iblt { cells })
+    }
+
+    /// List the IDs in the table by peeling pure cells. For a subtracted
+    /// table `a - b`, positive entries are only in `a`, negative only in `b`.
+    ///
+    /// Fails if the table is too full to peel completely, or is malformed:
+    /// an ID peeled twice, or more IDs than cells.
+    pub fn decode(&self) -> Result<IbltDiff, ChitinError> {
+        let mut table = self.clone();
+        let mut diff = IbltDiff::default();
+        let mut peeled = HashSet::new();
+        let mut pending: Vec<usize> = (0..table.cells.len()).collect();
+
+        while let Some(i) = pending.pop() {
+            let cell = table.cells[i];
+            if !cell.is_pure() {
+                continue;
+            }
+            if peeled.len() == table.cells.len() || !peeled.insert(cell.key_sum) {
+                return Err(ChitinError::InvalidState(
+                    "IBLT decode failed: malformed table".to_string(),
+                ));
+            }
+            let id = Uuid::from_u128(cell.key_sum);
+            if cell.count == 1 {
+                diff.local_only.push(id);
+            } else {
+                diff.remote_only.push(id);
+            }
+            for j in table.indices(cell.key_sum) {
+                table.cells[j].toggle(cell.key_sum, -cell.count);
+                pending.push(j);
+            }
+        }
+
+        if table.cells.iter().all(IbltCell::is_empty) {
+            diff.local_only.sort();
+            diff.remote_only.sort();
+            Ok(diff)
+        } else {
+            Err(ChitinError::InvalidState(
+                "IBLT decode failed: difference exceeds table capacity".to_string(),
+            ))
+        }
+    }
+
+    /// Serialize: `[4 bytes: cell count u32 LE]` then per cell
+    /// `[count i32 LE][key_sum u128 LE][hash_sum u64 LE]`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(4 + self.cells.len() * CELL_BYTES);
+        buf.extend_from_slice(&(self.cells.len() as u32).to_le_bytes());
+        for cell in &self.cells {
+            buf.extend_from_slice(&cell.count.to_le_bytes());
+            buf.extend_from_slice(&cell.key_sum.to_le_bytes());
+            buf.extend_from_slice(&cell.hash_sum.to_le_bytes());
+        }
+        buf
+    }
+
+    /// Deserialize from `to_bytes` output.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ChitinError> {
+        let header: [u8; 4] = data
+            .get(0..4)
+            .and_then(|h| h.try_into().ok())
+            .ok_or_else(|| ChitinError::Serialization("IBLT data too short".to_string()))?;
+        let count = u32::from_le_bytes(header) as usize;
+        if count == 0 || !count.is_multiple_of(IBLT_HASH_COUNT) || count > MAX_IBLT_CELLS {
+            return Err(ChitinError::Serialization(format!(
+                "Invalid IBLT cell count {}",
+                count
+            )));
+        }
+        if data.len() != 4 + count * CELL_BYTES {
+            return Err(ChitinError::Serialization(format!(
+                "IBLT data length {} does not match {} cells",
+                data.len(),
+                count
+            )));
+        }
+        let cells = data[4..]
+            .chunks_exact(CELL_BYTES)
+            .map(|c| IbltCell {
+                count: i32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                key_sum: u128::from_le_bytes(c[4..20].try_into().unwrap_or_default()),
+                hash_sum: u64::from_le_bytes(c[20..28].try_into().unwrap_or_default()),
+            })
+            .collect();
+        Ok(Iblt { cells })
+    }
+
+    /// Hex-encoded `to_bytes`, for JSON-RPC transport.
+    pub fn to_hex(&self) -> String {
+        crate::hex::encode(&self.to_bytes())
+    }
+
+    /// Deserialize from `to_hex` output.
+    pub fn from_hex(hex: &str) -> Result<Self, ChitinError> {
+        Self::from_bytes(&crate::hex::decode(hex, "IBLT")?)
+    }
+
+    fn toggle(&mut self, key: u128, delta: i32) {
+        for i in self.indices(key) {
+            self.cells[i].toggle(key, delta);
+        }
+    }
+
+    /// One cell per sub-table, so an ID never lands twice in the same cell.
+    fn indices(&self, key: u128) -> [usize; IBLT_HASH_COUNT] {
+        let per_table = self.cells.len() / IBLT_HASH_COUNT;
+        let mut out = [0; IBLT_HASH_COUNT];
+        for (t, slot) in out.iter_mut().enumerate() {
+            *slot = t * per_table + (hash_id(key, t as u64) % per_table as u64) as usize;
+        }
+        out
+    }
+}
+
+/// Strata estimator for the size of a set difference (Eppstein et al.).
+///
+/// IDs are assigned to stratum `i` with probability 2^-(i+1) and inserted
+/// into that stratum's small IBLT. Subtracting two estimators and decoding
+/// from the sparsest stratum down gives the difference size, scaled up from
+/// the first stratum that fails to decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrataEstimator {
+    strata: Vec<Iblt>,
+}
+
+impl StrataEstimator {
+    /// Build an estimator over `ids`.
+    pub fn from_ids<'a>(ids: impl IntoIterator<Item = &'a Uuid>) -> Self {
+        let mut strata = vec![Iblt::new(STRATA_CELLS); STRATA_COUNT];
+        for id in ids {
+            let level = (hash_id(id.as_u128(), STRATA_SEED).trailing_zeros() as usize)
+                .min(STRATA_COUNT - 1);
+            strata[level].insert(id);
+        }
+        Self { strata }
+    }
+
+    /// Estimate the symmetric difference size between this set and `other`.
+    pub fn estimate_difference(&self, other: &StrataEstimator) -> Result<usize, ChitinError> {
+        if self.strata.len() != other.strata.len() {
+            return Err(ChitinError::InvalidState(
+                "Strata estimator size mismatch".to_string(),
+            ));
+        }
+        let mut count = 0usize;
+        for level in (0..self.strata.len()).rev() {
+            match self.strata[level].subtract(&other.strata[level])?.decode() {
+                Ok(diff) => count += diff.len(),
+                Err(_) => return Ok(count << (level + 1)),
+            }
+        }
+        Ok(count)
+    }
+
+    /// Serialize: concatenated stratum IBLTs (each self-describing).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.strata.iter().flat_map(Iblt::to_bytes).collect()
+    }
+
+    /// Deserialize from `to_bytes` output.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ChitinError> {
+        let stratum_len = 4 + Iblt::new(STRATA_CELLS).cell_count() * CELL_BYTES;
+        if data.len() != stratum_len * STRATA_COUNT {
+            return Err(ChitinError::Serialization(format!(
+                "Strata estimator data length {} (expected {})",
+                data.len(),
+                stratum_len * STRATA_COUNT
+            )));
+        }
+        let strata = data
+            .chunks_exact(stratum_len)
+            .map(Iblt::from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { strata })
+    }
+
+    /// Hex-encoded `to_bytes`, for JSON-RPC transport.
+    pub fn to_hex(&self) -> String {
+        crate::hex::encode(&self.to_bytes())
+    }
+
+    /// Deserialize from `to_hex` output.
+    pub fn from_hex(hex: &str) -> Result<Self, ChitinError> {
+        Self::from_bytes(&crate::hex::decode(hex, "strata estimator")?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,6 +642,142 @@ mod tests {
         assert!(result.is_err(), "Should return error for invalid remote bytes");
     }
 
+    #[test]
+    fn vbf_exchange_finds_remote_only_ids() {
+        let shared: Vec<Uuid> = (0..20).map(|_| Uuid::now_v7()).collect();
+        let remote_only: Vec<Uuid> = (0..5).map(|_| Uuid::now_v7()).collect();
+        let local = SetReconciler::with_local_ids(shared.clone());
+        let remote = SetReconciler::with_local_ids([shared, remote_only.clone()].concat());
+
+        // Responder side: what is the requester missing?
+        let reported = remote.missing_from(&local.local_filter());
+        assert_eq!(reported, remote_only);
+
+        // Requester side.
+        let plan = local.plan_vbf_sync(&remote.local_filter(), remote.len(), reported);
+        assert_eq!(plan.missing, remote_only);
+        assert!(!plan.needs_id_list);
+    }
+
+    #[test]
+    fn hidden_ids_trigger_id_list_fallback() {
+        let shared: Vec<Uuid> = (0..10).map(|_| Uuid::now_v7()).collect();
+        let local = SetReconciler::with_local_ids(shared.clone());
+        let mut remote_ids = shared;
+        remote_ids.extend((0..3).map(|_| Uuid::now_v7()));
+        let remote = SetReconciler::with_local_ids(remote_ids);
+
+        // Simulate false positives hiding two of the three remote-only IDs.
+        let mut reported = remote.missing_from(&local.local_filter());
+        reported.truncate(1);
+        let plan = local.plan_vbf_sync(&remote.local_filter(), remote.len(), reported);
+        assert_eq!(plan.missing.len(), 1);
+        assert!(plan.needs_id_list);
+    }
+
+    /// `n` pseudo-random IDs, deterministic per `seed` so decode outcomes
+    /// are reproducible.
+    fn ids(seed: u64, n: usize) -> Vec<Uuid> {
+        (0..n as u64)
+            .map(|i| {
+                let hi = hash_id(i as u128, seed) as u128;
+                Uuid::from_u128((hi << 64) | hash_id(i as u128, !seed) as u128)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn iblt_recovers_exact_difference() {
+        let shared = ids(1, 500);
+        let local_only = ids(2, 7);
+        let remote_only = ids(3, 11);
+        let local = SetReconciler::with_local_ids([shared.clone(), local_only.clone()].concat());
+        let remote = SetReconciler::with_local_ids([shared, remote_only.clone()].concat());
+
+        // Responder sizes its IBLT from the requester's estimator.
+        let (remote_iblt, estimate) = remote.iblt_for(&local.local_estimator()).unwrap();
+        assert!(estimate > 0);
+        let wire = Iblt::from_hex(&remote_iblt.to_hex()).unwrap();
+        assert_eq!(wire, remote_iblt);
+
+        let diff = local.reconcile_iblt(&wire).unwrap();
+        let mut expected_local = local_only;
+        let mut expected_remote = remote_only;
+        expected_local.sort();
+        expected_remote.sort();
+        assert_eq!(diff.local_only, expected_local);
+        assert_eq!(diff.remote_only, expected_remote);
+    }
+
+    #[test]
+    fn undersized_iblt_fails_to_decode() {
+        let a = Iblt::from_ids(MIN_IBLT_CELLS, &ids(4, 200));
+        let b = Iblt::new(MIN_IBLT_CELLS);
+        assert!(a.subtract(&b).unwrap().decode().is_err());
+        assert!(a.subtract(&Iblt::new(MIN_IBLT_CELLS * 2)).is_err());
+    }
+
+    #[test]
+    fn crafted_iblt_cannot_peel_forever() {
+        // One pure cell whose key's other cells are empty would otherwise
+        // be peeled back and forth between them.
+        let key = ids(8, 1)[0].as_u128();
+        let crafted = |cells| {
+            let mut table = Iblt::new(cells);
+            let i = table.indices(key)[0];
+            table.cells[i] = IbltCell {
+                count: 1,
+                key_sum: key,
+                hash_sum: hash_id(key, CHECKSUM_SEED),
+            };
+            table
+        };
+        assert!(crafted(MIN_IBLT_CELLS).decode().is_err());
+
+        // Reached through a remote strata estimator.
+        let mut remote = StrataEstimator::from_ids(&[]);
+        remote.strata[0] = crafted(STRATA_CELLS);
+        assert!(remote
+            .estimate_difference(&StrataEstimator::from_ids(&[]))
+            .is_ok());
+
+        // Counts wrap instead of overflowing.
+        let mut extreme = Iblt::new(MIN_IBLT_CELLS);
+        for cell in &mut extreme.cells {
+            cell.count = i32::MIN;
+        }
+        let diff = extreme.subtract(&Iblt::from_ids(MIN_IBLT_CELLS, &ids(9, 1)));
+        assert!(diff.unwrap().decode().is_err());
+    }
+
+    #[test]
+    fn strata_estimate_is_in_the_right_range() {
+        let shared = ids(5, 2000);
+        let a = StrataEstimator::from_ids(&shared);
+        let b_ids = [shared.clone(), ids(6, 300)].concat();
+        let b = StrataEstimator::from_ids(&b_ids);
+        let estimate = StrataEstimator::from_hex(&b.to_hex())
+            .unwrap()
+            .estimate_difference(&a)
+            .unwrap();
+        assert!((100..=900).contains(&estimate), "estimate {}", estimate);
+        assert_eq!(a.estimate_difference(&a).unwrap(), 0);
+    }
+
+    #[test]
+    fn oversized_difference_is_refused() {
+        let local = SetReconciler::with_local_ids(ids(7, 40_000));
+        let remote = SetReconciler::new();
+        assert!(remote.iblt_for(&local.local_estimator()).is_err());
+    }
+
+    #[test]
+    fn malformed_iblt_bytes_are_rejected() {
+        assert!(Iblt::from_bytes(&[1, 0]).is_err());
+        assert!(Iblt::from_bytes(&[3, 0, 0, 0]).is_err());
+        assert!(StrataEstimator::from_bytes(&[0; 10]).is_err());
+    }
+
     #[test]
     fn request_missing_empty_ok() {
         let reconciler = SetReconciler::new();
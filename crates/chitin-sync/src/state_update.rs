@@ -0,0 +1,392 @@
+// crates/chitin-sync/src/state_update.rs
+//
+// Delta sync of Polyp state transitions.
+//
+// Set reconciliation only compares Polyp IDs, so a peer that already holds a
+// Polyp never learns when it moves from UnderReview to Approved or Hardened
+// on another node. A `PolypStateUpdate` carries just the transition: the new
+// state, its consensus and hardening metadata, the epoch it happened in, and
+// the announcing node's signature. Updates are gossiped when a transition
+// happens locally and pulled during sync from each peer's change log, a
+// time-ordered index of locally changed Polyps under
+// `state_change:{updated_ms:020}:{polyp_id}`.
+//
+// Conflicting updates resolve toward the higher epoch, then the later
+// lifecycle state. Hardened and Molted Polyps never move back to an earlier
+// state. Peers' updates must be signed by a registered validator and may not
+// claim a future epoch; molts are never taken from peers, since every node
+// molts its own Polyps.
+
+use chitin_core::consensus::{ConsensusMetadata, HardeningLineage};
+use chitin_core::crypto;
+use chitin_core::identity::NodeType;
+use chitin_core::polyp::{Polyp, PolypState};
+use chitin_core::registry::IdentityRegistry;
+use chitin_core::traits::PolypStore;
+use chitin_core::ChitinError;
+use chitin_store::RocksStore;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Key prefix for the change log: `state_change:{updated_ms:020}:{polyp_id}`.
+const CHANGE_PREFIX: &str = "state_change:";
+
+/// Key prefix for per-peer pull cursors: `state_cursor:{peer_url}`.
+const CURSOR_PREFIX: &str = "state_cursor:";
+
+/// Most updates a peer returns per `sync/state_updates` request.
+pub const MAX_STATE_UPDATES: usize = 512;
+
+/// A Polyp's state transition, as announced by the node that observed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolypStateUpdate {
+    /// The Polyp that changed.
+    pub polyp_id: Uuid,
+    /// Its new lifecycle state.
+    pub state: PolypState,
+    /// Consensus metadata, once evaluated.
+    #[serde(default)]
+    pub consensus: Option<ConsensusMetadata>,
+    /// Hardening lineage, once hardened.
+    #[serde(default)]
+    pub hardening: Option<HardeningLineage>,
+    /// Epoch in which the transition happened.
+    pub epoch: u64,
+    /// When the transition happened.
+    pub updated_at: DateTime<Utc>,
+    /// Hotkey of the announcing node, if signed.
+    #[serde(default)]
+    pub signer: Option<[u8; 32]>,
+    /// ed25519 signature over `signable_bytes()` by `signer`.
+    #[serde(default)]
+    pub signature: Option<Vec<u8>>,
+}
+
+impl PolypStateUpdate {
+    /// Describe `polyp`'s current state, which it entered in `epoch`.
+    ///
+    /// Polyps with consensus metadata use its epoch instead.
+    pub fn from_polyp(polyp: &Polyp, epoch: u64) -> Self {
+        Self {
+            polyp_id: polyp.id,
+            state: polyp.state.clone(),
+            consensus: polyp.consensus.clone(),
+            hardening: polyp.hardening.clone(),
+            epoch: polyp.consensus.as_ref().map_or(epoch, |c| c.epoch),
+            updated_at: polyp.updated_at,
+            signer: None,
+            signature: None,
+        }
+    }
+
+    /// Canonical bytes to sign: SHA-256 of the JSON-encoded transition.
+    pub fn signable_bytes(&self) -> Result<Vec<u8>, ChitinError> {
+        let body = serde_json::to_vec(&(
+            &self.polyp_id,
+            &self.state,
+            &self.consensus,
+            &self.hardening,
+            self.epoch,
+            &self.updated_at,
+        ))?;
+        Ok(crypto::hash_bytes(&body).to_vec())
+    }
+
+    /// Sign this update with the node's hotkey.
+    pub fn sign(&mut self, signing_key: &[u8; 32], hotkey: [u8; 32]) -> Result<(), ChitinError> {
+        let message = self.signable_bytes()?;
+        self.signature = Some(crypto::sign_message(signing_key, &message)?);
+        self.signer = Some(hotkey);
+        Ok(())
+    }
+
+    /// Verify the signature against `signer`.
+    ///
+    /// Returns `Ok(false)` for unsigned updates and invalid signatures.
+    pub fn verify_signature(&self) -> Result<bool, ChitinError> {
+        match (&self.signer, &self.signature) {
+            (Some(signer), Some(signature)) => {
+                crypto::verify_signature(signer, &self.signable_bytes()?, signature)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Check an update received from a peer: it must be signed by the
+    /// hotkey of a registered validator (Tide or Hybrid node), not be from an
+    /// epoch after `current_epoch`, and not be a molt.
+    pub fn verify_origin(
+        &self,
+        identities: &IdentityRegistry,
+        current_epoch: u64,
+    ) -> Result<(), ChitinError> {
+        if !self.verify_signature()? {
+            return Err(ChitinError::Verification(format!(
+                "State update for {} is unsigned or has an invalid signature",
+                self.polyp_id
+            )));
+        }
+        let validator = self
+            .signer
+            .and_then(|signer| identities.uid_of_hotkey(&signer))
+            .and_then(|uid| identities.get(uid))
+            .is_some_and(|record| matches!(record.node_type, NodeType::Tide | NodeType::Hybrid));
+        if !validator {
+            return Err(ChitinError::Verification(format!(
+                "State update for {} is not signed by a registered validator",
+                self.polyp_id
+            )));
+        }
+        if self.epoch > current_epoch {
+            return Err(ChitinError::Verification(format!(
+                "State update for {} is from future epoch {} (current {})",
+                self.polyp_id, self.epoch, current_epoch
+            )));
+        }
+        if matches!(self.state, PolypState::Molted { .. }) {
+            return Err(ChitinError::Verification(format!(
+                "Molt of {} not accepted from peers",
+                self.polyp_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// True if this update should replace a Polyp currently in `state`,
+    /// last evaluated in `epoch`.
+    ///
+    /// Higher epochs win, then later lifecycle states; Hardened and Molted
+    /// Polyps only move to a later state.
+    pub fn supersedes(&self, state: &PolypState, epoch: u64) -> bool {
+        let (new_rank, old_rank) = (state_rank(&self.state), state_rank(state));
+        if old_rank >= state_rank(&PolypState::Hardened) && new_rank <= old_rank {
+            return false;
+        }
+        (self.epoch, new_rank) > (epoch, old_rank)
+    }
+
+    /// True if this update should replace `polyp`'s current state.
+    pub fn supersedes_polyp(&self, polyp: &Polyp) -> bool {
+        self.polyp_id == polyp.id && self.supersedes(&polyp.state, polyp_epoch(polyp))
+    }
+
+    /// Apply the transition to `polyp`, keeping metadata it already has if
+    /// the update omits it.
+    pub fn apply(&self, polyp: &mut Polyp) {
+        polyp.state = self.state.clone();
+        if self.consensus.is_some() {
+            polyp.consensus = self.consensus.clone();
+        }
+        if self.hardening.is_some() {
+            polyp.hardening = self.hardening.clone();
+        }
+        polyp.updated_at = self.updated_at;
+    }
+}
+
+/// Position of a state in the lifecycle; later states rank higher.
+pub fn state_rank(state: &PolypState) -> u8 {
+    match state {
+        PolypState::Draft => 0,
+        PolypState::Soft => 1,
+        PolypState::UnderReview => 2,
+        PolypState::Approved | PolypState::Rejected => 3,
+        PolypState::Hardened => 4,
+        PolypState::Molted { .. } => 5,
+    }
+}
+
+/// Epoch a Polyp was last evaluated in (0 if never).
+pub fn polyp_epoch(polyp: &Polyp) -> u64 {
+    polyp.consensus.as_ref().map_or(0, |c| c.epoch)
+}
+
+/// Apply `update` to the stored Polyp if it supersedes the current state,
+/// saving it and recording the change. Returns `true` if applied.
+///
+/// Callers check the update's origin (`verify_origin`) and any hardening
+/// lineage first. Updates
+/// for Polyps not held locally are ignored; the Polyp arrives through sync
+/// in its current state.
+pub async fn store_state_update(
+    store: &RocksStore,
+    update: &PolypStateUpdate,
+) -> Result<bool, ChitinError> {
+    let mut polyp = match store.get_polyp(&update.polyp_id).await? {
+        Some(polyp) if update.supersedes_polyp(&polyp) => polyp,
+        _ => return Ok(false),
+    };
+    update.apply(&mut polyp);
+    store.save_polyp(&polyp).await?;
+    record_change(store, &polyp.id, Utc::now())?;
+    Ok(true)
+}
+
+// ---------------------------------------------------------------------------
+// Change log
+// ---------------------------------------------------------------------------
+
+/// Record that `polyp_id` changed state at `updated_at`.
+///
+/// Use the local time of the change, not the transition's own timestamp, so
+/// peers whose cursor has already passed that time still see it.
+pub fn record_change(
+    store: &RocksStore,
+    polyp_id: &Uuid,
+    updated_at: DateTime<Utc>,
+) -> Result<(), ChitinError> {
+    store.put_bytes(change_key(polyp_id, updated_at).as_bytes(), &[])
+}
+
+/// Polyps changed after the change-log `cursor` (from the start if `None`),
+/// oldest first, at most `limit`. Returns the changed IDs and the cursor of
+/// the last entry read.
+pub fn changes_after(
+    store: &RocksStore,
+    cursor: Option<&str>,
+    limit: usize,
+) -> Result<(Vec<Uuid>, Option<String>), ChitinError> {
+    let mut ids = Vec::new();
+    let mut last = None;
+    for (key, _) in store.scan_prefix(CHANGE_PREFIX.as_bytes())? {
+        let entry = match std::str::from_utf8(&key) {
+            Ok(key) => key[CHANGE_PREFIX.len()..].to_string(),
+            Err(_) => continue,
+        };
+        if cursor.is_some_and(|c| entry.as_str() <= c) {
+            continue;
+        }
+        if ids.len() == limit {
+            break;
+        }
+        let id = entry.rsplit(':').next().and_then(|id| id.parse::<Uuid>().ok());
+        if let Some(id) = id {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        last = Some(entry);
+    }
+    Ok((ids, last))
+}
+
+/// Load the change-log cursor reached on `peer_url`.
+pub fn load_cursor(store: &RocksStore, peer_url: &str) -> Result<Option<String>, ChitinError> {
+    let key = format!("{}{}", CURSOR_PREFIX, peer_url);
+    Ok(store
+        .get_bytes(key.as_bytes())?
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Persist the change-log cursor reached on `peer_url`.
+pub fn save_cursor(store: &RocksStore, peer_url: &str, cursor: &str) -> Result<(), ChitinError> {
+    let key = format!("{}{}", CURSOR_PREFIX, peer_url);
+    store.put_bytes(key.as_bytes(), cursor.as_bytes())
+}
+
+fn change_key(polyp_id: &Uuid, updated_at: DateTime<Utc>) -> String {
+    let ms = updated_at.timestamp_millis().max(0);
+    format!("{}{:020}:{}", CHANGE_PREFIX, ms, polyp_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chitin_core::crypto::Keypair;
+
+    fn update(state: PolypState, epoch: u64) -> PolypStateUpdate {
+        PolypStateUpdate {
+            polyp_id: Uuid::now_v7(),
+            state,
+            consensus: None,
+            hardening: None,
+            epoch,
+            updated_at: Utc::now(),
+            signer: None,
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn conflicts_prefer_higher_epoch_then_later_state() {
+        let hardened = update(PolypState::Hardened, 5);
+        assert!(hardened.supersedes(&PolypState::UnderReview, 5));
+        assert!(hardened.supersedes(&PolypState::Approved, 5));
+        assert!(!hardened.supersedes(&PolypState::Hardened, 5));
+
+        let approved = update(PolypState::Approved, 5);
+        assert!(!approved.supersedes(&PolypState::Hardened, 5));
+        // A later epoch wins, except over a hardened polyp.
+        assert!(update(PolypState::UnderReview, 6).supersedes(&PolypState::Approved, 5));
+        assert!(!update(PolypState::UnderReview, 6).supersedes(&PolypState::Hardened, 5));
+        assert!(!update(PolypState::Approved, 4).supersedes(&PolypState::UnderReview, 5));
+
+        let molted = update(PolypState::Molted { successor_id: Uuid::now_v7() }, 5);
+        assert!(molted.supersedes(&PolypState::Hardened, 5));
+    }
+
+    #[test]
+    fn signatures_cover_the_transition() {
+        let keypair = Keypair::generate();
+        let mut signed = update(PolypState::Approved, 3);
+        assert!(!signed.verify_signature().unwrap());
+
+        signed
+            .sign(&keypair.signing_key.to_bytes(), keypair.public_key_bytes())
+            .unwrap();
+        assert!(signed.verify_signature().unwrap());
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let decoded: PolypStateUpdate = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify_signature().unwrap());
+
+        let mut tampered = signed;
+        tampered.state = PolypState::Hardened;
+        assert!(!tampered.verify_signature().unwrap());
+    }
+
+    #[test]
+    fn peer_updates_need_a_validator_signature_and_a_past_epoch() {
+        let validator = Keypair::generate();
+        let coral = Keypair::generate();
+        let mut identities = IdentityRegistry::new();
+        identities
+            .register([1; 32], validator.public_key_bytes(), NodeType::Tide, 0)
+            .unwrap();
+        identities
+            .register([2; 32], coral.public_key_bytes(), NodeType::Coral, 0)
+            .unwrap();
+        let signed = |keypair: &Keypair, state: PolypState, epoch: u64| {
+            let mut update = update(state, epoch);
+            update
+                .sign(&keypair.signing_key.to_bytes(), keypair.public_key_bytes())
+                .unwrap();
+            update
+        };
+
+        assert!(signed(&validator, PolypState::Approved, 5)
+            .verify_origin(&identities, 5)
+            .is_ok());
+        // Unsigned, or signed by a non-validator or an unregistered key.
+        assert!(update(PolypState::Approved, 5)
+            .verify_origin(&identities, 5)
+            .is_err());
+        assert!(signed(&coral, PolypState::Approved, 5)
+            .verify_origin(&identities, 5)
+            .is_err());
+        assert!(signed(&Keypair::generate(), PolypState::Approved, 5)
+            .verify_origin(&identities, 5)
+            .is_err());
+        // A future epoch would pin the state against later evaluations.
+        assert!(signed(&validator, PolypState::Rejected, u64::MAX)
+            .verify_origin(&identities, 5)
+            .is_err());
+        let molted = PolypState::Molted {
+            successor_id: Uuid::now_v7(),
+        };
+        assert!(signed(&validator, molted, 5)
+            .verify_origin(&identities, 5)
+            .is_err());
+    }
+}
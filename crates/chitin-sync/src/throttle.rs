@@ -0,0 +1,239 @@
+// crates/chitin-sync/src/throttle.rs
+//
+// Backpressure for Polyp ingestion.
+//
+// Pull-sync and gossip (`peer/receive_polyp`) both write Polyps into the
+// local store, and a large catch-up can otherwise saturate it and starve RPC
+// queries. Every unit of ingestion work (a fetched batch, a relayed Polyp)
+// holds a permit: a global semaphore bounds work in flight across all
+// sources, and a per-source semaphore keeps one peer from taking every
+// permit. Store write latency is tracked as an exponentially weighted moving
+// average; while it exceeds the target, each unit of work is delayed in
+// proportion to the excess so writes back off before queries suffer.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chitin_core::ChitinError;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Weight of the newest sample in the write latency average.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Delay added per unit of write latency above target.
+const PACING_GAIN: f64 = 2.0;
+
+/// Per-source semaphores kept before idle ones are dropped.
+const MAX_TRACKED_SOURCES: usize = 1024;
+
+/// Limits for Polyp ingestion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThrottleConfig {
+    /// Units of ingestion work in flight across all sources.
+    pub max_inflight: usize,
+    /// Units in flight from any one peer.
+    pub per_peer_inflight: usize,
+    /// Store write latency above which ingestion is paced (ms).
+    pub target_write_ms: u64,
+    /// Longest delay pacing adds per unit of work (ms).
+    pub max_delay_ms: u64,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            max_inflight: 32,
+            per_peer_inflight: 4,
+            target_write_ms: 20,
+            max_delay_ms: 1000,
+        }
+    }
+}
+
+/// Shared ingestion throttle for pull-sync and gossip.
+#[derive(Debug)]
+pub struct SyncThrottle {
+    config: ThrottleConfig,
+    global: Arc<Semaphore>,
+    peers: Mutex<HashMap<String, Arc<Semaphore>>>,
+    /// Average store write latency in milliseconds, once sampled.
+    write_latency_ms: Mutex<Option<f64>>,
+}
+
+/// Permission to perform one unit of ingestion work; released on drop.
+#[derive(Debug)]
+pub struct SyncPermit {
+    _peer: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+impl SyncThrottle {
+    /// Create a throttle, rejecting zero limits (they would never admit work).
+    pub fn new(config: ThrottleConfig) -> Result<Self, ChitinError> {
+        if config.max_inflight == 0 || config.per_peer_inflight == 0 {
+            return Err(ChitinError::InvalidState(
+                "Throttle inflight limits must be at least 1".to_string(),
+            ));
+        }
+        Ok(Self {
+            global: Arc::new(Semaphore::new(config.max_inflight)),
+            config,
+            peers: Mutex::new(HashMap::new()),
+            write_latency_ms: Mutex::new(None),
+        })
+    }
+
+    /// The configured limits.
+    pub fn config(&self) -> &ThrottleConfig {
+        &self.config
+    }
+
+    /// Wait for a permit to ingest from `source`.
+    ///
+    /// The per-source permit is taken first, so a source at its cap waits
+    /// without holding a global permit other sources could use.
+    pub async fn acquire(&self, source: &str) -> SyncPermit {
+        let peer = self
+            .peer_semaphore(source)
+            .acquire_owned()
+            .await
+            .expect("throttle semaphores are never closed");
+        let global = self
+            .global
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("throttle semaphores are never closed");
+        SyncPermit {
+            _peer: peer,
+            _global: global,
+        }
+    }
+
+    /// Take a permit for `source` only if one is free right now.
+    pub fn try_acquire(&self, source: &str) -> Option<SyncPermit> {
+        let peer = self.peer_semaphore(source).try_acquire_owned().ok()?;
+        let global = self.global.clone().try_acquire_owned().ok()?;
+        Some(SyncPermit {
+            _peer: peer,
+            _global: global,
+        })
+    }
+
+    /// Permits currently free across all sources.
+    pub fn available(&self) -> usize {
+        self.global.available_permits()
+    }
+
+    /// Record how long one store write took.
+    pub fn record_write(&self, elapsed: Duration) {
+        let sample = elapsed.as_secs_f64() * 1000.0;
+        let mut average = self.write_latency_ms.lock().unwrap_or_else(|e| e.into_inner());
+        *average = Some(match *average {
+            Some(avg) => avg + LATENCY_EWMA_ALPHA * (sample - avg),
+            None => sample,
+        });
+    }
+
+    /// Average store write latency (zero before any write is recorded).
+    pub fn write_latency(&self) -> Duration {
+        let average = self.write_latency_ms.lock().unwrap_or_else(|e| e.into_inner());
+        from_ms(average.unwrap_or(0.0))
+    }
+
+    /// Delay to apply before the next unit of work.
+    ///
+    /// Zero while writes are within target; otherwise `PACING_GAIN` times
+    /// the excess latency, capped at `max_delay_ms`.
+    pub fn pacing_delay(&self) -> Duration {
+        let latency_ms = self.write_latency().as_secs_f64() * 1000.0;
+        let excess_ms = latency_ms - self.config.target_write_ms as f64;
+        if excess_ms <= 0.0 {
+            return Duration::ZERO;
+        }
+        let delay_ms = (excess_ms * PACING_GAIN).min(self.config.max_delay_ms as f64);
+        from_ms(delay_ms)
+    }
+
+    /// Sleep for the current pacing delay, if any.
+    pub async fn pace(&self) {
+        let delay = self.pacing_delay();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn peer_semaphore(&self, source: &str) -> Arc<Semaphore> {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        if peers.len() >= MAX_TRACKED_SOURCES {
+            // Sources with no work in flight lose nothing by starting afresh.
+            let cap = self.config.per_peer_inflight;
+            peers.retain(|_, semaphore| semaphore.available_permits() < cap);
+        }
+        peers
+            .entry(source.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.config.per_peer_inflight)))
+            .clone()
+    }
+}
+
+/// Duration from fractional milliseconds, rounded to the nanosecond.
+fn from_ms(ms: f64) -> Duration {
+    Duration::from_nanos((ms * 1_000_000.0).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(max_inflight: usize, per_peer_inflight: usize) -> SyncThrottle {
+        SyncThrottle::new(ThrottleConfig {
+            max_inflight,
+            per_peer_inflight,
+            ..ThrottleConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn permits_respect_global_and_per_peer_caps() {
+        let throttle = throttle(3, 2);
+        let a1 = throttle.acquire("a").await;
+        let _a2 = throttle.acquire("a").await;
+        assert!(throttle.try_acquire("a").is_none());
+
+        let _b1 = throttle.acquire("b").await;
+        assert_eq!(throttle.available(), 0);
+        assert!(throttle.try_acquire("c").is_none());
+
+        drop(a1);
+        assert!(throttle.try_acquire("c").is_some());
+        assert!(SyncThrottle::new(ThrottleConfig {
+            max_inflight: 0,
+            ..ThrottleConfig::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn pacing_tracks_write_latency() {
+        let throttle = throttle(4, 2);
+        assert_eq!(throttle.pacing_delay(), Duration::ZERO);
+
+        throttle.record_write(Duration::from_millis(10));
+        assert_eq!(throttle.pacing_delay(), Duration::ZERO);
+
+        // 10ms then 110ms: average 30ms, 10ms over the 20ms target.
+        throttle.record_write(Duration::from_millis(110));
+        assert_eq!(throttle.write_latency().as_millis(), 30);
+        assert_eq!(throttle.pacing_delay().as_millis(), 20);
+
+        for _ in 0..50 {
+            throttle.record_write(Duration::from_secs(5));
+        }
+        assert_eq!(throttle.pacing_delay(), Duration::from_millis(1000));
+    }
+}
@@ -0,0 +1,115 @@
+// crates/chitin-sync/src/tombstone.rs
+//
+// Tombstones for pruned Polyps.
+//
+// Non-archival nodes delete Polyps they no longer need to serve, such as
+// long-molted predecessors. Peers that still hold those Polyps keep
+// reporting them in set reconciliation, so without a record of the deletion
+// the next sync round would pull them straight back. A tombstone under
+// `tombstone:{polyp_id}` marks the Polyp as deliberately pruned; sync and
+// gossip skip tombstoned IDs. Tombstones are pruned in turn once peers can
+// be expected to have pruned the same Polyps.
+
+use std::collections::HashSet;
+
+use chitin_core::ChitinError;
+use chitin_store::{Reclaimed, RocksStore};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Key prefix for tombstones: `tombstone:{polyp_id}`.
+const KEY_PREFIX: &str = "tombstone:";
+
+/// Record of a Polyp deleted by pruning.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// The pruned Polyp.
+    pub polyp_id: Uuid,
+    /// Epoch in which it was pruned.
+    pub epoch: u64,
+    /// When it was pruned.
+    pub pruned_at: DateTime<Utc>,
+}
+
+impl Tombstone {
+    /// A tombstone for `polyp_id`, pruned now in `epoch`.
+    pub fn new(polyp_id: Uuid, epoch: u64) -> Self {
+        Self {
+            polyp_id,
+            epoch,
+            pruned_at: Utc::now(),
+        }
+    }
+
+    /// Persist this tombstone.
+    pub fn save(&self, store: &RocksStore) -> Result<(), ChitinError> {
+        store.put_bytes(tombstone_key(&self.polyp_id).as_bytes(), &serde_json::to_vec(self)?)
+    }
+
+    /// Remove the tombstone for `polyp_id`, if any.
+    pub fn delete(store: &RocksStore, polyp_id: &Uuid) -> Result<(), ChitinError> {
+        store.delete_bytes(tombstone_key(polyp_id).as_bytes())
+    }
+
+    /// Delete tombstones of Polyps pruned before `epoch`.
+    pub fn prune_before(store: &RocksStore, epoch: u64) -> Result<Reclaimed, ChitinError> {
+        store.prune_prefix(KEY_PREFIX.as_bytes(), |_, value| {
+            serde_json::from_slice::<Tombstone>(value).is_ok_and(|t| t.epoch < epoch)
+        })
+    }
+}
+
+/// True if `polyp_id` has been pruned locally.
+pub fn is_tombstoned(store: &RocksStore, polyp_id: &Uuid) -> Result<bool, ChitinError> {
+    Ok(store.get_bytes(tombstone_key(polyp_id).as_bytes())?.is_some())
+}
+
+/// IDs of every pruned Polyp.
+pub fn tombstoned_ids(store: &RocksStore) -> Result<HashSet<Uuid>, ChitinError> {
+    Ok(store
+        .scan_prefix(KEY_PREFIX.as_bytes())?
+        .into_iter()
+        .filter_map(|(key, _)| {
+            std::str::from_utf8(&key[KEY_PREFIX.len()..])
+                .ok()
+                .and_then(|id| id.parse().ok())
+        })
+        .collect())
+}
+
+fn tombstone_key(polyp_id: &Uuid) -> String {
+    format!("{}{}", KEY_PREFIX, polyp_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tombstones_mark_pruned_ids_until_pruned() {
+        let path = std::env::temp_dir().join(format!(
+            "chitin-sync-tombstone-test-{}",
+            std::process::id()
+        ));
+        let store = RocksStore::open(path.to_str().unwrap()).unwrap();
+        let (pruned, kept) = (Uuid::now_v7(), Uuid::now_v7());
+
+        Tombstone::new(pruned, 7).save(&store).unwrap();
+        assert!(is_tombstoned(&store, &pruned).unwrap());
+        assert!(!is_tombstoned(&store, &kept).unwrap());
+        assert_eq!(tombstoned_ids(&store).unwrap(), HashSet::from([pruned]));
+
+        Tombstone::new(kept, 9).save(&store).unwrap();
+        let reclaimed = Tombstone::prune_before(&store, 8).unwrap();
+        assert_eq!(reclaimed.entries, 1);
+        assert!(reclaimed.bytes > 0);
+        assert_eq!(tombstoned_ids(&store).unwrap(), HashSet::from([kept]));
+
+        Tombstone::delete(&store, &kept).unwrap();
+        assert!(tombstoned_ids(&store).unwrap().is_empty());
+
+        drop(store);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -0,0 +1,128 @@
+// crates/chitin-sync/src/transfer.rs
+//
+// Batched Polyp transfer encoding for pull-sync.
+//
+// Sync fetches missing Polyps in batches (`peer/get_polyps_batch`) instead of
+// one `polyp/get` per Polyp. A batch is sent either as plain JSON or, when
+// the requester asks for it, as a zstd-compressed JSON array carried as
+// base64. Embedding vectors dominate Polyp size and compress well, so
+// compression roughly halves transfer volume during initial sync.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chitin_core::polyp::Polyp;
+use chitin_core::ChitinError;
+use serde::{Deserialize, Serialize};
+
+/// Most Polyps a peer returns per batch request.
+pub const MAX_POLYP_BATCH: usize = 256;
+
+/// Largest decompressed batch accepted, in bytes.
+pub const MAX_DECOMPRESSED_BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+/// zstd level used for batches (fast, decent ratio).
+const ZSTD_LEVEL: i32 = 3;
+
+/// Compression applied to a Polyp batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// zstd over the JSON-encoded Polyp array.
+    Zstd,
+}
+
+/// A compressed Polyp batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressedPolyps {
+    /// Codec used.
+    pub compression: Compression,
+    /// Size of the JSON array before compression.
+    pub uncompressed_len: usize,
+    /// Base64-encoded compressed bytes.
+    pub data: String,
+}
+
+impl CompressedPolyps {
+    /// Compress a batch of Polyps.
+    pub fn compress(polyps: &[Polyp], compression: Compression) -> Result<Self, ChitinError> {
+        let json = serde_json::to_vec(polyps)?;
+        let compressed = match compression {
+            Compression::Zstd => zstd::bulk::compress(&json, ZSTD_LEVEL).map_err(zstd_error)?,
+        };
+        Ok(Self {
+            compression,
+            uncompressed_len: json.len(),
+            data: BASE64.encode(compressed),
+        })
+    }
+
+    /// Decompress and decode the batch.
+    pub fn decompress(&self) -> Result<Vec<Polyp>, ChitinError> {
+        if self.uncompressed_len > MAX_DECOMPRESSED_BATCH_BYTES {
+            return Err(ChitinError::Serialization(format!(
+                "Polyp batch of {} bytes exceeds limit of {}",
+                self.uncompressed_len, MAX_DECOMPRESSED_BATCH_BYTES
+            )));
+        }
+        let compressed = BASE64
+            .decode(&self.data)
+            .map_err(|e| ChitinError::Serialization(format!("Invalid batch base64: {}", e)))?;
+        let json = match self.compression {
+            Compression::Zstd => zstd::bulk::decompress(&compressed, MAX_DECOMPRESSED_BATCH_BYTES)
+                .map_err(zstd_error)?,
+        };
+        if json.len() != self.uncompressed_len {
+            return Err(ChitinError::Serialization(format!(
+                "Decompressed {} bytes, expected {}",
+                json.len(),
+                self.uncompressed_len
+            )));
+        }
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+/// A zstd failure as a serialization error.
+fn zstd_error(e: std::io::Error) -> ChitinError {
+    ChitinError::Serialization(format!("zstd: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zstd_round_trip_and_size_checks() {
+        let data = b"polyp polyp polyp polyp polyp polyp polyp polyp".repeat(100);
+        let compressed = zstd::bulk::compress(&data, ZSTD_LEVEL).unwrap();
+        assert!(compressed.len() < data.len() / 10);
+        assert_eq!(
+            zstd::bulk::decompress(&compressed, data.len()).unwrap(),
+            data
+        );
+        let batch = CompressedPolyps {
+            compression: Compression::Zstd,
+            uncompressed_len: data.len() - 1,
+            data: BASE64.encode(&compressed),
+        };
+        assert!(batch.decompress().is_err());
+        let garbage = CompressedPolyps {
+            data: BASE64.encode(b"not zstd"),
+            ..batch
+        };
+        assert!(garbage.decompress().is_err());
+    }
+
+    #[test]
+    fn compressed_batches_round_trip() {
+        let batch = CompressedPolyps::compress(&[], Compression::Zstd).unwrap();
+        assert!(batch.decompress().unwrap().is_empty());
+
+        let mut oversized = batch.clone();
+        oversized.uncompressed_len = MAX_DECOMPRESSED_BATCH_BYTES + 1;
+        assert!(oversized.decompress().is_err());
+
+        let json = serde_json::to_string(&batch).unwrap();
+        assert!(json.contains("\"zstd\""));
+    }
+}
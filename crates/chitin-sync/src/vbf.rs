@@ -11,6 +11,14 @@ use bloomfilter::Bloom;
 use chitin_core::ChitinError;
 use uuid::Uuid;
 
+/// Minimum capacity for filters built from ID sets, so small or empty sets
+/// still get a usable false positive rate.
+pub const MIN_FILTER_CAPACITY: usize = 64;
+
+/// Most hash functions a received filter may use. Filters built here use
+/// about 7; a larger count only makes each membership check slower.
+pub const MAX_HASH_FUNCTIONS: u32 = 64;
+
 /// A Vector Bloom Filter wrapping a probabilistic set membership structure.
 ///
 /// Used for efficient set reconciliation between peers. Each node
@@ -40,6 +48,15 @@ impl VectorBloomFilter {
         Self { inner: bloom }
     }
 
+    /// Build a filter sized for `ids` and insert all of them.
+    pub fn from_ids<'a>(ids: impl ExactSizeIterator<Item = &'a Uuid>) -> Self {
+        let mut vbf = Self::new(ids.len().max(MIN_FILTER_CAPACITY));
+        for id in ids {
+            vbf.insert(id);
+        }
+        vbf
+    }
+
     /// Insert a Polyp UUID into the Bloom filter.
     pub fn insert(&mut self, id: &Uuid) {
         self.inner.set(&id.into_bytes());
@@ -83,9 +100,22 @@ impl VectorBloomFilter {
         buf
     }
 
+    /// Serialize to a lowercase hex string (the `to_bytes` format), for
+    /// JSON-RPC transport.
+    pub fn to_hex(&self) -> String {
+        crate::hex::encode(&self.to_bytes())
+    }
+
+    /// Deserialize from a hex string produced by `to_hex`.
+    pub fn from_hex(hex: &str) -> Result<Self, ChitinError> {
+        Self::from_bytes(&crate::hex::decode(hex, "VBF")?)
+    }
+
     /// Deserialize a Bloom filter from bytes received from a peer.
     ///
-    /// Returns an error if the data is too short (< 44 bytes header).
+    /// Returns an error if the data is too short (< 44 bytes header), if
+    /// the bitmap is empty or its length disagrees with `bitmap_bits`, or if
+    /// `k_num` is 0 or above `MAX_HASH_FUNCTIONS`.
     pub fn from_bytes(data: &[u8]) -> Result<Self, ChitinError> {
         const HEADER_SIZE: usize = 44;
         if data.len() < HEADER_SIZE {
@@ -130,6 +160,20 @@ impl VectorBloomFilter {
         let sip_keys = [(sip_key_0_0, sip_key_0_1), (sip_key_1_0, sip_key_1_1)];
         let bitmap_bytes = &data[HEADER_SIZE..];
 
+        if bitmap_bits == 0 || bitmap_bits.div_ceil(8) != bitmap_bytes.len() as u64 {
+            return Err(ChitinError::Serialization(format!(
+                "VBF bitmap of {} bytes does not hold {} bits",
+                bitmap_bytes.len(),
+                bitmap_bits
+            )));
+        }
+        if k_num == 0 || k_num > MAX_HASH_FUNCTIONS {
+            return Err(ChitinError::Serialization(format!(
+                "VBF hash function count {} is outside 1..={}",
+                k_num, MAX_HASH_FUNCTIONS
+            )));
+        }
+
         let bloom = Bloom::from_existing(bitmap_bytes, bitmap_bits, k_num, sip_keys);
         Ok(VectorBloomFilter { inner: bloom })
     }
@@ -172,6 +216,16 @@ mod tests {
         assert!(!restored.contains(&id_absent));
     }
 
+    #[test]
+    fn hex_roundtrip_from_ids() {
+        let ids: Vec<Uuid> = (0..10).map(|_| Uuid::now_v7()).collect();
+        let vbf = VectorBloomFilter::from_ids(ids.iter());
+        let restored = VectorBloomFilter::from_hex(&vbf.to_hex()).expect("hex should decode");
+        assert!(ids.iter().all(|id| restored.contains(id)));
+        assert!(VectorBloomFilter::from_hex("abc").is_err());
+        assert!(VectorBloomFilter::from_hex("zz").is_err());
+    }
+
     #[test]
     fn from_bytes_too_short_returns_error() {
         let short_data = vec![0u8; 10];
@@ -182,6 +236,25 @@ mod tests {
         assert!(msg.contains("too short"), "Error message should mention 'too short', got: {}", msg);
     }
 
+    #[test]
+    fn from_bytes_rejects_inconsistent_headers() {
+        let bytes = VectorBloomFilter::new(100).to_bytes();
+
+        let mut zero_bits = bytes.clone();
+        zero_bits[0..8].copy_from_slice(&0u64.to_le_bytes());
+        let mut too_many_bits = bytes.clone();
+        too_many_bits[0..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut zero_hashes = bytes.clone();
+        zero_hashes[8..12].copy_from_slice(&0u32.to_le_bytes());
+        let mut too_many_hashes = bytes.clone();
+        too_many_hashes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        let truncated = bytes[..bytes.len() - 1].to_vec();
+
+        for data in [zero_bits, too_many_bits, zero_hashes, too_many_hashes, truncated] {
+            assert!(VectorBloomFilter::from_bytes(&data).is_err());
+        }
+    }
+
     #[test]
     fn roundtrip_100_items_preserved() {
         let mut vbf = VectorBloomFilter::new(200);
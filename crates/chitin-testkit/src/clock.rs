@@ -0,0 +1,125 @@
+// crates/chitin-testkit/src/clock.rs
+//
+// Synthetic block clock shared by every node of a simulated cluster.
+//
+// The test advances one `SimClock`; each node's scheduler follows it through
+// a `ClockBlockSource`, which yields every height in order (never skipping),
+// so all nodes see the same phase changes and epoch boundaries at the same
+// heights, however their tasks are scheduled. Nothing advances on its own:
+// blocks only arrive when the scenario says so.
+
+use tokio::sync::watch;
+
+use chitin_core::ChitinError;
+use chitin_node::block_source::BlockSource;
+
+/// The cluster's block height, advanced by the test.
+#[derive(Debug, Clone)]
+pub struct SimClock {
+    height: watch::Sender<u64>,
+}
+
+impl SimClock {
+    /// A clock at block 0.
+    pub fn new() -> Self {
+        Self {
+            height: watch::Sender::new(0),
+        }
+    }
+
+    /// The current height.
+    pub fn height(&self) -> u64 {
+        *self.height.borrow()
+    }
+
+    /// Advance by `blocks` and return the new height.
+    pub fn advance(&self, blocks: u64) -> u64 {
+        self.height.send_modify(|height| *height += blocks);
+        self.height()
+    }
+
+    /// A block source following this clock from block 0.
+    pub fn source(&self) -> ClockBlockSource {
+        ClockBlockSource {
+            height: self.height.subscribe(),
+            next: 1,
+        }
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Block source yielding each height of a `SimClock` once, in order.
+#[derive(Debug)]
+pub struct ClockBlockSource {
+    height: watch::Receiver<u64>,
+    /// The next height to yield.
+    next: u64,
+}
+
+#[async_trait::async_trait]
+impl BlockSource for ClockBlockSource {
+    fn describe(&self) -> String {
+        "simulated clock".to_string()
+    }
+
+    async fn next_block(&mut self) -> Result<Option<u64>, ChitinError> {
+        let next = self.next;
+        if self
+            .height
+            .wait_for(|height| *height >= next)
+            .await
+            .is_err()
+        {
+            // The clock was dropped with the cluster.
+            return Ok(None);
+        }
+        self.next += 1;
+        Ok(Some(next))
+    }
+
+    fn resume_from(&mut self, block: u64) {
+        self.next = self.next.max(block + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sources_yield_every_height_in_order() {
+        let clock = SimClock::new();
+        let mut source = clock.source();
+        assert_eq!(clock.advance(3), 3);
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(source.next_block().await.unwrap().unwrap());
+        }
+        assert_eq!(seen, vec![1, 2, 3]);
+
+        // Nothing more until the clock advances.
+        let pending =
+            tokio::time::timeout(std::time::Duration::from_millis(20), source.next_block()).await;
+        assert!(pending.is_err());
+        clock.advance(1);
+        assert_eq!(source.next_block().await.unwrap(), Some(4));
+    }
+
+    #[tokio::test]
+    async fn sources_end_with_the_clock_and_resume_past_restored_blocks() {
+        let clock = SimClock::new();
+        let mut source = clock.source();
+        source.resume_from(10);
+        clock.advance(11);
+        assert_eq!(source.next_block().await.unwrap(), Some(11));
+
+        drop(clock);
+        assert_eq!(source.next_block().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,600 @@
+// crates/chitin-testkit/src/cluster.rs
+//
+// In-process clusters of Chitin nodes.
+//
+// `ClusterBuilder` starts N nodes in this process, each with:
+//
+// - an in-memory polyp store (`RocksStore::open_in_memory`); the node's
+//   auxiliary databases live in a temporary directory removed on shutdown
+// - a fixed identity derived from its index, so DIDs and signatures are the
+//   same on every run
+// - a `ClockBlockSource` following the cluster's shared `SimClock`
+// - an RPC server on a loopback port, with every other node as a peer
+//
+// Nodes talk to each other over loopback HTTP exactly as deployed nodes do
+// (gossip, pull sync, state updates). A partition replaces each node's
+// configured peers with the nodes on its side, so no traffic crosses it;
+// healing restores the full mesh.
+//
+// Distributed behavior converges rather than completing, so assertions poll:
+// `await_polyps` and `await_consensus` wait until the given nodes agree and
+// report what differs if they do not within the timeout.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use chitin_consensus::history::ConsensusRecord;
+use chitin_core::crypto::Keypair;
+use chitin_core::identity::{NodeIdentity, NodeType};
+use chitin_core::keystore::SecretKey;
+use chitin_core::{ChitinError, PolypState};
+use chitin_node::{DaemonConfig, NodeBuilder, NodeHandle};
+use chitin_rpc::handlers::polyp::{SubmitPolypRequest, SubmitPolypResponse};
+use chitin_store::RocksStore;
+
+use crate::clock::SimClock;
+
+/// Default time to wait for nodes to converge.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often convergence conditions are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Unreachable IPFS endpoint: nodes open their hardened store, but pinning
+/// fails fast instead of waiting on a daemon.
+const NO_IPFS: &str = "http://127.0.0.1:9";
+
+/// The polyps a node holds, with their states.
+type PolypView = BTreeMap<Uuid, PolypState>;
+
+/// A config adjustment applied to node `index` before it starts.
+type Configure = Arc<dyn Fn(usize, &mut DaemonConfig) + Send + Sync>;
+
+/// Builds and starts a cluster.
+pub struct ClusterBuilder {
+    nodes: usize,
+    node_type: String,
+    blocks_per_epoch: u64,
+    configure: Option<Configure>,
+}
+
+impl ClusterBuilder {
+    /// `nodes` Hybrid nodes with 10-block epochs.
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            nodes,
+            node_type: "hybrid".to_string(),
+            blocks_per_epoch: 10,
+            configure: None,
+        }
+    }
+
+    /// Run the nodes as `node_type` ("coral" or "hybrid"; Tide nodes have no
+    /// polyp RPC to submit through).
+    pub fn node_type(mut self, node_type: &str) -> Self {
+        self.node_type = node_type.to_string();
+        self
+    }
+
+    /// Blocks per epoch on every node.
+    pub fn blocks_per_epoch(mut self, blocks: u64) -> Self {
+        self.blocks_per_epoch = blocks;
+        self
+    }
+
+    /// Adjust each node's configuration (called with the node's index).
+    pub fn configure(
+        mut self,
+        f: impl Fn(usize, &mut DaemonConfig) + Send + Sync + 'static,
+    ) -> Self {
+        self.configure = Some(Arc::new(f));
+        self
+    }
+
+    /// Start every node and wait for its RPC server to answer.
+    pub async fn start(self) -> Result<Cluster, ChitinError> {
+        if self.nodes == 0 {
+            return Err(ChitinError::InvalidState(
+                "A cluster needs at least one node".into(),
+            ));
+        }
+        let root = std::env::temp_dir().join(format!("chitin_testkit_{}", Uuid::now_v7()));
+        let clock = SimClock::new();
+        let urls = (0..self.nodes)
+            .map(|_| free_port().map(|port| format!("http://127.0.0.1:{}", port)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut cluster = Cluster {
+            nodes: Vec::with_capacity(self.nodes),
+            clock,
+            root,
+            blocks_per_epoch: self.blocks_per_epoch,
+            submitted: Vec::new(),
+        };
+        for index in 0..self.nodes {
+            let node = self.start_node(index, &urls, &cluster).await?;
+            cluster.nodes.push(node);
+        }
+        for index in 0..self.nodes {
+            let url = cluster.nodes[index].url.clone();
+            eventually(
+                DEFAULT_TIMEOUT,
+                &format!("node {} RPC at {}", index, url),
+                || {
+                    let url = url.clone();
+                    async move {
+                        tokio::net::TcpStream::connect(url.trim_start_matches("http://"))
+                            .await
+                            .ok()
+                    }
+                },
+            )
+            .await?;
+        }
+        Ok(cluster)
+    }
+
+    async fn start_node(
+        &self,
+        index: usize,
+        urls: &[String],
+        cluster: &Cluster,
+    ) -> Result<SimNode, ChitinError> {
+        let url = urls[index].clone();
+        let data_dir = cluster.root.join(format!("node{}", index));
+        std::fs::create_dir_all(&data_dir)
+            .map_err(|e| ChitinError::Storage(format!("Failed to create data dir: {}", e)))?;
+        let store = Arc::new(RocksStore::open_in_memory(&format!("node{}", index))?);
+
+        let mut config = DaemonConfig {
+            node_type: self.node_type.clone(),
+            data_dir: data_dir.to_string_lossy().to_string(),
+            rpc_host: "127.0.0.1".to_string(),
+            rpc_port: port_of(&url),
+            self_url: Some(url.clone()),
+            peers: urls.iter().filter(|u| **u != url).cloned().collect(),
+            sync_interval_secs: 1,
+            blocks_per_epoch: self.blocks_per_epoch,
+            ipfs_api_url: NO_IPFS.to_string(),
+            ..DaemonConfig::default()
+        };
+        if let Some(configure) = &self.configure {
+            configure(index, &mut config);
+        }
+
+        let (identity, signing_key) = identity(index, &self.node_type);
+        let handle = NodeBuilder::new(config)
+            .with_store(store)
+            .with_identity(identity, Some(signing_key))
+            .with_block_source(Box::new(cluster.clock.source()))
+            .start()
+            .await
+            .map_err(|e| {
+                ChitinError::InvalidState(format!("Node {} failed to start: {}", index, e))
+            })?;
+        Ok(SimNode { index, url, handle })
+    }
+}
+
+/// One node of a cluster.
+pub struct SimNode {
+    index: usize,
+    url: String,
+    handle: NodeHandle,
+}
+
+impl SimNode {
+    /// Position in the cluster.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Loopback URL peers reach the node at.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The running node.
+    pub fn handle(&self) -> &NodeHandle {
+        &self.handle
+    }
+
+    /// Every polyp the node holds, with its state.
+    pub fn polyps(&self) -> Result<BTreeMap<Uuid, PolypState>, ChitinError> {
+        let store = self.handle.store();
+        let mut polyps = BTreeMap::new();
+        for (key, _) in store.scan_prefix(b"polyp:")? {
+            let Some(id) = std::str::from_utf8(&key[b"polyp:".len()..])
+                .ok()
+                .and_then(|id| Uuid::parse_str(id).ok())
+            else {
+                continue;
+            };
+            if let Some(polyp) = store.get_polyp_sync(&id)? {
+                polyps.insert(id, polyp.state);
+            }
+        }
+        Ok(polyps)
+    }
+
+    /// Move every Draft polyp the node holds to Soft, so the next Scoring
+    /// phase scores it. Returns how many moved.
+    pub fn soften(&self) -> Result<usize, ChitinError> {
+        let store = self.handle.store();
+        let mut moved = 0;
+        for id in self.polyps()?.keys() {
+            let Some(mut polyp) = store.get_polyp_sync(id)? else {
+                continue;
+            };
+            if polyp.state == PolypState::Draft {
+                polyp.state = PolypState::Soft;
+                polyp.updated_at = chrono::Utc::now();
+                store.save_polyp_sync(&polyp)?;
+                moved += 1;
+            }
+        }
+        Ok(moved)
+    }
+
+    /// The consensus the node recorded for `epoch`, if it ran one.
+    pub fn consensus(&self, epoch: u64) -> Result<Option<ConsensusRecord>, ChitinError> {
+        ConsensusRecord::load(&self.handle.store(), epoch)
+    }
+
+    /// The block the node's scheduler has reached.
+    pub async fn height(&self) -> u64 {
+        self.handle
+            .shared()
+            .epoch_manager
+            .read()
+            .await
+            .current_block()
+    }
+}
+
+/// A running cluster of in-process nodes sharing one block clock.
+pub struct Cluster {
+    nodes: Vec<SimNode>,
+    clock: SimClock,
+    /// Temporary directory holding every node's data directory.
+    root: PathBuf,
+    blocks_per_epoch: u64,
+    /// Polyps submitted through the cluster, with the node each went to.
+    submitted: Vec<(usize, Uuid)>,
+}
+
+impl Cluster {
+    /// A builder for `nodes` nodes.
+    pub fn builder(nodes: usize) -> ClusterBuilder {
+        ClusterBuilder::new(nodes)
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// True if the cluster has no nodes (never, once started).
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Node `index`.
+    pub fn node(&self, index: usize) -> &SimNode {
+        &self.nodes[index]
+    }
+
+    /// Every node, in index order.
+    pub fn nodes(&self) -> &[SimNode] {
+        &self.nodes
+    }
+
+    /// Indices of every node.
+    pub fn all(&self) -> Vec<usize> {
+        (0..self.nodes.len()).collect()
+    }
+
+    /// The shared block clock.
+    pub fn clock(&self) -> &SimClock {
+        &self.clock
+    }
+
+    /// The epoch the clock is in.
+    pub fn epoch(&self) -> u64 {
+        self.clock.height() / self.blocks_per_epoch
+    }
+
+    /// Submit `content` as a new polyp on node `index`, which gossips it to
+    /// its peers.
+    pub async fn submit(&mut self, index: usize, content: &str) -> Result<Uuid, ChitinError> {
+        let request = SubmitPolypRequest {
+            content: content.to_string(),
+            content_type: "text/plain".to_string(),
+            language: Some("en".to_string()),
+            vector: None,
+            model_id: None,
+            source_url: None,
+            source_title: None,
+            license: None,
+            reef_zone: None,
+            pipeline: Vec::new(),
+        };
+        let response: SubmitPolypResponse = self.nodes[index]
+            .handle
+            .call("polyp/submit", request)
+            .await?;
+        self.submitted.push((index, response.polyp_id));
+        Ok(response.polyp_id)
+    }
+
+    /// Polyps submitted to any of `nodes`.
+    pub fn submitted_to(&self, nodes: &[usize]) -> BTreeSet<Uuid> {
+        self.submitted
+            .iter()
+            .filter(|(index, _)| nodes.contains(index))
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    /// Move Draft polyps to Soft on every node. Nodes never leave Draft on
+    /// their own, so scenarios that score polyps soften them first.
+    pub fn soften(&self) -> Result<usize, ChitinError> {
+        self.nodes.iter().map(SimNode::soften).sum()
+    }
+
+    /// Advance the clock by `blocks` and wait until every node's scheduler
+    /// has reached the new height.
+    pub async fn advance_blocks(&self, blocks: u64) -> Result<u64, ChitinError> {
+        let height = self.clock.advance(blocks);
+        for node in &self.nodes {
+            eventually(
+                DEFAULT_TIMEOUT,
+                &format!("node {} at block {}", node.index, height),
+                || async { (node.height().await >= height).then_some(()) },
+            )
+            .await?;
+        }
+        Ok(height)
+    }
+
+    /// Advance to the start of the next epoch (past its boundary).
+    pub async fn advance_epoch(&self) -> Result<u64, ChitinError> {
+        let height = self.clock.height();
+        let next = (height / self.blocks_per_epoch + 1) * self.blocks_per_epoch;
+        self.advance_blocks(next - height).await?;
+        Ok(self.epoch())
+    }
+
+    /// Split the cluster into `groups`: each node keeps only the nodes in
+    /// its group as peers. Nodes in no group are isolated.
+    pub async fn partition(&self, groups: &[&[usize]]) -> Result<(), ChitinError> {
+        for node in &self.nodes {
+            let group = groups.iter().find(|group| group.contains(&node.index));
+            let peers = group
+                .map(|group| {
+                    group
+                        .iter()
+                        .filter(|&&index| index != node.index)
+                        .map(|&index| self.nodes[index].url.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            self.set_peers(node, peers).await?;
+        }
+        Ok(())
+    }
+
+    /// Reconnect every node to every other.
+    pub async fn heal(&self) -> Result<(), ChitinError> {
+        self.partition(&[&self.all()]).await
+    }
+
+    async fn set_peers(&self, node: &SimNode, peers: Vec<String>) -> Result<(), ChitinError> {
+        let registry = node.handle.peers().ok_or_else(|| {
+            ChitinError::InvalidState(format!("Node {} has no peer networking", node.index))
+        })?;
+        registry.set_configured_peers(peers).await;
+        Ok(())
+    }
+
+    /// Wait until `nodes` hold the same polyps in the same states, including
+    /// every polyp submitted to them, and return that common view.
+    pub async fn await_polyps(
+        &self,
+        nodes: &[usize],
+        timeout: Duration,
+    ) -> Result<BTreeMap<Uuid, PolypState>, ChitinError> {
+        let expected = self.submitted_to(nodes);
+        let converged = eventually(timeout, "polyp convergence", || async {
+            let views = self.polyp_views(nodes).ok()?;
+            let first = views.first()?.1.clone();
+            let agree = views.iter().all(|(_, view)| *view == first);
+            let complete = expected.iter().all(|id| first.contains_key(id));
+            (agree && complete).then_some(first)
+        })
+        .await;
+        converged.map_err(|e| self.describe_divergence(nodes, &expected, e))
+    }
+
+    /// Wait until every polyp on `nodes` is in a state `accept`s (e.g. past
+    /// review), then until the nodes agree.
+    pub async fn await_polyp_states(
+        &self,
+        nodes: &[usize],
+        accept: impl Fn(&PolypState) -> bool,
+        timeout: Duration,
+    ) -> Result<BTreeMap<Uuid, PolypState>, ChitinError> {
+        eventually(timeout, "polyp states", || async {
+            let views = self.polyp_views(nodes).ok()?;
+            views
+                .iter()
+                .all(|(_, view)| view.values().all(&accept))
+                .then_some(())
+        })
+        .await?;
+        self.await_polyps(nodes, timeout).await
+    }
+
+    /// Wait until `nodes` have all recorded consensus for `epoch` with the
+    /// same consensus weights and incentives, and return one record.
+    pub async fn await_consensus(
+        &self,
+        nodes: &[usize],
+        epoch: u64,
+        timeout: Duration,
+    ) -> Result<ConsensusRecord, ChitinError> {
+        eventually(
+            timeout,
+            &format!("consensus for epoch {}", epoch),
+            || async {
+                let mut records = Vec::new();
+                for &index in nodes {
+                    records.push(self.nodes[index].consensus(epoch).ok()??);
+                }
+                let first = records.first()?;
+                records
+                    .iter()
+                    .all(|r| {
+                        r.result.consensus_weights == first.result.consensus_weights
+                            && r.result.incentives == first.result.incentives
+                    })
+                    .then(|| first.clone())
+            },
+        )
+        .await
+    }
+
+    fn polyp_views(&self, nodes: &[usize]) -> Result<Vec<(usize, PolypView)>, ChitinError> {
+        nodes
+            .iter()
+            .map(|&index| Ok((index, self.nodes[index].polyps()?)))
+            .collect()
+    }
+
+    /// Explain a convergence timeout: what each node is missing or holds in
+    /// another state than the first node.
+    fn describe_divergence(
+        &self,
+        nodes: &[usize],
+        expected: &BTreeSet<Uuid>,
+        error: ChitinError,
+    ) -> ChitinError {
+        let Ok(views) = self.polyp_views(nodes) else {
+            return error;
+        };
+        let mut lines = vec![message(&error)];
+        for (index, view) in &views {
+            let missing: Vec<&Uuid> = expected
+                .iter()
+                .filter(|id| !view.contains_key(id))
+                .collect();
+            lines.push(format!(
+                "node {}: {} polyps, missing {:?}, states {:?}",
+                index,
+                view.len(),
+                missing,
+                view.values().collect::<Vec<_>>()
+            ));
+        }
+        ChitinError::InvalidState(lines.join("\n"))
+    }
+
+    /// Stop every node and remove the cluster's data.
+    pub async fn shutdown(mut self) -> Result<(), ChitinError> {
+        let mut errors = Vec::new();
+        for node in self.nodes.drain(..) {
+            if let Err(e) = node.handle.stop().await {
+                errors.push(format!("node {}: {}", node.index, e));
+            }
+        }
+        let _ = std::fs::remove_dir_all(&self.root);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ChitinError::InvalidState(errors.join("; ")))
+        }
+    }
+}
+
+impl Drop for Cluster {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// The text of `error` without the variant's prefix, for wrapping in
+/// another error.
+pub(crate) fn message(error: &ChitinError) -> String {
+    match error {
+        ChitinError::InvalidState(message) => message.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Poll `check` until it returns a value, or fail after `timeout`.
+pub async fn eventually<T, F, Fut>(
+    timeout: Duration,
+    what: &str,
+    mut check: F,
+) -> Result<T, ChitinError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if let Some(value) = check().await {
+            return Ok(value);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(ChitinError::InvalidState(format!(
+                "Timed out after {:?} waiting for {}",
+                timeout, what
+            )));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// The fixed identity of node `index`: hotkey and coldkey derived from the
+/// index, so every run signs with the same keys.
+pub fn identity(index: usize, node_type: &str) -> (NodeIdentity, SecretKey) {
+    let seed = |tag: u8| {
+        let mut bytes = [tag; 32];
+        bytes[..8].copy_from_slice(&(index as u64).to_le_bytes());
+        bytes
+    };
+    let hotkey = seed(0x11);
+    let coldkey = Keypair::from_secret_bytes(&seed(0xc0)).public_key_bytes();
+    let node_type = match node_type {
+        "coral" => NodeType::Coral,
+        "tide" => NodeType::Tide,
+        _ => NodeType::Hybrid,
+    };
+    let identity = NodeIdentity::from_keypairs(
+        Keypair::from_secret_bytes(&hotkey).public_key_bytes(),
+        coldkey,
+        node_type,
+    );
+    (identity, SecretKey::new(hotkey))
+}
+
+/// A loopback port free at the time of the call.
+fn free_port() -> Result<u16, ChitinError> {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| ChitinError::Network(format!("No free loopback port: {}", e)))
+}
+
+fn port_of(url: &str) -> u16 {
+    url.rsplit(':')
+        .next()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(0)
+}
@@ -0,0 +1,20 @@
+// crates/chitin-testkit/src/lib.rs
+//
+// chitin-testkit: deterministic multi-node simulation for the Chitin
+// Protocol.
+//
+// Integration tests elsewhere drive one component or one node at a time.
+// This crate runs whole clusters in-process (in-memory polyp stores, a
+// shared synthetic block clock, loopback transport between real RPC
+// servers) and scripts scenarios against them: submissions, gossip and
+// sync, epochs, and network partitions, asserting that the nodes converge
+// on the same polyps and the same consensus. It is the foundation for
+// regression tests of distributed behavior; see `tests/scenarios.rs`.
+
+pub mod clock;
+pub mod cluster;
+pub mod scenario;
+
+pub use clock::{ClockBlockSource, SimClock};
+pub use cluster::{Cluster, ClusterBuilder, SimNode, DEFAULT_TIMEOUT};
+pub use scenario::{Scenario, ScenarioReport, Step};
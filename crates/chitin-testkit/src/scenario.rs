@@ -0,0 +1,205 @@
+// crates/chitin-testkit/src/scenario.rs
+//
+// Scripted scenarios over a `Cluster`.
+//
+// A `Scenario` is a list of steps (submit polyps, advance blocks or epochs,
+// partition and heal the network, and wait for convergence), built up with
+// chained calls and run in order against a started cluster:
+//
+//     Scenario::new("partition heals")
+//         .partition(&[&[0, 1], &[2]])
+//         .submit(0, "Coral polyps secrete calcium carbonate.")
+//         .submit(2, "Tides are driven by the moon.")
+//         .await_polyps(&[0, 1])
+//         .heal()
+//         .await_all_polyps()
+//         .run(&mut cluster)
+//         .await?;
+//
+// A failing step stops the run with an error naming the scenario and step.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use chitin_consensus::history::ConsensusRecord;
+use chitin_core::{ChitinError, PolypState};
+
+use crate::cluster::{message, Cluster, DEFAULT_TIMEOUT};
+
+/// One scenario step.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// Submit `content` as a polyp on `node`.
+    Submit { node: usize, content: String },
+    /// Move every node's Draft polyps to Soft.
+    Soften,
+    /// Advance the clock by this many blocks.
+    AdvanceBlocks(u64),
+    /// Advance to the start of the next epoch.
+    AdvanceEpoch,
+    /// Split the network into these groups of nodes.
+    Partition(Vec<Vec<usize>>),
+    /// Reconnect every node.
+    Heal,
+    /// Wait until these nodes (all, if `None`) hold the same polyps.
+    AwaitPolyps(Option<Vec<usize>>),
+    /// Wait until these nodes (all, if `None`) agree on the consensus of
+    /// the current epoch.
+    AwaitConsensus(Option<Vec<usize>>),
+}
+
+/// What a scenario run observed at its convergence steps.
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioReport {
+    /// Polyps submitted, in order.
+    pub submitted: Vec<Uuid>,
+    /// The agreed polyp view at each `AwaitPolyps` step.
+    pub polyps: Vec<BTreeMap<Uuid, PolypState>>,
+    /// The agreed consensus at each `AwaitConsensus` step.
+    pub consensus: Vec<ConsensusRecord>,
+}
+
+/// A named list of steps.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    name: String,
+    steps: Vec<Step>,
+    timeout: Duration,
+}
+
+impl Scenario {
+    /// An empty scenario.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            steps: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Wait at most `timeout` at each convergence step.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Append `step`.
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Submit `content` on `node`.
+    pub fn submit(self, node: usize, content: &str) -> Self {
+        self.step(Step::Submit {
+            node,
+            content: content.to_string(),
+        })
+    }
+
+    /// Move every node's Draft polyps to Soft, ready for scoring.
+    pub fn soften(self) -> Self {
+        self.step(Step::Soften)
+    }
+
+    /// Advance the clock by `blocks`.
+    pub fn advance_blocks(self, blocks: u64) -> Self {
+        self.step(Step::AdvanceBlocks(blocks))
+    }
+
+    /// Advance to the start of the next epoch.
+    pub fn advance_epoch(self) -> Self {
+        self.step(Step::AdvanceEpoch)
+    }
+
+    /// Split the network into `groups`.
+    pub fn partition(self, groups: &[&[usize]]) -> Self {
+        self.step(Step::Partition(groups.iter().map(|g| g.to_vec()).collect()))
+    }
+
+    /// Reconnect every node.
+    pub fn heal(self) -> Self {
+        self.step(Step::Heal)
+    }
+
+    /// Wait until `nodes` hold the same polyps.
+    pub fn await_polyps(self, nodes: &[usize]) -> Self {
+        self.step(Step::AwaitPolyps(Some(nodes.to_vec())))
+    }
+
+    /// Wait until every node holds the same polyps.
+    pub fn await_all_polyps(self) -> Self {
+        self.step(Step::AwaitPolyps(None))
+    }
+
+    /// Wait until every node agrees on the current epoch's consensus.
+    pub fn await_consensus(self) -> Self {
+        self.step(Step::AwaitConsensus(None))
+    }
+
+    /// Run every step against `cluster`.
+    pub async fn run(&self, cluster: &mut Cluster) -> Result<ScenarioReport, ChitinError> {
+        let mut report = ScenarioReport::default();
+        for (number, step) in self.steps.iter().enumerate() {
+            tracing::info!("Scenario {:?}, step {}: {:?}", self.name, number, step);
+            self.run_step(cluster, step, &mut report)
+                .await
+                .map_err(|e| {
+                    ChitinError::InvalidState(format!(
+                        "Scenario {:?} failed at step {} ({:?}): {}",
+                        self.name,
+                        number,
+                        step,
+                        message(&e)
+                    ))
+                })?;
+        }
+        Ok(report)
+    }
+
+    async fn run_step(
+        &self,
+        cluster: &mut Cluster,
+        step: &Step,
+        report: &mut ScenarioReport,
+    ) -> Result<(), ChitinError> {
+        let nodes = |nodes: &Option<Vec<usize>>, cluster: &Cluster| {
+            nodes.clone().unwrap_or_else(|| cluster.all())
+        };
+        match step {
+            Step::Submit { node, content } => {
+                report.submitted.push(cluster.submit(*node, content).await?);
+            }
+            Step::Soften => {
+                cluster.soften()?;
+            }
+            Step::AdvanceBlocks(blocks) => {
+                cluster.advance_blocks(*blocks).await?;
+            }
+            Step::AdvanceEpoch => {
+                cluster.advance_epoch().await?;
+            }
+            Step::Partition(groups) => {
+                let groups: Vec<&[usize]> = groups.iter().map(Vec::as_slice).collect();
+                cluster.partition(&groups).await?;
+            }
+            Step::Heal => cluster.heal().await?,
+            Step::AwaitPolyps(which) => {
+                let view = cluster
+                    .await_polyps(&nodes(which, cluster), self.timeout)
+                    .await?;
+                report.polyps.push(view);
+            }
+            Step::AwaitConsensus(which) => {
+                let epoch = cluster.epoch();
+                let record = cluster
+                    .await_consensus(&nodes(which, cluster), epoch, self.timeout)
+                    .await?;
+                report.consensus.push(record);
+            }
+        }
+        Ok(())
+    }
+}
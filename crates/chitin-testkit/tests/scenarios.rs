@@ -0,0 +1,92 @@
+// crates/chitin-testkit/tests/scenarios.rs
+//
+// Distributed behavior of in-process clusters: polyps submitted anywhere
+// reach every node, nodes that scored the same polyps agree on consensus,
+// and a partitioned network converges once healed.
+
+use chitin_core::PolypState;
+use chitin_testkit::{Cluster, Scenario};
+
+#[tokio::test]
+async fn test_submissions_reach_every_node() {
+    let mut cluster = Cluster::builder(3).start().await.unwrap();
+
+    let report = Scenario::new("gossip")
+        .submit(0, "Coral polyps secrete calcium carbonate skeletons.")
+        .submit(1, "Zooxanthellae photosynthesize inside coral tissue.")
+        .submit(2, "Reef crests break incoming ocean swell.")
+        .await_all_polyps()
+        .run(&mut cluster)
+        .await
+        .unwrap();
+
+    let view = &report.polyps[0];
+    assert_eq!(view.len(), 3);
+    assert!(report.submitted.iter().all(|id| view.contains_key(id)));
+    cluster.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_epochs_reach_the_same_consensus_on_every_node() {
+    let mut cluster = Cluster::builder(3)
+        .blocks_per_epoch(8)
+        .start()
+        .await
+        .unwrap();
+
+    let report = Scenario::new("epoch")
+        .submit(0, "Parrotfish grind dead coral into sand.")
+        .submit(1, "Spawning corals release gametes after a full moon.")
+        .await_all_polyps()
+        // Into the Scoring phase, then across the boundary to consensus.
+        .soften()
+        .advance_blocks(4)
+        .advance_epoch()
+        .await_consensus()
+        .run(&mut cluster)
+        .await
+        .unwrap();
+
+    let record = &report.consensus[0];
+    assert_eq!(record.epoch, 1);
+    assert_eq!(record.result.consensus_weights.len(), 2);
+    let incentives: f64 = record.result.incentives.iter().sum();
+    assert!((incentives - 1.0).abs() < 1e-9);
+
+    // Scoring took both polyps out of Soft on every node.
+    let states = cluster
+        .await_polyp_states(
+            &cluster.all(),
+            |state| !matches!(state, PolypState::Draft | PolypState::Soft),
+            chitin_testkit::DEFAULT_TIMEOUT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(states.len(), 2);
+    cluster.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_partitioned_nodes_converge_after_healing() {
+    let mut cluster = Cluster::builder(3).start().await.unwrap();
+
+    let report = Scenario::new("partition")
+        .partition(&[&[0, 1], &[2]])
+        .submit(0, "Mangroves shelter juvenile reef fish.")
+        .submit(2, "Sea urchins graze algae off reef surfaces.")
+        .await_polyps(&[0, 1])
+        .await_polyps(&[2])
+        .heal()
+        .await_all_polyps()
+        .run(&mut cluster)
+        .await
+        .unwrap();
+
+    // Each side only saw its own submission while partitioned.
+    let (majority, isolated) = (&report.polyps[0], &report.polyps[1]);
+    assert_eq!(majority.len(), 1);
+    assert_eq!(isolated.len(), 1);
+    assert!(majority.keys().all(|id| !isolated.contains_key(id)));
+    assert_eq!(report.polyps[2].len(), 2);
+    cluster.shutdown().await.unwrap();
+}
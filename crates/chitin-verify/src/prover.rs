@@ -10,6 +10,7 @@ use chrono::Utc;
 use sha2::{Digest, Sha256};
 
 use chitin_core::embedding::EmbeddingModelId;
+use chitin_core::light;
 use chitin_core::polyp::{ProofPublicInputs, ZkProof};
 
 /// Generates ZK proofs for Polyp submissions.
@@ -42,26 +43,10 @@ impl ProofGenerator {
         model_id: &EmbeddingModelId,
     ) -> Result<ZkProof, chitin_core::error::ChitinError> {
         // Compute SHA-256 hash of the source text
-        let text_hash = {
-            let mut hasher = Sha256::new();
-            hasher.update(text.as_bytes());
-            let result = hasher.finalize();
-            let mut hash = [0u8; 32];
-            hash.copy_from_slice(&result);
-            hash
-        };
+        let text_hash = light::text_hash(text);
 
         // Compute SHA-256 hash of the vector bytes (IEEE 754 little-endian)
-        let vector_hash = {
-            let mut hasher = Sha256::new();
-            for &val in vector {
-                hasher.update(val.to_le_bytes());
-            }
-            let result = hasher.finalize();
-            let mut hash = [0u8; 32];
-            hash.copy_from_slice(&result);
-            hash
-        };
+        let vector_hash = light::vector_hash(vector);
 
         // Phase 1: Generate a placeholder proof value by hashing (text_hash || vector_hash).
         // This is NOT a real ZK proof — it simply demonstrates the data flow.
@@ -5,9 +5,8 @@
 // Phase 1: Always returns Ok(true) — no real ZK verification is performed.
 // Phase 3: Real SP1/Risc0 proof verification will replace the placeholder logic.
 
-use sha2::{Digest, Sha256};
-
 use chitin_core::error::ChitinError;
+use chitin_core::light;
 use chitin_core::polyp::ZkProof;
 use chitin_core::traits::ProofVerifier;
 
@@ -33,12 +32,7 @@ impl PlaceholderVerifier {
     /// This check is independent of ZK proof verification — it validates
     /// that the public inputs are consistent with the claimed source text.
     pub fn verify_text_hash(proof: &ZkProof, text: &str) -> bool {
-        let mut hasher = Sha256::new();
-        hasher.update(text.as_bytes());
-        let result = hasher.finalize();
-        let mut expected = [0u8; 32];
-        expected.copy_from_slice(&result);
-        proof.public_inputs.text_hash == expected
+        proof.public_inputs.text_hash == light::text_hash(text)
     }
 
     /// Verify that the vector_hash in the proof's public inputs matches
@@ -47,14 +41,7 @@ impl PlaceholderVerifier {
     /// This check is independent of ZK proof verification — it validates
     /// that the public inputs are consistent with the claimed embedding vector.
     pub fn verify_vector_hash(proof: &ZkProof, vector: &[f32]) -> bool {
-        let mut hasher = Sha256::new();
-        for &val in vector {
-            hasher.update(val.to_le_bytes());
-        }
-        let result = hasher.finalize();
-        let mut expected = [0u8; 32];
-        expected.copy_from_slice(&result);
-        proof.public_inputs.vector_hash == expected
+        proof.public_inputs.vector_hash == light::vector_hash(vector)
     }
 }
 
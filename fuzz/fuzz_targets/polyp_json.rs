@@ -0,0 +1,20 @@
+// fuzz/fuzz_targets/polyp_json.rs
+//
+// Polyps arrive as JSON from peers (sync, gossip) and clients (import).
+// Any input must parse or fail cleanly, and a parsed Polyp must serialize
+// back to JSON that parses to the same Polyp.
+
+#![no_main]
+
+use chitin_core::Polyp;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(polyp) = serde_json::from_slice::<Polyp>(data) else {
+        return;
+    };
+    let json = serde_json::to_string(&polyp).expect("a parsed Polyp serializes");
+    let reparsed: Polyp = serde_json::from_str(&json).expect("serialized Polyp parses");
+    assert_eq!(serde_json::to_string(&reparsed).unwrap(), json);
+    let _ = polyp.signable_bytes();
+});
@@ -0,0 +1,23 @@
+// fuzz/fuzz_targets/rpc_envelope.rs
+//
+// The RPC server decodes every request body as a `JsonRpcRequest` envelope
+// before dispatch. Any body must decode or fail cleanly, and a decoded
+// envelope must round-trip through JSON unchanged.
+
+#![no_main]
+
+use chitin_rpc::server::{JsonRpcRequest, JsonRpcResponse};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(request) = serde_json::from_slice::<JsonRpcRequest>(data) {
+        let json = serde_json::to_vec(&request).expect("a parsed request serializes");
+        let reparsed: JsonRpcRequest = serde_json::from_slice(&json).expect("request parses");
+        assert_eq!(reparsed.method, request.method);
+        assert_eq!(reparsed.params, request.params);
+    }
+    if let Ok(response) = serde_json::from_slice::<JsonRpcResponse>(data) {
+        let json = serde_json::to_vec(&response).expect("a parsed response serializes");
+        assert!(serde_json::from_slice::<JsonRpcResponse>(&json).is_ok());
+    }
+});
@@ -0,0 +1,21 @@
+// fuzz/fuzz_targets/vbf_from_bytes.rs
+//
+// Vector Bloom Filters are exchanged with peers during sync. Any bytes must
+// decode or fail cleanly, and a decoded filter must answer membership
+// queries and re-encode without panicking.
+
+#![no_main]
+
+use chitin_sync::vbf::VectorBloomFilter;
+use libfuzzer_sys::fuzz_target;
+use uuid::Uuid;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(vbf) = VectorBloomFilter::from_bytes(data) else {
+        return;
+    };
+    let _ = vbf.contains(&Uuid::nil());
+    let _ = vbf.contains(&Uuid::from_bytes([0xff; 16]));
+    let bytes = vbf.to_bytes();
+    assert!(VectorBloomFilter::from_bytes(&bytes).is_ok());
+});
@@ -103,9 +103,10 @@ class PolypScores(BaseModel):
     novelty: float = Field(ge=0.0, le=1.0, description="How much new information does this Polyp add?")
     source_credibility: float = Field(ge=0.0, le=1.0, description="Reputation of creator + source quality")
     embedding_quality: float = Field(ge=0.0, le=1.0, description="Cosine similarity vs reference embedding")
+    provenance_integrity: float = Field(default=0.0, ge=0.0, le=1.0, description="Share of provenance checks that pass")
 
     # Default dimension weights for computing final score
-    DEFAULT_WEIGHTS: list[float] = [0.30, 0.25, 0.15, 0.15, 0.15]
+    DEFAULT_WEIGHTS: list[float] = [0.30, 0.25, 0.15, 0.10, 0.15, 0.05]
 
     def weighted_score(self) -> float:
         """Compute weighted final score."""
@@ -115,6 +116,7 @@ class PolypScores(BaseModel):
             self.novelty,
             self.source_credibility,
             self.embedding_quality,
+            self.provenance_integrity,
         ]
         return sum(v * w for v, w in zip(vals, self.DEFAULT_WEIGHTS))
 
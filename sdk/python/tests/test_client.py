@@ -130,13 +130,15 @@ class TestTypes:
             novelty=0.6,
             source_credibility=0.7,
             embedding_quality=0.9,
+            provenance_integrity=0.5,
         )
         expected = (
             1.0 * 0.30
             + 0.8 * 0.25
             + 0.6 * 0.15
-            + 0.7 * 0.15
+            + 0.7 * 0.10
             + 0.9 * 0.15
+            + 0.5 * 0.05
         )
         assert abs(scores.weighted_score() - expected) < 1e-10
 